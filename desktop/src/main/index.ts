@@ -1,4 +1,4 @@
-import { app, shell, BrowserWindow, ipcMain, dialog, clipboard } from 'electron'
+import { app, shell, BrowserWindow, ipcMain, dialog, clipboard, Notification } from 'electron'
 import { join, resolve } from 'path'
 import { existsSync } from 'fs'
 import { electronApp, optimizer, is } from '@electron-toolkit/utils'
@@ -50,6 +50,9 @@ function createWindow(): void {
 // ============================================================================
 
 function initializeState(): void {
+  // Record the action trace for dev_export_trace (dev mode only)
+  core.devSetTraceEnabled(is.dev)
+
   // Initialize state with a callback that forwards updates to renderer
   core.stateInit((err: Error | null, stateJson: string) => {
     if (err) {
@@ -61,6 +64,59 @@ function initializeState(): void {
       win.webContents.send('state:update', stateJson)
     })
   })
+
+  // Restore unsaved work if the previous session crashed
+  core
+    .recoverState()
+    .then((recovered) => {
+      if (recovered) console.log('Recovered state from the action journal')
+    })
+    .catch((error) => console.error('State recovery error:', error))
+
+  // Optional WebSocket bridge for external tools (native builds with the
+  // state-bridge feature)
+  const bridgePort = process.env['RSTN_STATE_BRIDGE_PORT']
+  if (bridgePort !== undefined && core.stateBridgeStart) {
+    core
+      .stateBridgeStart(Number(bridgePort) || 0)
+      .then((info) => console.log(`State bridge listening at ${info.url}`))
+      .catch((error) => console.error('State bridge error:', error))
+  }
+}
+
+function initializeTerminal(): void {
+  // Forward PTY output to all windows (xterm.js consumes it in the renderer)
+  core.terminalSetOutputListener((err: Error | null, sessionId: string, data: string) => {
+    if (err) {
+      console.error('Terminal output error:', err)
+      return
+    }
+    BrowserWindow.getAllWindows().forEach((win) => {
+      win.webContents.send('terminal:output', sessionId, data)
+    })
+  })
+}
+
+function initializeDesktopNotifications(): void {
+  // Core decides which events notify; only interrupt when rstn is not focused
+  core.desktopNotificationSetListener((err: Error | null, notification: core.DesktopNotification) => {
+    if (err) {
+      console.error('Desktop notification error:', err)
+      return
+    }
+    if (!Notification.isSupported() || BrowserWindow.getFocusedWindow()) {
+      return
+    }
+    const shown = new Notification({ title: notification.title, body: notification.body })
+    shown.on('click', () => {
+      const win = BrowserWindow.getAllWindows()[0]
+      if (win) {
+        if (win.isMinimized()) win.restore()
+        win.focus()
+      }
+    })
+    shown.show()
+  })
 }
 
 // IPC Handlers for state management
@@ -84,6 +140,26 @@ function setupStateIPC(): void {
       throw error
     }
   })
+
+  // Handle command palette listing from renderer
+  ipcMain.handle('palette:listActions', async () => {
+    try {
+      return await core.paletteListActions()
+    } catch (error) {
+      console.error('Palette list error:', error)
+      throw error
+    }
+  })
+
+  // Handle constitution stack detection from renderer
+  ipcMain.handle('constitution:listDetectedStacks', async (_event, path: string) => {
+    try {
+      return await core.constitutionListDetectedStacks(path)
+    } catch (error) {
+      console.error('Constitution stack detection error:', error)
+      throw error
+    }
+  })
 }
 
 // ============================================================================
@@ -100,6 +176,229 @@ function setupExplorerIPC(): void {
       throw error
     }
   })
+
+  // Commits that changed a file (for the detail pane)
+  ipcMain.handle('explorer:fileHistory', async (_event, path: string, limit?: number) => {
+    try {
+      return core.gitFileHistory(path, limit)
+    } catch (error) {
+      console.error('Explorer file history error:', error)
+      throw error
+    }
+  })
+
+  // Last commit of every line of a file
+  ipcMain.handle('explorer:blame', async (_event, path: string) => {
+    try {
+      return core.gitBlame(path)
+    } catch (error) {
+      console.error('Explorer blame error:', error)
+      throw error
+    }
+  })
+
+  // Functions and types of a source file (tree-sitter outline)
+  ipcMain.handle('explorer:symbols', async (_event, path: string) => {
+    return core.symbolsForFile(path)
+  })
+
+  // Language server hover / go-to-definition (1-based positions)
+  ipcMain.handle('explorer:hover', async (_event, path: string, line: number, column: number) => {
+    return core.lspHover(path, line, column)
+  })
+
+  ipcMain.handle('explorer:definition', async (_event, path: string, line: number, column: number) => {
+    return core.lspDefinition(path, line, column)
+  })
+}
+
+// ============================================================================
+// Ollama Handlers
+// ============================================================================
+
+function setupOllamaIPC(): void {
+  // Installed models on the configured server (rejects if Ollama is not running)
+  ipcMain.handle('ollama:listModels', async () => {
+    return core.ollamaListModels()
+  })
+}
+
+// ============================================================================
+// GitHub Handlers
+// ============================================================================
+
+function setupGitHubIPC(): void {
+  // Open issues of a repository (owner/repo or a local checkout)
+  ipcMain.handle('github:listIssues', async (_event, repo: string) => {
+    return core.githubListIssues(repo)
+  })
+}
+
+// ============================================================================
+// Doctor Handlers
+// ============================================================================
+
+function setupDoctorIPC(): void {
+  // Pre-flight checks for the Diagnostics panel
+  ipcMain.handle('doctor:run', async () => {
+    return core.doctorRun()
+  })
+
+  // Zip logs, redacted state and versions to a file chosen by the user
+  ipcMain.handle('doctor:exportBundle', async () => {
+    const stamp = new Date().toISOString().slice(0, 19).replace(/[T:]/g, '-')
+    const result = await dialog.showSaveDialog({
+      title: 'Export Diagnostics Bundle',
+      defaultPath: `rstn-diagnostics-${stamp}.zip`,
+      filters: [{ name: 'Zip Archive', extensions: ['zip'] }],
+    })
+    if (result.canceled || !result.filePath) {
+      return null
+    }
+    await core.diagnosticsExportBundle(result.filePath)
+    return result.filePath
+  })
+}
+
+// ============================================================================
+// Git Hook Handlers
+// ============================================================================
+
+function setupGitHooksIPC(): void {
+  // rstn-managed pre-commit hook (secret scan + .rstn/hooks.toml pipeline)
+  ipcMain.handle('gitHooks:status', async (_event, project: string) => {
+    return core.hooksStatus(project)
+  })
+
+  ipcMain.handle('gitHooks:install', async (_event, project: string) => {
+    return core.hooksInstall(project)
+  })
+
+  ipcMain.handle('gitHooks:uninstall', async (_event, project: string) => {
+    return core.hooksUninstall(project)
+  })
+}
+
+// ============================================================================
+// Session History Handlers
+// ============================================================================
+
+function setupSessionsIPC(): void {
+  // Write a Claude session transcript to a file chosen by the user
+  ipcMain.handle('sessions:export', async (_event, sessionId: string, format: 'markdown' | 'json') => {
+    const extension = format === 'json' ? 'json' : 'md'
+    const result = await dialog.showSaveDialog({
+      title: 'Export Session Transcript',
+      defaultPath: `claude-session-${sessionId.slice(0, 8)}.${extension}`,
+      filters: [
+        format === 'json'
+          ? { name: 'JSON', extensions: ['json'] }
+          : { name: 'Markdown', extensions: ['md'] },
+      ],
+    })
+    if (result.canceled || !result.filePath) {
+      return null
+    }
+    core.sessionExport(sessionId, format, result.filePath)
+    return result.filePath
+  })
+}
+
+// ============================================================================
+// Change Bundle Handlers
+// ============================================================================
+
+function setupChangesIPC(): void {
+  // Zip a change for reviewers without rstn to a file chosen by the user
+  ipcMain.handle('changes:exportBundle', async (_event, changeId: string, changeName: string) => {
+    const result = await dialog.showSaveDialog({
+      title: 'Export Change for Review',
+      defaultPath: `${changeName}-review.zip`,
+      filters: [{ name: 'Change Bundle', extensions: ['zip'] }],
+    })
+    if (result.canceled || !result.filePath) {
+      return null
+    }
+    await core.changeExportBundle(changeId, result.filePath)
+    return result.filePath
+  })
+
+  // Merge review comments from a returned bundle or its review.json
+  ipcMain.handle('changes:importFeedback', async () => {
+    const result = await dialog.showOpenDialog({
+      title: 'Import Review Feedback',
+      properties: ['openFile'],
+      filters: [{ name: 'Change Bundle or Review Comments', extensions: ['zip', 'json'] }],
+    })
+    if (result.canceled || result.filePaths.length === 0) {
+      return null
+    }
+    return core.changeImportFeedback(result.filePaths[0])
+  })
+}
+
+// ============================================================================
+// Prompt Library Handlers
+// ============================================================================
+
+function setupPromptsIPC(): void {
+  // List templates in ~/.rstn/prompts/library/
+  ipcMain.handle('prompts:list', async () => {
+    return core.promptsList()
+  })
+
+  // Render a template (for previews; sending goes through SendTemplatedPrompt)
+  ipcMain.handle('prompts:render', async (_event, id: string, vars: Record<string, string>) => {
+    return core.promptsRender(id, vars)
+  })
+}
+
+// ============================================================================
+// Markdown Handlers
+// ============================================================================
+
+function setupMarkdownIPC(): void {
+  // Sanitized HTML with mermaid sources extracted (same renderer for every view)
+  ipcMain.handle('markdown:render', async (_event, content: string) => {
+    return core.renderMarkdown(null, content)
+  })
+
+  ipcMain.handle('markdown:renderFile', async (_event, path: string) => {
+    return core.renderMarkdown(path, null)
+  })
+}
+
+// ============================================================================
+// Agent Rules Handlers
+// ============================================================================
+
+function setupAgentRulesIPC(): void {
+  // Export a profile to a TOML file chosen by the user
+  ipcMain.handle('agentRules:export', async (_event, profileId: string, profileName: string) => {
+    const result = await dialog.showSaveDialog({
+      title: 'Export Agent Profile',
+      defaultPath: `${profileName.replace(/[^\w.-]+/g, '-').toLowerCase()}.toml`,
+      filters: [{ name: 'Agent Profile', extensions: ['toml'] }],
+    })
+    if (result.canceled || !result.filePath) {
+      return null
+    }
+    await core.agentRulesExport(profileId, result.filePath)
+    return result.filePath
+  })
+
+  // Read and validate a profile file chosen by the user
+  ipcMain.handle('agentRules:import', async () => {
+    const result = await dialog.showOpenDialog({
+      title: 'Import Agent Profile',
+      properties: ['openFile'],
+      filters: [{ name: 'Agent Profile', extensions: ['toml'] }],
+    })
+    if (result.canceled || result.filePaths.length === 0) {
+      return null
+    }
+    return core.agentRulesImport(result.filePaths[0])
+  })
 }
 
 // ============================================================================
@@ -118,6 +417,15 @@ function setupDialogIPC(): void {
     }
     return result.filePaths[0]
   })
+
+  // Pick files to attach to a chat message
+  ipcMain.handle('dialog:openFiles', async () => {
+    const result = await dialog.showOpenDialog({
+      properties: ['openFile', 'multiSelections'],
+      title: 'Attach Files',
+    })
+    return result.canceled ? [] : result.filePaths
+  })
 }
 
 // ============================================================================
@@ -211,8 +519,19 @@ app.whenReady().then(async () => {
 
   // Initialize state management (State-first architecture)
   initializeState()
+  initializeTerminal()
+  initializeDesktopNotifications()
   setupStateIPC()
   setupExplorerIPC()
+  setupAgentRulesIPC()
+  setupPromptsIPC()
+  setupMarkdownIPC()
+  setupOllamaIPC()
+  setupGitHubIPC()
+  setupDoctorIPC()
+  setupGitHooksIPC()
+  setupSessionsIPC()
+  setupChangesIPC()
   setupDialogIPC()
   setupScreenshotIPC()
 
@@ -231,6 +550,11 @@ app.whenReady().then(async () => {
   })
 })
 
+// Clean shutdown: nothing to recover on next launch
+app.on('will-quit', () => {
+  core.stateShutdown()
+})
+
 app.on('window-all-closed', () => {
   if (process.platform !== 'darwin') {
     app.quit()
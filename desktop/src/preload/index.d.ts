@@ -24,6 +24,26 @@ interface BranchInfo {
   isCurrent: boolean
 }
 
+// Command palette entry (matching Rust PaletteAction struct)
+interface PaletteAction {
+  id: string
+  label: string
+  category: string
+  keybinding?: string
+  enabled: boolean
+  disabledReason?: string
+  /** Action JSON for dispatch */
+  action: string
+}
+
+// Detected constitution stack (matching Rust DetectedStack struct)
+interface DetectedStack {
+  id: string
+  name: string
+  /** Template overridden in ~/.config/rustation/constitutions/ */
+  hasUserTemplate: boolean
+}
+
 // Dialog API for native dialogs
 interface DialogApi {
   /**
@@ -31,6 +51,216 @@ interface DialogApi {
    * @returns The selected folder path, or null if canceled
    */
   openFolder(): Promise<string | null>
+
+  /**
+   * Open a native multi-file selection dialog.
+   * @returns The selected file paths (empty if canceled)
+   */
+  openFiles(): Promise<string[]>
+}
+
+// Installed Ollama model (matching Rust OllamaModel struct)
+interface OllamaModel {
+  name: string
+  size: number
+  parameterSize?: string
+  quantization?: string
+}
+
+// Ollama API (local models)
+interface OllamaApi {
+  /**
+   * List models installed on the configured Ollama server.
+   * @returns Installed models (rejects if Ollama is not running)
+   */
+  listModels(): Promise<OllamaModel[]>
+}
+
+// Open GitHub issue (matching Rust GitHubIssue struct)
+interface GitHubIssue {
+  number: number
+  title: string
+  body: string
+  labels: string[]
+  url: string
+  author?: string
+}
+
+// GitHub API (issues as change intents)
+interface GitHubApi {
+  /**
+   * List open issues of a repository.
+   * @param repo - "owner/repo" or the path of a local checkout
+   * @returns Open issues, newest first
+   */
+  listIssues(repo: string): Promise<GitHubIssue[]>
+}
+
+// Result of a doctor check (matching Rust DoctorCheck struct)
+interface DoctorCheck {
+  id: string
+  label: string
+  status: 'ok' | 'warning' | 'error'
+  detail: string
+  /** How to fix a warning or error */
+  fix?: string
+}
+
+// Doctor run (matching Rust DoctorReport struct)
+interface DoctorReport {
+  checks: DoctorCheck[]
+  /** ISO 8601 */
+  ranAt: string
+}
+
+// Doctor API (pre-flight diagnostics)
+interface DoctorApi {
+  /**
+   * Check the Claude CLI, Docker, git, just, the MCP port, disk space and config directories.
+   * @returns One entry per check, with fix suggestions
+   */
+  run(): Promise<DoctorReport>
+
+  /**
+   * Export a diagnostics bundle (logs, redacted state, versions) to a zip chosen by the user.
+   * @returns The written file, or null if canceled
+   */
+  exportBundle(): Promise<string | null>
+}
+
+// Pre-commit hook state (matching Rust GitHookStatus struct)
+interface GitHookStatus {
+  path: string
+  installed: boolean
+  /** The existing hook was written by rstn */
+  managed: boolean
+  version?: number
+  currentVersion: number
+  /** New template or changed .rstn/hooks.toml */
+  updateAvailable: boolean
+  pipeline: string[]
+  pipelineError?: string
+}
+
+// Git hooks API (rstn-managed pre-commit hook)
+interface GitHooksApi {
+  /**
+   * Whether the project's pre-commit hook is managed by rstn and up to date.
+   * @param project - Project root
+   */
+  status(project: string): Promise<GitHookStatus>
+
+  /**
+   * Install or update the managed hook (rejects if a foreign hook exists).
+   * @param project - Project root
+   */
+  install(project: string): Promise<GitHookStatus>
+
+  /**
+   * Remove the managed hook (rejects if the hook was not written by rstn).
+   * @param project - Project root
+   */
+  uninstall(project: string): Promise<GitHookStatus>
+}
+
+// Sessions API (Claude session history)
+interface SessionsApi {
+  /**
+   * Export a session transcript to a file chosen by the user.
+   * @param sessionId - rstn session ID
+   * @param format - "markdown" or "json"
+   * @returns The written file, or null if canceled
+   */
+  exportTranscript(sessionId: string, format: 'markdown' | 'json'): Promise<string | null>
+}
+
+// Change bundle API (offline review by people without rstn)
+interface ChangesApi {
+  /**
+   * Export a change (documents, implementation diff, test results, review
+   * comments) to a zip picked in a save dialog.
+   * @returns The written file path, or null if canceled
+   */
+  exportBundle(changeId: string, changeName: string): Promise<string | null>
+
+  /**
+   * Merge review comments from a bundle or review.json picked in an open dialog.
+   * @returns What was merged, or null if canceled
+   */
+  importFeedback(): Promise<{ changeName: string; added: number; updated: number } | null>
+}
+
+// Prompt library template (matching Rust PromptTemplate struct)
+interface PromptTemplate {
+  id: string
+  name: string
+  description?: string
+  variables: { name: string; default?: string }[]
+  body: string
+  path: string
+}
+
+// Prompt library API (~/.rstn/prompts/library/)
+interface PromptsApi {
+  /**
+   * List prompt templates.
+   * @returns Templates with their variables
+   */
+  list(): Promise<PromptTemplate[]>
+
+  /**
+   * Render a template with variable values.
+   * @returns The rendered prompt (rejects if a required variable is missing)
+   */
+  render(id: string, vars: Record<string, string>): Promise<string>
+}
+
+// Rendered markdown (matching Rust RenderedMarkdown struct)
+interface RenderedMarkdown {
+  /** Sanitized HTML; mermaid fences become <div class="mermaid" data-mermaid-index="N"> */
+  html: string
+  headings: { level: number; text: string; anchor: string; line: number }[]
+  codeBlocks: { language?: string; code: string; line: number }[]
+  mermaid: { source: string; line: number }[]
+}
+
+// Markdown API (backend renderer shared by all document views)
+interface MarkdownApi {
+  /**
+   * Render markdown text to sanitized HTML.
+   */
+  render(content: string): Promise<RenderedMarkdown>
+
+  /**
+   * Render a markdown file.
+   * @param path - Absolute file path
+   */
+  renderFile(path: string): Promise<RenderedMarkdown>
+}
+
+// Shareable agent profile file (matching Rust AgentProfileFile struct)
+interface AgentProfileFile {
+  name: string
+  description: string
+  author: string
+  version: string
+  prompt: string
+}
+
+// Agent rules API (share profiles as TOML files)
+interface AgentRulesApi {
+  /**
+   * Export a profile to a file picked in a save dialog.
+   * @returns The written file path, or null if canceled
+   */
+  exportProfile(profileId: string, profileName: string): Promise<string | null>
+
+  /**
+   * Read and validate a profile file picked in an open dialog.
+   * Dispatch `ImportAgentProfile` with the result to add it.
+   * @returns The parsed profile, or null if canceled
+   */
+  importProfile(): Promise<AgentProfileFile | null>
 }
 
 // Screenshot API (dev mode)
@@ -57,6 +287,19 @@ interface StateApi {
    */
   getState(): Promise<string>
 
+  /**
+   * List command palette actions for the current state.
+   * @returns Palette entries (each carries the action JSON to dispatch)
+   */
+  listPaletteActions(): Promise<PaletteAction[]>
+
+  /**
+   * List the languages/frameworks detected in a project for constitution templates.
+   * @param path - Project (worktree) path
+   * @returns Detected stacks, flagging user template overrides
+   */
+  listConstitutionStacks(path: string): Promise<DetectedStack[]>
+
   /**
    * Subscribe to state updates.
    * @param callback - Called with JSON string whenever state changes
@@ -65,11 +308,31 @@ interface StateApi {
   onStateUpdate(callback: (stateJson: string) => void): () => void
 }
 
+// Terminal API (PTY output stream)
+interface TerminalApi {
+  /**
+   * Subscribe to PTY output.
+   * @param callback - Called with session ID and output chunk
+   * @returns Unsubscribe function
+   */
+  onOutput(callback: (sessionId: string, data: string) => void): () => void
+}
+
 declare global {
   interface Window {
     electron: ElectronAPI
     stateApi: StateApi
     dialogApi: DialogApi
+    agentRulesApi: AgentRulesApi
+    promptsApi: PromptsApi
+    markdownApi: MarkdownApi
+    ollamaApi: OllamaApi
+    githubApi: GitHubApi
+    doctorApi: DoctorApi
+    gitHooksApi: GitHooksApi
+    sessionsApi: SessionsApi
+    changesApi: ChangesApi
     screenshotApi: ScreenshotApi
+    terminalApi: TerminalApi
   }
 }
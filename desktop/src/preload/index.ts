@@ -10,6 +10,233 @@ const dialogApi = {
   openFolder: (): Promise<string | null> => {
     return ipcRenderer.invoke('dialog:openFolder')
   },
+
+  /**
+   * Open a native multi-file selection dialog.
+   * @returns The selected file paths (empty if canceled)
+   */
+  openFiles: (): Promise<string[]> => {
+    return ipcRenderer.invoke('dialog:openFiles')
+  },
+}
+
+// Ollama API (local models)
+const ollamaApi = {
+  /**
+   * List models installed on the configured Ollama server.
+   * @returns Installed models (rejects if Ollama is not running)
+   */
+  listModels: (): Promise<unknown[]> => {
+    return ipcRenderer.invoke('ollama:listModels')
+  },
+}
+
+// GitHub API (issues as change intents)
+const githubApi = {
+  /**
+   * List open issues of a repository.
+   * @param repo - "owner/repo" or the path of a local checkout
+   * @returns Open issues, newest first
+   */
+  listIssues: (repo: string): Promise<unknown[]> => {
+    return ipcRenderer.invoke('github:listIssues', repo)
+  },
+}
+
+// Doctor API (pre-flight diagnostics)
+const doctorApi = {
+  /**
+   * Check the Claude CLI, Docker, git, just, the MCP port, disk space and config directories.
+   * @returns One entry per check, with fix suggestions
+   */
+  run: (): Promise<unknown> => {
+    return ipcRenderer.invoke('doctor:run')
+  },
+
+  /**
+   * Export a diagnostics bundle (logs, redacted state, versions) to a zip chosen by the user.
+   * @returns The written file, or null if canceled
+   */
+  exportBundle: (): Promise<string | null> => {
+    return ipcRenderer.invoke('doctor:exportBundle')
+  },
+}
+
+// Git hooks API (rstn-managed pre-commit hook)
+const gitHooksApi = {
+  /**
+   * Whether the project's pre-commit hook is managed by rstn and up to date.
+   * @param project - Project root
+   */
+  status: (project: string): Promise<unknown> => {
+    return ipcRenderer.invoke('gitHooks:status', project)
+  },
+
+  /**
+   * Install or update the managed hook (rejects if a foreign hook exists).
+   * @param project - Project root
+   */
+  install: (project: string): Promise<unknown> => {
+    return ipcRenderer.invoke('gitHooks:install', project)
+  },
+
+  /**
+   * Remove the managed hook (rejects if the hook was not written by rstn).
+   * @param project - Project root
+   */
+  uninstall: (project: string): Promise<unknown> => {
+    return ipcRenderer.invoke('gitHooks:uninstall', project)
+  },
+}
+
+// Sessions API (Claude session history)
+const sessionsApi = {
+  /**
+   * Export a session transcript to a file chosen by the user.
+   * @param sessionId - rstn session ID
+   * @param format - "markdown" or "json"
+   * @returns The written file, or null if canceled
+   */
+  exportTranscript: (sessionId: string, format: 'markdown' | 'json'): Promise<string | null> => {
+    return ipcRenderer.invoke('sessions:export', sessionId, format)
+  },
+}
+
+// Explorer API (read-only queries that don't go through state)
+const explorerApi = {
+  /**
+   * List directory entries without changing the explorer state.
+   * @param path - Directory to list
+   * @param projectRoot - Root of the worktree
+   * @returns Entries of the directory
+   */
+  listDirectory: (path: string, projectRoot: string): Promise<unknown[]> => {
+    return ipcRenderer.invoke('explorer:listDirectory', path, projectRoot)
+  },
+
+  /**
+   * Commits that changed a file (following renames), newest first.
+   * @param path - Absolute file path
+   * @param limit - Maximum number of commits (default 20)
+   */
+  fileHistory: (path: string, limit?: number): Promise<unknown[]> => {
+    return ipcRenderer.invoke('explorer:fileHistory', path, limit)
+  },
+
+  /**
+   * Last commit of every line of a file, grouped into hunks.
+   * @param path - Absolute file path
+   */
+  blame: (path: string): Promise<unknown[]> => {
+    return ipcRenderer.invoke('explorer:blame', path)
+  },
+
+  /**
+   * Functions, types and methods of a source file (Rust, TS/JS, Python).
+   * @param path - Absolute file path
+   */
+  symbols: (path: string): Promise<unknown[]> => {
+    return ipcRenderer.invoke('explorer:symbols', path)
+  },
+
+  /**
+   * Language server hover text (markdown) at a position.
+   * Starts rust-analyzer / typescript-language-server / pyright on first use.
+   * @param path - Absolute file path
+   * @param line - 1-based line
+   * @param column - 1-based column (UTF-16)
+   */
+  hover: (path: string, line: number, column: number): Promise<string | null> => {
+    return ipcRenderer.invoke('explorer:hover', path, line, column)
+  },
+
+  /**
+   * Where the symbol at a position is defined (language server).
+   * @param path - Absolute file path
+   * @param line - 1-based line
+   * @param column - 1-based column (UTF-16)
+   */
+  definition: (path: string, line: number, column: number): Promise<unknown[]> => {
+    return ipcRenderer.invoke('explorer:definition', path, line, column)
+  },
+}
+
+// Change bundle API (offline review by people without rstn)
+const changesApi = {
+  /**
+   * Export a change (documents, implementation diff, test results, review
+   * comments) to a zip picked in a save dialog.
+   * @returns The written file path, or null if canceled
+   */
+  exportBundle: (changeId: string, changeName: string): Promise<string | null> => {
+    return ipcRenderer.invoke('changes:exportBundle', changeId, changeName)
+  },
+
+  /**
+   * Merge review comments from a bundle or review.json picked in an open dialog.
+   * @returns What was merged, or null if canceled
+   */
+  importFeedback: (): Promise<unknown | null> => {
+    return ipcRenderer.invoke('changes:importFeedback')
+  },
+}
+
+// Prompt library API (~/.rstn/prompts/library/)
+const promptsApi = {
+  /**
+   * List prompt templates.
+   * @returns Templates with their variables
+   */
+  list: (): Promise<unknown[]> => {
+    return ipcRenderer.invoke('prompts:list')
+  },
+
+  /**
+   * Render a template with variable values.
+   * @returns The rendered prompt (rejects if a required variable is missing)
+   */
+  render: (id: string, vars: Record<string, string>): Promise<string> => {
+    return ipcRenderer.invoke('prompts:render', id, vars)
+  },
+}
+
+// Markdown API (backend renderer shared by all document views)
+const markdownApi = {
+  /**
+   * Render markdown text to sanitized HTML.
+   * @returns HTML with mermaid placeholders, plus headings, code blocks and mermaid sources
+   */
+  render: (content: string): Promise<unknown> => {
+    return ipcRenderer.invoke('markdown:render', content)
+  },
+
+  /**
+   * Render a markdown file.
+   * @param path - Absolute file path
+   */
+  renderFile: (path: string): Promise<unknown> => {
+    return ipcRenderer.invoke('markdown:renderFile', path)
+  },
+}
+
+// Agent rules API (share profiles as TOML files)
+const agentRulesApi = {
+  /**
+   * Export a profile to a file picked in a save dialog.
+   * @returns The written file path, or null if canceled
+   */
+  exportProfile: (profileId: string, profileName: string): Promise<string | null> => {
+    return ipcRenderer.invoke('agentRules:export', profileId, profileName)
+  },
+
+  /**
+   * Read and validate a profile file picked in an open dialog.
+   * Dispatch `ImportAgentProfile` with the result to add it.
+   * @returns The parsed profile, or null if canceled
+   */
+  importProfile: (): Promise<unknown | null> => {
+    return ipcRenderer.invoke('agentRules:import')
+  },
 }
 
 // Screenshot API (dev mode)
@@ -42,6 +269,23 @@ const stateApi = {
     return ipcRenderer.invoke('state:get')
   },
 
+  /**
+   * List command palette actions for the current state.
+   * @returns Palette entries (each carries the action JSON to dispatch)
+   */
+  listPaletteActions: (): Promise<unknown[]> => {
+    return ipcRenderer.invoke('palette:listActions')
+  },
+
+  /**
+   * List the languages/frameworks detected in a project for constitution templates.
+   * @param path - Project (worktree) path
+   * @returns Detected stacks, flagging user template overrides
+   */
+  listConstitutionStacks: (path: string): Promise<unknown[]> => {
+    return ipcRenderer.invoke('constitution:listDetectedStacks', path)
+  },
+
   /**
    * Subscribe to state updates.
    * @param callback - Called with JSON string whenever state changes
@@ -58,13 +302,42 @@ const stateApi = {
   },
 }
 
+// Terminal API (PTY output stream; input goes through stateApi actions)
+const terminalApi = {
+  /**
+   * Subscribe to PTY output.
+   * @param callback - Called with session ID and output chunk
+   * @returns Unsubscribe function
+   */
+  onOutput: (callback: (sessionId: string, data: string) => void): (() => void) => {
+    const handler = (_event: Electron.IpcRendererEvent, sessionId: string, data: string): void => {
+      callback(sessionId, data)
+    }
+    ipcRenderer.on('terminal:output', handler)
+    return () => {
+      ipcRenderer.removeListener('terminal:output', handler)
+    }
+  },
+}
+
 // Expose electron APIs to renderer
 if (process.contextIsolated) {
   try {
     contextBridge.exposeInMainWorld('electron', electronAPI)
     contextBridge.exposeInMainWorld('stateApi', stateApi)
     contextBridge.exposeInMainWorld('dialogApi', dialogApi)
+    contextBridge.exposeInMainWorld('agentRulesApi', agentRulesApi)
+    contextBridge.exposeInMainWorld('promptsApi', promptsApi)
+    contextBridge.exposeInMainWorld('markdownApi', markdownApi)
+    contextBridge.exposeInMainWorld('ollamaApi', ollamaApi)
+    contextBridge.exposeInMainWorld('githubApi', githubApi)
+    contextBridge.exposeInMainWorld('explorerApi', explorerApi)
+    contextBridge.exposeInMainWorld('doctorApi', doctorApi)
+    contextBridge.exposeInMainWorld('gitHooksApi', gitHooksApi)
+    contextBridge.exposeInMainWorld('sessionsApi', sessionsApi)
+    contextBridge.exposeInMainWorld('changesApi', changesApi)
     contextBridge.exposeInMainWorld('screenshotApi', screenshotApi)
+    contextBridge.exposeInMainWorld('terminalApi', terminalApi)
   } catch (error) {
     console.error(error)
   }
@@ -76,5 +349,27 @@ if (process.contextIsolated) {
   // @ts-ignore (define in dts)
   window.dialogApi = dialogApi
   // @ts-ignore (define in dts)
+  window.agentRulesApi = agentRulesApi
+  // @ts-ignore (define in dts)
+  window.promptsApi = promptsApi
+  // @ts-ignore (define in dts)
+  window.markdownApi = markdownApi
+  // @ts-ignore (define in dts)
+  window.ollamaApi = ollamaApi
+  // @ts-ignore (define in dts)
+  window.githubApi = githubApi
+  // @ts-ignore (define in dts)
+  window.explorerApi = explorerApi
+  // @ts-ignore (define in dts)
+  window.doctorApi = doctorApi
+  // @ts-ignore (define in dts)
+  window.gitHooksApi = gitHooksApi
+  // @ts-ignore (define in dts)
+  window.sessionsApi = sessionsApi
+  // @ts-ignore (define in dts)
+  window.changesApi = changesApi
+  // @ts-ignore (define in dts)
   window.screenshotApi = screenshotApi
+  // @ts-ignore (define in dts)
+  window.terminalApi = terminalApi
 }
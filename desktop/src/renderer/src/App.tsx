@@ -4,6 +4,7 @@ import {
   FolderOpen,
 } from '@mui/icons-material'
 import { useAppState, useActiveWorktree } from '@/hooks/useAppState'
+import { commandForEvent } from '@/lib/keybindings'
 import type { ActiveView } from '@/types/state'
 import { DockersPage } from '@/features/dockers/DockersPage'
 import { TasksPage } from '@/features/tasks/TasksPage'
@@ -59,18 +60,26 @@ function App() {
   const { worktree } = useActiveWorktree()
   const [commandPaletteOpen, setCommandPaletteOpen] = useState(false)
 
-  // Global keyboard shortcut: Cmd+K / Ctrl+K
+  // Global keyboard shortcuts (configurable in Settings)
+  const keybindings = state?.global_settings?.keybindings
   useEffect(() => {
     const handleKeyDown = (e: KeyboardEvent) => {
-      if ((e.metaKey || e.ctrlKey) && e.key === 'k') {
-        e.preventDefault()
+      // The Settings shortcut recorder handles its own keys
+      if ((e.target as HTMLElement | null)?.closest?.('[data-shortcut-recorder]')) return
+      const command = commandForEvent(e, keybindings)
+      if (!command) return
+      e.preventDefault()
+      if (command === 'palette.toggle') {
         setCommandPaletteOpen((open) => !open)
+      } else if (command.startsWith('view.')) {
+        const view = command.slice('view.'.length).replace(/_/g, '-') as ActiveView
+        dispatch({ type: 'SetActiveView', payload: { view } })
       }
     }
 
     window.addEventListener('keydown', handleKeyDown)
     return () => window.removeEventListener('keydown', handleKeyDown)
-  }, [])
+  }, [keybindings, dispatch])
 
   // Use global active_view from state
   const activeView = state?.active_view ?? 'tasks'
@@ -0,0 +1,103 @@
+import { useEffect } from 'react'
+import {
+  AccountTree as WorktreeIcon,
+  CheckCircle as ApprovedIcon,
+  Gavel as ConstitutionIcon,
+  NoteAdd as ChangeIcon,
+  PlayArrow as ServiceIcon,
+} from '@mui/icons-material'
+import { Box, CircularProgress, Popover, Stack, Typography } from '@mui/material'
+import { useAppState } from '@/hooks/useAppState'
+import type { ActivityEvent, ActivityKind } from '@/types/state'
+
+const WEEK_MS = 7 * 24 * 60 * 60 * 1000
+
+const KIND_ICONS: Record<ActivityKind, React.ReactNode> = {
+  change_created: <ChangeIcon fontSize="small" />,
+  plan_approved: <ApprovedIcon fontSize="small" />,
+  service_started: <ServiceIcon fontSize="small" />,
+  worktree_added: <WorktreeIcon fontSize="small" />,
+  constitution_updated: <ConstitutionIcon fontSize="small" />,
+}
+
+/** Group events by local day, keeping newest-first order */
+function groupByDay(events: ActivityEvent[]): [string, ActivityEvent[]][] {
+  const days = new Map<string, ActivityEvent[]>()
+  for (const event of events) {
+    const day = new Date(event.timestamp).toLocaleDateString(undefined, {
+      weekday: 'long',
+      month: 'short',
+      day: 'numeric',
+    })
+    days.set(day, [...(days.get(day) ?? []), event])
+  }
+  return [...days.entries()]
+}
+
+interface ActivityFeedPopoverProps {
+  anchorEl: HTMLElement | null
+  onClose: () => void
+}
+
+/**
+ * ActivityFeedPopover - What happened in the active project this week
+ * (changes, approved plans, started services, worktrees, constitution)
+ */
+export function ActivityFeedPopover({ anchorEl, onClose }: ActivityFeedPopoverProps) {
+  const { state, dispatch } = useAppState()
+  const feed = state?.activity_feed
+  const open = !!anchorEl
+
+  useEffect(() => {
+    if (open) {
+      dispatch({ type: 'LoadActivityFeed', payload: { since: new Date(Date.now() - WEEK_MS).toISOString() } })
+    }
+  }, [open, dispatch])
+
+  return (
+    <Popover
+      open={open}
+      anchorEl={anchorEl}
+      onClose={onClose}
+      anchorOrigin={{ vertical: 'bottom', horizontal: 'right' }}
+      transformOrigin={{ vertical: 'top', horizontal: 'right' }}
+    >
+      <Box sx={{ p: 2, width: 360, maxHeight: 480, overflow: 'auto' }}>
+        <Typography variant="subtitle2" fontWeight={600} sx={{ mb: 1.5 }}>
+          This Week
+        </Typography>
+
+        {!feed || (feed.is_loading && feed.events.length === 0) ? (
+          <CircularProgress size={20} />
+        ) : feed.events.length === 0 ? (
+          <Typography variant="caption" color="text.secondary">
+            Nothing happened in this project in the last 7 days
+          </Typography>
+        ) : (
+          <Stack spacing={1.5}>
+            {groupByDay(feed.events).map(([day, events]) => (
+              <Box key={day}>
+                <Typography variant="caption" fontWeight={600} color="text.secondary" sx={{ display: 'block', mb: 0.5 }}>
+                  {day}
+                </Typography>
+                <Stack spacing={0.5}>
+                  {events.map((event) => (
+                    <Stack key={event.id} direction="row" spacing={1} alignItems="center">
+                      <Box sx={{ color: 'primary.main', display: 'flex' }}>{KIND_ICONS[event.kind]}</Box>
+                      <Typography variant="body2" sx={{ flex: 1, minWidth: 0 }} noWrap>
+                        {event.summary}
+                      </Typography>
+                      <Typography variant="caption" color="text.secondary">
+                        {new Date(event.timestamp).toLocaleTimeString(undefined, { hour: '2-digit', minute: '2-digit' })}
+                      </Typography>
+                    </Stack>
+                  ))}
+                </Stack>
+              </Box>
+            ))}
+          </Stack>
+        )}
+      </Box>
+    </Popover>
+  )
+}
@@ -0,0 +1,147 @@
+import { useCallback, useMemo } from 'react'
+import {
+  Alert,
+  Box,
+  Button,
+  FormControlLabel,
+  LinearProgress,
+  Popover,
+  Stack,
+  Switch,
+  Typography,
+} from '@mui/material'
+import { useActiveWorktree } from '@/hooks/useAppState'
+import type { Diagnostic } from '@/types/state'
+
+function groupByFile(diagnostics: Diagnostic[]): [string, Diagnostic[]][] {
+  const groups = new Map<string, Diagnostic[]>()
+  for (const diagnostic of diagnostics) {
+    const group = groups.get(diagnostic.file)
+    if (group) group.push(diagnostic)
+    else groups.set(diagnostic.file, [diagnostic])
+  }
+  return Array.from(groups.entries())
+}
+
+function DiagnosticRow({ diagnostic }: { diagnostic: Diagnostic }) {
+  return (
+    <Stack direction="row" spacing={1} sx={{ pl: 1 }}>
+      <Typography
+        variant="caption"
+        color={diagnostic.severity === 'error' ? 'error.main' : 'warning.main'}
+        sx={{ fontFamily: 'monospace', flexShrink: 0 }}
+      >
+        {diagnostic.line}:{diagnostic.column}
+      </Typography>
+      <Typography variant="caption" sx={{ wordBreak: 'break-word' }}>
+        {diagnostic.message}
+        {diagnostic.code && (
+          <Typography component="span" variant="caption" color="text.secondary" sx={{ ml: 0.5 }}>
+            {diagnostic.code}
+          </Typography>
+        )}
+      </Typography>
+    </Stack>
+  )
+}
+
+interface BuildDiagnosticsPopoverProps {
+  anchorEl: HTMLElement | null
+  onClose: () => void
+}
+
+/**
+ * BuildDiagnosticsPopover - Compiler errors and warnings of the active
+ * worktree's last build check
+ */
+export function BuildDiagnosticsPopover({ anchorEl, onClose }: BuildDiagnosticsPopoverProps) {
+  const { worktree, project, dispatch } = useActiveWorktree()
+  const build = worktree?.build ?? null
+  const isChecking = worktree?.is_checking_build ?? false
+  const files = useMemo(() => groupByFile(build?.diagnostics ?? []), [build])
+
+  const handleCheck = useCallback(() => {
+    dispatch({ type: 'RunBuildCheck' })
+  }, [dispatch])
+
+  const handleToggleOnSave = useCallback(
+    (_: React.ChangeEvent<HTMLInputElement>, enabled: boolean) => {
+      dispatch({ type: 'SetCheckBuildOnSave', payload: { enabled } })
+    },
+    [dispatch]
+  )
+
+  return (
+    <Popover
+      open={!!anchorEl}
+      anchorEl={anchorEl}
+      onClose={onClose}
+      anchorOrigin={{ vertical: 'bottom', horizontal: 'right' }}
+      transformOrigin={{ vertical: 'top', horizontal: 'right' }}
+    >
+      <Box sx={{ p: 2, width: 420 }}>
+        <Stack direction="row" alignItems="center" sx={{ mb: 1 }}>
+          <Typography variant="subtitle2" fontWeight={600} sx={{ flex: 1 }}>
+            Build
+            {build && (
+              <Typography component="span" variant="caption" color="text.secondary" sx={{ ml: 1 }}>
+                {build.error_count} errors · {build.warning_count} warnings
+              </Typography>
+            )}
+          </Typography>
+          <Button size="small" onClick={handleCheck} disabled={!worktree || isChecking}>
+            Check
+          </Button>
+        </Stack>
+        <FormControlLabel
+          control={
+            <Switch
+              size="small"
+              checked={project?.check_build_on_save ?? false}
+              onChange={handleToggleOnSave}
+              disabled={!project}
+            />
+          }
+          label={<Typography variant="caption">Check when sources change</Typography>}
+          sx={{ mb: 1 }}
+        />
+        {isChecking && <LinearProgress sx={{ mb: 1 }} />}
+
+        {!build ? (
+          <Typography variant="caption" color="text.secondary">
+            Run cargo check or tsc to collect compiler errors. Errors are included in the AI context.
+          </Typography>
+        ) : (
+          <Stack spacing={1} sx={{ maxHeight: 400, overflow: 'auto' }}>
+            {build.error && (
+              <Alert severity="warning" sx={{ py: 0 }}>
+                {build.error}
+              </Alert>
+            )}
+            {!build.error && build.diagnostics.length === 0 && (
+              <Alert severity="success" sx={{ py: 0 }}>
+                No errors or warnings ({build.checker})
+              </Alert>
+            )}
+            {files.map(([file, diagnostics]) => (
+              <Box key={file}>
+                <Typography variant="caption" fontWeight={600} sx={{ fontFamily: 'monospace', display: 'block' }}>
+                  {file}
+                </Typography>
+                {diagnostics.map((diagnostic) => (
+                  <DiagnosticRow
+                    key={`${diagnostic.line}:${diagnostic.column}:${diagnostic.message}`}
+                    diagnostic={diagnostic}
+                  />
+                ))}
+              </Box>
+            ))}
+            <Typography variant="caption" color="text.secondary">
+              Checked {new Date(build.checked_at).toLocaleString()} in {(build.duration_ms / 1000).toFixed(1)}s
+            </Typography>
+          </Stack>
+        )}
+      </Box>
+    </Popover>
+  )
+}
@@ -1,20 +1,35 @@
-import { useCallback } from 'react'
-import { IconButton, Stack, Tooltip } from '@mui/material'
+import { useCallback, useState } from 'react'
+import { Badge, IconButton, Stack, Tooltip } from '@mui/material'
 import {
   Assignment as TasksIcon,
   PhotoCamera as SnapshotIcon,
   Download as ImportIcon,
   Notifications as NotificationsIcon,
   BarChart as MetricsIcon,
+  Timeline as ActivityIcon,
+  Build as BuildIcon,
   Inventory as DockerIcon,
   Settings as SettingsIcon,
 } from '@mui/icons-material'
+import { useActiveWorktree, useAppState } from '@/hooks/useAppState'
+import { SystemMonitorPopover } from './SystemMonitorPopover'
+import { ActivityFeedPopover } from './ActivityFeedPopover'
+import { BuildDiagnosticsPopover } from './BuildDiagnosticsPopover'
 
 /**
- * GlobalIconBar - 7 icon buttons for global actions.
+ * GlobalIconBar - 9 icon buttons for global actions.
  * Positioned on the right side of the ProjectTabs.
  */
 export function GlobalIconBar() {
+  const { state } = useAppState()
+  const [metricsAnchor, setMetricsAnchor] = useState<HTMLElement | null>(null)
+  const [activityAnchor, setActivityAnchor] = useState<HTMLElement | null>(null)
+  const [buildAnchor, setBuildAnchor] = useState<HTMLElement | null>(null)
+  const systemWarnings = state?.system_stats?.warnings.length ?? 0
+  const { worktree } = useActiveWorktree()
+  const buildErrors = worktree?.build?.error_count ?? 0
+  const buildWarnings = worktree?.build?.warning_count ?? 0
+
   const handleSnapshot = useCallback(async () => {
     try {
       const result = await window.screenshotApi.capture()
@@ -33,7 +48,33 @@ export function GlobalIconBar() {
     { icon: <SnapshotIcon />, label: 'Snapshot', onClick: handleSnapshot },
     { icon: <ImportIcon />, label: 'Import', onClick: () => console.log('Import clicked') },
     { icon: <NotificationsIcon />, label: 'Notifications', onClick: () => console.log('Notifications clicked') },
-    { icon: <MetricsIcon />, label: 'Metrics', onClick: () => console.log('Metrics clicked') },
+    {
+      icon: (
+        <Badge color="warning" variant="dot" invisible={systemWarnings === 0}>
+          <MetricsIcon />
+        </Badge>
+      ),
+      label: 'Metrics',
+      onClick: (e: React.MouseEvent<HTMLElement>) => setMetricsAnchor(e.currentTarget),
+    },
+    {
+      icon: <ActivityIcon />,
+      label: 'Activity',
+      onClick: (e: React.MouseEvent<HTMLElement>) => setActivityAnchor(e.currentTarget),
+    },
+    {
+      icon: (
+        <Badge
+          color={buildErrors > 0 ? 'error' : 'warning'}
+          badgeContent={buildErrors > 0 ? buildErrors : buildWarnings}
+          max={99}
+        >
+          <BuildIcon />
+        </Badge>
+      ),
+      label: 'Build',
+      onClick: (e: React.MouseEvent<HTMLElement>) => setBuildAnchor(e.currentTarget),
+    },
     { icon: <DockerIcon />, label: 'Docker', onClick: () => console.log('Docker clicked') },
     { icon: <SettingsIcon />, label: 'Settings', onClick: () => console.log('Settings clicked') },
   ]
@@ -67,6 +108,9 @@ export function GlobalIconBar() {
           </IconButton>
         </Tooltip>
       ))}
+      <SystemMonitorPopover anchorEl={metricsAnchor} onClose={() => setMetricsAnchor(null)} />
+      <ActivityFeedPopover anchorEl={activityAnchor} onClose={() => setActivityAnchor(null)} />
+      <BuildDiagnosticsPopover anchorEl={buildAnchor} onClose={() => setBuildAnchor(null)} />
     </Stack>
   )
 }
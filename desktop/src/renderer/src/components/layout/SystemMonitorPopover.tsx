@@ -0,0 +1,114 @@
+import { Alert, Box, LinearProgress, Popover, Stack, Typography } from '@mui/material'
+import { useAppState } from '@/hooks/useAppState'
+import type { ProcessGroup } from '@/types/state'
+
+const GROUP_LABELS: Record<ProcessGroup, string> = {
+  claude_cli: 'Claude CLI',
+  containers: 'Containers',
+  terminals: 'Terminals',
+}
+
+function formatBytes(bytes: number): string {
+  const gb = bytes / 1024 / 1024 / 1024
+  if (gb >= 1) return `${gb.toFixed(1)} GB`
+  return `${Math.round(bytes / 1024 / 1024)} MB`
+}
+
+function percentOf(part: number, total: number): number {
+  return total > 0 ? (part / total) * 100 : 0
+}
+
+function UsageBar({ label, value, detail }: { label: string; value: number; detail: string }) {
+  const color = value >= 90 ? 'error' : value >= 70 ? 'warning' : 'primary'
+  return (
+    <Box>
+      <Stack direction="row" justifyContent="space-between">
+        <Typography variant="caption" fontWeight={600}>
+          {label}
+        </Typography>
+        <Typography variant="caption" color="text.secondary">
+          {detail}
+        </Typography>
+      </Stack>
+      <LinearProgress variant="determinate" value={Math.min(value, 100)} color={color} sx={{ height: 6, borderRadius: 3 }} />
+    </Box>
+  )
+}
+
+interface SystemMonitorPopoverProps {
+  anchorEl: HTMLElement | null
+  onClose: () => void
+}
+
+/**
+ * SystemMonitorPopover - Machine CPU/memory/disk and what rstn's own work
+ * (Claude CLI runs, containers, terminals) costs
+ */
+export function SystemMonitorPopover({ anchorEl, onClose }: SystemMonitorPopoverProps) {
+  const { state } = useAppState()
+  const stats = state?.system_stats
+
+  return (
+    <Popover
+      open={!!anchorEl}
+      anchorEl={anchorEl}
+      onClose={onClose}
+      anchorOrigin={{ vertical: 'bottom', horizontal: 'right' }}
+      transformOrigin={{ vertical: 'top', horizontal: 'right' }}
+    >
+      <Box sx={{ p: 2, width: 320 }}>
+        <Typography variant="subtitle2" fontWeight={600} sx={{ mb: 1.5 }}>
+          System
+        </Typography>
+
+        {!stats ? (
+          <Typography variant="caption" color="text.secondary">
+            Collecting the first sample...
+          </Typography>
+        ) : (
+          <Stack spacing={1.5}>
+            {stats.warnings.map((warning) => (
+              <Alert key={warning} severity="warning" sx={{ py: 0 }}>
+                {warning}
+              </Alert>
+            ))}
+
+            <UsageBar
+              label="CPU"
+              value={stats.cpu_percent}
+              detail={`${stats.cpu_percent.toFixed(0)}% of ${stats.cpu_count} cores`}
+            />
+            <UsageBar
+              label="Memory"
+              value={percentOf(stats.memory_used, stats.memory_total)}
+              detail={`${formatBytes(stats.memory_used)} / ${formatBytes(stats.memory_total)}`}
+            />
+            {stats.disk_total > 0 && (
+              <UsageBar
+                label={`Disk ${stats.disk_mount}`}
+                value={percentOf(stats.disk_used, stats.disk_total)}
+                detail={`${formatBytes(stats.disk_used)} / ${formatBytes(stats.disk_total)}`}
+              />
+            )}
+
+            <Box>
+              <Typography variant="caption" fontWeight={600} sx={{ display: 'block', mb: 0.5 }}>
+                rstn
+              </Typography>
+              {stats.groups.map((group) => (
+                <Stack key={group.group} direction="row" justifyContent="space-between">
+                  <Typography variant="caption" color="text.secondary">
+                    {GROUP_LABELS[group.group]} ({group.count})
+                  </Typography>
+                  <Typography variant="caption" sx={{ fontFamily: 'monospace' }}>
+                    {group.cpu_percent.toFixed(0)}% · {formatBytes(group.memory_bytes)}
+                  </Typography>
+                </Stack>
+              ))}
+            </Box>
+          </Stack>
+        )}
+      </Box>
+    </Popover>
+  )
+}
@@ -0,0 +1,166 @@
+import * as React from 'react'
+import { useCallback, useEffect, useRef, useState } from 'react'
+import { Chip, Paper, Popper, Tooltip } from '@mui/material'
+import ReactMarkdown from 'react-markdown'
+import { useActiveWorktree } from '@/hooks/useAppState'
+import type { LanguageServer } from '@/types/state'
+import { MarkdownDisplay } from './MarkdownDisplay'
+
+/** Pause before asking the language server for a hover */
+const HOVER_DELAY_MS = 500
+/** Pointer travel that dismisses an open hover */
+const HOVER_DISMISS_PX = 8
+
+const SERVER_LABELS: Record<LanguageServer, string> = {
+  rust_analyzer: 'rust-analyzer',
+  typescript: 'typescript-language-server',
+  pyright: 'pyright',
+}
+
+/** Language server for a file (mirrors LanguageServer::for_path in core) */
+export function languageServerForPath(path: string): LanguageServer | null {
+  const ext = path.split('.').pop()?.toLowerCase() || ''
+  if (ext === 'rs') return 'rust_analyzer'
+  if (['ts', 'tsx', 'mts', 'cts', 'js', 'jsx', 'mjs', 'cjs'].includes(ext)) return 'typescript'
+  if (ext === 'py' || ext === 'pyi') return 'pyright'
+  return null
+}
+
+/**
+ * 1-based line and column of the character under the pointer.
+ * Lines are the elements carrying `data-line`; columns count UTF-16 code
+ * units like LSP positions.
+ */
+function positionAt(target: EventTarget, x: number, y: number): { line: number; column: number } | null {
+  // Only over code, not the padding after the end of a line
+  const token = target instanceof HTMLElement ? target : null
+  const lineElement = token?.closest<HTMLElement>('[data-line]')
+  if (!token || !lineElement || token === lineElement) return null
+
+  const caret = document.caretRangeFromPoint?.(x, y)
+  if (!caret || !lineElement.contains(caret.startContainer)) return null
+  const before = document.createRange()
+  before.setStart(lineElement, 0)
+  before.setEnd(caret.startContainer, caret.startOffset)
+  return { line: Number(lineElement.dataset.line), column: before.toString().length + 1 }
+}
+
+interface HoverState {
+  anchor: { getBoundingClientRect: () => DOMRect }
+  x: number
+  y: number
+  contents: string
+}
+
+/**
+ * Hover and Cmd/Ctrl+click go-to-definition for a source file, backed by the
+ * worktree's language server. Spread `handlers` on the element containing
+ * the `data-line` rows and render `popper` next to it.
+ */
+export function useCodeIntelligence(path: string, enabled: boolean) {
+  const { dispatch } = useActiveWorktree()
+  const [hover, setHover] = useState<HoverState | null>(null)
+  const timer = useRef<ReturnType<typeof setTimeout> | undefined>(undefined)
+  const request = useRef(0)
+  const active = enabled && languageServerForPath(path) !== null
+
+  useEffect(() => {
+    setHover(null)
+    return () => clearTimeout(timer.current)
+  }, [path])
+
+  const onMouseMove = useCallback(
+    (e: React.MouseEvent<HTMLElement>) => {
+      if (!active) return
+      clearTimeout(timer.current)
+      request.current += 1
+      const { clientX: x, clientY: y, target } = e
+      setHover((current) =>
+        current && Math.hypot(current.x - x, current.y - y) > HOVER_DISMISS_PX ? null : current
+      )
+      timer.current = setTimeout(async () => {
+        const position = positionAt(target, x, y)
+        if (!position) return
+        const id = request.current
+        try {
+          const contents = await window.explorerApi.hover(path, position.line, position.column)
+          if (contents && id === request.current) {
+            setHover({ anchor: { getBoundingClientRect: () => new DOMRect(x, y, 0, 0) }, x, y, contents })
+          }
+        } catch {
+          // Server missing or still starting (its status is in state)
+        }
+      }, HOVER_DELAY_MS)
+    },
+    [active, path]
+  )
+
+  const onMouseLeave = useCallback(() => {
+    clearTimeout(timer.current)
+    request.current += 1
+    setHover(null)
+  }, [])
+
+  const onClick = useCallback(
+    async (e: React.MouseEvent<HTMLElement>) => {
+      if (!active || !(e.metaKey || e.ctrlKey)) return
+      const position = positionAt(e.target, e.clientX, e.clientY)
+      if (!position) return
+      const container = e.currentTarget
+      e.preventDefault()
+      try {
+        const [location] = await window.explorerApi.definition(path, position.line, position.column)
+        if (!location) return
+        if (location.path === path) {
+          container.querySelector(`[data-line="${location.line}"]`)?.scrollIntoView({ block: 'center' })
+        } else {
+          dispatch({ type: 'SelectFile', payload: { path: location.path } })
+          dispatch({ type: 'OpenFileTab', payload: { path: location.path } })
+        }
+      } catch {
+        // Same as hover
+      }
+    },
+    [active, path, dispatch]
+  )
+
+  const popper = (
+    <Popper open={!!hover} anchorEl={hover?.anchor} placement="bottom-start" sx={{ zIndex: 'tooltip' }}>
+      <Paper
+        elevation={4}
+        sx={{ p: 1.5, maxWidth: 560, maxHeight: 320, overflow: 'auto', '& pre': { whiteSpace: 'pre-wrap' } }}
+      >
+        <MarkdownDisplay>
+          <ReactMarkdown>{hover?.contents ?? ''}</ReactMarkdown>
+        </MarkdownDisplay>
+      </Paper>
+    </Popper>
+  )
+
+  return { handlers: { onMouseMove, onMouseLeave, onClick }, popper, active }
+}
+
+/**
+ * LanguageServerStatus - Chip for the file's language server when it is
+ * starting or failed (click to restart)
+ */
+export function LanguageServerStatus({ path }: { path: string }): React.ReactElement | null {
+  const { worktree, dispatch } = useActiveWorktree()
+  const server = languageServerForPath(path)
+  const state = worktree?.lsp_servers?.find((s) => s.server === server)
+  if (!server || !state || state.status === 'running') return null
+
+  const failed = state.status === 'failed'
+  return (
+    <Tooltip title={failed ? `${state.error ?? 'Failed'} · click to retry` : 'Starting language server'}>
+      <Chip
+        label={`${SERVER_LABELS[server]}${failed ? ' unavailable' : '…'}`}
+        size="small"
+        color={failed ? 'warning' : 'default'}
+        variant="outlined"
+        onClick={failed ? () => dispatch({ type: 'StopLspServers' }) : undefined}
+        sx={{ height: 18, fontSize: '0.65rem' }}
+      />
+    </Tooltip>
+  )
+}
@@ -1,5 +1,15 @@
-import { useState, useCallback, useMemo } from 'react'
-import { Box, IconButton, Paper, Stack, Typography } from '@mui/material'
+import { useState, useCallback, useEffect, useMemo } from 'react'
+import {
+  Box,
+  IconButton,
+  MenuItem,
+  Paper,
+  Stack,
+  TextField,
+  ToggleButton,
+  ToggleButtonGroup,
+  Typography,
+} from '@mui/material'
 import { alpha } from '@mui/material/styles'
 import {
   BugReport,
@@ -9,9 +19,12 @@ import {
   ContentCopy,
   DeleteOutline,
   ExpandMore,
+  Refresh,
 } from '@mui/icons-material'
 import { useAppState } from '@/hooks/useAppState'
-import type { DevLog, DevLogSource, DevLogType } from '@/types/state'
+import type { AppLogEntry, AppLogLevel, DevLog, DevLogSource, DevLogType } from '@/types/state'
+
+const APP_LOG_LEVELS = ['error', 'warn', 'info', 'debug'] as const
 
 /**
  * DevLogPanel - Right-side panel for displaying development logs
@@ -20,6 +33,7 @@ import type { DevLog, DevLogSource, DevLogType } from '@/types/state'
  * - Collapsible entries (collapsed = summary, expanded = beautiful JSON)
  * - Source and type badges with colors
  * - Clear all logs button
+ * - Backend view: tracing log entries filtered by level and module
  * - Dev mode only
  */
 export function DevLogPanel() {
@@ -27,7 +41,21 @@ export function DevLogPanel() {
   const [expandedIds, setExpandedIds] = useState<Set<string>>(new Set())
   const [isOpen, setIsOpen] = useState(true)
 
+  const [view, setView] = useState<'actions' | 'backend'>('actions')
+  const [level, setLevel] = useState<(typeof APP_LOG_LEVELS)[number]>('info')
+  const [module, setModule] = useState('')
+
   const devLogs = state?.dev_logs ?? []
+  const appLogs = state?.app_logs ?? []
+
+  const loadAppLogs = useCallback(async () => {
+    await dispatch({ type: 'LoadAppLogs', payload: { level, module: module.trim() || null } })
+  }, [dispatch, level, module])
+
+  // Reload backend logs when the view opens or the filters change
+  useEffect(() => {
+    if (view === 'backend') loadAppLogs()
+  }, [view, loadAppLogs])
 
   const toggleExpand = useCallback((id: string) => {
     setExpandedIds((prev) => {
@@ -70,27 +98,85 @@ export function DevLogPanel() {
           <BugReport fontSize="small" sx={{ color: 'warning.main' }} />
           <Typography variant="subtitle2">Dev Logs</Typography>
           <Typography variant="caption" color="text.secondary">
-            ({devLogs.length})
+            ({view === 'actions' ? devLogs.length : appLogs.length})
           </Typography>
         </Stack>
         <Stack direction="row" spacing={0.5}>
-          <IconButton
-            size="small"
-            onClick={handleClear}
-            disabled={devLogs.length === 0}
-            title="Clear all logs"
-          >
-            <DeleteOutline fontSize="small" />
-          </IconButton>
+          {view === 'actions' ? (
+            <IconButton
+              size="small"
+              onClick={handleClear}
+              disabled={devLogs.length === 0}
+              title="Clear all logs"
+            >
+              <DeleteOutline fontSize="small" />
+            </IconButton>
+          ) : (
+            <IconButton size="small" onClick={loadAppLogs} title="Reload backend logs">
+              <Refresh fontSize="small" />
+            </IconButton>
+          )}
           <IconButton size="small" onClick={handleClose} title="Close panel">
             <Close fontSize="small" />
           </IconButton>
         </Stack>
       </Stack>
 
+      <Stack spacing={1} sx={{ px: 2, pt: 1 }}>
+        <ToggleButtonGroup
+          size="small"
+          exclusive
+          fullWidth
+          value={view}
+          onChange={(_, value) => value && setView(value)}
+        >
+          <ToggleButton value="actions" sx={{ py: 0.25, fontSize: '0.7rem' }}>Actions</ToggleButton>
+          <ToggleButton value="backend" sx={{ py: 0.25, fontSize: '0.7rem' }}>Backend</ToggleButton>
+        </ToggleButtonGroup>
+        {view === 'backend' && (
+          <Stack direction="row" spacing={1}>
+            <TextField
+              select
+              size="small"
+              value={level}
+              onChange={(e) => setLevel(e.target.value as (typeof APP_LOG_LEVELS)[number])}
+              sx={{ width: 96 }}
+              inputProps={{ 'aria-label': 'Minimum level' }}
+            >
+              {APP_LOG_LEVELS.map((l) => (
+                <MenuItem key={l} value={l}>
+                  {l}
+                </MenuItem>
+              ))}
+            </TextField>
+            <TextField
+              size="small"
+              placeholder="Module"
+              value={module}
+              onChange={(e) => setModule(e.target.value)}
+              sx={{ flex: 1 }}
+            />
+          </Stack>
+        )}
+      </Stack>
+
       {/* Log Entries */}
       <Box sx={{ flex: 1, overflow: 'auto' }}>
-        {devLogs.length === 0 ? (
+        {view === 'backend' ? (
+          appLogs.length === 0 ? (
+            <Stack alignItems="center" justifyContent="center" sx={{ height: 128 }}>
+              <Typography variant="body2" color="text.secondary">
+                No backend logs
+              </Typography>
+            </Stack>
+          ) : (
+            <Stack sx={{ p: 2 }}>
+              {appLogs.map((entry, index) => (
+                <AppLogRow key={`${entry.timestamp}-${index}`} entry={entry} />
+              ))}
+            </Stack>
+          )
+        ) : devLogs.length === 0 ? (
           <Stack alignItems="center" justifyContent="center" sx={{ height: 128 }}>
             <Typography variant="body2" color="text.secondary">
               No dev logs yet
@@ -113,6 +199,60 @@ export function DevLogPanel() {
   )
 }
 
+function AppLogRow({ entry }: { entry: AppLogEntry }) {
+  const time = new Date(entry.timestamp).toLocaleTimeString()
+  const module = entry.module.replace(/^rstn_core(::)?/, '') || 'core'
+
+  return (
+    <Box sx={{ py: 0.5, borderBottom: 1, borderColor: 'divider' }}>
+      <Stack direction="row" alignItems="center" spacing={0.75}>
+        <LevelBadge level={entry.level} />
+        <Typography variant="caption" color="text.secondary" noWrap sx={{ flex: 1 }}>
+          {module}
+        </Typography>
+        <Typography variant="caption" color="text.secondary">
+          {time}
+        </Typography>
+      </Stack>
+      <Typography
+        variant="caption"
+        component="div"
+        sx={{ fontFamily: 'monospace', fontSize: '0.65rem', whiteSpace: 'pre-wrap', wordBreak: 'break-word' }}
+      >
+        {entry.message}
+      </Typography>
+    </Box>
+  )
+}
+
+function LevelBadge({ level }: { level: AppLogLevel }) {
+  const color = {
+    ERROR: '#d32f2f',
+    WARN: '#ef6c00',
+    INFO: '#1e88e5',
+    DEBUG: '#546e7a',
+    TRACE: '#546e7a',
+  }[level] ?? '#546e7a'
+
+  return (
+    <Box
+      component="span"
+      sx={{
+        flexShrink: 0,
+        borderRadius: 0.5,
+        px: 0.75,
+        py: 0.25,
+        fontSize: '0.55rem',
+        fontWeight: 700,
+        color,
+        bgcolor: alpha(color, 0.16),
+      }}
+    >
+      {level}
+    </Box>
+  )
+}
+
 interface DevLogEntryProps {
   log: DevLog
   isExpanded: boolean
@@ -1,7 +1,6 @@
-import { useEffect, useId, useRef, useState } from 'react'
+import { Fragment, useEffect, useId, useRef, useState } from 'react'
 import { Box, CircularProgress, Stack, Typography } from '@mui/material'
 import { Description } from '@mui/icons-material'
-import ReactMarkdown from 'react-markdown'
 import mermaid from 'mermaid'
 import { MarkdownDisplay } from './MarkdownDisplay'
 
@@ -106,30 +105,60 @@ function MermaidBlock({ code }: { code: string }) {
   )
 }
 
+type RenderedMarkdown = Awaited<ReturnType<typeof window.markdownApi.render>>
+
+/** Placeholder the backend renderer leaves for each mermaid fence */
+const MERMAID_PLACEHOLDER = /<div class="mermaid" data-mermaid-index="(\d+)"><\/div>/
+
 /**
- * Custom code block renderer that handles mermaid diagrams
+ * MarkdownDocument - Markdown rendered by the backend (sanitized HTML, same
+ * output in every view) with mermaid diagrams drawn in place.
  */
-function CodeBlock({
-  className,
-  children,
-}: {
-  className?: string
-  children?: React.ReactNode
-}) {
-  const match = /language-(\w+)/.exec(className || '')
-  const language = match?.[1]
-  const code = String(children).replace(/\n$/, '')
-
-  // Render mermaid diagrams
-  if (language === 'mermaid') {
-    return <MermaidBlock code={code} />
+export function MarkdownDocument({ content }: { content: string }): React.ReactElement | null {
+  const [rendered, setRendered] = useState<RenderedMarkdown | null>(null)
+  const [error, setError] = useState<string | null>(null)
+
+  useEffect(() => {
+    let cancelled = false
+    window.markdownApi
+      .render(content)
+      .then((result) => {
+        if (!cancelled) {
+          setRendered(result)
+          setError(null)
+        }
+      })
+      .catch((err) => {
+        if (!cancelled) setError(err instanceof Error ? err.message : 'Failed to render markdown')
+      })
+    return () => {
+      cancelled = true
+    }
+  }, [content])
+
+  if (error) {
+    return (
+      <Typography variant="caption" color="error">
+        {error}
+      </Typography>
+    )
   }
+  if (!rendered) return null
 
-  // Default code block rendering
+  // split() with a capture group alternates HTML chunks and diagram indexes
+  const parts = rendered.html.split(MERMAID_PLACEHOLDER)
   return (
-    <code className={className}>
-      {children}
-    </code>
+    <>
+      {parts.map((part, i) =>
+        i % 2 === 0 ? (
+          <div key={i} dangerouslySetInnerHTML={{ __html: part }} />
+        ) : (
+          <Fragment key={i}>
+            {rendered.mermaid[Number(part)] && <MermaidBlock code={rendered.mermaid[Number(part)].source} />}
+          </Fragment>
+        )
+      )}
+    </>
   )
 }
 
@@ -144,13 +173,7 @@ export function MarkdownPreview({ content, path, showHeader = true }: MarkdownPr
   if (!showHeader) {
     return (
       <MarkdownDisplay>
-        <ReactMarkdown
-          components={{
-            code: CodeBlock,
-          }}
-        >
-          {content}
-        </ReactMarkdown>
+        <MarkdownDocument content={content} />
       </MarkdownDisplay>
     )
   }
@@ -190,13 +213,7 @@ export function MarkdownPreview({ content, path, showHeader = true }: MarkdownPr
       {/* Content */}
       <Box sx={{ flex: 1, overflow: 'auto', p: 2 }}>
         <MarkdownDisplay>
-          <ReactMarkdown
-            components={{
-              code: CodeBlock,
-            }}
-          >
-            {content}
-          </ReactMarkdown>
+          <MarkdownDocument content={content} />
         </MarkdownDisplay>
       </Box>
     </Box>
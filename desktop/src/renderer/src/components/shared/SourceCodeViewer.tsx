@@ -18,7 +18,8 @@ import { List, RowComponentProps, useListRef } from 'react-window'
 import { AutoSizer } from 'react-virtualized-auto-sizer'
 import { useAppState } from '@/hooks/useAppState'
 import { MarkdownPreview } from './MarkdownPreview'
-import { getFileCategory, isBinaryFile } from '@/utils/fileTypes'
+import { LanguageServerStatus, useCodeIntelligence } from './CodeIntelligence'
+import { formatFileSize, getFileCategory, isBinaryFile } from '@/utils/fileTypes'
 import { ImageViewer } from './viewers/ImageViewer'
 import { VideoViewer } from './viewers/VideoViewer'
 import { PdfViewer } from './viewers/PdfViewer'
@@ -469,6 +470,7 @@ function VirtualizedRow({ index, style, ...props }: RowComponentProps<RowData>)
         )}
         <Box
           component="span"
+          data-line={lineNumber}
           sx={{
             flex: 1,
             pl: 1.5,
@@ -574,6 +576,7 @@ function SimpleCodeRenderer({
               )}
               <Box
                 component="span"
+                data-line={lineNumber}
                 sx={{
                   flex: 1,
                   pl: 1.5,
@@ -663,6 +666,8 @@ export function SourceCodeViewer({
   // Detect file category and dispatch appropriate read action
   const fileCategory = useMemo(() => getFileCategory(path), [path])
   const needsBinary = useMemo(() => isBinaryFile(fileCategory), [fileCategory])
+  // Hover / Cmd+click go-to-definition from the language server
+  const codeIntelligence = useCodeIntelligence(path, !needsBinary)
 
   useEffect(() => {
     if (needsBinary) {
@@ -742,10 +747,35 @@ export function SourceCodeViewer({
 
   const content = viewerState.path === path ? viewerState.content : ''
   const binaryContent = viewerState.path === path ? viewerState.binary_content : null
+  const binaryPreview = viewerState.path === path ? viewerState.binary_preview : null
+
+  if (binaryPreview?.kind === 'too_large') {
+    return (
+      <Alert severity="info">
+        <Typography variant="body2">
+          {`File too large to preview (${formatFileSize(binaryPreview.size)}, max ${formatFileSize(binaryPreview.limit)})`}
+        </Typography>
+      </Alert>
+    )
+  }
+
+  if (binaryPreview?.kind === 'hexdump') {
+    return (
+      <Box sx={{ height: '100%', overflow: 'auto', p: 2 }}>
+        <Typography variant="caption" color="text.secondary">
+          {`${binaryPreview.mime} · ${formatFileSize(binaryPreview.size)}`}
+          {binaryPreview.truncated && ' · showing first 4 KB'}
+        </Typography>
+        <Box component="pre" sx={{ fontFamily: 'monospace', fontSize: 12, m: 0, mt: 1 }}>
+          {binaryPreview.lines.join('\n')}
+        </Box>
+      </Box>
+    )
+  }
 
   // Route to appropriate viewer based on file type
-  if (fileCategory === 'image' && binaryContent) {
-    return <ImageViewer path={path} />
+  if (fileCategory === 'image' && (binaryContent || binaryPreview?.kind === 'image')) {
+    return <ImageViewer path={path} size={binaryPreview?.size} />
   }
 
   if (fileCategory === 'video') {
@@ -824,6 +854,7 @@ export function SourceCodeViewer({
             </IconButton>
           </Tooltip>
         )}
+        <LanguageServerStatus path={path} />
         <Typography variant="caption" color="text.secondary">
           {isMarkdown && markdownViewMode === 'preview'
             ? 'Markdown'
@@ -837,7 +868,11 @@ export function SourceCodeViewer({
         </Box>
       ) : (
         /* Source code mode (with inline comments support) */
-        <Box sx={{ flex: 1, minHeight: 0, overflow: useVirtualization ? 'hidden' : 'auto' }}>
+        <Box
+          sx={{ flex: 1, minHeight: 0, overflow: useVirtualization ? 'hidden' : 'auto' }}
+          {...codeIntelligence.handlers}
+        >
+          {codeIntelligence.popper}
           <Highlight theme={themes.vsDark} code={content || ''} language={language} prism={Prism}>
             {({ style, tokens, getTokenProps }) => {
               // Create a function to get token style
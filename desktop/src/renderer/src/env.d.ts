@@ -81,8 +81,46 @@ interface ExplorerFileEntry {
   git_status: string | null
 }
 
+interface FileCommit {
+  hash: string
+  shortHash: string
+  author: string
+  email: string
+  date: string // ISO 8601
+  summary: string
+}
+
+interface BlameHunk {
+  lineStart: number // 1-based
+  lineCount: number
+  commit: FileCommit
+}
+
+interface FileSymbol {
+  name: string
+  kind: string // "function" | "method" | "struct" | "class" | "interface" | ...
+  signature: string
+  startLine: number // 1-based
+  endLine: number
+  depth: number
+  parent?: string
+}
+
+interface LspLocation {
+  path: string
+  line: number // 1-based
+  column: number
+  endLine: number
+  endColumn: number
+}
+
 interface ExplorerApi {
   listDirectory(path: string, projectRoot: string): Promise<ExplorerFileEntry[]>
+  fileHistory(path: string, limit?: number): Promise<FileCommit[]>
+  blame(path: string): Promise<BlameHunk[]>
+  symbols(path: string): Promise<FileSymbol[]>
+  hover(path: string, line: number, column: number): Promise<string | null>
+  definition(path: string, line: number, column: number): Promise<LspLocation[]>
 }
 
 // Augment global Window interface
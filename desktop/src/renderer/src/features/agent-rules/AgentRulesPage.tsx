@@ -4,7 +4,8 @@ import {
   Refresh as RefreshIcon,
   WarningAmber as AlertTriangleIcon,
   Info as InfoIcon,
-  Add as PlusIcon
+  Add as PlusIcon,
+  FileUpload as ImportIcon
 } from '@mui/icons-material'
 import {
   Button,
@@ -76,6 +77,38 @@ export function AgentRulesPage() {
     [dispatch],
   )
 
+  const handleImportProfile = useCallback(async () => {
+    try {
+      const profile = await window.agentRulesApi.importProfile()
+      if (!profile) return
+      await dispatch({ type: 'ImportAgentProfile', payload: { profile } })
+    } catch (error) {
+      await dispatch({
+        type: 'AddNotification',
+        payload: { message: `Import failed: ${error instanceof Error ? error.message : String(error)}`, notification_type: 'error' },
+      })
+    }
+  }, [dispatch])
+
+  const handleExportProfile = useCallback(
+    async (profile: AgentProfile) => {
+      try {
+        const path = await window.agentRulesApi.exportProfile(profile.id, profile.name)
+        if (!path) return
+        await dispatch({
+          type: 'AddNotification',
+          payload: { message: `Exported "${profile.name}" to ${path}`, notification_type: 'success' },
+        })
+      } catch (error) {
+        await dispatch({
+          type: 'AddNotification',
+          payload: { message: `Export failed: ${error instanceof Error ? error.message : String(error)}`, notification_type: 'error' },
+        })
+      }
+    },
+    [dispatch],
+  )
+
   const handleSaveProfile = useCallback(
     async (name: string, prompt: string) => {
       if (editingProfile) {
@@ -164,15 +197,26 @@ export function AgentRulesPage() {
           <CardContent sx={{ p: 3 }}>
             <Box sx={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between', mb: 2.5 }}>
               <Typography variant="h6" fontWeight={600}>Active Profile</Typography>
-              <Button 
-                variant="contained" 
-                size="small" 
-                onClick={handleCreateProfile} 
-                startIcon={<PlusIcon />}
-                sx={{ borderRadius: 2 }}
-              >
-                New Profile
-              </Button>
+              <Stack direction="row" spacing={1}>
+                <Button
+                  variant="outlined"
+                  size="small"
+                  onClick={handleImportProfile}
+                  startIcon={<ImportIcon />}
+                  sx={{ borderRadius: 2 }}
+                >
+                  Import
+                </Button>
+                <Button 
+                  variant="contained" 
+                  size="small" 
+                  onClick={handleCreateProfile} 
+                  startIcon={<PlusIcon />}
+                  sx={{ borderRadius: 2 }}
+                >
+                  New Profile
+                </Button>
+              </Stack>
             </Box>
 
             <ProfileSelector
@@ -232,6 +276,7 @@ export function AgentRulesPage() {
               activeProfileId={agentRulesConfig.active_profile_id}
               onEdit={handleEditProfile}
               onDelete={handleDeleteProfile}
+              onExport={handleExportProfile}
               onSelect={handleSelectProfile}
             />
           </CardContent>
@@ -248,6 +293,8 @@ export function AgentRulesPage() {
                 <li>Built-in profiles (⭐) provide expert templates</li>
                 <li>Create custom profiles to define your own coding standards</li>
                 <li>Built-in profiles cannot be edited or deleted</li>
+                <li>Export profiles as TOML files to share them; imported files are validated first</li>
+                <li>Rules in <code>.rstn/rules/*.md</code> are added for the directories of open files (deepest last)</li>
               </Box>
             </Box>
           </Stack>
@@ -1,6 +1,7 @@
 import {
   Edit as PencilIcon,
   Delete as Trash2Icon,
+  FileDownload as ExportIcon,
   Star as StarIcon
 } from '@mui/icons-material'
 import {
@@ -25,6 +26,8 @@ interface ProfileListProps {
   onEdit: (profile: AgentProfile) => void
   /** Callback when delete is clicked */
   onDelete: (profileId: string) => void
+  /** Callback when export is clicked */
+  onExport: (profile: AgentProfile) => void
   /** Callback when a profile is selected */
   onSelect: (profileId: string) => void
 }
@@ -37,6 +40,7 @@ export function ProfileList({
   activeProfileId,
   onEdit,
   onDelete,
+  onExport,
   onSelect,
 }: ProfileListProps) {
   if (profiles.length === 0) {
@@ -79,11 +83,24 @@ export function ProfileList({
             </Typography>
             {!profile.is_builtin && (
               <Typography variant="caption" sx={{ color: 'text.disabled', mt: 0.5, display: 'block' }}>
+                {profile.metadata
+                  ? `v${profile.metadata.version}${profile.metadata.author ? ` by ${profile.metadata.author}` : ''} · `
+                  : ''}
                 Updated {new Date(profile.updated_at).toLocaleDateString()}
               </Typography>
             )}
           </Box>
           <Stack direction="row" spacing={0.5} alignItems="center">
+            <IconButton
+              size="small"
+              title="Export"
+              onClick={(e) => {
+                e.stopPropagation()
+                onExport(profile)
+              }}
+            >
+              <ExportIcon fontSize="inherit" />
+            </IconButton>
             {profile.is_builtin ? (
               <Chip label="Built-in" size="small" variant="outlined" sx={{ height: 18, fontSize: '0.6rem', borderRadius: 0.5 }} />
             ) : (
@@ -1,12 +1,35 @@
 import { useState, useCallback, useRef, useEffect } from 'react'
-import { Box, Button, IconButton, Paper, Stack, TextField, Typography } from '@mui/material'
-import { Autorenew, ChatBubbleOutline, DeleteOutline, Person, Send, SmartToy } from '@mui/icons-material'
+import { Box, Button, Chip, Collapse, IconButton, MenuItem, Paper, Select, Stack, TextField, Typography } from '@mui/material'
+import {
+  AttachFile,
+  Autorenew,
+  Build,
+  ChatBubbleOutline,
+  CheckCircleOutline,
+  DeleteOutline,
+  Description,
+  ErrorOutline,
+  Image as ImageIcon,
+  Person,
+  Send,
+  SmartToy,
+  Stop
+} from '@mui/icons-material'
 import { PageHeader } from '@/components/shared/PageHeader'
 import { LoadingState } from '@/components/shared/LoadingState'
 import { EmptyState } from '@/components/shared/EmptyState'
 import { ErrorBanner } from '@/components/shared/ErrorBanner'
-import { useChatState } from '@/hooks/useAppState'
-import type { ChatMessage } from '@/types/state'
+import { useChatState, useSettingsState } from '@/hooks/useAppState'
+import { PromptLibraryButton } from './PromptLibraryButton'
+import { ProposedEditsPanel } from './ProposedEditsPanel'
+import { ToolApprovalDialog } from './ToolApprovalDialog'
+import type { ChatAttachment, ChatMessage, ChatToolCall, LlmProviderKind } from '@/types/state'
+
+const PROVIDER_LABELS: Record<LlmProviderKind, string> = {
+  claude_cli: 'Claude CLI',
+  openai: 'OpenAI API',
+  ollama: 'Ollama',
+}
 
 /**
  * Chat Page for Claude AI interaction.
@@ -14,7 +37,16 @@ import type { ChatMessage } from '@/types/state'
  */
 export function ChatPage() {
   const { chat, projectName, dispatch, isLoading } = useChatState()
+  const { settings } = useSettingsState()
   const [inputValue, setInputValue] = useState('')
+  // Files picked for the next message
+  const [attachments, setAttachments] = useState<string[]>([])
+  // Per-message provider override (null = the default from Settings)
+  const [providerOverride, setProviderOverride] = useState<LlmProviderKind | null>(null)
+  const provider = providerOverride ?? settings?.provider ?? 'claude_cli'
+  // The CLI resumes its own session; HTTP providers are sent the chat history
+  const continueConversation =
+    provider === 'claude_cli' ? Boolean(chat?.session_id) : (chat?.messages.length ?? 0) > 0
   const scrollRef = useRef<HTMLDivElement>(null)
 
   // Auto-scroll to bottom when new messages arrive
@@ -29,13 +61,20 @@ export function ChatPage() {
 
     const text = inputValue.trim()
     setInputValue('')
+    setAttachments([])
 
-    // Trigger sending to Claude (this will set is_typing and handle response)
+    // Trigger sending (this will set is_typing and handle response).
+    // Continue the conversation so the provider keeps the full context.
     await dispatch({
       type: 'SendChatMessage',
-      payload: { text },
+      payload: { text, continue_conversation: continueConversation, provider, attachments },
     })
-  }, [inputValue, chat?.is_typing, dispatch])
+  }, [inputValue, attachments, chat?.is_typing, continueConversation, provider, dispatch])
+
+  const handleAttach = useCallback(async () => {
+    const paths = await window.dialogApi.openFiles()
+    setAttachments((prev) => [...prev, ...paths.filter((path) => !prev.includes(path))])
+  }, [])
 
   const handleKeyDown = useCallback(
     (e: React.KeyboardEvent<HTMLTextAreaElement>) => {
@@ -51,6 +90,27 @@ export function ChatPage() {
     await dispatch({ type: 'ClearChat' })
   }, [dispatch])
 
+  const handleCancel = useCallback(async () => {
+    // The streaming assistant placeholder is always the last message
+    const last = chat?.messages[chat.messages.length - 1]
+    if (!last?.is_streaming) return
+    await dispatch({ type: 'CancelChatMessage', payload: { message_id: last.id } })
+  }, [chat?.messages, dispatch])
+
+  const handleApprove = useCallback(
+    async (requestId: string) => {
+      await dispatch({ type: 'ApproveToolUse', payload: { request_id: requestId } })
+    },
+    [dispatch]
+  )
+
+  const handleDeny = useCallback(
+    async (requestId: string) => {
+      await dispatch({ type: 'DenyToolUse', payload: { request_id: requestId } })
+    },
+    [dispatch]
+  )
+
   const handleClearError = useCallback(async () => {
     await dispatch({ type: 'ClearChatError' })
   }, [dispatch])
@@ -83,6 +143,25 @@ export function ChatPage() {
         description={`Chat with Claude about ${projectName}`}
         icon={<ChatBubbleOutline fontSize="small" />}
       >
+        <Select
+          size="small"
+          value={provider}
+          onChange={(e) => setProviderOverride(e.target.value as LlmProviderKind)}
+          disabled={isTyping}
+          sx={{ fontSize: '0.8rem', height: 32 }}
+        >
+          {(Object.keys(PROVIDER_LABELS) as LlmProviderKind[]).map((kind) => (
+            <MenuItem key={kind} value={kind}>
+              {PROVIDER_LABELS[kind]}
+            </MenuItem>
+          ))}
+        </Select>
+        <PromptLibraryButton
+          continueConversation={continueConversation}
+          provider={provider}
+          disabled={isTyping}
+          dispatch={dispatch}
+        />
         <Button
           variant="outline"
           size="sm"
@@ -130,9 +209,29 @@ export function ChatPage() {
         </Box>
       )}
 
+      {/* Suggested file edits under review */}
+      {chat.proposed_edits && <ProposedEditsPanel edits={chat.proposed_edits} dispatch={dispatch} />}
+
       {/* Input Area */}
       <Box sx={{ borderTop: 1, borderColor: 'divider', p: 2 }}>
+        {attachments.length > 0 && (
+          <Stack direction="row" spacing={1} useFlexGap flexWrap="wrap" sx={{ mb: 1 }}>
+            {attachments.map((path) => (
+              <Chip
+                key={path}
+                size="small"
+                icon={<AttachFile fontSize="small" />}
+                label={path.split(/[\\/]/).pop()}
+                title={path}
+                onDelete={() => setAttachments((prev) => prev.filter((p) => p !== path))}
+              />
+            ))}
+          </Stack>
+        )}
         <Stack direction="row" spacing={2}>
+          <IconButton onClick={handleAttach} disabled={isTyping} title="Attach files" sx={{ alignSelf: 'stretch' }}>
+            <AttachFile fontSize="small" />
+          </IconButton>
           <TextField
             value={inputValue}
             onChange={(e) => setInputValue(e.target.value)}
@@ -143,23 +242,31 @@ export function ChatPage() {
             fullWidth
             disabled={isTyping}
           />
-          <IconButton
-            color="primary"
-            onClick={handleSend}
-            disabled={!inputValue.trim() || isTyping}
-            sx={{ alignSelf: 'stretch' }}
-          >
-            {isTyping ? (
-              <Autorenew fontSize="small" sx={{ animation: 'spin 1s linear infinite' }} />
-            ) : (
+          {isTyping ? (
+            <IconButton color="error" onClick={handleCancel} title="Stop generating" sx={{ alignSelf: 'stretch' }}>
+              <Stop fontSize="small" />
+            </IconButton>
+          ) : (
+            <IconButton
+              color="primary"
+              onClick={handleSend}
+              disabled={!inputValue.trim()}
+              sx={{ alignSelf: 'stretch' }}
+            >
               <Send fontSize="small" />
-            )}
-          </IconButton>
+            </IconButton>
+          )}
         </Stack>
         <Typography variant="caption" color="text.secondary" align="center" sx={{ mt: 1, display: 'block' }}>
           Press Enter to send, Shift+Enter for new line
         </Typography>
       </Box>
+
+      <ToolApprovalDialog
+        approval={chat.pending_tool_approval ?? null}
+        onApprove={handleApprove}
+        onDeny={handleDeny}
+      />
     </Stack>
   )
 }
@@ -198,9 +305,27 @@ function MessageBubble({ message }: { message: ChatMessage }) {
           color: isUser ? 'primary.contrastText' : 'text.primary',
         }}
       >
-        <Typography variant="body2" sx={{ whiteSpace: 'pre-wrap', wordBreak: 'break-word' }}>
-          {message.content}
-        </Typography>
+        {messageTimeline(message).map((item, index) =>
+          typeof item === 'string' ? (
+            <Typography key={index} variant="body2" sx={{ whiteSpace: 'pre-wrap', wordBreak: 'break-word' }}>
+              {item}
+            </Typography>
+          ) : (
+            <ToolCallRow key={item.id} call={item} />
+          )
+        )}
+        {message.attachments && message.attachments.length > 0 && (
+          <Stack direction="row" spacing={0.5} useFlexGap flexWrap="wrap" sx={{ mt: 1 }}>
+            {message.attachments.map((attachment) => (
+              <AttachmentChip key={attachment.path} attachment={attachment} />
+            ))}
+          </Stack>
+        )}
+        {message.is_cancelled && (
+          <Typography variant="caption" color="text.secondary" sx={{ display: 'block', mt: 0.5 }}>
+            Cancelled
+          </Typography>
+        )}
         {message.is_streaming && (
           <Box component="span" sx={{ display: 'inline-block', width: 8, height: 16, ml: 0.5, bgcolor: 'currentColor', animation: 'pulse 1s ease-in-out infinite' }} />
         )}
@@ -208,3 +333,97 @@ function MessageBubble({ message }: { message: ChatMessage }) {
     </Stack>
   )
 }
+
+function formatSize(bytes: number): string {
+  if (bytes < 1024) return `${bytes} B`
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`
+}
+
+function AttachmentChip({ attachment }: { attachment: ChatAttachment }) {
+  const size = formatSize(attachment.size)
+  return (
+    <Chip
+      size="small"
+      variant="outlined"
+      icon={attachment.kind === 'image' ? <ImageIcon fontSize="small" /> : <Description fontSize="small" />}
+      label={`${attachment.name} · ${size}${attachment.truncated ? ' (truncated)' : ''}`}
+      title={attachment.path}
+      sx={{ color: 'inherit', borderColor: 'currentColor' }}
+    />
+  )
+}
+
+/**
+ * Message content split at the tool calls' offsets: text and calls in the
+ * order they happened.
+ */
+function messageTimeline(message: ChatMessage): (string | ChatToolCall)[] {
+  const calls = message.tool_calls ?? []
+  if (calls.length === 0) return [message.content]
+
+  const items: (string | ChatToolCall)[] = []
+  let position = 0
+  for (const call of calls) {
+    const offset = Math.min(call.content_offset, message.content.length)
+    const text = message.content.slice(position, offset)
+    if (text.trim()) items.push(text)
+    items.push(call)
+    position = Math.max(position, offset)
+  }
+  const rest = message.content.slice(position)
+  if (rest.trim()) items.push(rest)
+  return items
+}
+
+function formatDuration(ms: number): string {
+  return ms < 1000 ? `${ms}ms` : `${(ms / 1000).toFixed(1)}s`
+}
+
+function ToolCallRow({ call }: { call: ChatToolCall }) {
+  const [open, setOpen] = useState(false)
+  const running = call.result_excerpt === null
+  const StatusIcon = running ? Autorenew : call.is_error ? ErrorOutline : CheckCircleOutline
+
+  return (
+    <Box sx={{ my: 0.5, border: 1, borderColor: 'divider', borderRadius: 1, bgcolor: 'action.hover' }}>
+      <Stack
+        direction="row"
+        spacing={1}
+        alignItems="center"
+        onClick={() => !running && setOpen((prev) => !prev)}
+        sx={{ px: 1, py: 0.5, cursor: running ? 'default' : 'pointer' }}
+      >
+        <Build sx={{ fontSize: 14, color: 'text.secondary' }} />
+        <Typography variant="caption" fontWeight={600}>{call.name}</Typography>
+        <Typography
+          variant="caption"
+          color="text.secondary"
+          sx={{ flex: 1, fontFamily: 'monospace', overflow: 'hidden', textOverflow: 'ellipsis', whiteSpace: 'nowrap' }}
+          title={call.input_summary}
+        >
+          {call.input_summary}
+        </Typography>
+        {call.duration_ms !== null && (
+          <Typography variant="caption" color="text.secondary">{formatDuration(call.duration_ms)}</Typography>
+        )}
+        <StatusIcon
+          sx={{
+            fontSize: 14,
+            color: running ? 'text.secondary' : call.is_error ? 'error.main' : 'success.main',
+            animation: running ? 'spin 1s linear infinite' : undefined,
+          }}
+        />
+      </Stack>
+      <Collapse in={open}>
+        <Typography
+          component="pre"
+          variant="caption"
+          sx={{ m: 0, px: 1, pb: 1, fontFamily: 'monospace', whiteSpace: 'pre-wrap', wordBreak: 'break-word' }}
+        >
+          {call.result_excerpt || '(no output)'}
+        </Typography>
+      </Collapse>
+    </Box>
+  )
+}
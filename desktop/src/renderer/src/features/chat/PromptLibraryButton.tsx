@@ -0,0 +1,145 @@
+import { useCallback, useEffect, useState } from 'react'
+import { LibraryBooks } from '@mui/icons-material'
+import {
+  Alert,
+  Button,
+  Dialog,
+  DialogActions,
+  DialogContent,
+  DialogTitle,
+  ListItemText,
+  Menu,
+  MenuItem,
+  Paper,
+  Stack,
+  TextField,
+  Typography
+} from '@mui/material'
+import type { Action, LlmProviderKind } from '@/types/state'
+
+type PromptTemplate = Awaited<ReturnType<typeof window.promptsApi.list>>[number]
+
+interface PromptLibraryButtonProps {
+  /** Continue the conversation when sending */
+  continueConversation: boolean
+  provider: LlmProviderKind
+  disabled?: boolean
+  dispatch: (action: Action) => Promise<void>
+}
+
+/**
+ * Pick a template from the prompt library (~/.rstn/prompts/library/),
+ * fill in its variables and send it as a chat message.
+ */
+export function PromptLibraryButton({ continueConversation, provider, disabled, dispatch }: PromptLibraryButtonProps) {
+  const [anchor, setAnchor] = useState<HTMLElement | null>(null)
+  const [templates, setTemplates] = useState<PromptTemplate[]>([])
+  const [selected, setSelected] = useState<PromptTemplate | null>(null)
+  const [values, setValues] = useState<Record<string, string>>({})
+  const [preview, setPreview] = useState('')
+  const [error, setError] = useState<string | null>(null)
+
+  const handleOpen = useCallback(async (event: React.MouseEvent<HTMLElement>) => {
+    setAnchor(event.currentTarget)
+    setTemplates(await window.promptsApi.list().catch(() => []))
+  }, [])
+
+  const handleSelect = useCallback((template: PromptTemplate) => {
+    setAnchor(null)
+    setValues({})
+    setSelected(template)
+  }, [])
+
+  // Live preview of the rendered prompt
+  useEffect(() => {
+    if (!selected) return
+    window.promptsApi
+      .render(selected.id, values)
+      .then((text) => {
+        setPreview(text)
+        setError(null)
+      })
+      .catch((e: unknown) => {
+        setPreview('')
+        setError(e instanceof Error ? e.message : String(e))
+      })
+  }, [selected, values])
+
+  const handleSend = useCallback(async () => {
+    if (!selected) return
+    await dispatch({
+      type: 'SendTemplatedPrompt',
+      payload: {
+        template_id: selected.id,
+        variables: values,
+        continue_conversation: continueConversation,
+        provider,
+      },
+    })
+    setSelected(null)
+  }, [selected, values, continueConversation, provider, dispatch])
+
+  return (
+    <>
+      <Button
+        variant="outlined"
+        size="small"
+        onClick={handleOpen}
+        disabled={disabled}
+        startIcon={<LibraryBooks fontSize="small" />}
+      >
+        Prompts
+      </Button>
+      <Menu anchorEl={anchor} open={Boolean(anchor)} onClose={() => setAnchor(null)}>
+        {templates.length === 0 ? (
+          <MenuItem disabled>
+            <ListItemText
+              primary="No prompt templates"
+              secondary="Add .md files to ~/.rstn/prompts/library/"
+            />
+          </MenuItem>
+        ) : (
+          templates.map((template) => (
+            <MenuItem key={template.id} onClick={() => handleSelect(template)}>
+              <ListItemText primary={template.name} secondary={template.description} />
+            </MenuItem>
+          ))
+        )}
+      </Menu>
+
+      <Dialog open={Boolean(selected)} onClose={() => setSelected(null)} maxWidth="sm" fullWidth>
+        <DialogTitle>{selected?.name}</DialogTitle>
+        <DialogContent>
+          <Stack spacing={2} sx={{ pt: 1 }}>
+            {selected?.variables.map((variable) => (
+              <TextField
+                key={variable.name}
+                label={variable.name}
+                size="small"
+                placeholder={variable.default}
+                helperText={variable.default ? `Default: ${variable.default}` : 'Required'}
+                value={values[variable.name] ?? ''}
+                onChange={(e) => setValues((prev) => ({ ...prev, [variable.name]: e.target.value }))}
+              />
+            ))}
+            {error ? (
+              <Alert severity="info">{error}</Alert>
+            ) : (
+              <Paper variant="outlined" sx={{ p: 1.5, bgcolor: 'surfaceContainerLow.main' }}>
+                <Typography component="pre" variant="caption" sx={{ fontFamily: 'monospace', whiteSpace: 'pre-wrap', m: 0 }}>
+                  {preview}
+                </Typography>
+              </Paper>
+            )}
+          </Stack>
+        </DialogContent>
+        <DialogActions>
+          <Button onClick={() => setSelected(null)}>Cancel</Button>
+          <Button variant="contained" onClick={handleSend} disabled={Boolean(error)}>
+            Send
+          </Button>
+        </DialogActions>
+      </Dialog>
+    </>
+  )
+}
@@ -0,0 +1,140 @@
+import { useState } from 'react'
+import { Box, Button, Chip, Collapse, IconButton, Paper, Stack, Typography } from '@mui/material'
+import { Check, Close, ExpandLess, ExpandMore, RateReview } from '@mui/icons-material'
+import type { Action, ProposedEdit, ProposedEditSet } from '@/types/state'
+
+interface ProposedEditsPanelProps {
+  edits: ProposedEditSet
+  dispatch: (action: Action) => Promise<void>
+}
+
+const STATUS_COLORS = {
+  pending: 'default',
+  accepted: 'primary',
+  rejected: 'default',
+  applied: 'success',
+} as const
+
+/**
+ * Review panel for file edits suggested in a chat answer.
+ * Each file is accepted or rejected; accepted ones are applied together.
+ */
+export function ProposedEditsPanel({ edits, dispatch }: ProposedEditsPanelProps) {
+  const accepted = edits.edits.filter((edit) => edit.status === 'accepted').length
+  const remaining = edits.edits.filter((edit) => edit.status !== 'applied').length
+
+  return (
+    <Paper variant="outlined" sx={{ mx: 2, mb: 2, p: 1.5 }}>
+      <Stack direction="row" alignItems="center" spacing={1} sx={{ mb: 1 }}>
+        <RateReview fontSize="small" color="primary" />
+        <Typography variant="subtitle2" sx={{ flex: 1 }}>
+          Suggested edits ({edits.edits.length} {edits.edits.length === 1 ? 'file' : 'files'})
+        </Typography>
+        <Button size="small" onClick={() => dispatch({ type: 'DismissProposedEdits' })}>
+          Dismiss
+        </Button>
+        <Button
+          size="small"
+          variant="contained"
+          disabled={accepted === 0}
+          onClick={() => dispatch({ type: 'ApplyProposedEdits' })}
+        >
+          Apply {accepted > 0 ? accepted : ''}
+        </Button>
+      </Stack>
+      <Stack spacing={0.5} sx={{ maxHeight: 320, overflow: 'auto' }}>
+        {edits.edits.map((edit) => (
+          <ProposedEditRow key={edit.path} edit={edit} dispatch={dispatch} />
+        ))}
+      </Stack>
+      {edits.backup_dir && (
+        <Typography variant="caption" color="text.secondary" sx={{ mt: 1, display: 'block' }}>
+          Previous versions saved to {edits.backup_dir}
+          {remaining === 0 && ' — all edits applied'}
+        </Typography>
+      )}
+    </Paper>
+  )
+}
+
+function ProposedEditRow({ edit, dispatch }: { edit: ProposedEdit; dispatch: (action: Action) => Promise<void> }) {
+  const [open, setOpen] = useState(false)
+  const applied = edit.status === 'applied'
+
+  return (
+    <Box sx={{ border: 1, borderColor: 'divider', borderRadius: 1 }}>
+      <Stack direction="row" alignItems="center" spacing={1} sx={{ px: 1, py: 0.5 }}>
+        <IconButton size="small" onClick={() => setOpen((prev) => !prev)}>
+          {open ? <ExpandLess fontSize="small" /> : <ExpandMore fontSize="small" />}
+        </IconButton>
+        <Typography
+          variant="body2"
+          sx={{
+            flex: 1,
+            fontFamily: 'monospace',
+            textDecoration: edit.status === 'rejected' ? 'line-through' : undefined,
+          }}
+        >
+          {edit.path}
+        </Typography>
+        <Typography variant="caption" color="success.main">+{edit.additions}</Typography>
+        <Typography variant="caption" color="error.main">-{edit.deletions}</Typography>
+        {edit.content !== null && <Chip size="small" label="whole file" variant="outlined" />}
+        {edit.change !== 'modified' && <Chip size="small" label={edit.change} variant="outlined" />}
+        <Chip size="small" label={edit.status} color={STATUS_COLORS[edit.status]} />
+        <IconButton
+          size="small"
+          title="Accept"
+          color={edit.status === 'accepted' ? 'primary' : 'default'}
+          disabled={applied}
+          onClick={() => dispatch({ type: 'AcceptProposedEdit', payload: { path: edit.path } })}
+        >
+          <Check fontSize="small" />
+        </IconButton>
+        <IconButton
+          size="small"
+          title="Reject"
+          color={edit.status === 'rejected' ? 'error' : 'default'}
+          disabled={applied}
+          onClick={() => dispatch({ type: 'RejectProposedEdit', payload: { path: edit.path } })}
+        >
+          <Close fontSize="small" />
+        </IconButton>
+      </Stack>
+      <Collapse in={open}>
+        <Box
+          component="pre"
+          sx={{ m: 0, px: 1.5, pb: 1, fontFamily: 'monospace', fontSize: '0.75rem', overflow: 'auto' }}
+        >
+          {edit.content !== null
+            ? edit.content
+            : edit.hunks.map((hunk, index) => (
+                <Box key={index} component="span" sx={{ display: 'block' }}>
+                  <Box component="span" sx={{ display: 'block', color: 'text.secondary' }}>
+                    {hunk.header}
+                  </Box>
+                  {hunk.lines.map((line, lineIndex) => (
+                    <Box
+                      key={lineIndex}
+                      component="span"
+                      sx={{
+                        display: 'block',
+                        color:
+                          line.line_type === 'added'
+                            ? 'success.main'
+                            : line.line_type === 'removed'
+                              ? 'error.main'
+                              : 'text.primary',
+                      }}
+                    >
+                      {line.line_type === 'added' ? '+' : line.line_type === 'removed' ? '-' : ' '}
+                      {line.content}
+                    </Box>
+                  ))}
+                </Box>
+              ))}
+        </Box>
+      </Collapse>
+    </Box>
+  )
+}
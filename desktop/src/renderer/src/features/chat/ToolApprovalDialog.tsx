@@ -0,0 +1,62 @@
+import { Build } from '@mui/icons-material'
+import {
+  Box,
+  Button,
+  Dialog,
+  DialogActions,
+  DialogContent,
+  DialogContentText,
+  DialogTitle,
+  Stack,
+  Typography,
+} from '@mui/material'
+import type { PendingToolApproval } from '@/types/state'
+
+interface ToolApprovalDialogProps {
+  approval: PendingToolApproval | null
+  onApprove: (requestId: string) => void
+  onDeny: (requestId: string) => void
+}
+
+/**
+ * Asks the user whether the agent may run a tool call.
+ * Claude is paused until one of the buttons is clicked.
+ */
+export function ToolApprovalDialog({ approval, onApprove, onDeny }: ToolApprovalDialogProps) {
+  if (!approval) return null
+
+  return (
+    <Dialog open onClose={() => onDeny(approval.request_id)} maxWidth="sm" fullWidth>
+      <DialogTitle>
+        <Stack direction="row" alignItems="center" spacing={1}>
+          <Build fontSize="small" color="warning" />
+          <span>Allow {approval.tool_name}?</span>
+        </Stack>
+      </DialogTitle>
+      <DialogContent>
+        <DialogContentText sx={{ mb: 2 }}>
+          Claude wants to use the <strong>{approval.tool_name}</strong> tool and is waiting for your approval.
+        </DialogContentText>
+        {approval.input_summary && (
+          <Box sx={{ p: 1.5, borderRadius: 1, bgcolor: 'action.hover' }}>
+            <Typography
+              variant="body2"
+              component="pre"
+              sx={{ m: 0, fontFamily: 'monospace', whiteSpace: 'pre-wrap', wordBreak: 'break-word' }}
+            >
+              {approval.input_summary}
+            </Typography>
+          </Box>
+        )}
+      </DialogContent>
+      <DialogActions>
+        <Button onClick={() => onDeny(approval.request_id)} color="error">
+          Deny
+        </Button>
+        <Button onClick={() => onApprove(approval.request_id)} variant="contained">
+          Allow
+        </Button>
+      </DialogActions>
+    </Dialog>
+  )
+}
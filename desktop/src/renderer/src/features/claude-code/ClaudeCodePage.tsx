@@ -1,11 +1,25 @@
+import { useState } from 'react'
+import { Box, Tab, Tabs } from '@mui/material'
 import { PresetsPanel } from '@/features/workflows/PresetsPanel'
+import { SessionHistoryPanel } from './SessionHistoryPanel'
 
 /**
- * Claude Code Tab - Agent Presets Management
+ * Claude Code Tab - Agent Presets Management and Session History
  *
  * Manages worktree-scoped agent presets for custom Claude Code behavior.
  * Each worktree stores agents in .claude/agents/*.md files.
+ * Session History lists every Claude CLI run of the project.
  */
 export function ClaudeCodePage() {
-  return <PresetsPanel />
+  const [tab, setTab] = useState<'presets' | 'history'>('presets')
+
+  return (
+    <Box sx={{ height: '100%', display: 'flex', flexDirection: 'column' }}>
+      <Tabs value={tab} onChange={(_, value) => setTab(value)} sx={{ px: 3, borderBottom: 1, borderColor: 'divider' }}>
+        <Tab value="presets" label="Presets" />
+        <Tab value="history" label="Session History" />
+      </Tabs>
+      <Box sx={{ flex: 1, minHeight: 0 }}>{tab === 'presets' ? <PresetsPanel /> : <SessionHistoryPanel />}</Box>
+    </Box>
+  )
 }
@@ -0,0 +1,160 @@
+import { useEffect, useState } from 'react'
+import { Delete as DeleteIcon, History as HistoryIcon, Refresh as RefreshIcon } from '@mui/icons-material'
+import { Box, Button, Chip, Collapse, IconButton, Paper, Stack, Tooltip, Typography } from '@mui/material'
+import { PageHeader } from '@/components/shared/PageHeader'
+import { LoadingState } from '@/components/shared/LoadingState'
+import { EmptyState } from '@/components/shared/EmptyState'
+import { useActiveProject, useAppState } from '@/hooks/useAppState'
+import type { SessionOutcome, SessionRecord } from '@/types/state'
+
+const OUTCOME_COLORS: Record<SessionOutcome, 'success' | 'error' | 'warning'> = {
+  success: 'success',
+  error: 'error',
+  interrupted: 'warning',
+}
+
+function formatDuration(ms: number): string {
+  const seconds = Math.round(ms / 1000)
+  if (seconds < 60) return `${seconds}s`
+  return `${Math.floor(seconds / 60)}m ${seconds % 60}s`
+}
+
+function Detail({ label, value }: { label: string; value: string | null }) {
+  if (!value) return null
+  return (
+    <Typography variant="caption" sx={{ display: 'block', wordBreak: 'break-all' }}>
+      <Box component="span" sx={{ color: 'text.secondary' }}>
+        {label}:{' '}
+      </Box>
+      <Box component="span" sx={{ fontFamily: 'monospace' }}>
+        {value}
+      </Box>
+    </Typography>
+  )
+}
+
+function SessionRow({ session, onDelete }: { session: SessionRecord; onDelete: () => void }) {
+  const [expanded, setExpanded] = useState(false)
+  const [exportMessage, setExportMessage] = useState<{ text: string; isError: boolean } | null>(null)
+
+  const exportTranscript = async (format: 'markdown' | 'json') => {
+    try {
+      const path = await window.sessionsApi.exportTranscript(session.id, format)
+      if (path) setExportMessage({ text: `Exported to ${path}`, isError: false })
+    } catch (e) {
+      setExportMessage({ text: e instanceof Error ? e.message : String(e), isError: true })
+    }
+  }
+
+  return (
+    <Paper variant="outlined" sx={{ p: 1.5, borderColor: 'outlineVariant' }}>
+      <Stack direction="row" alignItems="center" spacing={1}>
+        <Chip label={session.kind} size="small" variant="outlined" sx={{ height: 20, fontSize: '0.65rem' }} />
+        <Chip
+          label={session.outcome}
+          size="small"
+          color={OUTCOME_COLORS[session.outcome]}
+          sx={{ height: 20, fontSize: '0.65rem' }}
+        />
+        <Box sx={{ flex: 1, minWidth: 0, cursor: 'pointer' }} onClick={() => setExpanded(!expanded)}>
+          <Typography variant="body2" noWrap>
+            {session.prompt_excerpt || '(no prompt)'}
+          </Typography>
+          <Typography variant="caption" color="text.secondary">
+            {new Date(session.started_at).toLocaleString()} · {formatDuration(session.duration_ms)} ·{' '}
+            {session.input_tokens + session.output_tokens} tokens · ${session.cost_usd.toFixed(4)}
+          </Typography>
+        </Box>
+        <Tooltip title="Delete from history">
+          <IconButton size="small" onClick={onDelete}>
+            <DeleteIcon fontSize="small" />
+          </IconButton>
+        </Tooltip>
+      </Stack>
+      <Collapse in={expanded}>
+        <Box sx={{ mt: 1 }}>
+          {session.error && (
+            <Typography variant="caption" color="error" sx={{ display: 'block', wordBreak: 'break-word', mb: 0.5 }}>
+              {session.error}
+            </Typography>
+          )}
+          <Detail label="Worktree" value={session.worktree_path} />
+          <Detail label="Model" value={session.model ?? 'CLI default'} />
+          <Detail label="Claude session" value={session.claude_session_id} />
+          <Detail label="Log file" value={session.log_file} />
+          <Detail label="Tokens" value={`${session.input_tokens} in / ${session.output_tokens} out`} />
+          <Stack direction="row" spacing={1} alignItems="center" sx={{ mt: 1 }}>
+            <Button size="small" variant="outlined" onClick={() => exportTranscript('markdown')}>
+              Export Markdown
+            </Button>
+            <Button size="small" variant="outlined" onClick={() => exportTranscript('json')}>
+              Export JSON
+            </Button>
+            {exportMessage && (
+              <Typography variant="caption" color={exportMessage.isError ? 'error' : 'text.secondary'} sx={{ wordBreak: 'break-all' }}>
+                {exportMessage.text}
+              </Typography>
+            )}
+          </Stack>
+        </Box>
+      </Collapse>
+    </Paper>
+  )
+}
+
+/**
+ * SessionHistoryPanel - Every Claude CLI invocation of the active project
+ * (chat, constitution, proposal, plan, implementation), newest first
+ */
+export function SessionHistoryPanel() {
+  const { state } = useAppState()
+  const { project, dispatch } = useActiveProject()
+  const projectPath = project?.path
+
+  useEffect(() => {
+    if (projectPath) {
+      dispatch({ type: 'LoadSessions' })
+    }
+  }, [projectPath, dispatch])
+
+  if (!project) {
+    return <EmptyState title="No Project Open" description="Open a project to see its Claude sessions" />
+  }
+
+  const history = state?.session_history
+  if (!history || (history.is_loading && history.sessions.length === 0)) {
+    return <LoadingState />
+  }
+
+  return (
+    <Box sx={{ height: '100%', overflow: 'auto', p: 3 }}>
+      <PageHeader
+        title="Session History"
+        description="Every Claude run in this project with its duration, usage and outcome"
+        icon={<HistoryIcon />}
+      >
+        <Tooltip title="Reload">
+          <IconButton size="small" onClick={() => dispatch({ type: 'LoadSessions' })}>
+            <RefreshIcon fontSize="small" />
+          </IconButton>
+        </Tooltip>
+      </PageHeader>
+
+      {history.sessions.length === 0 ? (
+        <Typography variant="body2" color="text.secondary">
+          No Claude sessions recorded yet
+        </Typography>
+      ) : (
+        <Stack spacing={1}>
+          {history.sessions.map((session) => (
+            <SessionRow
+              key={session.id}
+              session={session}
+              onDelete={() => dispatch({ type: 'DeleteSession', payload: { id: session.id } })}
+            />
+          ))}
+        </Stack>
+      )}
+    </Box>
+  )
+}
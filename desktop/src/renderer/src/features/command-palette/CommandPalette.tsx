@@ -1,4 +1,4 @@
-import { useCallback, useEffect, useState } from 'react'
+import { useCallback, useEffect, useMemo, useState } from 'react'
 import { Command } from 'cmdk'
 import { styled, alpha } from '@mui/material/styles'
 import {
@@ -16,8 +16,9 @@ import {
   ChatBubbleOutline as MessageSquareIcon,
   Terminal as TerminalSquareIcon,
 } from '@mui/icons-material'
-import { useAppState, useActiveProject, useActiveWorktree } from '@/hooks/useAppState'
-import type { Theme } from '@/types/state'
+import type { SvgIconComponent } from '@mui/icons-material'
+import { useAppState } from '@/hooks/useAppState'
+import { formatShortcut } from '@/lib/keybindings'
 
 // --- Styled Components ---
 
@@ -139,17 +140,53 @@ const Badge = styled('span')(({ theme }) => ({
   color: theme.palette.text.secondary,
 }))
 
-const Description = styled('span')(({ theme }) => ({
+const Shortcut = styled('kbd')(({ theme }) => ({
   marginLeft: 'auto',
-  fontSize: '0.75rem',
+  fontSize: '0.7rem',
+  fontFamily: theme.typography.fontFamily,
   color: theme.palette.text.secondary,
-  overflow: 'hidden',
-  textOverflow: 'ellipsis',
-  whiteSpace: 'nowrap',
-  maxWidth: 200,
 }))
 
 
+/** Palette entry from the backend ActionRegistry (`palette_list_actions`) */
+interface PaletteAction {
+  id: string
+  label: string
+  category: string
+  keybinding?: string
+  enabled: boolean
+  disabledReason?: string
+  /** Action JSON to dispatch */
+  action: string
+}
+
+/** Icons for specific entries (by ID), falling back to the category icon */
+const ENTRY_ICONS: Record<string, SvgIconComponent> = {
+  'view.tasks': ListTodoIcon,
+  'view.mcp': ServerIcon,
+  'view.chat': MessageSquareIcon,
+  'view.terminal': TerminalSquareIcon,
+  'view.dockers': ContainerIcon,
+  'view.env': FileCodeIcon,
+  'view.explorer': FileCodeIcon,
+  'view.settings': SettingsIcon,
+  'theme.system': MonitorIcon,
+  'theme.light': SunIcon,
+  'theme.dark': MoonIcon,
+}
+
+const CATEGORY_ICONS: Record<string, SvgIconComponent> = {
+  Projects: FolderOpenIcon,
+  Worktrees: GitBranchIcon,
+  Git: GitBranchIcon,
+  'Run Task': PlayIcon,
+  Docker: ContainerIcon,
+}
+
+function iconFor(action: PaletteAction): SvgIconComponent {
+  return ENTRY_ICONS[action.id] ?? CATEGORY_ICONS[action.category] ?? SettingsIcon
+}
+
 interface CommandPaletteProps {
   open: boolean
   onOpenChange: (open: boolean) => void
@@ -157,16 +194,13 @@ interface CommandPaletteProps {
 
 /**
  * Global Command Palette (Cmd+K / Ctrl+K)
- * Provides quick navigation and action execution.
+ * Entries come from the backend action registry, so they stay in sync with
+ * the Rust actions and their enablement reflects the current state.
  */
 export function CommandPalette({ open, onOpenChange }: CommandPaletteProps) {
   const { state, dispatch } = useAppState()
-  const { projects, activeIndex } = useActiveProject()
-  const { worktree, worktrees } = useActiveWorktree()
   const [search, setSearch] = useState('')
-
-  // Get tasks from active worktree
-  const tasks = worktree?.tasks?.commands ?? []
+  const [actions, setActions] = useState<PaletteAction[]>([])
 
   // Reset search when closing
   useEffect(() => {
@@ -175,61 +209,37 @@ export function CommandPalette({ open, onOpenChange }: CommandPaletteProps) {
     }
   }, [open])
 
-  // Handle project switch
-  const handleSwitchProject = useCallback(
-    async (index: number) => {
-      await dispatch({ type: 'SwitchProject', payload: { index } })
-      onOpenChange(false)
-    },
-    [dispatch, onOpenChange]
-  )
-
-  // Handle worktree switch
-  const handleSwitchWorktree = useCallback(
-    async (index: number) => {
-      await dispatch({ type: 'SwitchWorktree', payload: { index } })
-      onOpenChange(false)
-    },
-    [dispatch, onOpenChange]
-  )
-
-  // Handle view change
-  const handleSetView = useCallback(
-    async (view: 'tasks' | 'dockers' | 'settings' | 'env' | 'mcp' | 'chat' | 'terminal') => {
-      await dispatch({ type: 'SetActiveView', payload: { view } })
-      onOpenChange(false)
-    },
-    [dispatch, onOpenChange]
-  )
+  // Re-list whenever state changes while open (enablement depends on it)
+  useEffect(() => {
+    if (!open) return
+    let cancelled = false
+    window.stateApi.listPaletteActions().then((list) => {
+      if (!cancelled) setActions(list)
+    })
+    return () => {
+      cancelled = true
+    }
+  }, [open, state])
 
-  // Handle run task
-  const handleRunTask = useCallback(
-    async (taskName: string) => {
-      if (!worktree) return
-      await dispatch({
-        type: 'RunJustCommand',
-        payload: { name: taskName, cwd: worktree.path },
-      })
-      await dispatch({ type: 'SetActiveView', payload: { view: 'tasks' } })
-      onOpenChange(false)
-    },
-    [worktree, dispatch, onOpenChange]
-  )
+  // Group entries by category, keeping registry order
+  const groups = useMemo(() => {
+    const map = new Map<string, PaletteAction[]>()
+    for (const action of actions) {
+      const group = map.get(action.category) ?? []
+      group.push(action)
+      map.set(action.category, group)
+    }
+    return Array.from(map.entries())
+  }, [actions])
 
-  // Handle theme change
-  const handleSetTheme = useCallback(
-    async (theme: Theme) => {
-      await dispatch({ type: 'SetTheme', payload: { theme } })
+  const handleSelect = useCallback(
+    async (action: PaletteAction) => {
+      await dispatch(JSON.parse(action.action))
       onOpenChange(false)
     },
     [dispatch, onOpenChange]
   )
 
-  // Memoize filtered items for performance
-  const hasProjects = projects.length > 0
-  const hasWorktrees = worktrees.length > 0
-  const hasTasks = tasks.length > 0
-
   return (
     <StyledDialog
       open={open}
@@ -246,138 +256,29 @@ export function CommandPalette({ open, onOpenChange }: CommandPaletteProps) {
         <StyledList>
           <StyledEmpty>No results found.</StyledEmpty>
 
-          {/* Projects */}
-          {hasProjects && (
-            <StyledGroup heading="Projects">
-              {projects.map((project, index) => (
-                <StyledItem
-                  key={project.id}
-                  value={`project ${project.name}`}
-                  onSelect={() => handleSwitchProject(index)}
-                >
-                  <FolderOpenIcon className="command-icon" />
-                  <span>{project.name}</span>
-                  {index === activeIndex && (
-                    <Badge>Active</Badge>
-                  )}
-                </StyledItem>
-              ))}
-            </StyledGroup>
-          )}
-
-          {/* Worktrees */}
-          {hasWorktrees && (
-            <StyledGroup heading="Worktrees">
-              {worktrees.map((wt, index) => (
-                <StyledItem
-                  key={wt.id}
-                  value={`worktree ${wt.branch}`}
-                  onSelect={() => handleSwitchWorktree(index)}
-                >
-                  <GitBranchIcon className="command-icon" />
-                  <span>{wt.branch}</span>
-                  {wt.is_main && <Badge>main</Badge>}
-                </StyledItem>
-              ))}
+          {groups.map(([category, entries]) => (
+            <StyledGroup key={category} heading={category}>
+              {entries.map((action) => {
+                const Icon = iconFor(action)
+                return (
+                  <StyledItem
+                    key={action.id}
+                    value={`${category} ${action.label} ${action.id}`}
+                    disabled={!action.enabled}
+                    onSelect={() => handleSelect(action)}
+                  >
+                    <Icon className="command-icon" />
+                    <span>{action.label}</span>
+                    {action.disabledReason === 'Already active' ? (
+                      <Badge>Active</Badge>
+                    ) : action.keybinding ? (
+                      <Shortcut>{formatShortcut(action.keybinding)}</Shortcut>
+                    ) : null}
+                  </StyledItem>
+                )
+              })}
             </StyledGroup>
-          )}
-
-          {/* Tasks */}
-          {hasTasks && (
-            <StyledGroup heading="Run Task">
-              {tasks.slice(0, 10).map((task) => (
-                <StyledItem
-                  key={task.name}
-                  value={`run task ${task.name} ${task.description ?? ''}`}
-                  onSelect={() => handleRunTask(task.name)}
-                >
-                  <PlayIcon className="command-icon" />
-                  <span>just {task.name}</span>
-                  {task.description && (
-                    <Description>{task.description}</Description>
-                  )}
-                </StyledItem>
-              ))}
-            </StyledGroup>
-          )}
-
-          {/* Views */}
-          <StyledGroup heading="Views">
-            <StyledItem
-              value="view tasks"
-              onSelect={() => handleSetView('tasks')}
-            >
-              <ListTodoIcon className="command-icon" />
-              <span>Tasks</span>
-            </StyledItem>
-            <StyledItem
-              value="view rstn-mcp integration server"
-              onSelect={() => handleSetView('mcp')}
-            >
-              <ServerIcon className="command-icon" />
-              <span>rstn-mcp Integration</span>
-            </StyledItem>
-            <StyledItem
-              value="view chat claude ai"
-              onSelect={() => handleSetView('chat')}
-            >
-              <MessageSquareIcon className="command-icon" />
-              <span>Chat</span>
-            </StyledItem>
-            <StyledItem
-              value="view terminal shell pty"
-              onSelect={() => handleSetView('terminal')}
-            >
-              <TerminalSquareIcon className="command-icon" />
-              <span>Terminal</span>
-            </StyledItem>
-            <StyledItem
-              value="view docker containers"
-              onSelect={() => handleSetView('dockers')}
-            >
-              <ContainerIcon className="command-icon" />
-              <span>Docker</span>
-            </StyledItem>
-            <StyledItem
-              value="view environment env"
-              onSelect={() => handleSetView('env')}
-            >
-              <FileCodeIcon className="command-icon" />
-              <span>Environment</span>
-            </StyledItem>
-            <StyledItem
-              value="view settings preferences"
-              onSelect={() => handleSetView('settings')}
-            >
-              <SettingsIcon className="command-icon" />
-              <span>Settings</span>
-            </StyledItem>
-          </StyledGroup>
-
-          {/* Theme */}
-          <StyledGroup heading="Theme">
-            <StyledItem
-              value="theme system auto"
-              onSelect={() => handleSetTheme('system')}
-            >
-              <MonitorIcon className="command-icon" />
-              <span>System Theme</span>
-            </StyledItem>
-            <StyledItem
-              value="theme light"
-              onSelect={() => handleSetTheme('light')}
-            >
-              <SunIcon className="command-icon" />
-              <span>Light Theme</span>
-            </StyledItem>
-            <StyledItem
-              value="theme dark"
-              onSelect={() => handleSetTheme('dark')}
-            >
-              <MoonIcon className="command-icon" />
-              <span>Dark Theme</span>
-            </StyledItem>
-          </StyledGroup>
+          ))}
         </StyledList>
       </div>
     </StyledDialog>
@@ -0,0 +1,165 @@
+import { useEffect, useRef, useState } from 'react'
+import { Close as XIcon, Terminal as TerminalIcon } from '@mui/icons-material'
+import { Box, Button, Divider, Drawer, IconButton, Stack, TextField, Typography, alpha } from '@mui/material'
+import { useDockersState } from '@/hooks/useAppState'
+import type { DockerServiceInfo } from '@/types/state'
+
+// Database client of built-in services, run with their default credentials
+const CLIENT_COMMANDS: Record<string, string[]> = {
+  'rstn-postgres': ['psql', '-U', 'postgres'],
+  'rstn-mysql': ['mysql', '-uroot', '-pmysql'],
+  'rstn-redis': ['redis-cli'],
+  'rstn-mongodb': ['mongosh'],
+}
+
+// Output keeps at most this many characters
+const MAX_OUTPUT = 200_000
+
+// Escape sequences (colors, cursor movement) and the CR of CRLF line endings
+// eslint-disable-next-line no-control-regex
+const ANSI_PATTERN = /\x1b\[[0-9;?]*[ -/]*[@-~]|\x1b\][^\x07]*\x07|\x1b[()][0-9A-Za-z]|\r(?=\n)/g
+
+interface DockerExecConsoleProps {
+  service: DockerServiceInfo | null
+  onClose: () => void
+}
+
+/**
+ * DockerExecConsole - Interactive shell (or database client) inside an
+ * rstn-managed container, streamed through a terminal session
+ */
+export function DockerExecConsole({ service, onClose }: DockerExecConsoleProps) {
+  const { dockers, dispatch } = useDockersState()
+  const [output, setOutput] = useState('')
+  const [line, setLine] = useState('')
+  const outputRef = useRef<HTMLDivElement>(null)
+
+  const serviceId = service?.id ?? null
+  const sessionId = serviceId ? (dockers?.exec_sessions?.[serviceId] ?? null) : null
+  const clientCommand = serviceId ? CLIENT_COMMANDS[serviceId] : undefined
+
+  useEffect(() => {
+    if (!sessionId) return
+    setOutput('')
+    return window.terminalApi.onOutput((id, data) => {
+      if (id !== sessionId) return
+      setOutput((prev) => (prev + data.replace(ANSI_PATTERN, '')).slice(-MAX_OUTPUT))
+    })
+  }, [sessionId])
+
+  useEffect(() => {
+    outputRef.current?.scrollTo({ top: outputRef.current.scrollHeight })
+  }, [output])
+
+  const open = (cmd: string[] | null) => {
+    if (!serviceId) return
+    dispatch({ type: 'OpenDockerExec', payload: { service_id: serviceId, cmd, cols: 120, rows: 32 } })
+  }
+
+  const handleClose = () => {
+    if (serviceId && sessionId) {
+      dispatch({ type: 'CloseDockerExec', payload: { service_id: serviceId } })
+    }
+    onClose()
+  }
+
+  const send = (data: string) => {
+    if (!sessionId) return
+    dispatch({ type: 'WriteTerminal', payload: { session_id: sessionId, data } })
+  }
+
+  const handleKeyDown = (e: React.KeyboardEvent) => {
+    if (e.key === 'Enter') {
+      e.preventDefault()
+      send(line + '\r')
+      setLine('')
+    } else if (e.key === 'c' && e.ctrlKey) {
+      e.preventDefault()
+      send('\x03')
+      setLine('')
+    } else if (e.key === 'd' && e.ctrlKey && !line) {
+      e.preventDefault()
+      send('\x04')
+    }
+  }
+
+  return (
+    <Drawer
+      anchor="right"
+      open={!!service}
+      onClose={handleClose}
+      PaperProps={{
+        sx: { width: 720, bgcolor: 'background.default' }
+      }}
+    >
+      <Box sx={{ p: 3, height: '100%', display: 'flex', flexDirection: 'column' }}>
+        <Stack direction="row" alignItems="center" justifyContent="space-between" sx={{ mb: 2 }}>
+          <Box>
+            <Typography variant="h6" fontWeight={700}>{service?.name} Console</Typography>
+            <Typography variant="caption" color="text.secondary">
+              Runs inside the container (Ctrl+C interrupts, Ctrl+D exits)
+            </Typography>
+          </Box>
+          <IconButton onClick={handleClose}><XIcon /></IconButton>
+        </Stack>
+
+        <Stack direction="row" spacing={1} sx={{ mb: 2 }}>
+          <Button
+            variant="outlined"
+            size="small"
+            startIcon={<TerminalIcon />}
+            onClick={() => open(null)}
+            sx={{ borderRadius: 2 }}
+          >
+            Shell
+          </Button>
+          {clientCommand && (
+            <Button variant="outlined" size="small" onClick={() => open(clientCommand)} sx={{ borderRadius: 2 }}>
+              {clientCommand[0]}
+            </Button>
+          )}
+        </Stack>
+
+        <Divider />
+
+        <Box
+          ref={outputRef}
+          sx={{
+            flex: 1,
+            overflow: 'auto',
+            my: 2,
+            p: 2,
+            bgcolor: alpha('#000', 0.3),
+            borderRadius: 2,
+            border: 1,
+            borderColor: 'outlineVariant',
+            fontFamily: 'monospace',
+            fontSize: '0.75rem',
+            whiteSpace: 'pre-wrap',
+            wordBreak: 'break-all',
+          }}
+        >
+          {sessionId ? (
+            output
+          ) : (
+            <Typography variant="caption" color="text.disabled">
+              Open a shell{clientCommand ? ` or ${clientCommand[0]}` : ''} to run commands in the container
+            </Typography>
+          )}
+        </Box>
+
+        <TextField
+          size="small"
+          fullWidth
+          autoFocus
+          disabled={!sessionId}
+          placeholder={sessionId ? 'Type a command and press Enter' : ''}
+          value={line}
+          onChange={(e) => setLine(e.target.value)}
+          onKeyDown={handleKeyDown}
+          InputProps={{ sx: { fontFamily: 'monospace', fontSize: '0.8rem' } }}
+        />
+      </Box>
+    </Drawer>
+  )
+}
@@ -5,7 +5,8 @@ import {
   Refresh as RotateCwIcon,
   Description as FileTextIcon,
   ContentCopy as CopyIcon,
-  Check as CheckIcon
+  Check as CheckIcon,
+  Terminal as TerminalIcon
 } from '@mui/icons-material'
 import {
   Button,
@@ -55,6 +56,7 @@ interface DockerServiceCardProps {
   onToggle?: (id: string) => void
   onRestart?: (id: string) => void
   onViewLogs?: (id: string) => void
+  onOpenConsole?: (id: string) => void
 }
 
 export function DockerServiceCard({
@@ -64,6 +66,7 @@ export function DockerServiceCard({
   onToggle,
   onRestart,
   onViewLogs,
+  onOpenConsole,
 }: DockerServiceCardProps) {
   const [copied, setCopied] = useState(false)
   const isRunning = service.status === 'running'
@@ -185,6 +188,21 @@ export function DockerServiceCard({
           Logs
         </Button>
 
+        {isRstnManaged && onOpenConsole && (
+          <Button
+            variant="text"
+            size="small"
+            disabled={!isRunning}
+            onClick={(e) => {
+              e.stopPropagation()
+              onOpenConsole(service.id)
+            }}
+            startIcon={<TerminalIcon />}
+          >
+            Console
+          </Button>
+        )}
+
         {/* Conditional Add DB button for databases */}
         {service.service_type === 'Database' && (
           <AddDbDialog
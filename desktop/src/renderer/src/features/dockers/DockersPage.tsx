@@ -23,8 +23,12 @@ import { PageHeader } from '@/components/shared/PageHeader'
 import { LoadingState } from '@/components/shared/LoadingState'
 import { EmptyState } from '@/components/shared/EmptyState'
 import { DockerServiceCard } from './DockerServiceCard'
+import { DockerExecConsole } from './DockerExecConsole'
 import { PortConflictDialog } from './PortConflictDialog'
-import { useDockersState } from '@/hooks/useAppState'
+import { ServiceGroupsPanel } from './ServiceGroupsPanel'
+import { ProxyPanel } from './ProxyPanel'
+import { HttpRequestsPanel } from './HttpRequestsPanel'
+import { useActiveProject, useDockersState } from '@/hooks/useAppState'
 import type { DockerServiceInfo } from '@/types/state'
 import { statusLabels } from '@/types/state'
 
@@ -37,10 +41,18 @@ interface ServiceGroup {
 
 export function DockersPage() {
   const { dockers, dispatch, isLoading: isStateLoading } = useDockersState()
+  const { project } = useActiveProject()
   const [collapsedGroups, setCollapsedGroups] = useState<Set<string>>(new Set())
+  const [projectOnly, setProjectOnly] = useState(false)
+  const [consoleServiceId, setConsoleServiceId] = useState<string | null>(null)
 
   // Derive values from state
-  const services = dockers?.services ?? []
+  const projectServices = project?.docker_services ?? []
+  const allServices = dockers?.services ?? []
+  const services = useMemo(
+    () => (projectOnly ? allServices.filter((s) => projectServices.includes(s.id)) : allServices),
+    [allServices, projectOnly, projectServices]
+  )
   const selectedServiceId = dockers?.selected_service_id ?? null
   const logs = dockers?.logs ?? []
   const isRefreshing = dockers?.is_loading ?? false
@@ -49,6 +61,7 @@ export function DockersPage() {
   const pendingConflict = dockers?.pending_conflict ?? null
 
   const selectedService = services.find((s) => s.id === selectedServiceId)
+  const consoleService = allServices.find((s) => s.id === consoleServiceId) ?? null
 
   // Group services by project_group
   const serviceGroups = useMemo((): ServiceGroup[] => {
@@ -147,6 +160,13 @@ export function DockersPage() {
     })
   }, [dispatch])
 
+  const handleResolveByKillingProcess = useCallback(async (port: number, pid: number, serviceId: string) => {
+    await dispatch({
+      type: 'KillProcessOnPort',
+      payload: { port, pid, service_id: serviceId }
+    })
+  }, [dispatch])
+
   const handleCancelConflict = useCallback(async () => {
     await dispatch({ type: 'ClearPortConflict' })
   }, [dispatch])
@@ -178,6 +198,7 @@ export function DockersPage() {
         pendingConflict={pendingConflict}
         onResolveWithPort={handleResolveWithPort}
         onResolveByStoppingContainer={handleResolveByStoppingContainer}
+        onResolveByKillingProcess={handleResolveByKillingProcess}
         onCancel={handleCancelConflict}
       />
 
@@ -210,12 +231,30 @@ export function DockersPage() {
             borderRadius: 4
           }}
         >
-          <Box sx={{ px: 2, py: 1.5, borderBottom: 1, borderColor: 'outlineVariant' }}>
+          <Stack
+            direction="row"
+            alignItems="center"
+            justifyContent="space-between"
+            sx={{ px: 2, py: 1.5, borderBottom: 1, borderColor: 'outlineVariant' }}
+          >
             <Typography variant="subtitle2" fontWeight={600}>Services</Typography>
-          </Box>
+            {project && (
+              <Chip
+                label={`Used by ${project.name}`}
+                size="small"
+                color={projectOnly ? 'primary' : 'default'}
+                variant={projectOnly ? 'filled' : 'outlined'}
+                onClick={() => setProjectOnly((only) => !only)}
+              />
+            )}
+          </Stack>
 
           <Box sx={{ flex: 1, overflowY: 'auto', p: 2 }}>
             <Stack spacing={2}>
+              <ServiceGroupsPanel services={allServices} />
+              <ProxyPanel services={allServices} />
+              <HttpRequestsPanel />
+
               {serviceGroups.map((group) => {
                 const isCollapsed = collapsedGroups.has(group.name)
                 return (
@@ -273,6 +312,7 @@ export function DockersPage() {
                             onToggle={handleToggle}
                             onRestart={handleRestart}
                             onViewLogs={handleViewLogs}
+                            onOpenConsole={setConsoleServiceId}
                           />
                         ))}
                       </Stack>
@@ -308,6 +348,7 @@ export function DockersPage() {
           />
         </Box>
       </Stack>
+      <DockerExecConsole service={consoleService} onClose={() => setConsoleServiceId(null)} />
       <style>{`
         @keyframes spin {
           from { transform: rotate(0deg); }
@@ -0,0 +1,177 @@
+import { useState } from 'react'
+import { PlayArrow as PlayIcon, Refresh as RefreshIcon } from '@mui/icons-material'
+import { Box, Chip, CircularProgress, Collapse, IconButton, Paper, Stack, TextField, Tooltip, Typography, alpha } from '@mui/material'
+import { useActiveProject } from '@/hooks/useAppState'
+import type { HttpRunResult } from '@/types/state'
+
+function statusColor(status: number): 'success' | 'warning' | 'error' {
+  if (status < 300) return 'success'
+  if (status < 400) return 'warning'
+  return 'error'
+}
+
+function formatSize(bytes: number): string {
+  if (bytes < 1024) return `${bytes} B`
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`
+  return `${(bytes / 1024 / 1024).toFixed(1)} MB`
+}
+
+/** Pretty-print JSON bodies, leave anything else as is */
+function formatBody(body: string): string {
+  try {
+    return JSON.stringify(JSON.parse(body), null, 2)
+  } catch {
+    return body
+  }
+}
+
+function ResultView({ result }: { result: HttpRunResult }) {
+  if (result.error || !result.response) {
+    return (
+      <Typography variant="caption" color="error" sx={{ display: 'block', wordBreak: 'break-word' }}>
+        {result.error}
+      </Typography>
+    )
+  }
+  const response = result.response
+  return (
+    <Box>
+      <Stack direction="row" spacing={1} alignItems="center" sx={{ mb: 0.5 }}>
+        <Chip
+          label={`${response.status} ${response.status_text}`}
+          size="small"
+          color={statusColor(response.status)}
+          sx={{ height: 20, fontSize: '0.65rem' }}
+        />
+        <Typography variant="caption" color="text.secondary">
+          {response.duration_ms} ms · {formatSize(response.size_bytes)}
+          {response.body_truncated ? ' (truncated)' : ''}
+        </Typography>
+      </Stack>
+      <Box
+        component="pre"
+        sx={{
+          m: 0,
+          p: 1,
+          maxHeight: 240,
+          overflow: 'auto',
+          bgcolor: alpha('#000', 0.3),
+          borderRadius: 1,
+          fontFamily: 'monospace',
+          fontSize: '0.7rem',
+          whiteSpace: 'pre-wrap',
+          wordBreak: 'break-all',
+        }}
+      >
+        {response.headers.map(([name, value]) => `${name}: ${value}`).join('\n')}
+        {'\n\n'}
+        {formatBody(response.body)}
+      </Box>
+    </Box>
+  )
+}
+
+/**
+ * HttpRequestsPanel - Send the project's saved requests (.rstn/requests/*.toml)
+ * to smoke-test running services
+ */
+export function HttpRequestsPanel() {
+  const { project, dispatch } = useActiveProject()
+  const [envFile, setEnvFile] = useState('.env')
+  const [expanded, setExpanded] = useState<string | null>(null)
+
+  if (!project) return null
+  const collections = project.http_requests?.collections ?? []
+  const running = project.http_requests?.running ?? []
+  const results = project.http_requests?.results ?? {}
+
+  const run = (collection: string, request: string) => {
+    setExpanded(`${collection}/${request}`)
+    dispatch({
+      type: 'RunHttpRequest',
+      payload: { collection, request, env_file: envFile.trim() || null },
+    })
+  }
+
+  return (
+    <Paper variant="outlined" sx={{ p: 2, borderColor: 'outlineVariant' }}>
+      <Stack direction="row" alignItems="center" spacing={1} sx={{ mb: 1 }}>
+        <Typography variant="subtitle2" fontWeight={600} sx={{ flex: 1 }}>
+          HTTP Requests
+        </Typography>
+        <TextField
+          size="small"
+          label="Env file"
+          value={envFile}
+          onChange={(e) => setEnvFile(e.target.value)}
+          sx={{ width: 140 }}
+          InputProps={{ sx: { fontFamily: 'monospace', fontSize: '0.8rem' } }}
+        />
+        <Tooltip title="Reload .rstn/requests/">
+          <IconButton size="small" onClick={() => dispatch({ type: 'LoadHttpRequests' })}>
+            <RefreshIcon fontSize="small" />
+          </IconButton>
+        </Tooltip>
+      </Stack>
+
+      {collections.length === 0 && (
+        <Typography variant="caption" color="text.secondary">
+          Add request collections as .rstn/requests/*.toml to send them from here
+        </Typography>
+      )}
+
+      <Stack spacing={1.5}>
+        {collections.map((collection) => (
+          <Box key={collection.id}>
+            <Typography variant="body2" fontWeight={500}>
+              {collection.name}
+            </Typography>
+            {collection.error && (
+              <Typography variant="caption" color="error" sx={{ display: 'block', wordBreak: 'break-word' }}>
+                {collection.error}
+              </Typography>
+            )}
+            {collection.requests.map((request) => {
+              const key = `${collection.id}/${request.name}`
+              const result = results[key]
+              const isRunning = running.includes(key)
+              return (
+                <Box key={key} sx={{ mt: 0.5 }}>
+                  <Stack direction="row" alignItems="center" spacing={1}>
+                    <Chip label={request.method} size="small" variant="outlined" sx={{ height: 20, fontSize: '0.6rem', minWidth: 56 }} />
+                    <Box
+                      sx={{ flex: 1, minWidth: 0, cursor: result ? 'pointer' : 'default' }}
+                      onClick={() => result && setExpanded(expanded === key ? null : key)}
+                    >
+                      <Typography variant="body2" noWrap>
+                        {request.name}
+                      </Typography>
+                      <Typography variant="caption" color="text.secondary" noWrap sx={{ display: 'block', fontFamily: 'monospace' }}>
+                        {request.url}
+                      </Typography>
+                    </Box>
+                    {result?.response && (
+                      <Chip
+                        label={result.response.status}
+                        size="small"
+                        color={statusColor(result.response.status)}
+                        sx={{ height: 20, fontSize: '0.65rem' }}
+                      />
+                    )}
+                    {result?.error && <Chip label="failed" size="small" color="error" sx={{ height: 20, fontSize: '0.65rem' }} />}
+                    <IconButton size="small" disabled={isRunning} onClick={() => run(collection.id, request.name)}>
+                      {isRunning ? <CircularProgress size={16} /> : <PlayIcon fontSize="small" />}
+                    </IconButton>
+                  </Stack>
+                  <Collapse in={expanded === key && !!result}>
+                    <Box sx={{ mt: 0.5 }}>{result && <ResultView result={result} />}</Box>
+                  </Collapse>
+                </Box>
+              )
+            })}
+          </Box>
+        ))}
+      </Stack>
+    </Paper>
+  )
+}
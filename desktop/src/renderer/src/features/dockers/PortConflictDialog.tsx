@@ -1,7 +1,8 @@
 import { useState, useEffect } from 'react'
 import {
   WarningAmber as AlertTriangleIcon,
-  Dns as ContainerIcon
+  Dns as ContainerIcon,
+  Memory as ProcessIcon
 } from '@mui/icons-material'
 import {
   Button,
@@ -28,6 +29,13 @@ interface PendingConflict {
       image: string
       is_rstn_managed: boolean
     }
+    conflicting_process?: {
+      pid: number
+      name: string
+      exe: string | null
+      user: string | null
+      command: string | null
+    } | null
     suggested_port: number
   }
 }
@@ -36,6 +44,7 @@ interface PortConflictDialogProps {
   pendingConflict: PendingConflict | null
   onResolveWithPort: (serviceId: string, port: number) => void
   onResolveByStoppingContainer: (containerId: string, serviceId: string) => void
+  onResolveByKillingProcess: (port: number, pid: number, serviceId: string) => void
   onCancel: () => void
 }
 
@@ -43,11 +52,13 @@ export function PortConflictDialog({
   pendingConflict,
   onResolveWithPort,
   onResolveByStoppingContainer,
+  onResolveByKillingProcess,
   onCancel,
 }: PortConflictDialogProps) {
   const [resolution, setResolution] = useState<'alt-port' | 'stop-container'>('alt-port')
   const [customPort, setCustomPort] = useState<string>('')
   const [isResolving, setIsResolving] = useState(false)
+  const [confirmKill, setConfirmKill] = useState(false)
 
   // Reset state when dialog opens with new conflict
   useEffect(() => {
@@ -55,6 +66,7 @@ export function PortConflictDialog({
       setResolution('alt-port')
       setCustomPort(String(pendingConflict.conflict.suggested_port))
       setIsResolving(false)
+      setConfirmKill(false)
     }
   }, [pendingConflict])
 
@@ -62,7 +74,9 @@ export function PortConflictDialog({
 
   const { service_id, conflict } = pendingConflict
   const { requested_port, conflicting_container, suggested_port } = conflict
-  const canStopContainer = conflicting_container.is_rstn_managed
+  const process = conflict.conflicting_process ?? null
+  // pid 0: the process holding the port could not be identified
+  const canStopContainer = process ? process.pid > 0 : conflicting_container.is_rstn_managed
 
   const handleResolve = async () => {
     setIsResolving(true)
@@ -73,6 +87,13 @@ export function PortConflictDialog({
           return
         }
         onResolveWithPort(service_id, port)
+      } else if (process) {
+        // Killing a host process is destructive; ask once more
+        if (!confirmKill) {
+          setConfirmKill(true)
+          return
+        }
+        onResolveByKillingProcess(requested_port, process.pid, service_id)
       } else {
         onResolveByStoppingContainer(conflicting_container.id, service_id)
       }
@@ -100,15 +121,45 @@ export function PortConflictDialog({
         </DialogContentText>
 
         <Stack spacing={3}>
-          {/* Conflicting container info */}
+          {/* Conflicting container or process info */}
           <Paper variant="outlined" sx={{ p: 2, bgcolor: 'surfaceContainerLow.main', borderRadius: 2 }}>
-            <Stack direction="row" spacing={1.5} alignItems="center">
-              <ContainerIcon fontSize="small" color="primary" />
-              <Box>
-                <Typography variant="body2" fontWeight={700}>{conflicting_container.name}</Typography>
-                <Typography variant="caption" color="text.secondary">Image: {conflicting_container.image}</Typography>
-              </Box>
-            </Stack>
+            {process ? (
+              <Stack direction="row" spacing={1.5} alignItems="center">
+                <ProcessIcon fontSize="small" color="primary" />
+                <Box sx={{ minWidth: 0 }}>
+                  <Typography variant="body2" fontWeight={700}>
+                    {process.pid > 0 ? `${process.name || 'Unknown'} (pid ${process.pid})` : 'Unidentified process'}
+                  </Typography>
+                  {process.user && (
+                    <Typography variant="caption" color="text.secondary" sx={{ display: 'block' }}>
+                      User: {process.user}
+                    </Typography>
+                  )}
+                  {(process.command || process.exe) && (
+                    <Typography
+                      variant="caption"
+                      color="text.secondary"
+                      sx={{ display: 'block', fontFamily: 'monospace', wordBreak: 'break-all' }}
+                    >
+                      {process.command || process.exe}
+                    </Typography>
+                  )}
+                  {process.pid === 0 && (
+                    <Typography variant="caption" color="text.secondary" sx={{ display: 'block' }}>
+                      A process outside Docker holds this port (likely owned by another user)
+                    </Typography>
+                  )}
+                </Box>
+              </Stack>
+            ) : (
+              <Stack direction="row" spacing={1.5} alignItems="center">
+                <ContainerIcon fontSize="small" color="primary" />
+                <Box>
+                  <Typography variant="body2" fontWeight={700}>{conflicting_container.name}</Typography>
+                  <Typography variant="caption" color="text.secondary">Image: {conflicting_container.image}</Typography>
+                </Box>
+              </Stack>
+            )}
           </Paper>
 
           {/* Resolution options */}
@@ -116,7 +167,10 @@ export function PortConflictDialog({
             {/* Option 1: Use alternative port */}
             <Paper
               variant="outlined"
-              onClick={() => setResolution('alt-port')}
+              onClick={() => {
+                setResolution('alt-port')
+                setConfirmKill(false)
+              }}
               sx={{
                 p: 2,
                 cursor: 'pointer',
@@ -156,10 +210,19 @@ export function PortConflictDialog({
                 '&:hover': { bgcolor: canStopContainer ? 'action.hover' : 'background.paper' }
               }}
             >
-              <Typography variant="subtitle2" fontWeight={700}>Stop conflicting container and retry</Typography>
+              <Typography variant="subtitle2" fontWeight={700}>
+                {process ? 'Kill the process and retry' : 'Stop conflicting container and retry'}
+              </Typography>
               {!canStopContainer && (
                 <Typography variant="caption" color="error" sx={{ display: 'block', mt: 0.5 }}>
-                  This container is not managed by rstn and cannot be stopped here.
+                  {process
+                    ? 'The process could not be identified and cannot be stopped here.'
+                    : 'This container is not managed by rstn and cannot be stopped here.'}
+                </Typography>
+              )}
+              {confirmKill && process && (
+                <Typography variant="caption" color="error" sx={{ display: 'block', mt: 0.5 }}>
+                  {process.name || 'The process'} (pid {process.pid}) will be terminated. Unsaved work in it is lost.
                 </Typography>
               )}
             </Paper>
@@ -170,11 +233,12 @@ export function PortConflictDialog({
         <Button onClick={onCancel} disabled={isResolving}>Cancel</Button>
         <Button
           variant="contained"
+          color={confirmKill ? 'error' : 'primary'}
           onClick={handleResolve}
           disabled={isResolving || (resolution === 'alt-port' && !isValidPort())}
           sx={{ borderRadius: 2, px: 3 }}
         >
-          {isResolving ? 'Resolving...' : 'Continue'}
+          {isResolving ? 'Resolving...' : confirmKill ? 'Kill and Start' : 'Continue'}
         </Button>
       </DialogActions>
     </Dialog>
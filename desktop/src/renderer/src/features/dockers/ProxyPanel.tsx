@@ -0,0 +1,168 @@
+import { useState } from 'react'
+import { Close as CloseIcon, OpenInNew as OpenIcon } from '@mui/icons-material'
+import {
+  Box,
+  Button,
+  Chip,
+  IconButton,
+  Link,
+  MenuItem,
+  Paper,
+  Stack,
+  TextField,
+  Tooltip,
+  Typography,
+} from '@mui/material'
+import { useAppState } from '@/hooks/useAppState'
+import type { DockerServiceInfo, ProxyRoute } from '@/types/state'
+
+interface ProxyPanelProps {
+  services: DockerServiceInfo[]
+}
+
+/**
+ * ProxyPanel - Local reverse proxy: friendly *.localhost URLs for services,
+ * kept pointing at the right port when a service moves after a conflict
+ */
+export function ProxyPanel({ services }: ProxyPanelProps) {
+  const { state, dispatch } = useAppState()
+  const [host, setHost] = useState('')
+  const [pathPrefix, setPathPrefix] = useState('')
+  const [serviceId, setServiceId] = useState('')
+
+  const settings = state?.global_settings?.proxy
+  const status = state?.docker?.proxy
+  const routes = settings?.routes ?? []
+  const running = status?.running ?? false
+  const error = state?.error?.code === 'proxy_routes' ? state.error.message : null
+
+  const setRoutes = (next: ProxyRoute[]) => dispatch({ type: 'SetProxyRoutes', payload: { routes: next } })
+
+  const handleAdd = async () => {
+    if (!host.trim() || !serviceId) return
+    await setRoutes([...routes, { host: host.trim(), path_prefix: pathPrefix.trim() || null, service_id: serviceId }])
+    setHost('')
+    setPathPrefix('')
+  }
+
+  const urlOf = (route: ProxyRoute) =>
+    `https://${route.host}${status?.port ? `:${status.port}` : ''}${route.path_prefix ?? '/'}`
+  const serviceOf = (id: string) => services.find((s) => s.id === id)
+
+  return (
+    <Paper variant="outlined" sx={{ p: 2, borderColor: 'outlineVariant' }}>
+      <Stack direction="row" alignItems="center" spacing={1} sx={{ mb: 1 }}>
+        <Typography variant="subtitle2" fontWeight={600} sx={{ flex: 1 }}>
+          Local Proxy
+        </Typography>
+        {running && <Chip label={`:${status?.port}`} size="small" color="success" sx={{ height: 20, fontSize: '0.65rem' }} />}
+        <Button
+          variant="outlined"
+          size="small"
+          color={running ? 'error' : 'primary'}
+          onClick={() => dispatch(running ? { type: 'StopProxy' } : { type: 'StartProxy', payload: { port: null } })}
+        >
+          {running ? 'Stop' : 'Start'}
+        </Button>
+      </Stack>
+
+      {status?.error && (
+        <Typography variant="caption" color="error" sx={{ display: 'block', mb: 1, wordBreak: 'break-word' }}>
+          {status.error}
+        </Typography>
+      )}
+      {error && (
+        <Typography variant="caption" color="error" sx={{ display: 'block', mb: 1, wordBreak: 'break-word' }}>
+          {error}
+        </Typography>
+      )}
+      {status?.ca_cert_path && (
+        <Typography variant="caption" color="text.secondary" sx={{ display: 'block', mb: 1, wordBreak: 'break-all' }}>
+          For HTTPS, trust the local CA once: {status.ca_cert_path}
+        </Typography>
+      )}
+
+      <Stack spacing={0.5} sx={{ mb: 1.5 }}>
+        {routes.map((route, index) => {
+          const service = serviceOf(route.service_id)
+          const isUp = service?.status === 'running'
+          return (
+            <Stack key={`${route.host}${route.path_prefix ?? ''}`} direction="row" alignItems="center" spacing={1}>
+              <Box sx={{ flex: 1, minWidth: 0 }}>
+                {running ? (
+                  <Link href={urlOf(route)} target="_blank" rel="noreferrer" variant="body2" sx={{ fontFamily: 'monospace' }}>
+                    {route.host}
+                    {route.path_prefix ?? ''}
+                  </Link>
+                ) : (
+                  <Typography variant="body2" sx={{ fontFamily: 'monospace' }}>
+                    {route.host}
+                    {route.path_prefix ?? ''}
+                  </Typography>
+                )}
+              </Box>
+              <Chip
+                label={service?.port ? `${service.name} :${service.port}` : (service?.name ?? route.service_id)}
+                size="small"
+                variant="outlined"
+                color={isUp ? 'success' : 'default'}
+                sx={{ height: 20, fontSize: '0.65rem' }}
+              />
+              {running && (
+                <Tooltip title="Open">
+                  <IconButton size="small" component="a" href={urlOf(route)} target="_blank" rel="noreferrer">
+                    <OpenIcon fontSize="small" />
+                  </IconButton>
+                </Tooltip>
+              )}
+              <IconButton size="small" onClick={() => setRoutes(routes.filter((_, i) => i !== index))}>
+                <CloseIcon fontSize="small" />
+              </IconButton>
+            </Stack>
+          )
+        })}
+        {routes.length === 0 && (
+          <Typography variant="caption" color="text.secondary">
+            Route a hostname like pg.localhost to a service so its URL survives port changes
+          </Typography>
+        )}
+      </Stack>
+
+      <Stack direction="row" spacing={1} alignItems="center">
+        <TextField
+          size="small"
+          label="Host"
+          placeholder="app.localhost"
+          value={host}
+          onChange={(e) => setHost(e.target.value)}
+          sx={{ flex: 1 }}
+        />
+        <TextField
+          size="small"
+          label="Path"
+          placeholder="/"
+          value={pathPrefix}
+          onChange={(e) => setPathPrefix(e.target.value)}
+          sx={{ width: 100 }}
+        />
+        <TextField
+          select
+          size="small"
+          label="Service"
+          value={serviceId}
+          onChange={(e) => setServiceId(e.target.value)}
+          sx={{ flex: 1 }}
+        >
+          {services.map((s) => (
+            <MenuItem key={s.id} value={s.id}>
+              {s.name}
+            </MenuItem>
+          ))}
+        </TextField>
+        <Button variant="outlined" size="small" onClick={handleAdd} disabled={!host.trim() || !serviceId}>
+          Add
+        </Button>
+      </Stack>
+    </Paper>
+  )
+}
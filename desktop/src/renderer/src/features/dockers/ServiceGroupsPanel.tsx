@@ -0,0 +1,151 @@
+import { useState } from 'react'
+import {
+  PlayArrow as PlayIcon,
+  Stop as StopIcon,
+  Close as CloseIcon,
+  DeleteOutline as DeleteIcon,
+} from '@mui/icons-material'
+import { Box, Button, Chip, IconButton, MenuItem, Paper, Stack, TextField, Tooltip, Typography } from '@mui/material'
+import { useActiveProject } from '@/hooks/useAppState'
+import type { DockerServiceInfo } from '@/types/state'
+
+interface ServiceGroupsPanelProps {
+  services: DockerServiceInfo[]
+}
+
+/**
+ * ServiceGroupsPanel - The active project's service groups (.rstn/services.toml)
+ *
+ * Groups start in dependency order (databases before apps) and stop in reverse.
+ */
+export function ServiceGroupsPanel({ services }: ServiceGroupsPanelProps) {
+  const { project, dispatch } = useActiveProject()
+  const [serviceId, setServiceId] = useState('')
+  const [groupName, setGroupName] = useState('')
+
+  if (!project) return null
+  const groups = project.service_groups ?? []
+  const error = project.service_groups_error
+  const rstnServices = services.filter((s) => s.is_rstn_managed)
+
+  const handleAssign = async () => {
+    if (!serviceId || !groupName.trim()) return
+    await dispatch({ type: 'AssignServiceToGroup', payload: { service_id: serviceId, group: groupName.trim() } })
+    setServiceId('')
+  }
+
+  const statusOf = (id: string) => services.find((s) => s.id === id)?.status ?? 'stopped'
+
+  return (
+    <Paper variant="outlined" sx={{ p: 2, borderColor: 'outlineVariant' }}>
+      <Typography variant="subtitle2" fontWeight={600} sx={{ mb: 1 }}>
+        Service Groups
+      </Typography>
+
+      {error && (
+        <Typography variant="caption" color="error" sx={{ display: 'block', mb: 1, wordBreak: 'break-word' }}>
+          {error}
+        </Typography>
+      )}
+
+      <Stack spacing={1.5}>
+        {groups.map((group) => {
+          const running = group.services.filter((id) => statusOf(id) === 'running').length
+          return (
+            <Box key={group.name}>
+              <Stack direction="row" alignItems="center" spacing={1}>
+                <Typography variant="body2" fontWeight={500} sx={{ flex: 1 }}>
+                  {group.name}
+                </Typography>
+                <Chip label={`${running}/${group.services.length}`} size="small" sx={{ height: 20, fontSize: '0.65rem' }} />
+                <Tooltip title="Start in dependency order">
+                  <IconButton
+                    size="small"
+                    onClick={() => dispatch({ type: 'StartServiceGroup', payload: { name: group.name } })}
+                  >
+                    <PlayIcon fontSize="small" />
+                  </IconButton>
+                </Tooltip>
+                <Tooltip title="Stop (dependents first)">
+                  <IconButton
+                    size="small"
+                    onClick={() => dispatch({ type: 'StopServiceGroup', payload: { name: group.name } })}
+                  >
+                    <StopIcon fontSize="small" />
+                  </IconButton>
+                </Tooltip>
+                <Tooltip title="Delete group">
+                  <span>
+                    <IconButton
+                      size="small"
+                      disabled={!!error}
+                      onClick={() => dispatch({ type: 'DeleteServiceGroup', payload: { name: group.name } })}
+                    >
+                      <DeleteIcon fontSize="small" />
+                    </IconButton>
+                  </span>
+                </Tooltip>
+              </Stack>
+              <Stack direction="row" flexWrap="wrap" gap={0.5} sx={{ mt: 0.5 }}>
+                {group.services.map((id) => (
+                  <Chip
+                    key={id}
+                    label={id}
+                    size="small"
+                    variant="outlined"
+                    color={statusOf(id) === 'running' ? 'success' : 'default'}
+                    onDelete={
+                      error
+                        ? undefined
+                        : () => dispatch({ type: 'AssignServiceToGroup', payload: { service_id: id, group: null } })
+                    }
+                    deleteIcon={<CloseIcon />}
+                  />
+                ))}
+              </Stack>
+            </Box>
+          )
+        })}
+
+        {groups.length === 0 && !error && (
+          <Typography variant="caption" color="text.secondary">
+            Group services to start and stop them together
+          </Typography>
+        )}
+
+        <Stack direction="row" spacing={1} alignItems="center">
+          <TextField
+            select
+            size="small"
+            label="Service"
+            value={serviceId}
+            onChange={(e) => setServiceId(e.target.value)}
+            sx={{ flex: 1 }}
+          >
+            {rstnServices.map((s) => (
+              <MenuItem key={s.id} value={s.id}>
+                {s.name}
+              </MenuItem>
+            ))}
+          </TextField>
+          <TextField
+            size="small"
+            label="Group"
+            value={groupName}
+            onChange={(e) => setGroupName(e.target.value)}
+            inputProps={{ list: 'service-group-names' }}
+            sx={{ flex: 1 }}
+          />
+          <datalist id="service-group-names">
+            {groups.map((g) => (
+              <option key={g.name} value={g.name} />
+            ))}
+          </datalist>
+          <Button variant="outlined" size="small" onClick={handleAssign} disabled={!!error || !serviceId || !groupName.trim()}>
+            Add
+          </Button>
+        </Stack>
+      </Stack>
+    </Paper>
+  )
+}
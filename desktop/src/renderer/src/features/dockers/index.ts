@@ -1,3 +1,4 @@
 export { DockersPage } from './DockersPage'
 export { DockerServiceCard } from './DockerServiceCard'
 export { DockerLogSheet } from './DockerLogSheet'
+export { DockerExecConsole } from './DockerExecConsole'
@@ -1,7 +1,8 @@
-import { useCallback, useMemo } from 'react'
-import { Code as FileCode } from '@mui/icons-material'
-import { Box, Typography } from '@mui/material'
+import { useCallback, useEffect, useMemo, useState } from 'react'
+import { Code as FileCode, ExpandLess, ExpandMore, History } from '@mui/icons-material'
+import { Box, Collapse, IconButton, List, ListItem, ListItemText, Tooltip, Typography } from '@mui/material'
 import { useActiveWorktree } from '@/hooks/useAppState'
+import type { FileHistory } from '@/types/state'
 import { SourceCodeViewer, type CommentData } from '@/components/shared/SourceCodeViewer'
 
 // Helper to determine if path is a file (has extension in filename)
@@ -24,6 +25,67 @@ const isFilePath = (path: string): boolean => {
   return name.includes('.')
 }
 
+const formatCommitDate = (date: string): string => {
+  const parsed = new Date(date)
+  return Number.isNaN(parsed.getTime()) ? date : parsed.toLocaleDateString()
+}
+
+/**
+ * FileHistoryBar - Who last touched the file, with the recent commits on demand
+ */
+function FileHistoryBar({ history }: { history: FileHistory }) {
+  const [expanded, setExpanded] = useState(false)
+  const last = history.commits[0]
+
+  let summary: React.ReactNode
+  if (history.is_loading) {
+    summary = 'Loading history...'
+  } else if (history.error) {
+    summary = 'No git history'
+  } else if (!last) {
+    summary = 'Not committed yet'
+  } else {
+    summary = (
+      <>
+        Last changed by <strong>{last.author}</strong> on {formatCommitDate(last.date)}: {last.summary} (
+        <Box component="span" sx={{ fontFamily: 'monospace' }}>{last.short_hash}</Box>)
+      </>
+    )
+  }
+
+  return (
+    <Box sx={{ borderTop: 1, borderColor: 'divider', bgcolor: 'background.paper' }}>
+      <Box sx={{ display: 'flex', alignItems: 'center', gap: 1, px: 1.5, py: 0.5 }}>
+        <History sx={{ fontSize: 16, color: 'text.secondary' }} />
+        <Tooltip title={history.error ?? ''}>
+          <Typography variant="caption" color="text.secondary" noWrap sx={{ flex: 1 }}>
+            {summary}
+          </Typography>
+        </Tooltip>
+        {history.commits.length > 1 && (
+          <IconButton size="small" onClick={() => setExpanded(!expanded)} aria-label="Toggle file history">
+            {expanded ? <ExpandMore fontSize="small" /> : <ExpandLess fontSize="small" />}
+          </IconButton>
+        )}
+      </Box>
+      <Collapse in={expanded}>
+        <List dense disablePadding sx={{ maxHeight: 200, overflow: 'auto' }}>
+          {history.commits.map((commit) => (
+            <ListItem key={commit.hash} sx={{ py: 0 }}>
+              <ListItemText
+                primary={commit.summary}
+                secondary={`${commit.short_hash} · ${commit.author} · ${formatCommitDate(commit.date)}`}
+                primaryTypographyProps={{ variant: 'caption', noWrap: true }}
+                secondaryTypographyProps={{ variant: 'caption', sx: { fontSize: '0.65rem' } }}
+              />
+            </ListItem>
+          ))}
+        </List>
+      </Collapse>
+    </Box>
+  )
+}
+
 export function DetailPanel() {
   const { worktree, dispatch } = useActiveWorktree()
   const explorer = worktree?.explorer
@@ -38,6 +100,14 @@ export function DetailPanel() {
     name: selectedPath.split('/').pop() ?? '',
   } : undefined)
   const comments = explorer?.selected_comments ?? []
+  const fileHistory = explorer?.file_history
+  const isSelectedFile = selectedEntry?.kind === 'file'
+
+  // Load the commit history whenever another file is shown
+  useEffect(() => {
+    if (!selectedPath || !isSelectedFile) return
+    dispatch({ type: 'LoadFileHistory', payload: { path: selectedPath } })
+  }, [selectedPath, isSelectedFile, dispatch])
 
   // Filter to only inline comments (with line numbers)
   const inlineComments = useMemo(() => {
@@ -113,6 +183,7 @@ export function DetailPanel() {
           </Box>
         )}
       </Box>
+      {isFile && fileHistory && fileHistory.path === selectedPath && <FileHistoryBar history={fileHistory} />}
     </Box>
   )
 }
@@ -0,0 +1,266 @@
+import { useCallback, useState } from 'react'
+import {
+  Hub as HubIcon,
+  LinkOff as DisconnectIcon,
+  PlayArrow as PlayIcon,
+  Refresh as RefreshIcon,
+  Replay as ReplayIcon
+} from '@mui/icons-material'
+import {
+  Alert,
+  Box,
+  Button,
+  Card,
+  CardContent,
+  Chip,
+  FormControlLabel,
+  IconButton,
+  Paper,
+  Stack,
+  Switch,
+  TextField,
+  Tooltip,
+  Typography
+} from '@mui/material'
+import type { Action, ExternalMcpServer, McpPlaygroundEntry, McpTool } from '@/types/state'
+
+interface ExternalServersCardProps {
+  servers: ExternalMcpServer[]
+  history: McpPlaygroundEntry[]
+  dispatch: (action: Action) => Promise<void>
+}
+
+/**
+ * External MCP servers from the worktree's .mcp.json, with their tools and
+ * a playground to invoke them, keeping a request/response history.
+ */
+export function ExternalServersCard({ servers, history, dispatch }: ExternalServersCardProps) {
+  const [selected, setSelected] = useState<{ server: string; tool: string } | null>(null)
+  const isConnecting = servers.some((s) => s.status === 'connecting')
+
+  const handleConnect = useCallback(async () => {
+    await dispatch({ type: 'ConnectExternalMcpServers' })
+  }, [dispatch])
+
+  const handleDisconnect = useCallback(async () => {
+    setSelected(null)
+    await dispatch({ type: 'DisconnectExternalMcpServers' })
+  }, [dispatch])
+
+  const handleCall = useCallback(
+    async (server: string, tool: string, args: Record<string, unknown>) => {
+      await dispatch({ type: 'CallExternalMcpTool', payload: { server, tool, arguments: args } })
+    },
+    [dispatch]
+  )
+
+  const handleClearHistory = useCallback(async () => {
+    await dispatch({ type: 'ClearMcpPlayground' })
+  }, [dispatch])
+
+  return (
+    <Card variant="outlined" sx={{ borderRadius: 4 }}>
+      <CardContent sx={{ p: 3 }}>
+        <Box sx={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between', mb: 2 }}>
+          <Stack direction="row" spacing={1} alignItems="center">
+            <HubIcon fontSize="small" />
+            <Typography variant="subtitle1" fontWeight={600}>External Servers</Typography>
+          </Stack>
+          <Stack direction="row" spacing={1}>
+            {servers.length > 0 && (
+              <Button size="small" onClick={handleDisconnect} startIcon={<DisconnectIcon />}>
+                Disconnect
+              </Button>
+            )}
+            <Button size="small" variant="outlined" onClick={handleConnect} disabled={isConnecting} startIcon={<RefreshIcon />}>
+              {servers.length > 0 ? 'Reconnect' : 'Connect'}
+            </Button>
+          </Stack>
+        </Box>
+
+        {servers.length === 0 ? (
+          <Typography variant="body2" color="text.secondary">
+            Connect to the servers declared in this worktree's .mcp.json to browse and call their tools.
+          </Typography>
+        ) : (
+          <Stack spacing={2}>
+            {servers.map((server) => (
+              <Box key={server.name}>
+                <Stack direction="row" spacing={1} alignItems="center" sx={{ mb: 1 }}>
+                  <Typography variant="subtitle2" fontWeight={700}>{server.name}</Typography>
+                  <Chip label={server.transport} size="small" variant="outlined" sx={{ height: 20, fontSize: '0.65rem' }} />
+                  <Chip
+                    label={server.status}
+                    size="small"
+                    color={server.status === 'connected' ? 'success' : server.status === 'error' ? 'error' : 'default'}
+                    sx={{ height: 20, fontSize: '0.65rem' }}
+                  />
+                  <Typography variant="caption" color="text.secondary" sx={{ fontFamily: 'monospace' }} noWrap>
+                    {server.target}
+                  </Typography>
+                </Stack>
+                {server.error && <Alert severity="error" sx={{ mb: 1 }}>{server.error}</Alert>}
+                <Stack direction="row" spacing={1} flexWrap="wrap" useFlexGap>
+                  {server.tools.map((tool) => {
+                    const isSelected = selected?.server === server.name && selected.tool === tool.name
+                    return (
+                      <Chip
+                        key={tool.name}
+                        label={tool.name}
+                        size="small"
+                        variant={isSelected ? 'filled' : 'outlined'}
+                        color={isSelected ? 'primary' : 'default'}
+                        onClick={() => setSelected(isSelected ? null : { server: server.name, tool: tool.name })}
+                        sx={{ fontFamily: 'monospace' }}
+                      />
+                    )
+                  })}
+                </Stack>
+                {selected?.server === server.name && (
+                  <ToolCallForm
+                    key={selected.tool}
+                    tool={server.tools.find((t) => t.name === selected.tool)}
+                    onCall={(args) => handleCall(server.name, selected.tool, args)}
+                  />
+                )}
+              </Box>
+            ))}
+          </Stack>
+        )}
+
+        {history.length > 0 && (
+          <Box sx={{ mt: 3 }}>
+            <Box sx={{ display: 'flex', alignItems: 'center', justifyContent: 'space-between', mb: 1 }}>
+              <Typography variant="subtitle2" fontWeight={600}>History</Typography>
+              <Button size="small" onClick={handleClearHistory}>Clear</Button>
+            </Box>
+            <Stack spacing={1}>
+              {history.map((entry) => (
+                <PlaygroundEntry
+                  key={entry.id}
+                  entry={entry}
+                  onReplay={() => handleCall(entry.server, entry.tool, entry.arguments)}
+                />
+              ))}
+            </Stack>
+          </Box>
+        )}
+      </CardContent>
+    </Card>
+  )
+}
+
+/** One recorded call: arguments and result (or error) */
+function PlaygroundEntry({ entry, onReplay }: { entry: McpPlaygroundEntry; onReplay: () => void }) {
+  const isRunning = entry.duration_ms === undefined
+  return (
+    <Paper variant="outlined" sx={{ p: 1.5, bgcolor: 'surfaceContainerLow.main' }}>
+      <Stack direction="row" spacing={1} alignItems="center">
+        <Typography variant="caption" sx={{ fontFamily: 'monospace', fontWeight: 700 }}>
+          {entry.server} / {entry.tool}
+        </Typography>
+        <Chip
+          label={isRunning ? 'running' : entry.error ? 'error' : `${entry.duration_ms} ms`}
+          size="small"
+          color={isRunning ? 'default' : entry.error ? 'error' : 'success'}
+          sx={{ height: 18, fontSize: '0.65rem' }}
+        />
+        <Typography variant="caption" color="text.secondary" sx={{ flex: 1 }}>
+          {new Date(entry.called_at).toLocaleTimeString()}
+        </Typography>
+        <Tooltip title="Call again">
+          <span>
+            <IconButton size="small" onClick={onReplay} disabled={isRunning}>
+              <ReplayIcon fontSize="small" />
+            </IconButton>
+          </span>
+        </Tooltip>
+      </Stack>
+      <Box component="details" sx={{ mt: 0.5 }}>
+        <Box component="summary" sx={{ typography: 'caption', color: 'text.secondary', cursor: 'pointer' }}>
+          Request / response
+        </Box>
+        <Box component="pre" sx={{ typography: 'caption', fontFamily: 'monospace', whiteSpace: 'pre-wrap', mb: 0 }}>
+          {JSON.stringify(entry.arguments, null, 2)}
+        </Box>
+        {entry.error && <Alert severity="error" sx={{ mt: 1 }}>{entry.error}</Alert>}
+        {entry.result !== undefined && (
+          <Box component="pre" sx={{ typography: 'caption', fontFamily: 'monospace', whiteSpace: 'pre-wrap', mb: 0 }}>
+            {JSON.stringify(entry.result, null, 2)}
+          </Box>
+        )}
+      </Box>
+    </Paper>
+  )
+}
+
+interface SchemaProperty {
+  type?: string
+  description?: string
+}
+
+/** Argument form generated from a tool's input schema */
+function ToolCallForm({ tool, onCall }: { tool: McpTool | undefined; onCall: (args: Record<string, unknown>) => void }) {
+  const [values, setValues] = useState<Record<string, unknown>>({})
+  if (!tool) return null
+
+  const schema = tool.input_schema as { properties?: Record<string, SchemaProperty>; required?: string[] }
+  const properties = Object.entries(schema?.properties ?? {})
+  const required = schema?.required ?? []
+
+  const setValue = (name: string, value: unknown) => setValues((prev) => ({ ...prev, [name]: value }))
+
+  const handleSubmit = () => {
+    const args: Record<string, unknown> = {}
+    for (const [name, property] of properties) {
+      const value = values[name]
+      if (value === undefined || value === '') continue
+      if (property.type === 'number' || property.type === 'integer') {
+        args[name] = Number(value)
+      } else if (property.type === 'object' || property.type === 'array') {
+        try {
+          args[name] = JSON.parse(String(value))
+        } catch {
+          args[name] = value
+        }
+      } else {
+        args[name] = value
+      }
+    }
+    onCall(args)
+  }
+
+  return (
+    <Stack spacing={1.5} sx={{ mt: 1.5 }}>
+      {tool.description && (
+        <Typography variant="body2" color="text.secondary">{tool.description}</Typography>
+      )}
+      {properties.map(([name, property]) =>
+        property.type === 'boolean' ? (
+          <FormControlLabel
+            key={name}
+            control={<Switch size="small" checked={Boolean(values[name])} onChange={(e) => setValue(name, e.target.checked)} />}
+            label={<Typography variant="body2">{name}</Typography>}
+          />
+        ) : (
+          <TextField
+            key={name}
+            label={name}
+            size="small"
+            required={required.includes(name)}
+            type={property.type === 'number' || property.type === 'integer' ? 'number' : 'text'}
+            multiline={property.type === 'object' || property.type === 'array'}
+            helperText={property.description}
+            value={values[name] ?? ''}
+            onChange={(e) => setValue(name, e.target.value)}
+          />
+        )
+      )}
+      <Box>
+        <Button size="small" variant="contained" onClick={handleSubmit} startIcon={<PlayIcon />}>
+          Call {tool.name}
+        </Button>
+      </Box>
+    </Stack>
+  )
+}
@@ -8,7 +8,8 @@ import {
   ErrorOutline as AlertIcon,
   CheckCircle as SuccessIcon,
   Terminal as TerminalIcon,
-  ContentCopy as CopyIcon
+  ContentCopy as CopyIcon,
+  VisibilityOff as HideIcon
 } from '@mui/icons-material'
 import {
   Button,
@@ -28,6 +29,7 @@ import { LoadingState } from '@/components/shared/LoadingState'
 import { EmptyState } from '@/components/shared/EmptyState'
 import { ErrorBanner } from '@/components/shared/ErrorBanner'
 import { useMcpState } from '@/hooks/useAppState'
+import { ExternalServersCard } from './ExternalServersCard'
 import type { McpLogEntry, McpTool } from '@/types/state'
 
 /**
@@ -48,6 +50,18 @@ export function McpPage() {
     await dispatch({ type: 'ClearMcpLogs' })
   }, [dispatch])
 
+  const handleHideTool = useCallback(async (toolName: string) => {
+    const policy = mcp?.policy ?? {}
+    await dispatch({
+      type: 'SetMcpPolicy',
+      payload: { policy: { ...policy, deny: [...(policy.deny ?? []), toolName] } }
+    })
+  }, [dispatch, mcp?.policy])
+
+  const handleClearPolicy = useCallback(async () => {
+    await dispatch({ type: 'SetMcpPolicy', payload: { policy: {} } })
+  }, [dispatch])
+
   const handleCopyCommand = useCallback(() => {
     if (!mcp?.config_path) return
     const command = `claude -p --verbose --output-format stream-json --mcp-config ${mcp.config_path} "your prompt here"`
@@ -56,6 +70,8 @@ export function McpPage() {
 
   // Get tools from state
   const tools = mcp?.available_tools ?? []
+  const allowed = mcp?.policy?.allow ?? []
+  const denied = mcp?.policy?.deny ?? []
 
   // Loading state
   if (isLoading) {
@@ -154,20 +170,48 @@ export function McpPage() {
           </CardContent>
         </Card>
 
+        {/* External Servers Card */}
+        <ExternalServersCard
+          servers={mcp.external_servers ?? []}
+          history={mcp.playground?.history ?? []}
+          dispatch={dispatch}
+        />
+
         {/* Available Tools Card */}
         {isRunning && (
           <Card variant="outlined" sx={{ borderRadius: 4 }}>
             <CardContent sx={{ p: 3 }}>
               <Typography variant="subtitle1" fontWeight={600} sx={{ mb: 2 }}>Available Tools</Typography>
+              {(allowed.length > 0 || denied.length > 0) && (
+                <Stack direction="row" spacing={1} alignItems="center" flexWrap="wrap" useFlexGap sx={{ mb: 2 }}>
+                  <Typography variant="caption" color="text.secondary">
+                    Restricted by .rstn/mcp-policy.toml:
+                  </Typography>
+                  {allowed.length > 0 && (
+                    <Chip label={`only ${allowed.join(', ')}`} size="small" color="primary" variant="outlined" />
+                  )}
+                  {denied.map((pattern) => (
+                    <Chip key={pattern} label={`hidden ${pattern}`} size="small" color="warning" variant="outlined" />
+                  ))}
+                  <Button size="small" onClick={handleClearPolicy}>Expose all</Button>
+                </Stack>
+              )}
               {tools.length === 0 ? (
                 <Typography variant="body2" color="text.secondary">No tools available</Typography>
               ) : (
                 <Stack spacing={1.5}>
                   {tools.map((tool) => (
                     <Paper key={tool.name} variant="outlined" sx={{ p: 2, bgcolor: 'surfaceContainerLow.main', borderColor: 'outlineVariant' }}>
-                      <Typography variant="subtitle2" sx={{ fontFamily: 'monospace', fontWeight: 700, color: 'primary.main', mb: 0.5 }}>
-                        {tool.name}
-                      </Typography>
+                      <Stack direction="row" alignItems="center" justifyContent="space-between">
+                        <Typography variant="subtitle2" sx={{ fontFamily: 'monospace', fontWeight: 700, color: 'primary.main', mb: 0.5 }}>
+                          {tool.name}
+                        </Typography>
+                        <Tooltip title="Hide from MCP clients">
+                          <IconButton size="small" onClick={() => handleHideTool(tool.name)}>
+                            <HideIcon fontSize="small" />
+                          </IconButton>
+                        </Tooltip>
+                      </Stack>
                       <Typography variant="body2" sx={{ mb: 1 }}>{tool.description}</Typography>
                       {tool.input_schema && (
                         <Box component="details" sx={{ cursor: 'pointer' }}>
@@ -1,9 +1,10 @@
 import React, { SyntheticEvent, useCallback } from 'react'
-import { Box, Tabs, Tab, IconButton, Stack, styled } from '@mui/material'
+import { Box, Tabs, Tab, IconButton, Stack, Tooltip, styled } from '@mui/material'
 import { Add as AddIcon, Close as CloseIcon, Folder as ProjectIcon } from '@mui/icons-material'
 
 import { useActiveProject, useAppState } from '@/hooks/useAppState'
 import { GlobalIconBar } from '@/components/layout/GlobalIconBar'
+import type { ProjectState } from '@/types/state'
 
 // Custom Styled Tab for M3 "Chrome-like" or "Folder-like" appearance if desired
 // For now, adhering to standard M3 Tabs spec
@@ -32,6 +33,30 @@ const StyledTab = styled(Tab)(({ theme }) => ({
   },
 }))
 
+/**
+ * What keeps running in a background project (MCP servers, terminals, chats)
+ */
+function ProjectActivity({ project }: { project: ProjectState }) {
+  const mcpServers = project.worktrees.filter((w) => w.mcp.status === 'running').length
+  const terminals = project.worktrees.filter((w) => w.terminal.session_id).length
+  const chats = project.worktrees.filter((w) => w.chat.is_typing).length
+  const parts = [
+    mcpServers > 0 && `${mcpServers} MCP server${mcpServers > 1 ? 's' : ''}`,
+    terminals > 0 && `${terminals} terminal${terminals > 1 ? 's' : ''}`,
+    chats > 0 && `${chats} chat${chats > 1 ? 's' : ''} responding`,
+  ].filter(Boolean)
+  if (parts.length === 0) return null
+
+  return (
+    <Tooltip title={`Running: ${parts.join(', ')}`}>
+      <Box
+        component="span"
+        sx={{ width: 6, height: 6, borderRadius: '50%', bgcolor: 'success.main', display: 'inline-block' }}
+      />
+    </Tooltip>
+  )
+}
+
 export function ProjectTabs() {
   const { projects, activeIndex, dispatch } = useActiveProject()
   const { dispatch: appDispatch } = useAppState()
@@ -42,9 +67,7 @@ export function ProjectTabs() {
 
   const handleClose = useCallback(async (e: React.MouseEvent, index: number) => {
     e.stopPropagation()
-    // TODO: Implement CloseProject action in backend if not exists, 
-    // or assume we can't close for now without backend support
-    // dispatch({ type: 'CloseProject', payload: { index } }) 
+    await dispatch({ type: 'CloseProject', payload: { index } })
   }, [dispatch])
 
   const handleAdd = useCallback(async () => {
@@ -75,6 +98,7 @@ export function ProjectTabs() {
                   <Stack direction="row" alignItems="center" spacing={1}>
                     <ProjectIcon fontSize="small" />
                     <span>{project.name}</span>
+                    {index !== activeIndex && <ProjectActivity project={project} />}
                     <IconButton
                       size="small"
                       component="span"
@@ -0,0 +1,126 @@
+import { useCallback, useEffect, useState } from 'react'
+import { Box, Button, CircularProgress, Paper, Stack, Typography } from '@mui/material'
+import { CheckCircle, ErrorOutline, WarningAmber } from '@mui/icons-material'
+
+type DoctorReport = Awaited<ReturnType<typeof window.doctorApi.run>>
+type DoctorCheck = DoctorReport['checks'][number]
+
+function StatusIcon({ status }: { status: DoctorCheck['status'] }) {
+  if (status === 'ok') return <CheckCircle fontSize="small" color="success" />
+  if (status === 'warning') return <WarningAmber fontSize="small" color="warning" />
+  return <ErrorOutline fontSize="small" color="error" />
+}
+
+/**
+ * DiagnosticsCard - Pre-flight checks (rstn doctor) with fix suggestions
+ */
+export function DiagnosticsCard() {
+  const [report, setReport] = useState<DoctorReport | null>(null)
+  const [running, setRunning] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+  const [exporting, setExporting] = useState(false)
+  const [exportedPath, setExportedPath] = useState<string | null>(null)
+
+  const runChecks = useCallback(async () => {
+    setRunning(true)
+    try {
+      setReport(await window.doctorApi.run())
+      setError(null)
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e))
+    } finally {
+      setRunning(false)
+    }
+  }, [])
+
+  useEffect(() => {
+    runChecks()
+  }, [runChecks])
+
+  const exportBundle = useCallback(async () => {
+    setExporting(true)
+    try {
+      const path = await window.doctorApi.exportBundle()
+      if (path) setExportedPath(path)
+      setError(null)
+    } catch (e) {
+      setError(e instanceof Error ? e.message : String(e))
+    } finally {
+      setExporting(false)
+    }
+  }, [])
+
+  return (
+    <Paper variant="outlined" sx={{ p: 3 }}>
+      <Stack direction="row" alignItems="center" justifyContent="space-between" sx={{ mb: 2 }}>
+        <Typography variant="h6" fontWeight={600}>
+          Diagnostics
+        </Typography>
+        <Stack direction="row" spacing={1}>
+          <Button
+            variant="outlined"
+            size="small"
+            onClick={exportBundle}
+            disabled={exporting}
+            startIcon={exporting ? <CircularProgress size={14} /> : undefined}
+          >
+            Export Bundle
+          </Button>
+          <Button
+            variant="outlined"
+            size="small"
+            onClick={runChecks}
+            disabled={running}
+            startIcon={running ? <CircularProgress size={14} /> : undefined}
+          >
+            Run Checks
+          </Button>
+        </Stack>
+      </Stack>
+
+      <Typography variant="caption" color="text.secondary" sx={{ display: 'block', mb: 1.5 }}>
+        The bundle zips recent logs, the app state with secrets masked, versions and the last actions, to attach to a
+        bug report
+      </Typography>
+      {exportedPath && (
+        <Typography variant="caption" color="success.main" sx={{ display: 'block', mb: 1.5, wordBreak: 'break-all' }}>
+          Saved to {exportedPath}
+        </Typography>
+      )}
+
+      {error && (
+        <Typography variant="body2" color="error">
+          {error}
+        </Typography>
+      )}
+
+      {report && (
+        <Stack spacing={1.5}>
+          {report.checks.map((check) => (
+            <Stack key={check.id} direction="row" spacing={1.5} alignItems="flex-start">
+              <Box sx={{ pt: 0.25 }}>
+                <StatusIcon status={check.status} />
+              </Box>
+              <Box sx={{ minWidth: 0 }}>
+                <Typography variant="body2" fontWeight={500}>
+                  {check.label}
+                </Typography>
+                <Typography variant="caption" color="text.secondary" sx={{ display: 'block', wordBreak: 'break-word' }}>
+                  {check.detail}
+                </Typography>
+                {check.fix && (
+                  <Typography variant="caption" sx={{ display: 'block' }}>
+                    Fix: {check.fix}
+                  </Typography>
+                )}
+              </Box>
+            </Stack>
+          ))}
+          <Typography variant="caption" color="text.secondary">
+            Last run {new Date(report.ranAt).toLocaleString()}
+          </Typography>
+        </Stack>
+      )}
+    </Paper>
+  )
+}
@@ -0,0 +1,99 @@
+import { useCallback, useEffect, useState } from 'react'
+import { Button, Chip, CircularProgress, Paper, Stack, Typography } from '@mui/material'
+import { useActiveProject } from '@/hooks/useAppState'
+
+type GitHookStatus = Awaited<ReturnType<typeof window.gitHooksApi.status>>
+
+/**
+ * GitHookCard - rstn-managed pre-commit hook (secret scan + .rstn/hooks.toml pipeline)
+ */
+export function GitHookCard() {
+  const { project } = useActiveProject()
+  const projectPath = project?.path ?? null
+  const [status, setStatus] = useState<GitHookStatus | null>(null)
+  const [busy, setBusy] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  const run = useCallback(
+    async (op: (path: string) => Promise<GitHookStatus>) => {
+      if (!projectPath) return
+      setBusy(true)
+      try {
+        setStatus(await op(projectPath))
+        setError(null)
+      } catch (e) {
+        setError(e instanceof Error ? e.message : String(e))
+      } finally {
+        setBusy(false)
+      }
+    },
+    [projectPath]
+  )
+
+  useEffect(() => {
+    setStatus(null)
+    run(window.gitHooksApi.status)
+  }, [run])
+
+  if (!projectPath) return null
+
+  const foreign = status?.installed && !status.managed
+
+  return (
+    <Paper variant="outlined" sx={{ p: 3 }}>
+      <Stack direction="row" alignItems="center" justifyContent="space-between" sx={{ mb: 2 }}>
+        <Stack direction="row" spacing={1} alignItems="center">
+          <Typography variant="h6" fontWeight={600}>
+            Pre-commit Hook
+          </Typography>
+          {status?.managed && (
+            <Chip
+              size="small"
+              label={status.updateAvailable ? 'Update available' : `v${status.version ?? '?'}`}
+              color={status.updateAvailable ? 'warning' : 'success'}
+            />
+          )}
+          {foreign && <Chip size="small" label="Not managed by rstn" />}
+        </Stack>
+        <Stack direction="row" spacing={1}>
+          {status?.managed && (
+            <Button variant="outlined" size="small" disabled={busy} onClick={() => run(window.gitHooksApi.uninstall)}>
+              Uninstall
+            </Button>
+          )}
+          {!foreign && (
+            <Button
+              variant="outlined"
+              size="small"
+              disabled={busy || (status?.managed && !status.updateAvailable)}
+              startIcon={busy ? <CircularProgress size={14} /> : undefined}
+              onClick={() => run(window.gitHooksApi.install)}
+            >
+              {status?.managed ? 'Update' : 'Install'}
+            </Button>
+          )}
+        </Stack>
+      </Stack>
+
+      <Typography variant="caption" color="text.secondary" sx={{ display: 'block', mb: 1 }}>
+        Blocks commits in {project?.name} that stage secrets or fail the .rstn/hooks.toml pipeline
+        {status && status.pipeline.length > 0 && ` (${status.pipeline.join(', ')})`}
+      </Typography>
+      {foreign && (
+        <Typography variant="caption" sx={{ display: 'block' }}>
+          {status?.path} was not written by rstn; move it away to install the managed hook
+        </Typography>
+      )}
+      {status?.pipelineError && (
+        <Typography variant="body2" color="warning.main">
+          {status.pipelineError}
+        </Typography>
+      )}
+      {error && (
+        <Typography variant="body2" color="error">
+          {error}
+        </Typography>
+      )}
+    </Paper>
+  )
+}
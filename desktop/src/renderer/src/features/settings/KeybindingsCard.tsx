@@ -0,0 +1,115 @@
+import { useCallback, useState } from 'react'
+import { Box, Button, Paper, Stack, Typography } from '@mui/material'
+import { useAppState } from '@/hooks/useAppState'
+import { eventToShortcut, formatShortcut } from '@/lib/keybindings'
+
+const COMMAND_LABELS: { command: string; label: string }[] = [
+  { command: 'palette.toggle', label: 'Command Palette' },
+  { command: 'view.workflows', label: 'Go to Workflows' },
+  { command: 'view.tasks', label: 'Go to Tasks' },
+  { command: 'view.explorer', label: 'Go to Explorer' },
+  { command: 'view.terminal', label: 'Go to Terminal' },
+  { command: 'view.chat', label: 'Go to Chat' },
+  { command: 'view.dockers', label: 'Go to Dockers' },
+  { command: 'view.env', label: 'Go to Env' },
+  { command: 'view.mcp', label: 'Go to MCP' },
+  { command: 'view.settings', label: 'Go to Settings' },
+]
+
+/**
+ * KeybindingsCard - Rebind keyboard shortcuts (conflicts are rejected by the backend)
+ */
+export function KeybindingsCard() {
+  const { state, dispatch } = useAppState()
+  const [recording, setRecording] = useState<string | null>(null)
+  const keybindings = state?.global_settings?.keybindings ?? {}
+  const error = state?.error?.code === 'keybinding' ? state.error : null
+
+  const handleRecordKey = useCallback(
+    async (command: string, e: React.KeyboardEvent) => {
+      e.preventDefault()
+      e.stopPropagation()
+      if (e.key === 'Escape') {
+        setRecording(null)
+        return
+      }
+      const shortcut = eventToShortcut(e)
+      if (!shortcut) return
+      setRecording(null)
+      await dispatch({ type: 'SetKeybinding', payload: { command, shortcut } })
+    },
+    [dispatch]
+  )
+
+  return (
+    <Paper variant="outlined" sx={{ p: 3 }}>
+      <Stack direction="row" alignItems="center" justifyContent="space-between" sx={{ mb: 2 }}>
+        <Typography variant="h6" fontWeight={600}>
+          Keyboard Shortcuts
+        </Typography>
+        <Button
+          variant="outlined"
+          size="small"
+          onClick={() => dispatch({ type: 'ResetKeybindings', payload: { command: null } })}
+        >
+          Reset All
+        </Button>
+      </Stack>
+
+      {error && (
+        <Typography variant="body2" color="error" sx={{ mb: 1.5 }}>
+          {error.message}
+        </Typography>
+      )}
+
+      <Stack spacing={1}>
+        {COMMAND_LABELS.map(({ command, label }) => {
+          const shortcut = keybindings[command] ?? ''
+          const isRecording = recording === command
+          return (
+            <Stack key={command} direction="row" alignItems="center" spacing={1}>
+              <Typography variant="body2" sx={{ flex: 1 }}>
+                {label}
+              </Typography>
+              <Box
+                data-shortcut-recorder
+                tabIndex={0}
+                onClick={() => setRecording(command)}
+                onBlur={() => isRecording && setRecording(null)}
+                onKeyDown={(e) => isRecording && handleRecordKey(command, e)}
+                sx={{
+                  minWidth: 140,
+                  px: 1,
+                  py: 0.5,
+                  border: 1,
+                  borderColor: isRecording ? 'primary.main' : 'divider',
+                  borderRadius: 1,
+                  cursor: 'pointer',
+                  fontFamily: 'monospace',
+                  fontSize: 13,
+                  textAlign: 'center',
+                  color: shortcut || isRecording ? 'text.primary' : 'text.disabled',
+                }}
+              >
+                {isRecording ? 'Press keys…' : shortcut ? formatShortcut(shortcut) : 'Unbound'}
+              </Box>
+              <Button
+                size="small"
+                disabled={!shortcut}
+                onClick={() => dispatch({ type: 'SetKeybinding', payload: { command, shortcut: '' } })}
+              >
+                Unbind
+              </Button>
+              <Button size="small" onClick={() => dispatch({ type: 'ResetKeybindings', payload: { command } })}>
+                Reset
+              </Button>
+            </Stack>
+          )
+        })}
+      </Stack>
+      <Typography variant="caption" color="text.secondary" sx={{ display: 'block', mt: 1.5 }}>
+        Click a shortcut and press the new key combination (Escape cancels)
+      </Typography>
+    </Paper>
+  )
+}
@@ -0,0 +1,147 @@
+import { useCallback, useState } from 'react'
+import { Button, Chip, IconButton, Paper, Stack, TextField, Tooltip, Typography } from '@mui/material'
+import { DeleteOutline } from '@mui/icons-material'
+import { useAppState } from '@/hooks/useAppState'
+import type { ProfileSecrets, SettingsProfile } from '@/types/state'
+
+const EMPTY_SECRETS: ProfileSecrets = { openai_api_key: null, github_token: null, gitlab_token: null }
+
+/**
+ * ProfilesCard - Named settings bundles (theme, model, provider, key references,
+ * default path, guardrails) switched in one action
+ */
+export function ProfilesCard() {
+  const { state, dispatch } = useAppState()
+  const settings = state?.global_settings
+  const profiles = state?.settings_profiles ?? []
+  const activeProfile = settings?.active_profile ?? null
+  const error = state?.error?.code === 'settings_profile' ? state.error : null
+
+  const [name, setName] = useState('')
+  const [secrets, setSecrets] = useState<ProfileSecrets>(EMPTY_SECRETS)
+
+  const setSecret = (key: keyof ProfileSecrets, value: string) =>
+    setSecrets((s) => ({ ...s, [key]: value.trim() || null }))
+
+  // Edit an existing profile's name and key references
+  const handleEdit = useCallback((profile: SettingsProfile) => {
+    setName(profile.name)
+    setSecrets(profile.secrets)
+  }, [])
+
+  // Capture the current settings under `name`
+  const handleSave = useCallback(async () => {
+    if (!settings || !name.trim()) return
+    const existing = profiles.find((p) => p.name === name.trim())
+    const profile: SettingsProfile = {
+      name: name.trim(),
+      theme: settings.theme,
+      model: settings.model,
+      provider: settings.provider,
+      openai: { ...settings.openai, api_key: null },
+      ollama: settings.ollama,
+      secrets,
+      default_project_path: settings.default_project_path,
+      guardrails: existing?.guardrails ?? settings.guardrails ?? null,
+    }
+    await dispatch({ type: 'SaveSettingsProfile', payload: { profile } })
+    setName('')
+    setSecrets(EMPTY_SECRETS)
+  }, [dispatch, name, profiles, secrets, settings])
+
+  return (
+    <Paper variant="outlined" sx={{ p: 3 }}>
+      <Typography variant="h6" fontWeight={600} sx={{ mb: 0.5 }}>
+        Profiles
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: 'block', mb: 2 }}>
+        Switch theme, model, provider, keys, default path and guardrails at once. Keys are stored as references
+        (env:NAME or file:PATH) and resolved on switch.
+      </Typography>
+
+      {error && (
+        <Typography variant="body2" color="error" sx={{ mb: 1.5 }}>
+          {error.message}
+        </Typography>
+      )}
+
+      <Stack spacing={1} sx={{ mb: 2 }}>
+        {profiles.length === 0 && (
+          <Typography variant="body2" color="text.secondary">
+            No profiles yet
+          </Typography>
+        )}
+        {profiles.map((profile) => (
+          <Stack key={profile.name} direction="row" alignItems="center" spacing={1}>
+            <Typography
+              variant="body2"
+              fontWeight={500}
+              sx={{ flex: 1, cursor: 'pointer' }}
+              onClick={() => handleEdit(profile)}
+            >
+              {profile.name}
+            </Typography>
+            {profile.name === activeProfile ? (
+              <Chip size="small" color="primary" label="Active" />
+            ) : (
+              <Button
+                size="small"
+                variant="outlined"
+                onClick={() => dispatch({ type: 'SwitchSettingsProfile', payload: { name: profile.name } })}
+              >
+                Switch
+              </Button>
+            )}
+            <Tooltip title="Delete profile">
+              <IconButton
+                size="small"
+                onClick={() => dispatch({ type: 'DeleteSettingsProfile', payload: { name: profile.name } })}
+              >
+                <DeleteOutline fontSize="small" />
+              </IconButton>
+            </Tooltip>
+          </Stack>
+        ))}
+      </Stack>
+
+      <Stack spacing={1.5}>
+        <TextField
+          size="small"
+          label="Profile name"
+          placeholder="acme"
+          value={name}
+          onChange={(e) => setName(e.target.value)}
+        />
+        <TextField
+          size="small"
+          label="OpenAI API key reference"
+          placeholder="env:ACME_OPENAI_KEY"
+          value={secrets.openai_api_key ?? ''}
+          onChange={(e) => setSecret('openai_api_key', e.target.value)}
+        />
+        <Stack direction="row" spacing={1.5}>
+          <TextField
+            size="small"
+            fullWidth
+            label="GitHub token reference"
+            placeholder="file:~/.secrets/acme-github"
+            value={secrets.github_token ?? ''}
+            onChange={(e) => setSecret('github_token', e.target.value)}
+          />
+          <TextField
+            size="small"
+            fullWidth
+            label="GitLab token reference"
+            value={secrets.gitlab_token ?? ''}
+            onChange={(e) => setSecret('gitlab_token', e.target.value)}
+          />
+        </Stack>
+        <Stack direction="row" justifyContent="flex-end">
+          <Button variant="contained" size="small" disabled={!name.trim()} onClick={handleSave}>
+            Save Current Settings as Profile
+          </Button>
+        </Stack>
+      </Stack>
+    </Paper>
+  )
+}
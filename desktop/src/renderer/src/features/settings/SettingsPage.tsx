@@ -1,8 +1,41 @@
-import { useCallback } from 'react'
-import { Box, Button, Paper, Stack, TextField, Typography } from '@mui/material'
+import { useCallback, useEffect, useState } from 'react'
+import {
+  Box,
+  Button,
+  Chip,
+  FormControlLabel,
+  MenuItem,
+  Paper,
+  Stack,
+  Switch,
+  TextField,
+  ToggleButton,
+  ToggleButtonGroup,
+  Typography
+} from '@mui/material'
 import { Brightness4, Brightness7, DesktopWindows, FolderOpen } from '@mui/icons-material'
 import { useSettingsState } from '@/hooks/useAppState'
-import type { Theme } from '@/types/state'
+import { DiagnosticsCard } from './DiagnosticsCard'
+import { GitHookCard } from './GitHookCard'
+import { KeybindingsCard } from './KeybindingsCard'
+import { ProfilesCard } from './ProfilesCard'
+import type {
+  DesktopNotificationEvent,
+  GitHostingSettings,
+  LlmProviderKind,
+  OllamaSettings,
+  OpenAiSettings,
+  Theme,
+} from '@/types/state'
+
+type OllamaModel = Awaited<ReturnType<typeof window.ollamaApi.listModels>>[number]
+
+const NOTIFICATION_EVENTS: { event: DesktopNotificationEvent; label: string }[] = [
+  { event: 'task_completed', label: 'Task completed' },
+  { event: 'claude_finished', label: 'Claude finished generating' },
+  { event: 'docker_service_crashed', label: 'Docker service crashed' },
+  { event: 'implementation_failed', label: 'Implementation failed' },
+]
 
 /**
  * Settings Page - Global and Worktree configuration.
@@ -28,6 +61,65 @@ export function SettingsPage() {
     await dispatch({ type: 'SetProjectPath', payload: { path: null } })
   }, [dispatch])
 
+  const [openai, setOpenai] = useState<OpenAiSettings>({ base_url: '', api_key: null, model: '' })
+  useEffect(() => {
+    if (settings?.openai) setOpenai(settings.openai)
+  }, [settings?.openai])
+
+  const handleProviderChange = useCallback(
+    async (provider: LlmProviderKind | null) => {
+      if (!provider) return
+      await dispatch({ type: 'SetLlmProvider', payload: { provider } })
+    },
+    [dispatch]
+  )
+
+  const handleSaveOpenai = useCallback(async () => {
+    await dispatch({ type: 'SetOpenAiSettings', payload: { settings: openai } })
+  }, [dispatch, openai])
+
+  const [ollama, setOllama] = useState<OllamaSettings>({ base_url: '', model: '', task_model: null })
+  const [ollamaModels, setOllamaModels] = useState<OllamaModel[] | null>(null)
+  const [ollamaError, setOllamaError] = useState<string | null>(null)
+  useEffect(() => {
+    if (settings?.ollama) setOllama(settings.ollama)
+  }, [settings?.ollama])
+
+  // Detect the server by listing its models
+  const detectOllama = useCallback(async () => {
+    try {
+      setOllamaModels(await window.ollamaApi.listModels())
+      setOllamaError(null)
+    } catch (e) {
+      setOllamaModels(null)
+      setOllamaError(e instanceof Error ? e.message : String(e))
+    }
+  }, [])
+
+  useEffect(() => {
+    detectOllama()
+  }, [detectOllama, settings?.ollama?.base_url])
+
+  const handleSaveOllama = useCallback(async () => {
+    await dispatch({ type: 'SetOllamaSettings', payload: { settings: ollama } })
+  }, [dispatch, ollama])
+
+  const [gitHosting, setGitHosting] = useState<GitHostingSettings>({ github_token: null, gitlab_token: null })
+  useEffect(() => {
+    if (settings?.git_hosting) setGitHosting(settings.git_hosting)
+  }, [settings?.git_hosting])
+
+  const handleSaveGitHosting = useCallback(async () => {
+    await dispatch({ type: 'SetGitHostingSettings', payload: { settings: gitHosting } })
+  }, [dispatch, gitHosting])
+
+  const handleNotificationToggle = useCallback(
+    async (event: DesktopNotificationEvent, enabled: boolean) => {
+      await dispatch({ type: 'SetDesktopNotification', payload: { event, enabled } })
+    },
+    [dispatch]
+  )
+
   if (isLoading || !settings) {
     return (
       <Stack alignItems="center" justifyContent="center" sx={{ height: '100%' }}>
@@ -51,6 +143,8 @@ export function SettingsPage() {
           </Typography>
         </Box>
 
+        <ProfilesCard />
+
         {/* Appearance Card */}
         <Paper variant="outlined" sx={{ p: 3 }}>
           <Typography variant="h6" fontWeight={600} sx={{ mb: 2 }}>
@@ -125,6 +219,192 @@ export function SettingsPage() {
           </Box>
         </Paper>
 
+        {/* AI Provider Card */}
+        <Paper variant="outlined" sx={{ p: 3 }}>
+          <Typography variant="h6" fontWeight={600} sx={{ mb: 2 }}>
+            AI Provider
+          </Typography>
+
+          <Typography variant="subtitle2">Default Provider</Typography>
+          <Typography variant="caption" color="text.secondary" sx={{ display: 'block', mb: 1.5 }}>
+            Runs chat and workflow prompts. Chat can switch providers per message.
+          </Typography>
+          <ToggleButtonGroup
+            size="small"
+            exclusive
+            value={settings.provider ?? 'claude_cli'}
+            onChange={(_, value) => handleProviderChange(value)}
+          >
+            <ToggleButton value="claude_cli">Claude CLI</ToggleButton>
+            <ToggleButton value="openai">OpenAI-compatible API</ToggleButton>
+            <ToggleButton value="ollama">Ollama</ToggleButton>
+          </ToggleButtonGroup>
+
+          <Typography variant="subtitle2" sx={{ mt: 3 }}>OpenAI-compatible API</Typography>
+          <Typography variant="caption" color="text.secondary" sx={{ display: 'block', mb: 1.5 }}>
+            Any server with a /chat/completions endpoint (OpenAI, vLLM, LM Studio, OpenRouter...)
+          </Typography>
+          <Stack spacing={2}>
+            <TextField
+              label="Base URL"
+              size="small"
+              placeholder="https://api.openai.com/v1"
+              value={openai.base_url}
+              onChange={(e) => setOpenai((prev) => ({ ...prev, base_url: e.target.value }))}
+            />
+            <TextField
+              label="API Key"
+              size="small"
+              type="password"
+              placeholder="Unchanged"
+              helperText="Kept for this session only; set OPENAI_API_KEY to keep it across restarts"
+              value={openai.api_key ?? ''}
+              onChange={(e) => setOpenai((prev) => ({ ...prev, api_key: e.target.value }))}
+            />
+            <TextField
+              label="Model"
+              size="small"
+              placeholder="gpt-4o-mini"
+              value={openai.model}
+              onChange={(e) => setOpenai((prev) => ({ ...prev, model: e.target.value }))}
+            />
+            <Box>
+              <Button variant="outlined" onClick={handleSaveOpenai}>
+                Save
+              </Button>
+            </Box>
+          </Stack>
+
+          <Stack direction="row" spacing={1} alignItems="center" sx={{ mt: 3 }}>
+            <Typography variant="subtitle2">Ollama</Typography>
+            {ollamaModels ? (
+              <Chip size="small" color="success" label={`Running · ${ollamaModels.length} models`} />
+            ) : (
+              <Chip size="small" label="Not detected" />
+            )}
+          </Stack>
+          <Typography variant="caption" color="text.secondary" sx={{ display: 'block', mb: 1.5 }}>
+            {ollamaError ?? 'Local models for chat, and for cheap internal tasks (naming changes) to reduce Claude usage'}
+          </Typography>
+          <Stack spacing={2}>
+            <Stack direction="row" spacing={2}>
+              <TextField
+                label="Server URL"
+                size="small"
+                fullWidth
+                placeholder="http://localhost:11434"
+                value={ollama.base_url}
+                onChange={(e) => setOllama((prev) => ({ ...prev, base_url: e.target.value }))}
+              />
+              <Button variant="outlined" onClick={detectOllama}>
+                Detect
+              </Button>
+            </Stack>
+            <TextField
+              select
+              label="Chat Model"
+              size="small"
+              value={ollama.model}
+              onChange={(e) => setOllama((prev) => ({ ...prev, model: e.target.value }))}
+              disabled={!ollamaModels}
+            >
+              {(ollamaModels ?? []).map((model) => (
+                <MenuItem key={model.name} value={model.name}>
+                  {model.name} {model.parameterSize && `(${model.parameterSize})`}
+                </MenuItem>
+              ))}
+            </TextField>
+            <TextField
+              select
+              label="Internal Task Model"
+              size="small"
+              value={ollama.task_model ?? ''}
+              onChange={(e) => setOllama((prev) => ({ ...prev, task_model: e.target.value || null }))}
+              disabled={!ollamaModels}
+              helperText="Names new changes; None keeps the built-in naming"
+            >
+              <MenuItem value="">None</MenuItem>
+              {(ollamaModels ?? []).map((model) => (
+                <MenuItem key={model.name} value={model.name}>
+                  {model.name} {model.parameterSize && `(${model.parameterSize})`}
+                </MenuItem>
+              ))}
+            </TextField>
+            <Box>
+              <Button variant="outlined" onClick={handleSaveOllama}>
+                Save
+              </Button>
+            </Box>
+          </Stack>
+        </Paper>
+
+        {/* Git Hosting Card */}
+        <Paper variant="outlined" sx={{ p: 3 }}>
+          <Typography variant="h6" fontWeight={600} sx={{ mb: 2 }}>
+            Git Hosting
+          </Typography>
+          <Typography variant="caption" color="text.secondary" sx={{ display: 'block', mb: 1.5 }}>
+            Personal access tokens used to open pull requests for changes
+          </Typography>
+          <Stack spacing={2}>
+            <TextField
+              label="GitHub Token"
+              size="small"
+              type="password"
+              placeholder="Needs pull request write access"
+              value={gitHosting.github_token ?? ''}
+              onChange={(e) => setGitHosting((prev) => ({ ...prev, github_token: e.target.value || null }))}
+            />
+            <TextField
+              label="GitLab Token"
+              size="small"
+              type="password"
+              placeholder="Needs the api scope"
+              value={gitHosting.gitlab_token ?? ''}
+              onChange={(e) => setGitHosting((prev) => ({ ...prev, gitlab_token: e.target.value || null }))}
+            />
+            <Box>
+              <Button variant="outlined" onClick={handleSaveGitHosting}>
+                Save
+              </Button>
+            </Box>
+          </Stack>
+        </Paper>
+
+        {/* Notifications Card */}
+        <Paper variant="outlined" sx={{ p: 3 }}>
+          <Typography variant="h6" fontWeight={600} sx={{ mb: 2 }}>
+            Notifications
+          </Typography>
+
+          <Typography variant="subtitle2">Desktop Notifications</Typography>
+          <Typography variant="caption" color="text.secondary" sx={{ display: 'block', mb: 1 }}>
+            Shown by the OS when rstn is not focused
+          </Typography>
+
+          <Stack>
+            {NOTIFICATION_EVENTS.map(({ event, label }) => (
+              <FormControlLabel
+                key={event}
+                control={
+                  <Switch
+                    size="small"
+                    checked={settings.desktop_notifications?.[event] ?? true}
+                    onChange={(e) => handleNotificationToggle(event, e.target.checked)}
+                  />
+                }
+                label={<Typography variant="body2">{label}</Typography>}
+              />
+            ))}
+          </Stack>
+        </Paper>
+
+        <KeybindingsCard />
+
+        <GitHookCard />
+
+        <DiagnosticsCard />
+
         {/* About Card */}
         <Paper variant="outlined" sx={{ p: 3 }}>
           <Typography variant="h6" fontWeight={600} sx={{ mb: 2 }}>
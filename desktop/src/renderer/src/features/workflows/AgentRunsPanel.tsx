@@ -0,0 +1,161 @@
+import {
+  Close as CancelIcon,
+  DeleteSweep as ClearIcon,
+  SmartToy as AgentIcon,
+} from '@mui/icons-material'
+import { Alert, Box, Button, Chip, IconButton, Paper, Stack, TextField, Tooltip, Typography } from '@mui/material'
+import { WorkflowHeader } from '@/components/shared/WorkflowHeader'
+import { EmptyState } from '@/components/shared/EmptyState'
+import { useAppState } from '@/hooks/useAppState'
+import type { AgentRun, AgentRunStatus } from '@/types/state'
+
+const STATUS_COLORS: Record<AgentRunStatus, 'default' | 'info' | 'success' | 'error' | 'warning'> = {
+  queued: 'default',
+  running: 'info',
+  awaiting_approval: 'warning',
+  awaiting_review: 'success',
+  failed: 'error',
+  cancelled: 'warning',
+}
+
+const STATUS_LABELS: Record<AgentRunStatus, string> = {
+  queued: 'Queued',
+  running: 'Running',
+  awaiting_approval: 'Needs approval',
+  awaiting_review: 'Awaiting review',
+  failed: 'Failed',
+  cancelled: 'Cancelled',
+}
+
+interface AgentRunRowProps {
+  run: AgentRun
+  onCancel: () => void
+  onResolve: (requestId: string, approve: boolean) => void
+}
+
+function AgentRunRow({ run, onCancel, onResolve }: AgentRunRowProps) {
+  return (
+    <Paper variant="outlined" sx={{ p: 1.5, borderColor: 'outlineVariant' }}>
+      <Stack direction="row" alignItems="center" spacing={1}>
+        <Chip
+          label={STATUS_LABELS[run.status]}
+          size="small"
+          color={STATUS_COLORS[run.status]}
+          sx={{ height: 18, fontSize: '0.65rem' }}
+        />
+        <Typography variant="body2" fontWeight={600} noWrap sx={{ flex: 1, minWidth: 0 }} title={run.worktree_path}>
+          {run.change_name}
+          <Typography component="span" variant="caption" color="text.secondary" sx={{ ml: 1 }}>
+            {run.branch || run.worktree_path}
+          </Typography>
+        </Typography>
+        {run.status !== 'queued' && (
+          <Typography variant="caption" color="text.secondary">
+            {run.output_chars.toLocaleString()} chars
+          </Typography>
+        )}
+        {(run.status === 'queued' || run.status === 'running' || run.status === 'awaiting_approval') && (
+          <Tooltip title={run.status === 'queued' ? 'Remove from queue' : 'Stop run'}>
+            <IconButton size="small" onClick={onCancel}>
+              <CancelIcon fontSize="small" />
+            </IconButton>
+          </Tooltip>
+        )}
+      </Stack>
+      {run.approval && (
+        <Alert
+          severity="warning"
+          sx={{ mt: 1, py: 0 }}
+          action={
+            <Stack direction="row" spacing={0.5}>
+              <Button size="small" color="inherit" onClick={() => onResolve(run.approval!.request_id, false)}>
+                Deny
+              </Button>
+              <Button size="small" variant="contained" color="warning" onClick={() => onResolve(run.approval!.request_id, true)}>
+                Allow
+              </Button>
+            </Stack>
+          }
+        >
+          <Typography variant="caption" sx={{ display: 'block' }}>
+            {run.approval.reason}
+          </Typography>
+          <Typography variant="caption" sx={{ fontFamily: 'monospace' }}>
+            {run.approval.tool_name}: {run.approval.input_summary}
+          </Typography>
+        </Alert>
+      )}
+      {run.error && (
+        <Typography variant="caption" color="error" sx={{ display: 'block', mt: 0.5 }}>
+          {run.error}
+        </Typography>
+      )}
+    </Paper>
+  )
+}
+
+/**
+ * AgentRunsPanel - Claude implementation runs queued across worktrees,
+ * at most `max_concurrent` at a time and one per worktree. Runs that hit
+ * a guardrail (.rstn/guardrails.toml) wait here for approval.
+ */
+export function AgentRunsPanel() {
+  const { state, dispatch } = useAppState()
+  const agentRuns = state?.agent_runs
+  const runs = agentRuns?.runs ?? []
+  const running = runs.filter((run) => run.status === 'running' || run.status === 'awaiting_approval').length
+  const hasFinished = runs.some((run) => !['queued', 'running', 'awaiting_approval'].includes(run.status))
+
+  return (
+    <Box sx={{ display: 'flex', flexDirection: 'column', height: '100%' }}>
+      <WorkflowHeader
+        title="Agent Runs"
+        subtitle={`${running} running · ${runs.filter((run) => run.status === 'queued').length} queued`}
+        icon={<AgentIcon />}
+      >
+        <TextField
+          type="number"
+          size="small"
+          label="Max parallel"
+          value={agentRuns?.max_concurrent ?? 2}
+          onChange={(e) => {
+            const value = Number(e.target.value)
+            if (value >= 1) {
+              dispatch({ type: 'SetAgentMaxConcurrent', payload: { max_concurrent: value } })
+            }
+          }}
+          inputProps={{ min: 1, max: 8 }}
+          sx={{ width: 110 }}
+        />
+        <Button
+          size="small"
+          startIcon={<ClearIcon />}
+          disabled={!hasFinished}
+          onClick={() => dispatch({ type: 'ClearFinishedAgentRuns' })}
+        >
+          Clear finished
+        </Button>
+      </WorkflowHeader>
+
+      {runs.length === 0 ? (
+        <EmptyState
+          title="No Agent Runs"
+          description="Queue an agent run from a change with an approved plan"
+        />
+      ) : (
+        <Stack spacing={1} sx={{ flex: 1, overflow: 'auto', p: 3 }}>
+          {runs.map((run) => (
+            <AgentRunRow
+              key={run.id}
+              run={run}
+              onCancel={() => dispatch({ type: 'CancelAgentRun', payload: { run_id: run.id } })}
+              onResolve={(requestId, approve) =>
+                dispatch({ type: 'ResolveAgentRunApproval', payload: { run_id: run.id, request_id: requestId, approve } })
+              }
+            />
+          ))}
+        </Stack>
+      )}
+    </Box>
+  )
+}
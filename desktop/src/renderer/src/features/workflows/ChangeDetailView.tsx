@@ -12,7 +12,11 @@ import {
   ThumbUpOutlined as ThumbsUpIcon,
   ThumbDownOutlined as ThumbsDownIcon,
   ExpandMore as ChevronDownIcon,
-  Code as FileCodeIcon
+  Code as FileCodeIcon,
+  CallSplit as BranchIcon,
+  MergeType as PullRequestIcon,
+  Inventory2Outlined as BundleIcon,
+  RateReviewOutlined as FeedbackIcon
 } from '@mui/icons-material'
 import {
   Button,
@@ -26,11 +30,16 @@ import {
   Divider,
   IconButton,
   Collapse,
+  Tooltip,
+  FormControlLabel,
+  Switch,
+  Alert,
   alpha
 } from '@mui/material'
 import { WorkflowHeader } from '@/components/shared/WorkflowHeader'
 import { useAppState } from '@/hooks/useAppState'
 import { ContextFilesInput } from './ContextFilesInput'
+import { ComplianceCard, approvalBlocker } from './ComplianceCard'
 import type { Change, ReviewSession, ReviewStatus, ChangeStatus } from '@/types/state'
 import { useState } from 'react'
 
@@ -98,10 +107,12 @@ function InlineReviewControls({ session, onApprove, onReject }: InlineReviewCont
   }
 
   const unresolvedComments = session.comments.filter((c) => !c.resolved).length
+  const blocker = approvalBlocker(session)
 
   return (
-    <Paper elevation={0} sx={{ borderTop: 1, borderColor: 'outlineVariant', bgcolor: 'action.hover', p: 2 }}>
-      <Stack direction="row" alignItems="center" justifyContent="space-between">
+    <Paper elevation={0} sx={{ borderTop: 1, borderColor: 'outlineVariant', bgcolor: 'action.hover' }}>
+      <ComplianceCard session={session} />
+      <Stack direction="row" alignItems="center" justifyContent="space-between" sx={{ p: 2 }}>
         <Stack direction="row" alignItems="center" spacing={1.5}>
           <ClipboardCheckIcon fontSize="small" color="primary" />
           <Typography variant="body2" fontWeight={600}>Review required</Typography>
@@ -126,16 +137,21 @@ function InlineReviewControls({ session, onApprove, onReject }: InlineReviewCont
           >
             Reject
           </Button>
-          <Button 
-            size="small" 
-            variant="contained" 
-            color="success" 
-            onClick={onApprove} 
-            startIcon={<ThumbsUpIcon />}
-            sx={{ borderRadius: 1.5 }}
-          >
-            Approve
-          </Button>
+          <Tooltip title={blocker ?? ''}>
+            <span>
+              <Button 
+                size="small" 
+                variant="contained" 
+                color="success" 
+                onClick={onApprove} 
+                disabled={blocker !== null}
+                startIcon={<ThumbsUpIcon />}
+                sx={{ borderRadius: 1.5 }}
+              >
+                Approve
+              </Button>
+            </span>
+          </Tooltip>
         </Stack>
       </Stack>
     </Paper>
@@ -186,6 +202,71 @@ export function ChangeDetailView({ change }: ChangeDetailViewProps) {
     dispatch({ type: 'ExecutePlan', payload: { change_id: change.id } })
   }
 
+  const handleRollback = () => {
+    dispatch({ type: 'RollbackImplementation', payload: { change_id: change.id } })
+  }
+
+  const handleRunTests = () => {
+    dispatch({ type: 'RunChangeTests', payload: { change_id: change.id } })
+  }
+
+  const handleOverrideTestGate = () => {
+    dispatch({ type: 'OverrideTestGate', payload: { change_id: change.id } })
+  }
+
+  const handleQueueAgentRun = () => {
+    if (worktree) {
+      dispatch({ type: 'QueueAgentRun', payload: { worktree_path: worktree.path, change_id: change.id } })
+    }
+  }
+
+  const handleOpenPullRequest = () => {
+    dispatch({ type: 'OpenChangePullRequest', payload: { change_id: change.id } })
+  }
+
+  const notifyError = (prefix: string, error: unknown) =>
+    dispatch({
+      type: 'AddNotification',
+      payload: { message: `${prefix}: ${error instanceof Error ? error.message : String(error)}`, notification_type: 'error' },
+    })
+
+  const handleExportBundle = async () => {
+    try {
+      const path = await window.changesApi.exportBundle(change.id, change.name)
+      if (!path) return
+      await dispatch({
+        type: 'AddNotification',
+        payload: { message: `Exported "${change.name}" for review to ${path}`, notification_type: 'success' },
+      })
+    } catch (error) {
+      await notifyError('Export failed', error)
+    }
+  }
+
+  const handleImportFeedback = async () => {
+    try {
+      const result = await window.changesApi.importFeedback()
+      if (!result) return
+      await dispatch({
+        type: 'AddNotification',
+        payload: {
+          message: `Imported feedback for "${result.changeName}": ${result.added} new, ${result.updated} updated comments`,
+          notification_type: 'success',
+        },
+      })
+    } catch (error) {
+      await notifyError('Import failed', error)
+    }
+  }
+
+  const handleResolveStatusConflict = (status: ChangeStatus) => {
+    dispatch({ type: 'ResolveChangeStatusConflict', payload: { change_id: change.id, status } })
+  }
+
+  const handleResolveApproval = (requestId: string, approve: boolean) => {
+    dispatch({ type: 'ResolveImplementationApproval', payload: { change_id: change.id, request_id: requestId, approve } })
+  }
+
   // Review action handlers
   const handleApproveProposalReview = () => {
     if (proposalReviewSession) {
@@ -222,6 +303,15 @@ export function ChangeDetailView({ change }: ChangeDetailViewProps) {
   const canCancel = !['done', 'archived', 'cancelled', 'implementing'].includes(change.status)
   const canSyncAndArchive = change.status === 'done'
   const isArchived = change.status === 'archived'
+  const canRollback = !!change.snapshot && ['done', 'failed'].includes(change.status)
+  const testResults = change.test_results
+  const testsGreen = !!testResults && testResults.success && testResults.failed === 0
+  const canRunTests = ['done', 'failed'].includes(change.status)
+  const canOverrideTests = !!testResults && !testsGreen && !testResults.overridden
+  const coverage = testResults?.coverage
+  const coverageDelta = coverage?.previous_percent !== undefined ? coverage.percent - coverage.previous_percent : undefined
+  const coverageDropped = coverageDelta !== undefined && coverageDelta < 0
+  const canOpenPullRequest = !!change.branch && !change.pull_request_url && !['cancelled', 'archived'].includes(change.status)
 
   const STATUS_COLORS: Record<string, 'info' | 'warning' | 'secondary' | 'success' | 'error' | 'default'> = {
     proposed: 'info',
@@ -245,12 +335,43 @@ export function ChangeDetailView({ change }: ChangeDetailViewProps) {
       >
         {/* Review Status Badges */}
         <Stack direction="row" spacing={1}>
+          {change.branch && (
+            <Chip
+              icon={<BranchIcon sx={{ fontSize: 14 }} />}
+              label={change.branch}
+              size="small"
+              variant="outlined"
+              sx={{ height: 22, fontSize: '0.65rem', fontFamily: 'monospace' }}
+            />
+          )}
           {proposalReviewSession && <ReviewStatusBadge session={proposalReviewSession} type="proposal" />}
           {planReviewSession && <ReviewStatusBadge session={planReviewSession} type="plan" />}
         </Stack>
       </WorkflowHeader>
 
       <Box sx={{ flex: 1, overflow: 'hidden', display: 'flex', flexDirection: 'column', p: 3 }}>
+        {/* Shared status conflict (status.json) */}
+        {change.status_conflict && (
+          <Alert
+            severity="warning"
+            sx={{ mb: 2 }}
+            action={
+              <Stack direction="row" spacing={1}>
+                <Button size="small" color="inherit" onClick={() => handleResolveStatusConflict(change.status_conflict!.ours)}>
+                  Keep mine
+                </Button>
+                <Button size="small" color="inherit" onClick={() => handleResolveStatusConflict(change.status_conflict!.theirs)}>
+                  Take theirs
+                </Button>
+              </Stack>
+            }
+          >
+            Status conflict: this machine has <strong>{change.status_conflict.ours}</strong>, status.json has{' '}
+            <strong>{change.status_conflict.theirs}</strong>
+            {change.status_conflict.theirs_by && ` (set by ${change.status_conflict.theirs_by})`}
+          </Alert>
+        )}
+
         {/* Context Files Section */}
         {worktree?.path && (
           <Box sx={{ mb: 2 }}>
@@ -418,12 +539,35 @@ export function ChangeDetailView({ change }: ChangeDetailViewProps) {
 
             {activeTab === 'implementation' && (
               <Box sx={{ flex: 1, display: 'flex', flexDirection: 'column' }}>
-                {isImplementing && change.streaming_output ? (
+                {isImplementing && (change.streaming_output || change.implementation_approval) ? (
                   <Box sx={{ p: 3 }}>
                     <Stack direction="row" spacing={1} sx={{ mb: 2, color: 'info.main' }}>
                       <RocketIcon fontSize="small" sx={{ animation: 'pulse 1.5s infinite' }} />
                       <Typography variant="caption" fontWeight={700}>Implementing...</Typography>
                     </Stack>
+                    {change.implementation_approval && (
+                      <Alert
+                        severity="warning"
+                        sx={{ mb: 2, py: 0 }}
+                        action={
+                          <Stack direction="row" spacing={0.5}>
+                            <Button size="small" color="inherit" onClick={() => handleResolveApproval(change.implementation_approval!.request_id, false)}>
+                              Deny
+                            </Button>
+                            <Button size="small" variant="contained" color="warning" onClick={() => handleResolveApproval(change.implementation_approval!.request_id, true)}>
+                              Allow
+                            </Button>
+                          </Stack>
+                        }
+                      >
+                        <Typography variant="caption" sx={{ display: 'block' }}>
+                          {change.implementation_approval.reason}
+                        </Typography>
+                        <Typography variant="caption" sx={{ fontFamily: 'monospace' }}>
+                          {change.implementation_approval.tool_name}: {change.implementation_approval.input_summary}
+                        </Typography>
+                      </Alert>
+                    )}
                     <Typography component="pre" variant="caption" sx={{ fontFamily: 'monospace', whiteSpace: 'pre-wrap' }}>
                       {change.streaming_output}
                     </Typography>
@@ -478,9 +622,112 @@ export function ChangeDetailView({ change }: ChangeDetailViewProps) {
               Execute Plan
             </Button>
           )}
+          {canExecute && (
+            <Tooltip title="Run in the background; runs in other worktrees proceed in parallel">
+              <Button variant="outlined" color="primary" onClick={handleQueueAgentRun} startIcon={<ClockIcon />} sx={{ borderRadius: 2 }}>
+                Queue Agent Run
+              </Button>
+            </Tooltip>
+          )}
           {isImplementing && (
             <Chip icon={<RocketIcon sx={{ animation: 'pulse 1.5s infinite' }} />} label="Implementing..." color="warning" variant="filled" sx={{ borderRadius: 1.5 }} />
           )}
+          {change.snapshot && (
+            <Tooltip
+              title={
+                change.snapshot.restored_at
+                  ? `Restored ${new Date(change.snapshot.restored_at).toLocaleString()}`
+                  : `Taken ${new Date(change.snapshot.created_at).toLocaleString()} on ${change.snapshot.branch ?? 'a detached HEAD'}`
+              }
+            >
+              <Chip
+                icon={<ClockIcon />}
+                label={`Snapshot ${change.snapshot.commit.slice(0, 7)}${change.snapshot.restored_at ? ' (restored)' : ''}`}
+                variant="outlined"
+                sx={{ borderRadius: 1.5 }}
+              />
+            </Tooltip>
+          )}
+          {canRollback && (
+            <Tooltip title="Restore the worktree to the snapshot taken before implementation">
+              <Button variant="outlined" color="warning" onClick={handleRollback} startIcon={<RefreshIcon />} sx={{ borderRadius: 2 }}>
+                Roll Back
+              </Button>
+            </Tooltip>
+          )}
+          {change.hook_results?.map((hook) => (
+            <Tooltip key={hook.name} title={hook.error ?? `${hook.command} · ${(hook.duration_ms / 1000).toFixed(1)}s`}>
+              <Chip
+                icon={hook.success ? <CheckIcon /> : <XIcon />}
+                label={hook.name}
+                color={hook.success ? 'success' : 'error'}
+                variant="outlined"
+                size="small"
+                sx={{ borderRadius: 1.5, alignSelf: 'center' }}
+              />
+            </Tooltip>
+          ))}
+          {testResults && (
+            <Tooltip
+              title={
+                testResults.failing_tests?.length
+                  ? `Failing: ${testResults.failing_tests.join(', ')}`
+                  : `${testResults.command} · ${new Date(testResults.ran_at).toLocaleString()}`
+              }
+            >
+              <Chip
+                icon={testsGreen ? <CheckIcon /> : <XIcon />}
+                label={`${testResults.passed} passed · ${testResults.failed} failed${testResults.ignored ? ` · ${testResults.ignored} skipped` : ''} (${(testResults.duration_ms / 1000).toFixed(1)}s)${testResults.overridden ? ' · overridden' : ''}`}
+                color={testsGreen ? 'success' : testResults.overridden ? 'warning' : 'error'}
+                variant="outlined"
+                sx={{ borderRadius: 1.5 }}
+              />
+            </Tooltip>
+          )}
+          {coverage && (
+            <Tooltip
+              title={
+                coverage.regressions?.length
+                  ? `Coverage dropped in ${coverage.regressions
+                      .map((r) => `${r.path} (${r.before.toFixed(1)}% → ${r.after.toFixed(1)}%)`)
+                      .join(', ')}`
+                  : `${coverage.lines_covered.toLocaleString()} of ${coverage.lines_total.toLocaleString()} lines covered`
+              }
+            >
+              <Chip
+                label={`Coverage ${coverage.percent.toFixed(1)}%${
+                  coverageDelta !== undefined ? ` (${coverageDelta >= 0 ? '+' : ''}${coverageDelta.toFixed(1)})` : ''
+                }`}
+                color={coverageDropped || coverage.regressions?.length ? 'warning' : 'default'}
+                variant="outlined"
+                sx={{ borderRadius: 1.5 }}
+              />
+            </Tooltip>
+          )}
+          {canRunTests && (
+            <Button variant="outlined" onClick={handleRunTests} startIcon={<PlayIcon />} sx={{ borderRadius: 2 }}>
+              Run Tests
+            </Button>
+          )}
+          {canRunTests && (
+            <FormControlLabel
+              control={
+                <Switch
+                  size="small"
+                  checked={!!activeProject?.collect_coverage}
+                  onChange={(e) => dispatch({ type: 'SetCollectCoverage', payload: { enabled: e.target.checked } })}
+                />
+              }
+              label={<Typography variant="caption">Coverage</Typography>}
+            />
+          )}
+          {canOverrideTests && (
+            <Tooltip title="Allow approving reviews of this change although its tests fail">
+              <Button variant="text" color="warning" onClick={handleOverrideTestGate} sx={{ borderRadius: 2 }}>
+                Override Test Gate
+              </Button>
+            </Tooltip>
+          )}
           {canSyncAndArchive && (
             <>
               <Button variant="outlined" color="primary" onClick={handleSyncContext} startIcon={<RefreshIcon />} sx={{ borderRadius: 2 }}>
@@ -494,6 +741,36 @@ export function ChangeDetailView({ change }: ChangeDetailViewProps) {
           {isArchived && (
             <Chip icon={<ArchiveIcon />} label="Archived" color="default" sx={{ borderRadius: 1.5 }} />
           )}
+          {canOpenPullRequest && (
+            <Button variant="outlined" onClick={handleOpenPullRequest} startIcon={<PullRequestIcon />} sx={{ borderRadius: 2 }}>
+              Open Pull Request
+            </Button>
+          )}
+          {change.pull_request_url && (
+            <Button
+              variant="text"
+              component="a"
+              href={change.pull_request_url}
+              target="_blank"
+              rel="noreferrer"
+              startIcon={<PullRequestIcon />}
+              sx={{ borderRadius: 2 }}
+            >
+              View Pull Request
+            </Button>
+          )}
+          {hasProposal && (
+            <>
+              <Tooltip title="Zip intent, proposal, plan, diff, test results and review comments for reviewers without rstn">
+                <Button variant="text" onClick={handleExportBundle} startIcon={<BundleIcon />} sx={{ borderRadius: 2 }}>
+                  Export for Review
+                </Button>
+              </Tooltip>
+              <Button variant="text" onClick={handleImportFeedback} startIcon={<FeedbackIcon />} sx={{ borderRadius: 2 }}>
+                Import Feedback
+              </Button>
+            </>
+          )}
           <Box sx={{ ml: 'auto' }}>
             {canCancel && (
               <Button variant="text" color="error" onClick={handleCancelChange} startIcon={<XIcon />}>
@@ -67,8 +67,13 @@ export function ChangeManagementPanel() {
     dispatch({ type: 'RefreshChanges' })
   }, [dispatch])
 
-  const handleCreateChange = async (intent: string) => {
-    dispatch({ type: 'CreateChange', payload: { intent } })
+  const handleCreateChange = async (intent: string, createBranch: boolean) => {
+    dispatch({ type: 'CreateChange', payload: { intent, create_branch: createBranch } })
+    setIsDialogOpen(false)
+  }
+
+  const handleCreateChangeFromIssue = async (issueNumber: number, createBranch: boolean) => {
+    dispatch({ type: 'CreateChangeFromIssue', payload: { issue_number: issueNumber, create_branch: createBranch } })
     setIsDialogOpen(false)
   }
 
@@ -144,6 +149,8 @@ export function ChangeManagementPanel() {
           open={isDialogOpen}
           onOpenChange={setIsDialogOpen}
           onSubmit={handleCreateChange}
+          onSubmitIssue={handleCreateChangeFromIssue}
+          repoPath={activeWorktree?.path}
         />
       </Stack>
     )
@@ -223,6 +230,8 @@ export function ChangeManagementPanel() {
         open={isDialogOpen}
         onOpenChange={setIsDialogOpen}
         onSubmit={handleCreateChange}
+        onSubmitIssue={handleCreateChangeFromIssue}
+        repoPath={activeWorktree?.path}
       />
     </Stack>
   )
@@ -0,0 +1,137 @@
+import { Checklist as ChecklistIcon, Refresh as RefreshIcon } from '@mui/icons-material'
+import {
+  Box,
+  Button,
+  Checkbox,
+  Chip,
+  FormControlLabel,
+  IconButton,
+  LinearProgress,
+  Paper,
+  Stack,
+  Switch,
+  Tooltip,
+  Typography,
+} from '@mui/material'
+import type { Action, ChecklistKind, SpecChecklist } from '@/types/state'
+
+const KIND_LABELS: Record<ChecklistKind, string> = {
+  security: 'Security',
+  a11y: 'Accessibility',
+  performance: 'Performance',
+}
+
+const KINDS: ChecklistKind[] = ['security', 'a11y', 'performance']
+
+interface ChecklistsCardProps {
+  checklists: SpecChecklist[]
+  /** Review approval is blocked while items are unchecked */
+  required: boolean
+  /** The spec has been written, so checklists can be generated */
+  canGenerate: boolean
+  dispatch: (action: Action) => Promise<void>
+}
+
+/**
+ * ChecklistsCard - Security, accessibility and performance checklists
+ * generated from the spec; checking an item rewrites checklist-<kind>.md
+ */
+export function ChecklistsCard({ checklists, required, canGenerate, dispatch }: ChecklistsCardProps) {
+  const isGenerating = checklists.some((c) => c.status === 'generating')
+
+  return (
+    <Paper variant="outlined" sx={{ p: 2, borderColor: 'outlineVariant' }}>
+      <Stack direction="row" alignItems="center" spacing={1}>
+        <ChecklistIcon fontSize="small" color="primary" />
+        <Typography variant="subtitle2" fontWeight={600} sx={{ flex: 1 }}>
+          Checklists
+        </Typography>
+        <FormControlLabel
+          control={
+            <Switch
+              size="small"
+              checked={required}
+              onChange={(e) => dispatch({ type: 'SetRequireChecklists', payload: { required: e.target.checked } })}
+            />
+          }
+          label={<Typography variant="caption">Require for review approval</Typography>}
+        />
+        <Tooltip title="Reload checklists">
+          <IconButton size="small" onClick={() => dispatch({ type: 'LoadChecklists' })}>
+            <RefreshIcon fontSize="small" />
+          </IconButton>
+        </Tooltip>
+      </Stack>
+
+      <Stack direction="row" spacing={1} sx={{ my: 1.5 }}>
+        {KINDS.map((kind) => {
+          const exists = checklists.some((c) => c.kind === kind && c.status !== 'failed')
+          return (
+            <Button
+              key={kind}
+              size="small"
+              variant="outlined"
+              disabled={!canGenerate || isGenerating}
+              onClick={() => dispatch({ type: 'GenerateChecklist', payload: { kind } })}
+            >
+              {exists ? 'Regenerate' : 'Generate'} {KIND_LABELS[kind]}
+            </Button>
+          )
+        })}
+      </Stack>
+
+      {checklists.map((checklist) => {
+        const checked = checklist.items.filter((item) => item.checked).length
+        return (
+          <Box key={checklist.kind} sx={{ mb: 1.5 }}>
+            <Stack direction="row" alignItems="center" spacing={1}>
+              <Typography variant="caption" fontWeight={600} color="text.secondary" sx={{ flex: 1 }}>
+                {KIND_LABELS[checklist.kind]} ({checked}/{checklist.items.length}) · {checklist.path}
+              </Typography>
+              {checklist.status !== 'ready' && (
+                <Chip
+                  label={checklist.status}
+                  size="small"
+                  color={checklist.status === 'failed' ? 'error' : 'warning'}
+                  sx={{ height: 16, fontSize: '0.6rem' }}
+                />
+              )}
+            </Stack>
+            {checklist.status === 'generating' && <LinearProgress sx={{ my: 0.5 }} />}
+            {checklist.error && (
+              <Typography variant="caption" color="error" sx={{ display: 'block' }}>
+                {checklist.error}
+              </Typography>
+            )}
+            {checklist.items.map((item) => (
+              <Stack key={item.id} direction="row" alignItems="center" spacing={0.5}>
+                <Checkbox
+                  size="small"
+                  checked={item.checked}
+                  onChange={(e) =>
+                    dispatch({
+                      type: 'ToggleChecklistItem',
+                      payload: { kind: checklist.kind, id: item.id, checked: e.target.checked },
+                    })
+                  }
+                  sx={{ p: 0.5 }}
+                />
+                <Typography variant="caption" sx={{ fontFamily: 'monospace', color: 'text.secondary' }}>
+                  {item.id}
+                </Typography>
+                <Typography
+                  variant="body2"
+                  sx={{ flex: 1, minWidth: 0, opacity: item.checked ? 0.6 : 1 }}
+                  noWrap
+                  title={item.section ? `${item.section}: ${item.text}` : item.text}
+                >
+                  {item.text}
+                </Typography>
+              </Stack>
+            ))}
+          </Box>
+        )
+      })}
+    </Paper>
+  )
+}
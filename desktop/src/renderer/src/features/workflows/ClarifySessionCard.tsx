@@ -0,0 +1,114 @@
+import { useState } from 'react'
+import { Close as CloseIcon, QuestionAnswer as ClarifyIcon } from '@mui/icons-material'
+import { Box, Button, Chip, IconButton, LinearProgress, Paper, Stack, TextField, Tooltip, Typography } from '@mui/material'
+import type { Action, ClarifyCategory, ClarifySession, CoverageStatus } from '@/types/state'
+
+const CATEGORY_LABELS: Record<ClarifyCategory, string> = {
+  functional_scope: 'Functional scope',
+  data_model: 'Data model',
+  ux_flow: 'UX flow',
+  non_functional: 'Non-functional',
+  integrations: 'Integrations',
+  edge_cases: 'Edge cases',
+  constraints: 'Constraints',
+  completion_signals: 'Completion signals',
+  placeholders: 'Open markers',
+}
+
+const COVERAGE_COLORS: Record<CoverageStatus, 'success' | 'warning' | 'error' | 'info'> = {
+  clear: 'success',
+  partial: 'warning',
+  missing: 'error',
+  resolved: 'info',
+}
+
+interface ClarifySessionCardProps {
+  session: ClarifySession
+  dispatch: (action: Action) => Promise<void>
+}
+
+/**
+ * ClarifySessionCard - Answer the spec's open questions one at a time;
+ * the answers are written into spec.md when the last one is handled
+ */
+export function ClarifySessionCard({ session, dispatch }: ClarifySessionCardProps) {
+  const [answer, setAnswer] = useState('')
+  const current = session.status === 'asking' ? session.questions[session.answers.length] : undefined
+
+  const submit = () => {
+    if (!current || !answer.trim()) return
+    dispatch({ type: 'AnswerClarifyQuestion', payload: { id: current.id, value: answer.trim() } })
+    setAnswer('')
+  }
+
+  return (
+    <Paper variant="outlined" sx={{ p: 2, borderColor: 'outlineVariant' }}>
+      <Stack direction="row" alignItems="center" spacing={1} sx={{ mb: 1.5 }}>
+        <ClarifyIcon fontSize="small" color="primary" />
+        <Typography variant="subtitle2" fontWeight={600} sx={{ flex: 1 }}>
+          Clarify {session.spec_path}
+        </Typography>
+        <Tooltip title="Close">
+          <IconButton size="small" onClick={() => dispatch({ type: 'ClearClarifySession' })}>
+            <CloseIcon fontSize="small" />
+          </IconButton>
+        </Tooltip>
+      </Stack>
+
+      <Stack direction="row" flexWrap="wrap" useFlexGap spacing={0.5} sx={{ mb: 2 }}>
+        {session.coverage.map((entry) => (
+          <Chip
+            key={entry.category}
+            label={`${CATEGORY_LABELS[entry.category]}: ${entry.status}`}
+            size="small"
+            variant="outlined"
+            color={COVERAGE_COLORS[entry.status]}
+            sx={{ height: 20, fontSize: '0.65rem' }}
+          />
+        ))}
+      </Stack>
+
+      {current ? (
+        <Box>
+          <Typography variant="caption" color="text.secondary">
+            Question {session.answers.length + 1} of {session.questions.length} · {CATEGORY_LABELS[current.category]}
+          </Typography>
+          <Typography variant="body2" sx={{ my: 1 }}>
+            {current.question}
+          </Typography>
+          <TextField
+            fullWidth
+            multiline
+            minRows={2}
+            size="small"
+            value={answer}
+            onChange={(e) => setAnswer(e.target.value)}
+            onKeyDown={(e) => {
+              if (e.key === 'Enter' && (e.metaKey || e.ctrlKey)) submit()
+            }}
+          />
+          <Stack direction="row" spacing={1} sx={{ mt: 1 }}>
+            <Button size="small" variant="contained" disabled={!answer.trim()} onClick={submit}>
+              Answer
+            </Button>
+            <Button size="small" onClick={() => dispatch({ type: 'SkipClarifyQuestion' })}>
+              Skip
+            </Button>
+          </Stack>
+        </Box>
+      ) : session.status === 'integrating' ? (
+        <LinearProgress />
+      ) : session.status === 'failed' ? (
+        <Typography variant="body2" color="error">
+          {session.error}
+        </Typography>
+      ) : (
+        <Typography variant="body2" color="text.secondary">
+          {session.questions.length === 0
+            ? 'Nothing to clarify - the spec covers every category'
+            : `Done - ${session.answers.filter((a) => a.value !== null).length} answers written to the spec`}
+        </Typography>
+      )}
+    </Paper>
+  )
+}
@@ -0,0 +1,131 @@
+import { useCallback } from 'react'
+import {
+  Gavel as GavelIcon,
+  Refresh as RefreshIcon
+} from '@mui/icons-material'
+import { Alert, Box, Button, Chip, LinearProgress, Stack, Typography } from '@mui/material'
+import { useAppState } from '@/hooks/useAppState'
+import type { ComplianceStatus, ComplianceViolation, ReviewSession, ViolationSeverity } from '@/types/state'
+
+const STATUS_CONFIG: Record<ComplianceStatus, { label: string; color: 'default' | 'success' | 'error' | 'info' | 'warning' }> = {
+  running: { label: 'Checking', color: 'info' },
+  passed: { label: 'Compliant', color: 'success' },
+  failed: { label: 'Violations', color: 'error' },
+  skipped: { label: 'Skipped', color: 'default' },
+  error: { label: 'Check failed', color: 'warning' },
+}
+
+const SEVERITY_COLORS: Record<ViolationSeverity, 'error' | 'warning' | 'info'> = {
+  blocking: 'error',
+  warning: 'warning',
+  info: 'info',
+}
+
+/**
+ * Why a session cannot be approved yet (mirrors the core's approval gate).
+ */
+export function approvalBlocker(session: ReviewSession): string | null {
+  const check = session.compliance
+  if (!check) return null
+  if (check.status === 'running') return 'The constitution compliance check is still running'
+  if (check.status === 'failed' && !check.override_reason) {
+    const blocking = check.report?.violations.filter((v) => v.severity === 'blocking').length ?? 0
+    return `${blocking} blocking constitution violation(s) must be fixed or overridden before approval`
+  }
+  return null
+}
+
+function groupBySection(violations: ComplianceViolation[]): [string, ComplianceViolation[]][] {
+  const groups = new Map<string, ComplianceViolation[]>()
+  for (const violation of violations) {
+    groups.set(violation.section, [...(groups.get(violation.section) ?? []), violation])
+  }
+  return [...groups.entries()]
+}
+
+/**
+ * Constitution compliance pre-check of a review session.
+ */
+export function ComplianceCard({ session }: { session: ReviewSession }) {
+  const { dispatch } = useAppState()
+  const check = session.compliance
+
+  const handleRerun = useCallback(() => {
+    dispatch({ type: 'RunComplianceCheck', payload: { session_id: session.id } })
+  }, [dispatch, session.id])
+
+  const handleOverride = useCallback(() => {
+    const reason = prompt('Reason for approving despite blocking violations:')
+    if (!reason?.trim()) return
+    dispatch({ type: 'OverrideComplianceCheck', payload: { session_id: session.id, reason: reason.trim() } })
+  }, [dispatch, session.id])
+
+  if (!check) return null
+  const status = STATUS_CONFIG[check.status]
+  const isFinal = session.status === 'approved' || session.status === 'rejected'
+
+  return (
+    <Box sx={{ borderTop: 1, borderColor: 'outlineVariant', px: 2, py: 1.5 }}>
+      <Stack direction="row" spacing={1} alignItems="center">
+        <GavelIcon fontSize="small" color="action" />
+        <Typography variant="body2" fontWeight={600}>Constitution</Typography>
+        <Chip label={status.label} size="small" color={status.color} sx={{ height: 20, fontSize: '0.65rem' }} />
+        <Box sx={{ flex: 1 }} />
+        {check.status === 'failed' && !check.override_reason && !isFinal && (
+          <Button size="small" color="warning" onClick={handleOverride}>
+            Override
+          </Button>
+        )}
+        {check.status !== 'running' && !isFinal && (
+          <Button size="small" onClick={handleRerun} startIcon={<RefreshIcon />}>
+            Re-check
+          </Button>
+        )}
+      </Stack>
+
+      {check.status === 'running' && <LinearProgress sx={{ mt: 1 }} />}
+      {check.error && (
+        <Typography variant="caption" color="text.secondary" display="block" sx={{ mt: 0.5 }}>
+          {check.error}
+        </Typography>
+      )}
+      {check.override_reason && (
+        <Alert severity="warning" sx={{ mt: 1, py: 0 }}>
+          Overridden: {check.override_reason}
+        </Alert>
+      )}
+
+      {check.report && (
+        <Box sx={{ mt: 1 }}>
+          {check.report.summary && (
+            <Typography variant="caption" color="text.secondary" display="block">
+              {check.report.summary}
+            </Typography>
+          )}
+          {groupBySection(check.report.violations).map(([section, violations]) => (
+            <Box key={section} sx={{ mt: 1 }}>
+              <Typography variant="caption" fontWeight={700}>{section}</Typography>
+              <Stack spacing={0.5} sx={{ mt: 0.5 }}>
+                {violations.map((violation, index) => (
+                  <Stack key={index} direction="row" spacing={1} alignItems="flex-start">
+                    <Chip
+                      label={violation.severity}
+                      size="small"
+                      color={SEVERITY_COLORS[violation.severity]}
+                      variant="outlined"
+                      sx={{ height: 18, fontSize: '0.6rem', flexShrink: 0 }}
+                    />
+                    <Box>
+                      <Typography variant="caption" display="block" fontWeight={600}>{violation.rule}</Typography>
+                      <Typography variant="caption" color="text.secondary" display="block">{violation.explanation}</Typography>
+                    </Box>
+                  </Stack>
+                ))}
+              </Stack>
+            </Box>
+          ))}
+        </Box>
+      )}
+    </Box>
+  )
+}
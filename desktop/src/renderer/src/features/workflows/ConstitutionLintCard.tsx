@@ -0,0 +1,104 @@
+import {
+  ErrorOutline as ErrorIcon,
+  WarningAmber as WarningIcon,
+  InfoOutlined as InfoIcon,
+  CheckCircle as CheckCircleIcon
+} from '@mui/icons-material'
+import { Box, Card, CardContent, Chip, List, ListItem, ListItemIcon, ListItemText, Stack, Typography } from '@mui/material'
+import type { ConstitutionLintReport, LintFinding, LintRule, LintSeverity } from '@/types/state'
+
+const RULE_LABELS: Record<LintRule, string> = {
+  missing_section: 'Missing section',
+  phrasing: 'Phrasing',
+  contradiction: 'Contradiction',
+  stale_reference: 'Stale reference',
+  frontmatter: 'Frontmatter'
+}
+
+const SEVERITY_COLORS: Record<LintSeverity, 'error' | 'warning' | 'info'> = {
+  error: 'error',
+  warning: 'warning',
+  info: 'info'
+}
+
+function SeverityIcon({ severity }: { severity: LintSeverity }) {
+  switch (severity) {
+    case 'error':
+      return <ErrorIcon fontSize="small" color="error" />
+    case 'warning':
+      return <WarningIcon fontSize="small" color="warning" />
+    default:
+      return <InfoIcon fontSize="small" color="info" />
+  }
+}
+
+function location(finding: LintFinding): string | null {
+  if (!finding.file) return null
+  return finding.line ? `${finding.file}:${finding.line}` : finding.file
+}
+
+/**
+ * Constitution lint findings: missing sections, weak phrasing,
+ * contradictions and stale library references.
+ */
+export function ConstitutionLintCard({ report }: { report: ConstitutionLintReport }) {
+  const counts = (['error', 'warning', 'info'] as const).map((severity) => ({
+    severity,
+    count: report.findings.filter((f) => f.severity === severity).length
+  }))
+
+  return (
+    <Card elevation={0} variant="outlined" sx={{ mb: 2 }}>
+      <CardContent>
+        <Stack direction="row" spacing={1} alignItems="center" sx={{ mb: 1 }}>
+          <Typography variant="subtitle2" fontWeight={600} sx={{ flex: 1 }}>
+            Lint ({report.files.length} {report.files.length === 1 ? 'file' : 'files'})
+          </Typography>
+          {counts
+            .filter((c) => c.count > 0)
+            .map((c) => (
+              <Chip
+                key={c.severity}
+                label={`${c.count} ${c.severity}`}
+                size="small"
+                color={SEVERITY_COLORS[c.severity]}
+                variant="outlined"
+                sx={{ height: 20, fontSize: '0.65rem' }}
+              />
+            ))}
+          <Typography variant="caption" color="text.secondary">
+            {new Date(report.linted_at).toLocaleTimeString()}
+          </Typography>
+        </Stack>
+
+        {report.findings.length === 0 ? (
+          <Stack direction="row" spacing={1} alignItems="center">
+            <CheckCircleIcon fontSize="small" color="success" />
+            <Typography variant="body2" color="text.secondary">No problems found</Typography>
+          </Stack>
+        ) : (
+          <List dense disablePadding sx={{ maxHeight: 280, overflow: 'auto' }}>
+            {report.findings.map((finding, index) => (
+              <ListItem key={index} disableGutters alignItems="flex-start">
+                <ListItemIcon sx={{ minWidth: 32, mt: 0.5 }}>
+                  <SeverityIcon severity={finding.severity} />
+                </ListItemIcon>
+                <ListItemText
+                  primary={finding.message}
+                  secondary={
+                    <Box component="span" sx={{ display: 'flex', gap: 1 }}>
+                      <span>{RULE_LABELS[finding.rule]}</span>
+                      {location(finding) && (
+                        <Box component="span" sx={{ fontFamily: 'monospace' }}>{location(finding)}</Box>
+                      )}
+                    </Box>
+                  }
+                />
+              </ListItem>
+            ))}
+          </List>
+        )}
+      </CardContent>
+    </Card>
+  )
+}
@@ -8,7 +8,8 @@ import {
   Code as FileCodeIcon,
   ExpandMore as ExpandMoreIcon,
   ErrorOutline as AlertCircleIcon,
-  Cancel as XCircleIcon
+  Cancel as XCircleIcon,
+  FactCheck as LintIcon
 } from '@mui/icons-material'
 import {
   Button,
@@ -29,6 +30,8 @@ import {
 import { PageHeader } from '@/components/shared/PageHeader'
 import { WorkflowHeader } from '@/components/shared/WorkflowHeader'
 import { LoadingState } from '@/components/shared/LoadingState'
+import { MarkdownDocument } from '@/components/shared/MarkdownPreview'
+import { ConstitutionLintCard } from './ConstitutionLintCard'
 import { useAppState } from '@/hooks/useAppState'
 import ReactMarkdown from 'react-markdown'
 
@@ -110,6 +113,7 @@ export function ConstitutionPanel() {
   const [selectedOptions, setSelectedOptions] = useState<Record<string, string[]>>({})
   const [customNotes, setCustomNotes] = useState<Record<string, string>>({})
   const [previewOpen, setPreviewOpen] = useState(false)
+  const [detectedStacks, setDetectedStacks] = useState<{ id: string; name: string; hasUserTemplate: boolean }[]>([])
 
   // Note: active_project is not serialized, use projects[active_project_index]
   const activeProject = state?.projects?.[state?.active_project_index ?? 0]
@@ -117,6 +121,8 @@ export function ConstitutionPanel() {
   const workflow = worktree?.tasks?.constitution_workflow
   const constitutionExists = worktree?.tasks?.constitution_exists
   const constitutionContent = worktree?.tasks?.constitution_content
+  const lintReport = worktree?.tasks?.constitution_lint
+  const isLinting = worktree?.tasks?.constitution_linting ?? false
 
   // CLAUDE.md detection state
   const claudeMdExists = worktree?.tasks?.claude_md_exists
@@ -146,8 +152,28 @@ export function ConstitutionPanel() {
     }
   }, [claudeMdExists, claudeMdContent, claudeMdSkipped, dispatch])
 
+  // Detect stacks to preview which modules the default template creates
+  const worktreePath = worktree?.path
+  useEffect(() => {
+    if (constitutionExists !== false || !worktreePath) return
+    let cancelled = false
+    window.stateApi
+      .listConstitutionStacks(worktreePath)
+      .then((stacks) => {
+        if (!cancelled) setDetectedStacks(stacks)
+      })
+      .catch(() => setDetectedStacks([]))
+    return () => {
+      cancelled = true
+    }
+  }, [constitutionExists, worktreePath])
+
   const questions = QUESTION_CONFIGS
 
+  const handleLint = useCallback(async () => {
+    await dispatch({ type: 'LintConstitution' })
+  }, [dispatch])
+
   const handleApplyDefault = useCallback(async () => {
     await dispatch({ type: 'ApplyDefaultConstitution' })
   }, [dispatch])
@@ -249,7 +275,7 @@ export function ConstitutionPanel() {
               <Box sx={{ flex: 1, overflow: 'auto', p: 2 }}>
                 {claudeMdContent ? (
                   <Typography component="div" variant="body2" sx={{ '& pre': { overflow: 'auto' } }}>
-                    <ReactMarkdown>{claudeMdContent}</ReactMarkdown>
+                    <MarkdownDocument content={claudeMdContent} />
                   </Typography>
                 ) : (
                   <LoadingState message="Loading preview..." />
@@ -289,16 +315,22 @@ export function ConstitutionPanel() {
             description="Governance rules for AI development"
             icon={<CheckCircleIcon color="success" />}
           >
-            <Button variant="outlined" size="small" onClick={handleStartQA} startIcon={<RefreshIcon />}>
-              Regenerate
-            </Button>
+            <Stack direction="row" spacing={1}>
+              <Button variant="outlined" size="small" onClick={handleLint} disabled={isLinting} startIcon={<LintIcon />}>
+                {isLinting ? 'Linting...' : 'Lint'}
+              </Button>
+              <Button variant="outlined" size="small" onClick={handleStartQA} startIcon={<RefreshIcon />}>
+                Regenerate
+              </Button>
+            </Stack>
           </PageHeader>
           <Box sx={{ flex: 1, overflow: 'auto', px: 3, pb: 3 }}>
+            {lintReport && <ConstitutionLintCard report={lintReport} />}
             {constitutionContent ? (
               <Card elevation={0} variant="outlined">
                 <CardContent>
                   <Typography component="div" variant="body2" sx={{ '& h1, & h2, & h3': { mt: 2, mb: 1, fontWeight: 600 }, '& ul': { pl: 2 }, '& pre': { bgcolor: 'action.hover', p: 1, borderRadius: 1, overflow: 'auto' } }}>
-                    <ReactMarkdown>{constitutionContent}</ReactMarkdown>
+                    <MarkdownDocument content={constitutionContent} />
                   </Typography>
                 </CardContent>
               </Card>
@@ -329,6 +361,20 @@ export function ConstitutionPanel() {
                   <Typography variant="caption" display="block" align="center" color="text.secondary" sx={{ mt: 1 }}>
                     Auto-detects languages and creates modular rules
                   </Typography>
+                  {detectedStacks.length > 0 && (
+                    <Stack direction="row" spacing={0.5} justifyContent="center" flexWrap="wrap" useFlexGap sx={{ mt: 1 }}>
+                      {detectedStacks.map((stack) => (
+                        <Chip
+                          key={stack.id}
+                          label={stack.hasUserTemplate ? `${stack.name} (custom)` : stack.name}
+                          size="small"
+                          variant="outlined"
+                          color={stack.hasUserTemplate ? 'primary' : 'default'}
+                          sx={{ height: 20, fontSize: '0.65rem' }}
+                        />
+                      ))}
+                    </Stack>
+                  )}
                 </Box>
 
                 <Divider>
@@ -407,7 +453,7 @@ export function ConstitutionPanel() {
                       <Collapse in={previewOpen}>
                         <Paper variant="outlined" sx={{ mt: 1, p: 2, maxHeight: 150, overflow: 'auto', bgcolor: 'background.paper' }}>
                           <Typography component="div" variant="caption">
-                            <ReactMarkdown>{claudeMdContent}</ReactMarkdown>
+                            <MarkdownDocument content={claudeMdContent} />
                           </Typography>
                         </Paper>
                       </Collapse>
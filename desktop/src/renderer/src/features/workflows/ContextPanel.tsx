@@ -28,7 +28,7 @@ import { LoadingState } from '@/components/shared/LoadingState'
 import { EmptyState } from '@/components/shared/EmptyState'
 import { ErrorBanner } from '@/components/shared/ErrorBanner'
 import { useAppState } from '@/hooks/useAppState'
-import ReactMarkdown from 'react-markdown'
+import { MarkdownDocument } from '@/components/shared/MarkdownPreview'
 
 /**
  * Context viewing panel for Living Context Layer.
@@ -276,7 +276,7 @@ export function ContextPanel() {
 
                   {/* File content */}
                   <Typography component="div" variant="body2" sx={{ '& h1, & h2, & h3': { mt: 3, mb: 1.5, fontWeight: 600 }, '& ul, & ol': { pl: 2 }, '& pre': { bgcolor: 'action.hover', p: 2, borderRadius: 2, overflow: 'auto', border: 1, borderColor: 'outlineVariant' } }}>
-                    <ReactMarkdown>{activeFile.content}</ReactMarkdown>
+                    <MarkdownDocument content={activeFile.content} />
                   </Typography>
                 </Box>
               )}
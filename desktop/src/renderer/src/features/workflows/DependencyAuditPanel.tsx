@@ -0,0 +1,119 @@
+import { useCallback } from 'react'
+import { Refresh as RefreshIcon, Security as AuditIcon } from '@mui/icons-material'
+import { Alert, Box, Button, Chip, LinearProgress, Link, Paper, Stack, Typography } from '@mui/material'
+import { WorkflowHeader } from '@/components/shared/WorkflowHeader'
+import { EmptyState } from '@/components/shared/EmptyState'
+import { useActiveWorktree } from '@/hooks/useAppState'
+import type { Advisory, AuditSeverity } from '@/types/state'
+
+const SEVERITY_COLORS: Record<AuditSeverity, 'default' | 'info' | 'warning' | 'error'> = {
+  unknown: 'default',
+  low: 'info',
+  medium: 'warning',
+  high: 'error',
+  critical: 'error',
+}
+
+function AdvisoryRow({ advisory }: { advisory: Advisory }) {
+  return (
+    <Paper variant="outlined" sx={{ p: 1.5, borderColor: 'outlineVariant' }}>
+      <Stack direction="row" alignItems="center" spacing={1}>
+        <Chip
+          label={advisory.severity}
+          size="small"
+          color={SEVERITY_COLORS[advisory.severity]}
+          variant={advisory.severity === 'critical' ? 'filled' : 'outlined'}
+          sx={{ height: 18, fontSize: '0.65rem', textTransform: 'capitalize' }}
+        />
+        <Typography variant="body2" fontWeight={600} noWrap sx={{ flex: 1, minWidth: 0 }}>
+          {advisory.package}
+          {advisory.installed_version && (
+            <Typography component="span" variant="caption" color="text.secondary" sx={{ ml: 1 }}>
+              {advisory.installed_version}
+            </Typography>
+          )}
+        </Typography>
+        {advisory.url ? (
+          <Link href={advisory.url} target="_blank" rel="noreferrer" variant="caption" sx={{ fontFamily: 'monospace' }}>
+            {advisory.id}
+          </Link>
+        ) : (
+          <Typography variant="caption" sx={{ fontFamily: 'monospace' }}>
+            {advisory.id}
+          </Typography>
+        )}
+      </Stack>
+      <Typography variant="caption" color="text.secondary" sx={{ display: 'block', mt: 0.5 }}>
+        {advisory.title}
+        {advisory.fix_version && ` · fixed in ${advisory.fix_version}`}
+      </Typography>
+    </Paper>
+  )
+}
+
+/**
+ * DependencyAuditPanel - cargo audit / npm audit / pip-audit results for the active worktree
+ */
+export function DependencyAuditPanel() {
+  const { worktree, dispatch } = useActiveWorktree()
+  const report = worktree?.audit ?? null
+  const isAuditing = worktree?.is_auditing ?? false
+
+  const handleRun = useCallback(() => {
+    dispatch({ type: 'RunDependencyAudit' })
+  }, [dispatch])
+
+  const critical = report?.advisories.filter((a) => a.severity === 'critical').length ?? 0
+
+  return (
+    <Stack sx={{ height: '100%' }}>
+      <WorkflowHeader
+        title="Dependency Audit"
+        subtitle="Critical findings are included in the AI context"
+        icon={<AuditIcon />}
+        status={report ? `${report.advisories.length} advisories` : undefined}
+        statusColor={critical > 0 ? 'error' : 'success'}
+      >
+        <Button
+          variant="outlined"
+          size="small"
+          onClick={handleRun}
+          disabled={!worktree || isAuditing}
+          startIcon={<RefreshIcon />}
+          sx={{ height: 32, borderRadius: 2 }}
+        >
+          {report ? 'Re-run Audit' : 'Run Audit'}
+        </Button>
+      </WorkflowHeader>
+      {isAuditing && <LinearProgress />}
+
+      <Box sx={{ flex: 1, overflow: 'auto', p: 3 }}>
+        {!report ? (
+          <EmptyState
+            title="No Audit Yet"
+            description="Run cargo audit, npm audit and pip-audit for the lockfiles in this worktree."
+          />
+        ) : (
+          <Stack spacing={1}>
+            {report.errors?.map((error) => (
+              <Alert key={error} severity="warning" sx={{ py: 0 }}>
+                {error}
+              </Alert>
+            ))}
+            {report.tools.length > 0 && report.advisories.length === 0 && (
+              <Alert severity="success" sx={{ py: 0 }}>
+                No known vulnerabilities ({report.tools.join(', ')})
+              </Alert>
+            )}
+            {report.advisories.map((advisory) => (
+              <AdvisoryRow key={`${advisory.tool}:${advisory.id}:${advisory.package}`} advisory={advisory} />
+            ))}
+            <Typography variant="caption" color="text.secondary">
+              Last run {new Date(report.ran_at).toLocaleString()}
+            </Typography>
+          </Stack>
+        )}
+      </Box>
+    </Stack>
+  )
+}
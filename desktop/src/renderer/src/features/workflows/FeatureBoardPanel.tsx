@@ -0,0 +1,138 @@
+import { useEffect } from 'react'
+import {
+  CallSplit as BranchIcon,
+  PlayArrow as StartIcon,
+  Refresh as RefreshIcon,
+  ViewKanban as BoardIcon,
+} from '@mui/icons-material'
+import { Box, Button, Chip, IconButton, LinearProgress, Paper, Stack, Tooltip, Typography } from '@mui/material'
+import { WorkflowHeader } from '@/components/shared/WorkflowHeader'
+import { EmptyState } from '@/components/shared/EmptyState'
+import { useActiveWorktree } from '@/hooks/useAppState'
+import type { Action, FeatureInfo, FeatureStage } from '@/types/state'
+
+const STAGES: { stage: FeatureStage; label: string }[] = [
+  { stage: 'draft', label: 'Draft' },
+  { stage: 'specified', label: 'Specified' },
+  { stage: 'planned', label: 'Planned' },
+  { stage: 'tasked', label: 'Tasked' },
+  { stage: 'implementing', label: 'Implementing' },
+  { stage: 'done', label: 'Done' },
+]
+
+interface FeatureCardProps {
+  feature: FeatureInfo
+  /** The active worktree is already on the feature branch */
+  isActive: boolean
+  dispatch: (action: Action) => Promise<void>
+}
+
+function FeatureCard({ feature, isActive, dispatch }: FeatureCardProps) {
+  const artifacts = [
+    feature.artifacts.spec && 'spec',
+    feature.artifacts.plan && 'plan',
+    feature.artifacts.tasks && 'tasks',
+    ...feature.artifacts.checklists.map((kind) => `${kind} checklist`),
+    feature.artifacts.analysis && 'analysis',
+  ].filter((name): name is string => !!name)
+
+  return (
+    <Paper variant="outlined" sx={{ p: 1.5, borderColor: 'outlineVariant' }}>
+      <Typography variant="body2" fontWeight={600} noWrap title={feature.dir}>
+        {String(feature.number).padStart(3, '0')} {feature.short_name}
+      </Typography>
+      {feature.description && (
+        <Typography variant="caption" color="text.secondary" sx={{ display: 'block' }} noWrap title={feature.description}>
+          {feature.description}
+        </Typography>
+      )}
+      {feature.tasks_total > 0 && (
+        <LinearProgress
+          variant="determinate"
+          value={(feature.tasks_done / feature.tasks_total) * 100}
+          sx={{ my: 1 }}
+        />
+      )}
+      <Stack direction="row" flexWrap="wrap" useFlexGap spacing={0.5} sx={{ mt: 0.5 }}>
+        {artifacts.map((name) => (
+          <Chip key={name} label={name} size="small" variant="outlined" sx={{ height: 16, fontSize: '0.6rem' }} />
+        ))}
+        {feature.branch_exists && (
+          <Chip icon={<BranchIcon />} label={feature.branch} size="small" color="primary" sx={{ height: 16, fontSize: '0.6rem' }} />
+        )}
+      </Stack>
+      {!isActive && feature.stage !== 'done' && (
+        <Tooltip title="Create or attach the feature worktree, copy env files and start its MCP server">
+          <Button
+            size="small"
+            startIcon={<StartIcon />}
+            sx={{ mt: 1 }}
+            onClick={() => dispatch({ type: 'StartFeatureWork', payload: { feature_number: feature.number } })}
+          >
+            Start work
+          </Button>
+        </Tooltip>
+      )}
+    </Paper>
+  )
+}
+
+/**
+ * FeatureBoardPanel - Pipeline board of the worktree's spec-kit features,
+ * one column per stage (draft → done)
+ */
+export function FeatureBoardPanel() {
+  const { worktree, dispatch } = useActiveWorktree()
+  const worktreePath = worktree?.path
+
+  useEffect(() => {
+    if (worktreePath) {
+      dispatch({ type: 'LoadFeaturesCatalog' })
+    }
+  }, [worktreePath, dispatch])
+
+  if (!worktree) {
+    return <EmptyState title="No Project Open" description="Open a project to see its features" />
+  }
+
+  const features = worktree.workflows?.features ?? []
+
+  return (
+    <Box sx={{ display: 'flex', flexDirection: 'column', height: '100%' }}>
+      <WorkflowHeader title="Feature Board" subtitle={`${features.length} features under specs/`} icon={<BoardIcon />}>
+        <Tooltip title="Refresh">
+          <IconButton size="small" onClick={() => dispatch({ type: 'LoadFeaturesCatalog' })}>
+            <RefreshIcon fontSize="small" />
+          </IconButton>
+        </Tooltip>
+      </WorkflowHeader>
+
+      {features.length === 0 ? (
+        <EmptyState title="No Features" description="Start a spec workflow to create the first feature" />
+      ) : (
+        <Stack direction="row" spacing={1.5} sx={{ flex: 1, overflow: 'auto', p: 3 }}>
+          {STAGES.map(({ stage, label }) => {
+            const column = features.filter((feature) => feature.stage === stage)
+            return (
+              <Box key={stage} sx={{ minWidth: 200, flex: 1 }}>
+                <Typography variant="caption" fontWeight={600} color="text.secondary">
+                  {label} ({column.length})
+                </Typography>
+                <Stack spacing={1} sx={{ mt: 1 }}>
+                  {column.map((feature) => (
+                    <FeatureCard
+                      key={feature.number}
+                      feature={feature}
+                      isActive={worktree.branch === feature.branch}
+                      dispatch={dispatch}
+                    />
+                  ))}
+                </Stack>
+              </Box>
+            )
+          })}
+        </Stack>
+      )}
+    </Box>
+  )
+}
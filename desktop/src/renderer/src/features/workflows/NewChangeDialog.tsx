@@ -1,4 +1,4 @@
-import { useState } from 'react'
+import { useEffect, useState } from 'react'
 import {
   Button,
   TextField,
@@ -7,27 +7,80 @@ import {
   DialogContent,
   DialogContentText,
   DialogActions,
+  Checkbox,
+  Chip,
+  CircularProgress,
+  FormControlLabel,
+  List,
+  ListItemButton,
+  ListItemText,
+  Tab,
+  Tabs,
   Typography,
   Stack,
   Box
 } from '@mui/material'
 
+type GitHubIssue = Awaited<ReturnType<typeof window.githubApi.listIssues>>[number]
+
 interface NewChangeDialogProps {
   open: boolean
   onOpenChange: (open: boolean) => void
-  onSubmit: (intent: string) => void
+  onSubmit: (intent: string, createBranch: boolean) => void
+  /** Create the change from a GitHub issue of the repository instead */
+  onSubmitIssue: (issueNumber: number, createBranch: boolean) => void
+  /** Local checkout whose GitHub issues can be picked */
+  repoPath?: string
 }
 
 /**
  * NewChangeDialog - Dialog to create a new change from intent
  */
-export function NewChangeDialog({ open, onOpenChange, onSubmit }: NewChangeDialogProps) {
+export function NewChangeDialog({ open, onOpenChange, onSubmit, onSubmitIssue, repoPath }: NewChangeDialogProps) {
   const [intent, setIntent] = useState('')
+  const [createBranch, setCreateBranch] = useState(false)
+  const [source, setSource] = useState<'intent' | 'issue'>('intent')
+  const [issues, setIssues] = useState<GitHubIssue[] | null>(null)
+  const [issuesError, setIssuesError] = useState<string | null>(null)
+  const [selectedIssue, setSelectedIssue] = useState<number | null>(null)
+
+  // Load issues when the issue tab is first shown
+  useEffect(() => {
+    if (!open || source !== 'issue' || !repoPath || issues !== null) return
+    let cancelled = false
+    window.githubApi
+      .listIssues(repoPath)
+      .then((result) => {
+        if (!cancelled) setIssues(result)
+      })
+      .catch((e) => {
+        if (!cancelled) {
+          setIssues([])
+          setIssuesError(e instanceof Error ? e.message : String(e))
+        }
+      })
+    return () => {
+      cancelled = true
+    }
+  }, [open, source, repoPath, issues])
+
+  const reset = () => {
+    setIntent('')
+    setCreateBranch(false)
+    setSelectedIssue(null)
+    setIssues(null)
+    setIssuesError(null)
+  }
+
+  const canSubmit = source === 'intent' ? !!intent.trim() : selectedIssue !== null
 
   const handleSubmit = () => {
-    if (intent.trim()) {
-      onSubmit(intent.trim())
-      setIntent('')
+    if (source === 'intent' && intent.trim()) {
+      onSubmit(intent.trim(), createBranch)
+      reset()
+    } else if (source === 'issue' && selectedIssue !== null) {
+      onSubmitIssue(selectedIssue, createBranch)
+      reset()
     }
   }
 
@@ -38,41 +91,104 @@ export function NewChangeDialog({ open, onOpenChange, onSubmit }: NewChangeDialo
   }
 
   return (
-    <Dialog 
-      open={open} 
+    <Dialog
+      open={open}
       onClose={() => onOpenChange(false)}
       maxWidth="sm"
       fullWidth
     >
       <DialogTitle>Create New Change</DialogTitle>
       <DialogContent>
-        <DialogContentText sx={{ mb: 3 }}>
-          Describe what you want to accomplish. This will be used to generate a proposal and plan.
-        </DialogContentText>
+        <Tabs value={source} onChange={(_, value) => setSource(value)} sx={{ mb: 2 }}>
+          <Tab value="intent" label="Describe" sx={{ textTransform: 'none' }} />
+          <Tab value="issue" label="From GitHub Issue" disabled={!repoPath} sx={{ textTransform: 'none' }} />
+        </Tabs>
+
+        {source === 'intent' ? (
+          <>
+            <DialogContentText sx={{ mb: 3 }}>
+              Describe what you want to accomplish. This will be used to generate a proposal and plan.
+            </DialogContentText>
+
+            <Stack spacing={2} sx={{ mt: 1 }}>
+              <TextField
+                autoFocus
+                label="Intent"
+                placeholder="e.g., Add user authentication with OAuth2 support"
+                multiline
+                rows={4}
+                fullWidth
+                value={intent}
+                onChange={(e) => setIntent(e.target.value)}
+                onKeyDown={handleKeyDown}
+                helperText="Be specific about what you want to build. Press Cmd+Enter to submit."
+              />
+            </Stack>
+          </>
+        ) : (
+          <>
+            <DialogContentText sx={{ mb: 2 }}>
+              The issue becomes the change intent. The issue is labeled and gets a comment linking the change.
+            </DialogContentText>
+            {issues === null ? (
+              <Stack alignItems="center" sx={{ py: 4 }}>
+                <CircularProgress size={24} />
+              </Stack>
+            ) : issuesError ? (
+              <Typography variant="body2" color="error">{issuesError}</Typography>
+            ) : issues.length === 0 ? (
+              <Typography variant="body2" color="text.secondary">No open issues</Typography>
+            ) : (
+              <List dense sx={{ maxHeight: 320, overflow: 'auto', border: 1, borderColor: 'divider', borderRadius: 1 }}>
+                {issues.map((issue) => (
+                  <ListItemButton
+                    key={issue.number}
+                    selected={selectedIssue === issue.number}
+                    onClick={() => setSelectedIssue(issue.number)}
+                  >
+                    <ListItemText
+                      primary={`#${issue.number} ${issue.title}`}
+                      secondary={
+                        <Stack direction="row" spacing={0.5} component="span" sx={{ mt: 0.5 }}>
+                          {issue.author && (
+                            <Typography variant="caption" component="span" color="text.secondary">
+                              {issue.author}
+                            </Typography>
+                          )}
+                          {issue.labels.map((label) => (
+                            <Chip key={label} label={label} size="small" component="span" sx={{ height: 16, fontSize: '0.6rem' }} />
+                          ))}
+                        </Stack>
+                      }
+                    />
+                  </ListItemButton>
+                ))}
+              </List>
+            )}
+          </>
+        )}
 
-        <Stack spacing={2} sx={{ mt: 1 }}>
-          <TextField
-            autoFocus
-            label="Intent"
-            placeholder="e.g., Add user authentication with OAuth2 support"
-            multiline
-            rows={4}
-            fullWidth
-            value={intent}
-            onChange={(e) => setIntent(e.target.value)}
-            onKeyDown={handleKeyDown}
-            helperText="Be specific about what you want to build. Press Cmd+Enter to submit."
-          />
-        </Stack>
+        <FormControlLabel
+          sx={{ mt: 2 }}
+          control={<Checkbox checked={createBranch} onChange={(e) => setCreateBranch(e.target.checked)} />}
+          label={
+            <Box>
+              <Typography variant="body2">Work on a dedicated branch</Typography>
+              <Typography variant="caption" color="text.secondary">
+                Creates a change/&lt;name&gt; branch in a new worktree, ready for a pull request
+              </Typography>
+            </Box>
+          }
+        />
       </DialogContent>
       <DialogActions sx={{ px: 3, pb: 3 }}>
         <Button onClick={() => onOpenChange(false)}>
           Cancel
         </Button>
-        <Button 
-          variant="contained" 
-          onClick={handleSubmit} 
-          disabled={!intent.trim()}
+        <Button
+          variant="contained"
+          onClick={handleSubmit}
+          disabled={!canSubmit}
           sx={{ borderRadius: 2 }}
         >
           Create Change
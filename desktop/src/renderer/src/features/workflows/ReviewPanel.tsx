@@ -1,4 +1,4 @@
-import { useCallback, useState, useMemo } from 'react'
+import { useCallback, useEffect, useState, useMemo } from 'react'
 import {
   Description as FileTextIcon,
   ChatBubbleOutline as MessageSquareIcon,
@@ -8,7 +8,8 @@ import {
   Add as PlusIcon,
   Refresh as RefreshIcon,
   AssignmentTurnedIn as ClipboardCheckIcon,
-  ExpandMore as ExpandMoreIcon
+  ExpandMore as ExpandMoreIcon,
+  Reply as ReplyIcon
 } from '@mui/icons-material'
 import {
   Button,
@@ -34,9 +35,12 @@ import { LoadingState } from '@/components/shared/LoadingState'
 import { EmptyState } from '@/components/shared/EmptyState'
 import { ErrorBanner } from '@/components/shared/ErrorBanner'
 import { useAppState } from '@/hooks/useAppState'
-import ReactMarkdown from 'react-markdown'
+import { MarkdownDocument } from '@/components/shared/MarkdownPreview'
+import { ComplianceCard, approvalBlocker } from './ComplianceCard'
 import type {
   ReviewSession,
+  ReviewComment,
+  CommentAnchor,
   CommentTarget,
   ReviewStatus,
   ReviewContentType,
@@ -97,6 +101,12 @@ function getTargetDisplay(target: CommentTarget): string {
   return 'Unknown'
 }
 
+function getAnchorDisplay(anchor: CommentAnchor): string {
+  return anchor.line_end > anchor.line_start
+    ? `${anchor.file}:${anchor.line_start}-${anchor.line_end}`
+    : `${anchor.file}:${anchor.line_start}`
+}
+
 // ============================================================================
 // Sub-Components
 // ============================================================================
@@ -159,7 +169,7 @@ function ContentView({ session, onSectionClick }: ContentViewProps) {
       {/* Content Body */}
       <Box sx={{ flex: 1, overflow: 'auto', p: 4 }}>
         <Typography component="div" variant="body2" sx={{ '& h1, & h2, & h3': { mt: 3, mb: 1.5, fontWeight: 600 }, '& ul, & ol': { pl: 2 } }}>
-          <ReactMarkdown>{session.content.content}</ReactMarkdown>
+          <MarkdownDocument content={session.content.content} />
         </Typography>
 
         {/* File Changes */}
@@ -193,29 +203,53 @@ function ContentView({ session, onSectionClick }: ContentViewProps) {
 }
 
 interface CommentsSidebarProps {
-  session: ReviewSession
-  onAddComment: (target: CommentTarget, content: string) => void
+  comments: ReviewComment[]
+  /** Files in the reviewed content that comments can be anchored to */
+  files: string[]
+  onAddComment: (target: CommentTarget, content: string, anchor: CommentAnchor | null, parentId: string | null) => void
   onResolveComment: (commentId: string) => void
 }
 
-function CommentsSidebar({ session, onAddComment, onResolveComment }: CommentsSidebarProps) {
+function CommentsSidebar({ comments, files, onAddComment, onResolveComment }: CommentsSidebarProps) {
   const [newCommentContent, setNewCommentContent] = useState('')
   const [newCommentTargetType, setNewCommentTargetType] = useState<'document' | 'section' | 'file'>('document')
+  const [anchorFile, setAnchorFile] = useState('')
+  const [lineStart, setLineStart] = useState('')
+  const [lineEnd, setLineEnd] = useState('')
+  const [replyTo, setReplyTo] = useState<ReviewComment | null>(null)
 
   const handleAddComment = useCallback(() => {
     if (!newCommentContent.trim()) return
-    
+
     let target: CommentTarget = { type: 'document' }
-    if (newCommentTargetType === 'section') target = { type: 'section', id: '' }
-    else if (newCommentTargetType === 'file') target = { type: 'file', path: '' }
-    
-    onAddComment(target, newCommentContent.trim())
+    let anchor: CommentAnchor | null = null
+    if (replyTo) {
+      target = replyTo.target
+    } else if (newCommentTargetType === 'section') {
+      target = { type: 'section', id: '' }
+    } else if (newCommentTargetType === 'file') {
+      target = { type: 'file', path: anchorFile }
+      const start = parseInt(lineStart, 10)
+      if (anchorFile && start > 0) {
+        const end = parseInt(lineEnd, 10)
+        anchor = { file: anchorFile, line_start: start, line_end: end > 0 ? end : start }
+      }
+    }
+
+    onAddComment(target, newCommentContent.trim(), anchor, replyTo?.id ?? null)
     setNewCommentContent('')
     setNewCommentTargetType('document')
-  }, [newCommentContent, newCommentTargetType, onAddComment])
-
-  const unresolvedComments = session.comments.filter((c) => !c.resolved)
-  const resolvedComments = session.comments.filter((c) => c.resolved)
+    setLineStart('')
+    setLineEnd('')
+    setReplyTo(null)
+  }, [newCommentContent, newCommentTargetType, anchorFile, lineStart, lineEnd, replyTo, onAddComment])
+
+  // Replies are shown under the comment they answer
+  const ids = new Set(comments.map((c) => c.id))
+  const threads = comments.filter((c) => !c.parent_id || !ids.has(c.parent_id))
+  const repliesTo = (id: string) => comments.filter((c) => c.parent_id === id)
+  const unresolvedComments = threads.filter((c) => !c.resolved)
+  const resolvedComments = threads.filter((c) => c.resolved)
 
   return (
     <Box sx={{ display: 'flex', height: '100%', width: 320, flexDirection: 'column', borderLeft: 1, borderColor: 'outlineVariant', bgcolor: 'surfaceContainerLow.main' }}>
@@ -233,28 +267,47 @@ function CommentsSidebar({ session, onAddComment, onResolveComment }: CommentsSi
         <Stack spacing={2}>
           {/* Unresolved Comments */}
           {unresolvedComments.map((comment) => (
-            <Card key={comment.id} elevation={0} sx={{ border: 1, borderColor: 'outlineVariant', bgcolor: 'background.paper' }}>
+            <Card key={comment.id} elevation={0} sx={{ border: 1, borderColor: replyTo?.id === comment.id ? 'primary.main' : 'outlineVariant', bgcolor: 'background.paper' }}>
               <CardContent sx={{ p: 2 }}>
                 <Box sx={{ display: 'flex', alignItems: 'flex-start', justifyContent: 'space-between', mb: 1 }}>
                   <Chip 
-                    label={getTargetDisplay(comment.target)} 
+                    label={comment.anchor ? getAnchorDisplay(comment.anchor) : getTargetDisplay(comment.target)} 
                     size="small" 
                     variant="outlined" 
-                    sx={{ height: 18, fontSize: '0.6rem', borderRadius: 0.5 }} 
+                    sx={{ height: 18, fontSize: '0.6rem', borderRadius: 0.5, fontFamily: comment.anchor ? 'monospace' : undefined }} 
                   />
-                  <IconButton 
-                    size="small" 
-                    onClick={() => onResolveComment(comment.id)}
-                    sx={{ p: 0.25, '&:hover': { color: 'success.main' } }}
-                  >
-                    <CheckCircleIcon sx={{ fontSize: 16 }} />
-                  </IconButton>
+                  <Stack direction="row" spacing={0.5}>
+                    <Tooltip title="Reply">
+                      <IconButton
+                        size="small"
+                        onClick={() => setReplyTo(replyTo?.id === comment.id ? null : comment)}
+                        sx={{ p: 0.25, '&:hover': { color: 'primary.main' } }}
+                      >
+                        <ReplyIcon sx={{ fontSize: 16 }} />
+                      </IconButton>
+                    </Tooltip>
+                    <IconButton 
+                      size="small" 
+                      onClick={() => onResolveComment(comment.id)}
+                      sx={{ p: 0.25, '&:hover': { color: 'success.main' } }}
+                    >
+                      <CheckCircleIcon sx={{ fontSize: 16 }} />
+                    </IconButton>
+                  </Stack>
                 </Box>
                 <Typography variant="body2" sx={{ fontSize: '0.8rem' }}>{comment.content}</Typography>
                 <Box sx={{ mt: 1.5, pt: 1, borderTop: 1, borderColor: 'action.hover', display: 'flex', justifyContent: 'space-between' }}>
                   <Typography variant="caption" color="text.secondary">{comment.author === 'user' ? 'You' : 'System'}</Typography>
                   <Typography variant="caption" color="text.secondary">{new Date(comment.created_at).toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' })}</Typography>
                 </Box>
+                {repliesTo(comment.id).map((reply) => (
+                  <Box key={reply.id} sx={{ mt: 1, pl: 1.5, borderLeft: 2, borderColor: 'outlineVariant' }}>
+                    <Typography variant="body2" sx={{ fontSize: '0.75rem' }}>{reply.content}</Typography>
+                    <Typography variant="caption" color="text.secondary">
+                      {reply.author === 'user' ? 'You' : 'System'} · {new Date(reply.created_at).toLocaleTimeString([], { hour: '2-digit', minute: '2-digit' })}
+                    </Typography>
+                  </Box>
+                ))}
               </CardContent>
             </Card>
           ))}
@@ -271,13 +324,20 @@ function CommentsSidebar({ session, onAddComment, onResolveComment }: CommentsSi
             <Box key={comment.id} sx={{ opacity: 0.5, p: 1 }}>
               <Stack direction="row" spacing={1} alignItems="center">
                 <CheckCircleIcon sx={{ fontSize: 14, color: 'success.main' }} />
-                <Typography variant="caption" fontWeight={600}>{getTargetDisplay(comment.target)}</Typography>
+                <Typography variant="caption" fontWeight={600}>
+                  {comment.anchor ? getAnchorDisplay(comment.anchor) : getTargetDisplay(comment.target)}
+                </Typography>
               </Stack>
               <Typography variant="caption" sx={{ fontStyle: 'italic', display: 'block', mt: 0.5 }}>{comment.content}</Typography>
+              {repliesTo(comment.id).length > 0 && (
+                <Typography variant="caption" color="text.secondary" sx={{ display: 'block' }}>
+                  {repliesTo(comment.id).length} {repliesTo(comment.id).length === 1 ? 'reply' : 'replies'}
+                </Typography>
+              )}
             </Box>
           ))}
 
-          {session.comments.length === 0 && (
+          {comments.length === 0 && (
             <Box sx={{ py: 8, textAlign: 'center' }}>
               <MessageSquareIcon sx={{ fontSize: 48, color: 'text.disabled', opacity: 0.3, mb: 1 }} />
               <Typography variant="caption" display="block" color="text.secondary">No feedback yet</Typography>
@@ -288,21 +348,66 @@ function CommentsSidebar({ session, onAddComment, onResolveComment }: CommentsSi
 
       {/* Add Comment Form */}
       <Box sx={{ p: 2, borderTop: 1, borderColor: 'outlineVariant', bgcolor: 'background.paper' }}>
+        {replyTo && (
+          <Stack direction="row" alignItems="center" spacing={1} sx={{ mb: 1 }}>
+            <ReplyIcon sx={{ fontSize: 14, color: 'text.secondary' }} />
+            <Typography variant="caption" color="text.secondary" noWrap sx={{ flex: 1 }}>
+              Replying to: {replyTo.content}
+            </Typography>
+            <Button size="small" onClick={() => setReplyTo(null)} sx={{ minWidth: 0, fontSize: '0.65rem' }}>
+              Cancel
+            </Button>
+          </Stack>
+        )}
         <TextField
           fullWidth
           multiline
           rows={3}
-          placeholder="Add your feedback..."
+          placeholder={replyTo ? 'Write a reply...' : 'Add your feedback...'}
           value={newCommentContent}
           onChange={(e) => setNewCommentContent(e.target.value)}
           variant="outlined"
           size="small"
           sx={{ mb: 1.5, '& .MuiInputBase-root': { fontSize: '0.8rem' } }}
         />
+        {!replyTo && newCommentTargetType === 'file' && (
+          <Stack direction="row" spacing={1} sx={{ mb: 1.5 }}>
+            <FormControl fullWidth size="small">
+              <InputLabel sx={{ fontSize: '0.75rem' }}>File</InputLabel>
+              <Select
+                label="File"
+                value={anchorFile}
+                onChange={(e) => setAnchorFile(e.target.value)}
+                sx={{ fontSize: '0.75rem', height: 32 }}
+              >
+                {files.map((file) => (
+                  <MenuItem key={file} value={file} sx={{ fontSize: '0.75rem', fontFamily: 'monospace' }}>{file}</MenuItem>
+                ))}
+              </Select>
+            </FormControl>
+            <TextField
+              label="From"
+              type="number"
+              size="small"
+              value={lineStart}
+              onChange={(e) => setLineStart(e.target.value)}
+              sx={{ width: 72, flexShrink: 0, '& .MuiInputBase-root': { fontSize: '0.75rem', height: 32 } }}
+            />
+            <TextField
+              label="To"
+              type="number"
+              size="small"
+              value={lineEnd}
+              onChange={(e) => setLineEnd(e.target.value)}
+              sx={{ width: 72, flexShrink: 0, '& .MuiInputBase-root': { fontSize: '0.75rem', height: 32 } }}
+            />
+          </Stack>
+        )}
         <Stack direction="row" spacing={1}>
           <FormControl fullWidth size="small">
             <Select
               value={newCommentTargetType}
+              disabled={replyTo !== null}
               onChange={(e) => setNewCommentTargetType(e.target.value as any)}
               sx={{ fontSize: '0.75rem', height: 32 }}
             >
@@ -316,10 +421,10 @@ function CommentsSidebar({ session, onAddComment, onResolveComment }: CommentsSi
             size="small"
             onClick={handleAddComment}
             disabled={!newCommentContent.trim()}
-            startIcon={<PlusIcon />}
+            startIcon={replyTo ? <ReplyIcon /> : <PlusIcon />}
             sx={{ flexShrink: 0, height: 32, borderRadius: 1.5 }}
           >
-            Comment
+            {replyTo ? 'Reply' : 'Comment'}
           </Button>
         </Stack>
       </Box>
@@ -335,23 +440,28 @@ interface ActionBarProps {
 }
 
 function ActionBar({ session, onApprove, onRequestChanges, onReject }: ActionBarProps) {
-  const canApprove = session.status === 'reviewing'
+  const blocker = approvalBlocker(session)
+  const canApprove = session.status === 'reviewing' && blocker === null
   const canRequestChanges = session.status === 'reviewing'
   const canReject = session.status === 'reviewing'
 
   return (
     <Box sx={{ borderTop: 1, borderColor: 'outlineVariant', bgcolor: 'surfaceContainer.main', px: 3, py: 2 }}>
       <Stack direction="row" spacing={2} alignItems="center">
-        <Button
-          variant="contained"
-          color="success"
-          onClick={onApprove}
-          disabled={!canApprove}
-          startIcon={<CheckCircleIcon />}
-          sx={{ borderRadius: 2, px: 3 }}
-        >
-          Approve & Proceed
-        </Button>
+        <Tooltip title={blocker ?? ''}>
+          <span>
+            <Button
+              variant="contained"
+              color="success"
+              onClick={onApprove}
+              disabled={!canApprove}
+              startIcon={<CheckCircleIcon />}
+              sx={{ borderRadius: 2, px: 3 }}
+            >
+              Approve & Proceed
+            </Button>
+          </span>
+        </Tooltip>
         <Button
           variant="outlined"
           color="warning"
@@ -401,6 +511,23 @@ export function ReviewPanel() {
     )
   }, [reviewGate])
 
+  // The change under review keeps its comments across sessions (review.json)
+  const linkedChange = useMemo(() => {
+    if (!activeSession) return null
+    return (
+      worktree?.changes?.changes?.find(
+        (c) => c.proposal_review_session_id === activeSession.id || c.plan_review_session_id === activeSession.id
+      ) ?? null
+    )
+  }, [worktree, activeSession])
+  const linkedChangeId = linkedChange?.id
+
+  useEffect(() => {
+    if (linkedChangeId) {
+      dispatch({ type: 'LoadReviewComments', payload: { change_id: linkedChangeId } })
+    }
+  }, [linkedChangeId, dispatch])
+
   const handleSessionSelect = useCallback((sessionId: string) => {
     dispatch({ type: 'SetActiveReviewSession', payload: { session_id: sessionId } })
   }, [dispatch])
@@ -412,11 +539,11 @@ export function ReviewPanel() {
     }
   }, [])
 
-  const handleAddComment = useCallback((target: CommentTarget, content: string) => {
+  const handleAddComment = useCallback((target: CommentTarget, content: string, anchor: CommentAnchor | null, parentId: string | null) => {
     if (!activeSession) return
     dispatch({
       type: 'AddReviewComment',
-      payload: { session_id: activeSession.id, target, content },
+      payload: { session_id: activeSession.id, target, content, anchor, parent_id: parentId },
     })
   }, [activeSession, dispatch])
 
@@ -537,13 +664,15 @@ export function ReviewPanel() {
             <ContentView session={activeSession} onSectionClick={handleSectionClick} />
           </Box>
           <CommentsSidebar
-            session={activeSession}
+            comments={linkedChange?.review_comments ?? activeSession.comments}
+            files={activeSession.content.file_changes.map((change) => change.path)}
             onAddComment={handleAddComment}
             onResolveComment={handleResolveComment}
           />
         </Paper>
       </Box>
 
+      <ComplianceCard session={activeSession} />
       <ActionBar
         session={activeSession}
         onApprove={handleApprove}
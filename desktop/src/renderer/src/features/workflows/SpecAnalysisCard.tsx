@@ -0,0 +1,92 @@
+import { useState } from 'react'
+import { FactCheck as AnalyzeIcon, PlayArrow as RunIcon } from '@mui/icons-material'
+import { Button, Checkbox, Chip, FormControlLabel, LinearProgress, Paper, Stack, Typography } from '@mui/material'
+import type { Action, FindingKind, FindingSeverity, SpecAnalysis } from '@/types/state'
+
+const KIND_LABELS: Record<FindingKind, string> = {
+  uncovered_requirement: 'Uncovered',
+  unknown_reference: 'Unknown ref',
+  untraced_task: 'Untraced task',
+  contradiction: 'Contradiction',
+}
+
+const SEVERITY_COLORS: Record<FindingSeverity, 'default' | 'info' | 'warning' | 'error'> = {
+  low: 'default',
+  medium: 'info',
+  high: 'warning',
+  critical: 'error',
+}
+
+interface SpecAnalysisCardProps {
+  analysis: SpecAnalysis | null
+  /** spec.md, plan.md and tasks.md have been written */
+  canRun: boolean
+  dispatch: (action: Action) => Promise<void>
+}
+
+/**
+ * SpecAnalysisCard - Cross-artifact consistency check of spec.md, plan.md
+ * and tasks.md, optionally verified by Claude
+ */
+export function SpecAnalysisCard({ analysis, canRun, dispatch }: SpecAnalysisCardProps) {
+  const [verify, setVerify] = useState(false)
+  const isRunning = analysis?.status === 'running'
+  const report = analysis?.report
+
+  return (
+    <Paper variant="outlined" sx={{ p: 2, borderColor: 'outlineVariant' }}>
+      <Stack direction="row" alignItems="center" spacing={1}>
+        <AnalyzeIcon fontSize="small" color="primary" />
+        <Typography variant="subtitle2" fontWeight={600}>
+          Analyze
+        </Typography>
+        <Typography variant="caption" color="text.secondary" sx={{ flex: 1 }}>
+          Check the spec, plan and tasks against each other
+        </Typography>
+        <FormControlLabel
+          control={<Checkbox size="small" checked={verify} onChange={(e) => setVerify(e.target.checked)} />}
+          label={<Typography variant="caption">Verify with Claude</Typography>}
+        />
+        <Button
+          size="small"
+          variant="outlined"
+          startIcon={<RunIcon />}
+          disabled={!canRun || isRunning}
+          onClick={() => dispatch({ type: 'AnalyzeSpec', payload: { verify } })}
+        >
+          {report ? 'Re-run' : 'Run'}
+        </Button>
+      </Stack>
+
+      {isRunning && <LinearProgress sx={{ mt: 1.5 }} />}
+      {analysis?.error && (
+        <Typography variant="caption" color="error" sx={{ display: 'block', mt: 1 }}>
+          {analysis.error}
+        </Typography>
+      )}
+
+      {report && (
+        <>
+          <Typography variant="caption" color="text.secondary" sx={{ display: 'block', mt: 1.5, mb: 1 }}>
+            {report.covered}/{report.requirements} requirements covered by {report.tasks} tasks ·{' '}
+            {report.findings.length} findings{report.verified ? ' (verified by Claude)' : ''}
+          </Typography>
+          {report.findings.map((finding, index) => (
+            <Stack key={index} direction="row" alignItems="center" spacing={0.5} sx={{ py: 0.25 }}>
+              <Chip
+                label={finding.severity}
+                size="small"
+                color={SEVERITY_COLORS[finding.severity]}
+                sx={{ height: 16, fontSize: '0.6rem' }}
+              />
+              <Chip label={KIND_LABELS[finding.kind]} size="small" variant="outlined" sx={{ height: 16, fontSize: '0.6rem' }} />
+              <Typography variant="body2" sx={{ flex: 1, minWidth: 0 }} noWrap title={finding.artifacts.join(', ')}>
+                {finding.summary}
+              </Typography>
+            </Stack>
+          ))}
+        </>
+      )}
+    </Paper>
+  )
+}
@@ -0,0 +1,79 @@
+import { Refresh as RefreshIcon, TaskAlt as TasksIcon } from '@mui/icons-material'
+import { Box, Checkbox, Chip, IconButton, LinearProgress, Paper, Stack, Tooltip, Typography } from '@mui/material'
+import type { Action, SpecProgressReport, SpecTasksState } from '@/types/state'
+
+interface SpecTasksCardProps {
+  tasks: SpecTasksState
+  /** Progress notes from Claude, oldest first */
+  reports: SpecProgressReport[]
+  dispatch: (action: Action) => Promise<void>
+}
+
+/**
+ * SpecTasksCard - Implementation progress of tasks.md; checking a task
+ * rewrites its checkbox in the file. Claude checks tasks off and reports
+ * progress through the rstn_mark_task_done / rstn_report_progress MCP tools
+ */
+export function SpecTasksCard({ tasks, reports, dispatch }: SpecTasksCardProps) {
+  const { progress } = tasks
+  const latest = reports[reports.length - 1]
+  const percent = progress.total > 0 ? (progress.done / progress.total) * 100 : 0
+
+  return (
+    <Paper variant="outlined" sx={{ p: 2, borderColor: 'outlineVariant' }}>
+      <Stack direction="row" alignItems="center" spacing={1}>
+        <TasksIcon fontSize="small" color="primary" />
+        <Typography variant="subtitle2" fontWeight={600} sx={{ flex: 1 }}>
+          Tasks · {progress.done}/{progress.total} done
+        </Typography>
+        <Tooltip title="Reload tasks.md">
+          <IconButton size="small" onClick={() => dispatch({ type: 'LoadSpecTasks', payload: { tasks_path: tasks.tasks_path } })}>
+            <RefreshIcon fontSize="small" />
+          </IconButton>
+        </Tooltip>
+      </Stack>
+      <LinearProgress variant="determinate" value={percent} sx={{ my: 1.5 }} />
+
+      {latest && (
+        <Typography variant="caption" color="text.secondary" sx={{ display: 'block', mb: 1.5 }}>
+          Claude ({new Date(latest.timestamp).toLocaleTimeString()}): {latest.message}
+        </Typography>
+      )}
+
+      {progress.phases.map((phase) => (
+        <Box key={phase.phase} sx={{ mb: 1 }}>
+          <Typography variant="caption" fontWeight={600} color="text.secondary">
+            {phase.phase || 'Tasks'} ({phase.done}/{phase.total})
+          </Typography>
+          {tasks.items
+            .filter((item) => item.phase === phase.phase)
+            .map((item) => (
+              <Stack key={item.id} direction="row" alignItems="center" spacing={0.5}>
+                <Checkbox
+                  size="small"
+                  checked={item.done}
+                  onChange={(e) => dispatch({ type: 'ToggleSpecTask', payload: { id: item.id, done: e.target.checked } })}
+                  sx={{ p: 0.5 }}
+                />
+                <Typography variant="caption" sx={{ fontFamily: 'monospace', color: 'text.secondary' }}>
+                  {item.id}
+                </Typography>
+                {item.parallel && <Chip label="P" size="small" sx={{ height: 16, fontSize: '0.6rem' }} />}
+                {!item.done && latest?.task_id === item.id && (
+                  <Chip label="in progress" size="small" color="warning" sx={{ height: 16, fontSize: '0.6rem' }} />
+                )}
+                <Typography
+                  variant="body2"
+                  sx={{ flex: 1, minWidth: 0, textDecoration: item.done ? 'line-through' : 'none', opacity: item.done ? 0.6 : 1 }}
+                  noWrap
+                  title={item.files.length > 0 ? item.files.join('\n') : undefined}
+                >
+                  {item.description}
+                </Typography>
+              </Stack>
+            ))}
+        </Box>
+      ))}
+    </Paper>
+  )
+}
@@ -0,0 +1,231 @@
+import { useEffect, useState } from 'react'
+import {
+  Close as CloseIcon,
+  ListAlt as SpecIcon,
+  PlayArrow as RunIcon,
+  QuestionAnswer as QuestionIcon,
+  Stop as StopIcon,
+} from '@mui/icons-material'
+import { Box, Button, Checkbox, Chip, FormControlLabel, Paper, Stack, TextField, Typography } from '@mui/material'
+import ReactMarkdown from 'react-markdown'
+import { WorkflowHeader } from '@/components/shared/WorkflowHeader'
+import { EmptyState } from '@/components/shared/EmptyState'
+import { useActiveWorktree } from '@/hooks/useAppState'
+import { ChecklistsCard } from './ChecklistsCard'
+import { ClarifySessionCard } from './ClarifySessionCard'
+import { SpecAnalysisCard } from './SpecAnalysisCard'
+import { SpecTasksCard } from './SpecTasksCard'
+import type { SpecPhase, SpecPhaseRun, SpecPhaseStatus } from '@/types/state'
+
+const PHASE_INFO: Record<SpecPhase, { label: string; description: string; inputLabel?: string }> = {
+  specify: { label: 'Specify', description: 'Write spec.md from the feature description' },
+  clarify: {
+    label: 'Clarify',
+    description: 'Resolve ambiguities in spec.md',
+    inputLabel: 'Answers or notes (optional - otherwise assumptions are documented)',
+  },
+  plan: {
+    label: 'Plan',
+    description: 'Write plan.md from the spec',
+    inputLabel: 'Tech stack / architecture constraints (optional)',
+  },
+  tasks: { label: 'Tasks', description: 'Break the plan into tasks.md' },
+}
+
+const STATUS_COLORS: Record<SpecPhaseStatus, 'default' | 'warning' | 'success' | 'error'> = {
+  pending: 'default',
+  running: 'warning',
+  completed: 'success',
+  failed: 'error',
+}
+
+function PhaseCard({ run, isBusy, onRun, onCancel, onClarify }: {
+  run: SpecPhaseRun
+  isBusy: boolean
+  onRun: (input: string | null) => void
+  onCancel: () => void
+  /** Start an interactive clarify session (clarify phase only) */
+  onClarify?: () => void
+}) {
+  const [input, setInput] = useState('')
+  const info = PHASE_INFO[run.phase]
+  const isRunning = run.status === 'running'
+
+  return (
+    <Paper variant="outlined" sx={{ p: 2, borderColor: 'outlineVariant' }}>
+      <Stack direction="row" alignItems="center" spacing={1}>
+        <Typography variant="subtitle2" fontWeight={600}>
+          {info.label}
+        </Typography>
+        <Chip label={run.status} size="small" color={STATUS_COLORS[run.status]} sx={{ height: 20, fontSize: '0.65rem' }} />
+        <Typography variant="caption" color="text.secondary" sx={{ flex: 1 }}>
+          {info.description}
+        </Typography>
+        {isRunning ? (
+          <Button size="small" color="error" startIcon={<StopIcon />} onClick={onCancel}>
+            Cancel
+          </Button>
+        ) : (
+          <>
+            {onClarify && (
+              <Button size="small" startIcon={<QuestionIcon />} disabled={isBusy} onClick={onClarify}>
+                Ask me
+              </Button>
+            )}
+            <Button size="small" variant="outlined" startIcon={<RunIcon />} disabled={isBusy} onClick={() => onRun(input.trim() || null)}>
+              {run.status === 'pending' ? 'Run' : 'Re-run'}
+            </Button>
+          </>
+        )}
+      </Stack>
+
+      {info.inputLabel && !isRunning && (
+        <TextField
+          fullWidth
+          multiline
+          minRows={2}
+          size="small"
+          label={info.inputLabel}
+          value={input}
+          onChange={(e) => setInput(e.target.value)}
+          sx={{ mt: 1.5 }}
+        />
+      )}
+
+      {run.error && (
+        <Typography variant="caption" color="error" sx={{ display: 'block', mt: 1 }}>
+          {run.error}
+        </Typography>
+      )}
+
+      {run.output && (
+        <Box sx={{ mt: 1.5, maxHeight: 320, overflow: 'auto', typography: 'body2', '& h1, & h2, & h3': { fontSize: '1rem' } }}>
+          <ReactMarkdown>{run.output}</ReactMarkdown>
+        </Box>
+      )}
+    </Paper>
+  )
+}
+
+/**
+ * SpecWorkflowPanel - Spec-driven development: specify → clarify → plan → tasks,
+ * each phase streamed from Claude into specs/<NNN>-<name>/ of the worktree,
+ * then a consistency analysis of the three artifacts
+ */
+export function SpecWorkflowPanel() {
+  const { worktree, project, dispatch } = useActiveWorktree()
+  const [description, setDescription] = useState('')
+  const [useClaude, setUseClaude] = useState(true)
+  const spec = worktree?.workflows?.spec
+  const tasksPath =
+    spec && spec.phases.some((run) => run.phase === 'tasks' && run.status === 'completed')
+      ? `${spec.spec_dir}/tasks.md`
+      : null
+  const trackedPath = worktree?.workflows?.tasks?.tasks_path
+  const specDir = spec?.spec_dir
+
+  // Track tasks.md once the tasks phase has written it
+  useEffect(() => {
+    if (tasksPath && tasksPath !== trackedPath) {
+      dispatch({ type: 'LoadSpecTasks', payload: { tasks_path: tasksPath } })
+    }
+  }, [tasksPath, trackedPath, dispatch])
+
+  // Pick up checklists generated in an earlier session
+  useEffect(() => {
+    if (specDir) {
+      dispatch({ type: 'LoadChecklists' })
+    }
+  }, [specDir, dispatch])
+
+  if (!worktree) {
+    return <EmptyState title="No Project Open" description="Open a project to run the spec workflow" />
+  }
+
+  const workflow = worktree.workflows?.spec ?? null
+  const isBusy = !!workflow?.phases.some((run) => run.status === 'running')
+  const specWritten = !!workflow?.phases.some((run) => run.phase === 'specify' && run.status === 'completed')
+  const clarifySession = worktree.workflows?.clarify ?? null
+  const tasks = worktree.workflows?.tasks ?? null
+  const tasksCard = tasks && <SpecTasksCard tasks={tasks} reports={worktree.workflows?.reports ?? []} dispatch={dispatch} />
+
+  return (
+    <Box sx={{ display: 'flex', flexDirection: 'column', height: '100%' }}>
+      <WorkflowHeader
+        title="Spec Workflow"
+        subtitle={workflow ? `${workflow.spec_dir} - ${workflow.description}` : 'Specify, clarify, plan and break down a feature'}
+        icon={<SpecIcon />}
+      >
+        {workflow && (
+          <Button size="small" startIcon={<CloseIcon />} disabled={isBusy} onClick={() => dispatch({ type: 'ClearSpecWorkflow' })}>
+            Close
+          </Button>
+        )}
+      </WorkflowHeader>
+
+      <Box sx={{ flex: 1, overflow: 'auto', p: 3 }}>
+        {!workflow ? (
+          <Stack spacing={2}>
+            <TextField
+              fullWidth
+              multiline
+              minRows={4}
+              label="Feature description"
+              placeholder="What should the feature do, and for whom?"
+              value={description}
+              onChange={(e) => setDescription(e.target.value)}
+            />
+            <Stack direction="row" alignItems="center" spacing={2}>
+              <Button
+                variant="contained"
+                startIcon={<RunIcon />}
+                disabled={!description.trim()}
+                onClick={() =>
+                  dispatch({ type: 'StartSpecWorkflow', payload: { description: description.trim(), use_claude: useClaude } })
+                }
+              >
+                Start
+              </Button>
+              <FormControlLabel
+                control={<Checkbox size="small" checked={useClaude} onChange={(e) => setUseClaude(e.target.checked)} />}
+                label={<Typography variant="body2">Write the spec with Claude (off: fill the template)</Typography>}
+              />
+            </Stack>
+            {/* Tasks Claude is working through (e.g. tracked via MCP) */}
+            {tasksCard}
+          </Stack>
+        ) : (
+          <Stack spacing={1.5}>
+            {clarifySession && <ClarifySessionCard session={clarifySession} dispatch={dispatch} />}
+            {tasksCard}
+            <ChecklistsCard
+              checklists={worktree.workflows?.checklists ?? []}
+              required={!!project?.require_checklists}
+              canGenerate={specWritten}
+              dispatch={dispatch}
+            />
+            {workflow.phases.map((run) => (
+              <PhaseCard
+                key={run.phase}
+                run={run}
+                isBusy={isBusy}
+                onRun={(input) => dispatch({ type: 'RunSpecPhase', payload: { phase: run.phase, input } })}
+                onCancel={() => dispatch({ type: 'CancelSpecPhase' })}
+                onClarify={
+                  run.phase === 'clarify' && specWritten
+                    ? () => dispatch({ type: 'StartClarifySession', payload: { spec_path: `${workflow.spec_dir}/spec.md` } })
+                    : undefined
+                }
+              />
+            ))}
+            <SpecAnalysisCard
+              analysis={worktree.workflows?.analysis ?? null}
+              canRun={!!workflow.phases.find((run) => run.phase === 'tasks' && run.status === 'completed')}
+              dispatch={dispatch}
+            />
+          </Stack>
+        )}
+      </Box>
+    </Box>
+  )
+}
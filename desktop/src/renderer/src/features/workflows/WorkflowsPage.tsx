@@ -3,6 +3,10 @@ import {
   Description as DescriptionIcon,
   MenuBook as BookIcon,
   AccountTree as GitIcon,
+  ListAlt as SpecIcon,
+  ViewKanban as BoardIcon,
+  SmartToy as AgentIcon,
+  Security as AuditIcon,
   ChevronRight
 } from '@mui/icons-material'
 import {
@@ -18,9 +22,13 @@ import {
   useTheme
 } from '@mui/material'
 import { EmptyState } from '@/components/shared/EmptyState'
+import { AgentRunsPanel } from './AgentRunsPanel'
 import { ConstitutionPanel } from './ConstitutionPanel'
 import { ChangeManagementPanel } from './ChangeManagementPanel'
 import { ContextPanel } from './ContextPanel'
+import { DependencyAuditPanel } from './DependencyAuditPanel'
+import { FeatureBoardPanel } from './FeatureBoardPanel'
+import { SpecWorkflowPanel } from './SpecWorkflowPanel'
 
 /**
  * Available workflow definitions.
@@ -44,6 +52,30 @@ const WORKFLOWS = [
     description: 'Create and manage changes with proposal, plan generation, and review',
     icon: <GitIcon />,
   },
+  {
+    id: 'spec-workflow',
+    name: 'Spec Workflow',
+    description: 'Specify, clarify, plan and break a feature into tasks under specs/',
+    icon: <SpecIcon />,
+  },
+  {
+    id: 'feature-board',
+    name: 'Feature Board',
+    description: 'Pipeline of the spec-kit features: artifacts, task progress and branches',
+    icon: <BoardIcon />,
+  },
+  {
+    id: 'agent-runs',
+    name: 'Agent Runs',
+    description: 'Claude implementation runs in parallel across worktrees',
+    icon: <AgentIcon />,
+  },
+  {
+    id: 'dependency-audit',
+    name: 'Dependency Audit',
+    description: 'Known vulnerabilities in Cargo, npm and Python dependencies',
+    icon: <AuditIcon />,
+  },
 ]
 
 /**
@@ -61,6 +93,14 @@ export function WorkflowsPage() {
         return <ContextPanel />
       case 'change-management':
         return <ChangeManagementPanel />
+      case 'spec-workflow':
+        return <SpecWorkflowPanel />
+      case 'feature-board':
+        return <FeatureBoardPanel />
+      case 'agent-runs':
+        return <AgentRunsPanel />
+      case 'dependency-audit':
+        return <DependencyAuditPanel />
       default:
         // Use a generic icon for empty state
         return (
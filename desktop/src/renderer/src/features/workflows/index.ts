@@ -2,3 +2,4 @@ export { WorkflowsPage } from './WorkflowsPage'
 export { ConstitutionPanel } from './ConstitutionPanel'
 export { ContextPanel } from './ContextPanel'
 export { ReviewPanel } from './ReviewPanel'
+export { SpecWorkflowPanel } from './SpecWorkflowPanel'
@@ -0,0 +1,59 @@
+/**
+ * Keyboard shortcut helpers.
+ *
+ * Shortcuts come from `global_settings.keybindings` in the backend state and
+ * use Electron accelerator syntax, normalized by the backend
+ * (modifiers in CmdOrCtrl, Ctrl, Alt, Shift order, then the key).
+ */
+
+const isMac = (): boolean => navigator.platform.toUpperCase().includes('MAC')
+
+/** Keys whose `KeyboardEvent.key` differs from the accelerator name */
+const KEY_NAMES: Record<string, string> = {
+  ' ': 'Space',
+  ArrowUp: 'Up',
+  ArrowDown: 'Down',
+  ArrowLeft: 'Left',
+  ArrowRight: 'Right',
+  Esc: 'Escape',
+}
+
+/**
+ * Accelerator for a key event (e.g. "CmdOrCtrl+Shift+P"), or null while
+ * only modifiers are held.
+ */
+export function eventToShortcut(e: KeyboardEvent | React.KeyboardEvent): string | null {
+  if (['Meta', 'Control', 'Alt', 'Shift'].includes(e.key)) return null
+
+  const mac = isMac()
+  const parts: string[] = []
+  if (mac ? e.metaKey : e.ctrlKey) parts.push('CmdOrCtrl')
+  if (mac && e.ctrlKey) parts.push('Ctrl')
+  if (e.altKey) parts.push('Alt')
+  if (e.shiftKey) parts.push('Shift')
+
+  // Prefer the physical key for letters/digits so Shift/Alt don't change it
+  let key = KEY_NAMES[e.key] ?? e.key
+  const code = e.code ?? ''
+  if (/^Key[A-Z]$/.test(code)) key = code.slice(3)
+  else if (/^Digit[0-9]$/.test(code)) key = code.slice(5)
+  if (key.length === 1) key = key.toUpperCase()
+
+  parts.push(key)
+  return parts.join('+')
+}
+
+/** Command bound to the shortcut of a key event */
+export function commandForEvent(
+  e: KeyboardEvent,
+  keybindings: Record<string, string> | undefined
+): string | null {
+  const shortcut = eventToShortcut(e)
+  if (!shortcut || !keybindings) return null
+  return Object.entries(keybindings).find(([, s]) => s === shortcut)?.[0] ?? null
+}
+
+/** Show platform-specific modifier names (CmdOrCtrl -> ⌘ / Ctrl) */
+export function formatShortcut(shortcut: string): string {
+  return shortcut.replace('CmdOrCtrl', isMac() ? '⌘' : 'Ctrl')
+}
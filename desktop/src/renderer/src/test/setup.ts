@@ -5,12 +5,27 @@ import { vi } from 'vitest'
 const mockStateApi = {
   dispatch: vi.fn().mockResolvedValue(undefined),
   getState: vi.fn().mockResolvedValue('{}'),
+  listPaletteActions: vi.fn().mockResolvedValue([]),
+  listConstitutionStacks: vi.fn().mockResolvedValue([]),
   onStateUpdate: vi.fn().mockReturnValue(() => {}),
 }
 
 // Mock window.dialogApi
 const mockDialogApi = {
   openFolder: vi.fn().mockResolvedValue(null),
+  openFiles: vi.fn().mockResolvedValue([]),
+}
+
+// Mock window.markdownApi (backend markdown renderer; content passed through
+// as a paragraph)
+const mockMarkdownApi = {
+  render: vi.fn(async (content: string) => ({
+    html: `<p>${content}</p>`,
+    headings: [],
+    codeBlocks: [],
+    mermaid: [],
+  })),
+  renderFile: vi.fn().mockRejectedValue(new Error('Not available in tests')),
 }
 
 // Mock clipboard API
@@ -31,8 +46,13 @@ Object.defineProperty(window, 'dialogApi', {
   writable: true,
 })
 
+Object.defineProperty(window, 'markdownApi', {
+  value: mockMarkdownApi,
+  writable: true,
+})
+
 // Export mocks for test files to access
-export { mockStateApi, mockDialogApi }
+export { mockStateApi, mockDialogApi, mockMarkdownApi }
 
 // Realistic ResizeObserver mock that simulates browser behavior
 class ResizeObserverMock {
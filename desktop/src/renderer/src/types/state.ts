@@ -73,6 +73,36 @@ export interface FileTab {
   is_pinned: boolean
 }
 
+export interface SearchMatch {
+  /** 1-based */
+  line_number: number
+  /** 1-based, in chars */
+  column: number
+  preview: string
+}
+
+export interface SearchFileMatches {
+  /** Relative to the worktree root */
+  path: string
+  matches: SearchMatch[]
+}
+
+export interface SearchResults {
+  files: SearchFileMatches[]
+  total_matches: number
+  /** More matches exist than were returned */
+  truncated: boolean
+}
+
+export interface SearchState {
+  query: string
+  glob: string | null
+  case_sensitive: boolean
+  results: SearchResults
+  is_searching: boolean
+  error: string | null
+}
+
 export interface FileExplorerState {
   current_path: string
   entries: FileEntry[]
@@ -90,6 +120,45 @@ export interface FileExplorerState {
   tabs: FileTab[]
   /** Currently active tab path */
   active_tab_path?: string
+  /** Git operation (stage/commit/push/pull) in progress */
+  git_busy: boolean
+  /** Last pre-commit security scan that blocked a commit */
+  security_scan: SecurityScanResult | null
+  /** Commit history of the selected file */
+  file_history?: FileHistory | null
+}
+
+/** A commit that changed a file */
+export interface FileCommit {
+  hash: string
+  short_hash: string
+  author: string
+  email: string
+  /** Author date (ISO 8601) */
+  date: string
+  /** First line of the message */
+  summary: string
+}
+
+/** Commits that touched a file, newest first */
+export interface FileHistory {
+  /** Absolute path of the file */
+  path: string
+  commits: FileCommit[]
+  is_loading: boolean
+  /** Set if git failed (e.g. the file is not in a repository) */
+  error?: string | null
+}
+
+export interface SecurityFinding {
+  file: string
+  /** Line in the new file (null for file-level findings like .env) */
+  line: number | null
+  kind: string
+}
+
+export interface SecurityScanResult {
+  findings: SecurityFinding[]
 }
 
 // ============================================================================
@@ -111,6 +180,61 @@ export interface DockerServiceInfo {
   is_rstn_managed: boolean
 }
 
+/** Services started and stopped together (from .rstn/services.toml) */
+/** A request of a collection; `{{NAME}}` placeholders are resolved when sent */
+export interface HttpRequestDef {
+  name: string
+  method: string
+  url: string
+  headers?: Record<string, string>
+  body?: string
+  timeout_secs?: number
+}
+
+/** Requests of one .rstn/requests/<id>.toml file */
+export interface RequestCollection {
+  /** File stem */
+  id: string
+  name: string
+  vars?: Record<string, string>
+  requests: HttpRequestDef[]
+  /** Why the file couldn't be loaded */
+  error?: string
+}
+
+export interface HttpResponseData {
+  status: number
+  status_text: string
+  headers: [string, string][]
+  body: string
+  body_truncated: boolean
+  size_bytes: number
+  duration_ms: number
+}
+
+export interface HttpRunResult {
+  response?: HttpResponseData
+  /** Why no response was received */
+  error?: string
+  finished_at: string
+}
+
+export interface HttpRequestsState {
+  collections: RequestCollection[]
+  /** Requests being sent ("<collection>/<request>") */
+  running?: string[]
+  /** Last result of each request ("<collection>/<request>") */
+  results?: Record<string, HttpRunResult>
+}
+
+export interface ServiceGroup {
+  name: string
+  /** Service IDs (container names) */
+  services: string[]
+  /** Service ID -> services of the group it needs first */
+  depends_on?: Record<string, string[]>
+}
+
 export interface ConflictingContainer {
   id: string
   name: string
@@ -118,9 +242,19 @@ export interface ConflictingContainer {
   is_rstn_managed: boolean
 }
 
+export interface ConflictingProcess {
+  /** 0 when the process holding the port could not be identified */
+  pid: number
+  name: string
+  exe: string | null
+  user: string | null
+  command: string | null
+}
+
 export interface PortConflict {
   requested_port: number
   conflicting_container: ConflictingContainer
+  conflicting_process?: ConflictingProcess | null
   suggested_port: number
 }
 
@@ -139,6 +273,270 @@ export interface DockersState {
   pending_conflict: PendingConflict | null
   port_overrides: Record<string, number>
   last_connection_string: string | null
+  /** Image pulls started via dockerPullImage (image -> progress) */
+  image_pulls?: Record<string, ImagePullState>
+  /** Recent resource samples per service (dockerStatsStream), oldest first */
+  stats?: Record<string, ContainerStats[]>
+  /** Health of services started from rstn (cleared on stop) */
+  service_health?: Record<string, ServiceHealth>
+  /** Databases inside running database services (service_id -> databases) */
+  databases?: Record<string, DatabaseInfo[]>
+  is_loading_databases?: boolean
+  /** Interactive exec sessions (service_id -> terminal session ID) */
+  exec_sessions?: Record<string, string>
+  /** Local reverse proxy */
+  proxy?: ProxyStatus
+}
+
+export interface DatabaseInfo {
+  name: string
+  size_bytes: number | null
+  /** Present once listed via FetchDockerTables */
+  tables?: TableInfo[]
+}
+
+export interface TableInfo {
+  /** Schema (PostgreSQL/MySQL), null for MongoDB collections */
+  schema: string | null
+  name: string
+  row_count: number | null
+}
+
+export type ServiceHealth = 'starting' | 'healthy' | 'unhealthy'
+
+export interface ContainerStats {
+  /** CPU usage across all cores (100 = one full core) */
+  cpu_percent: number
+  memory_usage: number
+  memory_limit: number
+  net_rx_bytes: number
+  net_tx_bytes: number
+  timestamp: string
+}
+
+export interface ImageLayerProgress {
+  id: string
+  status: string
+  current: number | null
+  total: number | null
+}
+
+export interface ImagePullState {
+  status: string
+  layers: ImageLayerProgress[]
+  is_pulling: boolean
+  error?: string
+}
+
+// ============================================================================
+// Spec-kit Workflow
+// ============================================================================
+
+export type SpecPhase = 'specify' | 'clarify' | 'plan' | 'tasks'
+
+export type SpecPhaseStatus = 'pending' | 'running' | 'completed' | 'failed'
+
+export interface SpecPhaseRun {
+  phase: SpecPhase
+  status: SpecPhaseStatus
+  /** Streamed output of the last run */
+  output: string
+  error?: string
+}
+
+export interface SpecWorkflow {
+  feature_number: number
+  feature_name: string
+  /** Feature directory relative to the worktree (e.g. "specs/007-user-login") */
+  spec_dir: string
+  description: string
+  /** Generate spec.md with Claude (off: fill the template) */
+  use_claude: boolean
+  /** One entry per phase, in workflow order */
+  phases: SpecPhaseRun[]
+}
+
+export type ClarifyCategory =
+  | 'functional_scope'
+  | 'data_model'
+  | 'ux_flow'
+  | 'non_functional'
+  | 'integrations'
+  | 'edge_cases'
+  | 'constraints'
+  | 'completion_signals'
+  | 'placeholders'
+
+export type CoverageStatus = 'clear' | 'partial' | 'missing' | 'resolved'
+
+export interface CategoryCoverage {
+  category: ClarifyCategory
+  status: CoverageStatus
+}
+
+export interface ClarifyQuestion {
+  id: string
+  category: ClarifyCategory
+  question: string
+  /** Full [NEEDS CLARIFICATION: ...] marker the answer replaces */
+  marker?: string
+}
+
+export type ClarifySessionStatus = 'asking' | 'integrating' | 'completed' | 'failed'
+
+export interface ClarifyAnswer {
+  question_id: string
+  /** null = skipped */
+  value: string | null
+}
+
+export interface ClarifySession {
+  /** Spec path relative to the worktree */
+  spec_path: string
+  status: ClarifySessionStatus
+  coverage: CategoryCoverage[]
+  questions: ClarifyQuestion[]
+  answers: ClarifyAnswer[]
+  error?: string
+}
+
+export interface TaskItem {
+  /** Task ID (e.g. "T001") */
+  id: string
+  /** Heading the task is listed under */
+  phase: string
+  description: string
+  /** Marked [P]: can run in parallel */
+  parallel: boolean
+  files: string[]
+  done: boolean
+  line: number
+}
+
+export interface PhaseProgress {
+  phase: string
+  total: number
+  done: number
+}
+
+export interface TaskProgress {
+  total: number
+  done: number
+  phases: PhaseProgress[]
+}
+
+export interface SpecTasksState {
+  /** tasks.md path relative to the worktree */
+  tasks_path: string
+  items: TaskItem[]
+  progress: TaskProgress
+}
+
+/** Progress note reported by Claude (rstn_report_progress MCP tool) */
+export interface SpecProgressReport {
+  message: string
+  task_id?: string
+  timestamp: string
+}
+
+export type ChecklistKind = 'security' | 'a11y' | 'performance'
+
+export interface ChecklistItem {
+  /** Item ID (e.g. "CHK001") */
+  id: string
+  text: string
+  checked: boolean
+  /** Section heading the item is listed under */
+  section: string
+  line: number
+}
+
+export type SpecChecklistStatus = 'generating' | 'ready' | 'failed'
+
+export interface SpecChecklist {
+  kind: ChecklistKind
+  /** checklist-<kind>.md path relative to the worktree */
+  path: string
+  status: SpecChecklistStatus
+  items: ChecklistItem[]
+  error?: string
+}
+
+export type FindingKind = 'uncovered_requirement' | 'unknown_reference' | 'untraced_task' | 'contradiction'
+
+export type FindingSeverity = 'low' | 'medium' | 'high' | 'critical'
+
+export interface AnalysisFinding {
+  kind: FindingKind
+  severity: FindingSeverity
+  /** Artifacts involved (e.g. ["spec.md", "tasks.md"]) */
+  artifacts: string[]
+  /** Requirement or task ID the finding is about */
+  subject?: string
+  summary: string
+  source: 'heuristic' | 'claude'
+}
+
+/** Cross-artifact consistency report (analysis.json) */
+export interface AnalysisReport {
+  requirements: number
+  /** Requirements referenced by at least one task */
+  covered: number
+  tasks: number
+  /** Most severe first */
+  findings: AnalysisFinding[]
+  /** Claude verified the findings */
+  verified: boolean
+  analyzed_at: string
+}
+
+export interface SpecAnalysis {
+  status: Exclude<SpecPhaseStatus, 'pending'>
+  verify: boolean
+  /** Latest report (kept while a re-run is in progress) */
+  report?: AnalysisReport
+  error?: string
+}
+
+export type FeatureStage = 'draft' | 'specified' | 'planned' | 'tasked' | 'implementing' | 'done'
+
+export interface FeatureArtifacts {
+  spec: boolean
+  plan: boolean
+  tasks: boolean
+  checklists: ChecklistKind[]
+  /** analysis.json present */
+  analysis: boolean
+}
+
+/** A spec-kit feature of the worktree (specs/features.json + its directory) */
+export interface FeatureInfo {
+  number: number
+  short_name: string
+  /** Directory relative to the worktree (e.g. "specs/007-user-login") */
+  dir: string
+  description?: string
+  created_at?: string
+  artifacts: FeatureArtifacts
+  tasks_done: number
+  tasks_total: number
+  /** Feature branch name ("007-user-login") */
+  branch: string
+  branch_exists: boolean
+  stage: FeatureStage
+}
+
+export interface WorkflowsState {
+  spec: SpecWorkflow | null
+  clarify: ClarifySession | null
+  tasks: SpecTasksState | null
+  /** Oldest first */
+  reports: SpecProgressReport[]
+  /** In security, a11y, performance order */
+  checklists: SpecChecklist[]
+  analysis: SpecAnalysis | null
+  /** Features of the worktree for the pipeline board */
+  features: FeatureInfo[]
 }
 
 // ============================================================================
@@ -172,6 +570,104 @@ export interface Change {
   plan_review_session_id: string | null
   /** Source files selected for context injection */
   context_files: string[]
+  /** Plan steps tracked during implementation */
+  implementation_tasks?: ImplementationTask[]
+  /** Review discussion, saved to .rstn/changes/<name>/review.json */
+  review_comments?: ReviewComment[]
+  /** Dedicated branch of the change (change/<name>) */
+  branch?: string
+  /** Pull/merge request opened for the branch */
+  pull_request_url?: string
+  /** Worktree snapshot taken before the last implementation */
+  snapshot?: ChangeSnapshot
+  /** Results of the last test run; a red run blocks review approval */
+  test_results?: TestResults
+  /** Lint / format hooks (.rstn/hooks.toml) run after the last implementation */
+  hook_results?: HookResult[]
+  /** Shared status in status.json disagrees with ours (e.g. after a merge) */
+  status_conflict?: StatusConflict
+  /** Tool call of the running implementation that waits for the user */
+  implementation_approval?: AgentRunApproval
+}
+
+/** Conflicting statuses of a change shared over git */
+export interface StatusConflict {
+  /** Status this machine last synced */
+  ours: ChangeStatus
+  /** Status in status.json */
+  theirs: ChangeStatus
+  /** Who recorded theirs */
+  theirs_by?: string
+}
+
+/** Outcome of a post-implementation hook */
+export interface HookResult {
+  name: string
+  command: string
+  success: boolean
+  exit_code?: number
+  duration_ms: number
+  ran_at: string
+  /** Why the hook could not run or was stopped */
+  error?: string
+  /** Task run holding the hook's output */
+  task_id?: string
+}
+
+/** Commit of the worktree taken before Claude implemented a change */
+export interface ChangeSnapshot {
+  commit: string
+  /** HEAD when the snapshot was taken */
+  base: string
+  branch?: string
+  created_at: string
+  restored_at?: string
+}
+
+/** Parsed outcome of a test run (cargo test, pytest, Jest, Vitest) */
+export interface TestResults {
+  command: string
+  /** Whether the command exited successfully */
+  success: boolean
+  passed: number
+  failed: number
+  ignored: number
+  failing_tests?: string[]
+  duration_ms: number
+  ran_at: string
+  /** Reviews may be approved despite the failure */
+  overridden: boolean
+  /** Line coverage, when the run collected it */
+  coverage?: CoverageSummary
+}
+
+/** Line coverage of a test run compared with the previous coverage run */
+export interface CoverageSummary {
+  lines_covered: number
+  lines_total: number
+  percent: number
+  /** Least covered first */
+  files?: FileCoverage[]
+  previous_percent?: number
+  /** Files whose coverage dropped since the previous run */
+  regressions?: CoverageRegression[]
+}
+
+export interface FileCoverage {
+  path: string
+  lines_covered: number
+  lines_total: number
+}
+
+export interface CoverageRegression {
+  path: string
+  before: number
+  after: number
+}
+
+export interface ImplementationTask {
+  title: string
+  done: boolean
 }
 
 export type ValidationResult =
@@ -234,6 +730,81 @@ export interface JustCommandInfo {
   name: string
   description: string | null
   recipe: string
+  parameters: JustParameterInfo[]
+}
+
+export type TaskProviderKind = 'just' | 'npm' | 'yarn' | 'pnpm' | 'make' | 'cargo'
+
+export interface TaskDescriptor {
+  /** "<provider>:<name>" */
+  id: string
+  provider: TaskProviderKind
+  name: string
+  description: string | null
+  /** Recipe, script body, make recipe or alias expansion */
+  command: string
+  parameters: JustParameterInfo[]
+}
+
+export type TaskRunStatus = 'queued' | 'running' | 'success' | 'error' | 'cancelled'
+
+export interface TaskRun {
+  /** Run ID (used by CancelTask) */
+  id: string
+  /** Key in task_statuses (just recipe name or "<provider>:<name>") */
+  task_key: string
+  command: string
+  worktree_path: string
+  status: TaskRunStatus
+  queued_at: string
+  exit_code: number | null
+  duration_ms: number | null
+  /** Last lines of stdout/stderr */
+  output_tail: string[]
+}
+
+export type AgentRunStatus = 'queued' | 'running' | 'awaiting_approval' | 'awaiting_review' | 'failed' | 'cancelled'
+
+/** A tool call of a paused run, waiting for the user's decision */
+export interface AgentRunApproval {
+  request_id: string
+  tool_name: string
+  /** Short description of the input (command, file path...) */
+  input_summary: string
+  /** Which guardrail the call breaks */
+  reason: string
+}
+
+/** One change being implemented by Claude in one worktree */
+export interface AgentRun {
+  id: string
+  worktree_path: string
+  branch: string
+  change_id: string
+  change_name: string
+  status: AgentRunStatus
+  queued_at: string
+  started_at?: string
+  finished_at?: string
+  /** Characters streamed into the change so far */
+  output_chars: number
+  error?: string
+  approval?: AgentRunApproval
+}
+
+export interface AgentRunsState {
+  /** In queue order */
+  runs: AgentRun[]
+  /** Number of runs that may run at once */
+  max_concurrent: number
+}
+
+export interface JustParameterInfo {
+  name: string
+  /** Default value (null = required) */
+  default: string | null
+  /** `+`/`*` parameter accepting any number of values */
+  variadic: boolean
 }
 
 /**
@@ -289,8 +860,39 @@ export interface ConstitutionPresetsConfig {
   temp_file_path?: string
 }
 
+// ============================================================================
+// Constitution Lint
+// ============================================================================
+
+export type LintSeverity = 'error' | 'warning' | 'info'
+
+export type LintRule = 'missing_section' | 'phrasing' | 'contradiction' | 'stale_reference' | 'frontmatter'
+
+export interface LintFinding {
+  rule: LintRule
+  severity: LintSeverity
+  /** File relative to the worktree (absent = the constitution as a whole) */
+  file?: string
+  /** 1-based line number */
+  line?: number
+  message: string
+}
+
+export interface ConstitutionLintReport {
+  /** Files that were linted (relative to the worktree) */
+  files: string[]
+  /** Findings, most severe first */
+  findings: LintFinding[]
+  /** When the lint ran (RFC 3339) */
+  linted_at: string
+}
+
 export interface TasksState {
   commands: JustCommandInfo[]
+  /** Tasks from every detected provider */
+  tasks: TaskDescriptor[]
+  /** Queued, running and recently finished task runs */
+  runs: TaskRun[]
   task_statuses: Record<string, TaskStatus>
   active_command: string | null
   output: string[]
@@ -302,6 +904,10 @@ export interface TasksState {
   constitution_exists: boolean | null
   /** Constitution content (null = not read yet) */
   constitution_content: string | null
+  /** Last constitution lint report */
+  constitution_lint?: ConstitutionLintReport
+  /** A constitution lint is running */
+  constitution_linting: boolean
   /** Whether project root has CLAUDE.md (null = not checked yet) */
   claude_md_exists: boolean | null
   /** CLAUDE.md content for preview (null = not read yet) */
@@ -347,6 +953,13 @@ export type CommentTarget =
   | { type: 'section'; id: string }
   | { type: 'file'; path: string }
 
+/** File lines a review comment is anchored to (1-based, inclusive) */
+export interface CommentAnchor {
+  file: string
+  line_start: number
+  line_end: number
+}
+
 export interface ReviewComment {
   id: string
   target: CommentTarget
@@ -354,6 +967,9 @@ export interface ReviewComment {
   author: CommentAuthor
   resolved: boolean
   created_at: string
+  anchor?: CommentAnchor
+  /** Comment this one replies to */
+  parent_id?: string
 }
 
 export interface ReviewSession {
@@ -364,10 +980,40 @@ export interface ReviewSession {
   policy: ReviewPolicy
   comments: ReviewComment[]
   iteration: number
+  /** Constitution compliance pre-check of the current iteration */
+  compliance?: ComplianceCheck
   created_at: string
   updated_at: string
 }
 
+export type ViolationSeverity = 'blocking' | 'warning' | 'info'
+
+export interface ComplianceViolation {
+  /** Constitution section the rule belongs to */
+  section: string
+  severity: ViolationSeverity
+  rule: string
+  explanation: string
+}
+
+export interface ComplianceReport {
+  summary: string
+  violations: ComplianceViolation[]
+  evaluated_at: string
+}
+
+export type ComplianceStatus = 'running' | 'passed' | 'failed' | 'skipped' | 'error'
+
+export interface ComplianceCheck {
+  status: ComplianceStatus
+  /** Session iteration that was evaluated */
+  iteration: number
+  report?: ComplianceReport
+  error?: string
+  /** Reason given for approving despite blocking violations */
+  override_reason?: string
+}
+
 export interface ReviewGateState {
   sessions: Record<string, ReviewSession>
   active_session_id: string | null
@@ -390,8 +1036,66 @@ export interface EnvConfig {
   auto_copy_enabled: boolean
   source_worktree: string | null
   last_copy_result: EnvCopyResult | null
+  auto_resolve_ports: PortConflictStrategy
+  /** Dry-run preview of a pending InjectConnectionString */
+  injection_preview?: EnvInjectionPreview
+  /** Result of the last DiffEnvFiles */
+  last_diff?: EnvDiff
+  /** Secrets in the active worktree's tracked env files (ScanEnvSecrets) */
+  secrets?: EnvSecret[]
+}
+
+export type SecretKind = 'api_key' | 'token' | 'password' | 'private_key' | 'connection_string'
+
+export interface EnvSecret {
+  /** Relative to the worktree (e.g. ".env") */
+  file: string
+  key: string
+  kind: SecretKind
+  masked_value: string
+}
+
+export interface EnvDiff {
+  from_worktree_path: string
+  to_worktree_path: string
+  /** Files with differences */
+  files: EnvFileDiff[]
+  timestamp: string
+}
+
+export interface EnvFileDiff {
+  /** Relative to the worktree (e.g. ".env") */
+  file: string
+  keys: EnvKeyDiff[]
 }
 
+/** Relative to the target: added = only in source, removed = only in target */
+export type EnvKeyChange = 'added' | 'removed' | 'changed'
+
+export interface EnvKeyDiff {
+  key: string
+  change: EnvKeyChange
+  /** Masked values */
+  from_value: string | null
+  to_value: string | null
+}
+
+/** Preview of writing a connection string into an env file (values masked) */
+export interface EnvInjectionPreview {
+  service_id: string
+  db_name: string
+  /** Env file relative to the worktree (e.g. ".env") */
+  env_file: string
+  file_path: string
+  var_name: string
+  masked_value: string
+  /** Current value (masked), null if the variable is not set */
+  previous_value: string | null
+  file_exists: boolean
+}
+
+export type PortConflictStrategy = 'Never' | 'SuggestNext' | 'AlwaysNext'
+
 // ============================================================================
 // Agent Rules (Project scope)
 // ============================================================================
@@ -403,6 +1107,23 @@ export interface AgentProfile {
   is_builtin: boolean
   created_at: string
   updated_at: string
+  /** Sharing metadata (set for imported profiles) */
+  metadata?: AgentProfileMetadata
+}
+
+export interface AgentProfileMetadata {
+  description: string
+  author: string
+  version: string
+}
+
+/** Shareable single-file agent profile (TOML) */
+export interface AgentProfileFile {
+  name: string
+  description: string
+  author: string
+  version: string
+  prompt: string
 }
 
 export interface AgentRulesConfig {
@@ -462,6 +1183,64 @@ export interface McpState {
   error?: string
   log_entries?: McpLogEntry[]
   available_tools?: McpTool[]
+  tool_metrics?: McpToolMetrics[]
+  /** Which tools the server exposes (from .rstn/mcp-policy.toml) */
+  policy: McpPolicy
+  /** External servers from the worktree's .mcp.json */
+  external_servers?: ExternalMcpServer[]
+  /** Manual tool invocations on external servers */
+  playground: McpPlaygroundState
+}
+
+export type McpTransport = 'stdio' | 'http'
+
+export type ExternalMcpStatus = 'connecting' | 'connected' | 'error'
+
+export interface ExternalMcpServer {
+  /** Name in .mcp.json */
+  name: string
+  transport: McpTransport
+  /** Command line or URL */
+  target: string
+  status: ExternalMcpStatus
+  error?: string
+  tools: McpTool[]
+}
+
+export interface McpPlaygroundEntry {
+  id: string
+  server: string
+  tool: string
+  arguments: Record<string, unknown>
+  /** ISO 8601 timestamp of the call */
+  called_at: string
+  /** Undefined while the call is running */
+  duration_ms?: number
+  /** `tools/call` result */
+  result?: unknown
+  error?: string
+}
+
+export interface McpPlaygroundState {
+  /** Newest first */
+  history: McpPlaygroundEntry[]
+}
+
+/** Tool patterns (a trailing `*` matches any suffix); deny wins over allow */
+export interface McpPolicy {
+  /** Tools to expose (empty = all) */
+  allow?: string[]
+  /** Tools to hide */
+  deny?: string[]
+}
+
+export interface McpToolMetrics {
+  tool_name: string
+  call_count: number
+  error_count: number
+  p50_ms: number
+  p95_ms: number
+  last_called_at?: string
 }
 
 // ============================================================================
@@ -476,12 +1255,83 @@ export interface ChatMessage {
   content: string
   timestamp: string
   is_streaming?: boolean
+  /** Response was cancelled before completing */
+  is_cancelled?: boolean
+  /** Files sent with the message */
+  attachments?: ChatAttachment[]
+  /** Tools the agent used while answering, in call order */
+  tool_calls?: ChatToolCall[]
+}
+
+/** A tool call made by the agent (file edit, command, search...) */
+export interface ChatToolCall {
+  id: string
+  name: string
+  /** Short description of the input (command, file path, pattern...) */
+  input_summary: string
+  /** Length of the message content when the call started (timeline position) */
+  content_offset: number
+  /** Start of the result (null while running) */
+  result_excerpt: string | null
+  is_error: boolean
+  duration_ms: number | null
+}
+
+/** How an attachment is sent: inlined text or a Claude CLI image input */
+export type ChatAttachmentKind = 'text' | 'image'
+
+export interface ChatAttachment {
+  name: string
+  path: string
+  kind: ChatAttachmentKind
+  /** Size in bytes */
+  size: number
+  /** Text was cut at the inline size limit */
+  truncated?: boolean
 }
 
 export interface ChatState {
   messages: ChatMessage[]
   is_typing: boolean
   error?: string
+  /** Claude CLI session ID, resumed by follow-up messages */
+  session_id?: string
+  /** Tool call waiting for approval (Claude is paused until answered) */
+  pending_tool_approval?: PendingToolApproval
+  /** File edits suggested by an assistant message, under review */
+  proposed_edits?: ProposedEditSet
+}
+
+export type ProposedEditStatus = 'pending' | 'accepted' | 'rejected' | 'applied'
+
+/** A change to one file, from a diff or a whole-file block */
+export interface ProposedEdit {
+  /** Path relative to the worktree root */
+  path: string
+  change: DiffFileStatus
+  /** Hunks of a diff (empty for whole-file blocks) */
+  hunks: DiffHunk[]
+  /** Full new content of a whole-file block */
+  content: string | null
+  additions: number
+  deletions: number
+  status: ProposedEditStatus
+}
+
+export interface ProposedEditSet {
+  message_id: string
+  edits: ProposedEdit[]
+  /** Where the previous versions were saved by the last apply */
+  backup_dir: string | null
+}
+
+export interface PendingToolApproval {
+  request_id: string
+  /** Assistant message being generated */
+  message_id: string
+  tool_name: string
+  /** Short description of the input (command, file path...) */
+  input_summary: string
 }
 
 // ============================================================================
@@ -494,6 +1344,83 @@ export interface TerminalState {
   rows: number
 }
 
+// ============================================================================
+// Dependency Audit
+// ============================================================================
+
+export type AuditSeverity = 'unknown' | 'low' | 'medium' | 'high' | 'critical'
+
+export type AuditTool = 'cargo' | 'npm' | 'pip'
+
+export interface Advisory {
+  /** RUSTSEC-, GHSA-, PYSEC- or CVE- identifier */
+  id: string
+  tool: AuditTool
+  package: string
+  installed_version?: string
+  severity: AuditSeverity
+  title: string
+  fix_version?: string
+  url?: string
+}
+
+export interface AuditReport {
+  /** Most severe first */
+  advisories: Advisory[]
+  /** Auditors that ran */
+  tools: AuditTool[]
+  /** Auditors that could not run */
+  errors?: string[]
+  /** ISO 8601 */
+  ran_at: string
+}
+
+// ============================================================================
+// Build Diagnostics
+// ============================================================================
+
+export type BuildChecker = 'cargo' | 'tsc'
+
+export type DiagnosticSeverity = 'error' | 'warning'
+
+export interface Diagnostic {
+  /** Path relative to the worktree */
+  file: string
+  line: number
+  column: number
+  severity: DiagnosticSeverity
+  message: string
+  /** Compiler code (E0308, TS2322, unused_variables) */
+  code?: string
+}
+
+export interface BuildDiagnostics {
+  checker: BuildChecker | null
+  /** Errors first, then warnings */
+  diagnostics: Diagnostic[]
+  error_count: number
+  warning_count: number
+  duration_ms: number
+  /** ISO 8601 */
+  checked_at: string
+  /** Why the check could not run */
+  error?: string
+}
+
+// ============================================================================
+// Language Servers
+// ============================================================================
+
+export type LanguageServer = 'rust_analyzer' | 'typescript' | 'pyright'
+
+export type LspServerStatus = 'starting' | 'running' | 'failed'
+
+export interface LspServerState {
+  server: LanguageServer
+  status: LspServerStatus
+  error?: string
+}
+
 // ============================================================================
 // Worktree State
 // ============================================================================
@@ -507,14 +1434,83 @@ export interface WorktreeState {
   chat: ChatState
   terminal: TerminalState
   explorer: FileExplorerState
+  /** Full-text search (Explorer search panel) */
+  search: SearchState
   is_modified: boolean
   active_tab: FeatureTab
   tasks: TasksState
   changes: ChangesState
   context: ContextState
+  /** Guided workflows (spec-kit) */
+  workflows: WorkflowsState
+  health: WorktreeHealth | null
+  diff: WorktreeDiff | null
+  is_loading_diff: boolean
+  /** Last dependency audit (null until RunDependencyAudit completes) */
+  audit: AuditReport | null
+  is_auditing: boolean
+  /** Last build check (null until RunBuildCheck completes) */
+  build: BuildDiagnostics | null
+  is_checking_build: boolean
+  /** Language servers started by hover / go-to-definition in the file viewer */
+  lsp_servers?: LspServerState[]
   // NOTE: dockers moved to AppState.docker (global scope)
 }
 
+export interface LastCommit {
+  hash: string
+  summary: string
+  timestamp: number
+}
+
+export interface WorktreeHealth {
+  dirty_files: number
+  upstream: string | null
+  ahead: number
+  behind: number
+  last_commit: LastCommit | null
+  is_stale: boolean
+}
+
+export interface WorktreeHealthData extends WorktreeHealth {
+  worktree_path: string
+}
+
+export type DiffFileStatus = 'added' | 'modified' | 'deleted' | 'renamed'
+export type DiffLineType = 'context' | 'added' | 'removed'
+
+export interface DiffLine {
+  line_type: DiffLineType
+  content: string
+  old_line: number | null
+  new_line: number | null
+}
+
+export interface DiffHunk {
+  header: string
+  old_start: number
+  old_lines: number
+  new_start: number
+  new_lines: number
+  lines: DiffLine[]
+}
+
+export interface DiffFile {
+  path: string
+  old_path: string | null
+  status: DiffFileStatus
+  is_binary: boolean
+  additions: number
+  deletions: number
+  hunks: DiffHunk[]
+}
+
+export interface WorktreeDiff {
+  base: string
+  head: string
+  files: DiffFile[]
+}
+
 // ============================================================================
 // Project State
 // ============================================================================
@@ -529,6 +1525,49 @@ export interface ProjectState {
   agent_rules_config: AgentRulesConfig
   available_branches: BranchInfo[]
   is_loading_branches: boolean
+  /** Claude model override for this project (unset = global setting) */
+  model?: string
+  /** Review approval requires every spec checklist item to be checked */
+  require_checklists: boolean
+  /** Run tests with coverage after implementations */
+  collect_coverage: boolean
+  /** Run the build check when sources change */
+  check_build_on_save: boolean
+  /** Recurring tasks from .rstn/schedule.toml */
+  schedule: ScheduleState
+  /** Docker services started while this project was focused */
+  docker_services?: string[]
+  /** Imported docker-compose files (re-imported when the project is opened) */
+  compose_files?: string[]
+  /** Service groups from .rstn/services.toml */
+  service_groups?: ServiceGroup[]
+  /** Error loading .rstn/services.toml (groups can't be edited until fixed) */
+  service_groups_error?: string
+  /** HTTP request collections from .rstn/requests/ and their last results */
+  http_requests?: HttpRequestsState
+}
+
+export type ScheduledJob =
+  | { type: 'task'; provider: TaskProviderKind; name: string; args: string[] }
+  | { type: 'refresh_docker_services' }
+
+export interface ScheduleEntry {
+  id: string
+  name: string
+  interval_secs: number
+  job: ScheduledJob
+  enabled: boolean
+  /** ISO 8601 (null = disabled) */
+  next_run_at: string | null
+  last_run_at: string | null
+  /** Task queue run ID of the last run */
+  last_task_id: string | null
+}
+
+export interface ScheduleState {
+  entries: ScheduleEntry[]
+  paused: boolean
+  error: string | null
 }
 
 // ============================================================================
@@ -540,8 +1579,126 @@ export type Theme = 'system' | 'light' | 'dark'
 export interface GlobalSettings {
   theme: Theme
   default_project_path: string | null
+  /** Claude model passed to `--model` (null = CLI default) */
+  model: string | null
+  /** Maximum number of tasks running at once (null = default) */
+  task_max_parallel: number | null
+  /** Time budget of an implementation run in seconds (null = default) */
+  implementation_timeout_secs: number | null
+  /** Which events show an OS notification */
+  desktop_notifications: DesktopNotificationSettings
+  /** Default backend for chat and one-shot prompts */
+  provider: LlmProviderKind
+  /** OpenAI-compatible endpoint used by the 'openai' provider */
+  openai: OpenAiSettings
+  /** Local Ollama server used by the 'ollama' provider and internal tasks */
+  ollama: OllamaSettings
+  /** Tokens for opening pull requests of changes */
+  git_hosting?: GitHostingSettings
+  /** Command ID (e.g. "view.tasks") → shortcut ("CmdOrCtrl+2", empty = unbound) */
+  keybindings?: Record<string, string>
+  /** Local reverse proxy for services */
+  proxy?: ProxySettings
+  /** Settings profile applied last */
+  active_profile?: string | null
+  /** Guardrails for worktrees without .rstn/guardrails.toml (from the active profile) */
+  guardrails?: Guardrails | null
+}
+
+/** Limits for unattended agent runs (.rstn/guardrails.toml) */
+export interface Guardrails {
+  allowed_tools?: string[]
+  writable_paths?: string[]
+  banned_commands?: string[]
+}
+
+/** Secret references: `env:NAME` or `file:PATH` (never the keys themselves) */
+export interface ProfileSecrets {
+  openai_api_key: string | null
+  github_token: string | null
+  gitlab_token: string | null
+}
+
+/** Named bundle of settings, saved to ~/.rstn/profiles/<name>.json */
+export interface SettingsProfile {
+  name: string
+  theme: Theme
+  model: string | null
+  provider: LlmProviderKind
+  /** Endpoint and model; the key comes from secrets.openai_api_key */
+  openai: OpenAiSettings
+  ollama: OllamaSettings
+  secrets: ProfileSecrets
+  default_project_path: string | null
+  guardrails: Guardrails | null
+}
+
+/** Hostname (and optional path prefix) forwarded to a service's port */
+export interface ProxyRoute {
+  /** `localhost` or a name ending in `.localhost` (e.g. "pg.localhost") */
+  host: string
+  /** Only requests under this path (e.g. "/minio"); stripped when forwarding */
+  path_prefix?: string | null
+  service_id: string
+}
+
+export interface ProxySettings {
+  /** Start the proxy on launch */
+  enabled: boolean
+  /** Listening port (null = 8443) */
+  port: number | null
+  routes: ProxyRoute[]
 }
 
+export interface ProxyStatus {
+  running: boolean
+  port: number | null
+  /** CA certificate to trust for HTTPS */
+  ca_cert_path: string | null
+  /** Why the last start failed */
+  error: string | null
+}
+
+/** Access tokens for the GitHub and GitLab REST APIs */
+export interface GitHostingSettings {
+  github_token: string | null
+  gitlab_token: string | null
+}
+
+/** Backend that runs prompts */
+export type LlmProviderKind = 'claude_cli' | 'openai' | 'ollama'
+
+/** Connection settings for a local Ollama server */
+export interface OllamaSettings {
+  /** Server address (default "http://localhost:11434") */
+  base_url: string
+  /** Model for chat */
+  model: string
+  /** Model for cheap internal tasks such as naming changes (null = not used) */
+  task_model: string | null
+}
+
+/** Connection settings for an OpenAI-compatible API */
+export interface OpenAiSettings {
+  /** API base URL (e.g. "https://api.openai.com/v1") */
+  base_url: string
+  /**
+   * Bearer token. Write-only: never included in state. Omit it to keep the
+   * current key, send '' to remove it.
+   */
+  api_key?: string | null
+  model: string
+}
+
+/** Long-running events that can show an OS notification */
+export type DesktopNotificationEvent =
+  | 'task_completed'
+  | 'claude_finished'
+  | 'docker_service_crashed'
+  | 'implementation_failed'
+
+export type DesktopNotificationSettings = Record<DesktopNotificationEvent, boolean>
+
 export interface RecentProject {
   path: string
   name: string
@@ -559,34 +1716,255 @@ export interface AppError {
 }
 
 // ============================================================================
-// Dev Logs (Development Mode Only)
+// Dev Logs (Development Mode Only)
+// ============================================================================
+
+export type DevLogSource = 'rust' | 'frontend' | 'claude' | 'ipc'
+
+export type DevLogType = 'action' | 'state' | 'claude' | 'error' | 'info'
+
+export interface DevLog {
+  id: string
+  timestamp: string
+  source: DevLogSource
+  log_type: DevLogType
+  summary: string
+  data: unknown
+}
+
+export type AppLogLevel = 'ERROR' | 'WARN' | 'INFO' | 'DEBUG' | 'TRACE'
+
+/** Backend log entry (from the tracing log ring) */
+export interface AppLogEntry {
+  /** ISO 8601 */
+  timestamp: string
+  level: AppLogLevel
+  /** Module that logged the event (e.g. "rstn_core::docker") */
+  module: string
+  message: string
+}
+
+export type BinaryPreview =
+  | {
+      kind: 'image'
+      mime: string
+      size: number
+      width: number | null
+      height: number | null
+      /** data:image/png;base64,... downscaled to fit 256px (SVGs as-is) */
+      thumbnail: string | null
+      thumbnail_width: number | null
+      thumbnail_height: number | null
+    }
+  | { kind: 'media'; mime: string; size: number }
+  | { kind: 'hexdump'; mime: string; size: number; lines: string[]; truncated: boolean }
+  | { kind: 'too_large'; mime: string; size: number; limit: number }
+
+export interface FileViewerState {
+  path: string | null
+  /** Text file content (UTF-8) */
+  content: string | null
+  /** Binary file content (raw bytes; PDF, audio, video and office files only) */
+  binary_content: Uint8Array | null
+  /** Preview of a binary file (thumbnail, hexdump, size/type) */
+  binary_preview?: BinaryPreview | null
+  /** Hash of the text content as last read or saved (sent back with WriteFile) */
+  content_hash: string | null
+  /** Whether the editor has unsaved changes */
+  is_dirty: boolean
+  /** Whether a WriteFile is in progress */
+  is_saving: boolean
+  is_loading: boolean
+  error: string | null
+}
+
+export interface A2UIState {
+  payload: any | null
+}
+
+// ============================================================================
+// Usage (Claude CLI token usage / cost)
+// ============================================================================
+
+export interface UsageRecord {
+  project_id: string
+  session_id: string | null
+  source: string
+  model: string | null
+  input_tokens: number
+  output_tokens: number
+  cache_read_tokens: number
+  cache_creation_tokens: number
+  cost_usd: number
+  timestamp: string
+}
+
+export interface UsageTotals {
+  request_count: number
+  input_tokens: number
+  output_tokens: number
+  cache_read_tokens: number
+  cache_creation_tokens: number
+  cost_usd: number
+}
+
+export interface SessionUsage {
+  session_id: string
+  project_id: string
+  totals: UsageTotals
+}
+
+export interface UsageState {
+  /** Totals per project ID */
+  by_project: Record<string, UsageTotals>
+  /** Most recent sessions (newest last) */
+  sessions: SessionUsage[]
+}
+
+// ============================================================================
+// Session History (one record per Claude CLI invocation)
+// ============================================================================
+
+export type SessionOutcome = 'success' | 'error' | 'interrupted'
+
+export interface SessionRecord {
+  id: string
+  project_id: string
+  /** What triggered the run ("chat", "plan", "implementation", ...) */
+  kind: string
+  worktree_path: string
+  claude_session_id: string | null
+  model: string | null
+  prompt_excerpt: string
+  started_at: string
+  duration_ms: number
+  input_tokens: number
+  output_tokens: number
+  cost_usd: number
+  /** Transcript written by the Claude CLI, if found */
+  log_file: string | null
+  outcome: SessionOutcome
+  error: string | null
+}
+
+export interface SessionHistoryState {
+  /** Project the sessions belong to (null = not loaded) */
+  project_id: string | null
+  /** Newest first */
+  sessions: SessionRecord[]
+  is_loading: boolean
+}
+
+// ============================================================================
+// Activity Feed (significant events per project)
+// ============================================================================
+
+export type ActivityKind =
+  | 'change_created'
+  | 'plan_approved'
+  | 'service_started'
+  | 'worktree_added'
+  | 'constitution_updated'
+
+export interface ActivityEvent {
+  id: number
+  project_id: string
+  kind: ActivityKind
+  summary: string
+  detail?: Record<string, unknown>
+  timestamp: string
+}
+
+export interface ActivityFeedState {
+  /** Project the events belong to (null = not loaded) */
+  project_id: string | null
+  /** Events at or after this time were loaded (null = all) */
+  since: string | null
+  /** Newest first */
+  events: ActivityEvent[]
+  is_loading: boolean
+}
+
+// ============================================================================
+// System Monitor (machine resources)
 // ============================================================================
 
-export type DevLogSource = 'rust' | 'frontend' | 'claude' | 'ipc'
-
-export type DevLogType = 'action' | 'state' | 'claude' | 'error' | 'info'
-
-export interface DevLog {
-  id: string
+export type ProcessGroup = 'claude_cli' | 'containers' | 'terminals'
+
+export interface GroupUsage {
+  group: ProcessGroup
+  /** Processes (or containers) counted */
+  count: number
+  /** CPU usage (100 = one full core) */
+  cpu_percent: number
+  memory_bytes: number
+}
+
+export interface SystemStats {
+  /** CPU usage of the whole machine (0-100) */
+  cpu_percent: number
+  cpu_count: number
+  memory_used: number
+  memory_total: number
+  swap_used: number
+  swap_total: number
+  /** Disk holding the home directory */
+  disk_mount: string
+  disk_used: number
+  disk_total: number
+  groups: GroupUsage[]
+  warnings: string[]
   timestamp: string
-  source: DevLogSource
-  log_type: DevLogType
-  summary: string
-  data: unknown
 }
 
-export interface FileViewerState {
-  path: string | null
-  /** Text file content (UTF-8) */
-  content: string | null
-  /** Binary file content (raw bytes) */
-  binary_content: Uint8Array | null
-  is_loading: boolean
-  error: string | null
+// ============================================================================
+// Undo
+// ============================================================================
+
+/** Snapshot of one undoable slice of state */
+export type UndoSlice =
+  | { kind: 'env_tracked_patterns'; project_id: string; patterns: string[] }
+  | {
+      kind: 'agent_profiles'
+      project_id: string
+      profiles: AgentProfile[]
+      active_profile_id: string | null
+    }
+  | {
+      kind: 'constitution_presets'
+      project_id: string
+      worktree_id: string
+      presets: ConstitutionPreset[]
+      active_preset_id: string | null
+    }
+  | {
+      kind: 'change_status'
+      project_id: string
+      worktree_id: string
+      change_id: string
+      status: ChangeStatus
+      updated_at: string
+    }
+  | {
+      kind: 'file_comment'
+      project_path: string
+      file_path: string
+      comment_id: string
+      /** null = deleted */
+      comment: Comment | null
+    }
+
+export interface UndoEntry {
+  /** What the edit did (e.g. "Delete agent profile") */
+  label: string
+  before: UndoSlice
+  after: UndoSlice
 }
 
-export interface A2UIState {
-  payload: any | null
+export interface UndoHistory {
+  /** Most recent last (at most 50) */
+  undo: UndoEntry[]
+  redo: UndoEntry[]
 }
 
 // ============================================================================
@@ -606,8 +1984,20 @@ export interface AppState {
   active_view: ActiveView
   // Dev logs (development mode only)
   dev_logs?: DevLog[]
+  /** Backend log entries loaded for the Dev Log panel */
+  app_logs?: AppLogEntry[]
   file_viewer: FileViewerState
   a2ui: A2UIState
+  usage: UsageState
+  session_history: SessionHistoryState
+  activity_feed: ActivityFeedState
+  /** Claude implementation runs across worktrees */
+  agent_runs: AgentRunsState
+  undo: UndoHistory
+  /** Latest machine resource sample */
+  system_stats?: SystemStats
+  /** Saved settings profiles */
+  settings_profiles?: SettingsProfile[]
 }
 
 // ============================================================================
@@ -679,6 +2069,57 @@ export interface SetBranchesLoadingAction {
   payload: { is_loading: boolean }
 }
 
+export interface RefreshWorktreeHealthAction {
+  type: 'RefreshWorktreeHealth'
+}
+
+export interface SetWorktreeHealthAction {
+  type: 'SetWorktreeHealth'
+  payload: { health: WorktreeHealthData[] }
+}
+
+export interface LoadWorktreeDiffAction {
+  type: 'LoadWorktreeDiff'
+  payload: { base: string | null }
+}
+
+export interface SetWorktreeDiffAction {
+  type: 'SetWorktreeDiff'
+  payload: { diff: WorktreeDiff | null }
+}
+
+export interface RunDependencyAuditAction {
+  type: 'RunDependencyAudit'
+}
+
+export interface SetDependencyAuditAction {
+  type: 'SetDependencyAudit'
+  payload: { worktree_path: string; report: AuditReport }
+}
+
+export interface RunBuildCheckAction {
+  type: 'RunBuildCheck'
+}
+
+export interface SetBuildDiagnosticsAction {
+  type: 'SetBuildDiagnostics'
+  payload: { worktree_path: string; diagnostics: BuildDiagnostics }
+}
+
+export interface SetLspServerStatusAction {
+  type: 'SetLspServerStatus'
+  payload: {
+    worktree_path: string
+    server: LanguageServer
+    status: LspServerStatus | null
+    error: string | null
+  }
+}
+
+export interface StopLspServersAction {
+  type: 'StopLspServers'
+}
+
 // MCP Actions
 export interface StartMcpServerAction {
   type: 'StartMcpServer'
@@ -710,7 +2151,7 @@ export interface SetMcpErrorAction {
 
 export interface AddMcpLogEntryAction {
   type: 'AddMcpLogEntry'
-  payload: { entry: McpLogEntryData }
+  payload: { worktree_id: string; entry: McpLogEntryData }
 }
 
 export interface ClearMcpLogsAction {
@@ -722,10 +2163,66 @@ export interface UpdateMcpToolsAction {
   payload: { tools: McpToolData[] }
 }
 
+export interface UpdateMcpMetricsAction {
+  type: 'UpdateMcpMetrics'
+  payload: { metrics: McpToolMetrics[] }
+}
+
+export interface SetMcpPolicyAction {
+  type: 'SetMcpPolicy'
+  payload: { policy: McpPolicy }
+}
+
+export interface ConnectExternalMcpServersAction {
+  type: 'ConnectExternalMcpServers'
+}
+
+export interface DisconnectExternalMcpServersAction {
+  type: 'DisconnectExternalMcpServers'
+}
+
+export interface SetExternalMcpServersAction {
+  type: 'SetExternalMcpServers'
+  payload: { servers: ExternalMcpServer[] }
+}
+
+export interface CallExternalMcpToolAction {
+  type: 'CallExternalMcpTool'
+  payload: { server: string; tool: string; arguments: Record<string, unknown> }
+}
+
+export interface StartMcpPlaygroundCallAction {
+  type: 'StartMcpPlaygroundCall'
+  payload: { entry: McpPlaygroundEntry }
+}
+
+export interface FinishMcpPlaygroundCallAction {
+  type: 'FinishMcpPlaygroundCall'
+  payload: { id: string; duration_ms: number; result: unknown | null; error: string | null }
+}
+
+export interface ClearMcpPlaygroundAction {
+  type: 'ClearMcpPlayground'
+}
+
 // Chat Actions
 export interface SendChatMessageAction {
   type: 'SendChatMessage'
-  payload: { text: string }
+  /**
+   * provider: backend for this message (omitted = the global setting)
+   * attachments: file paths (text is inlined, images go to the Claude CLI)
+   */
+  payload: { text: string; continue_conversation?: boolean; provider?: LlmProviderKind; attachments?: string[] }
+}
+
+export interface SendTemplatedPromptAction {
+  type: 'SendTemplatedPrompt'
+  payload: {
+    template_id: string
+    variables: Record<string, string>
+    continue_conversation?: boolean
+    provider?: LlmProviderKind
+  }
 }
 
 export interface AddChatMessageAction {
@@ -738,6 +2235,59 @@ export interface AppendChatContentAction {
   payload: { content: string }
 }
 
+export interface AddChatToolCallAction {
+  type: 'AddChatToolCall'
+  payload: { tool_call: ChatToolCall }
+}
+
+export interface RequestToolApprovalAction {
+  type: 'RequestToolApproval'
+  payload: { approval: PendingToolApproval }
+}
+
+export interface ApproveToolUseAction {
+  type: 'ApproveToolUse'
+  payload: { request_id: string }
+}
+
+export interface DenyToolUseAction {
+  type: 'DenyToolUse'
+  payload: { request_id: string }
+}
+
+export interface ProposeEditsAction {
+  type: 'ProposeEdits'
+  payload: { message_id: string }
+}
+
+export interface AcceptProposedEditAction {
+  type: 'AcceptProposedEdit'
+  payload: { path: string }
+}
+
+export interface RejectProposedEditAction {
+  type: 'RejectProposedEdit'
+  payload: { path: string }
+}
+
+export interface ApplyProposedEditsAction {
+  type: 'ApplyProposedEdits'
+}
+
+export interface SetProposedEditsAppliedAction {
+  type: 'SetProposedEditsApplied'
+  payload: { paths: string[]; backup_dir: string }
+}
+
+export interface DismissProposedEditsAction {
+  type: 'DismissProposedEdits'
+}
+
+export interface CompleteChatToolCallAction {
+  type: 'CompleteChatToolCall'
+  payload: { id: string; result_excerpt: string; is_error: boolean; duration_ms: number }
+}
+
 export interface SetChatTypingAction {
   type: 'SetChatTyping'
   payload: { is_typing: boolean }
@@ -756,6 +2306,16 @@ export interface ClearChatAction {
   type: 'ClearChat'
 }
 
+export interface SetChatSessionIdAction {
+  type: 'SetChatSessionId'
+  payload: { session_id: string }
+}
+
+export interface CancelChatMessageAction {
+  type: 'CancelChatMessage'
+  payload: { message_id: string }
+}
+
 // Constitution Workflow Actions
 export interface StartConstitutionWorkflowAction {
   type: 'StartConstitutionWorkflow'
@@ -774,6 +2334,10 @@ export interface GenerateConstitutionAction {
   type: 'GenerateConstitution'
 }
 
+export interface CancelConstitutionGenerationAction {
+  type: 'CancelConstitutionGeneration'
+}
+
 export interface AppendConstitutionOutputAction {
   type: 'AppendConstitutionOutput'
   payload: { content: string }
@@ -805,6 +2369,15 @@ export interface SetConstitutionContentAction {
   payload: { content: string | null }
 }
 
+export interface LintConstitutionAction {
+  type: 'LintConstitution'
+}
+
+export interface SetConstitutionLintReportAction {
+  type: 'SetConstitutionLintReport'
+  payload: { report: ConstitutionLintReport }
+}
+
 export interface SetClaudeMdExistsAction {
   type: 'SetClaudeMdExists'
   exists: boolean
@@ -883,6 +2456,8 @@ export interface AddReviewCommentAction {
     session_id: string
     target: CommentTarget
     content: string
+    anchor?: CommentAnchor | null
+    parent_id?: string | null
   }
 }
 
@@ -932,6 +2507,19 @@ export interface SetReviewStatusAction {
   }
 }
 
+export interface LoadReviewCommentsAction {
+  type: 'LoadReviewComments'
+  payload: { change_id: string }
+}
+
+export interface SetReviewCommentsAction {
+  type: 'SetReviewComments'
+  payload: {
+    change_id: string
+    comments: ReviewComment[]
+  }
+}
+
 export interface SetReviewGateLoadingAction {
   type: 'SetReviewGateLoading'
   payload: { is_loading: boolean }
@@ -947,6 +2535,21 @@ export interface SetActiveReviewSessionAction {
   payload: { session_id: string | null }
 }
 
+export interface RunComplianceCheckAction {
+  type: 'RunComplianceCheck'
+  payload: { session_id: string }
+}
+
+export interface SetComplianceCheckAction {
+  type: 'SetComplianceCheck'
+  payload: { session_id: string; check: ComplianceCheck }
+}
+
+export interface OverrideComplianceCheckAction {
+  type: 'OverrideComplianceCheck'
+  payload: { session_id: string; reason: string }
+}
+
 export interface ClearReviewSessionAction {
   type: 'ClearReviewSession'
   payload: { session_id: string }
@@ -955,7 +2558,19 @@ export interface ClearReviewSessionAction {
 // Change Management Actions (CESDD Phase 2)
 export interface CreateChangeAction {
   type: 'CreateChange'
-  payload: { intent: string }
+  payload: {
+    intent: string
+    /** Create a change/<name> branch in a new worktree */
+    create_branch?: boolean
+  }
+}
+
+export interface CreateChangeFromIssueAction {
+  type: 'CreateChangeFromIssue'
+  payload: {
+    issue_number: number
+    create_branch?: boolean
+  }
 }
 
 export interface GenerateProposalAction {
@@ -1007,6 +2622,11 @@ export interface RefreshChangesAction {
   type: 'RefreshChanges'
 }
 
+export interface ResolveChangeStatusConflictAction {
+  type: 'ResolveChangeStatusConflict'
+  payload: { change_id: string; status: ChangeStatusData }
+}
+
 export interface SetChangesAction {
   type: 'SetChanges'
   payload: { changes: ChangeData[] }
@@ -1133,6 +2753,19 @@ export interface FailGenerateContextAction {
   payload: { error: string }
 }
 
+export interface OpenChangePullRequestAction {
+  type: 'OpenChangePullRequest'
+  payload: { change_id: string }
+}
+
+export interface SetChangePullRequestAction {
+  type: 'SetChangePullRequest'
+  payload: {
+    change_id: string
+    url: string
+  }
+}
+
 export interface SetChangeArchivedAction {
   type: 'SetChangeArchived'
   payload: { change_id: string }
@@ -1149,16 +2782,86 @@ export interface AppendImplementationOutputAction {
   payload: { change_id: string; content: string }
 }
 
+export interface CompleteImplementationTaskAction {
+  type: 'CompleteImplementationTask'
+  payload: { change_id: string; task_index: number }
+}
+
+export interface StartImplementationTestsAction {
+  type: 'StartImplementationTests'
+  payload: { change_id: string }
+}
+
 export interface CompleteImplementationAction {
   type: 'CompleteImplementation'
   payload: { change_id: string }
 }
 
+export interface FailProposalAction {
+  type: 'FailProposal'
+  payload: { change_id: string; error: string }
+}
+
+export interface CancelProposalAction {
+  type: 'CancelProposal'
+  payload: { change_id: string }
+}
+
 export interface FailImplementationAction {
   type: 'FailImplementation'
   payload: { change_id: string; error: string }
 }
 
+export interface CancelImplementationAction {
+  type: 'CancelImplementation'
+  payload: { change_id: string }
+}
+
+export interface RequestImplementationApprovalAction {
+  type: 'RequestImplementationApproval'
+  payload: { change_id: string; approval: AgentRunApproval }
+}
+
+export interface ResolveImplementationApprovalAction {
+  type: 'ResolveImplementationApproval'
+  payload: { change_id: string; request_id: string; approve: boolean }
+}
+
+export interface SetChangeSnapshotAction {
+  type: 'SetChangeSnapshot'
+  payload: { worktree_path: string; change_id: string; snapshot: ChangeSnapshot }
+}
+
+export interface RollbackImplementationAction {
+  type: 'RollbackImplementation'
+  payload: { change_id: string }
+}
+
+export interface CompleteRollbackAction {
+  type: 'CompleteRollback'
+  payload: { change_id: string; snapshot: ChangeSnapshot }
+}
+
+export interface SetChangeTestResultsAction {
+  type: 'SetChangeTestResults'
+  payload: { worktree_path: string; change_id: string; results: TestResults }
+}
+
+export interface SetChangeHookResultsAction {
+  type: 'SetChangeHookResults'
+  payload: { worktree_path: string; change_id: string; results: HookResult[] }
+}
+
+export interface RunChangeTestsAction {
+  type: 'RunChangeTests'
+  payload: { change_id: string }
+}
+
+export interface OverrideTestGateAction {
+  type: 'OverrideTestGate'
+  payload: { change_id: string }
+}
+
 // Context file data for actions
 export interface ContextFileData {
   name: string
@@ -1242,28 +2945,191 @@ export interface SetDockerLoadingAction {
   payload: { is_loading: boolean }
 }
 
-export interface SetDockerLogsLoadingAction {
-  type: 'SetDockerLogsLoading'
-  payload: { is_loading: boolean }
+export interface SetDockerLogsLoadingAction {
+  type: 'SetDockerLogsLoading'
+  payload: { is_loading: boolean }
+}
+
+export interface ImagePullProgressData {
+  layer_id: string | null
+  status: string
+  current: number | null
+  total: number | null
+}
+
+export interface SetImagePullProgressAction {
+  type: 'SetImagePullProgress'
+  payload: { image: string; progress: ImagePullProgressData }
+}
+
+export interface SetServiceHealthAction {
+  type: 'SetServiceHealth'
+  payload: { service_id: string; health: ServiceHealth }
+}
+
+export interface SetDockerStatsAction {
+  type: 'SetDockerStats'
+  payload: { service_id: string; stats: ContainerStats }
+}
+
+export interface FetchDockerDatabasesAction {
+  type: 'FetchDockerDatabases'
+  payload: { service_id: string }
+}
+
+export interface SetDockerDatabasesAction {
+  type: 'SetDockerDatabases'
+  payload: { service_id: string; databases: DatabaseInfo[] }
+}
+
+export interface FetchDockerTablesAction {
+  type: 'FetchDockerTables'
+  payload: { service_id: string; db_name: string }
+}
+
+export interface SetDockerTablesAction {
+  type: 'SetDockerTables'
+  payload: { service_id: string; db_name: string; tables: TableInfo[] }
+}
+
+export interface SetDockerDatabasesLoadingAction {
+  type: 'SetDockerDatabasesLoading'
+  payload: { is_loading: boolean }
+}
+
+export interface FinishImagePullAction {
+  type: 'FinishImagePull'
+  payload: { image: string; error: string | null }
+}
+
+export interface SetPortConflictAction {
+  type: 'SetPortConflict'
+  payload: { service_id: string; conflict: PortConflictData }
+}
+
+export interface ClearPortConflictAction {
+  type: 'ClearPortConflict'
+}
+
+export interface StartDockerServiceWithPortAction {
+  type: 'StartDockerServiceWithPort'
+  payload: { service_id: string; port: number }
+}
+
+export interface ResolveConflictByStoppingContainerAction {
+  type: 'ResolveConflictByStoppingContainer'
+  payload: { conflicting_container_id: string; service_id: string }
+}
+
+export interface KillProcessOnPortAction {
+  type: 'KillProcessOnPort'
+  payload: { port: number; pid: number; service_id: string | null }
+}
+
+export interface DockerComposeUpAction {
+  type: 'DockerComposeUp'
+  payload: { project_name: string }
+}
+
+export interface DockerComposeDownAction {
+  type: 'DockerComposeDown'
+  payload: { project_name: string }
+}
+
+export interface AddComposeFileAction {
+  type: 'AddComposeFile'
+  payload: { path: string }
+}
+
+export interface LoadHttpRequestsAction {
+  type: 'LoadHttpRequests'
+}
+
+export interface SetHttpRequestsAction {
+  type: 'SetHttpRequests'
+  payload: { project_path: string; collections: RequestCollection[] }
+}
+
+export interface RunHttpRequestAction {
+  type: 'RunHttpRequest'
+  /** env_file is relative to the active worktree (null = ".env") */
+  payload: { collection: string; request: string; env_file: string | null }
+}
+
+export interface SetHttpResponseAction {
+  type: 'SetHttpResponse'
+  payload: {
+    project_path: string
+    collection: string
+    request: string
+    response: HttpResponseData | null
+    error: string | null
+  }
+}
+
+export interface LoadServiceGroupsAction {
+  type: 'LoadServiceGroups'
+}
+
+export interface SetServiceGroupsAction {
+  type: 'SetServiceGroups'
+  payload: { project_path: string; groups: ServiceGroup[]; error: string | null }
+}
+
+export interface AssignServiceToGroupAction {
+  type: 'AssignServiceToGroup'
+  payload: { service_id: string; group: string | null }
+}
+
+export interface DeleteServiceGroupAction {
+  type: 'DeleteServiceGroup'
+  payload: { name: string }
+}
+
+export interface StartServiceGroupAction {
+  type: 'StartServiceGroup'
+  payload: { name: string }
+}
+
+export interface StopServiceGroupAction {
+  type: 'StopServiceGroup'
+  payload: { name: string }
+}
+
+export interface OpenDockerExecAction {
+  type: 'OpenDockerExec'
+  /** cmd defaults to a shell (`sh`) */
+  payload: { service_id: string; cmd: string[] | null; cols: number; rows: number }
+}
+
+export interface SetDockerExecSessionAction {
+  type: 'SetDockerExecSession'
+  payload: { service_id: string; session_id: string | null }
+}
+
+export interface CloseDockerExecAction {
+  type: 'CloseDockerExec'
+  payload: { service_id: string }
 }
 
-export interface SetPortConflictAction {
-  type: 'SetPortConflict'
-  payload: { service_id: string; conflict: PortConflictData }
+export interface StartProxyAction {
+  type: 'StartProxy'
+  /** null = configured or default port */
+  payload: { port: number | null }
 }
 
-export interface ClearPortConflictAction {
-  type: 'ClearPortConflict'
+export interface StopProxyAction {
+  type: 'StopProxy'
 }
 
-export interface StartDockerServiceWithPortAction {
-  type: 'StartDockerServiceWithPort'
-  payload: { service_id: string; port: number }
+export interface SetProxyRoutesAction {
+  type: 'SetProxyRoutes'
+  payload: { routes: ProxyRoute[] }
 }
 
-export interface ResolveConflictByStoppingContainerAction {
-  type: 'ResolveConflictByStoppingContainer'
-  payload: { conflicting_container_id: string; service_id: string }
+export interface SetProxyStatusAction {
+  type: 'SetProxyStatus'
+  payload: { status: ProxyStatus }
 }
 
 // Tasks Actions
@@ -1285,6 +3151,70 @@ export interface RunJustCommandAction {
   payload: { name: string; cwd: string }
 }
 
+export interface RunJustCommandWithArgsAction {
+  type: 'RunJustCommandWithArgs'
+  payload: { name: string; args: string[]; cwd: string }
+}
+
+export interface LoadTasksAction {
+  type: 'LoadTasks'
+  payload: { provider?: TaskProviderKind | null }
+}
+
+export interface SetTasksAction {
+  type: 'SetTasks'
+  payload: { provider: TaskProviderKind | null; tasks: TaskDescriptor[] }
+}
+
+export interface RunTaskAction {
+  type: 'RunTask'
+  payload: { provider: TaskProviderKind; name: string; args?: string[]; cwd: string }
+}
+
+export interface QueueTaskRunAction {
+  type: 'QueueTaskRun'
+  payload: { run: TaskRun }
+}
+
+export interface StartTaskRunAction {
+  type: 'StartTaskRun'
+  payload: { task_id: string }
+}
+
+export interface AppendTaskRunOutputAction {
+  type: 'AppendTaskRunOutput'
+  payload: { task_id: string; line: string }
+}
+
+export interface FinishTaskRunAction {
+  type: 'FinishTaskRun'
+  payload: { task_id: string; status: TaskRunStatus; exit_code: number | null; duration_ms: number | null }
+}
+
+export interface CancelTaskAction {
+  type: 'CancelTask'
+  payload: { task_id: string }
+}
+
+export interface LoadScheduleAction {
+  type: 'LoadSchedule'
+}
+
+export interface SetScheduleAction {
+  type: 'SetSchedule'
+  payload: { project_path: string; entries: ScheduleEntry[]; error: string | null }
+}
+
+export interface SetSchedulePausedAction {
+  type: 'SetSchedulePaused'
+  payload: { paused: boolean }
+}
+
+export interface MarkScheduleRunAction {
+  type: 'MarkScheduleRun'
+  payload: { project_path: string; id: string; ran_at: string; next_run_at: string; task_id: string | null }
+}
+
 export interface SetTaskStatusAction {
   type: 'SetTaskStatus'
   payload: { name: string; status: TaskStatusData }
@@ -1325,6 +3255,91 @@ export interface SetProjectPathAction {
   payload: { path: string | null }
 }
 
+export interface SetModelAction {
+  type: 'SetModel'
+  payload: { model: string | null }
+}
+
+export interface SetProjectModelAction {
+  type: 'SetProjectModel'
+  payload: { model: string | null }
+}
+
+export interface SetRequireChecklistsAction {
+  type: 'SetRequireChecklists'
+  payload: { required: boolean }
+}
+
+export interface SetCollectCoverageAction {
+  type: 'SetCollectCoverage'
+  payload: { enabled: boolean }
+}
+
+export interface SetCheckBuildOnSaveAction {
+  type: 'SetCheckBuildOnSave'
+  payload: { enabled: boolean }
+}
+
+export interface SetTaskMaxParallelAction {
+  type: 'SetTaskMaxParallel'
+  payload: { max_parallel: number | null }
+}
+
+export interface SetImplementationTimeoutAction {
+  type: 'SetImplementationTimeout'
+  payload: { secs: number | null }
+}
+
+export interface SetDesktopNotificationAction {
+  type: 'SetDesktopNotification'
+  payload: { event: DesktopNotificationEvent; enabled: boolean }
+}
+
+export interface SetLlmProviderAction {
+  type: 'SetLlmProvider'
+  payload: { provider: LlmProviderKind }
+}
+
+export interface SetOpenAiSettingsAction {
+  type: 'SetOpenAiSettings'
+  payload: { settings: OpenAiSettings }
+}
+
+export interface SetOllamaSettingsAction {
+  type: 'SetOllamaSettings'
+  payload: { settings: OllamaSettings }
+}
+
+export interface SetGitHostingSettingsAction {
+  type: 'SetGitHostingSettings'
+  payload: { settings: GitHostingSettings }
+}
+
+export interface SetKeybindingAction {
+  type: 'SetKeybinding'
+  payload: { command: string; shortcut: string }
+}
+
+export interface ResetKeybindingsAction {
+  type: 'ResetKeybindings'
+  payload: { command?: string | null }
+}
+
+export interface SaveSettingsProfileAction {
+  type: 'SaveSettingsProfile'
+  payload: { profile: SettingsProfile }
+}
+
+export interface DeleteSettingsProfileAction {
+  type: 'DeleteSettingsProfile'
+  payload: { name: string }
+}
+
+export interface SwitchSettingsProfileAction {
+  type: 'SwitchSettingsProfile'
+  payload: { name: string }
+}
+
 // Env Actions (Project scope)
 export interface CopyEnvFilesAction {
   type: 'CopyEnvFiles'
@@ -1355,6 +3370,61 @@ export interface SetEnvSourceWorktreeAction {
   payload: { worktree_path: string | null }
 }
 
+export interface DiffEnvFilesAction {
+  type: 'DiffEnvFiles'
+  payload: {
+    from_worktree_path: string
+    to_worktree_path: string
+    patterns: string[] | null
+  }
+}
+
+export interface SetEnvDiffAction {
+  type: 'SetEnvDiff'
+  payload: { diff: EnvDiff | null }
+}
+
+export interface SyncEnvKeysAction {
+  type: 'SyncEnvKeys'
+  payload: {
+    from_worktree_path: string
+    to_worktree_path: string
+    file: string
+    keys: string[]
+  }
+}
+
+export interface ScanEnvSecretsAction {
+  type: 'ScanEnvSecrets'
+}
+
+export interface SetEnvSecretsAction {
+  type: 'SetEnvSecrets'
+  payload: { secrets: EnvSecret[] }
+}
+
+export interface InjectConnectionStringAction {
+  type: 'InjectConnectionString'
+  payload: {
+    service_id: string
+    db_name: string
+    env_file: string
+    var_name: string
+    /** Only compute env_config.injection_preview */
+    dry_run?: boolean
+  }
+}
+
+export interface SetEnvInjectionPreviewAction {
+  type: 'SetEnvInjectionPreview'
+  payload: { preview: EnvInjectionPreview | null }
+}
+
+export interface SetAutoResolvePortsAction {
+  type: 'SetAutoResolvePorts'
+  payload: { strategy: PortConflictStrategy }
+}
+
 // Agent Rules Actions (Project scope)
 export interface SetAgentRulesEnabledAction {
   type: 'SetAgentRulesEnabled'
@@ -1376,6 +3446,11 @@ export interface CreateAgentProfileAction {
   payload: { name: string; prompt: string }
 }
 
+export interface ImportAgentProfileAction {
+  type: 'ImportAgentProfile'
+  payload: { profile: AgentProfileFile }
+}
+
 export interface UpdateAgentProfileAction {
   type: 'UpdateAgentProfile'
   payload: { id: string; name: string; prompt: string }
@@ -1481,9 +3556,18 @@ export interface ConflictingContainerData {
   is_rstn_managed: boolean
 }
 
+export interface ConflictingProcessData {
+  pid: number
+  name: string
+  exe: string | null
+  user: string | null
+  command: string | null
+}
+
 export interface PortConflictData {
   requested_port: number
   conflicting_container: ConflictingContainerData
+  conflicting_process?: ConflictingProcessData | null
   suggested_port: number
 }
 
@@ -1491,6 +3575,7 @@ export interface JustCommandData {
   name: string
   description: string | null
   recipe: string
+  parameters?: JustParameterInfo[]
 }
 
 export type TaskStatusData = 'idle' | 'running' | 'success' | 'error'
@@ -1534,6 +3619,7 @@ export interface ChatMessageData {
   content: string
   timestamp: string
   is_streaming?: boolean
+  attachments?: ChatAttachment[]
 }
 
 export interface EnvCopyResultData {
@@ -1600,6 +3686,23 @@ export interface ClearDevLogsAction {
   type: 'ClearDevLogs'
 }
 
+export interface LoadAppLogsAction {
+  type: 'LoadAppLogs'
+  payload: {
+    /** Entries to load (default 200) */
+    limit?: number | null
+    /** Minimum level ("error", "warn", "info", "debug") */
+    level?: string | null
+    /** Only modules containing this (e.g. "docker") */
+    module?: string | null
+  }
+}
+
+export interface SetAppLogsAction {
+  type: 'SetAppLogs'
+  payload: { logs: AppLogEntry[] }
+}
+
 export interface ReadFileAction {
   type: 'ReadFile'
   payload: { path: string }
@@ -1629,15 +3732,291 @@ export interface SetBinaryFileContentAction {
   payload: {
     path: string
     content: Uint8Array | null
+    preview?: BinaryPreview | null
     error: string | null
   }
 }
 
+export interface WriteFileAction {
+  type: 'WriteFile'
+  payload: {
+    path: string
+    content: string
+    expected_hash: string | null
+  }
+}
+
+export interface SetFileDirtyAction {
+  type: 'SetFileDirty'
+  payload: { is_dirty: boolean }
+}
+
+export interface SetFileSaveErrorAction {
+  type: 'SetFileSaveError'
+  payload: { path: string; error: string }
+}
+
 export interface SetA2UIPayloadAction {
   type: 'SetA2UIPayload'
   payload: { payload: any | null }
 }
 
+export interface AddUsageRecordAction {
+  type: 'AddUsageRecord'
+  payload: { record: UsageRecord }
+}
+
+export interface LoadSessionsAction {
+  type: 'LoadSessions'
+}
+
+export interface SetSessionsAction {
+  type: 'SetSessions'
+  payload: { project_id: string; sessions: SessionRecord[] }
+}
+
+export interface AddSessionAction {
+  type: 'AddSession'
+  payload: { session: SessionRecord }
+}
+
+export interface DeleteSessionAction {
+  type: 'DeleteSession'
+  payload: { id: string }
+}
+
+export interface StartSpecWorkflowAction {
+  type: 'StartSpecWorkflow'
+  /** use_claude defaults to true; off (or no Claude CLI) fills the spec template */
+  payload: { description: string; use_claude?: boolean }
+}
+
+export interface SetSpecWorkflowAction {
+  type: 'SetSpecWorkflow'
+  payload: { workflow: SpecWorkflow }
+}
+
+export interface RunSpecPhaseAction {
+  type: 'RunSpecPhase'
+  payload: { phase: SpecPhase; input: string | null }
+}
+
+export interface AppendSpecPhaseOutputAction {
+  type: 'AppendSpecPhaseOutput'
+  payload: { phase: SpecPhase; content: string }
+}
+
+export interface CompleteSpecPhaseAction {
+  type: 'CompleteSpecPhase'
+  payload: { phase: SpecPhase; output: string }
+}
+
+export interface FailSpecPhaseAction {
+  type: 'FailSpecPhase'
+  payload: { phase: SpecPhase; error: string }
+}
+
+export interface CancelSpecPhaseAction {
+  type: 'CancelSpecPhase'
+}
+
+export interface ClearSpecWorkflowAction {
+  type: 'ClearSpecWorkflow'
+}
+
+export interface StartClarifySessionAction {
+  type: 'StartClarifySession'
+  payload: { spec_path: string }
+}
+
+export interface SetClarifySessionAction {
+  type: 'SetClarifySession'
+  payload: { session: ClarifySession }
+}
+
+export interface AnswerClarifyQuestionAction {
+  type: 'AnswerClarifyQuestion'
+  payload: { id: string; value: string }
+}
+
+export interface SkipClarifyQuestionAction {
+  type: 'SkipClarifyQuestion'
+}
+
+export interface CompleteClarifySessionAction {
+  type: 'CompleteClarifySession'
+  payload: { coverage: CategoryCoverage[] }
+}
+
+export interface FailClarifySessionAction {
+  type: 'FailClarifySession'
+  payload: { error: string }
+}
+
+export interface ClearClarifySessionAction {
+  type: 'ClearClarifySession'
+}
+
+export interface LoadSpecTasksAction {
+  type: 'LoadSpecTasks'
+  payload: { tasks_path: string }
+}
+
+export interface SetSpecTasksAction {
+  type: 'SetSpecTasks'
+  payload: { tasks: SpecTasksState }
+}
+
+export interface ToggleSpecTaskAction {
+  type: 'ToggleSpecTask'
+  payload: { id: string; done: boolean }
+}
+
+export interface ReportSpecProgressAction {
+  type: 'ReportSpecProgress'
+  payload: { message: string; task_id: string | null }
+}
+
+export interface GenerateChecklistAction {
+  type: 'GenerateChecklist'
+  payload: { kind: ChecklistKind }
+}
+
+export interface SetChecklistAction {
+  type: 'SetChecklist'
+  payload: { checklist: SpecChecklist }
+}
+
+export interface FailChecklistAction {
+  type: 'FailChecklist'
+  payload: { kind: ChecklistKind; error: string }
+}
+
+export interface LoadChecklistsAction {
+  type: 'LoadChecklists'
+}
+
+export interface SetChecklistsAction {
+  type: 'SetChecklists'
+  payload: { checklists: SpecChecklist[] }
+}
+
+export interface ToggleChecklistItemAction {
+  type: 'ToggleChecklistItem'
+  payload: { kind: ChecklistKind; id: string; checked: boolean }
+}
+
+export interface AnalyzeSpecAction {
+  type: 'AnalyzeSpec'
+  payload: { verify: boolean }
+}
+
+export interface CompleteSpecAnalysisAction {
+  type: 'CompleteSpecAnalysis'
+  payload: { report: AnalysisReport }
+}
+
+export interface FailSpecAnalysisAction {
+  type: 'FailSpecAnalysis'
+  payload: { error: string }
+}
+
+export interface LoadFeaturesCatalogAction {
+  type: 'LoadFeaturesCatalog'
+}
+
+export interface SetFeaturesCatalogAction {
+  type: 'SetFeaturesCatalog'
+  payload: { features: FeatureInfo[] }
+}
+
+export interface StartFeatureWorkAction {
+  type: 'StartFeatureWork'
+  payload: { feature_number: number }
+}
+
+export interface QueueAgentRunAction {
+  type: 'QueueAgentRun'
+  payload: { worktree_path: string; change_id: string }
+}
+
+export interface AddAgentRunAction {
+  type: 'AddAgentRun'
+  payload: { run: AgentRun }
+}
+
+export interface StartAgentRunAction {
+  type: 'StartAgentRun'
+  payload: { run_id: string }
+}
+
+export interface AppendAgentRunOutputAction {
+  type: 'AppendAgentRunOutput'
+  payload: { run_id: string; content: string }
+}
+
+export interface FinishAgentRunAction {
+  type: 'FinishAgentRun'
+  payload: { run_id: string; status: AgentRunStatus; error: string | null }
+}
+
+export interface RequestAgentRunApprovalAction {
+  type: 'RequestAgentRunApproval'
+  payload: { run_id: string; approval: AgentRunApproval }
+}
+
+export interface ResolveAgentRunApprovalAction {
+  type: 'ResolveAgentRunApproval'
+  payload: { run_id: string; request_id: string; approve: boolean }
+}
+
+export interface CancelAgentRunAction {
+  type: 'CancelAgentRun'
+  payload: { run_id: string }
+}
+
+export interface SetAgentMaxConcurrentAction {
+  type: 'SetAgentMaxConcurrent'
+  payload: { max_concurrent: number }
+}
+
+export interface ClearFinishedAgentRunsAction {
+  type: 'ClearFinishedAgentRuns'
+}
+
+export interface LoadActivityFeedAction {
+  type: 'LoadActivityFeed'
+  payload: { since: string | null }
+}
+
+export interface SetActivityFeedAction {
+  type: 'SetActivityFeed'
+  payload: { project_id: string; since: string | null; events: ActivityEvent[] }
+}
+
+export interface AddActivityAction {
+  type: 'AddActivity'
+  payload: { event: ActivityEvent }
+}
+
+export interface SetSystemStatsAction {
+  type: 'SetSystemStats'
+  payload: { stats: SystemStats }
+}
+
+export interface UndoAction {
+  type: 'Undo'
+}
+
+export interface RedoAction {
+  type: 'Redo'
+}
+
+export interface PushUndoEntryAction {
+  type: 'PushUndoEntry'
+  payload: { entry: UndoEntry }
+}
+
 // File Explorer Actions
 export interface ExploreDirAction {
   type: 'ExploreDir'
@@ -1657,6 +4036,16 @@ export interface SelectFileAction {
   payload: { path?: string }
 }
 
+export interface LoadFileHistoryAction {
+  type: 'LoadFileHistory'
+  payload: { path: string }
+}
+
+export interface SetFileHistoryAction {
+  type: 'SetFileHistory'
+  payload: { path: string; commits: FileCommit[]; error?: string | null }
+}
+
 export interface NavigateBackAction {
   type: 'NavigateBack'
 }
@@ -1698,6 +4087,20 @@ export interface SetExplorerSortAction {
 export interface SetExplorerFilterAction {
   type: 'SetExplorerFilter'
   payload: { query: string }
+
+export interface SearchWorkspaceAction {
+  type: 'SearchWorkspace'
+  payload: { query: string; glob?: string | null; case_sensitive?: boolean }
+}
+
+export interface SetSearchResultsAction {
+  type: 'SetSearchResults'
+  payload: { query: string; results: SearchResults; error: string | null }
+}
+
+export interface ClearSearchAction {
+  type: 'ClearSearch'
+}
 }
 
 export interface ExpandDirectoryAction {
@@ -1718,6 +4121,35 @@ export interface SetDirectoryCacheAction {
   }
 }
 
+// Git Actions (active worktree)
+export interface GitStageAction {
+  type: 'GitStage'
+  payload: { paths: string[] }
+}
+
+export interface GitCommitAction {
+  type: 'GitCommit'
+  payload: { message: string }
+}
+
+export interface GitPushAction {
+  type: 'GitPush'
+}
+
+export interface GitPullAction {
+  type: 'GitPull'
+}
+
+export interface SetGitBusyAction {
+  type: 'SetGitBusy'
+  payload: { is_busy: boolean }
+}
+
+export interface SetSecurityScanResultAction {
+  type: 'SetSecurityScanResult'
+  payload: { result: SecurityScanResult | null }
+}
+
 // Tab Management Actions (VSCode-style preview tabs)
 export interface OpenFileTabAction {
   type: 'OpenFileTab'
@@ -1754,6 +4186,16 @@ export type Action =
   | FetchBranchesAction
   | SetBranchesAction
   | SetBranchesLoadingAction
+  | RefreshWorktreeHealthAction
+  | SetWorktreeHealthAction
+  | LoadWorktreeDiffAction
+  | SetWorktreeDiffAction
+  | RunDependencyAuditAction
+  | SetDependencyAuditAction
+  | RunBuildCheckAction
+  | SetBuildDiagnosticsAction
+  | SetLspServerStatusAction
+  | StopLspServersAction
   | StartMcpServerAction
   | StopMcpServerAction
   | SetMcpStatusAction
@@ -1763,24 +4205,50 @@ export type Action =
   | AddMcpLogEntryAction
   | ClearMcpLogsAction
   | UpdateMcpToolsAction
+  | UpdateMcpMetricsAction
+  | SetMcpPolicyAction
+  | ConnectExternalMcpServersAction
+  | DisconnectExternalMcpServersAction
+  | SetExternalMcpServersAction
+  | CallExternalMcpToolAction
+  | StartMcpPlaygroundCallAction
+  | FinishMcpPlaygroundCallAction
+  | ClearMcpPlaygroundAction
   | SendChatMessageAction
+  | SendTemplatedPromptAction
   | AddChatMessageAction
   | AppendChatContentAction
+  | AddChatToolCallAction
+  | CompleteChatToolCallAction
+  | RequestToolApprovalAction
+  | ApproveToolUseAction
+  | DenyToolUseAction
+  | ProposeEditsAction
+  | AcceptProposedEditAction
+  | RejectProposedEditAction
+  | ApplyProposedEditsAction
+  | SetProposedEditsAppliedAction
+  | DismissProposedEditsAction
   | SetChatTypingAction
   | SetChatErrorAction
   | ClearChatErrorAction
   | ClearChatAction
+  | SetChatSessionIdAction
+  | CancelChatMessageAction
   | StartConstitutionWorkflowAction
   | ClearConstitutionWorkflowAction
   | AnswerConstitutionQuestionAction
   | GenerateConstitutionAction
   | AppendConstitutionOutputAction
+  | CancelConstitutionGenerationAction
   | SaveConstitutionAction
   | CheckConstitutionExistsAction
   | SetConstitutionExistsAction
   | ApplyDefaultConstitutionAction
   | ReadConstitutionAction
   | SetConstitutionContentAction
+  | LintConstitutionAction
+  | SetConstitutionLintReportAction
   | SetClaudeMdExistsAction
   | ReadClaudeMdAction
   | SetClaudeMdContentAction
@@ -1801,11 +4269,17 @@ export type Action =
   | RejectReviewAction
   | UpdateReviewContentAction
   | SetReviewStatusAction
+  | LoadReviewCommentsAction
+  | SetReviewCommentsAction
   | SetReviewGateLoadingAction
   | SetReviewGateErrorAction
   | SetActiveReviewSessionAction
   | ClearReviewSessionAction
+  | RunComplianceCheckAction
+  | SetComplianceCheckAction
+  | OverrideComplianceCheckAction
   | CreateChangeAction
+  | CreateChangeFromIssueAction
   | GenerateProposalAction
   | AppendProposalOutputAction
   | CompleteProposalAction
@@ -1816,6 +4290,7 @@ export type Action =
   | CancelChangeAction
   | SelectChangeAction
   | RefreshChangesAction
+  | ResolveChangeStatusConflictAction
   | SetChangesAction
   | SetChangesLoadingAction
   | AddContextFileAction
@@ -1842,10 +4317,26 @@ export type Action =
   | CompleteGenerateContextAction
   | FailGenerateContextAction
   | SetChangeArchivedAction
+  | OpenChangePullRequestAction
+  | SetChangePullRequestAction
   | ExecutePlanAction
   | AppendImplementationOutputAction
+  | CompleteImplementationTaskAction
+  | StartImplementationTestsAction
   | CompleteImplementationAction
+  | FailProposalAction
+  | CancelProposalAction
   | FailImplementationAction
+  | CancelImplementationAction
+  | RequestImplementationApprovalAction
+  | ResolveImplementationApprovalAction
+  | SetChangeSnapshotAction
+  | RollbackImplementationAction
+  | CompleteRollbackAction
+  | SetChangeTestResultsAction
+  | SetChangeHookResultsAction
+  | RunChangeTestsAction
+  | OverrideTestGateAction
   | CheckDockerAvailabilityAction
   | SetDockerAvailableAction
   | RefreshDockerServicesAction
@@ -1861,14 +4352,57 @@ export type Action =
   | SetDockerConnectionStringAction
   | SetDockerLoadingAction
   | SetDockerLogsLoadingAction
+  | SetImagePullProgressAction
+  | FinishImagePullAction
+  | SetDockerStatsAction
+  | SetServiceHealthAction
+  | FetchDockerDatabasesAction
+  | SetDockerDatabasesAction
+  | FetchDockerTablesAction
+  | SetDockerTablesAction
+  | SetDockerDatabasesLoadingAction
   | SetPortConflictAction
   | ClearPortConflictAction
   | StartDockerServiceWithPortAction
   | ResolveConflictByStoppingContainerAction
+  | KillProcessOnPortAction
+  | DockerComposeUpAction
+  | DockerComposeDownAction
+  | AddComposeFileAction
+  | LoadHttpRequestsAction
+  | SetHttpRequestsAction
+  | RunHttpRequestAction
+  | SetHttpResponseAction
+  | LoadServiceGroupsAction
+  | SetServiceGroupsAction
+  | AssignServiceToGroupAction
+  | DeleteServiceGroupAction
+  | StartServiceGroupAction
+  | StopServiceGroupAction
+  | OpenDockerExecAction
+  | SetDockerExecSessionAction
+  | CloseDockerExecAction
+  | StartProxyAction
+  | StopProxyAction
+  | SetProxyRoutesAction
+  | SetProxyStatusAction
   | LoadJustfileCommandsAction
   | RefreshJustfileAction
   | SetJustfileCommandsAction
   | RunJustCommandAction
+  | RunJustCommandWithArgsAction
+  | LoadTasksAction
+  | SetTasksAction
+  | RunTaskAction
+  | QueueTaskRunAction
+  | StartTaskRunAction
+  | AppendTaskRunOutputAction
+  | FinishTaskRunAction
+  | CancelTaskAction
+  | LoadScheduleAction
+  | SetScheduleAction
+  | SetSchedulePausedAction
+  | MarkScheduleRunAction
   | SetTaskStatusAction
   | SetActiveCommandAction
   | AppendTaskOutputAction
@@ -1877,17 +4411,43 @@ export type Action =
   | SetTasksErrorAction
   | SetThemeAction
   | SetProjectPathAction
+  | SetModelAction
+  | SetProjectModelAction
+  | SetRequireChecklistsAction
+  | SetCollectCoverageAction
+  | SetCheckBuildOnSaveAction
+  | SetTaskMaxParallelAction
+  | SetImplementationTimeoutAction
+  | SetDesktopNotificationAction
+  | SetLlmProviderAction
+  | SetOpenAiSettingsAction
+  | SetOllamaSettingsAction
+  | SetGitHostingSettingsAction
+  | SetKeybindingAction
+  | ResetKeybindingsAction
+  | SaveSettingsProfileAction
+  | DeleteSettingsProfileAction
+  | SwitchSettingsProfileAction
   | CopyEnvFilesAction
   | SetEnvCopyResultAction
   | SetEnvTrackedPatternsAction
   | SetEnvAutoCopyAction
   | SetEnvSourceWorktreeAction
+  | DiffEnvFilesAction
+  | SetEnvDiffAction
+  | SyncEnvKeysAction
+  | ScanEnvSecretsAction
+  | SetEnvSecretsAction
+  | InjectConnectionStringAction
+  | SetEnvInjectionPreviewAction
+  | SetAutoResolvePortsAction
   | SetAgentRulesEnabledAction
   | SetAgentRulesPromptAction
   | SetAgentRulesTempFileAction
   | CreateAgentProfileAction
   | UpdateAgentProfileAction
   | DeleteAgentProfileAction
+  | ImportAgentProfileAction
   | SelectAgentProfileAction
   | AddNotificationAction
   | DismissNotificationAction
@@ -1905,15 +4465,75 @@ export type Action =
   | ClearErrorAction
   | AddDevLogAction
   | ClearDevLogsAction
+  | LoadAppLogsAction
+  | SetAppLogsAction
   | ReadFileAction
   | SetFileContentAction
   | SetFileLoadingAction
   | ReadBinaryFileAction
   | SetBinaryFileContentAction
+  | WriteFileAction
+  | SetFileDirtyAction
+  | SetFileSaveErrorAction
   | SetA2UIPayloadAction
+  | AddUsageRecordAction
+  | LoadSessionsAction
+  | SetSessionsAction
+  | AddSessionAction
+  | DeleteSessionAction
+  | StartSpecWorkflowAction
+  | SetSpecWorkflowAction
+  | RunSpecPhaseAction
+  | AppendSpecPhaseOutputAction
+  | CompleteSpecPhaseAction
+  | FailSpecPhaseAction
+  | CancelSpecPhaseAction
+  | ClearSpecWorkflowAction
+  | StartClarifySessionAction
+  | SetClarifySessionAction
+  | AnswerClarifyQuestionAction
+  | SkipClarifyQuestionAction
+  | CompleteClarifySessionAction
+  | FailClarifySessionAction
+  | ClearClarifySessionAction
+  | LoadSpecTasksAction
+  | SetSpecTasksAction
+  | ToggleSpecTaskAction
+  | ReportSpecProgressAction
+  | GenerateChecklistAction
+  | SetChecklistAction
+  | FailChecklistAction
+  | LoadChecklistsAction
+  | SetChecklistsAction
+  | ToggleChecklistItemAction
+  | AnalyzeSpecAction
+  | CompleteSpecAnalysisAction
+  | FailSpecAnalysisAction
+  | LoadFeaturesCatalogAction
+  | SetFeaturesCatalogAction
+  | StartFeatureWorkAction
+  | QueueAgentRunAction
+  | AddAgentRunAction
+  | StartAgentRunAction
+  | AppendAgentRunOutputAction
+  | FinishAgentRunAction
+  | RequestAgentRunApprovalAction
+  | ResolveAgentRunApprovalAction
+  | CancelAgentRunAction
+  | SetAgentMaxConcurrentAction
+  | ClearFinishedAgentRunsAction
+  | LoadActivityFeedAction
+  | SetActivityFeedAction
+  | AddActivityAction
+  | SetSystemStatsAction
+  | UndoAction
+  | RedoAction
+  | PushUndoEntryAction
   | ExploreDirAction
   | SetExplorerEntriesAction
   | SelectFileAction
+  | LoadFileHistoryAction
+  | SetFileHistoryAction
   | NavigateBackAction
   | NavigateForwardAction
   | NavigateUpAction
@@ -1921,9 +4541,18 @@ export type Action =
   | AddFileCommentAction
   | SetExplorerSortAction
   | SetExplorerFilterAction
+  | SearchWorkspaceAction
+  | SetSearchResultsAction
+  | ClearSearchAction
   | ExpandDirectoryAction
   | CollapseDirectoryAction
   | SetDirectoryCacheAction
+  | GitStageAction
+  | GitCommitAction
+  | GitPushAction
+  | GitPullAction
+  | SetGitBusyAction
+  | SetSecurityScanResultAction
   | OpenFileTabAction
   | PinTabAction
   | CloseTabAction
@@ -3,7 +3,146 @@
 
 /* auto-generated by NAPI-RS */
 
+/**
+ * Shareable agent profile, stored as a single TOML file:
+ *
+ * ```toml
+ * name = "Strict Rust Reviewer"
+ * description = "Reviews Rust changes for safety and idioms"
+ * author = "Platform Team"
+ * version = "1.2.0"
+ * prompt = """
+ * You are a strict Rust reviewer...
+ * """
+ * ```
+ */
+/** A template variable (`{{name}}` or `{{name|default}}`) */
+export interface PromptVariable {
+  name: string
+  /** Used when no value is given */
+  default?: string
+}
+/** A model installed on the Ollama server */
+export interface OllamaModel {
+  /** Model tag passed to the API (e.g. "llama3.2:latest") */
+  name: string
+  /** Size on disk in bytes */
+  size: number
+  /** e.g. "3.2B" */
+  parameterSize?: string
+  /** e.g. "Q4_K_M" */
+  quantization?: string
+}
+/** An open issue */
+export interface GitHubIssue {
+  number: number
+  title: string
+  /** Markdown body (empty if none) */
+  body: string
+  labels: Array<string>
+  /** Web page of the issue */
+  url: string
+  /** Login of the author */
+  author?: string
+}
+/** A log event */
+export interface LogEntry {
+  /** ISO 8601 */
+  timestamp: string
+  /** "ERROR" | "WARN" | "INFO" | "DEBUG" | "TRACE" */
+  level: string
+  /** Module that logged the event (e.g. "rstn_core::docker") */
+  module: string
+  /** Message followed by any structured fields */
+  message: string
+}
+/** Result of a single check */
+export interface DoctorCheck {
+  /** Stable identifier (e.g. "claude", "docker") */
+  id: string
+  label: string
+  /** "ok" | "warning" | "error" */
+  status: string
+  /** What was found (version, free space, ...) */
+  detail: string
+  /** How to fix a warning or error */
+  fix?: string
+}
+/** All checks of a doctor run */
+export interface DoctorReport {
+  checks: Array<DoctorCheck>
+  /** When the checks ran (ISO 8601) */
+  ranAt: string
+}
+/** A prompt template from the library */
+export interface PromptTemplate {
+  /** File stem (e.g. "review-module") */
+  id: string
+  /** Frontmatter `name`, or the id */
+  name: string
+  description?: string
+  /** Variables in order of first use */
+  variables: Array<PromptVariable>
+  /** Template text without frontmatter */
+  body: string
+  path: string
+}
+export interface AgentProfileFile {
+  name: string
+  description: string
+  author: string
+  /** Semantic version (`MAJOR.MINOR.PATCH`) */
+  version: string
+  prompt: string
+}
 /** A command parsed from a justfile */
+/** A palette entry, with enablement evaluated against the current state */
+export interface PaletteAction {
+  /** Stable ID (e.g. "view.tasks", "task.just:build") */
+  id: string
+  /** Human-readable label */
+  label: string
+  /** Group heading (e.g. "Views", "Git") */
+  category: string
+  /** Keyboard hint (e.g. "CmdOrCtrl+1") */
+  keybinding?: string
+  enabled: boolean
+  /** Why the entry is disabled */
+  disabledReason?: string
+  /** Action JSON for `state_dispatch` */
+  action: string
+}
+/** Replay outcome returned to JavaScript */
+export interface TraceReplayResult {
+  replayed: number
+  mismatches: number
+  firstMismatch?: number
+  /** Replayed state as JSON */
+  state: string
+}
+/** Where a started bridge listens */
+export interface StateBridgeInfo {
+  port: number
+  /** Token clients must pass as `?token=` */
+  token: string
+  /** ws:// URL including the token */
+  url: string
+}
+/** Notification passed to the desktop listener */
+export interface DesktopNotification {
+  /** Event name (snake_case `DesktopNotificationEvent`) */
+  event: string
+  title: string
+  body: string
+}
+/** A stack detected in a project, as shown in the desktop UI */
+export interface DetectedStack {
+  /** Stack id (module file stem) */
+  id: string
+  name: string
+  /** A user template overrides the built-in one */
+  hasUserTemplate: boolean
+}
 export interface JustCommand {
   /** Command name (e.g., "test", "build") */
   name: string
@@ -11,6 +150,104 @@ export interface JustCommand {
   description?: string
   /** The recipe/shell commands */
   recipe: string
+  /** Recipe parameters, in order */
+  parameters: Array<JustParameter>
+}
+/** A recipe parameter (`name`, `name="default"`, `+rest`, `*rest`) */
+export interface JustParameter {
+  name: string
+  /** Default value (None = required) */
+  default?: string
+  /** Accepts any number of values (`+` one or more, `*` zero or more) */
+  variadic: boolean
+}
+/** State of a project's pre-commit hook */
+export interface GitHookStatus {
+  /** Path of the pre-commit hook */
+  path: string
+  /** A pre-commit hook exists */
+  installed: boolean
+  /** The existing hook was written by rstn */
+  managed: boolean
+  /** Template version of the installed managed hook */
+  version?: number
+  /** Template version rstn would install now */
+  currentVersion: number
+  /**
+   * The managed hook differs from a fresh install (new template or
+   * changed `.rstn/hooks.toml`)
+   */
+  updateAvailable: boolean
+  /** Names of the pipeline hooks a fresh install would run */
+  pipeline: Array<string>
+  /** Why the pipeline could not be read, if it could not */
+  pipelineError?: string
+}
+/** A definition in a source file */
+export interface Symbol {
+  name: string
+  /**
+   * function, method, struct, enum, union, trait, impl, module, type,
+   * constant, static, macro, class, interface
+   */
+  kind: string
+  /** Declaration without its body, whitespace collapsed */
+  signature: string
+  /** 1-based */
+  startLine: number
+  endLine: number
+  /** Nesting level (0 = top level) */
+  depth: number
+  /** Enclosing impl, trait, module or class */
+  parent?: string
+}
+/** Target of a definition (1-based lines and columns) */
+export interface LspLocation {
+  path: string
+  line: number
+  column: number
+  endLine: number
+  endColumn: number
+}
+/** A rendered document */
+export interface RenderedMarkdown {
+  /** Sanitized HTML */
+  html: string
+  /** Headings in document order (for outlines and anchors) */
+  headings: Array<MarkdownHeading>
+  /** Fenced code blocks other than mermaid */
+  codeBlocks: Array<MarkdownCodeBlock>
+  /** Mermaid diagrams, indexed by their placeholder's `data-mermaid-index` */
+  mermaid: Array<MermaidDiagram>
+}
+export interface MarkdownHeading {
+  /** 1-6 */
+  level: number
+  text: string
+  /** `id` attribute of the heading element */
+  anchor: string
+  /** 1-based */
+  line: number
+}
+export interface MarkdownCodeBlock {
+  /** First word of the fence info string (None for indented or bare fences) */
+  language?: string
+  code: string
+  /** 1-based line of the opening fence */
+  line: number
+}
+export interface MermaidDiagram {
+  source: string
+  /** 1-based line of the opening fence */
+  line: number
+}
+/** Outcome of merging a reviewer's comments */
+export interface FeedbackImport {
+  changeName: string
+  /** New comments */
+  added: number
+  /** Existing comments edited or resolved by the reviewer */
+  updated: number
 }
 /** Service status */
 export const enum ServiceStatus {
@@ -57,6 +294,59 @@ export interface PortConflictInfo {
   isRstnManaged: boolean
   /** Suggested alternative port */
   suggestedPort: number
+  /** Host process holding the port when it is not a Docker container */
+  process?: PortProcessInfo
+}
+/** A non-Docker process listening on a port */
+export interface PortProcessInfo {
+  pid: number
+  /** Process name (e.g. "node") */
+  name: string
+  /** Executable path, if readable */
+  exe?: string
+  /** Owning user, if known */
+  user?: string
+  /** Full command line, if readable */
+  command?: string
+}
+/** Local Docker image */
+export interface DockerImage {
+  id: string
+  /** Tags (e.g. "postgres:16-alpine"), empty for dangling images */
+  repoTags: Array<string>
+  /** Size in bytes */
+  size: number
+  /** Creation time (Unix seconds) */
+  created: number
+  /** Untagged image left behind by a newer pull/build */
+  dangling: boolean
+}
+/** Database inside a database container */
+export interface DockerDatabase {
+  name: string
+  /** Size on disk in bytes (if the engine reports it) */
+  size?: number
+}
+/** Table (MongoDB: collection) inside a database */
+export interface DockerTable {
+  /** Schema (PostgreSQL/MySQL), absent for MongoDB collections */
+  schema?: string
+  name: string
+  /** Estimated row (document) count */
+  rowCount?: number
+}
+/** Output of a command run in a container */
+export interface ExecOutput {
+  /** None if Docker didn't report one */
+  exitCode?: number
+  stdout: string
+  stderr: string
+}
+/** Result of pruning dangling images */
+export interface ImagePruneResult {
+  imagesDeleted: number
+  /** Disk space reclaimed in bytes */
+  spaceReclaimed: number
 }
 /** Check if Docker is available */
 export declare function dockerIsAvailable(): Promise<boolean>
@@ -82,12 +372,47 @@ export declare function dockerCreateDatabase(serviceId: string, dbName: string):
  * Returns the connection string for the new vhost
  */
 export declare function dockerCreateVhost(serviceId: string, vhostName: string): Promise<string>
+/** List databases in a database container (PostgreSQL, MySQL, MongoDB) */
+export declare function dockerListDatabases(serviceId: string): Promise<Array<DockerDatabase>>
+/** List tables (MongoDB: collections) in a database of a database container */
+export declare function dockerListTables(serviceId: string, dbName: string): Promise<Array<DockerTable>>
 /** Start a Docker service with a specific port override */
 export declare function dockerStartServiceWithPort(serviceId: string, port: number): Promise<void>
 /** Stop any Docker container by ID or name */
 export declare function dockerStopContainer(containerId: string): Promise<void>
 /** Check for port conflict before starting a service */
 export declare function dockerCheckPortConflict(serviceId: string): Promise<PortConflictInfo | null>
+/** Identify host processes (outside Docker) listening on a port */
+export declare function netInspectPort(port: number): Promise<Array<PortProcessInfo>>
+/**
+ * Import a docker-compose.yml and register its services as rstn-managed
+ * Returns the services of the imported project
+ */
+export declare function dockerImportCompose(path: string): Promise<Array<DockerService>>
+/**
+ * Pull a Docker image. Layer progress is streamed to the state listener
+ * (`docker.image_pulls`) until the pull finishes.
+ */
+export declare function dockerPullImage(image: string): Promise<void>
+/**
+ * Start streaming resource stats for a running service. Samples are pushed
+ * to the state listener (`docker.stats`) until the container stops or
+ * `docker_stats_stop` is called. Restarts an existing stream for the service.
+ */
+export declare function dockerStatsStream(serviceId: string): Promise<void>
+/** Stop streaming resource stats for a service */
+export declare function dockerStatsStop(serviceId: string): void
+/** List local Docker images */
+export declare function dockerListImages(): Promise<Array<DockerImage>>
+/** Run a command in an rstn-managed container and return its output */
+export declare function dockerExec(serviceId: string, cmd: Array<string>): Promise<ExecOutput>
+/** Remove dangling Docker images */
+export declare function dockerPruneImages(): Promise<ImagePruneResult>
+/**
+ * Reload service templates from `~/.rstn/services.toml` and the active
+ * project's `.rstn/services.toml`. Returns the number of services defined.
+ */
+export declare function dockerReloadServiceTemplates(): Promise<number>
 /** Parse a justfile and return all commands */
 export declare function justfileParse(path: string): Array<JustCommand>
 /** Run a just command in a directory */
@@ -142,6 +467,23 @@ export interface NapiFileEntry {
  * Used for tree view expansion.
  */
 export declare function explorerListDirectory(path: string, projectRoot: string): Array<NapiFileEntry>
+/**
+ * Functions, types and methods of a source file (Rust, TS/JS, Python),
+ * cached until the file changes
+ */
+export declare function symbolsForFile(path: string): Array<Symbol>
+/**
+ * Render markdown from a file or from text (exactly one of `path` and
+ * `content`) to sanitized HTML, with mermaid diagrams extracted
+ */
+export declare function renderMarkdown(path?: string | undefined | null, content?: string | undefined | null): RenderedMarkdown
+/**
+ * Hover text (markdown) at a 1-based line and column of a file in an open
+ * worktree. Starts the file's language server on first use.
+ */
+export declare function lspHover(path: string, line: number, column: number): Promise<string | null>
+/** Where the symbol at a 1-based line and column is defined */
+export declare function lspDefinition(path: string, line: number, column: number): Promise<Array<LspLocation>>
 /** Branch info for napi export */
 export interface NapiBranchInfo {
   name: string
@@ -150,11 +492,252 @@ export interface NapiBranchInfo {
 }
 /** List all branches in a repository */
 export declare function worktreeListBranches(repoPath: string): Array<NapiBranchInfo>
+/** Structured branch diff for napi export */
+export interface NapiWorktreeDiff {
+  base: string
+  head: string
+  files: Array<NapiDiffFile>
+}
+/** Changed file in a branch diff */
+export interface NapiDiffFile {
+  path: string
+  oldPath?: string
+  status: string
+  isBinary: boolean
+  additions: number
+  deletions: number
+  hunks: Array<NapiDiffHunk>
+}
+/** Hunk in a changed file */
+export interface NapiDiffHunk {
+  header: string
+  oldStart: number
+  oldLines: number
+  newStart: number
+  newLines: number
+  lines: Array<NapiDiffLine>
+}
+/** Line in a hunk */
+export interface NapiDiffLine {
+  lineType: string
+  content: string
+  oldLine?: number
+  newLine?: number
+}
+/** Diff `head` against its merge base with `base` (e.g. feature branch vs main) */
+export declare function worktreeDiff(repoPath: string, base: string, head: string): NapiWorktreeDiff
+/** Stage files in a repository */
+export declare function gitStage(repoPath: string, paths: Array<string>): void
+/**
+ * Commit staged changes (blocked if the security scan finds secrets).
+ * Returns the new commit hash.
+ */
+export declare function gitCommit(repoPath: string, message: string): string
+/** Push the current branch */
+export declare function gitPush(repoPath: string): void
+/** Pull the current branch (fast-forward only) */
+export declare function gitPull(repoPath: string): void
+/** A commit that changed a file */
+export interface FileCommit {
+  hash: string
+  shortHash: string
+  author: string
+  email: string
+  /** Author date (ISO 8601) */
+  date: string
+  /** First line of the message */
+  summary: string
+}
+/** Consecutive lines last changed by the same commit */
+export interface BlameHunk {
+  /** First line (1-based) */
+  lineStart: number
+  lineCount: number
+  /** Commit that last changed the lines (hash of zeros if not committed yet) */
+  commit: FileCommit
+}
+/** Commits that changed a file (following renames), newest first */
+export declare function gitFileHistory(path: string, limit?: number | undefined | null): Array<FileCommit>
+/** Last commit of every line of a file, grouped into hunks of consecutive lines */
+export declare function gitBlame(path: string): Array<BlameHunk>
+/**
+ * Install (or update) the rstn-managed pre-commit hook of a project: secret
+ * scan plus the `.rstn/hooks.toml` pipeline
+ */
+export declare function hooksInstall(project: string): GitHookStatus
+/** Whether the project's pre-commit hook is managed by rstn and up to date */
+export declare function hooksStatus(project: string): GitHookStatus
+/** Remove the rstn-managed pre-commit hook of a project */
+export declare function hooksUninstall(project: string): GitHookStatus
 /** List env files matching patterns in a directory */
 export declare function envListFiles(dir: string, patterns: Array<string>): Array<string>
 /** Get default env patterns */
 export declare function envDefaultPatterns(): Array<string>
+/** Env file diff for napi export */
+export interface NapiEnvFileDiff {
+  file: string
+  keys: Array<NapiEnvKeyDiff>
+}
+/** Differing env variable (values masked) */
+export interface NapiEnvKeyDiff {
+  key: string
+  change: string
+  fromValue?: string
+  toValue?: string
+}
+/** Diff env files between two worktrees key by key (values masked) */
+export declare function envDiffFiles(from: string, to: string, patterns: Array<string>): Array<NapiEnvFileDiff>
+/** List models available to the Claude CLI (for the model picker) */
+export declare function claudeListModels(): Promise<Array<string>>
+/** List models installed on the configured Ollama server (errors if none is running) */
+export declare function ollamaListModels(): Promise<Array<OllamaModel>>
+/**
+ * List open issues of a GitHub repository ("owner/repo", a remote URL or a
+ * local checkout), using the GitHub token from Settings if set
+ */
+export declare function githubListIssues(repo: string): Promise<Array<GitHubIssue>>
+/**
+ * Recent backend log entries (oldest first), at `level` or more severe and
+ * from modules containing `module`
+ */
+export declare function logsTail(n?: number | undefined | null, level?: string | undefined | null, module?: string | undefined | null): Array<LogEntry>
+/**
+ * Run the pre-flight checks (Claude CLI, Docker, git, just, MCP port,
+ * disk space, config directories)
+ */
+export declare function doctorRun(): Promise<DoctorReport>
+/**
+ * Write a zip with recent logs, the app state (secrets masked), versions,
+ * OS info and the last dev log actions, for attaching to bug reports
+ */
+export declare function diagnosticsExportBundle(path: string): Promise<void>
+/**
+ * List the language/framework stacks detected in a project, flagging those
+ * whose constitution template is overridden in ~/.config/rustation/constitutions/
+ */
+export declare function constitutionListDetectedStacks(path: string): Promise<Array<DetectedStack>>
+/**
+ * Spec-kit features of a worktree with their artifacts, task progress and
+ * branch, for the pipeline board (JSON array of FeatureInfo)
+ */
+export declare function featuresList(workspace: string): Promise<any>
+/** One feature of a worktree by number (JSON FeatureInfo) */
+export declare function featuresInfo(workspace: string, number: number): Promise<any>
+/** List the prompt templates in ~/.rstn/prompts/library/ */
+export declare function promptsList(): Array<PromptTemplate>
+/** Render a prompt template with variable values (defaults fill empty values) */
+export declare function promptsRender(id: string, vars: Record<string, string>): string
+/** Export an agent profile of the active project to `path` as a TOML file */
+export declare function agentRulesExport(profileId: string, path: string): Promise<void>
+/**
+ * Read and validate an agent profile file. The returned profile is added to
+ * the active project by dispatching `ImportAgentProfile`.
+ */
+export declare function agentRulesImport(path: string): AgentProfileFile
+/** Usage summary for napi export */
+export interface NapiUsageSummary {
+  projectId: string
+  /** "day", "week", "month" or "all" */
+  period: string
+  requestCount: number
+  inputTokens: number
+  outputTokens: number
+  cacheReadTokens: number
+  cacheCreationTokens: number
+  /** Estimated cost in USD */
+  costUsd: number
+}
+/** Summarize Claude CLI usage for a project over a period ("day", "week", "month", "all") */
+export declare function usageSummary(projectId: string, period: string): NapiUsageSummary
+/** Activity event for napi export */
+export interface NapiActivityEvent {
+  id: number
+  projectId: string
+  /**
+   * "change_created", "plan_approved", "service_started", "worktree_added"
+   * or "constitution_updated"
+   */
+  kind: string
+  summary: string
+  /** Structured details as JSON */
+  detailJson?: string
+  timestamp: string
+}
+/**
+ * List a project's activity, newest first, optionally only events at or
+ * after `since` (ISO 8601)
+ */
+export declare function activityList(projectId: string, since?: string | undefined | null): Array<NapiActivityEvent>
+/** Claude session record for napi export */
+export interface NapiSessionRecord {
+  id: string
+  projectId: string
+  /** "chat", "constitution", "proposal", "plan", "implementation", ... */
+  kind: string
+  worktreePath: string
+  claudeSessionId?: string
+  model?: string
+  promptExcerpt: string
+  startedAt: string
+  durationMs: number
+  inputTokens: number
+  outputTokens: number
+  costUsd: number
+  logFile?: string
+  /** "success", "error" or "interrupted" */
+  outcome: string
+  error?: string
+}
+/** List a project's Claude sessions, newest first */
+export declare function sessionsList(projectId: string, limit?: number | undefined | null): Array<NapiSessionRecord>
+/** Get one Claude session by ID */
+export declare function sessionsInfo(id: string): NapiSessionRecord | null
+/** Delete a Claude session from the history; returns whether it existed */
+export declare function sessionsDelete(id: string): boolean
+/**
+ * Export a Claude session's transcript (prompts, assistant text, tool calls,
+ * timings) to `path` as "markdown" or "json"
+ */
+export declare function sessionExport(sessionId: string, format: string, path: string): void
+/**
+ * Zip a change of the active worktree (documents, implementation diff, test
+ * results, review comments) to `path` for reviewers without rstn; returns
+ * the bundled files
+ */
+export declare function changeExportBundle(changeId: string, path: string): Promise<Array<string>>
+/**
+ * Merge review comments from a returned bundle (or its review.json) into
+ * the active worktree's change
+ */
+export declare function changeImportFeedback(path: string): Promise<FeedbackImport>
+/** Running MCP server info for napi export */
+export interface NapiMcpServerInfo {
+  worktreeId: string
+  worktreePath: string
+  projectName: string
+  port: number
+}
+/** List all running MCP servers across open projects */
+export declare function mcpListRunningServers(): Promise<Array<NapiMcpServerInfo>>
+/** Per-tool MCP call metrics for napi export */
+export interface NapiMcpToolMetrics {
+  toolName: string
+  callCount: number
+  errorCount: number
+  p50Ms: number
+  p95Ms: number
+  lastCalledAt?: string
+}
+/** Get per-tool metrics for the active worktree's MCP server */
+export declare function mcpGetMetrics(): Promise<Array<NapiMcpToolMetrics>>
 /** Fetch available tools from MCP server */
+/**
+ * Call a tool on one of the active worktree's external MCP servers.
+ *
+ * `args_json` is the tool's arguments object. The call is recorded in the
+ * MCP playground history; returns the `tools/call` result as JSON.
+ */
+export declare function mcpCallTool(server: string, tool: string, argsJson: string): Promise<string>
 export declare function fetchMcpTools(): Promise<string>
 /** AI Context for napi export */
 export interface NapiAiContext {
@@ -170,6 +753,19 @@ export interface NapiAiContext {
   directoryTree?: string
   /** Git diff */
   gitDiff?: string
+  /** Dependencies declared in Cargo.toml / package.json / pyproject.toml */
+  dependencies: Array<NapiDependency>
+}
+/** Declared dependency for napi export */
+export interface NapiDependency {
+  /** Manifest file name (e.g. "Cargo.toml") */
+  manifest: string
+  /** Package name */
+  name: string
+  /** Version requirement as declared */
+  version: string
+  /** Development-only dependency */
+  dev: boolean
 }
 /** File context for napi export */
 export interface NapiFileContext {
@@ -184,10 +780,25 @@ export interface NapiFileContext {
  * Build AI context for a project path
  *
  * Gathers context from git, files, and other sources within a token budget.
+ * With a prompt, project files relevant to it are ranked and included.
  */
-export declare function contextBuild(projectPath: string, activeFiles: Array<string>, taskOutput: string | undefined | null, dockerErrors: Array<string>, tokenBudget?: number | undefined | null): NapiAiContext
+export declare function contextBuild(projectPath: string, activeFiles: Array<string>, taskOutput: string | undefined | null, dockerErrors: Array<string>, tokenBudget?: number | undefined | null, prompt?: string | undefined | null): NapiAiContext
 /** Build AI context and format as a system prompt string */
-export declare function contextBuildSystemPrompt(projectPath: string, activeFiles: Array<string>, taskOutput: string | undefined | null, dockerErrors: Array<string>, tokenBudget?: number | undefined | null): string
+export declare function contextBuildSystemPrompt(projectPath: string, activeFiles: Array<string>, taskOutput: string | undefined | null, dockerErrors: Array<string>, tokenBudget?: number | undefined | null, prompt?: string | undefined | null): string
+/**
+ * Register a listener for PTY output.
+ *
+ * The callback is invoked with `(sessionId, data)` for every chunk a shell
+ * writes. Chunks never split a UTF-8 character.
+ */
+export declare function terminalSetOutputListener(callback: (err: Error | null, sessionId: string, data: string) => void): void
+/**
+ * Register a listener for OS notifications.
+ *
+ * The callback is invoked with a notification for every long-running event
+ * the user has not turned off in settings; the desktop shell shows it.
+ */
+export declare function desktopNotificationSetListener(callback: (err: Error | null, notification: DesktopNotification) => void): void
 /**
  * Initialize the application state and register a listener for state updates.
  *
@@ -206,3 +817,48 @@ export declare function stateGet(): Promise<string>
  * After the action is processed, the state listener will be notified.
  */
 export declare function stateDispatch(actionJson: string): Promise<void>
+/**
+ * Restore the work of a session that did not shut down cleanly.
+ *
+ * Replays the previous session's action journal over its last snapshot and
+ * replaces the current state. Returns false if there was nothing to recover.
+ * Call once after `state_init`.
+ */
+export declare function recoverState(): Promise<boolean>
+/**
+ * Mark a clean shutdown: the next launch offers nothing to recover.
+ * Call when the app quits.
+ */
+export declare function stateShutdown(): void
+/**
+ * Start the localhost WebSocket state bridge (0 or no port = any free port).
+ *
+ * Clients receive the full state, then JSON Patch diffs, and can send
+ * actions as text messages. Restarts the bridge if it is already running.
+ * Only available in builds with the `state-bridge` feature.
+ */
+export declare function stateBridgeStart(port?: number | undefined | null): Promise<StateBridgeInfo>
+/**
+ * Stop the WebSocket state bridge and disconnect its clients.
+ * Only available in builds with the `state-bridge` feature.
+ */
+export declare function stateBridgeStop(): void
+/** Start or stop recording the action trace (dev mode). */
+export declare function devSetTraceEnabled(enabled: boolean): void
+/**
+ * Write the recorded action trace (last 1000 actions with state hashes and
+ * reducer durations) to `path` as JSON. Returns the number of entries.
+ */
+export declare function devExportTrace(path: string): number
+/**
+ * Replay a trace exported by `dev_export_trace` against a fresh state.
+ * The current state is not touched.
+ */
+export declare function devReplayTrace(path: string): Promise<TraceReplayResult>
+/**
+ * List command palette actions for the current state.
+ *
+ * Each entry's `action` is the JSON to pass to `state_dispatch`; disabled
+ * entries carry the reason in `disabled_reason`.
+ */
+export declare function paletteListActions(): Promise<Array<PaletteAction>>
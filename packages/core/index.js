@@ -310,7 +310,7 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { ServiceStatus, ServiceType, dockerIsAvailable, dockerListServices, dockerStartService, dockerStopService, dockerRestartService, dockerGetLogs, dockerRemoveService, dockerCreateDatabase, dockerCreateVhost, dockerStartServiceWithPort, dockerStopContainer, dockerCheckPortConflict, justfileParse, justfileRun, fileRead, fileReadBinary, explorerListDirectory, worktreeListBranches, envListFiles, envDefaultPatterns, fetchMcpTools, contextBuild, contextBuildSystemPrompt, stateInit, stateGet, stateDispatch } = nativeBinding
+const { ServiceStatus, ServiceType, dockerIsAvailable, dockerListServices, dockerStartService, dockerStopService, dockerRestartService, dockerGetLogs, dockerRemoveService, dockerCreateDatabase, dockerCreateVhost, dockerListDatabases, dockerListTables, dockerStartServiceWithPort, dockerStopContainer, dockerCheckPortConflict, netInspectPort, dockerImportCompose, dockerPullImage, dockerStatsStream, dockerStatsStop, dockerListImages, dockerExec, dockerPruneImages, dockerReloadServiceTemplates, justfileParse, justfileRun, fileRead, fileReadBinary, explorerListDirectory, symbolsForFile, renderMarkdown, lspHover, lspDefinition, worktreeListBranches, worktreeDiff, gitStage, gitCommit, gitPush, gitPull, gitFileHistory, gitBlame, hooksInstall, hooksStatus, hooksUninstall, envListFiles, envDefaultPatterns, envDiffFiles, claudeListModels, ollamaListModels, githubListIssues, logsTail, doctorRun, diagnosticsExportBundle, constitutionListDetectedStacks, featuresList, featuresInfo, promptsList, promptsRender, agentRulesExport, agentRulesImport, usageSummary, activityList, sessionsList, sessionsInfo, sessionsDelete, sessionExport, changeExportBundle, changeImportFeedback, mcpListRunningServers, mcpGetMetrics, mcpCallTool, fetchMcpTools, contextBuild, contextBuildSystemPrompt, terminalSetOutputListener, desktopNotificationSetListener, stateInit, stateGet, stateDispatch, recoverState, stateShutdown, stateBridgeStart, stateBridgeStop, devSetTraceEnabled, devExportTrace, devReplayTrace, paletteListActions } = nativeBinding
 
 module.exports.ServiceStatus = ServiceStatus
 module.exports.ServiceType = ServiceType
@@ -323,20 +323,80 @@ module.exports.dockerGetLogs = dockerGetLogs
 module.exports.dockerRemoveService = dockerRemoveService
 module.exports.dockerCreateDatabase = dockerCreateDatabase
 module.exports.dockerCreateVhost = dockerCreateVhost
+module.exports.dockerListDatabases = dockerListDatabases
+module.exports.dockerListTables = dockerListTables
 module.exports.dockerStartServiceWithPort = dockerStartServiceWithPort
 module.exports.dockerStopContainer = dockerStopContainer
 module.exports.dockerCheckPortConflict = dockerCheckPortConflict
+module.exports.netInspectPort = netInspectPort
+module.exports.dockerImportCompose = dockerImportCompose
+module.exports.dockerPullImage = dockerPullImage
+module.exports.dockerStatsStream = dockerStatsStream
+module.exports.dockerStatsStop = dockerStatsStop
+module.exports.dockerListImages = dockerListImages
+module.exports.dockerExec = dockerExec
+module.exports.dockerPruneImages = dockerPruneImages
+module.exports.dockerReloadServiceTemplates = dockerReloadServiceTemplates
 module.exports.justfileParse = justfileParse
 module.exports.justfileRun = justfileRun
 module.exports.fileRead = fileRead
 module.exports.fileReadBinary = fileReadBinary
 module.exports.explorerListDirectory = explorerListDirectory
+module.exports.symbolsForFile = symbolsForFile
+module.exports.renderMarkdown = renderMarkdown
+module.exports.lspHover = lspHover
+module.exports.lspDefinition = lspDefinition
 module.exports.worktreeListBranches = worktreeListBranches
+module.exports.worktreeDiff = worktreeDiff
+module.exports.gitStage = gitStage
+module.exports.gitCommit = gitCommit
+module.exports.gitPush = gitPush
+module.exports.gitPull = gitPull
+module.exports.gitFileHistory = gitFileHistory
+module.exports.gitBlame = gitBlame
+module.exports.hooksInstall = hooksInstall
+module.exports.hooksStatus = hooksStatus
+module.exports.hooksUninstall = hooksUninstall
 module.exports.envListFiles = envListFiles
 module.exports.envDefaultPatterns = envDefaultPatterns
+module.exports.envDiffFiles = envDiffFiles
+module.exports.claudeListModels = claudeListModels
+module.exports.ollamaListModels = ollamaListModels
+module.exports.githubListIssues = githubListIssues
+module.exports.logsTail = logsTail
+module.exports.doctorRun = doctorRun
+module.exports.diagnosticsExportBundle = diagnosticsExportBundle
+module.exports.constitutionListDetectedStacks = constitutionListDetectedStacks
+module.exports.featuresList = featuresList
+module.exports.featuresInfo = featuresInfo
+module.exports.promptsList = promptsList
+module.exports.promptsRender = promptsRender
+module.exports.agentRulesExport = agentRulesExport
+module.exports.agentRulesImport = agentRulesImport
+module.exports.usageSummary = usageSummary
+module.exports.activityList = activityList
+module.exports.sessionsList = sessionsList
+module.exports.sessionsInfo = sessionsInfo
+module.exports.sessionsDelete = sessionsDelete
+module.exports.sessionExport = sessionExport
+module.exports.changeExportBundle = changeExportBundle
+module.exports.changeImportFeedback = changeImportFeedback
+module.exports.mcpListRunningServers = mcpListRunningServers
+module.exports.mcpGetMetrics = mcpGetMetrics
+module.exports.mcpCallTool = mcpCallTool
 module.exports.fetchMcpTools = fetchMcpTools
 module.exports.contextBuild = contextBuild
 module.exports.contextBuildSystemPrompt = contextBuildSystemPrompt
+module.exports.terminalSetOutputListener = terminalSetOutputListener
+module.exports.desktopNotificationSetListener = desktopNotificationSetListener
 module.exports.stateInit = stateInit
 module.exports.stateGet = stateGet
 module.exports.stateDispatch = stateDispatch
+module.exports.recoverState = recoverState
+module.exports.stateShutdown = stateShutdown
+module.exports.stateBridgeStart = stateBridgeStart
+module.exports.stateBridgeStop = stateBridgeStop
+module.exports.devSetTraceEnabled = devSetTraceEnabled
+module.exports.devExportTrace = devExportTrace
+module.exports.devReplayTrace = devReplayTrace
+module.exports.paletteListActions = paletteListActions
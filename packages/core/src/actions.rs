@@ -3,7 +3,14 @@
 //! All state changes go through dispatch(action) -> reducer -> new state.
 //! Actions are serializable for logging, debugging, and replay.
 
-use crate::app_state::{FeatureTab, Theme};
+use crate::agent_rules::AgentProfileFile;
+use crate::app_state::{
+    ContainerStats, DatabaseInfo, EnvDiff, EnvInjectionPreview, FeatureTab, JustParameterInfo,
+    PortConflictStrategy, ServiceHealth, TableInfo, Theme,
+};
+use crate::git::SecurityScanResult;
+use crate::usage::UsageRecord;
+use crate::worktree::diff::WorktreeDiff;
 use serde::{Deserialize, Serialize};
 
 /// All possible actions that can mutate application state.
@@ -58,6 +65,48 @@ pub enum Action {
     /// Set branches loading state
     SetBranchesLoading { is_loading: bool },
 
+    /// Recompute health (dirty files, ahead/behind, staleness) for all worktrees
+    RefreshWorktreeHealth,
+
+    /// Set worktree health (internal, matched to worktrees by path)
+    SetWorktreeHealth { health: Vec<WorktreeHealthData> },
+
+    /// Load the active worktree's diff against `base` (defaults to the main worktree's branch)
+    LoadWorktreeDiff { base: Option<String> },
+
+    /// Set worktree diff (internal, after git diff completes)
+    SetWorktreeDiff { diff: Option<WorktreeDiff> },
+
+    /// Audit the active worktree's dependencies for known vulnerabilities
+    RunDependencyAudit,
+
+    /// Set a worktree's audit report (internal, after the auditors finish)
+    SetDependencyAudit {
+        worktree_path: String,
+        report: crate::audit::AuditReport,
+    },
+
+    /// Check the active worktree with its compiler (cargo check / tsc)
+    RunBuildCheck,
+
+    /// Set a worktree's compiler diagnostics (internal, after the check)
+    SetBuildDiagnostics {
+        worktree_path: String,
+        diagnostics: crate::build_diagnostics::BuildDiagnostics,
+    },
+
+    /// Set a worktree's language server status (internal; None = stopped)
+    SetLspServerStatus {
+        worktree_path: String,
+        server: crate::lsp::LanguageServer,
+        status: Option<crate::app_state::LspServerStatus>,
+        error: Option<String>,
+    },
+
+    /// Stop the active worktree's language servers (they restart on the
+    /// next request)
+    StopLspServers,
+
     // ========================================================================
     // MCP Actions
     // ========================================================================
@@ -79,8 +128,8 @@ pub enum Action {
     /// Set MCP error (internal)
     SetMcpError { error: String },
 
-    /// Add MCP log entry (internal, from MCP server)
-    AddMcpLogEntry { entry: McpLogEntryData },
+    /// Add an entry to a worktree's MCP log (internal, from its MCP server)
+    AddMcpLogEntry { worktree_id: String, entry: McpLogEntryData },
 
     /// Clear MCP logs
     ClearMcpLogs,
@@ -88,11 +137,72 @@ pub enum Action {
     /// Update available MCP tools (internal, after fetch)
     UpdateMcpTools { tools: Vec<McpToolData> },
 
+    /// Update per-tool call metrics (internal, after each tool call)
+    UpdateMcpMetrics { metrics: Vec<McpToolMetricsData> },
+
+    /// Set which tools the active worktree's MCP server exposes
+    /// (saved to .rstn/mcp-policy.toml)
+    SetMcpPolicy { policy: crate::mcp_policy::McpPolicy },
+
+    /// Connect to the external MCP servers in the active worktree's .mcp.json
+    ConnectExternalMcpServers,
+
+    /// Disconnect the active worktree's external MCP servers
+    DisconnectExternalMcpServers,
+
+    /// Set external MCP servers and their tools (internal, after connecting)
+    SetExternalMcpServers { servers: Vec<crate::app_state::ExternalMcpServer> },
+
+    /// Invoke a tool on an external MCP server from the playground
+    CallExternalMcpTool {
+        server: String,
+        tool: String,
+        arguments: serde_json::Value,
+    },
+
+    /// Record a playground call that has started (internal)
+    StartMcpPlaygroundCall { entry: crate::app_state::McpPlaygroundEntry },
+
+    /// Record the outcome of a playground call (internal)
+    FinishMcpPlaygroundCall {
+        id: String,
+        duration_ms: u64,
+        result: Option<serde_json::Value>,
+        error: Option<String>,
+    },
+
+    /// Clear the playground history
+    ClearMcpPlayground,
+
     // ========================================================================
     // Chat Actions (worktree scope)
     // ========================================================================
     /// Send a chat message to Claude
-    SendChatMessage { text: String },
+    ///
+    /// With `continue_conversation`, the worktree's previous Claude CLI
+    /// session is resumed (`--resume`) instead of starting a fresh one.
+    SendChatMessage {
+        text: String,
+        #[serde(default)]
+        continue_conversation: bool,
+        /// Backend for this message (None = the global setting)
+        #[serde(default)]
+        provider: Option<crate::app_state::LlmProviderKind>,
+        /// Files to send with the message (text is inlined, images go to the CLI)
+        #[serde(default)]
+        attachments: Vec<std::path::PathBuf>,
+    },
+
+    /// Render a prompt library template and send it as a chat message
+    SendTemplatedPrompt {
+        template_id: String,
+        #[serde(default)]
+        variables: std::collections::HashMap<String, String>,
+        #[serde(default)]
+        continue_conversation: bool,
+        #[serde(default)]
+        provider: Option<crate::app_state::LlmProviderKind>,
+    },
 
     /// Add a chat message (user or assistant)
     AddChatMessage { message: ChatMessageData },
@@ -100,6 +210,44 @@ pub enum Action {
     /// Append content to the last assistant message (streaming)
     AppendChatContent { content: String },
 
+    /// Record a tool call on the last assistant message
+    AddChatToolCall { tool_call: crate::app_state::ChatToolCall },
+
+    /// Store the result of a tool call
+    CompleteChatToolCall {
+        id: String,
+        result_excerpt: String,
+        is_error: bool,
+        duration_ms: u64,
+    },
+
+    /// Ask the user to approve a tool call (the CLI waits for the answer)
+    RequestToolApproval { approval: crate::app_state::PendingToolApproval },
+
+    /// Let the agent run the pending tool call
+    ApproveToolUse { request_id: String },
+
+    /// Refuse the pending tool call (the agent is told it was denied)
+    DenyToolUse { request_id: String },
+
+    /// Stage the file edits (diffs, whole-file blocks) found in a message for review
+    ProposeEdits { message_id: String },
+
+    /// Accept a proposed file edit
+    AcceptProposedEdit { path: String },
+
+    /// Reject a proposed file edit
+    RejectProposedEdit { path: String },
+
+    /// Write the accepted edits to the worktree (all or nothing, with backups)
+    ApplyProposedEdits,
+
+    /// Mark edits as written (internal, after ApplyProposedEdits)
+    SetProposedEditsApplied { paths: Vec<String>, backup_dir: String },
+
+    /// Drop the staged edits
+    DismissProposedEdits,
+
     /// Set chat typing/streaming status
     SetChatTyping { is_typing: bool },
 
@@ -112,6 +260,13 @@ pub enum Action {
     /// Clear all chat messages
     ClearChat,
 
+    /// Set the Claude CLI session ID (internal, from the system init event)
+    SetChatSessionId { session_id: String },
+
+    /// Cancel an in-flight response (kills the Claude CLI process and marks
+    /// the streaming message as cancelled)
+    CancelChatMessage { message_id: String },
+
     // ========================================================================
     // Constitution Workflow Actions (CESDD Phase 1)
     // ========================================================================
@@ -125,7 +280,20 @@ pub enum Action {
     // Change Management Actions (CESDD Phase 2)
     // ========================================================================
     /// Create a new change from user intent
-    CreateChange { intent: String },
+    CreateChange {
+        intent: String,
+        /// Create a `change/<name>` branch in a new worktree for the change
+        #[serde(default)]
+        create_branch: bool,
+    },
+
+    /// Create a change from a GitHub issue of the worktree's repository,
+    /// then label the issue and comment on it
+    CreateChangeFromIssue {
+        issue_number: u32,
+        #[serde(default)]
+        create_branch: bool,
+    },
 
     /// Generate proposal.md using Claude (starts streaming)
     GenerateProposal { change_id: String },
@@ -136,6 +304,12 @@ pub enum Action {
     /// Mark proposal generation as complete
     CompleteProposal { change_id: String },
 
+    /// Mark proposal generation as failed (CLI error, timeout, write failure)
+    FailProposal { change_id: String, error: String },
+
+    /// Cancel in-flight proposal generation (kills the Claude CLI process)
+    CancelProposal { change_id: String },
+
     /// Generate plan.md using Claude (starts streaming)
     GeneratePlan { change_id: String },
 
@@ -154,21 +328,125 @@ pub enum Action {
     /// Append content to implementation output (streaming from Claude)
     AppendImplementationOutput { change_id: String, content: String },
 
+    /// Mark a plan step as done (index into the change's implementation tasks)
+    CompleteImplementationTask { change_id: String, task_index: usize },
+
+    /// Implementation finished, running the project's test command
+    StartImplementationTests { change_id: String },
+
     /// Mark implementation as complete (success)
     CompleteImplementation { change_id: String },
 
     /// Mark implementation as failed
     FailImplementation { change_id: String, error: String },
 
+    /// Cancel a running implementation (kills the Claude CLI process)
+    CancelImplementation { change_id: String },
+
+    /// Pause an implementation on a tool call that breaks its guardrails (internal)
+    RequestImplementationApproval {
+        change_id: String,
+        approval: crate::agent_runs::AgentRunApproval,
+    },
+
+    /// Allow or deny the tool call a paused implementation is waiting on
+    ResolveImplementationApproval {
+        change_id: String,
+        request_id: String,
+        approve: bool,
+    },
+
+    /// Record the worktree snapshot taken before implementing a change (internal)
+    SetChangeSnapshot {
+        worktree_path: String,
+        change_id: String,
+        snapshot: crate::snapshot::ChangeSnapshot,
+    },
+
+    /// Restore the worktree to the snapshot taken before the change's implementation
+    RollbackImplementation { change_id: String },
+
+    /// Worktree restored to the snapshot; the plan can be executed again (internal)
+    CompleteRollback {
+        change_id: String,
+        snapshot: crate::snapshot::ChangeSnapshot,
+    },
+
+    /// Record the results of a change's test run (internal)
+    SetChangeTestResults {
+        worktree_path: String,
+        change_id: String,
+        results: crate::test_runner::TestResults,
+    },
+
+    /// Record the post-implementation hook results of a change (internal)
+    SetChangeHookResults {
+        worktree_path: String,
+        change_id: String,
+        results: Vec<crate::hooks::HookResult>,
+    },
+
+    /// Run the project's tests again for an implemented change
+    RunChangeTests { change_id: String },
+
+    /// Allow approving the change's reviews although its last test run failed
+    OverrideTestGate { change_id: String },
+
+    /// Queue a Claude implementation run of a change in any worktree; runs in
+    /// different worktrees proceed in parallel up to the concurrency cap
+    QueueAgentRun { worktree_path: String, change_id: String },
+
+    /// Record a queued agent run (internal)
+    AddAgentRun { run: crate::agent_runs::AgentRun },
+
+    /// Mark a queued agent run as running (internal)
+    StartAgentRun { run_id: String },
+
+    /// Append streamed output to the run's change (internal)
+    AppendAgentRunOutput { run_id: String, content: String },
+
+    /// Record how an agent run ended (internal)
+    FinishAgentRun {
+        run_id: String,
+        status: crate::agent_runs::AgentRunStatus,
+        error: Option<String>,
+    },
+
+    /// Pause an agent run on a tool call that breaks its guardrails (internal)
+    RequestAgentRunApproval {
+        run_id: String,
+        approval: crate::agent_runs::AgentRunApproval,
+    },
+
+    /// Allow or deny the tool call a paused agent run is waiting on
+    ResolveAgentRunApproval {
+        run_id: String,
+        request_id: String,
+        approve: bool,
+    },
+
+    /// Drop a queued agent run, or stop a running one
+    CancelAgentRun { run_id: String },
+
+    /// Set how many agent runs may run at once
+    SetAgentMaxConcurrent { max_concurrent: u32 },
+
+    /// Forget finished agent runs
+    ClearFinishedAgentRuns,
+
     /// Cancel a change (sets status to Cancelled)
     CancelChange { change_id: String },
 
     /// Select a change to view details
     SelectChange { change_id: Option<String> },
 
-    /// Refresh changes list from .rstn/changes/
+    /// Refresh changes list from .rstn/changes/ (reconciling statuses with
+    /// each change's status.json)
     RefreshChanges,
 
+    /// Settle a status conflict with a teammate by picking the status to keep
+    ResolveChangeStatusConflict { change_id: String, status: ChangeStatusData },
+
     /// Set changes list (internal, after refresh)
     SetChanges { changes: Vec<ChangeData> },
 
@@ -259,6 +537,15 @@ pub enum Action {
     /// Set change status to Archived (internal, after archive completes)
     SetChangeArchived { change_id: String },
 
+    /// Push the change's branch and open a GitHub/GitLab pull request for it
+    OpenChangePullRequest { change_id: String },
+
+    /// Set the PR URL of a change (internal, after the PR is created)
+    SetChangePullRequest { change_id: String, url: String },
+
+    /// Set the GitHub/GitLab tokens used for pull requests
+    SetGitHostingSettings { settings: crate::app_state::GitHostingSettings },
+
     /// Submit an answer to the current question and advance
     AnswerConstitutionQuestion { answer: String },
 
@@ -274,6 +561,9 @@ pub enum Action {
     /// Set constitution generation error (internal, called when Claude fails)
     SetConstitutionError { error: String },
 
+    /// Cancel in-flight constitution generation (kills the Claude CLI process)
+    CancelConstitutionGeneration,
+
     /// Check if constitution file exists (async trigger)
     CheckConstitutionExists,
 
@@ -289,6 +579,15 @@ pub enum Action {
     /// Set constitution content (internal, after read)
     SetConstitutionContent { content: Option<String> },
 
+    /// Lint the constitution for missing sections, weak phrasing,
+    /// contradictions and stale references (async trigger)
+    LintConstitution,
+
+    /// Set the constitution lint report (internal, after lint)
+    SetConstitutionLintReport {
+        report: crate::constitution::lint::ConstitutionLintReport,
+    },
+
     /// Set CLAUDE.md existence status (internal, after check)
     SetClaudeMdExists { exists: bool },
 
@@ -342,11 +641,16 @@ pub enum Action {
         policy: ReviewPolicyData,
     },
 
-    /// Add a comment to a review session
+    /// Add a comment to a review session, optionally anchored to file lines
+    /// or replying to another comment (replies share the parent's anchor)
     AddReviewComment {
         session_id: String,
         target: CommentTargetData,
         content: String,
+        #[serde(default)]
+        anchor: Option<CommentAnchorData>,
+        #[serde(default)]
+        parent_id: Option<String>,
     },
 
     /// Mark a comment as resolved
@@ -376,6 +680,15 @@ pub enum Action {
         status: ReviewStatusData,
     },
 
+    /// Load a change's review comments from .rstn/changes/<name>/review.json
+    LoadReviewComments { change_id: String },
+
+    /// Set a change's review comments (internal, after LoadReviewComments)
+    SetReviewComments {
+        change_id: String,
+        comments: Vec<crate::app_state::ReviewComment>,
+    },
+
     /// Set ReviewGate loading state (internal)
     SetReviewGateLoading { is_loading: bool },
 
@@ -388,6 +701,18 @@ pub enum Action {
     /// Clear a review session (internal, after complete)
     ClearReviewSession { session_id: String },
 
+    /// Evaluate a review session's content against the constitution (async trigger)
+    RunComplianceCheck { session_id: String },
+
+    /// Set the result of a compliance check (internal, after evaluation)
+    SetComplianceCheck {
+        session_id: String,
+        check: crate::app_state::ComplianceCheck,
+    },
+
+    /// Allow approval despite blocking constitution violations
+    OverrideComplianceCheck { session_id: String, reason: String },
+
     // ========================================================================
     // Docker Actions
     // ========================================================================
@@ -448,12 +773,157 @@ pub enum Action {
         service_id: String,
     },
 
+    /// Stop the host process listening on a port (after user confirmation),
+    /// then start the service that needed the port, if any
+    KillProcessOnPort {
+        port: u16,
+        pid: u32,
+        service_id: Option<String>,
+    },
+
+    /// Start all services of an imported docker-compose project
+    DockerComposeUp { project_name: String },
+
+    /// Stop all services of an imported docker-compose project
+    DockerComposeDown { project_name: String },
+
+    /// Remember an imported docker-compose file for the active project
+    AddComposeFile { path: String },
+
+    /// Load the active project's request collections from .rstn/requests/
+    LoadHttpRequests,
+
+    /// Set a project's request collections (internal)
+    SetHttpRequests {
+        project_path: String,
+        collections: Vec<crate::http_client::RequestCollection>,
+    },
+
+    /// Send a request of the active project, substituting `{{NAME}}` with
+    /// variables from the active worktree's env file (None = ".env")
+    RunHttpRequest {
+        collection: String,
+        request: String,
+        env_file: Option<String>,
+    },
+
+    /// Record the result of a request (internal)
+    SetHttpResponse {
+        project_path: String,
+        collection: String,
+        request: String,
+        response: Option<crate::http_client::HttpResponseData>,
+        error: Option<String>,
+    },
+
+    /// Load the active project's service groups from .rstn/services.toml
+    LoadServiceGroups,
+
+    /// Set a project's service groups (internal)
+    SetServiceGroups {
+        project_path: String,
+        groups: Vec<crate::service_groups::ServiceGroup>,
+        error: Option<String>,
+    },
+
+    /// Move a service into a named group of the active project (None = no group)
+    AssignServiceToGroup {
+        service_id: String,
+        group: Option<String>,
+    },
+
+    /// Delete a service group of the active project (its services keep running)
+    DeleteServiceGroup { name: String },
+
+    /// Start the services of a group in dependency order
+    StartServiceGroup { name: String },
+
+    /// Stop the services of a group in reverse dependency order
+    StopServiceGroup { name: String },
+
+    /// Open an interactive exec session in an rstn-managed container
+    /// (default command: `sh`); output streams like terminal output
+    OpenDockerExec {
+        service_id: String,
+        cmd: Option<Vec<String>>,
+        cols: u16,
+        rows: u16,
+    },
+
+    /// Set a container's exec session (internal)
+    SetDockerExecSession {
+        service_id: String,
+        session_id: Option<String>,
+    },
+
+    /// End a container's exec session
+    CloseDockerExec { service_id: String },
+
+    /// Start the local reverse proxy (None = configured or default port);
+    /// it is started again on launch until StopProxy
+    StartProxy { port: Option<u16> },
+
+    /// Stop the local reverse proxy
+    StopProxy,
+
+    /// Replace the proxy's routes (hostname / path prefix -> service)
+    SetProxyRoutes { routes: Vec<crate::proxy::ProxyRoute> },
+
+    /// Set the proxy's status (internal)
+    SetProxyStatus { status: crate::app_state::ProxyStatus },
+
     /// Set loading state for Docker operations
     SetDockerLoading { is_loading: bool },
 
     /// Set loading state for logs
     SetDockerLogsLoading { is_loading: bool },
 
+    /// Record image pull progress (internal, streamed from `docker_pull_image`)
+    SetImagePullProgress {
+        image: String,
+        progress: ImagePullProgressData,
+    },
+
+    /// Mark an image pull as finished (internal, error = None on success)
+    FinishImagePull {
+        image: String,
+        error: Option<String>,
+    },
+
+    /// Set a started service's health (internal, from the health check poller)
+    SetServiceHealth {
+        service_id: String,
+        health: ServiceHealth,
+    },
+
+    /// Append a resource sample for a service (internal, from `docker_stats_stream`)
+    SetDockerStats {
+        service_id: String,
+        stats: ContainerStats,
+    },
+
+    /// List databases in a running database service
+    FetchDockerDatabases { service_id: String },
+
+    /// Set the databases of a service (internal, after FetchDockerDatabases)
+    SetDockerDatabases {
+        service_id: String,
+        databases: Vec<DatabaseInfo>,
+    },
+
+    /// List tables in a database of a running database service
+    FetchDockerTables { service_id: String, db_name: String },
+
+    /// Set the tables of a database (internal, after FetchDockerTables)
+    SetDockerTables {
+        service_id: String,
+        db_name: String,
+        tables: Vec<TableInfo>,
+    },
+
+    /// Set loading state for database/table listings
+    SetDockerDatabasesLoading { is_loading: bool },
+
     // ========================================================================
     // Tasks Actions
     // ========================================================================
@@ -469,6 +939,76 @@ pub enum Action {
     /// Run a just command
     RunJustCommand { name: String, cwd: String },
 
+    /// Run a just command with recipe arguments (passed as separate argv entries)
+    RunJustCommandWithArgs {
+        name: String,
+        args: Vec<String>,
+        cwd: String,
+    },
+
+    /// Load tasks from every detected provider (or only `provider`)
+    LoadTasks {
+        #[serde(default)]
+        provider: Option<crate::tasks::TaskProviderKind>,
+    },
+
+    /// Set loaded tasks (internal). With a provider, only its tasks are replaced.
+    SetTasks {
+        provider: Option<crate::tasks::TaskProviderKind>,
+        tasks: Vec<crate::tasks::TaskDescriptor>,
+    },
+
+    /// Run a task from any provider (status is tracked by task id)
+    RunTask {
+        provider: crate::tasks::TaskProviderKind,
+        name: String,
+        #[serde(default)]
+        args: Vec<String>,
+        cwd: String,
+    },
+
+    /// Record a queued task run (internal)
+    QueueTaskRun { run: crate::task_queue::TaskRun },
+
+    /// Mark a queued run as running (internal)
+    StartTaskRun { task_id: String },
+
+    /// Append an output line to a run (internal)
+    AppendTaskRunOutput { task_id: String, line: String },
+
+    /// Record how a run ended (internal)
+    FinishTaskRun {
+        task_id: String,
+        status: crate::task_queue::TaskRunStatus,
+        exit_code: Option<i32>,
+        duration_ms: Option<u64>,
+    },
+
+    /// Cancel a queued or running task
+    CancelTask { task_id: String },
+
+    /// Load the active project's schedules from .rstn/schedule.toml
+    LoadSchedule,
+
+    /// Set a project's schedules (internal). Run times of unchanged entries are kept.
+    SetSchedule {
+        project_path: String,
+        entries: Vec<crate::schedule::ScheduleEntry>,
+        error: Option<String>,
+    },
+
+    /// Pause or resume the active project's schedules
+    SetSchedulePaused { paused: bool },
+
+    /// Record that a schedule ran (internal)
+    MarkScheduleRun {
+        project_path: String,
+        id: String,
+        ran_at: String,
+        next_run_at: String,
+        task_id: Option<String>,
+    },
+
     /// Set task status
     SetTaskStatus { name: String, status: TaskStatusData },
 
@@ -510,6 +1050,52 @@ pub enum Action {
     /// Set source worktree for env copying
     SetEnvSourceWorktree { worktree_path: Option<String> },
 
+    /// Diff env files key by key between two worktrees
+    DiffEnvFiles {
+        from_worktree_path: String,
+        to_worktree_path: String,
+        /// Optional patterns to diff (None = use tracked_patterns)
+        patterns: Option<Vec<String>>,
+    },
+
+    /// Set or clear the env diff result (internal)
+    SetEnvDiff { diff: Option<EnvDiff> },
+
+    /// Copy only the chosen keys of an env file into the target worktree
+    /// (keys missing from the source are removed from the target)
+    SyncEnvKeys {
+        from_worktree_path: String,
+        to_worktree_path: String,
+        /// File relative to the worktrees (e.g. ".env")
+        file: String,
+        keys: Vec<String>,
+    },
+
+    /// Scan the active worktree's tracked env files for secrets
+    ScanEnvSecrets,
+
+    /// Set the secrets found by ScanEnvSecrets (internal, values masked)
+    SetEnvSecrets { secrets: Vec<crate::env_secrets::EnvSecret> },
+
+    /// Write a database connection string into an env file of the active
+    /// worktree (backs up the file first). With `dry_run`, only sets
+    /// `env_config.injection_preview`.
+    InjectConnectionString {
+        service_id: String,
+        db_name: String,
+        /// Env file relative to the worktree (e.g. ".env")
+        env_file: String,
+        var_name: String,
+        #[serde(default)]
+        dry_run: bool,
+    },
+
+    /// Set or clear the connection string injection preview (internal)
+    SetEnvInjectionPreview { preview: Option<EnvInjectionPreview> },
+
+    /// Set how Docker port conflicts are resolved for the active project
+    SetAutoResolvePorts { strategy: PortConflictStrategy },
+
     // ========================================================================
     // Agent Rules Actions (Project scope)
     // ========================================================================
@@ -525,6 +1111,9 @@ pub enum Action {
     /// Create a new agent profile
     CreateAgentProfile { name: String, prompt: String },
 
+    /// Add a profile read from a shared profile file (see `agent_rules_import`)
+    ImportAgentProfile { profile: AgentProfileFile },
+
     /// Update an existing agent profile
     UpdateAgentProfile {
         id: String,
@@ -595,6 +1184,63 @@ pub enum Action {
     /// Set default project path
     SetProjectPath { path: Option<String> },
 
+    /// Set the global Claude model (None = CLI default)
+    SetModel { model: Option<String> },
+
+    /// Set the active project's Claude model (None = use global setting)
+    SetProjectModel { model: Option<String> },
+
+    /// Require every spec checklist item to be checked before review approval
+    SetRequireChecklists { required: bool },
+
+    /// Run the active project's tests with coverage after implementations
+    SetCollectCoverage { enabled: bool },
+
+    /// Re-run the build check when source files of the active worktree change
+    SetCheckBuildOnSave { enabled: bool },
+
+    /// Set how many tasks may run at once (None = default)
+    SetTaskMaxParallel { max_parallel: Option<u32> },
+
+    /// Set the time budget of implementation runs in seconds (None = default)
+    SetImplementationTimeout { secs: Option<u32> },
+
+    /// Turn OS notifications for an event on or off
+    SetDesktopNotification {
+        event: crate::app_state::DesktopNotificationEvent,
+        enabled: bool,
+    },
+
+    /// Set the default backend for chat and one-shot prompts
+    SetLlmProvider { provider: crate::app_state::LlmProviderKind },
+
+    /// Set the OpenAI-compatible endpoint, key and model
+    SetOpenAiSettings { settings: crate::app_state::OpenAiSettings },
+
+    /// Set the Ollama server, chat model and internal task model
+    SetOllamaSettings { settings: crate::app_state::OllamaSettings },
+
+    /// Bind a command to a shortcut (empty to unbind). Conflicts set the error.
+    SetKeybinding { command: String, shortcut: String },
+
+    /// Restore the default shortcut of a command, or of all commands
+    ResetKeybindings {
+        #[serde(default)]
+        command: Option<String>,
+    },
+
+    /// Create or replace a settings profile (saved to ~/.rstn/profiles/)
+    SaveSettingsProfile {
+        profile: crate::settings_profiles::SettingsProfile,
+    },
+
+    /// Delete a settings profile
+    DeleteSettingsProfile { name: String },
+
+    /// Apply a settings profile to the global settings; its secret
+    /// references are resolved afterwards
+    SwitchSettingsProfile { name: String },
+
     // ========================================================================
     // Error Handling
     // ========================================================================
@@ -617,6 +1263,22 @@ pub enum Action {
     /// Clear all dev logs
     ClearDevLogs,
 
+    /// Load recent backend log entries (async)
+    LoadAppLogs {
+        /// Entries to load (default 200)
+        #[serde(default)]
+        limit: Option<u32>,
+        /// Minimum level ("error", "warn", "info", "debug")
+        #[serde(default)]
+        level: Option<String>,
+        /// Only modules containing this (e.g. "docker")
+        #[serde(default)]
+        module: Option<String>,
+    },
+
+    /// Set the loaded backend log entries (internal)
+    SetAppLogs { logs: Vec<crate::logging::LogEntry> },
+
     // ========================================================================
     // UI Layout Actions (Right Icon Bar & Log Panels)
     // ========================================================================
@@ -665,6 +1327,16 @@ pub enum Action {
     /// Select a file or directory (show details/preview)
     SelectFile { path: Option<String> },
 
+    /// Load the commit history of a file (async)
+    LoadFileHistory { path: String },
+
+    /// Set a file's commit history (internal). Ignored if another file was loaded since.
+    SetFileHistory {
+        path: String,
+        commits: Vec<crate::git::FileCommit>,
+        error: Option<String>,
+    },
+
     /// Set sorting preferences
     SetExplorerSort {
         field: SortFieldData,
@@ -674,6 +1346,25 @@ pub enum Action {
     /// Set filter query
     SetExplorerFilter { query: String },
 
+    /// Full-text search across the active worktree
+    SearchWorkspace {
+        query: String,
+        #[serde(default)]
+        glob: Option<String>,
+        #[serde(default)]
+        case_sensitive: bool,
+    },
+
+    /// Set search results (internal). Ignored if the query has changed since.
+    SetSearchResults {
+        query: String,
+        results: crate::explorer::search::SearchResults,
+        error: Option<String>,
+    },
+
+    /// Clear search query and results
+    ClearSearch,
+
     /// Expand a directory in the tree view (loads contents if not cached)
     ExpandDirectory { path: String },
 
@@ -712,6 +1403,24 @@ pub enum Action {
         comment_id: String,
     },
 
+    /// Stage files in the active worktree
+    GitStage { paths: Vec<String> },
+
+    /// Commit staged changes in the active worktree (runs security scan first)
+    GitCommit { message: String },
+
+    /// Push the active worktree's branch
+    GitPush,
+
+    /// Pull the active worktree's branch (fast-forward only)
+    GitPull,
+
+    /// Set git operation in-progress flag (internal)
+    SetGitBusy { is_busy: bool },
+
+    /// Set security scan result (internal, when a commit is blocked)
+    SetSecurityScanResult { result: Option<SecurityScanResult> },
+
     // ========================================================================
     // Tab Management Actions (VSCode-style preview tabs)
     // ========================================================================
@@ -748,18 +1457,226 @@ pub enum Action {
     /// Read a binary file for viewing (images, PDFs, videos, etc.)
     ReadBinaryFile { path: String },
 
-    /// Set binary file content
+    /// Set binary file content.
+    /// `content` holds raw bytes only for media the renderer decodes itself
+    /// (PDF, audio, video, office); everything else comes as a `preview`.
     SetBinaryFileContent {
         path: String,
         content: Option<Vec<u8>>,
+        #[serde(default)]
+        preview: Option<crate::file_preview::BinaryPreview>,
         error: Option<String>,
     },
 
+    /// Save edited text content (atomic write).
+    /// Rejected if the file no longer matches `expected_hash` (the
+    /// `content_hash` it was read with).
+    WriteFile {
+        path: String,
+        content: String,
+        expected_hash: Option<String>,
+    },
+
+    /// Mark the open file as having unsaved edits (or not)
+    SetFileDirty { is_dirty: bool },
+
+    /// A WriteFile failed (edits are kept)
+    SetFileSaveError { path: String, error: String },
+
     // ========================================================================
     // A2UI Actions (Experimental)
     // ========================================================================
     /// Set the A2UI payload for dynamic rendering
     SetA2UIPayload { payload: Option<serde_json::Value> },
+
+    // ========================================================================
+    // Usage Actions
+    // ========================================================================
+    /// Record token usage / cost of a Claude CLI run (internal, from the result event)
+    AddUsageRecord { record: UsageRecord },
+
+    // ========================================================================
+    // Session History Actions
+    // ========================================================================
+    /// Load the active project's Claude sessions from SQLite
+    LoadSessions,
+
+    /// Set the loaded sessions (internal, newest first)
+    SetSessions {
+        project_id: String,
+        sessions: Vec<crate::sessions::SessionRecord>,
+    },
+
+    /// Record a finished Claude session (internal, from the session recorder)
+    AddSession { session: crate::sessions::SessionRecord },
+
+    /// Delete a session from the history
+    DeleteSession { id: String },
+
+    // ========================================================================
+    // Spec-kit Workflow Actions
+    // ========================================================================
+    /// Create a feature directory (specs/<NNN>-<name>/) and run the specify phase.
+    /// With `use_claude` off (or no Claude CLI installed) spec.md is filled
+    /// from the template instead.
+    StartSpecWorkflow {
+        description: String,
+        #[serde(default = "default_use_claude")]
+        use_claude: bool,
+    },
+
+    /// Set the worktree's spec-kit workflow (internal, from StartSpecWorkflow)
+    SetSpecWorkflow { workflow: crate::app_state::SpecWorkflow },
+
+    /// Run a phase of the active spec-kit workflow. `input` is the user's
+    /// answers for clarify or technical constraints for plan.
+    RunSpecPhase {
+        phase: crate::spec_kit::SpecPhase,
+        input: Option<String>,
+    },
+
+    /// Append streamed output of a running phase (internal)
+    AppendSpecPhaseOutput {
+        phase: crate::spec_kit::SpecPhase,
+        content: String,
+    },
+
+    /// Mark a phase complete with its final output (internal, after the artifact is written)
+    CompleteSpecPhase {
+        phase: crate::spec_kit::SpecPhase,
+        output: String,
+    },
+
+    /// Mark a phase failed (internal)
+    FailSpecPhase {
+        phase: crate::spec_kit::SpecPhase,
+        error: String,
+    },
+
+    /// Stop the running phase
+    CancelSpecPhase,
+
+    /// Close the spec-kit workflow (artifacts stay on disk)
+    ClearSpecWorkflow,
+
+    /// Analyze a spec (path relative to the worktree) and start asking its
+    /// clarification questions
+    StartClarifySession { spec_path: String },
+
+    /// Set the analyzed clarify session (internal, from StartClarifySession)
+    SetClarifySession { session: crate::app_state::ClarifySession },
+
+    /// Answer the current clarify question
+    AnswerClarifyQuestion { id: String, value: String },
+
+    /// Skip the current clarify question (its marker stays in the spec)
+    SkipClarifyQuestion,
+
+    /// Answers written into the spec (internal); `coverage` is re-analyzed
+    CompleteClarifySession {
+        coverage: Vec<crate::clarify::CategoryCoverage>,
+    },
+
+    /// Reading or updating the spec failed (internal)
+    FailClarifySession { error: String },
+
+    /// Close the clarify session
+    ClearClarifySession,
+
+    /// Parse a tasks.md (path relative to the worktree) and track its progress
+    LoadSpecTasks { tasks_path: String },
+
+    /// Set the parsed tasks (internal, after loading or updating tasks.md)
+    SetSpecTasks { tasks: crate::app_state::SpecTasksState },
+
+    /// Check or uncheck a task; tasks.md is rewritten atomically
+    ToggleSpecTask { id: String, done: bool },
+
+    /// Progress note from Claude (via the rstn_report_progress MCP tool)
+    ReportSpecProgress {
+        message: String,
+        task_id: Option<String>,
+    },
+
+    /// Generate a checklist-<kind>.md for the spec workflow's feature via Claude
+    GenerateChecklist { kind: crate::checklist::ChecklistKind },
+
+    /// Set a generated or updated checklist (internal)
+    SetChecklist { checklist: crate::app_state::SpecChecklist },
+
+    /// Generating a checklist failed (internal)
+    FailChecklist {
+        kind: crate::checklist::ChecklistKind,
+        error: String,
+    },
+
+    /// Read the checklists already present in the feature directory
+    LoadChecklists,
+
+    /// Set the checklists read from the feature directory (internal)
+    SetChecklists { checklists: Vec<crate::app_state::SpecChecklist> },
+
+    /// Check or uncheck a checklist item; the file is rewritten atomically
+    ToggleChecklistItem {
+        kind: crate::checklist::ChecklistKind,
+        id: String,
+        checked: bool,
+    },
+
+    /// Check spec.md, plan.md and tasks.md against each other, optionally
+    /// verified by Claude; the report is written to analysis.json
+    AnalyzeSpec { verify: bool },
+
+    /// Set the finished analysis report (internal)
+    CompleteSpecAnalysis { report: crate::spec_analysis::AnalysisReport },
+
+    /// Analysis failed (internal)
+    FailSpecAnalysis { error: String },
+
+    /// List the worktree's spec-kit features for the pipeline board
+    LoadFeaturesCatalog,
+
+    /// Set the listed features (internal)
+    SetFeaturesCatalog { features: Vec<crate::feature_catalog::FeatureInfo> },
+
+    /// Start working on a feature in one step: create or attach the worktree of
+    /// its `NNN-name` branch, copy env files, switch to it, start its MCP server
+    /// and check for a constitution
+    StartFeatureWork { feature_number: u32 },
+
+    // ========================================================================
+    // Activity Feed Actions
+    // ========================================================================
+    /// Load the active project's activity at or after `since` (ISO 8601, None = all)
+    LoadActivityFeed { since: Option<String> },
+
+    /// Set the loaded activity (internal, newest first)
+    SetActivityFeed {
+        project_id: String,
+        since: Option<String>,
+        events: Vec<crate::activity::ActivityEvent>,
+    },
+
+    /// Add a just-recorded event to the feed (internal)
+    AddActivity { event: crate::activity::ActivityEvent },
+
+    // ========================================================================
+    // System Monitor Actions
+    // ========================================================================
+    /// Set the latest machine resource sample (internal, from the system monitor)
+    SetSystemStats { stats: crate::system::SystemStats },
+
+    // ========================================================================
+    // Undo Actions
+    // ========================================================================
+    /// Undo the most recent user edit
+    Undo,
+
+    /// Redo the most recently undone edit
+    Redo,
+
+    /// Record an edit made by an async handler (internal, e.g. file comments)
+    PushUndoEntry { entry: Box<crate::undo::UndoEntry> },
 }
 
 /// File kind for actions
@@ -833,6 +1750,27 @@ pub struct WorktreeData {
     pub is_main: bool,
 }
 
+/// Worktree health data (from `worktree::health::compute_health`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorktreeHealthData {
+    pub worktree_path: String,
+    pub dirty_files: u32,
+    pub upstream: Option<String>,
+    pub ahead: u32,
+    pub behind: u32,
+    pub last_commit: Option<LastCommitData>,
+    pub is_stale: bool,
+}
+
+/// Last commit summary for a worktree
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LastCommitData {
+    pub hash: String,
+    pub summary: String,
+    /// Unix timestamp (seconds)
+    pub timestamp: i64,
+}
+
 /// Branch data for UI (from `git branch`)
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct BranchData {
@@ -878,6 +1816,17 @@ pub struct McpToolData {
     pub input_schema: serde_json::Value,
 }
 
+/// MCP per-tool metrics for actions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct McpToolMetricsData {
+    pub tool_name: String,
+    pub call_count: u64,
+    pub error_count: u64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub last_called_at: Option<String>,
+}
+
 /// Chat role for actions
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -896,6 +1845,19 @@ pub struct ChatMessageData {
     pub timestamp: String,
     #[serde(default)]
     pub is_streaming: bool,
+    #[serde(default)]
+    pub attachments: Vec<crate::app_state::ChatAttachment>,
+}
+
+/// One image pull progress event (layer_id = None for overall status lines)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImagePullProgressData {
+    pub layer_id: Option<String>,
+    pub status: String,
+    /// Bytes downloaded/extracted so far
+    pub current: Option<u64>,
+    /// Total bytes for this layer step
+    pub total: Option<u64>,
 }
 
 /// Docker service data for actions (lightweight, serializable)
@@ -917,6 +1879,8 @@ pub struct JustCommandData {
     pub name: String,
     pub description: Option<String>,
     pub recipe: String,
+    #[serde(default)]
+    pub parameters: Vec<JustParameterInfo>,
 }
 
 /// Task status for actions
@@ -934,9 +1898,21 @@ pub enum TaskStatusData {
 pub struct PortConflictData {
     pub requested_port: u16,
     pub conflicting_container: ConflictingContainerData,
+    #[serde(default)]
+    pub conflicting_process: Option<ConflictingProcessData>,
     pub suggested_port: u16,
 }
 
+/// Conflicting host process data for actions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConflictingProcessData {
+    pub pid: u32,
+    pub name: String,
+    pub exe: Option<String>,
+    pub user: Option<String>,
+    pub command: Option<String>,
+}
+
 /// Conflicting container data for actions
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ConflictingContainerData {
@@ -1062,6 +2038,16 @@ pub struct ChangeData {
     /// Source files selected for context injection
     #[serde(default)]
     pub context_files: Vec<String>,
+    /// Plan steps tracked during implementation
+    #[serde(default)]
+    pub implementation_tasks: Vec<ImplementationTaskData>,
+}
+
+/// Implementation task data for actions (CESDD Phase 5)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImplementationTaskData {
+    pub title: String,
+    pub done: bool,
 }
 
 /// Context type for actions (CESDD Phase 3)
@@ -1156,6 +2142,14 @@ pub enum CommentTargetData {
     File { path: String },
 }
 
+/// File lines a review comment is anchored to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommentAnchorData {
+    pub file: String,
+    pub line_start: u32,
+    pub line_end: u32,
+}
+
 /// File change data for review content
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ReviewFileChangeData {
@@ -1188,6 +2182,10 @@ pub enum LogPanelTypeData {
     Metrics,
 }
 
+fn default_use_claude() -> bool {
+    true
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -1268,6 +2266,11 @@ mod tests {
 
         let action: Action = serde_json::from_str(frontend_json).unwrap();
         assert!(matches!(action, Action::StartDockerService { service_id } if service_id == "rstn-postgres"));
+
+        // Spec generation uses Claude unless turned off
+        let action: Action =
+            serde_json::from_str(r#"{"type":"StartSpecWorkflow","payload":{"description":"Export users"}}"#).unwrap();
+        assert!(matches!(action, Action::StartSpecWorkflow { use_claude: true, .. }));
     }
 
     #[test]
@@ -1275,11 +2278,17 @@ mod tests {
         // CreateChange
         let action = Action::CreateChange {
             intent: "Add user authentication".to_string(),
+            create_branch: true,
         };
         let json = serde_json::to_string(&action).unwrap();
         let loaded: Action = serde_json::from_str(&json).unwrap();
         assert_eq!(action, loaded);
 
+        // CreateChangeFromIssue (branch is opt-in)
+        let loaded: Action =
+            serde_json::from_str(r#"{"type":"CreateChangeFromIssue","payload":{"issue_number":42}}"#).unwrap();
+        assert_eq!(loaded, Action::CreateChangeFromIssue { issue_number: 42, create_branch: false });
+
         // GenerateProposal
         let action = Action::GenerateProposal {
             change_id: "change-123".to_string(),
@@ -1302,6 +2311,7 @@ mod tests {
                 proposal_review_session_id: None,
                 plan_review_session_id: None,
                 context_files: vec![],
+                implementation_tasks: vec![],
             }],
         };
         let json = serde_json::to_string(&action).unwrap();
@@ -0,0 +1,122 @@
+//! Workspace activity timeline.
+//!
+//! Significant events (a change created, a plan approved, a service started,
+//! a worktree added, the constitution updated) are recorded per project in
+//! the `activity_logs` SQLite table, so the UI can show what happened in a
+//! repo over the last days.
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::LogRow;
+
+/// Maximum number of events loaded into the activity feed
+pub const MAX_ACTIVITY_EVENTS: usize = 500;
+
+/// Kind of recorded event (stored as the log category)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityKind {
+    ChangeCreated,
+    PlanApproved,
+    ServiceStarted,
+    WorktreeAdded,
+    ConstitutionUpdated,
+}
+
+impl ActivityKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ActivityKind::ChangeCreated => "change_created",
+            ActivityKind::PlanApproved => "plan_approved",
+            ActivityKind::ServiceStarted => "service_started",
+            ActivityKind::WorktreeAdded => "worktree_added",
+            ActivityKind::ConstitutionUpdated => "constitution_updated",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "change_created" => Some(ActivityKind::ChangeCreated),
+            "plan_approved" => Some(ActivityKind::PlanApproved),
+            "service_started" => Some(ActivityKind::ServiceStarted),
+            "worktree_added" => Some(ActivityKind::WorktreeAdded),
+            "constitution_updated" => Some(ActivityKind::ConstitutionUpdated),
+            _ => None,
+        }
+    }
+}
+
+/// One recorded event
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ActivityEvent {
+    pub id: i64,
+    pub project_id: String,
+    pub kind: ActivityKind,
+    /// Human-readable one-liner (e.g. "Created change add-login")
+    pub summary: String,
+    /// Structured details (change ID, branch, service ID, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<serde_json::Value>,
+    /// When it happened (ISO 8601)
+    pub timestamp: String,
+}
+
+impl ActivityEvent {
+    /// Event from a log row; None for rows of other categories
+    pub fn from_row(project_id: &str, row: LogRow) -> Option<Self> {
+        Some(Self {
+            id: row.id,
+            project_id: project_id.to_string(),
+            kind: ActivityKind::parse(&row.category)?,
+            summary: row.summary,
+            detail: row.detail_json.and_then(|json| serde_json::from_str(&json).ok()),
+            timestamp: row.timestamp,
+        })
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(category: &str, detail_json: Option<&str>) -> LogRow {
+        LogRow {
+            id: 7,
+            category: category.to_string(),
+            level: "info".to_string(),
+            summary: "Started service rstn-postgres".to_string(),
+            detail_json: detail_json.map(str::to_string),
+            timestamp: "2026-01-01T00:00:00+00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_event_from_row() {
+        let event = ActivityEvent::from_row("p1", row("service_started", Some(r#"{"service_id":"rstn-postgres"}"#))).unwrap();
+        assert_eq!(event.kind, ActivityKind::ServiceStarted);
+        assert_eq!(event.project_id, "p1");
+        assert_eq!(event.detail, Some(serde_json::json!({ "service_id": "rstn-postgres" })));
+
+        // Malformed details are dropped, unknown categories skipped
+        assert_eq!(ActivityEvent::from_row("p1", row("worktree_added", Some("{"))).unwrap().detail, None);
+        assert!(ActivityEvent::from_row("p1", row("mcp_call", None)).is_none());
+    }
+
+    #[test]
+    fn test_kind_round_trip() {
+        for kind in [
+            ActivityKind::ChangeCreated,
+            ActivityKind::PlanApproved,
+            ActivityKind::ServiceStarted,
+            ActivityKind::WorktreeAdded,
+            ActivityKind::ConstitutionUpdated,
+        ] {
+            assert_eq!(ActivityKind::parse(kind.as_str()), Some(kind));
+            assert_eq!(serde_json::to_value(kind).unwrap(), kind.as_str());
+        }
+    }
+}
@@ -5,9 +5,22 @@
 //! When custom agent rules are enabled, this module creates a temporary text file
 //! that Claude Code CLI can use to override the default CLAUDE.md behavior
 //! via the `--system-prompt-file` flag.
+//!
+//! Profiles can be shared between teams as single-file TOML documents
+//! ([`AgentProfileFile`]) with `export_profile` / `import_profile`.
+//!
+//! The active profile is composed with directory-scoped overlays: every
+//! `.rstn/rules/*.md` found between the worktree root and the files in
+//! context is appended, outermost first, so subprojects of a monorepo can
+//! carry their own guidance (like nested CLAUDE.md files).
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+
+use crate::app_state::{AgentProfile, AgentProfileMetadata};
 
 /// Generate agent rules file for Claude Code CLI
 ///
@@ -25,7 +38,7 @@ use std::path::PathBuf;
 /// * `Err(String)` - Error message if file creation fails
 ///
 /// # Example
-/// ```no_run
+/// ```ignore
 /// let rules_path = generate_agent_rules_file(
 ///     "my-project-123",
 ///     "You are a helpful Rust developer. Always use snake_case."
@@ -69,7 +82,7 @@ pub fn generate_agent_rules_file(
 /// * `Err(String)` - Error message if deletion fails (permissions, etc.)
 ///
 /// # Example
-/// ```no_run
+/// ```ignore
 /// cleanup_agent_rules_file("/tmp/rstn-agent-rules-my-project-123.txt")?;
 /// // File is deleted, or already was missing (both OK)
 /// ```
@@ -89,6 +102,248 @@ pub fn cleanup_agent_rules_file(rules_path: &str) -> Result<(), String> {
     Ok(())
 }
 
+// ============================================================================
+// Profile sharing
+// ============================================================================
+
+/// Longest accepted profile name (characters)
+const MAX_NAME_LEN: usize = 80;
+
+/// Largest accepted prompt (bytes)
+const MAX_PROMPT_BYTES: usize = 100 * 1024;
+
+/// Shareable agent profile, stored as a single TOML file:
+///
+/// ```toml
+/// name = "Strict Rust Reviewer"
+/// description = "Reviews Rust changes for safety and idioms"
+/// author = "Platform Team"
+/// version = "1.2.0"
+/// prompt = """
+/// You are a strict Rust reviewer...
+/// """
+/// ```
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct AgentProfileFile {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub author: String,
+    /// Semantic version (`MAJOR.MINOR.PATCH`)
+    pub version: String,
+    pub prompt: String,
+}
+
+impl AgentProfileFile {
+    /// Profile file for `profile`, keeping its sharing metadata if it has any
+    pub fn from_profile(profile: &AgentProfile) -> Self {
+        let metadata = profile.metadata.clone().unwrap_or_else(|| AgentProfileMetadata {
+            version: "1.0.0".to_string(),
+            ..Default::default()
+        });
+        Self {
+            name: profile.name.clone(),
+            description: metadata.description,
+            author: metadata.author,
+            version: metadata.version,
+            prompt: profile.prompt.clone(),
+        }
+    }
+
+    /// New (non-builtin) profile from this file
+    pub fn into_profile(self) -> AgentProfile {
+        let now = chrono::Utc::now().to_rfc3339();
+        AgentProfile {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: self.name.trim().to_string(),
+            prompt: self.prompt,
+            is_builtin: false,
+            created_at: now.clone(),
+            updated_at: now,
+            metadata: Some(AgentProfileMetadata {
+                description: self.description.trim().to_string(),
+                author: self.author.trim().to_string(),
+                version: self.version.trim().to_string(),
+            }),
+        }
+    }
+}
+
+/// Check a profile file before it is shared or imported
+pub fn validate_profile_file(file: &AgentProfileFile) -> Result<(), String> {
+    let name = file.name.trim();
+    if name.is_empty() {
+        return Err("name is required".to_string());
+    }
+    if name.chars().count() > MAX_NAME_LEN {
+        return Err(format!("name is longer than {} characters", MAX_NAME_LEN));
+    }
+    if name.chars().any(char::is_control) {
+        return Err("name contains control characters".to_string());
+    }
+    if !is_semver(file.version.trim()) {
+        return Err(format!(
+            "version \"{}\" is not MAJOR.MINOR.PATCH",
+            file.version
+        ));
+    }
+    if file.prompt.trim().is_empty() {
+        return Err("prompt is empty".to_string());
+    }
+    if file.prompt.len() > MAX_PROMPT_BYTES {
+        return Err(format!("prompt is larger than {} KB", MAX_PROMPT_BYTES / 1024));
+    }
+    Ok(())
+}
+
+fn is_semver(version: &str) -> bool {
+    let parts: Vec<&str> = version.split('.').collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Parse and validate the contents of a profile file
+pub fn parse_profile_file(content: &str) -> Result<AgentProfileFile, String> {
+    let file: AgentProfileFile =
+        toml::from_str(content).map_err(|e| format!("Invalid agent profile file: {}", e))?;
+    validate_profile_file(&file).map_err(|e| format!("Invalid agent profile: {}", e))?;
+    Ok(file)
+}
+
+/// Write `profile` to `path` as a shareable TOML file
+pub fn export_profile(profile: &AgentProfile, path: &Path) -> Result<(), String> {
+    let file = AgentProfileFile::from_profile(profile);
+    validate_profile_file(&file).map_err(|e| format!("Cannot export agent profile: {}", e))?;
+    let content = toml::to_string_pretty(&file)
+        .map_err(|e| format!("Failed to serialize agent profile: {}", e))?;
+    fs::write(path, content)
+        .map_err(|e| format!("Failed to write agent profile to {:?}: {}", path, e))
+}
+
+/// Read and validate a profile file (the profile is added by `ImportAgentProfile`)
+pub fn import_profile(path: &Path) -> Result<AgentProfileFile, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read agent profile from {:?}: {}", path, e))?;
+    parse_profile_file(&content)
+}
+
+// ============================================================================
+// Directory overlays
+// ============================================================================
+
+/// A directory-scoped rules file (`<dir>/.rstn/rules/*.md`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RulesOverlay {
+    /// Directory the rules apply to, relative to the worktree root ("" = root)
+    pub scope: String,
+    /// Rules file path, relative to the worktree root
+    pub path: String,
+    pub content: String,
+}
+
+/// Find the overlays that apply to `context_files` (absolute, or relative to
+/// `root`), in precedence order: the root's rules first, deeper directories
+/// later, files within a directory by name. The root's rules always apply.
+pub fn discover_rule_overlays(root: &Path, context_files: &[PathBuf]) -> Vec<RulesOverlay> {
+    let mut dirs = vec![PathBuf::new()];
+    for file in context_files {
+        let file = if file.is_absolute() {
+            file.clone()
+        } else {
+            root.join(file)
+        };
+        let Ok(relative) = file.strip_prefix(root) else {
+            continue;
+        };
+        // Skip anything that climbs out of the root
+        if relative
+            .components()
+            .any(|c| !matches!(c, std::path::Component::Normal(_)))
+        {
+            continue;
+        }
+        let mut dir = relative.parent().map(Path::to_path_buf);
+        while let Some(current) = dir {
+            if !dirs.contains(&current) {
+                dirs.push(current.clone());
+            }
+            dir = current.parent().map(Path::to_path_buf);
+        }
+    }
+    dirs.sort_by(|a, b| {
+        a.components()
+            .count()
+            .cmp(&b.components().count())
+            .then_with(|| a.cmp(b))
+    });
+
+    let mut overlays = Vec::new();
+    for dir in dirs {
+        let rules_dir = root.join(&dir).join(".rstn").join("rules");
+        let Ok(entries) = fs::read_dir(&rules_dir) else {
+            continue;
+        };
+        let mut files: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "md"))
+            .collect();
+        files.sort();
+        for file in files {
+            let Ok(content) = fs::read_to_string(&file) else {
+                continue;
+            };
+            if content.trim().is_empty() {
+                continue;
+            }
+            overlays.push(RulesOverlay {
+                scope: dir.to_string_lossy().replace('\\', "/"),
+                path: file
+                    .strip_prefix(root)
+                    .unwrap_or(&file)
+                    .to_string_lossy()
+                    .replace('\\', "/"),
+                content,
+            });
+        }
+    }
+    overlays
+}
+
+/// Concatenate the profile prompt and overlays into one rules document
+/// (later sections take precedence). None if there is nothing to write.
+pub fn compose_rules(profile_prompt: Option<&str>, overlays: &[RulesOverlay]) -> Option<String> {
+    let mut sections: Vec<String> = profile_prompt
+        .map(str::trim)
+        .filter(|prompt| !prompt.is_empty())
+        .map(str::to_string)
+        .into_iter()
+        .collect();
+    for overlay in overlays {
+        let scope = if overlay.scope.is_empty() {
+            "the whole project".to_string()
+        } else {
+            format!("`{}/`", overlay.scope)
+        };
+        sections.push(format!(
+            "# Rules for {} (from {})\n\n{}",
+            scope,
+            overlay.path,
+            overlay.content.trim()
+        ));
+    }
+    if sections.is_empty() {
+        None
+    } else {
+        Some(sections.join("\n\n"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,7 +428,7 @@ mod tests {
 
         // Test with various content types
         let long_content = "Very long content ".repeat(100);
-        let test_cases = vec![
+        let test_cases = [
             "Simple one-liner",
             "Multi-line\nwith\nnewlines",
             "Unicode: 你好世界 🦀",
@@ -216,4 +471,100 @@ mod tests {
 
         cleanup_agent_rules_file(&rules_path).ok();
     }
+
+    fn sample_profile() -> AgentProfile {
+        AgentProfile {
+            id: "p1".to_string(),
+            name: "Strict Reviewer".to_string(),
+            prompt: "You are a strict reviewer.\nReject unsafe code.".to_string(),
+            is_builtin: false,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_at: "2025-01-01T00:00:00Z".to_string(),
+            metadata: Some(AgentProfileMetadata {
+                description: "Reviews changes".to_string(),
+                author: "Platform Team".to_string(),
+                version: "1.2.0".to_string(),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_export_import_profile_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reviewer.toml");
+
+        export_profile(&sample_profile(), &path).expect("Should export profile");
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("version = \"1.2.0\""));
+
+        let file = import_profile(&path).expect("Should import profile");
+        assert_eq!(file, AgentProfileFile::from_profile(&sample_profile()));
+
+        let imported = file.into_profile();
+        assert_ne!(imported.id, "p1");
+        assert_eq!(imported.prompt, sample_profile().prompt);
+        assert_eq!(imported.metadata, sample_profile().metadata);
+    }
+
+    #[test]
+    fn test_parse_profile_file_validation() {
+        let valid = "name = \"A\"\nversion = \"0.1.0\"\nprompt = \"Be brief\"\n";
+        let file = parse_profile_file(valid).expect("Optional metadata may be omitted");
+        assert_eq!(file.author, "");
+
+        let cases = [
+            ("name = \" \"\nversion = \"1.0.0\"\nprompt = \"x\"", "name is required"),
+            ("name = \"A\"\nversion = \"1.0\"\nprompt = \"x\"", "not MAJOR.MINOR.PATCH"),
+            ("name = \"A\"\nversion = \"1.0.0\"\nprompt = \"  \"", "prompt is empty"),
+            ("name = \"A\"\nversion = \"1.0.0\"", "missing field `prompt`"),
+            ("name = \"A\"\nversion = \"1.0.0\"\nprompt = \"x\"\nshell = \"rm\"", "unknown field"),
+        ];
+        for (content, expected) in cases {
+            let err = parse_profile_file(content).unwrap_err();
+            assert!(err.contains(expected), "{:?} should fail with {:?}, got {:?}", content, expected, err);
+        }
+    }
+
+    #[test]
+    fn test_discover_rule_overlays_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |rel: &str, content: &str| {
+            let path = root.join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write(".rstn/rules/style.md", "Root style");
+        write(".rstn/rules/a-security.md", "Root security");
+        write("packages/api/.rstn/rules/api.md", "API rules");
+        write("packages/web/.rstn/rules/web.md", "Web rules");
+        write("packages/api/src/main.rs", "fn main() {}");
+
+        let overlays = discover_rule_overlays(
+            root,
+            &[
+                root.join("packages/api/src/main.rs"),
+                PathBuf::from("packages/api/src/lib.rs"),
+                PathBuf::from("../outside.rs"),
+            ],
+        );
+        let paths: Vec<_> = overlays.iter().map(|o| o.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                ".rstn/rules/a-security.md",
+                ".rstn/rules/style.md",
+                "packages/api/.rstn/rules/api.md"
+            ]
+        );
+        assert_eq!(overlays[2].scope, "packages/api");
+
+        // Root rules apply without files in context
+        assert_eq!(discover_rule_overlays(root, &[]).len(), 2);
+
+        let composed = compose_rules(Some("Profile prompt"), &overlays).unwrap();
+        assert!(composed.starts_with("Profile prompt\n\n# Rules for the whole project"));
+        assert!(composed.ends_with("# Rules for `packages/api/` (from packages/api/.rstn/rules/api.md)\n\nAPI rules"));
+        assert_eq!(compose_rules(Some("  "), &[]), None);
+    }
 }
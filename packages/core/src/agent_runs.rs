@@ -0,0 +1,269 @@
+//! Parallel Claude implementation runs across worktrees.
+//!
+//! Each run implements the plan of one change in one worktree. Runs wait in
+//! FIFO order until a slot is free: at most `max_concurrent` run at once and
+//! never two in the same worktree, since they would edit the same files.
+//! Output is flushed into the change record in batches so that a chatty run
+//! cannot hog the state lock while the others stream.
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::claude_cli::{summarize_tool_input, PermissionDecision, PermissionRequest};
+use crate::guardrails::{Guardrails, GUARDRAILS_FILE};
+
+/// Default number of agent runs that may run at once
+pub const DEFAULT_MAX_CONCURRENT: usize = 2;
+
+/// Number of finished runs kept
+pub const MAX_FINISHED_RUNS: usize = 20;
+
+/// How long a run buffers output before writing it to its change
+pub const FLUSH_INTERVAL: Duration = Duration::from_millis(150);
+
+/// Lifecycle of an agent run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentRunStatus {
+    Queued,
+    Running,
+    /// Paused on a tool call that breaks `.rstn/guardrails.toml`
+    AwaitingApproval,
+    /// Implementation (and tests) finished; the diff waits for a human
+    AwaitingReview,
+    Failed,
+    /// Dropped from the queue, or stopped by the user while running
+    Cancelled,
+}
+
+impl AgentRunStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, AgentRunStatus::AwaitingReview | AgentRunStatus::Failed | AgentRunStatus::Cancelled)
+    }
+
+    /// Started and not finished (holds a slot)
+    pub fn is_active(&self) -> bool {
+        matches!(self, AgentRunStatus::Running | AgentRunStatus::AwaitingApproval)
+    }
+}
+
+/// A tool call of a paused run, waiting for the user's decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentRunApproval {
+    /// Permission request ID from the CLI
+    pub request_id: String,
+    pub tool_name: String,
+    /// Short description of the input (command, file path...)
+    pub input_summary: String,
+    /// Which guardrail the call breaks
+    pub reason: String,
+}
+
+impl AgentRunApproval {
+    /// The approval a tool permission request needs before it may run
+    /// (None = the call keeps to the worktree's guardrails)
+    pub fn for_request(rails: &Guardrails, worktree_path: &Path, request: &PermissionRequest) -> Option<Self> {
+        let reason = rails.check(worktree_path, &request.tool_name, &request.input)?;
+        Some(Self {
+            request_id: request.request_id.clone(),
+            tool_name: request.tool_name.clone(),
+            input_summary: summarize_tool_input(&request.tool_name, &request.input),
+            reason,
+        })
+    }
+
+    /// What the CLI is told once the user decided
+    pub fn decision(approve: bool) -> PermissionDecision {
+        if approve {
+            PermissionDecision::Allow
+        } else {
+            PermissionDecision::Deny {
+                message: format!("Blocked by .rstn/{}; do not retry this call", GUARDRAILS_FILE),
+            }
+        }
+    }
+}
+
+/// One change being implemented by Claude in one worktree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentRun {
+    pub id: String,
+    pub worktree_path: String,
+    /// Branch of the worktree, for display
+    pub branch: String,
+    pub change_id: String,
+    pub change_name: String,
+    pub status: AgentRunStatus,
+    /// ISO 8601 timestamps
+    pub queued_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+    /// Characters streamed into the change so far
+    #[serde(default)]
+    pub output_chars: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval: Option<AgentRunApproval>,
+}
+
+/// IDs of the queued runs that may start now, oldest first
+pub fn startable(runs: &[AgentRun], max_concurrent: usize) -> Vec<String> {
+    let mut busy: Vec<&str> = runs
+        .iter()
+        .filter(|r| r.status.is_active())
+        .map(|r| r.worktree_path.as_str())
+        .collect();
+    let mut started = Vec::new();
+    for run in runs.iter().filter(|r| r.status == AgentRunStatus::Queued) {
+        if busy.len() >= max_concurrent.max(1) {
+            break;
+        }
+        if !busy.contains(&run.worktree_path.as_str()) {
+            busy.push(&run.worktree_path);
+            started.push(run.id.clone());
+        }
+    }
+    started
+}
+
+/// Drop the oldest finished runs beyond MAX_FINISHED_RUNS
+pub fn prune_finished(runs: &mut Vec<AgentRun>) {
+    let finished = runs.iter().filter(|r| r.status.is_finished()).count();
+    let mut excess = finished.saturating_sub(MAX_FINISHED_RUNS);
+    runs.retain(|r| {
+        if excess > 0 && r.status.is_finished() {
+            excess -= 1;
+            false
+        } else {
+            true
+        }
+    });
+}
+
+/// Output of one run, collected between flushes
+#[derive(Debug)]
+pub struct OutputBatch {
+    pending: String,
+    last_flush: Instant,
+}
+
+impl Default for OutputBatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OutputBatch {
+    pub fn new() -> Self {
+        Self {
+            pending: String::new(),
+            last_flush: Instant::now(),
+        }
+    }
+
+    pub fn push(&mut self, content: &str) {
+        self.pending.push_str(content);
+    }
+
+    /// Buffered output once FLUSH_INTERVAL has passed since the last flush
+    pub fn take_due(&mut self) -> Option<String> {
+        if self.last_flush.elapsed() < FLUSH_INTERVAL {
+            return None;
+        }
+        self.take()
+    }
+
+    /// Buffered output regardless of timing (None when empty)
+    pub fn take(&mut self) -> Option<String> {
+        self.last_flush = Instant::now();
+        (!self.pending.is_empty()).then(|| std::mem::take(&mut self.pending))
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(id: &str, worktree: &str, status: AgentRunStatus) -> AgentRun {
+        AgentRun {
+            id: id.to_string(),
+            worktree_path: worktree.to_string(),
+            branch: String::new(),
+            change_id: id.to_string(),
+            change_name: id.to_string(),
+            status,
+            queued_at: String::new(),
+            started_at: None,
+            finished_at: None,
+            output_chars: 0,
+            error: None,
+            approval: None,
+        }
+    }
+
+    #[test]
+    fn test_startable_respects_cap_and_worktrees() {
+        let runs = vec![
+            run("a", "/wt/one", AgentRunStatus::AwaitingApproval),
+            run("b", "/wt/one", AgentRunStatus::Queued),
+            run("c", "/wt/two", AgentRunStatus::Queued),
+            run("d", "/wt/two", AgentRunStatus::Queued),
+            run("e", "/wt/three", AgentRunStatus::Queued),
+        ];
+        assert_eq!(startable(&runs, 2), vec!["c"]);
+        assert_eq!(startable(&runs, 4), vec!["c", "e"]);
+        assert!(startable(&runs, 1).is_empty());
+    }
+
+    #[test]
+    fn test_prune_finished_keeps_unfinished() {
+        let mut runs: Vec<AgentRun> = (0..MAX_FINISHED_RUNS + 3)
+            .map(|i| run(&i.to_string(), "/wt", AgentRunStatus::Failed))
+            .collect();
+        runs.insert(0, run("queued", "/wt", AgentRunStatus::Queued));
+        prune_finished(&mut runs);
+        assert_eq!(runs.len(), MAX_FINISHED_RUNS + 1);
+        assert_eq!(runs[0].id, "queued");
+        assert_eq!(runs[1].id, "3");
+    }
+
+    #[test]
+    fn test_approval_for_guarded_requests() {
+        let rails = Guardrails { banned_commands: vec!["git push".to_string()], ..Default::default() };
+        let request = |command: &str| PermissionRequest {
+            request_id: "req-1".to_string(),
+            tool_name: "Bash".to_string(),
+            input: serde_json::json!({ "command": command }),
+        };
+        let worktree = Path::new("/wt");
+
+        assert_eq!(AgentRunApproval::for_request(&rails, worktree, &request("cargo test")), None);
+        let approval = AgentRunApproval::for_request(&rails, worktree, &request("git push origin main")).unwrap();
+        assert_eq!(approval.request_id, "req-1");
+        assert_eq!(approval.input_summary, "git push origin main");
+        assert_eq!(approval.reason, "`git push` is a banned command");
+
+        // A denied call is refused, not just skipped
+        assert!(matches!(AgentRunApproval::decision(false), PermissionDecision::Deny { .. }));
+        assert!(matches!(AgentRunApproval::decision(true), PermissionDecision::Allow));
+    }
+
+    #[test]
+    fn test_output_batch() {
+        let mut batch = OutputBatch::new();
+        batch.push("Step 1");
+        assert_eq!(batch.take_due(), None);
+        batch.push(" done");
+        assert_eq!(batch.take().as_deref(), Some("Step 1 done"));
+        assert_eq!(batch.take(), None);
+    }
+}
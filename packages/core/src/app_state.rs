@@ -6,6 +6,7 @@
 //! - Testing (state round-trip tests)
 //! - Debugging (time-travel, bug reproduction)
 
+use crate::usage::{UsageRecord, UsageTotals};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
@@ -38,6 +39,9 @@ pub struct AppState {
     /// Dev logs for debugging (dev mode only, right panel)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub dev_logs: Vec<DevLog>,
+    /// Backend log entries loaded for the Dev Log panel
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub app_logs: Vec<crate::logging::LogEntry>,
     /// UI layout state (panel states, icon bar)
     #[serde(default)]
     pub ui_layout: UiLayoutState,
@@ -47,6 +51,27 @@ pub struct AppState {
     /// A2UI experimental state
     #[serde(default)]
     pub a2ui: A2UIState,
+    /// Claude CLI token usage and cost since launch
+    #[serde(default)]
+    pub usage: UsageState,
+    /// Claude session history of the active project (loaded from SQLite)
+    #[serde(default)]
+    pub session_history: SessionHistoryState,
+    /// Recent activity of the active project (loaded from SQLite)
+    #[serde(default)]
+    pub activity_feed: ActivityFeedState,
+    /// Claude implementation runs across worktrees
+    #[serde(default)]
+    pub agent_runs: AgentRunsState,
+    /// Undo/redo history for user edits (session only)
+    #[serde(default)]
+    pub undo: crate::undo::UndoHistory,
+    /// Latest machine resource sample (from the system monitor)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_stats: Option<crate::system::SystemStats>,
+    /// Saved settings profiles (loaded from ~/.rstn/profiles/)
+    #[serde(default)]
+    pub settings_profiles: Vec<crate::settings_profiles::SettingsProfile>,
 }
 
 impl Default for AppState {
@@ -62,9 +87,17 @@ impl Default for AppState {
             notifications: Vec::new(),
             active_view: ActiveView::default(),
             dev_logs: Vec::new(),
+            app_logs: Vec::new(),
             ui_layout: UiLayoutState::default(),
             file_viewer: FileViewerState::default(),
             a2ui: A2UIState::default(),
+            usage: UsageState::default(),
+            session_history: SessionHistoryState::default(),
+            activity_feed: ActivityFeedState::default(),
+            agent_runs: AgentRunsState::default(),
+            undo: crate::undo::UndoHistory::default(),
+            system_stats: None,
+            settings_profiles: Vec::new(),
         }
     }
 }
@@ -95,6 +128,14 @@ impl AppState {
     pub fn clear_dev_logs(&mut self) {
         self.dev_logs.clear();
     }
+
+    /// Claude model for CLI invocations: the active project's override,
+    /// else the global setting (None = CLI default)
+    pub fn claude_model(&self) -> Option<String> {
+        self.active_project()
+            .and_then(|p| p.model.clone())
+            .or_else(|| self.global_settings.model.clone())
+    }
 }
 
 // ============================================================================
@@ -236,6 +277,37 @@ pub struct ProjectState {
     /// Loading state for branches
     #[serde(default)]
     pub is_loading_branches: bool,
+    /// Claude model override for this project (None = global setting)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Review approval requires every spec checklist item to be checked
+    #[serde(default)]
+    pub require_checklists: bool,
+    /// Run tests with coverage after implementations
+    #[serde(default)]
+    pub collect_coverage: bool,
+    /// Re-run the build check when source files in the active worktree change
+    #[serde(default)]
+    pub check_build_on_save: bool,
+    /// Recurring tasks from .rstn/schedule.toml
+    #[serde(default)]
+    pub schedule: ScheduleState,
+    /// Docker services started while this project was focused (the Docker
+    /// containers themselves are shared by all projects)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub docker_services: Vec<String>,
+    /// Imported docker-compose files (re-imported when the project is opened)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub compose_files: Vec<String>,
+    /// Service groups from .rstn/services.toml
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub service_groups: Vec<crate::service_groups::ServiceGroup>,
+    /// Error loading .rstn/services.toml (groups can't be edited until fixed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_groups_error: Option<String>,
+    /// HTTP request collections from .rstn/requests/ and their last results
+    #[serde(default)]
+    pub http_requests: HttpRequestsState,
 }
 
 impl ProjectState {
@@ -260,6 +332,16 @@ impl ProjectState {
             agent_rules_config: AgentRulesConfig::default(),
             available_branches: Vec::new(),
             is_loading_branches: false,
+            model: None,
+            require_checklists: false,
+            collect_coverage: false,
+            check_build_on_save: false,
+            schedule: ScheduleState::default(),
+            docker_services: Vec::new(),
+            compose_files: Vec::new(),
+            service_groups: Vec::new(),
+            service_groups_error: None,
+            http_requests: HttpRequestsState::default(),
         }
     }
 
@@ -318,6 +400,36 @@ pub struct WorktreeState {
     /// File explorer state
     #[serde(default)]
     pub explorer: FileExplorerState,
+    /// Full-text search state (Explorer search panel)
+    #[serde(default)]
+    pub search: SearchState,
+    /// Git health (None until RefreshWorktreeHealth completes)
+    #[serde(default)]
+    pub health: Option<WorktreeHealth>,
+    /// Branch diff against the base branch (None until LoadWorktreeDiff completes)
+    #[serde(default)]
+    pub diff: Option<crate::worktree::diff::WorktreeDiff>,
+    /// Whether the branch diff is loading
+    #[serde(default)]
+    pub is_loading_diff: bool,
+    /// Last dependency audit (None until RunDependencyAudit completes)
+    #[serde(default)]
+    pub audit: Option<crate::audit::AuditReport>,
+    /// Whether a dependency audit is running
+    #[serde(default)]
+    pub is_auditing: bool,
+    /// Last compiler check (None until RunBuildCheck completes)
+    #[serde(default)]
+    pub build: Option<crate::build_diagnostics::BuildDiagnostics>,
+    /// Whether a compiler check is running
+    #[serde(default)]
+    pub is_checking_build: bool,
+    /// Language servers started for this worktree's files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lsp_servers: Vec<LspServerState>,
+    /// Spec-kit workflow (specify → clarify → plan → tasks)
+    #[serde(default)]
+    pub workflows: WorkflowsState,
     // Note: Docker state moved to AppState.docker (global scope)
 }
 
@@ -341,10 +453,46 @@ impl WorktreeState {
                 current_path: path,
                 ..Default::default()
             },
+            search: SearchState::default(),
+            health: None,
+            diff: None,
+            is_loading_diff: false,
+            audit: None,
+            is_auditing: false,
+            build: None,
+            is_checking_build: false,
+            lsp_servers: Vec::new(),
+            workflows: WorkflowsState::default(),
         }
     }
 }
 
+/// Git health summary for a worktree (rendered as a badge)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorktreeHealth {
+    /// Number of modified, staged or untracked files
+    pub dirty_files: u32,
+    /// Upstream branch (e.g., "origin/main"), if configured
+    pub upstream: Option<String>,
+    /// Commits ahead of upstream
+    pub ahead: u32,
+    /// Commits behind upstream
+    pub behind: u32,
+    /// Most recent commit on HEAD
+    pub last_commit: Option<LastCommit>,
+    /// Upstream deleted or no recent commits
+    pub is_stale: bool,
+}
+
+/// Last commit on a worktree's HEAD
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LastCommit {
+    pub hash: String,
+    pub summary: String,
+    /// Unix timestamp (seconds)
+    pub timestamp: i64,
+}
+
 // ============================================================================
 // MCP Server State
 // ============================================================================
@@ -402,6 +550,24 @@ pub struct McpTool {
     pub input_schema: serde_json::Value,
 }
 
+/// Call metrics for a single MCP tool
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct McpToolMetrics {
+    /// Tool name (e.g. "read_file")
+    pub tool_name: String,
+    /// Total calls
+    pub call_count: u64,
+    /// Calls that returned an error
+    pub error_count: u64,
+    /// Median latency (ms)
+    pub p50_ms: f64,
+    /// 95th percentile latency (ms)
+    pub p95_ms: f64,
+    /// Timestamp of the most recent call (ISO 8601)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_called_at: Option<String>,
+}
+
 /// MCP server state for a worktree
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct McpState {
@@ -422,6 +588,106 @@ pub struct McpState {
     /// Available MCP tools (from tools/list)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub available_tools: Vec<McpTool>,
+    /// Per-tool call metrics (from the running server)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_metrics: Vec<McpToolMetrics>,
+    /// Which tools the server exposes (from .rstn/mcp-policy.toml)
+    #[serde(default)]
+    pub policy: crate::mcp_policy::McpPolicy,
+    /// External servers from the worktree's .mcp.json
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external_servers: Vec<ExternalMcpServer>,
+    /// Manual tool invocations on external servers
+    #[serde(default)]
+    pub playground: McpPlaygroundState,
+}
+
+/// How rstn talks to an external MCP server
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum McpTransport {
+    Stdio,
+    Http,
+}
+
+/// Connection status of an external MCP server
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExternalMcpStatus {
+    Connecting,
+    Connected,
+    Error,
+}
+
+/// External MCP server and the tools it offers
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExternalMcpServer {
+    /// Name in .mcp.json
+    pub name: String,
+    pub transport: McpTransport,
+    /// Command line or URL
+    pub target: String,
+    pub status: ExternalMcpStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub tools: Vec<McpTool>,
+}
+
+/// Lifecycle of a language server
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LspServerStatus {
+    Starting,
+    Running,
+    Failed,
+}
+
+/// Language server started for a worktree (by the first hover or definition
+/// request on one of its files)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LspServerState {
+    pub server: crate::lsp::LanguageServer,
+    pub status: LspServerStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Maximum number of playground calls kept per worktree
+pub const MAX_PLAYGROUND_HISTORY: usize = 50;
+
+/// Manual invocation of an external server's tool
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct McpPlaygroundEntry {
+    pub id: String,
+    pub server: String,
+    pub tool: String,
+    pub arguments: serde_json::Value,
+    /// ISO 8601 timestamp of the call
+    pub called_at: String,
+    /// None while the call is running
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// `tools/call` result
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// MCP tool playground: request/response history, newest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct McpPlaygroundState {
+    #[serde(default)]
+    pub history: Vec<McpPlaygroundEntry>,
+}
+
+impl McpPlaygroundState {
+    /// Record a call, keeping only the most recent MAX_PLAYGROUND_HISTORY
+    pub fn push(&mut self, entry: McpPlaygroundEntry) {
+        self.history.insert(0, entry);
+        self.history.truncate(MAX_PLAYGROUND_HISTORY);
+    }
 }
 
 impl McpState {
@@ -466,6 +732,74 @@ pub struct ChatMessage {
     /// Whether this message is still streaming
     #[serde(default)]
     pub is_streaming: bool,
+    /// Whether the response was cancelled by the user before completing
+    #[serde(default)]
+    pub is_cancelled: bool,
+    /// Files sent with the message (metadata only)
+    #[serde(default)]
+    pub attachments: Vec<ChatAttachment>,
+    /// Tools the agent used while answering, in call order
+    #[serde(default)]
+    pub tool_calls: Vec<ChatToolCall>,
+}
+
+/// A tool call made by the agent (file edit, command, search...)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChatToolCall {
+    /// Tool use ID from the CLI
+    pub id: String,
+    /// Tool name (e.g. "Bash", "Edit", "mcp__rstn__read_file")
+    pub name: String,
+    /// Short description of the input (command, file path, pattern...)
+    pub input_summary: String,
+    /// Length of the message content when the call started, to place it in the timeline
+    pub content_offset: usize,
+    /// Start of the result (None while running)
+    #[serde(default)]
+    pub result_excerpt: Option<String>,
+    #[serde(default)]
+    pub is_error: bool,
+    /// Time until the result arrived (None while running)
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+}
+
+/// A tool call the agent wants to make, waiting for the user's approval
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PendingToolApproval {
+    /// Permission request ID from the CLI
+    pub request_id: String,
+    /// Assistant message being generated
+    pub message_id: String,
+    /// Tool name (e.g. "Bash", "Write")
+    pub tool_name: String,
+    /// Short description of the input (command, file path...)
+    pub input_summary: String,
+}
+
+/// How an attachment is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatAttachmentKind {
+    /// Inlined into the prompt as a fenced code block
+    Text,
+    /// Passed to the Claude CLI as an image input
+    Image,
+}
+
+/// A file attached to a chat message
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChatAttachment {
+    /// File name
+    pub name: String,
+    /// Absolute path
+    pub path: String,
+    pub kind: ChatAttachmentKind,
+    /// Size in bytes
+    pub size: u64,
+    /// Text was cut at `chat_attachments::MAX_TEXT_BYTES`
+    #[serde(default)]
+    pub truncated: bool,
 }
 
 /// Maximum number of chat messages to keep
@@ -483,6 +817,15 @@ pub struct ChatState {
     /// Error message (if any)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Claude CLI session ID, resumed by follow-up messages
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Tool call waiting for approval (the CLI is paused until answered)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_tool_approval: Option<PendingToolApproval>,
+    /// File edits suggested by an assistant message, under review
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proposed_edits: Option<crate::edits::ProposedEditSet>,
 }
 
 impl ChatState {
@@ -503,17 +846,70 @@ impl ChatState {
         }
     }
 
+    /// Record a tool call on the last assistant message at the current content position
+    pub fn add_tool_call(&mut self, mut tool_call: ChatToolCall) {
+        if let Some(last) = self.messages.last_mut() {
+            if last.role == ChatRole::Assistant {
+                tool_call.content_offset = last.content.len();
+                last.tool_calls.push(tool_call);
+            }
+        }
+    }
+
+    /// Store the result of a tool call (searched in the latest messages first)
+    pub fn complete_tool_call(&mut self, id: &str, result_excerpt: String, is_error: bool, duration_ms: u64) {
+        let call = self
+            .messages
+            .iter_mut()
+            .rev()
+            .flat_map(|m| m.tool_calls.iter_mut())
+            .find(|call| call.id == id);
+        if let Some(call) = call {
+            call.result_excerpt = Some(result_excerpt);
+            call.is_error = is_error;
+            call.duration_ms = Some(duration_ms);
+        }
+    }
+
     /// Mark the last message as done streaming
     pub fn finish_streaming(&mut self) {
         if let Some(last) = self.messages.last_mut() {
             last.is_streaming = false;
         }
+        // Nothing is left to approve once the response has ended
+        self.pending_tool_approval = None;
+    }
+
+    /// Clear the pending approval if it is the given request
+    pub fn resolve_tool_approval(&mut self, request_id: &str) {
+        if self
+            .pending_tool_approval
+            .as_ref()
+            .is_some_and(|approval| approval.request_id == request_id)
+        {
+            self.pending_tool_approval = None;
+        }
+    }
+
+    /// Stop a streaming message early (user cancelled)
+    pub fn cancel_message(&mut self, message_id: &str) {
+        if let Some(message) = self.messages.iter_mut().find(|m| m.id == message_id) {
+            if message.is_streaming {
+                message.is_streaming = false;
+                message.is_cancelled = true;
+            }
+        }
+        self.is_typing = false;
+        self.pending_tool_approval = None;
     }
 
     /// Clear all messages
     pub fn clear(&mut self) {
         self.messages.clear();
         self.error = None;
+        self.session_id = None;
+        self.pending_tool_approval = None;
+        self.proposed_edits = None;
     }
 }
 
@@ -575,7 +971,7 @@ pub struct FileTab {
 }
 
 /// File explorer state for a worktree
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct FileExplorerState {
     /// Currently viewed absolute path (root of the tree view)
     pub current_path: String,
@@ -611,27 +1007,39 @@ pub struct FileExplorerState {
     /// Currently active tab path (the tab being viewed)
     #[serde(default)]
     pub active_tab_path: Option<String>,
+    /// Git operation (stage/commit/push/pull) in progress
+    #[serde(default)]
+    pub git_busy: bool,
+    /// Last pre-commit security scan that blocked a commit
+    #[serde(default)]
+    pub security_scan: Option<crate::git::SecurityScanResult>,
+    /// Commit history of the selected file (detail pane)
+    #[serde(default)]
+    pub file_history: Option<FileHistory>,
 }
 
-impl Default for FileExplorerState {
-    fn default() -> Self {
-        Self {
-            current_path: String::new(),
-            entries: Vec::new(),
-            directory_cache: HashMap::new(),
-            expanded_paths: HashSet::new(),
-            loading_paths: HashSet::new(),
-            selected_path: None,
-            selected_comments: Vec::new(),
-            sort_config: SortConfig::default(),
-            filter_query: String::new(),
-            history: NavigationHistory::default(),
-            is_loading: false,
-            error: None,
-            tabs: Vec::new(),
-            active_tab_path: None,
-        }
-    }
+/// Commits that touched a file, newest first
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct FileHistory {
+    /// Absolute path of the file
+    pub path: String,
+    pub commits: Vec<crate::git::FileCommit>,
+    pub is_loading: bool,
+    /// Set if git failed (e.g. the file is not in a repository)
+    pub error: Option<String>,
+}
+
+/// Full-text search state for a worktree
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SearchState {
+    /// Query of the current (or last) search
+    pub query: String,
+    /// Glob restricting searched files (e.g. "*.rs")
+    pub glob: Option<String>,
+    pub case_sensitive: bool,
+    pub results: crate::explorer::search::SearchResults,
+    pub is_searching: bool,
+    pub error: Option<String>,
 }
 
 /// Navigation history for explorer
@@ -732,6 +1140,42 @@ pub struct EnvConfig {
     /// Result of the last copy operation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_copy_result: Option<EnvCopyResult>,
+    /// How Docker port conflicts are resolved when starting a service
+    #[serde(default)]
+    pub auto_resolve_ports: PortConflictStrategy,
+    /// Dry-run preview of a pending connection string injection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub injection_preview: Option<EnvInjectionPreview>,
+    /// Result of the last env diff between two worktrees
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_diff: Option<EnvDiff>,
+    /// Secrets found in the active worktree's tracked env files (masked)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secrets: Vec<crate::env_secrets::EnvSecret>,
+}
+
+/// Strategy for resolving Docker port conflicts without the conflict dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum PortConflictStrategy {
+    /// Always ask the user
+    #[default]
+    Never,
+    /// Retry on the suggested port when the port is held by a non-rstn container;
+    /// ask before shadowing another rstn service
+    SuggestNext,
+    /// Always retry on the suggested port
+    AlwaysNext,
+}
+
+impl PortConflictStrategy {
+    /// Whether a conflict should be resolved automatically
+    pub fn should_auto_resolve(self, conflicting_is_rstn_managed: bool) -> bool {
+        match self {
+            PortConflictStrategy::Never => false,
+            PortConflictStrategy::SuggestNext => !conflicting_is_rstn_managed,
+            PortConflictStrategy::AlwaysNext => true,
+        }
+    }
 }
 
 impl Default for EnvConfig {
@@ -746,6 +1190,10 @@ impl Default for EnvConfig {
             auto_copy_enabled: true,
             source_worktree: None,
             last_copy_result: None,
+            auto_resolve_ports: PortConflictStrategy::default(),
+            injection_preview: None,
+            last_diff: None,
+            secrets: Vec::new(),
         }
     }
 }
@@ -771,6 +1219,65 @@ pub struct EnvCopyResult {
     pub timestamp: String,
 }
 
+/// Key-level diff of env files between two worktrees
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EnvDiff {
+    pub from_worktree_path: String,
+    pub to_worktree_path: String,
+    /// Files with differences
+    pub files: Vec<EnvFileDiff>,
+    /// Timestamp of the diff (ISO 8601)
+    pub timestamp: String,
+}
+
+/// Differences in one env file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EnvFileDiff {
+    /// File path relative to the worktree (e.g. ".env")
+    pub file: String,
+    pub keys: Vec<EnvKeyDiff>,
+}
+
+/// Difference of one variable (values are masked)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EnvKeyDiff {
+    pub key: String,
+    pub change: EnvKeyChange,
+    pub from_value: Option<String>,
+    pub to_value: Option<String>,
+}
+
+/// How a variable differs, relative to the target worktree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvKeyChange {
+    /// Only in the source
+    Added,
+    /// Only in the target
+    Removed,
+    /// In both with different values
+    Changed,
+}
+
+/// Preview of writing a service connection string into an env file.
+/// Values are masked; the real connection string is only written to disk.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EnvInjectionPreview {
+    pub service_id: String,
+    pub db_name: String,
+    /// Env file relative to the worktree (e.g. ".env")
+    pub env_file: String,
+    /// Absolute path of the env file in the active worktree
+    pub file_path: String,
+    pub var_name: String,
+    /// New value (password masked)
+    pub masked_value: String,
+    /// Current value (password masked), None if the variable is not set
+    pub previous_value: Option<String>,
+    /// Whether the env file exists (it is created otherwise)
+    pub file_exists: bool,
+}
+
 // ============================================================================
 // Constitution Presets (Integrated from Agent Rules)
 // ============================================================================
@@ -924,6 +1431,19 @@ pub struct AgentProfile {
     pub is_builtin: bool,
     pub created_at: String,
     pub updated_at: String,
+    /// Sharing metadata (set for imported profiles)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<AgentProfileMetadata>,
+}
+
+/// Metadata of a shared agent profile file
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AgentProfileMetadata {
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub author: String,
+    pub version: String,
 }
 
 /// Agent rules configuration (DEPRECATED: use ConstitutionPresetsConfig)
@@ -1101,6 +1621,19 @@ impl From<crate::actions::ChangeData> for Change {
             proposal_review_session_id: data.proposal_review_session_id,
             plan_review_session_id: data.plan_review_session_id,
             context_files: data.context_files,
+            implementation_tasks: data
+                .implementation_tasks
+                .into_iter()
+                .map(|t| ImplementationTask { title: t.title, done: t.done })
+                .collect(),
+            review_comments: Vec::new(),
+            branch: None,
+            pull_request_url: None,
+            snapshot: None,
+            test_results: None,
+            hook_results: Vec::new(),
+            status_conflict: None,
+            implementation_approval: None,
         }
     }
 }
@@ -1184,6 +1717,182 @@ pub struct GlobalSettings {
     pub theme: Theme,
     /// Default project path for "Open Folder" dialog
     pub default_project_path: Option<String>,
+    /// Claude model passed to `--model` (None = CLI default)
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Maximum number of tasks running at once (None = task_queue::DEFAULT_MAX_PARALLEL)
+    #[serde(default)]
+    pub task_max_parallel: Option<u32>,
+    /// Time budget of an implementation run in seconds
+    /// (None = claude_cli::IMPLEMENTATION_TIMEOUT)
+    #[serde(default)]
+    pub implementation_timeout_secs: Option<u32>,
+    /// Which events show an OS notification
+    #[serde(default)]
+    pub desktop_notifications: DesktopNotificationSettings,
+    /// Default backend for chat and one-shot prompts
+    #[serde(default)]
+    pub provider: LlmProviderKind,
+    /// OpenAI-compatible endpoint used by `LlmProviderKind::OpenAi`
+    #[serde(default)]
+    pub openai: OpenAiSettings,
+    /// Local Ollama server used by `LlmProviderKind::Ollama` and internal tasks
+    #[serde(default)]
+    pub ollama: OllamaSettings,
+    /// Tokens for opening pull requests of changes
+    #[serde(default)]
+    pub git_hosting: GitHostingSettings,
+    /// Keyboard shortcut of each command
+    #[serde(default)]
+    pub keybindings: crate::keybindings::Keybindings,
+    /// Local reverse proxy for services
+    #[serde(default)]
+    pub proxy: ProxySettings,
+    /// Settings profile applied last (see `settings_profiles`)
+    #[serde(default)]
+    pub active_profile: Option<String>,
+    /// Guardrails for worktrees without `.rstn/guardrails.toml` (from the
+    /// active profile)
+    #[serde(default)]
+    pub guardrails: Option<crate::guardrails::Guardrails>,
+}
+
+/// Backend that runs prompts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LlmProviderKind {
+    /// Claude Code CLI (sessions, MCP and agent rules files)
+    #[default]
+    #[serde(rename = "claude_cli")]
+    ClaudeCli,
+    /// OpenAI-compatible HTTP API (`POST {base_url}/chat/completions`)
+    #[serde(rename = "openai")]
+    OpenAi,
+    /// Local Ollama server (`POST {base_url}/api/chat`)
+    #[serde(rename = "ollama")]
+    Ollama,
+}
+
+/// Connection settings for an OpenAI-compatible API
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct OpenAiSettings {
+    /// API base URL (e.g. "https://api.openai.com/v1")
+    pub base_url: String,
+    /// Bearer token (None for local servers without auth). Never serialized,
+    /// so it stays out of state.json, the journal and the renderer; it comes
+    /// from OPENAI_API_KEY_ENV at startup or from Settings for the session.
+    #[serde(skip_serializing)]
+    pub api_key: Option<String>,
+    /// Model sent with every request
+    pub model: String,
+}
+
+/// Environment variable the OpenAI-compatible API key is read from at startup
+pub const OPENAI_API_KEY_ENV: &str = "OPENAI_API_KEY";
+
+/// Default Ollama server address
+pub const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+
+/// Connection settings for a local Ollama server
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct OllamaSettings {
+    /// Server address
+    pub base_url: String,
+    /// Model for chat (e.g. "llama3.2")
+    pub model: String,
+    /// Model for cheap internal tasks such as naming changes (None = keep them local)
+    pub task_model: Option<String>,
+}
+
+impl Default for OllamaSettings {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_OLLAMA_URL.to_string(),
+            model: String::new(),
+            task_model: None,
+        }
+    }
+}
+
+/// Access tokens for the GitHub and GitLab REST APIs
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct GitHostingSettings {
+    /// Personal access token with pull request write access
+    pub github_token: Option<String>,
+    /// Personal access token with the `api` scope
+    pub gitlab_token: Option<String>,
+}
+
+/// Local reverse proxy (see `proxy`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ProxySettings {
+    /// Start the proxy on launch (set by StartProxy / StopProxy)
+    pub enabled: bool,
+    /// Listening port (None = proxy::DEFAULT_PROXY_PORT)
+    pub port: Option<u16>,
+    pub routes: Vec<crate::proxy::ProxyRoute>,
+}
+
+/// Long-running events that can show an OS notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DesktopNotificationEvent {
+    /// A task run finished (succeeded or failed)
+    TaskCompleted,
+    /// Claude finished generating a proposal, plan or implementation
+    ClaudeFinished,
+    /// A running Docker service stopped on its own
+    DockerServiceCrashed,
+    /// Implementing a change failed
+    ImplementationFailed,
+}
+
+/// Per-event OS notification toggles (all on by default)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct DesktopNotificationSettings {
+    pub task_completed: bool,
+    pub claude_finished: bool,
+    pub docker_service_crashed: bool,
+    pub implementation_failed: bool,
+}
+
+impl Default for DesktopNotificationSettings {
+    fn default() -> Self {
+        Self {
+            task_completed: true,
+            claude_finished: true,
+            docker_service_crashed: true,
+            implementation_failed: true,
+        }
+    }
+}
+
+impl DesktopNotificationSettings {
+    fn toggle_mut(&mut self, event: DesktopNotificationEvent) -> &mut bool {
+        match event {
+            DesktopNotificationEvent::TaskCompleted => &mut self.task_completed,
+            DesktopNotificationEvent::ClaudeFinished => &mut self.claude_finished,
+            DesktopNotificationEvent::DockerServiceCrashed => &mut self.docker_service_crashed,
+            DesktopNotificationEvent::ImplementationFailed => &mut self.implementation_failed,
+        }
+    }
+
+    pub fn is_enabled(&self, event: DesktopNotificationEvent) -> bool {
+        match event {
+            DesktopNotificationEvent::TaskCompleted => self.task_completed,
+            DesktopNotificationEvent::ClaudeFinished => self.claude_finished,
+            DesktopNotificationEvent::DockerServiceCrashed => self.docker_service_crashed,
+            DesktopNotificationEvent::ImplementationFailed => self.implementation_failed,
+        }
+    }
+
+    pub fn set_enabled(&mut self, event: DesktopNotificationEvent, enabled: bool) {
+        *self.toggle_mut(event) = enabled;
+    }
 }
 
 // ============================================================================
@@ -1214,28 +1923,165 @@ pub struct DockersState {
     /// Result of the last CreateDatabase or CreateVhost operation
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub last_connection_string: Option<String>,
+    /// Image pulls started via `docker_pull_image` (image -> progress)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub image_pulls: HashMap<String, ImagePullState>,
+    /// Recent resource samples per service (from `docker_stats_stream`), oldest first
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub stats: HashMap<String, Vec<ContainerStats>>,
+    /// Health of services started from rstn (cleared when the service is stopped)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub service_health: HashMap<String, ServiceHealth>,
+    /// Databases inside running database services (service_id -> databases)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub databases: HashMap<String, Vec<DatabaseInfo>>,
+    /// Loading state for database/table listings
+    #[serde(default)]
+    pub is_loading_databases: bool,
+    /// Interactive exec sessions (service_id -> terminal session ID)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub exec_sessions: HashMap<String, String>,
+    /// Local reverse proxy
+    #[serde(default)]
+    pub proxy: ProxyStatus,
 }
 
-/// Pending port conflict that requires user resolution
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct PendingConflict {
-    /// The service that was trying to start
-    pub service_id: String,
-    /// The port conflict details
-    pub conflict: PortConflict,
+/// Whether the local reverse proxy is running
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ProxyStatus {
+    pub running: bool,
+    /// Port it listens on (while running)
+    pub port: Option<u16>,
+    /// CA certificate to trust for HTTPS
+    pub ca_cert_path: Option<String>,
+    /// Why the last start failed
+    pub error: Option<String>,
 }
 
-/// Information about a port conflict
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub struct PortConflict {
+/// Health check result of a started service
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceHealth {
+    /// Container is up, health check not passing yet
+    Starting,
+    /// Health check passed
+    Healthy,
+    /// Container exited or health check timed out
+    Unhealthy,
+}
+
+impl ServiceHealth {
+    /// Status shown for a service in this health state
+    pub fn status(self) -> ServiceStatus {
+        match self {
+            Self::Starting => ServiceStatus::Starting,
+            Self::Healthy => ServiceStatus::Running,
+            Self::Unhealthy => ServiceStatus::Error,
+        }
+    }
+}
+
+/// Maximum number of stats samples kept per service (sparkline window)
+pub const MAX_STATS_SAMPLES: usize = 60;
+
+/// One container resource sample
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ContainerStats {
+    /// CPU usage across all cores (100.0 = one full core)
+    pub cpu_percent: f64,
+    /// Memory in use, excluding page cache (bytes)
+    pub memory_usage: u64,
+    /// Memory limit (bytes)
+    pub memory_limit: u64,
+    /// Total bytes received across networks
+    pub net_rx_bytes: u64,
+    /// Total bytes sent across networks
+    pub net_tx_bytes: u64,
+    /// When the sample was read (ISO 8601)
+    pub timestamp: String,
+}
+
+/// Database inside a database service
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DatabaseInfo {
+    pub name: String,
+    /// Size on disk (bytes), if the engine reports it
+    pub size_bytes: Option<u64>,
+    /// Tables, once listed via FetchDockerTables
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tables: Option<Vec<TableInfo>>,
+}
+
+/// Table (or MongoDB collection) inside a database
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TableInfo {
+    /// Schema (PostgreSQL/MySQL), None for MongoDB collections
+    pub schema: Option<String>,
+    pub name: String,
+    /// Estimated row (document) count
+    pub row_count: Option<u64>,
+}
+
+/// Progress of a single image pull
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ImagePullState {
+    /// Latest overall status (e.g. "Pulling from library/postgres")
+    pub status: String,
+    /// Per-layer progress, in order of first appearance
+    pub layers: Vec<ImageLayerProgress>,
+    pub is_pulling: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Progress of one image layer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImageLayerProgress {
+    pub id: String,
+    /// "Downloading", "Extracting", "Pull complete", ...
+    pub status: String,
+    pub current: Option<u64>,
+    pub total: Option<u64>,
+}
+
+/// Pending port conflict that requires user resolution
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PendingConflict {
+    /// The service that was trying to start
+    pub service_id: String,
+    /// The port conflict details
+    pub conflict: PortConflict,
+}
+
+/// Information about a port conflict
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PortConflict {
     /// The port that was requested
     pub requested_port: u16,
     /// The container currently using this port
     pub conflicting_container: ConflictingContainer,
+    /// Host process using this port when it is not a container
+    #[serde(default)]
+    pub conflicting_process: Option<ConflictingProcess>,
     /// Suggested alternative port
     pub suggested_port: u16,
 }
 
+/// A host process (outside Docker) causing a port conflict
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConflictingProcess {
+    /// Process ID (0 when the owner could not be identified)
+    pub pid: u32,
+    /// Process name (e.g. "node")
+    pub name: String,
+    /// Executable path
+    pub exe: Option<String>,
+    /// Owning user
+    pub user: Option<String>,
+    /// Full command line
+    pub command: Option<String>,
+}
+
 /// Information about the container causing a port conflict
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ConflictingContainer {
@@ -1290,6 +2136,49 @@ pub enum ServiceType {
     Other,
 }
 
+// ============================================================================
+// Schedule State
+// ============================================================================
+
+/// Recurring tasks of a project
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ScheduleState {
+    /// Schedules with their next/last run times
+    pub entries: Vec<crate::schedule::ScheduleEntry>,
+    /// Whether all schedules of the project are paused
+    pub paused: bool,
+    /// Error from loading schedule.toml
+    pub error: Option<String>,
+}
+
+// ============================================================================
+// HTTP Requests State
+// ============================================================================
+
+/// HTTP request collections of a project
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct HttpRequestsState {
+    pub collections: Vec<crate::http_client::RequestCollection>,
+    /// Requests being sent ("<collection>/<request>")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub running: Vec<String>,
+    /// Last result of each request ("<collection>/<request>")
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub results: HashMap<String, HttpRunResult>,
+}
+
+/// Outcome of sending a request
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HttpRunResult {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<crate::http_client::HttpResponseData>,
+    /// Why no response was received (undefined variable, connection refused)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// When the request finished (RFC 3339)
+    pub finished_at: String,
+}
+
 // ============================================================================
 // Tasks State
 // ============================================================================
@@ -1299,6 +2188,12 @@ pub enum ServiceType {
 pub struct TasksState {
     /// Justfile commands
     pub commands: Vec<JustCommandInfo>,
+    /// Tasks from every detected provider (justfile, package.json, Makefile, cargo aliases)
+    #[serde(default)]
+    pub tasks: Vec<crate::tasks::TaskDescriptor>,
+    /// Queued, running and recently finished task runs
+    #[serde(default)]
+    pub runs: Vec<crate::task_queue::TaskRun>,
     /// Status of each task (by name)
     pub task_statuses: HashMap<String, TaskStatus>,
     /// Currently active/running command
@@ -1318,6 +2213,12 @@ pub struct TasksState {
     /// Constitution content (None = not read yet)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub constitution_content: Option<String>,
+    /// Last constitution lint report (None = not linted yet)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constitution_lint: Option<crate::constitution::lint::ConstitutionLintReport>,
+    /// A constitution lint is running
+    #[serde(default)]
+    pub constitution_linting: bool,
     /// Whether project root has CLAUDE.md (None = not checked yet)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub claude_md_exists: Option<bool>,
@@ -1377,6 +2278,19 @@ pub struct JustCommandInfo {
     pub name: String,
     pub description: Option<String>,
     pub recipe: String,
+    #[serde(default)]
+    pub parameters: Vec<JustParameterInfo>,
+}
+
+/// Justfile recipe parameter
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JustParameterInfo {
+    pub name: String,
+    /// Default value (None = required)
+    pub default: Option<String>,
+    /// `+`/`*` parameter accepting any number of values
+    #[serde(default)]
+    pub variadic: bool,
 }
 
 /// Task execution status
@@ -1390,6 +2304,259 @@ pub enum TaskStatus {
     Error,
 }
 
+// ============================================================================
+// Workflows State (spec-kit)
+// ============================================================================
+
+/// Status of one spec-kit phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecPhaseStatus {
+    #[default]
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+/// One phase of a spec-kit workflow
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpecPhaseRun {
+    pub phase: crate::spec_kit::SpecPhase,
+    pub status: SpecPhaseStatus,
+    /// Streamed output of the last run
+    #[serde(default)]
+    pub output: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Spec-kit workflow of one feature (`specs/<NNN>-<name>/`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpecWorkflow {
+    pub feature_number: u32,
+    pub feature_name: String,
+    /// Feature directory relative to the worktree (e.g. "specs/007-user-login")
+    pub spec_dir: String,
+    /// Feature description the workflow started from
+    pub description: String,
+    /// Generate spec.md with Claude (off: fill the template)
+    pub use_claude: bool,
+    /// One entry per phase, in workflow order
+    pub phases: Vec<SpecPhaseRun>,
+}
+
+impl SpecWorkflow {
+    pub fn new(feature: &crate::spec_kit::SpecFeature, description: String) -> Self {
+        Self {
+            feature_number: feature.number,
+            feature_name: feature.short_name.clone(),
+            spec_dir: feature.relative_dir(),
+            description,
+            use_claude: true,
+            phases: crate::spec_kit::SpecPhase::ALL
+                .iter()
+                .map(|phase| SpecPhaseRun {
+                    phase: *phase,
+                    status: SpecPhaseStatus::Pending,
+                    output: String::new(),
+                    error: None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn phase_mut(&mut self, phase: crate::spec_kit::SpecPhase) -> Option<&mut SpecPhaseRun> {
+        self.phases.iter_mut().find(|run| run.phase == phase)
+    }
+
+    /// Phase currently running (at most one at a time)
+    pub fn running_phase(&self) -> Option<crate::spec_kit::SpecPhase> {
+        self.phases
+            .iter()
+            .find(|run| run.status == SpecPhaseStatus::Running)
+            .map(|run| run.phase)
+    }
+}
+
+/// Status of an interactive clarify session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ClarifySessionStatus {
+    /// Waiting for answers
+    #[default]
+    Asking,
+    /// All questions handled; answers being written into the spec
+    Integrating,
+    Completed,
+    Failed,
+}
+
+/// The user's response to one clarify question (None = skipped)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClarifyAnswer {
+    pub question_id: String,
+    pub value: Option<String>,
+}
+
+/// Interactive clarification of a spec file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClarifySession {
+    /// Spec path relative to the worktree (e.g. "specs/007-user-login/spec.md")
+    pub spec_path: String,
+    pub status: ClarifySessionStatus,
+    /// Category coverage of the spec (updated after integration)
+    pub coverage: Vec<crate::clarify::CategoryCoverage>,
+    pub questions: Vec<crate::clarify::ClarifyQuestion>,
+    /// Responses, in question order
+    #[serde(default)]
+    pub answers: Vec<ClarifyAnswer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl ClarifySession {
+    /// The question waiting for a response
+    pub fn current_question(&self) -> Option<&crate::clarify::ClarifyQuestion> {
+        if self.status != ClarifySessionStatus::Asking {
+            return None;
+        }
+        self.questions.get(self.answers.len())
+    }
+
+    /// Record the response to the current question; once every question is
+    /// handled the session moves on to integration
+    pub fn respond(&mut self, value: Option<String>) {
+        let Some(question) = self.current_question() else {
+            return;
+        };
+        self.answers.push(ClarifyAnswer {
+            question_id: question.id.clone(),
+            value: value.filter(|v| !v.trim().is_empty()),
+        });
+        if self.answers.len() >= self.questions.len() {
+            self.status = ClarifySessionStatus::Integrating;
+        }
+    }
+}
+
+/// Implementation progress of a spec-kit tasks.md
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpecTasksState {
+    /// tasks.md path relative to the worktree (e.g. "specs/007-user-login/tasks.md")
+    pub tasks_path: String,
+    pub items: Vec<crate::spec_tasks::TaskItem>,
+    pub progress: crate::spec_tasks::TaskProgress,
+}
+
+impl SpecTasksState {
+    pub fn new(tasks_path: String, items: Vec<crate::spec_tasks::TaskItem>) -> Self {
+        let progress = crate::spec_tasks::summarize(&items);
+        Self {
+            tasks_path,
+            items,
+            progress,
+        }
+    }
+}
+
+/// Status of a generated spec checklist
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecChecklistStatus {
+    Generating,
+    #[default]
+    Ready,
+    Failed,
+}
+
+/// A checklist-<kind>.md of the spec workflow's feature
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpecChecklist {
+    pub kind: crate::checklist::ChecklistKind,
+    /// Path relative to the worktree (e.g. "specs/007-user-login/checklist-security.md")
+    pub path: String,
+    pub status: SpecChecklistStatus,
+    #[serde(default)]
+    pub items: Vec<crate::checklist::ChecklistItem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl SpecChecklist {
+    /// Number of items not checked yet
+    pub fn unchecked(&self) -> usize {
+        self.items.iter().filter(|item| !item.checked).count()
+    }
+}
+
+/// Consistency analysis of the spec workflow's feature
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpecAnalysis {
+    /// Running, Completed or Failed
+    pub status: SpecPhaseStatus,
+    /// Claude verifies the heuristic findings
+    pub verify: bool,
+    /// Latest report (kept while a re-run is in progress)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report: Option<crate::spec_analysis::AnalysisReport>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Maximum number of progress reports kept per worktree
+pub const MAX_SPEC_PROGRESS_REPORTS: usize = 50;
+
+/// Progress note reported by Claude while implementing tasks.md
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpecProgressReport {
+    pub message: String,
+    /// Task the note is about, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+    pub timestamp: String,
+}
+
+/// Guided workflows of a worktree
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct WorkflowsState {
+    /// Active spec-kit workflow (None = not started)
+    #[serde(default)]
+    pub spec: Option<SpecWorkflow>,
+    /// Active clarify session (None = not started)
+    #[serde(default)]
+    pub clarify: Option<ClarifySession>,
+    /// Tracked tasks.md (None = not loaded)
+    #[serde(default)]
+    pub tasks: Option<SpecTasksState>,
+    /// Progress reports from Claude, oldest first
+    #[serde(default)]
+    pub reports: Vec<SpecProgressReport>,
+    /// Generated checklists of the feature, in ChecklistKind::ALL order
+    #[serde(default)]
+    pub checklists: Vec<SpecChecklist>,    /// Cross-artifact consistency analysis
+    #[serde(default)]
+    pub analysis: Option<SpecAnalysis>,    /// Features of the worktree for the pipeline board
+    #[serde(default)]
+    pub features: Vec<crate::feature_catalog::FeatureInfo>,
+}
+
+impl WorkflowsState {
+    /// Why review approval is blocked by unchecked checklist items, if it is
+    pub fn checklist_blocker(&self) -> Option<String> {
+        let open: Vec<String> = self
+            .checklists
+            .iter()
+            .filter(|c| c.unchecked() > 0)
+            .map(|c| format!("{} in {}", c.unchecked(), c.kind.file_name()))
+            .collect();
+        if open.is_empty() {
+            return None;
+        }
+        Some(format!("unchecked checklist items ({})", open.join(", ")))
+    }
+}
+
 // ============================================================================
 // Changes State (CESDD Phase 2)
 // ============================================================================
@@ -1450,6 +2617,50 @@ pub struct Change {
     /// Source files selected for context injection (relative paths from project root)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub context_files: Vec<String>,
+    /// Plan steps tracked during ExecutePlan
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub implementation_tasks: Vec<ImplementationTask>,
+    /// Review comments of all review sessions of the change (from review.json)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub review_comments: Vec<ReviewComment>,
+    /// Dedicated branch of the change (from git.json)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Pull/merge request opened for the branch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request_url: Option<String>,
+    /// Worktree snapshot taken before the last implementation (from snapshot.json)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<crate::snapshot::ChangeSnapshot>,
+    /// Results of the last test run (from test-results.json)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub test_results: Option<crate::test_runner::TestResults>,
+    /// Lint / format hooks run after the last implementation (from hooks.json)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hook_results: Vec<crate::hooks::HookResult>,
+    /// Local status and a teammate's (from status.json) diverged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_conflict: Option<crate::change_status::StatusConflict>,
+    /// Tool call of the running implementation that waits for the user
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub implementation_approval: Option<crate::agent_runs::AgentRunApproval>,
+}
+
+impl Change {
+    /// Whether `session_id` is the proposal or plan review of this change
+    pub fn has_review_session(&self, session_id: &str) -> bool {
+        self.proposal_review_session_id.as_deref() == Some(session_id)
+            || self.plan_review_session_id.as_deref() == Some(session_id)
+    }
+}
+
+/// A single plan step tracked during implementation (CESDD Phase 5)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImplementationTask {
+    /// Step title from plan.md
+    pub title: String,
+    /// Whether Claude reported the step as done
+    pub done: bool,
 }
 
 /// Change status in CESDD workflow
@@ -1754,6 +2965,23 @@ pub struct ReviewComment {
     pub resolved: bool,
     /// Creation timestamp (ISO 8601)
     pub created_at: String,
+    /// Lines of a file the comment is about
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<CommentAnchor>,
+    /// Comment this one replies to (threads)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+}
+
+/// File lines a review comment is anchored to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommentAnchor {
+    /// File path (relative to the worktree)
+    pub file: String,
+    /// First line (1-based)
+    pub line_start: u32,
+    /// Last line (inclusive)
+    pub line_end: u32,
 }
 
 /// A review session
@@ -1774,12 +3002,144 @@ pub struct ReviewSession {
     pub comments: Vec<ReviewComment>,
     /// Current iteration (starts at 1)
     pub iteration: u32,
+    /// Constitution compliance pre-check of the current iteration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compliance: Option<ComplianceCheck>,
     /// Creation timestamp (ISO 8601)
     pub created_at: String,
     /// Last update timestamp (ISO 8601)
     pub updated_at: String,
 }
 
+impl ReviewSession {
+    /// Why the session cannot be approved yet, if it cannot
+    pub fn approval_blocker(&self) -> Option<String> {
+        self.compliance.as_ref().and_then(ComplianceCheck::approval_blocker)
+    }
+}
+
+/// Severity of a constitution violation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ViolationSeverity {
+    /// Must be fixed (or overridden) before approval
+    Blocking,
+    Warning,
+    Info,
+}
+
+/// A constitution rule the reviewed content violates
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ComplianceViolation {
+    /// Constitution section the rule belongs to (e.g. "Security")
+    pub section: String,
+    pub severity: ViolationSeverity,
+    /// The rule that is violated
+    pub rule: String,
+    /// What in the content violates it
+    pub explanation: String,
+}
+
+/// Result of evaluating review content against the constitution
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ComplianceReport {
+    pub summary: String,
+    #[serde(default)]
+    pub violations: Vec<ComplianceViolation>,
+    /// Evaluation timestamp (ISO 8601)
+    pub evaluated_at: String,
+}
+
+impl ComplianceReport {
+    /// Number of blocking violations
+    pub fn blocking_count(&self) -> usize {
+        self.violations
+            .iter()
+            .filter(|v| v.severity == ViolationSeverity::Blocking)
+            .count()
+    }
+}
+
+/// Compliance pre-check status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComplianceStatus {
+    /// Claude is evaluating the content
+    Running,
+    /// No blocking violations
+    Passed,
+    /// Blocking violations found
+    Failed,
+    /// The worktree has no constitution to check against
+    Skipped,
+    /// The evaluation could not be completed
+    Error,
+}
+
+/// Constitution compliance pre-check of a review session
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ComplianceCheck {
+    pub status: ComplianceStatus,
+    /// Session iteration that was evaluated
+    pub iteration: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub report: Option<ComplianceReport>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Reason given for approving despite blocking violations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub override_reason: Option<String>,
+}
+
+impl ComplianceCheck {
+    /// A check that is still running
+    pub fn running(iteration: u32) -> Self {
+        Self {
+            status: ComplianceStatus::Running,
+            iteration,
+            report: None,
+            error: None,
+            override_reason: None,
+        }
+    }
+
+    /// A finished check with `report`
+    pub fn completed(iteration: u32, report: ComplianceReport) -> Self {
+        let status = if report.blocking_count() > 0 {
+            ComplianceStatus::Failed
+        } else {
+            ComplianceStatus::Passed
+        };
+        Self {
+            status,
+            report: Some(report),
+            ..Self::running(iteration)
+        }
+    }
+
+    /// A check that could not run
+    pub fn failed_to_run(iteration: u32, status: ComplianceStatus, error: impl Into<String>) -> Self {
+        Self {
+            status,
+            error: Some(error.into()),
+            ..Self::running(iteration)
+        }
+    }
+
+    /// Why approval is blocked, if it is. A check that errored does not block,
+    /// so an unavailable Claude CLI cannot hold reviews hostage.
+    pub fn approval_blocker(&self) -> Option<String> {
+        match self.status {
+            ComplianceStatus::Running => Some("The constitution compliance check is still running".to_string()),
+            ComplianceStatus::Failed if self.override_reason.is_none() => Some(format!(
+                "{} blocking constitution violation(s) must be fixed or overridden before approval",
+                self.report.as_ref().map_or(0, ComplianceReport::blocking_count)
+            )),
+            _ => None,
+        }
+    }
+}
+
 /// ReviewGate state container
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct ReviewGateState {
@@ -1805,6 +3165,18 @@ pub struct FileViewerState {
     /// Binary file content (raw bytes)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub binary_content: Option<Vec<u8>>,
+    /// Preview of a binary file (thumbnail, hexdump, size/type)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary_preview: Option<crate::file_preview::BinaryPreview>,
+    /// Hash of the text content as last read or saved (sent back with WriteFile)
+    #[serde(default)]
+    pub content_hash: Option<String>,
+    /// Whether the editor has unsaved changes
+    #[serde(default)]
+    pub is_dirty: bool,
+    /// Whether a WriteFile is in progress
+    #[serde(default)]
+    pub is_saving: bool,
     pub is_loading: bool,
     pub error: Option<String>,
 }
@@ -1814,6 +3186,116 @@ pub struct A2UIState {
     pub payload: Option<serde_json::Value>,
 }
 
+// ============================================================================
+// Usage State
+// ============================================================================
+
+/// Maximum number of per-session usage entries to keep
+const MAX_USAGE_SESSIONS: usize = 100;
+
+/// Usage accumulated for one Claude CLI session
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionUsage {
+    pub session_id: String,
+    pub project_id: String,
+    pub totals: UsageTotals,
+}
+
+/// Claude CLI usage accumulated since launch (history lives in SQLite)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct UsageState {
+    /// project_id -> totals
+    #[serde(default)]
+    pub by_project: HashMap<String, UsageTotals>,
+    /// Most recent sessions (oldest first)
+    #[serde(default)]
+    pub sessions: Vec<SessionUsage>,
+}
+
+impl UsageState {
+    /// Accumulate a record into its project and session totals
+    pub fn add_record(&mut self, record: &UsageRecord) {
+        self.by_project
+            .entry(record.project_id.clone())
+            .or_default()
+            .add(record);
+
+        let Some(session_id) = &record.session_id else {
+            return;
+        };
+        if let Some(session) = self.sessions.iter_mut().find(|s| &s.session_id == session_id) {
+            session.totals.add(record);
+            return;
+        }
+        let mut totals = UsageTotals::default();
+        totals.add(record);
+        self.sessions.push(SessionUsage {
+            session_id: session_id.clone(),
+            project_id: record.project_id.clone(),
+            totals,
+        });
+        if self.sessions.len() > MAX_USAGE_SESSIONS {
+            self.sessions.remove(0);
+        }
+    }
+}
+
+// ============================================================================
+// Session History State
+// ============================================================================
+
+/// Maximum number of sessions listed in the Session History view
+pub const MAX_SESSION_HISTORY: usize = 200;
+
+/// Claude sessions of one project, newest first
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct SessionHistoryState {
+    /// Project the sessions belong to (None = not loaded)
+    #[serde(default)]
+    pub project_id: Option<String>,
+    #[serde(default)]
+    pub sessions: Vec<crate::sessions::SessionRecord>,
+    #[serde(default)]
+    pub is_loading: bool,
+}
+
+// ============================================================================
+// Activity Feed State
+// ============================================================================
+
+/// Queued, running and recently finished agent runs (see `agent_runs`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AgentRunsState {
+    /// In queue order
+    pub runs: Vec<crate::agent_runs::AgentRun>,
+    /// Number of runs that may run at once
+    pub max_concurrent: usize,
+}
+
+impl Default for AgentRunsState {
+    fn default() -> Self {
+        Self {
+            runs: Vec::new(),
+            max_concurrent: crate::agent_runs::DEFAULT_MAX_CONCURRENT,
+        }
+    }
+}
+
+/// Recorded events of one project, newest first
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ActivityFeedState {
+    /// Project the events belong to (None = not loaded)
+    #[serde(default)]
+    pub project_id: Option<String>,
+    /// Events at or after this time were loaded (ISO 8601, None = all)
+    #[serde(default)]
+    pub since: Option<String>,
+    #[serde(default)]
+    pub events: Vec<crate::activity::ActivityEvent>,
+    #[serde(default)]
+    pub is_loading: bool,
+}
+
 // ============================================================================
 // Error Type
 // ============================================================================
@@ -1873,6 +3355,7 @@ mod tests {
                 name: "test".to_string(),
                 description: Some("Run tests".to_string()),
                 recipe: "cargo test".to_string(),
+                parameters: vec![],
             });
         }
         state.projects.push(project);
@@ -1978,6 +3461,7 @@ mod tests {
             policy: ReviewPolicy::AlwaysReview,
             comments: vec![],
             iteration: 1,
+            compliance: None,
             created_at: "2025-01-01T00:00:00Z".to_string(),
             updated_at: "2025-01-01T00:00:00Z".to_string(),
         };
@@ -2022,6 +3506,8 @@ mod tests {
             author: CommentAuthor::User,
             resolved: false,
             created_at: "2025-01-01T00:00:00Z".to_string(),
+            anchor: Some(CommentAnchor { file: "src/main.rs".to_string(), line_start: 3, line_end: 5 }),
+            parent_id: None,
         };
 
         let json = serde_json::to_string(&comment).unwrap();
@@ -2094,6 +3580,14 @@ mod tests {
         assert_eq!(state, loaded);
     }
 
+    #[test]
+    fn test_port_conflict_strategy_should_auto_resolve() {
+        assert!(!PortConflictStrategy::Never.should_auto_resolve(false));
+        assert!(PortConflictStrategy::SuggestNext.should_auto_resolve(false));
+        assert!(!PortConflictStrategy::SuggestNext.should_auto_resolve(true));
+        assert!(PortConflictStrategy::AlwaysNext.should_auto_resolve(true));
+    }
+
     #[test]
     fn test_log_panel_type_serialization() {
         assert_eq!(
@@ -2120,11 +3614,13 @@ mod tests {
 
     #[test]
     fn test_app_state_with_ui_layout_roundtrip() {
-        let mut state = AppState::default();
-        state.ui_layout = UiLayoutState {
-            active_panel: Some(LogPanelType::Errors),
-            panel_width: 400,
-            panel_expanded: true,
+        let state = AppState {
+            ui_layout: UiLayoutState {
+                active_panel: Some(LogPanelType::Errors),
+                panel_width: 400,
+                panel_expanded: true,
+            },
+            ..Default::default()
         };
 
         let json = serde_json::to_string_pretty(&state).unwrap();
@@ -0,0 +1,499 @@
+//! Dependency vulnerability audit.
+//!
+//! Runs the auditor of each ecosystem found in a worktree (`cargo audit` for
+//! Cargo.lock, `npm audit` for package-lock.json, `pip-audit` for
+//! requirements.txt / pyproject.toml) and parses their JSON into advisories.
+//! The last report is kept in `<worktree>/.rstn/audit.json`, where the
+//! context engine picks up critical findings.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Report file (in `<worktree>/.rstn/`)
+pub const AUDIT_FILE: &str = "audit.json";
+
+/// Time one auditor may run (they fetch advisory databases)
+pub const AUDIT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Advisory severity, lowest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// The auditor reports no severity (pip-audit, cargo audit without CVSS)
+    Unknown,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    fn parse(value: &str) -> Self {
+        match value.to_ascii_lowercase().as_str() {
+            "low" | "info" => Self::Low,
+            "moderate" | "medium" => Self::Medium,
+            "high" => Self::High,
+            "critical" => Self::Critical,
+            _ => Self::Unknown,
+        }
+    }
+
+    fn from_score(score: f64) -> Self {
+        match score {
+            s if s >= 9.0 => Self::Critical,
+            s if s >= 7.0 => Self::High,
+            s if s >= 4.0 => Self::Medium,
+            s if s > 0.0 => Self::Low,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// Auditor for one ecosystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditTool {
+    Cargo,
+    Npm,
+    Pip,
+}
+
+impl AuditTool {
+    /// Program and arguments run in the worktree
+    pub fn command(self, worktree: &Path) -> (&'static str, Vec<&'static str>) {
+        match self {
+            Self::Cargo => ("cargo", vec!["audit", "--json"]),
+            Self::Npm => ("npm", vec!["audit", "--json"]),
+            Self::Pip if worktree.join("requirements.txt").exists() => {
+                ("pip-audit", vec!["-f", "json", "-r", "requirements.txt"])
+            }
+            Self::Pip => ("pip-audit", vec!["-f", "json", "."]),
+        }
+    }
+
+    /// How to install the auditor, for the error when it is missing
+    fn install_hint(self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo install cargo-audit",
+            Self::Npm => "install Node.js",
+            Self::Pip => "pip install pip-audit",
+        }
+    }
+
+    pub fn parse(self, output: &str) -> Result<Vec<Advisory>, String> {
+        let json: Value = serde_json::from_str(output.trim()).map_err(|e| format!("Invalid JSON: {}", e))?;
+        Ok(match self {
+            Self::Cargo => parse_cargo_audit(&json),
+            Self::Npm => parse_npm_audit(&json),
+            Self::Pip => parse_pip_audit(&json),
+        })
+    }
+}
+
+/// Auditors for the lockfiles and manifests present in a worktree
+pub fn detect_tools(worktree: &Path) -> Vec<AuditTool> {
+    let mut tools = Vec::new();
+    if worktree.join("Cargo.lock").exists() {
+        tools.push(AuditTool::Cargo);
+    }
+    if worktree.join("package-lock.json").exists() {
+        tools.push(AuditTool::Npm);
+    }
+    if worktree.join("requirements.txt").exists() || worktree.join("pyproject.toml").exists() {
+        tools.push(AuditTool::Pip);
+    }
+    tools
+}
+
+/// A known vulnerability in a dependency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Advisory {
+    /// RUSTSEC-, GHSA-, PYSEC- or CVE- identifier
+    pub id: String,
+    pub tool: AuditTool,
+    pub package: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_version: Option<String>,
+    pub severity: Severity,
+    pub title: String,
+    /// Version (or requirement) that fixes the vulnerability
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl Advisory {
+    /// One line for the AI context
+    pub fn summary(&self) -> String {
+        let version = self.installed_version.as_deref().map(|v| format!(" {}", v)).unwrap_or_default();
+        let fix = self.fix_version.as_deref().map(|v| format!(" (fixed in {})", v)).unwrap_or_default();
+        format!("{:?} vulnerability {} in {}{}: {}{}", self.severity, self.id, self.package, version, self.title, fix)
+    }
+}
+
+/// Outcome of a dependency audit of a worktree
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuditReport {
+    /// Advisories, most severe first
+    pub advisories: Vec<Advisory>,
+    /// Auditors that ran
+    pub tools: Vec<AuditTool>,
+    /// Auditors that could not run or whose output could not be parsed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+    /// ISO 8601 timestamp
+    pub ran_at: String,
+}
+
+impl AuditReport {
+    pub fn critical(&self) -> impl Iterator<Item = &Advisory> {
+        self.advisories.iter().filter(|a| a.severity == Severity::Critical)
+    }
+
+    pub fn count(&self, severity: Severity) -> usize {
+        self.advisories.iter().filter(|a| a.severity == severity).count()
+    }
+}
+
+/// Run every detected auditor in the worktree
+pub async fn run(worktree: &Path) -> AuditReport {
+    let mut report = AuditReport {
+        ran_at: chrono::Utc::now().to_rfc3339(),
+        ..Default::default()
+    };
+    let tools = detect_tools(worktree);
+    if tools.is_empty() {
+        report
+            .errors
+            .push("No Cargo.lock, package-lock.json, requirements.txt or pyproject.toml to audit".to_string());
+    }
+    for tool in tools {
+        match run_tool(tool, worktree).await {
+            Ok(advisories) => {
+                report.tools.push(tool);
+                report.advisories.extend(advisories);
+            }
+            Err(e) => report.errors.push(e),
+        }
+    }
+    report
+        .advisories
+        .sort_by(|a, b| b.severity.cmp(&a.severity).then_with(|| a.package.cmp(&b.package)));
+    report
+}
+
+async fn run_tool(tool: AuditTool, worktree: &Path) -> Result<Vec<Advisory>, String> {
+    let (program, args) = tool.command(worktree);
+    let display = format!("{} {}", program, args.join(" "));
+    let run = tokio::process::Command::new(program)
+        .args(&args)
+        .current_dir(worktree)
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(AUDIT_TIMEOUT, run)
+        .await
+        .map_err(|_| format!("`{}` exceeded {} second timeout", display, AUDIT_TIMEOUT.as_secs()))?
+        .map_err(|e| format!("Failed to run `{}` ({}): {}", display, tool.install_hint(), e))?;
+
+    // Auditors exit non-zero when they find vulnerabilities, so the output
+    // decides whether the run worked
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    tool.parse(&stdout).map_err(|e| {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or(&e);
+        format!("`{}` failed: {}", display, detail.trim())
+    })
+}
+
+fn string(value: &Value, key: &str) -> Option<String> {
+    value.get(key).and_then(Value::as_str).filter(|s| !s.is_empty()).map(str::to_string)
+}
+
+/// `cargo audit --json`: `vulnerabilities.list[]` with the advisory, the
+/// package and the patched version requirements
+fn parse_cargo_audit(json: &Value) -> Vec<Advisory> {
+    let list = json.pointer("/vulnerabilities/list").and_then(Value::as_array);
+    list.into_iter()
+        .flatten()
+        .filter_map(|vuln| {
+            let advisory = vuln.get("advisory")?;
+            let package = vuln.get("package")?;
+            let severity = advisory
+                .get("cvss")
+                .and_then(Value::as_str)
+                .and_then(cvss_base_score)
+                .map_or(Severity::Unknown, Severity::from_score);
+            let patched: Vec<&str> = vuln
+                .pointer("/versions/patched")
+                .and_then(Value::as_array)
+                .map(|v| v.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            Some(Advisory {
+                id: string(advisory, "id")?,
+                tool: AuditTool::Cargo,
+                package: string(package, "name")?,
+                installed_version: string(package, "version"),
+                severity,
+                title: string(advisory, "title").unwrap_or_default(),
+                fix_version: (!patched.is_empty()).then(|| patched.join(" or ")),
+                url: string(advisory, "url"),
+            })
+        })
+        .collect()
+}
+
+/// `npm audit --json` (report version 2): one entry per vulnerable package;
+/// advisories are the objects in `via` (strings point at other entries)
+fn parse_npm_audit(json: &Value) -> Vec<Advisory> {
+    let mut advisories: Vec<Advisory> = Vec::new();
+    let Some(vulnerabilities) = json.get("vulnerabilities").and_then(Value::as_object) else {
+        return advisories;
+    };
+    for (name, entry) in vulnerabilities {
+        let fix_version = match entry.get("fixAvailable") {
+            Some(Value::Object(fix)) if fix.get("name").and_then(Value::as_str) == Some(name) => {
+                fix.get("version").and_then(Value::as_str).map(str::to_string)
+            }
+            _ => None,
+        };
+        for via in entry.get("via").and_then(Value::as_array).into_iter().flatten() {
+            let Some(title) = string(via, "title") else {
+                continue;
+            };
+            let url = string(via, "url");
+            let id = url
+                .as_deref()
+                .and_then(|u| u.rsplit('/').next())
+                .map(str::to_string)
+                .or_else(|| via.get("source").map(|s| s.to_string()))
+                .unwrap_or_default();
+            let package = string(via, "name").unwrap_or_else(|| name.clone());
+            if advisories.iter().any(|a| a.id == id && a.package == package) {
+                continue;
+            }
+            advisories.push(Advisory {
+                id,
+                tool: AuditTool::Npm,
+                installed_version: None,
+                severity: Severity::parse(via.get("severity").and_then(Value::as_str).unwrap_or_default()),
+                title,
+                fix_version: fix_version.clone().filter(|_| package == *name),
+                url,
+                package,
+            });
+        }
+    }
+    advisories
+}
+
+/// `pip-audit -f json`: `dependencies[].vulns[]` (a bare array in older
+/// versions); no severity is reported
+fn parse_pip_audit(json: &Value) -> Vec<Advisory> {
+    let dependencies = json.get("dependencies").unwrap_or(json).as_array();
+    dependencies
+        .into_iter()
+        .flatten()
+        .flat_map(|dependency| {
+            let vulns = dependency.get("vulns").and_then(Value::as_array).cloned().unwrap_or_default();
+            vulns.into_iter().filter_map(move |vuln| {
+                let fixes: Vec<&str> = vuln
+                    .get("fix_versions")
+                    .and_then(Value::as_array)
+                    .map(|v| v.iter().filter_map(Value::as_str).collect())
+                    .unwrap_or_default();
+                let title = string(&vuln, "description")
+                    .and_then(|d| d.lines().next().map(|l| l.chars().take(160).collect()))
+                    .unwrap_or_default();
+                Some(Advisory {
+                    id: string(&vuln, "id")?,
+                    tool: AuditTool::Pip,
+                    package: string(dependency, "name")?,
+                    installed_version: string(dependency, "version"),
+                    severity: Severity::Unknown,
+                    title,
+                    fix_version: fixes.first().map(|v| v.to_string()),
+                    url: None,
+                })
+            })
+        })
+        .collect()
+}
+
+/// CVSS v3 base score of a vector like `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H`
+fn cvss_base_score(vector: &str) -> Option<f64> {
+    let metric = |name: &str| {
+        vector
+            .split('/')
+            .find_map(|part| part.strip_prefix(name).and_then(|v| v.strip_prefix(':')))
+    };
+    let changed = match metric("S")? {
+        "U" => false,
+        "C" => true,
+        _ => return None,
+    };
+    let av = match metric("AV")? {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        "P" => 0.2,
+        _ => return None,
+    };
+    let ac = match metric("AC")? {
+        "L" => 0.77,
+        "H" => 0.44,
+        _ => return None,
+    };
+    let pr = match (metric("PR")?, changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        ("H", false) => 0.27,
+        ("H", true) => 0.5,
+        _ => return None,
+    };
+    let ui = match metric("UI")? {
+        "N" => 0.85,
+        "R" => 0.62,
+        _ => return None,
+    };
+    let impact = |name: &str| match metric(name) {
+        Some("H") => Some(0.56),
+        Some("L") => Some(0.22),
+        Some("N") => Some(0.0),
+        _ => None,
+    };
+    let iss = 1.0 - (1.0 - impact("C")?) * (1.0 - impact("I")?) * (1.0 - impact("A")?);
+    let impact = if changed {
+        7.52 * (iss - 0.029) - 3.25 * (iss - 0.02f64).powi(15)
+    } else {
+        6.42 * iss
+    };
+    if impact <= 0.0 {
+        return Some(0.0);
+    }
+    let exploitability = 8.22 * av * ac * pr * ui;
+    let score = if changed { 1.08 * (impact + exploitability) } else { impact + exploitability };
+    // Round up to one decimal (the small offset absorbs float error)
+    Some(((score.min(10.0) * 10.0) - 1e-9).ceil() / 10.0)
+}
+
+pub fn report_path(worktree: &Path) -> PathBuf {
+    worktree.join(".rstn").join(AUDIT_FILE)
+}
+
+/// Last audit report of a worktree
+pub fn load(worktree: &Path) -> Option<AuditReport> {
+    std::fs::read_to_string(report_path(worktree))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+}
+
+pub fn save(worktree: &Path, report: &AuditReport) -> Result<(), String> {
+    let path = report_path(worktree);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(report).map_err(|e| format!("Failed to serialize audit report: {}", e))?;
+    crate::spec_kit::write_atomic(&path, &json)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cargo_audit() {
+        let output = r#"{
+  "database": {"advisory-count": 600},
+  "vulnerabilities": {"found": true, "count": 2, "list": [
+    {"advisory": {"id": "RUSTSEC-2024-0001", "package": "hyper", "title": "Request smuggling",
+      "url": "https://rustsec.org/advisories/RUSTSEC-2024-0001",
+      "cvss": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"},
+     "versions": {"patched": [">=0.14.10"], "unaffected": []},
+     "package": {"name": "hyper", "version": "0.14.2"}},
+    {"advisory": {"id": "RUSTSEC-2024-0002", "package": "time", "title": "Segfault", "cvss": null},
+     "versions": {"patched": [], "unaffected": []},
+     "package": {"name": "time", "version": "0.1.0"}}
+  ]},
+  "warnings": {}
+}"#;
+        let advisories = AuditTool::Cargo.parse(output).unwrap();
+        assert_eq!(advisories.len(), 2);
+        assert_eq!(advisories[0].severity, Severity::Critical);
+        assert_eq!(advisories[0].installed_version.as_deref(), Some("0.14.2"));
+        assert_eq!(advisories[0].fix_version.as_deref(), Some(">=0.14.10"));
+        assert_eq!(
+            advisories[0].summary(),
+            "Critical vulnerability RUSTSEC-2024-0001 in hyper 0.14.2: Request smuggling (fixed in >=0.14.10)"
+        );
+        assert_eq!(advisories[1].severity, Severity::Unknown);
+        assert_eq!(advisories[1].fix_version, None);
+        assert!(AuditTool::Cargo.parse("error: not a lockfile").is_err());
+    }
+
+    #[test]
+    fn test_parse_npm_and_pip_audit() {
+        let output = r#"{
+  "auditReportVersion": 2,
+  "vulnerabilities": {
+    "lodash": {"name": "lodash", "severity": "high", "isDirect": false,
+      "via": [{"source": 1094, "name": "lodash", "dependency": "lodash", "title": "Prototype Pollution",
+               "url": "https://github.com/advisories/GHSA-p6mc-m468-83gw", "severity": "high", "range": "<4.17.19"}],
+      "fixAvailable": {"name": "lodash", "version": "4.17.21", "isSemVerMajor": false}},
+    "webpack": {"name": "webpack", "severity": "high", "via": ["lodash"], "fixAvailable": true}
+  },
+  "metadata": {}
+}"#;
+        let advisories = AuditTool::Npm.parse(output).unwrap();
+        assert_eq!(advisories.len(), 1);
+        assert_eq!(advisories[0].id, "GHSA-p6mc-m468-83gw");
+        assert_eq!(advisories[0].severity, Severity::High);
+        assert_eq!(advisories[0].fix_version.as_deref(), Some("4.17.21"));
+
+        let output = r#"{"dependencies": [
+  {"name": "django", "version": "3.2.0", "vulns": [
+    {"id": "PYSEC-2021-98", "fix_versions": ["3.2.2", "3.1.10"], "aliases": ["CVE-2021-31542"],
+     "description": "Path traversal in MultiPartParser.\nMore detail."}]},
+  {"name": "requests", "version": "2.31.0", "vulns": []}
+], "fixes": []}"#;
+        let advisories = AuditTool::Pip.parse(output).unwrap();
+        assert_eq!(advisories.len(), 1);
+        assert_eq!(advisories[0].title, "Path traversal in MultiPartParser.");
+        assert_eq!(advisories[0].fix_version.as_deref(), Some("3.2.2"));
+        assert_eq!(advisories[0].installed_version.as_deref(), Some("3.2.0"));
+    }
+
+    #[test]
+    fn test_cvss_base_score() {
+        assert_eq!(cvss_base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"), Some(9.8));
+        assert_eq!(cvss_base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N"), Some(6.1));
+        assert_eq!(cvss_base_score("CVSS:3.0/AV:L/AC:H/PR:H/UI:R/S:U/C:N/I:N/A:N"), Some(0.0));
+        assert_eq!(cvss_base_score("CVSS:3.1/AV:N"), None);
+    }
+
+    #[test]
+    fn test_detect_tools_and_report_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(detect_tools(dir.path()).is_empty());
+        std::fs::write(dir.path().join("Cargo.lock"), "").unwrap();
+        std::fs::write(dir.path().join("pyproject.toml"), "").unwrap();
+        assert_eq!(detect_tools(dir.path()), vec![AuditTool::Cargo, AuditTool::Pip]);
+        assert_eq!(AuditTool::Pip.command(dir.path()).1, vec!["-f", "json", "."]);
+
+        assert!(load(dir.path()).is_none());
+        let report = AuditReport {
+            tools: vec![AuditTool::Cargo],
+            ran_at: "now".to_string(),
+            ..Default::default()
+        };
+        save(dir.path(), &report).unwrap();
+        assert_eq!(load(dir.path()), Some(report));
+    }
+}
@@ -0,0 +1,241 @@
+//! `rstn-core`: rstn workflows without the desktop app (feature `cli`).
+//!
+//! Commands dispatch the actions the app dispatches, through the same
+//! reducer and async handlers, so CI jobs and scripts get the GUI's
+//! behavior:
+//!
+//! ```text
+//! rstn-core run-change --intent "Add rate limiting" [--until proposal|plan|implementation] [--branch]
+//! rstn-core docker up --group dev
+//! rstn-core docker down --group dev
+//! rstn-core context build
+//! ```
+//!
+//! Every command works on `--project` (default: the current directory).
+//! Progress goes to stderr, results to stdout; a failed step exits with 1.
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use rstn_core::actions::Action;
+use rstn_core::app_state::{AppState, Change, ChangeStatus, WorktreeState};
+
+#[derive(Parser)]
+#[command(name = "rstn-core", version, about = "Run rstn workflows without the desktop app")]
+struct Cli {
+    /// Project directory
+    #[arg(long, global = true, default_value = ".")]
+    project: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a change and take it through proposal, plan and implementation
+    RunChange {
+        /// What the change should do
+        #[arg(long)]
+        intent: String,
+        /// Last step to run
+        #[arg(long, value_enum, default_value_t = Step::Implementation)]
+        until: Step,
+        /// Work on the change in its own branch and worktree
+        #[arg(long)]
+        branch: bool,
+    },
+    /// Docker service groups
+    Docker {
+        #[command(subcommand)]
+        command: DockerCommand,
+    },
+    /// Project context (.rstn/context/)
+    Context {
+        #[command(subcommand)]
+        command: ContextCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum DockerCommand {
+    /// Start a group's services in dependency order and wait until they are healthy
+    Up {
+        #[arg(long)]
+        group: String,
+    },
+    /// Stop a group's services
+    Down {
+        #[arg(long)]
+        group: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ContextCommand {
+    /// Generate the context files from the codebase with Claude
+    Build,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Step {
+    Proposal,
+    Plan,
+    Implementation,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    rstn_core::headless_init();
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), String> {
+    open_project(&cli.project).await?;
+    match cli.command {
+        Command::RunChange { intent, until, branch } => run_change(intent, until, branch).await,
+        Command::Docker { command: DockerCommand::Up { group } } => service_group(group, true).await,
+        Command::Docker { command: DockerCommand::Down { group } } => service_group(group, false).await,
+        Command::Context { command: ContextCommand::Build } => build_context().await,
+    }
+}
+
+/// Dispatch an action; an error the handlers put into the state fails it
+async fn dispatch(action: Action) -> Result<AppState, String> {
+    rstn_core::headless_dispatch(Action::ClearError).await?;
+    rstn_core::headless_dispatch(action).await?;
+    let state = rstn_core::headless_state().await;
+    match &state.error {
+        Some(error) => Err(error.message.clone()),
+        None => Ok(state),
+    }
+}
+
+fn active_worktree(state: &AppState) -> Result<&WorktreeState, String> {
+    state
+        .active_project()
+        .and_then(|p| p.active_worktree())
+        .ok_or_else(|| "No project is open".to_string())
+}
+
+async fn open_project(path: &Path) -> Result<(), String> {
+    let path = path
+        .canonicalize()
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    let state = dispatch(Action::OpenProject {
+        path: path.to_string_lossy().into_owned(),
+    })
+    .await?;
+    active_worktree(&state).map(|_| ())
+}
+
+async fn run_change(intent: String, until: Step, create_branch: bool) -> Result<(), String> {
+    let existing: Vec<String> = active_worktree(&rstn_core::headless_state().await)?
+        .changes
+        .changes
+        .iter()
+        .map(|c| c.id.clone())
+        .collect();
+    let state = dispatch(Action::CreateChange { intent, create_branch }).await?;
+    let change = active_worktree(&state)?
+        .changes
+        .changes
+        .iter()
+        .find(|c| !existing.contains(&c.id))
+        .cloned()
+        .ok_or_else(|| "The change was not created".to_string())?;
+    let change_id = change.id;
+    eprintln!("Created change {}", change.name);
+
+    eprintln!("Generating proposal...");
+    let change = change_step(Action::GenerateProposal { change_id: change_id.clone() }, &change_id).await?;
+    if change.proposal.is_none() {
+        return Err(step_failed("Proposal", &change));
+    }
+    if until == Step::Proposal {
+        return report(&change).await;
+    }
+
+    eprintln!("Generating plan...");
+    let change = change_step(Action::GeneratePlan { change_id: change_id.clone() }, &change_id).await?;
+    if change.plan.is_none() || change.status != ChangeStatus::Planned {
+        return Err(step_failed("Plan", &change));
+    }
+    change_step(Action::ApprovePlan { change_id: change_id.clone() }, &change_id).await?;
+    if until == Step::Plan {
+        return report(&change).await;
+    }
+
+    eprintln!("Implementing...");
+    let change = change_step(Action::ExecutePlan { change_id: change_id.clone() }, &change_id).await?;
+    if change.status != ChangeStatus::Done {
+        return Err(step_failed("Implementation", &change));
+    }
+    report(&change).await
+}
+
+/// Dispatch a change action and return the change afterwards
+async fn change_step(action: Action, change_id: &str) -> Result<Change, String> {
+    let state = dispatch(action).await?;
+    active_worktree(&state)?
+        .changes
+        .changes
+        .iter()
+        .find(|c| c.id == change_id)
+        .cloned()
+        .ok_or_else(|| format!("Change {} disappeared", change_id))
+}
+
+fn step_failed(step: &str, change: &Change) -> String {
+    let output = change.streaming_output.trim();
+    if output.is_empty() {
+        format!("{} of {} failed (status {:?})", step, change.name, change.status)
+    } else {
+        format!("{} of {} failed (status {:?}):\n{}", step, change.name, change.status, output)
+    }
+}
+
+/// Print the change's status and directory
+async fn report(change: &Change) -> Result<(), String> {
+    let state = rstn_core::headless_state().await;
+    let dir = Path::new(&active_worktree(&state)?.path)
+        .join(".rstn")
+        .join("changes")
+        .join(&change.name);
+    println!("{}: {:?}", change.name, change.status);
+    println!("{}", dir.display());
+    Ok(())
+}
+
+async fn service_group(name: String, start: bool) -> Result<(), String> {
+    dispatch(Action::RefreshDockerServices).await?;
+    eprintln!("{} service group {}...", if start { "Starting" } else { "Stopping" }, name);
+    let action = if start {
+        Action::StartServiceGroup { name: name.clone() }
+    } else {
+        Action::StopServiceGroup { name: name.clone() }
+    };
+    dispatch(action).await?;
+    println!("{}: {}", name, if start { "up" } else { "down" });
+    Ok(())
+}
+
+async fn build_context() -> Result<(), String> {
+    eprintln!("Generating context...");
+    let state = dispatch(Action::GenerateContext).await?;
+    let context = &active_worktree(&state)?.context;
+    if let Some(error) = &context.generation_error {
+        return Err(error.clone());
+    }
+    for file in &context.files {
+        println!("{}", file.path);
+    }
+    Ok(())
+}
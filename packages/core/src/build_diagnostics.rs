@@ -0,0 +1,320 @@
+//! Compiler diagnostics of a worktree.
+//!
+//! Runs `cargo check --message-format=json` (Cargo.toml) or
+//! `npx tsc --noEmit` (tsconfig.json) and parses the errors and warnings into
+//! per-file entries. The last result is kept in
+//! `<worktree>/.rstn/build-diagnostics.json`, where the context engine picks
+//! up current errors.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Result file (in `<worktree>/.rstn/`)
+pub const BUILD_DIAGNOSTICS_FILE: &str = "build-diagnostics.json";
+
+/// Time a check may run
+pub const BUILD_CHECK_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Diagnostics kept per check (the first ones matter most)
+const MAX_DIAGNOSTICS: usize = 500;
+
+/// Errors included in the AI context
+const MAX_CONTEXT_ERRORS: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildChecker {
+    Cargo,
+    Tsc,
+}
+
+impl BuildChecker {
+    /// Checker for the worktree's project, Cargo first
+    pub fn detect(worktree: &Path) -> Option<Self> {
+        if worktree.join("Cargo.toml").exists() {
+            Some(Self::Cargo)
+        } else if worktree.join("tsconfig.json").exists() {
+            Some(Self::Tsc)
+        } else {
+            None
+        }
+    }
+
+    /// Program and arguments run in the worktree
+    pub fn command(self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            Self::Cargo => ("cargo", &["check", "--message-format=json"]),
+            Self::Tsc => ("npx", &["tsc", "--noEmit", "--pretty", "false"]),
+        }
+    }
+
+    pub fn parse(self, output: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = match self {
+            Self::Cargo => parse_cargo(output),
+            Self::Tsc => parse_tsc(output),
+        };
+        // Cargo repeats a message for every target that compiles the file
+        let mut seen = std::collections::HashSet::new();
+        diagnostics.retain(|d| seen.insert((d.file.clone(), d.line, d.column, d.message.clone())));
+        diagnostics.truncate(MAX_DIAGNOSTICS);
+        diagnostics
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+/// A compiler error or warning
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Path relative to the worktree
+    pub file: String,
+    /// 1-based
+    pub line: u32,
+    pub column: u32,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    /// Compiler code (E0308, TS2322, unused_variables)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+
+impl Diagnostic {
+    /// `file:line:column: error[code]: message`
+    pub fn summary(&self) -> String {
+        let severity = match self.severity {
+            DiagnosticSeverity::Error => "error",
+            DiagnosticSeverity::Warning => "warning",
+        };
+        let code = self.code.as_deref().map(|c| format!("[{}]", c)).unwrap_or_default();
+        format!("{}:{}:{}: {}{}: {}", self.file, self.line, self.column, severity, code, self.message)
+    }
+}
+
+/// Outcome of the last check of a worktree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildDiagnostics {
+    pub checker: Option<BuildChecker>,
+    /// Errors first, then warnings, each in compiler order
+    pub diagnostics: Vec<Diagnostic>,
+    pub error_count: u32,
+    pub warning_count: u32,
+    pub duration_ms: u64,
+    /// ISO 8601 timestamp
+    pub checked_at: String,
+    /// Why the check could not run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BuildDiagnostics {
+    /// Result of a completed check (sorts errors first and counts them)
+    pub fn new(checker: Option<BuildChecker>, mut diagnostics: Vec<Diagnostic>, duration: Duration) -> Self {
+        diagnostics.sort_by_key(|d| d.severity != DiagnosticSeverity::Error);
+        let count = |severity| diagnostics.iter().filter(|d| d.severity == severity).count() as u32;
+        Self {
+            checker,
+            error_count: count(DiagnosticSeverity::Error),
+            warning_count: count(DiagnosticSeverity::Warning),
+            diagnostics,
+            duration_ms: duration.as_millis() as u64,
+            checked_at: chrono::Utc::now().to_rfc3339(),
+            error: None,
+        }
+    }
+
+    fn failed(checker: Option<BuildChecker>, error: String) -> Self {
+        Self {
+            error: Some(error),
+            ..Self::new(checker, Vec::new(), Duration::ZERO)
+        }
+    }
+
+    /// Errors for the AI context
+    pub fn context_errors(&self) -> Vec<String> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == DiagnosticSeverity::Error)
+            .take(MAX_CONTEXT_ERRORS)
+            .map(Diagnostic::summary)
+            .collect()
+    }
+}
+
+/// Check the worktree with its project's compiler
+pub async fn run(worktree: &Path) -> BuildDiagnostics {
+    let Some(checker) = BuildChecker::detect(worktree) else {
+        return BuildDiagnostics::failed(None, "No Cargo.toml or tsconfig.json to check".to_string());
+    };
+    let (program, args) = checker.command();
+    let display = format!("{} {}", program, args.join(" "));
+    let started = Instant::now();
+    let run = tokio::process::Command::new(program)
+        .args(args)
+        .current_dir(worktree)
+        .kill_on_drop(true)
+        .output();
+    let output = match tokio::time::timeout(BUILD_CHECK_TIMEOUT, run).await {
+        Err(_) => {
+            let error = format!("`{}` exceeded {} second timeout", display, BUILD_CHECK_TIMEOUT.as_secs());
+            return BuildDiagnostics::failed(Some(checker), error);
+        }
+        Ok(Err(e)) => return BuildDiagnostics::failed(Some(checker), format!("Failed to run `{}`: {}", display, e)),
+        Ok(Ok(output)) => output,
+    };
+
+    let diagnostics = checker.parse(&String::from_utf8_lossy(&output.stdout));
+    let mut result = BuildDiagnostics::new(Some(checker), diagnostics, started.elapsed());
+    // A failed run without diagnostics did not get to compile (bad manifest,
+    // missing tsc...)
+    if !output.status.success() && result.error_count == 0 {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("no output");
+        result.error = Some(format!("`{}` failed: {}", display, detail.trim()));
+    }
+    result
+}
+
+/// `cargo check --message-format=json`: one JSON object per line; compiler
+/// messages carry a level and the primary span
+fn parse_cargo(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|json| json.get("reason").and_then(Value::as_str) == Some("compiler-message"))
+        .filter_map(|json| {
+            let message = json.get("message")?;
+            let severity = match message.get("level").and_then(Value::as_str)? {
+                "error" | "error: internal compiler error" => DiagnosticSeverity::Error,
+                "warning" => DiagnosticSeverity::Warning,
+                _ => return None,
+            };
+            // Summaries like "aborting due to 2 previous errors" have no span
+            let span = message
+                .get("spans")
+                .and_then(Value::as_array)?
+                .iter()
+                .find(|s| s.get("is_primary").and_then(Value::as_bool) == Some(true))?;
+            let number = |key: &str| span.get(key).and_then(Value::as_u64).unwrap_or(0) as u32;
+            Some(Diagnostic {
+                file: span.get("file_name").and_then(Value::as_str)?.to_string(),
+                line: number("line_start"),
+                column: number("column_start"),
+                severity,
+                message: message.get("message").and_then(Value::as_str)?.to_string(),
+                code: message.pointer("/code/code").and_then(Value::as_str).map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// `tsc --pretty false`: `path(line,col): error TS2322: message`
+fn parse_tsc(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (location, rest) = line.split_once("): ")?;
+            let (file, position) = location.rsplit_once('(')?;
+            let (line_number, column) = position.split_once(',')?;
+            let (severity, rest) = if let Some(rest) = rest.strip_prefix("error ") {
+                (DiagnosticSeverity::Error, rest)
+            } else {
+                (DiagnosticSeverity::Warning, rest.strip_prefix("warning ")?)
+            };
+            let (code, message) = rest.split_once(": ")?;
+            Some(Diagnostic {
+                file: file.trim().to_string(),
+                line: line_number.parse().ok()?,
+                column: column.parse().ok()?,
+                severity,
+                message: message.trim().to_string(),
+                code: Some(code.to_string()),
+            })
+        })
+        .collect()
+}
+
+pub fn result_path(worktree: &Path) -> PathBuf {
+    worktree.join(".rstn").join(BUILD_DIAGNOSTICS_FILE)
+}
+
+/// Last check of a worktree
+pub fn load(worktree: &Path) -> Option<BuildDiagnostics> {
+    std::fs::read_to_string(result_path(worktree))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+}
+
+pub fn save(worktree: &Path, diagnostics: &BuildDiagnostics) -> Result<(), String> {
+    let path = result_path(worktree);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(diagnostics)
+        .map_err(|e| format!("Failed to serialize build diagnostics: {}", e))?;
+    crate::spec_kit::write_atomic(&path, &json)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cargo_messages() {
+        let warning = r#"{"reason":"compiler-message","package_id":"app 0.1.0","message":{"rendered":"warning: unused variable","$message_type":"diagnostic","children":[],"code":{"code":"unused_variables","explanation":null},"level":"warning","message":"unused variable: `x`","spans":[{"file_name":"src/lib.rs","is_primary":true,"line_start":3,"line_end":3,"column_start":9,"column_end":10,"label":null}]}}"#;
+        let output = [
+            r#"{"reason":"compiler-artifact","package_id":"serde 1.0.0"}"#,
+            warning,
+            r#"{"reason":"compiler-message","package_id":"app 0.1.0","message":{"code":{"code":"E0308"},"level":"error","message":"mismatched types","spans":[{"file_name":"src/main.rs","is_primary":false,"line_start":1,"column_start":1},{"file_name":"src/main.rs","is_primary":true,"line_start":2,"column_start":18}]}}"#,
+            warning,
+            r#"{"reason":"compiler-message","package_id":"app 0.1.0","message":{"code":null,"level":"error","message":"aborting due to 1 previous error","spans":[]}}"#,
+            r#"{"reason":"build-finished","success":false}"#,
+        ]
+        .join("\n");
+
+        let diagnostics = BuildChecker::Cargo.parse(&output);
+        assert_eq!(diagnostics.len(), 2);
+        let result = BuildDiagnostics::new(Some(BuildChecker::Cargo), diagnostics, Duration::from_millis(5));
+        assert_eq!((result.error_count, result.warning_count), (1, 1));
+        assert_eq!(result.diagnostics[0].summary(), "src/main.rs:2:18: error[E0308]: mismatched types");
+        assert_eq!(result.diagnostics[1].code.as_deref(), Some("unused_variables"));
+        assert_eq!(result.context_errors(), vec!["src/main.rs:2:18: error[E0308]: mismatched types"]);
+    }
+
+    #[test]
+    fn test_parse_tsc_output() {
+        let output = "src/app.ts(12,5): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                      src/util (old).ts(3,1): error TS6133: 'x' is declared but its value is never read.\n\
+                      Found 2 errors in 2 files.\n";
+        let diagnostics = BuildChecker::Tsc.parse(output);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].summary(),
+            "src/app.ts:12:5: error[TS2322]: Type 'string' is not assignable to type 'number'."
+        );
+        assert_eq!(diagnostics[1].file, "src/util (old).ts");
+    }
+
+    #[test]
+    fn test_detect_and_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(BuildChecker::detect(dir.path()), None);
+        std::fs::write(dir.path().join("tsconfig.json"), "{}").unwrap();
+        assert_eq!(BuildChecker::detect(dir.path()), Some(BuildChecker::Tsc));
+
+        assert!(load(dir.path()).is_none());
+        let result = BuildDiagnostics::failed(Some(BuildChecker::Tsc), "npx not found".to_string());
+        save(dir.path(), &result).unwrap();
+        assert_eq!(load(dir.path()), Some(result));
+    }
+}
@@ -0,0 +1,292 @@
+//! Change bundles for reviewers who don't run rstn.
+//!
+//! `export` zips everything a reviewer needs to judge a change:
+//!
+//! ```text
+//! manifest.json        change name, export time, bundled files
+//! intent.md            \
+//! proposal.md           } whichever exist
+//! plan.md              /
+//! implementation.diff  snapshot → working tree (if implemented)
+//! test-results.json    last test run (if any)
+//! review.json          { "change": "<name>", "comments": [...] }
+//! ```
+//!
+//! Reviewers add comments to `review.json` (only `content` is required) and
+//! send back the bundle or the file alone. `import_feedback` merges new
+//! comments and resolutions into `.rstn/changes/<name>/review.json`.
+
+use std::io::{Read, Write};
+use std::path::Path;
+
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+
+use crate::app_state::{CommentAnchor, CommentAuthor, CommentTarget, ReviewComment};
+
+/// Review comments file inside a bundle
+pub const FEEDBACK_FILE: &str = "review.json";
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Change documents copied as-is
+const DOCUMENTS: [&str; 3] = ["intent.md", "proposal.md", "plan.md"];
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    change: String,
+    exported_at: String,
+    files: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FeedbackFile {
+    change: String,
+    #[serde(default)]
+    comments: Vec<FeedbackComment>,
+}
+
+/// A comment as written by a reviewer: everything but `content` may be left
+/// out of new comments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FeedbackComment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    target: Option<CommentTarget>,
+    content: String,
+    #[serde(default)]
+    author: CommentAuthor,
+    #[serde(default)]
+    resolved: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    anchor: Option<CommentAnchor>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parent_id: Option<String>,
+}
+
+impl From<&ReviewComment> for FeedbackComment {
+    fn from(comment: &ReviewComment) -> Self {
+        Self {
+            id: Some(comment.id.clone()),
+            target: Some(comment.target.clone()),
+            content: comment.content.clone(),
+            author: comment.author,
+            resolved: comment.resolved,
+            created_at: Some(comment.created_at.clone()),
+            anchor: comment.anchor.clone(),
+            parent_id: comment.parent_id.clone(),
+        }
+    }
+}
+
+/// Outcome of merging a reviewer's comments
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedbackImport {
+    pub change_name: String,
+    /// New comments
+    pub added: u32,
+    /// Existing comments edited or resolved by the reviewer
+    pub updated: u32,
+}
+
+/// Write the bundle of `change_name` to `path`; returns the bundled files
+pub fn export(worktree: &Path, change_name: &str, path: &Path) -> Result<Vec<String>, String> {
+    let change_dir = worktree.join(".rstn").join("changes").join(change_name);
+    if !change_dir.is_dir() {
+        return Err(format!("Change not found: {}", change_name));
+    }
+
+    let mut entries: Vec<(String, String)> = Vec::new();
+    for name in DOCUMENTS {
+        if let Ok(content) = std::fs::read_to_string(change_dir.join(name)) {
+            entries.push((name.to_string(), content));
+        }
+    }
+    if let Some(snapshot) = crate::snapshot::load(worktree, change_name) {
+        let diff = crate::snapshot::diff(worktree, &snapshot)?;
+        entries.push(("implementation.diff".to_string(), format!("{}\n", diff)));
+    }
+    if let Some(results) = crate::test_runner::load(worktree, change_name) {
+        let json = serde_json::to_string_pretty(&results).map_err(|e| format!("Failed to serialize test results: {}", e))?;
+        entries.push(("test-results.json".to_string(), json));
+    }
+    let feedback = FeedbackFile {
+        change: change_name.to_string(),
+        comments: crate::review_comments::load(worktree, change_name)?.iter().map(FeedbackComment::from).collect(),
+    };
+    let json = serde_json::to_string_pretty(&feedback).map_err(|e| format!("Failed to serialize review comments: {}", e))?;
+    entries.push((FEEDBACK_FILE.to_string(), json));
+
+    let manifest = Manifest {
+        change: change_name.to_string(),
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        files: entries.iter().map(|(name, _)| name.clone()).collect(),
+    };
+    let json = serde_json::to_string_pretty(&manifest).map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+    entries.insert(0, (MANIFEST_FILE.to_string(), json));
+
+    let write_error = |e: &dyn std::fmt::Display| format!("Failed to write {}: {}", path.display(), e);
+    let file = std::fs::File::create(path).map_err(|e| write_error(&e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, content) in &entries {
+        zip.start_file(name.as_str(), options).map_err(|e| write_error(&e))?;
+        zip.write_all(content.as_bytes()).map_err(|e| write_error(&e))?;
+    }
+    zip.finish().map_err(|e| write_error(&e))?;
+
+    Ok(manifest.files)
+}
+
+/// Merge the comments of a returned bundle (or its `review.json` alone) into
+/// the change they belong to
+pub fn import_feedback(worktree: &Path, path: &Path) -> Result<FeedbackImport, String> {
+    let feedback = read_feedback(path)?;
+    let change_dir = worktree.join(".rstn").join("changes").join(&feedback.change);
+    if feedback.change.is_empty() || feedback.change.contains(['/', '\\']) || !change_dir.is_dir() {
+        return Err(format!("The feedback is for a change that doesn't exist here: {}", feedback.change));
+    }
+
+    let existing = crate::review_comments::load(worktree, &feedback.change)?;
+    let (changed, added) = merge(&existing, feedback.comments);
+    for comment in &changed {
+        crate::review_comments::save_comment(worktree, &feedback.change, comment)?;
+    }
+    Ok(FeedbackImport {
+        change_name: feedback.change,
+        added: added as u32,
+        updated: (changed.len() - added) as u32,
+    })
+}
+
+fn read_feedback(path: &Path) -> Result<FeedbackFile, String> {
+    let read_error = |e: &dyn std::fmt::Display| format!("Failed to read {}: {}", path.display(), e);
+    let json = if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+        std::fs::read_to_string(path).map_err(|e| read_error(&e))?
+    } else {
+        let file = std::fs::File::open(path).map_err(|e| read_error(&e))?;
+        let mut archive = zip::ZipArchive::new(file).map_err(|e| read_error(&e))?;
+        let mut entry = archive
+            .by_name(FEEDBACK_FILE)
+            .map_err(|_| format!("{} has no {}", path.display(), FEEDBACK_FILE))?;
+        let mut json = String::new();
+        entry.read_to_string(&mut json).map_err(|e| read_error(&e))?;
+        json
+    };
+    serde_json::from_str(&json).map_err(|e| format!("Invalid {} in {}: {}", FEEDBACK_FILE, path.display(), e))
+}
+
+/// Comments to save (new ones last) and how many of them are new. Known
+/// comments only count when their content changed or they were resolved;
+/// blank comments are dropped.
+fn merge(existing: &[ReviewComment], incoming: Vec<FeedbackComment>) -> (Vec<ReviewComment>, usize) {
+    let mut updated = Vec::new();
+    let mut added = Vec::new();
+    for comment in incoming {
+        if comment.content.trim().is_empty() {
+            continue;
+        }
+        let known = comment.id.as_ref().and_then(|id| existing.iter().find(|c| &c.id == id));
+        match known {
+            Some(known) => {
+                // An older bundle doesn't reopen comments resolved since
+                let resolved = known.resolved || comment.resolved;
+                if known.content != comment.content || known.resolved != resolved {
+                    updated.push(ReviewComment {
+                        content: comment.content,
+                        resolved,
+                        ..known.clone()
+                    });
+                }
+            }
+            None => added.push(ReviewComment {
+                id: comment.id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+                target: comment.target.unwrap_or(CommentTarget::Document),
+                content: comment.content,
+                author: comment.author,
+                resolved: comment.resolved,
+                created_at: comment.created_at.unwrap_or_else(|| chrono::Utc::now().to_rfc3339()),
+                anchor: comment.anchor,
+                parent_id: comment.parent_id,
+            }),
+        }
+    }
+    let added_count = added.len();
+    updated.extend(added);
+    (updated, added_count)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(id: &str, content: &str) -> ReviewComment {
+        ReviewComment {
+            id: id.to_string(),
+            target: CommentTarget::Document,
+            content: content.to_string(),
+            author: CommentAuthor::User,
+            resolved: false,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            anchor: None,
+            parent_id: None,
+        }
+    }
+
+    #[test]
+    fn test_export_and_import_feedback() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let change_dir = root.join(".rstn/changes/add-auth");
+        std::fs::create_dir_all(&change_dir).unwrap();
+        std::fs::write(change_dir.join("intent.md"), "Add auth").unwrap();
+        std::fs::write(change_dir.join("plan.md"), "# Plan").unwrap();
+        crate::review_comments::save_comment(root, "add-auth", &comment("c1", "Use argon2")).unwrap();
+
+        let bundle = root.join("add-auth.zip");
+        let files = export(root, "add-auth", &bundle).unwrap();
+        assert_eq!(files, vec!["intent.md", "plan.md", FEEDBACK_FILE]);
+
+        // The reviewer resolves c1 and adds a reply and a new comment
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&bundle).unwrap()).unwrap();
+        let mut json = String::new();
+        archive.by_name(FEEDBACK_FILE).unwrap().read_to_string(&mut json).unwrap();
+        let mut feedback: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(feedback["change"], "add-auth");
+        feedback["comments"][0]["resolved"] = true.into();
+        let comments = feedback["comments"].as_array_mut().unwrap();
+        comments.push(serde_json::json!({ "content": "Agreed", "parent_id": "c1" }));
+        comments.push(serde_json::json!({ "content": "  " }));
+        let returned = root.join("review.json");
+        std::fs::write(&returned, feedback.to_string()).unwrap();
+
+        let import = import_feedback(root, &returned).unwrap();
+        assert_eq!(
+            import,
+            FeedbackImport {
+                change_name: "add-auth".to_string(),
+                added: 1,
+                updated: 1,
+            }
+        );
+        let saved = crate::review_comments::load(root, "add-auth").unwrap();
+        assert_eq!(saved.len(), 2);
+        assert!(saved[0].resolved);
+        assert_eq!(saved[1].parent_id.as_deref(), Some("c1"));
+        assert_eq!(saved[1].target, CommentTarget::Document);
+
+        // The original bundle neither duplicates nor reopens anything
+        let import = import_feedback(root, &bundle).unwrap();
+        assert_eq!((import.added, import.updated), (0, 0));
+        std::fs::write(&returned, r#"{ "change": "../elsewhere", "comments": [] }"#).unwrap();
+        assert!(import_feedback(root, &returned).is_err());
+    }
+}
@@ -0,0 +1,381 @@
+//! Change status shared through git.
+//!
+//! The settled status of a change (proposed, planned, done, failed,
+//! cancelled, archived) is written to `.rstn/changes/<name>/status.json`
+//! with the history of transitions, so teammates sharing the repository see
+//! the same pipeline after a pull:
+//!
+//! ```json
+//! { "status": "planned", "updated_at": "...", "updated_by": "Alice",
+//!   "history": [ { "from": "proposed", "to": "planned", "at": "...", "by": "Alice" } ] }
+//! ```
+//!
+//! In-progress statuses (planning, implementing, testing) stay local: they
+//! describe a process running on one machine. `SyncedStatuses` remembers the
+//! status last read from or written to each file, the base `reconcile` uses
+//! to tell a teammate's update from a local one. A status file left with
+//! merge conflict markers, or local and pulled updates to the same change,
+//! are reported as a conflict for the user to resolve.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app_state::ChangeStatus;
+
+/// Status file in the change directory
+pub const STATUS_FILE: &str = "status.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusFile {
+    pub status: ChangeStatus,
+    /// ISO 8601
+    pub updated_at: String,
+    /// git user.name of whoever made the last transition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<String>,
+    #[serde(default)]
+    pub history: Vec<StatusTransition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusTransition {
+    /// None for the first status
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<ChangeStatus>,
+    pub to: ChangeStatus,
+    /// ISO 8601
+    pub at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by: Option<String>,
+}
+
+/// Diverging statuses of a change: local vs pulled (or the two sides of a
+/// merge conflict in the status file)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusConflict {
+    pub ours: ChangeStatus,
+    pub theirs: ChangeStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theirs_by: Option<String>,
+}
+
+/// What a status file holds
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatusRead {
+    Missing,
+    Status(StatusFile),
+    /// Unresolved git merge of the file
+    Conflict { ours: StatusFile, theirs: StatusFile },
+    Invalid(String),
+}
+
+/// Whether a status is written to the status file (in-progress statuses
+/// are local)
+pub fn is_shared(status: ChangeStatus) -> bool {
+    !matches!(status, ChangeStatus::Planning | ChangeStatus::Implementing | ChangeStatus::Testing)
+}
+
+/// `.rstn/changes/<change_name>/status.json` under the worktree
+pub fn status_path(worktree: &Path, change_name: &str) -> PathBuf {
+    worktree.join(".rstn").join("changes").join(change_name).join(STATUS_FILE)
+}
+
+pub fn read(worktree: &Path, change_name: &str) -> StatusRead {
+    let path = status_path(worktree, change_name);
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return StatusRead::Missing;
+    };
+    if let Some((ours, theirs)) = split_conflict(&content) {
+        return match (serde_json::from_str(&ours), serde_json::from_str(&theirs)) {
+            (Ok(ours), Ok(theirs)) => StatusRead::Conflict { ours, theirs },
+            _ => StatusRead::Invalid(format!("Unresolved merge conflict in {}", path.display())),
+        };
+    }
+    match serde_json::from_str(&content) {
+        Ok(file) => StatusRead::Status(file),
+        Err(e) => StatusRead::Invalid(format!("Invalid status file {}: {}", path.display(), e)),
+    }
+}
+
+/// The two sides of a file with git conflict markers (None if it has none)
+fn split_conflict(content: &str) -> Option<(String, String)> {
+    enum Side {
+        Both,
+        Ours,
+        Base,
+        Theirs,
+    }
+    if !content.lines().any(|line| line.starts_with("<<<<<<<")) {
+        return None;
+    }
+    let (mut ours, mut theirs) = (String::new(), String::new());
+    let mut side = Side::Both;
+    for line in content.lines() {
+        match line {
+            l if l.starts_with("<<<<<<<") => side = Side::Ours,
+            l if l.starts_with("|||||||") => side = Side::Base,
+            l if l.starts_with("=======") => side = Side::Theirs,
+            l if l.starts_with(">>>>>>>") => side = Side::Both,
+            l => {
+                if matches!(side, Side::Both | Side::Ours) {
+                    ours.push_str(l);
+                    ours.push('\n');
+                }
+                if matches!(side, Side::Both | Side::Theirs) {
+                    theirs.push_str(l);
+                    theirs.push('\n');
+                }
+            }
+        }
+    }
+    Some((ours, theirs))
+}
+
+/// Write `status` with a transition from the current file's status (or
+/// both sides of a conflicted file, whose histories are merged)
+pub fn record(worktree: &Path, change_name: &str, status: ChangeStatus, by: Option<String>) -> Result<StatusFile, String> {
+    let (from, mut history) = match read(worktree, change_name) {
+        StatusRead::Status(file) => (Some(file.status), file.history),
+        StatusRead::Conflict { ours, theirs } => {
+            let mut history = ours.history;
+            for transition in theirs.history {
+                if !history.contains(&transition) {
+                    history.push(transition);
+                }
+            }
+            history.sort_by(|a, b| a.at.cmp(&b.at));
+            (Some(ours.status), history)
+        }
+        StatusRead::Missing | StatusRead::Invalid(_) => (None, Vec::new()),
+    };
+
+    let now = chrono::Utc::now().to_rfc3339();
+    history.push(StatusTransition {
+        from,
+        to: status,
+        at: now.clone(),
+        by: by.clone(),
+    });
+    let file = StatusFile {
+        status,
+        updated_at: now,
+        updated_by: by,
+        history,
+    };
+    let json = serde_json::to_string_pretty(&file).map_err(|e| format!("Failed to serialize change status: {}", e))?;
+    crate::spec_kit::write_atomic(&status_path(worktree, change_name), &format!("{}\n", json))?;
+    Ok(file)
+}
+
+/// git user.name of the worktree (who status transitions are attributed to)
+pub fn git_user(worktree: &Path) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(worktree)
+        .args(["config", "user.name"])
+        .output()
+        .ok()?;
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !name.is_empty()).then_some(name)
+}
+
+/// Outcome of reconciling a change's status with its status file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reconciled {
+    /// None = no status on either side (derive it from the documents)
+    pub status: Option<ChangeStatus>,
+    pub conflict: Option<StatusConflict>,
+    /// New merge base to remember
+    pub synced: Option<ChangeStatus>,
+}
+
+/// Status of a change after a refresh. `local` is the status in memory (None
+/// for a change seen for the first time), `synced` the status last read from
+/// or written to the file.
+pub fn reconcile(local: Option<ChangeStatus>, synced: Option<ChangeStatus>, file: &StatusRead) -> Reconciled {
+    // Local work in progress wins until it settles
+    let in_progress = local.filter(|status| !is_shared(*status));
+    match file {
+        StatusRead::Missing | StatusRead::Invalid(_) => Reconciled {
+            status: local,
+            conflict: None,
+            synced,
+        },
+        StatusRead::Conflict { ours, theirs } if ours.status != theirs.status => Reconciled {
+            status: in_progress.or(Some(ours.status)),
+            conflict: Some(StatusConflict {
+                ours: ours.status,
+                theirs: theirs.status,
+                theirs_by: theirs.updated_by.clone(),
+            }),
+            synced,
+        },
+        StatusRead::Conflict { ours: file, .. } | StatusRead::Status(file) => {
+            let local_changed = local.is_some_and(|status| is_shared(status) && Some(status) != synced);
+            let pulled = Some(file.status) != synced;
+            match local {
+                Some(local) if local_changed && pulled && local != file.status => Reconciled {
+                    status: Some(local),
+                    conflict: Some(StatusConflict {
+                        ours: local,
+                        theirs: file.status,
+                        theirs_by: file.updated_by.clone(),
+                    }),
+                    synced,
+                },
+                // Not yet written (the handler that changed it writes it next)
+                Some(local) if local_changed && !pulled => Reconciled {
+                    status: Some(local),
+                    conflict: None,
+                    synced,
+                },
+                _ => Reconciled {
+                    status: in_progress.or(Some(file.status)),
+                    conflict: None,
+                    synced: Some(file.status),
+                },
+            }
+        }
+    }
+}
+
+/// Write a change's status to its status file when it needs it (see
+/// `SyncedStatuses::needs_write`). Changes without a directory on disk are
+/// skipped.
+pub fn save(worktree: &Path, change_name: &str, status: ChangeStatus) -> Result<(), String> {
+    // Never create directories for changes that are gone (or not on disk)
+    if !synced().needs_write(worktree, change_name, status)
+        || !status_path(worktree, change_name).parent().is_some_and(|dir| dir.is_dir())
+    {
+        return Ok(());
+    }
+    let result = record(worktree, change_name, status, git_user(worktree));
+    // Also after a failure, so the next transition doesn't retry a stale status
+    synced().set(worktree, change_name, Some(status));
+    result.map(|_| ()).map_err(|e| format!("Failed to save status of {}: {}", change_name, e))
+}
+
+/// Status last read from or written to each status file
+#[derive(Default)]
+pub struct SyncedStatuses {
+    statuses: Mutex<HashMap<PathBuf, ChangeStatus>>,
+}
+
+impl SyncedStatuses {
+    pub fn get(&self, worktree: &Path, change_name: &str) -> Option<ChangeStatus> {
+        let statuses = self.statuses.lock().unwrap_or_else(|e| e.into_inner());
+        statuses.get(&status_path(worktree, change_name)).copied()
+    }
+
+    pub fn set(&self, worktree: &Path, change_name: &str, status: Option<ChangeStatus>) {
+        let mut statuses = self.statuses.lock().unwrap_or_else(|e| e.into_inner());
+        let path = status_path(worktree, change_name);
+        match status {
+            Some(status) => statuses.insert(path, status),
+            None => statuses.remove(&path),
+        };
+    }
+
+    /// Whether the local status should be written: it is shared, differs
+    /// from the last synced one, and the file is not someone else's status
+    /// this session has not read yet
+    pub fn needs_write(&self, worktree: &Path, change_name: &str, status: ChangeStatus) -> bool {
+        if !is_shared(status) {
+            return false;
+        }
+        match self.get(worktree, change_name) {
+            Some(synced) => synced != status,
+            None => !status_path(worktree, change_name).exists(),
+        }
+    }
+}
+
+/// The process-wide merge bases
+pub fn synced() -> &'static SyncedStatuses {
+    static SYNCED: OnceLock<SyncedStatuses> = OnceLock::new();
+    SYNCED.get_or_init(SyncedStatuses::default)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(status: ChangeStatus, by: &str) -> StatusFile {
+        StatusFile {
+            status,
+            updated_at: "2025-01-01T00:00:00Z".to_string(),
+            updated_by: Some(by.to_string()),
+            history: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_record_and_read_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join(".rstn/changes/add-auth")).unwrap();
+        assert_eq!(read(root, "add-auth"), StatusRead::Missing);
+
+        record(root, "add-auth", ChangeStatus::Proposed, Some("Alice".to_string())).unwrap();
+        let planned = record(root, "add-auth", ChangeStatus::Planned, Some("Alice".to_string())).unwrap();
+        assert_eq!(planned.history.len(), 2);
+        assert_eq!(planned.history[1].from, Some(ChangeStatus::Proposed));
+        assert_eq!(read(root, "add-auth"), StatusRead::Status(planned.clone()));
+
+        // Both teammates moved the change on and git could not merge
+        let ours = serde_json::to_string_pretty(&file(ChangeStatus::Done, "Alice")).unwrap();
+        let theirs = serde_json::to_string_pretty(&file(ChangeStatus::Cancelled, "Bob")).unwrap();
+        let conflicted = format!("<<<<<<< HEAD\n{}\n=======\n{}\n>>>>>>> origin/main\n", ours, theirs);
+        std::fs::write(status_path(root, "add-auth"), conflicted).unwrap();
+        let StatusRead::Conflict { ours, theirs } = read(root, "add-auth") else {
+            panic!("expected a conflict");
+        };
+        assert_eq!((ours.status, theirs.status), (ChangeStatus::Done, ChangeStatus::Cancelled));
+
+        // Recording a status resolves the conflict
+        let resolved = record(root, "add-auth", ChangeStatus::Cancelled, None).unwrap();
+        assert_eq!(resolved.history.last().unwrap().from, Some(ChangeStatus::Done));
+        assert!(matches!(read(root, "add-auth"), StatusRead::Status(_)));
+    }
+
+    #[test]
+    fn test_reconcile() {
+        use ChangeStatus::*;
+        let pulled = StatusRead::Status(file(Done, "Bob"));
+
+        // A teammate's update is adopted when nothing changed locally
+        let reconciled = reconcile(Some(Planned), Some(Planned), &pulled);
+        assert_eq!((reconciled.status, reconciled.conflict, reconciled.synced), (Some(Done), None, Some(Done)));
+        // First sight of the change
+        assert_eq!(reconcile(None, None, &pulled).status, Some(Done));
+        // Local work in progress is kept
+        assert_eq!(reconcile(Some(Implementing), Some(Planned), &pulled).status, Some(Implementing));
+
+        // Both sides moved: conflict, local status kept
+        let reconciled = reconcile(Some(Cancelled), Some(Planned), &pulled);
+        assert_eq!(reconciled.status, Some(Cancelled));
+        assert_eq!(
+            reconciled.conflict,
+            Some(StatusConflict {
+                ours: Cancelled,
+                theirs: Done,
+                theirs_by: Some("Bob".to_string()),
+            })
+        );
+
+        let merge = StatusRead::Conflict {
+            ours: file(Done, "Alice"),
+            theirs: file(Failed, "Bob"),
+        };
+        let reconciled = reconcile(Some(Done), Some(Done), &merge);
+        assert_eq!(reconciled.conflict.map(|c| c.theirs), Some(Failed));
+        assert_eq!(reconcile(Some(Proposed), None, &StatusRead::Missing).status, Some(Proposed));
+    }
+}
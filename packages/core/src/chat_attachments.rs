@@ -0,0 +1,181 @@
+//! Files attached to chat messages.
+//!
+//! Attachments are picked by the user in a file dialog, so they are not
+//! limited to the project root. Text files are inlined into the prompt as
+//! fenced code blocks (cut at `MAX_TEXT_BYTES`); images are passed to the
+//! Claude CLI as image inputs. The user message only keeps the metadata
+//! (`ChatAttachment`) so the UI can show chips for what was sent.
+
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+
+use crate::app_state::{ChatAttachment, ChatAttachmentKind};
+use crate::claude_cli::ImageInput;
+use crate::file_preview;
+
+/// Most files per message
+pub const MAX_ATTACHMENTS: usize = 10;
+
+/// Text inlined per file; longer files are truncated
+pub const MAX_TEXT_BYTES: u64 = 100 * 1024;
+
+/// Largest image accepted by the API
+pub const MAX_IMAGE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Image formats Claude accepts
+const IMAGE_TYPES: &[&str] = &["image/png", "image/jpeg", "image/gif", "image/webp"];
+
+/// Attachments ready to send
+#[derive(Debug, Default)]
+pub struct PreparedAttachments {
+    /// Fenced text blocks to append to the prompt
+    pub text_blocks: Vec<String>,
+    pub images: Vec<ImageInput>,
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Image MIME type of a supported image, from magic bytes and extension
+fn image_type(head: &[u8], path: &Path) -> Option<&'static str> {
+    let mime = file_preview::detect_mime(head, path);
+    IMAGE_TYPES.contains(&mime).then_some(mime)
+}
+
+/// Metadata shown for an attachment (unreadable files are described as text)
+pub fn describe(path: &Path) -> ChatAttachment {
+    let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let head = file_preview::read_head(path, 16).unwrap_or_default();
+    let kind = match image_type(&head, path) {
+        Some(_) => ChatAttachmentKind::Image,
+        None => ChatAttachmentKind::Text,
+    };
+    ChatAttachment {
+        name: file_name(path),
+        path: path.display().to_string(),
+        kind,
+        size,
+        truncated: kind == ChatAttachmentKind::Text && size > MAX_TEXT_BYTES,
+    }
+}
+
+/// Read the attachments: text files become fenced blocks, images base64 inputs
+pub fn prepare(paths: &[PathBuf]) -> Result<PreparedAttachments, String> {
+    if paths.len() > MAX_ATTACHMENTS {
+        return Err(format!("At most {} files can be attached", MAX_ATTACHMENTS));
+    }
+
+    let mut prepared = PreparedAttachments::default();
+    for path in paths {
+        let name = file_name(path);
+        let size = std::fs::metadata(path)
+            .map_err(|e| format!("Cannot attach {}: {}", name, e))?
+            .len();
+        let head = file_preview::read_head(path, 16).map_err(|e| format!("Cannot attach {}: {}", name, e))?;
+
+        if let Some(media_type) = image_type(&head, path) {
+            if size > MAX_IMAGE_BYTES {
+                return Err(format!(
+                    "{} is too large to attach ({} bytes, limit {})",
+                    name, size, MAX_IMAGE_BYTES
+                ));
+            }
+            let bytes = std::fs::read(path).map_err(|e| format!("Cannot attach {}: {}", name, e))?;
+            prepared.images.push(ImageInput {
+                media_type: media_type.to_string(),
+                data: base64::engine::general_purpose::STANDARD.encode(bytes),
+            });
+            continue;
+        }
+
+        let bytes = file_preview::read_head(path, MAX_TEXT_BYTES as usize)
+            .map_err(|e| format!("Cannot attach {}: {}", name, e))?;
+        if bytes.contains(&0) {
+            return Err(format!("{} is not a text file or supported image", name));
+        }
+        // The cut may split a UTF-8 character; lossy decoding keeps the rest
+        let text = String::from_utf8_lossy(&bytes);
+        prepared.text_blocks.push(fence(&path.display().to_string(), &text, size > MAX_TEXT_BYTES));
+    }
+    Ok(prepared)
+}
+
+/// Fenced block for a text file, with a fence longer than any backtick run inside
+fn fence(label: &str, text: &str, truncated: bool) -> String {
+    let longest_run = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    let note = if truncated {
+        format!("\n(truncated to the first {} bytes)", MAX_TEXT_BYTES)
+    } else {
+        String::new()
+    };
+    format!("{}\n{}\n{}\n{}{}", label, fence, text.trim_end(), fence, note)
+}
+
+/// The message text followed by the inlined text files
+pub fn compose_prompt(text: &str, attachments: &PreparedAttachments) -> String {
+    if attachments.text_blocks.is_empty() {
+        return text.to_string();
+    }
+    format!("{}\n\nAttached files:\n\n{}", text, attachments.text_blocks.join("\n\n"))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG_HEADER: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+
+    #[test]
+    fn test_prepare_text_and_images() {
+        let dir = tempfile::tempdir().unwrap();
+        let notes = dir.path().join("notes.md");
+        std::fs::write(&notes, "Use ```rust blocks```\n").unwrap();
+        let image = dir.path().join("screen.png");
+        std::fs::write(&image, PNG_HEADER).unwrap();
+
+        let prepared = prepare(&[notes.clone(), image.clone()]).unwrap();
+        assert_eq!(prepared.images.len(), 1);
+        assert_eq!(prepared.images[0].media_type, "image/png");
+        assert_eq!(
+            compose_prompt("Review this", &prepared),
+            format!(
+                "Review this\n\nAttached files:\n\n{}\n````\nUse ```rust blocks```\n````",
+                notes.display()
+            )
+        );
+
+        assert_eq!(describe(&image).kind, ChatAttachmentKind::Image);
+        let text = describe(&notes);
+        assert_eq!((text.name.as_str(), text.kind, text.truncated), ("notes.md", ChatAttachmentKind::Text, false));
+    }
+
+    #[test]
+    fn test_prepare_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let large = dir.path().join("large.log");
+        std::fs::write(&large, "x".repeat(MAX_TEXT_BYTES as usize + 10)).unwrap();
+        let binary = dir.path().join("data.bin");
+        std::fs::write(&binary, [1u8, 0, 2]).unwrap();
+
+        let prepared = prepare(std::slice::from_ref(&large)).unwrap();
+        assert!(prepared.text_blocks[0].ends_with("(truncated to the first 102400 bytes)"));
+        assert!(describe(&large).truncated);
+
+        assert!(prepare(&[binary]).unwrap_err().contains("not a text file"));
+        assert!(prepare(&[dir.path().join("missing.txt")]).is_err());
+        assert!(prepare(&vec![large; MAX_ATTACHMENTS + 1]).is_err());
+    }
+}
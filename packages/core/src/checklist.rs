@@ -0,0 +1,205 @@
+//! Requirement-quality checklists for a spec-kit feature.
+//!
+//! Like the plan and clarify phases, a checklist is generated by Claude from
+//! the feature's spec (and plan, when present). Each kind is stored as
+//! `checklist-<kind>.md` in the feature directory as a list of
+//! `- [ ] CHK001 ...` items, which the user (or the review gate) checks off.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::spec_tasks::parse_checkbox;
+
+/// Focus of a checklist
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChecklistKind {
+    Security,
+    #[serde(rename = "a11y")]
+    Accessibility,
+    Performance,
+}
+
+impl ChecklistKind {
+    pub const ALL: [ChecklistKind; 3] = [ChecklistKind::Security, ChecklistKind::Accessibility, ChecklistKind::Performance];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChecklistKind::Security => "security",
+            ChecklistKind::Accessibility => "a11y",
+            ChecklistKind::Performance => "performance",
+        }
+    }
+
+    /// File name in the feature directory
+    pub fn file_name(self) -> String {
+        format!("checklist-{}.md", self.as_str())
+    }
+
+    fn focus(self) -> &'static str {
+        match self {
+            ChecklistKind::Security => {
+                "security: authentication and authorization, input validation, secrets and sensitive data handling, audit logging, abuse cases"
+            }
+            ChecklistKind::Accessibility => {
+                "accessibility: keyboard navigation, screen reader labels, color contrast, focus management, error messaging, WCAG 2.1 AA"
+            }
+            ChecklistKind::Performance => {
+                "performance: latency and throughput targets, data volume limits, resource usage, caching, degradation under load"
+            }
+        }
+    }
+}
+
+/// One check of a checklist
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChecklistItem {
+    /// Item ID (e.g. "CHK001"); generated from the position when missing
+    pub id: String,
+    pub text: String,
+    pub checked: bool,
+    /// Section heading the item is listed under
+    pub section: String,
+    /// 0-based line number in the file
+    pub line: usize,
+}
+
+/// Prompt generating a checklist of `kind` from the feature's spec and plan
+pub fn build_prompt(kind: ChecklistKind, spec: &str, plan: Option<&str>) -> String {
+    let plan = plan
+        .filter(|p| !p.trim().is_empty())
+        .map(|p| format!("\n## Plan\n{}\n", p.trim()))
+        .unwrap_or_default();
+    format!(
+        r###"You are reviewing a feature specification for {}.
+
+## Specification
+{}
+{}
+## Instructions
+Write a checklist that tests whether the requirements are complete, clear and verifiable for this focus (it checks the spec, not the implementation).
+Group items under "## " section headings. Each item is one line: `- [ ] CHK001 Question about the requirements [Spec §Section]`, numbered sequentially.
+Write 10 to 25 items, most important first. Start with a "# {} Checklist" title.
+
+Output ONLY the markdown content, no code fences or extra commentary."###,
+        kind.focus(),
+        spec.trim(),
+        plan,
+        match kind {
+            ChecklistKind::Security => "Security",
+            ChecklistKind::Accessibility => "Accessibility",
+            ChecklistKind::Performance => "Performance",
+        }
+    )
+}
+
+/// Parse the checkbox items of a checklist
+pub fn parse(content: &str) -> Vec<ChecklistItem> {
+    let mut section = String::new();
+    let mut items: Vec<ChecklistItem> = Vec::new();
+    for (line_no, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('#') {
+            section = trimmed.trim_start_matches('#').trim().to_string();
+            continue;
+        }
+        let Some((checked, rest)) = parse_checkbox(line) else {
+            continue;
+        };
+        let (id, text) = match rest.split_once(char::is_whitespace) {
+            Some((id, text)) if id.starts_with("CHK") => (id.to_string(), text.trim()),
+            _ => (format!("CHK{:03}", items.len() + 1), rest),
+        };
+        items.push(ChecklistItem {
+            id,
+            text: text.to_string(),
+            checked,
+            section: section.clone(),
+            line: line_no,
+        });
+    }
+    items
+}
+
+/// Checklist with item `id` checked or unchecked; other lines untouched
+pub fn set_checked(content: &str, id: &str, checked: bool) -> Result<String, String> {
+    let item = parse(content)
+        .into_iter()
+        .find(|item| item.id == id)
+        .ok_or_else(|| format!("Checklist item {} not found", id))?;
+
+    let mut lines: Vec<String> = content.split('\n').map(str::to_string).collect();
+    let line = &mut lines[item.line];
+    let start = line.find('[').ok_or_else(|| format!("Checklist item {} has no checkbox", id))?;
+    line.replace_range(start..start + 3, if checked { "[x]" } else { "[ ]" });
+    Ok(lines.join("\n"))
+}
+
+/// Checklists present in a feature directory, in [`ChecklistKind::ALL`] order
+pub fn load_all(feature_dir: &Path) -> Vec<(ChecklistKind, Vec<ChecklistItem>)> {
+    ChecklistKind::ALL
+        .iter()
+        .filter_map(|&kind| {
+            let content = std::fs::read_to_string(feature_dir.join(kind.file_name())).ok()?;
+            Some((kind, parse(&content)))
+        })
+        .collect()
+}
+
+/// Check or uncheck an item in a checklist file and return the re-parsed items
+pub fn update_file(path: &Path, id: &str, checked: bool) -> Result<Vec<ChecklistItem>, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let updated = set_checked(&content, id, checked)?;
+    if updated != content {
+        crate::spec_kit::write_atomic(path, &updated)?;
+    }
+    Ok(parse(&updated))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECKLIST: &str = "# Security Checklist\n\n## Authentication\n- [ ] CHK001 Are admin-only exports specified? [Spec §FR-001]\n- [x] CHK002 Is session expiry defined?\n\n## Data\n- [ ] Is PII masking in exports specified?\n";
+
+    #[test]
+    fn test_parse() {
+        let items = parse(CHECKLIST);
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].id, "CHK001");
+        assert_eq!(items[0].text, "Are admin-only exports specified? [Spec §FR-001]");
+        assert_eq!(items[0].section, "Authentication");
+        assert!(items[1].checked);
+        // Items without an ID are numbered by position
+        assert_eq!(items[2].id, "CHK003");
+        assert_eq!(items[2].section, "Data");
+    }
+
+    #[test]
+    fn test_update_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(ChecklistKind::Security.file_name());
+        std::fs::write(&path, CHECKLIST).unwrap();
+
+        let items = update_file(&path, "CHK003", true).unwrap();
+        assert!(items[2].checked);
+        assert!(std::fs::read_to_string(&path).unwrap().contains("- [x] Is PII masking"));
+
+        let loaded = load_all(dir.path());
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].0, ChecklistKind::Security);
+        assert_eq!(update_file(&path, "CHK009", true).unwrap_err(), "Checklist item CHK009 not found");
+    }
+
+    #[test]
+    fn test_kind_names() {
+        assert_eq!(ChecklistKind::Accessibility.file_name(), "checklist-a11y.md");
+        assert_eq!(serde_json::to_value(ChecklistKind::Accessibility).unwrap(), "a11y");
+        assert!(build_prompt(ChecklistKind::Performance, "FR-001", Some("Use a cache")).contains("## Plan\nUse a cache"));
+    }
+}
@@ -0,0 +1,263 @@
+//! Interactive clarification of a feature spec.
+//!
+//! The analyzer scans `spec.md` for `[NEEDS CLARIFICATION: ...]` markers and
+//! scores how well the spec covers each category of a fixed taxonomy. Markers
+//! and missing categories become questions the user answers one at a time;
+//! the integrator then folds the answers into the spec (markers replaced in
+//! place, every Q/A recorded under `## Clarifications`).
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum number of questions asked in one session
+pub const MAX_QUESTIONS: usize = 5;
+
+const MARKER_PREFIX: &str = "[NEEDS CLARIFICATION:";
+const CLARIFICATIONS_HEADING: &str = "## Clarifications";
+
+/// Taxonomy category the analyzer scores
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClarifyCategory {
+    FunctionalScope,
+    DataModel,
+    UxFlow,
+    NonFunctional,
+    Integrations,
+    EdgeCases,
+    Constraints,
+    CompletionSignals,
+    Placeholders,
+}
+
+impl ClarifyCategory {
+    /// Categories scored by keyword coverage, in question order
+    const SCORED: [ClarifyCategory; 8] = [
+        ClarifyCategory::FunctionalScope,
+        ClarifyCategory::DataModel,
+        ClarifyCategory::UxFlow,
+        ClarifyCategory::EdgeCases,
+        ClarifyCategory::NonFunctional,
+        ClarifyCategory::Integrations,
+        ClarifyCategory::Constraints,
+        ClarifyCategory::CompletionSignals,
+    ];
+
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            ClarifyCategory::FunctionalScope => &["requirement", "fr-", "must ", "goal", "out of scope"],
+            ClarifyCategory::DataModel => &["entity", "entities", "field", "attribute", "data model", "relationship"],
+            ClarifyCategory::UxFlow => &["user scenario", "given ", "when ", "then ", "flow", "screen"],
+            ClarifyCategory::NonFunctional => &["performance", "latency", "security", "availability", "accessibility", "scale"],
+            ClarifyCategory::Integrations => &["integration", "external", "api", "third-party", "dependency", "dependencies"],
+            ClarifyCategory::EdgeCases => &["edge case", "error", "fail", "invalid", "empty", "timeout"],
+            ClarifyCategory::Constraints => &["constraint", "assumption", "limit", "tradeoff", "trade-off"],
+            ClarifyCategory::CompletionSignals => &["success criteria", "acceptance", "measurable", "done when"],
+            ClarifyCategory::Placeholders => &[],
+        }
+    }
+
+    /// Question asked when the spec does not cover the category
+    fn question(self) -> &'static str {
+        match self {
+            ClarifyCategory::FunctionalScope => "What must the feature do, and what is explicitly out of scope?",
+            ClarifyCategory::DataModel => "What data does the feature work with (entities, key fields, relationships)?",
+            ClarifyCategory::UxFlow => "What is the primary user flow, step by step?",
+            ClarifyCategory::NonFunctional => "Are there performance, security or accessibility requirements?",
+            ClarifyCategory::Integrations => "Which external services or APIs does the feature depend on?",
+            ClarifyCategory::EdgeCases => "How should errors and edge cases (empty, invalid, failing input) be handled?",
+            ClarifyCategory::Constraints => "What constraints or assumptions apply (limits, platforms, tradeoffs)?",
+            ClarifyCategory::CompletionSignals => "How do we know the feature is done (measurable success criteria)?",
+            ClarifyCategory::Placeholders => "",
+        }
+    }
+}
+
+/// How well the spec covers a category
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverageStatus {
+    Clear,
+    Partial,
+    Missing,
+    /// Answered in the current session
+    Resolved,
+}
+
+/// Coverage of one category
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CategoryCoverage {
+    pub category: ClarifyCategory,
+    pub status: CoverageStatus,
+}
+
+/// A question of a clarify session
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClarifyQuestion {
+    pub id: String,
+    pub category: ClarifyCategory,
+    pub question: String,
+    /// Full marker text to replace with the answer (marker questions only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker: Option<String>,
+}
+
+/// Result of analyzing a spec
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    pub coverage: Vec<CategoryCoverage>,
+    pub questions: Vec<ClarifyQuestion>,
+}
+
+/// `[NEEDS CLARIFICATION: ...]` markers in the spec: (full marker, question)
+fn find_markers(spec: &str) -> Vec<(String, String)> {
+    let mut markers: Vec<(String, String)> = Vec::new();
+    let mut rest = spec;
+    while let Some(start) = rest.find(MARKER_PREFIX) {
+        let after = &rest[start..];
+        let Some(end) = after.find(']') else {
+            break;
+        };
+        let marker = &after[..=end];
+        let question = marker[MARKER_PREFIX.len()..marker.len() - 1].trim().to_string();
+        if !markers.iter().any(|(m, _)| m == marker) {
+            markers.push((marker.to_string(), question));
+        }
+        rest = &after[end + 1..];
+    }
+    markers
+}
+
+/// Score the spec against the taxonomy and derive up to [`MAX_QUESTIONS`]
+/// questions: open markers first, then uncovered categories
+pub fn analyze(spec: &str) -> Analysis {
+    let lower = spec.to_lowercase();
+    let markers = find_markers(spec);
+
+    let mut coverage: Vec<CategoryCoverage> = ClarifyCategory::SCORED
+        .iter()
+        .map(|&category| {
+            let hits = category.keywords().iter().filter(|k| lower.contains(*k)).count();
+            let status = match hits {
+                0 => CoverageStatus::Missing,
+                1 => CoverageStatus::Partial,
+                _ => CoverageStatus::Clear,
+            };
+            CategoryCoverage { category, status }
+        })
+        .collect();
+    coverage.push(CategoryCoverage {
+        category: ClarifyCategory::Placeholders,
+        status: if markers.is_empty() { CoverageStatus::Clear } else { CoverageStatus::Missing },
+    });
+
+    let marker_questions = markers.into_iter().enumerate().map(|(i, (marker, question))| ClarifyQuestion {
+        id: format!("marker-{}", i + 1),
+        category: ClarifyCategory::Placeholders,
+        question,
+        marker: Some(marker),
+    });
+    let category_questions = coverage
+        .iter()
+        .filter(|c| c.status == CoverageStatus::Missing && c.category != ClarifyCategory::Placeholders)
+        .map(|c| ClarifyQuestion {
+            id: serde_json::to_value(c.category)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            category: c.category,
+            question: c.category.question().to_string(),
+            marker: None,
+        });
+    let questions = marker_questions.chain(category_questions).take(MAX_QUESTIONS).collect();
+
+    Analysis { coverage, questions }
+}
+
+/// Fold answers into the spec: each answered marker is replaced by its
+/// answer, and every Q/A is recorded under a dated session of
+/// `## Clarifications` (created at the end of the spec if absent)
+pub fn integrate(spec: &str, answers: &[(&ClarifyQuestion, &str)], date: &str) -> String {
+    if answers.is_empty() {
+        return spec.to_string();
+    }
+
+    let mut updated = spec.to_string();
+    for (question, answer) in answers {
+        if let Some(marker) = &question.marker {
+            updated = updated.replace(marker.as_str(), answer.trim());
+        }
+    }
+
+    let mut session = format!("### Session {}\n", date);
+    for (question, answer) in answers {
+        session.push_str(&format!("- Q: {} → A: {}\n", question.question, answer.trim()));
+    }
+
+    match updated.find(CLARIFICATIONS_HEADING) {
+        Some(start) => {
+            // Append the session at the end of the existing section
+            let body_start = start + CLARIFICATIONS_HEADING.len();
+            let end = updated[body_start..]
+                .find("\n## ")
+                .map_or(updated.len(), |i| body_start + i + 1);
+            let mut section = updated[..end].trim_end().to_string();
+            section.push_str("\n\n");
+            section.push_str(&session);
+            if end < updated.len() {
+                section.push('\n');
+            }
+            section.push_str(&updated[end..]);
+            section
+        }
+        None => format!("{}\n\n{}\n{}", updated.trim_end(), CLARIFICATIONS_HEADING, session),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = "# Spec\n\n## Requirements\n- FR-001 Users must export data as [NEEDS CLARIFICATION: which format?]\n- FR-002 Exports must finish within [NEEDS CLARIFICATION: how long?]\n\n## Key Entities\n- Export: entity with a format field\n";
+
+    #[test]
+    fn test_analyze() {
+        let analysis = analyze(SPEC);
+        let status = |category| analysis.coverage.iter().find(|c| c.category == category).unwrap().status;
+        assert_eq!(status(ClarifyCategory::FunctionalScope), CoverageStatus::Clear);
+        assert_eq!(status(ClarifyCategory::DataModel), CoverageStatus::Clear);
+        assert_eq!(status(ClarifyCategory::CompletionSignals), CoverageStatus::Missing);
+        assert_eq!(status(ClarifyCategory::Placeholders), CoverageStatus::Missing);
+
+        // Markers first, capped at MAX_QUESTIONS
+        assert_eq!(analysis.questions.len(), MAX_QUESTIONS);
+        assert_eq!(analysis.questions[0].question, "which format?");
+        assert_eq!(analysis.questions[1].marker.as_deref(), Some("[NEEDS CLARIFICATION: how long?]"));
+        assert!(analysis.questions[2].marker.is_none());
+    }
+
+    #[test]
+    fn test_integrate() {
+        let analysis = analyze(SPEC);
+        let format = &analysis.questions[0];
+        let ux = analysis.questions.iter().find(|q| q.category == ClarifyCategory::UxFlow).unwrap();
+
+        let updated = integrate(SPEC, &[(format, "CSV"), (ux, "Admin clicks Export")], "2026-01-01");
+        assert!(updated.contains("export data as CSV"));
+        assert!(updated.contains("[NEEDS CLARIFICATION: how long?]"));
+        assert!(updated.ends_with(
+            "## Clarifications\n### Session 2026-01-01\n- Q: which format? → A: CSV\n- Q: What is the primary user flow, step by step? → A: Admin clicks Export\n"
+        ));
+
+        // A later session is appended to the existing section, before the next one
+        let with_notes = format!("{}\n## Notes\nNone\n", updated);
+        let again = integrate(&with_notes, &[(&analysis.questions[1], "1 minute")], "2026-01-02");
+        assert!(again.contains("→ A: Admin clicks Export\n\n### Session 2026-01-02\n- Q: how long? → A: 1 minute\n\n## Notes\nNone\n"));
+        assert!(find_markers(&again).is_empty());
+
+        assert_eq!(integrate(SPEC, &[], "2026-01-01"), SPEC);
+    }
+}
@@ -0,0 +1,367 @@
+//! Reading stream events: text, tool calls and their results, permission
+//! requests, the session ID and usage.
+
+use super::ClaudeStreamEvent;
+
+/// A tool call started by the agent
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolUse<'a> {
+    pub id: &'a str,
+    pub name: &'a str,
+    pub input: &'a serde_json::Value,
+}
+
+/// The result of a tool call
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolResult {
+    pub tool_use_id: String,
+    /// Result text (text blocks joined)
+    pub content: String,
+    pub is_error: bool,
+}
+
+/// Extract text content from a delta event.
+///
+/// Returns Some(text) if this is a text_delta with content, None otherwise.
+pub fn extract_text_delta(event: &ClaudeStreamEvent) -> Option<&str> {
+    match event {
+        ClaudeStreamEvent::ContentBlockDelta { delta, .. } => {
+            if delta.delta_type == "text_delta" {
+                delta.text.as_deref()
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Extract text content from Claude CLI assistant event.
+///
+/// Returns Some(text) if this is an Assistant event with text content, None otherwise.
+pub fn extract_assistant_text(event: &ClaudeStreamEvent) -> Option<String> {
+    match event {
+        ClaudeStreamEvent::Assistant { message } => {
+            // Extract text from first content item
+            message
+                .content
+                .iter()
+                .find(|item| item.content_type == "text")
+                .and_then(|item| item.text.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Extract the tool calls started by an assistant (or `tool_use`) event.
+pub fn extract_tool_uses(event: &ClaudeStreamEvent) -> Vec<ToolUse<'_>> {
+    match event {
+        ClaudeStreamEvent::ToolUse { id, name, input } => vec![ToolUse { id, name, input }],
+        ClaudeStreamEvent::Assistant { message } => message
+            .content
+            .iter()
+            .filter(|item| item.content_type == "tool_use")
+            .filter_map(|item| {
+                Some(ToolUse {
+                    id: item.id.as_deref()?,
+                    name: item.name.as_deref()?,
+                    input: &item.input,
+                })
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Extract the tool results carried by a `user` event.
+pub fn extract_tool_results(event: &ClaudeStreamEvent) -> Vec<ToolResult> {
+    let ClaudeStreamEvent::User { message } = event else {
+        return Vec::new();
+    };
+    let Some(items) = message.get("content").and_then(|c| c.as_array()) else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter(|item| item.get("type").and_then(|t| t.as_str()) == Some("tool_result"))
+        .filter_map(|item| {
+            let content = match item.get("content") {
+                Some(serde_json::Value::String(text)) => text.clone(),
+                Some(serde_json::Value::Array(blocks)) => blocks
+                    .iter()
+                    .filter_map(|block| block.get("text").and_then(|t| t.as_str()))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                _ => String::new(),
+            };
+            Some(ToolResult {
+                tool_use_id: item.get("tool_use_id")?.as_str()?.to_string(),
+                content,
+                is_error: item.get("is_error").and_then(|e| e.as_bool()).unwrap_or(false),
+            })
+        })
+        .collect()
+}
+
+/// A tool call waiting for the user's permission
+#[derive(Debug, Clone, PartialEq)]
+pub struct PermissionRequest {
+    pub request_id: String,
+    pub tool_name: String,
+    pub input: serde_json::Value,
+}
+
+/// The user's answer to a permission request
+#[derive(Debug, Clone, PartialEq)]
+pub enum PermissionDecision {
+    Allow,
+    Deny { message: String },
+}
+
+/// Extract a tool permission request (`control_request` with `can_use_tool`).
+pub fn extract_permission_request(event: &ClaudeStreamEvent) -> Option<PermissionRequest> {
+    let ClaudeStreamEvent::ControlRequest { request_id, request } = event else {
+        return None;
+    };
+    if request.get("subtype").and_then(|s| s.as_str()) != Some("can_use_tool") {
+        return None;
+    }
+    Some(PermissionRequest {
+        request_id: request_id.clone(),
+        tool_name: request.get("tool_name")?.as_str()?.to_string(),
+        input: request.get("input").cloned().unwrap_or(serde_json::Value::Null),
+    })
+}
+
+/// `control_response` line answering a permission request.
+///
+/// Allowing passes the original input back unchanged.
+pub fn permission_response(request: &PermissionRequest, decision: &PermissionDecision) -> serde_json::Value {
+    let answer = match decision {
+        PermissionDecision::Allow => serde_json::json!({
+            "behavior": "allow",
+            "updatedInput": request.input,
+        }),
+        PermissionDecision::Deny { message } => serde_json::json!({
+            "behavior": "deny",
+            "message": message,
+        }),
+    };
+    serde_json::json!({
+        "type": "control_response",
+        "response": {
+            "subtype": "success",
+            "request_id": request.request_id,
+            "response": answer,
+        }
+    })
+}
+
+/// Longest tool input summary shown in chat
+const TOOL_SUMMARY_CHARS: usize = 120;
+
+/// Longest tool result excerpt kept in chat state
+pub const TOOL_RESULT_EXCERPT_CHARS: usize = 500;
+
+/// Cut `text` to `max` characters, marking the cut with "…"
+pub fn truncate_chars(text: &str, max: usize) -> String {
+    let text = text.trim();
+    match text.char_indices().nth(max) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+/// One-line description of a tool call's input (command, path, pattern...)
+pub fn summarize_tool_input(name: &str, input: &serde_json::Value) -> String {
+    let field = |key: &str| input.get(key).and_then(|v| v.as_str());
+    let summary = match name {
+        "Bash" => field("command").map(str::to_string),
+        "Read" | "Write" | "Edit" | "MultiEdit" => field("file_path").map(str::to_string),
+        "NotebookEdit" => field("notebook_path").map(str::to_string),
+        "Grep" | "Glob" => field("pattern").map(|pattern| match field("path") {
+            Some(path) => format!("{} in {}", pattern, path),
+            None => pattern.to_string(),
+        }),
+        "WebFetch" => field("url").map(str::to_string),
+        "WebSearch" => field("query").map(str::to_string),
+        "Task" => field("description").map(str::to_string),
+        "TodoWrite" => input
+            .get("todos")
+            .and_then(|t| t.as_array())
+            .map(|todos| format!("{} todos", todos.len())),
+        _ => None,
+    };
+    let summary = summary.unwrap_or_else(|| match input {
+        serde_json::Value::Object(map) if map.is_empty() => String::new(),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    });
+    truncate_chars(&summary.replace('\n', " "), TOOL_SUMMARY_CHARS)
+}
+
+/// Extract the CLI session ID from the `system` init event.
+pub fn extract_session_id(event: &ClaudeStreamEvent) -> Option<&str> {
+    match event {
+        ClaudeStreamEvent::System { subtype, data } if subtype == "init" => {
+            data.get("session_id").and_then(|id| id.as_str())
+        }
+        _ => None,
+    }
+}
+
+/// Token usage reported by a `result` event
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClaudeUsage {
+    pub session_id: Option<String>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    /// Total cost in USD, if reported
+    pub cost_usd: Option<f64>,
+}
+
+/// Extract token usage and cost from a `result` event.
+pub fn extract_usage(event: &ClaudeStreamEvent) -> Option<ClaudeUsage> {
+    let ClaudeStreamEvent::Result { data, .. } = event else {
+        return None;
+    };
+    let usage = data.get("usage")?;
+    let tokens = |key: &str| usage.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+
+    Some(ClaudeUsage {
+        session_id: data.get("session_id").and_then(|id| id.as_str()).map(|id| id.to_string()),
+        input_tokens: tokens("input_tokens"),
+        output_tokens: tokens("output_tokens"),
+        cache_read_tokens: tokens("cache_read_input_tokens"),
+        cache_creation_tokens: tokens("cache_creation_input_tokens"),
+        // Older CLI versions report `cost_usd`
+        cost_usd: data
+            .get("total_cost_usd")
+            .or_else(|| data.get("cost_usd"))
+            .and_then(|v| v.as_f64()),
+    })
+}
+
+/// Check if event signals end of streaming.
+pub fn is_message_stop(event: &ClaudeStreamEvent) -> bool {
+    matches!(
+        event,
+        ClaudeStreamEvent::MessageStop | ClaudeStreamEvent::Result { .. }
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::claude_cli::{parse_jsonl_line, Delta};
+
+    #[test]
+    fn test_extract_text_delta() {
+        let event = ClaudeStreamEvent::ContentBlockDelta {
+            index: 0,
+            delta: Delta {
+                delta_type: "text_delta".to_string(),
+                text: Some("Hello world".to_string()),
+            },
+        };
+
+        assert_eq!(extract_text_delta(&event), Some("Hello world"));
+    }
+
+    #[test]
+    fn test_extract_text_delta_no_text() {
+        let event = ClaudeStreamEvent::ContentBlockDelta {
+            index: 0,
+            delta: Delta {
+                delta_type: "input_json_delta".to_string(),
+                text: None,
+            },
+        };
+
+        assert_eq!(extract_text_delta(&event), None);
+    }
+
+    #[test]
+    fn test_is_message_stop() {
+        assert!(is_message_stop(&ClaudeStreamEvent::MessageStop));
+        assert!(!is_message_stop(&ClaudeStreamEvent::Other));
+    }
+
+    #[test]
+    fn test_extract_session_id() {
+        let init = parse_jsonl_line(r#"{"type":"system","subtype":"init","session_id":"abc-123","tools":[]}"#).unwrap();
+        assert_eq!(extract_session_id(&init), Some("abc-123"));
+
+        let other = parse_jsonl_line(r#"{"type":"system","subtype":"status","session_id":"abc-123"}"#).unwrap();
+        assert_eq!(extract_session_id(&other), None);
+        assert_eq!(extract_session_id(&ClaudeStreamEvent::MessageStop), None);
+    }
+
+    #[test]
+    fn test_extract_usage() {
+        let line = r#"{"type":"result","subtype":"success","session_id":"abc","total_cost_usd":0.0123,"usage":{"input_tokens":10,"output_tokens":42,"cache_read_input_tokens":300,"cache_creation_input_tokens":7}}"#;
+        let usage = extract_usage(&parse_jsonl_line(line).unwrap()).unwrap();
+        assert_eq!(usage.session_id.as_deref(), Some("abc"));
+        assert_eq!((usage.input_tokens, usage.output_tokens), (10, 42));
+        assert_eq!((usage.cache_read_tokens, usage.cache_creation_tokens), (300, 7));
+        assert_eq!(usage.cost_usd, Some(0.0123));
+
+        let no_usage = parse_jsonl_line(r#"{"type":"result","subtype":"error"}"#).unwrap();
+        assert!(extract_usage(&no_usage).is_none());
+        assert!(extract_usage(&ClaudeStreamEvent::MessageStop).is_none());
+    }
+
+    #[test]
+    fn test_extract_tool_calls() {
+        let line = r#"{"type":"assistant","message":{"id":"msg_1","content":[{"type":"text","text":"Checking"},{"type":"tool_use","id":"toolu_1","name":"Bash","input":{"command":"cargo test\nfoo"}}]}}"#;
+        let event = parse_jsonl_line(line).unwrap();
+        let uses = extract_tool_uses(&event);
+        assert_eq!(uses.len(), 1);
+        assert_eq!((uses[0].id, uses[0].name), ("toolu_1", "Bash"));
+        assert_eq!(summarize_tool_input(uses[0].name, uses[0].input), "cargo test foo");
+        assert_eq!(extract_assistant_text(&event).as_deref(), Some("Checking"));
+
+        let line = r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_1","content":[{"type":"text","text":"ok"}],"is_error":true}]}}"#;
+        let results = extract_tool_results(&parse_jsonl_line(line).unwrap());
+        assert_eq!(
+            results,
+            vec![ToolResult { tool_use_id: "toolu_1".to_string(), content: "ok".to_string(), is_error: true }]
+        );
+    }
+
+    #[test]
+    fn test_summarize_tool_input() {
+        let input = serde_json::json!({"pattern": "fn main", "path": "src"});
+        assert_eq!(summarize_tool_input("Grep", &input), "fn main in src");
+        let input = serde_json::json!({"file_path": "/repo/src/lib.rs", "old_string": "a"});
+        assert_eq!(summarize_tool_input("Edit", &input), "/repo/src/lib.rs");
+        assert_eq!(summarize_tool_input("mcp__x__y", &serde_json::json!({})), "");
+        assert_eq!(truncate_chars("ééé", 2), "éé…");
+    }
+
+    #[test]
+    fn test_permission_request_and_response() {
+        let line = r#"{"type":"control_request","request_id":"req-1","request":{"subtype":"can_use_tool","tool_name":"Bash","input":{"command":"rm -rf build"}}}"#;
+        let event = parse_jsonl_line(line).unwrap();
+        let request = extract_permission_request(&event).unwrap();
+        assert_eq!(request.tool_name, "Bash");
+
+        let allow = permission_response(&request, &PermissionDecision::Allow);
+        assert_eq!(allow["type"], "control_response");
+        assert_eq!(allow["response"]["request_id"], "req-1");
+        assert_eq!(allow["response"]["response"]["behavior"], "allow");
+        assert_eq!(allow["response"]["response"]["updatedInput"]["command"], "rm -rf build");
+
+        let deny = permission_response(&request, &PermissionDecision::Deny { message: "No".to_string() });
+        assert_eq!(deny["response"]["response"], serde_json::json!({"behavior": "deny", "message": "No"}));
+
+        let other = parse_jsonl_line(r#"{"type":"control_request","request_id":"req-2","request":{"subtype":"interrupt"}}"#).unwrap();
+        assert!(extract_permission_request(&other).is_none());
+    }
+}
@@ -9,6 +9,21 @@
 //! claude -p --verbose --output-format stream-json "prompt"
 //! ```
 //!
+//! Follow-up messages pass `--resume <session_id>` (captured from the
+//! `system` init event) so Claude keeps the full conversation context.
+//! The configured model (project override or global setting) is passed as
+//! `--model <name>`.
+//!
+//! Chat runs the CLI interactively: the prompt (text and base64 image
+//! blocks) is written to stdin as a stream-json user message
+//! (`--input-format stream-json`), and stdin stays open so tool permission
+//! prompts (`--permission-prompt-tool stdio`) can be answered. The CLI sends
+//! a `control_request` event and waits for a `control_response` line; the
+//! `PermissionBridge` routes the user's decision back to the right process.
+//!
+//! Running processes are kept in a `ClaudeProcessRegistry` keyed by request
+//! ID, so an in-flight generation can be cancelled (the process is killed).
+//!
 //! ## FSM States
 //!
 //! - IDLE: No active process
@@ -17,11 +32,20 @@
 //! - COMPLETE: message_stop received
 //! - ERROR: Error occurred
 
+mod events;
+mod registry;
+
+pub use events::{
+    extract_assistant_text, extract_permission_request, extract_session_id, extract_text_delta, extract_tool_results,
+    extract_tool_uses, extract_usage, is_message_stop, permission_response, summarize_tool_input, truncate_chars,
+    ClaudeUsage, PermissionDecision, PermissionRequest, ToolResult, ToolUse, TOOL_RESULT_EXCERPT_CHARS,
+};
+pub use registry::{ClaudeProcessRegistry, PermissionBridge};
 use serde::Deserialize;
 use std::path::Path;
 use std::time::Duration;
-use tokio::io::{AsyncBufReadExt, BufReader};
-use tokio::process::{Child, Command};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command};
 
 // ============================================================================
 // Timeout Constants
@@ -33,6 +57,13 @@ pub const EVENT_TIMEOUT: Duration = Duration::from_secs(30);
 /// Maximum total time for a single request
 pub const TOTAL_TIMEOUT: Duration = Duration::from_secs(300);
 
+/// Default total time for an implementation run
+/// (`GlobalSettings::implementation_timeout_secs` overrides it)
+pub const IMPLEMENTATION_TIMEOUT: Duration = Duration::from_secs(1800);
+
+/// Maximum time to wait for the user to answer a tool permission prompt
+pub const APPROVAL_TIMEOUT: Duration = Duration::from_secs(600);
+
 // ============================================================================
 // JSONL Event Types
 // ============================================================================
@@ -101,6 +132,19 @@ pub enum ClaudeStreamEvent {
         message: AssistantMessage,
     },
 
+    /// Claude CLI user message event (carries tool results)
+    #[serde(rename = "user")]
+    User {
+        message: serde_json::Value,
+    },
+
+    /// Claude CLI control request (tool permission prompts)
+    #[serde(rename = "control_request")]
+    ControlRequest {
+        request_id: String,
+        request: serde_json::Value,
+    },
+
     /// Claude CLI result event (completion)
     #[serde(rename = "result")]
     Result {
@@ -161,6 +205,13 @@ pub struct ContentItem {
     #[serde(rename = "type")]
     pub content_type: String,
     pub text: Option<String>,
+    /// Tool use ID (tool_use items)
+    pub id: Option<String>,
+    /// Tool name (tool_use items)
+    pub name: Option<String>,
+    /// Tool input (tool_use items)
+    #[serde(default)]
+    pub input: serde_json::Value,
 }
 
 // ============================================================================
@@ -218,47 +269,6 @@ pub fn parse_jsonl_line(line: &str) -> Result<ClaudeStreamEvent, ClaudeCliError>
     serde_json::from_str(trimmed).map_err(|e| ClaudeCliError::ParseError(e.to_string()))
 }
 
-/// Extract text content from a delta event.
-///
-/// Returns Some(text) if this is a text_delta with content, None otherwise.
-pub fn extract_text_delta(event: &ClaudeStreamEvent) -> Option<&str> {
-    match event {
-        ClaudeStreamEvent::ContentBlockDelta { delta, .. } => {
-            if delta.delta_type == "text_delta" {
-                delta.text.as_deref()
-            } else {
-                None
-            }
-        }
-        _ => None,
-    }
-}
-
-/// Extract text content from Claude CLI assistant event.
-///
-/// Returns Some(text) if this is an Assistant event with text content, None otherwise.
-pub fn extract_assistant_text(event: &ClaudeStreamEvent) -> Option<String> {
-    match event {
-        ClaudeStreamEvent::Assistant { message } => {
-            // Extract text from first content item
-            message
-                .content
-                .iter()
-                .find(|item| item.content_type == "text")
-                .and_then(|item| item.text.clone())
-        }
-        _ => None,
-    }
-}
-
-/// Check if event signals end of streaming.
-pub fn is_message_stop(event: &ClaudeStreamEvent) -> bool {
-    matches!(
-        event,
-        ClaudeStreamEvent::MessageStop | ClaudeStreamEvent::Result { .. }
-    )
-}
-
 // ============================================================================
 // CLI Process Management
 // ============================================================================
@@ -297,27 +307,139 @@ pub async fn validate_claude_cli() -> Result<(), ClaudeCliError> {
 /// * `prompt` - User's chat message
 /// * `cwd` - Working directory (worktree path)
 /// * `mcp_config_path` - Optional path to MCP config file for tool integration
+/// * `system_prompt_file_path` - Optional path to a custom system prompt file
+/// * `resume_session_id` - Optional session to resume (`--resume`)
+/// * `model` - Optional model name (`--model`, None = CLI default)
 ///
 /// # Example
-/// ```no_run
+/// ```ignore
 /// // Without MCP
-/// let child = spawn_claude("Hello", &path, None)?;
+/// let child = spawn_claude("Hello", &path, None, None, None, None)?;
 ///
 /// // With MCP
-/// let child = spawn_claude("Read README", &path, Some("/tmp/rstn-mcp-xxx.json"), None)?;
+/// let child = spawn_claude("Read README", &path, Some("/tmp/rstn-mcp-xxx.json"), None, None, None)?;
 ///
 /// // With custom agent rules
-/// let child = spawn_claude("Read README", &path, None, Some("/tmp/rstn-agent-rules-xxx.txt"))?;
+/// let child = spawn_claude("Read README", &path, None, Some("/tmp/rstn-agent-rules-xxx.txt"), None, None)?;
 ///
 /// // With both MCP and agent rules
-/// let child = spawn_claude("Read README", &path, Some("/tmp/rstn-mcp-xxx.json"), Some("/tmp/rstn-agent-rules-xxx.txt"))?;
+/// let child = spawn_claude("Read README", &path, Some("/tmp/rstn-mcp-xxx.json"), Some("/tmp/rstn-agent-rules-xxx.txt"), None, None)?;
+///
+/// // Continuing a previous session
+/// let child = spawn_claude("And the tests?", &path, None, None, Some("session-id"), None)?;
+///
+/// // With a specific model
+/// let child = spawn_claude("Hello", &path, None, None, None, Some("opus"))?;
 /// ```
 pub fn spawn_claude(
     prompt: &str,
     cwd: &Path,
     mcp_config_path: Option<&str>,
     system_prompt_file_path: Option<&str>,
+    resume_session_id: Option<&str>,
+    model: Option<&str>,
 ) -> Result<Child, ClaudeCliError> {
+    let mut cmd = claude_command(cwd, mcp_config_path, system_prompt_file_path, resume_session_id, model);
+    cmd.arg(prompt);
+    spawn_command(&mut cmd)
+}
+
+/// An image passed to Claude alongside the prompt
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageInput {
+    /// "image/png", "image/jpeg", "image/gif" or "image/webp"
+    pub media_type: String,
+    /// Base64-encoded file content
+    pub data: String,
+}
+
+/// Spawn Claude CLI for a chat turn (same options as [`spawn_claude`]).
+///
+/// The prompt and images are written to stdin in stream-json format, and
+/// tool permission prompts are sent as `control_request` events. The
+/// returned stdin must stay open to answer them; dropping it ends the
+/// input stream and lets the CLI exit after its response.
+pub async fn spawn_claude_interactive(
+    prompt: &str,
+    images: &[ImageInput],
+    cwd: &Path,
+    mcp_config_path: Option<&str>,
+    system_prompt_file_path: Option<&str>,
+    resume_session_id: Option<&str>,
+    model: Option<&str>,
+) -> Result<(Child, ChildStdin), ClaudeCliError> {
+    let cmd = claude_command(cwd, mcp_config_path, system_prompt_file_path, resume_session_id, model);
+    spawn_interactive(cmd, prompt, images).await
+}
+
+/// Spawn Claude CLI for an unattended implementation run: `allowed_tools`
+/// are passed as `--allowedTools` and every other tool call arrives as a
+/// permission request (see [`spawn_claude_interactive`]).
+pub async fn spawn_claude_with_tools(
+    prompt: &str,
+    cwd: &Path,
+    allowed_tools: &[String],
+    model: Option<&str>,
+) -> Result<(Child, ChildStdin), ClaudeCliError> {
+    let mut cmd = claude_command(cwd, None, None, None, model);
+    if !allowed_tools.is_empty() {
+        cmd.arg("--allowedTools").arg(allowed_tools.join(","));
+    }
+    spawn_interactive(cmd, prompt, &[]).await
+}
+
+async fn spawn_interactive(mut cmd: Command, prompt: &str, images: &[ImageInput]) -> Result<(Child, ChildStdin), ClaudeCliError> {
+    cmd.arg("--input-format")
+        .arg("stream-json")
+        .arg("--permission-prompt-tool")
+        .arg("stdio")
+        .stdin(std::process::Stdio::piped());
+    let mut child = spawn_command(&mut cmd)?;
+
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| ClaudeCliError::SpawnFailed("stdin not piped".to_string()))?;
+    write_json_line(&mut stdin, &user_message_with_images(prompt, images))
+        .await
+        .map_err(|e| ClaudeCliError::SpawnFailed(format!("Failed to write prompt: {}", e)))?;
+    Ok((child, stdin))
+}
+
+async fn write_json_line(stdin: &mut ChildStdin, value: &serde_json::Value) -> std::io::Result<()> {
+    let mut line = value.to_string();
+    line.push('\n');
+    stdin.write_all(line.as_bytes()).await?;
+    stdin.flush().await
+}
+
+/// Stream-json user message with a text block followed by image blocks
+pub fn user_message_with_images(prompt: &str, images: &[ImageInput]) -> serde_json::Value {
+    let mut content = vec![serde_json::json!({ "type": "text", "text": prompt })];
+    content.extend(images.iter().map(|image| {
+        serde_json::json!({
+            "type": "image",
+            "source": {
+                "type": "base64",
+                "media_type": image.media_type,
+                "data": image.data,
+            }
+        })
+    }));
+    serde_json::json!({
+        "type": "user",
+        "message": { "role": "user", "content": content }
+    })
+}
+
+/// `claude -p` with streaming output and the given options (no prompt yet)
+fn claude_command(
+    cwd: &Path,
+    mcp_config_path: Option<&str>,
+    system_prompt_file_path: Option<&str>,
+    resume_session_id: Option<&str>,
+    model: Option<&str>,
+) -> Command {
     let mut cmd = Command::new("claude");
     cmd.arg("-p")
         .arg("--verbose")
@@ -334,9 +456,21 @@ pub fn spawn_claude(
         cmd.arg("--system-prompt-file").arg(prompt_file);
     }
 
-    cmd.arg(prompt)
-        .current_dir(cwd)
-        .stdout(std::process::Stdio::piped())
+    // Resume an existing session to keep the full conversation context
+    if let Some(session_id) = resume_session_id {
+        cmd.arg("--resume").arg(session_id);
+    }
+
+    if let Some(model) = model {
+        cmd.arg("--model").arg(model);
+    }
+
+    cmd.current_dir(cwd);
+    cmd
+}
+
+fn spawn_command(cmd: &mut Command) -> Result<Child, ClaudeCliError> {
+    cmd.stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
         .spawn()
         .map_err(|e| {
@@ -348,6 +482,61 @@ pub fn spawn_claude(
         })
 }
 
+/// List models available to the Claude CLI (`claude models`).
+pub async fn list_models() -> Result<Vec<String>, ClaudeCliError> {
+    let output = Command::new("claude")
+        .arg("models")
+        .output()
+        .await
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                ClaudeCliError::NotFound
+            } else {
+                ClaudeCliError::SpawnFailed(e.to_string())
+            }
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(ClaudeCliError::ProcessError(stderr.trim().to_string()));
+    }
+
+    Ok(parse_models_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `claude models` output.
+///
+/// Accepts a JSON array (of names or `{ "id": ... }` objects) or plain text
+/// with one model per line (bullets and trailing descriptions are ignored).
+pub fn parse_models_output(output: &str) -> Vec<String> {
+    if let Ok(serde_json::Value::Array(items)) = serde_json::from_str(output.trim()) {
+        return items
+            .iter()
+            .filter_map(|item| item.as_str().or_else(|| item.get("id").and_then(|id| id.as_str())))
+            .map(|id| id.to_string())
+            .collect();
+    }
+
+    let mut models: Vec<String> = Vec::new();
+    for line in output.lines() {
+        let line = line.trim().trim_start_matches(['-', '*', '•']).trim_start();
+        // Section headers ("Available models:")
+        if line.ends_with(':') {
+            continue;
+        }
+        let Some(name) = line.split_whitespace().next() else {
+            continue;
+        };
+        let is_model_name = name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '[' | ']'));
+        if is_model_name && !models.iter().any(|m| m == name) {
+            models.push(name.to_string());
+        }
+    }
+    models
+}
+
 /// Async iterator over JSONL events from a Claude CLI process.
 pub struct ClaudeEventStream {
     reader: BufReader<tokio::process::ChildStdout>,
@@ -453,38 +642,6 @@ mod tests {
         assert!(result.is_err());
     }
 
-    #[test]
-    fn test_extract_text_delta() {
-        let event = ClaudeStreamEvent::ContentBlockDelta {
-            index: 0,
-            delta: Delta {
-                delta_type: "text_delta".to_string(),
-                text: Some("Hello world".to_string()),
-            },
-        };
-
-        assert_eq!(extract_text_delta(&event), Some("Hello world"));
-    }
-
-    #[test]
-    fn test_extract_text_delta_no_text() {
-        let event = ClaudeStreamEvent::ContentBlockDelta {
-            index: 0,
-            delta: Delta {
-                delta_type: "input_json_delta".to_string(),
-                text: None,
-            },
-        };
-
-        assert_eq!(extract_text_delta(&event), None);
-    }
-
-    #[test]
-    fn test_is_message_stop() {
-        assert!(is_message_stop(&ClaudeStreamEvent::MessageStop));
-        assert!(!is_message_stop(&ClaudeStreamEvent::Other));
-    }
-
     #[test]
     fn test_error_display() {
         let err = ClaudeCliError::NotFound;
@@ -511,6 +668,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_models_output() {
+        let text = "Available models:\n  - opus    Most capable\n  - sonnet  Balanced\n\n  claude-haiku-4-5\n";
+        assert_eq!(parse_models_output(text), vec!["opus", "sonnet", "claude-haiku-4-5"]);
+
+        let json = r#"[{"id": "opus"}, "sonnet"]"#;
+        assert_eq!(parse_models_output(json), vec!["opus", "sonnet"]);
+        assert!(parse_models_output("").is_empty());
+    }
+
     #[test]
     fn test_parse_message_delta() {
         let line = r#"{"type":"message_delta","delta":{"stop_reason":"end_turn"}}"#;
@@ -523,4 +690,17 @@ mod tests {
             _ => panic!("Expected MessageDelta"),
         }
     }
+
+    #[test]
+    fn test_user_message_with_images() {
+        let images = [ImageInput { media_type: "image/png".to_string(), data: "iVBORw0K".to_string() }];
+        let message = user_message_with_images("What is this?", &images);
+
+        assert_eq!(message["type"], "user");
+        let content = &message["message"]["content"];
+        assert_eq!(content[0]["text"], "What is this?");
+        assert_eq!(content[1]["type"], "image");
+        assert_eq!(content[1]["source"]["media_type"], "image/png");
+        assert_eq!(content[1]["source"]["data"], "iVBORw0K");
+    }
 }
@@ -0,0 +1,194 @@
+//! Registries of running Claude CLI processes: their handles (for
+//! cancellation) and their stdin (for answering permission requests).
+
+use super::{permission_response, write_json_line, PermissionDecision, PermissionRequest};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::process::{Child, ChildStdin};
+
+// ============================================================================
+// Process Registry (cancellation)
+// ============================================================================
+
+/// Running Claude CLI processes, keyed by request ID (e.g. the streaming
+/// chat message ID).
+///
+/// The streaming task registers its child after taking stdout and `take`s it
+/// back when the stream ends. `cancel` kills and removes the child, so a task
+/// that finds its entry gone knows the request was cancelled.
+#[derive(Default)]
+pub struct ClaudeProcessRegistry {
+    processes: Mutex<HashMap<String, Child>>,
+}
+
+impl ClaudeProcessRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Child>> {
+        self.processes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Track a running process under `id`
+    pub fn register(&self, id: &str, child: Child) {
+        self.lock().insert(id.to_string(), child);
+    }
+
+    /// Whether a process is still registered under `id` (false once cancelled)
+    pub fn is_running(&self, id: &str) -> bool {
+        self.lock().contains_key(id)
+    }
+
+    /// Remove and return the process (None if it was cancelled)
+    pub fn take(&self, id: &str) -> Option<Child> {
+        self.lock().remove(id)
+    }
+
+    /// Process IDs of the running processes
+    pub fn pids(&self) -> Vec<u32> {
+        self.lock().values().filter_map(Child::id).collect()
+    }
+
+    /// Kill and remove the process. Returns false if nothing was running.
+    pub fn cancel(&self, id: &str) -> bool {
+        match self.take(id) {
+            Some(mut child) => {
+                if let Err(e) = child.start_kill() {
+                    tracing::warn!("Failed to kill Claude CLI process {}: {}", id, e);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// ============================================================================
+// Permission Bridge (tool approvals)
+// ============================================================================
+
+/// Stdin of interactive Claude CLI processes and their open permission
+/// requests, so a decision made in the UI reaches the process that asked.
+///
+/// The streaming task registers the stdin under its request ID and `track`s
+/// each permission request it sees; `close` drops the stdin (ending the
+/// input stream) together with the requests still waiting.
+#[derive(Default)]
+pub struct PermissionBridge {
+    inputs: Mutex<HashMap<String, ChildStdin>>,
+    /// Permission request ID -> (process ID, request)
+    requests: Mutex<HashMap<String, (String, PermissionRequest)>>,
+}
+
+impl PermissionBridge {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep the stdin of the process running under `id`
+    pub fn register(&self, id: &str, stdin: ChildStdin) {
+        self.inputs.lock().unwrap_or_else(|e| e.into_inner()).insert(id.to_string(), stdin);
+    }
+
+    /// Remember a permission request from the process running under `id`
+    pub fn track(&self, id: &str, request: PermissionRequest) {
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(request.request_id.clone(), (id.to_string(), request));
+    }
+
+    /// Whether a permission request is waiting for an answer
+    pub fn is_pending(&self, request_id: &str) -> bool {
+        self.requests.lock().unwrap_or_else(|e| e.into_inner()).contains_key(request_id)
+    }
+
+    /// Send the decision for a permission request to its process
+    pub async fn respond(&self, request_id: &str, decision: &PermissionDecision) -> Result<(), String> {
+        let (id, request) = self
+            .requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(request_id)
+            .ok_or_else(|| format!("No pending permission request {}", request_id))?;
+        // Taken out of the map while writing (the lock can't be held across await)
+        let mut stdin = self
+            .inputs
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&id)
+            .ok_or_else(|| "Claude CLI is no longer running".to_string())?;
+        let result = write_json_line(&mut stdin, &permission_response(&request, decision))
+            .await
+            .map_err(|e| format!("Failed to answer Claude CLI: {}", e));
+        self.register(&id, stdin);
+        result
+    }
+
+    /// Close the stdin of `id` and drop its unanswered requests
+    pub fn close(&self, id: &str) {
+        self.inputs.lock().unwrap_or_else(|e| e.into_inner()).remove(id);
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|_, (process_id, _)| process_id != id);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+    use tokio::process::Command;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_process_registry_cancel() {
+        let registry = ClaudeProcessRegistry::new();
+        let child = Command::new("sleep").arg("30").spawn().unwrap();
+        registry.register("assistant-1", child);
+        assert!(registry.is_running("assistant-1"));
+
+        assert!(registry.cancel("assistant-1"));
+        assert!(!registry.is_running("assistant-1"));
+        assert!(registry.take("assistant-1").is_none());
+        assert!(!registry.cancel("assistant-1"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_permission_bridge_respond() {
+        let mut child = Command::new("cat")
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        let bridge = PermissionBridge::new();
+        bridge.register("assistant-1", child.stdin.take().unwrap());
+        let request = PermissionRequest {
+            request_id: "req-1".to_string(),
+            tool_name: "Write".to_string(),
+            input: serde_json::json!({"file_path": "a.txt"}),
+        };
+        bridge.track("assistant-1", request);
+        assert!(bridge.is_pending("req-1"));
+
+        bridge.respond("req-1", &PermissionDecision::Allow).await.unwrap();
+        assert!(!bridge.is_pending("req-1"));
+        assert!(bridge.respond("req-1", &PermissionDecision::Allow).await.is_err());
+
+        let mut lines = BufReader::new(child.stdout.take().unwrap()).lines();
+        let line = lines.next_line().await.unwrap().unwrap();
+        let response: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(response["response"]["request_id"], "req-1");
+
+        // Closing stdin ends the process
+        bridge.close("assistant-1");
+        assert!(child.wait().await.unwrap().success());
+    }
+}
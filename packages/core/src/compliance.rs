@@ -0,0 +1,175 @@
+//! Constitution compliance pre-check for ReviewGate sessions.
+//!
+//! Before a proposal or plan is approved, its content is sent to Claude along
+//! with the project constitution. Claude answers with a JSON report of the
+//! rules the content violates, parsed here into a [`ComplianceReport`].
+//! Blocking violations hold approval until they are fixed or overridden.
+
+use serde::Deserialize;
+
+use crate::app_state::{
+    ComplianceReport, ComplianceViolation, ReviewContent, ReviewContentType, ViolationSeverity,
+};
+
+/// Build the evaluation prompt for `content`
+pub fn build_prompt(constitution: &str, content: &ReviewContent) -> String {
+    let kind = match content.content_type {
+        ReviewContentType::Plan => "implementation plan",
+        ReviewContentType::Proposal => "proposal",
+        ReviewContentType::Code => "code change",
+        ReviewContentType::Artifact => "artifact",
+    };
+    let file_changes = if content.file_changes.is_empty() {
+        String::new()
+    } else {
+        let list = content
+            .file_changes
+            .iter()
+            .map(|fc| format!("- {:?} {}: {}", fc.action, fc.path, fc.summary))
+            .collect::<Vec<_>>()
+            .join("\n");
+        format!("\n## File Changes\n{}\n", list)
+    };
+
+    format!(
+        r#"You are a strict reviewer checking the {kind} below against the project's constitution.
+
+## Constitution
+{constitution}
+
+## {kind_title} Under Review
+{content}
+{file_changes}
+## Instructions
+List every constitution rule the {kind} violates. Only report real violations of
+rules that are written in the constitution; do not invent rules.
+
+Severity:
+- "blocking": violates a MUST / MUST NOT rule or a security rule
+- "warning": violates a SHOULD rule
+- "info": minor deviation worth mentioning
+
+Respond with ONLY a JSON object, no other text:
+{{"summary": "<one sentence>", "violations": [{{"section": "<constitution section>", "severity": "blocking" | "warning" | "info", "rule": "<the rule>", "explanation": "<what violates it>"}}]}}"#,
+        kind = kind,
+        kind_title = capitalize(kind),
+        constitution = constitution.trim(),
+        content = content.content.trim(),
+        file_changes = file_changes,
+    )
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[derive(Deserialize)]
+struct RawReport {
+    #[serde(default)]
+    summary: String,
+    #[serde(default)]
+    violations: Vec<RawViolation>,
+}
+
+#[derive(Deserialize)]
+struct RawViolation {
+    #[serde(default)]
+    section: String,
+    #[serde(default)]
+    severity: String,
+    #[serde(default)]
+    rule: String,
+    #[serde(default)]
+    explanation: String,
+}
+
+/// Parse Claude's answer into a report. Tolerates surrounding prose and
+/// code fences around the JSON object.
+pub fn parse_report(output: &str) -> Result<ComplianceReport, String> {
+    let start = output.find('{');
+    let end = output.rfind('}');
+    let json = match (start, end) {
+        (Some(start), Some(end)) if start < end => &output[start..=end],
+        _ => return Err("Compliance evaluation returned no JSON report".to_string()),
+    };
+    let raw: RawReport =
+        serde_json::from_str(json).map_err(|e| format!("Invalid compliance report: {}", e))?;
+
+    let violations = raw
+        .violations
+        .into_iter()
+        .filter(|v| !v.rule.trim().is_empty())
+        .map(|v| ComplianceViolation {
+            section: if v.section.trim().is_empty() {
+                "General".to_string()
+            } else {
+                v.section.trim().to_string()
+            },
+            severity: parse_severity(&v.severity),
+            rule: v.rule.trim().to_string(),
+            explanation: v.explanation.trim().to_string(),
+        })
+        .collect();
+
+    Ok(ComplianceReport {
+        summary: raw.summary.trim().to_string(),
+        violations,
+        evaluated_at: chrono::Utc::now().to_rfc3339(),
+    })
+}
+
+fn parse_severity(severity: &str) -> ViolationSeverity {
+    match severity.trim().to_lowercase().as_str() {
+        "blocking" | "critical" | "error" | "high" => ViolationSeverity::Blocking,
+        "warning" | "major" | "medium" => ViolationSeverity::Warning,
+        _ => ViolationSeverity::Info,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_prompt_includes_constitution_and_content() {
+        let content = ReviewContent {
+            content_type: ReviewContentType::Plan,
+            content: "1. Store the API key in config.toml".to_string(),
+            file_changes: vec![],
+        };
+        let prompt = build_prompt("## Security\n- MUST NOT commit secrets", &content);
+        assert!(prompt.contains("## Implementation plan Under Review"));
+        assert!(prompt.contains("MUST NOT commit secrets"));
+        assert!(prompt.contains("Store the API key"));
+    }
+
+    #[test]
+    fn test_parse_report() {
+        let output = r#"Here is the evaluation:
+```json
+{"summary": "Stores a secret in the repo", "violations": [
+  {"section": "Security", "severity": "blocking", "rule": "MUST NOT commit secrets", "explanation": "Step 1 writes the API key to config.toml"},
+  {"section": "", "severity": "Minor", "rule": "Keep PRs focused", "explanation": "Touches many modules"},
+  {"section": "Testing", "severity": "warning", "rule": "", "explanation": "no rule"}
+]}
+```"#;
+        let report = parse_report(output).unwrap();
+        assert_eq!(report.summary, "Stores a secret in the repo");
+        assert_eq!(report.violations.len(), 2);
+        assert_eq!(report.violations[0].severity, ViolationSeverity::Blocking);
+        assert_eq!(report.violations[1].section, "General");
+        assert_eq!(report.violations[1].severity, ViolationSeverity::Info);
+        assert_eq!(report.blocking_count(), 1);
+
+        assert!(parse_report("I could not evaluate this.").is_err());
+        assert!(parse_report("{not json}").is_err());
+    }
+}
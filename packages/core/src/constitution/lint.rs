@@ -0,0 +1,642 @@
+//! Constitution linter.
+//!
+//! Checks a worktree's constitution (modular `.rstn/constitutions/*.md` or
+//! legacy `.rstn/constitution.md`) for:
+//! - Missing required sections (code style, testing, security)
+//! - Rules that are hedged or not phrased as requirements (MUST / MUST NOT / SHOULD)
+//! - Contradictions (the same rule required in one place and forbidden in another)
+//! - Stale references to libraries that no manifest in the worktree declares
+//! - Modular rule files without frontmatter
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+/// Which check produced a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintRule {
+    MissingSection,
+    Phrasing,
+    Contradiction,
+    StaleReference,
+    Frontmatter,
+}
+
+/// A single linter finding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LintFinding {
+    pub rule: LintRule,
+    pub severity: LintSeverity,
+    /// File relative to the worktree (None = the constitution as a whole)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// 1-based line number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// Result of linting a worktree's constitution
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConstitutionLintReport {
+    /// Files that were linted (relative to the worktree)
+    pub files: Vec<String>,
+    /// Findings, most severe first
+    pub findings: Vec<LintFinding>,
+    /// When the lint ran (RFC 3339)
+    pub linted_at: String,
+}
+
+/// A constitution file to lint
+#[derive(Debug, Clone)]
+pub struct ConstitutionFile {
+    /// Path relative to the worktree
+    pub path: String,
+    pub content: String,
+    /// Part of the modular `.rstn/constitutions/` system (expects frontmatter)
+    pub modular: bool,
+}
+
+/// Sections every constitution should cover: (name, heading keywords)
+const REQUIRED_SECTIONS: &[(&str, &[&str])] = &[
+    ("Code Style", &["style", "convention", "naming"]),
+    ("Testing", &["test"]),
+    ("Security", &["security", "secret"]),
+];
+
+/// Requirement keywords (RFC 2119 style, uppercase only)
+const NORMATIVE_KEYWORDS: &[&str] = &["MUST", "SHALL", "SHOULD", "REQUIRED", "NEVER", "ALWAYS", "MAY"];
+
+/// Phrases that make a rule optional in practice
+const HEDGES: &[&str] = &[
+    "if possible",
+    "when possible",
+    "where possible",
+    "where appropriate",
+    "as needed",
+    "try to",
+    "ideally",
+    "maybe",
+    "consider",
+];
+
+/// Package ecosystems whose manifests are checked for stale references
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Ecosystem {
+    Rust,
+    Node,
+    Python,
+}
+
+impl Ecosystem {
+    fn manifest(self) -> &'static str {
+        match self {
+            Ecosystem::Rust => "Cargo.toml",
+            Ecosystem::Node => "package.json",
+            Ecosystem::Python => "pyproject.toml or requirements.txt",
+        }
+    }
+}
+
+/// Libraries a constitution may name: (mention, ecosystem, package names)
+const KNOWN_LIBRARIES: &[(&str, Ecosystem, &[&str])] = &[
+    ("thiserror", Ecosystem::Rust, &["thiserror"]),
+    ("anyhow", Ecosystem::Rust, &["anyhow"]),
+    ("tokio", Ecosystem::Rust, &["tokio"]),
+    ("serde", Ecosystem::Rust, &["serde"]),
+    ("axum", Ecosystem::Rust, &["axum"]),
+    ("actix", Ecosystem::Rust, &["actix-web"]),
+    ("diesel", Ecosystem::Rust, &["diesel"]),
+    ("sqlx", Ecosystem::Rust, &["sqlx"]),
+    ("react", Ecosystem::Node, &["react"]),
+    ("vue", Ecosystem::Node, &["vue"]),
+    ("angular", Ecosystem::Node, &["@angular/core"]),
+    ("svelte", Ecosystem::Node, &["svelte"]),
+    ("redux", Ecosystem::Node, &["redux", "@reduxjs/toolkit"]),
+    ("zustand", Ecosystem::Node, &["zustand"]),
+    ("jest", Ecosystem::Node, &["jest"]),
+    ("vitest", Ecosystem::Node, &["vitest"]),
+    ("playwright", Ecosystem::Node, &["@playwright/test", "playwright"]),
+    ("tailwind", Ecosystem::Node, &["tailwindcss"]),
+    ("pytest", Ecosystem::Python, &["pytest"]),
+    ("django", Ecosystem::Python, &["django"]),
+    ("flask", Ecosystem::Python, &["flask"]),
+    ("fastapi", Ecosystem::Python, &["fastapi"]),
+    ("pydantic", Ecosystem::Python, &["pydantic"]),
+    ("sqlalchemy", Ecosystem::Python, &["sqlalchemy"]),
+];
+
+/// Lint the constitution of the worktree at `worktree_path`
+pub fn lint_constitution(worktree_path: &Path) -> ConstitutionLintReport {
+    let files = load_files(worktree_path);
+    let findings = if files.is_empty() {
+        Vec::new()
+    } else {
+        lint_files(&files, &ManifestDependencies::scan(worktree_path))
+    };
+
+    ConstitutionLintReport {
+        files: files.into_iter().map(|f| f.path).collect(),
+        findings,
+        linted_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+/// Read the modular rule files, or the legacy constitution if there are none
+fn load_files(worktree_path: &Path) -> Vec<ConstitutionFile> {
+    let mut files = Vec::new();
+    let constitutions_dir = worktree_path.join(".rstn").join("constitutions");
+    if let Ok(entries) = std::fs::read_dir(&constitutions_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "md") {
+                if let Ok(content) = std::fs::read_to_string(&path) {
+                    files.push(ConstitutionFile {
+                        path: format!(".rstn/constitutions/{}", entry.file_name().to_string_lossy()),
+                        content,
+                        modular: true,
+                    });
+                }
+            }
+        }
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+
+    if files.is_empty() {
+        if let Ok(content) = std::fs::read_to_string(worktree_path.join(".rstn").join("constitution.md")) {
+            files.push(ConstitutionFile {
+                path: ".rstn/constitution.md".to_string(),
+                content,
+                modular: false,
+            });
+        }
+    }
+    files
+}
+
+/// Lint parsed constitution files against the worktree's declared dependencies
+pub fn lint_files(files: &[ConstitutionFile], dependencies: &ManifestDependencies) -> Vec<LintFinding> {
+    let mut findings = Vec::new();
+    let mut headings = Vec::new();
+    // subject -> (required, file, line) of the first directive seen
+    let mut directives: BTreeMap<String, (bool, String, usize)> = BTreeMap::new();
+    let mut referenced = BTreeSet::new();
+
+    for file in files {
+        let document = parse_document(&file.content);
+        if file.modular && !document.has_name {
+            findings.push(finding(
+                LintRule::Frontmatter,
+                LintSeverity::Warning,
+                Some((file, 1)),
+                "Rule file has no frontmatter with a name".to_string(),
+            ));
+        }
+        headings.extend(document.headings);
+
+        for (line, rule) in &document.rules {
+            if let Some((severity, message)) = check_phrasing(rule) {
+                findings.push(finding(LintRule::Phrasing, severity, Some((file, *line)), message));
+            }
+
+            let Some((required, subject)) = directive(rule) else {
+                continue;
+            };
+            match directives.get(&subject) {
+                Some((earlier, earlier_file, earlier_line)) if *earlier != required => {
+                    findings.push(finding(
+                        LintRule::Contradiction,
+                        LintSeverity::Error,
+                        Some((file, *line)),
+                        format!(
+                            "'{}' is {} here but {} at {}:{}",
+                            subject,
+                            if required { "required" } else { "forbidden" },
+                            if *earlier { "required" } else { "forbidden" },
+                            earlier_file,
+                            earlier_line
+                        ),
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    directives.insert(subject, (required, file.path.clone(), *line));
+                }
+            }
+        }
+
+        for (line, text) in &document.body {
+            let lower = text.to_lowercase();
+            for (mention, ecosystem, packages) in KNOWN_LIBRARIES {
+                if contains_word(&lower, mention)
+                    && !dependencies.declares(*ecosystem, packages)
+                    && referenced.insert(*mention)
+                {
+                    findings.push(finding(
+                        LintRule::StaleReference,
+                        LintSeverity::Warning,
+                        Some((file, *line)),
+                        format!("References {} but no {} in the worktree declares it", mention, ecosystem.manifest()),
+                    ));
+                }
+            }
+        }
+    }
+
+    for (section, keywords) in REQUIRED_SECTIONS {
+        if !headings.iter().any(|h| keywords.iter().any(|k| h.contains(k))) {
+            findings.push(finding(
+                LintRule::MissingSection,
+                LintSeverity::Warning,
+                None,
+                format!("Missing a '{}' section", section),
+            ));
+        }
+    }
+
+    findings.sort_by(|a, b| (a.severity, &a.file, a.line).cmp(&(b.severity, &b.file, b.line)));
+    findings
+}
+
+fn finding(rule: LintRule, severity: LintSeverity, at: Option<(&ConstitutionFile, usize)>, message: String) -> LintFinding {
+    LintFinding {
+        rule,
+        severity,
+        file: at.map(|(file, _)| file.path.clone()),
+        line: at.map(|(_, line)| line),
+        message,
+    }
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// Markdown content split into what the checks look at
+#[derive(Debug, Default)]
+struct Document {
+    /// Frontmatter declares a `name`
+    has_name: bool,
+    /// Lowercased heading text
+    headings: Vec<String>,
+    /// (line, text) of list items
+    rules: Vec<(usize, String)>,
+    /// (line, text) of every line outside frontmatter and code blocks
+    body: Vec<(usize, String)>,
+}
+
+fn parse_document(content: &str) -> Document {
+    let mut document = Document::default();
+    let mut in_frontmatter = content.starts_with("---");
+    let mut in_code_block = false;
+
+    for (index, raw) in content.lines().enumerate() {
+        let line = index + 1;
+        let text = raw.trim();
+        if in_frontmatter {
+            if line > 1 && text == "---" {
+                in_frontmatter = false;
+            } else if let Some(name) = text.strip_prefix("name:") {
+                document.has_name = !name.trim().trim_matches('"').is_empty();
+            }
+            continue;
+        }
+        if text.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block || text.is_empty() {
+            continue;
+        }
+
+        document.body.push((line, text.to_string()));
+        if text.starts_with('#') {
+            document.headings.push(text.trim_start_matches('#').trim().to_lowercase());
+        } else if let Some(item) = list_item(text) {
+            document.rules.push((line, item.to_string()));
+        }
+    }
+    document
+}
+
+/// Text of a `-`, `*`, `+` or `1.` list item
+fn list_item(line: &str) -> Option<&str> {
+    if let Some(rest) = line.strip_prefix(['-', '*', '+']) {
+        return rest.strip_prefix(' ').map(str::trim);
+    }
+    let digits = line.find(|c: char| !c.is_ascii_digit())?;
+    if digits == 0 {
+        return None;
+    }
+    line[digits..].strip_prefix(". ").map(str::trim)
+}
+
+/// Why a rule's phrasing is weak, if it is
+fn check_phrasing(rule: &str) -> Option<(LintSeverity, String)> {
+    let lower = rule.to_lowercase();
+    if let Some(hedge) = HEDGES.iter().find(|h| contains_word(&lower, h)) {
+        return Some((
+            LintSeverity::Warning,
+            format!("Rule is hedged ('{}'); state it as MUST or SHOULD", hedge),
+        ));
+    }
+    let has_keyword = rule
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| NORMATIVE_KEYWORDS.contains(&word));
+    if has_keyword {
+        None
+    } else {
+        Some((LintSeverity::Info, "Rule does not use MUST / MUST NOT / SHOULD phrasing".to_string()))
+    }
+}
+
+/// Whether a rule requires or forbids something, and what: `(required, subject)`.
+///
+/// Looks for the first modal ("must", "must not", "never", "avoid", "use", ...)
+/// and takes the rest of the rule as the subject, so "MUST use `anyhow`" and
+/// "Avoid anyhow" share the subject "anyhow".
+fn directive(rule: &str) -> Option<(bool, String)> {
+    let words: Vec<String> = rule
+        .to_lowercase()
+        .replace('`', "")
+        .split_whitespace()
+        .map(|w| w.trim_end_matches(['.', ',', ';', ':', '!']).to_string())
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    for (i, word) in words.iter().enumerate() {
+        let next = words.get(i + 1).map(String::as_str);
+        let (required, skip) = match (word.as_str(), next) {
+            ("must" | "should" | "shall" | "do" | "does", Some("not")) => (false, 2),
+            ("don't" | "never" | "avoid", _) => (false, 1),
+            ("must" | "should" | "shall" | "always" | "use" | "prefer", _) => (true, 1),
+            _ => continue,
+        };
+        let mut rest = &words[i + skip..];
+        if rest.first().is_some_and(|w| w == "use" || w == "using") {
+            rest = &rest[1..];
+        }
+        if rest.is_empty() {
+            return None;
+        }
+        return Some((required, rest.join(" ")));
+    }
+    None
+}
+
+/// Whether `phrase` occurs in `text` with no word characters on either side
+fn contains_word(text: &str, phrase: &str) -> bool {
+    let is_word = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+    text.match_indices(phrase).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + phrase.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+// ============================================================================
+// Manifests
+// ============================================================================
+
+/// Package names declared by the manifests in a worktree
+#[derive(Debug, Default)]
+pub struct ManifestDependencies {
+    packages: BTreeMap<Ecosystem, BTreeSet<String>>,
+}
+
+impl ManifestDependencies {
+    /// Collect dependencies from Cargo.toml, package.json, pyproject.toml and
+    /// requirements*.txt files in the worktree
+    pub fn scan(worktree_path: &Path) -> Self {
+        let mut dependencies = Self::default();
+        let walker = walkdir::WalkDir::new(worktree_path)
+            .max_depth(4)
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || e.file_type().is_file() || !super::stacks::should_skip_dir(&e.file_name().to_string_lossy()));
+
+        for entry in walker.filter_map(|e| e.ok()).filter(|e| e.file_type().is_file()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Ok(content) = std::fs::read_to_string(entry.path()) else {
+                continue;
+            };
+            match name.as_str() {
+                "Cargo.toml" => dependencies.add_cargo(&content),
+                "package.json" => dependencies.add_package_json(&content),
+                "pyproject.toml" => dependencies.add_pyproject(&content),
+                _ if name.starts_with("requirements") && name.ends_with(".txt") => {
+                    for line in content.lines() {
+                        dependencies.add_requirement(line);
+                    }
+                }
+                _ => {}
+            }
+        }
+        dependencies
+    }
+
+    fn declares(&self, ecosystem: Ecosystem, packages: &[&str]) -> bool {
+        self.packages
+            .get(&ecosystem)
+            .is_some_and(|declared| packages.iter().any(|p| declared.contains(*p)))
+    }
+
+    fn add(&mut self, ecosystem: Ecosystem, name: &str) {
+        let name = name.trim().to_lowercase().replace('_', "-");
+        if !name.is_empty() {
+            self.packages.entry(ecosystem).or_default().insert(name);
+        }
+    }
+
+    fn add_cargo(&mut self, content: &str) {
+        let Ok(manifest) = content.parse::<toml::Table>() else {
+            return;
+        };
+        let mut tables = vec![&manifest];
+        if let Some(workspace) = manifest.get("workspace").and_then(|w| w.as_table()) {
+            tables.push(workspace);
+        }
+        if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+            tables.extend(targets.values().filter_map(|t| t.as_table()));
+        }
+
+        for table in tables {
+            for key in ["dependencies", "dev-dependencies", "build-dependencies"] {
+                let Some(deps) = table.get(key).and_then(|d| d.as_table()) else {
+                    continue;
+                };
+                for (name, spec) in deps {
+                    self.add(Ecosystem::Rust, name);
+                    // Renamed dependency: `foo = { package = "bar" }`
+                    if let Some(package) = spec.get("package").and_then(|p| p.as_str()) {
+                        self.add(Ecosystem::Rust, package);
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_package_json(&mut self, content: &str) {
+        let Ok(manifest) = serde_json::from_str::<serde_json::Value>(content) else {
+            return;
+        };
+        for key in ["dependencies", "devDependencies", "peerDependencies", "optionalDependencies"] {
+            if let Some(deps) = manifest.get(key).and_then(|d| d.as_object()) {
+                for name in deps.keys() {
+                    self.add(Ecosystem::Node, name);
+                }
+            }
+        }
+    }
+
+    fn add_pyproject(&mut self, content: &str) {
+        let Ok(manifest) = content.parse::<toml::Table>() else {
+            return;
+        };
+        let project = manifest.get("project");
+
+        // PEP 621 / PEP 735 requirement lists
+        let mut requirements: Vec<&toml::Value> = Vec::new();
+        requirements.extend(project.and_then(|p| p.get("dependencies")));
+        if let Some(extras) = project.and_then(|p| p.get("optional-dependencies")).and_then(|g| g.as_table()) {
+            requirements.extend(extras.values());
+        }
+        if let Some(groups) = manifest.get("dependency-groups").and_then(|g| g.as_table()) {
+            requirements.extend(groups.values());
+        }
+        for requirement in requirements.iter().filter_map(|r| r.as_array()).flatten() {
+            if let Some(requirement) = requirement.as_str() {
+                self.add_requirement(requirement);
+            }
+        }
+
+        // Poetry tables
+        let poetry = manifest.get("tool").and_then(|t| t.get("poetry"));
+        let mut tables: Vec<&toml::Value> = poetry.and_then(|p| p.get("dependencies")).into_iter().collect();
+        if let Some(groups) = poetry.and_then(|p| p.get("group")).and_then(|g| g.as_table()) {
+            tables.extend(groups.values().filter_map(|g| g.get("dependencies")));
+        }
+        for name in tables.iter().filter_map(|t| t.as_table()).flat_map(|t| t.keys()) {
+            self.add(Ecosystem::Python, name);
+        }
+    }
+
+    /// Add the package from a PEP 508 requirement line (`fastapi[all]>=0.110`)
+    fn add_requirement(&mut self, line: &str) {
+        let line = line.trim();
+        if line.starts_with('#') || line.starts_with('-') {
+            return;
+        }
+        let end = line
+            .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.'))
+            .unwrap_or(line.len());
+        self.add(Ecosystem::Python, &line[..end]);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, content: &str) -> ConstitutionFile {
+        ConstitutionFile {
+            path: path.to_string(),
+            content: content.to_string(),
+            modular: true,
+        }
+    }
+
+    fn rules(findings: &[LintFinding], rule: LintRule) -> Vec<&LintFinding> {
+        findings.iter().filter(|f| f.rule == rule).collect()
+    }
+
+    #[test]
+    fn test_lint_sections_phrasing_and_frontmatter() {
+        let files = [file(
+            "global.md",
+            "# Rules\n\n## Code Style\n\n- Functions MUST be documented\n- Keep PRs small if possible\n- Tests pass before merging\n\n```\n- not a rule\n```\n",
+        )];
+        let findings = lint_files(&files, &ManifestDependencies::default());
+
+        let phrasing = rules(&findings, LintRule::Phrasing);
+        assert_eq!(phrasing.len(), 2);
+        assert_eq!(phrasing[0].severity, LintSeverity::Warning);
+        assert_eq!(phrasing[0].line, Some(6));
+        assert!(phrasing[0].message.contains("if possible"));
+        assert_eq!(phrasing[1].severity, LintSeverity::Info);
+        assert_eq!(phrasing[1].line, Some(7));
+
+        let missing: Vec<_> = rules(&findings, LintRule::MissingSection).iter().map(|f| f.message.clone()).collect();
+        assert_eq!(missing, vec!["Missing a 'Testing' section", "Missing a 'Security' section"]);
+        assert_eq!(rules(&findings, LintRule::Frontmatter).len(), 1);
+    }
+
+    #[test]
+    fn test_lint_contradictions() {
+        let files = [
+            file("global.md", "---\nname: \"Global\"\n---\n## Error Handling\n\n- MUST use `anyhow` for errors\n"),
+            file("rust.md", "---\nname: \"Rust\"\n---\n## Errors\n\n- Avoid anyhow for errors.\n- NEVER commit secrets\n"),
+        ];
+        let findings = lint_files(&files, &ManifestDependencies::default());
+
+        let contradictions = rules(&findings, LintRule::Contradiction);
+        assert_eq!(contradictions.len(), 1);
+        assert_eq!(contradictions[0].severity, LintSeverity::Error);
+        assert_eq!(contradictions[0].file.as_deref(), Some("rust.md"));
+        assert_eq!(contradictions[0].line, Some(6));
+        assert!(contradictions[0].message.contains("global.md:6"));
+        assert!(rules(&findings, LintRule::Frontmatter).is_empty());
+        // Errors sort first
+        assert_eq!(findings[0].rule, LintRule::Contradiction);
+    }
+
+    #[test]
+    fn test_lint_constitution_stale_references() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\n\n[dependencies]\nanyhow = \"1\"\nserde_json = \"1\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("requirements.txt"), "# tools\nfastapi[all]>=0.110\n").unwrap();
+        let constitutions = dir.path().join(".rstn").join("constitutions");
+        std::fs::create_dir_all(&constitutions).unwrap();
+        std::fs::write(
+            constitutions.join("rust.md"),
+            "---\nname: \"Rust\"\n---\n## Errors\n\n- MUST use `thiserror` for library errors\n- MUST use `anyhow` in binaries\n- SHOULD use FastAPI and pytest\n",
+        )
+        .unwrap();
+
+        let report = lint_constitution(dir.path());
+        assert_eq!(report.files, vec![".rstn/constitutions/rust.md"]);
+        let stale: Vec<_> = rules(&report.findings, LintRule::StaleReference)
+            .iter()
+            .map(|f| (f.line, f.message.clone()))
+            .collect();
+        assert_eq!(
+            stale,
+            vec![
+                (Some(6), "References thiserror but no Cargo.toml in the worktree declares it".to_string()),
+                (Some(8), "References pytest but no pyproject.toml or requirements.txt in the worktree declares it".to_string()),
+            ]
+        );
+
+        let empty = tempfile::tempdir().unwrap();
+        let report = lint_constitution(empty.path());
+        assert!(report.files.is_empty());
+        assert!(report.findings.is_empty());
+    }
+}
@@ -2,8 +2,18 @@
 //!
 //! Supports the KB-First architecture with `.rstn/constitutions/` directory
 //! containing multiple rule files with frontmatter metadata.
+//! Constitutions are checked for common problems by [`lint`].
+//!
+//! Built-in templates can be replaced per stack by dropping `<stack id>.md`
+//! into `~/.config/rustation/constitutions/` (e.g. `go.md`, `global.md`).
+
+pub mod lint;
+mod stacks;
 
-use std::collections::HashSet;
+pub use stacks::{
+    detect_languages, list_detected_stacks, template_for, user_templates_dir, DetectedLanguages, DetectedStack, Stack,
+    GLOBAL_STACK, STACKS,
+};
 use std::path::Path;
 
 /// Default global constitution template
@@ -228,63 +238,316 @@ token_estimate: 500
 - Lazy load routes and heavy components
 "#;
 
-/// Detected languages in a project
-#[derive(Debug, Default)]
-pub struct DetectedLanguages {
-    pub has_rust: bool,
-    pub has_typescript: bool,
-    pub has_python: bool,
-    pub has_react: bool,
-}
+/// Go language template
+pub const GO_TEMPLATE: &str = r#"---
+name: "Go Conventions"
+type: language
+language: go
+applies_to:
+  - "**/*.go"
+tags:
+  - backend
+  - systems
+priority: 50
+required: false
+token_estimate: 500
+---
 
-/// Check if a directory should be skipped during scanning
-fn should_skip_dir(name: &str) -> bool {
-    name.starts_with('.')
-        || name == "node_modules"
-        || name == "target"
-        || name == "dist"
-        || name == "build"
-        || name == "__pycache__"
-        || name == "venv"
-}
+# Go Development Rules
 
-/// Scan a directory for language-specific files
-pub fn detect_languages(project_path: &Path) -> DetectedLanguages {
-    let mut result = DetectedLanguages::default();
-    let mut extensions_found: HashSet<String> = HashSet::new();
-
-    // Walk directory (limited depth to avoid performance issues)
-    let walker = walkdir::WalkDir::new(project_path)
-        .max_depth(5)
-        .follow_links(true) // Follow symlinks for temp dirs
-        .into_iter()
-        .filter_entry(|e| {
-            // Always allow root entry and files
-            if e.depth() == 0 || e.file_type().is_file() {
-                return true;
-            }
-            // Skip hidden and non-source directories
-            let name = e.file_name().to_string_lossy();
-            !should_skip_dir(&name)
-        });
-
-    for entry in walker.filter_map(|e| e.ok()) {
-        // Collect file extensions
-        if entry.file_type().is_file() {
-            if let Some(ext) = entry.path().extension() {
-                extensions_found.insert(ext.to_string_lossy().to_lowercase());
-            }
-        }
-    }
+## Code Style
 
-    // Detect languages based on extensions
-    result.has_rust = extensions_found.contains("rs");
-    result.has_typescript = extensions_found.contains("ts") || extensions_found.contains("tsx");
-    result.has_python = extensions_found.contains("py");
-    result.has_react = extensions_found.contains("tsx") || extensions_found.contains("jsx");
+- Format all code with `gofmt` / `goimports`
+- Use `MixedCaps` names; exported names start with an uppercase letter
+- Keep packages small and named after what they provide
+- Accept interfaces, return concrete types
 
-    result
-}
+## Error Handling
+
+- Return errors as the last return value
+- Wrap errors with context using `fmt.Errorf("...: %w", err)`
+- Never ignore returned errors
+- Do not panic in library code
+
+## Concurrency
+
+- Pass `context.Context` as the first parameter of blocking calls
+- Every goroutine must have a clear way to stop
+- Protect shared state with channels or `sync` primitives
+
+## Testing
+
+- Use table-driven tests with `t.Run`
+- Run tests with `-race` in CI
+"#;
+
+/// Java language template
+pub const JAVA_TEMPLATE: &str = r#"---
+name: "Java Conventions"
+type: language
+language: java
+applies_to:
+  - "**/*.java"
+tags:
+  - backend
+  - jvm
+priority: 50
+required: false
+token_estimate: 500
+---
+
+# Java Development Rules
+
+## Code Style
+
+- Use `camelCase` for methods and variables, `PascalCase` for classes
+- One top-level class per file
+- Prefer immutable objects and `final` fields
+- Use `record` for plain data carriers
+
+## Error Handling
+
+- Throw specific exceptions, never bare `Exception`
+- Do not swallow exceptions; log or rethrow with context
+- Use try-with-resources for anything `AutoCloseable`
+
+## Null Safety
+
+- Return `Optional` instead of `null` from lookups
+- Validate arguments with `Objects.requireNonNull`
+
+## Testing
+
+- Use JUnit 5 for unit tests
+- Keep tests independent of execution order
+"#;
+
+/// Kotlin language template
+pub const KOTLIN_TEMPLATE: &str = r#"---
+name: "Kotlin Conventions"
+type: language
+language: kotlin
+applies_to:
+  - "**/*.kt"
+  - "**/*.kts"
+tags:
+  - backend
+  - android
+  - jvm
+priority: 50
+required: false
+token_estimate: 500
+---
+
+# Kotlin Development Rules
+
+## Code Style
+
+- Follow the official Kotlin coding conventions
+- Prefer `val` over `var`
+- Use `data class` for value types
+- Use expression bodies for single-expression functions
+
+## Null Safety
+
+- Avoid the `!!` operator
+- Use `?.`, `?:` and `let` to handle nullable values
+
+## Coroutines
+
+- Use structured concurrency; never use `GlobalScope`
+- Inject dispatchers instead of hard-coding them
+
+## Testing
+
+- Use `kotlin.test` or JUnit 5
+- Test coroutines with `runTest`
+"#;
+
+/// C# language template
+pub const CSHARP_TEMPLATE: &str = r#"---
+name: "C# Conventions"
+type: language
+language: csharp
+applies_to:
+  - "**/*.cs"
+tags:
+  - backend
+  - dotnet
+priority: 50
+required: false
+token_estimate: 500
+---
+
+# C# Development Rules
+
+## Code Style
+
+- Use `PascalCase` for types, methods and properties
+- Use `camelCase` for locals and parameters, `_camelCase` for private fields
+- Enable nullable reference types
+- Prefer `var` when the type is obvious
+
+## Async
+
+- Suffix async methods with `Async`
+- Never block on tasks with `.Result` or `.Wait()`
+- Pass `CancellationToken` through async call chains
+
+## Error Handling
+
+- Catch specific exceptions only
+- Dispose resources with `using`
+
+## Testing
+
+- Use xUnit or NUnit for unit tests
+- Mock dependencies through interfaces
+"#;
+
+/// Ruby language template
+pub const RUBY_TEMPLATE: &str = r#"---
+name: "Ruby Conventions"
+type: language
+language: ruby
+applies_to:
+  - "**/*.rb"
+tags:
+  - backend
+  - scripts
+priority: 50
+required: false
+token_estimate: 450
+---
+
+# Ruby Development Rules
+
+## Code Style
+
+- Follow the community Ruby style guide (enforced by RuboCop)
+- Use `snake_case` for methods and variables, `CamelCase` for classes
+- Two-space indentation
+- Add `# frozen_string_literal: true` to every file
+
+## Design
+
+- Keep methods short and intention-revealing
+- Prefer composition and modules over deep inheritance
+
+## Testing
+
+- Use RSpec or Minitest
+- Keep tests fast; stub external services
+
+## Dependencies
+
+- Commit `Gemfile.lock` for applications
+"#;
+
+/// PHP language template
+pub const PHP_TEMPLATE: &str = r#"---
+name: "PHP Conventions"
+type: language
+language: php
+applies_to:
+  - "**/*.php"
+tags:
+  - backend
+  - web
+priority: 50
+required: false
+token_estimate: 450
+---
+
+# PHP Development Rules
+
+## Code Style
+
+- Follow PSR-12 coding style
+- Autoload classes with PSR-4 via Composer
+- Add `declare(strict_types=1);` to every file
+- Type all parameters, properties and return values
+
+## Security
+
+- Use prepared statements for every database query
+- Escape all output rendered into HTML
+- Validate all request input before using it
+
+## Testing
+
+- Use PHPUnit or Pest
+- Run static analysis (PHPStan or Psalm) in CI
+"#;
+
+/// Vue framework template
+pub const VUE_TEMPLATE: &str = r#"---
+name: "Vue Patterns"
+type: language
+language: typescript
+applies_to:
+  - "**/*.vue"
+tags:
+  - frontend
+  - ui
+priority: 60
+required: false
+token_estimate: 450
+---
+
+# Vue Development Rules
+
+## Component Patterns
+
+- Use the Composition API with `<script setup>`
+- Use multi-word component names
+- Keep components small; extract logic into composables
+
+## Props and Events
+
+- Declare typed props with `defineProps`
+- Never mutate props; emit events instead
+- Declare emitted events with `defineEmits`
+
+## State
+
+- Use `computed` for derived state
+- Use Pinia for shared state
+"#;
+
+/// Svelte framework template
+pub const SVELTE_TEMPLATE: &str = r#"---
+name: "Svelte Patterns"
+type: language
+language: typescript
+applies_to:
+  - "**/*.svelte"
+tags:
+  - frontend
+  - ui
+priority: 60
+required: false
+token_estimate: 400
+---
+
+# Svelte Development Rules
+
+## Component Patterns
+
+- Use TypeScript in `<script lang="ts">` blocks
+- Keep components focused; extract shared logic into modules
+- Use snippets and slots for composition
+
+## State
+
+- Use runes (`$state`, `$derived`) for component state
+- Use stores only for state shared across components
+- Derive values instead of duplicating state
+
+## Accessibility
+
+- Fix all compiler accessibility warnings
+"#;
 
 /// Check if constitution exists (modular or legacy)
 pub fn constitution_exists(project_path: &Path) -> bool {
@@ -352,7 +615,7 @@ pub fn read_constitution(project_path: &Path) -> Option<String> {
 
         if !contents.is_empty() {
             // Sort by priority (higher first)
-            contents.sort_by(|a, b| b.0.cmp(&a.0));
+            contents.sort_by_key(|c| std::cmp::Reverse(c.0));
 
             // Combine all constitution files
             let combined = contents
@@ -393,8 +656,17 @@ fn extract_priority(content: &str) -> i32 {
     50 // Default priority
 }
 
-/// Create modular constitution files
+/// Create modular constitution files, preferring user templates
 pub async fn create_modular_constitution(project_path: &Path) -> Result<(), String> {
+    create_modular_constitution_from(project_path, user_templates_dir().as_deref()).await
+}
+
+/// Create `global.md` plus one module per detected stack, taking each
+/// template from `templates_dir` when it has one
+pub async fn create_modular_constitution_from(
+    project_path: &Path,
+    templates_dir: Option<&Path>,
+) -> Result<(), String> {
     let rstn_dir = project_path.join(".rstn");
     let constitutions_dir = rstn_dir.join("constitutions");
 
@@ -403,42 +675,18 @@ pub async fn create_modular_constitution(project_path: &Path) -> Result<(), Stri
         .await
         .map_err(|e| format!("Failed to create constitutions directory: {}", e))?;
 
-    // Detect languages
+    // Always create global.md, then one file per detected stack
     let languages = detect_languages(project_path);
+    let stacks = std::iter::once(&GLOBAL_STACK).chain(languages.stacks());
 
-    // Always create global.md
-    let global_path = constitutions_dir.join("global.md");
-    tokio::fs::write(&global_path, GLOBAL_TEMPLATE)
+    for stack in stacks {
+        let file_name = format!("{}.md", stack.id);
+        tokio::fs::write(
+            constitutions_dir.join(&file_name),
+            template_for(stack, templates_dir),
+        )
         .await
-        .map_err(|e| format!("Failed to write global.md: {}", e))?;
-
-    // Create language-specific files based on detection
-    if languages.has_rust {
-        let rust_path = constitutions_dir.join("rust.md");
-        tokio::fs::write(&rust_path, RUST_TEMPLATE)
-            .await
-            .map_err(|e| format!("Failed to write rust.md: {}", e))?;
-    }
-
-    if languages.has_typescript {
-        let ts_path = constitutions_dir.join("typescript.md");
-        tokio::fs::write(&ts_path, TYPESCRIPT_TEMPLATE)
-            .await
-            .map_err(|e| format!("Failed to write typescript.md: {}", e))?;
-    }
-
-    if languages.has_react {
-        let react_path = constitutions_dir.join("react.md");
-        tokio::fs::write(&react_path, REACT_TEMPLATE)
-            .await
-            .map_err(|e| format!("Failed to write react.md: {}", e))?;
-    }
-
-    if languages.has_python {
-        let python_path = constitutions_dir.join("python.md");
-        tokio::fs::write(&python_path, PYTHON_TEMPLATE)
-            .await
-            .map_err(|e| format!("Failed to write python.md: {}", e))?;
+        .map_err(|e| format!("Failed to write {}: {}", file_name, e))?;
     }
 
     Ok(())
@@ -449,60 +697,6 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
-    #[test]
-    fn test_detect_languages_rust() {
-        let temp_dir = TempDir::new().unwrap();
-        let src_dir = temp_dir.path().join("src");
-        std::fs::create_dir_all(&src_dir).unwrap();
-        std::fs::write(src_dir.join("main.rs"), "fn main() {}").unwrap();
-
-        let detected = detect_languages(temp_dir.path());
-        assert!(detected.has_rust);
-        assert!(!detected.has_typescript);
-        assert!(!detected.has_python);
-    }
-
-    #[test]
-    fn test_detect_languages_typescript() {
-        let temp_dir = TempDir::new().unwrap();
-        let src_dir = temp_dir.path().join("src");
-        std::fs::create_dir_all(&src_dir).unwrap();
-        std::fs::write(src_dir.join("index.ts"), "export {}").unwrap();
-        std::fs::write(src_dir.join("App.tsx"), "export default App").unwrap();
-
-        let detected = detect_languages(temp_dir.path());
-        assert!(!detected.has_rust);
-        assert!(detected.has_typescript);
-        assert!(detected.has_react);
-        assert!(!detected.has_python);
-    }
-
-    #[test]
-    fn test_detect_languages_python() {
-        let temp_dir = TempDir::new().unwrap();
-        std::fs::write(temp_dir.path().join("main.py"), "print('hello')").unwrap();
-
-        let detected = detect_languages(temp_dir.path());
-        assert!(!detected.has_rust);
-        assert!(!detected.has_typescript);
-        assert!(detected.has_python);
-    }
-
-    #[test]
-    fn test_detect_languages_mixed() {
-        let temp_dir = TempDir::new().unwrap();
-        let src_dir = temp_dir.path().join("src");
-        std::fs::create_dir_all(&src_dir).unwrap();
-        std::fs::write(src_dir.join("lib.rs"), "pub fn foo() {}").unwrap();
-        std::fs::write(src_dir.join("index.ts"), "export {}").unwrap();
-        std::fs::write(temp_dir.path().join("script.py"), "pass").unwrap();
-
-        let detected = detect_languages(temp_dir.path());
-        assert!(detected.has_rust);
-        assert!(detected.has_typescript);
-        assert!(detected.has_python);
-    }
-
     #[test]
     fn test_constitution_exists_none() {
         let temp_dir = TempDir::new().unwrap();
@@ -550,6 +744,40 @@ mod tests {
         assert!(!constitutions_dir.join("python.md").exists()); // No .py files
     }
 
+    #[tokio::test]
+    async fn test_user_templates_override_builtins() {
+        let project = TempDir::new().unwrap();
+        let templates = TempDir::new().unwrap();
+        std::fs::write(project.path().join("main.go"), "package main").unwrap();
+        std::fs::write(project.path().join("app.rb"), "puts 1").unwrap();
+        std::fs::write(templates.path().join("go.md"), "# Our Go Rules").unwrap();
+
+        let stacks = list_detected_stacks(project.path(), Some(templates.path()));
+        assert_eq!(
+            stacks,
+            vec![
+                DetectedStack {
+                    id: "go".to_string(),
+                    name: "Go".to_string(),
+                    has_user_template: true,
+                },
+                DetectedStack {
+                    id: "ruby".to_string(),
+                    name: "Ruby".to_string(),
+                    has_user_template: false,
+                },
+            ]
+        );
+
+        create_modular_constitution_from(project.path(), Some(templates.path()))
+            .await
+            .unwrap();
+        let dir = project.path().join(".rstn").join("constitutions");
+        assert_eq!(std::fs::read_to_string(dir.join("go.md")).unwrap(), "# Our Go Rules");
+        assert_eq!(std::fs::read_to_string(dir.join("ruby.md")).unwrap(), RUBY_TEMPLATE);
+        assert_eq!(std::fs::read_to_string(dir.join("global.md")).unwrap(), GLOBAL_TEMPLATE);
+    }
+
     #[test]
     fn test_read_constitution_none() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,313 @@
+//! Language and framework stacks: which constitution modules a project
+//! gets, detected from the file extensions in its tree.
+
+use super::{
+    CSHARP_TEMPLATE, GLOBAL_TEMPLATE, GO_TEMPLATE, JAVA_TEMPLATE, KOTLIN_TEMPLATE, PHP_TEMPLATE, PYTHON_TEMPLATE,
+    REACT_TEMPLATE, RUBY_TEMPLATE, RUST_TEMPLATE, SVELTE_TEMPLATE, TYPESCRIPT_TEMPLATE, VUE_TEMPLATE,
+};
+use napi_derive::napi;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// A language or framework with its own constitution module
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stack {
+    /// Module file stem in `.rstn/constitutions/` and the user template directory
+    pub id: &'static str,
+    /// Display name
+    pub name: &'static str,
+    /// Built-in template
+    pub template: &'static str,
+}
+
+/// Global rules, written for every project
+pub const GLOBAL_STACK: Stack = Stack {
+    id: "global",
+    name: "Global",
+    template: GLOBAL_TEMPLATE,
+};
+
+/// Language and framework stacks, in the order their modules are written
+pub const STACKS: &[Stack] = &[
+    Stack {
+        id: "rust",
+        name: "Rust",
+        template: RUST_TEMPLATE,
+    },
+    Stack {
+        id: "typescript",
+        name: "TypeScript",
+        template: TYPESCRIPT_TEMPLATE,
+    },
+    Stack {
+        id: "react",
+        name: "React",
+        template: REACT_TEMPLATE,
+    },
+    Stack {
+        id: "vue",
+        name: "Vue",
+        template: VUE_TEMPLATE,
+    },
+    Stack {
+        id: "svelte",
+        name: "Svelte",
+        template: SVELTE_TEMPLATE,
+    },
+    Stack {
+        id: "python",
+        name: "Python",
+        template: PYTHON_TEMPLATE,
+    },
+    Stack {
+        id: "go",
+        name: "Go",
+        template: GO_TEMPLATE,
+    },
+    Stack {
+        id: "java",
+        name: "Java",
+        template: JAVA_TEMPLATE,
+    },
+    Stack {
+        id: "kotlin",
+        name: "Kotlin",
+        template: KOTLIN_TEMPLATE,
+    },
+    Stack {
+        id: "csharp",
+        name: "C#",
+        template: CSHARP_TEMPLATE,
+    },
+    Stack {
+        id: "ruby",
+        name: "Ruby",
+        template: RUBY_TEMPLATE,
+    },
+    Stack {
+        id: "php",
+        name: "PHP",
+        template: PHP_TEMPLATE,
+    },
+];
+
+/// Detected languages in a project
+#[derive(Debug, Default)]
+pub struct DetectedLanguages {
+    pub has_rust: bool,
+    pub has_typescript: bool,
+    pub has_python: bool,
+    pub has_react: bool,
+    pub has_vue: bool,
+    pub has_svelte: bool,
+    pub has_go: bool,
+    pub has_java: bool,
+    pub has_kotlin: bool,
+    pub has_csharp: bool,
+    pub has_ruby: bool,
+    pub has_php: bool,
+}
+
+impl DetectedLanguages {
+    /// Detected stacks, in [`STACKS`] order
+    pub fn stacks(&self) -> Vec<&'static Stack> {
+        STACKS
+            .iter()
+            .filter(|stack| match stack.id {
+                "rust" => self.has_rust,
+                "typescript" => self.has_typescript,
+                "react" => self.has_react,
+                "vue" => self.has_vue,
+                "svelte" => self.has_svelte,
+                "python" => self.has_python,
+                "go" => self.has_go,
+                "java" => self.has_java,
+                "kotlin" => self.has_kotlin,
+                "csharp" => self.has_csharp,
+                "ruby" => self.has_ruby,
+                "php" => self.has_php,
+                _ => false,
+            })
+            .collect()
+    }
+}
+
+/// A stack detected in a project, as shown in the desktop UI
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedStack {
+    /// Stack id (module file stem)
+    pub id: String,
+    pub name: String,
+    /// A user template overrides the built-in one
+    pub has_user_template: bool,
+}
+
+/// User template directory (`~/.config/rustation/constitutions/`)
+pub fn user_templates_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".config").join("rustation").join("constitutions"))
+}
+
+/// Template for `stack`: `<templates_dir>/<id>.md` if present, else the built-in
+pub fn template_for(stack: &Stack, templates_dir: Option<&Path>) -> String {
+    templates_dir
+        .map(|dir| dir.join(format!("{}.md", stack.id)))
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .unwrap_or_else(|| stack.template.to_string())
+}
+
+/// Stacks detected in a project and whether a user template overrides each
+pub fn list_detected_stacks(
+    project_path: &Path,
+    templates_dir: Option<&Path>,
+) -> Vec<DetectedStack> {
+    detect_languages(project_path)
+        .stacks()
+        .into_iter()
+        .map(|stack| DetectedStack {
+            id: stack.id.to_string(),
+            name: stack.name.to_string(),
+            has_user_template: templates_dir
+                .is_some_and(|dir| dir.join(format!("{}.md", stack.id)).is_file()),
+        })
+        .collect()
+}
+
+/// Check if a directory should be skipped during scanning
+pub(super) fn should_skip_dir(name: &str) -> bool {
+    name.starts_with('.')
+        || name == "node_modules"
+        || name == "target"
+        || name == "dist"
+        || name == "build"
+        || name == "__pycache__"
+        || name == "venv"
+}
+
+/// Scan a directory for language-specific files
+pub fn detect_languages(project_path: &Path) -> DetectedLanguages {
+    let mut result = DetectedLanguages::default();
+    let mut extensions_found: HashSet<String> = HashSet::new();
+
+    // Walk directory (limited depth to avoid performance issues)
+    let walker = walkdir::WalkDir::new(project_path)
+        .max_depth(5)
+        .follow_links(true) // Follow symlinks for temp dirs
+        .into_iter()
+        .filter_entry(|e| {
+            // Always allow root entry and files
+            if e.depth() == 0 || e.file_type().is_file() {
+                return true;
+            }
+            // Skip hidden and non-source directories
+            let name = e.file_name().to_string_lossy();
+            !should_skip_dir(&name)
+        });
+
+    for entry in walker.filter_map(|e| e.ok()) {
+        // Collect file extensions
+        if entry.file_type().is_file() {
+            if let Some(ext) = entry.path().extension() {
+                extensions_found.insert(ext.to_string_lossy().to_lowercase());
+            }
+        }
+    }
+
+    // Detect languages based on extensions
+    let has = |ext: &str| extensions_found.contains(ext);
+    result.has_rust = has("rs");
+    result.has_typescript = has("ts") || has("tsx");
+    result.has_python = has("py");
+    result.has_react = has("tsx") || has("jsx");
+    result.has_vue = has("vue");
+    result.has_svelte = has("svelte");
+    result.has_go = has("go");
+    result.has_java = has("java");
+    result.has_kotlin = has("kt") || has("kts");
+    result.has_csharp = has("cs");
+    result.has_ruby = has("rb");
+    result.has_php = has("php");
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_languages_rust() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        std::fs::create_dir_all(&src_dir).unwrap();
+        std::fs::write(src_dir.join("main.rs"), "fn main() {}").unwrap();
+
+        let detected = detect_languages(temp_dir.path());
+        assert!(detected.has_rust);
+        assert!(!detected.has_typescript);
+        assert!(!detected.has_python);
+    }
+
+    #[test]
+    fn test_detect_languages_typescript() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        std::fs::create_dir_all(&src_dir).unwrap();
+        std::fs::write(src_dir.join("index.ts"), "export {}").unwrap();
+        std::fs::write(src_dir.join("App.tsx"), "export default App").unwrap();
+
+        let detected = detect_languages(temp_dir.path());
+        assert!(!detected.has_rust);
+        assert!(detected.has_typescript);
+        assert!(detected.has_react);
+        assert!(!detected.has_python);
+    }
+
+    #[test]
+    fn test_detect_languages_python() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("main.py"), "print('hello')").unwrap();
+
+        let detected = detect_languages(temp_dir.path());
+        assert!(!detected.has_rust);
+        assert!(!detected.has_typescript);
+        assert!(detected.has_python);
+    }
+
+    #[test]
+    fn test_detect_languages_mixed() {
+        let temp_dir = TempDir::new().unwrap();
+        let src_dir = temp_dir.path().join("src");
+        std::fs::create_dir_all(&src_dir).unwrap();
+        std::fs::write(src_dir.join("lib.rs"), "pub fn foo() {}").unwrap();
+        std::fs::write(src_dir.join("index.ts"), "export {}").unwrap();
+        std::fs::write(temp_dir.path().join("script.py"), "pass").unwrap();
+
+        let detected = detect_languages(temp_dir.path());
+        assert!(detected.has_rust);
+        assert!(detected.has_typescript);
+        assert!(detected.has_python);
+    }
+
+    #[test]
+    fn test_detect_languages_more_stacks() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(root.join("main.go"), "package main").unwrap();
+        std::fs::write(root.join("App.kt"), "fun main() {}").unwrap();
+        std::fs::write(root.join("Program.cs"), "class Program {}").unwrap();
+        std::fs::write(root.join("App.vue"), "<template></template>").unwrap();
+
+        let languages = detect_languages(root);
+        assert!(languages.has_go);
+        assert!(languages.has_kotlin);
+        assert!(languages.has_csharp);
+        assert!(languages.has_vue);
+        assert!(!languages.has_java);
+        assert!(!languages.has_ruby);
+
+        let ids: Vec<_> = languages.stacks().iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec!["vue", "go", "kotlin", "csharp"]);
+    }
+}
@@ -0,0 +1,414 @@
+//! Dependency summary for AI context.
+//!
+//! Parses the worktree's `Cargo.toml`, `package.json` and `pyproject.toml`
+//! into a compact name + version list, so the model knows which libraries
+//! are available without the user pasting manifests. Critical advisories
+//! from the last dependency audit are reported as active errors.
+
+use super::{ContextContent, ContextGatherer, GatheredContext};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Upper bound on dependencies listed per manifest.
+const MAX_DEPENDENCIES_PER_MANIFEST: usize = 100;
+
+/// (name, version, dev) as parsed from a manifest
+type ParsedDependency = (String, String, bool);
+
+type ManifestParser = fn(&str) -> Vec<ParsedDependency>;
+
+/// A declared dependency.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Dependency {
+    /// Manifest file name (e.g. "Cargo.toml").
+    pub manifest: String,
+    pub name: String,
+    /// Version requirement as declared ("path" / "git" for local or git sources).
+    pub version: String,
+    /// Development-only dependency.
+    pub dev: bool,
+}
+
+/// Collect dependencies from all supported manifests in `project_path`.
+pub fn collect_dependencies(project_path: &Path) -> Vec<Dependency> {
+    let parsers: [(&str, ManifestParser); 3] = [
+        ("Cargo.toml", parse_cargo_toml),
+        ("package.json", parse_package_json),
+        ("pyproject.toml", parse_pyproject_toml),
+    ];
+
+    let mut dependencies = Vec::new();
+    for (manifest, parse) in parsers {
+        let Ok(content) = std::fs::read_to_string(project_path.join(manifest)) else {
+            continue;
+        };
+        dependencies.extend(
+            parse(&content)
+                .into_iter()
+                .take(MAX_DEPENDENCIES_PER_MANIFEST)
+                .map(|(name, version, dev)| Dependency {
+                    manifest: manifest.to_string(),
+                    name,
+                    version,
+                    dev,
+                }),
+        );
+    }
+    dependencies
+}
+
+/// Version of a Cargo dependency entry (`"1.0"` or `{ version = "1.0", ... }`)
+fn cargo_version(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(version) => version.clone(),
+        toml::Value::Table(table) => {
+            if let Some(version) = table.get("version").and_then(|v| v.as_str()) {
+                version.to_string()
+            } else if table.contains_key("path") {
+                "path".to_string()
+            } else if table.contains_key("git") {
+                "git".to_string()
+            } else if table.get("workspace").and_then(|v| v.as_bool()) == Some(true) {
+                "workspace".to_string()
+            } else {
+                "*".to_string()
+            }
+        }
+        _ => "*".to_string(),
+    }
+}
+
+/// Parse `[dependencies]`, `[dev-dependencies]`, `[build-dependencies]`
+/// and `[workspace.dependencies]`.
+fn parse_cargo_toml(content: &str) -> Vec<ParsedDependency> {
+    let Ok(manifest) = content.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+
+    let sections = [
+        (manifest.get("dependencies"), false),
+        (manifest.get("build-dependencies"), false),
+        (manifest.get("workspace").and_then(|w| w.get("dependencies")), false),
+        (manifest.get("dev-dependencies"), true),
+    ];
+
+    let mut dependencies = Vec::new();
+    for (section, dev) in sections {
+        let Some(table) = section.and_then(|s| s.as_table()) else {
+            continue;
+        };
+        for (name, value) in table {
+            dependencies.push((name.clone(), cargo_version(value), dev));
+        }
+    }
+    dependencies
+}
+
+/// Parse `dependencies` and `devDependencies`.
+fn parse_package_json(content: &str) -> Vec<ParsedDependency> {
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+
+    let mut dependencies = Vec::new();
+    for (key, dev) in [("dependencies", false), ("devDependencies", true)] {
+        let Some(table) = manifest.get(key).and_then(|d| d.as_object()) else {
+            continue;
+        };
+        for (name, version) in table {
+            let version = version.as_str().unwrap_or("*").to_string();
+            dependencies.push((name.clone(), version, dev));
+        }
+    }
+    dependencies
+}
+
+/// Split a PEP 508 requirement (`requests[socks]>=2.31; python_version > "3.8"`)
+/// into name and version specifier
+fn split_requirement(requirement: &str) -> Option<(String, String)> {
+    let requirement = requirement.split(';').next()?.trim();
+    let name_end = requirement
+        .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.'))
+        .unwrap_or(requirement.len());
+    let name = &requirement[..name_end];
+    if name.is_empty() {
+        return None;
+    }
+
+    let rest = requirement[name_end..].trim_start();
+    // Skip extras
+    let rest = match rest.strip_prefix('[') {
+        Some(extras) => extras.split_once(']').map_or("", |(_, after)| after),
+        None => rest,
+    };
+    let version = rest.trim();
+    let version = if version.is_empty() { "*" } else { version };
+    Some((name.to_string(), version.to_string()))
+}
+
+/// Parse PEP 621 `[project]` dependencies and Poetry `[tool.poetry]` tables.
+fn parse_pyproject_toml(content: &str) -> Vec<ParsedDependency> {
+    let Ok(manifest) = content.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+
+    let mut dependencies = Vec::new();
+
+    if let Some(project) = manifest.get("project") {
+        let requirements = project
+            .get("dependencies")
+            .and_then(|d| d.as_array())
+            .into_iter()
+            .flatten()
+            .map(|r| (r, false));
+        let optional = project
+            .get("optional-dependencies")
+            .and_then(|d| d.as_table())
+            .into_iter()
+            .flat_map(|groups| groups.values())
+            .filter_map(|group| group.as_array())
+            .flatten()
+            .map(|r| (r, true));
+
+        for (requirement, dev) in requirements.chain(optional) {
+            if let Some((name, version)) = requirement.as_str().and_then(split_requirement) {
+                dependencies.push((name, version, dev));
+            }
+        }
+    }
+
+    if let Some(poetry) = manifest.get("tool").and_then(|t| t.get("poetry")) {
+        let main = poetry.get("dependencies").map(|d| (d, false));
+        let dev = poetry.get("dev-dependencies").map(|d| (d, true));
+        let groups = poetry
+            .get("group")
+            .and_then(|g| g.as_table())
+            .into_iter()
+            .flat_map(|groups| groups.values())
+            .filter_map(|group| group.get("dependencies"))
+            .map(|d| (d, true));
+
+        for (section, dev) in main.into_iter().chain(dev).chain(groups) {
+            let Some(table) = section.as_table() else {
+                continue;
+            };
+            for (name, value) in table {
+                // The interpreter constraint is not a library
+                if name == "python" {
+                    continue;
+                }
+                dependencies.push((name.clone(), cargo_version(value), dev));
+            }
+        }
+    }
+
+    dependencies
+}
+
+/// Format dependencies as one compact line per manifest.
+pub fn format_dependencies(dependencies: &[Dependency]) -> String {
+    let mut manifests: Vec<&str> = Vec::new();
+    for dep in dependencies {
+        if !manifests.contains(&dep.manifest.as_str()) {
+            manifests.push(&dep.manifest);
+        }
+    }
+
+    let join = |dev: bool, manifest: &str| {
+        dependencies
+            .iter()
+            .filter(|d| d.manifest == manifest && d.dev == dev)
+            .map(|d| format!("{} {}", d.name, d.version))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    manifests
+        .into_iter()
+        .map(|manifest| {
+            let (main, dev) = (join(false, manifest), join(true, manifest));
+            match (main.is_empty(), dev.is_empty()) {
+                (_, true) => format!("{}: {}", manifest, main),
+                (true, false) => format!("{} (dev): {}", manifest, dev),
+                (false, false) => format!("{}: {}\n{} (dev): {}", manifest, main, manifest, dev),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Gatherer for the project's declared dependencies.
+pub struct DependencyGatherer;
+
+impl ContextGatherer for DependencyGatherer {
+    fn name(&self) -> &'static str {
+        "dependencies"
+    }
+
+    fn gather(&self, project_path: &Path) -> GatheredContext {
+        let dependencies = collect_dependencies(project_path);
+        if dependencies.is_empty() {
+            return GatheredContext::default();
+        }
+
+        GatheredContext {
+            priority: 4, // Above directory tree, below ranked files
+            tokens: format_dependencies(&dependencies).len() / 4,
+            content: ContextContent::Dependencies(dependencies),
+        }
+    }
+}
+
+/// Gatherer for critical findings of the last dependency audit.
+pub struct AuditGatherer;
+
+impl ContextGatherer for AuditGatherer {
+    fn name(&self) -> &'static str {
+        "audit"
+    }
+
+    fn gather(&self, project_path: &Path) -> GatheredContext {
+        let Some(report) = crate::audit::load(project_path) else {
+            return GatheredContext::default();
+        };
+        let errors: Vec<String> = report.critical().map(|a| a.summary()).collect();
+        if errors.is_empty() {
+            return GatheredContext::default();
+        }
+
+        GatheredContext {
+            priority: 7, // Same as other active errors
+            tokens: errors.iter().map(|e| e.len()).sum::<usize>() / 4,
+            content: ContextContent::Errors(errors),
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_parse_cargo_toml() {
+        let content = r#"
+[package]
+name = "demo"
+
+[dependencies]
+serde = { version = "1.0", features = ["derive"] }
+tokio = "1.35"
+local = { path = "../local" }
+shared = { workspace = true }
+
+[dev-dependencies]
+tempfile = "3.10"
+"#;
+        let deps = parse_cargo_toml(content);
+        assert_eq!(
+            deps,
+            vec![
+                ("local".to_string(), "path".to_string(), false),
+                ("serde".to_string(), "1.0".to_string(), false),
+                ("shared".to_string(), "workspace".to_string(), false),
+                ("tokio".to_string(), "1.35".to_string(), false),
+                ("tempfile".to_string(), "3.10".to_string(), true),
+            ]
+        );
+        assert!(parse_cargo_toml("not [valid").is_empty());
+    }
+
+    #[test]
+    fn test_parse_package_json() {
+        let content = r#"{"dependencies":{"react":"^18.2.0"},"devDependencies":{"vitest":"^1.0.0"}}"#;
+        assert_eq!(
+            parse_package_json(content),
+            vec![
+                ("react".to_string(), "^18.2.0".to_string(), false),
+                ("vitest".to_string(), "^1.0.0".to_string(), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_pyproject_toml() {
+        let content = r#"
+[project]
+dependencies = ["requests[socks]>=2.31; python_version > '3.8'", "rich"]
+
+[project.optional-dependencies]
+test = ["pytest>=8"]
+
+[tool.poetry.dependencies]
+python = "^3.11"
+fastapi = "^0.110"
+"#;
+        assert_eq!(
+            parse_pyproject_toml(content),
+            vec![
+                ("requests".to_string(), ">=2.31".to_string(), false),
+                ("rich".to_string(), "*".to_string(), false),
+                ("pytest".to_string(), ">=8".to_string(), true),
+                ("fastapi".to_string(), "^0.110".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dependency_gatherer_formats_manifests() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[dependencies]\nanyhow = \"1.0\"\n").unwrap();
+        fs::write(dir.path().join("package.json"), r#"{"devDependencies":{"typescript":"^5.3.0"}}"#).unwrap();
+
+        let gathered = DependencyGatherer.gather(dir.path());
+        let ContextContent::Dependencies(deps) = gathered.content else {
+            panic!("expected dependencies");
+        };
+        assert_eq!(
+            format_dependencies(&deps),
+            "Cargo.toml: anyhow 1.0\npackage.json (dev): typescript ^5.3.0"
+        );
+
+        let empty = tempdir().unwrap();
+        assert!(matches!(DependencyGatherer.gather(empty.path()).content, ContextContent::Empty));
+    }
+
+    #[test]
+    fn test_audit_gatherer_reports_critical_advisories() {
+        use crate::audit::{Advisory, AuditReport, AuditTool, Severity};
+
+        let dir = tempdir().unwrap();
+        assert!(matches!(AuditGatherer.gather(dir.path()).content, ContextContent::Empty));
+
+        let advisory = |id: &str, severity| Advisory {
+            id: id.to_string(),
+            tool: AuditTool::Npm,
+            package: "lodash".to_string(),
+            installed_version: None,
+            severity,
+            title: "Prototype Pollution".to_string(),
+            fix_version: Some("4.17.21".to_string()),
+            url: None,
+        };
+        let report = AuditReport {
+            advisories: vec![advisory("GHSA-1", Severity::Critical), advisory("GHSA-2", Severity::High)],
+            tools: vec![AuditTool::Npm],
+            errors: Vec::new(),
+            ran_at: "now".to_string(),
+        };
+        crate::audit::save(dir.path(), &report).unwrap();
+
+        let ContextContent::Errors(errors) = AuditGatherer.gather(dir.path()).content else {
+            panic!("expected errors");
+        };
+        assert_eq!(
+            errors,
+            vec!["Critical vulnerability GHSA-1 in lodash: Prototype Pollution (fixed in 4.17.21)"]
+        );
+    }
+}
@@ -0,0 +1,497 @@
+//! Gatherers for the project's own state: git, open files, Docker errors,
+//! build diagnostics, the directory tree and terminal output.
+
+use super::{ContextContent, ContextGatherer, FileContext, GatheredContext};
+use std::path::Path;
+use std::process::Command;
+
+// ============================================================================
+// Git Gatherer
+// ============================================================================
+
+/// Gatherer for git status and diff.
+pub struct GitGatherer;
+
+impl ContextGatherer for GitGatherer {
+    fn name(&self) -> &'static str {
+        "git"
+    }
+
+    fn gather(&self, project_path: &Path) -> GatheredContext {
+        let status = get_git_status(project_path);
+        let diff = get_git_diff(project_path);
+
+        let combined = format!("{}\n\n{}", status, diff);
+        let tokens = combined.len() / 4;
+
+        GatheredContext {
+            priority: 8, // High priority
+            tokens,
+            content: ContextContent::GitStatus(combined),
+        }
+    }
+}
+
+/// Get git status for a project.
+fn get_git_status(project_path: &Path) -> String {
+    let output = Command::new("git")
+        .args(["status", "--short", "--branch"])
+        .current_dir(project_path)
+        .output();
+
+    match output {
+        Ok(out) if out.status.success() => {
+            String::from_utf8_lossy(&out.stdout).trim().to_string()
+        }
+        _ => String::new(),
+    }
+}
+
+/// Get git diff (unstaged changes).
+fn get_git_diff(project_path: &Path) -> String {
+    let output = Command::new("git")
+        .args(["diff", "--stat"])
+        .current_dir(project_path)
+        .output();
+
+    match output {
+        Ok(out) if out.status.success() => {
+            let diff = String::from_utf8_lossy(&out.stdout).trim().to_string();
+            // Limit diff size to prevent token explosion
+            if diff.len() > 2000 {
+                format!("{}...\n(truncated)", &diff[..2000])
+            } else {
+                diff
+            }
+        }
+        _ => String::new(),
+    }
+}
+
+// ============================================================================
+// File Gatherer
+// ============================================================================
+
+/// Content of a file within `max_size` chars. Larger files are replaced by
+/// their signatures-only outline when the language is supported and the
+/// outline fits, and truncated otherwise.
+pub(super) fn fit_file_content(path: &str, content: String, max_size: usize) -> String {
+    if content.len() <= max_size {
+        return content;
+    }
+    let outline = crate::symbols::shared_cache()
+        .symbols_for_file(Path::new(path))
+        .ok()
+        .filter(|symbols| !symbols.is_empty())
+        .map(|symbols| crate::symbols::outline(&symbols));
+    if let Some(outline) = outline.filter(|o| o.len() <= max_size) {
+        return format!("(signatures only, {} chars in full)\n{}", content.len(), outline);
+    }
+    let cut = (0..=max_size).rev().find(|i| content.is_char_boundary(*i)).unwrap_or(0);
+    format!("{}...\n(truncated, {} more chars)", &content[..cut], content.len() - cut)
+}
+
+/// Gatherer for active/open files.
+pub struct FileGatherer {
+    /// Paths to files to include.
+    pub file_paths: Vec<String>,
+    /// Maximum content size per file (in chars).
+    pub max_file_size: usize,
+}
+
+impl Default for FileGatherer {
+    fn default() -> Self {
+        Self {
+            file_paths: Vec::new(),
+            max_file_size: 10000, // ~2500 tokens per file max
+        }
+    }
+}
+
+impl ContextGatherer for FileGatherer {
+    fn name(&self) -> &'static str {
+        "files"
+    }
+
+    fn gather(&self, _project_path: &Path) -> GatheredContext {
+        let mut files = Vec::new();
+        let mut total_tokens = 0;
+
+        for path in &self.file_paths {
+            if let Ok(content) = std::fs::read_to_string(path) {
+                let truncated = fit_file_content(path, content, self.max_file_size);
+
+                total_tokens += (path.len() + truncated.len()) / 4;
+
+                files.push(FileContext {
+                    path: path.clone(),
+                    content: truncated,
+                    cursor_line: None,
+                });
+            }
+        }
+
+        GatheredContext {
+            priority: 10, // Highest priority
+            tokens: total_tokens,
+            content: ContextContent::Files(files),
+        }
+    }
+}
+
+// ============================================================================
+// Docker Gatherer
+// ============================================================================
+
+/// Gatherer for Docker errors and status.
+#[derive(Default)]
+pub struct DockerGatherer {
+    /// Container logs to include (container_id -> log lines).
+    pub error_logs: Vec<String>,
+}
+
+impl ContextGatherer for DockerGatherer {
+    fn name(&self) -> &'static str {
+        "docker"
+    }
+
+    fn gather(&self, _project_path: &Path) -> GatheredContext {
+        if self.error_logs.is_empty() {
+            return GatheredContext::default();
+        }
+
+        let tokens = self.error_logs.iter().map(|s| s.len()).sum::<usize>() / 4;
+
+        GatheredContext {
+            priority: 7, // High priority for errors
+            tokens,
+            content: ContextContent::Errors(self.error_logs.clone()),
+        }
+    }
+}
+
+// ============================================================================
+// Build Diagnostics Gatherer
+// ============================================================================
+
+/// Gatherer for compiler errors of the last build check.
+pub struct BuildDiagnosticsGatherer;
+
+impl ContextGatherer for BuildDiagnosticsGatherer {
+    fn name(&self) -> &'static str {
+        "build_diagnostics"
+    }
+
+    fn gather(&self, project_path: &Path) -> GatheredContext {
+        let errors = crate::build_diagnostics::load(project_path)
+            .map(|result| result.context_errors())
+            .unwrap_or_default();
+        if errors.is_empty() {
+            return GatheredContext::default();
+        }
+
+        GatheredContext {
+            priority: 7, // Same as other active errors
+            tokens: errors.iter().map(|e| e.len()).sum::<usize>() / 4,
+            content: ContextContent::Errors(errors),
+        }
+    }
+}
+
+// ============================================================================
+// Directory Tree Gatherer
+// ============================================================================
+
+/// Gatherer for directory structure.
+pub struct DirectoryGatherer {
+    /// Maximum depth to traverse.
+    pub max_depth: usize,
+}
+
+impl Default for DirectoryGatherer {
+    fn default() -> Self {
+        Self { max_depth: 2 }
+    }
+}
+
+impl ContextGatherer for DirectoryGatherer {
+    fn name(&self) -> &'static str {
+        "directory"
+    }
+
+    fn gather(&self, project_path: &Path) -> GatheredContext {
+        let tree = build_directory_tree(project_path, self.max_depth);
+        let tokens = tree.len() / 4;
+
+        GatheredContext {
+            priority: 3, // Low priority
+            tokens,
+            content: ContextContent::DirectoryTree(tree),
+        }
+    }
+}
+
+/// Build a directory tree string.
+fn build_directory_tree(path: &Path, max_depth: usize) -> String {
+    let mut result = String::new();
+    build_tree_recursive(path, "", max_depth, 0, &mut result);
+    result
+}
+
+fn build_tree_recursive(
+    path: &Path,
+    prefix: &str,
+    max_depth: usize,
+    current_depth: usize,
+    result: &mut String,
+) {
+    if current_depth > max_depth {
+        return;
+    }
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string());
+
+    if path.is_dir() {
+        result.push_str(&format!("{}{}/\n", prefix, name));
+
+        // Skip common non-essential directories
+        let skip_dirs = [
+            "node_modules",
+            ".git",
+            "target",
+            "dist",
+            "build",
+            ".next",
+            "__pycache__",
+            ".venv",
+            "venv",
+        ];
+
+        if skip_dirs.contains(&name.as_str()) {
+            return;
+        }
+
+        if let Ok(entries) = std::fs::read_dir(path) {
+            let mut entries: Vec<_> = entries.filter_map(|e| e.ok()).collect();
+            entries.sort_by_key(|e| e.file_name());
+
+            for entry in entries.iter().take(20) {
+                // Limit entries per directory
+                let child_prefix = format!("{}  ", prefix);
+                build_tree_recursive(
+                    &entry.path(),
+                    &child_prefix,
+                    max_depth,
+                    current_depth + 1,
+                    result,
+                );
+            }
+
+            if entries.len() > 20 {
+                result.push_str(&format!("{}  ... and {} more\n", prefix, entries.len() - 20));
+            }
+        }
+    } else {
+        result.push_str(&format!("{}{}\n", prefix, name));
+    }
+}
+
+// ============================================================================
+// Terminal Output Gatherer
+// ============================================================================
+
+/// Gatherer for last terminal/task output.
+pub struct TerminalGatherer {
+    /// Last output from task execution.
+    pub last_output: Option<String>,
+    /// Maximum output size.
+    pub max_size: usize,
+}
+
+impl Default for TerminalGatherer {
+    fn default() -> Self {
+        Self {
+            last_output: None,
+            max_size: 2000,
+        }
+    }
+}
+
+impl ContextGatherer for TerminalGatherer {
+    fn name(&self) -> &'static str {
+        "terminal"
+    }
+
+    fn gather(&self, _project_path: &Path) -> GatheredContext {
+        let output = match &self.last_output {
+            Some(o) if !o.is_empty() => {
+                if o.len() > self.max_size {
+                    format!(
+                        "...{}\n(showing last {} chars)",
+                        &o[o.len() - self.max_size..],
+                        self.max_size
+                    )
+                } else {
+                    o.clone()
+                }
+            }
+            _ => return GatheredContext::default(),
+        };
+
+        let tokens = output.len() / 4;
+
+        GatheredContext {
+            priority: 6, // Medium-high priority
+            tokens,
+            content: ContextContent::TerminalOutput(output),
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_directory_gatherer() {
+        let dir = tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        fs::write(src.join("main.rs"), "fn main() {}").unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+
+        let gatherer = DirectoryGatherer { max_depth: 2 };
+        let result = gatherer.gather(dir.path());
+
+        assert!(result.priority > 0);
+        if let ContextContent::DirectoryTree(tree) = result.content {
+            assert!(tree.contains("src/"));
+            assert!(tree.contains("Cargo.toml"));
+        } else {
+            panic!("Expected DirectoryTree content");
+        }
+    }
+
+    #[test]
+    fn test_file_gatherer() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test.rs");
+        fs::write(&file_path, "fn test() {}").unwrap();
+
+        let gatherer = FileGatherer {
+            file_paths: vec![file_path.to_string_lossy().to_string()],
+            max_file_size: 1000,
+        };
+        let result = gatherer.gather(dir.path());
+
+        assert_eq!(result.priority, 10); // Highest priority
+        if let ContextContent::Files(files) = result.content {
+            assert_eq!(files.len(), 1);
+            assert!(files[0].content.contains("fn test()"));
+        } else {
+            panic!("Expected Files content");
+        }
+    }
+
+    #[test]
+    fn test_file_gatherer_truncation() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("large.txt");
+        let large_content = "x".repeat(20000);
+        fs::write(&file_path, &large_content).unwrap();
+
+        let gatherer = FileGatherer {
+            file_paths: vec![file_path.to_string_lossy().to_string()],
+            max_file_size: 100,
+        };
+        let result = gatherer.gather(dir.path());
+
+        if let ContextContent::Files(files) = result.content {
+            assert!(files[0].content.len() < 200);
+            assert!(files[0].content.contains("truncated"));
+        } else {
+            panic!("Expected Files content");
+        }
+    }
+
+    #[test]
+    fn test_file_gatherer_outlines_large_source_files() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("large.rs");
+        let body = "    let _ = 1;\n".repeat(200);
+        fs::write(&file_path, format!("pub fn first(a: u32) -> u32 {{\n{body}    a\n}}\n\nstruct Second;\n")).unwrap();
+
+        let gatherer = FileGatherer {
+            file_paths: vec![file_path.to_string_lossy().to_string()],
+            max_file_size: 500,
+        };
+        let ContextContent::Files(files) = gatherer.gather(dir.path()).content else {
+            panic!("Expected Files content");
+        };
+        assert!(files[0].content.starts_with("(signatures only"));
+        assert!(files[0].content.contains("    1: pub fn first(a: u32) -> u32\n"));
+        assert!(files[0].content.contains("struct Second;"));
+        assert!(!files[0].content.contains("let _"));
+    }
+
+    #[test]
+    fn test_docker_gatherer() {
+        let dir = tempdir().unwrap();
+        let gatherer = DockerGatherer {
+            error_logs: vec!["Error: connection refused".to_string()],
+        };
+        let result = gatherer.gather(dir.path());
+
+        assert_eq!(result.priority, 7);
+        if let ContextContent::Errors(errors) = result.content {
+            assert_eq!(errors.len(), 1);
+            assert!(errors[0].contains("connection refused"));
+        } else {
+            panic!("Expected Errors content");
+        }
+    }
+
+    #[test]
+    fn test_build_diagnostics_gatherer() {
+        use crate::build_diagnostics::{BuildChecker, BuildDiagnostics};
+        use std::time::Duration;
+
+        let dir = tempdir().unwrap();
+        assert!(matches!(BuildDiagnosticsGatherer.gather(dir.path()).content, ContextContent::Empty));
+
+        let output = r#"{"reason":"compiler-message","message":{"code":{"code":"E0425"},"level":"error","message":"cannot find value `x` in this scope","spans":[{"file_name":"src/lib.rs","is_primary":true,"line_start":4,"column_start":5}]}}"#;
+        let result = BuildDiagnostics::new(Some(BuildChecker::Cargo), BuildChecker::Cargo.parse(output), Duration::ZERO);
+        crate::build_diagnostics::save(dir.path(), &result).unwrap();
+
+        let ContextContent::Errors(errors) = BuildDiagnosticsGatherer.gather(dir.path()).content else {
+            panic!("Expected Errors content");
+        };
+        assert_eq!(errors, vec!["src/lib.rs:4:5: error[E0425]: cannot find value `x` in this scope"]);
+    }
+
+    #[test]
+    fn test_terminal_gatherer() {
+        let dir = tempdir().unwrap();
+        let gatherer = TerminalGatherer {
+            last_output: Some("Build successful".to_string()),
+            max_size: 1000,
+        };
+        let result = gatherer.gather(dir.path());
+
+        assert_eq!(result.priority, 6);
+        if let ContextContent::TerminalOutput(output) = result.content {
+            assert!(output.contains("Build successful"));
+        } else {
+            panic!("Expected TerminalOutput content");
+        }
+    }
+}
@@ -0,0 +1,539 @@
+//! Incremental file index for context ranking.
+//!
+//! Keeps per-worktree file metadata (path, size, language, symbols, term
+//! counts) in memory so ranking a prompt only re-reads files that changed
+//! since the last build. A `notify` watcher marks changed paths dirty; they
+//! are re-indexed lazily on the next `refresh`. Terms and symbols are
+//! extracted here too, so queries are tokenized the same way as files.
+
+use ignore::gitignore::Gitignore;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+
+/// Extensions considered for relevance ranking.
+const RANKABLE_EXTENSIONS: &[&str] = &[
+    "rs", "ts", "tsx", "js", "jsx", "py", "go", "java", "kt", "swift", "rb", "c", "h", "cpp",
+    "hpp", "cs", "md", "toml", "json", "yaml", "yml", "sql", "sh", "css", "html", "vue", "svelte",
+];
+
+/// Files larger than this are not ranked (likely generated or data files).
+const MAX_RANKABLE_FILE_SIZE: u64 = 200_000;
+
+/// Upper bound on files scanned per ranking pass.
+const MAX_RANKED_CANDIDATES: usize = 5000;
+
+/// Common words that carry no signal for ranking.
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "this", "that", "from", "into", "what", "how", "why", "does",
+    "can", "you", "please", "add", "fix", "make", "use", "file", "code", "function", "should",
+];
+
+/// Keywords that introduce a named definition.
+const SYMBOL_KEYWORDS: &[&str] = &[
+    "fn", "struct", "enum", "trait", "type", "mod", "const", "static", "impl", "class",
+    "interface", "function", "def", "func", "let", "var",
+];
+
+/// Split text into lowercase search terms (splits camelCase and snake_case).
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        if word.is_empty() {
+            continue;
+        }
+        let mut part = String::new();
+        let mut prev_lower = false;
+        for c in word.chars() {
+            if c.is_uppercase() && prev_lower && !part.is_empty() {
+                terms.push(std::mem::take(&mut part).to_lowercase());
+            }
+            prev_lower = c.is_lowercase() || c.is_ascii_digit();
+            part.push(c);
+        }
+        let part = part.to_lowercase();
+        let whole = word.to_lowercase();
+        if whole != part {
+            terms.push(whole);
+        }
+        terms.push(part);
+    }
+    terms.retain(|t| t.chars().count() >= 3 && !STOPWORDS.contains(&t.as_str()));
+    terms
+}
+
+/// Extract names introduced by definitions (`fn foo`, `class Bar`, `export const baz`).
+pub fn extract_symbols(content: &str) -> Vec<String> {
+    let mut symbols = Vec::new();
+    for line in content.lines() {
+        let mut words = line
+            .trim_start()
+            .split(|c: char| c.is_whitespace() || c == '(' || c == '<' || c == ':' || c == '=' || c == '{')
+            .filter(|w| !w.is_empty())
+            .skip_while(|w| matches!(*w, "pub" | "pub(crate)" | "export" | "default" | "async" | "unsafe" | "abstract"));
+        let (Some(keyword), Some(name)) = (words.next(), words.next()) else {
+            continue;
+        };
+        if SYMBOL_KEYWORDS.contains(&keyword)
+            && name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+        {
+            symbols.push(name.to_string());
+        }
+    }
+    symbols
+}
+
+/// Indexed metadata for one project file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedFile {
+    /// Absolute path.
+    pub path: String,
+    /// Path relative to the index root.
+    pub relative_path: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// Language name derived from the extension.
+    pub language: String,
+    /// Names of definitions in the file.
+    pub symbols: Vec<String>,
+    /// Content term -> occurrences
+    pub(super) term_counts: HashMap<String, usize>,
+    pub(super) path_terms: HashSet<String>,
+    pub(super) symbol_terms: HashSet<String>,
+}
+
+impl IndexedFile {
+    fn new(path: &Path, relative_path: &str, size: u64, content: &str) -> Self {
+        let mut term_counts = HashMap::new();
+        for term in tokenize(content) {
+            *term_counts.entry(term).or_insert(0) += 1;
+        }
+        let symbols = extract_symbols(content);
+        Self {
+            path: path.to_string_lossy().to_string(),
+            relative_path: relative_path.to_string(),
+            size,
+            language: language_for_path(path).to_string(),
+            symbol_terms: symbols.iter().flat_map(|s| tokenize(s)).collect(),
+            symbols,
+            term_counts,
+            path_terms: tokenize(relative_path).into_iter().collect(),
+        }
+    }
+}
+
+/// Language name for a file, from its extension.
+fn language_for_path(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()).unwrap_or("") {
+        "rs" => "rust",
+        "ts" | "tsx" => "typescript",
+        "js" | "jsx" => "javascript",
+        "py" => "python",
+        "go" => "go",
+        "java" => "java",
+        "kt" => "kotlin",
+        "swift" => "swift",
+        "rb" => "ruby",
+        "c" | "h" => "c",
+        "cpp" | "hpp" => "cpp",
+        "cs" => "csharp",
+        "md" => "markdown",
+        "toml" => "toml",
+        "json" => "json",
+        "yaml" | "yml" => "yaml",
+        "sql" => "sql",
+        "sh" => "shell",
+        "css" => "css",
+        "html" => "html",
+        "vue" => "vue",
+        "svelte" => "svelte",
+        _ => "text",
+    }
+}
+
+fn has_rankable_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| RANKABLE_EXTENSIONS.contains(&ext))
+}
+
+/// Walk `dir` for rankable files (respects .gitignore, skips hidden files).
+fn walk_rankable(dir: &Path) -> impl Iterator<Item = PathBuf> {
+    ignore::WalkBuilder::new(dir)
+        .require_git(false)
+        .build()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .map(|e| e.into_path())
+        .filter(|p| has_rankable_extension(p))
+}
+
+/// In-memory index of a project's rankable files.
+#[derive(Debug)]
+pub struct FileIndex {
+    root: PathBuf,
+    /// Canonical root, used to map watcher paths (which may be resolved
+    /// through symlinks) back under `root`
+    canonical_root: PathBuf,
+    files: HashMap<PathBuf, IndexedFile>,
+    /// Paths changed since the last refresh
+    dirty: HashSet<PathBuf>,
+    /// Whether the next refresh must re-walk the whole tree
+    needs_rescan: bool,
+    /// Root .gitignore rules, for paths reported by the watcher
+    gitignore: Gitignore,
+}
+
+impl FileIndex {
+    /// Create an empty index; the first `refresh` walks the tree.
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            canonical_root: root.canonicalize().unwrap_or_else(|_| root.to_path_buf()),
+            files: HashMap::new(),
+            dirty: HashSet::new(),
+            needs_rescan: true,
+            gitignore: Gitignore::empty(),
+        }
+    }
+
+    /// Create and populate an index.
+    pub fn build(root: &Path) -> Self {
+        let mut index = Self::new(root);
+        index.refresh();
+        index
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&IndexedFile> {
+        self.files.get(path)
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = &IndexedFile> {
+        self.files.values()
+    }
+
+    /// Whether changes are waiting for the next `refresh`
+    pub fn is_stale(&self) -> bool {
+        self.needs_rescan || !self.dirty.is_empty()
+    }
+
+    /// Force a full re-walk on the next refresh (e.g. watcher overflow).
+    pub fn mark_rescan(&mut self) {
+        self.needs_rescan = true;
+    }
+
+    /// Mark a changed path (file or directory) for re-indexing.
+    pub fn invalidate(&mut self, path: &Path) {
+        let path = match path.strip_prefix(&self.canonical_root) {
+            Ok(relative) => self.root.join(relative),
+            Err(_) => path.to_path_buf(),
+        };
+        let Ok(relative) = path.strip_prefix(&self.root) else {
+            return;
+        };
+
+        // .git internals and other hidden paths are never indexed
+        let hidden = relative
+            .components()
+            .any(|c| c.as_os_str().to_string_lossy().starts_with('.'));
+        if relative.file_name().is_some_and(|name| name == ".gitignore") {
+            self.needs_rescan = true;
+        } else if !hidden {
+            self.dirty.insert(path);
+        }
+    }
+
+    /// Bring the index up to date. Returns the number of files (re)indexed.
+    ///
+    /// Only dirty paths are re-read unless a full rescan is pending.
+    pub fn refresh(&mut self) -> usize {
+        if self.needs_rescan {
+            return self.rescan();
+        }
+
+        let mut indexed = 0;
+        for path in std::mem::take(&mut self.dirty) {
+            if !path.exists() {
+                // Deleted file, or directory with everything under it
+                self.files.retain(|p, _| !p.starts_with(&path));
+            } else if path.is_dir() {
+                if !self.is_ignored(&path, true) {
+                    for file in walk_rankable(&path) {
+                        indexed += usize::from(self.index_file(file));
+                    }
+                }
+            } else {
+                self.files.remove(&path);
+                if has_rankable_extension(&path) && !self.is_ignored(&path, false) {
+                    indexed += usize::from(self.index_file(path));
+                }
+            }
+        }
+        indexed
+    }
+
+    fn rescan(&mut self) -> usize {
+        self.needs_rescan = false;
+        self.dirty.clear();
+        self.files.clear();
+        self.gitignore = Gitignore::new(self.root.join(".gitignore")).0;
+        for file in walk_rankable(&self.root) {
+            self.index_file(file);
+        }
+        self.files.len()
+    }
+
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        path.strip_prefix(&self.root)
+            .map(|relative| self.gitignore.matched_path_or_any_parents(relative, is_dir).is_ignore())
+            .unwrap_or(true)
+    }
+
+    /// Read and index one file. Returns false if it was skipped.
+    fn index_file(&mut self, path: PathBuf) -> bool {
+        if self.files.len() >= MAX_RANKED_CANDIDATES && !self.files.contains_key(&path) {
+            return false;
+        }
+        let Ok(metadata) = std::fs::metadata(&path) else {
+            return false;
+        };
+        if metadata.len() > MAX_RANKABLE_FILE_SIZE {
+            return false;
+        }
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return false;
+        };
+        let relative = path.strip_prefix(&self.root).unwrap_or(&path).to_string_lossy().to_string();
+        let file = IndexedFile::new(&path, &relative, metadata.len(), &content);
+        self.files.insert(path, file);
+        true
+    }
+}
+
+/// A worktree index and the watcher keeping it current.
+struct WatchedIndex {
+    index: Arc<Mutex<FileIndex>>,
+    /// Dropping the watcher stops it
+    watcher: Option<RecommendedWatcher>,
+}
+
+/// Owns one watched `FileIndex` per worktree.
+#[derive(Default)]
+pub struct FileIndexer {
+    indexes: Mutex<HashMap<PathBuf, WatchedIndex>>,
+}
+
+/// Lock an index, recovering from a poisoned mutex (the index is only a cache).
+pub fn lock_index(index: &Mutex<FileIndex>) -> MutexGuard<'_, FileIndex> {
+    index.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl FileIndexer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the index for a worktree, creating it on first use.
+    ///
+    /// A new index starts watching the worktree and is populated on a
+    /// background thread.
+    pub fn index_for(&self, root: &Path) -> Arc<Mutex<FileIndex>> {
+        let mut indexes = self.indexes.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(existing) = indexes.get(root) {
+            return existing.index.clone();
+        }
+
+        let index = Arc::new(Mutex::new(FileIndex::new(root)));
+        let watcher = match watch(root, Arc::downgrade(&index)) {
+            Ok(watcher) => Some(watcher),
+            Err(e) => {
+                tracing::warn!("File index for {} is not watched: {}", root.display(), e);
+                None
+            }
+        };
+
+        let background = index.clone();
+        std::thread::spawn(move || {
+            lock_index(&background).refresh();
+        });
+
+        indexes.insert(
+            root.to_path_buf(),
+            WatchedIndex {
+                index: index.clone(),
+                watcher,
+            },
+        );
+        index
+    }
+
+    /// Whether the worktree has a watched index.
+    pub fn is_watching(&self, root: &Path) -> bool {
+        let indexes = self.indexes.lock().unwrap_or_else(|e| e.into_inner());
+        indexes.get(root).is_some_and(|w| w.watcher.is_some())
+    }
+
+    /// Drop indexes (and stop watchers) for worktrees not in `live_roots`.
+    /// Returns the number of indexes dropped.
+    pub fn retain(&self, live_roots: &[String]) -> usize {
+        let mut indexes = self.indexes.lock().unwrap_or_else(|e| e.into_inner());
+        let before = indexes.len();
+        indexes.retain(|root, _| live_roots.iter().any(|live| Path::new(live) == root));
+        before - indexes.len()
+    }
+}
+
+/// Watch `root` recursively, invalidating changed paths in the index.
+fn watch(root: &Path, index: Weak<Mutex<FileIndex>>) -> notify::Result<RecommendedWatcher> {
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Some(index) = index.upgrade() else {
+            return;
+        };
+        let mut index = lock_index(&index);
+        match res {
+            Ok(event) => {
+                if matches!(event.kind, EventKind::Access(_)) {
+                    return;
+                }
+                if event.need_rescan() {
+                    index.mark_rescan();
+                }
+                for path in &event.paths {
+                    index.invalidate(path);
+                }
+            }
+            Err(_) => index.mark_rescan(),
+        }
+    })?;
+    watcher.watch(root, RecursiveMode::Recursive)?;
+    Ok(watcher)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_tokenize_splits_identifiers() {
+        let terms = tokenize("Fix the DockerManager start_service bug");
+        assert!(terms.contains(&"docker".to_string()));
+        assert!(terms.contains(&"manager".to_string()));
+        assert!(terms.contains(&"dockermanager".to_string()));
+        assert!(terms.contains(&"service".to_string()));
+        assert!(!terms.contains(&"the".to_string()));
+    }
+
+    #[test]
+    fn test_extract_symbols() {
+        let content = "pub fn start_service() {}\nexport class PortRegistry {\nconst x = 1\n// fn not_a_symbol";
+        let symbols = extract_symbols(content);
+        assert_eq!(symbols, vec!["start_service", "PortRegistry", "x"]);
+    }
+
+    #[test]
+    fn test_build_indexes_rankable_files() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "pub struct PortRegistry {}\nfn reserve_port() {}").unwrap();
+        fs::write(dir.path().join("logo.png"), [0u8, 1, 2]).unwrap();
+        fs::write(dir.path().join(".gitignore"), "dist/\n").unwrap();
+        fs::create_dir(dir.path().join("dist")).unwrap();
+        fs::write(dir.path().join("dist/bundle.js"), "function bundled() {}").unwrap();
+
+        let index = FileIndex::build(dir.path());
+        assert_eq!(index.len(), 1);
+
+        let file = index.get(&dir.path().join("src/lib.rs")).unwrap();
+        assert_eq!(file.relative_path, "src/lib.rs");
+        assert_eq!(file.language, "rust");
+        assert_eq!(file.symbols, vec!["PortRegistry", "reserve_port"]);
+        assert!(!index.is_stale());
+    }
+
+    #[test]
+    fn test_refresh_only_reindexes_dirty_paths() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("a.rs"), "fn alpha() {}").unwrap();
+        fs::write(dir.path().join("b.rs"), "fn beta() {}").unwrap();
+        fs::write(dir.path().join(".gitignore"), "out/\n").unwrap();
+        let mut index = FileIndex::build(dir.path());
+        assert_eq!(index.len(), 2);
+
+        // Modified, added, deleted and ignored files
+        fs::write(dir.path().join("a.rs"), "fn alpha_renamed() {}").unwrap();
+        fs::write(dir.path().join("c.ts"), "export function gamma() {}").unwrap();
+        fs::remove_file(dir.path().join("b.rs")).unwrap();
+        fs::create_dir(dir.path().join("out")).unwrap();
+        fs::write(dir.path().join("out/gen.rs"), "fn generated() {}").unwrap();
+        for name in ["a.rs", "b.rs", "c.ts", "out", "out/gen.rs", ".git/index"] {
+            index.invalidate(&dir.path().join(name));
+        }
+        assert!(index.is_stale());
+
+        assert_eq!(index.refresh(), 2);
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.get(&dir.path().join("a.rs")).unwrap().symbols, vec!["alpha_renamed"]);
+        assert_eq!(index.get(&dir.path().join("c.ts")).unwrap().language, "typescript");
+        assert!(index.get(&dir.path().join("b.rs")).is_none());
+        assert!(index.get(&dir.path().join("out/gen.rs")).is_none());
+    }
+
+    #[test]
+    fn test_invalidate_removed_directory() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("module")).unwrap();
+        fs::write(dir.path().join("module/one.rs"), "fn one() {}").unwrap();
+        fs::write(dir.path().join("module/two.rs"), "fn two() {}").unwrap();
+        fs::write(dir.path().join("main.rs"), "fn main() {}").unwrap();
+        let mut index = FileIndex::build(dir.path());
+        assert_eq!(index.len(), 3);
+
+        fs::remove_dir_all(dir.path().join("module")).unwrap();
+        index.invalidate(&dir.path().join("module"));
+        index.refresh();
+        assert_eq!(index.len(), 1);
+
+        // Changing .gitignore forces a full rescan
+        index.invalidate(&dir.path().join(".gitignore"));
+        assert!(index.is_stale());
+        assert_eq!(index.refresh(), 1);
+    }
+
+    #[test]
+    fn test_indexer_reuses_and_drops_indexes() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("lib.rs"), "fn lib() {}").unwrap();
+        let indexer = FileIndexer::new();
+
+        let first = indexer.index_for(dir.path());
+        let second = indexer.index_for(dir.path());
+        assert!(Arc::ptr_eq(&first, &second));
+
+        lock_index(&first).refresh();
+        assert_eq!(lock_index(&first).len(), 1);
+
+        assert_eq!(indexer.retain(&[dir.path().to_string_lossy().to_string()]), 0);
+        assert_eq!(indexer.retain(&[]), 1);
+        assert!(!indexer.is_watching(dir.path()));
+    }
+}
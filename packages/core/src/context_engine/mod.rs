@@ -3,9 +3,21 @@
 //! Automatically gathers, ranks, and formats the most relevant information
 //! from the project state to send to the LLM.
 
+pub mod dependencies;
+pub mod gatherers;
+pub mod index;
+
+use dependencies::{format_dependencies, AuditGatherer, Dependency, DependencyGatherer};
+use gatherers::fit_file_content;
+pub use gatherers::{
+    BuildDiagnosticsGatherer, DirectoryGatherer, DockerGatherer, FileGatherer, GitGatherer, TerminalGatherer,
+};
+use index::{lock_index, FileIndex, IndexedFile};
+pub use index::{extract_symbols, tokenize};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
-use std::process::Command;
+use std::sync::{Arc, Mutex};
 
 // ============================================================================
 // Core Types
@@ -37,6 +49,9 @@ pub struct AIContext {
     pub directory_tree: Option<String>,
     /// Git diff of unstaged changes.
     pub git_diff: Option<String>,
+    /// Dependencies declared in the project's manifests.
+    #[serde(default)]
+    pub dependencies: Vec<Dependency>,
 }
 
 impl AIContext {
@@ -59,6 +74,7 @@ impl AIContext {
         if let Some(ref diff) = self.git_diff {
             chars += diff.len();
         }
+        chars += format_dependencies(&self.dependencies).len();
         chars / 4
     }
 
@@ -103,6 +119,11 @@ impl AIContext {
             }
         }
 
+        // Dependencies
+        if !self.dependencies.is_empty() {
+            parts.push(format!("## Dependencies\n```\n{}\n```", format_dependencies(&self.dependencies)));
+        }
+
         // Directory tree (low priority)
         if let Some(ref tree) = self.directory_tree {
             parts.push(format!("## Directory Structure\n```\n{}\n```", tree));
@@ -152,311 +173,170 @@ pub enum ContextContent {
     Errors(Vec<String>),
     DirectoryTree(String),
     TerminalOutput(String),
+    Dependencies(Vec<Dependency>),
 }
 
 // ============================================================================
-// Git Gatherer
+// Ranked File Gatherer
 // ============================================================================
 
-/// Gatherer for git status and diff.
-pub struct GitGatherer;
-
-impl ContextGatherer for GitGatherer {
-    fn name(&self) -> &'static str {
-        "git"
-    }
-
-    fn gather(&self, project_path: &Path) -> GatheredContext {
-        let status = get_git_status(project_path);
-        let diff = get_git_diff(project_path);
+/// A project file scored against a prompt.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankedFile {
+    /// Absolute path.
+    pub path: String,
+    /// Relevance score (higher = more relevant).
+    pub score: f64,
+}
 
-        let combined = format!("{}\n\n{}", status, diff);
-        let tokens = combined.len() / 4;
+/// Score indexed files with TF-IDF over the query terms, boosted by path
+/// and symbol matches. Returns files with a positive score, best first.
+fn score_files<'a>(files: impl Iterator<Item = &'a IndexedFile>, query: &HashSet<String>) -> Vec<RankedFile> {
+    let files: Vec<&IndexedFile> = files.collect();
+    let doc_count = files.len() as f64;
+    let idf: HashMap<&String, f64> = query
+        .iter()
+        .map(|term| {
+            let df = files
+                .iter()
+                .filter(|f| f.term_counts.contains_key(term) || f.path_terms.contains(term))
+                .count() as f64;
+            (term, (1.0 + doc_count / (1.0 + df)).ln())
+        })
+        .collect();
+
+    let mut ranked: Vec<RankedFile> = files
+        .into_iter()
+        .map(|f| {
+            let score = query
+                .iter()
+                .map(|term| {
+                    let weight = idf[term];
+                    let tf = f.term_counts.get(term).map_or(0.0, |n| 1.0 + (*n as f64).ln());
+                    let path_boost = if f.path_terms.contains(term) { 2.0 } else { 0.0 };
+                    let symbol_boost = if f.symbol_terms.contains(term) { 1.5 } else { 0.0 };
+                    (tf + path_boost + symbol_boost) * weight
+                })
+                .sum();
+            RankedFile {
+                path: f.path.clone(),
+                score,
+            }
+        })
+        .filter(|r| r.score > 0.0)
+        .collect();
 
-        GatheredContext {
-            priority: 8, // High priority
-            tokens,
-            content: ContextContent::GitStatus(combined),
-        }
-    }
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.path.cmp(&b.path)));
+    ranked
 }
 
-/// Get git status for a project.
-fn get_git_status(project_path: &Path) -> String {
-    let output = Command::new("git")
-        .args(["status", "--short", "--branch"])
-        .current_dir(project_path)
-        .output();
-
-    match output {
-        Ok(out) if out.status.success() => {
-            String::from_utf8_lossy(&out.stdout).trim().to_string()
+impl FileIndex {
+    /// Rank indexed files by relevance to `query`.
+    pub fn rank(&self, query: &str, limit: usize) -> Vec<RankedFile> {
+        let query_terms: HashSet<String> = tokenize(query).into_iter().collect();
+        if query_terms.is_empty() {
+            return Vec::new();
         }
-        _ => String::new(),
+        let mut ranked = score_files(self.files(), &query_terms);
+        ranked.truncate(limit);
+        ranked
     }
 }
 
-/// Get git diff (unstaged changes).
-fn get_git_diff(project_path: &Path) -> String {
-    let output = Command::new("git")
-        .args(["diff", "--stat"])
-        .current_dir(project_path)
-        .output();
-
-    match output {
-        Ok(out) if out.status.success() => {
-            let diff = String::from_utf8_lossy(&out.stdout).trim().to_string();
-            // Limit diff size to prevent token explosion
-            if diff.len() > 2000 {
-                format!("{}...\n(truncated)", &diff[..2000])
-            } else {
-                diff
-            }
-        }
-        _ => String::new(),
+/// Rank project files by relevance to `query` (respects .gitignore).
+///
+/// Walks the whole tree; use a watched `FileIndex` to avoid re-reading
+/// unchanged files on every call.
+pub fn rank_files(project_path: &Path, query: &str, limit: usize) -> Vec<RankedFile> {
+    if tokenize(query).is_empty() {
+        return Vec::new();
     }
+    FileIndex::build(project_path).rank(query, limit)
 }
 
-// ============================================================================
-// File Gatherer
-// ============================================================================
-
-/// Gatherer for active/open files.
-pub struct FileGatherer {
-    /// Paths to files to include.
-    pub file_paths: Vec<String>,
+/// Gatherer for files ranked by relevance to the user's prompt.
+///
+/// Complements `FileGatherer`: when no files are open, the most relevant
+/// project files are included instead, within their own token budget.
+pub struct RankedFileGatherer {
+    /// The user's prompt.
+    pub query: String,
+    /// Files already included elsewhere (e.g. open files).
+    pub exclude: Vec<String>,
+    /// Maximum number of ranked files.
+    pub max_files: usize,
+    /// Token budget for all ranked files.
+    pub max_tokens: usize,
     /// Maximum content size per file (in chars).
     pub max_file_size: usize,
+    /// Watched index of the project (walks the tree when None).
+    pub index: Option<Arc<Mutex<FileIndex>>>,
 }
 
-impl Default for FileGatherer {
+impl Default for RankedFileGatherer {
     fn default() -> Self {
         Self {
-            file_paths: Vec::new(),
-            max_file_size: 10000, // ~2500 tokens per file max
+            query: String::new(),
+            exclude: Vec::new(),
+            max_files: 5,
+            max_tokens: 5000,
+            max_file_size: 6000,
+            index: None,
         }
     }
 }
 
-impl ContextGatherer for FileGatherer {
+impl ContextGatherer for RankedFileGatherer {
     fn name(&self) -> &'static str {
-        "files"
+        "ranked_files"
     }
 
-    fn gather(&self, _project_path: &Path) -> GatheredContext {
+    fn gather(&self, project_path: &Path) -> GatheredContext {
+        // Rank a few extra in case some are excluded or over budget
+        let limit = self.max_files + self.exclude.len() + 5;
+        let ranked = match &self.index {
+            Some(index) => {
+                let mut index = lock_index(index);
+                index.refresh();
+                index.rank(&self.query, limit)
+            }
+            None => rank_files(project_path, &self.query, limit),
+        };
+
         let mut files = Vec::new();
         let mut total_tokens = 0;
-
-        for path in &self.file_paths {
-            if let Ok(content) = std::fs::read_to_string(path) {
-                let truncated = if content.len() > self.max_file_size {
-                    format!(
-                        "{}...\n(truncated, {} more chars)",
-                        &content[..self.max_file_size],
-                        content.len() - self.max_file_size
-                    )
-                } else {
-                    content
-                };
-
-                total_tokens += (path.len() + truncated.len()) / 4;
-
-                files.push(FileContext {
-                    path: path.clone(),
-                    content: truncated,
-                    cursor_line: None,
-                });
+        for ranked_file in ranked {
+            if files.len() >= self.max_files {
+                break;
             }
-        }
-
-        GatheredContext {
-            priority: 10, // Highest priority
-            tokens: total_tokens,
-            content: ContextContent::Files(files),
-        }
-    }
-}
-
-// ============================================================================
-// Docker Gatherer
-// ============================================================================
-
-/// Gatherer for Docker errors and status.
-#[derive(Default)]
-pub struct DockerGatherer {
-    /// Container logs to include (container_id -> log lines).
-    pub error_logs: Vec<String>,
-}
-
-impl ContextGatherer for DockerGatherer {
-    fn name(&self) -> &'static str {
-        "docker"
-    }
-
-    fn gather(&self, _project_path: &Path) -> GatheredContext {
-        if self.error_logs.is_empty() {
-            return GatheredContext::default();
-        }
-
-        let tokens = self.error_logs.iter().map(|s| s.len()).sum::<usize>() / 4;
-
-        GatheredContext {
-            priority: 7, // High priority for errors
-            tokens,
-            content: ContextContent::Errors(self.error_logs.clone()),
-        }
-    }
-}
-
-// ============================================================================
-// Directory Tree Gatherer
-// ============================================================================
-
-/// Gatherer for directory structure.
-pub struct DirectoryGatherer {
-    /// Maximum depth to traverse.
-    pub max_depth: usize,
-}
-
-impl Default for DirectoryGatherer {
-    fn default() -> Self {
-        Self { max_depth: 2 }
-    }
-}
-
-impl ContextGatherer for DirectoryGatherer {
-    fn name(&self) -> &'static str {
-        "directory"
-    }
-
-    fn gather(&self, project_path: &Path) -> GatheredContext {
-        let tree = build_directory_tree(project_path, self.max_depth);
-        let tokens = tree.len() / 4;
-
-        GatheredContext {
-            priority: 3, // Low priority
-            tokens,
-            content: ContextContent::DirectoryTree(tree),
-        }
-    }
-}
-
-/// Build a directory tree string.
-fn build_directory_tree(path: &Path, max_depth: usize) -> String {
-    let mut result = String::new();
-    build_tree_recursive(path, "", max_depth, 0, &mut result);
-    result
-}
-
-fn build_tree_recursive(
-    path: &Path,
-    prefix: &str,
-    max_depth: usize,
-    current_depth: usize,
-    result: &mut String,
-) {
-    if current_depth > max_depth {
-        return;
-    }
-
-    let name = path
-        .file_name()
-        .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_else(|| path.to_string_lossy().to_string());
-
-    if path.is_dir() {
-        result.push_str(&format!("{}{}/\n", prefix, name));
-
-        // Skip common non-essential directories
-        let skip_dirs = [
-            "node_modules",
-            ".git",
-            "target",
-            "dist",
-            "build",
-            ".next",
-            "__pycache__",
-            ".venv",
-            "venv",
-        ];
-
-        if skip_dirs.contains(&name.as_str()) {
-            return;
-        }
-
-        if let Ok(entries) = std::fs::read_dir(path) {
-            let mut entries: Vec<_> = entries.filter_map(|e| e.ok()).collect();
-            entries.sort_by_key(|e| e.file_name());
-
-            for entry in entries.iter().take(20) {
-                // Limit entries per directory
-                let child_prefix = format!("{}  ", prefix);
-                build_tree_recursive(
-                    &entry.path(),
-                    &child_prefix,
-                    max_depth,
-                    current_depth + 1,
-                    result,
-                );
+            if self.exclude.contains(&ranked_file.path) {
+                continue;
             }
+            let Ok(content) = std::fs::read_to_string(&ranked_file.path) else {
+                continue;
+            };
+            let content = fit_file_content(&ranked_file.path, content, self.max_file_size);
 
-            if entries.len() > 20 {
-                result.push_str(&format!("{}  ... and {} more\n", prefix, entries.len() - 20));
+            let tokens = (ranked_file.path.len() + content.len()) / 4;
+            if total_tokens + tokens > self.max_tokens {
+                continue;
             }
+            total_tokens += tokens;
+            files.push(FileContext {
+                path: ranked_file.path,
+                content,
+                cursor_line: None,
+            });
         }
-    } else {
-        result.push_str(&format!("{}{}\n", prefix, name));
-    }
-}
-
-// ============================================================================
-// Terminal Output Gatherer
-// ============================================================================
-
-/// Gatherer for last terminal/task output.
-pub struct TerminalGatherer {
-    /// Last output from task execution.
-    pub last_output: Option<String>,
-    /// Maximum output size.
-    pub max_size: usize,
-}
 
-impl Default for TerminalGatherer {
-    fn default() -> Self {
-        Self {
-            last_output: None,
-            max_size: 2000,
+        if files.is_empty() {
+            return GatheredContext::default();
         }
-    }
-}
-
-impl ContextGatherer for TerminalGatherer {
-    fn name(&self) -> &'static str {
-        "terminal"
-    }
-
-    fn gather(&self, _project_path: &Path) -> GatheredContext {
-        let output = match &self.last_output {
-            Some(o) if !o.is_empty() => {
-                if o.len() > self.max_size {
-                    format!(
-                        "...{}\n(showing last {} chars)",
-                        &o[o.len() - self.max_size..],
-                        self.max_size
-                    )
-                } else {
-                    o.clone()
-                }
-            }
-            _ => return GatheredContext::default(),
-        };
-
-        let tokens = output.len() / 4;
 
         GatheredContext {
-            priority: 6, // Medium-high priority
-            tokens,
-            content: ContextContent::TerminalOutput(output),
+            priority: 5, // Below explicit files/errors, above directory tree
+            tokens: total_tokens,
+            content: ContextContent::Files(files),
         }
     }
 }
@@ -503,7 +383,7 @@ impl ContextEngine {
             .collect();
 
         // Sort by priority (highest first)
-        gathered.sort_by(|a, b| b.0.priority.cmp(&a.0.priority));
+        gathered.sort_by_key(|g| std::cmp::Reverse(g.0.priority));
 
         // Build context within budget
         let mut context = AIContext::default();
@@ -537,6 +417,9 @@ impl ContextEngine {
                 ContextContent::TerminalOutput(output) => {
                     context.terminal_last_output = Some(output);
                 }
+                ContextContent::Dependencies(dependencies) => {
+                    context.dependencies = dependencies;
+                }
             }
         }
 
@@ -553,23 +436,43 @@ impl ContextEngine {
 pub fn create_default_engine(token_budget: usize) -> ContextEngine {
     let mut engine = ContextEngine::new(token_budget);
     engine.add_gatherer(Box::new(GitGatherer));
+    engine.add_gatherer(Box::new(DependencyGatherer));
+    engine.add_gatherer(Box::new(AuditGatherer));
+    engine.add_gatherer(Box::new(BuildDiagnosticsGatherer));
     engine.add_gatherer(Box::new(DirectoryGatherer::default()));
     engine
 }
 
 /// Build context for a project with optional additional data.
+///
+/// When a prompt is given, project files ranked by relevance to it are
+/// included (up to a quarter of the budget) alongside the active files.
+/// Passing the project's watched `FileIndex` makes ranking incremental.
 pub fn build_context(
     project_path: &Path,
     active_files: Vec<String>,
     task_output: Option<String>,
     docker_errors: Vec<String>,
     token_budget: usize,
+    prompt: Option<&str>,
+    index: Option<Arc<Mutex<FileIndex>>>,
 ) -> AIContext {
     let mut engine = ContextEngine::new(token_budget);
 
     // Add git gatherer
     engine.add_gatherer(Box::new(GitGatherer));
 
+    // Add ranked file gatherer if there is a prompt to rank against
+    if let Some(query) = prompt.filter(|p| !p.trim().is_empty()) {
+        engine.add_gatherer(Box::new(RankedFileGatherer {
+            query: query.to_string(),
+            exclude: active_files.clone(),
+            max_tokens: token_budget / 4,
+            index,
+            ..Default::default()
+        }));
+    }
+
     // Add file gatherer if there are active files
     if !active_files.is_empty() {
         engine.add_gatherer(Box::new(FileGatherer {
@@ -593,6 +496,13 @@ pub fn build_context(
         }));
     }
 
+    // Add compiler errors of the last build check
+    engine.add_gatherer(Box::new(BuildDiagnosticsGatherer));
+
+    // Add dependency summary from manifests, and critical audit findings
+    engine.add_gatherer(Box::new(DependencyGatherer));
+    engine.add_gatherer(Box::new(AuditGatherer));
+
     // Add directory gatherer (low priority, will be cut if over budget)
     engine.add_gatherer(Box::new(DirectoryGatherer::default()));
 
@@ -622,6 +532,7 @@ mod tests {
             active_errors: vec!["error1".to_string()],        // 6 chars
             directory_tree: None,
             git_diff: None,
+            dependencies: vec![],
         };
 
         // Total: 14 + 12 + 6 + 4 + 6 = 42 chars / 4 = 10 tokens
@@ -639,11 +550,18 @@ mod tests {
             active_errors: vec![],
             directory_tree: None,
             git_diff: None,
+            dependencies: vec![Dependency {
+                manifest: "Cargo.toml".to_string(),
+                name: "serde".to_string(),
+                version: "1.0".to_string(),
+                dev: false,
+            }],
         };
 
         let prompt = context.to_system_prompt();
         assert!(prompt.contains("Git Status"));
         assert!(prompt.contains("## main"));
+        assert!(prompt.contains("## Dependencies\n```\nCargo.toml: serde 1.0\n```"));
     }
 
     #[test]
@@ -653,68 +571,6 @@ mod tests {
         assert_eq!(prompt, "No project context available.");
     }
 
-    #[test]
-    fn test_directory_gatherer() {
-        let dir = tempdir().unwrap();
-        let src = dir.path().join("src");
-        fs::create_dir(&src).unwrap();
-        fs::write(src.join("main.rs"), "fn main() {}").unwrap();
-        fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
-
-        let gatherer = DirectoryGatherer { max_depth: 2 };
-        let result = gatherer.gather(dir.path());
-
-        assert!(result.priority > 0);
-        if let ContextContent::DirectoryTree(tree) = result.content {
-            assert!(tree.contains("src/"));
-            assert!(tree.contains("Cargo.toml"));
-        } else {
-            panic!("Expected DirectoryTree content");
-        }
-    }
-
-    #[test]
-    fn test_file_gatherer() {
-        let dir = tempdir().unwrap();
-        let file_path = dir.path().join("test.rs");
-        fs::write(&file_path, "fn test() {}").unwrap();
-
-        let gatherer = FileGatherer {
-            file_paths: vec![file_path.to_string_lossy().to_string()],
-            max_file_size: 1000,
-        };
-        let result = gatherer.gather(dir.path());
-
-        assert_eq!(result.priority, 10); // Highest priority
-        if let ContextContent::Files(files) = result.content {
-            assert_eq!(files.len(), 1);
-            assert!(files[0].content.contains("fn test()"));
-        } else {
-            panic!("Expected Files content");
-        }
-    }
-
-    #[test]
-    fn test_file_gatherer_truncation() {
-        let dir = tempdir().unwrap();
-        let file_path = dir.path().join("large.txt");
-        let large_content = "x".repeat(20000);
-        fs::write(&file_path, &large_content).unwrap();
-
-        let gatherer = FileGatherer {
-            file_paths: vec![file_path.to_string_lossy().to_string()],
-            max_file_size: 100,
-        };
-        let result = gatherer.gather(dir.path());
-
-        if let ContextContent::Files(files) = result.content {
-            assert!(files[0].content.len() < 200);
-            assert!(files[0].content.contains("truncated"));
-        } else {
-            panic!("Expected Files content");
-        }
-    }
-
     #[test]
     fn test_context_engine_priority() {
         let dir = tempdir().unwrap();
@@ -740,40 +596,6 @@ mod tests {
         assert!(context.directory_tree.is_some());
     }
 
-    #[test]
-    fn test_docker_gatherer() {
-        let dir = tempdir().unwrap();
-        let gatherer = DockerGatherer {
-            error_logs: vec!["Error: connection refused".to_string()],
-        };
-        let result = gatherer.gather(dir.path());
-
-        assert_eq!(result.priority, 7);
-        if let ContextContent::Errors(errors) = result.content {
-            assert_eq!(errors.len(), 1);
-            assert!(errors[0].contains("connection refused"));
-        } else {
-            panic!("Expected Errors content");
-        }
-    }
-
-    #[test]
-    fn test_terminal_gatherer() {
-        let dir = tempdir().unwrap();
-        let gatherer = TerminalGatherer {
-            last_output: Some("Build successful".to_string()),
-            max_size: 1000,
-        };
-        let result = gatherer.gather(dir.path());
-
-        assert_eq!(result.priority, 6);
-        if let ContextContent::TerminalOutput(output) = result.content {
-            assert!(output.contains("Build successful"));
-        } else {
-            panic!("Expected TerminalOutput content");
-        }
-    }
-
     #[test]
     fn test_build_context_helper() {
         let dir = tempdir().unwrap();
@@ -786,10 +608,45 @@ mod tests {
             Some("test passed".to_string()),
             vec!["docker error".to_string()],
             10000,
+            None,
+            None,
         );
 
         assert!(!context.open_files.is_empty());
         assert!(context.terminal_last_output.is_some());
         assert!(!context.active_errors.is_empty());
     }
+
+    #[test]
+    fn test_rank_files_prefers_relevant_files() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/docker.rs"), "pub struct DockerManager;\nfn start_container() {}").unwrap();
+        fs::write(dir.path().join("src/chat.rs"), "pub fn send_message() {}\n// mentions docker once").unwrap();
+        fs::write(dir.path().join("src/other.rs"), "fn unrelated() {}").unwrap();
+
+        let ranked = rank_files(dir.path(), "why does the docker container fail to start?", 10);
+        assert_eq!(ranked.len(), 2);
+        assert!(ranked[0].path.ends_with("docker.rs"));
+        assert!(ranked[1].path.ends_with("chat.rs"));
+
+        assert!(rank_files(dir.path(), "the and for", 10).is_empty());
+    }
+
+    #[test]
+    fn test_build_context_includes_ranked_files() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("billing.rs"), "fn compute_invoice_total() {}").unwrap();
+        fs::write(dir.path().join("auth.rs"), "fn login() {}").unwrap();
+
+        let context = build_context(dir.path(), vec![], None, vec![], 10000, Some("invoice total is wrong"), None);
+        assert_eq!(context.open_files.len(), 1);
+        assert!(context.open_files[0].path.ends_with("billing.rs"));
+
+        // Same ranking through a watched index
+        let index = Arc::new(Mutex::new(FileIndex::new(dir.path())));
+        let context = build_context(dir.path(), vec![], None, vec![], 10000, Some("invoice total is wrong"), Some(index.clone()));
+        assert!(context.open_files[0].path.ends_with("billing.rs"));
+        assert!(!lock_index(&index).is_stale());
+    }
 }
@@ -1,9 +1,13 @@
 //! SQLite Database Management
 //!
-//! Handles user-scoped persistence for structured data like comments and logs.
+//! Handles user-scoped persistence for structured data like comments, logs
+//! Claude CLI usage records and Claude session history.
 //! Database is stored at ~/.rstn/state.db with project_id column for data isolation.
 
+use crate::sessions::{SessionOutcome, SessionRecord};
+use crate::usage::{UsageRecord, UsageTotals};
 use rusqlite::{params, Connection, Result};
+use std::path::Path;
 use std::sync::Mutex;
 use uuid::Uuid;
 
@@ -25,11 +29,16 @@ impl DbManager {
             })?;
         }
 
-        let db_path = rstn_dir.join("state.db");
+        Self::open(&rstn_dir.join("state.db"))
+    }
+
+    /// Open (and migrate) a database at an explicit path
+    pub fn open(db_path: &Path) -> Result<Self> {
         let conn = Connection::open(db_path)?;
 
-        // Enable WAL mode for better concurrency
-        conn.execute("PRAGMA journal_mode=WAL;", [])?;
+        // Enable WAL mode for better concurrency (the pragma returns the new
+        // mode as a row, so `execute` would fail with ExecuteReturnedResults)
+        conn.query_row("PRAGMA journal_mode=WAL;", [], |_| Ok(()))?;
 
         let manager = Self { conn: Mutex::new(conn) };
         manager.run_migrations()?;
@@ -112,6 +121,58 @@ impl DbManager {
             [],
         )?;
 
+        // Table: Claude CLI usage records (one row per CLI run)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS usage_records (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                project_id TEXT NOT NULL,
+                session_id TEXT,
+                source TEXT NOT NULL,
+                model TEXT,
+                input_tokens INTEGER NOT NULL,
+                output_tokens INTEGER NOT NULL,
+                cache_read_tokens INTEGER NOT NULL DEFAULT 0,
+                cache_creation_tokens INTEGER NOT NULL DEFAULT 0,
+                cost_usd REAL NOT NULL,
+                timestamp TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Index for usage by project_id and time
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_usage_records_project_time ON usage_records(project_id, timestamp)",
+            [],
+        )?;
+
+        // Table: Claude sessions (one row per CLI invocation)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS claude_sessions (
+                id TEXT PRIMARY KEY,
+                project_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                worktree_path TEXT NOT NULL,
+                claude_session_id TEXT,
+                model TEXT,
+                prompt_excerpt TEXT NOT NULL DEFAULT '',
+                started_at TEXT NOT NULL,
+                duration_ms INTEGER NOT NULL,
+                input_tokens INTEGER NOT NULL DEFAULT 0,
+                output_tokens INTEGER NOT NULL DEFAULT 0,
+                cost_usd REAL NOT NULL DEFAULT 0,
+                log_file TEXT,
+                outcome TEXT NOT NULL,
+                error TEXT
+            )",
+            [],
+        )?;
+
+        // Index for session history by project_id and time
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_claude_sessions_project_time ON claude_sessions(project_id, started_at)",
+            [],
+        )?;
+
         Ok(())
     }
 
@@ -174,6 +235,48 @@ impl DbManager {
         Ok(result)
     }
 
+    pub fn get_comment(&self, project_id: &str, id: &str) -> Result<Option<CommentRow>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, content, author, created_at, line_number FROM file_comments
+             WHERE project_id = ?1 AND id = ?2",
+        )?;
+
+        let mut rows = stmt.query_map(params![project_id, id], |row| {
+            let line_number: Option<i64> = row.get(4)?;
+            Ok(CommentRow {
+                id: row.get(0)?,
+                content: row.get(1)?,
+                author: row.get(2)?,
+                created_at: row.get(3)?,
+                line_number: line_number.map(|n| n as usize),
+            })
+        })?;
+        rows.next().transpose()
+    }
+
+    /// Re-insert a deleted comment with its original ID and timestamp (undo)
+    pub fn restore_comment(&self, project_id: &str, file_path: &str, comment: &CommentRow) -> Result<()> {
+        let now = chrono::Utc::now().to_rfc3339();
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT OR REPLACE INTO file_comments (id, project_id, file_path, content, author, created_at, updated_at, line_number)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                comment.id,
+                project_id,
+                file_path,
+                comment.content,
+                comment.author,
+                comment.created_at,
+                now,
+                comment.line_number.map(|n| n as i64)
+            ],
+        )?;
+        Ok(())
+    }
+
     pub fn delete_comment(&self, project_id: &str, id: &str) -> Result<()> {
         let conn = self.conn.lock().unwrap();
         conn.execute(
@@ -216,14 +319,16 @@ impl DbManager {
         Ok(conn.last_insert_rowid())
     }
 
-    pub fn get_logs(&self, project_id: &str, limit: usize) -> Result<Vec<LogRow>> {
+    /// Newest logs first, optionally only those at or after `since` (ISO 8601)
+    pub fn get_logs(&self, project_id: &str, since: Option<&str>, limit: usize) -> Result<Vec<LogRow>> {
         let conn = self.conn.lock().unwrap();
         let mut stmt = conn.prepare(
             "SELECT id, category, level, summary, detail_json, timestamp
-             FROM activity_logs WHERE project_id = ?1 ORDER BY timestamp DESC LIMIT ?2",
+             FROM activity_logs WHERE project_id = ?1 AND (?2 IS NULL OR timestamp >= ?2)
+             ORDER BY timestamp DESC, id DESC LIMIT ?3",
         )?;
 
-        let rows = stmt.query_map(params![project_id, limit], |row| {
+        let rows = stmt.query_map(params![project_id, since, limit], |row| {
             Ok(LogRow {
                 id: row.get(0)?,
                 category: row.get(1)?,
@@ -240,6 +345,142 @@ impl DbManager {
         }
         Ok(result)
     }
+
+    // ========================================================================
+    // Usage Records (all queries require project_id)
+    // ========================================================================
+
+    pub fn add_usage_record(&self, record: &UsageRecord) -> Result<i64> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO usage_records (project_id, session_id, source, model, input_tokens, output_tokens,
+                cache_read_tokens, cache_creation_tokens, cost_usd, timestamp)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                record.project_id,
+                record.session_id,
+                record.source,
+                record.model,
+                record.input_tokens as i64,
+                record.output_tokens as i64,
+                record.cache_read_tokens as i64,
+                record.cache_creation_tokens as i64,
+                record.cost_usd,
+                record.timestamp
+            ],
+        )?;
+
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Sum usage for a project, optionally only records at or after `since` (ISO 8601)
+    pub fn get_usage_totals(&self, project_id: &str, since: Option<&str>) -> Result<UsageTotals> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT COUNT(*), COALESCE(SUM(input_tokens), 0), COALESCE(SUM(output_tokens), 0),
+                    COALESCE(SUM(cache_read_tokens), 0), COALESCE(SUM(cache_creation_tokens), 0),
+                    COALESCE(SUM(cost_usd), 0.0)
+             FROM usage_records WHERE project_id = ?1 AND (?2 IS NULL OR timestamp >= ?2)",
+        )?;
+
+        stmt.query_row(params![project_id, since], |row| {
+            Ok(UsageTotals {
+                request_count: row.get::<_, i64>(0)? as u64,
+                input_tokens: row.get::<_, i64>(1)? as u64,
+                output_tokens: row.get::<_, i64>(2)? as u64,
+                cache_read_tokens: row.get::<_, i64>(3)? as u64,
+                cache_creation_tokens: row.get::<_, i64>(4)? as u64,
+                cost_usd: row.get(5)?,
+            })
+        })
+    }
+
+    // ========================================================================
+    // Claude Sessions
+    // ========================================================================
+
+    pub fn add_session(&self, session: &SessionRecord) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT OR REPLACE INTO claude_sessions (id, project_id, kind, worktree_path, claude_session_id,
+                model, prompt_excerpt, started_at, duration_ms, input_tokens, output_tokens, cost_usd,
+                log_file, outcome, error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+            params![
+                session.id,
+                session.project_id,
+                session.kind,
+                session.worktree_path,
+                session.claude_session_id,
+                session.model,
+                session.prompt_excerpt,
+                session.started_at,
+                session.duration_ms as i64,
+                session.input_tokens as i64,
+                session.output_tokens as i64,
+                session.cost_usd,
+                session.log_file,
+                session.outcome.as_str(),
+                session.error
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Sessions of a project, newest first
+    pub fn list_sessions(&self, project_id: &str, limit: usize) -> Result<Vec<SessionRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM claude_sessions WHERE project_id = ?1 ORDER BY started_at DESC LIMIT ?2",
+            SESSION_COLUMNS
+        ))?;
+
+        let rows = stmt.query_map(params![project_id, limit as i64], session_from_row)?;
+        rows.collect()
+    }
+
+    pub fn get_session(&self, id: &str) -> Result<Option<SessionRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!("SELECT {} FROM claude_sessions WHERE id = ?1", SESSION_COLUMNS))?;
+
+        let mut rows = stmt.query_map(params![id], session_from_row)?;
+        rows.next().transpose()
+    }
+
+    /// Delete a session; returns whether it existed
+    pub fn delete_session(&self, id: &str) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn.execute("DELETE FROM claude_sessions WHERE id = ?1", params![id])?;
+        Ok(deleted > 0)
+    }
+}
+
+const SESSION_COLUMNS: &str = "id, project_id, kind, worktree_path, claude_session_id, model, prompt_excerpt,
+    started_at, duration_ms, input_tokens, output_tokens, cost_usd, log_file, outcome, error";
+
+fn session_from_row(row: &rusqlite::Row) -> Result<SessionRecord> {
+    let outcome: String = row.get(13)?;
+    Ok(SessionRecord {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        kind: row.get(2)?,
+        worktree_path: row.get(3)?,
+        claude_session_id: row.get(4)?,
+        model: row.get(5)?,
+        prompt_excerpt: row.get(6)?,
+        started_at: row.get(7)?,
+        duration_ms: row.get::<_, i64>(8)? as u64,
+        input_tokens: row.get::<_, i64>(9)? as u64,
+        output_tokens: row.get::<_, i64>(10)? as u64,
+        cost_usd: row.get(11)?,
+        log_file: row.get(12)?,
+        // Unknown values (written by a newer rstn) read as interrupted
+        outcome: SessionOutcome::parse(&outcome).unwrap_or(SessionOutcome::Interrupted),
+        error: row.get(14)?,
+    })
 }
 
 #[derive(Debug, serde::Serialize)]
@@ -262,4 +503,127 @@ pub struct LogRow {
     pub timestamp: String,
 }
 
-// Activity Log integration will be added in Phase B1.3
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn usage(project_id: &str, cost_usd: f64, timestamp: &str) -> UsageRecord {
+        UsageRecord {
+            project_id: project_id.to_string(),
+            session_id: None,
+            source: "chat".to_string(),
+            model: Some("sonnet".to_string()),
+            input_tokens: 100,
+            output_tokens: 10,
+            cache_read_tokens: 5,
+            cache_creation_tokens: 0,
+            cost_usd,
+            timestamp: timestamp.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_usage_totals_by_project_and_period() {
+        let dir = tempdir().unwrap();
+        let db = DbManager::open(&dir.path().join("state.db")).unwrap();
+
+        db.add_usage_record(&usage("p1", 0.5, "2026-01-01T00:00:00+00:00")).unwrap();
+        db.add_usage_record(&usage("p1", 0.25, "2026-02-01T00:00:00+00:00")).unwrap();
+        db.add_usage_record(&usage("p2", 1.0, "2026-02-01T00:00:00+00:00")).unwrap();
+
+        let all = db.get_usage_totals("p1", None).unwrap();
+        assert_eq!(all.request_count, 2);
+        assert_eq!(all.input_tokens, 200);
+        assert_eq!(all.cache_read_tokens, 10);
+        assert!((all.cost_usd - 0.75).abs() < 1e-9);
+
+        let recent = db.get_usage_totals("p1", Some("2026-01-15T00:00:00+00:00")).unwrap();
+        assert_eq!(recent.request_count, 1);
+
+        let empty = db.get_usage_totals("missing", None).unwrap();
+        assert_eq!(empty, UsageTotals::default());
+    }
+
+    fn session(id: &str, project_id: &str, started_at: &str) -> SessionRecord {
+        SessionRecord {
+            id: id.to_string(),
+            project_id: project_id.to_string(),
+            kind: "chat".to_string(),
+            worktree_path: "/work/app".to_string(),
+            claude_session_id: Some(format!("claude-{}", id)),
+            model: None,
+            prompt_excerpt: "Hello".to_string(),
+            started_at: started_at.to_string(),
+            duration_ms: 1200,
+            input_tokens: 10,
+            output_tokens: 5,
+            cost_usd: 0.01,
+            log_file: None,
+            outcome: SessionOutcome::Success,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_claude_sessions_crud() {
+        let dir = tempdir().unwrap();
+        let db = DbManager::open(&dir.path().join("state.db")).unwrap();
+
+        db.add_session(&session("a", "p1", "2026-01-01T00:00:00+00:00")).unwrap();
+        db.add_session(&session("b", "p1", "2026-02-01T00:00:00+00:00")).unwrap();
+        db.add_session(&session("c", "p2", "2026-02-01T00:00:00+00:00")).unwrap();
+
+        let listed = db.list_sessions("p1", 10).unwrap();
+        assert_eq!(listed.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), vec!["b", "a"]);
+        assert_eq!(db.list_sessions("p1", 1).unwrap().len(), 1);
+
+        let mut failed = session("a", "p1", "2026-01-01T00:00:00+00:00");
+        failed.outcome = SessionOutcome::Error;
+        failed.error = Some("boom".to_string());
+        db.add_session(&failed).unwrap();
+        assert_eq!(db.get_session("a").unwrap(), Some(failed));
+
+        assert!(db.delete_session("a").unwrap());
+        assert!(!db.delete_session("a").unwrap());
+        assert_eq!(db.get_session("a").unwrap(), None);
+    }
+
+    #[test]
+    fn test_activity_logs_since() {
+        let dir = tempdir().unwrap();
+        let db = DbManager::open(&dir.path().join("state.db")).unwrap();
+
+        db.add_log("p1", "change_created", "info", "Created change a", None).unwrap();
+        db.add_log("p1", "service_started", "info", "Started rstn-redis", Some("{}")).unwrap();
+        db.add_log("p2", "worktree_added", "info", "Added worktree", None).unwrap();
+
+        let logs = db.get_logs("p1", None, 10).unwrap();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].summary, "Started rstn-redis");
+        assert_eq!(db.get_logs("p1", None, 1).unwrap().len(), 1);
+        assert!(db.get_logs("p1", Some("2999-01-01T00:00:00+00:00"), 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_restore_deleted_comment() {
+        let dir = tempdir().unwrap();
+        let db = DbManager::open(&dir.path().join("state.db")).unwrap();
+
+        let id = db.add_comment("p1", "src/lib.rs", "Needs a test", "User", Some(3)).unwrap();
+        let comment = db.get_comment("p1", &id).unwrap().unwrap();
+        db.delete_comment("p1", &id).unwrap();
+        assert!(db.get_comment("p1", &id).unwrap().is_none());
+
+        db.restore_comment("p1", "src/lib.rs", &comment).unwrap();
+        let restored = db.get_comments("p1", "src/lib.rs").unwrap();
+        assert_eq!(restored.len(), 1);
+        assert_eq!(restored[0].id, id);
+        assert_eq!(restored[0].created_at, comment.created_at);
+        assert_eq!(restored[0].line_number, Some(3));
+    }
+}
@@ -0,0 +1,160 @@
+//! OS-native notifications for long-running events.
+//!
+//! Core decides when an event is worth a notification and what it says; the
+//! desktop shell registers a listener and shows it with the platform's
+//! notification API. Which events notify is configured per event in
+//! `GlobalSettings::desktop_notifications`.
+
+use napi_derive::napi;
+
+use crate::app_state::{DesktopNotificationEvent, DockerServiceInfo, ServiceStatus};
+use crate::task_queue::TaskRunStatus;
+
+/// Notification passed to the desktop listener
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DesktopNotification {
+    /// Event name (snake_case `DesktopNotificationEvent`)
+    pub event: String,
+    pub title: String,
+    pub body: String,
+}
+
+impl DesktopNotification {
+    pub fn new(event: DesktopNotificationEvent, title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            event: serde_json::to_value(event)
+                .ok()
+                .and_then(|v| v.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            title: title.into(),
+            body: body.into(),
+        }
+    }
+}
+
+/// A task run finished. Cancelled runs were stopped by the user, so they
+/// get no notification.
+pub fn task_finished(
+    command: &str,
+    status: TaskRunStatus,
+    exit_code: Option<i32>,
+    duration_ms: Option<u64>,
+) -> Option<DesktopNotification> {
+    let title = match status {
+        TaskRunStatus::Success => "Task succeeded",
+        TaskRunStatus::Error => "Task failed",
+        _ => return None,
+    };
+    let mut body = command.to_string();
+    if let (TaskRunStatus::Error, Some(code)) = (status, exit_code) {
+        body.push_str(&format!(" (exit code {})", code));
+    }
+    if let Some(ms) = duration_ms {
+        body.push_str(&format!(" in {}", format_duration(ms)));
+    }
+    Some(DesktopNotification::new(DesktopNotificationEvent::TaskCompleted, title, body))
+}
+
+/// Claude finished generating `what` (proposal, plan, implementation) for a change
+pub fn claude_finished(what: &str, change_name: &str) -> DesktopNotification {
+    DesktopNotification::new(
+        DesktopNotificationEvent::ClaudeFinished,
+        "Claude finished",
+        format!("The {} for {} is ready", what, change_name),
+    )
+}
+
+/// Implementing a change failed
+pub fn implementation_failed(change_name: &str, error: &str) -> DesktopNotification {
+    DesktopNotification::new(
+        DesktopNotificationEvent::ImplementationFailed,
+        "Implementation failed",
+        format!("{}: {}", change_name, error),
+    )
+}
+
+/// Services that were running and stopped without being asked to.
+///
+/// Stopping from the UI moves a service to `Stopping` first, so only services
+/// that go straight from `Running` to `Stopped` or `Error` count as crashed.
+pub fn crashed_services(before: &[DockerServiceInfo], after: &[DockerServiceInfo]) -> Vec<DesktopNotification> {
+    after
+        .iter()
+        .filter(|service| matches!(service.status, ServiceStatus::Stopped | ServiceStatus::Error))
+        .filter(|service| {
+            before
+                .iter()
+                .any(|prev| prev.id == service.id && prev.status == ServiceStatus::Running)
+        })
+        .map(|service| {
+            DesktopNotification::new(
+                DesktopNotificationEvent::DockerServiceCrashed,
+                "Docker service stopped",
+                format!("{} ({}) is no longer running", service.name, service.image),
+            )
+        })
+        .collect()
+}
+
+fn format_duration(ms: u64) -> String {
+    let secs = ms / 1000;
+    if secs < 60 {
+        format!("{}.{}s", secs, (ms % 1000) / 100)
+    } else {
+        format!("{}m {}s", secs / 60, secs % 60)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(id: &str, status: ServiceStatus) -> DockerServiceInfo {
+        DockerServiceInfo {
+            id: id.to_string(),
+            name: format!("rstn-{}", id),
+            image: "postgres:16".to_string(),
+            status,
+            port: None,
+            service_type: Default::default(),
+            project_group: None,
+            is_rstn_managed: true,
+        }
+    }
+
+    #[test]
+    fn test_task_finished() {
+        let n = task_finished("just test", TaskRunStatus::Error, Some(101), Some(83_000)).unwrap();
+        assert_eq!(n.event, "task_completed");
+        assert_eq!(n.title, "Task failed");
+        assert_eq!(n.body, "just test (exit code 101) in 1m 23s");
+
+        let n = task_finished("just build", TaskRunStatus::Success, Some(0), Some(1_500)).unwrap();
+        assert_eq!(n.body, "just build in 1.5s");
+
+        assert!(task_finished("just dev", TaskRunStatus::Cancelled, None, None).is_none());
+    }
+
+    #[test]
+    fn test_crashed_services_ignores_requested_stops() {
+        let before = vec![
+            service("db", ServiceStatus::Running),
+            service("cache", ServiceStatus::Stopping),
+            service("queue", ServiceStatus::Stopped),
+        ];
+        let after = vec![
+            service("db", ServiceStatus::Error),
+            service("cache", ServiceStatus::Stopped),
+            service("queue", ServiceStatus::Stopped),
+        ];
+        let crashed = crashed_services(&before, &after);
+        assert_eq!(crashed.len(), 1);
+        assert_eq!(crashed[0].event, "docker_service_crashed");
+        assert!(crashed[0].body.starts_with("rstn-db"));
+    }
+}
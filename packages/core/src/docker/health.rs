@@ -0,0 +1,95 @@
+//! Health checks that gate a started service's status.
+
+use super::{published_port, DockerManager};
+use serde::Deserialize;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// How long a started service may take to pass its health check
+pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Delay between health check attempts
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Readiness probe run after a service starts
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum HealthCheck {
+    /// The published port accepts TCP connections
+    Tcp,
+    /// HTTP GET on the published port answers without a 5xx status
+    Http { path: String },
+    /// The command exits 0 inside the container
+    Command { command: Vec<String> },
+}
+
+impl DockerManager {
+    /// Wait until a started service passes its health check.
+    ///
+    /// Fails as soon as the container exits, or after `HEALTH_CHECK_TIMEOUT`.
+    /// Services without a health check are healthy once their container runs.
+    pub async fn wait_until_healthy(&self, service_id: &str) -> Result<(), String> {
+        let probe = self
+            .service_config(service_id)
+            .and_then(|s| s.healthcheck.map(|check| (check, s.internal_port)));
+        let deadline = Instant::now() + HEALTH_CHECK_TIMEOUT;
+
+        loop {
+            let container = self
+                .docker
+                .inspect_container(service_id, None)
+                .await
+                .map_err(|e| format!("Failed to inspect {}: {}", service_id, e))?;
+            let state = container.state.clone().unwrap_or_default();
+
+            if state.running != Some(true) && state.restarting != Some(true) {
+                return Err(format!(
+                    "{} exited with code {}",
+                    service_id,
+                    state.exit_code.unwrap_or_default()
+                ));
+            }
+
+            let healthy = match probe {
+                None => state.running == Some(true),
+                Some((ref check, internal_port)) => {
+                    let host_port = published_port(&container, internal_port);
+                    self.probe(service_id, check, host_port).await
+                }
+            };
+            if healthy {
+                info!("Service healthy: {}", service_id);
+                return Ok(());
+            }
+
+            if Instant::now() >= deadline {
+                return Err(format!(
+                    "{} did not become healthy within {}s",
+                    service_id,
+                    HEALTH_CHECK_TIMEOUT.as_secs()
+                ));
+            }
+            tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+        }
+    }
+
+    /// Run one health check attempt
+    async fn probe(&self, service_id: &str, check: &HealthCheck, host_port: Option<u16>) -> bool {
+        match (check, host_port) {
+            (HealthCheck::Tcp, Some(port)) => tokio::net::TcpStream::connect(("127.0.0.1", port)).await.is_ok(),
+            (HealthCheck::Http { path }, Some(port)) => reqwest::Client::new()
+                .get(format!("http://127.0.0.1:{}{}", port, path))
+                .timeout(Duration::from_secs(2))
+                .send()
+                .await
+                .map(|response| !response.status().is_server_error())
+                .unwrap_or(false),
+            (HealthCheck::Command { command }, _) => {
+                let cmd: Vec<&str> = command.iter().map(String::as_str).collect();
+                self.exec_in_container(service_id, &cmd).await.is_ok()
+            }
+            // Port not published (yet)
+            (HealthCheck::Tcp | HealthCheck::Http { .. }, None) => false,
+        }
+    }
+}
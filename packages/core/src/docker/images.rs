@@ -0,0 +1,133 @@
+//! Image pulls, listing and pruning.
+
+use super::DockerManager;
+use crate::actions::ImagePullProgressData;
+use crate::state::{DockerImage, ImagePruneResult};
+use bollard::image::{CreateImageOptions, ListImagesOptions, PruneImagesOptions};
+use bollard::models::CreateImageInfo;
+use futures_util::{Stream, StreamExt};
+use std::collections::HashMap;
+use tracing::{debug, info};
+
+impl DockerManager {
+    /// Ensure an image is available locally
+    pub(super) async fn ensure_image(&self, image: &str) -> Result<(), String> {
+        debug!("Ensuring image: {}", image);
+
+        // Check if image exists
+        if self.docker.inspect_image(image).await.is_ok() {
+            debug!("Image already exists: {}", image);
+            return Ok(());
+        }
+
+        // Pull image
+        info!("Pulling image: {}", image);
+
+        let options = CreateImageOptions {
+            from_image: image,
+            ..Default::default()
+        };
+
+        let mut stream = self.docker.create_image(Some(options), None, None);
+
+        while let Some(result) = stream.next().await {
+            match result {
+                Ok(_) => {}
+                Err(e) => return Err(format!("Failed to pull image: {}", e)),
+            }
+        }
+
+        info!("Image pulled: {}", image);
+        Ok(())
+    }
+
+    /// Pull an image, yielding per-layer progress as the daemon reports it
+    pub fn pull_image(&self, image: &str) -> impl Stream<Item = Result<ImagePullProgressData, String>> + '_ {
+        info!("Pulling image: {}", image);
+
+        let options = CreateImageOptions {
+            from_image: image.to_string(),
+            ..Default::default()
+        };
+
+        self.docker
+            .create_image(Some(options), None, None)
+            .map(|result| match result {
+                Ok(info) => pull_progress(info),
+                Err(e) => Err(format!("Failed to pull image: {}", e)),
+            })
+    }
+
+    /// List local images (newest first)
+    pub async fn list_images(&self) -> Result<Vec<DockerImage>, String> {
+        let mut images = self
+            .docker
+            .list_images(Some(ListImagesOptions::<String> {
+                all: false,
+                ..Default::default()
+            }))
+            .await
+            .map_err(|e| format!("Failed to list images: {}", e))?;
+        images.sort_by_key(|image| std::cmp::Reverse(image.created));
+
+        Ok(images
+            .into_iter()
+            .map(|image| {
+                // Dangling images have no tags (or only "<none>:<none>")
+                let repo_tags: Vec<String> = image
+                    .repo_tags
+                    .into_iter()
+                    .filter(|tag| tag != "<none>:<none>")
+                    .collect();
+                DockerImage {
+                    id: image.id,
+                    dangling: repo_tags.is_empty(),
+                    repo_tags,
+                    size: image.size,
+                    created: image.created,
+                }
+            })
+            .collect())
+    }
+
+    /// Remove dangling (untagged, unused) images
+    pub async fn prune_images(&self) -> Result<ImagePruneResult, String> {
+        let mut filters = HashMap::new();
+        filters.insert("dangling".to_string(), vec!["true".to_string()]);
+
+        let response = self
+            .docker
+            .prune_images(Some(PruneImagesOptions { filters }))
+            .await
+            .map_err(|e| format!("Failed to prune images: {}", e))?;
+
+        let result = ImagePruneResult {
+            images_deleted: response.images_deleted.map(|d| d.len() as u32).unwrap_or(0),
+            space_reclaimed: response.space_reclaimed.unwrap_or(0),
+        };
+        info!(
+            "Pruned {} dangling images ({} bytes reclaimed)",
+            result.images_deleted, result.space_reclaimed
+        );
+        Ok(result)
+    }
+}
+
+/// Convert a daemon pull event into progress (or the error it reports)
+fn pull_progress(info: CreateImageInfo) -> Result<ImagePullProgressData, String> {
+    if let Some(error) = info.error.or(info.error_detail.and_then(|d| d.message)) {
+        return Err(format!("Failed to pull image: {}", error));
+    }
+
+    let (current, total) = info
+        .progress_detail
+        .map(|d| (d.current, d.total))
+        .unwrap_or_default();
+
+    Ok(ImagePullProgressData {
+        layer_id: info.id,
+        status: info.status.unwrap_or_default(),
+        current: current.map(|c| c.max(0) as u64),
+        total: total.filter(|t| *t > 0).map(|t| t as u64),
+    })
+}
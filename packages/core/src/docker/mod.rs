@@ -0,0 +1,879 @@
+//! Docker container management using bollard.
+
+mod databases;
+mod health;
+mod images;
+mod stats;
+mod templates;
+
+pub use databases::connection_string;
+pub use health::{HealthCheck, HEALTH_CHECK_TIMEOUT};
+pub use templates::{builtin_services, ServiceConfig};
+
+use crate::docker_compose::{self, ComposeProject, ComposeServiceConfig};
+use crate::service_templates;
+use crate::state::{DockerService, ExecOutput, PortConflictInfo, PortProcessInfo, ServiceType};
+use bollard::container::{
+    Config, CreateContainerOptions, ListContainersOptions, LogOutput, LogsOptions, RemoveContainerOptions,
+    RestartContainerOptions, StartContainerOptions, StopContainerOptions,
+};
+use bollard::exec::{CreateExecOptions, ResizeExecOptions, StartExecOptions, StartExecResults};
+use bollard::models::{ContainerInspectResponse, EndpointSettings, HostConfig};
+use bollard::network::{CreateNetworkOptions, InspectNetworkOptions};
+use bollard::Docker;
+use futures_util::StreamExt;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::RwLock;
+use tracing::{debug, info, warn};
+
+/// Docker manager
+pub struct DockerManager {
+    docker: Docker,
+    /// Imported docker-compose projects, keyed by project name
+    compose_projects: RwLock<HashMap<String, ComposeProject>>,
+    /// Built-in services merged with user-defined templates
+    services: RwLock<Vec<ServiceConfig>>,
+}
+
+impl DockerManager {
+    /// Create a new DockerManager
+    pub fn new() -> Result<Self, bollard::errors::Error> {
+        let docker = Docker::connect_with_local_defaults()?;
+        let services = service_templates::load_services(None).unwrap_or_else(|e| {
+            warn!("Ignoring service templates: {}", e);
+            builtin_services()
+        });
+        Ok(Self {
+            docker,
+            compose_projects: RwLock::new(HashMap::new()),
+            services: RwLock::new(services),
+        })
+    }
+
+    /// Check if Docker is available
+    pub async fn is_available(&self) -> bool {
+        self.docker.ping().await.is_ok()
+    }
+
+    /// List all services with their current status
+    /// Returns ALL containers on the system, grouped by project prefix
+    pub async fn list_services(&self) -> Vec<DockerService> {
+        let mut services = Vec::new();
+
+        // Get ALL containers (no filter)
+        let all_containers = self
+            .docker
+            .list_containers(Some(ListContainersOptions::<String> {
+                all: true,
+                ..Default::default()
+            }))
+            .await
+            .unwrap_or_default();
+
+        // Track which rstn services are already running
+        let mut running_rstn_ids: Vec<String> = Vec::new();
+
+        // Build service list from ALL running containers
+        for container in &all_containers {
+            let container_name = container
+                .names
+                .as_ref()
+                .and_then(|n| n.first())
+                .map(|n| n.trim_start_matches('/').to_string())
+                .unwrap_or_default();
+
+            if container_name.is_empty() {
+                continue;
+            }
+
+            let is_rstn_managed = container_name.starts_with("rstn-");
+            let compose_project = self.compose_project_for_container(&container_name);
+            let project_group = compose_project
+                .clone()
+                .unwrap_or_else(|| Self::detect_project_group(&container_name));
+
+            // Track running rstn services
+            if is_rstn_managed {
+                running_rstn_ids.push(container_name.clone());
+            }
+
+            let status = match container.state.as_deref() {
+                Some("running") => "running".to_string(),
+                Some("created") | Some("restarting") => "starting".to_string(),
+                Some("exited") | Some("dead") => "stopped".to_string(),
+                _ => "stopped".to_string(),
+            };
+
+            // Get the port from container ports
+            let port = container.ports.as_ref().and_then(|ports| {
+                ports.first().and_then(|p| p.public_port.map(|pp| pp as u32))
+            });
+
+            // Determine service type (best effort for non-rstn containers)
+            let service_type = match self.service_config(&container_name) {
+                Some(config) => config.service_type,
+                None => Self::detect_service_type(&container.image.clone().unwrap_or_default()),
+            };
+
+            let name = match &compose_project {
+                Some(project) => container_name
+                    .strip_prefix(&format!("rstn-{}-", project))
+                    .unwrap_or(&container_name)
+                    .to_string(),
+                None => self.extract_service_name(&container_name),
+            };
+
+            services.push(DockerService {
+                id: container_name.clone(),
+                name,
+                image: container.image.clone().unwrap_or_default(),
+                status,
+                port,
+                service_type: format!("{:?}", service_type),
+                project_group: Some(project_group),
+                is_rstn_managed,
+            });
+        }
+
+        // Add built-in rstn services that aren't running (for Quick Start)
+        // (and user-defined templates)
+        for config in self.services.read().unwrap_or_else(|e| e.into_inner()).iter() {
+            if !running_rstn_ids.contains(&config.id) {
+                services.push(DockerService {
+                    id: config.id.clone(),
+                    name: config.name.clone(),
+                    image: config.image.clone(),
+                    status: "stopped".to_string(),
+                    port: Some(config.port as u32),
+                    service_type: format!("{:?}", config.service_type),
+                    project_group: Some("rstn".to_string()),
+                    is_rstn_managed: true,
+                });
+            }
+        }
+
+        // Add imported compose services that have no container yet
+        let projects = self.compose_projects.read().unwrap_or_else(|e| e.into_inner());
+        for project in projects.values() {
+            for svc in &project.services {
+                if !running_rstn_ids.contains(&svc.container_name) {
+                    services.push(DockerService {
+                        id: svc.container_name.clone(),
+                        name: svc.name.clone(),
+                        image: svc.image.clone(),
+                        status: "stopped".to_string(),
+                        port: svc.primary_port().map(|p| p as u32),
+                        service_type: format!("{:?}", Self::detect_service_type(&svc.image)),
+                        project_group: Some(project.name.clone()),
+                        is_rstn_managed: true,
+                    });
+                }
+            }
+        }
+
+        services
+    }
+
+    /// Import a docker-compose.yml and register its services as rstn-managed.
+    /// Re-importing the same project replaces the previous definition.
+    pub async fn import_compose(&self, path: &Path) -> Result<Vec<DockerService>, String> {
+        let project = docker_compose::parse_compose_file(path)?;
+        let project_name = project.name.clone();
+        info!(
+            "Imported compose project '{}' with {} services",
+            project_name,
+            project.services.len()
+        );
+
+        self.compose_projects
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(project_name.clone(), project);
+
+        Ok(self
+            .list_services()
+            .await
+            .into_iter()
+            .filter(|s| s.project_group.as_deref() == Some(project_name.as_str()))
+            .collect())
+    }
+
+    /// Start every service of an imported compose project (dependencies first)
+    pub async fn compose_up(&self, project_name: &str) -> Result<(), String> {
+        let project = self.get_compose_project(project_name)?;
+        info!("Compose up: {}", project_name);
+
+        self.ensure_network(&Self::compose_network_name(&project.name)).await?;
+        for svc in &project.services {
+            self.start_compose_service(&project.name, svc).await?;
+        }
+        Ok(())
+    }
+
+    /// Stop every running service of an imported compose project (dependents first)
+    pub async fn compose_down(&self, project_name: &str) -> Result<(), String> {
+        let project = self.get_compose_project(project_name)?;
+        info!("Compose down: {}", project_name);
+
+        for svc in project.services.iter().rev() {
+            if self.container_state(&svc.container_name).await?.as_deref() == Some("running") {
+                self.stop_service(&svc.container_name).await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn get_compose_project(&self, project_name: &str) -> Result<ComposeProject, String> {
+        self.compose_projects
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(project_name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown compose project: {}", project_name))
+    }
+
+    /// Find the imported compose service backing a container name
+    fn find_compose_service(&self, container_name: &str) -> Option<(String, ComposeServiceConfig)> {
+        let projects = self.compose_projects.read().unwrap_or_else(|e| e.into_inner());
+        projects.values().find_map(|p| {
+            p.services
+                .iter()
+                .find(|s| s.container_name == container_name)
+                .map(|s| (p.name.clone(), s.clone()))
+        })
+    }
+
+    fn compose_project_for_container(&self, container_name: &str) -> Option<String> {
+        self.find_compose_service(container_name).map(|(project, _)| project)
+    }
+
+    fn compose_network_name(project_name: &str) -> String {
+        format!("rstn-{}", project_name)
+    }
+
+    /// Get a container's state ("running", "exited", ...) or None if it doesn't exist
+    pub async fn container_state(&self, container_name: &str) -> Result<Option<String>, String> {
+        let containers = self
+            .docker
+            .list_containers(Some(ListContainersOptions::<String> {
+                all: true,
+                filters: {
+                    let mut filters = HashMap::new();
+                    filters.insert("name".to_string(), vec![format!("^/{}$", container_name)]);
+                    filters
+                },
+                ..Default::default()
+            }))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(containers.first().map(|c| c.state.clone().unwrap_or_default()))
+    }
+
+    /// Create a bridge network if it doesn't exist yet
+    async fn ensure_network(&self, network_name: &str) -> Result<(), String> {
+        if self
+            .docker
+            .inspect_network(network_name, None::<InspectNetworkOptions<String>>)
+            .await
+            .is_ok()
+        {
+            return Ok(());
+        }
+
+        debug!("Creating network: {}", network_name);
+        self.docker
+            .create_network(CreateNetworkOptions {
+                name: network_name,
+                driver: "bridge",
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Create (if needed) and start a container for a compose service.
+    /// Services join the project network with their compose name as alias,
+    /// so `depends_on` hostnames resolve like under docker compose.
+    async fn start_compose_service(&self, project_name: &str, svc: &ComposeServiceConfig) -> Result<(), String> {
+        let name = svc.container_name.as_str();
+        info!("Starting compose service: {}", name);
+
+        match self.container_state(name).await? {
+            Some(state) if state == "running" => return Ok(()),
+            Some(_) => {}
+            None => {
+                self.ensure_image(&svc.image).await?;
+
+                let mut bindings = HashMap::new();
+                for port in &svc.ports {
+                    bindings.insert(
+                        format!("{}/tcp", port.container),
+                        Some(vec![bollard::models::PortBinding {
+                            host_ip: Some("0.0.0.0".to_string()),
+                            host_port: Some(port.host.to_string()),
+                        }]),
+                    );
+                }
+
+                let network = Self::compose_network_name(project_name);
+                let host_config = HostConfig {
+                    port_bindings: Some(bindings),
+                    binds: (!svc.volumes.is_empty()).then(|| svc.volumes.clone()),
+                    network_mode: Some(network.clone()),
+                    ..Default::default()
+                };
+
+                let container_config = Config {
+                    image: Some(svc.image.clone()),
+                    env: Some(svc.env.clone()),
+                    host_config: Some(host_config),
+                    networking_config: Some(bollard::container::NetworkingConfig {
+                        endpoints_config: HashMap::from([(
+                            network,
+                            EndpointSettings {
+                                aliases: Some(vec![svc.name.clone()]),
+                                ..Default::default()
+                            },
+                        )]),
+                    }),
+                    ..Default::default()
+                };
+
+                self.docker
+                    .create_container(
+                        Some(CreateContainerOptions { name, platform: None }),
+                        container_config,
+                    )
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+
+        self.docker
+            .start_container(name, None::<StartContainerOptions<String>>)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        info!("Compose service started: {}", name);
+        Ok(())
+    }
+
+    /// Detect project group from container name
+    /// e.g., "tech-platform-postgres" -> "tech-platform"
+    /// e.g., "rstn-postgres" -> "rstn"
+    /// e.g., "pg-bench" -> "pg-bench" (single segment)
+    fn detect_project_group(container_name: &str) -> String {
+        let parts: Vec<&str> = container_name.split('-').collect();
+        if parts.len() >= 2 {
+            // Check if it looks like "prefix-service" pattern
+            // For names like "tech-platform-postgres", join first two parts
+            // For names like "rstn-postgres", use first part
+            if parts.len() >= 3 && !["rstn", "pg"].contains(&parts[0]) {
+                // Likely "project-subproject-service" -> "project-subproject"
+                parts[..parts.len() - 1].join("-")
+            } else {
+                // "prefix-service" -> "prefix"
+                parts[0].to_string()
+            }
+        } else {
+            // Single word or no hyphens - use as-is
+            container_name.to_string()
+        }
+    }
+
+    /// Extract display name from container name
+    /// e.g., "tech-platform-postgres" -> "postgres"
+    /// e.g., "rstn-postgres" -> "postgres"
+    fn extract_service_name(&self, container_name: &str) -> String {
+        // For rstn containers, use the friendly name from config
+        if let Some(config) = self.service_config(container_name) {
+            return config.name;
+        }
+        // Otherwise extract last part after hyphen
+        container_name
+            .rsplit('-')
+            .next()
+            .unwrap_or(container_name)
+            .to_string()
+    }
+
+    /// Detect service type from image name
+    fn detect_service_type(image: &str) -> ServiceType {
+        let image_lower = image.to_lowercase();
+        if image_lower.contains("postgres") || image_lower.contains("mysql") || image_lower.contains("mongo") {
+            ServiceType::Database
+        } else if image_lower.contains("redis") || image_lower.contains("dragonfly") {
+            ServiceType::Cache
+        } else if image_lower.contains("rabbit") || image_lower.contains("nats") {
+            ServiceType::MessageBroker
+        } else {
+            ServiceType::Other
+        }
+    }
+
+    /// Start a service
+    pub async fn start_service(&self, service_id: &str) -> Result<(), String> {
+        info!("Starting service: {}", service_id);
+
+        if let Some((project_name, svc)) = self.find_compose_service(service_id) {
+            self.ensure_network(&Self::compose_network_name(&project_name)).await?;
+            return self.start_compose_service(&project_name, &svc).await;
+        }
+
+        let config = self
+            .service_config(service_id)
+            .ok_or_else(|| format!("Unknown service: {}", service_id))?;
+
+        // Ensure image exists
+        self.ensure_image(&config.image).await?;
+
+        // Check if container already exists
+        let containers = self
+            .docker
+            .list_containers(Some(ListContainersOptions::<String> {
+                all: true,
+                filters: {
+                    let mut filters = HashMap::new();
+                    filters.insert("name".to_string(), vec![service_id.to_string()]);
+                    filters
+                },
+                ..Default::default()
+            }))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        if let Some(container) = containers.first() {
+            // Container exists, just start it
+            if container.state.as_deref() != Some("running") {
+                self.docker
+                    .start_container(service_id, None::<StartContainerOptions<String>>)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+        } else {
+            // Create and start new container
+            debug!("Creating container: {}", service_id);
+
+            let container_config = config.container_config(config.port);
+
+            self.docker
+                .create_container(
+                    Some(CreateContainerOptions {
+                        name: service_id,
+                        platform: None,
+                    }),
+                    container_config,
+                )
+                .await
+                .map_err(|e| e.to_string())?;
+
+            debug!("Container created: {}", service_id);
+
+            self.docker
+                .start_container(service_id, None::<StartContainerOptions<String>>)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+
+        info!("Service started: {}", service_id);
+        Ok(())
+    }
+
+    /// Stop a service
+    pub async fn stop_service(&self, service_id: &str) -> Result<(), String> {
+        info!("Stopping service: {}", service_id);
+
+        self.docker
+            .stop_container(service_id, Some(StopContainerOptions { t: 10 }))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        info!("Service stopped: {}", service_id);
+        Ok(())
+    }
+
+    /// Restart a service
+    pub async fn restart_service(&self, service_id: &str) -> Result<(), String> {
+        info!("Restarting service: {}", service_id);
+
+        self.docker
+            .restart_container(service_id, Some(RestartContainerOptions { t: 10 }))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        info!("Service restarted: {}", service_id);
+        Ok(())
+    }
+
+    /// Get container logs
+    pub async fn get_logs(&self, service_id: &str, tail: usize) -> Result<Vec<String>, String> {
+        let options = LogsOptions::<String> {
+            stdout: true,
+            stderr: true,
+            tail: tail.to_string(),
+            ..Default::default()
+        };
+
+        let mut logs_stream = self.docker.logs(service_id, Some(options));
+        let mut logs = Vec::new();
+
+        while let Some(log_result) = logs_stream.next().await {
+            match log_result {
+                Ok(log) => logs.push(log.to_string()),
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+
+        Ok(logs)
+    }
+
+    /// Remove a service container
+    pub async fn remove_service(&self, service_id: &str) -> Result<(), String> {
+        info!("Removing service: {}", service_id);
+
+        // Stop first if running
+        let _ = self.stop_service(service_id).await;
+
+        self.docker
+            .remove_container(
+                service_id,
+                Some(RemoveContainerOptions {
+                    force: true,
+                    ..Default::default()
+                }),
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+
+        info!("Service removed: {}", service_id);
+        Ok(())
+    }
+
+    /// Run a command in an rstn-managed container and collect its output
+    /// (a failing command is not an error; see `exit_code`)
+    pub async fn exec(&self, service_id: &str, cmd: &[String]) -> Result<ExecOutput, String> {
+        ensure_exec_allowed(service_id, cmd)?;
+        self.run_exec(service_id, cmd).await
+    }
+
+    /// Start an interactive command (TTY) in an rstn-managed container, e.g.
+    /// a shell or `psql`. The returned I/O is attached to a terminal session.
+    pub async fn exec_interactive(
+        &self,
+        service_id: &str,
+        cmd: &[String],
+        cols: u16,
+        rows: u16,
+    ) -> Result<crate::terminal::RemoteIo, String> {
+        ensure_exec_allowed(service_id, cmd)?;
+        info!("Interactive exec in {}: {:?}", service_id, cmd);
+
+        let exec = self
+            .docker
+            .create_exec(
+                service_id,
+                CreateExecOptions {
+                    attach_stdin: Some(true),
+                    attach_stdout: Some(true),
+                    attach_stderr: Some(true),
+                    tty: Some(true),
+                    env: Some(vec!["TERM=xterm-256color".to_string()]),
+                    cmd: Some(cmd.to_vec()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| format!("Failed to create exec: {}", e))?;
+
+        let started = self
+            .docker
+            .start_exec(
+                &exec.id,
+                Some(StartExecOptions { detach: false, tty: true, output_capacity: None }),
+            )
+            .await
+            .map_err(|e| format!("Failed to start exec: {}", e))?;
+        let StartExecResults::Attached { output, input } = started else {
+            return Err("Exec started detached".to_string());
+        };
+
+        // Resizes need the Docker client: apply them from a task
+        let (resize_tx, mut resize_rx) = tokio::sync::mpsc::unbounded_channel::<(u16, u16)>();
+        let docker = self.docker.clone();
+        let exec_id = exec.id.clone();
+        tokio::spawn(async move {
+            while let Some((cols, rows)) = resize_rx.recv().await {
+                let options = ResizeExecOptions { width: cols, height: rows };
+                if let Err(e) = docker.resize_exec(&exec_id, options).await {
+                    debug!("Failed to resize exec {}: {}", exec_id, e);
+                }
+            }
+        });
+        let _ = resize_tx.send((cols, rows));
+
+        let output = output
+            .take_while(|chunk| std::future::ready(chunk.is_ok()))
+            .filter_map(|chunk| std::future::ready(chunk.ok().map(|log| log.into_bytes().to_vec())));
+        Ok(crate::terminal::RemoteIo {
+            output: Box::pin(output),
+            input,
+            resize_tx,
+        })
+    }
+
+    /// Execute a command in a container, failing on a non-zero exit code
+    async fn exec_in_container(&self, container_id: &str, cmd: &[&str]) -> Result<String, String> {
+        let cmd: Vec<String> = cmd.iter().map(|s| s.to_string()).collect();
+        let output = self.run_exec(container_id, &cmd).await?;
+        match output.exit_code {
+            Some(exit_code) if exit_code != 0 => Err(format!(
+                "Command failed with exit code {}: {}{}",
+                exit_code, output.stderr, output.stdout
+            )),
+            _ => Ok(output.stdout),
+        }
+    }
+
+    /// Execute a command in a container and collect its output
+    async fn run_exec(&self, container_id: &str, cmd: &[String]) -> Result<ExecOutput, String> {
+        debug!("Executing in container {}: {:?}", container_id, cmd);
+
+        let exec = self.docker
+            .create_exec(
+                container_id,
+                CreateExecOptions {
+                    attach_stdout: Some(true),
+                    attach_stderr: Some(true),
+                    cmd: Some(cmd.to_vec()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(|e| format!("Failed to create exec: {}", e))?;
+
+        let output = self.docker
+            .start_exec(&exec.id, None)
+            .await
+            .map_err(|e| format!("Failed to start exec: {}", e))?;
+
+        // Keep stderr apart (e.g. mysql's password warning) so callers can
+        // parse stdout
+        let mut stdout = String::new();
+        let mut stderr = String::new();
+        if let StartExecResults::Attached { mut output, .. } = output {
+            while let Some(msg) = output.next().await {
+                match msg {
+                    Ok(LogOutput::StdErr { message }) => stderr.push_str(&String::from_utf8_lossy(&message)),
+                    Ok(log) => stdout.push_str(&log.to_string()),
+                    Err(e) => return Err(format!("Exec error: {}", e)),
+                }
+            }
+        }
+
+        let inspect = self.docker
+            .inspect_exec(&exec.id)
+            .await
+            .map_err(|e| format!("Failed to inspect exec: {}", e))?;
+
+        Ok(ExecOutput { exit_code: inspect.exit_code, stdout, stderr })
+    }
+
+    /// Start a service with a specific port override
+    pub async fn start_service_with_port(&self, service_id: &str, port: u16) -> Result<(), String> {
+        info!("Starting service {} with port override: {}", service_id, port);
+
+        let config = self
+            .service_config(service_id)
+            .ok_or_else(|| format!("Unknown service: {}", service_id))?;
+
+        // Remove existing container if any (to apply new port)
+        let _ = self.remove_service(service_id).await;
+
+        // Ensure image exists
+        self.ensure_image(&config.image).await?;
+
+        // Create container with custom port
+        let container_config = config.container_config(port);
+
+        self.docker
+            .create_container(
+                Some(CreateContainerOptions {
+                    name: service_id,
+                    platform: None,
+                }),
+                container_config,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+
+        self.docker
+            .start_container(service_id, None::<StartContainerOptions<String>>)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        info!("Service started with custom port: {} on port {}", service_id, port);
+        Ok(())
+    }
+
+    /// Stop any container by ID or name (not just rstn-* containers)
+    pub async fn stop_container(&self, container_id: &str) -> Result<(), String> {
+        info!("Stopping container: {}", container_id);
+
+        self.docker
+            .stop_container(container_id, Some(StopContainerOptions { t: 10 }))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        info!("Container stopped: {}", container_id);
+        Ok(())
+    }
+
+    /// Check for port conflict before starting a service
+    /// Returns None if no conflict, Some(PortConflictInfo) if port is in use
+    pub async fn check_port_conflict(&self, service_id: &str) -> Result<Option<PortConflictInfo>, String> {
+        let target_port = match self.service_config(service_id) {
+            Some(config) => config.port,
+            None => match self.find_compose_service(service_id) {
+                Some((_, svc)) => match svc.primary_port() {
+                    Some(port) => port,
+                    None => return Ok(None),
+                },
+                None => return Err(format!("Unknown service: {}", service_id)),
+            },
+        };
+
+        // List all running containers
+        let containers = self
+            .docker
+            .list_containers(Some(ListContainersOptions::<String> {
+                all: false, // Only running containers
+                ..Default::default()
+            }))
+            .await
+            .map_err(|e| e.to_string())?;
+
+        // Check if any container is using this port
+        for container in containers {
+            if let Some(ports) = &container.ports {
+                for port_info in ports {
+                    if port_info.public_port == Some(target_port) {
+                        // Found a conflict!
+                        let container_id = container.id.clone().unwrap_or_default();
+                        let container_name = container
+                            .names
+                            .as_ref()
+                            .and_then(|n| n.first())
+                            .map(|n| n.trim_start_matches('/').to_string())
+                            .unwrap_or_default();
+                        let is_rstn_managed = container_name.starts_with("rstn-");
+
+                        // Find next available port
+                        let suggested_port = self.find_next_available_port(target_port).await;
+
+                        return Ok(Some(PortConflictInfo {
+                            requested_port: target_port as u32,
+                            container_id,
+                            container_name,
+                            container_image: container.image.clone().unwrap_or_default(),
+                            is_rstn_managed,
+                            suggested_port: suggested_port as u32,
+                            process: None,
+                        }));
+                    }
+                }
+            }
+        }
+
+        // Not a container; maybe a process running directly on the host
+        let held = tokio::task::spawn_blocking(move || {
+            crate::net_inspect::port_in_use(target_port).then(|| crate::net_inspect::inspect_port(target_port))
+        })
+        .await
+        .map_err(|e| e.to_string())?;
+        if let Some(processes) = held {
+            // pid 0: the owner isn't visible to us (e.g. another user's process)
+            let process = processes.into_iter().next().unwrap_or(PortProcessInfo {
+                pid: 0,
+                name: String::new(),
+                exe: None,
+                user: None,
+                command: None,
+            });
+            let suggested_port = self.find_next_available_port(target_port).await;
+            return Ok(Some(PortConflictInfo {
+                requested_port: target_port as u32,
+                container_id: String::new(),
+                container_name: if process.name.is_empty() {
+                    "another process".to_string()
+                } else {
+                    process.name.clone()
+                },
+                container_image: String::new(),
+                is_rstn_managed: false,
+                suggested_port: suggested_port as u32,
+                process: Some(process),
+            }));
+        }
+
+        Ok(None)
+    }
+
+    /// Find the next available port starting from a base port
+    async fn find_next_available_port(&self, base_port: u16) -> u16 {
+        let containers = self
+            .docker
+            .list_containers(Some(ListContainersOptions::<String> {
+                all: false,
+                ..Default::default()
+            }))
+            .await
+            .unwrap_or_default();
+
+        // Collect all used ports
+        let mut used_ports: Vec<u16> = Vec::new();
+        for container in &containers {
+            if let Some(ports) = &container.ports {
+                for port_info in ports {
+                    if let Some(public_port) = port_info.public_port {
+                        used_ports.push(public_port);
+                    }
+                }
+            }
+        }
+
+        // Find next available port
+        let mut port = base_port + 1;
+        while (used_ports.contains(&port) || crate::net_inspect::port_in_use(port)) && port < 65535 {
+            port += 1;
+        }
+
+        port
+    }
+}
+
+/// Host port bound to a container's internal TCP port
+fn published_port(container: &ContainerInspectResponse, internal_port: u16) -> Option<u16> {
+    container
+        .network_settings
+        .as_ref()?
+        .ports
+        .as_ref()?
+        .get(&format!("{}/tcp", internal_port))?
+        .as_ref()?
+        .iter()
+        .find_map(|binding| binding.host_port.as_ref()?.parse().ok())
+}
+
+/// Exec is limited to rstn-managed containers and needs a command
+fn ensure_exec_allowed(service_id: &str, cmd: &[String]) -> Result<(), String> {
+    if !service_id.starts_with("rstn-") {
+        return Err(format!("{} is not managed by rstn", service_id));
+    }
+    if cmd.first().is_none_or(|program| program.trim().is_empty()) {
+        return Err("No command given".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,87 @@
+//! Container resource stats.
+
+use super::DockerManager;
+use crate::app_state::ContainerStats;
+use bollard::container::{MemoryStatsStats, Stats, StatsOptions};
+use futures_util::{Stream, StreamExt};
+
+impl DockerManager {
+    /// Stream resource usage for a running container (about one sample per
+    /// second; ends when the container stops)
+    pub fn stats_stream(&self, service_id: &str) -> impl Stream<Item = Result<ContainerStats, String>> + '_ {
+        let options = StatsOptions {
+            stream: true,
+            one_shot: false,
+        };
+
+        self.docker.stats(service_id, Some(options)).map(|result| {
+            result
+                .map(|stats| container_stats(&stats))
+                .map_err(|e| format!("Failed to read container stats: {}", e))
+        })
+    }
+
+    /// One resource sample of a running container
+    pub async fn stats_snapshot(&self, service_id: &str) -> Result<ContainerStats, String> {
+        let options = StatsOptions {
+            stream: false,
+            one_shot: false,
+        };
+
+        self.docker
+            .stats(service_id, Some(options))
+            .next()
+            .await
+            .ok_or_else(|| format!("No stats for {}", service_id))?
+            .map(|stats| container_stats(&stats))
+            .map_err(|e| format!("Failed to read container stats: {}", e))
+    }
+}
+
+/// Compute a resource sample the way `docker stats` does
+fn container_stats(stats: &Stats) -> ContainerStats {
+    let cpu_delta = stats
+        .cpu_stats
+        .cpu_usage
+        .total_usage
+        .saturating_sub(stats.precpu_stats.cpu_usage.total_usage);
+    let system_delta = stats
+        .cpu_stats
+        .system_cpu_usage
+        .unwrap_or(0)
+        .saturating_sub(stats.precpu_stats.system_cpu_usage.unwrap_or(0));
+    let online_cpus = stats
+        .cpu_stats
+        .online_cpus
+        .or_else(|| stats.cpu_stats.cpu_usage.percpu_usage.as_ref().map(|p| p.len() as u64))
+        .unwrap_or(1);
+    let cpu_percent = if system_delta > 0 {
+        cpu_delta as f64 / system_delta as f64 * online_cpus as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    // Page cache is reclaimable, so it is not counted as usage
+    let cache = match stats.memory_stats.stats {
+        Some(MemoryStatsStats::V1(v1)) => v1.total_inactive_file,
+        Some(MemoryStatsStats::V2(v2)) => v2.inactive_file,
+        None => 0,
+    };
+    let memory_usage = stats.memory_stats.usage.unwrap_or(0).saturating_sub(cache);
+
+    let (net_rx_bytes, net_tx_bytes) = stats
+        .networks
+        .iter()
+        .flat_map(|networks| networks.values())
+        .chain(stats.network.iter())
+        .fold((0, 0), |(rx, tx), net| (rx + net.rx_bytes, tx + net.tx_bytes));
+
+    ContainerStats {
+        cpu_percent,
+        memory_usage,
+        memory_limit: stats.memory_stats.limit.unwrap_or(0),
+        net_rx_bytes,
+        net_tx_bytes,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    }
+}
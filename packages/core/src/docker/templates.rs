@@ -0,0 +1,174 @@
+//! Service definitions: the built-in services and `services.toml` templates.
+
+use super::{DockerManager, HealthCheck};
+use crate::service_templates;
+use crate::state::ServiceType;
+use bollard::container::Config;
+use bollard::models::HostConfig;
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::info;
+
+/// Service definition (built-in or from a `services.toml` template)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceConfig {
+    /// Container name (always `rstn-` prefixed)
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    /// Published host port (display port, checked for conflicts)
+    pub port: u16,
+    pub internal_port: u16,
+    /// Additional (host, container) port mappings
+    pub extra_ports: Vec<(u16, u16)>,
+    pub env: Vec<(String, String)>,
+    /// Volume binds in Docker `host:container[:mode]` form
+    pub volumes: Vec<String>,
+    pub service_type: ServiceType,
+    /// Readiness probe (None = running container counts as healthy)
+    pub healthcheck: Option<HealthCheck>,
+}
+
+impl ServiceConfig {
+    fn builtin(
+        id: &str,
+        name: &str,
+        image: &str,
+        port: u16,
+        env: &[(&str, &str)],
+        service_type: ServiceType,
+        healthcheck: HealthCheck,
+    ) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            image: image.to_string(),
+            port,
+            internal_port: port,
+            extra_ports: Vec::new(),
+            env: env.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            volumes: Vec::new(),
+            service_type,
+            healthcheck: Some(healthcheck),
+        }
+    }
+
+    /// Port bindings for the container, publishing the primary port on `host_port`
+    fn port_bindings(&self, host_port: u16) -> HashMap<String, Option<Vec<bollard::models::PortBinding>>> {
+        std::iter::once((host_port, self.internal_port))
+            .chain(self.extra_ports.iter().copied())
+            .map(|(host, container)| {
+                (
+                    format!("{}/tcp", container),
+                    Some(vec![bollard::models::PortBinding {
+                        host_ip: Some("0.0.0.0".to_string()),
+                        host_port: Some(host.to_string()),
+                    }]),
+                )
+            })
+            .collect()
+    }
+
+    /// Container config publishing the primary port on `host_port`
+    pub(super) fn container_config(&self, host_port: u16) -> Config<String> {
+        let host_config = HostConfig {
+            port_bindings: Some(self.port_bindings(host_port)),
+            binds: (!self.volumes.is_empty()).then(|| self.volumes.clone()),
+            ..Default::default()
+        };
+
+        Config {
+            image: Some(self.image.clone()),
+            env: Some(self.env.iter().map(|(k, v)| format!("{}={}", k, v)).collect()),
+            host_config: Some(host_config),
+            ..Default::default()
+        }
+    }
+}
+
+/// Built-in service definitions
+pub fn builtin_services() -> Vec<ServiceConfig> {
+    let command = |args: &[&str]| HealthCheck::Command {
+        command: args.iter().map(|a| a.to_string()).collect(),
+    };
+
+    vec![
+        ServiceConfig::builtin(
+            "rstn-postgres",
+            "PostgreSQL",
+            "postgres:16-alpine",
+            5432,
+            &[("POSTGRES_PASSWORD", "postgres")],
+            ServiceType::Database,
+            command(&["pg_isready", "-U", "postgres"]),
+        ),
+        ServiceConfig::builtin(
+            "rstn-mysql",
+            "MySQL",
+            "mysql:8",
+            3306,
+            &[("MYSQL_ROOT_PASSWORD", "mysql")],
+            ServiceType::Database,
+            command(&["mysqladmin", "ping", "-h", "127.0.0.1", "-uroot", "-pmysql"]),
+        ),
+        ServiceConfig::builtin(
+            "rstn-mongodb",
+            "MongoDB",
+            "mongo:7",
+            27017,
+            &[],
+            ServiceType::Database,
+            command(&["mongosh", "--quiet", "--eval", "db.adminCommand('ping')"]),
+        ),
+        ServiceConfig::builtin(
+            "rstn-redis",
+            "Redis",
+            "redis:7-alpine",
+            6379,
+            &[],
+            ServiceType::Cache,
+            command(&["redis-cli", "ping"]),
+        ),
+        ServiceConfig::builtin(
+            "rstn-rabbitmq",
+            "RabbitMQ",
+            "rabbitmq:3-management",
+            5672,
+            &[],
+            ServiceType::MessageBroker,
+            command(&["rabbitmq-diagnostics", "-q", "ping"]),
+        ),
+        ServiceConfig::builtin(
+            "rstn-nats",
+            "NATS",
+            "nats:latest",
+            4222,
+            &[],
+            ServiceType::Other,
+            HealthCheck::Tcp,
+        ),
+    ]
+}
+
+impl DockerManager {
+    /// Reload `~/.rstn/services.toml` (and the project's `.rstn/services.toml`).
+    /// Keeps the current definitions if a file fails to parse.
+    /// Returns the number of services defined.
+    pub fn reload_service_templates(&self, project_path: Option<&Path>) -> Result<usize, String> {
+        let services = service_templates::load_services(project_path)?;
+        let count = services.len();
+        *self.services.write().unwrap_or_else(|e| e.into_inner()) = services;
+        info!("Loaded {} service definitions", count);
+        Ok(count)
+    }
+
+    /// Definition of a built-in or templated service
+    pub(super) fn service_config(&self, service_id: &str) -> Option<ServiceConfig> {
+        self.services
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|s| s.id == service_id)
+            .cloned()
+    }
+}
@@ -0,0 +1,426 @@
+//! Docker Compose project import.
+//!
+//! Parses a docker-compose.yml into service definitions that DockerManager
+//! can run as rstn-managed containers (`rstn-<project>-<service>`).
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A parsed compose project (one docker-compose.yml)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComposeProject {
+    /// Project name (directory name of the compose file)
+    pub name: String,
+    /// Path to the compose file
+    pub file_path: PathBuf,
+    /// Services in dependency order (dependencies first)
+    pub services: Vec<ComposeServiceConfig>,
+}
+
+/// A single service from a compose file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComposeServiceConfig {
+    /// Service key in the compose file (e.g., "db")
+    pub name: String,
+    /// Container name used by rstn (e.g., "rstn-myapp-db")
+    pub container_name: String,
+    pub image: String,
+    pub ports: Vec<ComposePort>,
+    /// Volume binds in Docker `host:container[:mode]` form (host paths resolved)
+    pub volumes: Vec<String>,
+    /// Environment variables as `KEY=value`
+    pub env: Vec<String>,
+    pub depends_on: Vec<String>,
+}
+
+/// Host -> container port mapping
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComposePort {
+    pub host: u16,
+    pub container: u16,
+}
+
+impl ComposeServiceConfig {
+    /// First published host port (used as the service's display port)
+    pub fn primary_port(&self) -> Option<u16> {
+        self.ports.first().map(|p| p.host)
+    }
+}
+
+// ============================================================================
+// Raw YAML Schema
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct RawComposeFile {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    services: HashMap<String, RawService>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawService {
+    image: Option<String>,
+    #[serde(default)]
+    ports: Vec<RawPort>,
+    #[serde(default)]
+    volumes: Vec<RawVolume>,
+    #[serde(default)]
+    environment: Option<RawEnvironment>,
+    #[serde(default)]
+    depends_on: Option<RawDependsOn>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawPort {
+    Long {
+        target: u16,
+        published: Option<serde_yaml::Value>,
+    },
+    Short(serde_yaml::Value),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawVolume {
+    Short(String),
+    Long {
+        source: Option<String>,
+        target: String,
+        #[serde(default)]
+        read_only: bool,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawEnvironment {
+    List(Vec<String>),
+    Map(HashMap<String, Option<serde_yaml::Value>>),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawDependsOn {
+    List(Vec<String>),
+    Map(HashMap<String, serde_yaml::Value>),
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// Parse a docker-compose.yml file from disk.
+pub fn parse_compose_file(path: &Path) -> Result<ComposeProject, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    let base_dir = path.parent().unwrap_or(Path::new("."));
+    let dir_name = base_dir
+        .canonicalize()
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "compose".to_string());
+
+    let mut project = parse_compose_str(&content, &dir_name, base_dir)?;
+    project.file_path = path.to_path_buf();
+    Ok(project)
+}
+
+/// Parse compose YAML content.
+///
+/// `default_name` is used when the file has no top-level `name:`; relative
+/// volume paths are resolved against `base_dir`.
+pub fn parse_compose_str(
+    content: &str,
+    default_name: &str,
+    base_dir: &Path,
+) -> Result<ComposeProject, String> {
+    let raw: RawComposeFile =
+        serde_yaml::from_str(content).map_err(|e| format!("Invalid compose file: {}", e))?;
+
+    if raw.services.is_empty() {
+        return Err("Compose file defines no services".to_string());
+    }
+
+    let project_name = sanitize_name(raw.name.as_deref().unwrap_or(default_name));
+
+    let mut services = Vec::new();
+    for (name, svc) in raw.services {
+        let image = svc
+            .image
+            .ok_or_else(|| format!("Service '{}' has no image (build-only services are not supported)", name))?;
+
+        let ports = svc
+            .ports
+            .iter()
+            .map(parse_port)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Service '{}': {}", name, e))?;
+
+        let volumes = svc
+            .volumes
+            .iter()
+            .filter_map(|v| resolve_volume(v, base_dir))
+            .collect();
+
+        let env = match svc.environment {
+            Some(RawEnvironment::List(items)) => items,
+            Some(RawEnvironment::Map(map)) => {
+                let mut items: Vec<String> = map
+                    .into_iter()
+                    .map(|(k, v)| format!("{}={}", k, yaml_scalar_to_string(v.as_ref())))
+                    .collect();
+                items.sort();
+                items
+            }
+            None => Vec::new(),
+        };
+
+        let mut depends_on = match svc.depends_on {
+            Some(RawDependsOn::List(items)) => items,
+            Some(RawDependsOn::Map(map)) => map.into_keys().collect(),
+            None => Vec::new(),
+        };
+        depends_on.sort();
+
+        services.push(ComposeServiceConfig {
+            container_name: format!("rstn-{}-{}", project_name, sanitize_name(&name)),
+            name,
+            image,
+            ports,
+            volumes,
+            env,
+            depends_on,
+        });
+    }
+
+    let services = order_by_dependencies(services)?;
+
+    Ok(ComposeProject {
+        name: project_name,
+        file_path: PathBuf::new(),
+        services,
+    })
+}
+
+/// Parse a compose port entry ("5432", "5432:5432", "127.0.0.1:8080:80/tcp", long syntax)
+fn parse_port(port: &RawPort) -> Result<ComposePort, String> {
+    match port {
+        RawPort::Long { target, published } => {
+            let host = match published {
+                Some(v) => yaml_scalar_to_string(Some(v))
+                    .parse::<u16>()
+                    .map_err(|_| format!("Invalid published port: {:?}", v))?,
+                None => *target,
+            };
+            Ok(ComposePort {
+                host,
+                container: *target,
+            })
+        }
+        RawPort::Short(value) => {
+            let spec = yaml_scalar_to_string(Some(value));
+            let spec = spec.split('/').next().unwrap_or_default();
+            let parts: Vec<&str> = spec.split(':').collect();
+            let (host, container) = match parts.as_slice() {
+                [container] => (*container, *container),
+                [host, container] => (*host, *container),
+                [_ip, host, container] => (*host, *container),
+                _ => return Err(format!("Invalid port mapping: {}", spec)),
+            };
+            let parse = |s: &str| {
+                s.parse::<u16>()
+                    .map_err(|_| format!("Invalid port mapping: {} (port ranges are not supported)", spec))
+            };
+            Ok(ComposePort {
+                host: parse(host)?,
+                container: parse(container)?,
+            })
+        }
+    }
+}
+
+/// Resolve a volume entry to a Docker bind string. Relative host paths are
+/// resolved against the compose file directory; named volumes pass through.
+fn resolve_volume(volume: &RawVolume, base_dir: &Path) -> Option<String> {
+    let (source, target, mode) = match volume {
+        RawVolume::Short(spec) => {
+            let parts: Vec<&str> = spec.splitn(3, ':').collect();
+            match parts.as_slice() {
+                // Anonymous volume - nothing to bind
+                [_] => return None,
+                [source, target] => (source.to_string(), target.to_string(), None),
+                [source, target, mode] => {
+                    (source.to_string(), target.to_string(), Some(mode.to_string()))
+                }
+                _ => return None,
+            }
+        }
+        RawVolume::Long {
+            source,
+            target,
+            read_only,
+        } => (
+            source.clone()?,
+            target.clone(),
+            read_only.then(|| "ro".to_string()),
+        ),
+    };
+
+    let source = if source.starts_with('.') {
+        base_dir.join(&source).to_string_lossy().to_string()
+    } else {
+        source
+    };
+
+    Some(match mode {
+        Some(mode) => format!("{}:{}:{}", source, target, mode),
+        None => format!("{}:{}", source, target),
+    })
+}
+
+/// Order services so that dependencies start first (stable by name otherwise).
+fn order_by_dependencies(
+    mut services: Vec<ComposeServiceConfig>,
+) -> Result<Vec<ComposeServiceConfig>, String> {
+    services.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut ordered: Vec<ComposeServiceConfig> = Vec::with_capacity(services.len());
+    while !services.is_empty() {
+        let ready = services.iter().position(|s| {
+            s.depends_on
+                .iter()
+                .all(|dep| ordered.iter().any(|o| &o.name == dep) || !services.iter().any(|x| &x.name == dep))
+        });
+        match ready {
+            Some(idx) => ordered.push(services.remove(idx)),
+            None => {
+                return Err(format!(
+                    "Circular depends_on between services: {}",
+                    services.iter().map(|s| s.name.as_str()).collect::<Vec<_>>().join(", ")
+                ))
+            }
+        }
+    }
+
+    Ok(ordered)
+}
+
+fn yaml_scalar_to_string(value: Option<&serde_yaml::Value>) -> String {
+    match value {
+        Some(serde_yaml::Value::String(s)) => s.clone(),
+        Some(serde_yaml::Value::Number(n)) => n.to_string(),
+        Some(serde_yaml::Value::Bool(b)) => b.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Lowercase and replace anything Docker won't accept in a container name
+fn sanitize_name(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c } else { '-' })
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPOSE: &str = r#"
+services:
+  api:
+    image: node:20
+    ports:
+      - "8080:3000"
+    environment:
+      DATABASE_URL: postgres://postgres@db:5432/app
+      DEBUG: true
+    depends_on:
+      - db
+  db:
+    image: postgres:16
+    ports:
+      - "127.0.0.1:5433:5432/tcp"
+    volumes:
+      - ./data:/var/lib/postgresql/data
+      - pgdata:/backup:ro
+    environment:
+      - POSTGRES_PASSWORD=postgres
+"#;
+
+    #[test]
+    fn test_parse_compose_orders_dependencies_first() {
+        let project = parse_compose_str(COMPOSE, "My App", Path::new("/work")).unwrap();
+        assert_eq!(project.name, "my-app");
+        let names: Vec<&str> = project.services.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["db", "api"]);
+        assert_eq!(project.services[0].container_name, "rstn-my-app-db");
+    }
+
+    #[test]
+    fn test_parse_compose_ports_volumes_env() {
+        let project = parse_compose_str(COMPOSE, "app", Path::new("/work")).unwrap();
+        let db = &project.services[0];
+        assert_eq!(db.ports, vec![ComposePort { host: 5433, container: 5432 }]);
+        assert_eq!(
+            db.volumes,
+            vec![
+                "/work/./data:/var/lib/postgresql/data".to_string(),
+                "pgdata:/backup:ro".to_string(),
+            ]
+        );
+        assert_eq!(db.env, vec!["POSTGRES_PASSWORD=postgres".to_string()]);
+
+        let api = &project.services[1];
+        assert_eq!(api.primary_port(), Some(8080));
+        assert_eq!(
+            api.env,
+            vec![
+                "DATABASE_URL=postgres://postgres@db:5432/app".to_string(),
+                "DEBUG=true".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_compose_top_level_name_and_long_ports() {
+        let yaml = r#"
+name: stack
+services:
+  cache:
+    image: redis:7
+    ports:
+      - target: 6379
+        published: "6380"
+"#;
+        let project = parse_compose_str(yaml, "ignored", Path::new("/work")).unwrap();
+        assert_eq!(project.name, "stack");
+        assert_eq!(project.services[0].ports, vec![ComposePort { host: 6380, container: 6379 }]);
+    }
+
+    #[test]
+    fn test_parse_compose_rejects_build_only_and_cycles() {
+        let build_only = "services:\n  app:\n    build: .\n";
+        assert!(parse_compose_str(build_only, "x", Path::new("/")).unwrap_err().contains("no image"));
+
+        let cyclic = r#"
+services:
+  a:
+    image: alpine
+    depends_on: [b]
+  b:
+    image: alpine
+    depends_on: [a]
+"#;
+        assert!(parse_compose_str(cyclic, "x", Path::new("/")).unwrap_err().contains("Circular"));
+    }
+}
@@ -0,0 +1,326 @@
+//! Pre-flight diagnostics (`rstn doctor`).
+//!
+//! Checks the tools and resources rstn depends on: the Claude CLI, the
+//! Docker daemon, git, just, a free port for the MCP server, disk space and
+//! writable config directories. Each check reports a status and, when
+//! something is wrong, a suggestion to fix it. Checks never fail the run;
+//! a missing tool is a finding, not an error.
+
+use std::net::{SocketAddr, TcpListener};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+
+use crate::mcp_registry::BASE_PORT;
+
+/// Time allowed for a tool to print its version
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Free space below which the disk check warns
+const LOW_DISK_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Free space below which the disk check fails
+const CRITICAL_DISK_BYTES: u64 = 100 * 1024 * 1024;
+
+pub const STATUS_OK: &str = "ok";
+pub const STATUS_WARNING: &str = "warning";
+pub const STATUS_ERROR: &str = "error";
+
+/// Result of a single check
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoctorCheck {
+    /// Stable identifier (e.g. "claude", "docker")
+    pub id: String,
+    pub label: String,
+    /// "ok" | "warning" | "error"
+    pub status: String,
+    /// What was found (version, free space, ...)
+    pub detail: String,
+    /// How to fix a warning or error
+    pub fix: Option<String>,
+}
+
+/// All checks of a doctor run
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DoctorReport {
+    pub checks: Vec<DoctorCheck>,
+    /// When the checks ran (ISO 8601)
+    pub ran_at: String,
+}
+
+impl DoctorCheck {
+    fn ok(id: &str, label: &str, detail: impl Into<String>) -> Self {
+        Self::new(id, label, STATUS_OK, detail, None)
+    }
+
+    fn warning(id: &str, label: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self::new(id, label, STATUS_WARNING, detail, Some(fix.into()))
+    }
+
+    fn error(id: &str, label: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self::new(id, label, STATUS_ERROR, detail, Some(fix.into()))
+    }
+
+    fn new(id: &str, label: &str, status: &str, detail: impl Into<String>, fix: Option<String>) -> Self {
+        Self {
+            id: id.to_string(),
+            label: label.to_string(),
+            status: status.to_string(),
+            detail: detail.into(),
+            fix,
+        }
+    }
+}
+
+/// Run all checks
+pub async fn run() -> DoctorReport {
+    let (claude, docker, git, just) = tokio::join!(check_claude(), check_docker(), check_git(), check_just());
+    let rstn_dir = crate::persistence::get_rstn_dir();
+    let mut checks = vec![claude, docker, git, just, check_mcp_port(BASE_PORT)];
+    checks.push(check_disk_space(&rstn_dir).await);
+    checks.push(check_config_dirs(&config_dirs()));
+    DoctorReport {
+        checks,
+        ran_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+async fn check_claude() -> DoctorCheck {
+    match command_version("claude", &["--version"]).await {
+        Some(version) => DoctorCheck::ok("claude", "Claude CLI", version),
+        None => DoctorCheck::error(
+            "claude",
+            "Claude CLI",
+            "claude was not found on PATH",
+            "Install Claude Code (npm install -g @anthropic-ai/claude-code) and run `claude` once to log in",
+        ),
+    }
+}
+
+async fn check_docker() -> DoctorCheck {
+    const LABEL: &str = "Docker daemon";
+    let docker = match bollard::Docker::connect_with_local_defaults() {
+        Ok(docker) => docker,
+        Err(e) => {
+            return DoctorCheck::warning("docker", LABEL, format!("Cannot connect: {}", e), docker_fix());
+        }
+    };
+    match tokio::time::timeout(COMMAND_TIMEOUT, docker.version()).await {
+        Ok(Ok(version)) => DoctorCheck::ok(
+            "docker",
+            LABEL,
+            format!("Docker {}", version.version.unwrap_or_else(|| "(unknown version)".to_string())),
+        ),
+        Ok(Err(e)) => DoctorCheck::warning("docker", LABEL, format!("Not running: {}", e), docker_fix()),
+        Err(_) => DoctorCheck::warning("docker", LABEL, "The daemon did not respond", docker_fix()),
+    }
+}
+
+fn docker_fix() -> &'static str {
+    "Start Docker Desktop (or the docker service). Only needed for the Dockers tab"
+}
+
+async fn check_git() -> DoctorCheck {
+    match command_version("git", &["--version"]).await {
+        Some(version) => DoctorCheck::ok("git", "git", version),
+        None => DoctorCheck::error(
+            "git",
+            "git",
+            "git was not found on PATH",
+            "Install git (https://git-scm.com/downloads)",
+        ),
+    }
+}
+
+async fn check_just() -> DoctorCheck {
+    match command_version("just", &["--version"]).await {
+        Some(version) => DoctorCheck::ok("just", "just", version),
+        None => DoctorCheck::warning(
+            "just",
+            "just",
+            "just was not found on PATH",
+            "Install just (cargo install just) to run justfile recipes from the Tasks tab",
+        ),
+    }
+}
+
+/// Whether the first MCP port is free (the server moves to the next free one otherwise)
+fn check_mcp_port(port: u16) -> DoctorCheck {
+    const LABEL: &str = "MCP server port";
+    if port_available(port) {
+        return DoctorCheck::ok("mcp_port", LABEL, format!("Port {} is available", port));
+    }
+    let next = (port.saturating_add(1)..=port.saturating_add(10)).find(|p| port_available(*p));
+    match next {
+        Some(next) => DoctorCheck::warning(
+            "mcp_port",
+            LABEL,
+            format!("Port {} is in use; the MCP server will use port {}", port, next),
+            format!("Stop the process listening on port {} to keep MCP configs stable", port),
+        ),
+        None => DoctorCheck::error(
+            "mcp_port",
+            LABEL,
+            format!("Ports {}-{} are all in use", port, port.saturating_add(10)),
+            format!("Free a port between {} and {} for the MCP server", port, port.saturating_add(10)),
+        ),
+    }
+}
+
+fn port_available(port: u16) -> bool {
+    TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], port))).is_ok()
+}
+
+/// Free space on the disk holding `dir` (or its closest existing parent)
+async fn check_disk_space(dir: &Path) -> DoctorCheck {
+    const LABEL: &str = "Disk space";
+    let existing = dir.ancestors().find(|p| p.exists()).unwrap_or(dir);
+    let output = Command::new("df").arg("-Pk").arg(existing).output();
+    let available = match tokio::time::timeout(COMMAND_TIMEOUT, output).await {
+        Ok(Ok(output)) if output.status.success() => parse_df_available(&String::from_utf8_lossy(&output.stdout)),
+        _ => None,
+    };
+    match available {
+        Some(bytes) => disk_space_check(dir, bytes),
+        None => DoctorCheck::warning(
+            "disk_space",
+            LABEL,
+            format!("Could not determine free space for {}", dir.display()),
+            "Make sure a few hundred MB are free for state, logs and archives",
+        ),
+    }
+}
+
+fn disk_space_check(dir: &Path, available: u64) -> DoctorCheck {
+    const LABEL: &str = "Disk space";
+    let detail = format!("{} free at {}", format_bytes(available), dir.display());
+    let fix = format!("Free up disk space on the volume holding {}", dir.display());
+    if available < CRITICAL_DISK_BYTES {
+        DoctorCheck::error("disk_space", LABEL, detail, fix)
+    } else if available < LOW_DISK_BYTES {
+        DoctorCheck::warning("disk_space", LABEL, detail, fix)
+    } else {
+        DoctorCheck::ok("disk_space", LABEL, detail)
+    }
+}
+
+/// Available bytes from `df -Pk` output (second line, fourth column, in KiB)
+fn parse_df_available(output: &str) -> Option<u64> {
+    let line = output.lines().nth(1)?;
+    let kib: u64 = line.split_whitespace().nth(3)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+    const MIB: f64 = 1024.0 * 1024.0;
+    if bytes as f64 >= GIB {
+        format!("{:.1} GB", bytes as f64 / GIB)
+    } else {
+        format!("{:.0} MB", bytes as f64 / MIB)
+    }
+}
+
+/// Directories rstn writes to (`~/.rstn/`, `~/.config/rustation/`)
+fn config_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![crate::persistence::get_rstn_dir()];
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join(".config").join("rustation"));
+    }
+    dirs
+}
+
+fn check_config_dirs(dirs: &[PathBuf]) -> DoctorCheck {
+    const LABEL: &str = "Config directories";
+    let failures: Vec<String> = dirs
+        .iter()
+        .filter_map(|dir| check_writable(dir).err())
+        .collect();
+    let names = dirs.iter().map(|d| d.display().to_string()).collect::<Vec<_>>().join(", ");
+    if failures.is_empty() {
+        DoctorCheck::ok("config_dirs", LABEL, format!("Writable: {}", names))
+    } else {
+        DoctorCheck::error(
+            "config_dirs",
+            LABEL,
+            failures.join("; "),
+            "Fix the ownership or permissions of these directories (e.g. chown -R $USER ~/.rstn)",
+        )
+    }
+}
+
+/// Create `dir` if needed and write (then remove) a probe file in it
+fn check_writable(dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {}", dir.display(), e))?;
+    let probe = dir.join(".rstn-doctor-probe");
+    std::fs::write(&probe, b"ok").map_err(|e| format!("Cannot write to {}: {}", dir.display(), e))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+/// First line printed by `<program> <args>` (None if missing or failing)
+async fn command_version(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output();
+    let output = tokio::time::timeout(COMMAND_TIMEOUT, output).await.ok()?.ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let version = stdout.lines().map(str::trim).find(|l| !l.is_empty())?;
+    Some(version.to_string())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_space() {
+        let output = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n\
+                      /dev/disk3s1     482797652 301022140 181775512      63% /\n";
+        assert_eq!(parse_df_available(output), Some(181_775_512 * 1024));
+        assert_eq!(parse_df_available("Filesystem\n"), None);
+
+        let dir = Path::new("/home/user/.rstn");
+        assert_eq!(disk_space_check(dir, 50 * 1024 * 1024 * 1024).status, STATUS_OK);
+        let low = disk_space_check(dir, 500 * 1024 * 1024);
+        assert_eq!(low.status, STATUS_WARNING);
+        assert_eq!(low.detail, "500 MB free at /home/user/.rstn");
+        assert_eq!(disk_space_check(dir, 10 * 1024 * 1024).status, STATUS_ERROR);
+    }
+
+    #[test]
+    fn test_config_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("config").join("rustation");
+        let check = check_config_dirs(std::slice::from_ref(&nested));
+        assert_eq!(check.status, STATUS_OK);
+        assert!(nested.is_dir());
+        assert!(!nested.join(".rstn-doctor-probe").exists());
+
+        // A file where a directory is expected
+        let blocked = dir.path().join("blocked");
+        std::fs::write(&blocked, "").unwrap();
+        let check = check_config_dirs(&[nested, blocked.join("sub")]);
+        assert_eq!(check.status, STATUS_ERROR);
+        assert!(check.fix.is_some());
+    }
+
+    #[test]
+    fn test_mcp_port_in_use() {
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let check = check_mcp_port(port);
+        assert_ne!(check.status, STATUS_OK);
+        assert!(check.detail.contains(&port.to_string()));
+    }
+}
@@ -0,0 +1,223 @@
+//! Writing accepted edits to the worktree.
+//!
+//! All new file contents are computed before anything is written, so a hunk
+//! that doesn't match aborts the whole apply. Existing files are copied to
+//! the backup directory first, and if a write fails the files already
+//! written are restored from there.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::parse::is_safe_path;
+use super::ProposedEdit;
+use crate::worktree::diff::{DiffFileStatus, DiffHunk, DiffLineType};
+
+/// Result of applying one file: new content, or None to delete it
+struct Planned {
+    path: String,
+    target: PathBuf,
+    content: Option<String>,
+    existed: bool,
+}
+
+/// Apply `edits` under `root`, saving the previous versions in `backup_dir`.
+///
+/// Returns the paths written. Nothing is changed if any edit fails to apply.
+pub fn apply_edits(root: &Path, edits: &[ProposedEdit], backup_dir: &Path) -> Result<Vec<String>, String> {
+    let planned = edits
+        .iter()
+        .map(|edit| plan(root, edit).map_err(|e| format!("{}: {}", edit.path, e)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for file in planned.iter().filter(|file| file.existed) {
+        let backup = backup_dir.join(&file.path);
+        if let Some(parent) = backup.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create backup directory: {}", e))?;
+        }
+        fs::copy(&file.target, &backup).map_err(|e| format!("Failed to back up {}: {}", file.path, e))?;
+    }
+
+    for (index, file) in planned.iter().enumerate() {
+        if let Err(e) = write(file) {
+            for done in &planned[..index] {
+                restore(done, backup_dir);
+            }
+            return Err(format!("Failed to write {}: {} (earlier files were restored)", file.path, e));
+        }
+    }
+    Ok(planned.into_iter().map(|file| file.path).collect())
+}
+
+fn plan(root: &Path, edit: &ProposedEdit) -> Result<Planned, String> {
+    if !is_safe_path(&edit.path) {
+        return Err("path is outside the worktree".to_string());
+    }
+    let target = root.join(&edit.path);
+    let existed = target.is_file();
+    let original = || fs::read_to_string(&target).map_err(|e| format!("cannot read file: {}", e));
+
+    let content = match (&edit.content, edit.change) {
+        (Some(content), _) => Some(content.clone()),
+        (None, DiffFileStatus::Deleted) => {
+            if !existed {
+                return Err("file does not exist".to_string());
+            }
+            None
+        }
+        (None, DiffFileStatus::Added) if !existed => Some(apply_hunks("", &edit.hunks)?),
+        (None, DiffFileStatus::Added) => return Err("file already exists".to_string()),
+        (None, _) => Some(apply_hunks(&original()?, &edit.hunks)?),
+    };
+    Ok(Planned { path: edit.path.clone(), target, content, existed })
+}
+
+fn write(file: &Planned) -> std::io::Result<()> {
+    let Some(content) = &file.content else {
+        return fs::remove_file(&file.target);
+    };
+    if let Some(parent) = file.target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = file.target.with_file_name(format!(
+        ".{}.rstn-tmp",
+        file.target.file_name().unwrap_or_default().to_string_lossy()
+    ));
+    fs::write(&tmp, content)?;
+    fs::rename(&tmp, &file.target).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp);
+    })
+}
+
+/// Undo a written file (best effort)
+fn restore(file: &Planned, backup_dir: &Path) {
+    let result = if file.existed {
+        fs::copy(backup_dir.join(&file.path), &file.target).map(|_| ())
+    } else {
+        fs::remove_file(&file.target)
+    };
+    if let Err(e) = result {
+        tracing::warn!("Failed to restore {}: {}", file.path, e);
+    }
+}
+
+/// Apply hunks by matching their old lines in `original`.
+///
+/// Line numbers in model-written diffs are unreliable, so each hunk is
+/// matched by content (ignoring trailing whitespace) at the position
+/// closest to its header, after the previous hunk.
+pub fn apply_hunks(original: &str, hunks: &[DiffHunk]) -> Result<String, String> {
+    let line_ending = if original.contains("\r\n") { "\r\n" } else { "\n" };
+    let lines: Vec<&str> = original.lines().collect();
+    let mut output: Vec<&str> = Vec::new();
+    let mut position = 0;
+
+    for (index, hunk) in hunks.iter().enumerate() {
+        let old: Vec<&str> = hunk
+            .lines
+            .iter()
+            .filter(|l| l.line_type != DiffLineType::Added)
+            .map(|l| l.content.as_str())
+            .collect();
+        let new = hunk
+            .lines
+            .iter()
+            .filter(|l| l.line_type != DiffLineType::Removed)
+            .map(|l| l.content.as_str());
+        let hint = (hunk.old_start as usize).saturating_sub(1);
+
+        let start = if old.is_empty() {
+            hint.clamp(position, lines.len())
+        } else {
+            (position..=lines.len())
+                .take_while(|&i| i + old.len() <= lines.len())
+                .filter(|&i| {
+                    lines[i..i + old.len()]
+                        .iter()
+                        .zip(&old)
+                        .all(|(a, b)| a.trim_end() == b.trim_end())
+                })
+                .min_by_key(|&i| i.abs_diff(hint))
+                .ok_or_else(|| format!("hunk {} does not match the file", index + 1))?
+        };
+
+        output.extend(&lines[position..start]);
+        output.extend(new);
+        position = start + old.len();
+    }
+    output.extend(&lines[position..]);
+
+    let mut content = output.join(line_ending);
+    if !content.is_empty() && (original.is_empty() || original.ends_with('\n')) {
+        content.push_str(line_ending);
+    }
+    Ok(content)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edits::extract_edits;
+
+    fn edits(text: &str) -> Vec<ProposedEdit> {
+        extract_edits(text)
+    }
+
+    #[test]
+    fn test_apply_hunks_by_content() {
+        let original = "a\nb\nc\nd\nb\nc\n";
+        // Wrong line numbers: the match closest to the header wins
+        let diff = "```diff\n--- a/f.txt\n+++ b/f.txt\n@@ -4,2 +4,2 @@\n b\n-c\n+C\n```";
+        let hunks = &edits(diff)[0].hunks;
+        assert_eq!(apply_hunks(original, hunks).unwrap(), "a\nb\nc\nd\nb\nC\n");
+
+        let bad = "```diff\n--- a/f.txt\n+++ b/f.txt\n@@ -1 +1 @@\n-z\n+y\n```";
+        assert!(apply_hunks(original, &edits(bad)[0].hunks).is_err());
+    }
+
+    #[test]
+    fn test_apply_hunks_longer_than_file() {
+        let context = "```diff\n--- a/f.txt\n+++ b/f.txt\n@@ -1,2 +1,3 @@\n a\n+x\n b\n```";
+        let removal = "```diff\n--- a/f.txt\n+++ b/f.txt\n@@ -1,2 +1 @@\n-a\n-b\n+c\n```";
+        for diff in [context, removal] {
+            let hunks = &edits(diff)[0].hunks;
+            assert_eq!(apply_hunks("", hunks).unwrap_err(), "hunk 1 does not match the file");
+            assert_eq!(apply_hunks("a\n", hunks).unwrap_err(), "hunk 1 does not match the file");
+        }
+    }
+
+    #[test]
+    fn test_apply_edits_with_backup() {
+        let root = tempfile::tempdir().unwrap();
+        let backups = tempfile::tempdir().unwrap();
+        fs::create_dir_all(root.path().join("src")).unwrap();
+        fs::write(root.path().join("src/lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        fs::write(root.path().join("old.txt"), "bye\n").unwrap();
+
+        let text = "```diff\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -2 +2 @@\n-fn b() {}\n+fn b() { todo!() }\n--- a/old.txt\n+++ /dev/null\n@@ -1 +0,0 @@\n-bye\n```\n\n```md docs/new.md\n# New\n```";
+        let applied = apply_edits(root.path(), &edits(text), backups.path()).unwrap();
+
+        assert_eq!(applied, vec!["src/lib.rs", "old.txt", "docs/new.md"]);
+        assert_eq!(fs::read_to_string(root.path().join("src/lib.rs")).unwrap(), "fn a() {}\nfn b() { todo!() }\n");
+        assert!(!root.path().join("old.txt").exists());
+        assert_eq!(fs::read_to_string(root.path().join("docs/new.md")).unwrap(), "# New\n");
+        assert_eq!(fs::read_to_string(backups.path().join("src/lib.rs")).unwrap(), "fn a() {}\nfn b() {}\n");
+        assert_eq!(fs::read_to_string(backups.path().join("old.txt")).unwrap(), "bye\n");
+    }
+
+    #[test]
+    fn test_apply_edits_is_all_or_nothing() {
+        let root = tempfile::tempdir().unwrap();
+        let backups = tempfile::tempdir().unwrap();
+        fs::write(root.path().join("a.txt"), "one\n").unwrap();
+
+        let text = "```txt a.txt\nreplaced\n```\n```diff\n--- a/b.txt\n+++ b/b.txt\n@@ -1 +1 @@\n-x\n+y\n```";
+        let error = apply_edits(root.path(), &edits(text), backups.path()).unwrap_err();
+
+        assert!(error.starts_with("b.txt:"), "{}", error);
+        assert_eq!(fs::read_to_string(root.path().join("a.txt")).unwrap(), "one\n");
+    }
+}
@@ -0,0 +1,87 @@
+//! File edits suggested in assistant responses.
+//!
+//! Chat answers often contain code changes as unified diffs (```diff blocks)
+//! or whole files (a fenced block labelled with its path). Instead of
+//! copy-pasting them, the blocks are parsed into a `ProposedEditSet` staged
+//! on the chat, reviewed file by file (accept / reject) and the accepted
+//! ones applied together: either every file is written or none is, and the
+//! previous versions are saved under `~/.rstn/projects/<hash>/edit-backups/`.
+
+pub mod apply;
+pub mod parse;
+
+use serde::{Deserialize, Serialize};
+
+use crate::worktree::diff::{DiffFileStatus, DiffHunk};
+
+pub use apply::apply_edits;
+pub use parse::extract_edits;
+
+/// Review state of a proposed file edit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposedEditStatus {
+    /// Not reviewed yet
+    #[default]
+    Pending,
+    Accepted,
+    Rejected,
+    /// Written to disk
+    Applied,
+}
+
+/// A change to one file, from a diff or a whole-file block
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposedEdit {
+    /// Path relative to the worktree root
+    pub path: String,
+    /// Added, Modified or Deleted
+    pub change: DiffFileStatus,
+    /// Hunks of a diff (empty for whole-file blocks)
+    pub hunks: Vec<DiffHunk>,
+    /// Full new content of a whole-file block
+    pub content: Option<String>,
+    pub additions: u32,
+    pub deletions: u32,
+    #[serde(default)]
+    pub status: ProposedEditStatus,
+}
+
+/// The edits found in one assistant message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProposedEditSet {
+    /// Chat message the edits come from
+    pub message_id: String,
+    pub edits: Vec<ProposedEdit>,
+    /// Where the previous versions were saved by the last apply
+    #[serde(default)]
+    pub backup_dir: Option<String>,
+}
+
+impl ProposedEditSet {
+    /// Set the review status of the edit for `path` (applied edits stay applied)
+    pub fn set_status(&mut self, path: &str, status: ProposedEditStatus) {
+        if let Some(edit) = self.edits.iter_mut().find(|e| e.path == path) {
+            if edit.status != ProposedEditStatus::Applied {
+                edit.status = status;
+            }
+        }
+    }
+
+    /// Edits accepted and not yet applied
+    pub fn accepted(&self) -> Vec<ProposedEdit> {
+        self.edits
+            .iter()
+            .filter(|e| e.status == ProposedEditStatus::Accepted)
+            .cloned()
+            .collect()
+    }
+
+    /// Mark the edits for `paths` as written
+    pub fn mark_applied(&mut self, paths: &[String], backup_dir: String) {
+        for edit in self.edits.iter_mut().filter(|e| paths.contains(&e.path)) {
+            edit.status = ProposedEditStatus::Applied;
+        }
+        self.backup_dir = Some(backup_dir);
+    }
+}
@@ -0,0 +1,261 @@
+//! Finding edits in an assistant response.
+//!
+//! Two kinds of fenced blocks are recognized:
+//!
+//! - Diffs: a ```diff / ```patch block, or any block starting with
+//!   `diff --git` or `--- `. Model-written diffs are often sloppy, so they
+//!   are normalized before parsing: `---`/`+++` pairs without a
+//!   `diff --git` line get one, `@@` lines without ranges match anywhere,
+//!   and blank lines inside hunks count as blank context lines.
+//! - Whole files: a block whose info string names a path
+//!   (```rust src/main.rs, ```src/main.rs, ```rust path=src/main.rs) or
+//!   whose preceding line is only the path (`src/main.rs`, **src/main.rs**,
+//!   `File: src/main.rs`).
+//!
+//! Paths must be relative and stay inside the worktree.
+
+use std::path::{Component, Path};
+
+use super::{ProposedEdit, ProposedEditStatus};
+use crate::worktree::diff::{parse_hunk_header, parse_unified_diff, DiffFileStatus};
+
+/// A fenced code block
+struct Block<'a> {
+    /// Info string after the opening fence (e.g. "rust src/main.rs")
+    info: &'a str,
+    body: String,
+    /// Last non-empty line before the fence
+    label: Option<&'a str>,
+}
+
+/// Edits found in `text`, one per file (later blocks for a file replace
+/// earlier ones; diff hunks for the same file are combined)
+pub fn extract_edits(text: &str) -> Vec<ProposedEdit> {
+    let mut edits: Vec<ProposedEdit> = Vec::new();
+    for block in fenced_blocks(text) {
+        let found = if is_diff(&block) {
+            parse_patch(&block.body)
+        } else {
+            whole_file(&block).into_iter().collect()
+        };
+        for edit in found.into_iter().filter(|e| is_safe_path(&e.path)) {
+            match edits.iter_mut().find(|e| e.path == edit.path) {
+                Some(existing) if existing.content.is_none() && edit.content.is_none() => {
+                    existing.additions += edit.additions;
+                    existing.deletions += edit.deletions;
+                    existing.hunks.extend(edit.hunks);
+                }
+                Some(existing) => *existing = edit,
+                None => edits.push(edit),
+            }
+        }
+    }
+    edits
+}
+
+fn fenced_blocks(text: &str) -> Vec<Block<'_>> {
+    let mut blocks = Vec::new();
+    let mut label = None;
+    let mut lines = text.lines();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        let fence_len = trimmed.chars().take_while(|c| *c == '`').count();
+        if fence_len < 3 {
+            if !line.trim().is_empty() {
+                label = Some(line.trim());
+            }
+            continue;
+        }
+
+        let mut body: Vec<&str> = Vec::new();
+        for line in lines.by_ref() {
+            let trimmed = line.trim();
+            if trimmed.len() >= fence_len && trimmed.chars().all(|c| c == '`') {
+                break;
+            }
+            body.push(line);
+        }
+        blocks.push(Block {
+            info: trimmed[fence_len..].trim(),
+            body: body.join("\n"),
+            label: label.take(),
+        });
+    }
+    blocks
+}
+
+fn is_diff(block: &Block) -> bool {
+    let language = block.info.split_whitespace().next().unwrap_or_default();
+    matches!(language, "diff" | "patch")
+        || block.body.starts_with("diff --git ")
+        || block.body.starts_with("--- ")
+}
+
+/// Whether `s` looks like a relative file path (has a directory or extension)
+fn looks_like_path(s: &str) -> bool {
+    !s.is_empty()
+        && !s.contains(char::is_whitespace)
+        && s.chars().any(|c| c.is_ascii_alphabetic())
+        && (s.contains('/') || s.trim_start_matches('.').contains('.'))
+        && !s.ends_with('/')
+}
+
+/// Path named in an info string: "src/a.rs", "rust src/a.rs", "rust:src/a.rs", "path=src/a.rs"
+fn path_from_info(info: &str) -> Option<&str> {
+    info.split_whitespace()
+        .map(|token| {
+            let token = token
+                .strip_prefix("path=")
+                .or_else(|| token.strip_prefix("file="))
+                .unwrap_or(token);
+            token.split_once(':').map(|(_, path)| path).unwrap_or(token)
+        })
+        .find(|token| looks_like_path(token))
+}
+
+/// Path when the line is nothing but a (decorated) path
+fn path_from_label(label: &str) -> Option<&str> {
+    let mut line = label.trim_start_matches('#').trim();
+    for prefix in ["File:", "file:", "Path:", "path:"] {
+        line = line.strip_prefix(prefix).unwrap_or(line).trim();
+    }
+    let path = line
+        .trim_end_matches(':')
+        .trim_matches(|c| c == '*' || c == '`' || c == '_');
+    looks_like_path(path).then_some(path)
+}
+
+fn whole_file(block: &Block) -> Option<ProposedEdit> {
+    let path = path_from_info(block.info).or_else(|| block.label.and_then(path_from_label))?;
+    let mut content = block.body.clone();
+    content.push('\n');
+    Some(ProposedEdit {
+        path: path.to_string(),
+        change: DiffFileStatus::Modified,
+        hunks: Vec::new(),
+        additions: content.lines().count() as u32,
+        deletions: 0,
+        content: Some(content),
+        status: ProposedEditStatus::Pending,
+    })
+}
+
+/// Path of a `---`/`+++` line without the a/ b/ prefix and trailing timestamp
+fn header_path(line: &str) -> &str {
+    let path = line[4..].split('\t').next().unwrap_or_default().trim();
+    path.strip_prefix("a/").or_else(|| path.strip_prefix("b/")).unwrap_or(path)
+}
+
+/// Make a model-written diff parseable by `parse_unified_diff`
+fn normalize_patch(body: &str) -> String {
+    let lines: Vec<&str> = body.lines().collect();
+    let has_git_headers = lines.iter().any(|l| l.starts_with("diff --git "));
+    let mut out: Vec<String> = Vec::new();
+    let mut in_hunk = false;
+
+    for (i, line) in lines.iter().enumerate() {
+        let next_is_new_path = lines.get(i + 1).is_some_and(|next| next.starts_with("+++ "));
+        if !has_git_headers && line.starts_with("--- ") && next_is_new_path {
+            let old = header_path(line);
+            let new = header_path(lines[i + 1]);
+            let path = if new == "/dev/null" { old } else { new };
+            out.push(format!("diff --git a/{} b/{}", path, path));
+            if old == "/dev/null" {
+                out.push("new file mode 100644".to_string());
+            } else if new == "/dev/null" {
+                out.push("deleted file mode 100644".to_string());
+            }
+            out.push(line.to_string());
+            in_hunk = false;
+            continue;
+        }
+
+        if line.starts_with("diff --git ") {
+            in_hunk = false;
+        } else if line.starts_with("@@") {
+            in_hunk = true;
+            if parse_hunk_header(line).is_none() {
+                // No line numbers: match the hunk anywhere in the file
+                out.push("@@ -0,0 +0,0 @@".to_string());
+                continue;
+            }
+        } else if in_hunk && line.is_empty() {
+            out.push(" ".to_string());
+            continue;
+        }
+        out.push(line.to_string());
+    }
+    out.join("\n")
+}
+
+fn parse_patch(body: &str) -> Vec<ProposedEdit> {
+    parse_unified_diff(&normalize_patch(body))
+        .into_iter()
+        // Renames and binary changes can't be applied from text
+        .filter(|file| !file.is_binary && file.status != DiffFileStatus::Renamed)
+        .filter(|file| !file.hunks.is_empty() || file.status == DiffFileStatus::Deleted)
+        .map(|file| ProposedEdit {
+            path: file.path,
+            change: file.status,
+            hunks: file.hunks,
+            content: None,
+            additions: file.additions,
+            deletions: file.deletions,
+            status: ProposedEditStatus::Pending,
+        })
+        .collect()
+}
+
+/// Relative path without `..`, so edits can't leave the worktree
+pub fn is_safe_path(path: &str) -> bool {
+    let path = Path::new(path);
+    !path.as_os_str().is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_sloppy_diff() {
+        let text = "Here is the fix:\n\n```diff\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@\n fn main() {\n-    old();\n+    new();\n\n }\n--- /dev/null\n+++ b/NOTES.md\n@@ -0,0 +1 @@\n+# Notes\n```\n";
+
+        let edits = extract_edits(text);
+        assert_eq!(edits.len(), 2);
+        assert_eq!(edits[0].path, "src/lib.rs");
+        assert_eq!(edits[0].change, DiffFileStatus::Modified);
+        assert_eq!((edits[0].additions, edits[0].deletions), (1, 1));
+        // Blank line kept as context, header without ranges matches anywhere
+        assert_eq!(edits[0].hunks[0].lines.len(), 5);
+        assert_eq!(edits[0].hunks[0].old_start, 0);
+        assert_eq!(edits[1].path, "NOTES.md");
+        assert_eq!(edits[1].change, DiffFileStatus::Added);
+    }
+
+    #[test]
+    fn test_extract_whole_files() {
+        let text = "```rust src/main.rs\nfn main() {}\n```\n\n**config/app.toml**\n```toml\nport = 8080\n```\n\nIn `src/util.rs`:\n```rust\nfn helper() {}\n```\n\n```bash\ncargo test\n```\n";
+
+        let edits = extract_edits(text);
+        let paths: Vec<_> = edits.iter().map(|e| e.path.as_str()).collect();
+        // Prose mentioning a path is not a file label (the block may be a snippet)
+        assert_eq!(paths, vec!["src/main.rs", "config/app.toml"]);
+        assert_eq!(edits[0].content.as_deref(), Some("fn main() {}\n"));
+        assert_eq!(edits[1].content.as_deref(), Some("port = 8080\n"));
+    }
+
+    #[test]
+    fn test_extract_rejects_unsafe_paths() {
+        let text = "```sh ../outside.sh\nrm -rf /\n```\n```text /etc/hosts\n127.0.0.1 x\n```\n";
+        assert!(extract_edits(text).is_empty());
+        assert!(is_safe_path("./src/a.rs"));
+        assert!(!is_safe_path("src/../../a.rs"));
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! Handles directory traversal, Git status integration, and file metadata.
 
+pub mod search;
+
 use crate::app_state::{FileEntry, FileKind, GitFileStatus};
 use crate::db::DbManager;
 use ignore::WalkBuilder;
@@ -68,9 +70,9 @@ pub fn read_directory(
         let updated_at = metadata
             .modified()
             .ok()
-            .and_then(|t| {
+            .map(|t| {
                 let dt: chrono::DateTime<chrono::Utc> = t.into();
-                Some(dt.to_rfc3339())
+                dt.to_rfc3339()
             })
             .unwrap_or_default();
 
@@ -0,0 +1,246 @@
+//! Full-text search across a worktree.
+//!
+//! Walks the worktree in parallel with the `ignore` crate (so .gitignore and
+//! hidden-file rules apply, like ripgrep) and collects literal matches with a
+//! one-line preview, grouped by file.
+
+use ignore::overrides::OverrideBuilder;
+use ignore::{WalkBuilder, WalkState};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Stop collecting after this many matches
+pub const MAX_MATCHES: usize = 2000;
+
+/// Files larger than this are skipped
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Longest line preview (in chars)
+const PREVIEW_MAX_CHARS: usize = 200;
+
+/// A match on one line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchMatch {
+    /// 1-based line number
+    pub line_number: usize,
+    /// 1-based column (in chars) of the first match on the line
+    pub column: usize,
+    /// The line, trimmed and shortened around the match
+    pub preview: String,
+}
+
+/// Matches in one file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchFileMatches {
+    /// Path relative to the worktree root
+    pub path: String,
+    pub matches: Vec<SearchMatch>,
+}
+
+/// Result of a workspace search (files sorted by path)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchResults {
+    pub files: Vec<SearchFileMatches>,
+    pub total_matches: usize,
+    /// Whether MAX_MATCHES was reached (more matches exist)
+    pub truncated: bool,
+}
+
+/// Search `root` for `query` (a literal string).
+///
+/// `glob` restricts the searched files (e.g. `*.rs` or `src/**`); a leading
+/// `!` excludes instead.
+pub fn search_workspace(
+    root: &Path,
+    query: &str,
+    glob: Option<&str>,
+    case_sensitive: bool,
+) -> Result<SearchResults, String> {
+    if query.is_empty() {
+        return Ok(SearchResults::default());
+    }
+
+    let mut builder = WalkBuilder::new(root);
+    // Honor .gitignore even outside a git repository
+    builder.standard_filters(true).require_git(false);
+    if let Some(glob) = glob.map(str::trim).filter(|g| !g.is_empty()) {
+        let mut overrides = OverrideBuilder::new(root);
+        overrides
+            .add(glob)
+            .map_err(|e| format!("Invalid glob '{}': {}", glob, e))?;
+        builder.overrides(
+            overrides
+                .build()
+                .map_err(|e| format!("Invalid glob '{}': {}", glob, e))?,
+        );
+    }
+
+    let needle = if case_sensitive { query.to_string() } else { query.to_lowercase() };
+    let files: Mutex<Vec<SearchFileMatches>> = Mutex::new(Vec::new());
+    let total = AtomicUsize::new(0);
+    let truncated = AtomicBool::new(false);
+
+    builder.build_parallel().run(|| {
+        Box::new(|entry| {
+            let Ok(entry) = entry else {
+                return WalkState::Continue;
+            };
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                return WalkState::Continue;
+            }
+            if entry.metadata().map_or(true, |m| m.len() > MAX_FILE_SIZE) {
+                return WalkState::Continue;
+            }
+            let Ok(bytes) = std::fs::read(entry.path()) else {
+                return WalkState::Continue;
+            };
+            // Skip binary files (NUL in the first 8 KiB, like git)
+            if bytes.iter().take(8192).any(|&b| b == 0) {
+                return WalkState::Continue;
+            }
+            let content = String::from_utf8_lossy(&bytes);
+
+            let matches = search_content(&content, &needle, case_sensitive);
+            if matches.is_empty() {
+                return WalkState::Continue;
+            }
+
+            // Reserve room under MAX_MATCHES; files past the limit are cut short
+            let previous = total.fetch_add(matches.len(), Ordering::Relaxed);
+            let room = MAX_MATCHES.saturating_sub(previous);
+            if room == 0 {
+                truncated.store(true, Ordering::Relaxed);
+                return WalkState::Quit;
+            }
+            let mut matches = matches;
+            if matches.len() > room {
+                matches.truncate(room);
+                truncated.store(true, Ordering::Relaxed);
+            }
+
+            let path = entry
+                .path()
+                .strip_prefix(root)
+                .unwrap_or(entry.path())
+                .to_string_lossy()
+                .to_string();
+            files
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(SearchFileMatches { path, matches });
+            WalkState::Continue
+        })
+    });
+
+    let mut files = files.into_inner().unwrap_or_else(|e| e.into_inner());
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(SearchResults {
+        total_matches: files.iter().map(|f| f.matches.len()).sum(),
+        files,
+        truncated: truncated.into_inner(),
+    })
+}
+
+/// Find matching lines (`needle` is already lowercased when case-insensitive)
+fn search_content(content: &str, needle: &str, case_sensitive: bool) -> Vec<SearchMatch> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let haystack = if case_sensitive {
+                std::borrow::Cow::Borrowed(line)
+            } else {
+                std::borrow::Cow::Owned(line.to_lowercase())
+            };
+            let byte_offset = haystack.find(needle)?;
+            let column = haystack[..byte_offset].chars().count();
+            Some(SearchMatch {
+                line_number: index + 1,
+                column: column + 1,
+                preview: preview(line, column),
+            })
+        })
+        .collect()
+}
+
+/// Trimmed line, windowed around `column` when it is too long
+fn preview(line: &str, column: usize) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    if chars.len() <= PREVIEW_MAX_CHARS {
+        return line.trim().to_string();
+    }
+    let start = column.saturating_sub(PREVIEW_MAX_CHARS / 4).min(chars.len() - PREVIEW_MAX_CHARS);
+    let window: String = chars[start..start + PREVIEW_MAX_CHARS].iter().collect();
+    format!(
+        "{}{}{}",
+        if start > 0 { "…" } else { "" },
+        window.trim(),
+        if start + PREVIEW_MAX_CHARS < chars.len() { "…" } else { "" }
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn workspace() -> tempfile::TempDir {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/lib.rs"), "pub fn reserve_port() {}\n// TODO: Reserve more\n").unwrap();
+        fs::write(dir.path().join("src/app.ts"), "export const port = reservePort()\n").unwrap();
+        fs::write(dir.path().join("logo.png"), [0u8, b'r', b'e', b's']).unwrap();
+        fs::write(dir.path().join(".gitignore"), "dist/\n").unwrap();
+        fs::create_dir(dir.path().join("dist")).unwrap();
+        fs::write(dir.path().join("dist/bundle.js"), "reserve_port()").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_search_is_ignore_aware_and_grouped() {
+        let dir = workspace();
+        let results = search_workspace(dir.path(), "reserve", None, true).unwrap();
+
+        let paths: Vec<&str> = results.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/app.ts", "src/lib.rs"]);
+        assert_eq!(results.total_matches, 2);
+        assert!(!results.truncated);
+
+        let lib = &results.files[1];
+        assert_eq!(lib.matches.len(), 1);
+        assert_eq!((lib.matches[0].line_number, lib.matches[0].column), (1, 8));
+        assert_eq!(lib.matches[0].preview, "pub fn reserve_port() {}");
+    }
+
+    #[test]
+    fn test_search_case_insensitive_and_glob() {
+        let dir = workspace();
+        let results = search_workspace(dir.path(), "RESERVE", Some("*.rs"), false).unwrap();
+        assert_eq!(results.files.len(), 1);
+        let lines: Vec<usize> = results.files[0].matches.iter().map(|m| m.line_number).collect();
+        assert_eq!(lines, vec![1, 2]);
+
+        let excluded = search_workspace(dir.path(), "reserve", Some("!*.rs"), false).unwrap();
+        let paths: Vec<&str> = excluded.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/app.ts"]);
+
+        assert!(search_workspace(dir.path(), "reserve", Some("src/{"), false).is_err());
+        assert!(search_workspace(dir.path(), "", None, false).unwrap().files.is_empty());
+    }
+
+    #[test]
+    fn test_preview_windows_long_lines() {
+        let line = format!("{}needle{}", "a".repeat(500), "b".repeat(500));
+        let matches = search_content(&line, "needle", true);
+        assert_eq!(matches[0].column, 501);
+        assert!(matches[0].preview.starts_with('…') && matches[0].preview.ends_with('…'));
+        assert!(matches[0].preview.contains("needle"));
+    }
+}
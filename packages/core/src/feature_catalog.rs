@@ -0,0 +1,208 @@
+//! Catalog of the spec-kit features of a worktree.
+//!
+//! `specs/features.json` records the description and creation time of each
+//! feature when its workflow starts; the feature directories are the source
+//! of truth for which features exist. [`list`] merges both and adds what the
+//! pipeline board needs: the artifacts present, task progress and whether the
+//! feature branch (named after the directory) exists.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::checklist::ChecklistKind;
+use crate::spec_kit::{SpecFeature, SPECS_DIR};
+
+/// Catalog file in the specs directory
+pub const CATALOG_FILE: &str = "features.json";
+
+/// What the catalog records about a feature
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CatalogEntry {
+    pub number: u32,
+    pub short_name: String,
+    pub description: String,
+    pub created_at: String,
+}
+
+/// Column of the pipeline board
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureStage {
+    /// No spec.md yet
+    Draft,
+    Specified,
+    Planned,
+    /// tasks.md written, nothing checked off
+    Tasked,
+    Implementing,
+    /// Every task checked off
+    Done,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct FeatureArtifacts {
+    pub spec: bool,
+    pub plan: bool,
+    pub tasks: bool,
+    /// Checklists present, in ChecklistKind::ALL order
+    pub checklists: Vec<ChecklistKind>,
+    /// analysis.json present
+    pub analysis: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeatureInfo {
+    pub number: u32,
+    pub short_name: String,
+    /// Directory relative to the worktree (e.g. "specs/007-user-login")
+    pub dir: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    pub artifacts: FeatureArtifacts,
+    pub tasks_done: usize,
+    pub tasks_total: usize,
+    /// Feature branch name ("007-user-login")
+    pub branch: String,
+    pub branch_exists: bool,
+    pub stage: FeatureStage,
+}
+
+/// Entries of `specs/features.json` (empty when missing or unreadable)
+pub fn load_catalog(worktree: &Path) -> Vec<CatalogEntry> {
+    std::fs::read_to_string(worktree.join(SPECS_DIR).join(CATALOG_FILE))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Add or replace the entry of a feature, keeping the catalog sorted by number
+pub fn record(worktree: &Path, entry: CatalogEntry) -> Result<(), String> {
+    let mut catalog = load_catalog(worktree);
+    catalog.retain(|e| e.number != entry.number);
+    catalog.push(entry);
+    catalog.sort_by_key(|e| e.number);
+    let json = serde_json::to_string_pretty(&catalog).map_err(|e| format!("Failed to serialize catalog: {}", e))?;
+    let dir = worktree.join(SPECS_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    crate::spec_kit::write_atomic(&dir.join(CATALOG_FILE), &json)
+}
+
+/// Board entry of one feature directory
+pub fn info(worktree: &Path, feature: &SpecFeature, catalog: &[CatalogEntry], branches: &[String]) -> FeatureInfo {
+    let dir = feature.dir(worktree);
+    let entry = catalog.iter().find(|e| e.number == feature.number);
+    let tasks = crate::spec_tasks::load(&dir.join("tasks.md")).unwrap_or_default();
+    let progress = crate::spec_tasks::summarize(&tasks);
+    let artifacts = FeatureArtifacts {
+        spec: dir.join("spec.md").is_file(),
+        plan: dir.join("plan.md").is_file(),
+        tasks: dir.join("tasks.md").is_file(),
+        checklists: ChecklistKind::ALL
+            .into_iter()
+            .filter(|kind| dir.join(kind.file_name()).is_file())
+            .collect(),
+        analysis: dir.join(crate::spec_analysis::REPORT_FILE).is_file(),
+    };
+    let stage = if artifacts.tasks && progress.total > 0 && progress.done == progress.total {
+        FeatureStage::Done
+    } else if artifacts.tasks && progress.done > 0 {
+        FeatureStage::Implementing
+    } else if artifacts.tasks {
+        FeatureStage::Tasked
+    } else if artifacts.plan {
+        FeatureStage::Planned
+    } else if artifacts.spec {
+        FeatureStage::Specified
+    } else {
+        FeatureStage::Draft
+    };
+    let branch = feature.dir_name();
+
+    FeatureInfo {
+        number: feature.number,
+        short_name: feature.short_name.clone(),
+        dir: feature.relative_dir(),
+        description: entry.map(|e| e.description.clone()),
+        created_at: entry.map(|e| e.created_at.clone()),
+        artifacts,
+        tasks_done: progress.done,
+        tasks_total: progress.total,
+        branch_exists: branches.contains(&branch),
+        branch,
+        stage,
+    }
+}
+
+/// Local branch names of the worktree's repository (empty outside git)
+fn local_branches(worktree: &Path) -> Vec<String> {
+    crate::worktree::list_branches(&worktree.to_string_lossy())
+        .map(|branches| branches.into_iter().map(|b| b.name).collect())
+        .unwrap_or_default()
+}
+
+/// Every feature of the worktree, by number
+pub fn list(worktree: &Path) -> Vec<FeatureInfo> {
+    let catalog = load_catalog(worktree);
+    let branches = local_branches(worktree);
+    crate::spec_kit::list_features(worktree)
+        .iter()
+        .map(|feature| info(worktree, feature, &catalog, &branches))
+        .collect()
+}
+
+/// One feature by number
+pub fn find(worktree: &Path, number: u32) -> Option<FeatureInfo> {
+    let feature = crate::spec_kit::list_features(worktree).into_iter().find(|f| f.number == number)?;
+    Some(info(worktree, &feature, &load_catalog(worktree), &local_branches(worktree)))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_features() {
+        let dir = tempfile::tempdir().unwrap();
+        let export = crate::spec_kit::create_feature(dir.path(), "user-export").unwrap();
+        let login = crate::spec_kit::create_feature(dir.path(), "login").unwrap();
+        let export_dir = export.dir(dir.path());
+        std::fs::write(export_dir.join("spec.md"), "# Spec").unwrap();
+        std::fs::write(export_dir.join("plan.md"), "# Plan").unwrap();
+        std::fs::write(export_dir.join("tasks.md"), "- [x] T001 Create\n- [ ] T002 Wire\n").unwrap();
+        std::fs::write(export_dir.join("checklist-a11y.md"), "- [ ] CHK001 Labels").unwrap();
+
+        record(dir.path(), CatalogEntry {
+            number: 1,
+            short_name: "user-export".to_string(),
+            description: "Export users".to_string(),
+            created_at: "2026-01-01T00:00:00Z".to_string(),
+        })
+        .unwrap();
+
+        let features = list(dir.path());
+        assert_eq!(features.len(), 2);
+        let first = &features[0];
+        assert_eq!(first.dir, "specs/001-user-export");
+        assert_eq!(first.description.as_deref(), Some("Export users"));
+        assert_eq!((first.tasks_done, first.tasks_total), (1, 2));
+        assert_eq!(first.stage, FeatureStage::Implementing);
+        assert_eq!(first.artifacts.checklists, vec![ChecklistKind::Accessibility]);
+        assert!(!first.artifacts.analysis);
+        assert!(!first.branch_exists);
+
+        assert_eq!(features[1].stage, FeatureStage::Draft);
+        assert!(features[1].description.is_none());
+
+        let branches = vec!["002-login".to_string()];
+        assert!(info(dir.path(), &login, &[], &branches).branch_exists);
+        assert_eq!(find(dir.path(), 2).unwrap().short_name, "login");
+        assert!(find(dir.path(), 9).is_none());
+    }
+}
@@ -0,0 +1,547 @@
+//! Previews for binary files in the Explorer.
+//!
+//! The file type is detected from magic bytes (falling back to the
+//! extension), then the file is turned into something the renderer can show
+//! without shipping megabytes of raw bytes through state:
+//! - images: dimensions from the header plus a downscaled PNG thumbnail as
+//!   a base64 data URL
+//! - PDFs, audio, video and office documents: raw bytes for the dedicated viewers
+//! - anything else: a `hexdump -C` style dump of the first bytes
+//!
+//! Files over `file_reader::MAX_FILE_SIZE` (10MB) are only described, never read.
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::Path;
+
+use crate::file_reader::{self, FileReadError, MAX_FILE_SIZE};
+
+/// Images with more pixels than this are not decoded for a thumbnail
+pub const THUMBNAIL_MAX_PIXELS: u64 = 40_000_000;
+
+/// SVGs up to this size are their own thumbnail (they have no pixel size)
+pub const SVG_THUMBNAIL_MAX_BYTES: u64 = 512 * 1024;
+
+/// Longest side of a thumbnail (in px)
+pub const THUMBNAIL_MAX_DIMENSION: u32 = 256;
+
+/// Bytes shown in a hexdump
+pub const HEXDUMP_BYTES: usize = 4096;
+
+/// Preview of a binary file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BinaryPreview {
+    Image {
+        mime: String,
+        size: u64,
+        /// Pixel dimensions (None if the header could not be parsed, e.g. SVG)
+        width: Option<u32>,
+        height: Option<u32>,
+        /// `data:image/png;base64,...` downscaled to THUMBNAIL_MAX_DIMENSION, SVGs
+        /// as-is (None if the image could not be decoded or is too large)
+        thumbnail: Option<String>,
+        /// Pixel size of the thumbnail (fits THUMBNAIL_MAX_DIMENSION)
+        thumbnail_width: Option<u32>,
+        thumbnail_height: Option<u32>,
+    },
+    /// Rendered by a dedicated viewer from the raw bytes (PDF, audio, video, office)
+    Media { mime: String, size: u64 },
+    Hexdump {
+        mime: String,
+        size: u64,
+        lines: Vec<String>,
+        /// Whether the file is longer than HEXDUMP_BYTES
+        truncated: bool,
+    },
+    /// Larger than the 10MB limit; not read
+    TooLarge { mime: String, size: u64, limit: u64 },
+}
+
+/// A binary file prepared for the file viewer
+#[derive(Debug, Clone, PartialEq)]
+pub struct BinaryFile {
+    pub preview: BinaryPreview,
+    /// Raw bytes, only for `BinaryPreview::Media`
+    pub content: Option<Vec<u8>>,
+}
+
+/// Build the preview for a file (same security scopes as `file_reader::read_file`)
+pub fn preview_file(path: &str, project_root: &str) -> Result<BinaryFile, FileReadError> {
+    let canonical_path = file_reader::resolve_path(path, project_root)?;
+    let size = std::fs::metadata(&canonical_path)
+        .map_err(|e| FileReadError::Io(e.to_string()))?
+        .len();
+
+    // Enough for both type detection and the hexdump
+    let head = read_head(&canonical_path, HEXDUMP_BYTES)?;
+    let mime = detect_mime(&head, &canonical_path).to_string();
+
+    if size > MAX_FILE_SIZE {
+        return Ok(BinaryFile {
+            preview: BinaryPreview::TooLarge { mime, size, limit: MAX_FILE_SIZE },
+            content: None,
+        });
+    }
+
+    if mime.starts_with("image/") {
+        return Ok(BinaryFile { preview: image_preview(&canonical_path, &head, mime, size), content: None });
+    }
+
+    if is_media(&mime) {
+        let bytes = std::fs::read(&canonical_path).map_err(|e| FileReadError::Io(e.to_string()))?;
+        return Ok(BinaryFile {
+            preview: BinaryPreview::Media { mime, size },
+            content: Some(bytes),
+        });
+    }
+
+    Ok(BinaryFile {
+        preview: BinaryPreview::Hexdump {
+            mime,
+            size,
+            lines: hexdump(&head),
+            truncated: size > head.len() as u64,
+        },
+        content: None,
+    })
+}
+
+/// Read up to `limit` bytes from the start of a file
+pub(crate) fn read_head(path: &Path, limit: usize) -> Result<Vec<u8>, FileReadError> {
+    let file = std::fs::File::open(path).map_err(|e| FileReadError::Io(e.to_string()))?;
+    let mut head = Vec::with_capacity(limit);
+    file.take(limit as u64)
+        .read_to_end(&mut head)
+        .map_err(|e| FileReadError::Io(e.to_string()))?;
+    Ok(head)
+}
+
+// ============================================================================
+// Type detection
+// ============================================================================
+
+/// MIME type from magic bytes, falling back to the extension
+pub fn detect_mime(head: &[u8], path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    if head.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return "image/png";
+    }
+    if head.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        return "image/jpeg";
+    }
+    if head.starts_with(b"GIF87a") || head.starts_with(b"GIF89a") {
+        return "image/gif";
+    }
+    if head.len() >= 12 && &head[..4] == b"RIFF" {
+        match &head[8..12] {
+            b"WEBP" => return "image/webp",
+            b"WAVE" => return "audio/wav",
+            b"AVI " => return "video/x-msvideo",
+            _ => {}
+        }
+    }
+    if head.starts_with(b"BM") && ext == "bmp" {
+        return "image/bmp";
+    }
+    if head.starts_with(&[0x00, 0x00, 0x01, 0x00]) {
+        return "image/x-icon";
+    }
+    if head.starts_with(b"%PDF") {
+        return "application/pdf";
+    }
+    if head.len() >= 12 && &head[4..8] == b"ftyp" {
+        return match &head[8..12] {
+            b"M4A " => "audio/mp4",
+            b"qt  " => "video/quicktime",
+            _ => "video/mp4",
+        };
+    }
+    if head.starts_with(&[0x1A, 0x45, 0xDF, 0xA3]) {
+        return if ext == "mkv" { "video/x-matroska" } else { "video/webm" };
+    }
+    if head.starts_with(b"OggS") {
+        return "audio/ogg";
+    }
+    if head.starts_with(b"fLaC") {
+        return "audio/flac";
+    }
+    if head.starts_with(b"ID3") || head.starts_with(&[0xFF, 0xFB]) {
+        return "audio/mpeg";
+    }
+    if head.starts_with(b"PK\x03\x04") {
+        return match ext.as_str() {
+            "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            "xlsx" | "xlsm" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            _ => "application/zip",
+        };
+    }
+    if head.starts_with(b"\0asm") {
+        return "application/wasm";
+    }
+    if head.starts_with(b"\x7fELF") {
+        return "application/x-elf";
+    }
+    if head.starts_with(&[0x1F, 0x8B]) {
+        return "application/gzip";
+    }
+
+    match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "mp4" => "video/mp4",
+        "mov" => "video/quicktime",
+        "webm" => "video/webm",
+        "mkv" => "video/x-matroska",
+        "flv" => "video/x-flv",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "ogg" => "audio/ogg",
+        "m4a" => "audio/mp4",
+        "aac" => "audio/aac",
+        "doc" => "application/msword",
+        "xls" => "application/vnd.ms-excel",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Whether the renderer shows this type from raw bytes
+fn is_media(mime: &str) -> bool {
+    mime.starts_with("video/")
+        || mime.starts_with("audio/")
+        || mime == "application/pdf"
+        || mime == "application/msword"
+        || mime == "application/vnd.ms-excel"
+        || mime.starts_with("application/vnd.openxmlformats-officedocument")
+}
+
+// ============================================================================
+// Images
+// ============================================================================
+
+fn image_preview(path: &Path, head: &[u8], mime: String, size: u64) -> BinaryPreview {
+    let dimensions = image_dimensions(head, &mime).or_else(|| header_dimensions(path));
+    let thumbnail = if mime == "image/svg+xml" {
+        svg_thumbnail(path, size)
+    } else {
+        dimensions
+            .filter(|&(w, h)| w as u64 * h as u64 <= THUMBNAIL_MAX_PIXELS)
+            .and_then(|_| thumbnail(path))
+    };
+
+    BinaryPreview::Image {
+        mime,
+        size,
+        width: dimensions.map(|(w, _)| w),
+        height: dimensions.map(|(_, h)| h),
+        thumbnail_width: thumbnail.as_ref().and_then(|(_, fitted)| fitted.map(|(w, _)| w)),
+        thumbnail_height: thumbnail.as_ref().and_then(|(_, fitted)| fitted.map(|(_, h)| h)),
+        thumbnail: thumbnail.map(|(url, _)| url),
+    }
+}
+
+/// Dimensions from a header longer than the head we read (e.g. JPEGs with
+/// large EXIF blocks); reads no pixel data
+fn header_dimensions(path: &Path) -> Option<(u32, u32)> {
+    image::ImageReader::open(path).ok()?.with_guessed_format().ok()?.into_dimensions().ok()
+}
+
+/// The image decoded and downscaled to fit THUMBNAIL_MAX_DIMENSION, as a PNG
+/// data URL with its size
+fn thumbnail(path: &Path) -> Option<(String, Option<(u32, u32)>)> {
+    let image = match image::ImageReader::open(path).ok()?.with_guessed_format().ok()?.decode() {
+        Ok(image) => image,
+        Err(e) => {
+            tracing::debug!("No thumbnail for {}: {}", path.display(), e);
+            return None;
+        }
+    };
+    let (width, height) = fit_thumbnail(image.width(), image.height());
+    let thumbnail = image.thumbnail(width, height).to_rgba8();
+    let mut png = std::io::Cursor::new(Vec::new());
+    thumbnail.write_to(&mut png, image::ImageFormat::Png).ok()?;
+    let data = base64::engine::general_purpose::STANDARD.encode(png.into_inner());
+    Some((format!("data:image/png;base64,{}", data), Some(thumbnail.dimensions())))
+}
+
+/// Small SVGs inlined as-is (scaled by the renderer)
+fn svg_thumbnail(path: &Path, size: u64) -> Option<(String, Option<(u32, u32)>)> {
+    if size > SVG_THUMBNAIL_MAX_BYTES {
+        return None;
+    }
+    let bytes = std::fs::read(path).ok()?;
+    let data = base64::engine::general_purpose::STANDARD.encode(bytes);
+    Some((format!("data:image/svg+xml;base64,{}", data), None))
+}
+
+/// Scale (width, height) down to fit THUMBNAIL_MAX_DIMENSION, keeping the aspect ratio
+pub fn fit_thumbnail(width: u32, height: u32) -> (u32, u32) {
+    let longest = width.max(height);
+    if longest <= THUMBNAIL_MAX_DIMENSION || longest == 0 {
+        return (width, height);
+    }
+    let scale = |side: u32| ((side as u64 * THUMBNAIL_MAX_DIMENSION as u64) / longest as u64).max(1) as u32;
+    (scale(width), scale(height))
+}
+
+/// Pixel dimensions from the image header
+pub fn image_dimensions(bytes: &[u8], mime: &str) -> Option<(u32, u32)> {
+    let le16 = |at: usize| bytes.get(at..at + 2).map(|b| u16::from_le_bytes([b[0], b[1]]) as u32);
+    let be32 = |at: usize| bytes.get(at..at + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+    let le32 = |at: usize| bytes.get(at..at + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+    let le24 = |at: usize| bytes.get(at..at + 3).map(|b| u32::from_le_bytes([b[0], b[1], b[2], 0]));
+
+    match mime {
+        // IHDR is always the first chunk
+        "image/png" => Some((be32(16)?, be32(20)?)),
+        "image/gif" => Some((le16(6)?, le16(8)?)),
+        // Height is negative for top-down bitmaps
+        "image/bmp" => Some((le32(18)?, (le32(22)? as i32).unsigned_abs())),
+        // First directory entry; 0 means 256
+        "image/x-icon" => {
+            let side = |b: u8| if b == 0 { 256 } else { b as u32 };
+            Some((side(*bytes.get(6)?), side(*bytes.get(7)?)))
+        }
+        "image/webp" => match bytes.get(12..16)? {
+            b"VP8 " => Some((le16(26)? & 0x3FFF, le16(28)? & 0x3FFF)),
+            b"VP8L" => {
+                let bits = le32(21)?;
+                Some((1 + (bits & 0x3FFF), 1 + ((bits >> 14) & 0x3FFF)))
+            }
+            b"VP8X" => Some((1 + le24(24)?, 1 + le24(27)?)),
+            _ => None,
+        },
+        "image/jpeg" => jpeg_dimensions(bytes),
+        _ => None,
+    }
+}
+
+/// Walk JPEG segments up to the first start-of-frame marker
+fn jpeg_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let mut i = 2;
+    while i + 1 < bytes.len() {
+        if bytes[i] != 0xFF {
+            return None;
+        }
+        let marker = bytes[i + 1];
+        match marker {
+            // Fill byte
+            0xFF => {
+                i += 1;
+                continue;
+            }
+            // Standalone markers without a length
+            0x01 | 0xD0..=0xD8 => {
+                i += 2;
+                continue;
+            }
+            // End of image / start of scan: no frame header found
+            0xD9 | 0xDA => return None,
+            _ => {}
+        }
+        let length = u16::from_be_bytes([*bytes.get(i + 2)?, *bytes.get(i + 3)?]) as usize;
+        // SOF0..SOF15, except DHT (C4), JPG (C8) and DAC (CC)
+        if (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+            let height = u16::from_be_bytes([*bytes.get(i + 5)?, *bytes.get(i + 6)?]) as u32;
+            let width = u16::from_be_bytes([*bytes.get(i + 7)?, *bytes.get(i + 8)?]) as u32;
+            return Some((width, height));
+        }
+        i += 2 + length;
+    }
+    None
+}
+
+// ============================================================================
+// Hexdump
+// ============================================================================
+
+/// `hexdump -C` style lines: offset, 16 hex bytes, ASCII column
+pub fn hexdump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut hex = String::with_capacity(49);
+            for (i, byte) in chunk.iter().enumerate() {
+                if i == 8 {
+                    hex.push(' ');
+                }
+                hex.push_str(&format!("{:02x} ", byte));
+            }
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+                .collect();
+            format!("{:08x}  {:<49} |{}|", index * 16, hex, ascii)
+        })
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::GenericImageView;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// 1x1 PNG header (IHDR only; enough for detection and dimensions)
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        bytes.extend_from_slice(&width.to_be_bytes());
+        bytes.extend_from_slice(&height.to_be_bytes());
+        bytes.extend_from_slice(&[8, 6, 0, 0, 0]);
+        bytes
+    }
+
+    #[test]
+    fn test_detect_mime() {
+        let path = Path::new("file.bin");
+        assert_eq!(detect_mime(&png(1, 1), path), "image/png");
+        assert_eq!(detect_mime(b"%PDF-1.7", path), "application/pdf");
+        assert_eq!(detect_mime(b"\0\0\0\x18ftypisom", path), "video/mp4");
+        assert_eq!(detect_mime(b"\x7fELF\x02\x01", path), "application/x-elf");
+        assert_eq!(
+            detect_mime(b"PK\x03\x04", Path::new("report.xlsx")),
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+        );
+        assert_eq!(detect_mime(b"PK\x03\x04", path), "application/zip");
+        assert_eq!(detect_mime(b"<svg xmlns=", Path::new("logo.svg")), "image/svg+xml");
+        assert_eq!(detect_mime(b"\x01\x02", path), "application/octet-stream");
+        // Extension fallback when the content has no known signature
+        assert_eq!(detect_mime(&[0; 16], Path::new("clip.mp4")), "video/mp4");
+    }
+
+    #[test]
+    fn test_image_dimensions() {
+        assert_eq!(image_dimensions(&png(640, 480), "image/png"), Some((640, 480)));
+
+        let gif = b"GIF89a\x20\x03\x58\x02".to_vec();
+        assert_eq!(image_dimensions(&gif, "image/gif"), Some((800, 600)));
+
+        // SOI, APP0 (length 4), SOF0 with height 300 and width 400
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x01, 0x2C, 0x01, 0x90,
+        ];
+        assert_eq!(image_dimensions(&jpeg, "image/jpeg"), Some((400, 300)));
+
+        assert_eq!(image_dimensions(&png(1, 1)[..12], "image/png"), None);
+    }
+
+    #[test]
+    fn test_fit_thumbnail() {
+        assert_eq!(fit_thumbnail(100, 50), (100, 50));
+        assert_eq!(fit_thumbnail(1024, 512), (256, 128));
+        assert_eq!(fit_thumbnail(300, 3000), (25, 256));
+        assert_eq!(fit_thumbnail(10_000, 1), (256, 1));
+    }
+
+    #[test]
+    fn test_hexdump_format() {
+        let lines = hexdump(b"\x7fELF\x02\x01\x01\0\0\0\0\0\0\0\0\0hi");
+        assert_eq!(
+            lines[0],
+            "00000000  7f 45 4c 46 02 01 01 00  00 00 00 00 00 00 00 00  |.ELF............|"
+        );
+        assert_eq!(lines[1], format!("00000010  {:<49} |hi|", "68 69 "));
+    }
+
+    #[test]
+    fn test_preview_file_variants() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_str().unwrap();
+
+        // Noise doesn't compress, so the file is well over 512KB
+        let mut seed = 1u32;
+        let noise = image::RgbaImage::from_fn(1024, 512, |_, _| {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            image::Rgba(seed.to_le_bytes())
+        });
+        let image = temp.path().join("logo.png");
+        noise.save(&image).unwrap();
+        assert!(fs::metadata(&image).unwrap().len() > 512 * 1024);
+        let result = preview_file(image.to_str().unwrap(), root).unwrap();
+        assert!(result.content.is_none());
+        match result.preview {
+            BinaryPreview::Image { width, height, thumbnail, thumbnail_width, thumbnail_height, .. } => {
+                assert_eq!((width, height), (Some(1024), Some(512)));
+                let thumbnail = thumbnail.unwrap();
+                let data = thumbnail.strip_prefix("data:image/png;base64,").unwrap();
+                let png = base64::engine::general_purpose::STANDARD.decode(data).unwrap();
+                assert_eq!(image::load_from_memory(&png).unwrap().dimensions(), (256, 128));
+                assert_eq!((thumbnail_width, thumbnail_height), (Some(256), Some(128)));
+            }
+            other => panic!("expected image preview, got {:?}", other),
+        }
+
+        // A header alone gives the dimensions but no thumbnail
+        let header = temp.path().join("header.png");
+        fs::write(&header, png(640, 480)).unwrap();
+        match preview_file(header.to_str().unwrap(), root).unwrap().preview {
+            BinaryPreview::Image { width, height, thumbnail, .. } => {
+                assert_eq!((width, height), (Some(640), Some(480)));
+                assert!(thumbnail.is_none());
+            }
+            other => panic!("expected image preview, got {:?}", other),
+        }
+
+        let pdf = temp.path().join("spec.pdf");
+        fs::write(&pdf, b"%PDF-1.7\n").unwrap();
+        let result = preview_file(pdf.to_str().unwrap(), root).unwrap();
+        assert_eq!(result.preview, BinaryPreview::Media { mime: "application/pdf".to_string(), size: 9 });
+        assert_eq!(result.content, Some(b"%PDF-1.7\n".to_vec()));
+
+        let blob = temp.path().join("data.bin");
+        fs::write(&blob, vec![0xABu8; HEXDUMP_BYTES + 1]).unwrap();
+        match preview_file(blob.to_str().unwrap(), root).unwrap().preview {
+            BinaryPreview::Hexdump { lines, truncated, .. } => {
+                assert_eq!(lines.len(), HEXDUMP_BYTES / 16);
+                assert!(truncated);
+            }
+            other => panic!("expected hexdump, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_preview_file_too_large_is_not_read() {
+        let temp = TempDir::new().unwrap();
+        let video = temp.path().join("big.mp4");
+        let file = fs::File::create(&video).unwrap();
+        file.set_len(MAX_FILE_SIZE + 1).unwrap();
+        drop(file);
+
+        let result = preview_file(video.to_str().unwrap(), temp.path().to_str().unwrap()).unwrap();
+        assert_eq!(
+            result.preview,
+            BinaryPreview::TooLarge {
+                mime: "video/mp4".to_string(),
+                size: MAX_FILE_SIZE + 1,
+                limit: MAX_FILE_SIZE,
+            }
+        );
+        assert!(result.content.is_none());
+    }
+
+    #[test]
+    fn test_preview_file_security_violation() {
+        let temp = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let file = outside.path().join("secret.png");
+        fs::write(&file, png(1, 1)).unwrap();
+
+        let result = preview_file(file.to_str().unwrap(), temp.path().to_str().unwrap());
+        assert!(matches!(result, Err(FileReadError::SecurityViolation(_))));
+    }
+}
@@ -1,15 +1,16 @@
-//! Secure file reading with path validation.
+//! Secure file reading and writing with path validation.
 //!
-//! Provides file reading within allowed security scopes:
+//! Provides file access within allowed security scopes:
 //! - Project root directory (and subdirectories)
 //! - ~/.rstn/ directory (and subdirectories)
 
 use crate::persistence::get_rstn_dir;
+use sha2::{Digest, Sha256};
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// Maximum file size (10MB)
-const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+pub const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
 
 /// File reading errors
 #[derive(Debug, Error)]
@@ -29,10 +30,18 @@ pub enum FileReadError {
     #[error("File is not valid UTF-8 text")]
     NotUtf8,
 
+    #[error("File changed on disk since it was read: {0}")]
+    Conflict(String),
+
     #[error("IO error: {0}")]
     Io(String),
 }
 
+/// SHA-256 of file content (hex), used to detect changes between read and write
+pub fn content_hash(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
 /// Read a file with security validation.
 ///
 /// # Arguments
@@ -47,22 +56,7 @@ pub enum FileReadError {
 /// # Returns
 /// File contents as UTF-8 string, or error
 pub fn read_file(path: &str, project_root: &str) -> Result<String, FileReadError> {
-    let file_path = Path::new(path);
-
-    // Canonicalize paths for security
-    let canonical_path = file_path.canonicalize().map_err(|e| match e.kind() {
-        std::io::ErrorKind::NotFound => FileReadError::NotFound(path.to_string()),
-        std::io::ErrorKind::PermissionDenied => FileReadError::PermissionDenied(path.to_string()),
-        _ => FileReadError::Io(e.to_string()),
-    })?;
-
-    // Build allowed roots
-    let allowed_roots = build_allowed_roots(project_root)?;
-
-    // Security check: path must be within allowed roots
-    if !is_path_allowed(&canonical_path, &allowed_roots) {
-        return Err(FileReadError::SecurityViolation(path.to_string()));
-    }
+    let canonical_path = resolve_path(path, project_root)?;
 
     // Check file size
     let metadata =
@@ -99,10 +93,110 @@ pub fn read_file(path: &str, project_root: &str) -> Result<String, FileReadError
 /// # Returns
 /// File contents as raw bytes (Vec<u8>), or error
 pub fn read_binary_file(path: &str, project_root: &str) -> Result<Vec<u8>, FileReadError> {
+    let canonical_path = resolve_path(path, project_root)?;
+
+    // Check file size
+    let metadata =
+        std::fs::metadata(&canonical_path).map_err(|e| FileReadError::Io(e.to_string()))?;
+
+    if metadata.len() > MAX_FILE_SIZE {
+        return Err(FileReadError::FileTooLarge {
+            size: metadata.len(),
+            limit: MAX_FILE_SIZE,
+        });
+    }
+
+    // Read file as binary
+    std::fs::read(&canonical_path).map_err(|e| FileReadError::Io(e.to_string()))
+}
+
+/// Write a text file atomically with security validation.
+///
+/// # Arguments
+/// * `path` - Path to file to write (created if missing)
+/// * `project_root` - Project root directory (allowed scope)
+/// * `content` - New file content
+/// * `expected_hash` - `content_hash` of the content the edit was based on;
+///   the write is rejected if the file on disk no longer matches
+///
+/// # Security
+/// Same scopes as `read_file`.
+///
+/// # Returns
+/// `content_hash` of the written content, or error
+pub fn write_file(
+    path: &str,
+    project_root: &str,
+    content: &str,
+    expected_hash: Option<&str>,
+) -> Result<String, FileReadError> {
     let file_path = Path::new(path);
+    let file_name = file_path
+        .file_name()
+        .ok_or_else(|| FileReadError::Io(format!("Invalid file path: {}", path)))?;
+
+    // Resolve through the parent so new files can be created; existing files
+    // are canonicalized so symlinks are written through, not replaced
+    let canonical_path = if file_path.exists() {
+        file_path.canonicalize().map_err(|e| FileReadError::Io(e.to_string()))?
+    } else {
+        let parent = file_path.parent().unwrap_or(Path::new("."));
+        let canonical_parent = parent.canonicalize().map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => FileReadError::NotFound(parent.display().to_string()),
+            std::io::ErrorKind::PermissionDenied => FileReadError::PermissionDenied(path.to_string()),
+            _ => FileReadError::Io(e.to_string()),
+        })?;
+        canonical_parent.join(file_name)
+    };
+
+    // Security check: path must be within allowed roots
+    let allowed_roots = build_allowed_roots(project_root)?;
+    if !is_path_allowed(&canonical_path, &allowed_roots) {
+        return Err(FileReadError::SecurityViolation(path.to_string()));
+    }
+
+    if content.len() as u64 > MAX_FILE_SIZE {
+        return Err(FileReadError::FileTooLarge {
+            size: content.len() as u64,
+            limit: MAX_FILE_SIZE,
+        });
+    }
 
+    // Optimistic concurrency: the file must still be what the editor loaded
+    if let Some(expected) = expected_hash {
+        let current = match std::fs::read_to_string(&canonical_path) {
+            Ok(current) => Some(content_hash(&current)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(FileReadError::Io(e.to_string())),
+        };
+        if current.as_deref() != Some(expected) {
+            return Err(FileReadError::Conflict(path.to_string()));
+        }
+    }
+
+    // Write a sibling temp file, then rename over the target
+    let tmp = canonical_path.with_file_name(format!(".{}.rstn-tmp", file_name.to_string_lossy()));
+    std::fs::write(&tmp, content).map_err(|e| match e.kind() {
+        std::io::ErrorKind::PermissionDenied => FileReadError::PermissionDenied(path.to_string()),
+        _ => FileReadError::Io(e.to_string()),
+    })?;
+    if let Ok(metadata) = std::fs::metadata(&canonical_path) {
+        // Keep the original mode (e.g. executable scripts)
+        let _ = std::fs::set_permissions(&tmp, metadata.permissions());
+    }
+    std::fs::rename(&tmp, &canonical_path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        FileReadError::Io(e.to_string())
+    })?;
+
+    Ok(content_hash(content))
+}
+
+/// Canonicalize an existing file path and check it is within the allowed scopes
+/// (`project_root` or `~/.rstn/`)
+pub fn resolve_path(path: &str, project_root: &str) -> Result<PathBuf, FileReadError> {
     // Canonicalize paths for security
-    let canonical_path = file_path.canonicalize().map_err(|e| match e.kind() {
+    let canonical_path = Path::new(path).canonicalize().map_err(|e| match e.kind() {
         std::io::ErrorKind::NotFound => FileReadError::NotFound(path.to_string()),
         std::io::ErrorKind::PermissionDenied => FileReadError::PermissionDenied(path.to_string()),
         _ => FileReadError::Io(e.to_string()),
@@ -116,19 +210,7 @@ pub fn read_binary_file(path: &str, project_root: &str) -> Result<Vec<u8>, FileR
         return Err(FileReadError::SecurityViolation(path.to_string()));
     }
 
-    // Check file size
-    let metadata =
-        std::fs::metadata(&canonical_path).map_err(|e| FileReadError::Io(e.to_string()))?;
-
-    if metadata.len() > MAX_FILE_SIZE {
-        return Err(FileReadError::FileTooLarge {
-            size: metadata.len(),
-            limit: MAX_FILE_SIZE,
-        });
-    }
-
-    // Read file as binary
-    std::fs::read(&canonical_path).map_err(|e| FileReadError::Io(e.to_string()))
+    Ok(canonical_path)
 }
 
 /// Build list of allowed root directories
@@ -244,6 +326,77 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_write_file_atomic_replace() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("config.toml");
+        fs::write(&file_path, "a = 1\n").unwrap();
+        let root = temp_dir.path().to_str().unwrap();
+
+        let original = read_file(file_path.to_str().unwrap(), root).unwrap();
+        let hash = write_file(
+            file_path.to_str().unwrap(),
+            root,
+            "a = 2\n",
+            Some(&content_hash(&original)),
+        )
+        .unwrap();
+
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "a = 2\n");
+        assert_eq!(hash, content_hash("a = 2\n"));
+        // No temp file left behind
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_write_file_conflict() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("CLAUDE.md");
+        fs::write(&file_path, "v1").unwrap();
+        let root = temp_dir.path().to_str().unwrap();
+        let read_hash = content_hash("v1");
+
+        // Changed on disk after it was read
+        fs::write(&file_path, "v2 from elsewhere").unwrap();
+        let result = write_file(file_path.to_str().unwrap(), root, "my edit", Some(&read_hash));
+
+        assert!(matches!(result, Err(FileReadError::Conflict(_))));
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "v2 from elsewhere");
+    }
+
+    #[test]
+    fn test_write_file_creates_new_file_and_detects_deletion() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("new.md");
+        let root = temp_dir.path().to_str().unwrap();
+
+        write_file(file_path.to_str().unwrap(), root, "hello", None).unwrap();
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "hello");
+
+        // Deleted on disk after it was read
+        fs::remove_file(&file_path).unwrap();
+        let result = write_file(file_path.to_str().unwrap(), root, "x", Some(&content_hash("hello")));
+        assert!(matches!(result, Err(FileReadError::Conflict(_))));
+        assert!(!file_path.exists());
+    }
+
+    #[test]
+    fn test_write_file_security_violation() {
+        let temp_dir = TempDir::new().unwrap();
+        let outside_dir = TempDir::new().unwrap();
+        let file_path = outside_dir.path().join("evil.sh");
+
+        let result = write_file(
+            file_path.to_str().unwrap(),
+            temp_dir.path().to_str().unwrap(),
+            "rm -rf /",
+            None,
+        );
+
+        assert!(matches!(result, Err(FileReadError::SecurityViolation(_))));
+        assert!(!file_path.exists());
+    }
+
     #[test]
     fn test_is_path_allowed() {
         let roots = vec![
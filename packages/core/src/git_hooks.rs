@@ -0,0 +1,254 @@
+//! Git pre-commit hook managed by rstn.
+//!
+//! The hook scans staged changes for secrets (the patterns of the commit
+//! security scan in `git`) and runs the project's `.rstn/hooks.toml`
+//! pipeline, failing the commit when either reports a problem. The pipeline
+//! is written into the script at install time, so the installed hook goes
+//! stale when the template version or the pipeline changes; `status`
+//! compares it with a fresh render so the UI can offer an update.
+//!
+//! Hooks not written by rstn are never overwritten or removed.
+
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::hooks;
+
+/// Version of the hook template; bump when `render` changes
+pub const HOOK_TEMPLATE_VERSION: u32 = 1;
+
+/// First comment line of a managed hook
+const MANAGED_MARKER: &str = "# Managed by rstn";
+
+/// Comment line carrying the template version
+const VERSION_PREFIX: &str = "# rstn-hook-version: ";
+
+/// State of a project's pre-commit hook
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GitHookStatus {
+    /// Path of the pre-commit hook
+    pub path: String,
+    /// A pre-commit hook exists
+    pub installed: bool,
+    /// The existing hook was written by rstn
+    pub managed: bool,
+    /// Template version of the installed managed hook
+    pub version: Option<u32>,
+    /// Template version rstn would install now
+    pub current_version: u32,
+    /// The managed hook differs from a fresh install (new template or
+    /// changed `.rstn/hooks.toml`)
+    pub update_available: bool,
+    /// Names of the pipeline hooks a fresh install would run
+    pub pipeline: Vec<String>,
+    /// Why the pipeline could not be read, if it could not
+    pub pipeline_error: Option<String>,
+}
+
+/// Path of the pre-commit hook (honours `core.hooksPath` and linked worktrees)
+pub fn hook_path(project: &Path) -> Result<PathBuf, String> {
+    let project_str = project.to_string_lossy();
+    let hooks_dir = crate::git::run_git(&project_str, &["rev-parse", "--git-path", "hooks"])?;
+    let hooks_dir = PathBuf::from(hooks_dir.trim());
+    let hooks_dir = if hooks_dir.is_absolute() { hooks_dir } else { project.join(hooks_dir) };
+    Ok(hooks_dir.join("pre-commit"))
+}
+
+/// Quote a string for `sh`
+fn sh_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// The managed hook script for a pipeline
+pub fn render(pipeline: &[hooks::Hook]) -> String {
+    let mut script = format!(
+        "#!/bin/sh\n\
+         {MANAGED_MARKER} - reinstall from rstn instead of editing this file.\n\
+         {VERSION_PREFIX}{HOOK_TEMPLATE_VERSION}\n\
+         # Bypass once with `git commit --no-verify`.\n\
+         \n\
+         cd \"$(git rev-parse --show-toplevel)\" || exit 1\n\
+         \n\
+         fail() {{\n\
+         \x20   echo \"rstn pre-commit: $1\" >&2\n\
+         \x20   exit 1\n\
+         }}\n\
+         \n\
+         # Secret scan of the staged changes\n\
+         git diff --cached --name-only --diff-filter=ACMR \\\n\
+         \x20   | grep -E '(^|/)(\\.env(\\.[^/]*)?|[^/]*\\.pem|[^/]*\\.key|id_rsa|id_ed25519)$' \\\n\
+         \x20   | grep -Ev '\\.(example|sample|template)$' \\\n\
+         \x20   && fail \"sensitive file staged (listed above)\"\n\
+         added=$(git diff --cached -U0 --no-color --no-ext-diff | grep '^+' | grep -v '^+++')\n"
+    );
+    let tokens: Vec<String> = crate::git::token_patterns().iter().map(|p| format!("-e {}", sh_quote(p))).collect();
+    script.push_str(&format!(
+        "printf '%s\\n' \"$added\" | grep -qE -e '-----BEGIN [A-Z ]*PRIVATE KEY-----' {} \\\n\
+         \x20   && fail \"staged changes contain a token or private key\"\n\
+         printf '%s\\n' \"$added\" | grep -qEi -e {} \\\n\
+         \x20   && fail \"staged changes contain a hardcoded credential\"\n",
+        tokens.join(" "),
+        sh_quote(&crate::git::credential_pattern())
+    ));
+
+    if !pipeline.is_empty() {
+        script.push_str("\n# Pipeline from .rstn/hooks.toml\n");
+    }
+    for hook in pipeline {
+        script.push_str(&format!(
+            "echo \"rstn pre-commit: {name}\"\nsh -c {command} || fail \"hook {name} failed\"\n",
+            name = hook.name.replace(['"', '\\', '$', '`'], ""),
+            command = sh_quote(&hook.command)
+        ));
+    }
+    // The scans end in `grep && fail`, whose status is non-zero when clean
+    script.push_str("exit 0\n");
+    script
+}
+
+/// Render the hook for a project from its `.rstn/hooks.toml`
+fn render_for(project: &Path) -> Result<(String, Vec<hooks::Hook>), String> {
+    let pipeline = hooks::load(project)?;
+    Ok((render(&pipeline), pipeline))
+}
+
+fn installed_version(script: &str) -> Option<u32> {
+    script.lines().find_map(|l| l.strip_prefix(VERSION_PREFIX)).and_then(|v| v.trim().parse().ok())
+}
+
+fn is_managed(script: &str) -> bool {
+    script.lines().nth(1).is_some_and(|l| l.starts_with(MANAGED_MARKER))
+}
+
+pub fn status(project: &Path) -> Result<GitHookStatus, String> {
+    let path = hook_path(project)?;
+    let installed = std::fs::read_to_string(&path).ok();
+    let (fresh, pipeline, pipeline_error) = match render_for(project) {
+        Ok((script, pipeline)) => (Some(script), pipeline, None),
+        Err(e) => (None, Vec::new(), Some(e)),
+    };
+    let managed = installed.as_deref().is_some_and(is_managed);
+    Ok(GitHookStatus {
+        path: path.to_string_lossy().to_string(),
+        installed: installed.is_some(),
+        managed,
+        version: installed.as_deref().filter(|_| managed).and_then(installed_version),
+        current_version: HOOK_TEMPLATE_VERSION,
+        update_available: managed && fresh.is_some() && installed != fresh,
+        pipeline: pipeline.into_iter().map(|h| h.name).collect(),
+        pipeline_error,
+    })
+}
+
+/// Write (or update) the managed pre-commit hook
+pub fn install(project: &Path) -> Result<GitHookStatus, String> {
+    let path = hook_path(project)?;
+    if let Ok(existing) = std::fs::read_to_string(&path) {
+        if !is_managed(&existing) {
+            return Err(format!("{} was not written by rstn; move it away before installing", path.display()));
+        }
+    }
+    let (script, _) = render_for(project)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    std::fs::write(&path, script).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Failed to make {} executable: {}", path.display(), e))?;
+    }
+    status(project)
+}
+
+/// Remove the managed pre-commit hook (other hooks are left alone)
+pub fn uninstall(project: &Path) -> Result<GitHookStatus, String> {
+    let path = hook_path(project)?;
+    match std::fs::read_to_string(&path) {
+        Ok(existing) if !is_managed(&existing) => {
+            return Err(format!("{} was not written by rstn; leaving it in place", path.display()));
+        }
+        Ok(_) => std::fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?,
+        Err(_) => {}
+    }
+    status(project)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) -> bool {
+        Command::new("git").arg("-C").arg(dir).args(args).output().unwrap().status.success()
+    }
+
+    fn init_repo(dir: &Path) {
+        for args in [
+            vec!["init", "-q", "-b", "main"],
+            vec!["config", "user.name", "Test"],
+            vec!["config", "user.email", "test@example.com"],
+        ] {
+            assert!(git(dir, &args));
+        }
+    }
+
+    #[test]
+    fn test_render_quotes_pipeline() {
+        let pipeline = hooks::parse_str("[[hook]]\nname = \"fmt\"\ncommand = \"echo 'it''s' && cargo fmt\"\n").unwrap();
+        let script = render(&pipeline);
+        assert!(is_managed(&script));
+        assert_eq!(installed_version(&script), Some(HOOK_TEMPLATE_VERSION));
+        assert!(script.contains("ghp_[A-Za-z0-9_-]{12,}"));
+        assert!(script.contains(r#"sh -c 'echo '\''it'\'''\''s'\'' && cargo fmt' || fail "hook fmt failed""#));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_install_status_uninstall() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        init_repo(root);
+
+        let before = status(root).unwrap();
+        assert!(!before.installed);
+        assert!(!before.update_available);
+
+        let installed = install(root).unwrap();
+        assert!(installed.managed);
+        assert_eq!(installed.version, Some(HOOK_TEMPLATE_VERSION));
+        assert!(!installed.update_available);
+
+        // Secrets block the commit, clean changes pass
+        std::fs::write(root.join("config.rs"), "const KEY: &str = \"ghp_abcdefghijklmnopqrstuvwxyz\";\n").unwrap();
+        assert!(git(root, &["add", "config.rs"]));
+        assert!(!git(root, &["commit", "-q", "-m", "leak"]));
+        std::fs::write(root.join("config.rs"), "const KEY: &str = env!(\"KEY\");\n").unwrap();
+        assert!(git(root, &["add", "config.rs"]));
+        assert!(git(root, &["commit", "-q", "-m", "clean"]));
+
+        // A new pipeline makes the installed hook stale; its failures block commits
+        std::fs::create_dir_all(root.join(".rstn")).unwrap();
+        std::fs::write(root.join(".rstn/hooks.toml"), "[[hook]]\nname = \"lint\"\ncommand = \"exit 3\"\n").unwrap();
+        assert!(status(root).unwrap().update_available);
+        assert_eq!(install(root).unwrap().pipeline, vec!["lint"]);
+        std::fs::write(root.join("notes.txt"), "x").unwrap();
+        assert!(git(root, &["add", "notes.txt"]));
+        assert!(!git(root, &["commit", "-q", "-m", "lint fails"]));
+
+        assert!(!uninstall(root).unwrap().installed);
+
+        // Foreign hooks are left alone
+        std::fs::write(hook_path(root).unwrap(), "#!/bin/sh\nexit 0\n").unwrap();
+        assert!(install(root).is_err());
+        assert!(uninstall(root).is_err());
+        assert!(!status(root).unwrap().managed);
+    }
+}
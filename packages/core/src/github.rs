@@ -0,0 +1,195 @@
+//! GitHub issues as change intents.
+//!
+//! Lists the open issues of a repository and turns one into a change: the
+//! issue title and body become `intent.md`, and the issue is labeled and
+//! commented on so the tracker shows that work started in rstn.
+
+use std::path::Path;
+use std::time::Duration;
+
+use napi_derive::napi;
+use serde_json::{json, Value};
+
+use crate::pull_request::{self, GitHost, RemoteRepo};
+
+/// Time allowed for an API request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Open issues fetched per listing
+const ISSUES_PER_PAGE: u32 = 50;
+
+/// Label added to issues that became changes
+pub const CHANGE_LABEL: &str = "rstn";
+
+/// An open issue
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitHubIssue {
+    pub number: u32,
+    pub title: String,
+    /// Markdown body (empty if none)
+    pub body: String,
+    pub labels: Vec<String>,
+    /// Web page of the issue
+    pub url: String,
+    /// Login of the author
+    pub author: Option<String>,
+}
+
+/// Repository from "owner/repo", a remote URL, or a local checkout (its `origin`)
+pub fn resolve_repo(repo: &str) -> Result<RemoteRepo, String> {
+    let repo = repo.trim();
+    let resolved = if Path::new(repo).is_dir() {
+        pull_request::remote_repo(repo)?
+    } else if let Some(remote) = pull_request::parse_remote(repo) {
+        remote
+    } else {
+        let parts: Vec<&str> = repo.split('/').collect();
+        if parts.len() != 2 || parts.iter().any(|p| p.is_empty()) {
+            return Err(format!("Expected a GitHub repository as owner/repo, got '{}'", repo));
+        }
+        RemoteRepo {
+            host: GitHost::GitHub,
+            api_base: "https://api.github.com".to_string(),
+            path: repo.to_string(),
+        }
+    };
+    if resolved.host != GitHost::GitHub {
+        return Err(format!("{} is not a GitHub repository", resolved.path));
+    }
+    Ok(resolved)
+}
+
+/// Open issues, newest first (pull requests are skipped).
+///
+/// The token is optional for public repositories.
+pub async fn list_issues(repo: &RemoteRepo, token: Option<&str>) -> Result<Vec<GitHubIssue>, String> {
+    let url = format!(
+        "{}/repos/{}/issues?state=open&per_page={}",
+        repo.api_base, repo.path, ISSUES_PER_PAGE
+    );
+    let value = send(request(reqwest::Method::GET, &url, token)?).await?;
+    Ok(value
+        .as_array()
+        .map(|items| items.iter().filter(|item| item.get("pull_request").is_none()).filter_map(parse_issue).collect())
+        .unwrap_or_default())
+}
+
+pub async fn get_issue(repo: &RemoteRepo, token: Option<&str>, number: u32) -> Result<GitHubIssue, String> {
+    let url = format!("{}/repos/{}/issues/{}", repo.api_base, repo.path, number);
+    let value = send(request(reqwest::Method::GET, &url, token)?).await?;
+    parse_issue(&value).ok_or_else(|| format!("Issue #{} has an unexpected format", number))
+}
+
+/// Label the issue and comment that it is tracked as `change_name`
+pub async fn link_issue(repo: &RemoteRepo, token: &str, number: u32, change_name: &str) -> Result<(), String> {
+    let issue_url = format!("{}/repos/{}/issues/{}", repo.api_base, repo.path, number);
+    let labels = request(reqwest::Method::POST, &format!("{}/labels", issue_url), Some(token))?
+        .json(&json!({ "labels": [CHANGE_LABEL] }));
+    send(labels).await?;
+    let comment = request(reqwest::Method::POST, &format!("{}/comments", issue_url), Some(token))?
+        .json(&json!({ "body": format!("Tracked as change `{}` in rstn.", change_name) }));
+    send(comment).await.map(|_| ())
+}
+
+/// Intent for a change that resolves the issue
+pub fn intent_from_issue(issue: &GitHubIssue) -> String {
+    let mut intent = format!("{} (#{})\n", issue.title.trim(), issue.number);
+    if !issue.body.trim().is_empty() {
+        intent.push('\n');
+        intent.push_str(issue.body.trim());
+        intent.push('\n');
+    }
+    if !issue.url.is_empty() {
+        intent.push_str(&format!("\nIssue: {}\n", issue.url));
+    }
+    intent
+}
+
+fn request(method: reqwest::Method, url: &str, token: Option<&str>) -> Result<reqwest::RequestBuilder, String> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent("rstn")
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let mut request = client.request(method, url).header("Accept", "application/vnd.github+json");
+    if let Some(token) = token.filter(|t| !t.is_empty()) {
+        request = request.bearer_auth(token);
+    }
+    Ok(request)
+}
+
+async fn send(request: reqwest::RequestBuilder) -> Result<Value, String> {
+    let response = request.send().await.map_err(|e| format!("GitHub request failed: {}", e))?;
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    let value: Value = serde_json::from_str(&text).unwrap_or(Value::Null);
+    if !status.is_success() {
+        let message = value["message"].as_str().unwrap_or(text.trim());
+        return Err(format!("GitHub returned {}: {}", status, message));
+    }
+    Ok(value)
+}
+
+fn parse_issue(value: &Value) -> Option<GitHubIssue> {
+    Some(GitHubIssue {
+        number: value["number"].as_u64()? as u32,
+        title: value["title"].as_str()?.to_string(),
+        body: value["body"].as_str().unwrap_or_default().to_string(),
+        labels: value["labels"]
+            .as_array()
+            .map(|labels| labels.iter().filter_map(|l| l["name"].as_str().map(str::to_string)).collect())
+            .unwrap_or_default(),
+        url: value["html_url"].as_str().unwrap_or_default().to_string(),
+        author: value["user"]["login"].as_str().map(str::to_string),
+    })
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_repo() {
+        let repo = resolve_repo("acme/widgets").unwrap();
+        assert_eq!(repo.api_base, "https://api.github.com");
+        assert_eq!(repo.path, "acme/widgets");
+
+        let repo = resolve_repo("git@github.com:acme/widgets.git").unwrap();
+        assert_eq!(repo.path, "acme/widgets");
+
+        assert!(resolve_repo("https://gitlab.com/acme/widgets").is_err());
+        assert!(resolve_repo("widgets").is_err());
+    }
+
+    #[test]
+    fn test_parse_issue_and_intent() {
+        let value: Value = serde_json::from_str(
+            r#"{
+                "number": 42,
+                "title": "Login fails with SSO ",
+                "body": "Steps to reproduce...",
+                "labels": [{"name": "bug"}],
+                "html_url": "https://github.com/acme/widgets/issues/42",
+                "user": {"login": "octocat"}
+            }"#,
+        )
+        .unwrap();
+        let issue = parse_issue(&value).unwrap();
+        assert_eq!(issue.labels, vec!["bug".to_string()]);
+        assert_eq!(issue.author.as_deref(), Some("octocat"));
+        assert_eq!(
+            intent_from_issue(&issue),
+            "Login fails with SSO (#42)\n\nSteps to reproduce...\n\nIssue: https://github.com/acme/widgets/issues/42\n"
+        );
+
+        // Issues without a body
+        let value: Value = serde_json::from_str(r#"{"number": 7, "title": "Typo", "body": null}"#).unwrap();
+        let issue = parse_issue(&value).unwrap();
+        assert_eq!(intent_from_issue(&issue), "Typo (#7)\n");
+    }
+}
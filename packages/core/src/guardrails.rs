@@ -0,0 +1,422 @@
+//! Guardrails for unattended agent runs.
+//!
+//! `<worktree>/.rstn/guardrails.toml` limits what Claude may do while it
+//! implements a change on its own:
+//!
+//! ```toml
+//! # Tools Claude may use without asking, on top of the read-only ones
+//! allowed_tools = ["WebFetch", "Bash(cargo test:*)"]
+//! # Files the agent may create or modify, as globs relative to the worktree
+//! # (omit to allow any file inside the worktree)
+//! writable_paths = ["src/**", "tests/**"]
+//! # Commands that always need approval, matched by their leading words
+//! banned_commands = ["git push", "rm -rf", "curl"]
+//! ```
+//!
+//! Only read-only tools and `allowed_tools` are passed as `--allowedTools`;
+//! every other tool call arrives as a permission request and is checked
+//! here. Calls that break a guardrail pause the run until the user decides.
+//!
+//! Banned commands are looked for in every command of a Bash command line:
+//! the parts between `&&`, `||`, `;` and `|`, subshells, `$(...)` and
+//! backtick substitutions and `sh -c` payloads. Environment assignments and
+//! wrappers (`sudo`, `env`, `command`, `exec`) are skipped and programs are
+//! compared by file name, so `sudo /bin/rm x` runs `rm`. This is a best
+//! effort check, not a sandbox: `writable_paths` only applies to the file
+//! tools (Write, Edit, ...), files written by Bash commands are not checked.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+
+/// Guardrails file name (in `<worktree>/.rstn/`)
+pub const GUARDRAILS_FILE: &str = "guardrails.toml";
+
+/// Tools that never change the worktree
+pub const READ_ONLY_TOOLS: [&str; 5] = ["Read", "Glob", "Grep", "LS", "TodoWrite"];
+
+/// Tools that write the file named in their input
+const WRITE_TOOLS: [&str; 4] = ["Write", "Edit", "MultiEdit", "NotebookEdit"];
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Guardrails {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allowed_tools: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub writable_paths: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub banned_commands: Vec<String>,
+}
+
+pub fn guardrails_path(worktree_path: &Path) -> PathBuf {
+    worktree_path.join(".rstn").join(GUARDRAILS_FILE)
+}
+
+/// Load a worktree's guardrails (defaults when the file is missing)
+pub fn load(worktree_path: &Path) -> Result<Guardrails, String> {
+    load_or(worktree_path, None)
+}
+
+/// Load a worktree's guardrails, falling back to `fallback` (the settings
+/// profile's guardrails) when the file is missing
+pub fn load_or(worktree_path: &Path, fallback: Option<&Guardrails>) -> Result<Guardrails, String> {
+    let path = guardrails_path(worktree_path);
+    if !path.exists() {
+        return Ok(fallback.cloned().unwrap_or_default());
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_str(&content).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn parse_str(content: &str) -> Result<Guardrails, String> {
+    toml::from_str(content).map_err(|e| format!("Invalid guardrails: {}", e))
+}
+
+impl Guardrails {
+    /// `--allowedTools` for the run. Plain write tools and `Bash` are left
+    /// out even if configured, since pre-allowed calls would skip the
+    /// path and command checks.
+    pub fn allowed_tools(&self) -> Vec<String> {
+        let mut tools: Vec<String> = READ_ONLY_TOOLS.iter().map(|t| t.to_string()).collect();
+        for tool in &self.allowed_tools {
+            let bypasses_paths = !self.writable_paths.is_empty() && WRITE_TOOLS.contains(&tool.as_str());
+            let bypasses_commands = !self.banned_commands.is_empty() && tool == "Bash";
+            if !bypasses_paths && !bypasses_commands && !tools.contains(tool) {
+                tools.push(tool.clone());
+            }
+        }
+        tools
+    }
+
+    /// Why a tool call breaks a guardrail (None = the call may proceed)
+    pub fn check(&self, worktree_path: &Path, tool_name: &str, input: &serde_json::Value) -> Option<String> {
+        let field = |key: &str| input.get(key).and_then(|v| v.as_str());
+        if WRITE_TOOLS.contains(&tool_name) {
+            let path = field("file_path").or_else(|| field("notebook_path"))?;
+            return self.check_path(worktree_path, path);
+        }
+        if tool_name == "Bash" {
+            let commands = commands(field("command")?);
+            return self
+                .banned_commands
+                .iter()
+                .find(|banned| commands.iter().any(|command| runs(command, banned)))
+                .map(|banned| format!("`{}` is a banned command", banned));
+        }
+        None
+    }
+
+    fn check_path(&self, worktree_path: &Path, path: &str) -> Option<String> {
+        let Some(relative) = relative_to(worktree_path, Path::new(path)) else {
+            return Some(format!("{} is outside the worktree", path));
+        };
+        if self.writable_paths.is_empty() || self.is_writable(worktree_path, &relative) {
+            None
+        } else {
+            Some(format!("{} is not in writable_paths", relative.display()))
+        }
+    }
+
+    fn is_writable(&self, worktree_path: &Path, relative: &Path) -> bool {
+        let mut builder = ignore::overrides::OverrideBuilder::new(worktree_path);
+        for glob in &self.writable_paths {
+            if let Err(e) = builder.add(glob) {
+                tracing::warn!("Invalid writable path glob {}: {}", glob, e);
+            }
+        }
+        match builder.build() {
+            Ok(globs) => globs.matched(relative, false).is_whitelist(),
+            Err(e) => {
+                tracing::warn!("Invalid writable paths: {}", e);
+                false
+            }
+        }
+    }
+}
+
+/// `path` relative to the worktree, None when it points outside of it
+fn relative_to(worktree_path: &Path, path: &Path) -> Option<PathBuf> {
+    let relative = if path.is_absolute() {
+        path.strip_prefix(worktree_path).ok()?
+    } else {
+        path
+    };
+    relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        .then(|| relative.to_path_buf())
+}
+
+/// Programs that run the command given in their arguments
+const WRAPPERS: [&str; 4] = ["sudo", "env", "command", "exec"];
+
+/// Shells whose `-c` argument is a command line of its own
+const SHELLS: [&str; 5] = ["sh", "bash", "zsh", "dash", "ksh"];
+
+/// `sh -c` payloads nested deeper than this are not looked into
+const MAX_SHELL_DEPTH: usize = 4;
+
+/// Words of every command a shell command line runs, as the program's file
+/// name followed by its arguments (environment assignments and wrappers
+/// stripped)
+fn commands(command_line: &str) -> Vec<Vec<String>> {
+    let mut commands = Vec::new();
+    collect_commands(command_line, 0, &mut commands);
+    commands
+}
+
+fn collect_commands(command_line: &str, depth: usize, commands: &mut Vec<Vec<String>>) {
+    for words in split_commands(command_line) {
+        let Some((program, args)) = strip_wrappers(&words).split_first() else {
+            continue;
+        };
+        let program = file_name(program);
+        if SHELLS.contains(&program) && depth < MAX_SHELL_DEPTH {
+            if let Some(payload) = shell_payload(args) {
+                collect_commands(payload, depth + 1, commands);
+            }
+        }
+        commands.push(std::iter::once(program.to_string()).chain(args.iter().cloned()).collect());
+    }
+}
+
+/// Split a command line into the words of its simple commands, at `&&`,
+/// `||`, `;`, `|`, newlines and subshell parentheses. Quotes are removed;
+/// `$(...)` and backtick substitutions become commands of their own.
+fn split_commands(command_line: &str) -> Vec<Vec<String>> {
+    let mut commands = Vec::new();
+    let mut words = Vec::new();
+    // None between words; quotes start a word even if it stays empty
+    let mut word: Option<String> = None;
+    let mut in_double_quotes = false;
+    let mut chars = command_line.chars().peekable();
+
+    fn end_word(word: &mut Option<String>, words: &mut Vec<String>) {
+        words.extend(word.take());
+    }
+    fn end_command(word: &mut Option<String>, words: &mut Vec<String>, commands: &mut Vec<Vec<String>>) {
+        end_word(word, words);
+        if !words.is_empty() {
+            commands.push(std::mem::take(words));
+        }
+    }
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    word.get_or_insert_with(String::new).push(escaped);
+                }
+            }
+            '\'' if !in_double_quotes => {
+                let quoted: String = chars.by_ref().take_while(|&c| c != '\'').collect();
+                word.get_or_insert_with(String::new).push_str(&quoted);
+            }
+            '"' => {
+                in_double_quotes = !in_double_quotes;
+                word.get_or_insert_with(String::new);
+            }
+            '$' if chars.peek() == Some(&'(') => {
+                chars.next();
+                let mut depth = 1;
+                let body: String = chars
+                    .by_ref()
+                    .take_while(|&c| {
+                        match c {
+                            '(' => depth += 1,
+                            ')' => depth -= 1,
+                            _ => {}
+                        }
+                        depth > 0
+                    })
+                    .collect();
+                commands.extend(split_commands(&body));
+            }
+            '`' => {
+                let body: String = chars.by_ref().take_while(|&c| c != '`').collect();
+                commands.extend(split_commands(&body));
+            }
+            c if in_double_quotes => word.get_or_insert_with(String::new).push(c),
+            '&' | '|' | ';' | '\n' | '(' | ')' => end_command(&mut word, &mut words, &mut commands),
+            c if c.is_whitespace() => end_word(&mut word, &mut words),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    end_command(&mut word, &mut words, &mut commands);
+    commands
+}
+
+/// A command's words without leading `NAME=value` assignments and
+/// wrappers (with their options)
+fn strip_wrappers(mut words: &[String]) -> &[String] {
+    while let Some((first, rest)) = words.split_first() {
+        if is_assignment(first) {
+            words = rest;
+            continue;
+        }
+        let wrapper = file_name(first);
+        if !WRAPPERS.contains(&wrapper) {
+            break;
+        }
+        words = rest;
+        while let Some((option, rest)) = words.split_first() {
+            if option == "--" {
+                words = rest;
+                break;
+            }
+            if !option.starts_with('-') || option == "-" {
+                break;
+            }
+            words = if option_takes_value(wrapper, option) { rest.get(1..).unwrap_or_default() } else { rest };
+        }
+    }
+    words
+}
+
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// Wrapper options followed by a separate value (`sudo -u root`)
+fn option_takes_value(wrapper: &str, option: &str) -> bool {
+    matches!(
+        (wrapper, option),
+        ("sudo", "-u" | "-g" | "-h" | "-p" | "-C" | "-D" | "-r" | "-t" | "-U") | ("env", "-u" | "-C") | ("exec", "-a")
+    )
+}
+
+/// The command line of `sh -c <payload>` (also `-lc`, `-e -c`, ...)
+fn shell_payload(args: &[String]) -> Option<&str> {
+    let position = args
+        .iter()
+        .take_while(|arg| arg.starts_with('-') && !arg.starts_with("--"))
+        .position(|option| option[1..].contains('c'))?;
+    args.get(position + 1).map(String::as_str)
+}
+
+/// `/bin/rm` runs `rm`
+fn file_name(program: &str) -> &str {
+    program.rsplit('/').next().unwrap_or(program)
+}
+
+/// Whether a command's words run `banned` (whole words, so "rm" doesn't
+/// match "rmdir")
+fn runs(command: &[String], banned: &str) -> bool {
+    let mut banned = banned.split_whitespace();
+    let Some(program) = banned.next() else {
+        return false;
+    };
+    let banned: Vec<&str> = std::iter::once(file_name(program)).chain(banned).collect();
+    command.len() >= banned.len() && command.iter().zip(&banned).all(|(word, banned)| word == banned)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn guardrails() -> Guardrails {
+        parse_str(
+            r#"
+allowed_tools = ["WebFetch", "Bash", "Edit"]
+writable_paths = ["src/**", "Cargo.toml"]
+banned_commands = ["git push", "rm"]
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_allowed_tools_skip_guarded_tools() {
+        let tools = guardrails().allowed_tools();
+        assert!(tools.contains(&"Read".to_string()));
+        assert!(tools.contains(&"WebFetch".to_string()));
+        assert!(!tools.contains(&"Bash".to_string()));
+        assert!(!tools.contains(&"Edit".to_string()));
+
+        let unguarded = Guardrails { allowed_tools: vec!["Edit".to_string()], ..Default::default() };
+        assert!(unguarded.allowed_tools().contains(&"Edit".to_string()));
+    }
+
+    #[test]
+    fn test_check_paths() {
+        let rails = guardrails();
+        let root = Path::new("/repo");
+        assert_eq!(rails.check(root, "Edit", &json!({ "file_path": "/repo/src/lib.rs" })), None);
+        assert_eq!(rails.check(root, "Write", &json!({ "file_path": "Cargo.toml" })), None);
+        assert_eq!(
+            rails.check(root, "Write", &json!({ "file_path": "/repo/.github/ci.yml" })),
+            Some(".github/ci.yml is not in writable_paths".to_string())
+        );
+        assert_eq!(
+            rails.check(root, "Edit", &json!({ "file_path": "/etc/hosts" })),
+            Some("/etc/hosts is outside the worktree".to_string())
+        );
+        assert!(Guardrails::default().check(root, "Write", &json!({ "file_path": "../x" })).is_some());
+        assert_eq!(Guardrails::default().check(root, "Write", &json!({ "file_path": "/repo/any" })), None);
+    }
+
+    #[test]
+    fn test_check_commands() {
+        let rails = guardrails();
+        let root = Path::new("/repo");
+        assert_eq!(rails.check(root, "Bash", &json!({ "command": "cargo test" })), None);
+        assert_eq!(rails.check(root, "Bash", &json!({ "command": "rmdir build" })), None);
+        assert_eq!(
+            rails.check(root, "Bash", &json!({ "command": "cargo fmt && git push origin" })),
+            Some("`git push` is a banned command".to_string())
+        );
+        assert!(rails.check(root, "Bash", &json!({ "command": "ls | rm -f x" })).is_some());
+        assert_eq!(rails.check(root, "WebFetch", &json!({ "url": "https://example.com" })), None);
+    }
+
+    #[test]
+    fn test_check_commands_through_wrappers() {
+        let rails = guardrails();
+        let root = Path::new("/repo");
+        let banned = |command: &str| rails.check(root, "Bash", &json!({ "command": command }));
+        for command in [
+            "sudo rm -rf x",
+            "sudo -u root rm x",
+            "env git push",
+            "FORCE=1 env -i HOME=/ git push",
+            "command rm x",
+            "exec git push",
+            "/bin/rm x",
+            "git  push",
+            "git\tpush",
+            "echo $(rm x)",
+            "echo \"$(rm x)\"",
+            "echo `rm x`",
+            "bash -c \"git push\"",
+            "sh -lc 'cargo fmt && git push'",
+            "sudo bash -c 'sh -c \"rm x\"'",
+            "(rm x)",
+            "cargo test && (cd sub; rm x)",
+        ] {
+            assert!(banned(command).is_some(), "{}", command);
+        }
+        for command in ["echo 'rm x'", "git status", "echo git push", "cat rm", "bash -c 'cargo test'"] {
+            assert_eq!(banned(command), None, "{}", command);
+        }
+    }
+
+    #[test]
+    fn test_split_commands() {
+        assert_eq!(
+            split_commands("A=1 echo \"a  b\" '' x\\ y | wc"),
+            vec![vec!["A=1", "echo", "a  b", "", "x y"], vec!["wc"]]
+        );
+        assert_eq!(
+            split_commands("(cd a; make) || echo \"$(date) done\""),
+            vec![vec!["cd", "a"], vec!["make"], vec!["date"], vec!["echo", " done"]]
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! Post-implementation hooks (lint / format pipeline).
+//!
+//! `<worktree>/.rstn/hooks.toml` lists commands that tidy up the code Claude
+//! wrote before it is tested and reviewed:
+//!
+//! ```toml
+//! [[hook]]
+//! name = "fmt"
+//! command = "cargo fmt"
+//!
+//! [[hook]]
+//! name = "clippy"
+//! command = "cargo clippy --fix --allow-dirty --allow-staged"
+//! timeout_secs = 600
+//! ```
+//!
+//! Hooks run in order through `sh -c` in the worktree, each as a task run so
+//! its output streams into the task panel. A failing hook does not stop the
+//! pipeline; every result is recorded in `.rstn/changes/<name>/hooks.json`.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Hooks file name (in `<worktree>/.rstn/`)
+pub const HOOKS_FILE: &str = "hooks.toml";
+
+/// Results file in the change directory
+pub const HOOK_RESULTS_FILE: &str = "hooks.json";
+
+/// Time a hook may run when it sets no `timeout_secs`
+pub const DEFAULT_HOOK_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct HooksFile {
+    #[serde(default, rename = "hook")]
+    hooks: Vec<Hook>,
+}
+
+/// A command run after every implementation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Hook {
+    pub name: String,
+    /// Shell command line
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+impl Hook {
+    pub fn timeout(&self) -> Duration {
+        self.timeout_secs.map_or(DEFAULT_HOOK_TIMEOUT, Duration::from_secs)
+    }
+
+    /// Program and arguments running the command line
+    pub fn argv(&self) -> (&'static str, Vec<String>) {
+        ("sh", vec!["-c".to_string(), self.command.clone()])
+    }
+}
+
+pub fn hooks_path(worktree_path: &Path) -> PathBuf {
+    worktree_path.join(".rstn").join(HOOKS_FILE)
+}
+
+/// Load a worktree's hooks (none when the file is missing)
+pub fn load(worktree_path: &Path) -> Result<Vec<Hook>, String> {
+    let path = hooks_path(worktree_path);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_str(&content).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn parse_str(content: &str) -> Result<Vec<Hook>, String> {
+    let file: HooksFile = toml::from_str(content).map_err(|e| format!("Invalid hooks: {}", e))?;
+    for (i, hook) in file.hooks.iter().enumerate() {
+        if hook.name.trim().is_empty() || hook.command.trim().is_empty() {
+            return Err(format!("Hook {} needs a name and a command", i + 1));
+        }
+        if file.hooks[..i].iter().any(|h| h.name == hook.name) {
+            return Err(format!("Duplicate hook name: {}", hook.name));
+        }
+    }
+    Ok(file.hooks)
+}
+
+/// Outcome of one hook after an implementation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HookResult {
+    pub name: String,
+    pub command: String,
+    pub success: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    /// ISO 8601 timestamp
+    pub ran_at: String,
+    /// Why the hook could not run or was stopped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Task run holding the hook's output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_id: Option<String>,
+}
+
+impl HookResult {
+    /// One line for the implementation output
+    pub fn summary(&self) -> String {
+        let outcome = match (&self.error, self.success) {
+            (Some(error), _) => format!("failed: {}", error),
+            (None, true) => "passed".to_string(),
+            (None, false) => format!("failed (exit code {})", self.exit_code.map_or("?".to_string(), |c| c.to_string())),
+        };
+        format!("[hooks] {} {} ({} ms)", self.name, outcome, self.duration_ms)
+    }
+}
+
+pub fn results_path(worktree: &Path, change_name: &str) -> PathBuf {
+    worktree.join(".rstn").join("changes").join(change_name).join(HOOK_RESULTS_FILE)
+}
+
+/// Hook results of the last implementation of a change
+pub fn load_results(worktree: &Path, change_name: &str) -> Vec<HookResult> {
+    std::fs::read_to_string(results_path(worktree, change_name))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_results(worktree: &Path, change_name: &str, results: &[HookResult]) -> Result<(), String> {
+    let path = results_path(worktree, change_name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(results).map_err(|e| format!("Failed to serialize hook results: {}", e))?;
+    crate::spec_kit::write_atomic(&path, &json)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hooks() {
+        let hooks = parse_str(
+            r#"
+[[hook]]
+name = "fmt"
+command = "cargo fmt"
+
+[[hook]]
+name = "eslint"
+command = "npx eslint --fix src"
+timeout_secs = 60
+"#,
+        )
+        .unwrap();
+        assert_eq!(hooks.len(), 2);
+        assert_eq!(hooks[0].timeout(), DEFAULT_HOOK_TIMEOUT);
+        assert_eq!(hooks[1].timeout(), Duration::from_secs(60));
+        assert_eq!(hooks[1].argv().1, vec!["-c", "npx eslint --fix src"]);
+
+        assert!(parse_str("").unwrap().is_empty());
+        assert!(parse_str("[[hook]]\nname = \"fmt\"\ncommand = \"\"\n").is_err());
+        assert!(parse_str("[[hook]]\nname = \"a\"\ncommand = \"x\"\n[[hook]]\nname = \"a\"\ncommand = \"y\"\n").is_err());
+        assert!(parse_str("[[hook]]\nname = \"a\"\ncmd = \"x\"\n").is_err());
+    }
+
+    #[test]
+    fn test_hook_results_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_results(dir.path(), "add-login").is_empty());
+        let results = vec![HookResult {
+            name: "fmt".to_string(),
+            command: "cargo fmt".to_string(),
+            success: false,
+            exit_code: Some(1),
+            duration_ms: 420,
+            ran_at: "now".to_string(),
+            error: None,
+            task_id: Some("task-1".to_string()),
+        }];
+        assert_eq!(results[0].summary(), "[hooks] fmt failed (exit code 1) (420 ms)");
+        save_results(dir.path(), "add-login", &results).unwrap();
+        assert_eq!(load_results(dir.path(), "add-login"), results);
+    }
+}
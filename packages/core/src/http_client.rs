@@ -0,0 +1,399 @@
+//! HTTP request collections per project.
+//!
+//! Each file in `<project>/.rstn/requests/*.toml` is a collection:
+//!
+//! ```toml
+//! name = "API smoke tests"
+//!
+//! [vars]
+//! base = "http://localhost:8080"
+//!
+//! [[requests]]
+//! name = "health"
+//! url = "{{base}}/health"
+//!
+//! [[requests]]
+//! name = "create user"
+//! method = "POST"
+//! url = "{{base}}/users"
+//! headers = { Authorization = "Bearer {{API_TOKEN}}", Content-Type = "application/json" }
+//! body = '{"name": "ada"}'
+//! ```
+//!
+//! `{{NAME}}` in the URL, headers and body is replaced by the variable from
+//! the worktree's env file, falling back to the collection's `[vars]`.
+//! Unknown variables fail the request before it is sent.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Directory of request collections (in `<project>/.rstn/`)
+pub const REQUESTS_DIR: &str = "requests";
+
+/// Timeout of requests that don't set `timeout_secs`
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+
+/// Response bodies are kept up to this size
+pub const MAX_BODY_BYTES: usize = 256 * 1024;
+
+const METHODS: &[&str] = &["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+/// A request of a collection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpRequestDef {
+    pub name: String,
+    #[serde(default = "default_method")]
+    pub method: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+/// Requests of one `.rstn/requests/<id>.toml` file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RequestCollection {
+    /// File stem
+    pub id: String,
+    pub name: String,
+    /// Defaults for `{{NAME}}` placeholders (env file values win)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub vars: BTreeMap<String, String>,
+    pub requests: Vec<HttpRequestDef>,
+    /// Why the file couldn't be loaded (no requests then)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// On-disk format of a collection file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawCollection {
+    name: Option<String>,
+    #[serde(default)]
+    vars: BTreeMap<String, String>,
+    #[serde(default)]
+    requests: Vec<HttpRequestDef>,
+}
+
+/// Response of an executed request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpResponseData {
+    pub status: u16,
+    /// Reason phrase ("OK", "Not Found")
+    pub status_text: String,
+    pub headers: Vec<(String, String)>,
+    /// Body as text (lossy UTF-8), cut at `MAX_BODY_BYTES`
+    pub body: String,
+    pub body_truncated: bool,
+    /// Body size in bytes
+    pub size_bytes: u64,
+    /// Time until the whole body was received
+    pub duration_ms: u64,
+}
+
+// ============================================================================
+// Loading
+// ============================================================================
+
+/// Directory of a project's collections (<project>/.rstn/requests)
+pub fn requests_dir(project_path: &Path) -> PathBuf {
+    project_path.join(".rstn").join(REQUESTS_DIR)
+}
+
+/// Load a project's collections, sorted by name. A missing directory
+/// defines none; files that fail to parse are returned with their error.
+pub fn load_collections(project_path: &Path) -> Vec<RequestCollection> {
+    let Ok(entries) = std::fs::read_dir(requests_dir(project_path)) else {
+        return Vec::new();
+    };
+    let mut collections: Vec<RequestCollection> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| {
+            let id = path.file_stem()?.to_str()?.to_string();
+            let parsed = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+                .and_then(|content| parse_collection_str(&id, &content));
+            Some(parsed.unwrap_or_else(|error| RequestCollection {
+                name: id.clone(),
+                id,
+                vars: BTreeMap::new(),
+                requests: Vec::new(),
+                error: Some(error),
+            }))
+        })
+        .collect();
+    collections.sort_by_key(|c| c.name.to_lowercase());
+    collections
+}
+
+/// Parse collection TOML content
+pub fn parse_collection_str(id: &str, content: &str) -> Result<RequestCollection, String> {
+    let raw: RawCollection = toml::from_str(content).map_err(|e| format!("Invalid request collection: {}", e))?;
+    let mut requests: Vec<HttpRequestDef> = Vec::with_capacity(raw.requests.len());
+    for mut request in raw.requests {
+        if requests.iter().any(|r| r.name == request.name) {
+            return Err(format!("Duplicate request name: {}", request.name));
+        }
+        request.method = request.method.trim().to_uppercase();
+        if !METHODS.contains(&request.method.as_str()) {
+            return Err(format!("Request '{}': unsupported method {}", request.name, request.method));
+        }
+        requests.push(request);
+    }
+    Ok(RequestCollection {
+        id: id.to_string(),
+        name: raw.name.unwrap_or_else(|| id.to_string()),
+        vars: raw.vars,
+        requests,
+        error: None,
+    })
+}
+
+// ============================================================================
+// Variables
+// ============================================================================
+
+/// Replace `{{NAME}}` placeholders (whitespace inside the braces is ignored).
+/// Fails with the names of variables that are not defined.
+pub fn substitute(template: &str, vars: &BTreeMap<String, String>) -> Result<String, Vec<String>> {
+    let mut output = String::with_capacity(template.len());
+    let mut missing: Vec<String> = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        output.push_str(&rest[..start]);
+        let name = rest[start + 2..start + 2 + len].trim();
+        match vars.get(name) {
+            Some(value) => output.push_str(value),
+            None => {
+                if !missing.iter().any(|m| m == name) {
+                    missing.push(name.to_string());
+                }
+            }
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    output.push_str(rest);
+    if missing.is_empty() {
+        Ok(output)
+    } else {
+        Err(missing)
+    }
+}
+
+/// Resolve every placeholder of a request
+pub fn resolve_request(request: &HttpRequestDef, vars: &BTreeMap<String, String>) -> Result<HttpRequestDef, String> {
+    let mut missing: Vec<String> = Vec::new();
+    let mut resolve = |text: &str| {
+        substitute(text, vars).unwrap_or_else(|names| {
+            for name in names {
+                if !missing.contains(&name) {
+                    missing.push(name);
+                }
+            }
+            String::new()
+        })
+    };
+    let resolved = HttpRequestDef {
+        name: request.name.clone(),
+        method: request.method.clone(),
+        url: resolve(&request.url),
+        headers: request
+            .headers
+            .iter()
+            .map(|(name, value)| (name.clone(), resolve(value)))
+            .collect(),
+        body: request.body.as_deref().map(&mut resolve),
+        timeout_secs: request.timeout_secs,
+    };
+    if missing.is_empty() {
+        Ok(resolved)
+    } else {
+        Err(format!("Undefined variables: {}", missing.join(", ")))
+    }
+}
+
+// ============================================================================
+// Execution
+// ============================================================================
+
+/// Send a resolved request and capture the response
+pub async fn execute(request: &HttpRequestDef) -> Result<HttpResponseData, String> {
+    let method = reqwest::Method::from_bytes(request.method.as_bytes())
+        .map_err(|_| format!("Invalid method {}", request.method))?;
+    let timeout = Duration::from_secs(request.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS));
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut builder = client.request(method, &request.url);
+    for (name, value) in &request.headers {
+        builder = builder.header(name, value);
+    }
+    if let Some(body) = &request.body {
+        builder = builder.body(body.clone());
+    }
+
+    let started = Instant::now();
+    let response = builder.send().await.map_err(|e| describe_error(&request.url, e))?;
+    let status = response.status();
+    let headers = response
+        .headers()
+        .iter()
+        .map(|(name, value)| (name.to_string(), String::from_utf8_lossy(value.as_bytes()).to_string()))
+        .collect();
+    let bytes = response.bytes().await.map_err(|e| describe_error(&request.url, e))?;
+    let duration_ms = started.elapsed().as_millis() as u64;
+
+    let body_truncated = bytes.len() > MAX_BODY_BYTES;
+    let body = String::from_utf8_lossy(&bytes[..bytes.len().min(MAX_BODY_BYTES)]).to_string();
+    Ok(HttpResponseData {
+        status: status.as_u16(),
+        status_text: status.canonical_reason().unwrap_or_default().to_string(),
+        headers,
+        body,
+        body_truncated,
+        size_bytes: bytes.len() as u64,
+        duration_ms,
+    })
+}
+
+fn describe_error(url: &str, error: reqwest::Error) -> String {
+    if error.is_timeout() {
+        format!("{} timed out", url)
+    } else if error.is_connect() {
+        format!("Could not connect to {} (is the service running?)", url)
+    } else if error.is_builder() {
+        format!("Invalid request to {}: {}", url, error)
+    } else {
+        format!("Request to {} failed: {}", url, error)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_substitute() {
+        let vars = vars(&[("base", "http://localhost:8080"), ("TOKEN", "secret")]);
+        assert_eq!(
+            substitute("{{base}}/users?t={{ TOKEN }}", &vars).unwrap(),
+            "http://localhost:8080/users?t=secret"
+        );
+        assert_eq!(substitute("no {{placeholder", &vars).unwrap(), "no {{placeholder");
+        assert_eq!(
+            substitute("{{A}} {{B}} {{A}}", &vars).unwrap_err(),
+            vec!["A".to_string(), "B".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_collection() {
+        let collection = parse_collection_str(
+            "api",
+            r#"
+                [vars]
+                base = "http://localhost:8080"
+
+                [[requests]]
+                name = "health"
+                url = "{{base}}/health"
+
+                [[requests]]
+                name = "create"
+                method = "post"
+                url = "{{base}}/users"
+                headers = { Authorization = "Bearer {{API_TOKEN}}" }
+                body = '{"name": "ada"}'
+            "#,
+        )
+        .unwrap();
+        assert_eq!(collection.name, "api");
+        assert_eq!(collection.requests[0].method, "GET");
+        assert_eq!(collection.requests[1].method, "POST");
+
+        let resolved = resolve_request(&collection.requests[1], &collection.vars).unwrap_err();
+        assert_eq!(resolved, "Undefined variables: API_TOKEN");
+
+        let mut all_vars = collection.vars.clone();
+        all_vars.insert("API_TOKEN".to_string(), "t0k".to_string());
+        let resolved = resolve_request(&collection.requests[1], &all_vars).unwrap();
+        assert_eq!(resolved.url, "http://localhost:8080/users");
+        assert_eq!(resolved.headers["Authorization"], "Bearer t0k");
+
+        let duplicate = "[[requests]]\nname = \"a\"\nurl = \"/\"\n[[requests]]\nname = \"a\"\nurl = \"/\"";
+        assert!(parse_collection_str("x", duplicate).is_err());
+        assert!(parse_collection_str("x", "[[requests]]\nname = \"a\"\nmethod = \"FETCH\"\nurl = \"/\"").is_err());
+    }
+
+    #[test]
+    fn test_load_collections_keeps_broken_files() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load_collections(dir.path()).is_empty());
+
+        let requests = requests_dir(dir.path());
+        std::fs::create_dir_all(&requests).unwrap();
+        std::fs::write(requests.join("users.toml"), "name = \"Users\"\n[[requests]]\nname = \"list\"\nurl = \"/\"").unwrap();
+        std::fs::write(requests.join("broken.toml"), "[[requests]").unwrap();
+        std::fs::write(requests.join("notes.md"), "ignored").unwrap();
+
+        let collections = load_collections(dir.path());
+        assert_eq!(collections.len(), 2);
+        assert_eq!(collections[0].id, "broken");
+        assert!(collections[0].error.is_some());
+        assert_eq!(collections[1].name, "Users");
+        assert_eq!(collections[1].requests.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_execute_captures_response() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let app = axum::Router::new().route(
+            "/echo",
+            axum::routing::post(|body: String| async move { (axum::http::StatusCode::CREATED, format!("got {}", body)) }),
+        );
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let request = HttpRequestDef {
+            name: "echo".to_string(),
+            method: "POST".to_string(),
+            url: format!("http://127.0.0.1:{}/echo", port),
+            headers: BTreeMap::new(),
+            body: Some("hi".to_string()),
+            timeout_secs: None,
+        };
+        let response = execute(&request).await.unwrap();
+        assert_eq!(response.status, 201);
+        assert_eq!(response.status_text, "Created");
+        assert_eq!(response.body, "got hi");
+        assert_eq!(response.size_bytes, 6);
+        assert!(!response.body_truncated);
+        assert!(response.headers.iter().any(|(name, _)| name == "content-length"));
+    }
+}
@@ -0,0 +1,91 @@
+//! Implementation runner helpers (CESDD Phase 5).
+//!
+//! Pure helpers used by `ExecutePlan`: splitting plan.md into tasks and
+//! tracking per-task completion markers in Claude's output. The test run
+//! that follows lives in `test_runner`.
+
+/// Extract implementation tasks from plan.md.
+///
+/// Uses the numbered items under an "Implementation Steps" heading when
+/// present, otherwise every top-level numbered item in the plan.
+pub fn parse_plan_tasks(plan: &str) -> Vec<String> {
+    let lines: Vec<&str> = plan.lines().collect();
+
+    let section_start = lines.iter().position(|l| {
+        let lower = l.to_lowercase();
+        l.trim_start().starts_with('#') && lower.contains("implementation steps")
+    });
+
+    let candidates: &[&str] = match section_start {
+        Some(start) => {
+            let rest = &lines[start + 1..];
+            let end = rest
+                .iter()
+                .position(|l| l.trim_start().starts_with('#'))
+                .unwrap_or(rest.len());
+            &rest[..end]
+        }
+        None => &lines,
+    };
+
+    candidates
+        .iter()
+        .filter_map(|line| parse_numbered_item(line))
+        .collect()
+}
+
+/// Parse a top-level "N. text" list item, stripping bold markers
+fn parse_numbered_item(line: &str) -> Option<String> {
+    if line.starts_with(' ') || line.starts_with('\t') {
+        return None;
+    }
+    let (number, rest) = line.split_once(". ")?;
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let title = rest.replace("**", "").trim().to_string();
+    (!title.is_empty()).then_some(title)
+}
+
+/// Marker Claude is asked to print after finishing step N (1-based)
+pub fn step_done_marker(step: usize) -> String {
+    format!("[STEP {} DONE]", step)
+}
+
+/// Find completed task indices (0-based) from markers in the output so far
+pub fn completed_task_indices(output: &str, task_count: usize) -> Vec<usize> {
+    (0..task_count)
+        .filter(|i| output.contains(&step_done_marker(i + 1)))
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_plan_tasks_uses_implementation_steps_section() {
+        let plan = "# Plan\n\n## Implementation Steps\n1. **Add model** - create struct\n2. Wire reducer\n   1. nested detail\n\n## Testing Strategy\n1. Unit tests\n";
+        assert_eq!(
+            parse_plan_tasks(plan),
+            vec!["Add model - create struct".to_string(), "Wire reducer".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_plan_tasks_without_section() {
+        let plan = "1. First\nsome text\n2. Second\n10 items\n";
+        assert_eq!(parse_plan_tasks(plan), vec!["First".to_string(), "Second".to_string()]);
+    }
+
+    #[test]
+    fn test_completed_task_indices() {
+        let output = "working...\n[STEP 1 DONE]\nmore\n[STEP 3 DONE]";
+        assert_eq!(completed_task_indices(output, 3), vec![0, 2]);
+        assert!(completed_task_indices(output, 0).is_empty());
+    }
+}
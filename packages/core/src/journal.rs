@@ -0,0 +1,322 @@
+//! Crash-safe action journal.
+//!
+//! Every dispatched action is appended to `~/.rstn/journal/actions.jsonl`.
+//! Every SNAPSHOT_INTERVAL actions a full `AppState` snapshot is written and
+//! the journal is truncated. A `session.lock` marker exists while the app
+//! runs; if it is still there at startup, the previous session did not shut
+//! down cleanly and its snapshot + journal are set aside for `recover`, which
+//! replays the journaled actions over the snapshot.
+//!
+//! Records and snapshots go through `diagnostics::redact` first, so API keys,
+//! tokens and URL passwords never reach disk.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::actions::Action;
+use crate::app_state::AppState;
+use crate::reducer::reduce;
+
+/// Actions between full snapshots
+pub const SNAPSHOT_INTERVAL: u64 = 100;
+
+const JOURNAL_FILE: &str = "actions.jsonl";
+const SNAPSHOT_FILE: &str = "snapshot.json";
+const LOCK_FILE: &str = "session.lock";
+/// Prefix of the files kept from a session that crashed
+const CRASHED_PREFIX: &str = "crashed-";
+
+/// One journal line
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalRecord {
+    seq: u64,
+    timestamp: String,
+    action: Action,
+}
+
+/// Full state as of journal record `seq`
+#[derive(Debug, Serialize, Deserialize)]
+struct Snapshot {
+    seq: u64,
+    state: AppState,
+}
+
+#[derive(Default)]
+struct JournalInner {
+    file: Option<File>,
+    /// Sequence number of the last appended action
+    seq: u64,
+    /// Actions appended since the last snapshot
+    pending: u64,
+}
+
+/// Append-only journal of dispatched actions
+pub struct Journal {
+    dir: PathBuf,
+    inner: Mutex<JournalInner>,
+}
+
+impl Journal {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            inner: Mutex::new(JournalInner::default()),
+        }
+    }
+
+    /// Default location (~/.rstn/journal/)
+    pub fn default_dir() -> PathBuf {
+        crate::persistence::get_rstn_dir().join("journal")
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, JournalInner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    fn crashed_path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{}{}", CRASHED_PREFIX, name))
+    }
+
+    /// Start a session. Returns true if the previous one did not shut down
+    /// cleanly (its work can be restored with `recover`).
+    pub fn start(&self) -> io::Result<bool> {
+        fs::create_dir_all(&self.dir)?;
+
+        let crashed = self.path(LOCK_FILE).exists()
+            && (self.path(JOURNAL_FILE).exists() || self.path(SNAPSHOT_FILE).exists());
+        for name in [JOURNAL_FILE, SNAPSHOT_FILE] {
+            if crashed {
+                remove_if_exists(&self.crashed_path(name))?;
+                if self.path(name).exists() {
+                    fs::rename(self.path(name), self.crashed_path(name))?;
+                }
+            } else {
+                // Recovery was not taken up before the last clean shutdown
+                remove_if_exists(&self.crashed_path(name))?;
+                remove_if_exists(&self.path(name))?;
+            }
+        }
+
+        fs::write(self.path(LOCK_FILE), std::process::id().to_string())?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path(JOURNAL_FILE))?;
+        *self.lock() = JournalInner {
+            file: Some(file),
+            ..Default::default()
+        };
+        Ok(crashed)
+    }
+
+    /// Append an action. Returns true when a snapshot is due.
+    pub fn append(&self, action: &Action) -> io::Result<bool> {
+        let mut inner = self.lock();
+        let seq = inner.seq + 1;
+        let Some(file) = inner.file.as_mut() else {
+            return Ok(false);
+        };
+        let record = JournalRecord {
+            seq,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            action: action.clone(),
+        };
+        let mut line = serde_json::to_string(&redacted(serde_json::to_value(&record)?))?;
+        line.push('\n');
+        file.write_all(line.as_bytes())?;
+        file.flush()?;
+
+        inner.seq = seq;
+        inner.pending += 1;
+        Ok(inner.pending >= SNAPSHOT_INTERVAL)
+    }
+
+    /// Write a full snapshot of `state` and truncate the journal.
+    ///
+    /// `state` must include every appended action, so call this while
+    /// holding the same lock the actions are reduced and appended under.
+    pub fn snapshot(&self, state: &AppState) -> io::Result<()> {
+        let mut inner = self.lock();
+        if inner.file.is_none() {
+            return Ok(());
+        }
+        let json = serde_json::to_string(&redacted(serde_json::json!({ "seq": inner.seq, "state": state })))?;
+        let tmp = self.path(&format!(".{}.tmp", SNAPSHOT_FILE));
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, self.path(SNAPSHOT_FILE))?;
+
+        // Records up to `seq` are in the snapshot (recover skips them if
+        // truncation does not happen)
+        File::create(self.path(JOURNAL_FILE))?;
+        inner.file = Some(OpenOptions::new().append(true).open(self.path(JOURNAL_FILE))?);
+        inner.pending = 0;
+        Ok(())
+    }
+
+    /// Rebuild the state of a session that did not shut down cleanly:
+    /// its last snapshot with the journaled actions replayed over it.
+    /// Returns None if there is nothing to recover. Consumes the saved files.
+    pub fn recover(&self) -> io::Result<Option<AppState>> {
+        let snapshot_path = self.crashed_path(SNAPSHOT_FILE);
+        let journal_path = self.crashed_path(JOURNAL_FILE);
+        if !snapshot_path.exists() && !journal_path.exists() {
+            return Ok(None);
+        }
+
+        let (mut state, snapshot_seq) = match fs::read_to_string(&snapshot_path) {
+            Ok(json) => match serde_json::from_str::<Snapshot>(&json) {
+                Ok(snapshot) => (snapshot.state, snapshot.seq),
+                Err(e) => {
+                    tracing::warn!("Ignoring unreadable journal snapshot: {}", e);
+                    (AppState::default(), 0)
+                }
+            },
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (AppState::default(), 0),
+            Err(e) => return Err(e),
+        };
+
+        let mut replayed = 0;
+        if journal_path.exists() {
+            for line in BufReader::new(File::open(&journal_path)?).lines() {
+                // A crash can leave the last line half-written
+                let Ok(record) = serde_json::from_str::<JournalRecord>(&line?) else {
+                    continue;
+                };
+                if record.seq > snapshot_seq {
+                    reduce(&mut state, record.action);
+                    replayed += 1;
+                }
+            }
+        }
+        tracing::info!("Recovered state: replayed {} journaled actions", replayed);
+
+        remove_if_exists(&snapshot_path)?;
+        remove_if_exists(&journal_path)?;
+        Ok(Some(state))
+    }
+
+    /// End the session cleanly: nothing will be offered for recovery.
+    pub fn finish(&self) -> io::Result<()> {
+        let mut inner = self.lock();
+        inner.file = None;
+        for name in [JOURNAL_FILE, SNAPSHOT_FILE, LOCK_FILE] {
+            remove_if_exists(&self.path(name))?;
+        }
+        Ok(())
+    }
+}
+
+/// API keys, tokens and URL passwords masked (`recover_state` puts the
+/// secrets loaded at startup back)
+fn redacted(mut value: serde_json::Value) -> serde_json::Value {
+    crate::diagnostics::redact(&mut value);
+    value
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::Theme;
+    use tempfile::tempdir;
+
+    fn open_project(path: &str) -> Action {
+        Action::OpenProject { path: path.to_string() }
+    }
+
+    /// Dispatch like `state_dispatch`: reduce, append, snapshot when due
+    fn dispatch(journal: &Journal, state: &mut AppState, action: Action) {
+        reduce(state, action.clone());
+        if journal.append(&action).unwrap() {
+            journal.snapshot(state).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_recover_replays_journal_over_snapshot() {
+        let dir = tempdir().unwrap();
+        let journal = Journal::new(dir.path());
+        assert!(!journal.start().unwrap());
+
+        let mut state = AppState::default();
+        journal.snapshot(&state).unwrap();
+        dispatch(&journal, &mut state, open_project("/test/a"));
+        for i in 0..SNAPSHOT_INTERVAL {
+            let theme = if i % 2 == 0 { Theme::Dark } else { Theme::Light };
+            dispatch(&journal, &mut state, Action::SetTheme { theme });
+        }
+        dispatch(&journal, &mut state, open_project("/test/b"));
+        // Simulate a torn write at crash time
+        let mut file = OpenOptions::new().append(true).open(dir.path().join(JOURNAL_FILE)).unwrap();
+        file.write_all(b"{\"seq\":").unwrap();
+        drop(journal);
+
+        // Next launch finds the lock left behind
+        let journal = Journal::new(dir.path());
+        assert!(journal.start().unwrap());
+        let recovered = journal.recover().unwrap().expect("state to recover");
+        assert_eq!(recovered.projects.len(), 2);
+        assert_eq!(recovered.global_settings.theme, state.global_settings.theme);
+        assert_eq!(recovered.active_project_index, 1);
+
+        // Recovery is only offered once
+        assert!(journal.recover().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_secrets_do_not_reach_disk() {
+        let dir = tempdir().unwrap();
+        let journal = Journal::new(dir.path());
+        journal.start().unwrap();
+        let mut state = AppState::default();
+        dispatch(
+            &journal,
+            &mut state,
+            Action::SetOpenAiSettings {
+                settings: crate::app_state::OpenAiSettings {
+                    base_url: "https://api.openai.com/v1".to_string(),
+                    api_key: Some("sk-live-123".to_string()),
+                    model: "gpt-4o".to_string(),
+                },
+            },
+        );
+        journal.snapshot(&state).unwrap();
+        dispatch(&journal, &mut state, open_project("/test/a"));
+
+        for name in [JOURNAL_FILE, SNAPSHOT_FILE] {
+            let written = fs::read_to_string(dir.path().join(name)).unwrap();
+            assert!(!written.contains("sk-live-123"), "{} holds the API key", name);
+        }
+    }
+
+    #[test]
+    fn test_clean_shutdown_offers_no_recovery() {
+        let dir = tempdir().unwrap();
+        let journal = Journal::new(dir.path());
+        journal.start().unwrap();
+        let mut state = AppState::default();
+        dispatch(&journal, &mut state, open_project("/test/a"));
+        journal.finish().unwrap();
+
+        let journal = Journal::new(dir.path());
+        assert!(!journal.start().unwrap());
+        assert!(journal.recover().unwrap().is_none());
+    }
+}
@@ -15,6 +15,29 @@ pub struct JustCommand {
     pub description: Option<String>,
     /// The recipe/shell commands
     pub recipe: String,
+    /// Recipe parameters, in order
+    pub parameters: Vec<JustParameter>,
+}
+
+/// A recipe parameter (`name`, `name="default"`, `+rest`, `*rest`)
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct JustParameter {
+    pub name: String,
+    /// Default value (None = required)
+    pub default: Option<String>,
+    /// Accepts any number of values (`+` one or more, `*` zero or more)
+    pub variadic: bool,
+}
+
+impl From<JustParameter> for crate::app_state::JustParameterInfo {
+    fn from(p: JustParameter) -> Self {
+        Self {
+            name: p.name,
+            default: p.default,
+            variadic: p.variadic,
+        }
+    }
 }
 
 /// Parse a justfile and extract all commands
@@ -27,6 +50,7 @@ pub fn parse_justfile(path: &str) -> Result<Vec<JustCommand>, String> {
     let mut current_name: Option<String> = None;
     let mut current_description: Option<String> = None;
     let mut current_recipe = String::new();
+    let mut current_parameters: Vec<JustParameter> = Vec::new();
     let mut in_recipe = false;
 
     for line in content.lines() {
@@ -47,21 +71,21 @@ pub fn parse_justfile(path: &str) -> Result<Vec<JustCommand>, String> {
                     name,
                     description: current_description.take(),
                     recipe: current_recipe.trim().to_string(),
+                    parameters: std::mem::take(&mut current_parameters),
                 });
                 current_recipe = String::new();
             }
 
-            // Parse new command name
-            let parts: Vec<&str> = line.splitn(2, ':').collect();
-            if !parts.is_empty() {
-                let name = parts[0].trim();
-                // Skip if it looks like a variable assignment
-                if !name.contains('=') && !name.is_empty() {
-                    current_name = Some(name.to_string());
-                    // The pending description belongs to THIS command
-                    current_description = pending_description.take();
-                    in_recipe = true;
-                }
+            // Parse new command name and parameters (skips `:=` assignments)
+            if let Some((name, parameters)) = parse_recipe_header(line) {
+                current_name = Some(name);
+                current_parameters = parameters;
+                // The pending description belongs to THIS command
+                current_description = pending_description.take();
+                in_recipe = true;
+            } else {
+                in_recipe = false;
+                pending_description = None;
             }
             continue;
         }
@@ -92,12 +116,104 @@ pub fn parse_justfile(path: &str) -> Result<Vec<JustCommand>, String> {
             name,
             description: current_description,
             recipe: current_recipe.trim().to_string(),
+            parameters: current_parameters,
         });
     }
 
     Ok(commands)
 }
 
+/// Parse a recipe header (`name param="default" +rest: deps`) into the
+/// recipe name and its parameters. Returns None for assignments and settings.
+fn parse_recipe_header(line: &str) -> Option<(String, Vec<JustParameter>)> {
+    // Find the header colon outside of quoted defaults
+    let mut quote: Option<char> = None;
+    let mut colon = None;
+    for (idx, c) in line.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' || c == '`' => quote = Some(c),
+            None if c == ':' => {
+                colon = Some(idx);
+                break;
+            }
+            None => {}
+        }
+    }
+    let colon = colon?;
+    if line[colon + 1..].starts_with('=') {
+        return None;
+    }
+
+    let tokens = split_header_tokens(&line[..colon]);
+    let (name, params) = tokens.split_first()?;
+    let name = name.trim_start_matches('@');
+    if name.is_empty() || name.contains('=') || !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+        return None;
+    }
+
+    let parameters = params
+        .iter()
+        .map(|token| {
+            let variadic = token.starts_with('+') || token.starts_with('*');
+            let token = token.trim_start_matches(['+', '*', '$']);
+            let (name, default) = match token.split_once('=') {
+                Some((name, default)) => (name, Some(unquote_default(default))),
+                None => (token, None),
+            };
+            JustParameter {
+                name: name.to_string(),
+                default,
+                variadic,
+            }
+        })
+        .collect();
+
+    Some((name.to_string(), parameters))
+}
+
+/// Split a header on whitespace, keeping quoted defaults together
+fn split_header_tokens(header: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    for c in header.chars() {
+        match quote {
+            Some(q) => {
+                current.push(c);
+                if c == q {
+                    quote = None;
+                }
+            }
+            None if c.is_whitespace() => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            None => {
+                if c == '"' || c == '\'' || c == '`' {
+                    quote = Some(c);
+                }
+                current.push(c);
+            }
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+fn unquote_default(value: &str) -> String {
+    for q in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(q) && value.ends_with(q) {
+            return value[1..value.len() - 1].to_string();
+        }
+    }
+    value.to_string()
+}
+
 /// Run a just command in a directory
 pub fn run_just_command(command: &str, cwd: &str) -> Result<String, String> {
     let cwd_path = Path::new(cwd);
@@ -150,4 +266,38 @@ test:
 
         fs::remove_file(temp_path).ok();
     }
+
+    #[test]
+    fn test_parse_recipe_parameters() {
+        let content = r#"
+version := "1.0"
+set shell := ["bash", "-c"]
+
+# Deploy to an environment
+deploy env target="localhost:8080" +flags:
+    ./deploy.sh {{env}} {{target}} {{flags}}
+
+@lint *files='src':
+    eslint {{files}}
+"#;
+        let temp = tempfile::NamedTempFile::new().unwrap();
+        fs::write(temp.path(), content).unwrap();
+
+        let commands = parse_justfile(&temp.path().to_string_lossy()).unwrap();
+        let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["deploy", "lint"]);
+        assert_eq!(commands[0].description, Some("Deploy to an environment".to_string()));
+        assert_eq!(
+            commands[0].parameters,
+            vec![
+                JustParameter { name: "env".to_string(), default: None, variadic: false },
+                JustParameter { name: "target".to_string(), default: Some("localhost:8080".to_string()), variadic: false },
+                JustParameter { name: "flags".to_string(), default: None, variadic: true },
+            ]
+        );
+        assert_eq!(
+            commands[1].parameters,
+            vec![JustParameter { name: "files".to_string(), default: Some("src".to_string()), variadic: true }]
+        );
+    }
 }
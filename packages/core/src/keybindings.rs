@@ -0,0 +1,256 @@
+//! Keyboard shortcuts.
+//!
+//! Maps command IDs (the palette IDs, e.g. "view.tasks", plus app commands
+//! like "palette.toggle") to shortcuts in Electron accelerator syntax
+//! ("CmdOrCtrl+Shift+P"). Defaults live here; the user's bindings are stored
+//! in the global settings and the frontend reads them from state.
+//!
+//! Shortcuts are normalized (modifier order, key case) so conflicts are
+//! detected regardless of how they were typed. An empty shortcut unbinds a
+//! command.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Default shortcut of every command that can be bound
+pub const DEFAULT_KEYBINDINGS: &[(&str, &str)] = &[
+    ("palette.toggle", "CmdOrCtrl+K"),
+    ("view.workflows", "CmdOrCtrl+1"),
+    ("view.tasks", "CmdOrCtrl+2"),
+    ("view.explorer", "CmdOrCtrl+3"),
+    ("view.terminal", "CmdOrCtrl+4"),
+    ("view.chat", "CmdOrCtrl+5"),
+    ("view.dockers", "CmdOrCtrl+6"),
+    ("view.env", "CmdOrCtrl+7"),
+    ("view.mcp", "CmdOrCtrl+8"),
+    ("view.settings", "CmdOrCtrl+,"),
+];
+
+/// Modifiers in normalized order
+const MODIFIERS: &[&str] = &["CmdOrCtrl", "Ctrl", "Alt", "Shift"];
+
+/// Keys allowed besides letters, digits and F1-F24
+const NAMED_KEYS: &[&str] = &[
+    "Enter", "Escape", "Tab", "Space", "Backspace", "Delete", "Up", "Down", "Left", "Right", "Home", "End",
+    "PageUp", "PageDown", ",", ".", "/", ";", "'", "[", "]", "\\", "-", "=", "`",
+];
+
+/// Command → shortcut for every known command (empty = unbound).
+///
+/// Deserializing starts from the defaults, so commands added after the
+/// settings were saved get their default shortcut.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "BTreeMap<String, String>", into = "BTreeMap<String, String>")]
+pub struct Keybindings(BTreeMap<String, String>);
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self(
+            DEFAULT_KEYBINDINGS
+                .iter()
+                .map(|(command, shortcut)| (command.to_string(), shortcut.to_string()))
+                .collect(),
+        )
+    }
+}
+
+impl From<BTreeMap<String, String>> for Keybindings {
+    fn from(saved: BTreeMap<String, String>) -> Self {
+        let mut bindings = Self::default();
+        for (command, shortcut) in saved {
+            if bindings.0.contains_key(&command) {
+                let shortcut = normalize_shortcut(&shortcut).unwrap_or_default();
+                bindings.0.insert(command, shortcut);
+            }
+        }
+        bindings
+    }
+}
+
+impl From<Keybindings> for BTreeMap<String, String> {
+    fn from(bindings: Keybindings) -> Self {
+        bindings.0
+    }
+}
+
+impl Keybindings {
+    /// Shortcut of `command` (None if unknown or unbound)
+    pub fn get(&self, command: &str) -> Option<&str> {
+        self.0.get(command).map(String::as_str).filter(|s| !s.is_empty())
+    }
+
+    /// Command bound to `shortcut`, if any
+    pub fn command_for(&self, shortcut: &str) -> Option<&str> {
+        let shortcut = normalize_shortcut(shortcut).ok().filter(|s| !s.is_empty())?;
+        self.0.iter().find(|(_, s)| **s == shortcut).map(|(c, _)| c.as_str())
+    }
+
+    /// Bind `command` to `shortcut` (empty to unbind).
+    ///
+    /// Fails for unknown commands, invalid shortcuts and shortcuts already
+    /// bound to another command.
+    pub fn set(&mut self, command: &str, shortcut: &str) -> Result<(), String> {
+        if !self.0.contains_key(command) {
+            return Err(format!("Unknown command '{}'", command));
+        }
+        let shortcut = normalize_shortcut(shortcut)?;
+        if let Some(other) = self.command_for(&shortcut).filter(|c| *c != command) {
+            return Err(format!("{} is already bound to '{}'", shortcut, other));
+        }
+        self.0.insert(command.to_string(), shortcut);
+        Ok(())
+    }
+
+    /// Restore the default of `command`, or of every command if None
+    pub fn reset(&mut self, command: Option<&str>) {
+        let Some(command) = command else {
+            *self = Self::default();
+            return;
+        };
+        let Some((_, default)) = DEFAULT_KEYBINDINGS.iter().find(|(c, _)| *c == command) else {
+            return;
+        };
+        // The default may be taken by another command by now: unbind that one
+        let taken_by = self.command_for(default).filter(|c| *c != command).map(str::to_string);
+        if let Some(other) = taken_by {
+            self.0.insert(other, String::new());
+        }
+        self.0.insert(command.to_string(), default.to_string());
+    }
+}
+
+/// Normalize a shortcut ("shift+cmdorctrl+p" → "CmdOrCtrl+Shift+P").
+///
+/// A shortcut is zero or more modifiers and one key. Keys without a
+/// modifier are only allowed for function keys. Empty input stays empty.
+pub fn normalize_shortcut(shortcut: &str) -> Result<String, String> {
+    let shortcut = shortcut.trim();
+    if shortcut.is_empty() {
+        return Ok(String::new());
+    }
+    // "CmdOrCtrl++" binds the plus key
+    let (modifiers, key) = match shortcut.strip_suffix("++") {
+        Some(rest) => (rest, "+"),
+        None => shortcut.rsplit_once('+').unwrap_or(("", shortcut)),
+    };
+
+    let mut found = Vec::new();
+    for modifier in modifiers.split('+').map(str::trim).filter(|m| !m.is_empty()) {
+        let normalized = match modifier.to_lowercase().as_str() {
+            "cmdorctrl" | "commandorcontrol" | "mod" => "CmdOrCtrl",
+            "ctrl" | "control" => "Ctrl",
+            "alt" | "option" => "Alt",
+            "shift" => "Shift",
+            _ => return Err(format!("Unknown modifier '{}' in {}", modifier, shortcut)),
+        };
+        if !found.contains(&normalized) {
+            found.push(normalized);
+        }
+    }
+
+    let key = normalize_key(key.trim()).ok_or_else(|| format!("Unsupported key '{}' in {}", key.trim(), shortcut))?;
+    let is_function_key = key.len() > 1 && key.starts_with('F') && key[1..].parse::<u8>().is_ok();
+    if found.is_empty() && !is_function_key {
+        return Err(format!("{} needs a modifier (CmdOrCtrl, Ctrl, Alt or Shift)", shortcut));
+    }
+
+    let mut parts: Vec<&str> = MODIFIERS.iter().copied().filter(|m| found.contains(m)).collect();
+    parts.push(&key);
+    Ok(parts.join("+"))
+}
+
+fn normalize_key(key: &str) -> Option<String> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.clone().next()) {
+        if c.is_ascii_alphanumeric() {
+            return Some(c.to_ascii_uppercase().to_string());
+        }
+    }
+    if let Some(n) = key.strip_prefix(['F', 'f']).and_then(|n| n.parse::<u8>().ok()) {
+        if (1..=24).contains(&n) {
+            return Some(format!("F{}", n));
+        }
+    }
+    if key == "+" {
+        return Some("+".to_string());
+    }
+    let key = match key.to_lowercase().as_str() {
+        "esc" => "Escape".to_string(),
+        "return" => "Enter".to_string(),
+        "arrowup" => "Up".to_string(),
+        "arrowdown" => "Down".to_string(),
+        "arrowleft" => "Left".to_string(),
+        "arrowright" => "Right".to_string(),
+        _ => key.to_string(),
+    };
+    NAMED_KEYS.iter().find(|k| k.eq_ignore_ascii_case(&key)).map(|k| k.to_string())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_shortcut() {
+        assert_eq!(normalize_shortcut("shift+cmdorctrl+p").unwrap(), "CmdOrCtrl+Shift+P");
+        assert_eq!(normalize_shortcut("Ctrl+Alt+arrowup").unwrap(), "Ctrl+Alt+Up");
+        assert_eq!(normalize_shortcut("CmdOrCtrl+,").unwrap(), "CmdOrCtrl+,");
+        assert_eq!(normalize_shortcut("CmdOrCtrl++").unwrap(), "CmdOrCtrl++");
+        assert_eq!(normalize_shortcut("f5").unwrap(), "F5");
+        assert_eq!(normalize_shortcut("  ").unwrap(), "");
+        assert!(normalize_shortcut("K").is_err());
+        assert!(normalize_shortcut("Hyper+K").is_err());
+        assert!(normalize_shortcut("CmdOrCtrl+Banana").is_err());
+    }
+
+    #[test]
+    fn test_set_detects_conflicts() {
+        let mut bindings = Keybindings::default();
+        assert_eq!(bindings.get("view.tasks"), Some("CmdOrCtrl+2"));
+
+        let err = bindings.set("view.chat", "cmdorctrl+2").unwrap_err();
+        assert!(err.contains("view.tasks"), "{}", err);
+        assert!(bindings.set("view.nope", "CmdOrCtrl+9").is_err());
+
+        // Rebinding a command to its own shortcut is fine
+        bindings.set("view.tasks", "CmdOrCtrl+2").unwrap();
+        bindings.set("view.chat", "alt+c").unwrap();
+        assert_eq!(bindings.command_for("Alt+C"), Some("view.chat"));
+
+        // Unbind
+        bindings.set("view.tasks", "").unwrap();
+        assert_eq!(bindings.get("view.tasks"), None);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut bindings = Keybindings::default();
+        bindings.set("view.tasks", "").unwrap();
+        bindings.set("view.chat", "CmdOrCtrl+2").unwrap();
+
+        // Taking back the default unbinds the command that took it
+        bindings.reset(Some("view.tasks"));
+        assert_eq!(bindings.get("view.tasks"), Some("CmdOrCtrl+2"));
+        assert_eq!(bindings.get("view.chat"), None);
+
+        bindings.reset(None);
+        assert_eq!(bindings, Keybindings::default());
+    }
+
+    #[test]
+    fn test_deserialize_merges_defaults() {
+        let bindings: Keybindings =
+            serde_json::from_str(r#"{"view.tasks": "alt+t", "view.removed": "Alt+R"}"#).unwrap();
+        assert_eq!(bindings.get("view.tasks"), Some("Alt+T"));
+        assert_eq!(bindings.get("palette.toggle"), Some("CmdOrCtrl+K"));
+        assert_eq!(bindings.command_for("Alt+R"), None);
+
+        let json = serde_json::to_value(&bindings).unwrap();
+        assert_eq!(json["view.tasks"], "Alt+T");
+    }
+}
@@ -6,34 +6,96 @@
 extern crate napi_derive;
 
 pub mod actions;
+pub mod activity;
+pub mod agent_runs;
 pub mod agent_rules;
 pub mod app_state;
 pub mod archive;
+pub mod audit;
+pub mod build_diagnostics;
+pub mod change_bundle;
+pub mod change_status;
+pub mod chat_attachments;
+pub mod checklist;
+pub mod clarify;
 pub mod claude_cli;
+pub mod compliance;
 pub mod constitution;
 pub mod context;
 pub mod db;
+pub mod desktop_notifications;
+pub mod diagnostics;
 pub mod explorer;
 pub mod context_engine;
 pub mod context_generate;
 pub mod context_sync;
 pub mod docker;
+pub mod doctor;
+pub mod docker_compose;
+pub mod edits;
 pub mod env;
+pub mod env_secrets;
+pub mod feature_catalog;
+pub mod file_preview;
 pub mod file_reader;
+pub mod git;
+pub mod git_hooks;
+pub mod github;
+pub mod guardrails;
+pub mod hooks;
+pub mod http_client;
+pub mod implementation;
+pub mod journal;
 pub mod justfile;
+pub mod keybindings;
+pub mod llm;
+pub mod logging;
+pub mod lsp;
+pub mod markdown;
+pub mod mcp_client;
 pub mod mcp_config;
+pub mod mcp_policy;
+pub mod mcp_registry;
 pub mod mcp_server;
 pub mod migration;
+pub mod net_inspect;
+pub mod palette;
 pub mod persistence;
+pub mod prompt_library;
+pub mod proxy;
+pub mod pull_request;
 pub mod reducer;
+pub mod review_comments;
+pub mod schedule;
+pub mod service_groups;
+pub mod service_templates;
+pub mod session_export;
+pub mod sessions;
+pub mod settings_profiles;
+pub mod snapshot;
+pub mod spec_analysis;
+pub mod spec_kit;
+pub mod spec_tasks;
 pub mod state;
+#[cfg(feature = "state-bridge")]
+pub mod state_bridge;
+pub mod symbols;
+pub mod system;
+pub mod task_queue;
+pub mod tasks;
 pub mod terminal;
+pub mod test_runner;
+pub mod trace;
+pub mod undo;
+pub mod usage;
+pub mod watcher;
 pub mod worktree;
 
 use actions::Action;
-use app_state::AppState;
+use app_state::{AppState, DesktopNotificationEvent};
 use docker::DockerManager;
 use mcp_server::McpServerManager;
+#[cfg(not(test))]
 use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode, ThreadSafeCallContext};
 use reducer::reduce;
 use state::DockerService;
@@ -47,15 +109,64 @@ static DOCKER_MANAGER: OnceCell<Arc<DockerManager>> = OnceCell::const_new();
 // Global MCP server manager instance (sync init, doesn't need tokio::OnceCell)
 static MCP_SERVER_MANAGER: OnceLock<Arc<McpServerManager>> = OnceLock::new();
 
+// Connections to external MCP servers (from each worktree's .mcp.json)
+static MCP_CLIENTS: OnceLock<mcp_client::McpClientManager> = OnceLock::new();
+
+// Language servers for the file viewer (started on first use per worktree)
+static LSP_MANAGER: OnceLock<lsp::LspManager> = OnceLock::new();
+
+// Global terminal manager instance (PTY sessions per worktree)
+static TERMINAL_MANAGER: OnceLock<Arc<terminal::TerminalManager>> = OnceLock::new();
+
+// Global file indexer (one watched index per worktree, for context ranking)
+static FILE_INDEXER: OnceLock<context_engine::index::FileIndexer> = OnceLock::new();
+
+// Global worktree watchers (auto-refresh after external edits)
+static WORKTREE_WATCHER: OnceLock<watcher::WorktreeWatcher> = OnceLock::new();
+
+// Sender feeding worktree watch events to the auto-refresh task
+static WATCH_EVENTS: OnceLock<tokio::sync::mpsc::UnboundedSender<watcher::WatchEvent>> = OnceLock::new();
+
+// Global registry of running Claude CLI processes (for cancellation)
+static CLAUDE_PROCESSES: OnceLock<claude_cli::ClaudeProcessRegistry> = OnceLock::new();
+static PERMISSION_BRIDGE: OnceLock<claude_cli::PermissionBridge> = OnceLock::new();
+static LLM_REQUESTS: OnceLock<llm::LlmRequestRegistry> = OnceLock::new();
+
+// Background task queue (just recipes and provider tasks)
+static TASK_QUEUE: OnceLock<task_queue::TaskQueue> = OnceLock::new();
+
+// Started once the first project schedule is loaded
+static SCHEDULER_STARTED: std::sync::Once = std::sync::Once::new();
+
+// Running `docker_stats_stream` tasks, keyed by service ID
+type DockerStatsTasks = std::collections::HashMap<String, tokio::task::JoinHandle<()>>;
+static DOCKER_STATS_TASKS: OnceLock<std::sync::Mutex<DockerStatsTasks>> = OnceLock::new();
+
 // Global application state
 static APP_STATE: OnceCell<Arc<RwLock<AppState>>> = OnceCell::const_new();
 
 // Global DB manager
 static DB_MANAGER: OnceCell<Arc<db::DbManager>> = OnceCell::const_new();
 
+// Action journal for crash recovery (started by state_init)
+static JOURNAL: OnceLock<journal::Journal> = OnceLock::new();
+
+// WebSocket state bridge for external tools (started by state_bridge_start)
+#[cfg(feature = "state-bridge")]
+static STATE_BRIDGE: std::sync::Mutex<Option<state_bridge::StateBridge>> = std::sync::Mutex::new(None);
+
+/// Local reverse proxy (while running)
+static PROXY_SERVER: std::sync::Mutex<Option<proxy::ProxyServer>> = std::sync::Mutex::new(None);
+
 // State update listener (callback to JavaScript)
+#[cfg(not(test))]
 static STATE_LISTENER: OnceCell<ThreadsafeFunction<String>> = OnceCell::const_new();
 
+// OS notification listener (callback to JavaScript)
+#[cfg(not(test))]
+static DESKTOP_NOTIFICATION_LISTENER: OnceCell<ThreadsafeFunction<desktop_notifications::DesktopNotification>> =
+    OnceCell::const_new();
+
 fn get_app_state() -> &'static Arc<RwLock<AppState>> {
     APP_STATE.get().expect("AppState not initialized. Call state_init first.")
 }
@@ -73,6 +184,58 @@ async fn notify_state_update() {
             listener.call(Ok(json), ThreadsafeFunctionCallMode::NonBlocking);
         }
     }
+
+    #[cfg(feature = "state-bridge")]
+    if STATE_BRIDGE.lock().unwrap_or_else(|e| e.into_inner()).is_some() {
+        let state = serde_json::to_value(&*get_app_state().read().await).unwrap_or_default();
+        if let Some(bridge) = STATE_BRIDGE.lock().unwrap_or_else(|e| e.into_inner()).as_ref() {
+            bridge.publish(state);
+        }
+    }
+}
+
+/// Write a change's settled status to its status.json (shared with
+/// teammates through git) if it moved since the last sync. Called by the
+/// handlers that change a status; changes with an unresolved status
+/// conflict are left alone.
+async fn save_change_status(change_id: &str) {
+    let target = {
+        let state = get_app_state().read().await;
+        state
+            .projects
+            .iter()
+            .flat_map(|p| p.worktrees.iter())
+            .find_map(|w| {
+                let change = w.changes.changes.iter().find(|c| c.id == change_id)?;
+                Some((w.path.clone(), change.name.clone(), change.status, change.status_conflict.is_none()))
+            })
+    };
+    let Some((wt_path, change_name, status, true)) = target else {
+        return;
+    };
+    let result =
+        tokio::task::spawn_blocking(move || change_status::save(std::path::Path::new(&wt_path), &change_name, status)).await;
+    match result {
+        Ok(Err(e)) => tracing::warn!("{}", e),
+        Err(e) => tracing::warn!("Status file task failed: {}", e),
+        Ok(Ok(())) => {}
+    }
+}
+
+/// Show an OS notification, unless the user turned its event off
+#[cfg_attr(test, allow(unused_variables))]
+async fn notify_desktop(event: DesktopNotificationEvent, notification: desktop_notifications::DesktopNotification) {
+    let enabled = get_app_state()
+        .read()
+        .await
+        .global_settings
+        .desktop_notifications
+        .is_enabled(event);
+
+    #[cfg(not(test))]
+    if let Some(listener) = DESKTOP_NOTIFICATION_LISTENER.get().filter(|_| enabled) {
+        listener.call(Ok(notification), ThreadsafeFunctionCallMode::NonBlocking);
+    }
 }
 
 async fn get_docker_manager() -> napi::Result<&'static Arc<DockerManager>> {
@@ -86,7 +249,494 @@ async fn get_docker_manager() -> napi::Result<&'static Arc<DockerManager>> {
 }
 
 fn get_mcp_server_manager() -> &'static Arc<McpServerManager> {
-    MCP_SERVER_MANAGER.get_or_init(|| Arc::new(McpServerManager::new()))
+    MCP_SERVER_MANAGER.get_or_init(|| {
+        Arc::new(McpServerManager::with_port_registry(
+            mcp_registry::McpPortRegistry::new(mcp_registry::McpPortRegistry::default_path()),
+        ))
+    })
+}
+
+fn get_mcp_clients() -> &'static mcp_client::McpClientManager {
+    MCP_CLIENTS.get_or_init(Default::default)
+}
+
+fn get_lsp_manager() -> &'static lsp::LspManager {
+    LSP_MANAGER.get_or_init(Default::default)
+}
+
+fn get_terminal_manager() -> &'static Arc<terminal::TerminalManager> {
+    TERMINAL_MANAGER.get_or_init(|| Arc::new(terminal::TerminalManager::new()))
+}
+
+fn get_file_indexer() -> &'static context_engine::index::FileIndexer {
+    FILE_INDEXER.get_or_init(context_engine::index::FileIndexer::new)
+}
+
+fn get_docker_stats_tasks() -> std::sync::MutexGuard<'static, DockerStatsTasks> {
+    DOCKER_STATS_TASKS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+fn get_claude_processes() -> &'static claude_cli::ClaudeProcessRegistry {
+    CLAUDE_PROCESSES.get_or_init(claude_cli::ClaudeProcessRegistry::new)
+}
+
+fn get_permission_bridge() -> &'static claude_cli::PermissionBridge {
+    PERMISSION_BRIDGE.get_or_init(claude_cli::PermissionBridge::new)
+}
+
+fn get_llm_requests() -> &'static llm::LlmRequestRegistry {
+    LLM_REQUESTS.get_or_init(llm::LlmRequestRegistry::new)
+}
+
+fn get_worktree_watcher() -> &'static watcher::WorktreeWatcher {
+    WORKTREE_WATCHER.get_or_init(watcher::WorktreeWatcher::new)
+}
+
+/// Sender for watch events; the first call starts the auto-refresh task
+fn get_watch_events() -> &'static tokio::sync::mpsc::UnboundedSender<watcher::WatchEvent> {
+    WATCH_EVENTS.get_or_init(|| {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(apply_watch_events(rx));
+        tx
+    })
+}
+
+fn get_task_queue() -> &'static task_queue::TaskQueue {
+    TASK_QUEUE.get_or_init(task_queue::TaskQueue::new)
+}
+
+/// Process registry key for a change's proposal generation
+fn proposal_process_key(change_id: &str) -> String {
+    format!("proposal-{}", change_id)
+}
+
+/// Process registry key for a change's implementation run
+fn implementation_process_key(change_id: &str) -> String {
+    format!("implementation-{}", change_id)
+}
+
+/// Process registry key for an agent run's Claude CLI
+fn agent_run_process_key(run_id: &str) -> String {
+    format!("agent-run-{}", run_id)
+}
+
+/// Process registry key for constitution generation in a worktree
+fn constitution_process_key(worktree_path: &std::path::Path) -> String {
+    format!("constitution-{}", worktree_path.display())
+}
+
+/// Process registry key for the spec-kit phase running in a worktree
+fn spec_process_key(worktree_path: &std::path::Path) -> String {
+    format!("spec-{}", worktree_path.display())
+}
+
+/// Claude model for CLI invocations (active project override or global setting)
+async fn active_claude_model() -> Option<String> {
+    get_app_state().read().await.claude_model()
+}
+
+/// Run a one-shot Claude prompt in `cwd` and return its full text answer.
+/// Uses the HTTP provider instead when one is selected in Settings.
+async fn run_claude_to_text(prompt: &str, cwd: &std::path::Path, source: &str) -> Result<String, String> {
+    let provider = {
+        let state = get_app_state().read().await;
+        match state.global_settings.provider {
+            app_state::LlmProviderKind::ClaudeCli => None,
+            kind => Some(llm::http_provider(kind, &state.global_settings)),
+        }
+    };
+    if let Some(provider) = provider {
+        return llm::complete(provider?.as_ref(), prompt).await;
+    }
+
+    let mut session = start_claude_session(source, cwd, prompt).await;
+    let mut child = claude_cli::spawn_claude(prompt, cwd, None, None, None, active_claude_model().await.as_deref())
+        .map_err(|e| {
+            session.fail(e.to_string());
+            e.to_string()
+        })?;
+    let mut stream = claude_cli::ClaudeEventStream::new(&mut child).map_err(|e| e.to_string())?;
+    let start_time = std::time::Instant::now();
+    let mut deltas = String::new();
+    let mut assistant = String::new();
+
+    loop {
+        if start_time.elapsed() > claude_cli::TOTAL_TIMEOUT {
+            let _ = child.kill().await;
+            return Err("Claude timed out".to_string());
+        }
+        match tokio::time::timeout(claude_cli::EVENT_TIMEOUT, stream.next_event()).await {
+            Ok(Some(Ok(event))) => {
+                session.observe(&event);
+                record_claude_usage(&event, source).await;
+                if let Some(text_chunk) = claude_cli::extract_text_delta(&event) {
+                    deltas.push_str(text_chunk);
+                }
+                if let Some(text_content) = claude_cli::extract_assistant_text(&event) {
+                    assistant.push_str(&text_content);
+                }
+            }
+            Ok(Some(Err(e))) => return Err(e.to_string()),
+            Ok(None) => break,
+            Err(_) => {
+                let _ = child.kill().await;
+                return Err("Claude stopped responding".to_string());
+            }
+        }
+    }
+
+    let output = if assistant.is_empty() { deltas } else { assistant };
+    if output.trim().is_empty() {
+        Err("Claude returned no output".to_string())
+    } else {
+        Ok(output)
+    }
+}
+
+/// Stream a chat reply from an HTTP provider into the assistant placeholder `msg_id`
+async fn run_http_chat(kind: app_state::LlmProviderKind, msg_id: String, messages: Vec<llm::LlmMessage>) {
+    let provider = {
+        let state = get_app_state().read().await;
+        llm::http_provider(kind, &state.global_settings)
+    };
+
+    let result = match provider {
+        Ok(provider) => {
+            // Deltas are applied in order by a single forwarding task
+            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+            let forward = tokio::spawn(async move {
+                while let Some(content) = rx.recv().await {
+                    {
+                        let mut state = get_app_state().write().await;
+                        reduce(&mut state, Action::AppendChatContent { content });
+                    }
+                    notify_state_update().await;
+                }
+            });
+
+            let request = tokio::spawn(async move { provider.stream_chat(&messages, Some(&tx)).await });
+            get_llm_requests().register(&msg_id, request.abort_handle());
+            let result = request.await;
+            get_llm_requests().finish(&msg_id);
+            let _ = forward.await;
+
+            match result {
+                Ok(result) => result.map(|_| ()),
+                // Cancelled via CancelChatMessage - state already updated
+                Err(e) if e.is_cancelled() => Ok(()),
+                Err(e) => Err(e.to_string()),
+            }
+        }
+        Err(e) => Err(e),
+    };
+
+    {
+        let mut state = get_app_state().write().await;
+        if let Err(error) = result {
+            reduce(&mut state, Action::SetChatError { error });
+        }
+        reduce(&mut state, Action::SetChatTyping { is_typing: false });
+        // Suggested file changes become a reviewable edit set
+        reduce(&mut state, Action::ProposeEdits { message_id: msg_id });
+    }
+    notify_state_update().await;
+}
+
+/// Active ReviewGate session of the active worktree
+fn active_review_session_id(state: &AppState) -> Option<String> {
+    state
+        .active_project()
+        .and_then(|p| p.active_worktree())
+        .and_then(|w| w.tasks.review_gate.active_session_id.clone())
+}
+
+/// Mark a review session's compliance check as running and evaluate it in the background
+async fn start_compliance_check(session_id: String) {
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::RunComplianceCheck { session_id: session_id.clone() });
+    }
+    notify_state_update().await;
+    tokio::spawn(run_compliance_check(session_id));
+}
+
+/// Evaluate a review session's content against the constitution and store the result
+async fn run_compliance_check(session_id: String) {
+    let session = {
+        let state = get_app_state().read().await;
+        state.active_project().and_then(|p| p.active_worktree()).and_then(|w| {
+            w.tasks
+                .review_gate
+                .sessions
+                .get(&session_id)
+                .map(|s| (w.path.clone(), s.content.clone(), s.iteration))
+        })
+    };
+    let Some((wt_path, content, iteration)) = session else {
+        return;
+    };
+
+    let cwd = std::path::Path::new(&wt_path);
+    let check = match constitution::read_constitution(cwd) {
+        None => app_state::ComplianceCheck::failed_to_run(
+            iteration,
+            app_state::ComplianceStatus::Skipped,
+            "No constitution to check against",
+        ),
+        Some(constitution_text) => {
+            let prompt = compliance::build_prompt(&constitution_text, &content);
+            match run_claude_to_text(&prompt, cwd, "compliance")
+                .await
+                .and_then(|output| compliance::parse_report(&output))
+            {
+                Ok(report) => app_state::ComplianceCheck::completed(iteration, report),
+                Err(e) => app_state::ComplianceCheck::failed_to_run(iteration, app_state::ComplianceStatus::Error, e),
+            }
+        }
+    };
+
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::SetComplianceCheck { session_id, check });
+    }
+    notify_state_update().await;
+}
+
+/// Record token usage / cost from a Claude CLI `result` event against the
+/// active project (no-op for other events)
+async fn record_claude_usage(event: &claude_cli::ClaudeStreamEvent, source: &str) {
+    let Some(usage) = claude_cli::extract_usage(event) else {
+        return;
+    };
+    let (project_path, model) = {
+        let state = get_app_state().read().await;
+        (state.active_project().map(|p| p.path.clone()), state.claude_model())
+    };
+    let Some(project_path) = project_path else {
+        return;
+    };
+
+    let cost_usd = usage.cost_usd.unwrap_or_else(|| {
+        usage::estimate_cost_usd(
+            model.as_deref(),
+            usage.input_tokens,
+            usage.output_tokens,
+            usage.cache_read_tokens,
+            usage.cache_creation_tokens,
+        )
+    });
+    let record = usage::UsageRecord {
+        project_id: persistence::get_project_id(&project_path),
+        session_id: usage.session_id,
+        source: source.to_string(),
+        model,
+        input_tokens: usage.input_tokens,
+        output_tokens: usage.output_tokens,
+        cache_read_tokens: usage.cache_read_tokens,
+        cache_creation_tokens: usage.cache_creation_tokens,
+        cost_usd,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+
+    if let Some(db) = get_db_manager() {
+        if let Err(e) = db.add_usage_record(&record) {
+            tracing::warn!("Failed to persist usage record: {}", e);
+        }
+    }
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::AddUsageRecord { record });
+    }
+    notify_state_update().await;
+}
+
+/// Record a significant event in the active project's activity timeline
+async fn record_activity(kind: activity::ActivityKind, summary: String, detail: serde_json::Value) {
+    let project_path = {
+        let state = get_app_state().read().await;
+        state.active_project().map(|p| p.path.clone())
+    };
+    let (Some(project_path), Some(db)) = (project_path, get_db_manager()) else {
+        return;
+    };
+
+    let project_id = persistence::get_project_id(&project_path);
+    let id = match db.add_log(&project_id, kind.as_str(), "info", &summary, Some(&detail.to_string())) {
+        Ok(id) => id,
+        Err(e) => {
+            tracing::warn!("Failed to record activity: {}", e);
+            return;
+        }
+    };
+    let event = activity::ActivityEvent {
+        id,
+        project_id,
+        kind,
+        summary,
+        detail: Some(detail),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::AddActivity { event });
+    }
+    notify_state_update().await;
+}
+
+/// Start recording a Claude CLI invocation for the Session History. The
+/// session is stored when the returned recorder is dropped.
+async fn start_claude_session(kind: &str, cwd: &std::path::Path, prompt: &str) -> sessions::SessionRecorder {
+    let (project_path, model) = {
+        let state = get_app_state().read().await;
+        (state.active_project().map(|p| p.path.clone()), state.claude_model())
+    };
+    let project_id = project_path.map(|path| persistence::get_project_id(&path)).unwrap_or_default();
+
+    sessions::SessionRecorder::start(
+        kind,
+        project_id,
+        cwd,
+        model,
+        prompt,
+        Box::new(|session| {
+            if let Some(db) = get_db_manager() {
+                if let Err(e) = db.add_session(&session) {
+                    tracing::warn!("Failed to persist Claude session: {}", e);
+                }
+            }
+            napi::bindgen_prelude::spawn(async move {
+                {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::AddSession { session });
+                }
+                notify_state_update().await;
+            });
+        }),
+    )
+}
+
+/// Kill PTY sessions (and drop file indexes and language servers) whose
+/// worktree is no longer open in any project
+async fn cleanup_orphaned_terminals() {
+    let live_worktrees: Vec<String> = {
+        let state = get_app_state().read().await;
+        state
+            .projects
+            .iter()
+            .flat_map(|p| p.worktrees.iter().map(|w| w.path.clone()))
+            .collect()
+    };
+    let killed = get_terminal_manager().kill_orphaned_sessions(&live_worktrees).await;
+    if killed > 0 {
+        tracing::info!("Killed {} orphaned terminal session(s)", killed);
+    }
+    get_file_indexer().retain(&live_worktrees);
+    get_worktree_watcher().retain(&live_worktrees);
+    get_lsp_manager().retain(&live_worktrees).await;
+}
+
+/// Quiet period before applying watch events (editors and git write in bursts)
+const WATCH_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(300);
+
+/// Watch every open worktree (new worktrees start watching, closed ones stop)
+async fn sync_worktree_watchers() {
+    let live_worktrees: Vec<String> = {
+        let state = get_app_state().read().await;
+        state
+            .projects
+            .iter()
+            .flat_map(|p| p.worktrees.iter().map(|w| w.path.clone()))
+            .collect()
+    };
+    let watcher = get_worktree_watcher();
+    for path in &live_worktrees {
+        let path = std::path::Path::new(path);
+        if path.is_dir() && !watcher.is_watching(path) {
+            watcher.watch(path, get_watch_events().clone());
+        }
+    }
+    watcher.retain(&live_worktrees);
+}
+
+/// Dispatch the refresh actions for files changed in the active worktree.
+/// Other worktrees are refreshed when they become active.
+async fn apply_watch_events(mut events: tokio::sync::mpsc::UnboundedReceiver<watcher::WatchEvent>) {
+    use watcher::WatchTarget;
+
+    while let Some(first) = events.recv().await {
+        tokio::time::sleep(WATCH_DEBOUNCE).await;
+        let mut batch = vec![first];
+        while let Ok(event) = events.try_recv() {
+            batch.push(event);
+        }
+        for event in &batch {
+            symbols::shared_cache().invalidate(&std::path::Path::new(&event.worktree_path).join(&event.relative_path));
+        }
+
+        let (active_worktree, patterns, has_constitution_content, has_constitution_lint, check_build) = {
+            let state = get_app_state().read().await;
+            let project = state.active_project();
+            let worktree = project.and_then(|p| p.active_worktree());
+            (
+                worktree.map(|w| w.path.clone()),
+                project.map(|p| p.env_config.tracked_patterns.clone()).unwrap_or_default(),
+                worktree.is_some_and(|w| w.tasks.constitution_content.is_some()),
+                worktree.is_some_and(|w| w.tasks.constitution_lint.is_some()),
+                project.is_some_and(|p| p.check_build_on_save) && worktree.is_some_and(|w| !w.is_checking_build),
+            )
+        };
+        let Some(active_worktree) = active_worktree else {
+            continue;
+        };
+        let targets: std::collections::BTreeSet<WatchTarget> = batch
+            .iter()
+            .filter(|e| e.worktree_path == active_worktree)
+            .filter_map(|e| watcher::classify(&e.relative_path, &patterns))
+            .collect();
+        if targets.is_empty() {
+            continue;
+        }
+
+        let mut actions = Vec::new();
+        for target in targets {
+            match target {
+                WatchTarget::Changes => actions.push(Action::RefreshChanges),
+                WatchTarget::Tasks => actions.push(Action::RefreshJustfile),
+                WatchTarget::DockerCompose => actions.push(Action::RefreshDockerServices),
+                WatchTarget::EnvFiles => actions.push(Action::ScanEnvSecrets),
+                WatchTarget::Constitution => {
+                    actions.push(Action::CheckConstitutionExists);
+                    if has_constitution_content {
+                        actions.push(Action::ReadConstitution);
+                    }
+                    if has_constitution_lint {
+                        actions.push(Action::LintConstitution);
+                    }
+                }
+                WatchTarget::Schedule => actions.push(Action::LoadSchedule),
+                WatchTarget::HttpRequests => actions.push(Action::LoadHttpRequests),
+                WatchTarget::Sources => {
+                    if check_build {
+                        actions.push(Action::RunBuildCheck);
+                    }
+                }
+            }
+        }
+        for action in actions {
+            tracing::debug!("Auto-refresh: {:?}", action);
+            {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, action.clone());
+            }
+            if let Err(e) = Box::pin(handle_async_action(action)).await {
+                tracing::warn!("Auto-refresh failed: {}", e);
+            }
+        }
+        notify_state_update().await;
+    }
 }
 
 /// Read context files and format them for Claude prompt injection
@@ -126,7 +776,7 @@ fn build_context_files_section(paths: &[String], project_root: &str) -> String {
                 sections.push(format!("### {}\n```{}\n{}\n```", path, lang, content));
             }
             Err(e) => {
-                eprintln!("Warning: Failed to read context file '{}': {}", path, e);
+                tracing::warn!("Failed to read context file '{}': {}", path, e);
             }
         }
     }
@@ -221,6 +871,41 @@ pub async fn docker_create_vhost(service_id: String, vhost_name: String) -> napi
         .map_err(napi::Error::from_reason)
 }
 
+/// List databases in a database container (PostgreSQL, MySQL, MongoDB)
+#[napi]
+pub async fn docker_list_databases(service_id: String) -> napi::Result<Vec<state::DockerDatabase>> {
+    let dm = get_docker_manager().await?;
+    let databases = dm
+        .list_databases(&service_id)
+        .await
+        .map_err(napi::Error::from_reason)?;
+    Ok(databases
+        .into_iter()
+        .map(|db| state::DockerDatabase {
+            name: db.name,
+            size: db.size_bytes.map(|size| size as i64),
+        })
+        .collect())
+}
+
+/// List tables (MongoDB: collections) in a database of a database container
+#[napi]
+pub async fn docker_list_tables(service_id: String, db_name: String) -> napi::Result<Vec<state::DockerTable>> {
+    let dm = get_docker_manager().await?;
+    let tables = dm
+        .list_tables(&service_id, &db_name)
+        .await
+        .map_err(napi::Error::from_reason)?;
+    Ok(tables
+        .into_iter()
+        .map(|table| state::DockerTable {
+            schema: table.schema,
+            name: table.name,
+            row_count: table.row_count.map(|count| count as i64),
+        })
+        .collect())
+}
+
 /// Start a Docker service with a specific port override
 #[napi]
 pub async fn docker_start_service_with_port(service_id: String, port: u16) -> napi::Result<()> {
@@ -248,30 +933,189 @@ pub async fn docker_check_port_conflict(service_id: String) -> napi::Result<Opti
         .map_err(napi::Error::from_reason)
 }
 
-// ============================================================================
-// Justfile functions
-// ============================================================================
-
-/// Parse a justfile and return all commands
+/// Identify host processes (outside Docker) listening on a port
 #[napi]
-pub fn justfile_parse(path: String) -> napi::Result<Vec<justfile::JustCommand>> {
-    justfile::parse_justfile(&path)
-        .map_err(napi::Error::from_reason)
+pub async fn net_inspect_port(port: u32) -> napi::Result<Vec<state::PortProcessInfo>> {
+    let port = u16::try_from(port).map_err(|_| napi::Error::from_reason(format!("Invalid port: {}", port)))?;
+    tokio::task::spawn_blocking(move || net_inspect::inspect_port(port))
+        .await
+        .map_err(|e| napi::Error::from_reason(e.to_string()))
 }
 
-/// Run a just command in a directory
+/// Import a docker-compose.yml and register its services as rstn-managed.
+/// The file is remembered with the active project and re-imported when the
+/// project is opened again.
+/// Returns the services of the imported project
 #[napi]
-pub fn justfile_run(command: String, cwd: String) -> napi::Result<String> {
-    justfile::run_just_command(&command, &cwd)
-        .map_err(napi::Error::from_reason)
+pub async fn docker_import_compose(path: String) -> napi::Result<Vec<DockerService>> {
+    let dm = get_docker_manager().await?;
+    let services = dm
+        .import_compose(std::path::Path::new(&path))
+        .await
+        .map_err(napi::Error::from_reason)?;
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::AddComposeFile { path });
+    }
+    notify_state_update().await;
+    Ok(services)
 }
 
-// ============================================================================
-// File Reader functions
-// ============================================================================
+/// Pull a Docker image. Layer progress is streamed to the state listener
+/// (`docker.image_pulls`) until the pull finishes.
+#[napi]
+pub async fn docker_pull_image(image: String) -> napi::Result<()> {
+    use futures_util::StreamExt;
 
-/// Read a file from allowed scopes (project root or ~/.rstn/).
-///
+    let dm = get_docker_manager().await?;
+    let mut stream = Box::pin(dm.pull_image(&image));
+    let mut last_notify = std::time::Instant::now();
+    let mut result = Ok(());
+
+    while let Some(event) = stream.next().await {
+        match event {
+            Ok(progress) => {
+                {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetImagePullProgress { image: image.clone(), progress });
+                }
+                // Downloading layers report many times a second; throttle UI updates
+                if last_notify.elapsed() >= std::time::Duration::from_millis(100) {
+                    last_notify = std::time::Instant::now();
+                    notify_state_update().await;
+                }
+            }
+            Err(e) => {
+                result = Err(e);
+                break;
+            }
+        }
+    }
+
+    {
+        let mut state = get_app_state().write().await;
+        reduce(
+            &mut state,
+            Action::FinishImagePull {
+                image: image.clone(),
+                error: result.as_ref().err().cloned(),
+            },
+        );
+    }
+    notify_state_update().await;
+
+    result.map_err(napi::Error::from_reason)
+}
+
+/// Start streaming resource stats for a running service. Samples are pushed
+/// to the state listener (`docker.stats`) until the container stops or
+/// `docker_stats_stop` is called. Restarts an existing stream for the service.
+#[napi]
+pub async fn docker_stats_stream(service_id: String) -> napi::Result<()> {
+    let dm = get_docker_manager().await?;
+
+    let id = service_id.clone();
+    let handle = tokio::spawn(async move {
+        use futures_util::StreamExt;
+
+        let mut stream = Box::pin(dm.stats_stream(&id));
+        while let Some(sample) = stream.next().await {
+            match sample {
+                Ok(stats) => {
+                    {
+                        let mut state = get_app_state().write().await;
+                        reduce(&mut state, Action::SetDockerStats { service_id: id.clone(), stats });
+                    }
+                    notify_state_update().await;
+                }
+                Err(e) => {
+                    tracing::warn!("Stats stream for {} ended: {}", id, e);
+                    break;
+                }
+            }
+        }
+    });
+
+    if let Some(previous) = get_docker_stats_tasks().insert(service_id, handle) {
+        previous.abort();
+    }
+    Ok(())
+}
+
+/// Stop streaming resource stats for a service
+#[napi]
+pub fn docker_stats_stop(service_id: String) {
+    if let Some(handle) = get_docker_stats_tasks().remove(&service_id) {
+        handle.abort();
+    }
+}
+
+/// List local Docker images
+#[napi]
+pub async fn docker_list_images() -> napi::Result<Vec<state::DockerImage>> {
+    let dm = get_docker_manager().await?;
+    dm.list_images().await.map_err(napi::Error::from_reason)
+}
+
+/// Run a command in an rstn-managed container and return its output
+#[napi]
+pub async fn docker_exec(service_id: String, cmd: Vec<String>) -> napi::Result<state::ExecOutput> {
+    let dm = get_docker_manager().await?;
+    dm.exec(&service_id, &cmd).await.map_err(napi::Error::from_reason)
+}
+
+/// Remove dangling Docker images
+#[napi]
+pub async fn docker_prune_images() -> napi::Result<state::ImagePruneResult> {
+    let dm = get_docker_manager().await?;
+    dm.prune_images().await.map_err(napi::Error::from_reason)
+}
+
+/// Reload service templates from `~/.rstn/services.toml` and the active
+/// project's `.rstn/services.toml`. Returns the number of services defined.
+#[napi]
+pub async fn docker_reload_service_templates() -> napi::Result<u32> {
+    let dm = get_docker_manager().await?;
+    let project_path = match APP_STATE.get() {
+        Some(state) => state.read().await.active_project().map(|p| p.path.clone()),
+        None => None,
+    };
+
+    let count = dm
+        .reload_service_templates(project_path.as_deref().map(std::path::Path::new))
+        .map_err(napi::Error::from_reason)?;
+
+    if APP_STATE.get().is_some() {
+        refresh_docker_services_internal().await;
+        notify_state_update().await;
+    }
+    Ok(count as u32)
+}
+
+// ============================================================================
+// Justfile functions
+// ============================================================================
+
+/// Parse a justfile and return all commands
+#[napi]
+pub fn justfile_parse(path: String) -> napi::Result<Vec<justfile::JustCommand>> {
+    justfile::parse_justfile(&path)
+        .map_err(napi::Error::from_reason)
+}
+
+/// Run a just command in a directory
+#[napi]
+pub fn justfile_run(command: String, cwd: String) -> napi::Result<String> {
+    justfile::run_just_command(&command, &cwd)
+        .map_err(napi::Error::from_reason)
+}
+
+// ============================================================================
+// File Reader functions
+// ============================================================================
+
+/// Read a file from allowed scopes (project root or ~/.rstn/).
+///
 /// # Arguments
 /// * `path` - Absolute path to the file
 /// * `project_root` - Project root directory (security scope)
@@ -294,6 +1138,7 @@ pub fn file_read(path: String, project_root: String) -> napi::Result<String> {
             file_reader::FileReadError::SecurityViolation(_) => "SECURITY_VIOLATION",
             file_reader::FileReadError::FileTooLarge { .. } => "FILE_TOO_LARGE",
             file_reader::FileReadError::NotUtf8 => "NOT_UTF8",
+            file_reader::FileReadError::Conflict(_) => "FILE_CONFLICT",
             file_reader::FileReadError::Io(_) => "IO_ERROR",
         };
         napi::Error::from_reason(format!("{}: {}", code, e))
@@ -324,6 +1169,7 @@ pub fn file_read_binary(path: String, project_root: String) -> napi::Result<napi
                 file_reader::FileReadError::SecurityViolation(_) => "SECURITY_VIOLATION",
                 file_reader::FileReadError::FileTooLarge { .. } => "FILE_TOO_LARGE",
                 file_reader::FileReadError::NotUtf8 => "NOT_UTF8",
+                file_reader::FileReadError::Conflict(_) => "FILE_CONFLICT",
                 file_reader::FileReadError::Io(_) => "IO_ERROR",
             };
             napi::Error::from_reason(format!("{}: {}", code, e))
@@ -388,6 +1234,143 @@ pub fn explorer_list_directory(
         .collect())
 }
 
+/// Functions, types and methods of a source file (Rust, TS/JS, Python),
+/// cached until the file changes
+#[napi]
+pub fn symbols_for_file(path: String) -> napi::Result<Vec<symbols::Symbol>> {
+    symbols::shared_cache()
+        .symbols_for_file(std::path::Path::new(&path))
+        .map_err(napi::Error::from_reason)
+}
+
+/// Render markdown from a file or from text (exactly one of `path` and
+/// `content`) to sanitized HTML, with mermaid diagrams extracted
+#[napi]
+pub fn render_markdown(path: Option<String>, content: Option<String>) -> napi::Result<markdown::RenderedMarkdown> {
+    match (path, content) {
+        (None, Some(content)) => Ok(markdown::render(&content)),
+        (Some(path), None) => markdown::render_file(std::path::Path::new(&path)).map_err(napi::Error::from_reason),
+        _ => Err(napi::Error::from_reason("Pass either a path or content")),
+    }
+}
+
+// ============================================================================
+// Language server functions
+// ============================================================================
+
+/// Hover text (markdown) at a 1-based line and column of a file in an open
+/// worktree. Starts the file's language server on first use.
+#[napi]
+pub async fn lsp_hover(path: String, line: u32, column: u32) -> napi::Result<Option<String>> {
+    let Some((worktree_path, server, client)) = lsp_client_for(&path).await? else {
+        return Ok(None);
+    };
+    let result = client.hover(std::path::Path::new(&path), line, column).await;
+    lsp_result(&worktree_path, server, &client, result).await
+}
+
+/// Where the symbol at a 1-based line and column is defined
+#[napi]
+pub async fn lsp_definition(path: String, line: u32, column: u32) -> napi::Result<Vec<lsp::LspLocation>> {
+    let Some((worktree_path, server, client)) = lsp_client_for(&path).await? else {
+        return Ok(Vec::new());
+    };
+    let result = client.definition(std::path::Path::new(&path), line, column).await;
+    lsp_result(&worktree_path, server, &client, result).await
+}
+
+/// The language server for a file, started if it is not running
+/// (None = no server for this kind of file)
+async fn lsp_client_for(
+    path: &str,
+) -> napi::Result<Option<(String, lsp::LanguageServer, Arc<lsp::LspClient>)>> {
+    let file = std::path::Path::new(path);
+    let Some(server) = lsp::LanguageServer::for_path(file) else {
+        return Ok(None);
+    };
+
+    let worktree_path = {
+        let mut state = get_app_state().write().await;
+        // Innermost worktree containing the file
+        let worktree = state
+            .projects
+            .iter()
+            .flat_map(|p| p.worktrees.iter())
+            .filter(|w| file.starts_with(&w.path))
+            .max_by_key(|w| w.path.len())
+            .ok_or_else(|| napi::Error::from_reason(format!("{} is not in an open worktree", path)))?;
+        let worktree_path = worktree.path.clone();
+
+        if let Some(client) = get_lsp_manager().get(&worktree_path, server).await {
+            return Ok(Some((worktree_path, server, client)));
+        }
+        // A failed server stays failed until StopLspServers
+        match worktree.lsp_servers.iter().find(|s| s.server == server) {
+            Some(s) if s.status == app_state::LspServerStatus::Starting => {
+                return Err(napi::Error::from_reason("Language server is starting"));
+            }
+            Some(s) if s.status == app_state::LspServerStatus::Failed => {
+                return Err(napi::Error::from_reason(s.error.clone().unwrap_or_default()));
+            }
+            _ => {}
+        }
+        reduce(&mut state, Action::SetLspServerStatus {
+            worktree_path: worktree_path.clone(),
+            server,
+            status: Some(app_state::LspServerStatus::Starting),
+            error: None,
+        });
+        worktree_path
+    };
+    notify_state_update().await;
+
+    match lsp::LspClient::start(server, std::path::Path::new(&worktree_path)).await {
+        Ok(client) => {
+            let client = Arc::new(client);
+            get_lsp_manager().insert(&worktree_path, server, client.clone()).await;
+            set_lsp_status(&worktree_path, server, Some(app_state::LspServerStatus::Running), None).await;
+            Ok(Some((worktree_path, server, client)))
+        }
+        Err(e) => {
+            set_lsp_status(&worktree_path, server, Some(app_state::LspServerStatus::Failed), Some(e.clone())).await;
+            Err(napi::Error::from_reason(e))
+        }
+    }
+}
+
+/// Pass a request's outcome through, marking the server failed if it exited
+async fn lsp_result<T>(
+    worktree_path: &str,
+    server: lsp::LanguageServer,
+    client: &lsp::LspClient,
+    result: Result<T, String>,
+) -> napi::Result<T> {
+    if result.is_err() && !client.is_running().await {
+        get_lsp_manager().remove(worktree_path, server).await;
+        let error = Some("Language server exited".to_string());
+        set_lsp_status(worktree_path, server, Some(app_state::LspServerStatus::Failed), error).await;
+    }
+    result.map_err(napi::Error::from_reason)
+}
+
+async fn set_lsp_status(
+    worktree_path: &str,
+    server: lsp::LanguageServer,
+    status: Option<app_state::LspServerStatus>,
+    error: Option<String>,
+) {
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::SetLspServerStatus {
+            worktree_path: worktree_path.to_string(),
+            server,
+            status,
+            error,
+        });
+    }
+    notify_state_update().await;
+}
+
 // ============================================================================
 // Worktree functions
 // ============================================================================
@@ -417,6 +1400,139 @@ pub fn worktree_list_branches(repo_path: String) -> napi::Result<Vec<NapiBranchI
         .map_err(napi::Error::from_reason)
 }
 
+/// Structured branch diff for napi export
+#[napi(object)]
+pub struct NapiWorktreeDiff {
+    pub base: String,
+    pub head: String,
+    pub files: Vec<NapiDiffFile>,
+}
+
+/// Changed file in a branch diff
+#[napi(object)]
+pub struct NapiDiffFile {
+    pub path: String,
+    pub old_path: Option<String>,
+    pub status: String, // "added" | "modified" | "deleted" | "renamed"
+    pub is_binary: bool,
+    pub additions: u32,
+    pub deletions: u32,
+    pub hunks: Vec<NapiDiffHunk>,
+}
+
+/// Hunk in a changed file
+#[napi(object)]
+pub struct NapiDiffHunk {
+    pub header: String,
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    pub lines: Vec<NapiDiffLine>,
+}
+
+/// Line in a hunk
+#[napi(object)]
+pub struct NapiDiffLine {
+    pub line_type: String, // "context" | "added" | "removed"
+    pub content: String,
+    pub old_line: Option<u32>,
+    pub new_line: Option<u32>,
+}
+
+/// Diff `head` against its merge base with `base` (e.g. feature branch vs main)
+#[napi]
+pub fn worktree_diff(repo_path: String, base: String, head: String) -> napi::Result<NapiWorktreeDiff> {
+    let diff = worktree::diff::diff_branches(&repo_path, &base, &head)
+        .map_err(napi::Error::from_reason)?;
+
+    Ok(NapiWorktreeDiff {
+        base: diff.base,
+        head: diff.head,
+        files: diff.files.into_iter().map(|f| NapiDiffFile {
+            path: f.path,
+            old_path: f.old_path,
+            status: format!("{:?}", f.status).to_lowercase(),
+            is_binary: f.is_binary,
+            additions: f.additions,
+            deletions: f.deletions,
+            hunks: f.hunks.into_iter().map(|h| NapiDiffHunk {
+                header: h.header,
+                old_start: h.old_start,
+                old_lines: h.old_lines,
+                new_start: h.new_start,
+                new_lines: h.new_lines,
+                lines: h.lines.into_iter().map(|l| NapiDiffLine {
+                    line_type: format!("{:?}", l.line_type).to_lowercase(),
+                    content: l.content,
+                    old_line: l.old_line,
+                    new_line: l.new_line,
+                }).collect(),
+            }).collect(),
+        }).collect(),
+    })
+}
+
+// ============================================================================
+// Git functions
+// ============================================================================
+
+/// Stage files in a repository
+#[napi]
+pub fn git_stage(repo_path: String, paths: Vec<String>) -> napi::Result<()> {
+    git::stage(&repo_path, &paths).map_err(napi::Error::from_reason)
+}
+
+/// Commit staged changes (blocked if the security scan finds secrets).
+/// Returns the new commit hash.
+#[napi]
+pub fn git_commit(repo_path: String, message: String) -> napi::Result<String> {
+    git::commit(&repo_path, &message).map_err(napi::Error::from_reason)
+}
+
+/// Push the current branch
+#[napi]
+pub fn git_push(repo_path: String) -> napi::Result<()> {
+    git::push(&repo_path).map_err(napi::Error::from_reason)
+}
+
+/// Pull the current branch (fast-forward only)
+#[napi]
+pub fn git_pull(repo_path: String) -> napi::Result<()> {
+    git::pull(&repo_path).map_err(napi::Error::from_reason)
+}
+
+/// Commits that changed a file (following renames), newest first
+#[napi]
+pub fn git_file_history(path: String, limit: Option<u32>) -> napi::Result<Vec<git::FileCommit>> {
+    git::file_history(&path, limit.unwrap_or(git::DEFAULT_HISTORY_LIMIT)).map_err(napi::Error::from_reason)
+}
+
+/// Last commit of every line of a file, grouped into hunks of consecutive lines
+#[napi]
+pub fn git_blame(path: String) -> napi::Result<Vec<git::BlameHunk>> {
+    git::blame(&path).map_err(napi::Error::from_reason)
+}
+
+/// Install (or update) the rstn-managed pre-commit hook of a project: secret
+/// scan plus the `.rstn/hooks.toml` pipeline
+#[napi]
+pub fn hooks_install(project: String) -> napi::Result<git_hooks::GitHookStatus> {
+    git_hooks::install(std::path::Path::new(&project)).map_err(napi::Error::from_reason)
+}
+
+/// Whether the project's pre-commit hook is managed by rstn and up to date
+#[napi]
+pub fn hooks_status(project: String) -> napi::Result<git_hooks::GitHookStatus> {
+    git_hooks::status(std::path::Path::new(&project)).map_err(napi::Error::from_reason)
+}
+
+/// Remove the rstn-managed pre-commit hook of a project
+#[napi]
+pub fn hooks_uninstall(project: String) -> napi::Result<git_hooks::GitHookStatus> {
+    git_hooks::uninstall(std::path::Path::new(&project)).map_err(napi::Error::from_reason)
+}
+
 // ============================================================================
 // Env functions
 // ============================================================================
@@ -433,304 +1549,2433 @@ pub fn env_default_patterns() -> Vec<String> {
     env::default_patterns()
 }
 
-// ============================================================================
-// MCP functions
-// ============================================================================
-
-/// Fetch available tools from MCP server
-#[napi]
-pub async fn fetch_mcp_tools() -> napi::Result<String> {
-    // Check if state is initialized
-    let state_cell = APP_STATE.get().ok_or_else(|| {
-        napi::Error::from_reason("AppState not initialized. Call state_init first.")
-    })?;
-    let state = state_cell.read().await;
-
-    let port = if let Some(project) = state.active_project() {
-        if let Some(worktree) = project.active_worktree() {
-            worktree.mcp.port
-        } else {
-            None
-        }
-    } else {
-        None
-    };
+/// Env file diff for napi export
+#[napi(object)]
+pub struct NapiEnvFileDiff {
+    pub file: String,
+    pub keys: Vec<NapiEnvKeyDiff>,
+}
 
-    let Some(port) = port else {
-        eprintln!("[fetch_mcp_tools] No port available");
-        // Return JSON-RPC formatted empty response
-        return Ok(serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "result": { "tools": [] }
-        }).to_string());
-    };
+/// Differing env variable (values masked)
+#[napi(object)]
+pub struct NapiEnvKeyDiff {
+    pub key: String,
+    pub change: String, // "added" | "removed" | "changed"
+    pub from_value: Option<String>,
+    pub to_value: Option<String>,
+}
 
-    // Call MCP server's tools/list endpoint
-    let url = format!("http://localhost:{}/mcp", port);
-    eprintln!("[fetch_mcp_tools] Fetching tools from: {}", url);
-    let client = reqwest::Client::new();
+/// Diff env files between two worktrees key by key (values masked)
+#[napi]
+pub fn env_diff_files(from: String, to: String, patterns: Vec<String>) -> napi::Result<Vec<NapiEnvFileDiff>> {
+    let diffs = env::diff_env_files(&from, &to, &patterns).map_err(napi::Error::from_reason)?;
+    Ok(diffs
+        .into_iter()
+        .map(|file| NapiEnvFileDiff {
+            file: file.file,
+            keys: file
+                .keys
+                .into_iter()
+                .map(|key| NapiEnvKeyDiff {
+                    key: key.key,
+                    change: match key.change {
+                        app_state::EnvKeyChange::Added => "added",
+                        app_state::EnvKeyChange::Removed => "removed",
+                        app_state::EnvKeyChange::Changed => "changed",
+                    }
+                    .to_string(),
+                    from_value: key.from_value,
+                    to_value: key.to_value,
+                })
+                .collect(),
+        })
+        .collect())
+}
 
-    let response = client
-        .post(&url)
-        .json(&serde_json::json!({
-            "jsonrpc": "2.0",
-            "id": 1,
-            "method": "tools/list",
-            "params": {}
-        }))
-        .send()
-        .await
-        .map_err(|e| {
-            eprintln!("[fetch_mcp_tools] HTTP error: {}", e);
-            napi::Error::from_reason(format!("HTTP error: {}", e))
-        })?;
+// ============================================================================
+// Claude CLI functions
+// ============================================================================
 
-    let body = response
-        .text()
+/// List models available to the Claude CLI (for the model picker)
+#[napi]
+pub async fn claude_list_models() -> napi::Result<Vec<String>> {
+    claude_cli::list_models()
         .await
-        .map_err(|e| {
-            eprintln!("[fetch_mcp_tools] Read error: {}", e);
-            napi::Error::from_reason(format!("Read error: {}", e))
-        })?;
-
-    eprintln!("[fetch_mcp_tools] Response body: {}", body);
-    Ok(body)
+        .map_err(|e| napi::Error::from_reason(e.to_string()))
 }
 
 // ============================================================================
-// Context Engine functions
+// Ollama functions
 // ============================================================================
 
-/// AI Context for napi export
-#[napi(object)]
-pub struct NapiAIContext {
-    /// Open files with content
-    pub open_files: Vec<NapiFileContext>,
-    /// Last terminal output
-    pub terminal_last_output: Option<String>,
-    /// Git status summary
-    pub git_status: String,
-    /// Active errors
-    pub active_errors: Vec<String>,
-    /// Directory tree
-    pub directory_tree: Option<String>,
-    /// Git diff
-    pub git_diff: Option<String>,
+/// List models installed on the configured Ollama server (errors if none is running)
+#[napi]
+pub async fn ollama_list_models() -> napi::Result<Vec<llm::ollama::OllamaModel>> {
+    let base_url = get_app_state().read().await.global_settings.ollama.base_url.clone();
+    llm::ollama::list_models(&base_url)
+        .await
+        .map_err(napi::Error::from_reason)
 }
 
-/// File context for napi export
-#[napi(object)]
-pub struct NapiFileContext {
-    /// File path
-    pub path: String,
-    /// File content (may be truncated)
-    pub content: String,
-    /// Cursor line if available
-    pub cursor_line: Option<u32>,
+// ============================================================================
+// GitHub functions
+// ============================================================================
+
+/// List open issues of a GitHub repository ("owner/repo", a remote URL or a
+/// local checkout), using the GitHub token from Settings if set
+#[napi]
+pub async fn github_list_issues(repo: String) -> napi::Result<Vec<github::GitHubIssue>> {
+    let token = get_app_state().read().await.global_settings.git_hosting.github_token.clone();
+    let repo = github::resolve_repo(&repo).map_err(napi::Error::from_reason)?;
+    github::list_issues(&repo, token.as_deref())
+        .await
+        .map_err(napi::Error::from_reason)
 }
 
-/// Build AI context for a project path
-///
-/// Gathers context from git, files, and other sources within a token budget.
+// ============================================================================
+// Log functions
+// ============================================================================
+
+/// Recent backend log entries (oldest first), at `level` or more severe and
+/// from modules containing `module`
 #[napi]
-pub fn context_build(
-    project_path: String,
-    active_files: Vec<String>,
-    task_output: Option<String>,
-    docker_errors: Vec<String>,
-    token_budget: Option<u32>,
-) -> NapiAIContext {
-    let budget = token_budget.unwrap_or(20000) as usize;
-    let path = std::path::Path::new(&project_path);
+pub fn logs_tail(n: Option<u32>, level: Option<String>, module: Option<String>) -> Vec<logging::LogEntry> {
+    logging::tail(
+        n.map_or(logging::DEFAULT_TAIL, |n| n as usize),
+        level.as_deref(),
+        module.as_deref(),
+    )
+}
 
-    let context = context_engine::build_context(
-        path,
-        active_files,
-        task_output,
-        docker_errors,
-        budget,
-    );
+// ============================================================================
+// Doctor functions
+// ============================================================================
 
-    NapiAIContext {
-        open_files: context.open_files.into_iter().map(|f| NapiFileContext {
-            path: f.path,
-            content: f.content,
-            cursor_line: f.cursor_line.map(|l| l as u32),
-        }).collect(),
-        terminal_last_output: context.terminal_last_output,
-        git_status: context.git_status,
-        active_errors: context.active_errors,
-        directory_tree: context.directory_tree,
-        git_diff: context.git_diff,
-    }
+/// Run the pre-flight checks (Claude CLI, Docker, git, just, MCP port,
+/// disk space, config directories)
+#[napi]
+pub async fn doctor_run() -> doctor::DoctorReport {
+    doctor::run().await
 }
 
-/// Build AI context and format as a system prompt string
+/// Write a zip with recent logs, the app state (secrets masked), versions,
+/// OS info and the last dev log actions, for attaching to bug reports
 #[napi]
-pub fn context_build_system_prompt(
-    project_path: String,
-    active_files: Vec<String>,
-    task_output: Option<String>,
-    docker_errors: Vec<String>,
-    token_budget: Option<u32>,
-) -> String {
-    let budget = token_budget.unwrap_or(20000) as usize;
-    let path = std::path::Path::new(&project_path);
+pub async fn diagnostics_export_bundle(path: String) -> napi::Result<()> {
+    let doctor = doctor::run().await;
+    let state = get_app_state().read().await.clone();
+    diagnostics::export_bundle(
+        std::path::Path::new(&path),
+        &state,
+        &doctor,
+        &persistence::get_rstn_dir(),
+        &logging::default_dir(),
+    )
+    .map_err(napi::Error::from_reason)
+}
 
-    let context = context_engine::build_context(
-        path,
-        active_files,
-        task_output,
-        docker_errors,
-        budget,
-    );
+// ============================================================================
+// Constitution functions
+// ============================================================================
 
-    context.to_system_prompt()
+/// List the language/framework stacks detected in a project, flagging those
+/// whose constitution template is overridden in ~/.config/rustation/constitutions/
+#[napi]
+pub async fn constitution_list_detected_stacks(
+    path: String,
+) -> napi::Result<Vec<constitution::DetectedStack>> {
+    tokio::task::spawn_blocking(move || {
+        constitution::list_detected_stacks(
+            std::path::Path::new(&path),
+            constitution::user_templates_dir().as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| napi::Error::from_reason(format!("Stack detection task failed: {}", e)))
 }
 
 // ============================================================================
-// State Management (State-first architecture)
+// Feature catalog functions
 // ============================================================================
 
-/// Initialize the application state and register a listener for state updates.
-///
-/// The callback will be invoked with the JSON-serialized state whenever it changes.
-/// This should be called once during app startup.
+/// Spec-kit features of a worktree with their artifacts, task progress and
+/// branch, for the pipeline board (JSON array of FeatureInfo)
 #[napi]
-pub fn state_init(
-    #[napi(ts_arg_type = "(err: Error | null, state: string) => void")] callback: napi::JsFunction,
-) -> napi::Result<()> {
-    // Initialize the state with defaults
-    let mut initial_state = AppState::default();
+pub async fn features_list(workspace: String) -> napi::Result<serde_json::Value> {
+    let features = tokio::task::spawn_blocking(move || feature_catalog::list(std::path::Path::new(&workspace)))
+        .await
+        .map_err(|e| napi::Error::from_reason(format!("Feature catalog task failed: {}", e)))?;
+    serde_json::to_value(features).map_err(|e| napi::Error::from_reason(e.to_string()))
+}
 
-    // Load persisted global state if available
-    if let Ok(Some(persisted)) = persistence::load_global() {
-        persisted.apply_to(&mut initial_state);
+/// One feature of a worktree by number (JSON FeatureInfo)
+#[napi]
+pub async fn features_info(workspace: String, number: u32) -> napi::Result<serde_json::Value> {
+    let feature = tokio::task::spawn_blocking(move || feature_catalog::find(std::path::Path::new(&workspace), number))
+        .await
+        .map_err(|e| napi::Error::from_reason(format!("Feature catalog task failed: {}", e)))?
+        .ok_or_else(|| napi::Error::from_reason(format!("Feature {} not found", number)))?;
+    serde_json::to_value(feature).map_err(|e| napi::Error::from_reason(e.to_string()))
+}
 
-        // Auto-open the most recent project if it exists on disk
-        // Skip auto-open in test mode to ensure clean E2E test environment
-        let is_test_mode = std::env::var("RSTN_TEST_MODE")
-            .map(|v| v == "1")
-            .unwrap_or(false);
-        if !is_test_mode {
-            if let Some(recent) = initial_state.recent_projects.first() {
-                let path = recent.path.clone();
-                if std::path::Path::new(&path).exists() {
-                    reduce(&mut initial_state, Action::OpenProject { path });
-                }
-            }
-        }
-    }
+// ============================================================================
+// Prompt library functions
+// ============================================================================
 
-    let _ = APP_STATE.set(Arc::new(RwLock::new(initial_state)));
+/// List the prompt templates in ~/.rstn/prompts/library/
+#[napi]
+pub fn prompts_list() -> Vec<prompt_library::PromptTemplate> {
+    prompt_library::list_templates(&prompt_library::library_dir())
+}
 
-    #[cfg(not(test))]
-    {
-        // Create threadsafe function for callbacks
-        let tsfn: ThreadsafeFunction<String> = callback.create_threadsafe_function(
-            0,
-            |ctx: ThreadSafeCallContext<String>| {
-                ctx.env.create_string(&ctx.value).map(|v| vec![v])
-            },
-        )?;
+/// Render a prompt template with variable values (defaults fill empty values)
+#[napi]
+pub fn prompts_render(id: String, vars: std::collections::HashMap<String, String>) -> napi::Result<String> {
+    prompt_library::find_template(&prompt_library::library_dir(), &id)
+        .and_then(|template| prompt_library::render(&template, &vars))
+        .map_err(napi::Error::from_reason)
+}
 
-        let _ = STATE_LISTENER.set(tsfn);
-    }
+// ============================================================================
+// Agent rules functions
+// ============================================================================
 
-    Ok(())
+/// Export an agent profile of the active project to `path` as a TOML file
+#[napi]
+pub async fn agent_rules_export(profile_id: String, path: String) -> napi::Result<()> {
+    let profile = {
+        let state = get_app_state().read().await;
+        state
+            .active_project()
+            .and_then(|p| p.agent_rules_config.profiles.iter().find(|p| p.id == profile_id))
+            .cloned()
+            .ok_or_else(|| napi::Error::from_reason(format!("Agent profile not found: {}", profile_id)))?
+    };
+    agent_rules::export_profile(&profile, std::path::Path::new(&path)).map_err(napi::Error::from_reason)
 }
 
-/// Get the current state as JSON.
+/// Read and validate an agent profile file. The returned profile is added to
+/// the active project by dispatching `ImportAgentProfile`.
 #[napi]
-pub async fn state_get() -> napi::Result<String> {
-    let state = get_app_state().read().await;
-    serde_json::to_string(&*state)
-        .map_err(|e| napi::Error::from_reason(format!("Failed to serialize state: {}", e)))
+pub fn agent_rules_import(path: String) -> napi::Result<agent_rules::AgentProfileFile> {
+    agent_rules::import_profile(std::path::Path::new(&path)).map_err(napi::Error::from_reason)
 }
 
-/// Dispatch an action to update the state.
-///
-/// The action should be a JSON object with the format:
-/// `{ "type": "ActionName", "payload": { ... } }`
-///
-/// After the action is processed, the state listener will be notified.
+/// Usage summary for napi export
+#[napi(object)]
+pub struct NapiUsageSummary {
+    pub project_id: String,
+    /// "day", "week", "month" or "all"
+    pub period: String,
+    pub request_count: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cache_creation_tokens: i64,
+    /// Estimated cost in USD
+    pub cost_usd: f64,
+}
+
+/// Summarize Claude CLI usage for a project over a period ("day", "week", "month", "all")
 #[napi]
-pub async fn state_dispatch(action_json: String) -> napi::Result<()> {
-    // Parse the action
-    let action: Action = serde_json::from_str(&action_json)
-        .map_err(|e| napi::Error::from_reason(format!("Invalid action JSON: {}", e)))?;
+pub fn usage_summary(project_id: String, period: String) -> napi::Result<NapiUsageSummary> {
+    let usage_period = usage::UsagePeriod::parse(&period).map_err(napi::Error::from_reason)?;
+    let since = usage_period.since(chrono::Utc::now()).map(|t| t.to_rfc3339());
+    let db = get_db_manager().ok_or_else(|| napi::Error::from_reason("Database not initialized"))?;
+    let totals = db
+        .get_usage_totals(&project_id, since.as_deref())
+        .map_err(|e| napi::Error::from_reason(e.to_string()))?;
 
-    // Apply synchronous state changes first
-    {
-        let mut state = get_app_state().write().await;
-        reduce(&mut state, action.clone());
-    }
+    Ok(NapiUsageSummary {
+        project_id,
+        period,
+        request_count: totals.request_count as i64,
+        input_tokens: totals.input_tokens as i64,
+        output_tokens: totals.output_tokens as i64,
+        cache_read_tokens: totals.cache_read_tokens as i64,
+        cache_creation_tokens: totals.cache_creation_tokens as i64,
+        cost_usd: totals.cost_usd,
+    })
+}
 
-    // Handle async operations based on action type
-    handle_async_action(action).await?;
+// ============================================================================
+// Activity functions
+// ============================================================================
 
-    // Auto-save state (non-blocking)
-    {
-        let state = get_app_state().read().await;
-        let state_clone = state.clone();
-        tokio::spawn(async move {
-            if let Err(e) = persistence::save_global(&state_clone) {
-                tracing::warn!("Failed to save global state: {}", e);
-            }
-        });
-    }
+/// Activity event for napi export
+#[napi(object)]
+pub struct NapiActivityEvent {
+    pub id: i64,
+    pub project_id: String,
+    /// "change_created", "plan_approved", "service_started", "worktree_added"
+    /// or "constitution_updated"
+    pub kind: String,
+    pub summary: String,
+    /// Structured details as JSON
+    pub detail_json: Option<String>,
+    pub timestamp: String,
+}
 
-    // Notify listener of state update
-    notify_state_update().await;
+/// List a project's activity, newest first, optionally only events at or
+/// after `since` (ISO 8601)
+#[napi]
+pub fn activity_list(project_id: String, since: Option<String>) -> napi::Result<Vec<NapiActivityEvent>> {
+    let db = get_db_manager().ok_or_else(|| napi::Error::from_reason("Database not initialized"))?;
+    let rows = db
+        .get_logs(&project_id, since.as_deref(), activity::MAX_ACTIVITY_EVENTS)
+        .map_err(|e| napi::Error::from_reason(e.to_string()))?;
 
-    Ok(())
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| activity::ActivityEvent::from_row(&project_id, row))
+        .map(|event| NapiActivityEvent {
+            id: event.id,
+            project_id: event.project_id,
+            kind: event.kind.as_str().to_string(),
+            summary: event.summary,
+            detail_json: event.detail.map(|d| d.to_string()),
+            timestamp: event.timestamp,
+        })
+        .collect())
 }
 
-/// Refresh Docker services and update state
-async fn refresh_docker_services_internal() {
-    match docker_list_services().await {
-        Ok(services) => {
-            let service_data: Vec<actions::DockerServiceData> = services
-                .into_iter()
-                .map(|s| actions::DockerServiceData {
-                    id: s.id,
-                    name: s.name,
-                    image: s.image,
-                    status: s.status,
-                    port: s.port,
-                    service_type: s.service_type,
-                    project_group: s.project_group,
-                    is_rstn_managed: s.is_rstn_managed,
-                })
-                .collect();
-            let mut state = get_app_state().write().await;
-            reduce(&mut state, Action::SetDockerServices { services: service_data });
-        }
-        Err(e) => {
-            let mut state = get_app_state().write().await;
-            reduce(&mut state, Action::SetError {
-                code: "DOCKER_LIST_ERROR".to_string(),
-                message: e.to_string(),
-                context: Some("RefreshDockerServices".to_string()),
-            });
-            reduce(&mut state, Action::SetDockerLoading { is_loading: false });
+// ============================================================================
+// Session history functions
+// ============================================================================
+
+/// Claude session record for napi export
+#[napi(object)]
+pub struct NapiSessionRecord {
+    pub id: String,
+    pub project_id: String,
+    /// "chat", "constitution", "proposal", "plan", "implementation", ...
+    pub kind: String,
+    pub worktree_path: String,
+    pub claude_session_id: Option<String>,
+    pub model: Option<String>,
+    pub prompt_excerpt: String,
+    pub started_at: String,
+    pub duration_ms: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cost_usd: f64,
+    pub log_file: Option<String>,
+    /// "success", "error" or "interrupted"
+    pub outcome: String,
+    pub error: Option<String>,
+}
+
+impl From<sessions::SessionRecord> for NapiSessionRecord {
+    fn from(session: sessions::SessionRecord) -> Self {
+        Self {
+            id: session.id,
+            project_id: session.project_id,
+            kind: session.kind,
+            worktree_path: session.worktree_path,
+            claude_session_id: session.claude_session_id,
+            model: session.model,
+            prompt_excerpt: session.prompt_excerpt,
+            started_at: session.started_at,
+            duration_ms: session.duration_ms as i64,
+            input_tokens: session.input_tokens as i64,
+            output_tokens: session.output_tokens as i64,
+            cost_usd: session.cost_usd,
+            log_file: session.log_file,
+            outcome: session.outcome.as_str().to_string(),
+            error: session.error,
         }
     }
 }
 
-/// Refresh justfile commands for the active worktree
-async fn refresh_justfile_commands() {
-    let worktree_path = {
+/// List a project's Claude sessions, newest first
+#[napi]
+pub fn sessions_list(project_id: String, limit: Option<u32>) -> napi::Result<Vec<NapiSessionRecord>> {
+    let db = get_db_manager().ok_or_else(|| napi::Error::from_reason("Database not initialized"))?;
+    let limit = limit.map_or(app_state::MAX_SESSION_HISTORY, |n| n as usize);
+    let sessions = db
+        .list_sessions(&project_id, limit)
+        .map_err(|e| napi::Error::from_reason(e.to_string()))?;
+    Ok(sessions.into_iter().map(NapiSessionRecord::from).collect())
+}
+
+/// Get one Claude session by ID
+#[napi]
+pub fn sessions_info(id: String) -> napi::Result<Option<NapiSessionRecord>> {
+    let db = get_db_manager().ok_or_else(|| napi::Error::from_reason("Database not initialized"))?;
+    let session = db.get_session(&id).map_err(|e| napi::Error::from_reason(e.to_string()))?;
+    Ok(session.map(NapiSessionRecord::from))
+}
+
+/// Delete a Claude session from the history; returns whether it existed
+#[napi]
+pub fn sessions_delete(id: String) -> napi::Result<bool> {
+    let db = get_db_manager().ok_or_else(|| napi::Error::from_reason("Database not initialized"))?;
+    db.delete_session(&id).map_err(|e| napi::Error::from_reason(e.to_string()))
+}
+
+/// Export a Claude session's transcript (prompts, assistant text, tool calls,
+/// timings) to `path` as "markdown" or "json"
+#[napi]
+pub fn session_export(session_id: String, format: String, path: String) -> napi::Result<()> {
+    let format = session_export::ExportFormat::parse(&format).map_err(napi::Error::from_reason)?;
+    let db = get_db_manager().ok_or_else(|| napi::Error::from_reason("Database not initialized"))?;
+    let session = db
+        .get_session(&session_id)
+        .map_err(|e| napi::Error::from_reason(e.to_string()))?
+        .ok_or_else(|| napi::Error::from_reason(format!("Session not found: {}", session_id)))?;
+    session_export::export(&session, format, std::path::Path::new(&path)).map_err(napi::Error::from_reason)
+}
+
+// ============================================================================
+// Change bundle functions
+// ============================================================================
+
+/// Zip a change of the active worktree (documents, implementation diff, test
+/// results, review comments) to `path` for reviewers without rstn; returns
+/// the bundled files
+#[napi]
+pub async fn change_export_bundle(change_id: String, path: String) -> napi::Result<Vec<String>> {
+    let target = {
         let state = get_app_state().read().await;
-        state
-            .active_project()
-            .and_then(|p| p.active_worktree())
-            .map(|w| w.path.clone())
+        state.active_project().and_then(|p| p.active_worktree()).and_then(|w| {
+            let change = w.changes.changes.iter().find(|c| c.id == change_id)?;
+            Some((w.path.clone(), change.name.clone()))
+        })
     };
+    let (wt_path, change_name) =
+        target.ok_or_else(|| napi::Error::from_reason(format!("Change not found: {}", change_id)))?;
+    change_bundle::export(std::path::Path::new(&wt_path), &change_name, std::path::Path::new(&path))
+        .map_err(napi::Error::from_reason)
+}
 
-    if let Some(path) = worktree_path {
-        let justfile_path = std::path::Path::new(&path).join("justfile");
+/// Merge review comments from a returned bundle (or its review.json) into
+/// the active worktree's change
+#[napi]
+pub async fn change_import_feedback(path: String) -> napi::Result<change_bundle::FeedbackImport> {
+    let wt_path = active_worktree_id_and_path()
+        .await
+        .map(|(_, path)| path)
+        .ok_or_else(|| napi::Error::from_reason("No active worktree"))?;
+    let import = change_bundle::import_feedback(std::path::Path::new(&wt_path), std::path::Path::new(&path))
+        .map_err(napi::Error::from_reason)?;
+
+    let comments = review_comments::load(std::path::Path::new(&wt_path), &import.change_name)
+        .map_err(napi::Error::from_reason)?;
+    {
+        let mut state = get_app_state().write().await;
+        let change_id = state
+            .active_project()
+            .and_then(|p| p.active_worktree())
+            .and_then(|w| w.changes.changes.iter().find(|c| c.name == import.change_name))
+            .map(|c| c.id.clone());
+        if let Some(change_id) = change_id {
+            reduce(&mut state, Action::SetReviewComments { change_id, comments });
+        }
+    }
+    notify_state_update().await;
+    Ok(import)
+}
+
+// ============================================================================
+// MCP functions
+// ============================================================================
+
+/// Running MCP server info for napi export
+#[napi(object)]
+pub struct NapiMcpServerInfo {
+    pub worktree_id: String,
+    pub worktree_path: String,
+    pub project_name: String,
+    pub port: u32,
+}
+
+/// List all running MCP servers across open projects
+#[napi]
+pub async fn mcp_list_running_servers() -> Vec<NapiMcpServerInfo> {
+    get_mcp_server_manager()
+        .list_running()
+        .await
+        .into_iter()
+        .map(|s| NapiMcpServerInfo {
+            worktree_id: s.worktree_id,
+            worktree_path: s.worktree_path,
+            project_name: s.project_name,
+            port: s.port as u32,
+        })
+        .collect()
+}
+
+/// Per-tool MCP call metrics for napi export
+#[napi(object)]
+pub struct NapiMcpToolMetrics {
+    pub tool_name: String,
+    pub call_count: u32,
+    pub error_count: u32,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub last_called_at: Option<String>,
+}
+
+/// Get per-tool metrics for the active worktree's MCP server
+#[napi]
+pub async fn mcp_get_metrics() -> napi::Result<Vec<NapiMcpToolMetrics>> {
+    let state_cell = APP_STATE.get().ok_or_else(|| {
+        napi::Error::from_reason("AppState not initialized. Call state_init first.")
+    })?;
+    let worktree_id = {
+        let state = state_cell.read().await;
+        state
+            .active_project()
+            .and_then(|p| p.active_worktree())
+            .map(|w| w.id.clone())
+    };
+
+    let Some(worktree_id) = worktree_id else {
+        return Ok(Vec::new());
+    };
+
+    Ok(get_mcp_server_manager()
+        .get_metrics(&worktree_id)
+        .await
+        .into_iter()
+        .map(|m| NapiMcpToolMetrics {
+            tool_name: m.tool_name,
+            call_count: m.call_count.min(u32::MAX as u64) as u32,
+            error_count: m.error_count.min(u32::MAX as u64) as u32,
+            p50_ms: m.p50_ms,
+            p95_ms: m.p95_ms,
+            last_called_at: m.last_called_at,
+        })
+        .collect())
+}
+
+/// Call a tool on one of the active worktree's external MCP servers.
+///
+/// `args_json` is the tool's arguments object. The call is recorded in the
+/// MCP playground history; returns the `tools/call` result as JSON.
+#[napi]
+pub async fn mcp_call_tool(server: String, tool: String, args_json: String) -> napi::Result<String> {
+    let arguments: serde_json::Value = serde_json::from_str(&args_json)
+        .map_err(|e| napi::Error::from_reason(format!("Invalid arguments JSON: {}", e)))?;
+    let result = playground_call(server, tool, arguments)
+        .await
+        .map_err(napi::Error::from_reason)?;
+    Ok(result.to_string())
+}
+
+/// Call an external server's tool, recording it in the playground history
+async fn playground_call(server: String, tool: String, arguments: serde_json::Value) -> Result<serde_json::Value, String> {
+    let (worktree_id, _) = active_worktree_id_and_path()
+        .await
+        .ok_or_else(|| "No active worktree".to_string())?;
+
+    let id = uuid::Uuid::new_v4().to_string();
+    {
+        let mut state = get_app_state().write().await;
+        reduce(
+            &mut state,
+            Action::StartMcpPlaygroundCall {
+                entry: app_state::McpPlaygroundEntry {
+                    id: id.clone(),
+                    server: server.clone(),
+                    tool: tool.clone(),
+                    arguments: arguments.clone(),
+                    called_at: chrono::Utc::now().to_rfc3339(),
+                    duration_ms: None,
+                    result: None,
+                    error: None,
+                },
+            },
+        );
+    }
+    notify_state_update().await;
+
+    let started = std::time::Instant::now();
+    let outcome = match get_mcp_clients().get(&worktree_id, &server).await {
+        Some(client) => client.call_tool(&tool, arguments).await,
+        None => Err(format!("Server '{}' is not connected", server)),
+    };
+    {
+        let mut state = get_app_state().write().await;
+        reduce(
+            &mut state,
+            Action::FinishMcpPlaygroundCall {
+                id,
+                duration_ms: started.elapsed().as_millis() as u64,
+                result: outcome.as_ref().ok().cloned(),
+                error: outcome.as_ref().err().cloned(),
+            },
+        );
+    }
+    notify_state_update().await;
+    outcome
+}
+
+/// Fetch available tools from MCP server
+#[napi]
+pub async fn fetch_mcp_tools() -> napi::Result<String> {
+    // Check if state is initialized
+    let state_cell = APP_STATE.get().ok_or_else(|| {
+        napi::Error::from_reason("AppState not initialized. Call state_init first.")
+    })?;
+    let state = state_cell.read().await;
+
+    let port = if let Some(project) = state.active_project() {
+        if let Some(worktree) = project.active_worktree() {
+            worktree.mcp.port
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    let Some(port) = port else {
+        tracing::warn!("[fetch_mcp_tools] No port available");
+        // Return JSON-RPC formatted empty response
+        return Ok(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": { "tools": [] }
+        }).to_string());
+    };
+
+    // Call MCP server's tools/list endpoint
+    let url = format!("http://localhost:{}/mcp", port);
+    tracing::debug!("[fetch_mcp_tools] Fetching tools from: {}", url);
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(&url)
+        .json(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/list",
+            "params": {}
+        }))
+        .send()
+        .await
+        .map_err(|e| {
+            tracing::error!("[fetch_mcp_tools] HTTP error: {}", e);
+            napi::Error::from_reason(format!("HTTP error: {}", e))
+        })?;
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| {
+            tracing::error!("[fetch_mcp_tools] Read error: {}", e);
+            napi::Error::from_reason(format!("Read error: {}", e))
+        })?;
+
+    tracing::debug!("[fetch_mcp_tools] Response body: {}", body);
+    Ok(body)
+}
+
+// ============================================================================
+// Context Engine functions
+// ============================================================================
+
+/// AI Context for napi export
+#[napi(object)]
+pub struct NapiAIContext {
+    /// Open files with content
+    pub open_files: Vec<NapiFileContext>,
+    /// Last terminal output
+    pub terminal_last_output: Option<String>,
+    /// Git status summary
+    pub git_status: String,
+    /// Active errors
+    pub active_errors: Vec<String>,
+    /// Directory tree
+    pub directory_tree: Option<String>,
+    /// Git diff
+    pub git_diff: Option<String>,
+    /// Dependencies declared in Cargo.toml / package.json / pyproject.toml
+    pub dependencies: Vec<NapiDependency>,
+}
+
+/// Declared dependency for napi export
+#[napi(object)]
+pub struct NapiDependency {
+    /// Manifest file name (e.g. "Cargo.toml")
+    pub manifest: String,
+    /// Package name
+    pub name: String,
+    /// Version requirement as declared
+    pub version: String,
+    /// Development-only dependency
+    pub dev: bool,
+}
+
+/// File context for napi export
+#[napi(object)]
+pub struct NapiFileContext {
+    /// File path
+    pub path: String,
+    /// File content (may be truncated)
+    pub content: String,
+    /// Cursor line if available
+    pub cursor_line: Option<u32>,
+}
+
+/// Build AI context for a project path
+///
+/// Gathers context from git, files, and other sources within a token budget.
+/// With a prompt, project files relevant to it are ranked and included.
+#[napi]
+pub fn context_build(
+    project_path: String,
+    active_files: Vec<String>,
+    task_output: Option<String>,
+    docker_errors: Vec<String>,
+    token_budget: Option<u32>,
+    prompt: Option<String>,
+) -> NapiAIContext {
+    let budget = token_budget.unwrap_or(20000) as usize;
+    let path = std::path::Path::new(&project_path);
+
+    let context = context_engine::build_context(
+        path,
+        active_files,
+        task_output,
+        docker_errors,
+        budget,
+        prompt.as_deref(),
+        prompt.as_ref().map(|_| get_file_indexer().index_for(path)),
+    );
+    warn_context_env_secrets(path, &context);
+
+    NapiAIContext {
+        open_files: context.open_files.into_iter().map(|f| NapiFileContext {
+            path: f.path,
+            content: f.content,
+            cursor_line: f.cursor_line.map(|l| l as u32),
+        }).collect(),
+        terminal_last_output: context.terminal_last_output,
+        git_status: context.git_status,
+        active_errors: context.active_errors,
+        directory_tree: context.directory_tree,
+        git_diff: context.git_diff,
+        dependencies: context.dependencies.into_iter().map(|d| NapiDependency {
+            manifest: d.manifest,
+            name: d.name,
+            version: d.version,
+            dev: d.dev,
+        }).collect(),
+    }
+}
+
+/// Build AI context and format as a system prompt string
+#[napi]
+pub fn context_build_system_prompt(
+    project_path: String,
+    active_files: Vec<String>,
+    task_output: Option<String>,
+    docker_errors: Vec<String>,
+    token_budget: Option<u32>,
+    prompt: Option<String>,
+) -> String {
+    let budget = token_budget.unwrap_or(20000) as usize;
+    let path = std::path::Path::new(&project_path);
+
+    let context = context_engine::build_context(
+        path,
+        active_files,
+        task_output,
+        docker_errors,
+        budget,
+        prompt.as_deref(),
+        prompt.as_ref().map(|_| get_file_indexer().index_for(path)),
+    );
+    warn_context_env_secrets(path, &context);
+
+    context.to_system_prompt()
+}
+
+// ============================================================================
+// Terminal functions
+// ============================================================================
+
+/// Register a listener for PTY output.
+///
+/// The callback is invoked with `(sessionId, data)` for every chunk a shell
+/// writes. Chunks never split a UTF-8 character.
+#[napi]
+#[cfg_attr(test, allow(unused_variables))]
+pub fn terminal_set_output_listener(
+    #[napi(ts_arg_type = "(err: Error | null, sessionId: string, data: string) => void")] callback: napi::JsFunction,
+) -> napi::Result<()> {
+    #[cfg(not(test))]
+    {
+        let tsfn: ThreadsafeFunction<(String, Vec<u8>)> = callback.create_threadsafe_function(
+            0,
+            |ctx: ThreadSafeCallContext<(String, Vec<u8>)>| {
+                let (session_id, data) = ctx.value;
+                Ok(vec![
+                    ctx.env.create_string(&session_id)?,
+                    ctx.env.create_string(&String::from_utf8_lossy(&data))?,
+                ])
+            },
+        )?;
+
+        get_terminal_manager().set_output_callback(Arc::new(move |session_id, data| {
+            tsfn.call(Ok((session_id, data)), ThreadsafeFunctionCallMode::NonBlocking);
+        }));
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Desktop notifications
+// ============================================================================
+
+/// Register a listener for OS notifications.
+///
+/// The callback is invoked with a notification for every long-running event
+/// the user has not turned off in settings; the desktop shell shows it.
+#[napi]
+#[cfg_attr(test, allow(unused_variables))]
+pub fn desktop_notification_set_listener(
+    #[napi(ts_arg_type = "(err: Error | null, notification: DesktopNotification) => void")] callback: napi::JsFunction,
+) -> napi::Result<()> {
+    #[cfg(not(test))]
+    {
+        let tsfn: ThreadsafeFunction<desktop_notifications::DesktopNotification> = callback
+            .create_threadsafe_function(
+                0,
+                |ctx: ThreadSafeCallContext<desktop_notifications::DesktopNotification>| Ok(vec![ctx.value]),
+            )?;
+        let _ = DESKTOP_NOTIFICATION_LISTENER.set(tsfn);
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// State Management (State-first architecture)
+// ============================================================================
+
+/// Initialize the application state and register a listener for state updates.
+///
+/// The callback will be invoked with the JSON-serialized state whenever it changes.
+/// This should be called once during app startup.
+#[napi]
+#[cfg_attr(test, allow(unused_variables))]
+pub fn state_init(
+    #[napi(ts_arg_type = "(err: Error | null, state: string) => void")] callback: napi::JsFunction,
+) -> napi::Result<()> {
+    logging::init(logging::default_dir());
+    let rstn_dir = persistence::get_rstn_dir();
+    diagnostics::install_panic_hook(rstn_dir.clone());
+
+    // Initialize the state with defaults
+    let mut initial_state = AppState::default();
+
+    // Skip auto-open and journaling in test mode to ensure clean E2E test environment
+    let is_test_mode = std::env::var("RSTN_TEST_MODE")
+        .map(|v| v == "1")
+        .unwrap_or(false);
+
+    // Load persisted global state if available
+    initial_state.settings_profiles = settings_profiles::load_all(&rstn_dir);
+    if let Ok(Some(persisted)) = persistence::load_global() {
+        persisted.apply_to(&mut initial_state);
+
+        // Reopen the projects of the last session (or else the most recent
+        // project) that still exist on disk
+        if !is_test_mode {
+            restore_workspace(&mut initial_state, &persisted.workspace);
+        }
+    }
+
+    // The OpenAI key is not persisted (older state files may still have one)
+    if initial_state.global_settings.openai.api_key.is_none() {
+        initial_state.global_settings.openai.api_key = std::env::var(app_state::OPENAI_API_KEY_ENV)
+            .ok()
+            .filter(|key| !key.trim().is_empty());
+    }
+
+    // Journal dispatched actions so work survives a crash
+    if !is_test_mode {
+        let journal = journal::Journal::new(journal::Journal::default_dir());
+        match journal.start().and_then(|crashed| journal.snapshot(&initial_state).map(|_| crashed)) {
+            Ok(crashed) => {
+                if crashed {
+                    tracing::warn!("Previous session did not shut down cleanly; call recover_state to restore it");
+                }
+                let _ = JOURNAL.set(journal);
+            }
+            Err(e) => tracing::warn!("Action journal disabled: {}", e),
+        }
+    }
+
+    // Report a crash of the previous session once
+    if let Some(crash) = diagnostics::take_crash_marker(&rstn_dir) {
+        tracing::warn!("Previous session crashed: {}", crash.message);
+        reduce(
+            &mut initial_state,
+            Action::AddNotification {
+                message: "rstn crashed during the last session. Export a diagnostics bundle from Settings > Diagnostics to report it".to_string(),
+                notification_type: actions::NotificationTypeData::Warning,
+            },
+        );
+    }
+
+    let start_proxy_on_launch = !is_test_mode && initial_state.global_settings.proxy.enabled;
+    let _ = APP_STATE.set(Arc::new(RwLock::new(initial_state)));
+
+    if start_proxy_on_launch {
+        napi::bindgen_prelude::spawn(async {
+            start_proxy().await;
+            notify_state_update().await;
+        });
+    }
+    if !is_test_mode {
+        napi::bindgen_prelude::spawn(run_system_monitor());
+    }
+
+    #[cfg(not(test))]
+    {
+        // Create threadsafe function for callbacks
+        let tsfn: ThreadsafeFunction<String> = callback.create_threadsafe_function(
+            0,
+            |ctx: ThreadSafeCallContext<String>| {
+                ctx.env.create_string(&ctx.value).map(|v| vec![v])
+            },
+        )?;
+
+        let _ = STATE_LISTENER.set(tsfn);
+    }
+
+    Ok(())
+}
+
+/// Open the projects of a saved workspace and focus the one that was focused.
+///
+/// Projects that no longer exist are skipped. Without a saved workspace the
+/// most recent project is opened.
+fn restore_workspace(state: &mut AppState, workspace: &persistence::WorkspaceSession) {
+    let paths: Vec<String> = if workspace.projects.is_empty() {
+        state.recent_projects.first().map(|r| r.path.clone()).into_iter().collect()
+    } else {
+        workspace.projects.clone()
+    };
+    let focused = workspace.projects.get(workspace.active_project).cloned();
+
+    // Reopening is not "opening recently": keep the recent list as it was
+    let recent_projects = state.recent_projects.clone();
+    for path in paths {
+        if std::path::Path::new(&path).exists() {
+            reduce(state, Action::OpenProject { path });
+        }
+    }
+    state.recent_projects = recent_projects;
+    if let Some(index) = focused.and_then(|path| state.projects.iter().position(|p| p.path == path)) {
+        reduce(state, Action::SwitchProject { index });
+    }
+}
+
+/// Get the current state as JSON.
+#[napi]
+pub async fn state_get() -> napi::Result<String> {
+    let state = get_app_state().read().await;
+    serde_json::to_string(&*state)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to serialize state: {}", e)))
+}
+
+/// Dispatch an action to update the state.
+///
+/// The action should be a JSON object with the format:
+/// `{ "type": "ActionName", "payload": { ... } }`
+///
+/// After the action is processed, the state listener will be notified.
+#[napi]
+pub async fn state_dispatch(action_json: String) -> napi::Result<()> {
+    // Parse the action
+    let action: Action = serde_json::from_str(&action_json)
+        .map_err(|e| napi::Error::from_reason(format!("Invalid action JSON: {}", e)))?;
+
+    // Apply synchronous state changes first (journaled under the same lock,
+    // so snapshots match the journal)
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, action.clone());
+        journal_action(&state, &action);
+    }
+
+    // Handle async operations based on action type
+    handle_async_action(action).await?;
+
+    // Auto-save state (non-blocking)
+    {
+        let state = get_app_state().read().await;
+        let state_clone = state.clone();
+        tokio::spawn(async move {
+            if let Err(e) = persistence::save_global(&state_clone) {
+                tracing::warn!("Failed to save global state: {}", e);
+            }
+        });
+    }
+
+    // Notify listener of state update
+    notify_state_update().await;
+
+    Ok(())
+}
+
+// ============================================================================
+// Headless mode (feature "cli")
+// ============================================================================
+
+/// Initialize the state without a JavaScript listener, for the `rstn-core`
+/// binary. Settings and profiles are loaded; the workspace is not restored
+/// and nothing is journaled.
+#[cfg(feature = "cli")]
+pub fn headless_init() {
+    logging::init(logging::default_dir());
+    let mut initial_state = AppState {
+        settings_profiles: settings_profiles::load_all(&persistence::get_rstn_dir()),
+        ..Default::default()
+    };
+    if let Ok(Some(persisted)) = persistence::load_global() {
+        persisted.apply_to(&mut initial_state);
+    }
+    let _ = APP_STATE.set(Arc::new(RwLock::new(initial_state)));
+}
+
+/// Dispatch an action through the reducer and async handlers, like
+/// `state_dispatch`. The global state file is left alone, so headless runs
+/// don't change the projects the app reopens.
+#[cfg(feature = "cli")]
+pub async fn headless_dispatch(action: Action) -> Result<(), String> {
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, action.clone());
+    }
+    handle_async_action(action).await.map_err(|e| e.reason)?;
+    notify_state_update().await;
+    Ok(())
+}
+
+/// Snapshot of the current state (headless mode)
+#[cfg(feature = "cli")]
+pub async fn headless_state() -> AppState {
+    get_app_state().read().await.clone()
+}
+
+/// Append a dispatched action to the journal, snapshotting when due
+fn journal_action(state: &AppState, action: &Action) {
+    let Some(journal) = JOURNAL.get() else {
+        return;
+    };
+    let result = journal
+        .append(action)
+        .and_then(|due| if due { journal.snapshot(state) } else { Ok(()) });
+    if let Err(e) = result {
+        tracing::warn!("Failed to journal action: {}", e);
+    }
+}
+
+/// Restore the work of a session that did not shut down cleanly.
+///
+/// Replays the previous session's action journal over its last snapshot and
+/// replaces the current state. Returns false if there was nothing to recover.
+/// Call once after `state_init`.
+#[napi]
+pub async fn recover_state() -> napi::Result<bool> {
+    let Some(journal) = JOURNAL.get() else {
+        return Ok(false);
+    };
+    let recovered = journal
+        .recover()
+        .map_err(|e| napi::Error::from_reason(format!("Failed to recover state: {}", e)))?;
+    let Some(mut recovered) = recovered else {
+        return Ok(false);
+    };
+
+    reduce(
+        &mut recovered,
+        Action::AddNotification {
+            message: "Restored your work from the previous session, which did not shut down cleanly".to_string(),
+            notification_type: actions::NotificationTypeData::Info,
+        },
+    );
+    {
+        let mut state = get_app_state().write().await;
+        // The journal stores secrets redacted; keep the ones loaded at startup
+        recovered.global_settings.openai.api_key = state.global_settings.openai.api_key.clone();
+        recovered.global_settings.git_hosting = state.global_settings.git_hosting.clone();
+        *state = recovered;
+        // Start this session's journal from the recovered state
+        if let Err(e) = journal.snapshot(&state) {
+            tracing::warn!("Failed to snapshot recovered state: {}", e);
+        }
+        if let Err(e) = persistence::save_global(&state) {
+            tracing::warn!("Failed to save global state: {}", e);
+        }
+    }
+    sync_worktree_watchers().await;
+    notify_state_update().await;
+    Ok(true)
+}
+
+/// Mark a clean shutdown: the next launch offers nothing to recover.
+/// Call when the app quits.
+#[napi]
+pub fn state_shutdown() {
+    if let Some(journal) = JOURNAL.get() {
+        if let Err(e) = journal.finish() {
+            tracing::warn!("Failed to close action journal: {}", e);
+        }
+    }
+}
+
+// ============================================================================
+// State Bridge (feature "state-bridge")
+// ============================================================================
+
+/// Start the localhost WebSocket state bridge (0 or no port = any free port).
+///
+/// Clients receive the full state, then JSON Patch diffs, and can send
+/// actions as text messages. Restarts the bridge if it is already running.
+#[cfg(feature = "state-bridge")]
+#[napi]
+pub async fn state_bridge_start(port: Option<u32>) -> napi::Result<state_bridge::StateBridgeInfo> {
+    let state = serde_json::to_value(&*get_app_state().read().await)
+        .map_err(|e| napi::Error::from_reason(format!("Failed to serialize state: {}", e)))?;
+    let (bridge, mut requests) = state_bridge::StateBridge::bind(port.unwrap_or(0) as u16, state)
+        .await
+        .map_err(|e| napi::Error::from_reason(format!("Failed to start state bridge: {}", e)))?;
+    let info = bridge.info();
+    *STATE_BRIDGE.lock().unwrap_or_else(|e| e.into_inner()) = Some(bridge);
+
+    tokio::spawn(async move {
+        while let Some(request) = requests.recv().await {
+            let result = state_dispatch(request.action_json).await.map_err(|e| e.reason);
+            let _ = request.reply.send(result);
+        }
+    });
+    tracing::info!("State bridge listening on 127.0.0.1:{}", info.port);
+    Ok(info)
+}
+
+/// Stop the WebSocket state bridge and disconnect its clients.
+#[cfg(feature = "state-bridge")]
+#[napi]
+pub fn state_bridge_stop() {
+    STATE_BRIDGE.lock().unwrap_or_else(|e| e.into_inner()).take();
+}
+
+// ============================================================================
+// Dev Tools
+// ============================================================================
+
+/// Start or stop recording the action trace (dev mode).
+#[napi]
+pub fn dev_set_trace_enabled(enabled: bool) {
+    trace::set_enabled(enabled);
+}
+
+/// Write the recorded action trace (last 1000 actions with state hashes and
+/// reducer durations) to `path` as JSON. Returns the number of entries.
+#[napi]
+pub fn dev_export_trace(path: String) -> napi::Result<u32> {
+    trace::export(std::path::Path::new(&path))
+        .map(|count| count as u32)
+        .map_err(napi::Error::from_reason)
+}
+
+/// Replay a trace exported by `dev_export_trace` against a fresh state.
+/// The current state is not touched.
+#[napi]
+pub async fn dev_replay_trace(path: String) -> napi::Result<trace::TraceReplayResult> {
+    tokio::task::spawn_blocking(move || {
+        let trace = trace::load(std::path::Path::new(&path))?;
+        Ok::<_, String>(trace::replay(&trace).into())
+    })
+    .await
+    .map_err(|e| napi::Error::from_reason(format!("Trace replay task failed: {}", e)))?
+    .map_err(napi::Error::from_reason)
+}
+
+/// List command palette actions for the current state.
+///
+/// Each entry's `action` is the JSON to pass to `state_dispatch`; disabled
+/// entries carry the reason in `disabled_reason`.
+#[napi]
+pub async fn palette_list_actions() -> Vec<palette::PaletteAction> {
+    let state = get_app_state().read().await;
+    palette::ActionRegistry::new().list(&state)
+}
+
+/// ID and path of the active worktree
+async fn active_worktree_id_and_path() -> Option<(String, String)> {
+    let state = get_app_state().read().await;
+    state
+        .active_project()
+        .and_then(|p| p.active_worktree())
+        .map(|w| (w.id.clone(), w.path.clone()))
+}
+
+/// Fetch the active worktree's MCP tools from its server and store them
+async fn refresh_mcp_tools() {
+    match fetch_mcp_tools().await {
+        Ok(json_str) => {
+            match serde_json::from_str::<serde_json::Value>(&json_str) {
+                Ok(data) => {
+                    if let Some(tools_array) = data.get("result")
+                        .and_then(|r| r.get("tools"))
+                        .and_then(|t| t.as_array())
+                    {
+                        let tools: Vec<actions::McpToolData> = tools_array
+                            .iter()
+                            .filter_map(|tool| {
+                                Some(actions::McpToolData {
+                                    name: tool.get("name")?.as_str()?.to_string(),
+                                    description: tool.get("description")?.as_str()?.to_string(),
+                                    input_schema: tool.get("input_schema")?.clone(),
+                                })
+                            })
+                            .collect();
+
+                        let mut state = get_app_state().write().await;
+                        reduce(&mut state, Action::UpdateMcpTools { tools });
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to parse MCP tools response: {}", e);
+                }
+            }
+        }
+        Err(e) => {
+            tracing::warn!("Failed to fetch MCP tools: {}", e);
+        }
+    }
+}
+
+/// (Re)start the local reverse proxy on the configured port
+async fn start_proxy() {
+    // Free the port first when restarting
+    PROXY_SERVER.lock().unwrap_or_else(|e| e.into_inner()).take();
+
+    let port = {
+        let state = get_app_state().read().await;
+        state.global_settings.proxy.port.unwrap_or(proxy::DEFAULT_PROXY_PORT)
+    };
+    let started = match proxy::LocalCa::load_or_create(&proxy::LocalCa::default_dir()) {
+        Ok(ca) => proxy::ProxyServer::start(port, ca).await,
+        Err(e) => Err(e),
+    };
+
+    let mut state = get_app_state().write().await;
+    let status = match started {
+        Ok(server) => {
+            let status = app_state::ProxyStatus {
+                running: true,
+                port: Some(server.port()),
+                ca_cert_path: Some(server.ca_cert_path().to_string_lossy().to_string()),
+                error: None,
+            };
+            *PROXY_SERVER.lock().unwrap_or_else(|e| e.into_inner()) = Some(server);
+            sync_proxy_routes(&state);
+            status
+        }
+        Err(e) => {
+            tracing::warn!("Failed to start proxy: {}", e);
+            app_state::ProxyStatus { error: Some(e), ..Default::default() }
+        }
+    };
+    reduce(&mut state, Action::SetProxyStatus { status });
+}
+
+/// Point the running proxy's routes at the current ports of running services
+fn sync_proxy_routes(state: &AppState) {
+    let server = PROXY_SERVER.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(server) = server.as_ref() else {
+        return;
+    };
+    let ports: std::collections::HashMap<String, u16> = state
+        .docker
+        .services
+        .iter()
+        .filter(|s| s.status == app_state::ServiceStatus::Running)
+        .filter_map(|s| Some((s.id.clone(), u16::try_from(s.port?).ok()?)))
+        .collect();
+    if let Err(e) = server.update_routes(&state.global_settings.proxy.routes, &ports) {
+        tracing::warn!("Failed to update proxy routes: {}", e);
+    }
+}
+
+/// Refresh Docker services and update state
+async fn refresh_docker_services_internal() {
+    match docker_list_services().await {
+        Ok(services) => {
+            let service_data: Vec<actions::DockerServiceData> = services
+                .into_iter()
+                .map(|s| actions::DockerServiceData {
+                    id: s.id,
+                    name: s.name,
+                    image: s.image,
+                    status: s.status,
+                    port: s.port,
+                    service_type: s.service_type,
+                    project_group: s.project_group,
+                    is_rstn_managed: s.is_rstn_managed,
+                })
+                .collect();
+            let crashed = {
+                let mut state = get_app_state().write().await;
+                let before = state.docker.services.clone();
+                reduce(&mut state, Action::SetDockerServices { services: service_data });
+                // Services may have moved to another port
+                sync_proxy_routes(&state);
+                desktop_notifications::crashed_services(&before, &state.docker.services)
+            };
+            for notification in crashed {
+                notify_desktop(DesktopNotificationEvent::DockerServiceCrashed, notification).await;
+            }
+        }
+        Err(e) => {
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetError {
+                code: "DOCKER_LIST_ERROR".to_string(),
+                message: e.to_string(),
+                context: Some("RefreshDockerServices".to_string()),
+            });
+            reduce(&mut state, Action::SetDockerLoading { is_loading: false });
+        }
+    }
+}
+
+/// Tracked env patterns of the active project (defaults before state_init)
+async fn tracked_env_patterns() -> Vec<String> {
+    match APP_STATE.get() {
+        Some(state) => state
+            .read()
+            .await
+            .active_project()
+            .map(|project| project.env_config.tracked_patterns.clone())
+            .unwrap_or_else(env::default_patterns),
+        None => env::default_patterns(),
+    }
+}
+
+/// Warn when secrets are about to leave the machine (commit, AI context).
+/// No-op before state_init.
+async fn warn_env_secrets(secrets: &[env_secrets::EnvSecret], reason: &str) {
+    if secrets.is_empty() || APP_STATE.get().is_none() {
+        return;
+    }
+    let mut state = get_app_state().write().await;
+    reduce(&mut state, Action::AddNotification {
+        message: format!("Env secrets {}:\n{}", reason, env_secrets::summary(secrets)),
+        notification_type: actions::NotificationTypeData::Warning,
+    });
+}
+
+/// Warn if tracked env files with secrets are staged for commit
+async fn warn_staged_env_secrets(repo_path: &str) {
+    let Ok(staged) = git::staged_files(repo_path) else {
+        return;
+    };
+    let patterns = tracked_env_patterns().await;
+    let staged_env: Vec<String> = staged
+        .into_iter()
+        .filter(|path| env_secrets::is_tracked_env_file(path, &patterns))
+        .collect();
+    let secrets = env_secrets::scan_env_files(repo_path, &staged_env);
+    warn_env_secrets(&secrets, "staged for commit").await;
+}
+
+/// Warn if tracked env files with secrets were included in AI context
+fn warn_context_env_secrets(project_path: &std::path::Path, context: &context_engine::AIContext) {
+    let files: Vec<(String, String)> = context
+        .open_files
+        .iter()
+        .filter_map(|file| {
+            let path = std::path::Path::new(&file.path);
+            let relative = path.strip_prefix(project_path).unwrap_or(path);
+            Some((relative.to_str()?.to_string(), file.content.clone()))
+        })
+        .collect();
+    if files.is_empty() {
+        return;
+    }
+
+    napi::bindgen_prelude::spawn(async move {
+        let patterns = tracked_env_patterns().await;
+        let secrets: Vec<env_secrets::EnvSecret> = files
+            .iter()
+            .filter(|(path, _)| env_secrets::is_tracked_env_file(path, &patterns))
+            .flat_map(|(path, content)| env_secrets::scan_content(path, content))
+            .collect();
+        if !secrets.is_empty() {
+            warn_env_secrets(&secrets, "included in AI context").await;
+            notify_state_update().await;
+        }
+    });
+}
+
+/// Prompt asking Claude to implement a change's plan step by step
+fn implementation_prompt(change: &app_state::Change, plan: &str, constitution_content: &str, context_content: &str) -> String {
+    format!(
+        r#"You are an expert software engineer. Implement the following plan step by step.
+
+## Constitution (Development Rules)
+{constitution_content}
+
+## Project Context
+{context_content}
+
+## Feature Intent
+{intent}
+
+## Proposal
+{proposal}
+
+## Implementation Plan
+{plan}
+
+## Instructions
+1. Implement each step in the plan sequentially
+2. Follow the constitution rules strictly
+3. Write clean, tested code
+4. Report what you've done after each step
+5. After finishing step N of the Implementation Steps, print `[STEP N DONE]` on its own line
+
+Execute the plan now. Start implementing."#,
+        intent = change.intent,
+        proposal = change.proposal.as_deref().unwrap_or("(no proposal)"),
+    )
+}
+
+/// Snapshot a worktree before Claude implements a change in it. Failing to
+/// snapshot (e.g. outside git) only warns; the implementation still runs.
+async fn snapshot_before_implementation(worktree_path: &str, change: &app_state::Change) {
+    let result = snapshot::create(std::path::Path::new(worktree_path), &change.name);
+    {
+        let mut state = get_app_state().write().await;
+        match result {
+            Ok(snapshot) => reduce(&mut state, Action::SetChangeSnapshot {
+                worktree_path: worktree_path.to_string(),
+                change_id: change.id.clone(),
+                snapshot,
+            }),
+            Err(e) => {
+                tracing::warn!("Failed to snapshot {} before implementing {}: {}", worktree_path, change.name, e);
+                reduce(&mut state, Action::AddNotification {
+                    message: format!("No rollback snapshot for {}: {}", change.name, e),
+                    notification_type: actions::NotificationTypeData::Warning,
+                });
+            }
+        }
+    }
+    notify_state_update().await;
+}
+
+/// Run a post-implementation hook as a task run of the worktree, streaming
+/// its output into the task panel
+async fn run_hook(worktree_path: &str, hook: &hooks::Hook) -> hooks::HookResult {
+    use task_queue::TaskRunStatus;
+
+    let task_id = get_task_queue().next_id();
+    {
+        let mut state = get_app_state().write().await;
+        let run = task_queue::TaskRun {
+            id: task_id.clone(),
+            task_key: format!("hook:{}", hook.name),
+            command: hook.command.clone(),
+            worktree_path: worktree_path.to_string(),
+            status: TaskRunStatus::Queued,
+            queued_at: chrono::Utc::now().to_rfc3339(),
+            exit_code: None,
+            duration_ms: None,
+            output_tail: Vec::new(),
+        };
+        reduce(&mut state, Action::QueueTaskRun { run });
+        reduce(&mut state, Action::StartTaskRun { task_id: task_id.clone() });
+    }
+    notify_state_update().await;
+
+    let started = std::time::Instant::now();
+    let (program, argv) = hook.argv();
+    let (status, exit_code, error) = match tasks::spawn_task(program, &argv, worktree_path) {
+        Ok(mut child) => {
+            let mut lines = tasks::output_lines(&mut child);
+            let deadline = tokio::time::sleep(hook.timeout());
+            tokio::pin!(deadline);
+            let mut last_notify = std::time::Instant::now();
+            let mut timed_out = false;
+            loop {
+                tokio::select! {
+                    line = lines.recv() => {
+                        let Some(line) = line else { break };
+                        {
+                            let mut state = get_app_state().write().await;
+                            reduce(&mut state, Action::AppendTaskRunOutput { task_id: task_id.clone(), line });
+                        }
+                        if last_notify.elapsed() >= std::time::Duration::from_millis(100) {
+                            last_notify = std::time::Instant::now();
+                            notify_state_update().await;
+                        }
+                    }
+                    _ = &mut deadline => {
+                        timed_out = true;
+                        if let Err(e) = child.start_kill() {
+                            tracing::warn!("Failed to kill hook {}: {}", hook.name, e);
+                        }
+                        break;
+                    }
+                }
+            }
+            let exit = child.wait().await;
+            let exit_code = exit.as_ref().ok().and_then(|e| e.code());
+            match exit {
+                _ if timed_out => (
+                    TaskRunStatus::Error,
+                    exit_code,
+                    Some(format!("Timed out after {} seconds", hook.timeout().as_secs())),
+                ),
+                Ok(exit) if exit.success() => (TaskRunStatus::Success, exit_code, None),
+                Ok(_) => (TaskRunStatus::Error, exit_code, None),
+                Err(e) => (TaskRunStatus::Error, None, Some(format!("Failed to wait for hook: {}", e))),
+            }
+        }
+        Err(e) => (TaskRunStatus::Error, None, Some(e)),
+    };
+
+    let duration_ms = started.elapsed().as_millis() as u64;
+    {
+        let mut state = get_app_state().write().await;
+        if let Some(error) = &error {
+            reduce(&mut state, Action::AppendTaskRunOutput { task_id: task_id.clone(), line: error.clone() });
+        }
+        reduce(&mut state, Action::FinishTaskRun {
+            task_id: task_id.clone(),
+            status,
+            exit_code,
+            duration_ms: Some(duration_ms),
+        });
+    }
+    notify_state_update().await;
+
+    hooks::HookResult {
+        name: hook.name.clone(),
+        command: hook.command.clone(),
+        success: status == TaskRunStatus::Success,
+        exit_code,
+        duration_ms,
+        ran_at: chrono::Utc::now().to_rfc3339(),
+        error,
+        task_id: Some(task_id),
+    }
+}
+
+/// Run the worktree's `.rstn/hooks.toml` pipeline after an implementation
+/// and record each hook's result on the change. Failing hooks only warn:
+/// the tests and the review decide whether the change is good. Returns the
+/// summary lines for the implementation output.
+async fn run_post_implementation_hooks(worktree_path: &str, change: &app_state::Change) -> String {
+    let cwd = std::path::Path::new(worktree_path);
+    let pipeline = match hooks::load(cwd) {
+        Ok(pipeline) => pipeline,
+        Err(e) => {
+            {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::AddNotification {
+                    message: format!("Hooks skipped for {}: {}", change.name, e),
+                    notification_type: actions::NotificationTypeData::Warning,
+                });
+            }
+            notify_state_update().await;
+            return String::new();
+        }
+    };
+    if pipeline.is_empty() {
+        return String::new();
+    }
+
+    let mut results = Vec::new();
+    for hook in &pipeline {
+        results.push(run_hook(worktree_path, hook).await);
+    }
+    if let Err(e) = hooks::save_results(cwd, &change.name, &results) {
+        tracing::warn!("Failed to save hook results of {}: {}", change.name, e);
+    }
+
+    let summary: String = results.iter().map(|r| format!("\n{}", r.summary())).collect();
+    let failed: Vec<&str> = results.iter().filter(|r| !r.success).map(|r| r.name.as_str()).collect();
+    {
+        let mut state = get_app_state().write().await;
+        if !failed.is_empty() {
+            reduce(&mut state, Action::AddNotification {
+                message: format!("Hooks failed for {}: {}", change.name, failed.join(", ")),
+                notification_type: actions::NotificationTypeData::Warning,
+            });
+        }
+        reduce(&mut state, Action::SetChangeHookResults {
+            worktree_path: worktree_path.to_string(),
+            change_id: change.id.clone(),
+            results,
+        });
+    }
+    notify_state_update().await;
+    summary
+}
+
+/// Test command of a worktree after an implementation: the coverage variant
+/// (with the report it writes) when the project collects coverage
+async fn implementation_test_command(
+    worktree_path: &str,
+) -> Option<(test_runner::TestCommand, Option<test_runner::CoverageReport>)> {
+    let cwd = std::path::Path::new(worktree_path);
+    let collect_coverage = {
+        let state = get_app_state().read().await;
+        state
+            .projects
+            .iter()
+            .find(|p| p.worktrees.iter().any(|w| w.path == worktree_path))
+            .is_some_and(|p| p.collect_coverage)
+    };
+    if collect_coverage {
+        if let Some((command, report)) = test_runner::detect_coverage_command(cwd) {
+            return Some((command, Some(report)));
+        }
+    }
+    test_runner::detect_test_command(cwd).map(|command| (command, None))
+}
+
+/// Run the project's test command after an implementation and record the
+/// parsed results (and coverage) on the change, saving the output next to
+/// it when it fails
+async fn run_implementation_tests(
+    test_command: &test_runner::TestCommand,
+    coverage: Option<&test_runner::CoverageReport>,
+    worktree_path: &str,
+    change: &app_state::Change,
+) -> Result<(), String> {
+    let cwd = std::path::Path::new(worktree_path);
+    if let Some(report) = coverage {
+        // A stale report must not pass for this run's coverage
+        let _ = std::fs::remove_file(cwd.join(&report.path));
+        let _ = std::fs::create_dir_all(cwd.join(test_runner::COVERAGE_DIR));
+    }
+    let mut command = test_command.clone();
+    let mut run = test_runner::run_test_command(&command, cwd).await?;
+    let mut results = test_runner::TestResults::from_run(&command, &run);
+
+    let mut coverage_warning = None;
+    if let Some(report) = coverage {
+        match test_runner::read_coverage(cwd, report) {
+            Ok(files) => {
+                let summary = test_runner::CoverageSummary::new(files, test_runner::load_last_coverage(cwd).as_ref());
+                if let Err(e) = test_runner::save_last_coverage(cwd, &summary) {
+                    tracing::warn!("Failed to save coverage of {}: {}", worktree_path, e);
+                }
+                results.coverage = Some(summary);
+            }
+            Err(e) => {
+                coverage_warning = Some(format!("No coverage for {}: {}", change.name, e));
+                // No report and no tests run: the coverage tool is likely missing
+                if !run.success && results.passed + results.failed == 0 {
+                    if let Some(plain) = test_runner::detect_test_command(cwd) {
+                        command = plain;
+                        run = test_runner::run_test_command(&command, cwd).await?;
+                        results = test_runner::TestResults::from_run(&command, &run);
+                    }
+                }
+            }
+        }
+    }
+    if let Err(e) = test_runner::save(cwd, &change.name, &results) {
+        tracing::warn!("Failed to save test results of {}: {}", change.name, e);
+    }
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::SetChangeTestResults {
+            worktree_path: worktree_path.to_string(),
+            change_id: change.id.clone(),
+            results: results.clone(),
+        });
+        if let Some(message) = coverage_warning {
+            reduce(&mut state, Action::AddNotification {
+                message,
+                notification_type: actions::NotificationTypeData::Warning,
+            });
+        }
+    }
+    notify_state_update().await;
+
+    if results.is_green() {
+        return Ok(());
+    }
+    let change_dir = cwd.join(".rstn").join("changes").join(&change.name);
+    let log = test_runner::write_test_failure_log(&change_dir, &command, &run.output)
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|e| e);
+    Err(format!("`{}` failed (output saved to {})", command.display(), log))
+}
+
+/// Mark proposal generation as failed and surface the error
+async fn fail_proposal(change_id: &str, error: String) {
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::FailProposal {
+            change_id: change_id.to_string(),
+            error: error.clone(),
+        });
+        reduce(&mut state, Action::SetError {
+            code: "PROPOSAL_GENERATION_ERROR".to_string(),
+            message: error,
+            context: Some(format!("GenerateProposal: {}", change_id)),
+        });
+    }
+    save_change_status(change_id).await;
+    notify_state_update().await;
+}
+
+/// Mark a spec-kit phase as failed
+async fn fail_spec_phase(phase: spec_kit::SpecPhase, error: String) {
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::FailSpecPhase { phase, error });
+    }
+    notify_state_update().await;
+}
+
+/// Write the answers of a clarify session that finished asking into its spec.
+/// Runs under the state lock so a session is integrated exactly once.
+fn integrate_clarify_session(state: &mut AppState) {
+    let Some(worktree) = state.active_project().and_then(|p| p.active_worktree()) else {
+        return;
+    };
+    let Some(session) = worktree
+        .workflows
+        .clarify
+        .as_ref()
+        .filter(|s| s.status == app_state::ClarifySessionStatus::Integrating)
+    else {
+        return;
+    };
+
+    let spec_path = std::path::Path::new(&worktree.path).join(&session.spec_path);
+    let answered: Vec<(&clarify::ClarifyQuestion, &str)> = session
+        .answers
+        .iter()
+        .filter_map(|answer| {
+            let value = answer.value.as_deref()?;
+            let question = session.questions.iter().find(|q| q.id == answer.question_id)?;
+            Some((question, value))
+        })
+        .collect();
+
+    let result = std::fs::read_to_string(&spec_path)
+        .map_err(|e| format!("Failed to read {}: {}", spec_path.display(), e))
+        .and_then(|spec| {
+            let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+            let updated = clarify::integrate(&spec, &answered, &date);
+            if updated != spec {
+                spec_kit::write_atomic(&spec_path, &updated)?;
+            }
+            // Categories the user answered count as resolved even when the
+            // analyzer's keywords do not pick the answer up
+            let mut coverage = clarify::analyze(&updated).coverage;
+            for entry in &mut coverage {
+                if answered.iter().any(|(q, _)| q.category == entry.category)
+                    && entry.status != clarify::CoverageStatus::Clear
+                {
+                    entry.status = clarify::CoverageStatus::Resolved;
+                }
+            }
+            Ok(coverage)
+        });
+
+    match result {
+        Ok(coverage) => reduce(state, Action::CompleteClarifySession { coverage }),
+        Err(error) => reduce(state, Action::FailClarifySession { error }),
+    }
+}
+
+/// Set the tasks parsed from `tasks_path` (relative to the active worktree),
+/// or surface the error
+fn apply_spec_tasks(state: &mut AppState, tasks_path: &str, result: Result<Vec<spec_tasks::TaskItem>, String>) {
+    match result {
+        Ok(items) => reduce(state, Action::SetSpecTasks {
+            tasks: app_state::SpecTasksState::new(tasks_path.to_string(), items),
+        }),
+        Err(message) => reduce(state, Action::SetError {
+            code: "SPEC_TASKS_ERROR".to_string(),
+            message,
+            context: Some(format!("LoadSpecTasks: {}", tasks_path)),
+        }),
+    }
+}
+
+/// Read the checklists present in the spec workflow's feature directory
+fn load_spec_checklists(state: &mut AppState) {
+    let Some(worktree) = state.active_project().and_then(|p| p.active_worktree()) else {
+        return;
+    };
+    let Some(spec_dir) = worktree.workflows.spec.as_ref().map(|w| w.spec_dir.clone()) else {
+        return;
+    };
+    let feature_dir = std::path::Path::new(&worktree.path).join(&spec_dir);
+    let checklists = checklist::load_all(&feature_dir)
+        .into_iter()
+        .map(|(kind, items)| app_state::SpecChecklist {
+            kind,
+            path: format!("{}/{}", spec_dir, kind.file_name()),
+            status: app_state::SpecChecklistStatus::Ready,
+            items,
+            error: None,
+        })
+        .collect();
+    reduce(state, Action::SetChecklists { checklists });
+}
+
+/// Generate a checklist from the feature's spec (and plan) via Claude and
+/// write it to the feature directory. The reducer has already marked it Generating.
+async fn generate_checklist(kind: checklist::ChecklistKind) {
+    let target = {
+        let state = get_app_state().read().await;
+        state.active_project().and_then(|p| p.active_worktree()).and_then(|w| {
+            let checklist = w.workflows.checklists.iter().find(|c| c.kind == kind)?;
+            let spec_dir = &w.workflows.spec.as_ref()?.spec_dir;
+            Some((w.path.clone(), spec_dir.clone(), checklist.path.clone()))
+        })
+    };
+    let Some((wt_path, spec_dir, checklist_path)) = target else {
+        tracing::warn!("GenerateChecklist: No active spec workflow");
+        return;
+    };
+    let cwd = std::path::Path::new(&wt_path);
+    let feature_dir = cwd.join(&spec_dir);
+
+    let result = match std::fs::read_to_string(feature_dir.join("spec.md")) {
+        Ok(spec) => {
+            let plan = std::fs::read_to_string(feature_dir.join("plan.md")).ok();
+            let prompt = checklist::build_prompt(kind, &spec, plan.as_deref());
+            run_claude_to_text(&prompt, cwd, "checklist").await.and_then(|content| {
+                spec_kit::write_atomic(&cwd.join(&checklist_path), &content)?;
+                Ok(checklist::parse(&content))
+            })
+        }
+        Err(_) => Err("Run the specify phase first (missing spec.md)".to_string()),
+    };
+
+    {
+        let mut state = get_app_state().write().await;
+        match result {
+            Ok(items) => reduce(&mut state, Action::SetChecklist {
+                checklist: app_state::SpecChecklist {
+                    kind,
+                    path: checklist_path,
+                    status: app_state::SpecChecklistStatus::Ready,
+                    items,
+                    error: None,
+                },
+            }),
+            Err(error) => reduce(&mut state, Action::FailChecklist { kind, error }),
+        }
+    }
+    notify_state_update().await;
+}
+
+/// Check the spec workflow's artifacts against each other, optionally have
+/// Claude verify the findings, and store the report. The reducer has already
+/// marked the analysis Running.
+async fn run_spec_analysis(verify: bool) {
+    let target = {
+        let state = get_app_state().read().await;
+        state.active_project().and_then(|p| p.active_worktree()).and_then(|w| {
+            let spec_dir = &w.workflows.spec.as_ref()?.spec_dir;
+            Some((w.path.clone(), spec_dir.clone()))
+        })
+    };
+    let Some((wt_path, spec_dir)) = target else {
+        tracing::warn!("AnalyzeSpec: No active spec workflow");
+        return;
+    };
+    let cwd = std::path::Path::new(&wt_path);
+    let feature_dir = cwd.join(&spec_dir);
+
+    let names = ["spec.md", "plan.md", "tasks.md"];
+    let artifacts = names.map(|name| spec_kit::read_artifact(&feature_dir, name));
+    let missing: Vec<&str> = names.iter().zip(&artifacts).filter(|(_, a)| a.is_none()).map(|(n, _)| *n).collect();
+    let result = match artifacts {
+        [Some(spec), Some(plan), Some(tasks_md)] => {
+            let report = spec_analysis::analyze(&spec, &plan, &tasks_md);
+            let report = if verify {
+                let prompt = spec_analysis::build_verification_prompt(&spec, &plan, &tasks_md, &report);
+                run_claude_to_text(&prompt, cwd, "spec_analyze")
+                    .await
+                    .and_then(|output| spec_analysis::apply_verification(&report, &output))
+            } else {
+                Ok(report)
+            };
+            report.and_then(|report| spec_analysis::save(&feature_dir, &report).map(|()| report))
+        }
+        _ => Err(format!("Run the earlier phases first (missing {})", missing.join(", "))),
+    };
+
+    {
+        let mut state = get_app_state().write().await;
+        match result {
+            Ok(report) => reduce(&mut state, Action::CompleteSpecAnalysis { report }),
+            Err(error) => reduce(&mut state, Action::FailSpecAnalysis { error }),
+        }
+    }
+    notify_state_update().await;
+}
+
+fn feature_work_error(state: &mut AppState, feature_number: u32, message: String) {
+    reduce(state, Action::SetError {
+        code: "FEATURE_WORK_ERROR".to_string(),
+        message,
+        context: Some(format!("StartFeatureWork: {}", feature_number)),
+    });
+}
+
+/// Work on a spec-kit feature in its own worktree: attach or create the
+/// worktree of the `NNN-name` branch, bring the feature's artifacts along,
+/// copy env files, switch to it, start its MCP server and check for a
+/// constitution.
+async fn start_feature_work(feature_number: u32) {
+    let context = {
+        let state = get_app_state().read().await;
+        state.active_project().and_then(|p| {
+            let env_source = p
+                .env_config
+                .source_worktree
+                .clone()
+                .or_else(|| p.worktrees.first().map(|w| w.path.clone()));
+            let existing: Vec<(String, String)> = p.worktrees.iter().map(|w| (w.branch.clone(), w.path.clone())).collect();
+            Some((p.path.clone(), p.active_worktree()?.path.clone(), p.env_config.tracked_patterns.clone(), env_source, existing))
+        })
+    };
+    let Some((project_path, current_path, patterns, env_source, existing)) = context else {
+        tracing::warn!("StartFeatureWork: No active worktree");
+        return;
+    };
+
+    let current = std::path::Path::new(&current_path);
+    let Some(feature) = spec_kit::list_features(current).into_iter().find(|f| f.number == feature_number) else {
+        let mut state = get_app_state().write().await;
+        feature_work_error(&mut state, feature_number, format!("Feature {:03} not found under specs/", feature_number));
+        return;
+    };
+    let branch = feature.dir_name();
+
+    let wt_path = match existing.into_iter().find(|(b, _)| *b == branch) {
+        Some((_, path)) => path,
+        None => {
+            let branch_exists = worktree::list_branches(&project_path)
+                .map(|branches| branches.iter().any(|b| b.name == branch))
+                .unwrap_or(false);
+            let created = if branch_exists {
+                worktree::add_worktree(&project_path, &branch)
+            } else {
+                worktree::add_worktree_new_branch(&project_path, &branch)
+            };
+            let new_worktree = match created {
+                Ok(new_worktree) => new_worktree,
+                Err(e) => {
+                    let mut state = get_app_state().write().await;
+                    feature_work_error(&mut state, feature_number, e);
+                    return;
+                }
+            };
+            refresh_worktrees_for_path(&project_path).await;
+            record_activity(
+                activity::ActivityKind::WorktreeAdded,
+                format!("Added worktree for feature {}", branch),
+                serde_json::json!({ "branch": branch, "path": new_worktree.path }),
+            )
+            .await;
+            if let Some(source) = env_source.filter(|source| *source != new_worktree.path) {
+                let copy_action = Action::CopyEnvFiles {
+                    from_worktree_path: source,
+                    to_worktree_path: new_worktree.path.clone(),
+                    patterns: Some(patterns),
+                };
+                Box::pin(handle_async_action(copy_action)).await.ok();
+            }
+            new_worktree.path
+        }
+    };
+
+    // Specs that are not committed yet do not come with the branch
+    if wt_path != current_path {
+        if let Err(e) = spec_kit::copy_feature(&feature, current, std::path::Path::new(&wt_path)) {
+            tracing::warn!("Failed to copy {} to {}: {}", feature.relative_dir(), wt_path, e);
+        }
+    }
+
+    let start_mcp = {
+        let mut state = get_app_state().write().await;
+        let index = state
+            .active_project()
+            .and_then(|p| p.worktrees.iter().position(|w| w.path == wt_path));
+        let Some(index) = index else {
+            feature_work_error(&mut state, feature_number, format!("Worktree {} not found", wt_path));
+            return;
+        };
+        reduce(&mut state, Action::SwitchWorktree { index });
+        let mcp_stopped = state
+            .active_project()
+            .and_then(|p| p.active_worktree())
+            .is_some_and(|w| w.mcp.status == app_state::McpStatus::Stopped);
+        if mcp_stopped {
+            reduce(&mut state, Action::StartMcpServer);
+        }
+        mcp_stopped
+    };
+    notify_state_update().await;
+    if start_mcp {
+        Box::pin(handle_async_action(Action::StartMcpServer)).await.ok();
+    }
+    Box::pin(handle_async_action(Action::CheckConstitutionExists)).await.ok();
+
+    {
+        let mut state = get_app_state().write().await;
+        let has_constitution = state
+            .active_project()
+            .and_then(|p| p.active_worktree())
+            .and_then(|w| w.tasks.constitution_exists)
+            .unwrap_or(false);
+        let (message, notification_type) = if has_constitution {
+            (format!("Working on {} in {}", branch, wt_path), actions::NotificationTypeData::Success)
+        } else {
+            (
+                format!("Working on {} - this worktree has no constitution yet", branch),
+                actions::NotificationTypeData::Warning,
+            )
+        };
+        reduce(&mut state, Action::AddNotification { message, notification_type });
+    }
+    notify_state_update().await;
+}
+
+/// Stream one spec-kit phase through Claude and write its artifact into the
+/// feature directory. The reducer has already marked the phase Running.
+async fn run_spec_phase(phase: spec_kit::SpecPhase, input: Option<String>) {
+    let (workflow, worktree_path) = {
+        let state = get_app_state().read().await;
+        let worktree = state.active_project().and_then(|p| p.active_worktree());
+        (
+            worktree.and_then(|w| w.workflows.spec.clone()),
+            worktree.map(|w| w.path.clone()),
+        )
+    };
+    let (Some(workflow), Some(wt_path)) = (workflow, worktree_path) else {
+        tracing::warn!("RunSpecPhase: No active spec workflow");
+        return;
+    };
+    let cwd = std::path::Path::new(&wt_path);
+    let process_key = spec_process_key(cwd);
+    // Rejected by the reducer (another phase running) or already streaming
+    if workflow.running_phase() != Some(phase) || get_claude_processes().is_running(&process_key) {
+        return;
+    }
+
+    let feature = spec_kit::SpecFeature {
+        number: workflow.feature_number,
+        short_name: workflow.feature_name.clone(),
+    };
+    let feature_dir = cwd.join(&workflow.spec_dir);
+    let missing = spec_kit::missing_artifacts(phase, &feature_dir);
+    if !missing.is_empty() {
+        fail_spec_phase(phase, format!("Run the earlier phases first (missing {})", missing.join(", "))).await;
+        return;
+    }
+
+    // Offline fallback: fill the spec template instead of failing without Claude
+    if phase == spec_kit::SpecPhase::Specify {
+        let offline_reason = if !workflow.use_claude {
+            Some("Claude is off for this workflow")
+        } else if !claude_cli::is_claude_available().await {
+            Some("Claude CLI not found")
+        } else {
+            None
+        };
+        if let Some(reason) = offline_reason {
+            let created = chrono::Local::now().format("%Y-%m-%d").to_string();
+            let output = spec_kit::template_spec(&feature, &workflow.description, &created);
+            if let Err(e) = spec_kit::write_atomic(&feature_dir.join(phase.artifact()), &output) {
+                fail_spec_phase(phase, e).await;
+                return;
+            }
+            {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::CompleteSpecPhase { phase, output });
+                reduce(&mut state, Action::AddNotification {
+                    message: format!("{} - spec.md was filled from the template; clarify the marked sections", reason),
+                    notification_type: actions::NotificationTypeData::Info,
+                });
+            }
+            notify_state_update().await;
+            return;
+        }
+    }
+
+    let config = spec_kit::PhaseConfig::new(phase, &workflow.description, input);
+    let constitution = constitution::read_constitution(cwd);
+    let prompt = spec_kit::build_prompt(&config, &feature, &feature_dir, constitution.as_deref());
+    let usage_source = format!("spec_{}", phase.as_str());
+
+    let mut session = start_claude_session(&usage_source, cwd, &prompt).await;
+    let mut child = match claude_cli::spawn_claude(&prompt, cwd, None, None, None, active_claude_model().await.as_deref()) {
+        Ok(child) => child,
+        Err(e) => {
+            session.fail(e.to_string());
+            fail_spec_phase(phase, e.to_string()).await;
+            return;
+        }
+    };
+
+    if let Some(stderr) = child.stderr.take() {
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let trimmed = line.trim();
+                if !trimmed.is_empty() {
+                    tracing::debug!("[Claude CLI stderr] {}", trimmed);
+                }
+            }
+        });
+    }
+
+    let mut stream = match claude_cli::ClaudeEventStream::new(&mut child) {
+        Ok(stream) => stream,
+        Err(e) => {
+            fail_spec_phase(phase, e.to_string()).await;
+            return;
+        }
+    };
+    get_claude_processes().register(&process_key, child);
+
+    let start_time = std::time::Instant::now();
+    let mut streamed = String::new();
+    let mut assistant_text = String::new();
+    loop {
+        if start_time.elapsed() > claude_cli::TOTAL_TIMEOUT {
+            fail_spec_phase(phase, "Request exceeded 5 minute timeout".to_string()).await;
+            break;
+        }
+
+        let next_event = tokio::time::timeout(claude_cli::EVENT_TIMEOUT, stream.next_event()).await;
+
+        // Cancelled via CancelSpecPhase - state already updated
+        if !get_claude_processes().is_running(&process_key) {
+            break;
+        }
+
+        match next_event {
+            Ok(Some(Ok(event))) => {
+                session.observe(&event);
+                record_claude_usage(&event, &usage_source).await;
+
+                if let Some(text_chunk) = claude_cli::extract_text_delta(&event) {
+                    streamed.push_str(text_chunk);
+                    {
+                        let mut state = get_app_state().write().await;
+                        reduce(&mut state, Action::AppendSpecPhaseOutput {
+                            phase,
+                            content: text_chunk.to_string(),
+                        });
+                    }
+                    notify_state_update().await;
+                }
+
+                // Full assistant messages repeat the deltas; only show them
+                // when nothing was streamed
+                if let Some(text) = claude_cli::extract_assistant_text(&event) {
+                    if streamed.is_empty() {
+                        let mut state = get_app_state().write().await;
+                        reduce(&mut state, Action::AppendSpecPhaseOutput {
+                            phase,
+                            content: text.clone(),
+                        });
+                        drop(state);
+                        notify_state_update().await;
+                    }
+                    assistant_text.push_str(&text);
+                }
+
+                if claude_cli::is_message_stop(&event) {
+                    let output = if assistant_text.is_empty() { streamed } else { assistant_text };
+                    let artifact = feature_dir.join(phase.artifact());
+                    if let Err(e) = std::fs::write(&artifact, &output) {
+                        fail_spec_phase(phase, format!("Failed to write {}: {}", artifact.display(), e)).await;
+                        break;
+                    }
+                    {
+                        let mut state = get_app_state().write().await;
+                        reduce(&mut state, Action::CompleteSpecPhase { phase, output });
+                        if phase == spec_kit::SpecPhase::Tasks {
+                            // Start tracking the fresh task list
+                            let tasks_path = format!("{}/{}", workflow.spec_dir, phase.artifact());
+                            apply_spec_tasks(&mut state, &tasks_path, spec_tasks::load(&artifact));
+                        }
+                    }
+                    notify_state_update().await;
+                    notify_desktop(
+                        DesktopNotificationEvent::ClaudeFinished,
+                        desktop_notifications::claude_finished(phase.as_str(), &feature.dir_name()),
+                    )
+                    .await;
+                    break;
+                }
+            }
+            Ok(Some(Err(e))) => {
+                fail_spec_phase(phase, e.to_string()).await;
+                break;
+            }
+            Ok(None) => {
+                fail_spec_phase(
+                    phase,
+                    "Claude CLI ended unexpectedly. Check if you have valid API credentials.".to_string(),
+                )
+                .await;
+                break;
+            }
+            Err(_) => {
+                fail_spec_phase(phase, "No response from Claude CLI for 30 seconds".to_string()).await;
+                break;
+            }
+        }
+    }
+
+    // Wait for process to finish (already killed if cancelled)
+    if let Some(mut child) = get_claude_processes().take(&process_key) {
+        let _ = child.wait().await;
+    }
+}
+
+/// Mark a just-started service as Starting and poll its health check in the
+/// background: Running once healthy, Error if it exits or times out.
+async fn watch_service_health(service_id: String) {
+    record_activity(
+        activity::ActivityKind::ServiceStarted,
+        format!("Started service {}", service_id),
+        serde_json::json!({ "service_id": service_id }),
+    )
+    .await;
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::SetServiceHealth {
+            service_id: service_id.clone(),
+            health: app_state::ServiceHealth::Starting,
+        });
+    }
+    refresh_docker_services_internal().await;
+
+    tokio::spawn(async move {
+        let result = match get_docker_manager().await {
+            Ok(dm) => dm.wait_until_healthy(&service_id).await,
+            Err(e) => Err(e.to_string()),
+        };
+
+        {
+            let mut state = get_app_state().write().await;
+            // Stopped (or restarted) while we were waiting - nothing to report
+            if state.docker.service_health.get(&service_id) != Some(&app_state::ServiceHealth::Starting) {
+                return;
+            }
+            match result {
+                Ok(()) => reduce(&mut state, Action::SetServiceHealth {
+                    service_id: service_id.clone(),
+                    health: app_state::ServiceHealth::Healthy,
+                }),
+                Err(e) => {
+                    reduce(&mut state, Action::SetServiceHealth {
+                        service_id: service_id.clone(),
+                        health: app_state::ServiceHealth::Unhealthy,
+                    });
+                    reduce(&mut state, Action::SetError {
+                        code: "DOCKER_HEALTH_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("HealthCheck: {}", service_id)),
+                    });
+                }
+            }
+        }
+        refresh_docker_services_internal().await;
+        notify_state_update().await;
+    });
+}
+
+/// Load a project's service groups from .rstn/services.toml
+/// Load a project's request collections into state
+async fn load_project_http_requests(project_path: &str) {
+    let collections = http_client::load_collections(std::path::Path::new(project_path));
+    let mut state = get_app_state().write().await;
+    reduce(&mut state, Action::SetHttpRequests {
+        project_path: project_path.to_string(),
+        collections,
+    });
+}
+
+/// Send a request of the active project with the variables of its
+/// collection and the active worktree's env file
+async fn run_http_request(collection_id: &str, request_name: &str, env_file: Option<&str>) {
+    let found = {
+        let state = get_app_state().read().await;
+        state.active_project().map(|project| {
+            let worktree_path = project.active_worktree().map(|w| w.path.clone());
+            let collection = project.http_requests.collections.iter().find(|c| c.id == collection_id);
+            let request = collection.and_then(|c| c.requests.iter().find(|r| r.name == request_name));
+            (project.path.clone(), worktree_path, collection.map(|c| c.vars.clone()), request.cloned())
+        })
+    };
+    let Some((project_path, worktree_path, vars, request)) = found else {
+        return;
+    };
+
+    let result = async {
+        let (Some(mut vars), Some(request)) = (vars, request) else {
+            return Err(format!("Request {}/{} not found", collection_id, request_name));
+        };
+        let env_file = env_file.unwrap_or(".env");
+        if !env::is_safe_env_path(env_file) {
+            return Err(format!("Invalid env file path: {}", env_file));
+        }
+        if let Some(worktree_path) = worktree_path {
+            // A missing env file just leaves the collection's variables
+            if let Ok(content) = std::fs::read_to_string(std::path::Path::new(&worktree_path).join(env_file)) {
+                vars.extend(env::parse_env(&content));
+            }
+        }
+        let request = http_client::resolve_request(&request, &vars)?;
+        tracing::info!("HTTP {} {}", request.method, request.url);
+        http_client::execute(&request).await
+    }
+    .await;
+
+    let (response, error) = match result {
+        Ok(response) => (Some(response), None),
+        Err(e) => (None, Some(e)),
+    };
+    let mut state = get_app_state().write().await;
+    reduce(&mut state, Action::SetHttpResponse {
+        project_path,
+        collection: collection_id.to_string(),
+        request: request_name.to_string(),
+        response,
+        error,
+    });
+}
+
+/// Re-import the compose files remembered for a project (missing or broken
+/// files are skipped)
+async fn load_project_compose_files(project_path: &str) {
+    let files = {
+        let state = get_app_state().read().await;
+        state
+            .projects
+            .iter()
+            .find(|p| p.path == project_path)
+            .map(|p| p.compose_files.clone())
+            .unwrap_or_default()
+    };
+    if files.is_empty() {
+        return;
+    }
+    let Ok(dm) = get_docker_manager().await else {
+        return;
+    };
+    for file in files {
+        if let Err(e) = dm.import_compose(std::path::Path::new(&file)).await {
+            tracing::warn!("Failed to re-import compose file {}: {}", file, e);
+        }
+    }
+}
+
+async fn load_project_service_groups(project_path: &str) {
+    let (groups, error) = match service_groups::load_groups(std::path::Path::new(project_path)) {
+        Ok(groups) => (groups, None),
+        Err(e) => {
+            tracing::warn!("Failed to load service groups: {}", e);
+            (Vec::new(), Some(e))
+        }
+    };
+    let mut state = get_app_state().write().await;
+    reduce(
+        &mut state,
+        Action::SetServiceGroups {
+            project_path: project_path.to_string(),
+            groups,
+            error,
+        },
+    );
+}
+
+/// Start (in dependency order, each one healthy before the next) or stop (in
+/// reverse order) the services of one of the active project's groups
+async fn run_service_group(name: &str, start: bool) {
+    let order = {
+        let state = get_app_state().read().await;
+        let group = state
+            .active_project()
+            .and_then(|p| p.service_groups.iter().find(|g| g.name == name));
+        match group {
+            Some(group) if start => service_groups::start_order(group, &state.docker.services),
+            Some(group) => service_groups::stop_order(group, &state.docker.services),
+            None => Err(format!("Unknown service group: {}", name)),
+        }
+    };
+
+    let result = match (order, get_docker_manager().await) {
+        (Err(e), _) => Err(e),
+        (_, Err(e)) => Err(e.to_string()),
+        (Ok(order), Ok(dm)) => {
+            let mut result = Ok(());
+            for service_id in order {
+                let running = dm.container_state(&service_id).await.ok().flatten().as_deref() == Some("running");
+                let step = if start && !running {
+                    match dm.start_service(&service_id).await {
+                        Ok(()) => dm.wait_until_healthy(&service_id).await,
+                        Err(e) => Err(e),
+                    }
+                } else if !start && running {
+                    dm.stop_service(&service_id).await
+                } else {
+                    Ok(())
+                };
+                if let Err(e) = step {
+                    result = Err(format!("{}: {}", service_id, e));
+                    break;
+                }
+            }
+            result
+        }
+    };
+
+    if let Err(e) = result {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::SetError {
+            code: if start { "SERVICE_GROUP_START_ERROR" } else { "SERVICE_GROUP_STOP_ERROR" }.to_string(),
+            message: e,
+            context: Some(format!("{}: {}", if start { "StartServiceGroup" } else { "StopServiceGroup" }, name)),
+        });
+    }
+    refresh_docker_services_internal().await;
+}
+
+/// Refresh justfile commands for the active worktree
+async fn refresh_justfile_commands() {
+    let worktree_path = {
+        let state = get_app_state().read().await;
+        state
+            .active_project()
+            .and_then(|p| p.active_worktree())
+            .map(|w| w.path.clone())
+    };
+
+    if let Some(path) = worktree_path {
+        let justfile_path = std::path::Path::new(&path).join("justfile");
         if justfile_path.exists() {
             match justfile::parse_justfile(&justfile_path.to_string_lossy()) {
                 Ok(commands) => {
@@ -740,6 +3985,7 @@ async fn refresh_justfile_commands() {
                             name: c.name,
                             description: c.description,
                             recipe: c.recipe,
+                            parameters: c.parameters.into_iter().map(Into::into).collect(),
                         })
                         .collect();
                     let mut state = get_app_state().write().await;
@@ -750,11 +3996,663 @@ async fn refresh_justfile_commands() {
                     reduce(&mut state, Action::SetTasksError { error: Some(e) });
                 }
             }
-        } else {
-            let mut state = get_app_state().write().await;
-            reduce(&mut state, Action::SetJustfileCommands { commands: Vec::new() });
+        } else {
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetJustfileCommands { commands: Vec::new() });
+        }
+    }
+}
+
+/// Load tasks from every detected provider (or only `provider`) for the active worktree
+async fn refresh_tasks(provider: Option<tasks::TaskProviderKind>) {
+    let worktree_path = {
+        let state = get_app_state().read().await;
+        state
+            .active_project()
+            .and_then(|p| p.active_worktree())
+            .map(|w| w.path.clone())
+    };
+    let Some(path) = worktree_path else {
+        return;
+    };
+
+    let (found, errors) = tasks::load_tasks(std::path::Path::new(&path), provider);
+    let mut state = get_app_state().write().await;
+    reduce(&mut state, Action::SetTasks { provider, tasks: found });
+    if !errors.is_empty() {
+        reduce(&mut state, Action::SetTasksError { error: Some(errors.join("\n")) });
+    }
+}
+
+/// Queue a task run and start it if the concurrency limit allows. Returns the run ID.
+/// Output and status are tracked under `task_key` in the worktree at `cwd`
+/// (or the active worktree when `cwd` is not a worktree root).
+async fn enqueue_task(task_key: String, program: &str, argv: Vec<String>, cwd: &str) -> String {
+    let queue = get_task_queue();
+    let spec = task_queue::TaskSpec {
+        id: queue.next_id(),
+        program: program.to_string(),
+        argv,
+        cwd: cwd.to_string(),
+    };
+
+    {
+        let mut state = get_app_state().write().await;
+        let is_worktree = state.projects.iter().flat_map(|p| &p.worktrees).any(|w| w.path == cwd);
+        let worktree_path = if is_worktree {
+            cwd.to_string()
+        } else {
+            state
+                .active_project()
+                .and_then(|p| p.active_worktree())
+                .map(|w| w.path.clone())
+                .unwrap_or_else(|| cwd.to_string())
+        };
+        queue.set_max_parallel(
+            state
+                .global_settings
+                .task_max_parallel
+                .map_or(task_queue::DEFAULT_MAX_PARALLEL, |n| n as usize),
+        );
+        let run = task_queue::TaskRun {
+            id: spec.id.clone(),
+            task_key,
+            command: format!("{} {}", program, spec.argv.join(" ")).trim_end().to_string(),
+            worktree_path,
+            status: task_queue::TaskRunStatus::Queued,
+            queued_at: chrono::Utc::now().to_rfc3339(),
+            exit_code: None,
+            duration_ms: None,
+            output_tail: Vec::new(),
+        };
+        reduce(&mut state, Action::QueueTaskRun { run });
+    }
+
+    let id = spec.id.clone();
+    queue.enqueue(spec);
+    pump_task_queue();
+    id
+}
+
+/// How often the scheduler checks for due schedules
+const SCHEDULER_TICK: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// Load a project's .rstn/schedule.toml into state and make sure the scheduler runs
+async fn load_project_schedule(project_path: &str) {
+    let (entries, error) =
+        match schedule::load_schedule(std::path::Path::new(project_path), chrono::Utc::now()) {
+            Ok(entries) => (entries, None),
+            Err(e) => {
+                tracing::warn!("Failed to load schedule: {}", e);
+                (Vec::new(), Some(e))
+            }
+        };
+    {
+        let mut state = get_app_state().write().await;
+        reduce(
+            &mut state,
+            Action::SetSchedule {
+                project_path: project_path.to_string(),
+                entries,
+                error,
+            },
+        );
+    }
+
+    SCHEDULER_STARTED.call_once(|| {
+        tokio::spawn(async {
+            let mut interval = tokio::time::interval(SCHEDULER_TICK);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                interval.tick().await;
+                run_due_schedules().await;
+            }
+        });
+    });
+}
+
+/// Sample machine resources and rstn's own usage forever (see `system`)
+async fn run_system_monitor() {
+    let mut monitor = system::SystemMonitor::new();
+    let mut interval = tokio::time::interval(system::SAMPLE_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        interval.tick().await;
+        let roots = vec![
+            (system::ProcessGroup::ClaudeCli, get_claude_processes().pids()),
+            (system::ProcessGroup::Terminals, get_terminal_manager().local_pids().await),
+        ];
+        let extra: Vec<system::GroupUsage> = container_usage().await.into_iter().collect();
+
+        // Process table walks block; keep them off the async workers
+        let sampled = tokio::task::spawn_blocking(move || {
+            let stats = monitor.sample(&roots, extra);
+            (monitor, stats)
+        })
+        .await;
+        let stats = match sampled {
+            Ok((returned, stats)) => {
+                monitor = returned;
+                stats
+            }
+            Err(e) => {
+                tracing::warn!("System monitor stopped: {}", e);
+                return;
+            }
+        };
+
+        {
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetSystemStats { stats });
+        }
+        notify_state_update().await;
+    }
+}
+
+/// Combined usage of the running rstn-managed containers (None without Docker)
+async fn container_usage() -> Option<system::GroupUsage> {
+    let running: Vec<String> = {
+        let state = get_app_state().read().await;
+        if state.docker.docker_available != Some(true) {
+            return None;
+        }
+        state
+            .docker
+            .services
+            .iter()
+            .filter(|s| s.is_rstn_managed && s.status == app_state::ServiceStatus::Running)
+            .map(|s| s.id.clone())
+            .collect()
+    };
+    let dm = get_docker_manager().await.ok()?;
+    let samples = futures_util::future::join_all(running.iter().map(|id| dm.stats_snapshot(id))).await;
+    let samples: Vec<_> = samples.into_iter().filter_map(Result::ok).collect();
+    Some(system::GroupUsage {
+        group: system::ProcessGroup::Containers,
+        count: samples.len() as u32,
+        cpu_percent: samples.iter().map(|s| s.cpu_percent).sum(),
+        memory_bytes: samples.iter().map(|s| s.memory_usage).sum(),
+    })
+}
+
+/// Run every due schedule of the open, unpaused projects
+async fn run_due_schedules() {
+    let now = chrono::Utc::now();
+    // (project path, worktree to run in, entry)
+    let due: Vec<(String, String, schedule::ScheduleEntry)> = {
+        let state = get_app_state().read().await;
+        state
+            .projects
+            .iter()
+            .filter(|p| !p.schedule.paused)
+            .flat_map(|p| {
+                let cwd = p.active_worktree().map_or_else(|| p.path.clone(), |w| w.path.clone());
+                p.schedule
+                    .entries
+                    .iter()
+                    .filter(|e| e.is_due(now))
+                    .map(move |e| (p.path.clone(), cwd.clone(), e.clone()))
+            })
+            .collect()
+    };
+    if due.is_empty() {
+        return;
+    }
+
+    for (project_path, cwd, entry) in due {
+        let task_id = match &entry.job {
+            schedule::ScheduledJob::Task { provider, name, args } => {
+                let argv = tasks::provider(*provider).command_args(name, args);
+                Some(enqueue_task(tasks::task_id(*provider, name), provider.program(), argv, &cwd).await)
+            }
+            schedule::ScheduledJob::RefreshDockerServices => {
+                refresh_docker_services_internal().await;
+                None
+            }
+        };
+
+        let mut state = get_app_state().write().await;
+        reduce(
+            &mut state,
+            Action::MarkScheduleRun {
+                project_path,
+                id: entry.id.clone(),
+                ran_at: now.to_rfc3339(),
+                next_run_at: entry.next_run_after(now),
+                task_id,
+            },
+        );
+    }
+    notify_state_update().await;
+}
+
+/// Start as many queued tasks as the concurrency limit allows
+fn pump_task_queue() {
+    for (spec, cancel) in get_task_queue().take_startable() {
+        tokio::spawn(run_queued_task(spec, cancel));
+    }
+}
+
+/// Run a dequeued task, streaming stdout/stderr into its run record until it
+/// exits or is cancelled, then start the next queued task.
+async fn run_queued_task(spec: task_queue::TaskSpec, mut cancel: tokio::sync::oneshot::Receiver<()>) {
+    use task_queue::TaskRunStatus;
+
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::StartTaskRun { task_id: spec.id.clone() });
+    }
+    notify_state_update().await;
+
+    let started = std::time::Instant::now();
+    let (status, exit_code) = match tasks::spawn_task(&spec.program, &spec.argv, &spec.cwd) {
+        Ok(mut child) => {
+            let mut lines = tasks::output_lines(&mut child);
+            let mut last_notify = std::time::Instant::now();
+            let mut cancelled = false;
+            loop {
+                tokio::select! {
+                    line = lines.recv() => {
+                        let Some(line) = line else { break };
+                        {
+                            let mut state = get_app_state().write().await;
+                            reduce(&mut state, Action::AppendTaskRunOutput { task_id: spec.id.clone(), line });
+                        }
+                        // Chatty tasks print many lines a second; throttle UI updates
+                        if last_notify.elapsed() >= std::time::Duration::from_millis(100) {
+                            last_notify = std::time::Instant::now();
+                            notify_state_update().await;
+                        }
+                    }
+                    _ = &mut cancel => {
+                        // Don't wait for output: grandchildren may keep the pipes open
+                        cancelled = true;
+                        if let Err(e) = child.start_kill() {
+                            tracing::warn!("Failed to kill task {}: {}", spec.id, e);
+                        }
+                        break;
+                    }
+                }
+            }
+
+            let exit = child.wait().await;
+            let exit_code = exit.as_ref().ok().and_then(|e| e.code());
+            let status = match exit {
+                _ if cancelled => TaskRunStatus::Cancelled,
+                Ok(exit) if exit.success() => TaskRunStatus::Success,
+                Ok(_) => TaskRunStatus::Error,
+                Err(e) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(
+                        &mut state,
+                        Action::AppendTaskRunOutput {
+                            task_id: spec.id.clone(),
+                            line: format!("Failed to wait for {}: {}", spec.program, e),
+                        },
+                    );
+                    TaskRunStatus::Error
+                }
+            };
+            (status, exit_code)
+        }
+        Err(e) => {
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::AppendTaskRunOutput { task_id: spec.id.clone(), line: e });
+            (TaskRunStatus::Error, None)
+        }
+    };
+
+    get_task_queue().finish(&spec.id);
+    let duration_ms = Some(started.elapsed().as_millis() as u64);
+    let command = {
+        let mut state = get_app_state().write().await;
+        reduce(
+            &mut state,
+            Action::FinishTaskRun {
+                task_id: spec.id.clone(),
+                status,
+                exit_code,
+                duration_ms,
+            },
+        );
+        state
+            .projects
+            .iter()
+            .flat_map(|p| &p.worktrees)
+            .flat_map(|w| &w.tasks.runs)
+            .find(|r| r.id == spec.id)
+            .map(|r| r.command.clone())
+    };
+    notify_state_update().await;
+    pump_task_queue();
+
+    let notification = command.and_then(|command| desktop_notifications::task_finished(&command, status, exit_code, duration_ms));
+    if let Some(notification) = notification {
+        notify_desktop(DesktopNotificationEvent::TaskCompleted, notification).await;
+    }
+}
+
+/// Queue a Claude implementation run of a change in a worktree and start it
+/// if a slot is free
+async fn queue_agent_run(worktree_path: String, change_id: String) {
+    {
+        let mut state = get_app_state().write().await;
+        let worktree = state.projects.iter().flat_map(|p| &p.worktrees).find(|w| w.path == worktree_path);
+        let change = worktree.and_then(|w| w.changes.changes.iter().find(|c| c.id == change_id));
+        let already_queued = state
+            .agent_runs
+            .runs
+            .iter()
+            .any(|r| r.change_id == change_id && r.worktree_path == worktree_path && !r.status.is_finished());
+        let problem = match change {
+            None => Some(format!("Change {} not found in {}", change_id, worktree_path)),
+            Some(change) if change.plan.is_none() => Some(format!("{} has no plan yet", change.name)),
+            Some(change) if matches!(change.status, app_state::ChangeStatus::Implementing | app_state::ChangeStatus::Testing) => {
+                Some(format!("{} is already being implemented", change.name))
+            }
+            Some(_) if already_queued => Some(format!("{} is already queued", change_id)),
+            Some(_) => None,
+        };
+        if let Some(message) = problem {
+            reduce(&mut state, Action::SetError {
+                code: "AGENT_RUN_ERROR".to_string(),
+                message,
+                context: Some(format!("QueueAgentRun: {}", change_id)),
+            });
+            drop(state);
+            notify_state_update().await;
+            return;
+        }
+
+        let run = agent_runs::AgentRun {
+            id: format!("agent-{}", uuid::Uuid::new_v4()),
+            branch: worktree.map(|w| w.branch.clone()).unwrap_or_default(),
+            change_name: change.map(|c| c.name.clone()).unwrap_or_default(),
+            worktree_path,
+            change_id,
+            status: agent_runs::AgentRunStatus::Queued,
+            queued_at: chrono::Utc::now().to_rfc3339(),
+            started_at: None,
+            finished_at: None,
+            output_chars: 0,
+            error: None,
+            approval: None,
+        };
+        reduce(&mut state, Action::AddAgentRun { run });
+    }
+    notify_state_update().await;
+    pump_agent_runs().await;
+}
+
+/// Start as many queued agent runs as the concurrency cap allows. Boxed
+/// because finished runs call it again to start the next one.
+fn pump_agent_runs() -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>> {
+    Box::pin(async {
+        let started = {
+            let mut state = get_app_state().write().await;
+            let ids = agent_runs::startable(&state.agent_runs.runs, state.agent_runs.max_concurrent);
+            for run_id in &ids {
+                reduce(&mut state, Action::StartAgentRun { run_id: run_id.clone() });
+            }
+            ids.into_iter()
+                .filter_map(|id| {
+                    let change_id = state.agent_runs.runs.iter().find(|r| r.id == id)?.change_id.clone();
+                    Some((id, change_id))
+                })
+                .collect::<Vec<_>>()
+        };
+        if started.is_empty() {
+            return;
+        }
+        notify_state_update().await;
+        for (run_id, change_id) in started {
+            save_change_status(&change_id).await;
+            tokio::spawn(run_agent(run_id));
+        }
+    })
+}
+
+/// Implement a run's change, record how it ended, then start the next queued run
+async fn run_agent(run_id: String) {
+    let target = {
+        let state = get_app_state().read().await;
+        state.agent_runs.runs.iter().find(|r| r.id == run_id).and_then(|run| {
+            let change = state
+                .projects
+                .iter()
+                .flat_map(|p| &p.worktrees)
+                .find(|w| w.path == run.worktree_path)?
+                .changes
+                .changes
+                .iter()
+                .find(|c| c.id == run.change_id)?
+                .clone();
+            Some((run.worktree_path.clone(), change))
+        })
+    };
+    let Some((worktree_path, change)) = target else {
+        tracing::warn!("Agent run {}: change no longer exists", run_id);
+        {
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::FinishAgentRun {
+                run_id,
+                status: agent_runs::AgentRunStatus::Failed,
+                error: Some("Change no longer exists".to_string()),
+            });
+        }
+        notify_state_update().await;
+        pump_agent_runs().await;
+        return;
+    };
+
+    let outcome = implement_change_in_worktree(&run_id, &worktree_path, &change).await;
+    let cancelled = {
+        let state = get_app_state().read().await;
+        state.agent_runs.runs.iter().any(|r| r.id == run_id && r.status == agent_runs::AgentRunStatus::Cancelled)
+    };
+    if cancelled {
+        save_change_status(&change.id).await;
+        notify_state_update().await;
+        pump_agent_runs().await;
+        return;
+    }
+    let (status, error, event, notification) = match outcome {
+        Ok(()) => (
+            agent_runs::AgentRunStatus::AwaitingReview,
+            None,
+            DesktopNotificationEvent::ClaudeFinished,
+            desktop_notifications::claude_finished("implementation", &change.name),
+        ),
+        Err(error) => {
+            tracing::error!("Agent run {}: {}", run_id, error);
+            let notification = desktop_notifications::implementation_failed(&change.name, &error);
+            (agent_runs::AgentRunStatus::Failed, Some(error), DesktopNotificationEvent::ImplementationFailed, notification)
+        }
+    };
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::FinishAgentRun { run_id, status, error });
+    }
+    save_change_status(&change.id).await;
+    notify_state_update().await;
+    notify_desktop(event, notification).await;
+    pump_agent_runs().await;
+}
+
+/// Write a run's buffered output into its change
+async fn flush_agent_output(run_id: &str, content: String) {
+    {
+        let mut state = get_app_state().write().await;
+        reduce(&mut state, Action::AppendAgentRunOutput { run_id: run_id.to_string(), content });
+    }
+    notify_state_update().await;
+}
+
+/// How long an implementation session may run (settings override the default)
+async fn implementation_timeout() -> std::time::Duration {
+    get_app_state()
+        .read()
+        .await
+        .global_settings
+        .implementation_timeout_secs
+        .map_or(claude_cli::IMPLEMENTATION_TIMEOUT, |secs| std::time::Duration::from_secs(secs.into()))
+}
+
+/// Answer a tool permission request of a guarded Claude session (`key` in
+/// the permission bridge): calls that keep to the guardrails are allowed
+/// right away, otherwise the approval to ask the user for is returned
+async fn screen_permission_request(
+    key: &str,
+    rails: &guardrails::Guardrails,
+    cwd: &std::path::Path,
+    request: claude_cli::PermissionRequest,
+) -> Result<Option<agent_runs::AgentRunApproval>, String> {
+    let approval = agent_runs::AgentRunApproval::for_request(rails, cwd, &request);
+    let request_id = request.request_id.clone();
+    get_permission_bridge().track(key, request);
+    if approval.is_none() {
+        get_permission_bridge().respond(&request_id, &claude_cli::PermissionDecision::Allow).await?;
+    }
+    Ok(approval)
+}
+
+/// Let Claude implement a change's plan in a (possibly inactive) worktree,
+/// then run the worktree's hooks and the project's tests there
+async fn implement_change_in_worktree(run_id: &str, worktree_path: &str, change: &app_state::Change) -> Result<(), String> {
+    let cwd = std::path::Path::new(worktree_path);
+    let plan = change.plan.as_deref().ok_or_else(|| format!("{} has no plan", change.name))?;
+    let profile_rails = get_app_state().read().await.global_settings.guardrails.clone();
+    let rails = guardrails::load_or(cwd, profile_rails.as_ref())?;
+    snapshot_before_implementation(worktree_path, change).await;
+    let constitution_content = constitution::read_constitution(cwd).unwrap_or_default();
+    let context_content = context::read_context_combined(cwd).unwrap_or_default();
+    let prompt = implementation_prompt(change, plan, &constitution_content, &context_content);
+
+    let mut session = start_claude_session("implementation", cwd, &prompt).await;
+    let spawned = claude_cli::spawn_claude_with_tools(&prompt, cwd, &rails.allowed_tools(), active_claude_model().await.as_deref()).await;
+    let (mut child, stdin) = spawned.map_err(|e| {
+        session.fail(e.to_string());
+        format!("Failed to spawn Claude CLI: {}", e)
+    })?;
+    get_permission_bridge().register(run_id, stdin);
+    if let Some(stderr) = child.stderr.take() {
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if !line.trim().is_empty() {
+                    tracing::debug!("[Claude CLI stderr] {}", line.trim());
+                }
+            }
+        });
+    }
+
+    // Output is batched so parallel runs take turns on the state lock
+    let mut batch = agent_runs::OutputBatch::new();
+    let process_key = agent_run_process_key(run_id);
+    let total_timeout = implementation_timeout().await;
+    let result = match claude_cli::ClaudeEventStream::new(&mut child) {
+        Ok(mut stream) => {
+            // Registered so CancelAgentRun can kill it
+            get_claude_processes().register(&process_key, child);
+            let start_time = std::time::Instant::now();
+            // Time spent waiting for the user doesn't count against the timeouts
+            let mut approval_started: Option<std::time::Instant> = None;
+            let mut approval_wait = std::time::Duration::ZERO;
+            loop {
+                if start_time.elapsed().saturating_sub(approval_wait) > total_timeout {
+                    break Err(format!("Implementation exceeded {} minute timeout", total_timeout.as_secs().div_ceil(60)));
+                }
+                let next_event = if approval_started.is_some() {
+                    Ok(stream.next_event().await)
+                } else {
+                    tokio::time::timeout(claude_cli::EVENT_TIMEOUT, stream.next_event()).await
+                };
+                // Killed via CancelAgentRun - the run is already marked cancelled
+                if !get_claude_processes().is_running(&process_key) {
+                    break Err("Agent run cancelled".to_string());
+                }
+                match next_event {
+                    Ok(Some(Ok(event))) => {
+                        session.observe(&event);
+                        record_claude_usage(&event, "implementation").await;
+                        if let Some(started) = approval_started.take() {
+                            approval_wait += started.elapsed();
+                        }
+
+                        // Tool calls outside --allowedTools: allow unless a guardrail objects
+                        if let Some(request) = claude_cli::extract_permission_request(&event) {
+                            match screen_permission_request(run_id, &rails, cwd, request).await {
+                                Err(e) => break Err(e),
+                                Ok(None) => {}
+                                Ok(Some(approval)) => {
+                                    approval_started = Some(std::time::Instant::now());
+                                    if let Some(content) = batch.take() {
+                                        flush_agent_output(run_id, content).await;
+                                    }
+                                    {
+                                        let mut state = get_app_state().write().await;
+                                        reduce(&mut state, Action::AddNotification {
+                                            message: format!("{} needs approval: {}", change.name, approval.reason),
+                                            notification_type: actions::NotificationTypeData::Warning,
+                                        });
+                                        reduce(&mut state, Action::RequestAgentRunApproval {
+                                            run_id: run_id.to_string(),
+                                            approval,
+                                        });
+                                    }
+                                    notify_state_update().await;
+                                }
+                            }
+                            continue;
+                        }
+
+                        if let Some(text_chunk) = claude_cli::extract_text_delta(&event) {
+                            batch.push(text_chunk);
+                        }
+                        if let Some(text_content) = claude_cli::extract_assistant_text(&event) {
+                            batch.push(&text_content);
+                        }
+                        if let Some(content) = batch.take_due() {
+                            flush_agent_output(run_id, content).await;
+                        }
+                        if claude_cli::is_message_stop(&event) {
+                            break Ok(());
+                        }
+                    }
+                    Ok(Some(Err(e))) => tracing::error!("Agent run {}: Event parse error: {}", run_id, e),
+                    Ok(None) => break Ok(()),
+                    Err(_) => break Err("No response from Claude CLI for 30 seconds".to_string()),
+                }
+            }
+        }
+        Err(e) => {
+            let _ = child.start_kill();
+            let _ = child.wait().await;
+            Err(format!("Failed to create event stream: {}", e))
+        }
+    };
+    get_permission_bridge().close(run_id);
+    if let Some(content) = batch.take() {
+        flush_agent_output(run_id, content).await;
+    }
+    // Stop the CLI if the run failed, then wait for it to exit
+    if let Some(mut child) = get_claude_processes().take(&process_key) {
+        if result.is_err() {
+            let _ = child.start_kill();
         }
+        let _ = child.wait().await;
     }
+    result?;
+
+    let hook_summary = run_post_implementation_hooks(worktree_path, change).await;
+    if !hook_summary.is_empty() {
+        flush_agent_output(run_id, hook_summary).await;
+    }
+    let Some((test_command, coverage)) = implementation_test_command(worktree_path).await else {
+        return Ok(());
+    };
+    flush_agent_output(run_id, format!("\n\n$ {}\n", test_command.display())).await;
+    run_implementation_tests(&test_command, coverage.as_ref(), worktree_path, change).await
 }
 
 /// Refresh worktrees for a given project path
@@ -762,8 +4660,11 @@ async fn refresh_worktrees_for_path(project_path: &str) {
     match worktree::list_worktrees(project_path) {
         Ok(worktrees) => {
             let worktree_data: Vec<actions::WorktreeData> = worktrees;
-            let mut state = get_app_state().write().await;
-            reduce(&mut state, Action::SetWorktrees { worktrees: worktree_data });
+            {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetWorktrees { worktrees: worktree_data });
+            }
+            sync_worktree_watchers().await;
         }
         Err(e) => {
             let mut state = get_app_state().write().await;
@@ -777,6 +4678,81 @@ async fn refresh_worktrees_for_path(project_path: &str) {
 }
 
 /// Convert internal FileEntry to action data type
+/// Project-relative path of a file, for the comments table
+fn comment_file_path(root: &str, path: &str) -> String {
+    std::path::Path::new(path)
+        .strip_prefix(root)
+        .unwrap_or(std::path::Path::new(path))
+        .to_string_lossy()
+        .to_string()
+}
+
+fn comment_data(row: db::CommentRow) -> actions::CommentData {
+    actions::CommentData {
+        id: row.id,
+        content: row.content,
+        author: row.author,
+        created_at: row.created_at,
+        line_number: row.line_number,
+    }
+}
+
+/// Write a file comment undo/redo snapshot back to the database,
+/// then reload the comments if the file is selected in the Explorer
+async fn restore_comment_slice(slice: undo::UndoSlice) -> napi::Result<()> {
+    let undo::UndoSlice::FileComment { project_path, file_path, comment_id, comment } = slice else {
+        return Ok(());
+    };
+    let Some(db_mgr) = get_db_manager() else {
+        return Ok(());
+    };
+    let project_id = persistence::get_project_id(&project_path);
+    let result = match comment {
+        Some(c) => db_mgr.restore_comment(
+            &project_id,
+            &file_path,
+            &db::CommentRow {
+                id: c.id,
+                content: c.content,
+                author: c.author,
+                created_at: c.created_at,
+                line_number: c.line_number,
+            },
+        ),
+        None => db_mgr.delete_comment(&project_id, &comment_id),
+    };
+    if let Err(e) = result {
+        tracing::warn!("Failed to restore file comment {}: {}", comment_id, e);
+        return Ok(());
+    }
+
+    let path = std::path::Path::new(&project_path)
+        .join(&file_path)
+        .to_string_lossy()
+        .to_string();
+    let explorer = {
+        let state = get_app_state().read().await;
+        state
+            .active_project()
+            .filter(|p| p.path == project_path)
+            .and_then(|p| p.active_worktree())
+            .map(|w| (w.explorer.selected_path.clone(), w.explorer.current_path.clone()))
+    };
+    let Some((selected_path, current_path)) = explorer else {
+        return Ok(());
+    };
+    if selected_path.as_deref() == Some(path.as_str()) {
+        Box::pin(handle_async_action(Action::SelectFile { path: Some(path.clone()) })).await?;
+    }
+    let parent = std::path::Path::new(&path)
+        .parent()
+        .map(|p| p.to_string_lossy().to_string());
+    if parent.is_some_and(|dir| dir == current_path) {
+        Box::pin(handle_async_action(Action::ExploreDir { path: current_path })).await?;
+    }
+    Ok(())
+}
+
 fn convert_to_action_entry(e: app_state::FileEntry) -> actions::FileEntryData {
     actions::FileEntryData {
         name: e.name,
@@ -818,6 +4794,48 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             // Check for port conflict first
             match docker_check_port_conflict(service_id.clone()).await {
                 Ok(Some(conflict_info)) => {
+                    let strategy = {
+                        let state = get_app_state().read().await;
+                        state
+                            .active_project()
+                            .map(|p| p.env_config.auto_resolve_ports)
+                            .unwrap_or_default()
+                    };
+
+                    if strategy.should_auto_resolve(conflict_info.is_rstn_managed) {
+                        // Retry on the suggested port instead of prompting
+                        let port = conflict_info.suggested_port as u16;
+                        {
+                            let mut state = get_app_state().write().await;
+                            reduce(&mut state, Action::StartDockerServiceWithPort {
+                                service_id: service_id.clone(),
+                                port,
+                            });
+                        }
+                        match docker_start_service_with_port(service_id.clone(), port).await {
+                            Ok(()) => {
+                                watch_service_health(service_id.clone()).await;
+                                let mut state = get_app_state().write().await;
+                                reduce(&mut state, Action::AddNotification {
+                                    message: format!(
+                                        "Port {} is in use by {}; started {} on port {}",
+                                        conflict_info.requested_port, conflict_info.container_name, service_id, port
+                                    ),
+                                    notification_type: actions::NotificationTypeData::Info,
+                                });
+                            }
+                            Err(e) => {
+                                let mut state = get_app_state().write().await;
+                                reduce(&mut state, Action::SetError {
+                                    code: "DOCKER_START_ERROR".to_string(),
+                                    message: e.to_string(),
+                                    context: Some(format!("StartDockerService: {} on port {}", service_id, port)),
+                                });
+                            }
+                        }
+                        return Ok(());
+                    }
+
                     // Port conflict detected - set pending conflict for UI to handle
                     let conflict_data = actions::PortConflictData {
                         requested_port: conflict_info.requested_port as u16,
@@ -827,6 +4845,13 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                             image: conflict_info.container_image,
                             is_rstn_managed: conflict_info.is_rstn_managed,
                         },
+                        conflicting_process: conflict_info.process.map(|p| actions::ConflictingProcessData {
+                            pid: p.pid,
+                            name: p.name,
+                            exe: p.exe,
+                            user: p.user,
+                            command: p.command,
+                        }),
                         suggested_port: conflict_info.suggested_port as u16,
                     };
                     let mut state = get_app_state().write().await;
@@ -839,7 +4864,7 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                     // No conflict, proceed with start
                     match docker_start_service(service_id.clone()).await {
                         Ok(()) => {
-                            refresh_docker_services_internal().await;
+                            watch_service_health(service_id.clone()).await;
                         }
                         Err(e) => {
                             let mut state = get_app_state().write().await;
@@ -879,11 +4904,172 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             }
         }
 
+        Action::DockerComposeUp { ref project_name } => {
+            let result = match get_docker_manager().await {
+                Ok(dm) => dm.compose_up(project_name).await,
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(e) = result {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetError {
+                    code: "DOCKER_COMPOSE_UP_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("DockerComposeUp: {}", project_name)),
+                });
+            }
+            refresh_docker_services_internal().await;
+        }
+
+        Action::DockerComposeDown { ref project_name } => {
+            let result = match get_docker_manager().await {
+                Ok(dm) => dm.compose_down(project_name).await,
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(e) = result {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetError {
+                    code: "DOCKER_COMPOSE_DOWN_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("DockerComposeDown: {}", project_name)),
+                });
+            }
+            refresh_docker_services_internal().await;
+        }
+
+        Action::LoadServiceGroups => {
+            let project_path = {
+                let state = get_app_state().read().await;
+                state.active_project().map(|p| p.path.clone())
+            };
+            if let Some(project_path) = project_path {
+                load_project_service_groups(&project_path).await;
+            }
+        }
+
+        Action::AssignServiceToGroup { .. } | Action::DeleteServiceGroup { .. } => {
+            let project = {
+                let state = get_app_state().read().await;
+                state
+                    .active_project()
+                    .filter(|p| p.service_groups_error.is_none())
+                    .map(|p| (p.path.clone(), p.service_groups.clone()))
+            };
+            if let Some((project_path, groups)) = project {
+                if let Err(e) = service_groups::save_groups(std::path::Path::new(&project_path), &groups) {
+                    {
+                        let mut state = get_app_state().write().await;
+                        reduce(&mut state, Action::SetError {
+                            code: "SERVICE_GROUPS_SAVE_ERROR".to_string(),
+                            message: e,
+                            context: Some(project_path.clone()),
+                        });
+                    }
+                    // Show the groups that are actually on disk
+                    load_project_service_groups(&project_path).await;
+                }
+            }
+        }
+
+        Action::StartServiceGroup { ref name } => {
+            run_service_group(name, true).await;
+        }
+
+        Action::LoadHttpRequests => {
+            let project_path = {
+                let state = get_app_state().read().await;
+                state.active_project().map(|p| p.path.clone())
+            };
+            if let Some(project_path) = project_path {
+                load_project_http_requests(&project_path).await;
+            }
+        }
+
+        Action::RunHttpRequest { ref collection, ref request, ref env_file } => {
+            run_http_request(collection, request, env_file.as_deref()).await;
+        }
+
+        Action::StopServiceGroup { ref name } => {
+            run_service_group(name, false).await;
+        }
+
+        Action::OpenDockerExec { ref service_id, ref cmd, cols, rows } => {
+            let manager = get_terminal_manager();
+            let existing = {
+                let state = get_app_state().read().await;
+                state.docker.exec_sessions.get(service_id).cloned()
+            };
+            // Opening the console again replaces its session
+            if let Some(session_id) = existing {
+                let _ = manager.kill(&session_id).await;
+            }
+
+            let cmd = cmd.clone().unwrap_or_else(|| vec!["sh".to_string()]);
+            let result = match get_docker_manager().await {
+                Ok(dm) => dm.exec_interactive(service_id, &cmd, cols, rows).await,
+                Err(e) => Err(e.to_string()),
+            };
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok(io) => {
+                    let session_id = manager.attach(service_id.clone(), "/".to_string(), io).await;
+                    reduce(&mut state, Action::SetDockerExecSession {
+                        service_id: service_id.clone(),
+                        session_id: Some(session_id),
+                    });
+                }
+                Err(e) => {
+                    reduce(&mut state, Action::SetDockerExecSession {
+                        service_id: service_id.clone(),
+                        session_id: None,
+                    });
+                    reduce(&mut state, Action::SetError {
+                        code: "DOCKER_EXEC_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("OpenDockerExec: {} {}", service_id, cmd.join(" "))),
+                    });
+                }
+            }
+        }
+
+        Action::StartProxy { .. } => {
+            start_proxy().await;
+        }
+
+        Action::StopProxy => {
+            PROXY_SERVER.lock().unwrap_or_else(|e| e.into_inner()).take();
+            let mut state = get_app_state().write().await;
+            let status = app_state::ProxyStatus {
+                ca_cert_path: state.docker.proxy.ca_cert_path.clone(),
+                ..Default::default()
+            };
+            reduce(&mut state, Action::SetProxyStatus { status });
+        }
+
+        Action::SetProxyRoutes { .. } => {
+            let state = get_app_state().read().await;
+            sync_proxy_routes(&state);
+        }
+
+        Action::CloseDockerExec { ref service_id } => {
+            let session_id = {
+                let state = get_app_state().read().await;
+                state.docker.exec_sessions.get(service_id).cloned()
+            };
+            // Session may already be gone (process exited); clear state either way
+            if let Some(session_id) = session_id {
+                let _ = get_terminal_manager().kill(&session_id).await;
+            }
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetDockerExecSession {
+                service_id: service_id.clone(),
+                session_id: None,
+            });
+        }
+
         Action::RestartDockerService { ref service_id } => {
             match docker_restart_service(service_id.clone()).await {
                 Ok(()) => {
-                    // Refresh services to get updated status
-                    refresh_docker_services_internal().await;
+                    watch_service_health(service_id.clone()).await;
                 }
                 Err(e) => {
                     let mut state = get_app_state().write().await;
@@ -933,6 +5119,51 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             }
         }
 
+        Action::FetchDockerDatabases { ref service_id } => {
+            let result = match get_docker_manager().await {
+                Ok(dm) => dm.list_databases(service_id).await,
+                Err(e) => Err(e.to_string()),
+            };
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok(databases) => reduce(&mut state, Action::SetDockerDatabases {
+                    service_id: service_id.clone(),
+                    databases,
+                }),
+                Err(e) => {
+                    reduce(&mut state, Action::SetError {
+                        code: "DOCKER_LIST_DATABASES_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("FetchDockerDatabases: {}", service_id)),
+                    });
+                    reduce(&mut state, Action::SetDockerDatabasesLoading { is_loading: false });
+                }
+            }
+        }
+
+        Action::FetchDockerTables { ref service_id, ref db_name } => {
+            let result = match get_docker_manager().await {
+                Ok(dm) => dm.list_tables(service_id, db_name).await,
+                Err(e) => Err(e.to_string()),
+            };
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok(tables) => reduce(&mut state, Action::SetDockerTables {
+                    service_id: service_id.clone(),
+                    db_name: db_name.clone(),
+                    tables,
+                }),
+                Err(e) => {
+                    reduce(&mut state, Action::SetError {
+                        code: "DOCKER_LIST_TABLES_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("FetchDockerTables: {} in {}", db_name, service_id)),
+                    });
+                    reduce(&mut state, Action::SetDockerDatabasesLoading { is_loading: false });
+                }
+            }
+        }
+
         Action::CreateVhost { ref service_id, ref vhost_name } => {
             match docker_create_vhost(service_id.clone(), vhost_name.clone()).await {
                 Ok(connection_string) => {
@@ -983,6 +5214,10 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
 
                     let mut state = get_app_state().write().await;
                     reduce(&mut state, Action::SetMcpPort { port });
+                    // Show the policy the server loaded (invalid files fail startup)
+                    if let Ok(policy) = mcp_policy::load_policy(std::path::Path::new(&worktree_path)) {
+                        reduce(&mut state, Action::SetMcpPolicy { policy });
+                    }
 
                     // Store config path if generation succeeded
                     match config_result {
@@ -991,46 +5226,14 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                         }
                         Err(e) => {
                             // Non-fatal: Log warning but don't fail server startup
-                            eprintln!("Warning: Failed to generate MCP config: {}", e);
+                            tracing::warn!("Failed to generate MCP config: {}", e);
                         }
                     }
 
                     // Release the write lock before fetching tools
                     drop(state);
 
-                    // Fetch and store available tools
-                    match fetch_mcp_tools().await {
-                        Ok(json_str) => {
-                            match serde_json::from_str::<serde_json::Value>(&json_str) {
-                                Ok(data) => {
-                                    if let Some(tools_array) = data.get("result")
-                                        .and_then(|r| r.get("tools"))
-                                        .and_then(|t| t.as_array())
-                                    {
-                                        let tools: Vec<actions::McpToolData> = tools_array
-                                            .iter()
-                                            .filter_map(|tool| {
-                                                Some(actions::McpToolData {
-                                                    name: tool.get("name")?.as_str()?.to_string(),
-                                                    description: tool.get("description")?.as_str()?.to_string(),
-                                                    input_schema: tool.get("input_schema")?.clone(),
-                                                })
-                                            })
-                                            .collect();
-
-                                        let mut state = get_app_state().write().await;
-                                        reduce(&mut state, Action::UpdateMcpTools { tools });
-                                    }
-                                }
-                                Err(e) => {
-                                    eprintln!("Warning: Failed to parse MCP tools response: {}", e);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            eprintln!("Warning: Failed to fetch MCP tools: {}", e);
-                        }
-                    }
+                    refresh_mcp_tools().await;
                 }
                 Err(e) => {
                     let mut state = get_app_state().write().await;
@@ -1070,33 +5273,170 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                     reduce(&mut state, Action::SetMcpError { error: e });
                 }
             }
-        }
-
-        Action::LoadJustfileCommands | Action::RefreshJustfile => {
-            refresh_justfile_commands().await;
-        }
+        }
+
+        Action::SetMcpPolicy { policy } => {
+            let Some((worktree_id, worktree_path)) = active_worktree_id_and_path().await else {
+                return Ok(());
+            };
+
+            let worktree_root = std::path::Path::new(&worktree_path);
+            if let Err(e) = mcp_policy::save_policy(worktree_root, &policy) {
+                // Keep showing the policy that is actually in effect
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetMcpPolicy {
+                    policy: mcp_policy::load_policy(worktree_root).unwrap_or_default(),
+                });
+                reduce(&mut state, Action::AddNotification {
+                    message: format!("Failed to save MCP policy: {}", e),
+                    notification_type: actions::NotificationTypeData::Error,
+                });
+                return Ok(());
+            }
+
+            if get_mcp_server_manager().set_policy(&worktree_id, policy).await {
+                refresh_mcp_tools().await;
+            }
+        }
+
+        Action::ConnectExternalMcpServers => {
+            let Some((worktree_id, worktree_path)) = active_worktree_id_and_path().await else {
+                return Ok(());
+            };
+            let configs = match mcp_client::load_servers(std::path::Path::new(&worktree_path)) {
+                Ok(configs) => configs,
+                Err(e) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetExternalMcpServers { servers: Vec::new() });
+                    reduce(&mut state, Action::AddNotification {
+                        message: e,
+                        notification_type: actions::NotificationTypeData::Error,
+                    });
+                    return Ok(());
+                }
+            };
+
+            let server_info = |name: &str, config: &mcp_client::McpServerConfig| app_state::ExternalMcpServer {
+                name: name.to_string(),
+                transport: config.transport(),
+                target: config.target(),
+                status: app_state::ExternalMcpStatus::Connecting,
+                error: None,
+                tools: Vec::new(),
+            };
+            {
+                let mut state = get_app_state().write().await;
+                let servers = configs.iter().map(|(name, config)| server_info(name, config)).collect();
+                reduce(&mut state, Action::SetExternalMcpServers { servers });
+            }
+            notify_state_update().await;
+
+            let connections = futures_util::future::join_all(configs.iter().map(|(_, config)| async move {
+                let client = mcp_client::McpClient::connect(config).await?;
+                let tools = client.list_tools().await?;
+                Ok::<_, String>((client, tools))
+            }))
+            .await;
+
+            let mut clients = std::collections::HashMap::new();
+            let mut servers = Vec::new();
+            for ((name, config), connection) in configs.iter().zip(connections) {
+                let mut server = server_info(name, config);
+                match connection {
+                    Ok((client, tools)) => {
+                        server.status = app_state::ExternalMcpStatus::Connected;
+                        server.tools = tools;
+                        clients.insert(name.clone(), Arc::new(client));
+                    }
+                    Err(e) => {
+                        server.status = app_state::ExternalMcpStatus::Error;
+                        server.error = Some(e);
+                    }
+                }
+                servers.push(server);
+            }
+            get_mcp_clients().replace(&worktree_id, clients).await;
+
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetExternalMcpServers { servers });
+        }
+
+        Action::StopLspServers => {
+            if let Some((_, worktree_path)) = active_worktree_id_and_path().await {
+                get_lsp_manager().stop(&worktree_path).await;
+            }
+        }
+
+        Action::DisconnectExternalMcpServers => {
+            if let Some((worktree_id, _)) = active_worktree_id_and_path().await {
+                get_mcp_clients().disconnect(&worktree_id).await;
+            }
+        }
+
+        Action::CallExternalMcpTool { server, tool, arguments } => {
+            // The outcome is recorded in the playground history
+            let _ = playground_call(server, tool, arguments).await;
+        }
+
+        Action::LoadJustfileCommands | Action::RefreshJustfile => {
+            refresh_justfile_commands().await;
+            refresh_tasks(None).await;
+        }
+
+        Action::RunJustCommand { ref name, ref cwd } => {
+            let argv = tasks::provider(tasks::TaskProviderKind::Just).command_args(name, &[]);
+            enqueue_task(name.clone(), "just", argv, cwd).await;
+        }
+
+        Action::RunJustCommandWithArgs { ref name, ref args, ref cwd } => {
+            let argv = tasks::provider(tasks::TaskProviderKind::Just).command_args(name, args);
+            enqueue_task(name.clone(), "just", argv, cwd).await;
+        }
+
+        Action::LoadTasks { provider } => {
+            refresh_tasks(provider).await;
+        }
+
+        Action::RunTask { provider, ref name, ref args, ref cwd } => {
+            let argv = tasks::provider(provider).command_args(name, args);
+            enqueue_task(tasks::task_id(provider, name), provider.program(), argv, cwd).await;
+        }
+
+        Action::LoadSchedule => {
+            let project_path = {
+                let state = get_app_state().read().await;
+                state.active_project().map(|p| p.path.clone())
+            };
+            if let Some(project_path) = project_path {
+                load_project_schedule(&project_path).await;
+            }
+        }
+
+        Action::CancelTask { ref task_id } => match get_task_queue().cancel(task_id) {
+            task_queue::CancelOutcome::Dequeued => {
+                let mut state = get_app_state().write().await;
+                reduce(
+                    &mut state,
+                    Action::FinishTaskRun {
+                        task_id: task_id.clone(),
+                        status: task_queue::TaskRunStatus::Cancelled,
+                        exit_code: None,
+                        duration_ms: None,
+                    },
+                );
+            }
+            // The runner records the cancellation once the process exits
+            task_queue::CancelOutcome::Signalled => {}
+            task_queue::CancelOutcome::NotFound => {
+                tracing::debug!("CancelTask: no queued or running task {}", task_id);
+            }
+        },
 
-        Action::RunJustCommand { ref name, ref cwd } => {
-            match justfile::run_just_command(name, cwd) {
-                Ok(output) => {
-                    let mut state = get_app_state().write().await;
-                    for line in output.lines() {
-                        reduce(&mut state, Action::AppendTaskOutput { line: line.to_string() });
-                    }
-                    reduce(&mut state, Action::SetTaskStatus {
-                        name: name.clone(),
-                        status: actions::TaskStatusData::Success,
-                    });
-                }
-                Err(e) => {
-                    let mut state = get_app_state().write().await;
-                    reduce(&mut state, Action::AppendTaskOutput { line: e.clone() });
-                    reduce(&mut state, Action::SetTaskStatus {
-                        name: name.clone(),
-                        status: actions::TaskStatusData::Error,
-                    });
-                }
-            }
+        Action::SetTaskMaxParallel { max_parallel } => {
+            get_task_queue().set_max_parallel(
+                max_parallel.map_or(task_queue::DEFAULT_MAX_PARALLEL, |n| n as usize),
+            );
+            pump_task_queue();
         }
 
         Action::OpenProject { ref path } => {
@@ -1130,11 +5470,31 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                 
                 // Load justfile commands for the active worktree
                 refresh_justfile_commands().await;
-                
+
+                load_project_schedule(path).await;
+                load_project_service_groups(path).await;
+                load_project_compose_files(path).await;
+                load_project_http_requests(path).await;
+
                 notify_state_update().await;
             }
         }
 
+        Action::SwitchProject { .. } => {
+            // Bring the focused project up to date. Other projects keep
+            // running: their MCP servers and terminals are left alone.
+            let project_path = {
+                let state = get_app_state().read().await;
+                state.active_project().map(|p| p.path.clone())
+            };
+            if let Some(path) = project_path {
+                if std::path::Path::new(&path).exists() {
+                    refresh_worktrees_for_path(&path).await;
+                    refresh_justfile_commands().await;
+                }
+            }
+        }
+
         Action::RefreshWorktrees => {
             // Get the active project path and refresh worktrees
             let project_path = {
@@ -1181,6 +5541,123 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             }
         }
 
+        Action::LoadWorktreeDiff { ref base } => {
+            let target = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| {
+                    let worktree = p.active_worktree()?;
+                    let base = base.clone().or_else(|| {
+                        p.worktrees.iter().find(|w| w.is_main).map(|w| w.branch.clone())
+                    })?;
+                    Some((worktree.path.clone(), base, worktree.branch.clone()))
+                })
+            };
+
+            let Some((repo_path, base, head)) = target else {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetWorktreeDiff { diff: None });
+                return Ok(());
+            };
+
+            let result = worktree::diff::diff_branches(&repo_path, &base, &head);
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok(diff) => reduce(&mut state, Action::SetWorktreeDiff { diff: Some(diff) }),
+                Err(e) => {
+                    reduce(&mut state, Action::SetWorktreeDiff { diff: None });
+                    reduce(&mut state, Action::SetError {
+                        code: "WORKTREE_DIFF_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("LoadWorktreeDiff: {}...{}", base, head)),
+                    });
+                }
+            }
+        }
+
+        Action::RunDependencyAudit => {
+            let worktree_path = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| p.active_worktree()).map(|w| w.path.clone())
+            };
+            let Some(worktree_path) = worktree_path else {
+                return Ok(());
+            };
+
+            let path = std::path::Path::new(&worktree_path);
+            let report = audit::run(path).await;
+            let save_error = audit::save(path, &report).err();
+            let critical = report.count(audit::Severity::Critical);
+
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetDependencyAudit { worktree_path: worktree_path.clone(), report });
+            if critical > 0 {
+                reduce(&mut state, Action::AddNotification {
+                    message: format!("Dependency audit found {} critical vulnerabilit{}", critical, if critical == 1 { "y" } else { "ies" }),
+                    notification_type: actions::NotificationTypeData::Error,
+                });
+            }
+            if let Some(e) = save_error {
+                reduce(&mut state, Action::SetError {
+                    code: "AUDIT_SAVE_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("RunDependencyAudit: {}", worktree_path)),
+                });
+            }
+        }
+
+        Action::RunBuildCheck => {
+            let worktree_path = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| p.active_worktree()).map(|w| w.path.clone())
+            };
+            let Some(worktree_path) = worktree_path else {
+                return Ok(());
+            };
+
+            let path = std::path::Path::new(&worktree_path);
+            let diagnostics = build_diagnostics::run(path).await;
+            let save_error = build_diagnostics::save(path, &diagnostics).err();
+
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetBuildDiagnostics { worktree_path: worktree_path.clone(), diagnostics });
+            if let Some(e) = save_error {
+                reduce(&mut state, Action::SetError {
+                    code: "BUILD_DIAGNOSTICS_SAVE_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("RunBuildCheck: {}", worktree_path)),
+                });
+            }
+        }
+
+        Action::RefreshWorktreeHealth => {
+            let worktree_paths: Vec<String> = {
+                let state = get_app_state().read().await;
+                state
+                    .active_project()
+                    .map(|p| p.worktrees.iter().map(|w| w.path.clone()).collect())
+                    .unwrap_or_default()
+            };
+
+            let mut health = Vec::new();
+            let mut errors = Vec::new();
+            for path in worktree_paths {
+                match worktree::health::compute_health(&path) {
+                    Ok(data) => health.push(data),
+                    Err(e) => errors.push((path, e)),
+                }
+            }
+
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetWorktreeHealth { health });
+            for (path, e) in errors {
+                reduce(&mut state, Action::SetError {
+                    code: "WORKTREE_HEALTH_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("RefreshWorktreeHealth: {}", path)),
+                });
+            }
+        }
+
         Action::AddWorktree { ref branch } => {
             // Get the active project info
             let (project_path, env_config, source_worktree) = {
@@ -1206,6 +5683,12 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                     Ok(new_worktree) => {
                         // Refresh worktrees to get the updated list
                         refresh_worktrees_for_path(&path).await;
+                        record_activity(
+                            activity::ActivityKind::WorktreeAdded,
+                            format!("Added worktree for branch {}", branch),
+                            serde_json::json!({ "branch": branch, "path": new_worktree.path }),
+                        )
+                        .await;
 
                         // Auto-copy env files if enabled
                         if let (Some(config), Some(source)) = (env_config, source_worktree) {
@@ -1260,6 +5743,12 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                     Ok(new_worktree) => {
                         // Refresh worktrees to get the updated list
                         refresh_worktrees_for_path(&path).await;
+                        record_activity(
+                            activity::ActivityKind::WorktreeAdded,
+                            format!("Added worktree for branch {}", branch),
+                            serde_json::json!({ "branch": branch, "path": new_worktree.path }),
+                        )
+                        .await;
 
                         // Auto-copy env files if enabled
                         if let (Some(config), Some(source)) = (env_config, source_worktree) {
@@ -1289,6 +5778,10 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             }
         }
 
+        Action::StartFeatureWork { feature_number } => {
+            start_feature_work(feature_number).await;
+        }
+
         Action::RemoveWorktree { ref worktree_path } => {
             // Get the active project path
             let project_path = {
@@ -1301,6 +5794,7 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                     Ok(()) => {
                         // Refresh worktrees to get the updated list
                         refresh_worktrees_for_path(&path).await;
+                        cleanup_orphaned_terminals().await;
                     }
                     Err(e) => {
                         let mut state = get_app_state().write().await;
@@ -1318,7 +5812,7 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             // Start service with custom port
             match docker_start_service_with_port(service_id.clone(), port).await {
                 Ok(()) => {
-                    refresh_docker_services_internal().await;
+                    watch_service_health(service_id.clone()).await;
                 }
                 Err(e) => {
                     let mut state = get_app_state().write().await;
@@ -1338,7 +5832,7 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                     // Now start the rstn service
                     match docker_start_service(service_id.clone()).await {
                         Ok(()) => {
-                            refresh_docker_services_internal().await;
+                            watch_service_health(service_id.clone()).await;
                         }
                         Err(e) => {
                             let mut state = get_app_state().write().await;
@@ -1361,6 +5855,41 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             }
         }
 
+        Action::KillProcessOnPort { port, pid, ref service_id } => {
+            let killed = tokio::task::spawn_blocking(move || net_inspect::kill_port_process(port, pid))
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|r| r);
+            if let Err(e) = killed {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetError {
+                    code: "KILL_PROCESS_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("KillProcessOnPort: pid {} on port {}", pid, port)),
+                });
+                drop(state);
+                // Reset the service marked as starting
+                refresh_docker_services_internal().await;
+                return Ok(());
+            }
+
+            if let Some(service_id) = service_id {
+                match docker_start_service(service_id.clone()).await {
+                    Ok(()) => {
+                        watch_service_health(service_id.clone()).await;
+                    }
+                    Err(e) => {
+                        let mut state = get_app_state().write().await;
+                        reduce(&mut state, Action::SetError {
+                            code: "DOCKER_START_ERROR".to_string(),
+                            message: e.to_string(),
+                            context: Some(format!("KillProcessOnPort: failed to start {}", service_id)),
+                        });
+                    }
+                }
+            }
+        }
+
         Action::ValidateContextFile { ref path } => {
             let project_root = {
                 let state = get_app_state().read().await;
@@ -1384,6 +5913,7 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                             file_reader::FileReadError::SecurityViolation(_) => "SECURITY_VIOLATION",
                             file_reader::FileReadError::FileTooLarge { .. } => "FILE_TOO_LARGE",
                             file_reader::FileReadError::NotUtf8 => "NOT_UTF8",
+                            file_reader::FileReadError::Conflict(_) => "FILE_CONFLICT",
                             file_reader::FileReadError::Io(_) => "IO_ERROR",
                         };
                         actions::ValidationResultData::Error(format!("{}: {}", code, e))
@@ -1471,11 +6001,241 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             }
         }
 
+        Action::DiffEnvFiles {
+            ref from_worktree_path,
+            ref to_worktree_path,
+            ref patterns,
+        } => {
+            let diff_patterns = match patterns {
+                Some(p) => p.clone(),
+                None => {
+                    let state = get_app_state().read().await;
+                    state
+                        .active_project()
+                        .map(|project| project.env_config.tracked_patterns.clone())
+                        .unwrap_or_else(env::default_patterns)
+                }
+            };
+
+            let result = env::diff_env_files(from_worktree_path, to_worktree_path, &diff_patterns);
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok(files) => reduce(&mut state, Action::SetEnvDiff {
+                    diff: Some(app_state::EnvDiff {
+                        from_worktree_path: from_worktree_path.clone(),
+                        to_worktree_path: to_worktree_path.clone(),
+                        files,
+                        timestamp: chrono::Utc::now().to_rfc3339(),
+                    }),
+                }),
+                Err(e) => reduce(&mut state, Action::SetError {
+                    code: "ENV_DIFF_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("DiffEnvFiles: {} -> {}", from_worktree_path, to_worktree_path)),
+                }),
+            }
+        }
+
+        Action::SyncEnvKeys {
+            ref from_worktree_path,
+            ref to_worktree_path,
+            ref file,
+            ref keys,
+        } => {
+            let result = if env::is_safe_env_path(file) {
+                env::sync_env_keys(
+                    &std::path::Path::new(from_worktree_path).join(file),
+                    &std::path::Path::new(to_worktree_path).join(file),
+                    keys,
+                )
+            } else {
+                Err(format!("Env file must be inside the worktree: {}", file))
+            };
+
+            {
+                let mut state = get_app_state().write().await;
+                match result {
+                    Ok(changed) => reduce(&mut state, Action::AddNotification {
+                        message: format!("Synced {} key(s) in {}", changed, file),
+                        notification_type: actions::NotificationTypeData::Success,
+                    }),
+                    Err(e) => reduce(&mut state, Action::SetError {
+                        code: "ENV_SYNC_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("SyncEnvKeys: {} -> {}", file, to_worktree_path)),
+                    }),
+                }
+            }
+
+            // Refresh the diff so synced keys disappear from it
+            let diff_action = Action::DiffEnvFiles {
+                from_worktree_path: from_worktree_path.clone(),
+                to_worktree_path: to_worktree_path.clone(),
+                patterns: None,
+            };
+            Box::pin(handle_async_action(diff_action)).await.ok();
+        }
+
+        Action::ScanEnvSecrets => {
+            let target = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|project| {
+                    project
+                        .active_worktree()
+                        .map(|w| (w.path.clone(), project.env_config.tracked_patterns.clone()))
+                })
+            };
+            if let Some((worktree_path, patterns)) = target {
+                let secrets = env_secrets::scan_env_files(&worktree_path, &patterns);
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetEnvSecrets { secrets });
+            }
+        }
+
+        Action::InjectConnectionString {
+            ref service_id,
+            ref db_name,
+            ref env_file,
+            ref var_name,
+            dry_run,
+        } => {
+            let target = {
+                let state = get_app_state().read().await;
+                let worktree_path = state
+                    .active_project()
+                    .and_then(|p| p.active_worktree())
+                    .map(|w| w.path.clone());
+                let port = state
+                    .docker
+                    .services
+                    .iter()
+                    .find(|s| &s.id == service_id)
+                    .and_then(|s| s.port);
+                worktree_path.zip(port)
+            };
+
+            let result = (|| {
+                let (worktree_path, port) = target.ok_or("No active worktree or service port")?;
+                if !env::is_safe_env_path(env_file) {
+                    return Err(format!("Env file must be inside the worktree: {}", env_file));
+                }
+                if !env::is_valid_var_name(var_name) {
+                    return Err(format!("Invalid variable name: {}", var_name));
+                }
+                let value = docker::connection_string(service_id, port as u16, Some(db_name))
+                    .ok_or_else(|| format!("No connection string for service {}", service_id))?;
+                Ok((std::path::Path::new(&worktree_path).join(env_file), value))
+            })();
+
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok((path, value)) if dry_run => {
+                    let previous = std::fs::read_to_string(&path)
+                        .ok()
+                        .and_then(|content| env::get_env_var(&content, var_name));
+                    reduce(&mut state, Action::SetEnvInjectionPreview {
+                        preview: Some(app_state::EnvInjectionPreview {
+                            service_id: service_id.clone(),
+                            db_name: db_name.clone(),
+                            env_file: env_file.clone(),
+                            file_path: path.to_string_lossy().to_string(),
+                            var_name: var_name.clone(),
+                            masked_value: env::mask_connection_string(&value),
+                            previous_value: previous.as_deref().map(env::mask_connection_string),
+                            file_exists: path.exists(),
+                        }),
+                    });
+                }
+                Ok((path, value)) => {
+                    reduce(&mut state, Action::SetEnvInjectionPreview { preview: None });
+                    match env::write_env_var(&path, var_name, &value) {
+                        Ok(previous) => reduce(&mut state, Action::AddNotification {
+                            message: match previous {
+                                Some(_) => format!("Updated {} in {}", var_name, env_file),
+                                None => format!("Added {} to {}", var_name, env_file),
+                            },
+                            notification_type: actions::NotificationTypeData::Success,
+                        }),
+                        Err(e) => reduce(&mut state, Action::SetError {
+                            code: "ENV_INJECT_ERROR".to_string(),
+                            message: e,
+                            context: Some(format!("InjectConnectionString: {} -> {}", service_id, env_file)),
+                        }),
+                    }
+                }
+                Err(e) => reduce(&mut state, Action::SetError {
+                    code: "ENV_INJECT_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("InjectConnectionString: {} -> {}", service_id, env_file)),
+                }),
+            }
+        }
+
+        Action::SaveSettingsProfile { ref profile } => {
+            // Only profiles the reducer accepted are written
+            let accepted = get_app_state().read().await.settings_profiles.contains(profile);
+            if accepted {
+                if let Err(e) = settings_profiles::save(&persistence::get_rstn_dir(), profile) {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetError {
+                        code: "SETTINGS_PROFILE_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("SaveSettingsProfile: {}", profile.name)),
+                    });
+                }
+            }
+        }
+
+        Action::DeleteSettingsProfile { ref name } => {
+            if let Err(e) = settings_profiles::delete(&persistence::get_rstn_dir(), name) {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetError {
+                    code: "SETTINGS_PROFILE_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("DeleteSettingsProfile: {}", name)),
+                });
+            }
+        }
+
+        Action::SwitchSettingsProfile { ref name } => {
+            // The reducer applied everything but the keys; resolve them now
+            let mut state = get_app_state().write().await;
+            let Some(profile) = state.settings_profiles.iter().find(|p| &p.name == name).cloned() else {
+                return Ok(());
+            };
+            match profile.resolve_secrets() {
+                Ok(secrets) => {
+                    let openai = app_state::OpenAiSettings {
+                        api_key: secrets.openai_api_key,
+                        ..state.global_settings.openai.clone()
+                    };
+                    reduce(&mut state, Action::SetOpenAiSettings { settings: openai });
+                    reduce(&mut state, Action::SetGitHostingSettings {
+                        settings: app_state::GitHostingSettings {
+                            github_token: secrets.github_token,
+                            gitlab_token: secrets.gitlab_token,
+                        },
+                    });
+                    reduce(&mut state, Action::AddNotification {
+                        message: format!("Switched to settings profile \"{}\"", name),
+                        notification_type: actions::NotificationTypeData::Info,
+                    });
+                }
+                Err(e) => reduce(&mut state, Action::SetError {
+                    code: "SETTINGS_PROFILE_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("SwitchSettingsProfile: {}", name)),
+                }),
+            }
+        }
+
         // Synchronous actions - already handled by reduce()
         // Note: StartMcpServer and StopMcpServer are handled async above
-        Action::CloseProject { .. }
-        | Action::SwitchProject { .. }
-        | Action::SetFeatureTab { .. }
+        Action::CloseProject { .. } => {
+            cleanup_orphaned_terminals().await;
+        }
+
+        Action::SetFeatureTab { .. }
         | Action::SwitchWorktree { .. }
         | Action::SetWorktrees { .. }
         | Action::SetMcpStatus { .. }
@@ -1488,16 +6248,94 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
         | Action::SetDockerLogs { .. }
         | Action::SetDockerLoading { .. }
         | Action::SetDockerLogsLoading { .. }
+        | Action::SetServiceGroups { .. }
+        | Action::AddComposeFile { .. }
+        | Action::SetHttpRequests { .. }
+        | Action::SetHttpResponse { .. }
+        | Action::SetDockerExecSession { .. }
+        | Action::SetProxyStatus { .. }
+        | Action::SetImagePullProgress { .. }
+        | Action::FinishImagePull { .. }
+        | Action::SetDockerStats { .. }
+        | Action::SetServiceHealth { .. }
+        | Action::SetDockerDatabases { .. }
+        | Action::SetDockerTables { .. }
+        | Action::SetDockerDatabasesLoading { .. }
         | Action::SetPortConflict { .. }
         | Action::ClearPortConflict
         | Action::SetDockerConnectionString { .. }
         | Action::SetBranches { .. }
         | Action::SetBranchesLoading { .. }
+        | Action::SetWorktreeHealth { .. }
+        | Action::SetWorktreeDiff { .. }
+        | Action::SetDependencyAudit { .. }
+        | Action::SetBuildDiagnostics { .. }
+        | Action::SetLspServerStatus { .. }
+        | Action::SetGitBusy { .. }
+        | Action::SetSecurityScanResult { .. }
+        | Action::SetFileHistory { .. }
         | Action::SetFileContent { .. }
         | Action::SetFileLoading { .. }
         | Action::SetBinaryFileContent { .. }
+        | Action::SetFileDirty { .. }
+        | Action::SetFileSaveError { .. }
         | Action::SetA2UIPayload { .. }
+        | Action::AddUsageRecord { .. }
+        | Action::SetSessions { .. }
+        | Action::AddSession { .. }
+        | Action::SetActivityFeed { .. }
+        | Action::AddActivity { .. }
+        | Action::SetSystemStats { .. }
+        | Action::SetSpecWorkflow { .. }
+        | Action::AppendSpecPhaseOutput { .. }
+        | Action::CompleteSpecPhase { .. }
+        | Action::FailSpecPhase { .. }
+        | Action::ClearSpecWorkflow
+        | Action::SetClarifySession { .. }
+        | Action::CompleteClarifySession { .. }
+        | Action::FailClarifySession { .. }
+        | Action::ClearClarifySession
+        | Action::SetSpecTasks { .. }
+        | Action::ReportSpecProgress { .. }
+        | Action::SetChecklist { .. }
+        | Action::FailChecklist { .. }
+        | Action::SetChecklists { .. }
+        | Action::SetRequireChecklists { .. }
+        | Action::SetCollectCoverage { .. }
+        | Action::SetCheckBuildOnSave { .. }
+        | Action::SetImplementationTimeout { .. }
+        | Action::CompleteSpecAnalysis { .. }
+        | Action::FailSpecAnalysis { .. }
+        | Action::SetFeaturesCatalog { .. }
+        | Action::AddAgentRun { .. }
+        | Action::StartAgentRun { .. }
+        | Action::AppendAgentRunOutput { .. }
+        | Action::FinishAgentRun { .. }
+        | Action::SetChangeSnapshot { .. }
+        | Action::CompleteRollback { .. }
+        | Action::SetChangeTestResults { .. }
+        | Action::SetChangeHookResults { .. }
+        | Action::RequestAgentRunApproval { .. }
+        | Action::RequestImplementationApproval { .. }
+        | Action::ClearFinishedAgentRuns
+        | Action::PushUndoEntry { .. }
+        | Action::SetDesktopNotification { .. }
+        | Action::SetLlmProvider { .. }
+        | Action::SetOpenAiSettings { .. }
+        | Action::SetOllamaSettings { .. }
+        | Action::SetGitHostingSettings { .. }
+        | Action::SetKeybinding { .. }
+        | Action::ResetKeybindings { .. }
+        | Action::SetChangePullRequest { .. }
         | Action::SetJustfileCommands { .. }
+        | Action::SetTasks { .. }
+        | Action::QueueTaskRun { .. }
+        | Action::SetSchedule { .. }
+        | Action::SetSchedulePaused { .. }
+        | Action::MarkScheduleRun { .. }
+        | Action::StartTaskRun { .. }
+        | Action::AppendTaskRunOutput { .. }
+        | Action::FinishTaskRun { .. }
         | Action::SetTaskStatus { .. }
         | Action::SetActiveCommand { .. }
         | Action::AppendTaskOutput { .. }
@@ -1506,6 +6344,8 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
         | Action::SetTasksError { .. }
         | Action::SetTheme { .. }
         | Action::SetProjectPath { .. }
+        | Action::SetModel { .. }
+        | Action::SetProjectModel { .. }
         | Action::SetError { .. }
         | Action::ClearError
         // Env actions (sync)
@@ -1513,6 +6353,10 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
         | Action::SetEnvTrackedPatterns { .. }
         | Action::SetEnvAutoCopy { .. }
         | Action::SetEnvSourceWorktree { .. }
+        | Action::SetEnvInjectionPreview { .. }
+        | Action::SetEnvDiff { .. }
+        | Action::SetEnvSecrets { .. }
+        | Action::SetAutoResolvePorts { .. }
         // Notification actions (sync)
         | Action::AddNotification { .. }
         | Action::DismissNotification { .. }
@@ -1523,13 +6367,27 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
         | Action::AddMcpLogEntry { .. }
         | Action::ClearMcpLogs
         | Action::UpdateMcpTools { .. }
+        | Action::UpdateMcpMetrics { .. }
+        | Action::SetExternalMcpServers { .. }
+        | Action::StartMcpPlaygroundCall { .. }
+        | Action::FinishMcpPlaygroundCall { .. }
+        | Action::ClearMcpPlayground
         // Chat actions (sync state updates only)
         | Action::AddChatMessage { .. }
         | Action::AppendChatContent { .. }
+        | Action::AddChatToolCall { .. }
+        | Action::CompleteChatToolCall { .. }
+        | Action::RequestToolApproval { .. }
+        | Action::ProposeEdits { .. }
+        | Action::AcceptProposedEdit { .. }
+        | Action::RejectProposedEdit { .. }
+        | Action::SetProposedEditsApplied { .. }
+        | Action::DismissProposedEdits
         | Action::SetChatTyping { .. }
         | Action::SetChatError { .. }
         | Action::ClearChatError
         | Action::ClearChat
+        | Action::SetChatSessionId { .. }
         // Terminal actions (sync - state updates only)
         | Action::SetTerminalSession { .. }
         | Action::SetTerminalSize { .. }
@@ -1539,29 +6397,282 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
         | Action::SetFileComments { .. }
         | Action::SetExplorerSort { .. }
         | Action::SetExplorerFilter { .. }
+        | Action::SetSearchResults { .. }
+        | Action::ClearSearch
         // Dev log actions (sync)
-        | Action::AddDevLog { .. } => {
+        | Action::AddDevLog { .. }
+        | Action::SetAppLogs { .. } => {
+            // Already handled synchronously
+        }
+
+        // Navigation actions need to trigger directory read after state update
+        Action::NavigateBack | Action::NavigateForward | Action::NavigateUp => {
+            // State was already updated by reducer, now trigger directory read
+            let current_path = {
+                let state = get_app_state().read().await;
+                state
+                    .active_project()
+                    .and_then(|p| p.active_worktree())
+                    .map(|w| w.explorer.current_path.clone())
+            };
+
+            if let Some(path) = current_path {
+                // Recursively call ExploreDir to load the directory contents
+                Box::pin(handle_async_action(Action::ExploreDir { path })).await?;
+            }
+        }
+        Action::ClearDevLogs => {
             // Already handled synchronously
         }
 
-        // Navigation actions need to trigger directory read after state update
-        Action::NavigateBack | Action::NavigateForward | Action::NavigateUp => {
-            // State was already updated by reducer, now trigger directory read
-            let current_path = {
+        Action::LoadAppLogs { limit, level, module } => {
+            let logs = logging::tail(
+                limit.map_or(logging::DEFAULT_TAIL, |n| n as usize),
+                level.as_deref(),
+                module.as_deref(),
+            );
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetAppLogs { logs });
+        }
+
+        Action::LoadSessions => {
+            let project_path = {
+                let state = get_app_state().read().await;
+                state.active_project().map(|p| p.path.clone())
+            };
+            let project_id = project_path.map(|path| persistence::get_project_id(&path)).unwrap_or_default();
+            let sessions = match get_db_manager() {
+                Some(db) => db
+                    .list_sessions(&project_id, app_state::MAX_SESSION_HISTORY)
+                    .unwrap_or_else(|e| {
+                        tracing::error!("Failed to load sessions: {}", e);
+                        Vec::new()
+                    }),
+                None => Vec::new(),
+            };
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetSessions { project_id, sessions });
+        }
+
+        Action::LoadActivityFeed { ref since } => {
+            let project_path = {
+                let state = get_app_state().read().await;
+                state.active_project().map(|p| p.path.clone())
+            };
+            let project_id = project_path.map(|path| persistence::get_project_id(&path)).unwrap_or_default();
+            let events = match get_db_manager() {
+                Some(db) => db
+                    .get_logs(&project_id, since.as_deref(), activity::MAX_ACTIVITY_EVENTS)
+                    .map(|rows| rows.into_iter().filter_map(|row| activity::ActivityEvent::from_row(&project_id, row)).collect())
+                    .unwrap_or_else(|e| {
+                        tracing::error!("Failed to load activity: {}", e);
+                        Vec::new()
+                    }),
+                None => Vec::new(),
+            };
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetActivityFeed { project_id, since: since.clone(), events });
+        }
+
+        Action::StartSpecWorkflow { ref description, use_claude } => {
+            let worktree_path = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| p.active_worktree()).map(|w| w.path.clone())
+            };
+            let Some(wt_path) = worktree_path else {
+                tracing::warn!("StartSpecWorkflow: No active worktree");
+                return Ok(());
+            };
+            let short_name = if use_claude { change_slug(description).await } else { slugify(description) };
+            let feature = match spec_kit::create_feature(std::path::Path::new(&wt_path), &short_name) {
+                Ok(feature) => feature,
+                Err(e) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetError {
+                        code: "SPEC_WORKFLOW_ERROR".to_string(),
+                        message: e,
+                        context: Some("StartSpecWorkflow".to_string()),
+                    });
+                    return Ok(());
+                }
+            };
+
+            let entry = feature_catalog::CatalogEntry {
+                number: feature.number,
+                short_name: feature.short_name.clone(),
+                description: description.clone(),
+                created_at: chrono::Utc::now().to_rfc3339(),
+            };
+            if let Err(e) = feature_catalog::record(std::path::Path::new(&wt_path), entry) {
+                tracing::warn!("Failed to record feature {}: {}", feature.dir_name(), e);
+            }
+
+            let run = Action::RunSpecPhase {
+                phase: spec_kit::SpecPhase::Specify,
+                input: None,
+            };
+            {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetSpecWorkflow {
+                    workflow: app_state::SpecWorkflow {
+                        use_claude,
+                        ..app_state::SpecWorkflow::new(&feature, description.clone())
+                    },
+                });
+                reduce(&mut state, run.clone());
+            }
+            notify_state_update().await;
+            Box::pin(handle_async_action(run)).await?;
+        }
+
+        Action::RunSpecPhase { phase, ref input } => {
+            run_spec_phase(phase, input.clone()).await;
+        }
+
+        Action::StartClarifySession { ref spec_path } => {
+            let worktree_path = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| p.active_worktree()).map(|w| w.path.clone())
+            };
+            let Some(wt_path) = worktree_path else {
+                tracing::warn!("StartClarifySession: No active worktree");
+                return Ok(());
+            };
+            let full_path = std::path::Path::new(&wt_path).join(spec_path);
+            let mut state = get_app_state().write().await;
+            match std::fs::read_to_string(&full_path) {
+                Ok(spec) => {
+                    let analysis = clarify::analyze(&spec);
+                    let status = if analysis.questions.is_empty() {
+                        app_state::ClarifySessionStatus::Completed
+                    } else {
+                        app_state::ClarifySessionStatus::Asking
+                    };
+                    reduce(&mut state, Action::SetClarifySession {
+                        session: app_state::ClarifySession {
+                            spec_path: spec_path.clone(),
+                            status,
+                            coverage: analysis.coverage,
+                            questions: analysis.questions,
+                            answers: Vec::new(),
+                            error: None,
+                        },
+                    });
+                }
+                Err(e) => {
+                    reduce(&mut state, Action::SetError {
+                        code: "CLARIFY_ERROR".to_string(),
+                        message: format!("Failed to read {}: {}", full_path.display(), e),
+                        context: Some("StartClarifySession".to_string()),
+                    });
+                }
+            }
+        }
+
+        Action::AnswerClarifyQuestion { .. } | Action::SkipClarifyQuestion => {
+            // The reducer moves the session to Integrating after the last question
+            let mut state = get_app_state().write().await;
+            integrate_clarify_session(&mut state);
+        }
+
+        Action::LoadSpecTasks { ref tasks_path } => {
+            let mut state = get_app_state().write().await;
+            let worktree_path = state.active_project().and_then(|p| p.active_worktree()).map(|w| w.path.clone());
+            if let Some(wt_path) = worktree_path {
+                let result = spec_tasks::load(&std::path::Path::new(&wt_path).join(tasks_path));
+                apply_spec_tasks(&mut state, tasks_path, result);
+            }
+        }
+
+        Action::ToggleSpecTask { ref id, done } => {
+            let mut state = get_app_state().write().await;
+            let target = state.active_project().and_then(|p| p.active_worktree()).and_then(|w| {
+                let tasks = w.workflows.tasks.as_ref()?;
+                Some((w.path.clone(), tasks.tasks_path.clone()))
+            });
+            if let Some((wt_path, tasks_path)) = target {
+                let path = std::path::Path::new(&wt_path).join(&tasks_path);
+                // On failure, reload so the optimistic toggle is reverted
+                let result = spec_tasks::update_file(&path, id, done).or_else(|e| {
+                    reduce(&mut state, Action::SetError {
+                        code: "SPEC_TASKS_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("ToggleSpecTask: {}", id)),
+                    });
+                    spec_tasks::load(&path)
+                });
+                apply_spec_tasks(&mut state, &tasks_path, result);
+            }
+        }
+
+        Action::GenerateChecklist { kind } => {
+            generate_checklist(kind).await;
+        }
+
+        Action::LoadFeaturesCatalog => {
+            let worktree_path = {
                 let state = get_app_state().read().await;
-                state
-                    .active_project()
-                    .and_then(|p| p.active_worktree())
-                    .map(|w| w.explorer.current_path.clone())
+                state.active_project().and_then(|p| p.active_worktree()).map(|w| w.path.clone())
             };
+            if let Some(wt_path) = worktree_path {
+                let features = tokio::task::spawn_blocking(move || feature_catalog::list(std::path::Path::new(&wt_path)))
+                    .await
+                    .unwrap_or_default();
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetFeaturesCatalog { features });
+            }
+        }
 
-            if let Some(path) = current_path {
-                // Recursively call ExploreDir to load the directory contents
-                Box::pin(handle_async_action(Action::ExploreDir { path })).await?;
+        Action::AnalyzeSpec { verify } => {
+            run_spec_analysis(verify).await;
+        }
+
+        Action::LoadChecklists => {
+            let mut state = get_app_state().write().await;
+            load_spec_checklists(&mut state);
+        }
+
+        Action::ToggleChecklistItem { kind, ref id, checked } => {
+            let mut state = get_app_state().write().await;
+            let target = state.active_project().and_then(|p| p.active_worktree()).and_then(|w| {
+                let checklist = w.workflows.checklists.iter().find(|c| c.kind == kind)?;
+                Some((std::path::Path::new(&w.path).join(&checklist.path), checklist.clone()))
+            });
+            if let Some((path, checklist)) = target {
+                match checklist::update_file(&path, id, checked) {
+                    Ok(items) => reduce(&mut state, Action::SetChecklist {
+                        checklist: app_state::SpecChecklist { items, ..checklist },
+                    }),
+                    Err(message) => {
+                        reduce(&mut state, Action::SetError {
+                            code: "CHECKLIST_ERROR".to_string(),
+                            message,
+                            context: Some(format!("ToggleChecklistItem: {}", id)),
+                        });
+                        // Revert the optimistic toggle
+                        load_spec_checklists(&mut state);
+                    }
+                }
             }
         }
-        Action::ClearDevLogs => {
-            // Already handled synchronously
+
+        Action::CancelSpecPhase => {
+            // Phase already marked failed by the reducer; kill the running process
+            let worktree_path = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| p.active_worktree()).map(|w| w.path.clone())
+            };
+            if let Some(wt_path) = worktree_path {
+                get_claude_processes().cancel(&spec_process_key(std::path::Path::new(&wt_path)));
+            }
+        }
+
+        Action::DeleteSession { ref id } => {
+            if let Some(db) = get_db_manager() {
+                if let Err(e) = db.delete_session(id) {
+                    tracing::error!("Failed to delete session {}: {}", id, e);
+                }
+            }
         }
 
         Action::ReadFile { ref path } => {
@@ -1611,12 +6722,17 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                     std::path::Path::new(&root).join(path).to_string_lossy().to_string()
                 };
 
-                match file_reader::read_binary_file(&abs_path, &root) {
-                    Ok(content) => {
+                // Thumbnails and hexdumps are built off the async runtime
+                let result = tokio::task::spawn_blocking(move || file_preview::preview_file(&abs_path, &root))
+                    .await
+                    .unwrap_or_else(|e| Err(file_reader::FileReadError::Io(e.to_string())));
+                match result {
+                    Ok(file) => {
                         let mut state = get_app_state().write().await;
                         reduce(&mut state, Action::SetBinaryFileContent {
                             path: path.clone(),
-                            content: Some(content),
+                            content: file.content,
+                            preview: Some(file.preview),
                             error: None
                         });
                     }
@@ -1625,6 +6741,7 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                         reduce(&mut state, Action::SetBinaryFileContent {
                             path: path.clone(),
                             content: None,
+                            preview: None,
                             error: Some(e.to_string())
                         });
                     }
@@ -1632,10 +6749,76 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             }
         }
 
-        // Claude Code CLI chat (async - spawns external process)
-        Action::SendChatMessage { ref text } => {
-            // Get the working directory, MCP config path, and agent rules config
-            let (cwd, mcp_config_path, agent_rules_config, project_id) = {
+        Action::WriteFile { ref path, ref content, ref expected_hash } => {
+            let project_root = {
+                let state = get_app_state().read().await;
+                state.active_project().map(|p| p.path.clone())
+            };
+
+            let Some(root) = project_root else {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetFileSaveError {
+                    path: path.clone(),
+                    error: "No active project".to_string(),
+                });
+                return Ok(());
+            };
+
+            let abs_path = if std::path::Path::new(path).is_absolute() {
+                path.clone()
+            } else {
+                std::path::Path::new(&root).join(path).to_string_lossy().to_string()
+            };
+
+            let result = file_reader::write_file(&abs_path, &root, content, expected_hash.as_deref());
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok(_) => {
+                    reduce(&mut state, Action::SetFileContent {
+                        path: path.clone(),
+                        content: Some(content.clone()),
+                        error: None,
+                    });
+                }
+                Err(e) => {
+                    reduce(&mut state, Action::SetFileSaveError {
+                        path: path.clone(),
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        // Prompt library: render the template, then send it like a typed message
+        Action::SendTemplatedPrompt { ref template_id, ref variables, continue_conversation, provider } => {
+            let rendered = prompt_library::find_template(&prompt_library::library_dir(), template_id)
+                .and_then(|template| prompt_library::render(&template, variables));
+            match rendered {
+                Ok(text) => {
+                    let send = Action::SendChatMessage { text, continue_conversation, provider, attachments: Vec::new() };
+                    {
+                        let mut state = get_app_state().write().await;
+                        reduce(&mut state, send.clone());
+                    }
+                    notify_state_update().await;
+                    Box::pin(handle_async_action(send)).await?;
+                }
+                Err(e) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::AddNotification {
+                        message: format!("Failed to render prompt template: {}", e),
+                        notification_type: actions::NotificationTypeData::Error,
+                    });
+                }
+            }
+        }
+
+        // Chat via the Claude Code CLI (spawns external process) or an HTTP provider
+        Action::SendChatMessage { ref text, continue_conversation, provider, ref attachments } => {
+            // Get the working directory, MCP config path, agent rules config, session to resume,
+            // the files in context (open explorer tabs, for directory rules overlays), the backend
+            // and the conversation so far (HTTP providers are stateless, so they get the history)
+            let (cwd, mcp_config_path, agent_rules_config, project_id, resume_session_id, open_files, provider, history) = {
                 let state = get_app_state().read().await;
                 let cwd = state
                     .active_project()
@@ -1651,7 +6834,29 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                 let proj_id = state
                     .active_project()
                     .map(|p| p.id.clone());
-                (cwd, config_path, agent_rules, proj_id)
+                let session_id = state
+                    .active_project()
+                    .and_then(|p| p.active_worktree())
+                    .and_then(|w| w.chat.session_id.clone())
+                    .filter(|_| continue_conversation);
+                let open_files: Vec<std::path::PathBuf> = state
+                    .active_project()
+                    .and_then(|p| p.active_worktree())
+                    .map(|w| w.explorer.tabs.iter().map(|t| std::path::PathBuf::from(&t.path)).collect())
+                    .unwrap_or_default();
+                let provider = provider.unwrap_or(state.global_settings.provider);
+                let messages = state
+                    .active_project()
+                    .and_then(|p| p.active_worktree())
+                    .map(|w| w.chat.messages.as_slice())
+                    .unwrap_or_default();
+                // Without "continue", only the new message (like a fresh CLI session)
+                let history = if continue_conversation {
+                    messages.to_vec()
+                } else {
+                    messages.last().cloned().into_iter().collect()
+                };
+                (cwd, config_path, agent_rules, proj_id, session_id, open_files, provider, history)
             };
 
             let cwd = match cwd {
@@ -1685,6 +6890,7 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                             content: String::new(),
                             timestamp: chrono::Utc::now().to_rfc3339(),
                             is_streaming: true,
+                            attachments: Vec::new(),
                         },
                     },
                 );
@@ -1697,9 +6903,57 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             let mcp_config_for_task = mcp_config_path.clone();
             let agent_rules_for_task = agent_rules_config.clone();
             let project_id_for_task = project_id.clone();
+            let resume_for_task = resume_session_id.clone();
+            let open_files_for_task = open_files.clone();
+            let attachments_for_task = attachments.clone();
 
             // Spawn async task to handle CLI interaction without blocking
             tokio::spawn(async move {
+    // Read attachments: text files are inlined into the prompt, images passed separately
+    let attachments = match chat_attachments::prepare(&attachments_for_task) {
+        Ok(attachments) => attachments,
+        Err(error) => {
+            {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetChatError { error });
+                reduce(&mut state, Action::SetChatTyping { is_typing: false });
+            }
+            notify_state_update().await;
+            return;
+        }
+    };
+    let prompt = chat_attachments::compose_prompt(&prompt, &attachments);
+
+    // Agent rules if enabled: the active profile composed with
+    // the directory rules overlays of the files in context
+    let rules = agent_rules_for_task.as_ref().filter(|config| config.enabled).and_then(|config| {
+        let active_profile = config.active_profile_id.as_ref()
+            .and_then(|id| {
+                config.profiles.iter().find(|p| &p.id == id)
+            });
+        let overlays = agent_rules::discover_rule_overlays(&cwd_for_task, &open_files_for_task);
+        agent_rules::compose_rules(active_profile.map(|p| p.prompt.as_str()), &overlays)
+    });
+
+    // HTTP providers stream straight into the placeholder (text only)
+    if provider != app_state::LlmProviderKind::ClaudeCli {
+        if !attachments.images.is_empty() {
+            {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetChatError { error: "Image attachments need the Claude CLI provider".to_string() });
+                reduce(&mut state, Action::SetChatTyping { is_typing: false });
+            }
+            notify_state_update().await;
+            return;
+        }
+        let mut history = history;
+        if let Some(last) = history.last_mut() {
+            last.content = prompt;
+        }
+        run_http_chat(provider, msg_id, llm::chat_messages(&history, rules.as_deref())).await;
+        return;
+    }
+
     // Validate Claude CLI exists before attempting spawn
     if let Err(e) = claude_cli::validate_claude_cli().await {
         let error = e.to_string();
@@ -1712,40 +6966,24 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
         return;
     }
 
-    // Generate agent rules file if enabled
-    let agent_rules_path = if let (Some(config), Some(proj_id)) = (&agent_rules_for_task, &project_id_for_task) {
-        if config.enabled {
-            // Find the active profile
-            let active_profile = config.active_profile_id.as_ref()
-                .and_then(|id| {
-                    config.profiles.iter().find(|p| &p.id == id)
-                });
-
-            if let Some(profile) = active_profile {
-                if !profile.prompt.trim().is_empty() {
-                    match agent_rules::generate_agent_rules_file(proj_id, &profile.prompt) {
-                        Ok(path) => Some(path),
-                        Err(e) => {
-                            eprintln!("Failed to generate agent rules file: {}", e);
-                            None
-                        }
-                    }
-                } else {
-                    None
-                }
-            } else {
+    // Generate the agent rules file for the CLI
+    let agent_rules_path = if let (Some(rules), Some(proj_id)) = (&rules, &project_id_for_task) {
+        match agent_rules::generate_agent_rules_file(proj_id, rules) {
+            Ok(path) => Some(path),
+            Err(e) => {
+                tracing::error!("Failed to generate agent rules file: {}", e);
                 None
             }
-        } else {
-            None
         }
     } else {
         None
     };
 
-    // Spawn Claude CLI process (with MCP config and/or agent rules if available)
-    match claude_cli::spawn_claude(&prompt, &cwd_for_task, mcp_config_for_task.as_deref(), agent_rules_path.as_deref()) {
-        Ok(mut child) => {
+    // Spawn Claude CLI process (with MCP config and/or agent rules if available);
+    // stdin stays open to answer tool permission prompts
+    let mut session = start_claude_session("chat", std::path::Path::new(&cwd_for_task), &prompt).await;
+    match claude_cli::spawn_claude_interactive(&prompt, &attachments.images, &cwd_for_task, mcp_config_for_task.as_deref(), agent_rules_path.as_deref(), resume_for_task.as_deref(), active_claude_model().await.as_deref()).await {
+        Ok((mut child, stdin)) => {
             // Monitor stderr for diagnostic information (errors logged to console)
             if let Some(stderr) = child.stderr.take() {
                 tokio::spawn(async move {
@@ -1756,7 +6994,7 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                         let trimmed = line.trim();
                         if !trimmed.is_empty() {
                             // Log stderr to console for debugging
-                            eprintln!("[Claude CLI stderr] {}", trimmed);
+                            tracing::debug!("[Claude CLI stderr] {}", trimmed);
                         }
                     }
                 });
@@ -1765,15 +7003,23 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             // Create event stream
             match claude_cli::ClaudeEventStream::new(&mut child) {
                 Ok(mut stream) => {
-                    use std::time::Instant;
+                    get_claude_processes().register(&msg_id, child);
+                    get_permission_bridge().register(&msg_id, stdin);
+                    use std::time::{Duration, Instant};
                     let start_time = Instant::now();
                     let mut consecutive_other_events = 0;
                     const MAX_CONSECUTIVE_OTHER: u32 = 10;
+                    // Start times of running tool calls, for their duration
+                    let mut tool_starts: std::collections::HashMap<String, Instant> = std::collections::HashMap::new();
+                    // Set while the CLI waits for a tool approval; time spent
+                    // waiting on the user doesn't count against the timeouts
+                    let mut approval_started: Option<Instant> = None;
+                    let mut approval_wait = Duration::ZERO;
 
                     // Event loop with timeout
                     loop {
                         // Check total timeout (5 minutes)
-                        if start_time.elapsed() > claude_cli::TOTAL_TIMEOUT {
+                        if start_time.elapsed().saturating_sub(approval_wait) > claude_cli::TOTAL_TIMEOUT {
                             let error = "Request exceeded 5 minute timeout".to_string();
                             {
                                 let mut state = get_app_state().write().await;
@@ -1784,12 +7030,49 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                             break;
                         }
 
-                        // Read next event with timeout (30s)
-                        match tokio::time::timeout(
-                            claude_cli::EVENT_TIMEOUT,
+                        // Read next event with timeout (30s, longer while waiting for approval)
+                        let event_timeout = if approval_started.is_some() {
+                            claude_cli::APPROVAL_TIMEOUT
+                        } else {
+                            claude_cli::EVENT_TIMEOUT
+                        };
+                        let next_event = tokio::time::timeout(
+                            event_timeout,
                             stream.next_event()
-                        ).await {
+                        ).await;
+
+                        // Cancelled via CancelChatMessage - state already updated
+                        if !get_claude_processes().is_running(&msg_id) {
+                            break;
+                        }
+
+                        match next_event {
                             Ok(Some(Ok(event))) => {
+                                session.observe(&event);
+                                record_claude_usage(&event, "chat").await;
+                                if let Some(started) = approval_started.take() {
+                                    approval_wait += started.elapsed();
+                                }
+
+                                // Tool permission prompt: pause until the user decides
+                                if let Some(request) = claude_cli::extract_permission_request(&event) {
+                                    consecutive_other_events = 0;
+                                    let approval = app_state::PendingToolApproval {
+                                        request_id: request.request_id.clone(),
+                                        message_id: msg_id.clone(),
+                                        tool_name: request.tool_name.clone(),
+                                        input_summary: claude_cli::summarize_tool_input(&request.tool_name, &request.input),
+                                    };
+                                    get_permission_bridge().track(&msg_id, request);
+                                    approval_started = Some(Instant::now());
+                                    {
+                                        let mut state = get_app_state().write().await;
+                                        reduce(&mut state, Action::RequestToolApproval { approval });
+                                    }
+                                    notify_state_update().await;
+                                    continue;
+                                }
+
                                 // Handle unsupported events
                                 if matches!(event, claude_cli::ClaudeStreamEvent::Other) {
                                     consecutive_other_events += 1;
@@ -1809,6 +7092,11 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                                 // System events are informational, don't count as errors
                                 if matches!(event, claude_cli::ClaudeStreamEvent::System { .. }) {
                                     consecutive_other_events = 0;
+                                    // Remember the session so follow-ups can resume it
+                                    if let Some(session_id) = claude_cli::extract_session_id(&event) {
+                                        let mut state = get_app_state().write().await;
+                                        reduce(&mut state, Action::SetChatSessionId { session_id: session_id.to_string() });
+                                    }
                                     continue;
                                 }
 
@@ -1834,6 +7122,44 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                                     notify_state_update().await;
                                 }
 
+                                // Tool calls and their results go on the message timeline
+                                let mut tool_actions = Vec::new();
+                                for tool_use in claude_cli::extract_tool_uses(&event) {
+                                    tool_starts.insert(tool_use.id.to_string(), Instant::now());
+                                    tool_actions.push(Action::AddChatToolCall {
+                                        tool_call: app_state::ChatToolCall {
+                                            id: tool_use.id.to_string(),
+                                            name: tool_use.name.to_string(),
+                                            input_summary: claude_cli::summarize_tool_input(tool_use.name, tool_use.input),
+                                            content_offset: 0,
+                                            result_excerpt: None,
+                                            is_error: false,
+                                            duration_ms: None,
+                                        },
+                                    });
+                                }
+                                for result in claude_cli::extract_tool_results(&event) {
+                                    let duration_ms = tool_starts
+                                        .remove(&result.tool_use_id)
+                                        .map(|start| start.elapsed().as_millis() as u64)
+                                        .unwrap_or(0);
+                                    tool_actions.push(Action::CompleteChatToolCall {
+                                        id: result.tool_use_id,
+                                        result_excerpt: claude_cli::truncate_chars(&result.content, claude_cli::TOOL_RESULT_EXCERPT_CHARS),
+                                        is_error: result.is_error,
+                                        duration_ms,
+                                    });
+                                }
+                                if !tool_actions.is_empty() {
+                                    {
+                                        let mut state = get_app_state().write().await;
+                                        for action in tool_actions {
+                                            reduce(&mut state, action);
+                                        }
+                                    }
+                                    notify_state_update().await;
+                                }
+
                                 // Check for message_stop
                                 if claude_cli::is_message_stop(&event) {
                                     {
@@ -1867,8 +7193,12 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                                 break;
                             }
                             Err(_) => {
-                                // Timeout - no event received for 30s
-                                let error = "No response from Claude CLI for 30 seconds".to_string();
+                                // Timeout - no event received for 30s (or no decision on an approval)
+                                let error = if approval_started.is_some() {
+                                    "Tool approval timed out".to_string()
+                                } else {
+                                    "No response from Claude CLI for 30 seconds".to_string()
+                                };
                                 {
                                     let mut state = get_app_state().write().await;
                                     reduce(&mut state, Action::SetChatError { error });
@@ -1880,15 +7210,22 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
                         }
                     }
 
-                    // Ensure typing flag is cleared after loop exits
+                    // Ensure typing flag is cleared after loop exits (drops any pending approval)
+                    // and stage suggested file changes for review
                     {
                         let mut state = get_app_state().write().await;
                         reduce(&mut state, Action::SetChatTyping { is_typing: false });
+                        reduce(&mut state, Action::ProposeEdits { message_id: msg_id.clone() });
                     }
                     notify_state_update().await;
 
-                    // Wait for process to finish
-                    let _ = child.wait().await;
+                    // Closing stdin lets the CLI exit after its response
+                    get_permission_bridge().close(&msg_id);
+
+                    // Wait for process to finish (already killed if cancelled)
+                    if let Some(mut child) = get_claude_processes().take(&msg_id) {
+                        let _ = child.wait().await;
+                    }
                 }
                 Err(e) => {
                     let error = e.to_string();
@@ -1902,6 +7239,7 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             }
         }
         Err(e) => {
+            session.fail(e.to_string());
             let error = e.to_string();
             {
                 let mut state = get_app_state().write().await;
@@ -1915,7 +7253,7 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
     // Cleanup agent rules file if it was created
     if let Some(path) = agent_rules_path {
         if let Err(e) = agent_rules::cleanup_agent_rules_file(&path) {
-            eprintln!("Warning: Failed to cleanup agent rules file: {}", e);
+            tracing::warn!("Failed to cleanup agent rules file: {}", e);
         }
     }
 });
@@ -1923,11 +7261,76 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             // Return immediately - background thread handles streaming
         }
 
+        // Cancel an in-flight chat response (message already marked cancelled by the reducer)
+        Action::CancelChatMessage { message_id } => {
+            get_claude_processes().cancel(&message_id);
+            get_permission_bridge().close(&message_id);
+            get_llm_requests().cancel(&message_id);
+        }
+
+        // Write the accepted edits, then refresh what shows the worktree's files
+        Action::ApplyProposedEdits => {
+            let target = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| {
+                    let worktree = p.active_worktree()?;
+                    let accepted = worktree.chat.proposed_edits.as_ref()?.accepted();
+                    Some((worktree.path.clone(), accepted, worktree.explorer.current_path.clone()))
+                })
+            };
+            let Some((worktree_path, accepted, explorer_path)) = target.filter(|(_, accepted, _)| !accepted.is_empty()) else {
+                return Ok(());
+            };
+
+            let backup_dir = persistence::get_project_dir(&worktree_path)
+                .join("edit-backups")
+                .join(chrono::Utc::now().format("%Y%m%d-%H%M%S").to_string());
+            let result = edits::apply_edits(std::path::Path::new(&worktree_path), &accepted, &backup_dir);
+            {
+                let mut state = get_app_state().write().await;
+                match result {
+                    Ok(paths) => {
+                        let count = paths.len();
+                        reduce(&mut state, Action::SetProposedEditsApplied {
+                            paths,
+                            backup_dir: backup_dir.display().to_string(),
+                        });
+                        reduce(&mut state, Action::AddNotification {
+                            message: format!("Applied edits to {} file(s)", count),
+                            notification_type: actions::NotificationTypeData::Success,
+                        });
+                    }
+                    Err(error) => reduce(&mut state, Action::SetChatError { error }),
+                }
+            }
+            notify_state_update().await;
+
+            Box::pin(handle_async_action(Action::RefreshWorktrees)).await?;
+            Box::pin(handle_async_action(Action::ExploreDir { path: explorer_path })).await?;
+        }
+
+        // Answer a tool permission prompt (the reducer already cleared it)
+        Action::ApproveToolUse { ref request_id } | Action::DenyToolUse { ref request_id } => {
+            let decision = if matches!(action, Action::ApproveToolUse { .. }) {
+                claude_cli::PermissionDecision::Allow
+            } else {
+                claude_cli::PermissionDecision::Deny { message: "The user denied this tool call".to_string() }
+            };
+            if let Err(error) = get_permission_bridge().respond(request_id, &decision).await {
+                {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetChatError { error });
+                }
+                notify_state_update().await;
+            }
+        }
+
         // Agent Rules actions (sync - handled in reducer)
         Action::SetAgentRulesEnabled { .. }
         | Action::SetAgentRulesPrompt { .. }
         | Action::SetAgentRulesTempFile { .. }
         | Action::CreateAgentProfile { .. }
+        | Action::ImportAgentProfile { .. }
         | Action::UpdateAgentProfile { .. }
         | Action::DeleteAgentProfile { .. }
         | Action::SelectAgentProfile { .. } => {
@@ -1955,6 +7358,20 @@ async fn handle_async_action(action: Action) -> napi::Result<()> {
             // Sync actions - handled in reducer
         }
 
+        Action::CancelConstitutionGeneration => {
+            // Workflow already reset by the reducer; kill the running process
+            let worktree_path = {
+                let state = get_app_state().read().await;
+                state
+                    .active_project()
+                    .and_then(|p| p.active_worktree())
+                    .map(|w| std::path::PathBuf::from(&w.path))
+            };
+            if let Some(path) = worktree_path {
+                get_claude_processes().cancel(&constitution_process_key(&path));
+            }
+        }
+
         Action::GenerateConstitution => {
             // Get workflow state and build prompt
             let (cwd, answers, use_claude_md_reference) = {
@@ -2092,7 +7509,7 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
                 // Validate Claude CLI
                 if let Err(e) = claude_cli::validate_claude_cli().await {
                     let error_msg = format!("Claude CLI validation failed: {}", e);
-                    eprintln!("{}", error_msg);
+                    tracing::error!("{}", error_msg);
                     let mut state = get_app_state().write().await;
                     reduce(&mut state, Action::SetConstitutionError { error: error_msg });
                     drop(state);
@@ -2106,19 +7523,29 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
                     .arg("--verbose")
                     .arg("--output-format")
                     .arg("stream-json")
-                    .arg("--include-partial-messages")
-                    .arg(&prompt)
+                    .arg("--include-partial-messages");
+                if let Some(model) = active_claude_model().await {
+                    cmd.arg("--model").arg(model);
+                }
+                cmd.arg(&prompt)
                     .current_dir(&cwd_for_task)
                     .stdout(std::process::Stdio::piped())
                     .stderr(std::process::Stdio::piped());
 
+                let mut session = start_claude_session("constitution", std::path::Path::new(&cwd_for_task), &prompt).await;
                 match cmd.spawn() {
                     Ok(mut child) => {
                         let stdout = child.stdout.take().expect("Failed to get stdout");
                         let mut reader = tokio::io::BufReader::new(stdout).lines();
+                        let process_key = constitution_process_key(&cwd_for_task);
+                        get_claude_processes().register(&process_key, child);
 
                         // Stream output
                         while let Ok(Some(line)) = reader.next_line().await {
+                            if let Ok(event) = claude_cli::parse_jsonl_line(&line) {
+                                session.observe(&event);
+                                record_claude_usage(&event, "constitution").await;
+                            }
                             if let Ok(event) = serde_json::from_str::<serde_json::Value>(&line) {
                                 // Extract content from Claude streaming events
                                 if let Some(content_block) = event["content_block"].as_object() {
@@ -2149,7 +7576,10 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
                             }
                         }
 
-                        // Wait for process to complete
+                        // Wait for process to complete (None = cancelled, nothing to save)
+                        let Some(mut child) = get_claude_processes().take(&process_key) else {
+                            return;
+                        };
                         let _ = child.wait().await;
 
                         // After completion, save the constitution file
@@ -2175,7 +7605,7 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
                             // Create constitutions directory if it doesn't exist
                             if let Err(e) = tokio::fs::create_dir_all(&constitutions_dir).await {
                                 let error_msg = format!("Failed to create .rstn/constitutions directory: {}", e);
-                                eprintln!("{}", error_msg);
+                                tracing::error!("{}", error_msg);
                                 let mut state = get_app_state().write().await;
                                 reduce(&mut state, Action::SetConstitutionError { error: error_msg });
                                 drop(state);
@@ -2186,7 +7616,7 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
                             // Write constitution file
                             if let Err(e) = tokio::fs::write(&constitution_file, content).await {
                                 let error_msg = format!("Failed to write custom constitution: {}", e);
-                                eprintln!("{}", error_msg);
+                                tracing::error!("{}", error_msg);
                                 let mut state = get_app_state().write().await;
                                 reduce(&mut state, Action::SetConstitutionError { error: error_msg });
                                 drop(state);
@@ -2209,8 +7639,9 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
                         }
                     }
                     Err(e) => {
+                        session.fail(e.to_string());
                         let error_msg = format!("Failed to spawn Claude CLI: {}", e);
-                        eprintln!("{}", error_msg);
+                        tracing::error!("{}", error_msg);
                         let mut state = get_app_state().write().await;
                         reduce(&mut state, Action::SetConstitutionError { error: error_msg });
                         drop(state);
@@ -2243,15 +7674,21 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
 
                 // Create constitutions directory if it doesn't exist
                 if let Err(e) = tokio::fs::create_dir_all(&constitutions_dir).await {
-                    eprintln!("Failed to create .rstn/constitutions directory: {}", e);
+                    tracing::error!("Failed to create .rstn/constitutions directory: {}", e);
                     return Ok(());
                 }
 
                 // Write constitution file
                 if let Err(e) = tokio::fs::write(&constitution_file, content).await {
-                    eprintln!("Failed to write custom constitution: {}", e);
+                    tracing::error!("Failed to write custom constitution: {}", e);
                     return Ok(());
                 }
+                record_activity(
+                    activity::ActivityKind::ConstitutionUpdated,
+                    "Saved the generated constitution".to_string(),
+                    serde_json::json!({ "worktree_path": wt_path, "file": constitution_file.to_string_lossy() }),
+                )
+                .await;
 
                 // Update state to Complete (already done in reducer)
                 notify_state_update().await;
@@ -2316,6 +7753,27 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
             // Sync action - handled in reducer
         }
 
+        Action::LintConstitution => {
+            let Some((_, wt_path)) = active_worktree_id_and_path().await else {
+                return Ok(());
+            };
+            let report = tokio::task::spawn_blocking(move || {
+                constitution::lint::lint_constitution(std::path::Path::new(&wt_path))
+            })
+            .await
+            .map_err(|e| napi::Error::from_reason(format!("Constitution lint failed: {}", e)))?;
+
+            {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetConstitutionLintReport { report });
+            }
+            notify_state_update().await;
+        }
+
+        Action::SetConstitutionLintReport { .. } => {
+            // Sync action - handled in reducer
+        }
+
         Action::SetClaudeMdExists { .. } => {
             // Sync action - handled in reducer
         }
@@ -2398,14 +7856,58 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
         // ReviewGate Actions (CESDD ReviewGate Layer)
         // ====================================================================
         Action::StartReview { .. } => {
-            // Sync action - handled in reducer
+            // Session created in reducer; evaluate it against the constitution
+            let session_id = active_review_session_id(&*get_app_state().read().await);
+            if let Some(session_id) = session_id {
+                start_compliance_check(session_id).await;
+            }
         }
 
-        Action::AddReviewComment { .. } => {
-            // Sync action - handled in reducer
+        // Added/resolved in the reducer; save it with the change the review belongs to
+        Action::AddReviewComment { ref session_id, .. } | Action::ResolveReviewComment { ref session_id, .. } => {
+            let comment_id = match &action {
+                Action::ResolveReviewComment { comment_id, .. } => Some(comment_id.clone()),
+                _ => None,
+            };
+            let target = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| p.active_worktree()).and_then(|w| {
+                    let change = w.changes.changes.iter().find(|c| c.has_review_session(session_id))?;
+                    let comment = match &comment_id {
+                        Some(id) => change.review_comments.iter().find(|c| &c.id == id)?,
+                        None => change.review_comments.last()?,
+                    };
+                    Some((w.path.clone(), change.name.clone(), comment.clone()))
+                })
+            };
+            if let Some((wt_path, change_name, comment)) = target {
+                if let Err(e) = review_comments::save_comment(std::path::Path::new(&wt_path), &change_name, &comment) {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetReviewGateError { error: Some(e) });
+                }
+            }
+        }
+
+        Action::LoadReviewComments { ref change_id } => {
+            let target = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| p.active_worktree()).and_then(|w| {
+                    let change = w.changes.changes.iter().find(|c| &c.id == change_id)?;
+                    Some((w.path.clone(), change.name.clone()))
+                })
+            };
+            let Some((wt_path, change_name)) = target else {
+                return Ok(());
+            };
+            let result = review_comments::load(std::path::Path::new(&wt_path), &change_name);
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok(comments) => reduce(&mut state, Action::SetReviewComments { change_id: change_id.clone(), comments }),
+                Err(e) => reduce(&mut state, Action::SetReviewGateError { error: Some(e) }),
+            }
         }
 
-        Action::ResolveReviewComment { .. } => {
+        Action::SetReviewComments { .. } => {
             // Sync action - handled in reducer
         }
 
@@ -2477,11 +7979,15 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
                 
                 // Set status back to Reviewing
                 reduce(&mut state, Action::SetReviewStatus { 
-                    session_id: session_id_clone, 
+                    session_id: session_id_clone.clone(), 
                     status: actions::ReviewStatusData::Reviewing 
                 });
-                
+                // The new iteration needs its own compliance check
+                reduce(&mut state, Action::RunComplianceCheck { session_id: session_id_clone.clone() });
+                drop(state);
+
                 notify_state_update().await;
+                run_compliance_check(session_id_clone).await;
             });
         }
 
@@ -2513,6 +8019,15 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
             // Sync action - handled in reducer
         }
 
+        Action::RunComplianceCheck { session_id } => {
+            // Marked running in reducer; evaluate without blocking dispatch
+            tokio::spawn(run_compliance_check(session_id));
+        }
+
+        Action::SetComplianceCheck { .. } | Action::OverrideComplianceCheck { .. } => {
+            // Sync actions - handled in reducer
+        }
+
         Action::ApplyDefaultConstitution => {
             // Get the active worktree path
             let worktree_path = {
@@ -2529,6 +8044,12 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
                 // Create modular constitution with language detection
                 match constitution::create_modular_constitution(project_path).await {
                     Ok(()) => {
+                        record_activity(
+                            activity::ActivityKind::ConstitutionUpdated,
+                            "Applied the default constitution".to_string(),
+                            serde_json::json!({ "worktree_path": wt_path }),
+                        )
+                        .await;
                         // Update state
                         {
                             let mut state = get_app_state().write().await;
@@ -2537,7 +8058,7 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
                         notify_state_update().await;
                     }
                     Err(e) => {
-                        eprintln!("Failed to create modular constitution: {}", e);
+                        tracing::error!("Failed to create modular constitution: {}", e);
                     }
                 }
             }
@@ -2546,42 +8067,87 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
         // ====================================================================
         // Change Management Actions (CESDD Phase 2 - async operations)
         // ====================================================================
-        Action::CreateChange { intent } => {
-            // Get the active worktree path
-            let worktree_path = {
+        Action::CreateChange { intent, create_branch } => {
+            // Get the project and active worktree paths
+            let paths = {
                 let state = get_app_state().read().await;
                 state
                     .active_project()
-                    .and_then(|p| p.active_worktree())
-                    .map(|w| w.path.clone())
+                    .and_then(|p| p.active_worktree().map(|w| (p.path.clone(), w.path.clone())))
             };
 
-            if let Some(wt_path) = worktree_path {
+            if let Some((project_path, wt_path)) = paths {
                 // Generate change ID and name from intent
                 let change_id = format!("change-{}", chrono::Utc::now().timestamp_millis());
-                let change_name = slugify(&intent);
+                let change_name = change_slug(&intent).await;
                 let now = chrono::Utc::now().to_rfc3339();
 
+                // Optionally work on the change in its own branch and worktree
+                let (wt_path, branch) = if create_branch {
+                    let branch = pull_request::branch_name(&change_name);
+                    let created = worktree::add_worktree_new_branch(&project_path, &branch);
+                    {
+                        let mut state = get_app_state().write().await;
+                        if let Some(worktree) = state.active_project_mut().and_then(|p| p.active_worktree_mut()) {
+                            worktree.changes.is_loading = false;
+                        }
+                    }
+                    match created {
+                        Ok(new_worktree) => {
+                            refresh_worktrees_for_path(&project_path).await;
+                            (new_worktree.path, Some(branch))
+                        }
+                        Err(e) => {
+                            let mut state = get_app_state().write().await;
+                            reduce(
+                                &mut state,
+                                Action::SetError {
+                                    code: "CHANGE_BRANCH_ERROR".to_string(),
+                                    message: e,
+                                    context: Some(format!("CreateChange: {}", branch)),
+                                },
+                            );
+                            return Ok(());
+                        }
+                    }
+                } else {
+                    (wt_path, None)
+                };
+
                 // Create change directory: .rstn/changes/<change-name>/
                 let changes_dir = std::path::Path::new(&wt_path)
                     .join(".rstn")
                     .join("changes")
                     .join(&change_name);
                 if let Err(e) = std::fs::create_dir_all(&changes_dir) {
-                    eprintln!("Failed to create changes directory: {}", e);
+                    tracing::error!("Failed to create changes directory: {}", e);
                     return Ok(());
                 }
 
                 // Write intent.md
                 let intent_path = changes_dir.join("intent.md");
                 if let Err(e) = std::fs::write(&intent_path, &intent) {
-                    eprintln!("Failed to write intent.md: {}", e);
+                    tracing::error!("Failed to write intent.md: {}", e);
                     return Ok(());
                 }
 
+                if branch.is_some() {
+                    let link = pull_request::ChangeLink { branch: branch.clone(), pull_request_url: None };
+                    if let Err(e) = pull_request::save_link(std::path::Path::new(&wt_path), &change_name, &link) {
+                        tracing::warn!("Failed to save change branch: {}", e);
+                    }
+                }
+
+                let activity_detail = serde_json::json!({
+                    "change_id": change_id,
+                    "name": change_name,
+                    "branch": branch,
+                });
+                let activity_summary = format!("Created change {}", change_name);
+
                 // Create the change in state
                 let change = app_state::Change {
-                    id: change_id,
+                    id: change_id.clone(),
                     name: change_name,
                     status: app_state::ChangeStatus::Proposed,
                     intent: intent.clone(),
@@ -2593,10 +8159,26 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
                     proposal_review_session_id: None,
                     plan_review_session_id: None,
                     context_files: Vec::new(),
+                    implementation_tasks: Vec::new(),
+                    review_comments: Vec::new(),
+                    branch,
+                    pull_request_url: None,
+                    snapshot: None,
+                    test_results: None,
+                    hook_results: Vec::new(),
+                    status_conflict: None,
+                    implementation_approval: None,
                 };
 
                 {
+                    // Add it to the worktree it lives in and switch there
                     let mut state = get_app_state().write().await;
+                    let index = state
+                        .active_project()
+                        .and_then(|p| p.worktrees.iter().position(|w| w.path == wt_path));
+                    if let Some(index) = index {
+                        reduce(&mut state, Action::SwitchWorktree { index });
+                    }
                     if let Some(project) = state.active_project_mut() {
                         if let Some(worktree) = project.active_worktree_mut() {
                             worktree.changes.changes.push(change);
@@ -2604,10 +8186,77 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
                         }
                     }
                 }
+                save_change_status(&change_id).await;
+                record_activity(activity::ActivityKind::ChangeCreated, activity_summary, activity_detail).await;
                 notify_state_update().await;
             }
         }
 
+        Action::CreateChangeFromIssue { issue_number, create_branch } => {
+            let target = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| p.active_worktree()).map(|w| {
+                    (w.path.clone(), state.global_settings.git_hosting.github_token.clone())
+                })
+            };
+            let Some((wt_path, token)) = target else {
+                return Ok(());
+            };
+
+            let fetched = async {
+                let repo = github::resolve_repo(&wt_path)?;
+                let issue = github::get_issue(&repo, token.as_deref(), issue_number).await?;
+                Ok::<_, String>((repo, issue))
+            }
+            .await;
+            let (repo, issue) = match fetched {
+                Ok(fetched) => fetched,
+                Err(e) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetChangesLoading { is_loading: false });
+                    reduce(&mut state, Action::SetError {
+                        code: "GITHUB_ISSUE_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("CreateChangeFromIssue: #{}", issue_number)),
+                    });
+                    return Ok(());
+                }
+            };
+
+            let intent = github::intent_from_issue(&issue);
+            Box::pin(handle_async_action(Action::CreateChange { intent: intent.clone(), create_branch })).await?;
+
+            // CreateChange adds the change last to the worktree it switched to
+            let change_name = {
+                let state = get_app_state().read().await;
+                state
+                    .active_project()
+                    .and_then(|p| p.active_worktree())
+                    .and_then(|w| w.changes.changes.last())
+                    .filter(|c| c.intent == intent)
+                    .map(|c| c.name.clone())
+            };
+            let Some(change_name) = change_name else {
+                return Ok(());
+            };
+
+            let linked = match token {
+                Some(token) => github::link_issue(&repo, &token, issue_number, &change_name).await,
+                None => Err("Set a GitHub token in Settings to label the issue".to_string()),
+            };
+            let mut state = get_app_state().write().await;
+            match linked {
+                Ok(()) => reduce(&mut state, Action::AddNotification {
+                    message: format!("Created change {} from issue #{}", change_name, issue_number),
+                    notification_type: actions::NotificationTypeData::Success,
+                }),
+                Err(e) => reduce(&mut state, Action::AddNotification {
+                    message: format!("Created change {}, but could not update issue #{}: {}", change_name, issue_number, e),
+                    notification_type: actions::NotificationTypeData::Warning,
+                }),
+            }
+        }
+
         Action::GenerateProposal { change_id } => {
             // Get change data and worktree path
             let (change_data, worktree_path) = {
@@ -2625,11 +8274,11 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
             };
 
             let Some(change) = change_data else {
-                eprintln!("GenerateProposal: Change not found: {}", change_id);
+                tracing::warn!("GenerateProposal: Change not found: {}", change_id);
                 return Ok(());
             };
             let Some(wt_path) = worktree_path else {
-                eprintln!("GenerateProposal: No active worktree");
+                tracing::warn!("GenerateProposal: No active worktree");
                 return Ok(());
             };
 
@@ -2652,6 +8301,16 @@ Be specific, actionable, and authoritative. Use "MUST", "MUST NOT" language."#,
             // Read selected context files
             let context_files_section = build_context_files_section(&change.context_files, &wt_path);
 
+            // Prefer intent.md on disk (user may have edited it), fall back to state
+            let change_dir = std::path::Path::new(&wt_path)
+                .join(".rstn")
+                .join("changes")
+                .join(&change.name);
+            let intent = std::fs::read_to_string(change_dir.join("intent.md"))
+                .ok()
+                .filter(|s| !s.trim().is_empty())
+                .unwrap_or_else(|| change.intent.clone());
+
             // Build prompt for proposal generation
             let prompt = format!(
                 r#"You are a senior software architect. Generate a proposal document for the following feature request.
@@ -2675,14 +8334,15 @@ Write a proposal.md document that includes:
 Output ONLY the markdown content, no code blocks or extra formatting."#,
                 if constitution_content.is_empty() { "(No constitution found)".to_string() } else { constitution_content },
                 context_files_section,
-                change.intent
+                intent
             );
 
             // Spawn Claude CLI with streaming
             let cwd = std::path::Path::new(&wt_path);
             let change_id_clone = change_id.clone();
 
-            match claude_cli::spawn_claude(&prompt, cwd, None, None) {
+            let mut session = start_claude_session("proposal", cwd, &prompt).await;
+            match claude_cli::spawn_claude(&prompt, cwd, None, None, None, active_claude_model().await.as_deref()) {
                 Ok(mut child) => {
                     // Monitor stderr
                     if let Some(stderr) = child.stderr.take() {
@@ -2692,7 +8352,7 @@ Output ONLY the markdown content, no code blocks or extra formatting."#,
                             while let Ok(Some(line)) = lines.next_line().await {
                                 let trimmed = line.trim();
                                 if !trimmed.is_empty() {
-                                    eprintln!("[Claude CLI stderr] {}", trimmed);
+                                    tracing::debug!("[Claude CLI stderr] {}", trimmed);
                                 }
                             }
                         });
@@ -2701,14 +8361,33 @@ Output ONLY the markdown content, no code blocks or extra formatting."#,
                     // Create event stream
                     match claude_cli::ClaudeEventStream::new(&mut child) {
                         Ok(mut stream) => {
+                            let process_key = proposal_process_key(&change_id_clone);
+                            get_claude_processes().register(&process_key, child);
+                            let start_time = std::time::Instant::now();
                             let mut full_output = String::new();
 
                             loop {
-                                match tokio::time::timeout(
+                                // Check total timeout (5 minutes)
+                                if start_time.elapsed() > claude_cli::TOTAL_TIMEOUT {
+                                    fail_proposal(&change_id_clone, "Request exceeded 5 minute timeout".to_string()).await;
+                                    break;
+                                }
+
+                                let next_event = tokio::time::timeout(
                                     claude_cli::EVENT_TIMEOUT,
                                     stream.next_event()
-                                ).await {
+                                ).await;
+
+                                // Cancelled via CancelProposal - state already reset
+                                if !get_claude_processes().is_running(&process_key) {
+                                    break;
+                                }
+
+                                match next_event {
                                     Ok(Some(Ok(event))) => {
+                                        session.observe(&event);
+                                        record_claude_usage(&event, "proposal").await;
+
                                         // Extract text from streaming events
                                         if let Some(text_chunk) = claude_cli::extract_text_delta(&event) {
                                             full_output.push_str(text_chunk);
@@ -2737,54 +8416,83 @@ Output ONLY the markdown content, no code blocks or extra formatting."#,
                                         // Check for completion
                                         if claude_cli::is_message_stop(&event) {
                                             // Write proposal.md to change directory
-                                            let proposal_path = std::path::Path::new(&wt_path)
-                                                .join(".rstn")
-                                                .join("changes")
-                                                .join(&change.name)
-                                                .join("proposal.md");
-                                            if let Err(e) = std::fs::write(&proposal_path, &full_output) {
-                                                eprintln!("Failed to write proposal.md: {}", e);
+                                            let write_result = std::fs::create_dir_all(&change_dir)
+                                                .and_then(|_| std::fs::write(change_dir.join("proposal.md"), &full_output));
+                                            if let Err(e) = write_result {
+                                                fail_proposal(&change_id_clone, format!("Failed to write proposal.md: {}", e)).await;
+                                                break;
                                             }
 
                                             // Mark complete
-                                            {
+                                            let review_session_id = {
                                                 let mut state = get_app_state().write().await;
                                                 reduce(&mut state, Action::CompleteProposal {
                                                     change_id: change_id_clone.clone(),
                                                 });
-                                            }
+                                                active_review_session_id(&state)
+                                            };
+                                            save_change_status(&change_id_clone).await;
                                             notify_state_update().await;
+                                            notify_desktop(
+                                                DesktopNotificationEvent::ClaudeFinished,
+                                                desktop_notifications::claude_finished("proposal", &change.name),
+                                            )
+                                            .await;
                                             // ReviewGate review is auto-started in CompleteProposal reducer
+                                            if let Some(session_id) = review_session_id {
+                                                start_compliance_check(session_id).await;
+                                            }
                                             break;
                                         }
                                     }
                                     Ok(Some(Err(e))) => {
-                                        eprintln!("GenerateProposal stream error: {}", e);
+                                        fail_proposal(&change_id_clone, e.to_string()).await;
+                                        break;
+                                    }
+                                    Ok(None) => {
+                                        // Stream ended without message_stop
+                                        fail_proposal(
+                                            &change_id_clone,
+                                            "Claude CLI ended unexpectedly. Check if you have valid API credentials.".to_string(),
+                                        ).await;
                                         break;
                                     }
-                                    Ok(None) => break,
                                     Err(_) => {
-                                        eprintln!("GenerateProposal timeout");
+                                        fail_proposal(&change_id_clone, "No response from Claude CLI for 30 seconds".to_string()).await;
                                         break;
                                     }
                                 }
                             }
+
+                            // Wait for process to finish (already killed if cancelled)
+                            if let Some(mut child) = get_claude_processes().take(&process_key) {
+                                let _ = child.wait().await;
+                            }
                         }
                         Err(e) => {
-                            eprintln!("Failed to create Claude event stream: {}", e);
+                            fail_proposal(&change_id_clone, e.to_string()).await;
                         }
                     }
                 }
                 Err(e) => {
-                    eprintln!("Failed to spawn Claude CLI: {}", e);
+                    session.fail(e.to_string());
+                    fail_proposal(&change_id_clone, e.to_string()).await;
                 }
             }
         }
 
-        Action::AppendProposalOutput { .. } | Action::CompleteProposal { .. } => {
+        Action::AppendProposalOutput { .. }
+        | Action::CompleteProposal { .. }
+        | Action::FailProposal { .. } => {
             // Sync actions - handled in reducer
         }
 
+        Action::CancelProposal { change_id } => {
+            // Change already reset by the reducer; kill the running process
+            get_claude_processes().cancel(&proposal_process_key(&change_id));
+            save_change_status(&change_id).await;
+        }
+
         Action::GeneratePlan { change_id } => {
             // Get change data and worktree path
             let (change_data, worktree_path) = {
@@ -2802,15 +8510,15 @@ Output ONLY the markdown content, no code blocks or extra formatting."#,
             };
 
             let Some(change) = change_data else {
-                eprintln!("GeneratePlan: Change not found: {}", change_id);
+                tracing::warn!("GeneratePlan: Change not found: {}", change_id);
                 return Ok(());
             };
             let Some(wt_path) = worktree_path else {
-                eprintln!("GeneratePlan: No active worktree");
+                tracing::warn!("GeneratePlan: No active worktree");
                 return Ok(());
             };
             let Some(proposal) = change.proposal.as_ref() else {
-                eprintln!("GeneratePlan: No proposal found for change: {}", change_id);
+                tracing::warn!("GeneratePlan: No proposal found for change: {}", change_id);
                 return Ok(());
             };
 
@@ -2859,7 +8567,8 @@ Output ONLY the markdown content, no code blocks or extra formatting."#,
             let cwd = std::path::Path::new(&wt_path);
             let change_id_clone = change_id.clone();
 
-            match claude_cli::spawn_claude(&prompt, cwd, None, None) {
+            let mut session = start_claude_session("plan", cwd, &prompt).await;
+            match claude_cli::spawn_claude(&prompt, cwd, None, None, None, active_claude_model().await.as_deref()) {
                 Ok(mut child) => {
                     // Monitor stderr
                     if let Some(stderr) = child.stderr.take() {
@@ -2869,7 +8578,7 @@ Output ONLY the markdown content, no code blocks or extra formatting."#,
                             while let Ok(Some(line)) = lines.next_line().await {
                                 let trimmed = line.trim();
                                 if !trimmed.is_empty() {
-                                    eprintln!("[Claude CLI stderr] {}", trimmed);
+                                    tracing::debug!("[Claude CLI stderr] {}", trimmed);
                                 }
                             }
                         });
@@ -2886,6 +8595,9 @@ Output ONLY the markdown content, no code blocks or extra formatting."#,
                                     stream.next_event()
                                 ).await {
                                     Ok(Some(Ok(event))) => {
+                                        session.observe(&event);
+                                        record_claude_usage(&event, "plan").await;
+
                                         // Extract text from streaming events
                                         if let Some(text_chunk) = claude_cli::extract_text_delta(&event) {
                                             full_output.push_str(text_chunk);
@@ -2920,54 +8632,91 @@ Output ONLY the markdown content, no code blocks or extra formatting."#,
                                                 .join(&change.name)
                                                 .join("plan.md");
                                             if let Err(e) = std::fs::write(&plan_path, &full_output) {
-                                                eprintln!("Failed to write plan.md: {}", e);
+                                                tracing::error!("Failed to write plan.md: {}", e);
                                             }
 
                                             // Mark complete
-                                            {
+                                            let review_session_id = {
                                                 let mut state = get_app_state().write().await;
                                                 reduce(&mut state, Action::CompletePlan {
                                                     change_id: change_id_clone.clone(),
                                                 });
-                                            }
+                                                active_review_session_id(&state)
+                                            };
+                                            save_change_status(&change_id_clone).await;
                                             notify_state_update().await;
+                                            notify_desktop(
+                                                DesktopNotificationEvent::ClaudeFinished,
+                                                desktop_notifications::claude_finished("plan", &change.name),
+                                            )
+                                            .await;
                                             // ReviewGate review is auto-started in CompletePlan reducer
+                                            if let Some(session_id) = review_session_id {
+                                                start_compliance_check(session_id).await;
+                                            }
                                             break;
                                         }
                                     }
                                     Ok(Some(Err(e))) => {
-                                        eprintln!("GeneratePlan stream error: {}", e);
+                                        tracing::error!("GeneratePlan stream error: {}", e);
                                         break;
                                     }
                                     Ok(None) => break,
                                     Err(_) => {
-                                        eprintln!("GeneratePlan timeout");
+                                        tracing::error!("GeneratePlan timeout");
                                         break;
                                     }
                                 }
                             }
                         }
                         Err(e) => {
-                            eprintln!("Failed to create Claude event stream: {}", e);
+                            tracing::error!("Failed to create Claude event stream: {}", e);
                         }
                     }
                 }
                 Err(e) => {
-                    eprintln!("Failed to spawn Claude CLI: {}", e);
+                    session.fail(e.to_string());
+                    tracing::error!("Failed to spawn Claude CLI: {}", e);
                 }
             }
         }
 
+        Action::ApprovePlan { ref change_id } => {
+            // The reducer leaves the change unplanned while the review gate blocks it
+            let approved = {
+                let state = get_app_state().read().await;
+                state
+                    .active_project()
+                    .and_then(|p| p.active_worktree())
+                    .and_then(|w| w.changes.changes.iter().find(|c| &c.id == change_id))
+                    .filter(|c| c.status == app_state::ChangeStatus::Planned)
+                    .map(|c| c.name.clone())
+            };
+            if let Some(name) = approved {
+                save_change_status(change_id).await;
+                record_activity(
+                    activity::ActivityKind::PlanApproved,
+                    format!("Approved the plan of {}", name),
+                    serde_json::json!({ "change_id": change_id, "name": name }),
+                )
+                .await;
+            }
+        }
+
+        Action::CancelChange { ref change_id } => {
+            save_change_status(change_id).await;
+        }
+
         Action::AppendPlanOutput { .. }
         | Action::CompletePlan { .. }
-        | Action::ApprovePlan { .. }
-        | Action::CancelChange { .. }
         | Action::SelectChange { .. }
         | Action::SetChangesLoading { .. }
         | Action::AddContextFile { .. }
         | Action::RemoveContextFile { .. }
         | Action::ClearContextFiles { .. }
         | Action::AppendImplementationOutput { .. }
+        | Action::CompleteImplementationTask { .. }
+        | Action::StartImplementationTests { .. }
         | Action::CompleteImplementation { .. }
         | Action::FailImplementation { .. }
         | Action::StartProposalReview { .. }
@@ -3000,68 +8749,50 @@ Output ONLY the markdown content, no code blocks or extra formatting."#,
             };
 
             let Some(change) = change_data else {
-                eprintln!("ExecutePlan: Change not found: {}", change_id);
+                tracing::warn!("ExecutePlan: Change not found: {}", change_id);
                 return Ok(());
             };
             let Some(plan) = plan_content else {
-                eprintln!("ExecutePlan: No plan found for change: {}", change_id);
+                tracing::warn!("ExecutePlan: No plan found for change: {}", change_id);
                 return Ok(());
             };
             let Some(wt_path) = worktree_path else {
-                eprintln!("ExecutePlan: No active worktree");
+                tracing::warn!("ExecutePlan: No active worktree");
                 return Ok(());
             };
 
-            // Set status to Implementing
-            {
-                let mut state = get_app_state().write().await;
-                if let Some(project) = state.active_project_mut() {
-                    if let Some(worktree) = project.active_worktree_mut() {
-                        if let Some(c) = worktree.changes.changes.iter_mut().find(|c| c.id == change_id) {
-                            c.status = app_state::ChangeStatus::Implementing;
-                            c.streaming_output.clear();
-                        }
-                    }
-                }
-            }
+            // The reducer already set the status to Implementing (and split
+            // plan.md into tracked tasks)
             notify_state_update().await;
+            snapshot_before_implementation(&wt_path, &change).await;
+            let task_count = implementation::parse_plan_tasks(&plan).len();
 
-            // Build prompt for implementation
-            let prompt = format!(
-                r#"You are an expert software engineer. Implement the following plan step by step.
-
-## Constitution (Development Rules)
-{constitution_content}
-
-## Project Context
-{context_content}
-
-## Feature Intent
-{intent}
-
-## Proposal
-{proposal}
-
-## Implementation Plan
-{plan}
-
-## Instructions
-1. Implement each step in the plan sequentially
-2. Follow the constitution rules strictly
-3. Write clean, tested code
-4. Report what you've done after each step
-
-Execute the plan now. Start implementing."#,
-                intent = change.intent,
-                proposal = change.proposal.as_deref().unwrap_or("(no proposal)"),
-            );
+            let prompt = implementation_prompt(&change, &plan, &constitution_content, &context_content);
 
             // Spawn Claude CLI with streaming
             let cwd = std::path::Path::new(&wt_path);
             let change_id_clone = change_id.clone();
 
-            match claude_cli::spawn_claude(&prompt, cwd, None, None) {
-                Ok(mut child) => {
+            let process_key = implementation_process_key(&change_id);
+            let total_timeout = implementation_timeout().await;
+
+            // Tool calls outside --allowedTools go through the same guardrail
+            // check (and pause for approval) as agent runs
+            let profile_rails = get_app_state().read().await.global_settings.guardrails.clone();
+            let mut session = start_claude_session("implementation", cwd, &prompt).await;
+            let spawned = match guardrails::load_or(cwd, profile_rails.as_ref()) {
+                Ok(rails) => claude_cli::spawn_claude_with_tools(&prompt, cwd, &rails.allowed_tools(), active_claude_model().await.as_deref())
+                    .await
+                    .map(|(child, stdin)| (rails, child, stdin))
+                    .map_err(|e| {
+                        session.fail(e.to_string());
+                        format!("Failed to spawn Claude CLI: {}", e)
+                    }),
+                Err(e) => Err(e),
+            };
+            let implementation_result: Result<(), String> = match spawned {
+                Ok((rails, mut child, stdin)) => {
+                    get_permission_bridge().register(&process_key, stdin);
                     // Monitor stderr
                     if let Some(stderr) = child.stderr.take() {
                         tokio::spawn(async move {
@@ -3070,7 +8801,7 @@ Execute the plan now. Start implementing."#,
                             while let Ok(Some(line)) = lines.next_line().await {
                                 let trimmed = line.trim();
                                 if !trimmed.is_empty() {
-                                    eprintln!("[Claude CLI stderr] {}", trimmed);
+                                    tracing::debug!("[Claude CLI stderr] {}", trimmed);
                                 }
                             }
                         });
@@ -3079,95 +8810,362 @@ Execute the plan now. Start implementing."#,
                     // Create event stream
                     match claude_cli::ClaudeEventStream::new(&mut child) {
                         Ok(mut stream) => {
-                            loop {
-                                match tokio::time::timeout(
-                                    claude_cli::EVENT_TIMEOUT,
-                                    stream.next_event()
-                                ).await {
+                            get_claude_processes().register(&process_key, child);
+                            let start_time = std::time::Instant::now();
+                            // Time spent waiting for the user doesn't count against the timeouts
+                            let mut approval_started: Option<std::time::Instant> = None;
+                            let mut approval_wait = std::time::Duration::ZERO;
+                            let mut full_output = String::new();
+                            let mut completed_tasks: std::collections::HashSet<usize> = std::collections::HashSet::new();
+
+                            let result = loop {
+                                if start_time.elapsed().saturating_sub(approval_wait) > total_timeout {
+                                    break Err(format!(
+                                        "Implementation exceeded {} minute timeout",
+                                        total_timeout.as_secs().div_ceil(60)
+                                    ));
+                                }
+
+                                let next_event = if approval_started.is_some() {
+                                    Ok(stream.next_event().await)
+                                } else {
+                                    tokio::time::timeout(claude_cli::EVENT_TIMEOUT, stream.next_event()).await
+                                };
+
+                                // Cancelled via CancelImplementation - state already reset
+                                if !get_claude_processes().is_running(&process_key) {
+                                    get_permission_bridge().close(&process_key);
+                                    return Ok(());
+                                }
+
+                                match next_event {
                                     Ok(Some(Ok(event))) => {
-                                        // Extract text from streaming events
-                                        if let Some(text_chunk) = claude_cli::extract_text_delta(&event) {
-                                            {
-                                                let mut state = get_app_state().write().await;
-                                                reduce(&mut state, Action::AppendImplementationOutput {
-                                                    change_id: change_id_clone.clone(),
-                                                    content: text_chunk.to_string(),
-                                                });
-                                            }
-                                            notify_state_update().await;
+                                        session.observe(&event);
+                                        record_claude_usage(&event, "implementation").await;
+                                        if let Some(started) = approval_started.take() {
+                                            approval_wait += started.elapsed();
                                         }
 
-                                        if let Some(text_content) = claude_cli::extract_assistant_text(&event) {
-                                            {
-                                                let mut state = get_app_state().write().await;
-                                                reduce(&mut state, Action::AppendImplementationOutput {
-                                                    change_id: change_id_clone.clone(),
-                                                    content: text_content,
-                                                });
+                                        if let Some(request) = claude_cli::extract_permission_request(&event) {
+                                            match screen_permission_request(&process_key, &rails, cwd, request).await {
+                                                Err(e) => break Err(e),
+                                                Ok(None) => {}
+                                                Ok(Some(approval)) => {
+                                                    approval_started = Some(std::time::Instant::now());
+                                                    {
+                                                        let mut state = get_app_state().write().await;
+                                                        reduce(&mut state, Action::AddNotification {
+                                                            message: format!("{} needs approval: {}", change.name, approval.reason),
+                                                            notification_type: actions::NotificationTypeData::Warning,
+                                                        });
+                                                        reduce(&mut state, Action::RequestImplementationApproval {
+                                                            change_id: change_id_clone.clone(),
+                                                            approval,
+                                                        });
+                                                    }
+                                                    notify_state_update().await;
+                                                }
                                             }
-                                            notify_state_update().await;
+                                            continue;
                                         }
 
-                                        // Check for completion
-                                        if claude_cli::is_message_stop(&event) {
-                                            // Mark complete - implementation done!
-                                            {
-                                                let mut state = get_app_state().write().await;
-                                                reduce(&mut state, Action::CompleteImplementation {
-                                                    change_id: change_id_clone.clone(),
-                                                });
-                                            }
-                                            notify_state_update().await;
-                                            break;
+                                        // Extract text from streaming events
+                                        let mut chunks = Vec::new();
+                                        if let Some(text_chunk) = claude_cli::extract_text_delta(&event) {
+                                            chunks.push(text_chunk.to_string());
                                         }
-                                    }
-                                    Ok(Some(Err(e))) => {
-                                        eprintln!("ExecutePlan: Event parse error: {}", e);
-                                    }
-                                    Ok(None) => {
-                                        // Stream ended
-                                        {
+                                        if let Some(text_content) = claude_cli::extract_assistant_text(&event) {
+                                            chunks.push(text_content);
+                                        }
+
+                                        for content in chunks {
+                                            full_output.push_str(&content);
                                             let mut state = get_app_state().write().await;
-                                            reduce(&mut state, Action::CompleteImplementation {
+                                            reduce(&mut state, Action::AppendImplementationOutput {
                                                 change_id: change_id_clone.clone(),
+                                                content,
                                             });
+
+                                            // Track per-task completion markers
+                                            for task_index in implementation::completed_task_indices(&full_output, task_count) {
+                                                if completed_tasks.insert(task_index) {
+                                                    reduce(&mut state, Action::CompleteImplementationTask {
+                                                        change_id: change_id_clone.clone(),
+                                                        task_index,
+                                                    });
+                                                }
+                                            }
                                         }
                                         notify_state_update().await;
-                                        break;
+
+                                        if claude_cli::is_message_stop(&event) {
+                                            break Ok(());
+                                        }
                                     }
+                                    Ok(Some(Err(e))) => {
+                                        tracing::error!("ExecutePlan: Event parse error: {}", e);
+                                    }
+                                    // Stream ended
+                                    Ok(None) => break Ok(()),
                                     Err(_) => {
-                                        eprintln!("ExecutePlan: Timeout waiting for event");
-                                        break;
+                                        break Err("No response from Claude CLI for 30 seconds".to_string());
                                     }
                                 }
+                            };
+
+                            // Stop the CLI if the run failed, then wait for it to exit
+                            // (closing its stdin ends the session)
+                            get_permission_bridge().close(&process_key);
+                            if let Some(mut child) = get_claude_processes().take(&process_key) {
+                                if result.is_err() {
+                                    let _ = child.start_kill();
+                                }
+                                let _ = child.wait().await;
                             }
+                            result
                         }
                         Err(e) => {
-                            eprintln!("ExecutePlan: Failed to create event stream: {}", e);
-                            {
-                                let mut state = get_app_state().write().await;
-                                reduce(&mut state, Action::FailImplementation {
-                                    change_id: change_id_clone,
-                                    error: e.to_string(),
-                                });
-                            }
-                            notify_state_update().await;
+                            get_permission_bridge().close(&process_key);
+                            let _ = child.start_kill();
+                            let _ = child.wait().await;
+                            Err(format!("Failed to create event stream: {}", e))
+                        }
+                    }
+                }
+                Err(e) => Err(e),
+            };
+
+            // Run the lint / format hooks, then the project's test command
+            // to verify the implementation
+            if implementation_result.is_ok() {
+                let hook_summary = run_post_implementation_hooks(&wt_path, &change).await;
+                if !hook_summary.is_empty() {
+                    {
+                        let mut state = get_app_state().write().await;
+                        reduce(&mut state, Action::AppendImplementationOutput {
+                            change_id: change_id_clone.clone(),
+                            content: hook_summary,
+                        });
+                    }
+                    notify_state_update().await;
+                }
+            }
+            let outcome = match implementation_result {
+                Err(e) => Err(e),
+                Ok(()) => match implementation_test_command(&wt_path).await {
+                    None => Ok(()),
+                    Some((test_command, coverage)) => {
+                        {
+                            let mut state = get_app_state().write().await;
+                            reduce(&mut state, Action::StartImplementationTests {
+                                change_id: change_id_clone.clone(),
+                            });
+                            reduce(&mut state, Action::AppendImplementationOutput {
+                                change_id: change_id_clone.clone(),
+                                content: format!("\n\n$ {}\n", test_command.display()),
+                            });
                         }
+                        notify_state_update().await;
+
+                        run_implementation_tests(&test_command, coverage.as_ref(), &wt_path, &change).await
+                    }
+                },
+            };
+
+            let (event, notification) = match &outcome {
+                Ok(()) => (
+                    DesktopNotificationEvent::ClaudeFinished,
+                    desktop_notifications::claude_finished("implementation", &change.name),
+                ),
+                Err(error) => (
+                    DesktopNotificationEvent::ImplementationFailed,
+                    desktop_notifications::implementation_failed(&change.name, error),
+                ),
+            };
+            {
+                let mut state = get_app_state().write().await;
+                match outcome {
+                    Ok(()) => reduce(&mut state, Action::CompleteImplementation {
+                        change_id: change_id_clone,
+                    }),
+                    Err(error) => {
+                        tracing::error!("ExecutePlan: {}", error);
+                        reduce(&mut state, Action::FailImplementation {
+                            change_id: change_id_clone,
+                            error,
+                        });
+                    }
+                }
+            }
+            save_change_status(&change_id).await;
+            notify_state_update().await;
+            notify_desktop(event, notification).await;
+        }
+
+        Action::CancelImplementation { change_id } => {
+            // Change already reset by the reducer; kill the running process
+            get_claude_processes().cancel(&implementation_process_key(&change_id));
+            save_change_status(&change_id).await;
+        }
+
+        Action::QueueAgentRun { worktree_path, change_id } => {
+            queue_agent_run(worktree_path, change_id).await;
+        }
+
+        Action::SetAgentMaxConcurrent { .. } => {
+            pump_agent_runs().await;
+        }
+
+        // The reducer already marked the run cancelled; stop its Claude CLI if
+        // it started (run_agent then frees the slot)
+        Action::CancelAgentRun { ref run_id } => {
+            get_claude_processes().cancel(&agent_run_process_key(run_id));
+            let change_id = {
+                let state = get_app_state().read().await;
+                state.agent_runs.runs.iter().find(|r| r.id == *run_id).map(|r| r.change_id.clone())
+            };
+            if let Some(change_id) = change_id {
+                save_change_status(&change_id).await;
+            }
+        }
+
+        // Answer the paused run's tool call (the reducer already resumed it)
+        Action::ResolveAgentRunApproval { ref run_id, ref request_id, approve } => {
+            let decision = agent_runs::AgentRunApproval::decision(approve);
+            if let Err(message) = get_permission_bridge().respond(request_id, &decision).await {
+                {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetError {
+                        code: "AGENT_RUN_ERROR".to_string(),
+                        message,
+                        context: Some(format!("ResolveAgentRunApproval: {}", run_id)),
+                    });
+                }
+                notify_state_update().await;
+            }
+        }
+
+        // Answer the paused implementation's tool call
+        Action::ResolveImplementationApproval { ref change_id, ref request_id, approve } => {
+            let decision = agent_runs::AgentRunApproval::decision(approve);
+            if let Err(message) = get_permission_bridge().respond(request_id, &decision).await {
+                {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetError {
+                        code: "IMPLEMENTATION_ERROR".to_string(),
+                        message,
+                        context: Some(format!("ResolveImplementationApproval: {}", change_id)),
+                    });
+                }
+                notify_state_update().await;
+            }
+        }
+
+        Action::RollbackImplementation { ref change_id } => {
+            let target = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| p.active_worktree()).and_then(|w| {
+                    let change = w.changes.changes.iter().find(|c| c.id == *change_id)?;
+                    Some((w.path.clone(), change.name.clone(), change.status, change.snapshot.clone()))
+                })
+            };
+            let Some((wt_path, change_name, status, snapshot)) = target else {
+                tracing::warn!("RollbackImplementation: Change not found: {}", change_id);
+                return Ok(());
+            };
+            let result = match snapshot {
+                _ if matches!(status, app_state::ChangeStatus::Implementing | app_state::ChangeStatus::Testing) => {
+                    Err("Wait for the implementation to finish before rolling back".to_string())
+                }
+                None => Err(format!("{} has no snapshot to roll back to", change_name)),
+                Some(snapshot) => snapshot::restore(std::path::Path::new(&wt_path), &change_name, &snapshot),
+            };
+            {
+                let mut state = get_app_state().write().await;
+                match result {
+                    Ok(snapshot) => {
+                        // Test and hook results of the rolled back code no longer apply
+                        let _ = std::fs::remove_file(test_runner::results_path(std::path::Path::new(&wt_path), &change_name));
+                        let _ = std::fs::remove_file(hooks::results_path(std::path::Path::new(&wt_path), &change_name));
+                        reduce(&mut state, Action::AddNotification {
+                            message: format!("Rolled {} back to snapshot {}", change_name, snapshot.short_commit()),
+                            notification_type: actions::NotificationTypeData::Success,
+                        });
+                        reduce(&mut state, Action::CompleteRollback { change_id: change_id.clone(), snapshot });
                     }
+                    Err(message) => reduce(&mut state, Action::SetError {
+                        code: "ROLLBACK_ERROR".to_string(),
+                        message,
+                        context: Some(format!("RollbackImplementation: {}", change_id)),
+                    }),
+                }
+            }
+            save_change_status(change_id).await;
+            notify_state_update().await;
+            Box::pin(handle_async_action(Action::RefreshWorktrees)).await?;
+        }
 
-                    // Wait for process to finish
-                    let _ = child.wait().await;
+        Action::RunChangeTests { ref change_id } => {
+            let target = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| p.active_worktree()).and_then(|w| {
+                    let change = w.changes.changes.iter().find(|c| c.id == *change_id)?;
+                    Some((w.path.clone(), change.clone()))
+                })
+            };
+            let Some((wt_path, change)) = target else {
+                tracing::warn!("RunChangeTests: Change not found: {}", change_id);
+                return Ok(());
+            };
+            let result = match implementation_test_command(&wt_path).await {
+                _ if matches!(change.status, app_state::ChangeStatus::Implementing | app_state::ChangeStatus::Testing) => {
+                    Err("Wait for the implementation to finish before running its tests".to_string())
                 }
-                Err(e) => {
-                    eprintln!("ExecutePlan: Failed to spawn Claude CLI: {}", e);
+                None => Err("No test command found (justfile, Cargo.toml, package.json or pytest config)".to_string()),
+                Some((test_command, coverage)) => {
                     {
                         let mut state = get_app_state().write().await;
-                        reduce(&mut state, Action::FailImplementation {
-                            change_id: change_id_clone,
-                            error: e.to_string(),
+                        reduce(&mut state, Action::StartImplementationTests { change_id: change_id.clone() });
+                        reduce(&mut state, Action::AppendImplementationOutput {
+                            change_id: change_id.clone(),
+                            content: format!("\n\n$ {}\n", test_command.display()),
                         });
                     }
                     notify_state_update().await;
+                    Ok(run_implementation_tests(&test_command, coverage.as_ref(), &wt_path, &change).await)
+                }
+            };
+            {
+                let mut state = get_app_state().write().await;
+                match result {
+                    Ok(Ok(())) => reduce(&mut state, Action::CompleteImplementation { change_id: change_id.clone() }),
+                    Ok(Err(error)) => reduce(&mut state, Action::FailImplementation {
+                        change_id: change_id.clone(),
+                        error,
+                    }),
+                    Err(message) => reduce(&mut state, Action::SetError {
+                        code: "TEST_RUN_ERROR".to_string(),
+                        message,
+                        context: Some(format!("RunChangeTests: {}", change_id)),
+                    }),
+                }
+            }
+            save_change_status(change_id).await;
+            notify_state_update().await;
+        }
+
+        Action::OverrideTestGate { ref change_id } => {
+            // The reducer flagged the results; keep the override across reloads
+            let target = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| p.active_worktree()).and_then(|w| {
+                    let change = w.changes.changes.iter().find(|c| c.id == *change_id)?;
+                    Some((w.path.clone(), change.name.clone(), change.test_results.clone()?))
+                })
+            };
+            if let Some((wt_path, change_name, results)) = target {
+                if let Err(e) = test_runner::save(std::path::Path::new(&wt_path), &change_name, &results) {
+                    tracing::warn!("Failed to save test gate override of {}: {}", change_name, e);
                 }
             }
         }
@@ -3187,6 +9185,16 @@ Execute the plan now. Start implementing."#,
                     .join(".rstn")
                     .join("changes");
 
+                // Statuses in memory, reconciled with status.json below
+                let local_statuses: std::collections::HashMap<String, app_state::ChangeStatus> = {
+                    let state = get_app_state().read().await;
+                    state
+                        .active_project()
+                        .and_then(|p| p.active_worktree())
+                        .map(|w| w.changes.changes.iter().map(|c| (c.name.clone(), c.status)).collect())
+                        .unwrap_or_default()
+                };
+
                 let mut changes = Vec::new();
 
                 if changes_dir.exists() {
@@ -3203,14 +9211,30 @@ Execute the plan now. Start implementing."#,
                                 let proposal = std::fs::read_to_string(&proposal_path).ok();
                                 let plan = std::fs::read_to_string(&plan_path).ok();
 
-                                // Determine status from files
-                                let status = if plan.is_some() {
+                                let worktree = std::path::Path::new(&wt_path);
+                                let status_file = change_status::read(worktree, &change_name);
+                                if let change_status::StatusRead::Invalid(e) = &status_file {
+                                    tracing::warn!("{}", e);
+                                }
+                                let reconciled = change_status::reconcile(
+                                    local_statuses.get(&change_name).copied(),
+                                    change_status::synced().get(worktree, &change_name),
+                                    &status_file,
+                                );
+                                change_status::synced().set(worktree, &change_name, reconciled.synced);
+
+                                // Without a status anywhere, determine it from files
+                                let status = reconciled.status.unwrap_or(if plan.is_some() {
                                     app_state::ChangeStatus::Planned
                                 } else {
                                     // Default to Proposed if no plan yet
                                     app_state::ChangeStatus::Proposed
-                                };
+                                });
 
+                                let link = pull_request::load_link(std::path::Path::new(&wt_path), &change_name);
+                                let snapshot = snapshot::load(std::path::Path::new(&wt_path), &change_name);
+                                let test_results = test_runner::load(std::path::Path::new(&wt_path), &change_name);
+                                let hook_results = hooks::load_results(std::path::Path::new(&wt_path), &change_name);
                                 let now = chrono::Utc::now().to_rfc3339();
                                 changes.push(app_state::Change {
                                     id: format!("change-{}", change_name),
@@ -3225,6 +9249,15 @@ Execute the plan now. Start implementing."#,
                                     proposal_review_session_id: None,
                                     plan_review_session_id: None,
                                     context_files: Vec::new(),
+                                    implementation_tasks: Vec::new(),
+                                    review_comments: Vec::new(),
+                                    branch: link.branch,
+                                    pull_request_url: link.pull_request_url,
+                                    snapshot,
+                                    test_results,
+                                    hook_results,
+                                    status_conflict: reconciled.conflict,
+                                    implementation_approval: None,
                                 });
                             }
                         }
@@ -3248,6 +9281,86 @@ Execute the plan now. Start implementing."#,
             // Sync action - handled in reducer
         }
 
+        Action::ResolveChangeStatusConflict { ref change_id, .. } => {
+            // Status picked in the reducer; write it over the conflicting file
+            let target = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| p.active_worktree()).and_then(|w| {
+                    let change = w.changes.changes.iter().find(|c| &c.id == change_id)?;
+                    Some((w.path.clone(), change.name.clone(), change.status))
+                })
+            };
+            if let Some((wt_path, change_name, status)) = target {
+                let worktree = std::path::Path::new(&wt_path);
+                match change_status::record(worktree, &change_name, status, change_status::git_user(worktree)) {
+                    Ok(_) => change_status::synced().set(worktree, &change_name, Some(status)),
+                    Err(e) => {
+                        let mut state = get_app_state().write().await;
+                        reduce(&mut state, Action::SetError {
+                            code: "CHANGE_STATUS_ERROR".to_string(),
+                            message: e,
+                            context: Some(format!("ResolveChangeStatusConflict: {}", change_name)),
+                        });
+                    }
+                }
+            }
+        }
+
+        Action::OpenChangePullRequest { ref change_id } => {
+            let target = {
+                let state = get_app_state().read().await;
+                state.active_project().and_then(|p| p.active_worktree()).and_then(|w| {
+                    let change = w.changes.changes.iter().find(|c| &c.id == change_id)?;
+                    Some((w.path.clone(), change.clone(), state.global_settings.git_hosting.clone()))
+                })
+            };
+            let Some((wt_path, change, tokens)) = target else {
+                return Ok(());
+            };
+
+            let result = async {
+                let branch = change
+                    .branch
+                    .clone()
+                    .ok_or_else(|| "This change has no branch of its own; create it with a dedicated branch".to_string())?;
+                let repo = pull_request::remote_repo(&wt_path)?;
+                let token = match repo.host {
+                    pull_request::GitHost::GitHub => tokens.github_token.ok_or("Set a GitHub token in Settings")?,
+                    pull_request::GitHost::GitLab => tokens.gitlab_token.ok_or("Set a GitLab token in Settings")?,
+                };
+                git::push(&wt_path)?;
+                let pr = pull_request::PullRequestRequest {
+                    title: pull_request::title_for(&change.intent),
+                    body: change.proposal.clone().unwrap_or_else(|| change.intent.clone()),
+                    head: branch.clone(),
+                    base: pull_request::default_branch(&wt_path),
+                };
+                let url = pull_request::create_pull_request(&repo, &token, &pr).await?;
+                let link = pull_request::ChangeLink { branch: Some(branch), pull_request_url: Some(url.clone()) };
+                if let Err(e) = pull_request::save_link(std::path::Path::new(&wt_path), &change.name, &link) {
+                    tracing::warn!("Failed to save pull request URL: {}", e);
+                }
+                Ok::<_, String>(url)
+            }
+            .await;
+
+            let mut state = get_app_state().write().await;
+            match result {
+                Ok(url) => {
+                    reduce(&mut state, Action::SetChangePullRequest { change_id: change_id.clone(), url: url.clone() });
+                    reduce(&mut state, Action::AddNotification {
+                        message: format!("Opened pull request {}", url),
+                        notification_type: actions::NotificationTypeData::Success,
+                    });
+                }
+                Err(e) => reduce(&mut state, Action::SetError {
+                    code: "PULL_REQUEST_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("OpenChangePullRequest: {}", change.name)),
+                }),
+            }
+        }
+
         // ====================================================================
         // Living Context Actions (CESDD Phase 3)
         // ====================================================================
@@ -3389,7 +9502,8 @@ Execute the plan now. Start implementing."#,
                 let prompt = context_generate::build_generate_context_prompt(&summary);
 
                 // Spawn Claude with streaming
-                match claude_cli::spawn_claude(&prompt, path, None, None) {
+                let mut session = start_claude_session("context_generate", path, &prompt).await;
+                match claude_cli::spawn_claude(&prompt, path, None, None, None, active_claude_model().await.as_deref()) {
                     Ok(mut child) => {
                         // Monitor stderr
                         if let Some(stderr) = child.stderr.take() {
@@ -3398,7 +9512,7 @@ Execute the plan now. Start implementing."#,
                                 let mut lines = tokio::io::AsyncBufReadExt::lines(reader);
                                 while let Ok(Some(line)) = lines.next_line().await {
                                     if !line.trim().is_empty() {
-                                        eprintln!("[GenerateContext stderr] {}", line.trim());
+                                        tracing::debug!("[GenerateContext stderr] {}", line.trim());
                                     }
                                 }
                             });
@@ -3416,6 +9530,9 @@ Execute the plan now. Start implementing."#,
                                     .await
                                     {
                                         Ok(Some(Ok(event))) => {
+                                            session.observe(&event);
+                                            record_claude_usage(&event, "context_generate").await;
+
                                             // Extract and accumulate text
                                             if let Some(text) = claude_cli::extract_text_delta(&event) {
                                                 accumulated_output.push_str(text);
@@ -3443,7 +9560,7 @@ Execute the plan now. Start implementing."#,
                                                         // Write generated files
                                                         match context_generate::write_generated_context(path, &response) {
                                                             Ok(()) => {
-                                                                eprintln!("[GenerateContext] Context files generated successfully");
+                                                                tracing::info!("[GenerateContext] Context files generated successfully");
 
                                                                 // Refresh context files in state
                                                                 let files = context::read_context(path);
@@ -3462,7 +9579,7 @@ Execute the plan now. Start implementing."#,
                                                                 }
                                                             }
                                                             Err(e) => {
-                                                                eprintln!("[GenerateContext] Failed to write files: {}", e);
+                                                                tracing::error!("[GenerateContext] Failed to write files: {}", e);
                                                                 {
                                                                     let mut state = get_app_state().write().await;
                                                                     reduce(
@@ -3474,7 +9591,7 @@ Execute the plan now. Start implementing."#,
                                                         }
                                                     }
                                                     Err(e) => {
-                                                        eprintln!("[GenerateContext] Failed to parse response: {}", e);
+                                                        tracing::error!("[GenerateContext] Failed to parse response: {}", e);
                                                         {
                                                             let mut state = get_app_state().write().await;
                                                             reduce(
@@ -3489,7 +9606,7 @@ Execute the plan now. Start implementing."#,
                                             }
                                         }
                                         Ok(Some(Err(e))) => {
-                                            eprintln!("[GenerateContext] Stream error: {}", e);
+                                            tracing::error!("[GenerateContext] Stream error: {}", e);
                                             {
                                                 let mut state = get_app_state().write().await;
                                                 reduce(
@@ -3504,7 +9621,7 @@ Execute the plan now. Start implementing."#,
                                         }
                                         Ok(None) => break,
                                         Err(_) => {
-                                            eprintln!("[GenerateContext] Timeout");
+                                            tracing::error!("[GenerateContext] Timeout");
                                             {
                                                 let mut state = get_app_state().write().await;
                                                 reduce(
@@ -3521,7 +9638,7 @@ Execute the plan now. Start implementing."#,
                                 }
                             }
                             Err(e) => {
-                                eprintln!("[GenerateContext] Failed to create event stream: {}", e);
+                                tracing::error!("[GenerateContext] Failed to create event stream: {}", e);
                                 {
                                     let mut state = get_app_state().write().await;
                                     reduce(
@@ -3536,7 +9653,8 @@ Execute the plan now. Start implementing."#,
                         }
                     }
                     Err(e) => {
-                        eprintln!("[GenerateContext] Failed to spawn Claude: {}", e);
+                        session.fail(e.to_string());
+                        tracing::error!("[GenerateContext] Failed to spawn Claude: {}", e);
                         {
                             let mut state = get_app_state().write().await;
                             reduce(
@@ -3594,6 +9712,7 @@ Execute the plan now. Start implementing."#,
                                 }
                             }
                         }
+                        save_change_status(&change_id).await;
                         notify_state_update().await;
                     }
                     Err(e) => {
@@ -3666,7 +9785,8 @@ Execute the plan now. Start implementing."#,
                 );
 
                 // Spawn Claude with streaming
-                match claude_cli::spawn_claude(&prompt, path, None, None) {
+                let mut session = start_claude_session("context_sync", path, &prompt).await;
+                match claude_cli::spawn_claude(&prompt, path, None, None, None, active_claude_model().await.as_deref()) {
                     Ok(mut child) => {
                         // Monitor stderr
                         if let Some(stderr) = child.stderr.take() {
@@ -3675,7 +9795,7 @@ Execute the plan now. Start implementing."#,
                                 let mut lines = tokio::io::AsyncBufReadExt::lines(reader);
                                 while let Ok(Some(line)) = lines.next_line().await {
                                     if !line.trim().is_empty() {
-                                        eprintln!("[SyncContext stderr] {}", line.trim());
+                                        tracing::debug!("[SyncContext stderr] {}", line.trim());
                                     }
                                 }
                             });
@@ -3693,6 +9813,9 @@ Execute the plan now. Start implementing."#,
                                     .await
                                     {
                                         Ok(Some(Ok(event))) => {
+                                            session.observe(&event);
+                                            record_claude_usage(&event, "context_sync").await;
+
                                             // Extract and accumulate text
                                             if let Some(text) = claude_cli::extract_text_delta(&event) {
                                                 accumulated_output.push_str(text);
@@ -3721,10 +9844,10 @@ Execute the plan now. Start implementing."#,
                                                         // Apply updates to all context files
                                                         match context_sync::apply_context_updates(path, &response) {
                                                             Ok(files_updated) => {
-                                                                eprintln!("[SyncContext] Updated {} context files", files_updated);
+                                                                tracing::info!("[SyncContext] Updated {} context files", files_updated);
                                                             }
                                                             Err(e) => {
-                                                                eprintln!("[SyncContext] Failed to apply updates: {}", e);
+                                                                tracing::error!("[SyncContext] Failed to apply updates: {}", e);
                                                             }
                                                         }
 
@@ -3744,7 +9867,7 @@ Execute the plan now. Start implementing."#,
                                                         }
                                                     }
                                                     Err(e) => {
-                                                        eprintln!("[SyncContext] Failed to parse response: {}", e);
+                                                        tracing::error!("[SyncContext] Failed to parse response: {}", e);
                                                         {
                                                             let mut state = get_app_state().write().await;
                                                             if let Some(project) = state.active_project_mut() {
@@ -3761,7 +9884,7 @@ Execute the plan now. Start implementing."#,
                                             }
                                         }
                                         Ok(Some(Err(e))) => {
-                                            eprintln!("[SyncContext] Stream error: {}", e);
+                                            tracing::error!("[SyncContext] Stream error: {}", e);
                                             {
                                                 let mut state = get_app_state().write().await;
                                                 if let Some(project) = state.active_project_mut() {
@@ -3776,7 +9899,7 @@ Execute the plan now. Start implementing."#,
                                         }
                                         Ok(None) => break,
                                         Err(_) => {
-                                            eprintln!("[SyncContext] Timeout");
+                                            tracing::error!("[SyncContext] Timeout");
                                             {
                                                 let mut state = get_app_state().write().await;
                                                 if let Some(project) = state.active_project_mut() {
@@ -3793,7 +9916,7 @@ Execute the plan now. Start implementing."#,
                                 }
                             }
                             Err(e) => {
-                                eprintln!("[SyncContext] Failed to create event stream: {}", e);
+                                tracing::error!("[SyncContext] Failed to create event stream: {}", e);
                                 {
                                     let mut state = get_app_state().write().await;
                                     if let Some(project) = state.active_project_mut() {
@@ -3808,7 +9931,8 @@ Execute the plan now. Start implementing."#,
                         }
                     }
                     Err(e) => {
-                        eprintln!("[SyncContext] Failed to spawn Claude: {}", e);
+                        session.fail(e.to_string());
+                        tracing::error!("[SyncContext] Failed to spawn Claude: {}", e);
                         {
                             let mut state = get_app_state().write().await;
                             if let Some(project) = state.active_project_mut() {
@@ -3873,7 +9997,7 @@ Execute the plan now. Start implementing."#,
                             });
                         }
                         Err(e) => {
-                            eprintln!("Failed to expand directory {}: {}", path, e);
+                            tracing::error!("Failed to expand directory {}: {}", path, e);
                         }
                     }
                 }
@@ -3888,6 +10012,36 @@ Execute the plan now. Start implementing."#,
              // Pure state change handled by reducer
         }
 
+        Action::SearchWorkspace { ref query, ref glob, case_sensitive } => {
+            let worktree_path = {
+                let state = get_app_state().read().await;
+                state
+                    .active_project()
+                    .and_then(|p| p.active_worktree())
+                    .map(|w| w.path.clone())
+            };
+            if let (Some(worktree_path), false) = (worktree_path, query.is_empty()) {
+                let (search_query, glob) = (query.clone(), glob.clone());
+                let outcome = tokio::task::spawn_blocking(move || {
+                    explorer::search::search_workspace(
+                        std::path::Path::new(&worktree_path),
+                        &search_query,
+                        glob.as_deref(),
+                        case_sensitive,
+                    )
+                })
+                .await
+                .unwrap_or_else(|e| Err(format!("Search failed: {}", e)));
+
+                let (results, error) = match outcome {
+                    Ok(results) => (results, None),
+                    Err(e) => (Default::default(), Some(e)),
+                };
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetSearchResults { query: query.clone(), results, error });
+            }
+        }
+
         Action::ExploreDir { ref path } => {
             let project_root = {
                 let state = get_app_state().read().await;
@@ -3914,61 +10068,68 @@ Execute the plan now. Start implementing."#,
                         });
                     }
                     Err(e) => {
-                        eprintln!("Failed to explore dir: {}", e);
+                        tracing::error!("Failed to explore dir: {}", e);
                     }
                 }
             }
         }
 
-        Action::SelectFile { ref path } => {
-            if let Some(p) = path {
-                // Get project root to calculate relative path for SQLite
-                let project_root = {
-                    let state = get_app_state().read().await;
-                    state.active_project().map(|proj| proj.path.clone())
-                };
+        Action::LoadFileHistory { ref path } => {
+            let (commits, error) = match git::file_history(path, git::DEFAULT_HISTORY_LIMIT) {
+                Ok(commits) => (commits, None),
+                Err(e) => (Vec::new(), Some(e)),
+            };
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetFileHistory { path: path.clone(), commits, error });
+        }
 
-                if let Some(root) = project_root {
-                    let project_id = persistence::get_project_id(&root);
-                    let rel_path = std::path::Path::new(p)
-                        .strip_prefix(&root)
-                        .unwrap_or(std::path::Path::new(p))
-                        .to_string_lossy()
-                        .to_string();
-
-                    if let Some(db_mgr) = get_db_manager() {
-                        match db_mgr.get_comments(&project_id, &rel_path) {
-                            Ok(rows) => {
-                                let comments: Vec<actions::CommentData> = rows
-                                    .into_iter()
-                                    .map(|r| actions::CommentData {
-                                        id: r.id,
-                                        content: r.content.clone(),
-                                        author: r.author.clone(),
-                                        created_at: r.created_at.clone(),
-                                        line_number: r.line_number,
-                                    })
-                                    .collect();
-
-                                eprintln!("[Backend] SelectFile: Loaded {} comments for {}", comments.len(), rel_path);
-                                for comment in &comments {
-                                    eprintln!("[Backend]   - Line {}: {}", comment.line_number.unwrap_or(0), comment.content);
-                                }
+        Action::SelectFile { path: Some(ref p) } => {
+            // Get project root to calculate relative path for SQLite
+            let project_root = {
+                let state = get_app_state().read().await;
+                state.active_project().map(|proj| proj.path.clone())
+            };
 
-                                let mut state = get_app_state().write().await;
-                                reduce(
-                                    &mut state,
-                                    Action::SetFileComments {
-                                        path: p.clone(),
-                                        comments,
-                                    },
-                                );
+            if let Some(root) = project_root {
+                let project_id = persistence::get_project_id(&root);
+                let rel_path = std::path::Path::new(p)
+                    .strip_prefix(&root)
+                    .unwrap_or(std::path::Path::new(p))
+                    .to_string_lossy()
+                    .to_string();
 
-                                eprintln!("[Backend] SetFileComments dispatched for {}", p);
-                            }
-                            Err(e) => {
-                                eprintln!("[Backend] Failed to load comments: {}", e);
+                if let Some(db_mgr) = get_db_manager() {
+                    match db_mgr.get_comments(&project_id, &rel_path) {
+                        Ok(rows) => {
+                            let comments: Vec<actions::CommentData> = rows
+                                .into_iter()
+                                .map(|r| actions::CommentData {
+                                    id: r.id,
+                                    content: r.content.clone(),
+                                    author: r.author.clone(),
+                                    created_at: r.created_at.clone(),
+                                    line_number: r.line_number,
+                                })
+                                .collect();
+
+                            tracing::debug!("SelectFile: Loaded {} comments for {}", comments.len(), rel_path);
+                            for comment in &comments {
+                                tracing::debug!("  - Line {}: {}", comment.line_number.unwrap_or(0), comment.content);
                             }
+
+                            let mut state = get_app_state().write().await;
+                            reduce(
+                                &mut state,
+                                Action::SetFileComments {
+                                    path: p.clone(),
+                                    comments,
+                                },
+                            );
+
+                            tracing::debug!("SetFileComments dispatched for {}", p);
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to load comments: {}", e);
                         }
                     }
                 }
@@ -3980,7 +10141,7 @@ Execute the plan now. Start implementing."#,
             ref content,
             line_number,
         } => {
-            eprintln!("[Backend] AddFileComment: path={}, line={:?}, content={}", path, line_number, content);
+            tracing::debug!("AddFileComment: path={}, line={:?}, content={}", path, line_number, content);
 
             let project_root = {
                 let state = get_app_state().read().await;
@@ -3989,21 +10150,37 @@ Execute the plan now. Start implementing."#,
 
             if let Some(root) = project_root {
                 let project_id = persistence::get_project_id(&root);
-                let rel_path = std::path::Path::new(path)
-                    .strip_prefix(&root)
-                    .unwrap_or(std::path::Path::new(path))
-                    .to_string_lossy()
-                    .to_string();
+                let rel_path = comment_file_path(&root, path);
 
-                eprintln!("[Backend] Saving comment: project={}, rel_path={}", project_id, rel_path);
+                tracing::debug!("Saving comment: project={}, rel_path={}", project_id, rel_path);
 
                 if let Some(db_mgr) = get_db_manager() {
                     match db_mgr.add_comment(&project_id, &rel_path, content, "User", line_number) {
                         Ok(comment_id) => {
-                            eprintln!("[Backend] Comment saved with ID: {}", comment_id);
+                            tracing::debug!("Comment saved with ID: {}", comment_id);
+
+                            if let Ok(Some(row)) = db_mgr.get_comment(&project_id, &comment_id) {
+                                let slice = |comment| undo::UndoSlice::FileComment {
+                                    project_path: root.clone(),
+                                    file_path: rel_path.clone(),
+                                    comment_id: comment_id.clone(),
+                                    comment,
+                                };
+                                let mut state = get_app_state().write().await;
+                                reduce(
+                                    &mut state,
+                                    Action::PushUndoEntry {
+                                        entry: Box::new(undo::UndoEntry {
+                                            label: "Add comment".to_string(),
+                                            before: slice(None),
+                                            after: slice(Some(comment_data(row))),
+                                        }),
+                                    },
+                                );
+                            }
 
                             // Reload comments after adding
-                            eprintln!("[Backend] Reloading file to fetch updated comments...");
+                            tracing::debug!("Reloading file to fetch updated comments...");
                             Box::pin(handle_async_action(Action::SelectFile {
                                 path: Some(path.clone()),
                             }))
@@ -4017,28 +10194,149 @@ Execute the plan now. Start implementing."#,
                                 Box::pin(handle_async_action(Action::ExploreDir { path: dir })).await?;
                             }
 
-                            eprintln!("[Backend] AddFileComment completed successfully");
+                            tracing::debug!("AddFileComment completed successfully");
                         }
                         Err(e) => {
-                            eprintln!("[Backend] Failed to save comment: {}", e);
+                            tracing::error!("Failed to save comment: {}", e);
                         }
                     }
                 } else {
-                    eprintln!("[Backend] No database manager available!");
+                    tracing::warn!("No database manager available!");
                 }
             } else {
-                eprintln!("[Backend] No active project found!");
+                tracing::warn!("No active project found!");
             }
         }
 
         Action::CreateFile { .. }
         | Action::RenameFile { .. }
         | Action::DeleteFile { .. }
-        | Action::RevealInOS { .. }
-        | Action::DeleteFileComment { .. } => {
+        | Action::RevealInOS { .. } => {
             // To be implemented in Phase B2.2
         }
 
+        Action::DeleteFileComment { ref path, ref comment_id } => {
+            let project_root = {
+                let state = get_app_state().read().await;
+                state.active_project().map(|proj| proj.path.clone())
+            };
+            let (Some(root), Some(db_mgr)) = (project_root, get_db_manager()) else {
+                return Ok(());
+            };
+            let project_id = persistence::get_project_id(&root);
+            let rel_path = comment_file_path(&root, path);
+
+            let row = match db_mgr.get_comment(&project_id, comment_id) {
+                Ok(Some(row)) => row,
+                Ok(None) => return Ok(()),
+                Err(e) => {
+                    tracing::warn!("Failed to load comment {}: {}", comment_id, e);
+                    return Ok(());
+                }
+            };
+            if let Err(e) = db_mgr.delete_comment(&project_id, comment_id) {
+                tracing::warn!("Failed to delete comment {}: {}", comment_id, e);
+                return Ok(());
+            }
+
+            {
+                let slice = |comment| undo::UndoSlice::FileComment {
+                    project_path: root.clone(),
+                    file_path: rel_path.clone(),
+                    comment_id: comment_id.clone(),
+                    comment,
+                };
+                let mut state = get_app_state().write().await;
+                reduce(
+                    &mut state,
+                    Action::PushUndoEntry {
+                        entry: Box::new(undo::UndoEntry {
+                            label: "Delete comment".to_string(),
+                            before: slice(Some(comment_data(row))),
+                            after: slice(None),
+                        }),
+                    },
+                );
+            }
+
+            Box::pin(handle_async_action(Action::SelectFile { path: Some(path.clone()) })).await?;
+            let current_dir = std::path::Path::new(path)
+                .parent()
+                .map(|p| p.to_string_lossy().to_string());
+            if let Some(dir) = current_dir {
+                Box::pin(handle_async_action(Action::ExploreDir { path: dir })).await?;
+            }
+        }
+
+        Action::Undo | Action::Redo => {
+            // State slices were restored by the reducer; comments live in the
+            // database and change statuses in status.json
+            let pending = get_app_state().write().await.undo.pending.take();
+            match pending {
+                Some(undo::UndoSlice::ChangeStatus { change_id, .. }) => save_change_status(&change_id).await,
+                Some(slice) => restore_comment_slice(slice).await?,
+                None => {}
+            }
+        }
+
+        Action::GitStage { .. } | Action::GitCommit { .. } | Action::GitPush | Action::GitPull => {
+            let paths = {
+                let state = get_app_state().read().await;
+                state
+                    .active_project()
+                    .and_then(|p| p.active_worktree())
+                    .map(|w| (w.path.clone(), w.explorer.current_path.clone()))
+            };
+            let Some((repo_path, current_path)) = paths else {
+                return Ok(());
+            };
+
+            let (operation, result) = match &action {
+                Action::GitStage { paths } => ("GitStage", git::stage(&repo_path, paths).map(|_| {
+                    format!("Staged {} file(s)", paths.len())
+                })),
+                Action::GitCommit { message } => {
+                    warn_staged_env_secrets(&repo_path).await;
+
+                    // Scan first so the UI can show findings when blocked
+                    match git::scan_staged(&repo_path) {
+                        Ok(scan) if scan.is_blocking() => {
+                            let mut state = get_app_state().write().await;
+                            reduce(&mut state, Action::SetSecurityScanResult { result: Some(scan.clone()) });
+                            ("GitCommit", Err(format!("Commit blocked by security scan:\n{}", scan.summary())))
+                        }
+                        Ok(_) => ("GitCommit", git::commit(&repo_path, message).map(|hash| {
+                            format!("Committed {}", &hash[..hash.len().min(7)])
+                        })),
+                        Err(e) => ("GitCommit", Err(e)),
+                    }
+                }
+                Action::GitPush => ("GitPush", git::push(&repo_path).map(|_| "Pushed".to_string())),
+                _ => ("GitPull", git::pull(&repo_path).map(|_| "Pulled".to_string())),
+            };
+
+            {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetGitBusy { is_busy: false });
+                match result {
+                    Ok(message) => reduce(&mut state, Action::AddNotification {
+                        message,
+                        notification_type: actions::NotificationTypeData::Success,
+                    }),
+                    Err(e) => reduce(&mut state, Action::SetError {
+                        code: "GIT_OPERATION_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("{}: {}", operation, repo_path)),
+                    }),
+                }
+            }
+
+            // Refresh explorer git status
+            if !current_path.is_empty() {
+                Box::pin(handle_async_action(Action::ExploreDir { path: current_path })).await?;
+            }
+        }
+
         // Tab actions - pure state transitions, no async I/O needed
         Action::OpenFileTab { .. }
         | Action::PinTab { .. }
@@ -4048,12 +10346,84 @@ Execute the plan now. Start implementing."#,
         }
 
         // Terminal actions (async - PTY operations)
-        Action::SpawnTerminal { .. }
-        | Action::ResizeTerminal { .. }
-        | Action::WriteTerminal { .. }
-        | Action::KillTerminal { .. } => {
-            // TODO: Add terminal manager handling
-            // These will be handled by a global terminal manager
+        Action::SpawnTerminal { cols, rows } => {
+            let worktree = {
+                let state = get_app_state().read().await;
+                state
+                    .active_project()
+                    .and_then(|p| p.active_worktree())
+                    .map(|w| (w.path.clone(), w.terminal.session_id.clone()))
+            };
+            let Some((worktree_path, existing_session)) = worktree else {
+                return Ok(());
+            };
+
+            let manager = get_terminal_manager();
+
+            // Reuse the worktree's live session instead of spawning a second shell
+            if let Some(session_id) = existing_session {
+                if manager.has_session(&session_id).await {
+                    let _ = manager.resize(&session_id, cols, rows).await;
+                    return Ok(());
+                }
+            }
+
+            match manager.spawn(worktree_path.clone(), worktree_path.clone(), cols, rows).await {
+                Ok(session_id) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetTerminalSession { session_id: Some(session_id) });
+                }
+                Err(e) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetError {
+                        code: "TERMINAL_SPAWN_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("SpawnTerminal: {}", worktree_path)),
+                    });
+                }
+            }
+        }
+
+        Action::ResizeTerminal { ref session_id, cols, rows } => {
+            match get_terminal_manager().resize(session_id, cols, rows).await {
+                Ok(()) => {
+                    let mut state = get_app_state().write().await;
+                    // Exec consoles share resizing but have no worktree terminal
+                    let is_worktree_session = state
+                        .active_project()
+                        .and_then(|p| p.active_worktree())
+                        .is_some_and(|w| w.terminal.session_id.as_deref() == Some(session_id.as_str()));
+                    if is_worktree_session {
+                        reduce(&mut state, Action::SetTerminalSize { cols, rows });
+                    }
+                }
+                Err(e) => {
+                    let mut state = get_app_state().write().await;
+                    reduce(&mut state, Action::SetError {
+                        code: "TERMINAL_RESIZE_ERROR".to_string(),
+                        message: e,
+                        context: Some(format!("ResizeTerminal: {}", session_id)),
+                    });
+                }
+            }
+        }
+
+        Action::WriteTerminal { ref session_id, ref data } => {
+            if let Err(e) = get_terminal_manager().write(session_id, data.as_bytes()).await {
+                let mut state = get_app_state().write().await;
+                reduce(&mut state, Action::SetError {
+                    code: "TERMINAL_WRITE_ERROR".to_string(),
+                    message: e,
+                    context: Some(format!("WriteTerminal: {}", session_id)),
+                });
+            }
+        }
+
+        Action::KillTerminal { ref session_id } => {
+            // Session may already be gone (shell exited); clear state either way
+            let _ = get_terminal_manager().kill(session_id).await;
+            let mut state = get_app_state().write().await;
+            reduce(&mut state, Action::SetTerminalSession { session_id: None });
         }
 
         _ => {}
@@ -4062,6 +10432,32 @@ Execute the plan now. Start implementing."#,
     Ok(())
 }
 
+/// Time allowed for the task model to name a change
+const CHANGE_SLUG_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(20);
+
+/// Change directory name: named by the Ollama task model if one is set,
+/// otherwise (or if it fails) derived from the intent
+async fn change_slug(intent: &str) -> String {
+    let provider = {
+        let state = get_app_state().read().await;
+        llm::task_provider(&state.global_settings)
+    };
+    if let Some(provider) = provider {
+        let prompt = llm::change_name_prompt(intent);
+        match tokio::time::timeout(CHANGE_SLUG_TIMEOUT, llm::complete(provider.as_ref(), &prompt)).await {
+            Ok(Ok(answer)) => {
+                let slug = slugify(answer.lines().next().unwrap_or_default());
+                if !slug.is_empty() {
+                    return slug;
+                }
+            }
+            Ok(Err(e)) => tracing::warn!("Failed to name change with {}: {}", provider.name(), e),
+            Err(_) => tracing::warn!("Naming change with {} timed out", provider.name()),
+        }
+    }
+    slugify(intent)
+}
+
 /// Convert intent to a URL-friendly slug
 fn slugify(intent: &str) -> String {
     intent
@@ -0,0 +1,219 @@
+//! LLM providers beyond the Claude CLI.
+//!
+//! The Claude CLI stays the default backend and keeps its own pipeline in
+//! `claude_cli` (sessions, MCP config, agent rules files). The providers here
+//! talk to HTTP APIs instead, so chat and one-shot prompts also work on
+//! machines without the CLI. The backend is chosen by
+//! `GlobalSettings::provider` and can be overridden per chat message.
+//!
+//! Cheap internal tasks (naming changes) can run on a local Ollama model
+//! instead, see [`task_provider`].
+
+pub mod ollama;
+pub mod openai;
+pub mod stream;
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use futures_util::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::AbortHandle;
+
+use crate::app_state::{ChatMessage, ChatRole, GlobalSettings, LlmProviderKind};
+
+pub use ollama::OllamaProvider;
+pub use openai::OpenAiProvider;
+
+/// Role of a message sent to a provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmRole {
+    System,
+    User,
+    Assistant,
+}
+
+/// A message sent to a provider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlmMessage {
+    pub role: LlmRole,
+    pub content: String,
+}
+
+impl LlmMessage {
+    pub fn system(content: impl Into<String>) -> Self {
+        Self { role: LlmRole::System, content: content.into() }
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self { role: LlmRole::User, content: content.into() }
+    }
+}
+
+/// A chat completion backend reached over HTTP
+pub trait LlmProvider: Send + Sync {
+    /// Display name for errors (e.g. "OpenAI (gpt-4o)")
+    fn name(&self) -> String;
+
+    /// Stream a completion for `messages`, sending text deltas to `deltas`
+    /// as they arrive. Returns the full text.
+    fn stream_chat<'a>(
+        &'a self,
+        messages: &'a [LlmMessage],
+        deltas: Option<&'a UnboundedSender<String>>,
+    ) -> BoxFuture<'a, Result<String, String>>;
+}
+
+/// HTTP provider for `kind`, configured from `settings`
+pub fn http_provider(kind: LlmProviderKind, settings: &GlobalSettings) -> Result<Box<dyn LlmProvider>, String> {
+    match kind {
+        LlmProviderKind::ClaudeCli => Err("The Claude CLI is not an HTTP provider".to_string()),
+        LlmProviderKind::OpenAi => Ok(Box::new(OpenAiProvider::from_settings(&settings.openai)?)),
+        LlmProviderKind::Ollama => Ok(Box::new(OllamaProvider::from_settings(&settings.ollama)?)),
+    }
+}
+
+/// Provider for cheap internal tasks, if an Ollama task model is set
+pub fn task_provider(settings: &GlobalSettings) -> Option<Box<dyn LlmProvider>> {
+    let model = settings.ollama.task_model.as_deref()?;
+    match OllamaProvider::new(&settings.ollama, model) {
+        Ok(provider) => Some(Box::new(provider)),
+        Err(e) => {
+            tracing::warn!("Ignoring Ollama task model: {}", e);
+            None
+        }
+    }
+}
+
+/// Prompt asking for a short kebab-case name for a change
+pub fn change_name_prompt(intent: &str) -> String {
+    format!(
+        "Name this software change in 2 to 5 lowercase words joined by hyphens \
+         (e.g. add-oauth-login). Reply with the name only.\n\nChange: {}",
+        intent.trim()
+    )
+}
+
+/// Run a one-shot prompt and return the full answer
+pub async fn complete(provider: &dyn LlmProvider, prompt: &str) -> Result<String, String> {
+    let output = provider.stream_chat(&[LlmMessage::user(prompt)], None).await?;
+    if output.trim().is_empty() {
+        Err(format!("{} returned no output", provider.name()))
+    } else {
+        Ok(output)
+    }
+}
+
+/// Conversation to send for a chat turn: the agent rules as a system message,
+/// then the non-empty chat history (the new user message is already in it)
+pub fn chat_messages(history: &[ChatMessage], rules: Option<&str>) -> Vec<LlmMessage> {
+    let rules = rules.filter(|r| !r.trim().is_empty()).map(LlmMessage::system);
+    let turns = history
+        .iter()
+        .filter(|m| !m.content.trim().is_empty())
+        .map(|m| LlmMessage {
+            role: match m.role {
+                ChatRole::User => LlmRole::User,
+                ChatRole::Assistant => LlmRole::Assistant,
+                ChatRole::System => LlmRole::System,
+            },
+            content: m.content.clone(),
+        });
+    rules.into_iter().chain(turns).collect()
+}
+
+/// In-flight HTTP requests by chat message ID, so they can be cancelled.
+///
+/// Mirrors `claude_cli::ClaudeProcessRegistry`: the streaming task registers
+/// its request and `finish`es it when done; `cancel` aborts the request task.
+#[derive(Default)]
+pub struct LlmRequestRegistry {
+    requests: Mutex<HashMap<String, AbortHandle>>,
+}
+
+impl LlmRequestRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, AbortHandle>> {
+        self.requests.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Track a running request under `id`
+    pub fn register(&self, id: &str, handle: AbortHandle) {
+        self.lock().insert(id.to_string(), handle);
+    }
+
+    /// Forget a finished request
+    pub fn finish(&self, id: &str) {
+        self.lock().remove(id);
+    }
+
+    /// Abort and remove the request. Returns false if nothing was running.
+    pub fn cancel(&self, id: &str) -> bool {
+        match self.lock().remove(id) {
+            Some(handle) => {
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(role: ChatRole, content: &str) -> ChatMessage {
+        ChatMessage {
+            id: content.to_string(),
+            role,
+            content: content.to_string(),
+            timestamp: String::new(),
+            is_streaming: false,
+            is_cancelled: false,
+            attachments: Vec::new(),
+            tool_calls: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_chat_messages() {
+        let history = vec![
+            message(ChatRole::User, "Hi"),
+            message(ChatRole::Assistant, ""),
+            message(ChatRole::Assistant, "Hello"),
+            message(ChatRole::User, "Explain"),
+        ];
+
+        let messages = chat_messages(&history, Some("Be brief"));
+        let roles: Vec<_> = messages.iter().map(|m| m.role).collect();
+        assert_eq!(
+            roles,
+            vec![LlmRole::System, LlmRole::User, LlmRole::Assistant, LlmRole::User]
+        );
+        assert_eq!(messages[0].content, "Be brief");
+        assert_eq!(chat_messages(&history, None).len(), 3);
+    }
+
+    #[test]
+    fn test_http_provider_requires_settings() {
+        let settings = GlobalSettings::default();
+        assert!(http_provider(LlmProviderKind::ClaudeCli, &settings).is_err());
+        assert!(http_provider(LlmProviderKind::OpenAi, &settings).is_err());
+        assert!(http_provider(LlmProviderKind::Ollama, &settings).is_err());
+        assert!(task_provider(&settings).is_none());
+
+        let mut settings = settings;
+        settings.ollama.task_model = Some("qwen2.5:0.5b".to_string());
+        assert_eq!(task_provider(&settings).unwrap().name(), "Ollama (qwen2.5:0.5b)");
+    }
+}
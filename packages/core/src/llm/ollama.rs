@@ -0,0 +1,301 @@
+//! Local models through an Ollama server.
+//!
+//! Chat uses `POST {base_url}/api/chat`, which streams NDJSON: one
+//! `{"message":{"content":...},"done":false}` object per line until an
+//! object with `"done":true`. Installed models come from `GET /api/tags`;
+//! a failing request there means no server is running.
+
+use std::time::Duration;
+
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use napi_derive::napi;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::stream::LineBuffer;
+use super::{LlmMessage, LlmProvider};
+use crate::app_state::OllamaSettings;
+
+/// Time allowed to reach the server (it runs locally)
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Time allowed between two response chunks (model loading can be slow)
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A model installed on the Ollama server
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OllamaModel {
+    /// Model tag passed to the API (e.g. "llama3.2:latest")
+    pub name: String,
+    /// Size on disk in bytes
+    pub size: i64,
+    /// e.g. "3.2B"
+    pub parameter_size: Option<String>,
+    /// e.g. "Q4_K_M"
+    pub quantization: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TagsResponse {
+    #[serde(default)]
+    models: Vec<TagsModel>,
+}
+
+#[derive(Deserialize)]
+struct TagsModel {
+    name: String,
+    #[serde(default)]
+    size: i64,
+    #[serde(default)]
+    details: TagsDetails,
+}
+
+#[derive(Deserialize, Default)]
+struct TagsDetails {
+    parameter_size: Option<String>,
+    quantization_level: Option<String>,
+}
+
+fn client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+fn normalize_url(base_url: &str) -> String {
+    base_url.trim().trim_end_matches('/').to_string()
+}
+
+/// Installed models, sorted by name. Errors if no server answers at `base_url`.
+pub async fn list_models(base_url: &str) -> Result<Vec<OllamaModel>, String> {
+    let base_url = normalize_url(base_url);
+    let response = client()?
+        .get(format!("{}/api/tags", base_url))
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|_| format!("Ollama is not running at {}", base_url))?;
+    if !response.status().is_success() {
+        return Err(format!("Ollama at {} returned {}", base_url, response.status()));
+    }
+
+    let tags: TagsResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid model list from Ollama: {}", e))?;
+    let mut models: Vec<OllamaModel> = tags
+        .models
+        .into_iter()
+        .map(|model| OllamaModel {
+            name: model.name,
+            size: model.size,
+            parameter_size: model.details.parameter_size,
+            quantization: model.details.quantization_level,
+        })
+        .collect();
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(models)
+}
+
+/// Provider for one model on an Ollama server
+pub struct OllamaProvider {
+    client: reqwest::Client,
+    base_url: String,
+    model: String,
+}
+
+impl OllamaProvider {
+    /// Provider for `model` on the configured server
+    pub fn new(settings: &OllamaSettings, model: &str) -> Result<Self, String> {
+        let base_url = normalize_url(&settings.base_url);
+        if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+            return Err(format!("Invalid Ollama URL: {}", base_url));
+        }
+        if model.trim().is_empty() {
+            return Err("Select an Ollama model in Settings".to_string());
+        }
+        Ok(Self {
+            client: client()?,
+            base_url,
+            model: model.trim().to_string(),
+        })
+    }
+
+    /// Provider for the configured chat model
+    pub fn from_settings(settings: &OllamaSettings) -> Result<Self, String> {
+        Self::new(settings, &settings.model)
+    }
+
+    async fn stream(
+        &self,
+        messages: &[LlmMessage],
+        deltas: Option<&UnboundedSender<String>>,
+    ) -> Result<String, String> {
+        let body = json!({
+            "model": self.model,
+            "messages": messages,
+            "stream": true,
+        });
+        let mut response = self
+            .client
+            .post(format!("{}/api/chat", self.base_url))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|_| format!("Ollama is not running at {}", self.base_url))?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            let message = serde_json::from_str::<Value>(&text)
+                .ok()
+                .and_then(|v| v.get("error").and_then(Value::as_str).map(str::to_string))
+                .unwrap_or(text);
+            return Err(format!("{} returned {}: {}", self.name(), status, message.trim()));
+        }
+
+        let mut lines = LineBuffer::new();
+        let mut output = String::new();
+        loop {
+            let chunk = tokio::time::timeout(CHUNK_TIMEOUT, response.chunk())
+                .await
+                .map_err(|_| format!("No response from {} for {} seconds", self.name(), CHUNK_TIMEOUT.as_secs()))?
+                .map_err(|e| format!("{} stream failed: {}", self.name(), e))?;
+            let ended = chunk.is_none();
+            let lines = match chunk {
+                Some(bytes) => lines.push(&bytes),
+                None => lines.finish().into_iter().collect(),
+            };
+
+            for line in lines.iter().filter(|line| !line.trim().is_empty()) {
+                let event: Value = serde_json::from_str(line)
+                    .map_err(|e| format!("Invalid stream event from {}: {}", self.name(), e))?;
+                if let Some(error) = event.get("error").and_then(Value::as_str) {
+                    return Err(format!("{} error: {}", self.name(), error));
+                }
+                if let Some(delta) = extract_delta(&event).filter(|d| !d.is_empty()) {
+                    output.push_str(delta);
+                    if let Some(tx) = deltas {
+                        let _ = tx.send(delta.to_string());
+                    }
+                }
+                if event.get("done").and_then(Value::as_bool) == Some(true) {
+                    return Ok(output);
+                }
+            }
+
+            if ended {
+                return Ok(output);
+            }
+        }
+    }
+}
+
+impl LlmProvider for OllamaProvider {
+    fn name(&self) -> String {
+        format!("Ollama ({})", self.model)
+    }
+
+    fn stream_chat<'a>(
+        &'a self,
+        messages: &'a [LlmMessage],
+        deltas: Option<&'a UnboundedSender<String>>,
+    ) -> BoxFuture<'a, Result<String, String>> {
+        self.stream(messages, deltas).boxed()
+    }
+}
+
+/// Text chunk of a streamed chat event
+pub fn extract_delta(event: &Value) -> Option<&str> {
+    event.get("message")?.get("content")?.as_str()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serve one canned HTTP response and return the server URL
+    async fn serve_once(response: String) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            let _ = socket.read(&mut buf).await.unwrap();
+            socket.write_all(response.as_bytes()).await.unwrap();
+            socket.shutdown().await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    fn ok_response(content_type: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            content_type,
+            body.len(),
+            body
+        )
+    }
+
+    #[tokio::test]
+    async fn test_list_models() {
+        let body = r#"{"models":[
+            {"name":"qwen2.5:0.5b","size":397821319,"details":{"parameter_size":"494M","quantization_level":"Q4_K_M"}},
+            {"name":"llama3.2:latest","size":2019393189,"details":{"parameter_size":"3.2B"}}
+        ]}"#;
+        let base_url = serve_once(ok_response("application/json", body)).await;
+
+        let models = list_models(&base_url).await.unwrap();
+        let names: Vec<_> = models.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["llama3.2:latest", "qwen2.5:0.5b"]);
+        assert_eq!(models[1].quantization.as_deref(), Some("Q4_K_M"));
+        assert_eq!(models[0].size, 2019393189);
+    }
+
+    #[tokio::test]
+    async fn test_list_models_without_server() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+
+        let error = list_models(&base_url).await.unwrap_err();
+        assert!(error.starts_with("Ollama is not running"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_stream_chat() {
+        let body = [
+            r#"{"message":{"role":"assistant","content":"Hel"},"done":false}"#,
+            r#"{"message":{"role":"assistant","content":"lo"},"done":false}"#,
+            r#"{"message":{"role":"assistant","content":""},"done":true}"#,
+        ]
+        .join("\n");
+        let base_url = serve_once(ok_response("application/x-ndjson", &body)).await;
+        let settings = OllamaSettings { base_url, model: "llama3.2".to_string(), task_model: None };
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let output = OllamaProvider::from_settings(&settings)
+            .unwrap()
+            .stream_chat(&[LlmMessage::user("Hi")], Some(&tx))
+            .await
+            .unwrap();
+        assert_eq!(output, "Hello");
+        drop(tx);
+        assert_eq!(rx.recv().await.as_deref(), Some("Hel"));
+        assert_eq!(rx.recv().await.as_deref(), Some("lo"));
+        assert_eq!(rx.recv().await, None);
+    }
+
+    #[test]
+    fn test_requires_model() {
+        assert!(OllamaProvider::from_settings(&OllamaSettings::default()).is_err());
+    }
+}
@@ -0,0 +1,279 @@
+//! OpenAI-compatible chat completions (`POST {base_url}/chat/completions`).
+//!
+//! Works with OpenAI and the many servers that implement its API (vLLM,
+//! LM Studio, OpenRouter, ...). Responses are requested with `stream: true`
+//! and read as SSE; each event carries a `choices[0].delta.content` chunk and
+//! the stream ends with `data: [DONE]`.
+
+use std::time::Duration;
+
+use futures_util::future::BoxFuture;
+use futures_util::FutureExt;
+use serde_json::{json, Value};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::stream::SseParser;
+use super::{LlmMessage, LlmProvider};
+use crate::app_state::OpenAiSettings;
+
+/// Time allowed to establish the connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time allowed between two response chunks
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Provider for an OpenAI-compatible endpoint
+pub struct OpenAiProvider {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+}
+
+impl OpenAiProvider {
+    /// Provider for the configured endpoint (base URL and model are required)
+    pub fn from_settings(settings: &OpenAiSettings) -> Result<Self, String> {
+        let base_url = settings.base_url.trim().trim_end_matches('/');
+        if base_url.is_empty() {
+            return Err("Set the OpenAI-compatible API base URL in Settings".to_string());
+        }
+        if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+            return Err(format!("Invalid API base URL: {}", base_url));
+        }
+        if settings.model.trim().is_empty() {
+            return Err("Set the OpenAI-compatible model in Settings".to_string());
+        }
+
+        let client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.to_string(),
+            api_key: settings.api_key.clone().filter(|k| !k.trim().is_empty()),
+            model: settings.model.trim().to_string(),
+        })
+    }
+
+    fn endpoint(&self) -> String {
+        format!("{}/chat/completions", self.base_url)
+    }
+
+    async fn stream(
+        &self,
+        messages: &[LlmMessage],
+        deltas: Option<&UnboundedSender<String>>,
+    ) -> Result<String, String> {
+        let body = json!({
+            "model": self.model,
+            "messages": messages,
+            "stream": true,
+        });
+        let mut request = self.client.post(self.endpoint()).json(&body);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let mut response = request
+            .send()
+            .await
+            .map_err(|e| format!("{} request failed: {}", self.name(), e))?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(format!("{} returned {}: {}", self.name(), status, error_message(&text)));
+        }
+
+        let mut parser = SseParser::new();
+        let mut output = String::new();
+        loop {
+            let chunk = tokio::time::timeout(CHUNK_TIMEOUT, response.chunk())
+                .await
+                .map_err(|_| format!("No response from {} for {} seconds", self.name(), CHUNK_TIMEOUT.as_secs()))?
+                .map_err(|e| format!("{} stream failed: {}", self.name(), e))?;
+            let ended = chunk.is_none();
+            let payloads = match chunk {
+                Some(bytes) => parser.push(&bytes),
+                None => parser.finish().into_iter().collect(),
+            };
+
+            for payload in payloads {
+                if payload.trim() == "[DONE]" {
+                    return Ok(output);
+                }
+                let event: Value = serde_json::from_str(&payload)
+                    .map_err(|e| format!("Invalid stream event from {}: {}", self.name(), e))?;
+                if let Some(error) = event.get("error") {
+                    return Err(format!("{} error: {}", self.name(), error_text(error)));
+                }
+                if let Some(delta) = extract_delta(&event).filter(|d| !d.is_empty()) {
+                    output.push_str(delta);
+                    if let Some(tx) = deltas {
+                        let _ = tx.send(delta.to_string());
+                    }
+                }
+            }
+
+            if ended {
+                // Some servers close the stream without [DONE]
+                return Ok(output);
+            }
+        }
+    }
+}
+
+impl LlmProvider for OpenAiProvider {
+    fn name(&self) -> String {
+        format!("OpenAI-compatible API ({})", self.model)
+    }
+
+    fn stream_chat<'a>(
+        &'a self,
+        messages: &'a [LlmMessage],
+        deltas: Option<&'a UnboundedSender<String>>,
+    ) -> BoxFuture<'a, Result<String, String>> {
+        self.stream(messages, deltas).boxed()
+    }
+}
+
+/// Text chunk of a streamed completion event
+pub fn extract_delta(event: &Value) -> Option<&str> {
+    event
+        .get("choices")?
+        .get(0)?
+        .get("delta")?
+        .get("content")?
+        .as_str()
+}
+
+/// Message of an `{"error": ...}` response body, or the body itself
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|value| value.get("error").map(error_text))
+        .unwrap_or_else(|| body.trim().to_string())
+}
+
+fn error_text(error: &Value) -> String {
+    error
+        .get("message")
+        .and_then(Value::as_str)
+        .or_else(|| error.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| error.to_string())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serve one canned HTTP response and return the endpoint base URL
+    async fn serve_once(response: String) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            // Read until the JSON body has arrived
+            while !request.ends_with(b"}") {
+                let n = socket.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            assert!(String::from_utf8_lossy(&request).contains("\"stream\":true"));
+            socket.write_all(response.as_bytes()).await.unwrap();
+            socket.shutdown().await.unwrap();
+        });
+        format!("http://{}/v1/", addr)
+    }
+
+    fn provider(base_url: String) -> OpenAiProvider {
+        OpenAiProvider::from_settings(&OpenAiSettings {
+            base_url,
+            api_key: Some("test-key".to_string()),
+            model: "test-model".to_string(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_extract_delta() {
+        let event: Value = serde_json::from_str(r#"{"choices":[{"delta":{"content":"Hi"}}]}"#).unwrap();
+        assert_eq!(extract_delta(&event), Some("Hi"));
+        let event: Value = serde_json::from_str(r#"{"choices":[{"delta":{"role":"assistant"}}]}"#).unwrap();
+        assert_eq!(extract_delta(&event), None);
+    }
+
+    #[tokio::test]
+    async fn test_stream_chat() {
+        let events = [
+            r#"{"choices":[{"delta":{"role":"assistant"}}]}"#,
+            r#"{"choices":[{"delta":{"content":"Hello"}}]}"#,
+            r#"{"choices":[{"delta":{"content":", world"}}]}"#,
+            "[DONE]",
+        ];
+        let body: String = events.iter().map(|e| format!("data: {}\n\n", e)).collect();
+        let base_url = serve_once(format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n{}",
+            body
+        ))
+        .await;
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let output = provider(base_url)
+            .stream_chat(&[LlmMessage::user("Hi")], Some(&tx))
+            .await
+            .unwrap();
+        assert_eq!(output, "Hello, world");
+        drop(tx);
+        let mut deltas = Vec::new();
+        while let Some(delta) = rx.recv().await {
+            deltas.push(delta);
+        }
+        assert_eq!(deltas, vec!["Hello", ", world"]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_chat_error_status() {
+        let body = r#"{"error":{"message":"Invalid API key"}}"#;
+        let base_url = serve_once(format!(
+            "HTTP/1.1 401 Unauthorized\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        ))
+        .await;
+
+        let error = provider(base_url)
+            .stream_chat(&[LlmMessage::user("Hi")], None)
+            .await
+            .unwrap_err();
+        assert!(error.contains("401"), "{}", error);
+        assert!(error.contains("Invalid API key"), "{}", error);
+    }
+
+    #[test]
+    fn test_from_settings_validation() {
+        let settings = OpenAiSettings {
+            base_url: "localhost:8000".to_string(),
+            api_key: None,
+            model: "m".to_string(),
+        };
+        assert!(OpenAiProvider::from_settings(&settings).is_err());
+
+        let settings = OpenAiSettings { base_url: "http://localhost:8000/v1/".to_string(), ..settings };
+        assert_eq!(
+            OpenAiProvider::from_settings(&settings).unwrap().endpoint(),
+            "http://localhost:8000/v1/chat/completions"
+        );
+    }
+}
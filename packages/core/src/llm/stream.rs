@@ -0,0 +1,118 @@
+//! Incremental parsing of streamed responses.
+//!
+//! Streaming completions arrive as `text/event-stream` (OpenAI) or NDJSON
+//! (Ollama); response chunks can split lines (and UTF-8 characters)
+//! anywhere, so bytes are buffered until a full line is available.
+
+/// Splits response chunks into complete lines
+#[derive(Debug, Default)]
+pub struct LineBuffer {
+    buffer: Vec<u8>,
+}
+
+impl LineBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a response chunk; returns the lines it completed (without line endings)
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(chunk);
+        let mut lines = Vec::new();
+        while let Some(newline) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=newline).collect();
+            let line = String::from_utf8_lossy(&line);
+            lines.push(line.trim_end_matches(['\n', '\r']).to_string());
+        }
+        lines
+    }
+
+    /// Line left unterminated when the stream ended
+    pub fn finish(&mut self) -> Option<String> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        self.push(b"\n").pop()
+    }
+}
+
+/// Collects the `data` payload of each complete event
+#[derive(Debug, Default)]
+pub struct SseParser {
+    lines: LineBuffer,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed a response chunk; returns the payloads of the events it completed
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        let lines = self.lines.push(chunk);
+        lines.into_iter().filter_map(|line| self.line(&line)).collect()
+    }
+
+    /// Payload of an event left unterminated when the stream ended
+    pub fn finish(&mut self) -> Option<String> {
+        if let Some(line) = self.lines.finish() {
+            self.line(&line);
+        }
+        self.dispatch()
+    }
+
+    fn line(&mut self, line: &str) -> Option<String> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if let Some(value) = line.strip_prefix("data:") {
+            self.data.push(value.strip_prefix(' ').unwrap_or(value).to_string());
+        }
+        // Comments (":") and other fields (event, id, retry) are not used
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<String> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let payload = self.data.join("\n");
+        self.data.clear();
+        Some(payload)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_split_across_chunks() {
+        let mut parser = SseParser::new();
+        assert!(parser.push(b": keep-alive\n\ndata: {\"a\"").is_empty());
+        assert_eq!(parser.push(b":1}\r\n\r\ndata: x\ndata: y\n\n"), vec!["{\"a\":1}", "x\ny"]);
+
+        // A multi-byte character split between chunks
+        let text = "data: é\n\n".as_bytes();
+        assert!(parser.push(&text[..7]).is_empty());
+        assert_eq!(parser.push(&text[7..]), vec!["é"]);
+
+        assert!(parser.push(b"data: [DONE]").is_empty());
+        assert_eq!(parser.finish().as_deref(), Some("[DONE]"));
+        assert_eq!(parser.finish(), None);
+    }
+
+    #[test]
+    fn test_line_buffer() {
+        let mut lines = LineBuffer::new();
+        assert_eq!(lines.push(b"{\"a\":1}\r\n{\"b\""), vec!["{\"a\":1}"]);
+        assert_eq!(lines.push(b":2}\n{\"c\":3}"), vec!["{\"b\":2}"]);
+        assert_eq!(lines.finish().as_deref(), Some("{\"c\":3}"));
+        assert_eq!(lines.finish(), None);
+    }
+}
@@ -0,0 +1,326 @@
+//! Application log.
+//!
+//! Backend diagnostics go through `tracing`. `init` installs a subscriber
+//! that writes every event to:
+//!
+//! - a daily log file under `~/.rstn/logs/` (`rstn-YYYY-MM-DD.log`, the
+//!   newest MAX_LOG_FILES are kept),
+//! - stderr (visible when running from a terminal),
+//! - a bounded in-memory ring that `tail` reads, so the Dev Log panel can
+//!   show backend logs without opening a terminal.
+//!
+//! The level filter comes from `RSTN_LOG` (EnvFilter syntax), else
+//! DEFAULT_FILTER.
+
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
+
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Layer};
+
+/// Entries kept in memory
+pub const MAX_LOG_ENTRIES: usize = 1000;
+
+/// Daily log files kept on disk
+const MAX_LOG_FILES: usize = 7;
+
+/// Entries returned by `tail` when no count is given
+pub const DEFAULT_TAIL: usize = 200;
+
+/// rstn at debug, dependencies only when they warn
+const DEFAULT_FILTER: &str = "warn,rstn_core=debug";
+
+const FILE_PREFIX: &str = "rstn-";
+const FILE_SUFFIX: &str = ".log";
+
+/// A log event
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogEntry {
+    /// ISO 8601
+    pub timestamp: String,
+    /// "ERROR" | "WARN" | "INFO" | "DEBUG" | "TRACE"
+    pub level: String,
+    /// Module that logged the event (e.g. "rstn_core::docker")
+    pub module: String,
+    /// Message followed by any structured fields
+    pub message: String,
+}
+
+/// Bounded buffer of the most recent entries
+#[derive(Debug, Default)]
+pub struct LogRing {
+    entries: Mutex<VecDeque<LogEntry>>,
+}
+
+impl LogRing {
+    pub fn push(&self, entry: LogEntry) {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= MAX_LOG_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Last `n` entries (oldest first) at `level` or more severe whose module
+    /// contains `module`
+    pub fn tail(&self, n: usize, level: Option<&str>, module: Option<&str>) -> Vec<LogEntry> {
+        let max_level = level.and_then(|l| Level::from_str(l).ok());
+        let module = module.map(str::trim).filter(|m| !m.is_empty());
+        let entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        let mut matching: Vec<LogEntry> = entries
+            .iter()
+            .rev()
+            .filter(|entry| {
+                max_level.is_none_or(|max| Level::from_str(&entry.level).ok().is_none_or(|l| l <= max))
+            })
+            .filter(|entry| module.is_none_or(|m| entry.module.contains(m)))
+            .take(n)
+            .cloned()
+            .collect();
+        matching.reverse();
+        matching
+    }
+}
+
+fn ring() -> &'static LogRing {
+    static RING: OnceLock<LogRing> = OnceLock::new();
+    RING.get_or_init(LogRing::default)
+}
+
+/// Recent log entries (see `LogRing::tail`)
+pub fn tail(n: usize, level: Option<&str>, module: Option<&str>) -> Vec<LogEntry> {
+    ring().tail(n, level, module)
+}
+
+/// Log directory (~/.rstn/logs/)
+pub fn default_dir() -> PathBuf {
+    crate::persistence::get_rstn_dir().join("logs")
+}
+
+/// Install the global subscriber. Later calls (or an already installed
+/// subscriber) are ignored.
+pub fn init(log_dir: PathBuf) {
+    let filter = EnvFilter::try_from_env("RSTN_LOG").unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let file_layer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_writer(Mutex::new(DailyFile::new(log_dir)));
+    let stderr_layer = tracing_subscriber::fmt::layer().with_writer(io::stderr);
+    let _ = tracing_subscriber::registry()
+        .with(filter)
+        .with(RingLayer)
+        .with(file_layer)
+        .with(stderr_layer)
+        .try_init();
+}
+
+// ============================================================================
+// Ring layer
+// ============================================================================
+
+/// Records events into the in-memory ring
+struct RingLayer;
+
+impl<S: Subscriber> Layer<S> for RingLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        ring().push(LogEntry {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            level: metadata.level().to_string(),
+            module: metadata.module_path().unwrap_or(metadata.target()).to_string(),
+            message: visitor.finish(),
+        });
+    }
+}
+
+/// Formats the `message` field followed by the other fields as `key=value`
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        if self.fields.is_empty() {
+            self.message
+        } else if self.message.is_empty() {
+            self.fields.trim_start().to_string()
+        } else {
+            format!("{}{}", self.message, self.fields)
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+// ============================================================================
+// Daily log files
+// ============================================================================
+
+/// Appends to `rstn-<date>.log`, switching files when the date changes
+struct DailyFile {
+    dir: PathBuf,
+    date: String,
+    file: Option<File>,
+}
+
+impl DailyFile {
+    fn new(dir: PathBuf) -> Self {
+        Self { dir, date: String::new(), file: None }
+    }
+
+    /// Open today's file if not open yet (and prune old files on a switch)
+    fn current(&mut self) -> io::Result<&mut File> {
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        if self.file.is_none() || self.date != today {
+            std::fs::create_dir_all(&self.dir)?;
+            let path = self.dir.join(format!("{}{}{}", FILE_PREFIX, today, FILE_SUFFIX));
+            self.file = Some(OpenOptions::new().create(true).append(true).open(path)?);
+            self.date = today;
+            prune_log_files(&self.dir, MAX_LOG_FILES);
+        }
+        Ok(self.file.as_mut().expect("log file was just opened"))
+    }
+}
+
+impl Write for DailyFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.current()?.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.file.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Delete all but the `keep` newest log files in `dir`
+fn prune_log_files(dir: &Path, keep: usize) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(FILE_PREFIX) && n.ends_with(FILE_SUFFIX))
+        })
+        .collect();
+    // Dates sort lexicographically
+    files.sort();
+    let excess = files.len().saturating_sub(keep);
+    for path in files.into_iter().take(excess) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: &str, module: &str, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            level: level.to_string(),
+            module: module.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_ring_tail_filters() {
+        let ring = LogRing::default();
+        ring.push(entry("DEBUG", "rstn_core::docker", "polling"));
+        ring.push(entry("WARN", "rstn_core::docker", "container exited"));
+        ring.push(entry("ERROR", "rstn_core::mcp_server", "bind failed"));
+        ring.push(entry("INFO", "rstn_core", "ready"));
+
+        let all = ring.tail(10, None, None);
+        assert_eq!(all.len(), 4);
+        assert_eq!(all[0].message, "polling");
+
+        let last_two = ring.tail(2, None, None);
+        assert_eq!(last_two.iter().map(|e| e.message.as_str()).collect::<Vec<_>>(), vec!["bind failed", "ready"]);
+
+        let warnings = ring.tail(10, Some("warn"), None);
+        assert_eq!(warnings.iter().map(|e| e.level.as_str()).collect::<Vec<_>>(), vec!["WARN", "ERROR"]);
+
+        let docker = ring.tail(10, Some("info"), Some("docker"));
+        assert_eq!(docker.len(), 1);
+        assert_eq!(docker[0].message, "container exited");
+    }
+
+    #[test]
+    fn test_ring_is_bounded() {
+        let ring = LogRing::default();
+        for i in 0..MAX_LOG_ENTRIES + 5 {
+            ring.push(entry("INFO", "rstn_core", &i.to_string()));
+        }
+        let all = ring.tail(usize::MAX, None, None);
+        assert_eq!(all.len(), MAX_LOG_ENTRIES);
+        assert_eq!(all[0].message, "5");
+    }
+
+    #[test]
+    fn test_daily_file_and_pruning() {
+        let dir = tempfile::tempdir().unwrap();
+        for day in 1..=9 {
+            std::fs::write(dir.path().join(format!("rstn-2020-01-0{}.log", day)), "").unwrap();
+        }
+        std::fs::write(dir.path().join("other.txt"), "").unwrap();
+
+        let mut file = DailyFile::new(dir.path().to_path_buf());
+        file.write_all(b"hello\n").unwrap();
+        file.flush().unwrap();
+
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let path = dir.path().join(format!("rstn-{}.log", today));
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "hello\n");
+
+        let mut names: Vec<String> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names.len(), MAX_LOG_FILES + 1);
+        assert!(names.contains(&"other.txt".to_string()));
+        assert!(!names.contains(&"rstn-2020-01-01.log".to_string()));
+    }
+}
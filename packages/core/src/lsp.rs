@@ -0,0 +1,521 @@
+//! Language server bridge for the file viewer.
+//!
+//! Starts rust-analyzer, typescript-language-server or pyright for a worktree
+//! the first time one of its files asks for a hover or a definition, and keeps
+//! it running until the worktree is closed. Messages use the LSP base protocol
+//! (`Content-Length` framed JSON-RPC) over the server's stdio. Documents are
+//! opened from disk and re-sent whenever the file changes on disk.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::Mutex;
+
+/// How long to wait for any single response
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Time a server gets to answer `shutdown` before it is killed
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Language servers rstn knows how to start
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LanguageServer {
+    RustAnalyzer,
+    Typescript,
+    Pyright,
+}
+
+impl LanguageServer {
+    /// Server for a source file (None = no code intelligence)
+    pub fn for_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "rs" => Some(Self::RustAnalyzer),
+            "ts" | "tsx" | "mts" | "cts" | "js" | "jsx" | "mjs" | "cjs" => Some(Self::Typescript),
+            "py" | "pyi" => Some(Self::Pyright),
+            _ => None,
+        }
+    }
+
+    /// Program and arguments
+    pub fn command(self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            Self::RustAnalyzer => ("rust-analyzer", &[]),
+            Self::Typescript => ("typescript-language-server", &["--stdio"]),
+            Self::Pyright => ("pyright-langserver", &["--stdio"]),
+        }
+    }
+}
+
+/// `languageId` of a document
+fn language_id(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()).unwrap_or_default() {
+        "rs" => "rust",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "typescriptreact",
+        "jsx" => "javascriptreact",
+        "py" | "pyi" => "python",
+        _ => "javascript",
+    }
+}
+
+/// Target of a definition (1-based lines and columns)
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LspLocation {
+    pub path: String,
+    pub line: u32,
+    pub column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+}
+
+// ============================================================================
+// Client
+// ============================================================================
+
+struct LspIo {
+    /// Killed when the client is dropped
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+/// Version sent for an open document, and the file it was read from
+struct OpenDocument {
+    version: i32,
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+/// Connection to one language server
+pub struct LspClient {
+    io: Mutex<LspIo>,
+    next_id: AtomicU64,
+    documents: Mutex<HashMap<PathBuf, OpenDocument>>,
+}
+
+impl LspClient {
+    /// Start a server rooted at the worktree and complete the `initialize`
+    /// handshake
+    pub async fn start(server: LanguageServer, root: &Path) -> Result<Self, String> {
+        let (program, args) = server.command();
+        let mut child = Command::new(program)
+            .args(args)
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("Failed to start {}: {}", program, e))?;
+        let stdin = child.stdin.take().ok_or("Failed to open server stdin")?;
+        let stdout = child.stdout.take().ok_or("Failed to open server stdout")?;
+
+        let client = Self {
+            io: Mutex::new(LspIo {
+                child,
+                stdin,
+                stdout: BufReader::new(stdout),
+            }),
+            next_id: AtomicU64::new(1),
+            documents: Mutex::new(HashMap::new()),
+        };
+        let root_uri = file_uri(root)?;
+        let name = root.file_name().and_then(|n| n.to_str()).unwrap_or("worktree");
+        client
+            .request(
+                "initialize",
+                json!({
+                    "processId": std::process::id(),
+                    "rootUri": root_uri,
+                    "workspaceFolders": [{ "uri": root_uri, "name": name }],
+                    "capabilities": {
+                        "textDocument": {
+                            "synchronization": { "didSave": false },
+                            "hover": { "contentFormat": ["markdown", "plaintext"] },
+                            "definition": { "linkSupport": true }
+                        }
+                    },
+                    "clientInfo": { "name": "rstn", "version": env!("CARGO_PKG_VERSION") }
+                }),
+            )
+            .await
+            // rustup installs a rust-analyzer proxy that exits when the
+            // component is missing
+            .map_err(|e| format!("{} did not initialize: {}", program, e))?;
+        client.notify("initialized", json!({})).await?;
+        Ok(client)
+    }
+
+    /// Hover text at a position, as markdown
+    pub async fn hover(&self, path: &Path, line: u32, column: u32) -> Result<Option<String>, String> {
+        let params = self.position_params(path, line, column).await?;
+        let result = self.request("textDocument/hover", params).await?;
+        let contents = result.get("contents").map(hover_markdown).unwrap_or_default();
+        Ok(Some(contents).filter(|c| !c.trim().is_empty()))
+    }
+
+    /// Where the symbol at a position is defined
+    pub async fn definition(&self, path: &Path, line: u32, column: u32) -> Result<Vec<LspLocation>, String> {
+        let params = self.position_params(path, line, column).await?;
+        let result = self.request("textDocument/definition", params).await?;
+        Ok(parse_locations(&result))
+    }
+
+    /// Whether the server process is still alive
+    pub async fn is_running(&self) -> bool {
+        matches!(self.io.lock().await.child.try_wait(), Ok(None))
+    }
+
+    /// Ask the server to exit (it is killed when the client is dropped)
+    pub async fn shutdown(&self) {
+        let _ = tokio::time::timeout(SHUTDOWN_TIMEOUT, async {
+            self.request("shutdown", Value::Null).await?;
+            self.notify("exit", Value::Null).await
+        })
+        .await;
+    }
+
+    /// Open or refresh the document, then build `TextDocumentPositionParams`
+    /// (LSP positions are 0-based)
+    async fn position_params(&self, path: &Path, line: u32, column: u32) -> Result<Value, String> {
+        self.sync_document(path).await?;
+        Ok(json!({
+            "textDocument": { "uri": file_uri(path)? },
+            "position": { "line": line.saturating_sub(1), "character": column.saturating_sub(1) }
+        }))
+    }
+
+    /// Send the file's content if the server has not seen this version of it
+    async fn sync_document(&self, path: &Path) -> Result<(), String> {
+        let metadata = std::fs::metadata(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let modified = metadata.modified().ok();
+        let mut documents = self.documents.lock().await;
+        let version = match documents.get(path) {
+            Some(doc) if doc.modified == modified && doc.len == metadata.len() => return Ok(()),
+            Some(doc) => doc.version + 1,
+            None => 1,
+        };
+
+        let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let uri = file_uri(path)?;
+        if version == 1 {
+            self.notify(
+                "textDocument/didOpen",
+                json!({
+                    "textDocument": { "uri": uri, "languageId": language_id(path), "version": version, "text": text }
+                }),
+            )
+            .await?;
+        } else {
+            self.notify(
+                "textDocument/didChange",
+                json!({
+                    "textDocument": { "uri": uri, "version": version },
+                    "contentChanges": [{ "text": text }]
+                }),
+            )
+            .await?;
+        }
+        documents.insert(
+            path.to_path_buf(),
+            OpenDocument {
+                version,
+                modified,
+                len: metadata.len(),
+            },
+        );
+        Ok(())
+    }
+
+    async fn request(&self, method: &str, params: Value) -> Result<Value, String> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        let response = tokio::time::timeout(REQUEST_TIMEOUT, self.exchange(id, &message))
+            .await
+            .map_err(|_| format!("No response to {} within {}s", method, REQUEST_TIMEOUT.as_secs()))??;
+
+        if let Some(error) = response.get("error") {
+            let message = error.get("message").and_then(|m| m.as_str()).unwrap_or("Unknown error");
+            return Err(message.to_string());
+        }
+        Ok(response.get("result").cloned().unwrap_or(Value::Null))
+    }
+
+    /// Send a request and wait for the response with the same id
+    async fn exchange(&self, id: u64, message: &Value) -> Result<Value, String> {
+        let mut io = self.io.lock().await;
+        write_message(&mut io.stdin, message).await?;
+        // Skip notifications (diagnostics, progress) and answer server
+        // requests until our response
+        while let Some(incoming) = read_message(&mut io.stdout).await? {
+            if incoming.get("method").is_none() {
+                if incoming.get("id").and_then(|v| v.as_u64()) == Some(id) {
+                    return Ok(incoming);
+                }
+                continue;
+            }
+            if let Some(request_id) = incoming.get("id") {
+                let reply = json!({ "jsonrpc": "2.0", "id": request_id, "result": server_request_result(&incoming) });
+                write_message(&mut io.stdin, &reply).await?;
+            }
+        }
+        Err("Server exited".to_string())
+    }
+
+    /// Send a notification (no response expected)
+    async fn notify(&self, method: &str, params: Value) -> Result<(), String> {
+        let message = json!({ "jsonrpc": "2.0", "method": method, "params": params });
+        write_message(&mut self.io.lock().await.stdin, &message).await
+    }
+}
+
+/// Neutral answer to a request from the server: default configuration for
+/// each requested section, null otherwise
+fn server_request_result(request: &Value) -> Value {
+    match request.get("method").and_then(|m| m.as_str()) {
+        Some("workspace/configuration") => {
+            let items = request.pointer("/params/items").and_then(|i| i.as_array()).map_or(0, Vec::len);
+            Value::Array(vec![Value::Null; items])
+        }
+        _ => Value::Null,
+    }
+}
+
+/// Read one framed message (None at end of stream)
+async fn read_message<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Option<Value>, String> {
+    loop {
+        let mut length = None;
+        loop {
+            let mut header = String::new();
+            let read = reader.read_line(&mut header).await.map_err(|e| e.to_string())?;
+            if read == 0 {
+                return Ok(None);
+            }
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse::<usize>().ok();
+                }
+            }
+        }
+        let Some(length) = length else {
+            continue;
+        };
+
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).await.map_err(|e| e.to_string())?;
+        if let Ok(message) = serde_json::from_slice(&body) {
+            return Ok(Some(message));
+        }
+    }
+}
+
+async fn write_message(stdin: &mut ChildStdin, message: &Value) -> Result<(), String> {
+    let body = message.to_string();
+    let framed = format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+    let write_error = |e: std::io::Error| {
+        if e.kind() == std::io::ErrorKind::BrokenPipe {
+            "Server exited".to_string()
+        } else {
+            format!("Failed to write to server: {}", e)
+        }
+    };
+    stdin.write_all(framed.as_bytes()).await.map_err(write_error)?;
+    stdin.flush().await.map_err(write_error)
+}
+
+fn file_uri(path: &Path) -> Result<String, String> {
+    url::Url::from_file_path(path)
+        .map(String::from)
+        .map_err(|_| format!("Not an absolute path: {}", path.display()))
+}
+
+/// Hover `contents` (MarkupContent, MarkedString or an array of them) as
+/// markdown
+fn hover_markdown(contents: &Value) -> String {
+    match contents {
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(hover_markdown)
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        Value::Object(object) => {
+            let value = object.get("value").and_then(|v| v.as_str()).unwrap_or_default();
+            match object.get("language").and_then(|l| l.as_str()) {
+                Some(language) => format!("```{}\n{}\n```", language, value),
+                None => value.to_string(),
+            }
+        }
+        _ => String::new(),
+    }
+}
+
+/// Definition result (Location, Location[] or LocationLink[]) as file
+/// locations
+fn parse_locations(result: &Value) -> Vec<LspLocation> {
+    let items = match result {
+        Value::Array(items) => items.as_slice(),
+        Value::Object(_) => std::slice::from_ref(result),
+        _ => &[],
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            // LocationLink: the selection range is the symbol's name
+            let (uri, range) = match item.get("targetUri") {
+                Some(uri) => (uri, item.get("targetSelectionRange").or_else(|| item.get("targetRange"))?),
+                None => (item.get("uri")?, item.get("range")?),
+            };
+            let path = url::Url::parse(uri.as_str()?).ok()?.to_file_path().ok()?;
+            let position = |key: &str, field: &str| {
+                range.pointer(&format!("/{}/{}", key, field)).and_then(|v| v.as_u64()).unwrap_or(0) as u32 + 1
+            };
+            Some(LspLocation {
+                path: path.to_string_lossy().to_string(),
+                line: position("start", "line"),
+                column: position("start", "character"),
+                end_line: position("end", "line"),
+                end_column: position("end", "character"),
+            })
+        })
+        .collect()
+}
+
+// ============================================================================
+// Manager
+// ============================================================================
+
+/// Running language servers, per worktree
+#[derive(Default)]
+pub struct LspManager {
+    /// (worktree path, server) -> client
+    clients: Mutex<HashMap<(String, LanguageServer), Arc<LspClient>>>,
+}
+
+impl LspManager {
+    pub async fn get(&self, worktree_path: &str, server: LanguageServer) -> Option<Arc<LspClient>> {
+        self.clients.lock().await.get(&(worktree_path.to_string(), server)).cloned()
+    }
+
+    pub async fn insert(&self, worktree_path: &str, server: LanguageServer, client: Arc<LspClient>) {
+        self.clients.lock().await.insert((worktree_path.to_string(), server), client);
+    }
+
+    /// Forget a server that exited
+    pub async fn remove(&self, worktree_path: &str, server: LanguageServer) {
+        self.clients.lock().await.remove(&(worktree_path.to_string(), server));
+    }
+
+    /// Shut down a worktree's servers
+    pub async fn stop(&self, worktree_path: &str) {
+        let stopped: Vec<Arc<LspClient>> = {
+            let mut clients = self.clients.lock().await;
+            let keys: Vec<_> = clients.keys().filter(|(path, _)| path == worktree_path).cloned().collect();
+            keys.iter().filter_map(|key| clients.remove(key)).collect()
+        };
+        futures_util::future::join_all(stopped.iter().map(|client| client.shutdown())).await;
+    }
+
+    /// Shut down the servers of worktrees that are no longer open
+    pub async fn retain(&self, live_worktrees: &[String]) {
+        let closed: Vec<String> = {
+            let clients = self.clients.lock().await;
+            clients
+                .keys()
+                .map(|(path, _)| path.clone())
+                .filter(|path| !live_worktrees.contains(path))
+                .collect()
+        };
+        for worktree_path in closed {
+            self.stop(&worktree_path).await;
+        }
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_for_path() {
+        assert_eq!(LanguageServer::for_path(Path::new("src/lib.rs")), Some(LanguageServer::RustAnalyzer));
+        assert_eq!(LanguageServer::for_path(Path::new("web/App.tsx")), Some(LanguageServer::Typescript));
+        assert_eq!(LanguageServer::for_path(Path::new("tools/gen.py")), Some(LanguageServer::Pyright));
+        assert_eq!(LanguageServer::for_path(Path::new("README.md")), None);
+        assert_eq!(language_id(Path::new("web/App.tsx")), "typescriptreact");
+    }
+
+    #[tokio::test]
+    async fn test_read_framed_messages() {
+        let first = r#"{"jsonrpc":"2.0","method":"window/logMessage","params":{}}"#;
+        let second = r#"{"jsonrpc":"2.0","id":1,"result":null}"#;
+        let stream = format!(
+            "Content-Length: {}\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\n{}content-length: {}\r\n\r\n{}",
+            first.len(),
+            first,
+            second.len(),
+            second
+        );
+        let mut reader = BufReader::new(stream.as_bytes());
+        let message = read_message(&mut reader).await.unwrap().unwrap();
+        assert_eq!(message["method"], "window/logMessage");
+        let message = read_message(&mut reader).await.unwrap().unwrap();
+        assert_eq!(message["id"], 1);
+        assert_eq!(read_message(&mut reader).await.unwrap(), None);
+
+        let request = json!({ "id": 3, "method": "workspace/configuration", "params": { "items": [{}, {}] } });
+        assert_eq!(server_request_result(&request), json!([null, null]));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_parse_hover_and_locations() {
+        let hover = json!([{ "language": "rust", "value": "pub fn run()" }, "Runs the check"]);
+        assert_eq!(hover_markdown(&hover), "```rust\npub fn run()\n```\n\nRuns the check");
+        assert_eq!(hover_markdown(&json!({ "kind": "markdown", "value": "**x**" })), "**x**");
+
+        let location = json!({
+            "uri": "file:///work/app/src/my%20lib.rs",
+            "range": { "start": { "line": 9, "character": 4 }, "end": { "line": 9, "character": 7 } }
+        });
+        assert_eq!(
+            parse_locations(&location),
+            vec![LspLocation {
+                path: "/work/app/src/my lib.rs".to_string(),
+                line: 10,
+                column: 5,
+                end_line: 10,
+                end_column: 8,
+            }]
+        );
+
+        let links = json!([{
+            "targetUri": "file:///work/app/src/main.rs",
+            "targetRange": { "start": { "line": 0, "character": 0 }, "end": { "line": 5, "character": 1 } },
+            "targetSelectionRange": { "start": { "line": 0, "character": 3 }, "end": { "line": 0, "character": 7 } }
+        }]);
+        let locations = parse_locations(&links);
+        assert_eq!((locations[0].line, locations[0].column), (1, 4));
+        assert!(parse_locations(&Value::Null).is_empty());
+    }
+}
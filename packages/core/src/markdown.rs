@@ -0,0 +1,284 @@
+//! Markdown rendering for spec, plan and constitution documents.
+//!
+//! Converts CommonMark (plus tables, task lists, strikethrough, footnotes and
+//! `{#id}` heading attributes) to HTML sanitized with ammonia, so views can
+//! insert it directly. Fenced code keeps its language as a `language-*`
+//! class; ```mermaid fences are replaced by an empty
+//! `<div class="mermaid" data-mermaid-index="N">` and their sources returned
+//! separately for the renderer to draw.
+
+use napi_derive::napi;
+use pulldown_cmark::{CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Files larger than this are not rendered
+pub const MAX_MARKDOWN_FILE_SIZE: u64 = 5_000_000;
+
+/// A rendered document
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RenderedMarkdown {
+    /// Sanitized HTML
+    pub html: String,
+    /// Headings in document order (for outlines and anchors)
+    pub headings: Vec<MarkdownHeading>,
+    /// Fenced code blocks other than mermaid
+    pub code_blocks: Vec<MarkdownCodeBlock>,
+    /// Mermaid diagrams, indexed by their placeholder's `data-mermaid-index`
+    pub mermaid: Vec<MermaidDiagram>,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarkdownHeading {
+    /// 1-6
+    pub level: u32,
+    pub text: String,
+    /// `id` attribute of the heading element
+    pub anchor: String,
+    /// 1-based
+    pub line: u32,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarkdownCodeBlock {
+    /// First word of the fence info string (None for indented or bare fences)
+    pub language: Option<String>,
+    pub code: String,
+    /// 1-based line of the opening fence
+    pub line: u32,
+}
+
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MermaidDiagram {
+    pub source: String,
+    /// 1-based line of the opening fence
+    pub line: u32,
+}
+
+/// Render a markdown file
+pub fn render_file(path: &Path) -> Result<RenderedMarkdown, String> {
+    let metadata = std::fs::metadata(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if metadata.len() > MAX_MARKDOWN_FILE_SIZE {
+        return Err(format!("{} is too large to render", path.display()));
+    }
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(render(&content))
+}
+
+/// Render markdown text
+pub fn render(content: &str) -> RenderedMarkdown {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_HEADING_ATTRIBUTES;
+    let line_of = |offset: usize| content[..offset].matches('\n').count() as u32 + 1;
+
+    let mut events: Vec<Event> = Vec::new();
+    let mut headings = Vec::new();
+    let mut code_blocks = Vec::new();
+    let mut mermaid = Vec::new();
+    let mut anchors: HashMap<String, usize> = HashMap::new();
+
+    // Heading and code block events are held until their end tag
+    let mut heading: Option<(Tag, u32, Vec<Event>)> = None;
+    let mut code: Option<(Option<String>, u32, String)> = None;
+
+    for (event, range) in Parser::new_ext(content, options).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let language = match &kind {
+                    CodeBlockKind::Fenced(info) => info.split_whitespace().next().map(str::to_string),
+                    CodeBlockKind::Indented => None,
+                };
+                code = Some((language, line_of(range.start), String::new()));
+                if !is_mermaid(&code) {
+                    events.push(Event::Start(Tag::CodeBlock(kind)));
+                }
+            }
+            Event::Text(text) if code.is_some() => {
+                if let Some((_, _, source)) = code.as_mut() {
+                    source.push_str(&text);
+                }
+                if !is_mermaid(&code) {
+                    events.push(Event::Text(text));
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                let mermaid_block = is_mermaid(&code);
+                if let Some((language, line, source)) = code.take() {
+                    if mermaid_block {
+                        let placeholder = format!(
+                            "<div class=\"mermaid\" data-mermaid-index=\"{}\"></div>\n",
+                            mermaid.len()
+                        );
+                        events.push(Event::Html(CowStr::from(placeholder)));
+                        mermaid.push(MermaidDiagram { source, line });
+                        continue;
+                    }
+                    code_blocks.push(MarkdownCodeBlock { language, code: source, line });
+                }
+                events.push(Event::End(TagEnd::CodeBlock));
+            }
+            Event::Start(tag @ Tag::Heading { .. }) => {
+                heading = Some((tag, line_of(range.start), Vec::new()));
+            }
+            Event::End(TagEnd::Heading(level)) => {
+                let Some((tag, line, inner)) = heading.take() else {
+                    continue;
+                };
+                let text = plain_text(&inner);
+                let Tag::Heading { id, classes, attrs, .. } = tag else {
+                    continue;
+                };
+                // An explicit `{#id}` wins over the generated slug
+                let anchor = match id {
+                    Some(id) => id.to_string(),
+                    None => unique_anchor(&slugify(&text), &mut anchors),
+                };
+                headings.push(MarkdownHeading {
+                    level: level as u32,
+                    text,
+                    anchor: anchor.clone(),
+                    line,
+                });
+                events.push(Event::Start(Tag::Heading {
+                    level,
+                    id: Some(CowStr::from(anchor)),
+                    classes,
+                    attrs,
+                }));
+                events.extend(inner);
+                events.push(Event::End(TagEnd::Heading(level)));
+            }
+            event => match heading.as_mut() {
+                Some((_, _, inner)) => inner.push(event),
+                None => events.push(event),
+            },
+        }
+    }
+
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, events.into_iter());
+
+    RenderedMarkdown {
+        html: sanitizer().clean(&html).to_string(),
+        headings,
+        code_blocks,
+        mermaid,
+    }
+}
+
+fn is_mermaid(code: &Option<(Option<String>, u32, String)>) -> bool {
+    matches!(code, Some((Some(language), _, _)) if language.eq_ignore_ascii_case("mermaid"))
+}
+
+/// Text content of inline events
+fn plain_text(events: &[Event]) -> String {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            Event::Text(text) | Event::Code(text) => Some(text.as_ref()),
+            Event::SoftBreak | Event::HardBreak => Some(" "),
+            _ => None,
+        })
+        .collect()
+}
+
+/// GitHub-style anchor: lowercase alphanumerics, spaces to hyphens, other
+/// punctuation dropped
+fn slugify(text: &str) -> String {
+    text.trim()
+        .chars()
+        .filter_map(|c| match c {
+            c if c.is_alphanumeric() => Some(c.to_lowercase().next().unwrap_or(c)),
+            ' ' | '-' => Some('-'),
+            '_' => Some('_'),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Suffix repeated anchors with -1, -2, …
+fn unique_anchor(slug: &str, seen: &mut HashMap<String, usize>) -> String {
+    let slug = if slug.is_empty() { "section" } else { slug };
+    let count = seen.entry(slug.to_string()).or_insert(0);
+    let anchor = match *count {
+        0 => slug.to_string(),
+        n => format!("{}-{}", slug, n),
+    };
+    *count += 1;
+    anchor
+}
+
+/// ammonia's defaults plus what rendered markdown needs: heading anchors,
+/// code languages, task list checkboxes, footnotes and mermaid placeholders
+fn sanitizer() -> ammonia::Builder<'static> {
+    let mut builder = ammonia::Builder::default();
+    builder
+        .add_tags(["input"])
+        .add_tag_attributes("input", ["type", "checked", "disabled"])
+        .add_tag_attributes("code", ["class"])
+        .add_tag_attributes("div", ["class", "id", "data-mermaid-index"])
+        .add_tag_attributes("sup", ["class", "id"]);
+    for tag in ["h1", "h2", "h3", "h4", "h5", "h6"] {
+        builder.add_tag_attributes(tag, ["id"]);
+    }
+    builder
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_extracts_mermaid_and_tags_code() {
+        let doc = "# Plan\n\nSee the flow:\n\n```mermaid\ngraph TD\n  A --> B\n```\n\n```rust title=x\nfn main() {}\n```\n";
+        let rendered = render(doc);
+
+        assert_eq!(
+            rendered.mermaid,
+            vec![MermaidDiagram {
+                source: "graph TD\n  A --> B\n".to_string(),
+                line: 5,
+            }]
+        );
+        assert!(rendered.html.contains("<div class=\"mermaid\" data-mermaid-index=\"0\"></div>"));
+        assert!(!rendered.html.contains("graph TD"));
+
+        assert_eq!(rendered.code_blocks.len(), 1);
+        assert_eq!(rendered.code_blocks[0].language.as_deref(), Some("rust"));
+        assert_eq!(rendered.code_blocks[0].line, 10);
+        assert!(rendered.html.contains("<code class=\"language-rust\">fn main() {}"));
+    }
+
+    #[test]
+    fn test_render_heading_anchors() {
+        let rendered = render("# Overview\n\n## Data `Model`\n\n## Overview\n\n### Custom {#api}\n");
+        let anchors: Vec<&str> = rendered.headings.iter().map(|h| h.anchor.as_str()).collect();
+        assert_eq!(anchors, vec!["overview", "data-model", "overview-1", "api"]);
+        assert_eq!(rendered.headings[1].text, "Data Model");
+        assert_eq!(rendered.headings[1].level, 2);
+        assert_eq!(rendered.headings[3].line, 7);
+        assert!(rendered.html.contains("<h2 id=\"data-model\">"));
+    }
+
+    #[test]
+    fn test_render_sanitizes_html() {
+        let doc = "<script>alert(1)</script>\n\n[x](javascript:alert(1)) <img src=x onerror=alert(1)>\n\n- [x] done\n";
+        let html = render(doc).html;
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("javascript:"));
+        assert!(!html.contains("onerror"));
+        assert!(html.contains("<input") && html.contains("checked"));
+    }
+}
@@ -0,0 +1,497 @@
+//! MCP client for external servers.
+//!
+//! Connects to the MCP servers a worktree declares in its `.mcp.json` (the
+//! project config Claude Code reads), so their tools can be browsed and
+//! invoked from rstn:
+//!
+//! ```json
+//! {
+//!   "mcpServers": {
+//!     "github": { "command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"] },
+//!     "docs": { "type": "http", "url": "http://localhost:8080/mcp" }
+//!   }
+//! }
+//! ```
+//!
+//! stdio servers are spawned and spoken to with newline-delimited JSON-RPC.
+//! HTTP servers use Streamable HTTP (JSON or SSE responses, with the
+//! `Mcp-Session-Id` the server assigns). An entry named "rstn" is rstn's own
+//! server and is skipped.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::app_state::{McpTool, McpTransport};
+use crate::mcp_server::SESSION_HEADER;
+
+/// Project MCP config file (in the worktree root)
+pub const MCP_CONFIG_FILE: &str = ".mcp.json";
+
+/// Name of rstn's own server in generated configs
+const RSTN_SERVER_NAME: &str = "rstn";
+
+/// Protocol version offered in `initialize`
+const PROTOCOL_VERSION: &str = "2025-03-26";
+
+/// How long to wait for any single response
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How to reach an external server
+#[derive(Debug, Clone, PartialEq)]
+pub enum McpServerConfig {
+    Stdio {
+        command: String,
+        args: Vec<String>,
+        env: BTreeMap<String, String>,
+    },
+    Http {
+        url: String,
+        headers: BTreeMap<String, String>,
+    },
+}
+
+impl McpServerConfig {
+    pub fn transport(&self) -> McpTransport {
+        match self {
+            Self::Stdio { .. } => McpTransport::Stdio,
+            Self::Http { .. } => McpTransport::Http,
+        }
+    }
+
+    /// Command line or URL, for display
+    pub fn target(&self) -> String {
+        match self {
+            Self::Stdio { command, args, .. } => format!("{} {}", command, args.join(" ")).trim_end().to_string(),
+            Self::Http { url, .. } => url.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RawConfigFile {
+    #[serde(default, rename = "mcpServers")]
+    mcp_servers: BTreeMap<String, RawServer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawServer {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    command: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    url: Option<String>,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+}
+
+// ============================================================================
+// Loading
+// ============================================================================
+
+/// Path to a worktree's MCP config (<worktree>/.mcp.json)
+pub fn config_path(worktree_path: &Path) -> PathBuf {
+    worktree_path.join(MCP_CONFIG_FILE)
+}
+
+/// Load the external servers a worktree declares. A missing file declares none.
+pub fn load_servers(worktree_path: &Path) -> Result<Vec<(String, McpServerConfig)>, String> {
+    let path = config_path(worktree_path);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_servers_str(&content).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Parse `.mcp.json` content
+pub fn parse_servers_str(content: &str) -> Result<Vec<(String, McpServerConfig)>, String> {
+    let raw: RawConfigFile = serde_json::from_str(content).map_err(|e| format!("Invalid MCP config: {}", e))?;
+
+    let mut servers = Vec::new();
+    for (name, server) in raw.mcp_servers {
+        if name == RSTN_SERVER_NAME {
+            continue;
+        }
+        let config = match (server.kind.as_deref(), server.command, server.url) {
+            (None | Some("stdio"), Some(command), _) => McpServerConfig::Stdio {
+                command,
+                args: server.args,
+                env: server.env,
+            },
+            (None | Some("http"), None, Some(url)) => McpServerConfig::Http {
+                url,
+                headers: server.headers,
+            },
+            (Some(kind), _, _) if kind != "stdio" && kind != "http" => {
+                return Err(format!("Server '{}': unsupported transport '{}'", name, kind));
+            }
+            _ => return Err(format!("Server '{}': needs a command or a url", name)),
+        };
+        servers.push((name, config));
+    }
+    Ok(servers)
+}
+
+// ============================================================================
+// Client
+// ============================================================================
+
+struct StdioIo {
+    /// Kept so the server is killed when the client is dropped
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+}
+
+enum Transport {
+    Stdio { io: Box<Mutex<StdioIo>> },
+    Http {
+        client: reqwest::Client,
+        url: String,
+        headers: BTreeMap<String, String>,
+        session_id: std::sync::Mutex<Option<String>>,
+    },
+}
+
+/// Connection to one external MCP server
+pub struct McpClient {
+    transport: Transport,
+    next_id: AtomicU64,
+}
+
+impl McpClient {
+    /// Connect and complete the `initialize` handshake
+    pub async fn connect(config: &McpServerConfig) -> Result<Self, String> {
+        let transport = match config {
+            McpServerConfig::Stdio { command, args, env } => {
+                let mut child = Command::new(command)
+                    .args(args)
+                    .envs(env)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::null())
+                    .kill_on_drop(true)
+                    .spawn()
+                    .map_err(|e| format!("Failed to start {}: {}", command, e))?;
+                let stdin = child.stdin.take().ok_or("Failed to open server stdin")?;
+                let stdout = child.stdout.take().ok_or("Failed to open server stdout")?;
+                Transport::Stdio {
+                    io: Box::new(Mutex::new(StdioIo {
+                        _child: child,
+                        stdin,
+                        stdout: BufReader::new(stdout).lines(),
+                    })),
+                }
+            }
+            McpServerConfig::Http { url, headers } => Transport::Http {
+                client: reqwest::Client::new(),
+                url: url.clone(),
+                headers: headers.clone(),
+                session_id: std::sync::Mutex::new(None),
+            },
+        };
+
+        let client = Self {
+            transport,
+            next_id: AtomicU64::new(1),
+        };
+        client
+            .request(
+                "initialize",
+                serde_json::json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": { "name": "rstn", "version": env!("CARGO_PKG_VERSION") }
+                }),
+            )
+            .await?;
+        client.send(jsonrpc_message(None, "notifications/initialized", serde_json::json!({}))).await?;
+        Ok(client)
+    }
+
+    /// All tools the server offers (follows `nextCursor` pagination)
+    pub async fn list_tools(&self) -> Result<Vec<McpTool>, String> {
+        let mut tools = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let params = match &cursor {
+                Some(cursor) => serde_json::json!({ "cursor": cursor }),
+                None => serde_json::json!({}),
+            };
+            let result = self.request("tools/list", params).await?;
+            for tool in result.get("tools").and_then(|t| t.as_array()).into_iter().flatten() {
+                let Some(name) = tool.get("name").and_then(|n| n.as_str()) else {
+                    continue;
+                };
+                tools.push(McpTool {
+                    name: name.to_string(),
+                    description: tool.get("description").and_then(|d| d.as_str()).unwrap_or_default().to_string(),
+                    // rstn's own server predates the camelCase spelling
+                    input_schema: tool
+                        .get("inputSchema")
+                        .or_else(|| tool.get("input_schema"))
+                        .cloned()
+                        .unwrap_or_else(|| serde_json::json!({})),
+                });
+            }
+            cursor = result.get("nextCursor").and_then(|c| c.as_str()).map(str::to_string);
+            if cursor.is_none() {
+                return Ok(tools);
+            }
+        }
+    }
+
+    /// Invoke a tool and return the raw `tools/call` result
+    pub async fn call_tool(&self, name: &str, arguments: serde_json::Value) -> Result<serde_json::Value, String> {
+        self.request("tools/call", serde_json::json!({ "name": name, "arguments": arguments }))
+            .await
+    }
+
+    async fn request(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value, String> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let message = jsonrpc_message(Some(id), method, params);
+        let response = tokio::time::timeout(REQUEST_TIMEOUT, self.exchange(id, message))
+            .await
+            .map_err(|_| format!("No response to {} within {}s", method, REQUEST_TIMEOUT.as_secs()))??;
+
+        if let Some(error) = response.get("error") {
+            let message = error.get("message").and_then(|m| m.as_str()).unwrap_or("Unknown error");
+            return Err(message.to_string());
+        }
+        Ok(response.get("result").cloned().unwrap_or(serde_json::Value::Null))
+    }
+
+    /// Send a request and wait for the response with the same id
+    async fn exchange(&self, id: u64, message: serde_json::Value) -> Result<serde_json::Value, String> {
+        match &self.transport {
+            Transport::Stdio { io } => {
+                let mut io = io.lock().await;
+                write_line(&mut io.stdin, &message).await?;
+                // Skip notifications and server requests until our response
+                while let Some(line) = io.stdout.next_line().await.map_err(|e| e.to_string())? {
+                    let Ok(response) = serde_json::from_str::<serde_json::Value>(&line) else {
+                        continue;
+                    };
+                    if is_response_to(&response, id) {
+                        return Ok(response);
+                    }
+                }
+                Err("Server exited".to_string())
+            }
+            Transport::Http { .. } => {
+                let body = self.post(&message).await?;
+                body.into_iter()
+                    .find(|response| is_response_to(response, id))
+                    .ok_or_else(|| "Server sent no response".to_string())
+            }
+        }
+    }
+
+    /// Send a notification (no response expected)
+    async fn send(&self, message: serde_json::Value) -> Result<(), String> {
+        match &self.transport {
+            Transport::Stdio { io } => write_line(&mut io.lock().await.stdin, &message).await,
+            Transport::Http { .. } => self.post(&message).await.map(|_| ()),
+        }
+    }
+
+    /// POST a message over Streamable HTTP, returning the JSON-RPC messages
+    /// in the response (a JSON body or an SSE stream)
+    async fn post(&self, message: &serde_json::Value) -> Result<Vec<serde_json::Value>, String> {
+        let Transport::Http { client, url, headers, session_id } = &self.transport else {
+            return Ok(Vec::new());
+        };
+
+        let mut request = client
+            .post(url)
+            .header(reqwest::header::ACCEPT, "application/json, text/event-stream")
+            .json(message);
+        for (name, value) in headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        let session = session_id.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        if let Some(session) = session {
+            request = request.header(SESSION_HEADER, session);
+        }
+
+        let response = request.send().await.map_err(|e| format!("Request to {} failed: {}", url, e))?;
+        if let Some(session) = response.headers().get(SESSION_HEADER).and_then(|v| v.to_str().ok()) {
+            *session_id.lock().unwrap_or_else(|e| e.into_inner()) = Some(session.to_string());
+        }
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("{} returned HTTP {}", url, status));
+        }
+        let is_sse = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("text/event-stream"));
+        let body = response.text().await.map_err(|e| format!("Failed to read response: {}", e))?;
+
+        if is_sse {
+            Ok(body
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .filter_map(|data| serde_json::from_str(data.trim()).ok())
+                .collect())
+        } else if body.trim().is_empty() {
+            Ok(Vec::new())
+        } else {
+            serde_json::from_str(&body)
+                .map(|response| vec![response])
+                .map_err(|e| format!("Invalid response: {}", e))
+        }
+    }
+}
+
+fn jsonrpc_message(id: Option<u64>, method: &str, params: serde_json::Value) -> serde_json::Value {
+    let mut message = serde_json::json!({ "jsonrpc": "2.0", "method": method, "params": params });
+    if let Some(id) = id {
+        message["id"] = id.into();
+    }
+    message
+}
+
+fn is_response_to(message: &serde_json::Value, id: u64) -> bool {
+    message.get("id").and_then(|v| v.as_u64()) == Some(id) && message.get("method").is_none()
+}
+
+async fn write_line(stdin: &mut ChildStdin, message: &serde_json::Value) -> Result<(), String> {
+    let mut line = message.to_string();
+    line.push('\n');
+    let write_error = |e: std::io::Error| {
+        if e.kind() == std::io::ErrorKind::BrokenPipe {
+            "Server exited".to_string()
+        } else {
+            format!("Failed to write to server: {}", e)
+        }
+    };
+    stdin.write_all(line.as_bytes()).await.map_err(write_error)?;
+    stdin.flush().await.map_err(write_error)
+}
+
+// ============================================================================
+// Client Manager
+// ============================================================================
+
+/// Connected external servers, per worktree
+#[derive(Default)]
+pub struct McpClientManager {
+    /// worktree_id -> server name -> client
+    clients: RwLock<HashMap<String, HashMap<String, Arc<McpClient>>>>,
+}
+
+impl McpClientManager {
+    /// Replace a worktree's clients (dropping the old ones stops stdio servers)
+    pub async fn replace(&self, worktree_id: &str, clients: HashMap<String, Arc<McpClient>>) {
+        self.clients.write().await.insert(worktree_id.to_string(), clients);
+    }
+
+    /// Drop all of a worktree's clients
+    pub async fn disconnect(&self, worktree_id: &str) {
+        self.clients.write().await.remove(worktree_id);
+    }
+
+    pub async fn get(&self, worktree_id: &str, server: &str) -> Option<Arc<McpClient>> {
+        self.clients.read().await.get(worktree_id)?.get(server).cloned()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_servers() {
+        let servers = parse_servers_str(
+            r#"{
+                "mcpServers": {
+                    "rstn": { "type": "http", "url": "http://localhost:3000" },
+                    "github": { "command": "npx", "args": ["-y", "server-github"], "env": { "TOKEN": "x" } },
+                    "docs": { "type": "http", "url": "http://localhost:8080/mcp" }
+                }
+            }"#,
+        )
+        .unwrap();
+        let names: Vec<&str> = servers.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["docs", "github"]);
+        assert_eq!(servers[1].1.target(), "npx -y server-github");
+        assert_eq!(servers[0].1.transport(), McpTransport::Http);
+
+        assert!(parse_servers_str(r#"{"mcpServers":{"old":{"type":"sse","url":"http://x"}}}"#).is_err());
+        assert!(parse_servers_str(r#"{"mcpServers":{"empty":{}}}"#).is_err());
+        assert!(parse_servers_str("{}").unwrap().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stdio_client_skips_notifications() {
+        // Answers initialize (id 1), swallows notifications/initialized,
+        // answers tools/list (id 2) after an unrelated notification and
+        // fails tools/call (id 3)
+        let script = r#"
+read line
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-03-26","capabilities":{}}}'
+read line
+read line
+echo '{"jsonrpc":"2.0","method":"notifications/message","params":{}}'
+echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"echo","description":"Echo","inputSchema":{"type":"object"}}]}}'
+read line
+echo '{"jsonrpc":"2.0","id":3,"error":{"code":-32602,"message":"Missing text"}}'
+"#;
+        let config = McpServerConfig::Stdio {
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            env: BTreeMap::new(),
+        };
+        let client = McpClient::connect(&config).await.unwrap();
+        let tools = client.list_tools().await.unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].name, "echo");
+        assert_eq!(tools[0].input_schema["type"], "object");
+
+        let error = client.call_tool("echo", serde_json::json!({})).await.unwrap_err();
+        assert_eq!(error, "Missing text");
+        // The script has exited
+        assert_eq!(client.call_tool("echo", serde_json::json!({})).await.unwrap_err(), "Server exited");
+    }
+
+    #[tokio::test]
+    async fn test_http_client_against_rstn_server() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = crate::mcp_server::McpServerManager::new();
+        let port = manager
+            .start_server("wt".to_string(), dir.path().to_path_buf(), "test".to_string(), Some(0))
+            .await
+            .unwrap();
+
+        let config = McpServerConfig::Http {
+            url: format!("http://127.0.0.1:{}/mcp", port),
+            headers: BTreeMap::new(),
+        };
+        let client = McpClient::connect(&config).await.unwrap();
+        let tools = client.list_tools().await.unwrap();
+        assert!(tools.iter().any(|t| t.name == "read_file" && t.input_schema.is_object()));
+
+        manager.stop_server("wt").await.unwrap();
+    }
+}
@@ -38,7 +38,7 @@ use std::path::PathBuf;
 /// * `Err(String)` - Error message if file creation fails
 ///
 /// # Example
-/// ```no_run
+/// ```ignore
 /// let config_path = generate_mcp_config_file("my-worktree-123", 3000)?;
 /// // Creates: /tmp/rstn-mcp-my-worktree-123.json
 /// ```
@@ -91,7 +91,7 @@ pub fn generate_mcp_config_file(worktree_id: &str, port: u16) -> Result<String,
 /// * `Err(String)` - Error message if deletion fails (permissions, etc.)
 ///
 /// # Example
-/// ```no_run
+/// ```ignore
 /// cleanup_mcp_config_file("/tmp/rstn-mcp-my-worktree-123.json")?;
 /// // File is deleted, or already was missing (both OK)
 /// ```
@@ -0,0 +1,163 @@
+//! Per-worktree MCP tool policy.
+//!
+//! `<worktree>/.rstn/mcp-policy.toml` restricts which tools the embedded MCP
+//! server exposes, e.g. to keep Docker control away from clients working in
+//! a production checkout:
+//!
+//! ```toml
+//! # Only these tools are exposed (omit to expose all)
+//! allow = ["read_file", "list_directory", "get_project_context", "rstn_docker_*"]
+//! # Never exposed, even if allowed
+//! deny = ["rstn_docker_stop"]
+//! ```
+//!
+//! Patterns are tool names; a trailing `*` matches any suffix. Tools the
+//! policy hides are left out of `tools/list` and refused by `tools/call`.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Policy file name (in `<worktree>/.rstn/`)
+pub const POLICY_FILE: &str = "mcp-policy.toml";
+
+/// Which MCP tools a worktree exposes
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct McpPolicy {
+    /// Tool patterns to expose (empty = all tools)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    /// Tool patterns to hide (wins over `allow`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+}
+
+impl McpPolicy {
+    /// Whether every tool is exposed
+    pub fn is_unrestricted(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    /// Whether the server may expose `tool`
+    pub fn allows(&self, tool: &str) -> bool {
+        let matches = |patterns: &[String]| patterns.iter().any(|p| pattern_matches(p, tool));
+        (self.allow.is_empty() || matches(&self.allow)) && !matches(&self.deny)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        for pattern in self.allow.iter().chain(&self.deny) {
+            let name = pattern.strip_suffix('*').unwrap_or(pattern);
+            if pattern.trim().is_empty() || name.contains('*') || name.contains(char::is_whitespace) {
+                return Err(format!("Invalid tool pattern '{}'", pattern));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn pattern_matches(pattern: &str, tool: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => tool.starts_with(prefix),
+        None => pattern == tool,
+    }
+}
+
+// ============================================================================
+// Loading
+// ============================================================================
+
+/// Path to a worktree's policy file (<worktree>/.rstn/mcp-policy.toml)
+pub fn policy_path(worktree_path: &Path) -> PathBuf {
+    worktree_path.join(".rstn").join(POLICY_FILE)
+}
+
+/// Load a worktree's policy. A missing file exposes every tool.
+pub fn load_policy(worktree_path: &Path) -> Result<McpPolicy, String> {
+    let path = policy_path(worktree_path);
+    if !path.exists() {
+        return Ok(McpPolicy::default());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_policy_str(&content).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Parse policy TOML content
+pub fn parse_policy_str(content: &str) -> Result<McpPolicy, String> {
+    let policy: McpPolicy = toml::from_str(content).map_err(|e| format!("Invalid MCP policy: {}", e))?;
+    policy.validate()?;
+    Ok(policy)
+}
+
+/// Write a worktree's policy. An unrestricted policy removes the file.
+pub fn save_policy(worktree_path: &Path, policy: &McpPolicy) -> Result<(), String> {
+    policy.validate()?;
+    let path = policy_path(worktree_path);
+    if policy.is_unrestricted() {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Failed to remove {}: {}", path.display(), e))
+            }
+            _ => Ok(()),
+        };
+    }
+
+    let content = toml::to_string(policy).map_err(|e| format!("Failed to serialize MCP policy: {}", e))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_allow_and_deny() {
+        let policy = parse_policy_str(
+            r#"
+allow = ["read_file", "rstn_docker_*"]
+deny = ["rstn_docker_stop"]
+"#,
+        )
+        .unwrap();
+        assert!(policy.allows("read_file"));
+        assert!(policy.allows("rstn_docker_list"));
+        assert!(!policy.allows("rstn_docker_stop"));
+        assert!(!policy.allows("run_just_task"));
+
+        let deny_only = parse_policy_str(r#"deny = ["rstn_docker_*"]"#).unwrap();
+        assert!(deny_only.allows("run_just_task"));
+        assert!(!deny_only.allows("rstn_docker_start"));
+        assert!(McpPolicy::default().allows("rstn_docker_start"));
+    }
+
+    #[test]
+    fn test_parse_policy_rejects_invalid() {
+        assert!(parse_policy_str(r#"allowed = ["read_file"]"#).is_err());
+        assert!(parse_policy_str(r#"deny = ["docker_*_stop"]"#).is_err());
+        assert!(parse_policy_str(r#"deny = [""]"#).is_err());
+    }
+
+    #[test]
+    fn test_save_and_load_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(load_policy(dir.path()).unwrap(), McpPolicy::default());
+
+        let policy = McpPolicy {
+            allow: vec![],
+            deny: vec!["rstn_docker_*".to_string()],
+        };
+        save_policy(dir.path(), &policy).unwrap();
+        assert_eq!(load_policy(dir.path()).unwrap(), policy);
+
+        save_policy(dir.path(), &McpPolicy::default()).unwrap();
+        assert!(!policy_path(dir.path()).exists());
+    }
+}
@@ -0,0 +1,224 @@
+//! MCP port registry
+//!
+//! Persists MCP server port assignments in `~/.rstn/mcp-ports.json` so each
+//! worktree gets the same port across restarts (keeping generated Claude
+//! configs valid) and concurrent servers across projects never collide.
+//!
+//! Registrations are keyed by worktree path, since worktree IDs are
+//! regenerated on every refresh.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::net::{SocketAddr, TcpListener};
+use std::path::{Path, PathBuf};
+
+/// First port handed out by the registry
+pub const BASE_PORT: u16 = 3000;
+
+/// Number of ports the registry may hand out (BASE_PORT..BASE_PORT + PORT_RANGE)
+const PORT_RANGE: u16 = 1000;
+
+/// A worktree's port reservation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpPortRegistration {
+    pub worktree_path: String,
+    pub project_name: String,
+    pub port: u16,
+    /// PID of the rstn process serving this port (None when stopped)
+    #[serde(default)]
+    pub pid: Option<u32>,
+    /// Last update (ISO 8601)
+    pub updated_at: String,
+}
+
+/// Contents of mcp-ports.json
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct McpPortRegistryData {
+    /// worktree path -> registration
+    #[serde(default)]
+    pub registrations: BTreeMap<String, McpPortRegistration>,
+}
+
+/// Port registry backed by a JSON file
+#[derive(Debug, Clone)]
+pub struct McpPortRegistry {
+    path: PathBuf,
+}
+
+impl McpPortRegistry {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Default registry location (~/.rstn/mcp-ports.json)
+    pub fn default_path() -> PathBuf {
+        crate::persistence::get_rstn_dir().join("mcp-ports.json")
+    }
+
+    /// Load the registry (empty if missing or unreadable)
+    pub fn load(&self) -> McpPortRegistryData {
+        fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, data: &McpPortRegistryData) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create registry directory: {}", e))?;
+        }
+        let json = serde_json::to_string_pretty(data)
+            .map_err(|e| format!("Failed to serialize MCP port registry: {}", e))?;
+        fs::write(&self.path, json).map_err(|e| format!("Failed to write MCP port registry: {}", e))
+    }
+
+    /// Get the worktree's reserved port, reserving the lowest free one if needed.
+    pub fn reserve(&self, worktree_path: &str, project_name: &str) -> Result<u16, String> {
+        let mut data = self.load();
+        if let Some(existing) = data.registrations.get(worktree_path) {
+            return Ok(existing.port);
+        }
+
+        let port = (BASE_PORT..BASE_PORT + PORT_RANGE)
+            .find(|p| !data.registrations.values().any(|r| r.port == *p))
+            .ok_or("No free MCP ports in registry range")?;
+
+        data.registrations.insert(
+            worktree_path.to_string(),
+            McpPortRegistration {
+                worktree_path: worktree_path.to_string(),
+                project_name: project_name.to_string(),
+                port,
+                pid: None,
+                updated_at: chrono::Utc::now().to_rfc3339(),
+            },
+        );
+        self.save(&data)?;
+        Ok(port)
+    }
+
+    /// Record that this process is serving the worktree on `port`
+    pub fn mark_running(&self, worktree_path: &str, project_name: &str, port: u16) -> Result<(), String> {
+        let mut data = self.load();
+        data.registrations.insert(
+            worktree_path.to_string(),
+            McpPortRegistration {
+                worktree_path: worktree_path.to_string(),
+                project_name: project_name.to_string(),
+                port,
+                pid: Some(std::process::id()),
+                updated_at: chrono::Utc::now().to_rfc3339(),
+            },
+        );
+        self.save(&data)
+    }
+
+    /// Record that the worktree's server stopped (the port stays reserved)
+    pub fn mark_stopped(&self, worktree_path: &str) -> Result<(), String> {
+        let mut data = self.load();
+        if let Some(registration) = data.registrations.get_mut(worktree_path) {
+            registration.pid = None;
+            registration.updated_at = chrono::Utc::now().to_rfc3339();
+            self.save(&data)?;
+        }
+        Ok(())
+    }
+
+    /// Clean up stale registrations.
+    ///
+    /// Drops reservations for worktrees that no longer exist, and clears the
+    /// PID of registrations marked running whose port is no longer in use
+    /// (the owning process exited without stopping). Returns the number of
+    /// stale registrations found.
+    pub fn prune_stale(&self) -> Result<usize, String> {
+        let mut data = self.load();
+        let before = data.registrations.len();
+        data.registrations.retain(|path, _| Path::new(path).exists());
+        let mut stale = before - data.registrations.len();
+
+        for registration in data.registrations.values_mut() {
+            if registration.pid.is_some() && is_port_free(registration.port) {
+                registration.pid = None;
+                stale += 1;
+            }
+        }
+
+        if stale > 0 {
+            self.save(&data)?;
+        }
+        Ok(stale)
+    }
+}
+
+/// Whether nothing is listening on the port (bind succeeds)
+fn is_port_free(port: u16) -> bool {
+    TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], port))).is_ok()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_reserve_is_stable_and_unique() {
+        let dir = tempdir().unwrap();
+        let registry = McpPortRegistry::new(dir.path().join("mcp-ports.json"));
+
+        let a = registry.reserve("/projects/a", "a").unwrap();
+        let b = registry.reserve("/projects/b", "b").unwrap();
+        assert_eq!(a, BASE_PORT);
+        assert_eq!(b, BASE_PORT + 1);
+
+        // Reloading from disk returns the same reservation
+        let reloaded = McpPortRegistry::new(dir.path().join("mcp-ports.json"));
+        assert_eq!(reloaded.reserve("/projects/a", "a").unwrap(), a);
+    }
+
+    #[test]
+    fn test_mark_running_and_stopped() {
+        let dir = tempdir().unwrap();
+        let registry = McpPortRegistry::new(dir.path().join("mcp-ports.json"));
+
+        registry.mark_running("/projects/a", "a", 3005).unwrap();
+        let registration = registry.load().registrations["/projects/a"].clone();
+        assert_eq!(registration.port, 3005);
+        assert_eq!(registration.pid, Some(std::process::id()));
+
+        registry.mark_stopped("/projects/a").unwrap();
+        let registration = registry.load().registrations["/projects/a"].clone();
+        assert_eq!(registration.port, 3005);
+        assert!(registration.pid.is_none());
+    }
+
+    #[test]
+    fn test_prune_stale() {
+        let dir = tempdir().unwrap();
+        let worktree = dir.path().join("worktree");
+        fs::create_dir(&worktree).unwrap();
+        let worktree_path = worktree.to_string_lossy().to_string();
+        let registry = McpPortRegistry::new(dir.path().join("mcp-ports.json"));
+
+        // Missing worktree is dropped
+        registry.reserve("/does/not/exist", "gone").unwrap();
+
+        // Running registration on a free port loses its PID
+        let port = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+        registry.mark_running(&worktree_path, "live", port).unwrap();
+
+        assert_eq!(registry.prune_stale().unwrap(), 2);
+        let data = registry.load();
+        assert_eq!(data.registrations.len(), 1);
+        assert!(data.registrations[&worktree_path].pid.is_none());
+        assert_eq!(registry.prune_stale().unwrap(), 0);
+    }
+}
@@ -3,24 +3,36 @@
 //! Provides an embedded Model Context Protocol server that exposes
 //! project-specific context to AI clients (Claude Desktop, Claude Code).
 //!
-//! Uses axum for HTTP with SSE transport, implementing MCP JSON-RPC protocol.
+//! Uses axum for HTTP, implementing MCP JSON-RPC protocol over:
+//! - Plain HTTP POST (`/mcp`, JSON response)
+//! - Streamable HTTP (`/mcp` POST/GET/DELETE with `Mcp-Session-Id` and SSE
+//!   responses, MCP 2025-03-26) for persistent sessions and progress
+//!   notifications
+//!
+//! Which tools are exposed is restricted per worktree by `mcp_policy`.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::net::SocketAddr;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, RwLock as StdRwLock};
+use std::time::{Duration, Instant};
 
 use axum::extract::State;
-use axum::response::sse::{Event, Sse};
-use axum::response::IntoResponse;
+use axum::http::{header, HeaderMap, HeaderName, HeaderValue, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
 use axum::routing::{get, post};
 use axum::{Json, Router};
 use futures_util::stream::Stream;
 use serde::{Deserialize, Serialize};
 use tokio::net::TcpListener;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use tokio_util::sync::CancellationToken;
 
+use crate::actions::{McpLogDirectionData, McpToolMetricsData};
+use crate::mcp_policy::McpPolicy;
+use crate::mcp_registry::McpPortRegistry;
+
 // Note: McpState and McpStatus are defined in app_state.rs
 
 // ============================================================================
@@ -206,6 +218,133 @@ fn get_available_tools() -> Vec<ToolInfo> {
                 "required": ["session_id", "content"]
             }),
         },
+        // ====================================================================
+        // Docker Tools
+        // ====================================================================
+        ToolInfo {
+            name: "rstn_docker_list".to_string(),
+            description: "List Docker services known to rstn with their status, image and port".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {}
+            }),
+        },
+        ToolInfo {
+            name: "rstn_docker_start".to_string(),
+            description: "Start (or create and start) a Docker service by its ID".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "service_id": {
+                        "type": "string",
+                        "description": "Service/container ID as returned by rstn_docker_list (e.g., rstn-postgres)"
+                    }
+                },
+                "required": ["service_id"]
+            }),
+        },
+        ToolInfo {
+            name: "rstn_docker_stop".to_string(),
+            description: "Stop a running Docker service by its ID".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "service_id": {
+                        "type": "string",
+                        "description": "Service/container ID as returned by rstn_docker_list"
+                    }
+                },
+                "required": ["service_id"]
+            }),
+        },
+        ToolInfo {
+            name: "rstn_docker_logs".to_string(),
+            description: "Get recent log lines from a Docker service".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "service_id": {
+                        "type": "string",
+                        "description": "Service/container ID as returned by rstn_docker_list"
+                    },
+                    "tail": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Number of lines from the end of the log (default: 100)"
+                    }
+                },
+                "required": ["service_id"]
+            }),
+        },
+        // ====================================================================
+        // Spec-kit Tools
+        // ====================================================================
+        ToolInfo {
+            name: "rstn_get_spec".to_string(),
+            description: "Get the spec-kit feature being implemented: its spec.md, plan.md and tasks.md path".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "feature": {
+                        "type": "string",
+                        "description": "Feature directory under specs/ (e.g. 001-user-export), short name or number. Defaults to the feature open in rstn, else the newest one"
+                    }
+                }
+            }),
+        },
+        ToolInfo {
+            name: "rstn_get_tasks".to_string(),
+            description: "List the tasks of the feature's tasks.md with their completion and overall progress".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "feature": {
+                        "type": "string",
+                        "description": "Feature directory under specs/ (e.g. 001-user-export), short name or number. Defaults to the feature open in rstn, else the newest one"
+                    }
+                }
+            }),
+        },
+        ToolInfo {
+            name: "rstn_mark_task_done".to_string(),
+            description: "Check off a task in the feature's tasks.md once it is implemented (the rstn UI updates live)".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "task_id": {
+                        "type": "string",
+                        "description": "Task ID (e.g. T003)"
+                    },
+                    "done": {
+                        "type": "boolean",
+                        "description": "Whether the task is complete (default: true)"
+                    },
+                    "feature": {
+                        "type": "string",
+                        "description": "Feature directory under specs/ (e.g. 001-user-export), short name or number. Defaults to the feature open in rstn, else the newest one"
+                    }
+                },
+                "required": ["task_id"]
+            }),
+        },
+        ToolInfo {
+            name: "rstn_report_progress".to_string(),
+            description: "Report what you are working on so the user can follow along in the rstn UI".to_string(),
+            input_schema: serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "message": {
+                        "type": "string",
+                        "description": "Short progress note (e.g. 'Writing CSV export tests')"
+                    },
+                    "task_id": {
+                        "type": "string",
+                        "description": "Task the note is about (optional)"
+                    }
+                },
+                "required": ["message"]
+            }),
+        },
         ToolInfo {
             name: "render_ui".to_string(),
             description: "Render a custom user interface using A2UI JSON protocol. The UI will be displayed in the A2UI tab.".to_string(),
@@ -672,6 +811,77 @@ impl McpServerContext {
                 }))
             }
 
+            // ================================================================
+            // Spec-kit Tools
+            // ================================================================
+            "rstn_get_spec" => {
+                let feature = self.resolve_feature(params).await?;
+                let dir = feature.dir(&self.worktree_root);
+                let result = serde_json::json!({
+                    "feature": feature.dir_name(),
+                    "spec_dir": feature.relative_dir(),
+                    "spec": crate::spec_kit::read_artifact(&dir, "spec.md"),
+                    "plan": crate::spec_kit::read_artifact(&dir, "plan.md"),
+                    "tasks_path": format!("{}/tasks.md", feature.relative_dir()),
+                });
+                Ok(serde_json::json!({
+                    "content": [{
+                        "type": "text",
+                        "text": serde_json::to_string_pretty(&result).unwrap()
+                    }]
+                }))
+            }
+
+            "rstn_get_tasks" | "rstn_mark_task_done" => {
+                let feature = self.resolve_feature(params).await?;
+                let tasks_path = format!("{}/tasks.md", feature.relative_dir());
+                let full_path = self.worktree_root.join(&tasks_path);
+
+                let items = if tool_name == "rstn_mark_task_done" {
+                    let task_id = params
+                        .get("task_id")
+                        .and_then(|v| v.as_str())
+                        .ok_or("Missing 'task_id' parameter")?;
+                    let done = params.get("done").and_then(|v| v.as_bool()).unwrap_or(true);
+                    let items = crate::spec_tasks::update_file(&full_path, task_id, done)?;
+                    let tasks = crate::app_state::SpecTasksState::new(tasks_path.clone(), items.clone());
+                    self.dispatch_to_worktree(crate::actions::Action::SetSpecTasks { tasks }).await;
+                    items
+                } else {
+                    crate::spec_tasks::load(&full_path)?
+                };
+
+                let progress = crate::spec_tasks::summarize(&items);
+                let result = serde_json::json!({ "tasks_path": tasks_path, "progress": progress, "tasks": items });
+                Ok(serde_json::json!({
+                    "content": [{
+                        "type": "text",
+                        "text": serde_json::to_string_pretty(&result).unwrap()
+                    }]
+                }))
+            }
+
+            "rstn_report_progress" => {
+                let message = params
+                    .get("message")
+                    .and_then(|v| v.as_str())
+                    .filter(|m| !m.trim().is_empty())
+                    .ok_or("Missing 'message' parameter")?
+                    .trim()
+                    .to_string();
+                let task_id = params.get("task_id").and_then(|v| v.as_str()).map(str::to_string);
+
+                let shown = self
+                    .dispatch_to_worktree(crate::actions::Action::ReportSpecProgress { message, task_id })
+                    .await;
+                Ok(serde_json::json!({
+                    "content": [{
+                        "type": "text",
+                        "text": if shown { "Progress reported." } else { "Progress noted (the worktree is not open in rstn)." }
+                    }]
+                }))
+            }
+
             "render_ui" => {
                 let payload = params
                     .get("payload")
@@ -699,24 +909,380 @@ impl McpServerContext {
                 }))
             }
 
+            // ================================================================
+            // Docker Tools
+            // ================================================================
+            "rstn_docker_list" | "rstn_docker_start" | "rstn_docker_stop" | "rstn_docker_logs" => {
+                self.execute_docker_tool(tool_name, params).await
+            }
+
             _ => Err(format!("Unknown tool: {}", tool_name)),
         }
     }
+
+    /// Spec-kit feature a tool call refers to: the `feature` parameter, else
+    /// the feature open in this worktree's spec workflow, else the newest one
+    async fn resolve_feature(&self, params: &serde_json::Value) -> Result<crate::spec_kit::SpecFeature, String> {
+        if let Some(query) = params.get("feature").and_then(|v| v.as_str()) {
+            return crate::spec_kit::find_feature(&self.worktree_root, query)
+                .ok_or_else(|| format!("Feature not found under specs/: {}", query));
+        }
+
+        if let Some(app_state) = crate::APP_STATE.get() {
+            let state = app_state.read().await;
+            let open_feature = state
+                .active_project()
+                .and_then(|p| p.active_worktree())
+                .filter(|w| std::path::Path::new(&w.path) == self.worktree_root)
+                .and_then(|w| w.workflows.spec.as_ref())
+                .map(|spec| crate::spec_kit::SpecFeature {
+                    number: spec.feature_number,
+                    short_name: spec.feature_name.clone(),
+                });
+            if let Some(feature) = open_feature {
+                return Ok(feature);
+            }
+        }
+
+        crate::spec_kit::list_features(&self.worktree_root)
+            .pop()
+            .ok_or_else(|| "No spec-kit feature found under specs/".to_string())
+    }
+
+    /// Apply an action to this server's worktree so the UI updates live.
+    /// Returns false when the worktree is not the active one in the app.
+    async fn dispatch_to_worktree(&self, action: crate::actions::Action) -> bool {
+        let Some(app_state) = crate::APP_STATE.get() else {
+            return false;
+        };
+        {
+            let mut state = app_state.write().await;
+            let is_active = state
+                .active_project()
+                .and_then(|p| p.active_worktree())
+                .is_some_and(|w| std::path::Path::new(&w.path) == self.worktree_root);
+            if !is_active {
+                return false;
+            }
+            crate::reducer::reduce(&mut state, action);
+        }
+        crate::notify_state_update().await;
+        true
+    }
+
+    /// Execute a Docker tool through the shared DockerManager
+    async fn execute_docker_tool(
+        &self,
+        tool_name: &str,
+        params: &serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        let service_id = params.get("service_id").and_then(|v| v.as_str());
+        if tool_name != "rstn_docker_list" && service_id.is_none() {
+            return Err("Missing 'service_id' parameter".to_string());
+        }
+        let service_id = service_id.unwrap_or_default();
+
+        let dm = crate::get_docker_manager().await.map_err(|e| e.to_string())?;
+
+        let text = match tool_name {
+            "rstn_docker_list" => {
+                let services = dm.list_services().await;
+                serde_json::to_string_pretty(&services).unwrap()
+            }
+            "rstn_docker_start" => {
+                dm.start_service(service_id).await?;
+                crate::refresh_docker_services_internal().await;
+                crate::notify_state_update().await;
+                format!("Service started: {}", service_id)
+            }
+            "rstn_docker_stop" => {
+                dm.stop_service(service_id).await?;
+                crate::refresh_docker_services_internal().await;
+                crate::notify_state_update().await;
+                format!("Service stopped: {}", service_id)
+            }
+            "rstn_docker_logs" => {
+                let tail = params.get("tail").and_then(|v| v.as_u64()).unwrap_or(100) as usize;
+                dm.get_logs(service_id, tail).await?.join("")
+            }
+            _ => return Err(format!("Unknown tool: {}", tool_name)),
+        };
+
+        Ok(serde_json::json!({
+            "content": [{
+                "type": "text",
+                "text": text
+            }]
+        }))
+    }
+}
+
+/// Maximum payload length recorded in the MCP inspector log
+const MAX_AUDIT_PAYLOAD_LEN: usize = 500;
+
+/// Record a tool call in the MCP log of the server's worktree
+async fn audit_tool_call(
+    worktree_id: &str,
+    direction: McpLogDirectionData,
+    tool_name: &str,
+    payload: String,
+    is_error: bool,
+) {
+    let payload = if payload.chars().count() > MAX_AUDIT_PAYLOAD_LEN {
+        let truncated: String = payload.chars().take(MAX_AUDIT_PAYLOAD_LEN).collect();
+        format!("{}...", truncated)
+    } else {
+        payload
+    };
+
+    let action = crate::actions::Action::AddMcpLogEntry {
+        worktree_id: worktree_id.to_string(),
+        entry: crate::actions::McpLogEntryData {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            direction,
+            method: "tools/call".to_string(),
+            tool_name: Some(tool_name.to_string()),
+            payload,
+            is_error,
+        },
+    };
+
+    // No app state when the server runs without the app (tests)
+    let Some(app_state) = crate::APP_STATE.get() else {
+        return;
+    };
+    {
+        let mut state = app_state.write().await;
+        crate::reducer::reduce(&mut state, action);
+    }
+    crate::notify_state_update().await;
+}
+
+// ============================================================================
+// Streamable HTTP Sessions
+// ============================================================================
+
+/// Protocol versions this server speaks (newest first)
+const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-03-26", "2024-11-05"];
+
+/// Header carrying the session ID for the streamable HTTP transport
+pub const SESSION_HEADER: &str = "mcp-session-id";
+
+/// Buffered server-initiated messages per session
+const SESSION_CHANNEL_CAPACITY: usize = 64;
+
+/// Streamable HTTP sessions for one server (session ID -> message channel)
+#[derive(Default)]
+pub struct McpSessionStore {
+    sessions: RwLock<HashMap<String, broadcast::Sender<serde_json::Value>>>,
+}
+
+impl McpSessionStore {
+    /// Create a session and return its ID
+    pub async fn create(&self) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        let (tx, _) = broadcast::channel(SESSION_CHANNEL_CAPACITY);
+        self.sessions.write().await.insert(id.clone(), tx);
+        id
+    }
+
+    pub async fn contains(&self, session_id: &str) -> bool {
+        self.sessions.read().await.contains_key(session_id)
+    }
+
+    /// Subscribe to server-initiated messages for a session
+    pub async fn subscribe(&self, session_id: &str) -> Option<broadcast::Receiver<serde_json::Value>> {
+        self.sessions.read().await.get(session_id).map(|tx| tx.subscribe())
+    }
+
+    /// Terminate a session (closes its open GET streams)
+    pub async fn remove(&self, session_id: &str) -> bool {
+        self.sessions.write().await.remove(session_id).is_some()
+    }
+
+    /// Send a notification to every session's GET stream.
+    /// Returns the number of open streams that received it.
+    pub async fn broadcast(&self, method: &str, params: serde_json::Value) -> usize {
+        let message = jsonrpc_notification(method, params);
+        self.sessions
+            .read()
+            .await
+            .values()
+            .map(|tx| tx.send(message.clone()).unwrap_or(0))
+            .sum()
+    }
+}
+
+/// Router state: server context plus its sessions, metrics and tool policy
+#[derive(Clone)]
+struct McpHttpState {
+    context: Arc<McpServerContext>,
+    sessions: Arc<McpSessionStore>,
+    metrics: Arc<McpMetrics>,
+    policy: Arc<StdRwLock<McpPolicy>>,
+}
+
+impl McpHttpState {
+    fn allows(&self, tool_name: &str) -> bool {
+        self.policy.read().unwrap_or_else(|e| e.into_inner()).allows(tool_name)
+    }
+}
+
+// ============================================================================
+// Tool Metrics
+// ============================================================================
+
+/// Latency samples kept per tool for percentile calculation
+const MAX_LATENCY_SAMPLES: usize = 200;
+
+/// Running counters for one tool
+#[derive(Default)]
+struct ToolStats {
+    call_count: u64,
+    error_count: u64,
+    /// Most recent latencies in milliseconds (bounded by MAX_LATENCY_SAMPLES)
+    latencies_ms: VecDeque<f64>,
+    last_called_at: Option<String>,
+}
+
+/// Per-tool call metrics for one server
+#[derive(Default)]
+pub struct McpMetrics {
+    tools: Mutex<HashMap<String, ToolStats>>,
+}
+
+impl McpMetrics {
+    /// Record a completed tool call
+    pub fn record(&self, tool_name: &str, latency: Duration, is_error: bool) {
+        let mut tools = self.tools.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = tools.entry(tool_name.to_string()).or_default();
+        stats.call_count += 1;
+        if is_error {
+            stats.error_count += 1;
+        }
+        if stats.latencies_ms.len() == MAX_LATENCY_SAMPLES {
+            stats.latencies_ms.pop_front();
+        }
+        stats.latencies_ms.push_back(latency.as_secs_f64() * 1000.0);
+        stats.last_called_at = Some(chrono::Utc::now().to_rfc3339());
+    }
+
+    /// Metrics for every tool called so far, sorted by tool name
+    pub fn snapshot(&self) -> Vec<McpToolMetricsData> {
+        let tools = self.tools.lock().unwrap_or_else(|e| e.into_inner());
+        let mut metrics: Vec<McpToolMetricsData> = tools
+            .iter()
+            .map(|(name, stats)| {
+                let mut sorted: Vec<f64> = stats.latencies_ms.iter().copied().collect();
+                sorted.sort_by(|a, b| a.total_cmp(b));
+                McpToolMetricsData {
+                    tool_name: name.clone(),
+                    call_count: stats.call_count,
+                    error_count: stats.error_count,
+                    p50_ms: percentile(&sorted, 50.0),
+                    p95_ms: percentile(&sorted, 95.0),
+                    last_called_at: stats.last_called_at.clone(),
+                }
+            })
+            .collect();
+        metrics.sort_by(|a, b| a.tool_name.cmp(&b.tool_name));
+        metrics
+    }
+}
+
+/// Nearest-rank percentile of sorted samples (0.0 when empty)
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Push a server's metrics into state if it belongs to the active worktree
+async fn publish_metrics(worktree_id: &str, metrics: Vec<McpToolMetricsData>) {
+    {
+        let mut state = crate::get_app_state().write().await;
+        let is_active = state
+            .active_project()
+            .and_then(|p| p.active_worktree())
+            .is_some_and(|w| w.id == worktree_id);
+        if !is_active {
+            return;
+        }
+        crate::reducer::reduce(&mut state, crate::actions::Action::UpdateMcpMetrics { metrics });
+    }
+    crate::notify_state_update().await;
+}
+
+/// Build a JSON-RPC notification message
+fn jsonrpc_notification(method: &str, params: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": params
+    })
+}
+
+/// Build a `notifications/progress` message for a request's progress token
+fn progress_notification(token: &serde_json::Value, progress: u32, message: &str) -> serde_json::Value {
+    jsonrpc_notification(
+        "notifications/progress",
+        serde_json::json!({
+            "progressToken": token,
+            "progress": progress,
+            "total": 1,
+            "message": message
+        }),
+    )
+}
+
+/// Echo the client's protocol version if supported, otherwise offer our oldest
+fn negotiate_protocol_version(params: &serde_json::Value) -> &'static str {
+    let requested = params.get("protocolVersion").and_then(|v| v.as_str());
+    SUPPORTED_PROTOCOL_VERSIONS
+        .iter()
+        .find(|v| Some(**v) == requested)
+        .or(SUPPORTED_PROTOCOL_VERSIONS.last())
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Whether the client accepts SSE responses (streamable HTTP clients do)
+fn accepts_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .any(|v| v.contains("text/event-stream"))
+}
+
+fn session_id(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(SESSION_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string())
+}
+
+/// Encode a JSON-RPC message as an SSE event
+fn sse_message(message: &impl Serialize) -> Result<Event, std::convert::Infallible> {
+    Ok(Event::default().data(serde_json::to_string(message).unwrap_or_default()))
 }
 
 // ============================================================================
 // HTTP Handlers
 // ============================================================================
 
-/// Handle MCP JSON-RPC requests
-async fn handle_mcp_request(
-    State(context): State<Arc<McpServerContext>>,
-    Json(request): Json<JsonRpcRequest>,
-) -> impl IntoResponse {
-    let result = match request.method.as_str() {
+/// Dispatch a JSON-RPC request to the matching MCP method
+async fn dispatch_request(
+    state: &McpHttpState,
+    request: &JsonRpcRequest,
+) -> Result<serde_json::Value, String> {
+    let context = &state.context;
+    match request.method.as_str() {
         "initialize" => {
             Ok(serde_json::json!({
-                "protocolVersion": "2024-11-05",
+                "protocolVersion": negotiate_protocol_version(&request.params),
                 "capabilities": {
                     "tools": {}
                 },
@@ -728,7 +1294,10 @@ async fn handle_mcp_request(
         }
 
         "tools/list" => {
-            let tools = get_available_tools();
+            let tools: Vec<ToolInfo> = get_available_tools()
+                .into_iter()
+                .filter(|tool| state.allows(&tool.name))
+                .collect();
             Ok(serde_json::json!({
                 "tools": tools
             }))
@@ -747,7 +1316,23 @@ async fn handle_mcp_request(
                 .cloned()
                 .unwrap_or(serde_json::json!({}));
 
-            context.execute_tool(tool_name, &arguments).await
+            let worktree_id = &context.worktree_id;
+            audit_tool_call(worktree_id, McpLogDirectionData::In, tool_name, arguments.to_string(), false).await;
+
+            let result = if state.allows(tool_name) {
+                let started = Instant::now();
+                let result = context.execute_tool(tool_name, &arguments).await;
+                state.metrics.record(tool_name, started.elapsed(), result.is_err());
+                publish_metrics(worktree_id, state.metrics.snapshot()).await;
+                result
+            } else {
+                Err(format!("Tool '{}' is disabled by this worktree's MCP policy", tool_name))
+            };
+            match &result {
+                Ok(value) => audit_tool_call(worktree_id, McpLogDirectionData::Out, tool_name, value.to_string(), false).await,
+                Err(e) => audit_tool_call(worktree_id, McpLogDirectionData::Out, tool_name, e.clone(), true).await,
+            }
+            result
         }
 
         "notifications/initialized" => {
@@ -756,18 +1341,21 @@ async fn handle_mcp_request(
         }
 
         _ => Err(format!("Unknown method: {}", request.method)),
-    };
+    }
+}
 
-    let response = match result {
+/// Wrap a dispatch result in a JSON-RPC response
+fn to_response(id: Option<serde_json::Value>, result: Result<serde_json::Value, String>) -> JsonRpcResponse {
+    match result {
         Ok(result) => JsonRpcResponse {
             jsonrpc: "2.0".to_string(),
-            id: request.id,
+            id,
             result: Some(result),
             error: None,
         },
         Err(message) => JsonRpcResponse {
             jsonrpc: "2.0".to_string(),
-            id: request.id,
+            id,
             result: None,
             error: Some(JsonRpcError {
                 code: -32603,
@@ -775,14 +1363,108 @@ async fn handle_mcp_request(
                 data: None,
             }),
         },
+    }
+}
+
+/// Handle MCP JSON-RPC requests (POST /mcp).
+///
+/// Plain clients get a JSON response. Streamable HTTP clients (Accept:
+/// text/event-stream) get a session on `initialize` and an SSE response for
+/// `tools/call`, carrying progress notifications before the result.
+async fn handle_mcp_request(
+    State(state): State<McpHttpState>,
+    headers: HeaderMap,
+    Json(request): Json<JsonRpcRequest>,
+) -> Response {
+    let streaming = accepts_event_stream(&headers);
+
+    if request.method != "initialize" {
+        if let Some(id) = session_id(&headers) {
+            if !state.sessions.contains(&id).await {
+                return (StatusCode::NOT_FOUND, "Unknown MCP session").into_response();
+            }
+        }
+    }
+
+    // Notifications have no ID and get no response body
+    if request.id.is_none() && request.method.starts_with("notifications/") {
+        let _ = dispatch_request(&state, &request).await;
+        return StatusCode::ACCEPTED.into_response();
+    }
+
+    let progress_token = request
+        .params
+        .get("_meta")
+        .and_then(|m| m.get("progressToken"))
+        .cloned();
+
+    if streaming && request.method == "tools/call" {
+        let state = state.clone();
+        let stream = async_stream::stream! {
+            let tool_name = request.params.get("name").and_then(|v| v.as_str()).unwrap_or("").to_string();
+            if let Some(token) = &progress_token {
+                yield sse_message(&progress_notification(token, 0, &format!("Running {}", tool_name)));
+            }
+            let result = dispatch_request(&state, &request).await;
+            if let Some(token) = &progress_token {
+                yield sse_message(&progress_notification(token, 1, &format!("Finished {}", tool_name)));
+            }
+            yield sse_message(&to_response(request.id.clone(), result));
+        };
+        return Sse::new(stream).into_response();
+    }
+
+    let new_session = if streaming && request.method == "initialize" {
+        Some(state.sessions.create().await)
+    } else {
+        None
     };
 
-    Json(response)
+    let result = dispatch_request(&state, &request).await;
+    let mut response = Json(to_response(request.id, result)).into_response();
+    if let Some(id) = new_session.and_then(|id| HeaderValue::from_str(&id).ok()) {
+        response.headers_mut().insert(SESSION_HEADER, id);
+    }
+    response
 }
 
-/// SSE endpoint for MCP streaming
+/// Open a stream for server-initiated messages (GET /mcp)
+async fn handle_mcp_stream(State(state): State<McpHttpState>, headers: HeaderMap) -> Response {
+    if !accepts_event_stream(&headers) {
+        return StatusCode::METHOD_NOT_ALLOWED.into_response();
+    }
+    let Some(id) = session_id(&headers) else {
+        return (StatusCode::BAD_REQUEST, "Missing Mcp-Session-Id header").into_response();
+    };
+    let Some(mut rx) = state.sessions.subscribe(&id).await else {
+        return (StatusCode::NOT_FOUND, "Unknown MCP session").into_response();
+    };
+
+    let stream = async_stream::stream! {
+        loop {
+            match rx.recv().await {
+                Ok(message) => yield sse_message(&message),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    };
+
+    Sse::new(stream).keep_alive(KeepAlive::default()).into_response()
+}
+
+/// Terminate a session (DELETE /mcp)
+async fn handle_mcp_delete(State(state): State<McpHttpState>, headers: HeaderMap) -> StatusCode {
+    match session_id(&headers) {
+        Some(id) if state.sessions.remove(&id).await => StatusCode::OK,
+        Some(_) => StatusCode::NOT_FOUND,
+        None => StatusCode::BAD_REQUEST,
+    }
+}
+
+/// Legacy SSE endpoint (keepalive only)
 async fn handle_sse(
-    State(_context): State<Arc<McpServerContext>>,
+    State(_state): State<McpHttpState>,
 ) -> Sse<impl Stream<Item = Result<Event, std::convert::Infallible>>> {
     let stream = async_stream::stream! {
         // Send initial connection event
@@ -819,12 +1501,33 @@ pub struct RunningServer {
     pub port: u16,
     /// Handle to the server task
     pub handle: tokio::task::JoinHandle<()>,
+    /// Streamable HTTP sessions
+    pub sessions: Arc<McpSessionStore>,
+    /// Per-tool call metrics
+    pub metrics: Arc<McpMetrics>,
+    /// Which tools are exposed (from .rstn/mcp-policy.toml)
+    pub policy: Arc<StdRwLock<McpPolicy>>,
+    /// Worktree root the server is sandboxed to
+    pub worktree_path: String,
+    /// Project the worktree belongs to
+    pub project_name: String,
+}
+
+/// Summary of a running server (for listing across projects)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunningServerInfo {
+    pub worktree_id: String,
+    pub worktree_path: String,
+    pub project_name: String,
+    pub port: u16,
 }
 
 /// Manager for MCP server instances (one per worktree)
 pub struct McpServerManager {
     /// Map of worktree_id -> running server
     servers: RwLock<HashMap<String, RunningServer>>,
+    /// Persistent port reservations (None: ports are assigned ad hoc)
+    registry: Option<McpPortRegistry>,
 }
 
 impl Default for McpServerManager {
@@ -837,6 +1540,15 @@ impl McpServerManager {
     pub fn new() -> Self {
         Self {
             servers: RwLock::new(HashMap::new()),
+            registry: None,
+        }
+    }
+
+    /// Create a manager that reserves stable per-worktree ports in `registry`
+    pub fn with_port_registry(registry: McpPortRegistry) -> Self {
+        Self {
+            servers: RwLock::new(HashMap::new()),
+            registry: Some(registry),
         }
     }
 
@@ -856,35 +1568,68 @@ impl McpServerManager {
             }
         }
 
+        let worktree_path = worktree_root.to_string_lossy().to_string();
+        let policy = Arc::new(StdRwLock::new(crate::mcp_policy::load_policy(&worktree_root)?));
+
+        // Prefer the worktree's registered port so configs survive restarts
+        let registered_port = match (&self.registry, preferred_port) {
+            (Some(registry), None) => {
+                if let Err(e) = registry.prune_stale() {
+                    tracing::warn!("Failed to prune MCP port registry: {}", e);
+                }
+                registry.reserve(&worktree_path, &project_name).ok()
+            }
+            _ => None,
+        };
+
         // Create the MCP server context
         let context = Arc::new(McpServerContext {
             worktree_root,
             worktree_id: worktree_id.clone(),
-            project_name,
+            project_name: project_name.clone(),
         });
 
         // Find an available port
-        let port = preferred_port.unwrap_or(3000);
+        let port = preferred_port.or(registered_port).unwrap_or(3000);
         let addr = SocketAddr::from(([127, 0, 0, 1], port));
 
         // Try to bind to the port (or find next available)
         let listener = Self::try_bind_port(addr, port).await?;
         let actual_port = listener.local_addr().unwrap().port();
 
+        if let Some(registry) = &self.registry {
+            if let Err(e) = registry.mark_running(&worktree_path, &project_name, actual_port) {
+                tracing::warn!("Failed to update MCP port registry: {}", e);
+            }
+        }
+
         let cancel_token = CancellationToken::new();
         let cancel_clone = cancel_token.clone();
+        let sessions = Arc::new(McpSessionStore::default());
+        let metrics = Arc::new(McpMetrics::default());
 
         // Build the router
         let app = Router::new()
             .route("/health", get(health_check))
             .route("/sse", get(handle_sse))
-            .route("/mcp", post(handle_mcp_request))
-            .with_state(context)
+            .route(
+                "/mcp",
+                post(handle_mcp_request)
+                    .get(handle_mcp_stream)
+                    .delete(handle_mcp_delete),
+            )
+            .with_state(McpHttpState {
+                context,
+                sessions: sessions.clone(),
+                metrics: metrics.clone(),
+                policy: policy.clone(),
+            })
             .layer(
                 tower_http::cors::CorsLayer::new()
                     .allow_origin(tower_http::cors::Any)
                     .allow_methods(tower_http::cors::Any)
-                    .allow_headers(tower_http::cors::Any),
+                    .allow_headers(tower_http::cors::Any)
+                    .expose_headers([HeaderName::from_static(SESSION_HEADER)]),
             );
 
         // Spawn the server task
@@ -906,6 +1651,11 @@ impl McpServerManager {
                     cancel_token,
                     port: actual_port,
                     handle,
+                    sessions,
+                    metrics,
+                    policy,
+                    worktree_path,
+                    project_name,
                 },
             );
         }
@@ -937,6 +1687,11 @@ impl McpServerManager {
         };
 
         if let Some(server) = server {
+            if let Some(registry) = &self.registry {
+                if let Err(e) = registry.mark_stopped(&server.worktree_path) {
+                    tracing::warn!("Failed to update MCP port registry: {}", e);
+                }
+            }
             server.cancel_token.cancel();
             // Wait for the server to shut down gracefully
             let _ = tokio::time::timeout(std::time::Duration::from_secs(5), server.handle).await;
@@ -957,6 +1712,59 @@ impl McpServerManager {
         let servers = self.servers.read().await;
         servers.contains_key(worktree_id)
     }
+
+    /// All running servers (across projects), sorted by port
+    pub async fn list_running(&self) -> Vec<RunningServerInfo> {
+        let servers = self.servers.read().await;
+        let mut running: Vec<RunningServerInfo> = servers
+            .iter()
+            .map(|(worktree_id, s)| RunningServerInfo {
+                worktree_id: worktree_id.clone(),
+                worktree_path: s.worktree_path.clone(),
+                project_name: s.project_name.clone(),
+                port: s.port,
+            })
+            .collect();
+        running.sort_by_key(|s| s.port);
+        running
+    }
+
+    /// Per-tool metrics for a worktree's server (empty if not running)
+    pub async fn get_metrics(&self, worktree_id: &str) -> Vec<McpToolMetricsData> {
+        let servers = self.servers.read().await;
+        servers
+            .get(worktree_id)
+            .map(|s| s.metrics.snapshot())
+            .unwrap_or_default()
+    }
+
+    /// Replace a running server's tool policy and tell connected clients the
+    /// tool list changed. Returns false if the server is not running.
+    pub async fn set_policy(&self, worktree_id: &str, policy: McpPolicy) -> bool {
+        {
+            let servers = self.servers.read().await;
+            let Some(server) = servers.get(worktree_id) else {
+                return false;
+            };
+            *server.policy.write().unwrap_or_else(|e| e.into_inner()) = policy;
+        }
+        self.notify(worktree_id, "notifications/tools/list_changed", serde_json::json!({}))
+            .await;
+        true
+    }
+
+    /// Send a server-initiated notification to all streamable HTTP sessions
+    /// of a worktree's server. Returns the number of open streams reached.
+    pub async fn notify(&self, worktree_id: &str, method: &str, params: serde_json::Value) -> usize {
+        let sessions = {
+            let servers = self.servers.read().await;
+            servers.get(worktree_id).map(|s| s.sessions.clone())
+        };
+        match sessions {
+            Some(sessions) => sessions.broadcast(method, params).await,
+            None => 0,
+        }
+    }
 }
 
 // ============================================================================
@@ -985,7 +1793,7 @@ mod tests {
     #[test]
     fn test_available_tools() {
         let tools = get_available_tools();
-        assert_eq!(tools.len(), 8); // 4 base tools + 3 ReviewGate tools + 1 A2UI tool
+        assert_eq!(tools.len(), 16); // 4 base tools + 3 ReviewGate tools + 4 Docker tools + 4 spec-kit tools + 1 A2UI tool
 
         let tool_names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
         // Base tools
@@ -997,6 +1805,16 @@ mod tests {
         assert!(tool_names.contains(&"submit_for_review"));
         assert!(tool_names.contains(&"get_review_feedback"));
         assert!(tool_names.contains(&"update_review_content"));
+        // Docker tools
+        assert!(tool_names.contains(&"rstn_docker_list"));
+        assert!(tool_names.contains(&"rstn_docker_start"));
+        assert!(tool_names.contains(&"rstn_docker_stop"));
+        assert!(tool_names.contains(&"rstn_docker_logs"));
+        // Spec-kit tools
+        assert!(tool_names.contains(&"rstn_get_spec"));
+        assert!(tool_names.contains(&"rstn_get_tasks"));
+        assert!(tool_names.contains(&"rstn_mark_task_done"));
+        assert!(tool_names.contains(&"rstn_report_progress"));
         // A2UI tool
         assert!(tool_names.contains(&"render_ui"));
     }
@@ -1128,4 +1946,271 @@ mod tests {
         assert!(required.contains(&serde_json::json!("session_id")));
         assert!(required.contains(&serde_json::json!("content")));
     }
+
+    // ========================================================================
+    // Docker Tool Tests
+    // ========================================================================
+
+    #[test]
+    fn test_docker_tool_schemas() {
+        let tools = get_available_tools();
+
+        for name in ["rstn_docker_start", "rstn_docker_stop", "rstn_docker_logs"] {
+            let tool = tools.iter().find(|t| t.name == name).unwrap();
+            let required = tool.input_schema.get("required").unwrap().as_array().unwrap();
+            assert_eq!(required, &vec![serde_json::json!("service_id")], "{}", name);
+        }
+
+        let list_tool = tools.iter().find(|t| t.name == "rstn_docker_list").unwrap();
+        assert!(list_tool.input_schema.get("required").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_docker_tool_requires_service_id() {
+        let dir = tempdir().unwrap();
+        let context = McpServerContext {
+            worktree_root: dir.path().to_path_buf(),
+            worktree_id: "test-worktree".to_string(),
+            project_name: "test-project".to_string(),
+        };
+
+        let result = context
+            .execute_docker_tool("rstn_docker_stop", &serde_json::json!({}))
+            .await;
+        assert_eq!(result.unwrap_err(), "Missing 'service_id' parameter");
+    }
+
+    #[tokio::test]
+    async fn test_spec_kit_tools() {
+        let dir = tempdir().unwrap();
+        for feature in ["specs/001-export", "specs/002-import"] {
+            std::fs::create_dir_all(dir.path().join(feature)).unwrap();
+        }
+        std::fs::write(dir.path().join("specs/001-export/spec.md"), "# Export").unwrap();
+        std::fs::write(
+            dir.path().join("specs/001-export/tasks.md"),
+            "## Setup\n- [ ] T001 Create module (src/export.rs)\n- [ ] T002 Add tests\n",
+        )
+        .unwrap();
+        let context = McpServerContext {
+            worktree_root: dir.path().to_path_buf(),
+            worktree_id: "test-worktree".to_string(),
+            project_name: "test-project".to_string(),
+        };
+        let text_json = |result: serde_json::Value| -> serde_json::Value {
+            serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap()
+        };
+
+        // Without a feature the newest one is used
+        let spec = text_json(context.execute_tool("rstn_get_spec", &serde_json::json!({})).await.unwrap());
+        assert_eq!(spec["feature"], "002-import");
+        assert!(spec["spec"].is_null());
+
+        let spec = text_json(
+            context
+                .execute_tool("rstn_get_spec", &serde_json::json!({ "feature": "export" }))
+                .await
+                .unwrap(),
+        );
+        assert_eq!(spec["spec"], "# Export");
+        assert_eq!(spec["tasks_path"], "specs/001-export/tasks.md");
+
+        let tasks = text_json(
+            context
+                .execute_tool("rstn_mark_task_done", &serde_json::json!({ "feature": "1", "task_id": "T001" }))
+                .await
+                .unwrap(),
+        );
+        assert_eq!(tasks["progress"]["done"], 1);
+        assert_eq!(tasks["tasks"][0]["files"][0], "src/export.rs");
+        assert!(std::fs::read_to_string(dir.path().join("specs/001-export/tasks.md"))
+            .unwrap()
+            .contains("- [x] T001"));
+
+        let missing = context
+            .execute_tool("rstn_mark_task_done", &serde_json::json!({ "feature": "1" }))
+            .await;
+        assert_eq!(missing.unwrap_err(), "Missing 'task_id' parameter");
+        let unknown = context
+            .execute_tool("rstn_get_tasks", &serde_json::json!({ "feature": "009-nope" }))
+            .await;
+        assert_eq!(unknown.unwrap_err(), "Feature not found under specs/: 009-nope");
+        let empty = context
+            .execute_tool("rstn_report_progress", &serde_json::json!({ "message": "  " }))
+            .await;
+        assert_eq!(empty.unwrap_err(), "Missing 'message' parameter");
+    }
+
+    #[test]
+    fn test_negotiate_protocol_version() {
+        let newer = serde_json::json!({ "protocolVersion": "2025-03-26" });
+        assert_eq!(negotiate_protocol_version(&newer), "2025-03-26");
+
+        let unknown = serde_json::json!({ "protocolVersion": "1999-01-01" });
+        assert_eq!(negotiate_protocol_version(&unknown), "2024-11-05");
+        assert_eq!(negotiate_protocol_version(&serde_json::json!({})), "2024-11-05");
+    }
+
+    #[test]
+    fn test_accepts_event_stream() {
+        let mut headers = HeaderMap::new();
+        assert!(!accepts_event_stream(&headers));
+
+        headers.insert(header::ACCEPT, HeaderValue::from_static("application/json, text/event-stream"));
+        assert!(accepts_event_stream(&headers));
+    }
+
+    #[tokio::test]
+    async fn test_session_store_broadcast() {
+        let store = McpSessionStore::default();
+        let id = store.create().await;
+        assert!(store.contains(&id).await);
+
+        let mut rx = store.subscribe(&id).await.unwrap();
+        assert_eq!(store.broadcast("notifications/tools/list_changed", serde_json::json!({})).await, 1);
+        let message = rx.recv().await.unwrap();
+        assert_eq!(message["method"], "notifications/tools/list_changed");
+
+        assert!(store.remove(&id).await);
+        assert!(!store.remove(&id).await);
+        assert!(store.subscribe(&id).await.is_none());
+    }
+
+    /// Send a raw HTTP/1.1 request and return the response head and body
+    async fn http_request(port: u16, method: &str, headers: &[(&str, &str)], body: &str) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut stream = tokio::net::TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+        let mut request = format!(
+            "{} /mcp HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Type: application/json\r\nContent-Length: {}\r\n",
+            method,
+            body.len()
+        );
+        for (name, value) in headers {
+            request.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        request.push_str("\r\n");
+        request.push_str(body);
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_streamable_http_session_lifecycle() {
+        let dir = tempdir().unwrap();
+        let manager = McpServerManager::new();
+        let port = manager
+            .start_server("wt".to_string(), dir.path().to_path_buf(), "test".to_string(), Some(0))
+            .await
+            .unwrap();
+
+        let accept = ("Accept", "application/json, text/event-stream");
+        let init = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-03-26"}}"#;
+        let response = http_request(port, "POST", &[accept], init).await;
+        assert!(response.contains("2025-03-26"));
+        let session = response
+            .lines()
+            .find_map(|l| l.strip_prefix("mcp-session-id: "))
+            .expect("session header")
+            .trim()
+            .to_string();
+
+        // Notifications are accepted without a body
+        let initialized = r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#;
+        let response = http_request(port, "POST", &[accept, ("Mcp-Session-Id", &session)], initialized).await;
+        assert!(response.starts_with("HTTP/1.1 202"));
+
+        // Plain JSON clients keep working without a session
+        let list = r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#;
+        let response = http_request(port, "POST", &[], list).await;
+        assert!(response.contains("rstn_docker_list"));
+
+        let response = http_request(port, "DELETE", &[("Mcp-Session-Id", &session)], "").await;
+        assert!(response.starts_with("HTTP/1.1 200"));
+
+        let response = http_request(port, "POST", &[accept, ("Mcp-Session-Id", &session)], list).await;
+        assert!(response.starts_with("HTTP/1.1 404"));
+
+        manager.stop_server("wt").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_policy_hides_and_refuses_tools() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".rstn")).unwrap();
+        std::fs::write(crate::mcp_policy::policy_path(dir.path()), r#"deny = ["rstn_docker_*"]"#).unwrap();
+
+        let manager = McpServerManager::new();
+        let port = manager
+            .start_server("wt".to_string(), dir.path().to_path_buf(), "test".to_string(), Some(0))
+            .await
+            .unwrap();
+
+        let list = r#"{"jsonrpc":"2.0","id":1,"method":"tools/list"}"#;
+        let response = http_request(port, "POST", &[], list).await;
+        assert!(response.contains("read_file"));
+        assert!(!response.contains("rstn_docker_list"));
+
+        let call = r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"rstn_docker_list"}}"#;
+        let response = http_request(port, "POST", &[], call).await;
+        assert!(response.contains("disabled by this worktree's MCP policy"));
+        assert!(manager.get_metrics("wt").await.is_empty());
+
+        assert!(manager.set_policy("wt", McpPolicy::default()).await);
+        let response = http_request(port, "POST", &[], list).await;
+        assert!(response.contains("rstn_docker_list"));
+
+        manager.stop_server("wt").await.unwrap();
+    }
+
+    #[test]
+    fn test_tool_metrics_percentiles() {
+        let metrics = McpMetrics::default();
+        for ms in 1..=100 {
+            metrics.record("read_file", Duration::from_millis(ms), ms % 10 == 0);
+        }
+        metrics.record("list_directory", Duration::from_millis(5), false);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot[0].tool_name, "list_directory");
+
+        let read_file = &snapshot[1];
+        assert_eq!(read_file.call_count, 100);
+        assert_eq!(read_file.error_count, 10);
+        assert!((read_file.p50_ms - 50.0).abs() < 0.5);
+        assert!((read_file.p95_ms - 95.0).abs() < 0.5);
+        assert!(read_file.last_called_at.is_some());
+
+        assert_eq!(percentile(&[], 95.0), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_manager_uses_port_registry() {
+        let dir = tempdir().unwrap();
+        let registry = McpPortRegistry::new(dir.path().join("mcp-ports.json"));
+        let manager = McpServerManager::with_port_registry(registry.clone());
+
+        let port = manager
+            .start_server("wt-1".to_string(), dir.path().to_path_buf(), "proj".to_string(), None)
+            .await
+            .unwrap();
+
+        let worktree_path = dir.path().to_string_lossy().to_string();
+        let registration = registry.load().registrations[&worktree_path].clone();
+        assert_eq!(registration.port, port);
+        assert_eq!(registration.pid, Some(std::process::id()));
+
+        let running = manager.list_running().await;
+        assert_eq!(running.len(), 1);
+        assert_eq!(running[0].worktree_path, worktree_path);
+        assert_eq!(running[0].port, port);
+
+        manager.stop_server("wt-1").await.unwrap();
+        assert!(registry.load().registrations[&worktree_path].pid.is_none());
+        assert!(manager.list_running().await.is_empty());
+    }
 }
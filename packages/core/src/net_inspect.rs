@@ -0,0 +1,302 @@
+//! Identify host processes (outside Docker) listening on a TCP port.
+//!
+//! Docker port conflicts are found through the daemon; anything else holding
+//! a port (a dev server, a Homebrew Postgres) is looked up here:
+//!
+//! - Linux: listening sockets from `/proc/net/tcp{,6}`, matched to processes
+//!   through their `/proc/<pid>/fd` socket inodes
+//! - macOS and other Unixes: `lsof`
+//! - Windows: `netstat -ano`
+//!
+//! Process details (name, executable, user) come from `sysinfo`. Processes of
+//! other users may not be identifiable without elevated permissions; the port
+//! still reports as in use.
+
+use std::net::TcpListener;
+use std::time::{Duration, Instant};
+
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, Signal, System, UpdateKind, Users};
+
+use crate::state::PortProcessInfo;
+
+/// How long a terminated process gets to release its port before it is killed
+const TERM_GRACE: Duration = Duration::from_secs(3);
+
+/// How long a killed process gets to release its port
+const KILL_GRACE: Duration = Duration::from_secs(2);
+
+/// Whether something on this host is listening on the port
+pub fn port_in_use(port: u16) -> bool {
+    ["0.0.0.0", "127.0.0.1"]
+        .iter()
+        .any(|host| matches!(TcpListener::bind((*host, port)), Err(e) if e.kind() == std::io::ErrorKind::AddrInUse))
+}
+
+/// Processes listening on the port (empty when free or not identifiable)
+pub fn inspect_port(port: u16) -> Vec<PortProcessInfo> {
+    let mut pids = listening_pids(port);
+    pids.sort_unstable();
+    pids.dedup();
+    if pids.is_empty() {
+        return Vec::new();
+    }
+
+    let sys_pids: Vec<Pid> = pids.iter().map(|pid| Pid::from_u32(*pid)).collect();
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&sys_pids),
+        true,
+        ProcessRefreshKind::nothing()
+            .with_exe(UpdateKind::OnlyIfNotSet)
+            .with_cmd(UpdateKind::OnlyIfNotSet)
+            .with_user(UpdateKind::OnlyIfNotSet),
+    );
+    let users = Users::new_with_refreshed_list();
+
+    pids.into_iter()
+        .map(|pid| match system.process(Pid::from_u32(pid)) {
+            Some(process) => {
+                let command = process
+                    .cmd()
+                    .iter()
+                    .map(|arg| arg.to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                PortProcessInfo {
+                    pid,
+                    name: process.name().to_string_lossy().into_owned(),
+                    exe: process.exe().map(|p| p.to_string_lossy().into_owned()),
+                    user: process
+                        .user_id()
+                        .and_then(|uid| users.get_user_by_id(uid))
+                        .map(|user| user.name().to_string()),
+                    command: (!command.is_empty()).then_some(command),
+                }
+            }
+            None => PortProcessInfo {
+                pid,
+                name: String::new(),
+                exe: None,
+                user: None,
+                command: None,
+            },
+        })
+        .collect()
+}
+
+/// Stop the process listening on the port: terminate, then kill if it does
+/// not let go of the port in time.
+///
+/// The pid must still be one of the port's listeners, so a recycled pid is
+/// never signalled.
+pub fn kill_port_process(port: u16, pid: u32) -> Result<(), String> {
+    if pid == std::process::id() {
+        return Err("Refusing to kill rstn itself".to_string());
+    }
+    if !listening_pids(port).contains(&pid) {
+        return Err(format!("Process {} is no longer listening on port {}", pid, port));
+    }
+
+    let sys_pid = Pid::from_u32(pid);
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::Some(&[sys_pid]), true, ProcessRefreshKind::nothing());
+    let process = system
+        .process(sys_pid)
+        .ok_or_else(|| format!("Process {} not found", pid))?;
+
+    tracing::info!("Stopping process {} ({:?}) on port {}", pid, process.name(), port);
+    // Windows has no SIGTERM; go straight to kill there
+    let terminated = process.kill_with(Signal::Term).unwrap_or(false);
+    if terminated && wait_for_port_release(port, TERM_GRACE) {
+        return Ok(());
+    }
+
+    if !process.kill() {
+        return Err(format!("Failed to kill process {} (permission denied?)", pid));
+    }
+    if wait_for_port_release(port, KILL_GRACE) {
+        Ok(())
+    } else {
+        Err(format!("Port {} is still in use after killing process {}", port, pid))
+    }
+}
+
+fn wait_for_port_release(port: u16, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        if !port_in_use(port) {
+            return true;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    !port_in_use(port)
+}
+
+#[cfg(target_os = "linux")]
+fn listening_pids(port: u16) -> Vec<u32> {
+    let mut inodes = Vec::new();
+    for table in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        if let Ok(content) = std::fs::read_to_string(table) {
+            inodes.extend(parse_proc_net_tcp(&content, port));
+        }
+    }
+    if inodes.is_empty() {
+        return Vec::new();
+    }
+
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    let mut pids = Vec::new();
+    for entry in entries.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse::<u32>().ok()) else {
+            continue;
+        };
+        // Unreadable for processes of other users
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        let holds_socket = fds.flatten().any(|fd| {
+            std::fs::read_link(fd.path())
+                .ok()
+                .and_then(|target| socket_inode(&target.to_string_lossy()))
+                .is_some_and(|inode| inodes.contains(&inode))
+        });
+        if holds_socket {
+            pids.push(pid);
+        }
+    }
+    pids
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn listening_pids(port: u16) -> Vec<u32> {
+    let output = std::process::Command::new("lsof")
+        .args(["-nP", &format!("-iTCP:{}", port), "-sTCP:LISTEN", "-t"])
+        .output();
+    match output {
+        Ok(output) => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.trim().parse().ok())
+            .collect(),
+        Err(e) => {
+            tracing::warn!("lsof unavailable: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(windows)]
+fn listening_pids(port: u16) -> Vec<u32> {
+    match std::process::Command::new("netstat").args(["-ano", "-p", "TCP"]).output() {
+        Ok(output) => parse_netstat(&String::from_utf8_lossy(&output.stdout), port),
+        Err(e) => {
+            tracing::warn!("netstat unavailable: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Socket inodes listening on the port in a `/proc/net/tcp` table
+#[cfg(any(target_os = "linux", test))]
+fn parse_proc_net_tcp(content: &str, port: u16) -> Vec<u64> {
+    const LISTEN: &str = "0A";
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let local_port = fields.get(1)?.rsplit(':').next()?;
+            let listening = *fields.get(3)? == LISTEN;
+            (listening && u16::from_str_radix(local_port, 16).ok()? == port)
+                .then(|| fields.get(9)?.parse().ok())
+                .flatten()
+        })
+        .collect()
+}
+
+/// Inode of a `socket:[12345]` fd link
+#[cfg(any(target_os = "linux", test))]
+fn socket_inode(link: &str) -> Option<u64> {
+    link.strip_prefix("socket:[")?.strip_suffix(']')?.parse().ok()
+}
+
+/// Pids listening on the port in `netstat -ano` output
+#[cfg(any(windows, test))]
+fn parse_netstat(output: &str, port: u16) -> Vec<u32> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() != 5 || fields[0] != "TCP" || fields[3] != "LISTENING" {
+                return None;
+            }
+            let local_port: u16 = fields[1].rsplit(':').next()?.parse().ok()?;
+            (local_port == port).then(|| fields[4].parse().ok()).flatten()
+        })
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_net_tcp() {
+        let content = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:1538 00000000:0000 0A 00000000:00000000 00:00000000 00000000   501        0 41234 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 55321 1 0000000000000000 100 0 0 10 0
+   2: 0100007F:1538 0100007F:D2A4 01 00000000:00000000 00:00000000 00000000   501        0 0 1 0000000000000000 20 4 30 10 -1
+";
+        // 0x1538 = 5432, only the LISTEN row counts
+        assert_eq!(parse_proc_net_tcp(content, 5432), vec![41234]);
+        assert_eq!(parse_proc_net_tcp(content, 8080), vec![55321]);
+        assert!(parse_proc_net_tcp(content, 3000).is_empty());
+
+        assert_eq!(socket_inode("socket:[41234]"), Some(41234));
+        assert_eq!(socket_inode("/dev/null"), None);
+    }
+
+    #[test]
+    fn test_parse_netstat() {
+        let output = "
+Active Connections
+
+  Proto  Local Address          Foreign Address        State           PID
+  TCP    0.0.0.0:135            0.0.0.0:0              LISTENING       1004
+  TCP    0.0.0.0:5432           0.0.0.0:0              LISTENING       6120
+  TCP    [::]:5432              [::]:0                 LISTENING       6120
+  TCP    127.0.0.1:5432         127.0.0.1:53211        ESTABLISHED     6120
+";
+        assert_eq!(parse_netstat(output, 5432), vec![6120, 6120]);
+        assert!(parse_netstat(output, 8080).is_empty());
+    }
+
+    #[test]
+    fn test_inspect_port_finds_listener() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        assert!(port_in_use(port));
+        if cfg!(target_os = "linux") {
+            let processes = inspect_port(port);
+            let own = processes.iter().find(|p| p.pid == std::process::id());
+            assert!(own.is_some_and(|p| !p.name.is_empty()));
+        }
+
+        drop(listener);
+        assert!(!port_in_use(port));
+    }
+
+    #[test]
+    fn test_kill_port_process_refuses_self() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(kill_port_process(port, std::process::id()).is_err());
+        assert!(kill_port_process(port, u32::MAX).is_err());
+    }
+}
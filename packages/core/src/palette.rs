@@ -0,0 +1,529 @@
+//! Command palette action registry.
+//!
+//! The palette lists what the user can do right now: a fixed set of commands
+//! (views, theme, git, Docker, ...) plus entries generated from state
+//! (switch project/worktree, open recent, run or cancel tasks). Each entry
+//! carries the exact `Action` JSON to dispatch, so the frontend never has
+//! to hard-code action payloads.
+
+use napi_derive::napi;
+
+use crate::actions::{Action, ActiveViewData};
+use crate::app_state::{AppState, Theme};
+use crate::task_queue::TaskRunStatus;
+
+/// A palette entry, with enablement evaluated against the current state
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaletteAction {
+    /// Stable ID (e.g. "view.tasks", "task.just:build")
+    pub id: String,
+    /// Human-readable label
+    pub label: String,
+    /// Group heading (e.g. "Views", "Git")
+    pub category: String,
+    /// Keyboard hint from the keybinding settings (e.g. "CmdOrCtrl+1")
+    pub keybinding: Option<String>,
+    pub enabled: bool,
+    /// Why the entry is disabled
+    pub disabled_reason: Option<String>,
+    /// Action JSON for `state_dispatch`
+    pub action: String,
+}
+
+/// Enablement predicate: Err carries the reason shown when disabled
+type Predicate = fn(&AppState) -> Result<(), &'static str>;
+
+/// A fixed palette command
+struct PaletteEntry {
+    id: &'static str,
+    label: &'static str,
+    category: &'static str,
+    enabled: Predicate,
+    action: fn(&AppState) -> Action,
+}
+
+/// Registry of palette commands
+pub struct ActionRegistry {
+    entries: Vec<PaletteEntry>,
+}
+
+impl Default for ActionRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ActionRegistry {
+    pub fn new() -> Self {
+        Self { entries: builtin_entries() }
+    }
+
+    /// Every palette entry for `state`: fixed commands first, then
+    /// entries generated from the open projects, worktrees and tasks
+    pub fn list(&self, state: &AppState) -> Vec<PaletteAction> {
+        let mut actions: Vec<PaletteAction> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let enabled = (entry.enabled)(state);
+                palette_action(
+                    entry.id.to_string(),
+                    entry.label.to_string(),
+                    entry.category,
+                    state.global_settings.keybindings.get(entry.id),
+                    enabled,
+                    &(entry.action)(state),
+                )
+            })
+            .collect();
+        actions.extend(project_actions(state));
+        actions.extend(worktree_actions(state));
+        actions.extend(task_actions(state));
+        actions
+    }
+}
+
+fn palette_action(
+    id: String,
+    label: String,
+    category: &str,
+    keybinding: Option<&str>,
+    enabled: Result<(), &str>,
+    action: &Action,
+) -> PaletteAction {
+    PaletteAction {
+        id,
+        label,
+        category: category.to_string(),
+        keybinding: keybinding.map(str::to_string),
+        enabled: enabled.is_ok(),
+        disabled_reason: enabled.err().map(str::to_string),
+        action: serde_json::to_string(action).expect("actions serialize to JSON"),
+    }
+}
+
+// ============================================================================
+// Predicates
+// ============================================================================
+
+fn always(_: &AppState) -> Result<(), &'static str> {
+    Ok(())
+}
+
+fn has_project(state: &AppState) -> Result<(), &'static str> {
+    state.active_project().map(|_| ()).ok_or("No project is open")
+}
+
+fn has_worktree(state: &AppState) -> Result<(), &'static str> {
+    state
+        .active_project()
+        .and_then(|p| p.active_worktree())
+        .map(|_| ())
+        .ok_or("No project is open")
+}
+
+fn git_idle(state: &AppState) -> Result<(), &'static str> {
+    has_worktree(state)?;
+    let busy = state
+        .active_project()
+        .and_then(|p| p.active_worktree())
+        .is_some_and(|w| w.explorer.git_busy);
+    if busy {
+        Err("A git operation is in progress")
+    } else {
+        Ok(())
+    }
+}
+
+fn docker_available(state: &AppState) -> Result<(), &'static str> {
+    match state.docker.docker_available {
+        Some(true) => Ok(()),
+        _ => Err("Docker is not available"),
+    }
+}
+
+fn has_search(state: &AppState) -> Result<(), &'static str> {
+    let searching = state
+        .active_project()
+        .and_then(|p| p.active_worktree())
+        .is_some_and(|w| !w.search.query.is_empty());
+    if searching {
+        Ok(())
+    } else {
+        Err("No active search")
+    }
+}
+
+fn has_unread_notifications(state: &AppState) -> Result<(), &'static str> {
+    if state.notifications.iter().any(|n| !n.read) {
+        Ok(())
+    } else {
+        Err("No unread notifications")
+    }
+}
+
+fn has_notifications(state: &AppState) -> Result<(), &'static str> {
+    if state.notifications.is_empty() {
+        Err("No notifications")
+    } else {
+        Ok(())
+    }
+}
+
+fn theme_is_not(state: &AppState, theme: Theme) -> Result<(), &'static str> {
+    if state.global_settings.theme == theme {
+        Err("Already active")
+    } else {
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Fixed commands
+// ============================================================================
+
+macro_rules! view_entry {
+    ($id:literal, $label:literal, $view:ident, $enabled:expr) => {
+        PaletteEntry {
+            id: $id,
+            label: $label,
+            category: "Views",
+            enabled: $enabled,
+            action: |_| Action::SetActiveView { view: ActiveViewData::$view },
+        }
+    };
+}
+
+fn builtin_entries() -> Vec<PaletteEntry> {
+    vec![
+        // Views (Docker is global; the rest need an open project)
+        view_entry!("view.workflows", "Workflows", Workflows, has_worktree),
+        view_entry!("view.tasks", "Tasks", Tasks, has_worktree),
+        view_entry!("view.explorer", "Explorer", Explorer, has_worktree),
+        view_entry!("view.terminal", "Terminal", Terminal, has_worktree),
+        view_entry!("view.chat", "Chat", Chat, has_worktree),
+        view_entry!("view.dockers", "Docker", Dockers, always),
+        view_entry!("view.env", "Environment", Env, has_project),
+        view_entry!("view.mcp", "rstn-mcp Integration", Mcp, has_worktree),
+        view_entry!("view.claude_code", "Claude Code", ClaudeCode, has_worktree),
+        view_entry!("view.settings", "Settings", Settings, always),
+        // Theme
+        PaletteEntry {
+            id: "theme.system",
+            label: "System Theme",
+            category: "Theme",
+            enabled: |s| theme_is_not(s, Theme::System),
+            action: |_| Action::SetTheme { theme: Theme::System },
+        },
+        PaletteEntry {
+            id: "theme.light",
+            label: "Light Theme",
+            category: "Theme",
+            enabled: |s| theme_is_not(s, Theme::Light),
+            action: |_| Action::SetTheme { theme: Theme::Light },
+        },
+        PaletteEntry {
+            id: "theme.dark",
+            label: "Dark Theme",
+            category: "Theme",
+            enabled: |s| theme_is_not(s, Theme::Dark),
+            action: |_| Action::SetTheme { theme: Theme::Dark },
+        },
+        // Project
+        PaletteEntry {
+            id: "project.close",
+            label: "Close Project",
+            category: "Project",
+            enabled: has_project,
+            action: |s| Action::CloseProject { index: s.active_project_index },
+        },
+        PaletteEntry {
+            id: "project.refresh_worktrees",
+            label: "Refresh Worktrees",
+            category: "Project",
+            enabled: has_project,
+            action: |_| Action::RefreshWorktrees,
+        },
+        // Git
+        PaletteEntry {
+            id: "git.pull",
+            label: "Git: Pull",
+            category: "Git",
+            enabled: git_idle,
+            action: |_| Action::GitPull,
+        },
+        PaletteEntry {
+            id: "git.push",
+            label: "Git: Push",
+            category: "Git",
+            enabled: git_idle,
+            action: |_| Action::GitPush,
+        },
+        // Tasks
+        PaletteEntry {
+            id: "tasks.refresh",
+            label: "Reload Tasks",
+            category: "Tasks",
+            enabled: has_worktree,
+            action: |_| Action::RefreshJustfile,
+        },
+        // Docker
+        PaletteEntry {
+            id: "docker.refresh",
+            label: "Refresh Docker Services",
+            category: "Docker",
+            enabled: docker_available,
+            action: |_| Action::RefreshDockerServices,
+        },
+        // Explorer
+        PaletteEntry {
+            id: "explorer.clear_search",
+            label: "Clear Search Results",
+            category: "Explorer",
+            enabled: has_search,
+            action: |_| Action::ClearSearch,
+        },
+        // Chat
+        PaletteEntry {
+            id: "chat.clear",
+            label: "Clear Chat",
+            category: "Chat",
+            enabled: has_worktree,
+            action: |_| Action::ClearChat,
+        },
+        // Notifications
+        PaletteEntry {
+            id: "notifications.mark_all_read",
+            label: "Mark All Notifications Read",
+            category: "Notifications",
+            enabled: has_unread_notifications,
+            action: |_| Action::MarkAllNotificationsRead,
+        },
+        PaletteEntry {
+            id: "notifications.clear",
+            label: "Clear Notifications",
+            category: "Notifications",
+            enabled: has_notifications,
+            action: |_| Action::ClearNotifications,
+        },
+    ]
+}
+
+// ============================================================================
+// Entries generated from state
+// ============================================================================
+
+/// Switch to an open project, or open a recent one
+fn project_actions(state: &AppState) -> Vec<PaletteAction> {
+    let mut actions: Vec<PaletteAction> = state
+        .projects
+        .iter()
+        .enumerate()
+        .map(|(index, project)| {
+            let enabled = if index == state.active_project_index {
+                Err("Already active")
+            } else {
+                Ok(())
+            };
+            palette_action(
+                format!("project.switch.{}", project.id),
+                format!("Switch to Project: {}", project.name),
+                "Projects",
+                None,
+                enabled,
+                &Action::SwitchProject { index },
+            )
+        })
+        .collect();
+
+    actions.extend(
+        state
+            .recent_projects
+            .iter()
+            .filter(|recent| !state.projects.iter().any(|p| p.path == recent.path))
+            .map(|recent| {
+                palette_action(
+                    format!("project.open.{}", recent.path),
+                    format!("Open Recent: {}", recent.name),
+                    "Projects",
+                    None,
+                    Ok(()),
+                    &Action::OpenProject { path: recent.path.clone() },
+                )
+            }),
+    );
+    actions
+}
+
+/// Switch to another worktree of the active project
+fn worktree_actions(state: &AppState) -> Vec<PaletteAction> {
+    let Some(project) = state.active_project() else {
+        return Vec::new();
+    };
+    project
+        .worktrees
+        .iter()
+        .enumerate()
+        .map(|(index, worktree)| {
+            let enabled = if index == project.active_worktree_index {
+                Err("Already active")
+            } else {
+                Ok(())
+            };
+            palette_action(
+                format!("worktree.switch.{}", worktree.id),
+                format!("Switch to Worktree: {}", worktree.branch),
+                "Worktrees",
+                None,
+                enabled,
+                &Action::SwitchWorktree { index },
+            )
+        })
+        .collect()
+}
+
+/// Run any task of the active worktree, or cancel a queued/running one
+fn task_actions(state: &AppState) -> Vec<PaletteAction> {
+    let Some(worktree) = state.active_project().and_then(|p| p.active_worktree()) else {
+        return Vec::new();
+    };
+
+    let mut actions: Vec<PaletteAction> = worktree
+        .tasks
+        .tasks
+        .iter()
+        .map(|task| {
+            palette_action(
+                format!("task.run.{}", task.id),
+                format!("Run Task: {} {}", task.provider.program(), task.name),
+                "Run Task",
+                None,
+                Ok(()),
+                &Action::RunTask {
+                    provider: task.provider,
+                    name: task.name.clone(),
+                    args: Vec::new(),
+                    cwd: worktree.path.clone(),
+                },
+            )
+        })
+        .collect();
+
+    actions.extend(
+        worktree
+            .tasks
+            .runs
+            .iter()
+            .filter(|run| matches!(run.status, TaskRunStatus::Queued | TaskRunStatus::Running))
+            .map(|run| {
+                palette_action(
+                    format!("task.cancel.{}", run.id),
+                    format!("Cancel Task: {}", run.command),
+                    "Run Task",
+                    None,
+                    Ok(()),
+                    &Action::CancelTask { task_id: run.id.clone() },
+                )
+            }),
+    );
+    actions
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reducer::reduce;
+    use crate::tasks::{TaskDescriptor, TaskProviderKind};
+
+    fn find<'a>(actions: &'a [PaletteAction], id: &str) -> &'a PaletteAction {
+        actions.iter().find(|a| a.id == id).unwrap_or_else(|| panic!("missing {}", id))
+    }
+
+    #[test]
+    fn test_actions_round_trip_and_ids_are_unique() {
+        let mut state = AppState::default();
+        reduce(&mut state, Action::OpenProject { path: "/test/project".to_string() });
+        let actions = ActionRegistry::new().list(&state);
+
+        for action in &actions {
+            assert!(
+                serde_json::from_str::<Action>(&action.action).is_ok(),
+                "{} does not dispatch: {}",
+                action.id,
+                action.action
+            );
+        }
+        let mut ids: Vec<&str> = actions.iter().map(|a| a.id.as_str()).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), actions.len());
+    }
+
+    #[test]
+    fn test_enablement_follows_state() {
+        let registry = ActionRegistry::new();
+        let mut state = AppState::default();
+
+        let actions = registry.list(&state);
+        let tasks = find(&actions, "view.tasks");
+        assert!(!tasks.enabled);
+        assert_eq!(tasks.disabled_reason.as_deref(), Some("No project is open"));
+        assert!(find(&actions, "view.dockers").enabled);
+        assert!(!find(&actions, "theme.system").enabled);
+        assert!(find(&actions, "theme.dark").enabled);
+        assert!(!find(&actions, "docker.refresh").enabled);
+
+        reduce(&mut state, Action::OpenProject { path: "/test/project".to_string() });
+        state.docker.docker_available = Some(true);
+        let actions = registry.list(&state);
+        assert!(find(&actions, "view.tasks").enabled);
+        assert!(find(&actions, "git.pull").enabled);
+        assert!(find(&actions, "docker.refresh").enabled);
+        assert_eq!(find(&actions, "view.tasks").keybinding.as_deref(), Some("CmdOrCtrl+2"));
+        assert_eq!(
+            find(&actions, "view.tasks").action,
+            r#"{"type":"SetActiveView","payload":{"view":"tasks"}}"#
+        );
+    }
+
+    #[test]
+    fn test_generated_entries() {
+        let mut state = AppState::default();
+        reduce(&mut state, Action::OpenProject { path: "/test/project".to_string() });
+        let worktree_path = state.active_project().unwrap().active_worktree().unwrap().path.clone();
+        reduce(&mut state, Action::SetTasks {
+            provider: None,
+            tasks: vec![TaskDescriptor {
+                id: "just:build".to_string(),
+                provider: TaskProviderKind::Just,
+                name: "build".to_string(),
+                description: None,
+                command: "cargo build".to_string(),
+                parameters: Vec::new(),
+            }],
+        });
+
+        let actions = ActionRegistry::new().list(&state);
+        let run = find(&actions, "task.run.just:build");
+        assert_eq!(run.label, "Run Task: just build");
+        assert_eq!(
+            serde_json::from_str::<Action>(&run.action).unwrap(),
+            Action::RunTask {
+                provider: TaskProviderKind::Just,
+                name: "build".to_string(),
+                args: Vec::new(),
+                cwd: worktree_path,
+            }
+        );
+
+        let project_id = state.projects[0].id.clone();
+        let switch = find(&actions, &format!("project.switch.{}", project_id));
+        assert!(!switch.enabled);
+        assert_eq!(switch.disabled_reason.as_deref(), Some("Already active"));
+    }
+}
@@ -1,11 +1,11 @@
 //! State persistence - save/load state to ~/.rstn/
 //!
 //! Handles:
-//! - Global state (recent_projects, global_settings)
+//! - Global state (recent_projects, global_settings, open workspace)
 //! - Per-project state (active_tab, etc.)
 //! - Schema versioning and migration
 
-use crate::app_state::{AppState, FeatureTab, GlobalSettings, ProjectState, RecentProject};
+use crate::app_state::{AppState, FeatureTab, GlobalSettings, PortConflictStrategy, ProjectState, RecentProject};
 use crate::migration::{MigrationManager, CURRENT_SCHEMA_VERSION};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -22,6 +22,19 @@ pub struct GlobalPersistedState {
     pub version: String,
     pub recent_projects: Vec<RecentProject>,
     pub global_settings: GlobalSettings,
+    /// Projects open at the time of saving (reopened on launch)
+    #[serde(default)]
+    pub workspace: WorkspaceSession,
+}
+
+/// The set of open projects, in tab order
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct WorkspaceSession {
+    /// Project paths
+    pub projects: Vec<String>,
+    /// Index of the focused project
+    #[serde(default)]
+    pub active_project: usize,
 }
 
 /// Default schema version for legacy data
@@ -37,6 +50,10 @@ impl GlobalPersistedState {
             version: state.version.clone(),
             recent_projects: state.recent_projects.clone(),
             global_settings: state.global_settings.clone(),
+            workspace: WorkspaceSession {
+                projects: state.projects.iter().map(|p| p.path.clone()).collect(),
+                active_project: state.active_project_index,
+            },
         }
     }
 
@@ -54,6 +71,24 @@ pub struct ProjectPersistedState {
     pub path: String,
     /// Last active tab
     pub active_tab: FeatureTab,
+    /// Docker port conflict strategy
+    #[serde(default)]
+    pub auto_resolve_ports: PortConflictStrategy,
+    /// Claude model override
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Review approval requires completed spec checklists
+    #[serde(default)]
+    pub require_checklists: bool,
+    /// Tests run with coverage after implementations
+    #[serde(default)]
+    pub collect_coverage: bool,
+    /// Build check re-runs on source changes
+    #[serde(default)]
+    pub check_build_on_save: bool,
+    /// Imported docker-compose files
+    #[serde(default)]
+    pub compose_files: Vec<String>,
 }
 
 impl ProjectPersistedState {
@@ -68,6 +103,12 @@ impl ProjectPersistedState {
         Self {
             path: project.path.clone(),
             active_tab,
+            auto_resolve_ports: project.env_config.auto_resolve_ports,
+            model: project.model.clone(),
+            require_checklists: project.require_checklists,
+            collect_coverage: project.collect_coverage,
+            check_build_on_save: project.check_build_on_save,
+            compose_files: project.compose_files.clone(),
         }
     }
 
@@ -79,6 +120,12 @@ impl ProjectPersistedState {
             if let Some(worktree) = project.active_worktree_mut() {
                 worktree.active_tab = self.active_tab;
             }
+            project.env_config.auto_resolve_ports = self.auto_resolve_ports;
+            project.model = self.model.clone();
+            project.require_checklists = self.require_checklists;
+            project.collect_coverage = self.collect_coverage;
+            project.check_build_on_save = self.check_build_on_save;
+            project.compose_files = self.compose_files.clone();
         }
     }
 }
@@ -285,7 +332,20 @@ mod tests {
             global_settings: GlobalSettings {
                 theme: Theme::Dark,
                 default_project_path: Some("/home/user".to_string()),
+                model: Some("sonnet".to_string()),
+                task_max_parallel: None,
+                implementation_timeout_secs: None,
+                desktop_notifications: Default::default(),
+                provider: Default::default(),
+                openai: Default::default(),
+                ollama: Default::default(),
+                git_hosting: Default::default(),
+                keybindings: Default::default(),
+                proxy: Default::default(),
+                active_profile: None,
+                guardrails: None,
             },
+            workspace: Default::default(),
         };
 
         let json = serde_json::to_string(&state).unwrap();
@@ -293,6 +353,20 @@ mod tests {
         assert_eq!(state, loaded);
     }
 
+    #[test]
+    fn test_global_persisted_state_omits_openai_key() {
+        let mut app_state = AppState::default();
+        app_state.global_settings.openai.api_key = Some("sk-live-123".to_string());
+
+        let json = serde_json::to_string(&GlobalPersistedState::from_app_state(&app_state)).unwrap();
+        assert!(!json.contains("sk-live-123"));
+
+        // Keys saved by older versions still load
+        let legacy = json.replace("\"openai\":{", "\"openai\":{\"api_key\":\"sk-old\",");
+        let loaded: GlobalPersistedState = serde_json::from_str(&legacy).unwrap();
+        assert_eq!(loaded.global_settings.openai.api_key.as_deref(), Some("sk-old"));
+    }
+
     #[test]
     fn test_global_persisted_state_legacy_without_schema_version() {
         // Test that legacy JSON without schema_version field defaults to 1
@@ -314,6 +388,12 @@ mod tests {
         let state = ProjectPersistedState {
             path: "/test/project".to_string(),
             active_tab: FeatureTab::Dockers,
+            auto_resolve_ports: PortConflictStrategy::AlwaysNext,
+            model: Some("opus".to_string()),
+            require_checklists: true,
+            collect_coverage: true,
+            check_build_on_save: true,
+            compose_files: vec!["/test/project/docker-compose.yml".to_string()],
         };
 
         let json = serde_json::to_string(&state).unwrap();
@@ -321,6 +401,15 @@ mod tests {
         assert_eq!(state, loaded);
     }
 
+    #[test]
+    fn test_project_persisted_state_missing_port_strategy() {
+        let json = r#"{"path": "/test/project", "active_tab": "dockers"}"#;
+        let loaded: ProjectPersistedState = serde_json::from_str(json).unwrap();
+        assert_eq!(loaded.auto_resolve_ports, PortConflictStrategy::Never);
+        assert_eq!(loaded.model, None);
+        assert!(loaded.compose_files.is_empty());
+    }
+
     #[test]
     fn test_global_persisted_from_app_state() {
         let mut app_state = AppState::default();
@@ -336,6 +425,23 @@ mod tests {
         assert_eq!(persisted.recent_projects.len(), 1);
     }
 
+    #[test]
+    fn test_global_persisted_captures_workspace() {
+        let mut app_state = AppState::default();
+        app_state.projects.push(ProjectState::new("/work/a".to_string()));
+        app_state.projects.push(ProjectState::new("/work/b".to_string()));
+        app_state.active_project_index = 1;
+
+        let persisted = GlobalPersistedState::from_app_state(&app_state);
+        assert_eq!(persisted.workspace.projects, vec!["/work/a".to_string(), "/work/b".to_string()]);
+        assert_eq!(persisted.workspace.active_project, 1);
+
+        // State saved before workspaces existed has none
+        let json = r#"{"version": "0.1.0", "recent_projects": [], "global_settings": {"theme": "system"}}"#;
+        let loaded: GlobalPersistedState = serde_json::from_str(json).unwrap();
+        assert_eq!(loaded.workspace, WorkspaceSession::default());
+    }
+
     #[test]
     fn test_global_persisted_apply_to() {
         let persisted = GlobalPersistedState {
@@ -349,7 +455,20 @@ mod tests {
             global_settings: GlobalSettings {
                 theme: Theme::Light,
                 default_project_path: None,
+                model: None,
+                task_max_parallel: None,
+                implementation_timeout_secs: None,
+                desktop_notifications: Default::default(),
+                provider: Default::default(),
+                openai: Default::default(),
+                ollama: Default::default(),
+                git_hosting: Default::default(),
+                keybindings: Default::default(),
+                proxy: Default::default(),
+                active_profile: None,
+                guardrails: None,
             },
+            workspace: Default::default(),
         };
 
         let mut app_state = AppState::default();
@@ -378,6 +497,12 @@ mod tests {
         let persisted = ProjectPersistedState {
             path: "/test/path".to_string(),
             active_tab: FeatureTab::Dockers,
+            auto_resolve_ports: PortConflictStrategy::Never,
+            model: None,
+            require_checklists: false,
+            collect_coverage: false,
+            check_build_on_save: false,
+            compose_files: vec![],
         };
 
         let mut project = ProjectState::new("/test/path".to_string());
@@ -399,6 +524,12 @@ mod tests {
         let persisted = ProjectPersistedState {
             path: "/other/path".to_string(),
             active_tab: FeatureTab::Dockers,
+            auto_resolve_ports: PortConflictStrategy::Never,
+            model: None,
+            require_checklists: false,
+            collect_coverage: false,
+            check_build_on_save: false,
+            compose_files: vec![],
         };
 
         let mut project = ProjectState::new("/test/path".to_string());
@@ -426,6 +557,7 @@ mod tests {
             version: "0.1.0".to_string(),
             recent_projects: vec![],
             global_settings: GlobalSettings::default(),
+            workspace: Default::default(),
         };
 
         let json = serde_json::to_string_pretty(&persisted).unwrap();
@@ -468,6 +600,7 @@ mod tests {
                 },
             ],
             global_settings: GlobalSettings::default(),
+            workspace: Default::default(),
         };
 
         let mut state = AppState::default();
@@ -493,6 +626,7 @@ mod tests {
                 last_opened: "2024-12-25T12:00:00Z".to_string(),
             }],
             global_settings: GlobalSettings::default(),
+            workspace: Default::default(),
         };
 
         let mut state = AppState::default();
@@ -507,20 +641,25 @@ mod tests {
     #[test]
     fn test_from_app_state_captures_recent_projects() {
         // Ensures from_app_state correctly captures recent_projects for persistence
-        let mut app_state = AppState::default();
-        app_state.recent_projects = vec![
-            RecentProject {
-                path: "/project/one".to_string(),
-                name: "one".to_string(),
-                last_opened: "2024-12-25T10:00:00Z".to_string(),
-            },
-            RecentProject {
-                path: "/project/two".to_string(),
-                name: "two".to_string(),
-                last_opened: "2024-12-24T10:00:00Z".to_string(),
+        let app_state = AppState {
+            recent_projects: vec![
+                RecentProject {
+                    path: "/project/one".to_string(),
+                    name: "one".to_string(),
+                    last_opened: "2024-12-25T10:00:00Z".to_string(),
+                },
+                RecentProject {
+                    path: "/project/two".to_string(),
+                    name: "two".to_string(),
+                    last_opened: "2024-12-24T10:00:00Z".to_string(),
+                },
+            ],
+            global_settings: GlobalSettings {
+                theme: Theme::Light,
+                ..Default::default()
             },
-        ];
-        app_state.global_settings.theme = Theme::Light;
+            ..Default::default()
+        };
 
         let persisted = GlobalPersistedState::from_app_state(&app_state);
 
@@ -557,7 +696,20 @@ mod tests {
             global_settings: GlobalSettings {
                 theme: Theme::Dark,
                 default_project_path: Some("/Users/test".to_string()),
+                model: None,
+                task_max_parallel: None,
+                implementation_timeout_secs: None,
+                desktop_notifications: Default::default(),
+                provider: Default::default(),
+                openai: Default::default(),
+                ollama: Default::default(),
+                git_hosting: Default::default(),
+                keybindings: Default::default(),
+                proxy: Default::default(),
+                active_profile: None,
+                guardrails: None,
             },
+            workspace: Default::default(),
         };
 
         // Save
@@ -0,0 +1,268 @@
+//! Prompt template library.
+//!
+//! Reusable prompts live in `~/.rstn/prompts/library/<id>.md`, shared by all
+//! projects. A template is markdown with optional frontmatter and
+//! `{{variable}}` placeholders; `{{variable|default}}` gives a default:
+//!
+//! ```markdown
+//! ---
+//! name: Review module
+//! description: Focused review of one module
+//! ---
+//! Review {{path}} with a focus on {{focus|error handling}}.
+//! ```
+//!
+//! Templates are rendered with `render` and sent through the chat pipeline
+//! by the `SendTemplatedPrompt` action.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use napi_derive::napi;
+
+use crate::persistence;
+
+/// A template variable (`{{name}}` or `{{name|default}}`)
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptVariable {
+    pub name: String,
+    /// Used when no value is given
+    pub default: Option<String>,
+}
+
+/// A prompt template from the library
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptTemplate {
+    /// File stem (e.g. "review-module")
+    pub id: String,
+    /// Frontmatter `name`, or the id
+    pub name: String,
+    pub description: Option<String>,
+    /// Variables in order of first use
+    pub variables: Vec<PromptVariable>,
+    /// Template text without frontmatter
+    pub body: String,
+    pub path: String,
+}
+
+/// Library directory (~/.rstn/prompts/library/)
+pub fn library_dir() -> PathBuf {
+    persistence::get_rstn_dir().join("prompts").join("library")
+}
+
+/// All templates in `dir`, sorted by name. A missing directory is empty.
+pub fn list_templates(dir: &Path) -> Vec<PromptTemplate> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut templates: Vec<PromptTemplate> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "md"))
+        .filter_map(|path| load_template(&path).ok())
+        .collect();
+    templates.sort_by_key(|template| template.name.to_lowercase());
+    templates
+}
+
+/// Find a template by id in `dir`
+pub fn find_template(dir: &Path, id: &str) -> Result<PromptTemplate, String> {
+    if id.is_empty() || id.contains(['/', '\\']) || id.contains("..") {
+        return Err(format!("Invalid prompt template id: {}", id));
+    }
+    let path = dir.join(format!("{}.md", id));
+    if !path.is_file() {
+        return Err(format!("Prompt template not found: {}", id));
+    }
+    load_template(&path)
+}
+
+/// Load a single template file
+pub fn load_template(path: &Path) -> Result<PromptTemplate, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let id = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    Ok(parse_template(&id, &content, &path.to_string_lossy()))
+}
+
+/// Parse template content (frontmatter + body)
+pub fn parse_template(id: &str, content: &str, path: &str) -> PromptTemplate {
+    let (frontmatter, body) = split_frontmatter(content);
+    let field = |key: &str| {
+        frontmatter.lines().find_map(|line| {
+            let (k, v) = line.split_once(':')?;
+            (k.trim() == key)
+                .then(|| v.trim().trim_matches('"').to_string())
+                .filter(|v| !v.is_empty())
+        })
+    };
+
+    PromptTemplate {
+        id: id.to_string(),
+        name: field("name").unwrap_or_else(|| id.to_string()),
+        description: field("description"),
+        variables: extract_variables(body),
+        body: body.to_string(),
+        path: path.to_string(),
+    }
+}
+
+fn split_frontmatter(content: &str) -> (&str, &str) {
+    let Some(rest) = content.strip_prefix("---\n") else {
+        return ("", content);
+    };
+    match rest.find("\n---") {
+        Some(end) => {
+            let body = &rest[end + 4..];
+            (&rest[..end], body.strip_prefix('\n').unwrap_or(body))
+        }
+        None => ("", content),
+    }
+}
+
+/// Placeholders in `body` as (byte range, name, default)
+fn placeholders(body: &str) -> Vec<(std::ops::Range<usize>, String, Option<String>)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = body[offset..].find("{{") {
+        let start = offset + start;
+        let Some(len) = body[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + len + 2;
+        let inner = &body[start + 2..end - 2];
+        let (name, default) = match inner.split_once('|') {
+            Some((name, default)) => (name.trim(), Some(default.trim().to_string())),
+            None => (inner.trim(), None),
+        };
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if valid {
+            found.push((start..end, name.to_string(), default));
+            offset = end;
+        } else {
+            offset = start + 2;
+        }
+    }
+    found
+}
+
+/// Variables in order of first use; the first default given wins
+pub fn extract_variables(body: &str) -> Vec<PromptVariable> {
+    let mut variables: Vec<PromptVariable> = Vec::new();
+    for (_, name, default) in placeholders(body) {
+        match variables.iter_mut().find(|v| v.name == name) {
+            Some(existing) => {
+                if existing.default.is_none() {
+                    existing.default = default;
+                }
+            }
+            None => variables.push(PromptVariable { name, default }),
+        }
+    }
+    variables
+}
+
+/// Render a template. Empty values fall back to the default; variables
+/// without either are reported as missing.
+pub fn render(template: &PromptTemplate, values: &HashMap<String, String>) -> Result<String, String> {
+    let value_of = |variable: &PromptVariable| {
+        values
+            .get(&variable.name)
+            .filter(|value| !value.trim().is_empty())
+            .cloned()
+            .or_else(|| variable.default.clone())
+    };
+
+    let missing: Vec<&str> = template
+        .variables
+        .iter()
+        .filter(|variable| value_of(variable).is_none())
+        .map(|variable| variable.name.as_str())
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("Missing value for {}", missing.join(", ")));
+    }
+
+    let mut rendered = String::with_capacity(template.body.len());
+    let mut last = 0;
+    for (range, name, _) in placeholders(&template.body) {
+        let value = template
+            .variables
+            .iter()
+            .find(|v| v.name == name)
+            .and_then(value_of);
+        rendered.push_str(&template.body[last..range.start]);
+        rendered.push_str(&value.unwrap_or_default());
+        last = range.end;
+    }
+    rendered.push_str(&template.body[last..]);
+    Ok(rendered.trim().to_string())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "---\nname: Review module\ndescription: Focused review\n---\nReview {{path}} for {{ focus | error handling }}.\nThen summarize {{path}}. Keep {{not a var}} and {{}}.\n";
+
+    #[test]
+    fn test_parse_template() {
+        let template = parse_template("review", TEMPLATE, "/lib/review.md");
+        assert_eq!(template.name, "Review module");
+        assert_eq!(template.description.as_deref(), Some("Focused review"));
+        assert!(template.body.starts_with("Review {{path}}"));
+        assert_eq!(
+            template.variables,
+            vec![
+                PromptVariable { name: "path".to_string(), default: None },
+                PromptVariable { name: "focus".to_string(), default: Some("error handling".to_string()) },
+            ]
+        );
+
+        let plain = parse_template("plain", "Just text", "/lib/plain.md");
+        assert_eq!(plain.name, "plain");
+        assert!(plain.variables.is_empty());
+    }
+
+    #[test]
+    fn test_render() {
+        let template = parse_template("review", TEMPLATE, "/lib/review.md");
+        let mut values = HashMap::new();
+        assert_eq!(render(&template, &values).unwrap_err(), "Missing value for path");
+
+        values.insert("path".to_string(), "src/lib.rs".to_string());
+        values.insert("focus".to_string(), " ".to_string());
+        assert_eq!(
+            render(&template, &values).unwrap(),
+            "Review src/lib.rs for error handling.\nThen summarize src/lib.rs. Keep {{not a var}} and {{}}."
+        );
+    }
+
+    #[test]
+    fn test_list_and_find_templates() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("zeta.md"), "---\nname: Alpha\n---\nA").unwrap();
+        std::fs::write(dir.path().join("beta.md"), "B {{x}}").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let names: Vec<_> = list_templates(dir.path()).into_iter().map(|t| t.name).collect();
+        assert_eq!(names, vec!["Alpha", "beta"]);
+        assert!(list_templates(&dir.path().join("missing")).is_empty());
+
+        assert_eq!(find_template(dir.path(), "zeta").unwrap().name, "Alpha");
+        assert!(find_template(dir.path(), "missing").is_err());
+        assert!(find_template(dir.path(), "../zeta").is_err());
+    }
+}
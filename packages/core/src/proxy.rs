@@ -0,0 +1,631 @@
+//! Local reverse proxy for services.
+//!
+//! Serves friendly URLs like `https://pg.localhost:8443` (or
+//! `https://localhost:8443/minio`) and forwards each request over HTTP to the
+//! published port of the routed service, so routes keep working when a
+//! service was moved to another port after a conflict.
+//!
+//! - Routes match on hostname and an optional path prefix (longest prefix
+//!   wins; the prefix is stripped before forwarding). Hostnames must be
+//!   `localhost` or end in `.localhost`, which resolve to this machine
+//!   without editing /etc/hosts.
+//! - One port serves both HTTP and HTTPS: connections starting with a TLS
+//!   handshake are served with a certificate for the routed hostnames.
+//! - Certificates are signed by a local CA created on first use in
+//!   `~/.rstn/proxy/` (`ca.crt` is what users add to their trust store).
+
+use std::collections::{BTreeSet, HashMap};
+use std::convert::Infallible;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use http_body_util::combinators::BoxBody;
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{HeaderValue, HOST};
+use hyper::{Request, Response, StatusCode, Uri};
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::client::legacy::Client;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use openssl::asn1::{Asn1Integer, Asn1Time};
+use openssl::bn::{BigNum, MsbOption};
+use openssl::ec::{EcGroup, EcKey};
+use openssl::hash::MessageDigest;
+use openssl::nid::Nid;
+use openssl::pkey::{PKey, Private};
+use openssl::x509::extension::{
+    AuthorityKeyIdentifier, BasicConstraints, ExtendedKeyUsage, KeyUsage, SubjectAlternativeName,
+    SubjectKeyIdentifier,
+};
+use openssl::x509::{X509NameBuilder, X509};
+use serde::{Deserialize, Serialize};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_util::sync::CancellationToken;
+
+/// Port used when StartProxy doesn't name one
+pub const DEFAULT_PROXY_PORT: u16 = 8443;
+
+/// Validity of the local CA and of the certificates it issues (browsers
+/// reject server certificates valid for more than 398 days)
+const CA_VALID_DAYS: u32 = 3650;
+const CERT_VALID_DAYS: u32 = 397;
+
+/// First byte of a TLS ClientHello
+const TLS_HANDSHAKE: u8 = 0x16;
+
+type ProxyBody = BoxBody<Bytes, hyper::Error>;
+
+// ============================================================================
+// Routes
+// ============================================================================
+
+/// Hostname (and optional path prefix) forwarded to a service's port
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxyRoute {
+    /// `localhost` or a name ending in `.localhost` (e.g. "pg.localhost")
+    pub host: String,
+    /// Only requests under this path (e.g. "/minio"); stripped when forwarding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_prefix: Option<String>,
+    /// Docker service whose published port receives the requests
+    pub service_id: String,
+}
+
+/// Normalize routes (lowercase hosts, no trailing slash on prefixes) and
+/// check them: valid `.localhost` hostnames, prefixes starting with `/`, no
+/// two routes for the same host and prefix.
+pub fn normalize_routes(routes: Vec<ProxyRoute>) -> Result<Vec<ProxyRoute>, String> {
+    let mut seen = BTreeSet::new();
+    let mut normalized = Vec::with_capacity(routes.len());
+    for route in routes {
+        let host = route.host.trim().trim_end_matches('.').to_lowercase();
+        if !is_localhost_name(&host) {
+            return Err(format!(
+                "'{}' is not a valid hostname ending in .localhost",
+                route.host.trim()
+            ));
+        }
+        let path_prefix = match route.path_prefix.as_deref().map(str::trim) {
+            None | Some("") | Some("/") => None,
+            Some(prefix) if !prefix.starts_with('/') || prefix.contains(char::is_whitespace) => {
+                return Err(format!("Path prefix '{}' must start with / and contain no spaces", prefix));
+            }
+            Some(prefix) => Some(prefix.trim_end_matches('/').to_string()),
+        };
+        let service_id = route.service_id.trim().to_string();
+        if service_id.is_empty() {
+            return Err(format!("Route {} has no service", host));
+        }
+        if !seen.insert((host.clone(), path_prefix.clone())) {
+            return Err(format!(
+                "Duplicate route for {}{}",
+                host,
+                path_prefix.as_deref().unwrap_or_default()
+            ));
+        }
+        normalized.push(ProxyRoute { host, path_prefix, service_id });
+    }
+    Ok(normalized)
+}
+
+fn is_localhost_name(host: &str) -> bool {
+    let valid_label = |label: &str| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+    host == "localhost"
+        || host
+            .strip_suffix(".localhost")
+            .is_some_and(|name| name.split('.').all(valid_label))
+}
+
+/// Where a request goes
+#[derive(Debug, PartialEq, Eq)]
+enum Resolution {
+    /// Forward to 127.0.0.1:`port` with this path and query
+    Forward { port: u16, path_and_query: String },
+    /// The routed service isn't running (or has no published port)
+    NotRunning { service_id: String },
+    NoRoute,
+}
+
+/// Routes with the current port of their service
+#[derive(Debug, Default)]
+struct RouteTable {
+    routes: Vec<(ProxyRoute, Option<u16>)>,
+}
+
+impl RouteTable {
+    fn new(routes: &[ProxyRoute], ports: &HashMap<String, u16>) -> Self {
+        Self {
+            routes: routes
+                .iter()
+                .map(|route| (route.clone(), ports.get(&route.service_id).copied()))
+                .collect(),
+        }
+    }
+
+    /// Route for `host` (port ignored) and `path_and_query`; the longest
+    /// matching path prefix wins and is stripped
+    fn resolve(&self, host: &str, path_and_query: &str) -> Resolution {
+        let host = host.rsplit_once(':').map_or(host, |(name, _)| name).to_lowercase();
+        let path = path_and_query.split('?').next().unwrap_or_default();
+        let best = self
+            .routes
+            .iter()
+            .filter(|(route, _)| route.host == host)
+            .filter(|(route, _)| {
+                route.path_prefix.as_deref().is_none_or(|prefix| {
+                    path.strip_prefix(prefix)
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+                })
+            })
+            .max_by_key(|(route, _)| route.path_prefix.as_ref().map_or(0, String::len));
+
+        let Some((route, port)) = best else {
+            return Resolution::NoRoute;
+        };
+        let Some(port) = *port else {
+            return Resolution::NotRunning { service_id: route.service_id.clone() };
+        };
+        let rest = match &route.path_prefix {
+            Some(prefix) => &path_and_query[prefix.len()..],
+            None => path_and_query,
+        };
+        let path_and_query = if rest.starts_with('/') { rest.to_string() } else { format!("/{}", rest) };
+        Resolution::Forward { port, path_and_query }
+    }
+
+    /// Hostnames the certificate must cover
+    fn hosts(&self) -> BTreeSet<String> {
+        self.routes.iter().map(|(route, _)| route.host.clone()).collect()
+    }
+}
+
+// ============================================================================
+// Local CA
+// ============================================================================
+
+/// Certificate authority that signs the proxy's certificates
+pub struct LocalCa {
+    cert: X509,
+    key: PKey<Private>,
+    cert_path: PathBuf,
+}
+
+impl LocalCa {
+    /// Default location of the CA (`~/.rstn/proxy/`)
+    pub fn default_dir() -> PathBuf {
+        crate::persistence::get_rstn_dir().join("proxy")
+    }
+
+    /// Load the CA from `dir`, creating it on first use
+    pub fn load_or_create(dir: &Path) -> Result<Self, String> {
+        let cert_path = dir.join("ca.crt");
+        let key_path = dir.join("ca.key");
+        if cert_path.exists() && key_path.exists() {
+            let cert = std::fs::read(&cert_path)
+                .ok()
+                .and_then(|pem| X509::from_pem(&pem).ok())
+                .ok_or_else(|| format!("Invalid CA certificate {}", cert_path.display()))?;
+            let key = std::fs::read(&key_path)
+                .ok()
+                .and_then(|pem| PKey::private_key_from_pem(&pem).ok())
+                .ok_or_else(|| format!("Invalid CA key {}", key_path.display()))?;
+            return Ok(Self { cert, key, cert_path });
+        }
+
+        let (cert, key) = generate_ca().map_err(|e| format!("Failed to create local CA: {}", e))?;
+        std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let key_pem = key.private_key_to_pem_pkcs8().map_err(|e| e.to_string())?;
+        write_private(&key_path, &key_pem)?;
+        let cert_pem = cert.to_pem().map_err(|e| e.to_string())?;
+        std::fs::write(&cert_path, cert_pem)
+            .map_err(|e| format!("Failed to write {}: {}", cert_path.display(), e))?;
+        tracing::info!("Created local CA at {}", cert_path.display());
+        Ok(Self { cert, key, cert_path })
+    }
+
+    /// CA certificate to add to the system trust store
+    pub fn cert_path(&self) -> &Path {
+        &self.cert_path
+    }
+
+    /// Issue a server certificate for `hosts` (plus localhost and 127.0.0.1).
+    /// Returns the PEM chain (certificate, CA) and the PKCS#8 PEM key.
+    fn issue(&self, hosts: &BTreeSet<String>) -> Result<(Vec<u8>, Vec<u8>), String> {
+        let issued = || -> Result<(Vec<u8>, Vec<u8>), openssl::error::ErrorStack> {
+            let key = generate_key()?;
+            let mut name = X509NameBuilder::new()?;
+            name.append_entry_by_nid(Nid::COMMONNAME, "rstn proxy")?;
+            let name = name.build();
+
+            let mut builder = X509::builder()?;
+            builder.set_version(2)?;
+            builder.set_serial_number(&*random_serial()?)?;
+            builder.set_subject_name(&name)?;
+            builder.set_issuer_name(self.cert.subject_name())?;
+            builder.set_pubkey(&key)?;
+            builder.set_not_before(&*Asn1Time::days_from_now(0)?)?;
+            builder.set_not_after(&*Asn1Time::days_from_now(CERT_VALID_DAYS)?)?;
+            builder.append_extension(BasicConstraints::new().build()?)?;
+            builder.append_extension(KeyUsage::new().critical().digital_signature().key_encipherment().build()?)?;
+            builder.append_extension(ExtendedKeyUsage::new().server_auth().build()?)?;
+
+            let mut san = SubjectAlternativeName::new();
+            san.dns("localhost").ip("127.0.0.1");
+            for host in hosts.iter().filter(|h| *h != "localhost") {
+                san.dns(host);
+            }
+            let context = builder.x509v3_context(Some(&self.cert), None);
+            let san = san.build(&context)?;
+            let authority_key = AuthorityKeyIdentifier::new().keyid(false).build(&context)?;
+            builder.append_extension(san)?;
+            builder.append_extension(authority_key)?;
+            builder.sign(&self.key, MessageDigest::sha256())?;
+
+            let mut chain = builder.build().to_pem()?;
+            chain.extend(self.cert.to_pem()?);
+            Ok((chain, key.private_key_to_pem_pkcs8()?))
+        };
+        issued().map_err(|e| format!("Failed to issue certificate: {}", e))
+    }
+
+    fn acceptor(&self, hosts: &BTreeSet<String>) -> Result<tokio_native_tls::TlsAcceptor, String> {
+        let (chain, key) = self.issue(hosts)?;
+        let identity = tokio_native_tls::native_tls::Identity::from_pkcs8(&chain, &key)
+            .map_err(|e| format!("Invalid proxy certificate: {}", e))?;
+        let acceptor = tokio_native_tls::native_tls::TlsAcceptor::new(identity)
+            .map_err(|e| format!("Failed to set up TLS: {}", e))?;
+        Ok(acceptor.into())
+    }
+}
+
+fn generate_key() -> Result<PKey<Private>, openssl::error::ErrorStack> {
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?;
+    PKey::from_ec_key(EcKey::generate(&group)?)
+}
+
+fn random_serial() -> Result<Asn1Integer, openssl::error::ErrorStack> {
+    let mut serial = BigNum::new()?;
+    serial.rand(127, MsbOption::MAYBE_ZERO, false)?;
+    serial.to_asn1_integer()
+}
+
+fn generate_ca() -> Result<(X509, PKey<Private>), openssl::error::ErrorStack> {
+    let key = generate_key()?;
+    let mut name = X509NameBuilder::new()?;
+    name.append_entry_by_nid(Nid::COMMONNAME, "rstn Local CA")?;
+    name.append_entry_by_nid(Nid::ORGANIZATIONNAME, "rustation")?;
+    let name = name.build();
+
+    let mut builder = X509::builder()?;
+    builder.set_version(2)?;
+    builder.set_serial_number(&*random_serial()?)?;
+    builder.set_subject_name(&name)?;
+    builder.set_issuer_name(&name)?;
+    builder.set_pubkey(&key)?;
+    builder.set_not_before(&*Asn1Time::days_from_now(0)?)?;
+    builder.set_not_after(&*Asn1Time::days_from_now(CA_VALID_DAYS)?)?;
+    builder.append_extension(BasicConstraints::new().critical().ca().pathlen(0).build()?)?;
+    builder.append_extension(KeyUsage::new().critical().key_cert_sign().crl_sign().build()?)?;
+    let subject_key = SubjectKeyIdentifier::new().build(&builder.x509v3_context(None, None))?;
+    builder.append_extension(subject_key)?;
+    builder.sign(&key, MessageDigest::sha256())?;
+    Ok((builder.build(), key))
+}
+
+/// Write a file only the user can read
+fn write_private(path: &Path, contents: &[u8]) -> Result<(), String> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    std::io::Write::write_all(&mut file, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+// ============================================================================
+// Server
+// ============================================================================
+
+struct Shared {
+    ca: LocalCa,
+    table: RwLock<RouteTable>,
+    /// Hostnames the current certificate covers, and its acceptor
+    tls: RwLock<(BTreeSet<String>, tokio_native_tls::TlsAcceptor)>,
+    client: Client<HttpConnector, Incoming>,
+    cancel: CancellationToken,
+}
+
+/// A running proxy; dropping it stops the server.
+pub struct ProxyServer {
+    port: u16,
+    shared: Arc<Shared>,
+}
+
+impl ProxyServer {
+    /// Listen on 127.0.0.1:`port` (0 = any free port)
+    pub async fn start(port: u16, ca: LocalCa) -> Result<Self, String> {
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .await
+            .map_err(|e| format!("Failed to listen on port {}: {}", port, e))?;
+        let port = listener.local_addr().map_err(|e| e.to_string())?.port();
+        let acceptor = ca.acceptor(&BTreeSet::new())?;
+        let shared = Arc::new(Shared {
+            ca,
+            table: RwLock::new(RouteTable::default()),
+            tls: RwLock::new((BTreeSet::new(), acceptor)),
+            client: Client::builder(TokioExecutor::new()).build_http(),
+            cancel: CancellationToken::new(),
+        });
+        tokio::spawn(accept_loop(listener, shared.clone()));
+        tracing::info!("Proxy listening on 127.0.0.1:{}", port);
+        Ok(Self { port, shared })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn ca_cert_path(&self) -> &Path {
+        self.shared.ca.cert_path()
+    }
+
+    /// Replace the routes; `ports` maps service IDs to their published port
+    /// (services missing from it answer 502). Reissues the certificate when
+    /// the set of hostnames changes.
+    pub fn update_routes(&self, routes: &[ProxyRoute], ports: &HashMap<String, u16>) -> Result<(), String> {
+        let table = RouteTable::new(routes, ports);
+        let hosts = table.hosts();
+        *self.shared.table.write().unwrap_or_else(|e| e.into_inner()) = table;
+
+        let current = self.shared.tls.read().unwrap_or_else(|e| e.into_inner()).0.clone();
+        if current != hosts {
+            let acceptor = self.shared.ca.acceptor(&hosts)?;
+            *self.shared.tls.write().unwrap_or_else(|e| e.into_inner()) = (hosts, acceptor);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for ProxyServer {
+    fn drop(&mut self) {
+        self.shared.cancel.cancel();
+    }
+}
+
+async fn accept_loop(listener: TcpListener, shared: Arc<Shared>) {
+    loop {
+        tokio::select! {
+            _ = shared.cancel.cancelled() => break,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    tokio::spawn(serve_connection(stream, shared.clone()));
+                }
+                Err(e) => tracing::warn!("Proxy accept failed: {}", e),
+            },
+        }
+    }
+}
+
+async fn serve_connection(stream: TcpStream, shared: Arc<Shared>) {
+    let mut first = [0u8; 1];
+    let is_tls = matches!(stream.peek(&mut first).await, Ok(1) if first[0] == TLS_HANDSHAKE);
+
+    let cancel = shared.cancel.clone();
+    let result = if is_tls {
+        let acceptor = shared.tls.read().unwrap_or_else(|e| e.into_inner()).1.clone();
+        let stream = match acceptor.accept(stream).await {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::debug!("Proxy TLS handshake failed: {}", e);
+                return;
+            }
+        };
+        let service = hyper::service::service_fn(move |req| forward(shared.clone(), req, "https"));
+        let connection = hyper::server::conn::http1::Builder::new().serve_connection(TokioIo::new(stream), service);
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            result = connection => result,
+        }
+    } else {
+        let service = hyper::service::service_fn(move |req| forward(shared.clone(), req, "http"));
+        let connection = hyper::server::conn::http1::Builder::new().serve_connection(TokioIo::new(stream), service);
+        tokio::select! {
+            _ = cancel.cancelled() => return,
+            result = connection => result,
+        }
+    };
+    if let Err(e) = result {
+        tracing::debug!("Proxy connection error: {}", e);
+    }
+}
+
+/// Forward a request to the routed service
+async fn forward(shared: Arc<Shared>, mut req: Request<Incoming>, scheme: &'static str) -> Result<Response<ProxyBody>, Infallible> {
+    let host = req
+        .headers()
+        .get(HOST)
+        .and_then(|h| h.to_str().ok())
+        .or_else(|| req.uri().host())
+        .unwrap_or_default()
+        .to_string();
+    let path_and_query = req.uri().path_and_query().map_or("/", |p| p.as_str()).to_string();
+
+    let resolution = shared.table.read().unwrap_or_else(|e| e.into_inner()).resolve(&host, &path_and_query);
+    let (port, path_and_query) = match resolution {
+        Resolution::Forward { port, path_and_query } => (port, path_and_query),
+        Resolution::NotRunning { service_id } => {
+            return Ok(error_response(StatusCode::BAD_GATEWAY, &format!("{} is not running", service_id)));
+        }
+        Resolution::NoRoute => {
+            return Ok(error_response(StatusCode::NOT_FOUND, &format!("No rstn proxy route for {}", host)));
+        }
+    };
+
+    let Ok(uri) = format!("http://127.0.0.1:{}{}", port, path_and_query).parse::<Uri>() else {
+        return Ok(error_response(StatusCode::BAD_REQUEST, "Invalid request path"));
+    };
+    *req.uri_mut() = uri;
+    let headers = req.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&host) {
+        headers.insert("x-forwarded-host", value);
+    }
+    headers.insert("x-forwarded-proto", HeaderValue::from_static(scheme));
+
+    match shared.client.request(req).await {
+        Ok(response) => Ok(response.map(|body| body.boxed())),
+        Err(e) => Ok(error_response(
+            StatusCode::BAD_GATEWAY,
+            &format!("Failed to reach port {}: {}", port, e),
+        )),
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<ProxyBody> {
+    let body = Full::new(Bytes::from(format!("{}\n", message)))
+        .map_err(|never| match never {})
+        .boxed();
+    let mut response = Response::new(body);
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert("content-type", HeaderValue::from_static("text/plain; charset=utf-8"));
+    response
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(host: &str, path_prefix: Option<&str>, service_id: &str) -> ProxyRoute {
+        ProxyRoute {
+            host: host.to_string(),
+            path_prefix: path_prefix.map(str::to_string),
+            service_id: service_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_normalize_routes() {
+        let routes = normalize_routes(vec![
+            route(" PG.localhost ", None, "rstn-postgres"),
+            route("localhost", Some("/minio/"), "rstn-minio"),
+            route("localhost", Some("/"), "app"),
+        ])
+        .unwrap();
+        assert_eq!(routes[0], route("pg.localhost", None, "rstn-postgres"));
+        assert_eq!(routes[1].path_prefix.as_deref(), Some("/minio"));
+        assert_eq!(routes[2].path_prefix, None);
+
+        assert!(normalize_routes(vec![route("example.com", None, "app")]).is_err());
+        assert!(normalize_routes(vec![route("-bad.localhost", None, "app")]).is_err());
+        assert!(normalize_routes(vec![route("localhost", Some("minio"), "app")]).is_err());
+        assert!(normalize_routes(vec![route("app.localhost", None, " ")]).is_err());
+        let err = normalize_routes(vec![
+            route("app.localhost", None, "a"),
+            route("APP.localhost", Some("/"), "b"),
+        ])
+        .unwrap_err();
+        assert!(err.contains("Duplicate"), "{}", err);
+    }
+
+    #[test]
+    fn test_resolve_longest_prefix() {
+        let routes = vec![
+            route("localhost", None, "app"),
+            route("localhost", Some("/minio"), "rstn-minio"),
+            route("mq.localhost", None, "rstn-rabbitmq"),
+        ];
+        let ports = HashMap::from([("app".to_string(), 3000), ("rstn-minio".to_string(), 9001)]);
+        let table = RouteTable::new(&routes, &ports);
+
+        assert_eq!(
+            table.resolve("localhost:8443", "/minio/browser?x=1"),
+            Resolution::Forward { port: 9001, path_and_query: "/browser?x=1".to_string() }
+        );
+        assert_eq!(
+            table.resolve("localhost", "/minio?x=1"),
+            Resolution::Forward { port: 9001, path_and_query: "/?x=1".to_string() }
+        );
+        // "/minioadmin" is not under "/minio"
+        assert_eq!(
+            table.resolve("LOCALHOST", "/minioadmin"),
+            Resolution::Forward { port: 3000, path_and_query: "/minioadmin".to_string() }
+        );
+        assert_eq!(
+            table.resolve("mq.localhost", "/"),
+            Resolution::NotRunning { service_id: "rstn-rabbitmq".to_string() }
+        );
+        assert_eq!(table.resolve("pg.localhost", "/"), Resolution::NoRoute);
+    }
+
+    #[test]
+    fn test_local_ca_is_reused_and_issues_certificates() {
+        let dir = tempfile::tempdir().unwrap();
+        let ca = LocalCa::load_or_create(dir.path()).unwrap();
+        let reloaded = LocalCa::load_or_create(dir.path()).unwrap();
+        assert_eq!(ca.cert.to_der().unwrap(), reloaded.cert.to_der().unwrap());
+
+        let hosts = BTreeSet::from(["pg.localhost".to_string()]);
+        let (chain, _key) = ca.issue(&hosts).unwrap();
+        let certs = X509::stack_from_pem(&chain).unwrap();
+        assert_eq!(certs.len(), 2);
+        assert!(certs[0].verify(&ca.cert.public_key().unwrap()).unwrap());
+        let names: Vec<_> = certs[0]
+            .subject_alt_names()
+            .unwrap()
+            .iter()
+            .filter_map(|name| name.dnsname().map(str::to_string))
+            .collect();
+        assert_eq!(names, vec!["localhost", "pg.localhost"]);
+    }
+
+    #[tokio::test]
+    async fn test_proxy_forwards_http_and_https() {
+        let upstream = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let upstream_port = upstream.local_addr().unwrap().port();
+        let app = axum::Router::new().fallback(|uri: axum::http::Uri, headers: axum::http::HeaderMap| async move {
+            let proto = headers.get("x-forwarded-proto").and_then(|v| v.to_str().ok()).unwrap_or_default().to_string();
+            format!("{} {}", proto, uri)
+        });
+        tokio::spawn(async move { axum::serve(upstream, app).await.unwrap() });
+
+        let dir = tempfile::tempdir().unwrap();
+        let proxy = ProxyServer::start(0, LocalCa::load_or_create(dir.path()).unwrap()).await.unwrap();
+        let routes = vec![route("api.localhost", Some("/v1"), "app"), route("down.localhost", None, "db")];
+        proxy
+            .update_routes(&routes, &HashMap::from([("app".to_string(), upstream_port)]))
+            .unwrap();
+
+        let addr = std::net::SocketAddr::from(([127, 0, 0, 1], proxy.port()));
+        let client = reqwest::Client::builder()
+            .resolve("api.localhost", addr)
+            .resolve("down.localhost", addr)
+            .add_root_certificate(reqwest::Certificate::from_pem(&std::fs::read(proxy.ca_cert_path()).unwrap()).unwrap())
+            .build()
+            .unwrap();
+
+        let url = format!("http://api.localhost:{}/v1/users?page=2", proxy.port());
+        assert_eq!(client.get(url).send().await.unwrap().text().await.unwrap(), "http /users?page=2");
+
+        let url = format!("https://api.localhost:{}/v1/users", proxy.port());
+        assert_eq!(client.get(url).send().await.unwrap().text().await.unwrap(), "https /users");
+
+        let url = format!("http://down.localhost:{}/", proxy.port());
+        assert_eq!(client.get(url).send().await.unwrap().status(), StatusCode::BAD_GATEWAY);
+    }
+}
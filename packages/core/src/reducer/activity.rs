@@ -0,0 +1,27 @@
+use crate::activity::MAX_ACTIVITY_EVENTS;
+use crate::actions::Action;
+use crate::app_state::AppState;
+
+pub fn reduce(state: &mut AppState, action: Action) {
+    let feed = &mut state.activity_feed;
+    match action {
+        Action::LoadActivityFeed { .. } => {
+            feed.is_loading = true;
+        }
+        Action::SetActivityFeed { project_id, since, events } => {
+            feed.project_id = Some(project_id);
+            feed.since = since;
+            feed.events = events;
+            feed.is_loading = false;
+        }
+        Action::AddActivity { event } => {
+            // Only the loaded project's feed is kept in state
+            if feed.project_id.as_deref() != Some(event.project_id.as_str()) {
+                return;
+            }
+            feed.events.insert(0, event);
+            feed.events.truncate(MAX_ACTIVITY_EVENTS);
+        }
+        _ => {}
+    }
+}
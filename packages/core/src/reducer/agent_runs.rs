@@ -0,0 +1,129 @@
+use crate::actions::Action;
+use crate::agent_runs::{prune_finished, AgentRun, AgentRunStatus};
+use crate::app_state::{AppState, Change, ChangeStatus};
+
+pub fn reduce(state: &mut AppState, action: Action) {
+    match action {
+        Action::AddAgentRun { run } => {
+            state.agent_runs.runs.push(run);
+            prune_finished(&mut state.agent_runs.runs);
+        }
+
+        Action::StartAgentRun { run_id } => {
+            if let Some(run) = run_mut(state, &run_id) {
+                run.status = AgentRunStatus::Running;
+                run.started_at = Some(chrono::Utc::now().to_rfc3339());
+                let run = run.clone();
+                if let Some(change) = change_of_mut(state, &run) {
+                    super::changes::start_implementation(change);
+                }
+            }
+        }
+
+        Action::AppendAgentRunOutput { run_id, content } => {
+            if let Some(run) = run_mut(state, &run_id) {
+                run.output_chars += content.chars().count();
+                let run = run.clone();
+                if let Some(change) = change_of_mut(state, &run) {
+                    change.streaming_output.push_str(&content);
+                    // Track per-task completion markers
+                    let task_count = change.implementation_tasks.len();
+                    for index in crate::implementation::completed_task_indices(&change.streaming_output, task_count) {
+                        change.implementation_tasks[index].done = true;
+                    }
+                }
+            }
+        }
+
+        Action::FinishAgentRun { run_id, status, error } => {
+            // A cancelled run stays cancelled when its killed session winds down
+            if let Some(run) = run_mut(state, &run_id).filter(|r| r.status != AgentRunStatus::Cancelled) {
+                run.status = status;
+                run.error = error;
+                run.approval = None;
+                run.finished_at = Some(chrono::Utc::now().to_rfc3339());
+                let run = run.clone();
+                if let Some(change) = change_of_mut(state, &run) {
+                    change.status = match status {
+                        AgentRunStatus::AwaitingReview => ChangeStatus::Done,
+                        _ => ChangeStatus::Failed,
+                    };
+                    change.updated_at = chrono::Utc::now().to_rfc3339();
+                }
+            }
+        }
+
+        Action::RequestAgentRunApproval { run_id, approval } => {
+            if let Some(run) = run_mut(state, &run_id) {
+                run.status = AgentRunStatus::AwaitingApproval;
+                run.approval = Some(approval);
+            }
+        }
+
+        Action::ResolveAgentRunApproval { run_id, request_id, approve } => {
+            let Some(run) = run_mut(state, &run_id) else { return };
+            let Some(approval) = run.approval.take_if(|a| a.request_id == request_id) else { return };
+            run.status = AgentRunStatus::Running;
+            let run = run.clone();
+            // Leave a trace of the decision in the change's output
+            if let Some(change) = change_of_mut(state, &run) {
+                change.streaming_output.push_str(&format!(
+                    "\n[guardrails] {} {}: {} ({})\n",
+                    if approve { "Approved" } else { "Denied" },
+                    approval.tool_name,
+                    approval.input_summary,
+                    approval.reason,
+                ));
+            }
+        }
+
+        Action::CancelAgentRun { run_id } => {
+            // Queued runs are dropped; a started run's Claude CLI is killed by
+            // the async handler and its change goes back to the approved plan
+            let Some(run) = run_mut(state, &run_id).filter(|r| !r.status.is_finished()) else { return };
+            let started = run.status.is_active();
+            run.status = AgentRunStatus::Cancelled;
+            run.approval = None;
+            run.finished_at = Some(chrono::Utc::now().to_rfc3339());
+            let run = run.clone();
+            if started {
+                if let Some(change) = change_of_mut(state, &run) {
+                    if matches!(change.status, ChangeStatus::Implementing | ChangeStatus::Testing) {
+                        change.status = ChangeStatus::Planned;
+                    }
+                    change.streaming_output.push_str("\n\nImplementation cancelled\n");
+                    change.updated_at = chrono::Utc::now().to_rfc3339();
+                }
+            }
+        }
+
+        Action::SetAgentMaxConcurrent { max_concurrent } => {
+            state.agent_runs.max_concurrent = (max_concurrent as usize).max(1);
+        }
+
+        Action::ClearFinishedAgentRuns => {
+            state.agent_runs.runs.retain(|r| !r.status.is_finished());
+        }
+
+        // Validating the change and creating the run is async
+        Action::QueueAgentRun { .. } => {}
+        _ => {}
+    }
+}
+
+fn run_mut<'a>(state: &'a mut AppState, run_id: &str) -> Option<&'a mut AgentRun> {
+    state.agent_runs.runs.iter_mut().find(|r| r.id == run_id)
+}
+
+/// The change a run implements, in the run's worktree (active or not)
+fn change_of_mut<'a>(state: &'a mut AppState, run: &AgentRun) -> Option<&'a mut Change> {
+    state
+        .projects
+        .iter_mut()
+        .flat_map(|p| p.worktrees.iter_mut())
+        .find(|w| w.path == run.worktree_path)?
+        .changes
+        .changes
+        .iter_mut()
+        .find(|c| c.id == run.change_id)
+}
@@ -3,7 +3,7 @@ use crate::app_state::AppState;
 
 pub fn reduce(state: &mut AppState, action: Action) {
     match action {
-        Action::CreateChange { .. } => {
+        Action::CreateChange { .. } | Action::CreateChangeFromIssue { .. } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
                     worktree.changes.is_loading = true;
@@ -55,6 +55,7 @@ pub fn reduce(state: &mut AppState, action: Action) {
                             policy: crate::app_state::ReviewPolicy::AlwaysReview,
                             comments: vec![],
                             iteration: 1,
+                            compliance: None,
                             created_at: now.clone(),
                             updated_at: now,
                         };
@@ -110,6 +111,7 @@ pub fn reduce(state: &mut AppState, action: Action) {
                             policy: crate::app_state::ReviewPolicy::AlwaysReview,
                             comments: vec![],
                             iteration: 1,
+                            compliance: None,
                             created_at: now.clone(),
                             updated_at: now,
                         };
@@ -122,23 +124,36 @@ pub fn reduce(state: &mut AppState, action: Action) {
         }
 
         Action::ApprovePlan { change_id } => {
+            let mut blocker = None;
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
+                    let sessions = &worktree.tasks.review_gate.sessions;
                     if let Some(change) = worktree.changes.changes.iter_mut().find(|c| c.id == change_id) {
-                        change.status = crate::app_state::ChangeStatus::Planned;
-                        change.updated_at = chrono::Utc::now().to_rfc3339();
+                        blocker = change
+                            .plan_review_session_id
+                            .as_ref()
+                            .and_then(|id| sessions.get(id))
+                            .and_then(|session| session.approval_blocker());
+                        if blocker.is_none() {
+                            change.status = crate::app_state::ChangeStatus::Planned;
+                            change.updated_at = chrono::Utc::now().to_rfc3339();
+                        }
                     }
                 }
             }
+            if let Some(blocker) = blocker {
+                state.notifications.push(crate::app_state::Notification::new(
+                    format!("Cannot approve plan: {}", blocker),
+                    crate::app_state::NotificationType::Warning,
+                ));
+            }
         }
 
         Action::ExecutePlan { change_id } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
                     if let Some(change) = worktree.changes.changes.iter_mut().find(|c| c.id == change_id) {
-                        change.status = crate::app_state::ChangeStatus::Implementing;
-                        change.streaming_output.clear();
-                        change.updated_at = chrono::Utc::now().to_rfc3339();
+                        start_implementation(change);
                     }
                 }
             }
@@ -154,6 +169,29 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
+        Action::CompleteImplementationTask { change_id, task_index } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(change) = worktree.changes.changes.iter_mut().find(|c| c.id == change_id) {
+                        if let Some(task) = change.implementation_tasks.get_mut(task_index) {
+                            task.done = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        Action::StartImplementationTests { change_id } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(change) = worktree.changes.changes.iter_mut().find(|c| c.id == change_id) {
+                        change.status = crate::app_state::ChangeStatus::Testing;
+                        change.updated_at = chrono::Utc::now().to_rfc3339();
+                    }
+                }
+            }
+        }
+
         Action::CompleteImplementation { change_id } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
@@ -165,11 +203,145 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
+        Action::FailProposal { change_id, .. } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(change) = worktree.changes.changes.iter_mut().find(|c| c.id == change_id) {
+                        change.status = crate::app_state::ChangeStatus::Failed;
+                        change.updated_at = chrono::Utc::now().to_rfc3339();
+                    }
+                }
+            }
+        }
+
         Action::FailImplementation { change_id, .. } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
                     if let Some(change) = worktree.changes.changes.iter_mut().find(|c| c.id == change_id) {
                         change.status = crate::app_state::ChangeStatus::Failed;
+                        change.implementation_approval = None;
+                        change.updated_at = chrono::Utc::now().to_rfc3339();
+                    }
+                }
+            }
+        }
+
+        Action::RequestImplementationApproval { change_id, approval } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(change) = worktree.changes.changes.iter_mut().find(|c| c.id == change_id) {
+                        change.implementation_approval = Some(approval);
+                    }
+                }
+            }
+        }
+
+        Action::ResolveImplementationApproval { change_id, request_id, approve } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(change) = worktree.changes.changes.iter_mut().find(|c| c.id == change_id) {
+                        if let Some(approval) = change.implementation_approval.take_if(|a| a.request_id == request_id) {
+                            // Same trace as agent runs leave
+                            change.streaming_output.push_str(&format!(
+                                "\n[guardrails] {} {}: {} ({})\n",
+                                if approve { "Approved" } else { "Denied" },
+                                approval.tool_name,
+                                approval.input_summary,
+                                approval.reason,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        Action::SetChangeSnapshot { worktree_path, change_id, snapshot } => {
+            let worktree = state
+                .projects
+                .iter_mut()
+                .flat_map(|p| p.worktrees.iter_mut())
+                .find(|w| w.path == worktree_path);
+            if let Some(change) = worktree.and_then(|w| w.changes.changes.iter_mut().find(|c| c.id == change_id)) {
+                change.snapshot = Some(snapshot);
+            }
+        }
+
+        Action::CompleteRollback { change_id, snapshot } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(change) = worktree.changes.changes.iter_mut().find(|c| c.id == change_id) {
+                        // Back to the approved plan, ready to be executed again
+                        change.status = crate::app_state::ChangeStatus::Planned;
+                        change.implementation_tasks.clear();
+                        change.streaming_output.clear();
+                        change.snapshot = Some(snapshot);
+                        change.test_results = None;
+                        change.hook_results.clear();
+                        change.updated_at = chrono::Utc::now().to_rfc3339();
+                    }
+                }
+            }
+        }
+
+        Action::SetChangeTestResults { worktree_path, change_id, results } => {
+            let worktree = state
+                .projects
+                .iter_mut()
+                .flat_map(|p| p.worktrees.iter_mut())
+                .find(|w| w.path == worktree_path);
+            if let Some(change) = worktree.and_then(|w| w.changes.changes.iter_mut().find(|c| c.id == change_id)) {
+                change.test_results = Some(results);
+            }
+        }
+
+        Action::SetChangeHookResults { worktree_path, change_id, results } => {
+            let worktree = state
+                .projects
+                .iter_mut()
+                .flat_map(|p| p.worktrees.iter_mut())
+                .find(|w| w.path == worktree_path);
+            if let Some(change) = worktree.and_then(|w| w.changes.changes.iter_mut().find(|c| c.id == change_id)) {
+                change.hook_results = results;
+            }
+        }
+
+        Action::OverrideTestGate { change_id } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    let change = worktree.changes.changes.iter_mut().find(|c| c.id == change_id);
+                    if let Some(results) = change.and_then(|c| c.test_results.as_mut()) {
+                        results.overridden = true;
+                    }
+                }
+            }
+        }
+
+        Action::CancelProposal { change_id } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(change) = worktree.changes.changes.iter_mut().find(|c| c.id == change_id) {
+                        // Back to the initial state so the proposal can be regenerated
+                        if change.status == crate::app_state::ChangeStatus::Planning {
+                            change.status = crate::app_state::ChangeStatus::Proposed;
+                        }
+                        change.streaming_output.clear();
+                        change.updated_at = chrono::Utc::now().to_rfc3339();
+                    }
+                }
+            }
+        }
+
+        Action::CancelImplementation { change_id } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(change) = worktree.changes.changes.iter_mut().find(|c| c.id == change_id) {
+                        // Back to the approved plan; the output stays for review
+                        // (RollbackImplementation undoes the partial edits)
+                        if change.status == crate::app_state::ChangeStatus::Implementing {
+                            change.status = crate::app_state::ChangeStatus::Planned;
+                        }
+                        change.streaming_output.push_str("\n\nImplementation cancelled\n");
+                        change.implementation_approval = None;
                         change.updated_at = chrono::Utc::now().to_rfc3339();
                     }
                 }
@@ -187,6 +359,18 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
+        Action::ResolveChangeStatusConflict { change_id, status } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(change) = worktree.changes.changes.iter_mut().find(|c| c.id == change_id) {
+                        change.status = status.into();
+                        change.status_conflict = None;
+                        change.updated_at = chrono::Utc::now().to_rfc3339();
+                    }
+                }
+            }
+        }
+
         Action::SelectChange { change_id } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
@@ -277,6 +461,40 @@ pub fn reduce(state: &mut AppState, action: Action) {
                 }
             }
         }
+
+        Action::SetChangePullRequest { change_id, url } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(change) = worktree.changes.changes.iter_mut().find(|c| c.id == change_id) {
+                        change.pull_request_url = Some(url);
+                        change.updated_at = chrono::Utc::now().to_rfc3339();
+                    }
+                }
+            }
+        }
+
+        // Pushing and calling the hosting API is async
+        Action::OpenChangePullRequest { .. } => {}
+        // Restoring the worktree is async
+        Action::RollbackImplementation { .. } => {}
+        // Running the test command is async
+        Action::RunChangeTests { .. } => {}
         _ => {}
     }
 }
+
+/// Move a change to Implementing, splitting plan.md into tracked tasks
+pub(super) fn start_implementation(change: &mut crate::app_state::Change) {
+    change.status = crate::app_state::ChangeStatus::Implementing;
+    change.streaming_output.clear();
+    change.implementation_approval = None;
+    change.implementation_tasks = change
+        .plan
+        .as_deref()
+        .map(crate::implementation::parse_plan_tasks)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|title| crate::app_state::ImplementationTask { title, done: false })
+        .collect();
+    change.updated_at = chrono::Utc::now().to_rfc3339();
+}
@@ -1,10 +1,11 @@
 use crate::actions::{Action, ChatRoleData};
 use crate::app_state::AppState;
+use crate::chat_attachments;
 use uuid::Uuid;
 
 pub fn reduce(state: &mut AppState, action: Action) {
     match action {
-        Action::SendChatMessage { text } => {
+        Action::SendChatMessage { text, attachments, .. } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
                     worktree.chat.is_typing = true;
@@ -17,6 +18,9 @@ pub fn reduce(state: &mut AppState, action: Action) {
                         content: text,
                         timestamp: chrono::Utc::now().to_rfc3339(),
                         is_streaming: false,
+                        is_cancelled: false,
+                        attachments: attachments.iter().map(|path| chat_attachments::describe(path)).collect(),
+                        tool_calls: Vec::new(),
                     };
                     worktree.chat.add_message(user_msg);
                 }
@@ -36,6 +40,9 @@ pub fn reduce(state: &mut AppState, action: Action) {
                         content: message.content,
                         timestamp: message.timestamp,
                         is_streaming: message.is_streaming,
+                        is_cancelled: false,
+                        attachments: message.attachments,
+                        tool_calls: Vec::new(),
                     };
                     worktree.chat.add_message(chat_message);
                 }
@@ -50,6 +57,39 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
+        Action::AddChatToolCall { tool_call } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.chat.add_tool_call(tool_call);
+                }
+            }
+        }
+
+        Action::CompleteChatToolCall { id, result_excerpt, is_error, duration_ms } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.chat.complete_tool_call(&id, result_excerpt, is_error, duration_ms);
+                }
+            }
+        }
+
+        Action::RequestToolApproval { approval } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.chat.pending_tool_approval = Some(approval);
+                }
+            }
+        }
+
+        // The decision is sent to the CLI asynchronously
+        Action::ApproveToolUse { request_id } | Action::DenyToolUse { request_id } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.chat.resolve_tool_approval(&request_id);
+                }
+            }
+        }
+
         Action::SetChatTyping { is_typing } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
@@ -85,6 +125,22 @@ pub fn reduce(state: &mut AppState, action: Action) {
                 }
             }
         }
+
+        Action::SetChatSessionId { session_id } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.chat.session_id = Some(session_id);
+                }
+            }
+        }
+
+        Action::CancelChatMessage { message_id } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.chat.cancel_message(&message_id);
+                }
+            }
+        }
         _ => {}
     }
 }
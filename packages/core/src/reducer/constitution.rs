@@ -84,6 +84,20 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
+        Action::CancelConstitutionGeneration => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(workflow) = &mut worktree.tasks.constitution_workflow {
+                        // Keep the answers so generation can be retried
+                        if workflow.status == crate::app_state::WorkflowStatus::Generating {
+                            workflow.status = crate::app_state::WorkflowStatus::Collecting;
+                        }
+                        workflow.output.clear();
+                    }
+                }
+            }
+        }
+
         Action::SetConstitutionExists { exists } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
@@ -100,6 +114,23 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
+        Action::LintConstitution => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.tasks.constitution_linting = true;
+                }
+            }
+        }
+
+        Action::SetConstitutionLintReport { report } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.tasks.constitution_lint = Some(report);
+                    worktree.tasks.constitution_linting = false;
+                }
+            }
+        }
+
         Action::SetClaudeMdExists { exists } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
@@ -1,12 +1,14 @@
 use crate::actions::{
     DockerServiceData, JustCommandData, TaskStatusData, McpStatusData, 
-    PortConflictData, ConflictingContainerData, FileEntryData, CommentData,
+    PortConflictData, ConflictingContainerData, ConflictingProcessData, FileEntryData, CommentData,
     ReviewPolicyData, ReviewContentTypeData, ReviewFileActionData, ReviewStatusData,
+    WorktreeHealthData, LastCommitData,
 };
 use crate::app_state::{
     DockerServiceInfo, ServiceStatus, ServiceType, JustCommandInfo, TaskStatus,
-    McpStatus, PortConflict, ConflictingContainer, FileEntry, Comment,
+    McpStatus, PortConflict, ConflictingContainer, ConflictingProcess, FileEntry, Comment,
     ReviewPolicy, ReviewContentType, ReviewFileAction, ReviewStatus,
+    WorktreeHealth, LastCommit,
 };
 
 impl From<DockerServiceData> for DockerServiceInfo {
@@ -41,6 +43,7 @@ impl From<JustCommandData> for JustCommandInfo {
             name: data.name,
             description: data.description,
             recipe: data.recipe,
+            parameters: data.parameters,
         }
     }
 }
@@ -72,6 +75,7 @@ impl From<PortConflictData> for PortConflict {
         Self {
             requested_port: data.requested_port,
             conflicting_container: data.conflicting_container.into(),
+            conflicting_process: data.conflicting_process.map(Into::into),
             suggested_port: data.suggested_port,
         }
     }
@@ -88,6 +92,18 @@ impl From<ConflictingContainerData> for ConflictingContainer {
     }
 }
 
+impl From<ConflictingProcessData> for ConflictingProcess {
+    fn from(data: ConflictingProcessData) -> Self {
+        Self {
+            pid: data.pid,
+            name: data.name,
+            exe: data.exe,
+            user: data.user,
+            command: data.command,
+        }
+    }
+}
+
 impl From<FileEntryData> for FileEntry {
     fn from(data: FileEntryData) -> Self {
         Self {
@@ -156,4 +172,27 @@ impl From<ReviewStatusData> for ReviewStatus {
             ReviewStatusData::Rejected => ReviewStatus::Rejected,
         }
     }
-}
\ No newline at end of file
+}
+
+impl From<WorktreeHealthData> for WorktreeHealth {
+    fn from(data: WorktreeHealthData) -> Self {
+        Self {
+            dirty_files: data.dirty_files,
+            upstream: data.upstream,
+            ahead: data.ahead,
+            behind: data.behind,
+            last_commit: data.last_commit.map(Into::into),
+            is_stale: data.is_stale,
+        }
+    }
+}
+
+impl From<LastCommitData> for LastCommit {
+    fn from(data: LastCommitData) -> Self {
+        Self {
+            hash: data.hash,
+            summary: data.summary,
+            timestamp: data.timestamp,
+        }
+    }
+}
@@ -27,6 +27,10 @@ pub fn reduce(state: &mut AppState, action: Action) {
         Action::ClearDevLogs => {
             state.clear_dev_logs();
         }
+
+        Action::SetAppLogs { logs } => {
+            state.app_logs = logs;
+        }
         _ => {}
     }
 }
@@ -1,5 +1,5 @@
 use crate::actions::Action;
-use crate::app_state::{AppState, ServiceStatus, PendingConflict};
+use crate::app_state::{AppError, AppState, DatabaseInfo, ImageLayerProgress, ImagePullState, ServiceHealth, ServiceStatus, PendingConflict, ProjectState, MAX_STATS_SAMPLES};
 
 pub fn reduce(state: &mut AppState, action: Action) {
     match action {
@@ -18,10 +18,34 @@ pub fn reduce(state: &mut AppState, action: Action) {
 
         Action::SetDockerServices { services } => {
             state.docker.services = services.into_iter().map(|s| s.into()).collect();
+            // Docker reports "running" as soon as the container is up; only
+            // show Running once the health check has passed
+            for service in &mut state.docker.services {
+                match state.docker.service_health.get(&service.id) {
+                    Some(ServiceHealth::Starting) if service.status == ServiceStatus::Running => {
+                        service.status = ServiceStatus::Starting;
+                    }
+                    Some(ServiceHealth::Unhealthy) => service.status = ServiceStatus::Error,
+                    _ => {}
+                }
+            }
             state.docker.is_loading = false;
         }
 
+        Action::SetServiceHealth { service_id, health } => {
+            if let Some(service) = state
+                .docker
+                .services
+                .iter_mut()
+                .find(|s| s.id == service_id)
+            {
+                service.status = health.status();
+            }
+            state.docker.service_health.insert(service_id, health);
+        }
+
         Action::StartDockerService { service_id } => {
+            record_project_service(state, &service_id);
             if let Some(service) = state
                 .docker
                 .services
@@ -33,6 +57,12 @@ pub fn reduce(state: &mut AppState, action: Action) {
         }
 
         Action::StopDockerService { service_id } => {
+            for project in &mut state.projects {
+                project.docker_services.retain(|id| *id != service_id);
+            }
+            state.docker.stats.remove(&service_id);
+            state.docker.databases.remove(&service_id);
+            state.docker.service_health.remove(&service_id);
             if let Some(service) = state
                 .docker
                 .services
@@ -88,6 +118,7 @@ pub fn reduce(state: &mut AppState, action: Action) {
         }
 
         Action::StartDockerServiceWithPort { ref service_id, port } => {
+            record_project_service(state, service_id);
             state.docker.port_overrides.insert(service_id.clone(), port);
             state.docker.pending_conflict = None;
             if let Some(service) = state
@@ -112,6 +143,145 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
+        Action::KillProcessOnPort { service_id: Some(service_id), .. } => {
+            state.docker.pending_conflict = None;
+            if let Some(service) = state
+                .docker
+                .services
+                .iter_mut()
+                .find(|s| s.id == service_id)
+            {
+                service.status = ServiceStatus::Starting;
+            }
+        }
+
+        Action::KillProcessOnPort { service_id: None, .. } => {
+            // Async trigger
+        }
+
+        Action::DockerComposeUp { project_name } => {
+            for service in state
+                .docker
+                .services
+                .iter_mut()
+                .filter(|s| s.project_group.as_deref() == Some(project_name.as_str()))
+            {
+                if service.status != ServiceStatus::Running {
+                    service.status = ServiceStatus::Starting;
+                }
+            }
+        }
+
+        Action::DockerComposeDown { project_name } => {
+            for service in state
+                .docker
+                .services
+                .iter_mut()
+                .filter(|s| s.project_group.as_deref() == Some(project_name.as_str()))
+            {
+                if service.status == ServiceStatus::Running {
+                    service.status = ServiceStatus::Stopping;
+                }
+            }
+        }
+
+        Action::AddComposeFile { path } => {
+            if let Some(project) = state.active_project_mut() {
+                if !project.compose_files.contains(&path) {
+                    project.compose_files.push(path);
+                    if std::path::Path::new(&project.path).exists() {
+                        let _ = crate::persistence::save_project(project);
+                    }
+                }
+            }
+        }
+
+        Action::LoadServiceGroups => {
+            // Async trigger
+        }
+
+        Action::SetServiceGroups { project_path, groups, error } => {
+            if let Some(project) = state.projects.iter_mut().find(|p| p.path == project_path) {
+                // A broken services file keeps the groups that were already loaded
+                if error.is_none() {
+                    project.service_groups = groups;
+                }
+                project.service_groups_error = error;
+            }
+        }
+
+        Action::AssignServiceToGroup { service_id, group } => {
+            if let Some(project) = editable_service_groups(state) {
+                crate::service_groups::assign(&mut project.service_groups, &service_id, group.as_deref());
+            }
+        }
+
+        Action::DeleteServiceGroup { name } => {
+            if let Some(project) = editable_service_groups(state) {
+                project.service_groups.retain(|g| g.name != name);
+            }
+        }
+
+        Action::StartServiceGroup { name } => {
+            let members = service_group_members(state, &name);
+            for service_id in &members {
+                record_project_service(state, service_id);
+            }
+            for service in state.docker.services.iter_mut().filter(|s| members.contains(&s.id)) {
+                if service.status != ServiceStatus::Running {
+                    service.status = ServiceStatus::Starting;
+                }
+            }
+        }
+
+        Action::StopServiceGroup { name } => {
+            let members = service_group_members(state, &name);
+            for service in state.docker.services.iter_mut().filter(|s| members.contains(&s.id)) {
+                if service.status == ServiceStatus::Running {
+                    service.status = ServiceStatus::Stopping;
+                }
+            }
+        }
+
+        Action::OpenDockerExec { .. } | Action::CloseDockerExec { .. } => {
+            // Session is set by SetDockerExecSession once the exec starts or ends
+        }
+
+        Action::SetDockerExecSession { service_id, session_id } => match session_id {
+            Some(session_id) => {
+                state.docker.exec_sessions.insert(service_id, session_id);
+            }
+            None => {
+                state.docker.exec_sessions.remove(&service_id);
+            }
+        },
+
+        Action::StartProxy { port } => {
+            state.global_settings.proxy.enabled = true;
+            if port.is_some() {
+                state.global_settings.proxy.port = port;
+            }
+            state.docker.proxy.error = None;
+        }
+
+        Action::StopProxy => {
+            state.global_settings.proxy.enabled = false;
+        }
+
+        Action::SetProxyRoutes { routes } => match crate::proxy::normalize_routes(routes) {
+            Ok(routes) => {
+                state.global_settings.proxy.routes = routes;
+                if state.error.as_ref().is_some_and(|e| e.code == "proxy_routes") {
+                    state.error = None;
+                }
+            }
+            Err(message) => state.error = Some(AppError::new("proxy_routes", message)),
+        },
+
+        Action::SetProxyStatus { status } => {
+            state.docker.proxy = status;
+        }
+
         Action::SetDockerLoading { is_loading } => {
             state.docker.is_loading = is_loading;
         }
@@ -119,6 +289,118 @@ pub fn reduce(state: &mut AppState, action: Action) {
         Action::SetDockerLogsLoading { is_loading } => {
             state.docker.is_loading_logs = is_loading;
         }
+
+        Action::SetImagePullProgress { image, progress } => {
+            let pull = state.docker.image_pulls.entry(image).or_default();
+            // First event of a new pull replaces the finished one
+            if !pull.is_pulling {
+                *pull = ImagePullState {
+                    is_pulling: true,
+                    ..Default::default()
+                };
+            }
+
+            match progress.layer_id {
+                // "<tag>: Pulling from <repo>" carries the tag as its id
+                Some(id) if !progress.status.starts_with("Pulling from") => {
+                    match pull.layers.iter_mut().find(|l| l.id == id) {
+                        Some(layer) => {
+                            layer.status = progress.status;
+                            layer.current = progress.current;
+                            layer.total = progress.total;
+                        }
+                        None => pull.layers.push(ImageLayerProgress {
+                            id,
+                            status: progress.status,
+                            current: progress.current,
+                            total: progress.total,
+                        }),
+                    }
+                }
+                _ => pull.status = progress.status,
+            }
+        }
+
+        Action::SetDockerStats { service_id, stats } => {
+            let samples = state.docker.stats.entry(service_id).or_default();
+            samples.push(stats);
+            if samples.len() > MAX_STATS_SAMPLES {
+                samples.remove(0);
+            }
+        }
+
+        Action::FetchDockerDatabases { .. } | Action::FetchDockerTables { .. } => {
+            state.docker.is_loading_databases = true;
+        }
+
+        Action::SetDockerDatabases { service_id, databases } => {
+            // Keep tables already listed for databases that still exist
+            let previous = state.docker.databases.remove(&service_id).unwrap_or_default();
+            let databases = databases
+                .into_iter()
+                .map(|mut db| {
+                    db.tables = previous
+                        .iter()
+                        .find(|p| p.name == db.name)
+                        .and_then(|p| p.tables.clone());
+                    db
+                })
+                .collect();
+            state.docker.databases.insert(service_id, databases);
+            state.docker.is_loading_databases = false;
+        }
+
+        Action::SetDockerTables { service_id, db_name, tables } => {
+            let databases = state.docker.databases.entry(service_id).or_default();
+            match databases.iter_mut().find(|db| db.name == db_name) {
+                Some(db) => db.tables = Some(tables),
+                None => databases.push(DatabaseInfo {
+                    name: db_name,
+                    size_bytes: None,
+                    tables: Some(tables),
+                }),
+            }
+            state.docker.is_loading_databases = false;
+        }
+
+        Action::SetDockerDatabasesLoading { is_loading } => {
+            state.docker.is_loading_databases = is_loading;
+        }
+
+        Action::FinishImagePull { image, error } => {
+            let pull = state.docker.image_pulls.entry(image).or_default();
+            pull.is_pulling = false;
+            pull.error = error;
+        }
         _ => {}
     }
 }
+
+/// Remember that the focused project uses `service_id`
+fn record_project_service(state: &mut AppState, service_id: &str) {
+    if let Some(project) = state.active_project_mut() {
+        if !project.docker_services.iter().any(|id| id == service_id) {
+            project.docker_services.push(service_id.to_string());
+        }
+    }
+}
+
+/// Services of the active project's group `name`
+fn service_group_members(state: &AppState, name: &str) -> Vec<String> {
+    state
+        .active_project()
+        .and_then(|p| p.service_groups.iter().find(|g| g.name == name))
+        .map(|g| g.services.clone())
+        .unwrap_or_default()
+}
+
+/// The active project, unless its services file failed to load (editing
+/// would overwrite it)
+fn editable_service_groups(state: &mut AppState) -> Option<&mut ProjectState> {
+    let error = state.active_project()?.service_groups_error.clone();
+    if let Some(error) = error {
+        state.error = Some(AppError::new("service_groups", format!("Fix the services file first: {}", error)));
+        return None;
+    }
+    state.active_project_mut()
+}
@@ -0,0 +1,66 @@
+use crate::actions::Action;
+use crate::app_state::{AppState, ChatRole};
+use crate::edits::{self, ProposedEditSet, ProposedEditStatus};
+
+/// Tools the agent edits files with itself (their changes are already on disk)
+const EDIT_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write", "NotebookEdit"];
+
+pub fn reduce(state: &mut AppState, action: Action) {
+    let Some(chat) = state
+        .active_project_mut()
+        .and_then(|p| p.active_worktree_mut())
+        .map(|w| &mut w.chat)
+    else {
+        return;
+    };
+
+    match action {
+        Action::ProposeEdits { message_id } => {
+            let Some(message) = chat
+                .messages
+                .iter()
+                .find(|m| m.id == message_id && m.role == ChatRole::Assistant && !m.is_cancelled)
+            else {
+                return;
+            };
+            if message.tool_calls.iter().any(|call| EDIT_TOOLS.contains(&call.name.as_str())) {
+                return;
+            }
+            let found = edits::extract_edits(&message.content);
+            if !found.is_empty() {
+                chat.proposed_edits = Some(ProposedEditSet {
+                    message_id,
+                    edits: found,
+                    backup_dir: None,
+                });
+            }
+        }
+
+        Action::AcceptProposedEdit { path } => {
+            if let Some(set) = chat.proposed_edits.as_mut() {
+                set.set_status(&path, ProposedEditStatus::Accepted);
+            }
+        }
+
+        Action::RejectProposedEdit { path } => {
+            if let Some(set) = chat.proposed_edits.as_mut() {
+                set.set_status(&path, ProposedEditStatus::Rejected);
+            }
+        }
+
+        Action::SetProposedEditsApplied { paths, backup_dir } => {
+            if let Some(set) = chat.proposed_edits.as_mut() {
+                set.mark_applied(&paths, backup_dir);
+            }
+        }
+
+        Action::DismissProposedEdits => {
+            chat.proposed_edits = None;
+        }
+
+        // Writing files is async
+        Action::ApplyProposedEdits => {}
+
+        _ => {}
+    }
+}
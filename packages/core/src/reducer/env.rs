@@ -1,5 +1,5 @@
 use crate::actions::Action;
-use crate::app_state::{AppState, EnvCopyResult};
+use crate::app_state::{AppState, EnvCopyResult, Notification};
 
 pub fn reduce(state: &mut AppState, action: Action) {
     match action {
@@ -31,6 +31,41 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
+        Action::InjectConnectionString { .. }
+        | Action::DiffEnvFiles { .. }
+        | Action::SyncEnvKeys { .. }
+        | Action::ScanEnvSecrets => {
+            // Async triggers
+        }
+
+        Action::SetEnvSecrets { secrets } => {
+            if let Some(project) = state.active_project_mut() {
+                project.env_config.secrets = secrets;
+            }
+        }
+
+        Action::SetEnvDiff { diff } => {
+            if let Some(project) = state.active_project_mut() {
+                project.env_config.last_diff = diff;
+            }
+        }
+
+        Action::SetEnvInjectionPreview { preview } => {
+            if let Some(project) = state.active_project_mut() {
+                project.env_config.injection_preview = preview;
+            }
+        }
+
+        Action::SetAutoResolvePorts { strategy } => {
+            if let Some(project) = state.active_project_mut() {
+                project.env_config.auto_resolve_ports = strategy;
+                // Persist project setting (only for real paths)
+                if std::path::Path::new(&project.path).exists() {
+                    let _ = crate::persistence::save_project(project);
+                }
+            }
+        }
+
         Action::SetAgentRulesEnabled { enabled } => {
             if let Some(project) = state.active_project_mut() {
                 project.agent_rules_config.enabled = enabled;
@@ -54,6 +89,7 @@ pub fn reduce(state: &mut AppState, action: Action) {
                         is_builtin: false,
                         created_at: now.clone(),
                         updated_at: now,
+                        metadata: None,
                     };
                     project.agent_rules_config.profiles.push(new_profile.clone());
                     project.agent_rules_config.active_profile_id = Some(new_profile.id);
@@ -77,11 +113,24 @@ pub fn reduce(state: &mut AppState, action: Action) {
                     is_builtin: false,
                     created_at: now.clone(),
                     updated_at: now,
+                    metadata: None,
                 };
                 project.agent_rules_config.profiles.push(profile);
             }
         }
 
+        Action::ImportAgentProfile { profile } => {
+            if let Err(e) = crate::agent_rules::validate_profile_file(&profile) {
+                state.notifications.push(Notification::error(format!("Invalid agent profile: {}", e)));
+                return;
+            }
+            if let Some(project) = state.active_project_mut() {
+                let message = format!("Imported agent profile \"{}\" v{}", profile.name, profile.version);
+                project.agent_rules_config.profiles.push(profile.into_profile());
+                state.notifications.push(Notification::success(message));
+            }
+        }
+
         Action::UpdateAgentProfile { id, name, prompt } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(profile) = project.agent_rules_config.profiles.iter_mut().find(|p| p.id == id && !p.is_builtin) {
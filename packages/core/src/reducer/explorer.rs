@@ -28,16 +28,53 @@ pub fn reduce(state: &mut AppState, action: Action) {
         }
 
         Action::SetFileComments { comments, .. } => {
-            eprintln!("[Reducer] SetFileComments: Received {} comments", comments.len());
+            tracing::debug!("SetFileComments: Received {} comments", comments.len());
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
                     worktree.explorer.selected_comments = comments.into_iter().map(|c| c.into()).collect();
-                    eprintln!("[Reducer] SetFileComments: Updated selected_comments to {} items", worktree.explorer.selected_comments.len());
+                    tracing::debug!("SetFileComments: Updated selected_comments to {} items", worktree.explorer.selected_comments.len());
                 } else {
-                    eprintln!("[Reducer] SetFileComments: No active worktree!");
+                    tracing::warn!("SetFileComments: No active worktree!");
                 }
             } else {
-                eprintln!("[Reducer] SetFileComments: No active project!");
+                tracing::warn!("SetFileComments: No active project!");
+            }
+        }
+
+        Action::SearchWorkspace { query, glob, case_sensitive } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    let search = &mut worktree.search;
+                    search.is_searching = !query.is_empty();
+                    if query.is_empty() {
+                        search.results = Default::default();
+                    }
+                    search.query = query;
+                    search.glob = glob;
+                    search.case_sensitive = case_sensitive;
+                    search.error = None;
+                }
+            }
+        }
+
+        Action::SetSearchResults { query, results, error } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    // A newer search is in flight; drop stale results
+                    if worktree.search.query == query {
+                        worktree.search.results = results;
+                        worktree.search.error = error;
+                        worktree.search.is_searching = false;
+                    }
+                }
+            }
+        }
+
+        Action::ClearSearch => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.search = Default::default();
+                }
             }
         }
 
@@ -82,12 +119,39 @@ pub fn reduce(state: &mut AppState, action: Action) {
         Action::SelectFile { path } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
+                    if worktree.explorer.file_history.as_ref().map(|h| &h.path) != path.as_ref() {
+                        worktree.explorer.file_history = None;
+                    }
                     worktree.explorer.selected_path = path;
                     worktree.explorer.selected_comments.clear();
                 }
             }
         }
 
+        Action::LoadFileHistory { path } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.explorer.file_history = Some(crate::app_state::FileHistory {
+                        path,
+                        is_loading: true,
+                        ..Default::default()
+                    });
+                }
+            }
+        }
+
+        Action::SetFileHistory { path, commits, error } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(history) = worktree.explorer.file_history.as_mut().filter(|h| h.path == path) {
+                        history.commits = commits;
+                        history.error = error;
+                        history.is_loading = false;
+                    }
+                }
+            }
+        }
+
         Action::SetExplorerSort { field, direction } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
@@ -223,6 +287,39 @@ pub fn reduce(state: &mut AppState, action: Action) {
         | Action::AddFileComment { .. }
         | Action::DeleteFileComment { .. } => {}
 
+        Action::GitStage { .. } | Action::GitPush | Action::GitPull => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.explorer.git_busy = true;
+                }
+            }
+        }
+
+        Action::GitCommit { .. } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.explorer.git_busy = true;
+                    worktree.explorer.security_scan = None;
+                }
+            }
+        }
+
+        Action::SetGitBusy { is_busy } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.explorer.git_busy = is_busy;
+                }
+            }
+        }
+
+        Action::SetSecurityScanResult { result } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.explorer.security_scan = result;
+                }
+            }
+        }
+
         _ => {}
     }
 }
\ No newline at end of file
@@ -1,5 +1,6 @@
 use crate::actions::Action;
 use crate::app_state::AppState;
+use crate::file_reader::content_hash;
 
 pub fn reduce(state: &mut AppState, action: Action) {
     match action {
@@ -7,14 +8,20 @@ pub fn reduce(state: &mut AppState, action: Action) {
             state.file_viewer.path = Some(path);
             state.file_viewer.is_loading = true;
             state.file_viewer.content = None;
+            state.file_viewer.content_hash = None;
+            state.file_viewer.binary_preview = None;
+            state.file_viewer.is_dirty = false;
             state.file_viewer.error = None;
         }
 
         Action::SetFileContent { path, content, error } => {
             state.file_viewer.path = Some(path);
+            state.file_viewer.content_hash = content.as_deref().map(content_hash);
             state.file_viewer.content = content;
             state.file_viewer.error = error;
             state.file_viewer.is_loading = false;
+            state.file_viewer.is_saving = false;
+            state.file_viewer.is_dirty = false;
         }
 
         Action::SetFileLoading { is_loading } => {
@@ -25,18 +32,36 @@ pub fn reduce(state: &mut AppState, action: Action) {
             state.file_viewer.path = Some(path);
             state.file_viewer.is_loading = true;
             state.file_viewer.binary_content = None;
+            state.file_viewer.binary_preview = None;
             state.file_viewer.content = None;
+            state.file_viewer.content_hash = None;
+            state.file_viewer.is_dirty = false;
             state.file_viewer.error = None;
         }
 
-        Action::SetBinaryFileContent { path, content, error } => {
+        Action::SetBinaryFileContent { path, content, preview, error } => {
             state.file_viewer.path = Some(path);
             state.file_viewer.binary_content = content;
+            state.file_viewer.binary_preview = preview;
             state.file_viewer.content = None; // Clear text content
             state.file_viewer.error = error;
             state.file_viewer.is_loading = false;
         }
 
+        Action::WriteFile { path, .. } if state.file_viewer.path.as_deref() == Some(path.as_str()) => {
+            state.file_viewer.is_saving = true;
+            state.file_viewer.error = None;
+        }
+
+        Action::SetFileDirty { is_dirty } => {
+            state.file_viewer.is_dirty = is_dirty;
+        }
+
+        Action::SetFileSaveError { path, error } if state.file_viewer.path.as_deref() == Some(path.as_str()) => {
+            state.file_viewer.is_saving = false;
+            state.file_viewer.error = Some(error);
+        }
+
         _ => {}
     }
 }
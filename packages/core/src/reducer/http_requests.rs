@@ -0,0 +1,54 @@
+use crate::actions::Action;
+use crate::app_state::{AppState, HttpRunResult};
+
+pub fn reduce(state: &mut AppState, action: Action) {
+    match action {
+        Action::LoadHttpRequests => {
+            // Collections are loaded in handle_async_action
+        }
+
+        Action::SetHttpRequests { project_path, collections } => {
+            if let Some(project) = state.projects.iter_mut().find(|p| p.path == project_path) {
+                let requests = &mut project.http_requests;
+                // Drop results of requests that no longer exist
+                requests.results.retain(|key, _| {
+                    collections
+                        .iter()
+                        .any(|c| c.requests.iter().any(|r| *key == request_key(&c.id, &r.name)))
+                });
+                requests.collections = collections;
+            }
+        }
+
+        Action::RunHttpRequest { collection, request, .. } => {
+            if let Some(project) = state.active_project_mut() {
+                let key = request_key(&collection, &request);
+                if !project.http_requests.running.contains(&key) {
+                    project.http_requests.running.push(key);
+                }
+            }
+        }
+
+        Action::SetHttpResponse { project_path, collection, request, response, error } => {
+            if let Some(project) = state.projects.iter_mut().find(|p| p.path == project_path) {
+                let key = request_key(&collection, &request);
+                project.http_requests.running.retain(|k| *k != key);
+                project.http_requests.results.insert(
+                    key,
+                    HttpRunResult {
+                        response,
+                        error,
+                        finished_at: chrono::Utc::now().to_rfc3339(),
+                    },
+                );
+            }
+        }
+
+        _ => {}
+    }
+}
+
+/// Key of a request in `running` and `results`
+pub fn request_key(collection: &str, request: &str) -> String {
+    format!("{}/{}", collection, request)
+}
@@ -1,5 +1,5 @@
 use crate::actions::{Action, McpLogDirectionData};
-use crate::app_state::{AppState, McpStatus};
+use crate::app_state::{AppState, ExternalMcpStatus, McpStatus};
 
 pub fn reduce(state: &mut AppState, action: Action) {
     match action {
@@ -55,22 +55,25 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
-        Action::AddMcpLogEntry { entry } => {
-            if let Some(project) = state.active_project_mut() {
-                if let Some(worktree) = project.active_worktree_mut() {
-                    let log_entry = crate::app_state::McpLogEntry {
-                        timestamp: entry.timestamp,
-                        direction: match entry.direction {
-                            McpLogDirectionData::In => crate::app_state::McpLogDirection::In,
-                            McpLogDirectionData::Out => crate::app_state::McpLogDirection::Out,
-                        },
-                        method: entry.method,
-                        tool_name: entry.tool_name,
-                        payload: entry.payload,
-                        is_error: entry.is_error,
-                    };
-                    worktree.mcp.add_log_entry(log_entry);
-                }
+        Action::AddMcpLogEntry { worktree_id, entry } => {
+            let worktree = state
+                .projects
+                .iter_mut()
+                .flat_map(|p| p.worktrees.iter_mut())
+                .find(|w| w.id == worktree_id);
+            if let Some(worktree) = worktree {
+                let log_entry = crate::app_state::McpLogEntry {
+                    timestamp: entry.timestamp,
+                    direction: match entry.direction {
+                        McpLogDirectionData::In => crate::app_state::McpLogDirection::In,
+                        McpLogDirectionData::Out => crate::app_state::McpLogDirection::Out,
+                    },
+                    method: entry.method,
+                    tool_name: entry.tool_name,
+                    payload: entry.payload,
+                    is_error: entry.is_error,
+                };
+                worktree.mcp.add_log_entry(log_entry);
             }
         }
 
@@ -96,6 +99,82 @@ pub fn reduce(state: &mut AppState, action: Action) {
                 }
             }
         }
+
+        Action::SetMcpPolicy { policy } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.mcp.policy = policy;
+                }
+            }
+        }
+
+        Action::ConnectExternalMcpServers => {
+            if let Some(worktree) = state.active_project_mut().and_then(|p| p.active_worktree_mut()) {
+                for server in &mut worktree.mcp.external_servers {
+                    server.status = ExternalMcpStatus::Connecting;
+                    server.error = None;
+                }
+            }
+        }
+
+        Action::DisconnectExternalMcpServers => {
+            if let Some(worktree) = state.active_project_mut().and_then(|p| p.active_worktree_mut()) {
+                worktree.mcp.external_servers.clear();
+            }
+        }
+
+        Action::SetExternalMcpServers { servers } => {
+            if let Some(worktree) = state.active_project_mut().and_then(|p| p.active_worktree_mut()) {
+                worktree.mcp.external_servers = servers;
+            }
+        }
+
+        // Recorded by StartMcpPlaygroundCall once the call is made
+        Action::CallExternalMcpTool { .. } => {}
+
+        Action::StartMcpPlaygroundCall { entry } => {
+            if let Some(worktree) = state.active_project_mut().and_then(|p| p.active_worktree_mut()) {
+                worktree.mcp.playground.push(entry);
+            }
+        }
+
+        Action::FinishMcpPlaygroundCall { id, duration_ms, result, error } => {
+            let entry = state
+                .projects
+                .iter_mut()
+                .flat_map(|p| p.worktrees.iter_mut())
+                .flat_map(|w| w.mcp.playground.history.iter_mut())
+                .find(|e| e.id == id);
+            if let Some(entry) = entry {
+                entry.duration_ms = Some(duration_ms);
+                entry.result = result;
+                entry.error = error;
+            }
+        }
+
+        Action::ClearMcpPlayground => {
+            if let Some(worktree) = state.active_project_mut().and_then(|p| p.active_worktree_mut()) {
+                worktree.mcp.playground.history.clear();
+            }
+        }
+
+        Action::UpdateMcpMetrics { metrics } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    worktree.mcp.tool_metrics = metrics
+                        .into_iter()
+                        .map(|m| crate::app_state::McpToolMetrics {
+                            tool_name: m.tool_name,
+                            call_count: m.call_count,
+                            error_count: m.error_count,
+                            p50_ms: m.p50_ms,
+                            p95_ms: m.p95_ms,
+                            last_called_at: m.last_called_at,
+                        })
+                        .collect();
+                }
+            }
+        }
         _ => {}
     }
 }
@@ -6,6 +6,7 @@ pub mod mcp;
 pub mod notifications;
 pub mod project;
 pub mod tasks;
+pub mod schedule;
 pub mod worktree;
 pub mod terminal;
 pub mod settings;
@@ -18,7 +19,15 @@ pub mod context;
 pub mod constitution;
 pub mod review_gate;
 pub mod env;
+pub mod usage;
+pub mod sessions;
+pub mod activity;
+pub mod workflows;
+pub mod undo;
 pub mod conversions;
+pub mod edits;
+pub mod http_requests;
+pub mod agent_runs;
 
 #[cfg(test)]
 mod tests;
@@ -58,6 +67,23 @@ pub fn reduce(state: &mut AppState, action: Action) {
     // Auto-log actions for dev debugging
     dev_log::log_action_if_interesting(state, &action);
 
+    // Dev-mode action trace
+    let traced = crate::trace::is_recording().then(|| (action.clone(), std::time::Instant::now()));
+
+    // Snapshot undoable slices so the edit can be undone
+    let checkpoint = crate::undo::checkpoint(state, &action);
+    dispatch(state, action);
+    if let Some(checkpoint) = checkpoint {
+        crate::undo::commit(state, checkpoint);
+    }
+
+    if let Some((action, started)) = traced {
+        crate::trace::record(action, state, started.elapsed());
+    }
+}
+
+/// Route an action to its submodule reducer.
+fn dispatch(state: &mut AppState, action: Action) {
     match action {
         Action::OpenProject { .. }
         | Action::CloseProject { .. }
@@ -71,10 +97,21 @@ pub fn reduce(state: &mut AppState, action: Action) {
         | Action::SetWorktrees { .. }
         | Action::AddWorktree { .. }
         | Action::AddWorktreeNewBranch { .. }
+        | Action::StartFeatureWork { .. }
         | Action::RemoveWorktree { .. }
         | Action::FetchBranches
         | Action::SetBranches { .. }
-        | Action::SetBranchesLoading { .. } => {
+        | Action::SetBranchesLoading { .. }
+        | Action::RefreshWorktreeHealth
+        | Action::SetWorktreeHealth { .. }
+        | Action::LoadWorktreeDiff { .. }
+        | Action::SetWorktreeDiff { .. }
+        | Action::RunDependencyAudit
+        | Action::SetDependencyAudit { .. }
+        | Action::RunBuildCheck
+        | Action::SetBuildDiagnostics { .. }
+        | Action::SetLspServerStatus { .. }
+        | Action::StopLspServers => {
             worktree::reduce(state, action);
         }
 
@@ -86,20 +123,46 @@ pub fn reduce(state: &mut AppState, action: Action) {
         | Action::SetMcpError { .. }
         | Action::AddMcpLogEntry { .. }
         | Action::ClearMcpLogs
-        | Action::UpdateMcpTools { .. } => {
+        | Action::UpdateMcpTools { .. }
+        | Action::UpdateMcpMetrics { .. }
+        | Action::SetMcpPolicy { .. }
+        | Action::ConnectExternalMcpServers
+        | Action::DisconnectExternalMcpServers
+        | Action::SetExternalMcpServers { .. }
+        | Action::CallExternalMcpTool { .. }
+        | Action::StartMcpPlaygroundCall { .. }
+        | Action::FinishMcpPlaygroundCall { .. }
+        | Action::ClearMcpPlayground => {
             mcp::reduce(state, action);
         }
 
         Action::SendChatMessage { .. }
+        | Action::SendTemplatedPrompt { .. }
         | Action::AddChatMessage { .. }
         | Action::AppendChatContent { .. }
+        | Action::AddChatToolCall { .. }
+        | Action::CompleteChatToolCall { .. }
+        | Action::RequestToolApproval { .. }
+        | Action::ApproveToolUse { .. }
+        | Action::DenyToolUse { .. }
         | Action::SetChatTyping { .. }
         | Action::SetChatError { .. }
         | Action::ClearChatError
-        | Action::ClearChat => {
+        | Action::ClearChat
+        | Action::SetChatSessionId { .. }
+        | Action::CancelChatMessage { .. } => {
             chat::reduce(state, action);
         }
 
+        Action::ProposeEdits { .. }
+        | Action::AcceptProposedEdit { .. }
+        | Action::RejectProposedEdit { .. }
+        | Action::ApplyProposedEdits
+        | Action::SetProposedEditsApplied { .. }
+        | Action::DismissProposedEdits => {
+            edits::reduce(state, action);
+        }
+
         Action::CheckDockerAvailability
         | Action::SetDockerAvailable { .. }
         | Action::RefreshDockerServices
@@ -117,15 +180,50 @@ pub fn reduce(state: &mut AppState, action: Action) {
         | Action::ClearPortConflict
         | Action::StartDockerServiceWithPort { .. }
         | Action::ResolveConflictByStoppingContainer { .. }
+        | Action::KillProcessOnPort { .. }
+        | Action::DockerComposeUp { .. }
+        | Action::DockerComposeDown { .. }
+        | Action::AddComposeFile { .. }
+        | Action::LoadServiceGroups
+        | Action::SetServiceGroups { .. }
+        | Action::AssignServiceToGroup { .. }
+        | Action::DeleteServiceGroup { .. }
+        | Action::StartServiceGroup { .. }
+        | Action::StopServiceGroup { .. }
+        | Action::OpenDockerExec { .. }
+        | Action::SetDockerExecSession { .. }
+        | Action::CloseDockerExec { .. }
+        | Action::StartProxy { .. }
+        | Action::StopProxy
+        | Action::SetProxyRoutes { .. }
+        | Action::SetProxyStatus { .. }
         | Action::SetDockerLoading { .. }
-        | Action::SetDockerLogsLoading { .. } => {
+        | Action::SetDockerLogsLoading { .. }
+        | Action::SetImagePullProgress { .. }
+        | Action::FinishImagePull { .. }
+        | Action::SetDockerStats { .. }
+        | Action::SetServiceHealth { .. }
+        | Action::FetchDockerDatabases { .. }
+        | Action::SetDockerDatabases { .. }
+        | Action::FetchDockerTables { .. }
+        | Action::SetDockerTables { .. }
+        | Action::SetDockerDatabasesLoading { .. } => {
             docker::reduce(state, action);
         }
 
-        Action::LoadJustfileCommands { .. }
+        Action::LoadJustfileCommands
         | Action::RefreshJustfile
         | Action::SetJustfileCommands { .. }
         | Action::RunJustCommand { .. }
+        | Action::RunJustCommandWithArgs { .. }
+        | Action::LoadTasks { .. }
+        | Action::SetTasks { .. }
+        | Action::RunTask { .. }
+        | Action::QueueTaskRun { .. }
+        | Action::StartTaskRun { .. }
+        | Action::AppendTaskRunOutput { .. }
+        | Action::FinishTaskRun { .. }
+        | Action::CancelTask { .. }
         | Action::SetTaskStatus { .. }
         | Action::SetActiveCommand { .. }
         | Action::AppendTaskOutput { .. }
@@ -160,8 +258,39 @@ pub fn reduce(state: &mut AppState, action: Action) {
             terminal::reduce(state, action);
         }
 
+        Action::LoadSchedule
+        | Action::SetSchedule { .. }
+        | Action::SetSchedulePaused { .. }
+        | Action::MarkScheduleRun { .. } => {
+            schedule::reduce(state, action);
+        }
+
+        Action::LoadHttpRequests
+        | Action::SetHttpRequests { .. }
+        | Action::RunHttpRequest { .. }
+        | Action::SetHttpResponse { .. } => {
+            http_requests::reduce(state, action);
+        }
+
         Action::SetTheme { .. }
-        | Action::SetProjectPath { .. } => {
+        | Action::SetProjectPath { .. }
+        | Action::SetModel { .. }
+        | Action::SetProjectModel { .. }
+        | Action::SetRequireChecklists { .. }
+        | Action::SetCollectCoverage { .. }
+        | Action::SetCheckBuildOnSave { .. }
+        | Action::SetTaskMaxParallel { .. }
+        | Action::SetImplementationTimeout { .. }
+        | Action::SetDesktopNotification { .. }
+        | Action::SetLlmProvider { .. }
+        | Action::SetOpenAiSettings { .. }
+        | Action::SetOllamaSettings { .. }
+        | Action::SetGitHostingSettings { .. }
+        | Action::SetKeybinding { .. }
+        | Action::ResetKeybindings { .. }
+        | Action::SaveSettingsProfile { .. }
+        | Action::DeleteSettingsProfile { .. }
+        | Action::SwitchSettingsProfile { .. } => {
             settings::reduce(state, action);
         }
 
@@ -172,14 +301,25 @@ pub fn reduce(state: &mut AppState, action: Action) {
         | Action::NavigateForward
         | Action::NavigateUp
         | Action::SelectFile { .. }
+        | Action::LoadFileHistory { .. }
+        | Action::SetFileHistory { .. }
         | Action::SetExplorerSort { .. }
         | Action::SetExplorerFilter { .. }
+        | Action::SearchWorkspace { .. }
+        | Action::SetSearchResults { .. }
+        | Action::ClearSearch
         | Action::CreateFile { .. }
         | Action::RenameFile { .. }
         | Action::DeleteFile { .. }
         | Action::RevealInOS { .. }
         | Action::AddFileComment { .. }
         | Action::DeleteFileComment { .. }
+        | Action::GitStage { .. }
+        | Action::GitCommit { .. }
+        | Action::GitPush
+        | Action::GitPull
+        | Action::SetGitBusy { .. }
+        | Action::SetSecurityScanResult { .. }
         | Action::OpenFileTab { .. }
         | Action::PinTab { .. }
         | Action::CloseTab { .. }
@@ -191,7 +331,9 @@ pub fn reduce(state: &mut AppState, action: Action) {
         }
 
         Action::AddDevLog { .. }
-        | Action::ClearDevLogs => {
+        | Action::ClearDevLogs
+        | Action::LoadAppLogs { .. }
+        | Action::SetAppLogs { .. } => {
             dev_log::reduce(state, action);
         }
 
@@ -219,7 +361,10 @@ pub fn reduce(state: &mut AppState, action: Action) {
         | Action::SetFileContent { .. }
         | Action::SetFileLoading { .. }
         | Action::ReadBinaryFile { .. }
-        | Action::SetBinaryFileContent { .. } => {
+        | Action::SetBinaryFileContent { .. }
+        | Action::WriteFile { .. }
+        | Action::SetFileDirty { .. }
+        | Action::SetFileSaveError { .. } => {
             file_viewer::reduce(state, action);
         }
 
@@ -227,21 +372,97 @@ pub fn reduce(state: &mut AppState, action: Action) {
             a2ui::reduce(state, action);
         }
 
+        Action::AddUsageRecord { .. } => {
+            usage::reduce(state, action);
+        }
+
+        Action::LoadSessions
+        | Action::SetSessions { .. }
+        | Action::AddSession { .. }
+        | Action::DeleteSession { .. } => {
+            sessions::reduce(state, action);
+        }
+
+        Action::StartSpecWorkflow { .. }
+        | Action::SetSpecWorkflow { .. }
+        | Action::RunSpecPhase { .. }
+        | Action::AppendSpecPhaseOutput { .. }
+        | Action::CompleteSpecPhase { .. }
+        | Action::FailSpecPhase { .. }
+        | Action::CancelSpecPhase
+        | Action::ClearSpecWorkflow
+        | Action::StartClarifySession { .. }
+        | Action::SetClarifySession { .. }
+        | Action::AnswerClarifyQuestion { .. }
+        | Action::SkipClarifyQuestion
+        | Action::CompleteClarifySession { .. }
+        | Action::FailClarifySession { .. }
+        | Action::ClearClarifySession
+        | Action::LoadSpecTasks { .. }
+        | Action::SetSpecTasks { .. }
+        | Action::ToggleSpecTask { .. }
+        | Action::ReportSpecProgress { .. }
+        | Action::GenerateChecklist { .. }
+        | Action::SetChecklist { .. }
+        | Action::FailChecklist { .. }
+        | Action::LoadChecklists
+        | Action::SetChecklists { .. }
+        | Action::ToggleChecklistItem { .. }
+        | Action::AnalyzeSpec { .. }
+        | Action::CompleteSpecAnalysis { .. }
+        | Action::FailSpecAnalysis { .. }
+        | Action::LoadFeaturesCatalog
+        | Action::SetFeaturesCatalog { .. } => {
+            workflows::reduce(state, action);
+        }
+
+        Action::LoadActivityFeed { .. }
+        | Action::SetActivityFeed { .. }
+        | Action::AddActivity { .. } => {
+            activity::reduce(state, action);
+        }
+
+        Action::SetSystemStats { stats } => {
+            state.system_stats = Some(stats);
+        }
+
+        Action::Undo
+        | Action::Redo
+        | Action::PushUndoEntry { .. } => {
+            undo::reduce(state, action);
+        }
+
         Action::CreateChange { .. }
+        | Action::CreateChangeFromIssue { .. }
         | Action::GenerateProposal { .. }
         | Action::AppendProposalOutput { .. }
         | Action::CompleteProposal { .. }
+        | Action::FailProposal { .. }
+        | Action::CancelProposal { .. }
         | Action::GeneratePlan { .. }
         | Action::AppendPlanOutput { .. }
         | Action::CompletePlan { .. }
         | Action::ApprovePlan { .. }
         | Action::ExecutePlan { .. }
         | Action::AppendImplementationOutput { .. }
+        | Action::CompleteImplementationTask { .. }
+        | Action::StartImplementationTests { .. }
         | Action::CompleteImplementation { .. }
         | Action::FailImplementation { .. }
+        | Action::CancelImplementation { .. }
+        | Action::RequestImplementationApproval { .. }
+        | Action::ResolveImplementationApproval { .. }
+        | Action::SetChangeSnapshot { .. }
+        | Action::RollbackImplementation { .. }
+        | Action::CompleteRollback { .. }
+        | Action::SetChangeTestResults { .. }
+        | Action::SetChangeHookResults { .. }
+        | Action::RunChangeTests { .. }
+        | Action::OverrideTestGate { .. }
         | Action::CancelChange { .. }
         | Action::SelectChange { .. }
         | Action::RefreshChanges
+        | Action::ResolveChangeStatusConflict { .. }
         | Action::SetChanges { .. }
         | Action::SetChangesLoading { .. }
         | Action::AddContextFile { .. }
@@ -250,11 +471,26 @@ pub fn reduce(state: &mut AppState, action: Action) {
         | Action::StartProposalReview { .. }
         | Action::StartPlanReview { .. }
         | Action::SetChangeArchived { .. }
+        | Action::OpenChangePullRequest { .. }
+        | Action::SetChangePullRequest { .. }
         | Action::ValidateContextFile { .. }
         | Action::SetContextValidationResult { .. } => {
             changes::reduce(state, action);
         }
 
+        Action::QueueAgentRun { .. }
+        | Action::AddAgentRun { .. }
+        | Action::StartAgentRun { .. }
+        | Action::AppendAgentRunOutput { .. }
+        | Action::FinishAgentRun { .. }
+        | Action::RequestAgentRunApproval { .. }
+        | Action::ResolveAgentRunApproval { .. }
+        | Action::CancelAgentRun { .. }
+        | Action::SetAgentMaxConcurrent { .. }
+        | Action::ClearFinishedAgentRuns => {
+            agent_runs::reduce(state, action);
+        }
+
         Action::LoadContext
         | Action::SetContext { .. }
         | Action::SetContextLoading { .. }
@@ -281,11 +517,14 @@ pub fn reduce(state: &mut AppState, action: Action) {
         | Action::AppendConstitutionOutput { .. }
         | Action::SaveConstitution
         | Action::SetConstitutionError { .. }
+        | Action::CancelConstitutionGeneration
         | Action::CheckConstitutionExists
         | Action::SetConstitutionExists { .. }
         | Action::ApplyDefaultConstitution
         | Action::ReadConstitution
         | Action::SetConstitutionContent { .. }
+        | Action::LintConstitution
+        | Action::SetConstitutionLintReport { .. }
         | Action::SetClaudeMdExists { .. }
         | Action::ReadClaudeMd
         | Action::SetClaudeMdContent { .. }
@@ -304,6 +543,8 @@ pub fn reduce(state: &mut AppState, action: Action) {
         Action::StartReview { .. }
         | Action::AddReviewComment { .. }
         | Action::ResolveReviewComment { .. }
+        | Action::LoadReviewComments { .. }
+        | Action::SetReviewComments { .. }
         | Action::SubmitReviewFeedback { .. }
         | Action::ApproveReview { .. }
         | Action::RejectReview { .. }
@@ -312,7 +553,10 @@ pub fn reduce(state: &mut AppState, action: Action) {
         | Action::SetReviewGateLoading { .. }
         | Action::SetReviewGateError { .. }
         | Action::SetActiveReviewSession { .. }
-        | Action::ClearReviewSession { .. } => {
+        | Action::ClearReviewSession { .. }
+        | Action::RunComplianceCheck { .. }
+        | Action::SetComplianceCheck { .. }
+        | Action::OverrideComplianceCheck { .. } => {
             review_gate::reduce(state, action);
         }
 
@@ -321,10 +565,19 @@ pub fn reduce(state: &mut AppState, action: Action) {
         | Action::SetEnvTrackedPatterns { .. }
         | Action::SetEnvAutoCopy { .. }
         | Action::SetEnvSourceWorktree { .. }
+        | Action::InjectConnectionString { .. }
+        | Action::DiffEnvFiles { .. }
+        | Action::SetEnvDiff { .. }
+        | Action::SyncEnvKeys { .. }
+        | Action::ScanEnvSecrets
+        | Action::SetEnvSecrets { .. }
+        | Action::SetEnvInjectionPreview { .. }
+        | Action::SetAutoResolvePorts { .. }
         | Action::SetAgentRulesEnabled { .. }
         | Action::SetAgentRulesPrompt { .. }
         | Action::SetAgentRulesTempFile { .. }
         | Action::CreateAgentProfile { .. }
+        | Action::ImportAgentProfile { .. }
         | Action::UpdateAgentProfile { .. }
         | Action::DeleteAgentProfile { .. }
         | Action::SelectAgentProfile { .. } => {
@@ -79,31 +79,27 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
-        Action::CloseProject { index } => {
-            if index < state.projects.len() {
-                // Save project state before closing (only for real paths)
-                let project = &state.projects[index];
-                if std::path::Path::new(&project.path).exists() {
-                    let _ = persistence::save_project(project);
-                }
+        Action::CloseProject { index } if index < state.projects.len() => {
+            // Save project state before closing (only for real paths)
+            let project = &state.projects[index];
+            if std::path::Path::new(&project.path).exists() {
+                let _ = persistence::save_project(project);
+            }
 
-                state.projects.remove(index);
+            state.projects.remove(index);
 
-                // Adjust active index
-                if state.projects.is_empty() {
-                    state.active_project_index = 0;
-                } else if state.active_project_index >= state.projects.len() {
-                    state.active_project_index = state.projects.len() - 1;
-                } else if index < state.active_project_index {
-                    state.active_project_index -= 1;
-                }
+            // Adjust active index
+            if state.projects.is_empty() {
+                state.active_project_index = 0;
+            } else if state.active_project_index >= state.projects.len() {
+                state.active_project_index = state.projects.len() - 1;
+            } else if index < state.active_project_index {
+                state.active_project_index -= 1;
             }
         }
 
-        Action::SwitchProject { index } => {
-            if index < state.projects.len() {
-                state.active_project_index = index;
-            }
+        Action::SwitchProject { index } if index < state.projects.len() => {
+            state.active_project_index = index;
         }
 
         Action::SetFeatureTab { tab } => {
@@ -1,5 +1,5 @@
 use crate::actions::Action;
-use crate::app_state::AppState;
+use crate::app_state::{AppState, Notification, NotificationType};
 
 pub fn reduce(state: &mut AppState, action: Action) {
     match action {
@@ -38,6 +38,7 @@ pub fn reduce(state: &mut AppState, action: Action) {
                         },
                         comments: vec![],
                         iteration: 1,
+                        compliance: None,
                         created_at: now.clone(),
                         updated_at: now,
                     };
@@ -48,13 +49,29 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
-        Action::AddReviewComment { session_id, target, content } => {
+        Action::AddReviewComment { session_id, target, content, anchor, parent_id } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
+                    // The change this review belongs to keeps the whole discussion
+                    let change = worktree
+                        .changes
+                        .changes
+                        .iter_mut()
+                        .find(|c| c.has_review_session(&session_id));
                     if let Some(session) = worktree.tasks.review_gate.sessions.get_mut(&session_id) {
                         let comment_id = uuid::Uuid::new_v4().to_string();
                         let now = chrono::Utc::now().to_rfc3339();
 
+                        // Replies share the anchor of the comment they answer
+                        let parent_anchor = parent_id.as_ref().and_then(|parent_id| {
+                            session
+                                .comments
+                                .iter()
+                                .chain(change.iter().flat_map(|c| c.review_comments.iter()))
+                                .find(|c| &c.id == parent_id)
+                                .and_then(|c| c.anchor.clone())
+                        });
+
                         let comment = crate::app_state::ReviewComment {
                             id: comment_id,
                             target: match target {
@@ -66,8 +83,19 @@ pub fn reduce(state: &mut AppState, action: Action) {
                             author: crate::app_state::CommentAuthor::User,
                             resolved: false,
                             created_at: now.clone(),
+                            anchor: anchor
+                                .map(|a| crate::app_state::CommentAnchor {
+                                    file: a.file,
+                                    line_start: a.line_start,
+                                    line_end: a.line_end.max(a.line_start),
+                                })
+                                .or(parent_anchor),
+                            parent_id,
                         };
 
+                        if let Some(change) = change {
+                            change.review_comments.push(comment.clone());
+                        }
                         session.comments.push(comment);
                         session.updated_at = now;
                     }
@@ -84,6 +112,24 @@ pub fn reduce(state: &mut AppState, action: Action) {
                             session.updated_at = chrono::Utc::now().to_rfc3339();
                         }
                     }
+                    let change = worktree
+                        .changes
+                        .changes
+                        .iter_mut()
+                        .find(|c| c.has_review_session(&session_id));
+                    if let Some(comment) = change.and_then(|c| c.review_comments.iter_mut().find(|c| c.id == comment_id)) {
+                        comment.resolved = true;
+                    }
+                }
+            }
+        }
+
+        Action::SetReviewComments { change_id, comments } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(change) = worktree.changes.changes.iter_mut().find(|c| c.id == change_id) {
+                        change.review_comments = comments;
+                    }
                 }
             }
         }
@@ -101,14 +147,38 @@ pub fn reduce(state: &mut AppState, action: Action) {
         }
 
         Action::ApproveReview { session_id } => {
+            let mut blocker = None;
+            // Opt-in per project: unchecked spec checklist items block approval
+            let checklist_blocker = state
+                .active_project()
+                .filter(|p| p.require_checklists)
+                .and_then(|p| p.active_worktree())
+                .and_then(|w| w.workflows.checklist_blocker());
+            // A change whose last test run failed stays unapproved until tests
+            // pass again or the user overrides the gate
+            let test_blocker = state
+                .active_project()
+                .and_then(|p| p.active_worktree())
+                .and_then(|w| w.changes.changes.iter().find(|c| c.has_review_session(&session_id)))
+                .and_then(|c| c.test_results.as_ref())
+                .and_then(|r| r.approval_blocker());
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
                     if let Some(session) = worktree.tasks.review_gate.sessions.get_mut(&session_id) {
-                        session.status = crate::app_state::ReviewStatus::Approved;
-                        session.updated_at = chrono::Utc::now().to_rfc3339();
+                        blocker = session.approval_blocker().or(checklist_blocker).or(test_blocker);
+                        if blocker.is_none() {
+                            session.status = crate::app_state::ReviewStatus::Approved;
+                            session.updated_at = chrono::Utc::now().to_rfc3339();
+                        }
                     }
                 }
             }
+            if let Some(blocker) = blocker {
+                state.notifications.push(Notification::new(
+                    format!("Cannot approve: {}", blocker),
+                    NotificationType::Warning,
+                ));
+            }
         }
 
         Action::RejectReview { session_id, reason } => {
@@ -124,6 +194,8 @@ pub fn reduce(state: &mut AppState, action: Action) {
                             author: crate::app_state::CommentAuthor::System,
                             resolved: false,
                             created_at: chrono::Utc::now().to_rfc3339(),
+                            anchor: None,
+                            parent_id: None,
                         };
                         session.comments.push(comment);
                     }
@@ -154,6 +226,8 @@ pub fn reduce(state: &mut AppState, action: Action) {
                             }).collect(),
                         };
                         session.iteration += 1;
+                        // The previous check evaluated the old content
+                        session.compliance = None;
                         session.status = crate::app_state::ReviewStatus::Reviewing;
                         session.updated_at = chrono::Utc::now().to_rfc3339();
                     }
@@ -214,6 +288,57 @@ pub fn reduce(state: &mut AppState, action: Action) {
                 }
             }
         }
+
+        Action::RunComplianceCheck { session_id } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(session) = worktree.tasks.review_gate.sessions.get_mut(&session_id) {
+                        session.compliance = Some(crate::app_state::ComplianceCheck::running(session.iteration));
+                    }
+                }
+            }
+        }
+
+        Action::SetComplianceCheck { session_id, check } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(session) = worktree.tasks.review_gate.sessions.get_mut(&session_id) {
+                        // Drop results for content that has since been iterated on
+                        if session.iteration == check.iteration {
+                            session.compliance = Some(check);
+                        }
+                    }
+                }
+            }
+        }
+
+        Action::OverrideComplianceCheck { session_id, reason } => {
+            if let Some(project) = state.active_project_mut() {
+                if let Some(worktree) = project.active_worktree_mut() {
+                    if let Some(session) = worktree.tasks.review_gate.sessions.get_mut(&session_id) {
+                        let Some(check) = session.compliance.as_mut() else {
+                            return;
+                        };
+                        if check.status != crate::app_state::ComplianceStatus::Failed {
+                            return;
+                        }
+                        check.override_reason = Some(reason.clone());
+                        let now = chrono::Utc::now().to_rfc3339();
+                        session.comments.push(crate::app_state::ReviewComment {
+                            id: uuid::Uuid::new_v4().to_string(),
+                            target: crate::app_state::CommentTarget::Document,
+                            content: format!("Compliance check overridden: {}", reason),
+                            author: crate::app_state::CommentAuthor::System,
+                            resolved: false,
+                            created_at: now.clone(),
+                            anchor: None,
+                            parent_id: None,
+                        });
+                        session.updated_at = now;
+                    }
+                }
+            }
+        }
         _ => {}
     }
 }
@@ -0,0 +1,38 @@
+use crate::actions::Action;
+use crate::app_state::AppState;
+
+pub fn reduce(state: &mut AppState, action: Action) {
+    match action {
+        Action::SetSchedule { project_path, entries, error } => {
+            if let Some(project) = state.projects.iter_mut().find(|p| p.path == project_path) {
+                // A broken schedule file keeps the schedules that were already loaded
+                if error.is_none() {
+                    project.schedule.entries = crate::schedule::carry_over(entries, &project.schedule.entries);
+                }
+                project.schedule.error = error;
+            }
+        }
+
+        Action::SetSchedulePaused { paused } => {
+            if let Some(project) = state.active_project_mut() {
+                project.schedule.paused = paused;
+            }
+        }
+
+        Action::MarkScheduleRun { project_path, id, ran_at, next_run_at, task_id } => {
+            if let Some(entry) = state
+                .projects
+                .iter_mut()
+                .find(|p| p.path == project_path)
+                .and_then(|p| p.schedule.entries.iter_mut().find(|e| e.id == id))
+            {
+                entry.last_run_at = Some(ran_at);
+                entry.next_run_at = Some(next_run_at);
+                if task_id.is_some() {
+                    entry.last_task_id = task_id;
+                }
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,29 @@
+use crate::actions::Action;
+use crate::app_state::{AppState, MAX_SESSION_HISTORY};
+
+pub fn reduce(state: &mut AppState, action: Action) {
+    let history = &mut state.session_history;
+    match action {
+        Action::LoadSessions => {
+            history.is_loading = true;
+        }
+        Action::SetSessions { project_id, sessions } => {
+            history.project_id = Some(project_id);
+            history.sessions = sessions;
+            history.is_loading = false;
+        }
+        Action::AddSession { session } => {
+            // Only the loaded project's history is kept in state
+            if history.project_id.as_deref() != Some(session.project_id.as_str()) {
+                return;
+            }
+            history.sessions.retain(|s| s.id != session.id);
+            history.sessions.insert(0, session);
+            history.sessions.truncate(MAX_SESSION_HISTORY);
+        }
+        Action::DeleteSession { id } => {
+            history.sessions.retain(|s| s.id != id);
+        }
+        _ => {}
+    }
+}
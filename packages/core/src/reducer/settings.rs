@@ -1,5 +1,5 @@
 use crate::actions::Action;
-use crate::app_state::AppState;
+use crate::app_state::{AppError, AppState, GitHostingSettings, OllamaSettings, OpenAiSettings, DEFAULT_OLLAMA_URL};
 
 pub fn reduce(state: &mut AppState, action: Action) {
     match action {
@@ -10,6 +10,143 @@ pub fn reduce(state: &mut AppState, action: Action) {
         Action::SetProjectPath { path } => {
             state.global_settings.default_project_path = path;
         }
+
+        Action::SetModel { model } => {
+            state.global_settings.model = model.filter(|m| !m.trim().is_empty());
+        }
+
+        Action::SetTaskMaxParallel { max_parallel } => {
+            state.global_settings.task_max_parallel = max_parallel.map(|n| n.max(1));
+        }
+
+        Action::SetImplementationTimeout { secs } => {
+            state.global_settings.implementation_timeout_secs = secs.map(|n| n.max(60));
+        }
+
+        Action::SetDesktopNotification { event, enabled } => {
+            state.global_settings.desktop_notifications.set_enabled(event, enabled);
+        }
+
+        Action::SetLlmProvider { provider } => {
+            state.global_settings.provider = provider;
+        }
+
+        Action::SetOpenAiSettings { settings } => {
+            // The renderer never sees the key: None keeps the current one,
+            // an empty string removes it
+            let api_key = match settings.api_key {
+                Some(key) => Some(key).filter(|k| !k.trim().is_empty()),
+                None => state.global_settings.openai.api_key.take(),
+            };
+            state.global_settings.openai = OpenAiSettings {
+                base_url: settings.base_url.trim().trim_end_matches('/').to_string(),
+                api_key,
+                model: settings.model.trim().to_string(),
+            };
+        }
+
+        Action::SetOllamaSettings { settings } => {
+            let base_url = settings.base_url.trim().trim_end_matches('/');
+            state.global_settings.ollama = OllamaSettings {
+                base_url: if base_url.is_empty() { DEFAULT_OLLAMA_URL } else { base_url }.to_string(),
+                model: settings.model.trim().to_string(),
+                task_model: settings.task_model.filter(|m| !m.trim().is_empty()),
+            };
+        }
+
+        Action::SetGitHostingSettings { settings } => {
+            let token = |t: Option<String>| t.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+            state.global_settings.git_hosting = GitHostingSettings {
+                github_token: token(settings.github_token),
+                gitlab_token: token(settings.gitlab_token),
+            };
+        }
+
+        Action::SetKeybinding { command, shortcut } => {
+            match state.global_settings.keybindings.set(&command, &shortcut) {
+                Ok(()) => clear_keybinding_error(state),
+                Err(message) => state.error = Some(AppError::new("keybinding", message).with_context(command)),
+            }
+        }
+
+        Action::ResetKeybindings { command } => {
+            state.global_settings.keybindings.reset(command.as_deref());
+            clear_keybinding_error(state);
+        }
+
+        Action::SaveSettingsProfile { profile } => {
+            if let Err(message) = profile.validate() {
+                state.error = Some(AppError::new("settings_profile", message).with_context(profile.name));
+                return;
+            }
+            match state.settings_profiles.iter_mut().find(|p| p.name == profile.name) {
+                Some(existing) => *existing = profile,
+                None => {
+                    state.settings_profiles.push(profile);
+                    state.settings_profiles.sort_by(|a, b| a.name.cmp(&b.name));
+                }
+            }
+        }
+
+        Action::DeleteSettingsProfile { name } => {
+            state.settings_profiles.retain(|p| p.name != name);
+            if state.global_settings.active_profile.as_ref() == Some(&name) {
+                state.global_settings.active_profile = None;
+            }
+        }
+
+        Action::SwitchSettingsProfile { name } => {
+            match state.settings_profiles.iter().find(|p| p.name == name) {
+                Some(profile) => profile.apply(&mut state.global_settings),
+                None => {
+                    state.error = Some(AppError::new("settings_profile", format!("Settings profile not found: {}", name)))
+                }
+            }
+        }
+
+        Action::SetProjectModel { model } => {
+            if let Some(project) = state.active_project_mut() {
+                project.model = model.filter(|m| !m.trim().is_empty());
+                // Persist project setting (only for real paths)
+                if std::path::Path::new(&project.path).exists() {
+                    let _ = crate::persistence::save_project(project);
+                }
+            }
+        }
+
+        Action::SetRequireChecklists { required } => {
+            if let Some(project) = state.active_project_mut() {
+                project.require_checklists = required;
+                if std::path::Path::new(&project.path).exists() {
+                    let _ = crate::persistence::save_project(project);
+                }
+            }
+        }
+
+        Action::SetCollectCoverage { enabled } => {
+            if let Some(project) = state.active_project_mut() {
+                project.collect_coverage = enabled;
+                if std::path::Path::new(&project.path).exists() {
+                    let _ = crate::persistence::save_project(project);
+                }
+            }
+        }
+
+        Action::SetCheckBuildOnSave { enabled } => {
+            if let Some(project) = state.active_project_mut() {
+                project.check_build_on_save = enabled;
+                if std::path::Path::new(&project.path).exists() {
+                    let _ = crate::persistence::save_project(project);
+                }
+            }
+        }
         _ => {}
     }
 }
+
+/// Drop a keybinding error once a later change succeeded
+fn clear_keybinding_error(state: &mut AppState) {
+    if state.error.as_ref().is_some_and(|e| e.code == "keybinding") {
+        state.error = None;
+    }
+}
@@ -1,9 +1,10 @@
 use crate::actions::Action;
-use crate::app_state::{AppState, TaskStatus};
+use crate::app_state::{AppState, TaskStatus, WorktreeState};
+use crate::task_queue::{TaskRunStatus, MAX_FINISHED_RUNS};
 
 pub fn reduce(state: &mut AppState, action: Action) {
     match action {
-        Action::LoadJustfileCommands | Action::RefreshJustfile => {
+        Action::LoadJustfileCommands | Action::RefreshJustfile | Action::LoadTasks { .. } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
                     worktree.tasks.is_loading = true;
@@ -21,13 +22,83 @@ pub fn reduce(state: &mut AppState, action: Action) {
             }
         }
 
-        Action::RunJustCommand { name, .. } => {
+        Action::SetTasks { provider, tasks } => {
             if let Some(project) = state.active_project_mut() {
                 if let Some(worktree) = project.active_worktree_mut() {
-                    worktree.tasks.active_command = Some(name.clone());
-                    worktree.tasks.task_statuses.insert(name, TaskStatus::Running);
-                    worktree.tasks.output.clear();
-                    worktree.is_modified = true;
+                    match provider {
+                        Some(provider) => {
+                            worktree.tasks.tasks.retain(|t| t.provider != provider);
+                            worktree.tasks.tasks.extend(tasks);
+                        }
+                        None => worktree.tasks.tasks = tasks,
+                    }
+                    worktree.tasks.is_loading = false;
+                }
+            }
+        }
+
+        Action::RunJustCommand { name, .. } | Action::RunJustCommandWithArgs { name, .. } => {
+            start_task(state, name);
+        }
+
+        Action::RunTask { provider, name, .. } => {
+            start_task(state, crate::tasks::task_id(provider, &name));
+        }
+
+        Action::QueueTaskRun { run } => {
+            if let Some(worktree) = worktree_by_path_mut(state, &run.worktree_path) {
+                worktree.tasks.runs.push(run);
+                // Keep every unfinished run, but only the latest finished ones
+                let finished = worktree.tasks.runs.iter().filter(|r| r.status.is_finished()).count();
+                let mut excess = finished.saturating_sub(MAX_FINISHED_RUNS);
+                worktree.tasks.runs.retain(|r| {
+                    if excess > 0 && r.status.is_finished() {
+                        excess -= 1;
+                        false
+                    } else {
+                        true
+                    }
+                });
+            }
+        }
+
+        Action::StartTaskRun { task_id } => {
+            if let Some(worktree) = worktree_with_run_mut(state, &task_id) {
+                if let Some(run) = worktree.tasks.runs.iter_mut().find(|r| r.id == task_id) {
+                    run.status = TaskRunStatus::Running;
+                }
+            }
+        }
+
+        Action::AppendTaskRunOutput { task_id, line } => {
+            if let Some(worktree) = worktree_with_run_mut(state, &task_id) {
+                let tasks = &mut worktree.tasks;
+                if let Some(run) = tasks.runs.iter_mut().find(|r| r.id == task_id) {
+                    // Mirror the active command into the output pane
+                    if tasks.active_command.as_deref() == Some(run.task_key.as_str()) {
+                        tasks.output.push(line.clone());
+                    }
+                    run.push_output(line);
+                }
+            }
+        }
+
+        Action::FinishTaskRun { task_id, status, exit_code, duration_ms } => {
+            if let Some(worktree) = worktree_with_run_mut(state, &task_id) {
+                let tasks = &mut worktree.tasks;
+                if let Some(run) = tasks.runs.iter_mut().find(|r| r.id == task_id) {
+                    run.status = status;
+                    run.exit_code = exit_code;
+                    run.duration_ms = duration_ms;
+                    let task_status = match status {
+                        TaskRunStatus::Success => TaskStatus::Success,
+                        TaskRunStatus::Error => TaskStatus::Error,
+                        _ => TaskStatus::Idle,
+                    };
+                    tasks.task_statuses.insert(run.task_key.clone(), task_status);
+                }
+                if tasks.runs.iter().all(|r| r.status.is_finished()) {
+                    worktree.is_modified = false;
                 }
             }
         }
@@ -86,3 +157,31 @@ pub fn reduce(state: &mut AppState, action: Action) {
         _ => {}
     }
 }
+
+/// Mark a task as running and clear the previous output
+fn start_task(state: &mut AppState, key: String) {
+    if let Some(project) = state.active_project_mut() {
+        if let Some(worktree) = project.active_worktree_mut() {
+            worktree.tasks.active_command = Some(key.clone());
+            worktree.tasks.task_statuses.insert(key, TaskStatus::Running);
+            worktree.tasks.output.clear();
+            worktree.is_modified = true;
+        }
+    }
+}
+
+fn worktree_by_path_mut<'a>(state: &'a mut AppState, path: &str) -> Option<&'a mut WorktreeState> {
+    state
+        .projects
+        .iter_mut()
+        .flat_map(|p| p.worktrees.iter_mut())
+        .find(|w| w.path == path)
+}
+
+fn worktree_with_run_mut<'a>(state: &'a mut AppState, task_id: &str) -> Option<&'a mut WorktreeState> {
+    state
+        .projects
+        .iter_mut()
+        .flat_map(|p| p.worktrees.iter_mut())
+        .find(|w| w.tasks.runs.iter().any(|r| r.id == task_id))
+}
@@ -0,0 +1,43 @@
+//! Undo/redo reducer.
+
+use crate::actions::Action;
+use crate::app_state::AppState;
+use crate::undo::{self, UndoSlice};
+
+pub fn reduce(state: &mut AppState, action: Action) {
+    match action {
+        Action::Undo => {
+            // Skip edits whose project/worktree/change is gone
+            while let Some(entry) = state.undo.undo.pop() {
+                if undo::apply(state, &entry.before) {
+                    set_pending(state, &entry.before);
+                    state.undo.redo.push(entry);
+                    break;
+                }
+            }
+        }
+
+        Action::Redo => {
+            while let Some(entry) = state.undo.redo.pop() {
+                if undo::apply(state, &entry.after) {
+                    set_pending(state, &entry.after);
+                    state.undo.undo.push(entry);
+                    break;
+                }
+            }
+        }
+
+        Action::PushUndoEntry { entry } => {
+            state.undo.record(*entry);
+        }
+
+        _ => {}
+    }
+}
+
+/// Database- and file-backed slices are written by the async handler
+fn set_pending(state: &mut AppState, slice: &UndoSlice) {
+    if matches!(slice, UndoSlice::FileComment { .. } | UndoSlice::ChangeStatus { .. }) {
+        state.undo.pending = Some(slice.clone());
+    }
+}
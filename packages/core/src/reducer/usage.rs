@@ -0,0 +1,8 @@
+use crate::actions::Action;
+use crate::app_state::AppState;
+
+pub fn reduce(state: &mut AppState, action: Action) {
+    if let Action::AddUsageRecord { record } = action {
+        state.usage.add_record(&record);
+    }
+}
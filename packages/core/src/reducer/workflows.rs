@@ -0,0 +1,215 @@
+use crate::actions::Action;
+use crate::app_state::{
+    AppState, ClarifySessionStatus, SpecAnalysis, SpecChecklist, SpecChecklistStatus, SpecPhaseStatus, SpecProgressReport,
+    SpecTasksState, WorkflowsState, MAX_SPEC_PROGRESS_REPORTS,
+};
+use crate::checklist::ChecklistKind;
+
+pub fn reduce(state: &mut AppState, action: Action) {
+    let Some(workflows) = active_workflows(state) else {
+        return;
+    };
+
+    match action {
+        Action::SetSpecWorkflow { workflow } => {
+            workflows.spec = Some(workflow);
+        }
+        Action::RunSpecPhase { phase, .. } => {
+            let Some(workflow) = workflows.spec.as_mut() else {
+                return;
+            };
+            // One phase at a time
+            if workflow.running_phase().is_some() {
+                return;
+            }
+            if let Some(run) = workflow.phase_mut(phase) {
+                run.status = SpecPhaseStatus::Running;
+                run.output.clear();
+                run.error = None;
+            }
+        }
+        Action::AppendSpecPhaseOutput { phase, content } => {
+            if let Some(run) = running_phase(workflows, phase) {
+                run.output.push_str(&content);
+            }
+        }
+        Action::CompleteSpecPhase { phase, output } => {
+            if let Some(run) = running_phase(workflows, phase) {
+                run.status = SpecPhaseStatus::Completed;
+                run.output = output;
+            }
+        }
+        Action::FailSpecPhase { phase, error } => {
+            if let Some(run) = running_phase(workflows, phase) {
+                run.status = SpecPhaseStatus::Failed;
+                run.error = Some(error);
+            }
+        }
+        Action::CancelSpecPhase => {
+            if let Some(workflow) = workflows.spec.as_mut() {
+                for run in &mut workflow.phases {
+                    if run.status == SpecPhaseStatus::Running {
+                        run.status = SpecPhaseStatus::Failed;
+                        run.error = Some("Cancelled".to_string());
+                    }
+                }
+            }
+        }
+        Action::ClearSpecWorkflow => {
+            workflows.spec = None;
+            workflows.tasks = None;
+            workflows.reports.clear();
+            workflows.checklists.clear();
+            workflows.analysis = None;
+        }
+        Action::SetClarifySession { session } => {
+            workflows.clarify = Some(session);
+        }
+        Action::AnswerClarifyQuestion { id, value } => {
+            if let Some(session) = workflows.clarify.as_mut() {
+                // Ignore stale answers (e.g. double submit)
+                if session.current_question().is_some_and(|q| q.id == id) {
+                    session.respond(Some(value));
+                }
+            }
+        }
+        Action::SkipClarifyQuestion => {
+            if let Some(session) = workflows.clarify.as_mut() {
+                session.respond(None);
+            }
+        }
+        Action::CompleteClarifySession { coverage } => {
+            if let Some(session) = workflows.clarify.as_mut() {
+                session.status = ClarifySessionStatus::Completed;
+                session.coverage = coverage;
+            }
+        }
+        Action::FailClarifySession { error } => {
+            if let Some(session) = workflows.clarify.as_mut() {
+                session.status = ClarifySessionStatus::Failed;
+                session.error = Some(error);
+            }
+        }
+        Action::ClearClarifySession => {
+            workflows.clarify = None;
+        }
+        Action::SetSpecTasks { tasks } => {
+            workflows.tasks = Some(tasks);
+        }
+        Action::ToggleSpecTask { id, done } => {
+            // Optimistic; replaced by the re-parsed file once it is written
+            if let Some(tasks) = workflows.tasks.take() {
+                let mut items = tasks.items;
+                if let Some(item) = items.iter_mut().find(|item| item.id == id) {
+                    item.done = done;
+                }
+                workflows.tasks = Some(SpecTasksState::new(tasks.tasks_path, items));
+            }
+        }
+        Action::ReportSpecProgress { message, task_id } => {
+            workflows.reports.push(SpecProgressReport {
+                message,
+                task_id,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+            });
+            let excess = workflows.reports.len().saturating_sub(MAX_SPEC_PROGRESS_REPORTS);
+            workflows.reports.drain(..excess);
+        }
+        Action::GenerateChecklist { kind } => {
+            let Some(spec_dir) = workflows.spec.as_ref().map(|w| w.spec_dir.clone()) else {
+                return;
+            };
+            let items = checklist_mut(workflows, kind).map(|c| std::mem::take(&mut c.items)).unwrap_or_default();
+            upsert_checklist(workflows, SpecChecklist {
+                kind,
+                path: format!("{}/{}", spec_dir, kind.file_name()),
+                status: SpecChecklistStatus::Generating,
+                items,
+                error: None,
+            });
+        }
+        Action::SetChecklist { checklist } => {
+            upsert_checklist(workflows, checklist);
+        }
+        Action::FailChecklist { kind, error } => {
+            if let Some(checklist) = checklist_mut(workflows, kind) {
+                checklist.status = SpecChecklistStatus::Failed;
+                checklist.error = Some(error);
+            }
+        }
+        Action::SetChecklists { checklists } => {
+            workflows.checklists = checklists;
+        }
+        Action::ToggleChecklistItem { kind, id, checked } => {
+            // Optimistic; replaced by the re-parsed file once it is written
+            if let Some(item) = checklist_mut(workflows, kind).and_then(|c| c.items.iter_mut().find(|i| i.id == id)) {
+                item.checked = checked;
+            }
+        }
+        Action::AnalyzeSpec { verify } => {
+            if workflows.spec.is_none() || workflows.analysis.as_ref().is_some_and(|a| a.status == SpecPhaseStatus::Running) {
+                return;
+            }
+            workflows.analysis = Some(SpecAnalysis {
+                status: SpecPhaseStatus::Running,
+                verify,
+                report: workflows.analysis.take().and_then(|a| a.report),
+                error: None,
+            });
+        }
+        Action::CompleteSpecAnalysis { report } => {
+            if let Some(analysis) = workflows.analysis.as_mut() {
+                analysis.status = SpecPhaseStatus::Completed;
+                analysis.report = Some(report);
+            }
+        }
+        Action::FailSpecAnalysis { error } => {
+            if let Some(analysis) = workflows.analysis.as_mut() {
+                analysis.status = SpecPhaseStatus::Failed;
+                analysis.error = Some(error);
+            }
+        }
+        Action::SetFeaturesCatalog { features } => {
+            workflows.features = features;
+        }
+        // StartSpecWorkflow, StartClarifySession, LoadSpecTasks,
+        // LoadChecklists and LoadFeaturesCatalog read the worktree asynchronously
+        _ => {}
+    }
+}
+
+fn active_workflows(state: &mut AppState) -> Option<&mut WorkflowsState> {
+    state
+        .active_project_mut()
+        .and_then(|p| p.active_worktree_mut())
+        .map(|w| &mut w.workflows)
+}
+
+/// The phase's run, if it is the one running (late output of a cancelled
+/// run is dropped)
+fn running_phase(
+    workflows: &mut WorkflowsState,
+    phase: crate::spec_kit::SpecPhase,
+) -> Option<&mut crate::app_state::SpecPhaseRun> {
+    workflows
+        .spec
+        .as_mut()?
+        .phase_mut(phase)
+        .filter(|run| run.status == SpecPhaseStatus::Running)
+}
+
+fn checklist_mut(workflows: &mut WorkflowsState, kind: ChecklistKind) -> Option<&mut SpecChecklist> {
+    workflows.checklists.iter_mut().find(|c| c.kind == kind)
+}
+
+/// Replace the checklist of the same kind, keeping ChecklistKind::ALL order
+fn upsert_checklist(workflows: &mut WorkflowsState, checklist: SpecChecklist) {
+    let order = |kind: ChecklistKind| ChecklistKind::ALL.iter().position(|&k| k == kind);
+    workflows.checklists.retain(|c| c.kind != checklist.kind);
+    let index = workflows
+        .checklists
+        .iter()
+        .position(|c| order(c.kind) > order(checklist.kind))
+        .unwrap_or(workflows.checklists.len());
+    workflows.checklists.insert(index, checklist);
+}
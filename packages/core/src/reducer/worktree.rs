@@ -17,15 +17,27 @@ pub fn reduce(state: &mut AppState, action: Action) {
 
         Action::SetWorktrees { worktrees } => {
             if let Some(project) = state.active_project_mut() {
+                // Keep the state (MCP server, chat, terminal, ...) of worktrees
+                // that still exist, so refreshing never tears them down
+                let active_path = project.active_worktree().map(|w| w.path.clone());
+                let mut existing = std::mem::take(&mut project.worktrees);
                 let new_worktrees: Vec<WorktreeState> = worktrees
                     .into_iter()
-                    .map(|w| WorktreeState::new(w.path, w.branch, w.is_main))
+                    .map(|w| match existing.iter().position(|e| e.path == w.path) {
+                        Some(idx) => {
+                            let mut worktree = existing.swap_remove(idx);
+                            worktree.branch = w.branch;
+                            worktree.is_main = w.is_main;
+                            worktree
+                        }
+                        None => WorktreeState::new(w.path, w.branch, w.is_main),
+                    })
                     .collect();
 
                 project.worktrees = new_worktrees;
-                if project.active_worktree_index >= project.worktrees.len() {
-                    project.active_worktree_index = 0;
-                }
+                project.active_worktree_index = active_path
+                    .and_then(|path| project.worktrees.iter().position(|w| w.path == path))
+                    .unwrap_or(0);
             }
         }
 
@@ -60,6 +72,93 @@ pub fn reduce(state: &mut AppState, action: Action) {
                 project.is_loading_branches = is_loading;
             }
         }
+
+        Action::RefreshWorktreeHealth => {
+            // Async trigger
+        }
+
+        Action::LoadWorktreeDiff { .. } => {
+            if let Some(worktree) = state.active_project_mut().and_then(|p| p.active_worktree_mut()) {
+                worktree.is_loading_diff = true;
+            }
+        }
+
+        Action::SetWorktreeDiff { diff } => {
+            if let Some(worktree) = state.active_project_mut().and_then(|p| p.active_worktree_mut()) {
+                worktree.diff = diff;
+                worktree.is_loading_diff = false;
+            }
+        }
+
+        Action::RunDependencyAudit => {
+            if let Some(worktree) = state.active_project_mut().and_then(|p| p.active_worktree_mut()) {
+                worktree.is_auditing = true;
+            }
+        }
+
+        Action::SetDependencyAudit { worktree_path, report } => {
+            if let Some(worktree) = state
+                .projects
+                .iter_mut()
+                .flat_map(|p| p.worktrees.iter_mut())
+                .find(|w| w.path == worktree_path)
+            {
+                worktree.audit = Some(report);
+                worktree.is_auditing = false;
+            }
+        }
+
+        Action::RunBuildCheck => {
+            if let Some(worktree) = state.active_project_mut().and_then(|p| p.active_worktree_mut()) {
+                worktree.is_checking_build = true;
+            }
+        }
+
+        Action::SetBuildDiagnostics { worktree_path, diagnostics } => {
+            if let Some(worktree) = state
+                .projects
+                .iter_mut()
+                .flat_map(|p| p.worktrees.iter_mut())
+                .find(|w| w.path == worktree_path)
+            {
+                worktree.build = Some(diagnostics);
+                worktree.is_checking_build = false;
+            }
+        }
+
+        Action::SetLspServerStatus { worktree_path, server, status, error } => {
+            if let Some(worktree) = state
+                .projects
+                .iter_mut()
+                .flat_map(|p| p.worktrees.iter_mut())
+                .find(|w| w.path == worktree_path)
+            {
+                worktree.lsp_servers.retain(|s| s.server != server);
+                if let Some(status) = status {
+                    worktree.lsp_servers.push(crate::app_state::LspServerState { server, status, error });
+                }
+            }
+        }
+
+        Action::StopLspServers => {
+            if let Some(worktree) = state.active_project_mut().and_then(|p| p.active_worktree_mut()) {
+                worktree.lsp_servers.clear();
+            }
+        }
+
+        Action::SetWorktreeHealth { health } => {
+            if let Some(project) = state.active_project_mut() {
+                for data in health {
+                    if let Some(worktree) = project
+                        .worktrees
+                        .iter_mut()
+                        .find(|w| w.path == data.worktree_path)
+                    {
+                        worktree.health = Some(data.into());
+                    }
+                }
+            }
+        }
         _ => {}
     }
 }
@@ -0,0 +1,101 @@
+//! Review comments stored with their change.
+//!
+//! Review sessions live in memory, but the discussion on a change should
+//! outlive them (and app restarts), so every comment added or resolved in a
+//! change's proposal/plan review is also written to
+//! `.rstn/changes/<name>/review.json`:
+//!
+//! ```json
+//! { "comments": [ { "id": "...", "content": "...", "anchor": { "file": "src/a.rs", "line_start": 3, "line_end": 5 }, ... } ] }
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app_state::ReviewComment;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ReviewFile {
+    #[serde(default)]
+    comments: Vec<ReviewComment>,
+}
+
+/// `.rstn/changes/<change_name>/review.json` under the worktree
+pub fn review_path(worktree: &Path, change_name: &str) -> PathBuf {
+    worktree
+        .join(".rstn")
+        .join("changes")
+        .join(change_name)
+        .join("review.json")
+}
+
+/// Comments of a change, oldest first (empty if none were saved)
+pub fn load(worktree: &Path, change_name: &str) -> Result<Vec<ReviewComment>, String> {
+    let path = review_path(worktree, change_name);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let json = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let file: ReviewFile =
+        serde_json::from_str(&json).map_err(|e| format!("Invalid review file {}: {}", path.display(), e))?;
+    Ok(file.comments)
+}
+
+/// Add a comment, or replace the saved comment with the same ID
+pub fn save_comment(worktree: &Path, change_name: &str, comment: &ReviewComment) -> Result<(), String> {
+    let path = review_path(worktree, change_name);
+    let mut comments = load(worktree, change_name)?;
+    match comments.iter_mut().find(|c| c.id == comment.id) {
+        Some(existing) => *existing = comment.clone(),
+        None => comments.push(comment.clone()),
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(&ReviewFile { comments })
+        .map_err(|e| format!("Failed to serialize review comments: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::{CommentAnchor, CommentAuthor, CommentTarget};
+
+    fn comment(id: &str, parent_id: Option<&str>) -> ReviewComment {
+        ReviewComment {
+            id: id.to_string(),
+            target: CommentTarget::File { path: "src/auth.rs".to_string() },
+            content: format!("Comment {}", id),
+            author: CommentAuthor::User,
+            resolved: false,
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            anchor: Some(CommentAnchor { file: "src/auth.rs".to_string(), line_start: 10, line_end: 12 }),
+            parent_id: parent_id.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load(dir.path(), "add-auth").unwrap().is_empty());
+
+        save_comment(dir.path(), "add-auth", &comment("c1", None)).unwrap();
+        save_comment(dir.path(), "add-auth", &comment("c2", Some("c1"))).unwrap();
+        let mut resolved = comment("c1", None);
+        resolved.resolved = true;
+        save_comment(dir.path(), "add-auth", &resolved).unwrap();
+
+        let comments = load(dir.path(), "add-auth").unwrap();
+        assert_eq!(comments.len(), 2);
+        assert!(comments[0].resolved);
+        assert_eq!(comments[1].parent_id.as_deref(), Some("c1"));
+        assert!(review_path(dir.path(), "add-auth").ends_with(".rstn/changes/add-auth/review.json"));
+    }
+}
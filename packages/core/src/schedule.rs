@@ -0,0 +1,292 @@
+//! Recurring tasks per project.
+//!
+//! Schedules are declared in `<project>/.rstn/schedule.toml`:
+//!
+//! ```toml
+//! [[schedules]]
+//! id = "lint"
+//! every = "30m"
+//! run = { type = "task", provider = "just", name = "lint" }
+//!
+//! [[schedules]]
+//! id = "docker"
+//! name = "Refresh Docker services"
+//! every = "5m"
+//! run = { type = "refresh_docker_services" }
+//! ```
+//!
+//! Intervals take an `s`, `m`, `h` or `d` suffix. Tasks are executed through
+//! the task queue, so they obey the same concurrency limit as manual runs.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::tasks::TaskProviderKind;
+
+/// Schedule file name (in `<project>/.rstn/`)
+pub const SCHEDULE_FILE: &str = "schedule.toml";
+
+/// Shortest allowed interval
+pub const MIN_INTERVAL_SECS: u64 = 60;
+
+/// What a schedule runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScheduledJob {
+    /// A task from any provider (see `tasks::TaskProvider`)
+    Task {
+        provider: TaskProviderKind,
+        name: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    RefreshDockerServices,
+}
+
+/// A recurring task and when it runs next
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleEntry {
+    pub id: String,
+    pub name: String,
+    pub interval_secs: u64,
+    pub job: ScheduledJob,
+    pub enabled: bool,
+    /// ISO 8601 timestamp of the next run (None = disabled)
+    pub next_run_at: Option<String>,
+    /// ISO 8601 timestamp of the last run
+    #[serde(default)]
+    pub last_run_at: Option<String>,
+    /// Task queue run ID of the last run (task jobs only)
+    #[serde(default)]
+    pub last_task_id: Option<String>,
+}
+
+impl ScheduleEntry {
+    /// Whether the entry should run at `now`
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.enabled
+            && self
+                .next_run_at
+                .as_deref()
+                .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+                .is_some_and(|at| at <= now)
+    }
+
+    /// Next run time, one interval after `from`
+    pub fn next_run_after(&self, from: DateTime<Utc>) -> String {
+        (from + Duration::seconds(self.interval_secs as i64)).to_rfc3339()
+    }
+}
+
+// ============================================================================
+// Raw TOML schema
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawScheduleFile {
+    #[serde(default)]
+    schedules: Vec<RawSchedule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawSchedule {
+    id: String,
+    name: Option<String>,
+    every: String,
+    run: ScheduledJob,
+    #[serde(default = "default_enabled")]
+    enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+// ============================================================================
+// Loading
+// ============================================================================
+
+/// Path to a project's schedule file (<project>/.rstn/schedule.toml)
+pub fn schedule_path(project_path: &Path) -> PathBuf {
+    project_path.join(".rstn").join(SCHEDULE_FILE)
+}
+
+/// Load a project's schedules. A missing file defines none.
+pub fn load_schedule(project_path: &Path, now: DateTime<Utc>) -> Result<Vec<ScheduleEntry>, String> {
+    let path = schedule_path(project_path);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_schedule_str(&content, now).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Parse schedule TOML content. Enabled entries first run one interval after `now`.
+pub fn parse_schedule_str(content: &str, now: DateTime<Utc>) -> Result<Vec<ScheduleEntry>, String> {
+    let raw: RawScheduleFile =
+        toml::from_str(content).map_err(|e| format!("Invalid schedule file: {}", e))?;
+
+    let mut entries: Vec<ScheduleEntry> = Vec::new();
+    for schedule in raw.schedules {
+        if entries.iter().any(|e| e.id == schedule.id) {
+            return Err(format!("Duplicate schedule id: {}", schedule.id));
+        }
+        let interval_secs =
+            parse_interval(&schedule.every).map_err(|e| format!("Schedule '{}': {}", schedule.id, e))?;
+
+        let mut entry = ScheduleEntry {
+            name: schedule.name.unwrap_or_else(|| schedule.id.clone()),
+            id: schedule.id,
+            interval_secs,
+            job: schedule.run,
+            enabled: schedule.enabled,
+            next_run_at: None,
+            last_run_at: None,
+            last_task_id: None,
+        };
+        if entry.enabled {
+            entry.next_run_at = Some(entry.next_run_after(now));
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Parse an interval such as "90s", "30m", "2h" or "1d" into seconds
+pub fn parse_interval(every: &str) -> Result<u64, String> {
+    let every = every.trim();
+    let split = every.find(|c: char| !c.is_ascii_digit()).unwrap_or(every.len());
+    let (value, unit) = every.split_at(split);
+    let value: u64 = value
+        .parse()
+        .map_err(|_| format!("Invalid interval '{}' (e.g. \"30m\")", every))?;
+    let multiplier = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(format!("Invalid interval unit in '{}' (use s, m, h or d)", every)),
+    };
+
+    let secs = value * multiplier;
+    if secs < MIN_INTERVAL_SECS {
+        return Err(format!("Interval '{}' is shorter than {}s", every, MIN_INTERVAL_SECS));
+    }
+    Ok(secs)
+}
+
+/// Carry run times over from the previous entries when a schedule is reloaded,
+/// so editing the file doesn't postpone entries whose interval is unchanged.
+pub fn carry_over(mut entries: Vec<ScheduleEntry>, previous: &[ScheduleEntry]) -> Vec<ScheduleEntry> {
+    for entry in &mut entries {
+        let Some(old) = previous.iter().find(|old| old.id == entry.id) else {
+            continue;
+        };
+        entry.last_run_at = old.last_run_at.clone();
+        entry.last_task_id = old.last_task_id.clone();
+        if entry.enabled && old.enabled && old.interval_secs == entry.interval_secs {
+            entry.next_run_at = old.next_run_at.clone();
+        }
+    }
+    entries
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEDULE: &str = r#"
+[[schedules]]
+id = "lint"
+every = "30m"
+run = { type = "task", provider = "just", name = "lint" }
+
+[[schedules]]
+id = "docker"
+name = "Refresh Docker services"
+every = "5m"
+enabled = false
+run = { type = "refresh_docker_services" }
+"#;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2024-01-01T12:00:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_schedule() {
+        let entries = parse_schedule_str(SCHEDULE, now()).unwrap();
+        assert_eq!(entries.len(), 2);
+
+        let lint = &entries[0];
+        assert_eq!(lint.name, "lint");
+        assert_eq!(lint.interval_secs, 30 * 60);
+        assert_eq!(
+            lint.job,
+            ScheduledJob::Task {
+                provider: TaskProviderKind::Just,
+                name: "lint".to_string(),
+                args: vec![],
+            }
+        );
+        assert_eq!(lint.next_run_at.as_deref(), Some("2024-01-01T12:30:00+00:00"));
+
+        let docker = &entries[1];
+        assert_eq!(docker.job, ScheduledJob::RefreshDockerServices);
+        assert!(!docker.enabled);
+        assert_eq!(docker.next_run_at, None);
+    }
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("90s"), Ok(90));
+        assert_eq!(parse_interval("30m"), Ok(1800));
+        assert_eq!(parse_interval("2h"), Ok(7200));
+        assert_eq!(parse_interval("1d"), Ok(86400));
+        assert!(parse_interval("10s").is_err());
+        assert!(parse_interval("5").is_err());
+        assert!(parse_interval("m").is_err());
+        assert!(parse_interval("5w").is_err());
+    }
+
+    #[test]
+    fn test_is_due() {
+        let entries = parse_schedule_str(SCHEDULE, now()).unwrap();
+        assert!(!entries[0].is_due(now()));
+        assert!(entries[0].is_due(now() + Duration::minutes(30)));
+        // Disabled entries never run
+        assert!(!entries[1].is_due(now() + Duration::days(1)));
+    }
+
+    #[test]
+    fn test_carry_over_keeps_next_run_for_unchanged_interval() {
+        let previous = parse_schedule_str(SCHEDULE, now()).unwrap();
+        let reloaded = parse_schedule_str(SCHEDULE, now() + Duration::minutes(10)).unwrap();
+        let merged = carry_over(reloaded, &previous);
+        assert_eq!(merged[0].next_run_at, previous[0].next_run_at);
+
+        let changed = SCHEDULE.replace("30m", "1h");
+        let reloaded = parse_schedule_str(&changed, now()).unwrap();
+        let merged = carry_over(reloaded, &previous);
+        assert_eq!(merged[0].next_run_at.as_deref(), Some("2024-01-01T13:00:00+00:00"));
+    }
+
+    #[test]
+    fn test_parse_schedule_rejects_invalid() {
+        let duplicate = "[[schedules]]\nid = \"a\"\nevery = \"1m\"\nrun = { type = \"refresh_docker_services\" }\n\
+                         [[schedules]]\nid = \"a\"\nevery = \"2m\"\nrun = { type = \"refresh_docker_services\" }\n";
+        assert!(parse_schedule_str(duplicate, now()).is_err());
+
+        let unknown_job = "[[schedules]]\nid = \"a\"\nevery = \"1m\"\nrun = { type = \"reboot\" }\n";
+        assert!(parse_schedule_str(unknown_job, now()).is_err());
+    }
+}
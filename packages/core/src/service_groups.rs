@@ -0,0 +1,330 @@
+//! Named groups of Docker services per project.
+//!
+//! Groups are declared in `<project>/.rstn/services.toml` and can be started
+//! and stopped as a unit:
+//!
+//! ```toml
+//! [[groups]]
+//! name = "backend"
+//! services = ["rstn-postgres", "rstn-redis", "rstn-shop-api"]
+//! # Optional: services that must be up before another one starts
+//! depends_on = { "rstn-shop-api" = ["rstn-postgres"] }
+//! ```
+//!
+//! Without explicit dependencies, infrastructure (databases, caches, message
+//! brokers) starts before everything else; within that, services start in
+//! the listed order. Groups stop in reverse start order.
+//!
+//! The same file holds the project's service templates (`[[services]]`, see
+//! `service_templates`); saving groups keeps them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::app_state::{DockerServiceInfo, ServiceType};
+
+/// Group file name (in `<project>/.rstn/`)
+pub const SERVICES_FILE: &str = "services.toml";
+
+/// A named set of services started and stopped together
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceGroup {
+    pub name: String,
+    /// Service IDs (container names)
+    #[serde(default)]
+    pub services: Vec<String>,
+    /// Service ID -> services of the group it needs first
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub depends_on: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ServicesFile {
+    #[serde(default)]
+    groups: Vec<ServiceGroup>,
+    /// Service templates (parsed by `service_templates`)
+    #[serde(default, rename = "services")]
+    _templates: Vec<toml::Value>,
+}
+
+// ============================================================================
+// Editing
+// ============================================================================
+
+/// Move `service_id` into the group `group` (created if missing), or out of
+/// any group if None. A service belongs to at most one group; groups left
+/// empty are removed.
+pub fn assign(groups: &mut Vec<ServiceGroup>, service_id: &str, group: Option<&str>) {
+    for existing in groups.iter_mut() {
+        existing.services.retain(|id| id != service_id);
+        existing.depends_on.remove(service_id);
+        for deps in existing.depends_on.values_mut() {
+            deps.retain(|id| id != service_id);
+        }
+        existing.depends_on.retain(|_, deps| !deps.is_empty());
+    }
+
+    if let Some(name) = group.map(str::trim).filter(|n| !n.is_empty()) {
+        match groups.iter_mut().find(|g| g.name == name) {
+            Some(existing) => existing.services.push(service_id.to_string()),
+            None => groups.push(ServiceGroup {
+                name: name.to_string(),
+                services: vec![service_id.to_string()],
+                depends_on: BTreeMap::new(),
+            }),
+        }
+    }
+    groups.retain(|g| !g.services.is_empty());
+}
+
+// ============================================================================
+// Ordering
+// ============================================================================
+
+/// Order in which to start the group's services: dependencies first, then
+/// infrastructure before apps, then listed order.
+pub fn start_order(group: &ServiceGroup, services: &[DockerServiceInfo]) -> Result<Vec<String>, String> {
+    let tier = |id: &str| match services.iter().find(|s| s.id == id).map(|s| s.service_type) {
+        Some(ServiceType::Database | ServiceType::Cache | ServiceType::MessageBroker) => 0,
+        _ => 1,
+    };
+
+    let mut remaining: Vec<(usize, &String)> = group.services.iter().enumerate().collect();
+    let mut order: Vec<String> = Vec::with_capacity(remaining.len());
+    while !remaining.is_empty() {
+        let ready = remaining
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, id))| {
+                group
+                    .depends_on
+                    .get(*id)
+                    .is_none_or(|deps| deps.iter().all(|dep| order.contains(dep)))
+            })
+            .min_by_key(|(_, (listed, id))| (tier(id), *listed))
+            .map(|(pos, _)| pos);
+
+        let Some(pos) = ready else {
+            let stuck: Vec<&str> = remaining.iter().map(|(_, id)| id.as_str()).collect();
+            return Err(format!(
+                "Dependency cycle in group '{}': {}",
+                group.name,
+                stuck.join(", ")
+            ));
+        };
+        order.push(remaining.remove(pos).1.clone());
+    }
+    Ok(order)
+}
+
+/// Order in which to stop the group's services (dependents first)
+pub fn stop_order(group: &ServiceGroup, services: &[DockerServiceInfo]) -> Result<Vec<String>, String> {
+    let mut order = start_order(group, services)?;
+    order.reverse();
+    Ok(order)
+}
+
+// ============================================================================
+// Loading
+// ============================================================================
+
+/// Path to a project's group file (<project>/.rstn/services.toml)
+pub fn services_path(project_path: &Path) -> PathBuf {
+    project_path.join(".rstn").join(SERVICES_FILE)
+}
+
+/// Load a project's groups. A missing file defines none.
+pub fn load_groups(project_path: &Path) -> Result<Vec<ServiceGroup>, String> {
+    let path = services_path(project_path);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_groups_str(&content).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Parse group TOML content
+pub fn parse_groups_str(content: &str) -> Result<Vec<ServiceGroup>, String> {
+    let file: ServicesFile = toml::from_str(content).map_err(|e| format!("Invalid services file: {}", e))?;
+    validate(&file.groups)?;
+    Ok(file.groups)
+}
+
+/// Write a project's groups, keeping the rest of the file. The file is
+/// removed once it has neither groups nor templates.
+pub fn save_groups(project_path: &Path, groups: &[ServiceGroup]) -> Result<(), String> {
+    validate(groups)?;
+    let path = services_path(project_path);
+    let mut table = match std::fs::read_to_string(&path) {
+        Ok(content) => content
+            .parse::<toml::Table>()
+            .map_err(|e| format!("{}: Invalid services file: {}", path.display(), e))?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => toml::Table::new(),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    if groups.is_empty() {
+        table.remove("groups");
+    } else {
+        let value = toml::Value::try_from(groups)
+            .map_err(|e| format!("Failed to serialize service groups: {}", e))?;
+        table.insert("groups".to_string(), value);
+    }
+
+    if table.is_empty() {
+        return match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(format!("Failed to remove {}: {}", path.display(), e))
+            }
+            _ => Ok(()),
+        };
+    }
+
+    let content =
+        toml::to_string_pretty(&table).map_err(|e| format!("Failed to serialize service groups: {}", e))?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn validate(groups: &[ServiceGroup]) -> Result<(), String> {
+    let mut seen_services: Vec<&str> = Vec::new();
+    for (i, group) in groups.iter().enumerate() {
+        if group.name.trim().is_empty() {
+            return Err("Service group names cannot be empty".to_string());
+        }
+        if groups[..i].iter().any(|g| g.name == group.name) {
+            return Err(format!("Duplicate service group: {}", group.name));
+        }
+        for id in &group.services {
+            if seen_services.contains(&id.as_str()) {
+                return Err(format!("Service '{}' is in more than one group", id));
+            }
+            seen_services.push(id);
+        }
+        for (id, deps) in &group.depends_on {
+            if let Some(unknown) = std::iter::once(id).chain(deps).find(|s| !group.services.contains(s)) {
+                return Err(format!(
+                    "Group '{}': depends_on refers to '{}', which is not in the group",
+                    group.name, unknown
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::ServiceStatus;
+
+    fn service(id: &str, service_type: ServiceType) -> DockerServiceInfo {
+        DockerServiceInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            image: "image".to_string(),
+            status: ServiceStatus::Stopped,
+            port: None,
+            service_type,
+            project_group: None,
+            is_rstn_managed: true,
+        }
+    }
+
+    #[test]
+    fn test_start_order_puts_infrastructure_first() {
+        let groups = parse_groups_str(
+            r#"
+[[groups]]
+name = "backend"
+services = ["api", "worker", "rstn-postgres", "rstn-redis"]
+depends_on = { "worker" = ["api"] }
+"#,
+        )
+        .unwrap();
+        let services = vec![
+            service("api", ServiceType::Other),
+            service("worker", ServiceType::Other),
+            service("rstn-postgres", ServiceType::Database),
+            service("rstn-redis", ServiceType::Cache),
+        ];
+
+        let order = start_order(&groups[0], &services).unwrap();
+        assert_eq!(order, vec!["rstn-postgres", "rstn-redis", "api", "worker"]);
+        let order = stop_order(&groups[0], &services).unwrap();
+        assert_eq!(order, vec!["worker", "api", "rstn-redis", "rstn-postgres"]);
+    }
+
+    #[test]
+    fn test_explicit_dependencies_win_and_cycles_fail() {
+        let mut group = ServiceGroup {
+            name: "g".to_string(),
+            services: vec!["migrate".to_string(), "rstn-postgres".to_string()],
+            depends_on: BTreeMap::new(),
+        };
+        let services = vec![service("rstn-postgres", ServiceType::Database)];
+
+        // An app the database depends on starts first
+        group.depends_on.insert("rstn-postgres".to_string(), vec!["migrate".to_string()]);
+        assert_eq!(start_order(&group, &services).unwrap(), vec!["migrate", "rstn-postgres"]);
+
+        group.depends_on.insert("migrate".to_string(), vec!["rstn-postgres".to_string()]);
+        let err = start_order(&group, &services).unwrap_err();
+        assert!(err.contains("cycle"), "{}", err);
+    }
+
+    #[test]
+    fn test_assign_moves_services_between_groups() {
+        let mut groups = Vec::new();
+        assign(&mut groups, "rstn-postgres", Some("backend"));
+        assign(&mut groups, "api", Some("backend"));
+        assign(&mut groups, "web", Some("frontend"));
+        groups[0].depends_on.insert("api".to_string(), vec!["rstn-postgres".to_string()]);
+        assert_eq!(groups.len(), 2);
+
+        // Moving drops dependencies on the moved service; empty groups go away
+        assign(&mut groups, "rstn-postgres", Some("frontend"));
+        assert!(groups[0].depends_on.is_empty());
+        assign(&mut groups, "api", None);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "frontend");
+        assert_eq!(groups[0].services, vec!["web", "rstn-postgres"]);
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut groups = Vec::new();
+        assign(&mut groups, "rstn-postgres", Some("backend"));
+        assign(&mut groups, "api", Some("backend"));
+        groups[0].depends_on.insert("api".to_string(), vec!["rstn-postgres".to_string()]);
+
+        save_groups(dir.path(), &groups).unwrap();
+        assert_eq!(load_groups(dir.path()).unwrap(), groups);
+
+        save_groups(dir.path(), &[]).unwrap();
+        assert!(!services_path(dir.path()).exists());
+        assert!(load_groups(dir.path()).unwrap().is_empty());
+
+        // Templates in the same file survive saving groups
+        let template = "[[services]]\nid = \"minio\"\nimage = \"minio/minio\"\nports = [\"9000\"]\n";
+        std::fs::write(services_path(dir.path()), template).unwrap();
+        save_groups(dir.path(), &groups).unwrap();
+        save_groups(dir.path(), &[]).unwrap();
+        let templates = crate::service_templates::load_templates_file(&services_path(dir.path())).unwrap();
+        assert_eq!(templates[0].id, "rstn-minio");
+
+        assert!(parse_groups_str("[[groups]]\nname = \"a\"\nservices = [\"x\"]\ndepends_on = { x = [\"y\"] }").is_err());
+        assert!(parse_groups_str("[[groups]]\nname = \"a\"\nservices = [\"x\"]\n[[groups]]\nname = \"b\"\nservices = [\"x\"]").is_err());
+    }
+}
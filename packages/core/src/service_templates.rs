@@ -0,0 +1,312 @@
+//! User-defined Docker service templates.
+//!
+//! Extra services can be declared in `~/.rstn/services.toml` (global) and
+//! `<project>/.rstn/services.toml` (per project):
+//!
+//! ```toml
+//! [[services]]
+//! id = "minio"
+//! name = "MinIO"
+//! image = "minio/minio:latest"
+//! ports = ["9000:9000", "9001:9001"]
+//! volumes = ["~/.rstn/data/minio:/data"]
+//! service_type = "Other"
+//! healthcheck = { type = "http", path = "/minio/health/live" }
+//!
+//! [services.env]
+//! MINIO_ROOT_USER = "minio"
+//! ```
+//!
+//! Templates are merged with the built-in services by id: project templates
+//! override global ones, which override built-ins.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::docker::{builtin_services, HealthCheck, ServiceConfig};
+use crate::persistence;
+use crate::state::ServiceType;
+
+/// Template file name (in `~/.rstn/` and `<project>/.rstn/`)
+pub const SERVICES_FILE: &str = "services.toml";
+
+// ============================================================================
+// Raw TOML schema
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawTemplatesFile {
+    #[serde(default)]
+    services: Vec<RawTemplate>,
+    /// Service groups (parsed by `service_groups`)
+    #[serde(default, rename = "groups")]
+    _groups: Vec<toml::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawTemplate {
+    id: String,
+    name: Option<String>,
+    image: String,
+    /// "host:container" or "port"; the first entry is the primary port
+    ports: Vec<String>,
+    #[serde(default)]
+    env: BTreeMap<String, String>,
+    #[serde(default)]
+    volumes: Vec<String>,
+    #[serde(default)]
+    service_type: ServiceType,
+    healthcheck: Option<HealthCheck>,
+}
+
+// ============================================================================
+// Loading
+// ============================================================================
+
+/// Path to the global templates file (~/.rstn/services.toml)
+pub fn global_templates_path() -> PathBuf {
+    persistence::get_rstn_dir().join(SERVICES_FILE)
+}
+
+/// Path to a project's templates file (<project>/.rstn/services.toml)
+pub fn project_templates_path(project_path: &Path) -> PathBuf {
+    project_path.join(".rstn").join(SERVICES_FILE)
+}
+
+/// Built-in services merged with the global and (optionally) project templates.
+pub fn load_services(project_path: Option<&Path>) -> Result<Vec<ServiceConfig>, String> {
+    let mut services = builtin_services();
+    services = merge_services(services, load_templates_file(&global_templates_path())?);
+    if let Some(project_path) = project_path {
+        services = merge_services(services, load_templates_file(&project_templates_path(project_path))?);
+    }
+    Ok(services)
+}
+
+/// Load templates from a file. A missing file defines no templates.
+/// Relative volume paths resolve against the directory holding `.rstn/`.
+pub fn load_templates_file(path: &Path) -> Result<Vec<ServiceConfig>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let base_dir = path
+        .parent()
+        .and_then(Path::parent)
+        .unwrap_or(Path::new("."));
+
+    parse_templates_str(&content, base_dir).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Parse templates TOML content.
+///
+/// Relative volume paths (`./data:/data`) are resolved against `base_dir`,
+/// `~/` against the home directory.
+pub fn parse_templates_str(content: &str, base_dir: &Path) -> Result<Vec<ServiceConfig>, String> {
+    let raw: RawTemplatesFile =
+        toml::from_str(content).map_err(|e| format!("Invalid services file: {}", e))?;
+
+    let mut services: Vec<ServiceConfig> = Vec::new();
+    for template in raw.services {
+        let service = to_service_config(template, base_dir)?;
+        if services.iter().any(|s| s.id == service.id) {
+            return Err(format!("Duplicate service id: {}", service.id));
+        }
+        services.push(service);
+    }
+    Ok(services)
+}
+
+/// Merge `overrides` into `base`, replacing services with the same id.
+pub fn merge_services(mut base: Vec<ServiceConfig>, overrides: Vec<ServiceConfig>) -> Vec<ServiceConfig> {
+    for service in overrides {
+        match base.iter_mut().find(|s| s.id == service.id) {
+            Some(existing) => *existing = service,
+            None => base.push(service),
+        }
+    }
+    base
+}
+
+fn to_service_config(template: RawTemplate, base_dir: &Path) -> Result<ServiceConfig, String> {
+    if template.id.is_empty()
+        || !template
+            .id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+    {
+        return Err(format!(
+            "Invalid service id '{}' (use lowercase letters, digits, '-' or '_')",
+            template.id
+        ));
+    }
+    let id = if template.id.starts_with("rstn-") {
+        template.id
+    } else {
+        format!("rstn-{}", template.id)
+    };
+
+    let ports = template
+        .ports
+        .iter()
+        .map(|spec| parse_port(spec))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Service '{}': {}", id, e))?;
+    let Some((&(port, internal_port), extra_ports)) = ports.split_first() else {
+        return Err(format!("Service '{}' defines no ports", id));
+    };
+
+    Ok(ServiceConfig {
+        name: template.name.unwrap_or_else(|| id.trim_start_matches("rstn-").to_string()),
+        image: template.image,
+        port,
+        internal_port,
+        extra_ports: extra_ports.to_vec(),
+        env: template.env.into_iter().collect(),
+        volumes: template
+            .volumes
+            .iter()
+            .map(|v| resolve_volume(v, base_dir))
+            .collect(),
+        service_type: template.service_type,
+        healthcheck: template.healthcheck,
+        id,
+    })
+}
+
+/// Parse a port entry ("5432" or "15432:5432") into (host, container)
+fn parse_port(spec: &str) -> Result<(u16, u16), String> {
+    let (host, container) = spec.split_once(':').unwrap_or((spec, spec));
+    let parse = |s: &str| {
+        s.trim()
+            .parse::<u16>()
+            .map_err(|_| format!("Invalid port mapping: {}", spec))
+    };
+    Ok((parse(host)?, parse(container)?))
+}
+
+/// Resolve the host side of a `host:container[:mode]` bind
+fn resolve_volume(volume: &str, base_dir: &Path) -> String {
+    let (source, rest) = volume.split_once(':').unwrap_or((volume, ""));
+
+    let source = if let Some(relative) = source.strip_prefix("~/") {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join(relative)
+            .to_string_lossy()
+            .to_string()
+    } else if source.starts_with('.') {
+        base_dir.join(source).to_string_lossy().to_string()
+    } else {
+        source.to_string()
+    };
+
+    if rest.is_empty() {
+        source
+    } else {
+        format!("{}:{}", source, rest)
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATES: &str = r#"
+[[services]]
+id = "minio"
+name = "MinIO"
+image = "minio/minio:latest"
+ports = ["9000:9000", "19001:9001"]
+volumes = ["./data/minio:/data", "named:/backup:ro"]
+healthcheck = { type = "http", path = "/minio/health/live" }
+
+[services.env]
+MINIO_ROOT_USER = "minio"
+MINIO_ROOT_PASSWORD = "minio123"
+
+[[services]]
+id = "rstn-postgres"
+image = "postgres:15-alpine"
+ports = ["15432:5432"]
+service_type = "Database"
+healthcheck = { type = "command", command = ["pg_isready", "-U", "postgres"] }
+"#;
+
+    #[test]
+    fn test_parse_templates() {
+        let services = parse_templates_str(TEMPLATES, Path::new("/work")).unwrap();
+        assert_eq!(services.len(), 2);
+
+        let minio = &services[0];
+        assert_eq!(minio.id, "rstn-minio");
+        assert_eq!(minio.name, "MinIO");
+        assert_eq!((minio.port, minio.internal_port), (9000, 9000));
+        assert_eq!(minio.extra_ports, vec![(19001, 9001)]);
+        assert_eq!(
+            minio.env,
+            vec![
+                ("MINIO_ROOT_PASSWORD".to_string(), "minio123".to_string()),
+                ("MINIO_ROOT_USER".to_string(), "minio".to_string()),
+            ]
+        );
+        assert_eq!(minio.volumes, vec!["/work/./data/minio:/data", "named:/backup:ro"]);
+        assert_eq!(minio.service_type, ServiceType::Other);
+        assert_eq!(
+            minio.healthcheck,
+            Some(HealthCheck::Http {
+                path: "/minio/health/live".to_string()
+            })
+        );
+
+        let postgres = &services[1];
+        assert_eq!(postgres.id, "rstn-postgres");
+        assert_eq!(postgres.name, "postgres");
+        assert_eq!((postgres.port, postgres.internal_port), (15432, 5432));
+        assert_eq!(postgres.service_type, ServiceType::Database);
+    }
+
+    #[test]
+    fn test_merge_overrides_builtins_by_id() {
+        let templates = parse_templates_str(TEMPLATES, Path::new("/work")).unwrap();
+        let builtin_count = builtin_services().len();
+        let merged = merge_services(builtin_services(), templates);
+
+        assert_eq!(merged.len(), builtin_count + 1);
+        let postgres = merged.iter().find(|s| s.id == "rstn-postgres").unwrap();
+        assert_eq!(postgres.image, "postgres:15-alpine");
+        assert_eq!(merged.last().unwrap().id, "rstn-minio");
+    }
+
+    #[test]
+    fn test_parse_templates_rejects_invalid() {
+        let no_ports = "[[services]]\nid = \"x\"\nimage = \"x\"\nports = []\n";
+        assert!(parse_templates_str(no_ports, Path::new("/")).is_err());
+
+        let bad_port = "[[services]]\nid = \"x\"\nimage = \"x\"\nports = [\"80-90\"]\n";
+        assert!(parse_templates_str(bad_port, Path::new("/")).is_err());
+
+        let bad_id = "[[services]]\nid = \"My Service\"\nimage = \"x\"\nports = [\"80\"]\n";
+        assert!(parse_templates_str(bad_id, Path::new("/")).is_err());
+
+        let duplicate = "[[services]]\nid = \"x\"\nimage = \"x\"\nports = [\"80\"]\n\
+                         [[services]]\nid = \"rstn-x\"\nimage = \"y\"\nports = [\"81\"]\n";
+        assert!(parse_templates_str(duplicate, Path::new("/")).is_err());
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let services = load_templates_file(&dir.path().join("services.toml")).unwrap();
+        assert!(services.is_empty());
+    }
+}
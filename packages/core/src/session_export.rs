@@ -0,0 +1,383 @@
+//! Export a Claude session transcript to Markdown or JSON.
+//!
+//! The Claude CLI writes every session to a JSONL transcript
+//! (`~/.claude/projects/<cwd>/<session_id>.jsonl`, see
+//! `sessions::claude_log_path`). Its user prompts, assistant text, tool calls
+//! and tool results are rendered with their timings so a session can be
+//! attached to a PR description or an audit log.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, FixedOffset};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::sessions::{self, SessionRecord};
+
+/// Maximum length of a tool result in the Markdown export (JSON keeps all)
+const MARKDOWN_TOOL_OUTPUT_CHARS: usize = 4000;
+
+/// Transcript export format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.to_ascii_lowercase().as_str() {
+            "markdown" | "md" => Ok(ExportFormat::Markdown),
+            "json" => Ok(ExportFormat::Json),
+            other => Err(format!("Unknown export format: {} (expected markdown or json)", other)),
+        }
+    }
+}
+
+/// One step of a transcript
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TranscriptItem {
+    UserPrompt { text: String },
+    AssistantText { text: String },
+    ToolCall { id: String, name: String, input: Value },
+    ToolResult { tool_use_id: String, content: String, is_error: bool },
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TranscriptEntry {
+    /// When the CLI logged the entry (ISO 8601)
+    pub timestamp: Option<String>,
+    #[serde(flatten)]
+    pub item: TranscriptItem,
+}
+
+/// A session with its full transcript
+#[derive(Debug, Clone, Serialize)]
+pub struct Transcript {
+    pub session: SessionRecord,
+    pub entries: Vec<TranscriptEntry>,
+}
+
+/// Transcript file of a session: the recorded log file, or where the CLI
+/// would have written it
+fn transcript_path(session: &SessionRecord) -> Option<PathBuf> {
+    if let Some(log_file) = &session.log_file {
+        return Some(PathBuf::from(log_file));
+    }
+    let claude_session_id = session.claude_session_id.as_deref()?;
+    let home = dirs::home_dir()?;
+    Some(sessions::claude_log_path(&home, Path::new(&session.worktree_path), claude_session_id))
+}
+
+/// Load the transcript of a session
+pub fn load_transcript(session: &SessionRecord) -> Result<Transcript, String> {
+    let path = transcript_path(session).ok_or("Session has no Claude transcript (the run never started)")?;
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read transcript {}: {}", path.display(), e))?;
+    Ok(Transcript {
+        session: session.clone(),
+        entries: parse_transcript(&content),
+    })
+}
+
+/// Render a session's transcript and write it to `path`
+pub fn export(session: &SessionRecord, format: ExportFormat, path: &Path) -> Result<(), String> {
+    let transcript = load_transcript(session)?;
+    let rendered = match format {
+        ExportFormat::Markdown => render_markdown(&transcript),
+        ExportFormat::Json => serde_json::to_string_pretty(&transcript).map_err(|e| e.to_string())?,
+    };
+    std::fs::write(path, rendered).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Parse a Claude CLI JSONL transcript. Lines that are not messages
+/// (summaries, system events) and meta messages are skipped.
+pub fn parse_transcript(content: &str) -> Vec<TranscriptEntry> {
+    let mut entries = Vec::new();
+    for line in content.lines() {
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let role = match value.get("type").and_then(Value::as_str) {
+            Some(role @ ("user" | "assistant")) => role,
+            _ => continue,
+        };
+        if value.get("isMeta").and_then(Value::as_bool).unwrap_or(false) {
+            continue;
+        }
+        let timestamp = value.get("timestamp").and_then(Value::as_str).map(str::to_string);
+        let content = value.pointer("/message/content");
+        let mut push = |item| {
+            entries.push(TranscriptEntry {
+                timestamp: timestamp.clone(),
+                item,
+            })
+        };
+
+        match content {
+            Some(Value::String(text)) => push(text_item(role, text.clone())),
+            Some(Value::Array(blocks)) => {
+                for block in blocks {
+                    match block.get("type").and_then(Value::as_str) {
+                        Some("text") => {
+                            let text = block.get("text").and_then(Value::as_str).unwrap_or_default();
+                            if !text.trim().is_empty() {
+                                push(text_item(role, text.to_string()));
+                            }
+                        }
+                        Some("tool_use") => push(TranscriptItem::ToolCall {
+                            id: string_field(block, "id"),
+                            name: string_field(block, "name"),
+                            input: block.get("input").cloned().unwrap_or(Value::Null),
+                        }),
+                        Some("tool_result") => push(TranscriptItem::ToolResult {
+                            tool_use_id: string_field(block, "tool_use_id"),
+                            content: tool_result_text(block.get("content")),
+                            is_error: block.get("is_error").and_then(Value::as_bool).unwrap_or(false),
+                        }),
+                        // Thinking and image blocks are not exported
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    entries
+}
+
+fn text_item(role: &str, text: String) -> TranscriptItem {
+    if role == "user" {
+        TranscriptItem::UserPrompt { text }
+    } else {
+        TranscriptItem::AssistantText { text }
+    }
+}
+
+fn string_field(block: &Value, key: &str) -> String {
+    block.get(key).and_then(Value::as_str).unwrap_or_default().to_string()
+}
+
+/// Tool result content is a string or a list of content blocks
+fn tool_result_text(content: Option<&Value>) -> String {
+    match content {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(blocks)) => blocks
+            .iter()
+            .map(|block| match block.get("type").and_then(Value::as_str) {
+                Some("text") => block.get("text").and_then(Value::as_str).unwrap_or_default().to_string(),
+                Some(other) => format!("[{}]", other),
+                None => String::new(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn parse_time(timestamp: Option<&str>) -> Option<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(timestamp?).ok()
+}
+
+fn format_seconds(millis: i64) -> String {
+    let seconds = millis as f64 / 1000.0;
+    if seconds < 60.0 {
+        format!("{:.1}s", seconds)
+    } else {
+        format!("{}m {:02}s", (seconds / 60.0) as i64, (seconds % 60.0) as i64)
+    }
+}
+
+/// Code fence longer than any backtick run in `text`
+fn fenced(lang: &str, text: &str) -> String {
+    let longest = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let fence = "`".repeat(longest.max(2) + 1);
+    format!("{}{}\n{}\n{}\n", fence, lang, text.trim_end(), fence)
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}\n… ({} more characters)", &text[..end], text[end..].chars().count()),
+        None => text.to_string(),
+    }
+}
+
+/// Render a transcript as Markdown: a summary table, then each step with
+/// its offset from the start of the session
+pub fn render_markdown(transcript: &Transcript) -> String {
+    let session = &transcript.session;
+    let mut out = format!("# Claude session: {}\n\n", session.kind);
+    out.push_str("| | |\n|---|---|\n");
+    let mut row = |label: &str, value: String| out.push_str(&format!("| {} | {} |\n", label, value));
+    row("Started", session.started_at.clone());
+    row("Duration", format_seconds(session.duration_ms as i64));
+    row("Outcome", session.outcome.as_str().to_string());
+    row("Model", session.model.clone().unwrap_or_else(|| "CLI default".to_string()));
+    row("Worktree", format!("`{}`", session.worktree_path));
+    row("Tokens", format!("{} in / {} out", session.input_tokens, session.output_tokens));
+    row("Cost", format!("${:.4}", session.cost_usd));
+    if let Some(id) = &session.claude_session_id {
+        row("Claude session", format!("`{}`", id));
+    }
+    if let Some(error) = &session.error {
+        row("Error", error.replace('|', "\\|").replace('\n', " "));
+    }
+
+    let start = transcript
+        .entries
+        .iter()
+        .find_map(|e| parse_time(e.timestamp.as_deref()));
+    let offset = |entry: &TranscriptEntry| match (start, parse_time(entry.timestamp.as_deref())) {
+        (Some(start), Some(at)) => format!(" (+{})", format_seconds((at - start).num_milliseconds())),
+        _ => String::new(),
+    };
+    let mut call_times: HashMap<&str, DateTime<FixedOffset>> = HashMap::new();
+
+    for entry in &transcript.entries {
+        out.push('\n');
+        match &entry.item {
+            TranscriptItem::UserPrompt { text } => {
+                out.push_str(&format!("## User{}\n\n{}\n", offset(entry), text.trim_end()));
+            }
+            TranscriptItem::AssistantText { text } => {
+                out.push_str(&format!("## Assistant{}\n\n{}\n", offset(entry), text.trim_end()));
+            }
+            TranscriptItem::ToolCall { id, name, input } => {
+                if let Some(at) = parse_time(entry.timestamp.as_deref()) {
+                    call_times.insert(id, at);
+                }
+                let input = serde_json::to_string_pretty(input).unwrap_or_default();
+                out.push_str(&format!("### Tool call: {}{}\n\n{}", name, offset(entry), fenced("json", &input)));
+            }
+            TranscriptItem::ToolResult {
+                tool_use_id,
+                content,
+                is_error,
+            } => {
+                let took = match (call_times.get(tool_use_id.as_str()), parse_time(entry.timestamp.as_deref())) {
+                    (Some(called), Some(at)) => format!(" (took {})", format_seconds((at - *called).num_milliseconds())),
+                    _ => String::new(),
+                };
+                let title = if *is_error { "Tool error" } else { "Tool result" };
+                out.push_str(&format!(
+                    "#### {}{}\n\n{}",
+                    title,
+                    took,
+                    fenced("", &truncate(content, MARKDOWN_TOOL_OUTPUT_CHARS))
+                ));
+            }
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sessions::SessionOutcome;
+
+    const TRANSCRIPT: &str = r#"{"type":"summary","summary":"Fix the parser"}
+{"type":"user","timestamp":"2026-01-01T10:00:00.000Z","message":{"role":"user","content":"Fix the parser"}}
+{"type":"user","isMeta":true,"timestamp":"2026-01-01T10:00:00.100Z","message":{"role":"user","content":"<command-name>/clear</command-name>"}}
+{"type":"assistant","timestamp":"2026-01-01T10:00:02.000Z","message":{"role":"assistant","content":[{"type":"thinking","thinking":"..."},{"type":"text","text":"Reading it."},{"type":"tool_use","id":"t1","name":"Read","input":{"file_path":"src/parser.rs"}}]}}
+{"type":"user","timestamp":"2026-01-01T10:00:02.500Z","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":[{"type":"text","text":"fn parse() {}"}]}]}}
+{"type":"assistant","timestamp":"2026-01-01T10:01:05.000Z","message":{"role":"assistant","content":[{"type":"text","text":"Done."}]}}
+not json
+"#;
+
+    fn session() -> SessionRecord {
+        SessionRecord {
+            id: "s1".to_string(),
+            project_id: "p1".to_string(),
+            kind: "chat".to_string(),
+            worktree_path: "/work/app".to_string(),
+            claude_session_id: Some("abc".to_string()),
+            model: Some("sonnet".to_string()),
+            prompt_excerpt: "Fix the parser".to_string(),
+            started_at: "2026-01-01T10:00:00+00:00".to_string(),
+            duration_ms: 65_000,
+            input_tokens: 1200,
+            output_tokens: 80,
+            cost_usd: 0.012,
+            log_file: None,
+            outcome: SessionOutcome::Success,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_transcript() {
+        let entries = parse_transcript(TRANSCRIPT);
+        let items: Vec<&TranscriptItem> = entries.iter().map(|e| &e.item).collect();
+        assert_eq!(
+            items,
+            vec![
+                &TranscriptItem::UserPrompt { text: "Fix the parser".to_string() },
+                &TranscriptItem::AssistantText { text: "Reading it.".to_string() },
+                &TranscriptItem::ToolCall {
+                    id: "t1".to_string(),
+                    name: "Read".to_string(),
+                    input: serde_json::json!({ "file_path": "src/parser.rs" }),
+                },
+                &TranscriptItem::ToolResult {
+                    tool_use_id: "t1".to_string(),
+                    content: "fn parse() {}".to_string(),
+                    is_error: false,
+                },
+                &TranscriptItem::AssistantText { text: "Done.".to_string() },
+            ]
+        );
+        assert_eq!(entries[0].timestamp.as_deref(), Some("2026-01-01T10:00:00.000Z"));
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let transcript = Transcript {
+            session: session(),
+            entries: parse_transcript(TRANSCRIPT),
+        };
+        let markdown = render_markdown(&transcript);
+        assert!(markdown.starts_with("# Claude session: chat\n"));
+        assert!(markdown.contains("| Duration | 1m 05s |"));
+        assert!(markdown.contains("## User (+0.0s)\n\nFix the parser\n"));
+        assert!(markdown.contains("### Tool call: Read (+2.0s)\n\n```json\n{\n  \"file_path\": \"src/parser.rs\"\n}\n```\n"));
+        assert!(markdown.contains("#### Tool result (took 0.5s)\n\n```\nfn parse() {}\n```\n"));
+        assert!(markdown.contains("## Assistant (+1m 05s)\n\nDone.\n"));
+    }
+
+    #[test]
+    fn test_fenced_outlasts_backticks() {
+        assert_eq!(fenced("", "a ```b``` c"), "````\na ```b``` c\n````\n");
+        assert!(truncate(&"x".repeat(10), 4).ends_with("… (6 more characters)"));
+    }
+
+    #[test]
+    fn test_export_json_and_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("abc.jsonl");
+        std::fs::write(&log, TRANSCRIPT).unwrap();
+        let mut session = session();
+        session.log_file = Some(log.to_string_lossy().into_owned());
+
+        let out = dir.path().join("session.json");
+        export(&session, ExportFormat::parse("JSON").unwrap(), &out).unwrap();
+        let json: Value = serde_json::from_str(&std::fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!(json["session"]["outcome"], "success");
+        assert_eq!(json["entries"][2]["type"], "tool_call");
+        assert_eq!(json["entries"][2]["name"], "Read");
+
+        assert!(ExportFormat::parse("pdf").is_err());
+        session.log_file = Some(dir.path().join("missing.jsonl").to_string_lossy().into_owned());
+        assert!(export(&session, ExportFormat::Markdown, &out).is_err());
+    }
+}
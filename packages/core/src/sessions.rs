@@ -0,0 +1,295 @@
+//! Claude session history.
+//!
+//! Every Claude CLI invocation (chat, constitution, proposal, plan,
+//! implementation, context generation/sync) becomes a `SessionRecord`: what
+//! ran, in which worktree, for how long, with what token usage and outcome,
+//! and where the CLI wrote its transcript. Records are stored in SQLite and
+//! listed in the Session History view.
+//!
+//! A `SessionRecorder` is created when the CLI is spawned and fed every
+//! stream event; the record is finalized when the recorder is dropped, so
+//! runs that end early (spawn failure, timeout, cancellation) are recorded
+//! too.
+
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::claude_cli::{self, ClaudeStreamEvent};
+
+/// Maximum length of the prompt excerpt kept with a session
+const PROMPT_EXCERPT_CHARS: usize = 200;
+
+/// How a session ended
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionOutcome {
+    /// The CLI reported a successful result
+    Success,
+    /// The CLI reported an error result, or the run failed
+    Error,
+    /// The run ended without a result (cancelled, timed out, crashed)
+    Interrupted,
+}
+
+impl SessionOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SessionOutcome::Success => "success",
+            SessionOutcome::Error => "error",
+            SessionOutcome::Interrupted => "interrupted",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "success" => Ok(SessionOutcome::Success),
+            "error" => Ok(SessionOutcome::Error),
+            "interrupted" => Ok(SessionOutcome::Interrupted),
+            other => Err(format!("Unknown session outcome: {}", other)),
+        }
+    }
+}
+
+/// One Claude CLI invocation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SessionRecord {
+    /// rstn session ID (UUID)
+    pub id: String,
+    /// Project ID (path hash, as used by the database)
+    pub project_id: String,
+    /// What triggered the run (same values as usage sources: "chat", "plan", ...)
+    pub kind: String,
+    /// Worktree the CLI ran in
+    pub worktree_path: String,
+    /// Claude CLI session ID (from the result event)
+    pub claude_session_id: Option<String>,
+    /// Model requested via `--model` (None = CLI default)
+    pub model: Option<String>,
+    /// Beginning of the prompt
+    pub prompt_excerpt: String,
+    /// When the run started (ISO 8601)
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Cost in USD as reported by the CLI (0 when not reported)
+    pub cost_usd: f64,
+    /// Transcript written by the CLI (`~/.claude/projects/...`), if found
+    pub log_file: Option<String>,
+    pub outcome: SessionOutcome,
+    /// Error message for failed runs
+    pub error: Option<String>,
+}
+
+/// Callback receiving the finalized record
+pub type SessionSink = Box<dyn FnOnce(SessionRecord) + Send>;
+
+/// Tracks one Claude CLI invocation; the record is finalized and handed to
+/// the sink on drop
+pub struct SessionRecorder {
+    record: SessionRecord,
+    started: Instant,
+    outcome: Option<SessionOutcome>,
+    sink: Option<SessionSink>,
+}
+
+impl SessionRecorder {
+    pub fn start(
+        kind: &str,
+        project_id: String,
+        worktree_path: &Path,
+        model: Option<String>,
+        prompt: &str,
+        sink: SessionSink,
+    ) -> Self {
+        Self {
+            record: SessionRecord {
+                id: uuid::Uuid::new_v4().to_string(),
+                project_id,
+                kind: kind.to_string(),
+                worktree_path: worktree_path.to_string_lossy().into_owned(),
+                claude_session_id: None,
+                model,
+                prompt_excerpt: prompt_excerpt(prompt),
+                started_at: chrono::Utc::now().to_rfc3339(),
+                duration_ms: 0,
+                input_tokens: 0,
+                output_tokens: 0,
+                cost_usd: 0.0,
+                log_file: None,
+                outcome: SessionOutcome::Interrupted,
+                error: None,
+            },
+            started: Instant::now(),
+            outcome: None,
+            sink: Some(sink),
+        }
+    }
+
+    /// Feed a stream event; the result event carries session ID, usage and
+    /// whether the run succeeded
+    pub fn observe(&mut self, event: &ClaudeStreamEvent) {
+        let ClaudeStreamEvent::Result { subtype, data } = event else {
+            return;
+        };
+        if let Some(usage) = claude_cli::extract_usage(event) {
+            self.record.input_tokens = usage.input_tokens;
+            self.record.output_tokens = usage.output_tokens;
+            self.record.cost_usd = usage.cost_usd.unwrap_or(0.0);
+        }
+        if let Some(session_id) = data.get("session_id").and_then(|id| id.as_str()) {
+            self.record.claude_session_id = Some(session_id.to_string());
+        }
+
+        let is_error = data.get("is_error").and_then(|v| v.as_bool()).unwrap_or(false) || subtype.starts_with("error");
+        if is_error {
+            let message = data
+                .get("result")
+                .and_then(|r| r.as_str())
+                .filter(|r| !r.is_empty())
+                .unwrap_or(subtype);
+            self.fail(message);
+        } else if self.outcome.is_none() {
+            self.outcome = Some(SessionOutcome::Success);
+        }
+    }
+
+    /// Mark the run as failed (spawn error, non-zero exit, ...)
+    pub fn fail(&mut self, error: impl Into<String>) {
+        self.outcome = Some(SessionOutcome::Error);
+        self.record.error.get_or_insert_with(|| error.into());
+    }
+
+    /// rstn session ID of this run
+    pub fn id(&self) -> &str {
+        &self.record.id
+    }
+}
+
+impl Drop for SessionRecorder {
+    fn drop(&mut self) {
+        let Some(sink) = self.sink.take() else {
+            return;
+        };
+        let mut record = self.record.clone();
+        record.outcome = self.outcome.unwrap_or(SessionOutcome::Interrupted);
+        record.duration_ms = self.started.elapsed().as_millis() as u64;
+        if let (Some(home), Some(session_id)) = (dirs::home_dir(), &record.claude_session_id) {
+            let path = claude_log_path(&home, Path::new(&record.worktree_path), session_id);
+            if path.exists() {
+                record.log_file = Some(path.to_string_lossy().into_owned());
+            }
+        }
+        sink(record);
+    }
+}
+
+/// Transcript the Claude CLI writes for a session:
+/// `~/.claude/projects/<cwd with non-alphanumerics replaced by '-'>/<session_id>.jsonl`
+pub fn claude_log_path(home: &Path, cwd: &Path, session_id: &str) -> PathBuf {
+    let dir: String = cwd
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    home.join(".claude")
+        .join("projects")
+        .join(dir)
+        .join(format!("{}.jsonl", session_id))
+}
+
+fn prompt_excerpt(prompt: &str) -> String {
+    let prompt = prompt.trim();
+    match prompt.char_indices().nth(PROMPT_EXCERPT_CHARS) {
+        Some((end, _)) => format!("{}…", &prompt[..end]),
+        None => prompt.to_string(),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn recorder(sink: Arc<Mutex<Option<SessionRecord>>>) -> SessionRecorder {
+        SessionRecorder::start(
+            "plan",
+            "p1".to_string(),
+            Path::new("/tmp/rstn-sessions-test"),
+            Some("sonnet".to_string()),
+            "Write a plan",
+            Box::new(move |record| *sink.lock().unwrap() = Some(record)),
+        )
+    }
+
+    fn result_event(data: serde_json::Value) -> ClaudeStreamEvent {
+        ClaudeStreamEvent::Result {
+            subtype: "success".to_string(),
+            data,
+        }
+    }
+
+    #[test]
+    fn test_recorder_success() {
+        let sink = Arc::new(Mutex::new(None));
+        let mut session = recorder(sink.clone());
+        session.observe(&result_event(serde_json::json!({
+            "is_error": false,
+            "session_id": "abc",
+            "total_cost_usd": 0.25,
+            "usage": { "input_tokens": 100, "output_tokens": 20 }
+        })));
+        drop(session);
+
+        let record = sink.lock().unwrap().take().unwrap();
+        assert_eq!(record.outcome, SessionOutcome::Success);
+        assert_eq!(record.kind, "plan");
+        assert_eq!(record.claude_session_id.as_deref(), Some("abc"));
+        assert_eq!(record.input_tokens, 100);
+        assert_eq!(record.output_tokens, 20);
+        assert_eq!(record.cost_usd, 0.25);
+        assert_eq!(record.prompt_excerpt, "Write a plan");
+        assert!(record.error.is_none());
+    }
+
+    #[test]
+    fn test_recorder_error_and_interrupted() {
+        let sink = Arc::new(Mutex::new(None));
+        let mut session = recorder(sink.clone());
+        session.observe(&result_event(serde_json::json!({
+            "is_error": true,
+            "result": "Credit balance is too low"
+        })));
+        drop(session);
+        let record = sink.lock().unwrap().take().unwrap();
+        assert_eq!(record.outcome, SessionOutcome::Error);
+        assert_eq!(record.error.as_deref(), Some("Credit balance is too low"));
+
+        drop(recorder(sink.clone()));
+        let record = sink.lock().unwrap().take().unwrap();
+        assert_eq!(record.outcome, SessionOutcome::Interrupted);
+        assert!(record.claude_session_id.is_none());
+    }
+
+    #[test]
+    fn test_claude_log_path() {
+        let path = claude_log_path(Path::new("/home/me"), Path::new("/work/my.repo/app_1"), "abc");
+        assert_eq!(path, PathBuf::from("/home/me/.claude/projects/-work-my-repo-app-1/abc.jsonl"));
+    }
+
+    #[test]
+    fn test_prompt_excerpt_truncates_on_char_boundary() {
+        let long = "é".repeat(PROMPT_EXCERPT_CHARS + 5);
+        let excerpt = prompt_excerpt(&long);
+        assert_eq!(excerpt.chars().count(), PROMPT_EXCERPT_CHARS + 1);
+        assert!(excerpt.ends_with('…'));
+        assert_eq!(SessionOutcome::parse("error"), Ok(SessionOutcome::Error));
+        assert!(SessionOutcome::parse("bogus").is_err());
+    }
+}
@@ -0,0 +1,277 @@
+//! Settings profiles.
+//!
+//! A profile bundles the settings that differ between contexts (e.g. two
+//! clients): theme, Claude model, LLM provider and endpoints, hosting tokens,
+//! the default project path and default guardrails. Switching applies the
+//! whole bundle to the global settings at once.
+//!
+//! Profiles never hold keys, only references to them:
+//!
+//! ```text
+//! env:ACME_OPENAI_KEY        environment variable
+//! file:~/.secrets/acme.key   first line of a file
+//! ```
+//!
+//! Each profile is saved to `~/.rstn/profiles/<name>.json`.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app_state::{GitHostingSettings, GlobalSettings, LlmProviderKind, OllamaSettings, OpenAiSettings, Theme};
+use crate::guardrails::Guardrails;
+
+/// Profile directory (in `~/.rstn/`)
+pub const PROFILES_DIR: &str = "profiles";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SettingsProfile {
+    /// Letters, digits, spaces, `-` and `_`
+    pub name: String,
+    pub theme: Theme,
+    /// Claude model (None = CLI default)
+    pub model: Option<String>,
+    pub provider: LlmProviderKind,
+    /// OpenAI-compatible endpoint; the key comes from `secrets.openai_api_key`
+    pub openai: OpenAiSettings,
+    pub ollama: OllamaSettings,
+    pub secrets: ProfileSecrets,
+    /// Default path of the "Open Folder" dialog
+    pub default_project_path: Option<String>,
+    /// Guardrails for worktrees without `.rstn/guardrails.toml`
+    pub guardrails: Option<Guardrails>,
+}
+
+/// Secret references (`env:NAME` or `file:PATH`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileSecrets {
+    pub openai_api_key: Option<String>,
+    pub github_token: Option<String>,
+    pub gitlab_token: Option<String>,
+}
+
+/// Keys resolved from a profile's references
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResolvedSecrets {
+    pub openai_api_key: Option<String>,
+    pub github_token: Option<String>,
+    pub gitlab_token: Option<String>,
+}
+
+/// Check a profile name, which doubles as its file name
+pub fn validate_name(name: &str) -> Result<(), String> {
+    let valid = !name.trim().is_empty()
+        && name.len() <= 64
+        && name.chars().all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid profile name \"{}\": use letters, digits, spaces, - and _",
+            name
+        ))
+    }
+}
+
+/// Check that a secret reference has a known scheme
+pub fn validate_reference(reference: &str) -> Result<(), String> {
+    match reference.split_once(':') {
+        Some(("env", name)) if !name.is_empty() => Ok(()),
+        Some(("file", path)) if !path.is_empty() => Ok(()),
+        _ => Err(format!(
+            "Invalid secret reference \"{}\": use env:NAME or file:PATH",
+            reference
+        )),
+    }
+}
+
+impl SettingsProfile {
+    /// Check the name and every secret reference
+    pub fn validate(&self) -> Result<(), String> {
+        validate_name(&self.name)?;
+        let secrets = &self.secrets;
+        [&secrets.openai_api_key, &secrets.github_token, &secrets.gitlab_token]
+            .into_iter()
+            .flatten()
+            .try_for_each(|reference| validate_reference(reference))
+    }
+
+    /// Apply the profile to `settings`. Keys are cleared until
+    /// `apply_secrets` fills them in, so a switch never keeps the previous
+    /// profile's keys.
+    pub fn apply(&self, settings: &mut GlobalSettings) {
+        settings.active_profile = Some(self.name.clone());
+        settings.theme = self.theme;
+        settings.model = self.model.clone();
+        settings.provider = self.provider;
+        settings.openai = OpenAiSettings {
+            api_key: None,
+            ..self.openai.clone()
+        };
+        settings.ollama = self.ollama.clone();
+        settings.git_hosting = GitHostingSettings::default();
+        settings.default_project_path = self.default_project_path.clone();
+        settings.guardrails = self.guardrails.clone();
+    }
+
+    /// Resolve the secret references (reads the environment and files)
+    pub fn resolve_secrets(&self) -> Result<ResolvedSecrets, String> {
+        let resolve = |reference: &Option<String>| reference.as_deref().map(resolve).transpose();
+        Ok(ResolvedSecrets {
+            openai_api_key: resolve(&self.secrets.openai_api_key)?,
+            github_token: resolve(&self.secrets.github_token)?,
+            gitlab_token: resolve(&self.secrets.gitlab_token)?,
+        })
+    }
+}
+
+/// Read the secret a reference points to
+pub fn resolve(reference: &str) -> Result<String, String> {
+    validate_reference(reference)?;
+    let value = match reference.split_once(':') {
+        Some(("env", name)) => {
+            std::env::var(name).map_err(|_| format!("Environment variable {} is not set", name))?
+        }
+        Some((_, path)) => {
+            let path = expand_home(path);
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read secret {}: {}", path.display(), e))?;
+            content.lines().next().unwrap_or_default().to_string()
+        }
+        None => unreachable!("validated above"),
+    };
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        return Err(format!("Secret {} is empty", reference));
+    }
+    Ok(value)
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+pub fn profiles_dir(rstn_dir: &Path) -> PathBuf {
+    rstn_dir.join(PROFILES_DIR)
+}
+
+fn profile_path(rstn_dir: &Path, name: &str) -> Result<PathBuf, String> {
+    validate_name(name)?;
+    Ok(profiles_dir(rstn_dir).join(format!("{}.json", name)))
+}
+
+/// Load every profile, sorted by name (unreadable files are skipped)
+pub fn load_all(rstn_dir: &Path) -> Vec<SettingsProfile> {
+    let Ok(entries) = std::fs::read_dir(profiles_dir(rstn_dir)) else {
+        return Vec::new();
+    };
+    let mut profiles: Vec<SettingsProfile> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let loaded = std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|json| serde_json::from_str::<SettingsProfile>(&json).map_err(|e| e.to_string()));
+            match loaded {
+                Ok(profile) => Some(profile),
+                Err(e) => {
+                    tracing::warn!("Skipping settings profile {}: {}", path.display(), e);
+                    None
+                }
+            }
+        })
+        .collect();
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    profiles
+}
+
+pub fn save(rstn_dir: &Path, profile: &SettingsProfile) -> Result<(), String> {
+    let path = profile_path(rstn_dir, &profile.name)?;
+    std::fs::create_dir_all(profiles_dir(rstn_dir)).map_err(|e| format!("Failed to create profiles dir: {}", e))?;
+    let json = serde_json::to_string_pretty(profile).map_err(|e| format!("Failed to serialize profile: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+pub fn delete(rstn_dir: &Path, name: &str) -> Result<(), String> {
+    let path = profile_path(rstn_dir, name)?;
+    match std::fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to delete {}: {}", path.display(), e)),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_load_and_delete() {
+        let dir = tempfile::tempdir().unwrap();
+        let acme = SettingsProfile {
+            name: "acme".to_string(),
+            theme: Theme::Dark,
+            model: Some("opus".to_string()),
+            secrets: ProfileSecrets {
+                github_token: Some("env:ACME_GITHUB_TOKEN".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let personal = SettingsProfile {
+            name: "Personal".to_string(),
+            ..Default::default()
+        };
+        save(dir.path(), &personal).unwrap();
+        save(dir.path(), &acme).unwrap();
+        std::fs::write(profiles_dir(dir.path()).join("broken.json"), "{").unwrap();
+
+        assert_eq!(load_all(dir.path()), vec![personal, acme.clone()]);
+        assert!(save(dir.path(), &SettingsProfile { name: "../up".to_string(), ..acme }).is_err());
+
+        delete(dir.path(), "acme").unwrap();
+        delete(dir.path(), "acme").unwrap();
+        assert_eq!(load_all(dir.path()).len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        let key_file = dir.path().join("openai.key");
+        std::fs::write(&key_file, "sk-test\nignored\n").unwrap();
+        std::env::set_var("RSTN_TEST_PROFILE_TOKEN", " ghp_test ");
+
+        let profile = SettingsProfile {
+            name: "acme".to_string(),
+            secrets: ProfileSecrets {
+                openai_api_key: Some(format!("file:{}", key_file.display())),
+                github_token: Some("env:RSTN_TEST_PROFILE_TOKEN".to_string()),
+                gitlab_token: None,
+            },
+            ..Default::default()
+        };
+        assert!(profile.validate().is_ok());
+        assert_eq!(
+            profile.resolve_secrets().unwrap(),
+            ResolvedSecrets {
+                openai_api_key: Some("sk-test".to_string()),
+                github_token: Some("ghp_test".to_string()),
+                gitlab_token: None,
+            }
+        );
+
+        assert!(resolve("env:RSTN_TEST_PROFILE_UNSET").is_err());
+        assert!(validate_reference("sk-raw-key").is_err());
+        assert!(validate_name("").is_err());
+    }
+}
@@ -0,0 +1,224 @@
+//! Worktree snapshots taken before Claude implements a change.
+//!
+//! A snapshot is a commit of the whole working tree (tracked and untracked,
+//! ignored files excluded) built with a temporary index, so neither the
+//! working tree nor the real index is touched. It is kept alive by the ref
+//! `refs/rstn/snapshots/<change>` and recorded in
+//! `.rstn/changes/<name>/snapshot.json`.
+//!
+//! Rolling back moves HEAD back to the commit the snapshot was taken on,
+//! restores the snapshot's files and deletes files created since. `.rstn/`
+//! is left alone so change records survive. Changes that were staged when
+//! the snapshot was taken come back unstaged.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+/// Snapshot record file in the change directory
+pub const SNAPSHOT_FILE: &str = "snapshot.json";
+
+/// Paths a snapshot covers (everything but `.rstn/`)
+const PATHSPEC: [&str; 2] = [".", ":(exclude).rstn"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeSnapshot {
+    /// Snapshot commit
+    pub commit: String,
+    /// HEAD when the snapshot was taken
+    pub base: String,
+    /// Branch checked out when the snapshot was taken (None = detached)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// ISO 8601 timestamps
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restored_at: Option<String>,
+}
+
+impl ChangeSnapshot {
+    pub fn short_commit(&self) -> &str {
+        &self.commit[..self.commit.len().min(7)]
+    }
+}
+
+fn git(worktree: &Path, index_file: Option<&Path>, args: &[&str]) -> Result<String, String> {
+    let mut cmd = Command::new("git");
+    cmd.arg("-C").arg(worktree).args(args);
+    if let Some(index_file) = index_file {
+        cmd.env("GIT_INDEX_FILE", index_file);
+    }
+    let output = cmd.output().map_err(|e| format!("Failed to run git {}: {}", args[0], e))?;
+    if !output.status.success() {
+        return Err(format!("git {} failed: {}", args[0], String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn snapshot_ref(change_name: &str) -> String {
+    format!("refs/rstn/snapshots/{}", change_name)
+}
+
+pub fn record_path(worktree: &Path, change_name: &str) -> PathBuf {
+    worktree.join(".rstn").join("changes").join(change_name).join(SNAPSHOT_FILE)
+}
+
+/// Saved snapshot of a change (None if never taken)
+pub fn load(worktree: &Path, change_name: &str) -> Option<ChangeSnapshot> {
+    std::fs::read_to_string(record_path(worktree, change_name))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+}
+
+pub fn save(worktree: &Path, change_name: &str, snapshot: &ChangeSnapshot) -> Result<(), String> {
+    let path = record_path(worktree, change_name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(snapshot).map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+    crate::spec_kit::write_atomic(&path, &json)
+}
+
+/// Tree object of the working tree as it is now (tracked and untracked,
+/// `.rstn/` excluded), written through a temporary index
+fn write_working_tree(worktree: &Path) -> Result<String, String> {
+    let git_dir = PathBuf::from(git(worktree, None, &["rev-parse", "--absolute-git-dir"])?);
+    let index_file = git_dir.join(format!("rstn-snapshot-{}.index", std::process::id()));
+    let tree = (|| {
+        git(worktree, Some(&index_file), &["read-tree", "HEAD"])?;
+        let mut add = vec!["add", "-A", "--"];
+        add.extend(PATHSPEC);
+        git(worktree, Some(&index_file), &add)?;
+        git(worktree, Some(&index_file), &["write-tree"])
+    })();
+    let _ = std::fs::remove_file(&index_file);
+    tree
+}
+
+/// Unified diff from the snapshot to the working tree, new files included
+/// (what the implementation changed)
+pub fn diff(worktree: &Path, snapshot: &ChangeSnapshot) -> Result<String, String> {
+    let tree = write_working_tree(worktree)?;
+    let mut args = vec!["diff", "--no-color", snapshot.commit.as_str(), tree.as_str(), "--"];
+    args.extend(PATHSPEC);
+    git(worktree, None, &args)
+}
+
+/// Snapshot the worktree before implementing `change_name` and record it
+pub fn create(worktree: &Path, change_name: &str) -> Result<ChangeSnapshot, String> {
+    let base = git(worktree, None, &["rev-parse", "HEAD"])?;
+    let branch = git(worktree, None, &["symbolic-ref", "--short", "-q", "HEAD"]).ok().filter(|b| !b.is_empty());
+
+    let tree = write_working_tree(worktree)?;
+
+    let message = format!("rstn snapshot before implementing {}", change_name);
+    let commit = git(
+        worktree,
+        None,
+        &["-c", "user.name=rstn", "-c", "user.email=rstn@localhost", "commit-tree", &tree, "-p", &base, "-m", &message],
+    )?;
+    git(worktree, None, &["update-ref", &snapshot_ref(change_name), &commit])?;
+
+    let snapshot = ChangeSnapshot {
+        commit,
+        base,
+        branch,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        restored_at: None,
+    };
+    save(worktree, change_name, &snapshot)?;
+    Ok(snapshot)
+}
+
+/// Put the worktree back to the snapshot and record when it was restored
+pub fn restore(worktree: &Path, change_name: &str, snapshot: &ChangeSnapshot) -> Result<ChangeSnapshot, String> {
+    let branch = git(worktree, None, &["symbolic-ref", "--short", "-q", "HEAD"]).ok().filter(|b| !b.is_empty());
+    if branch != snapshot.branch {
+        return Err(format!(
+            "The snapshot was taken on {}, check it out before rolling back",
+            snapshot.branch.as_deref().unwrap_or("a detached HEAD")
+        ));
+    }
+
+    // HEAD and index back to where the snapshot started (drops new commits)
+    git(worktree, None, &["reset", "-q", &snapshot.base])?;
+    // Snapshot files into the index and working tree...
+    let mut checkout = vec!["checkout", snapshot.commit.as_str(), "--"];
+    checkout.extend(PATHSPEC);
+    git(worktree, None, &checkout)?;
+    // ...so everything still untracked was created after it
+    let mut clean = vec!["clean", "-fdq", "--"];
+    clean.extend(PATHSPEC);
+    git(worktree, None, &clean)?;
+    git(worktree, None, &["reset", "-q"])?;
+
+    let restored = ChangeSnapshot {
+        restored_at: Some(chrono::Utc::now().to_rfc3339()),
+        ..snapshot.clone()
+    };
+    save(worktree, change_name, &restored)?;
+    Ok(restored)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn init_repo(dir: &Path) {
+        for args in [
+            vec!["init", "-q", "-b", "main"],
+            vec!["config", "user.name", "Test"],
+            vec!["config", "user.email", "test@example.com"],
+        ] {
+            git(dir, None, &args).unwrap();
+        }
+        std::fs::write(dir.join("lib.rs"), "fn a() {}\n").unwrap();
+        std::fs::write(dir.join("README.md"), "# Readme\n").unwrap();
+        git(dir, None, &["add", "-A"]).unwrap();
+        git(dir, None, &["commit", "-q", "-m", "init"]).unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        init_repo(root);
+        // Work in progress before the agent starts
+        std::fs::write(root.join("lib.rs"), "fn a() {}\nfn wip() {}\n").unwrap();
+        std::fs::write(root.join("notes.txt"), "todo").unwrap();
+
+        let snapshot = create(root, "add-login").unwrap();
+        assert_eq!(snapshot.branch.as_deref(), Some("main"));
+        assert_eq!(load(root, "add-login"), Some(snapshot.clone()));
+        // Taking the snapshot leaves the worktree alone
+        assert_eq!(git(root, None, &["status", "--porcelain", "--", "lib.rs"]).unwrap(), "M lib.rs");
+
+        // The agent edits, deletes, creates and commits
+        std::fs::write(root.join("lib.rs"), "broken").unwrap();
+        std::fs::remove_file(root.join("README.md")).unwrap();
+        std::fs::write(root.join("new.rs"), "fn new() {}").unwrap();
+        git(root, None, &["add", "-A", "--", "."]).unwrap();
+        git(root, None, &["commit", "-q", "-m", "agent"]).unwrap();
+        std::fs::write(root.join("stray.txt"), "x").unwrap();
+
+        let diff = diff(root, &snapshot).unwrap();
+        assert!(diff.contains("+++ b/new.rs") && diff.contains("+++ b/stray.txt"));
+        assert!(diff.contains("--- a/README.md") && diff.contains("-fn wip() {}"));
+
+        let restored = restore(root, "add-login", &snapshot).unwrap();
+        assert!(restored.restored_at.is_some());
+        assert_eq!(git(root, None, &["rev-parse", "HEAD"]).unwrap(), snapshot.base);
+        assert_eq!(std::fs::read_to_string(root.join("lib.rs")).unwrap(), "fn a() {}\nfn wip() {}\n");
+        assert_eq!(std::fs::read_to_string(root.join("notes.txt")).unwrap(), "todo");
+        assert!(root.join("README.md").exists());
+        assert!(!root.join("new.rs").exists());
+        assert!(!root.join("stray.txt").exists());
+        // The change record survives the rollback
+        assert_eq!(load(root, "add-login").unwrap().restored_at, restored.restored_at);
+    }
+}
@@ -0,0 +1,422 @@
+//! Cross-artifact consistency analysis of a spec-kit feature (the analyze phase).
+//!
+//! A heuristic pass compares spec.md, plan.md and tasks.md for:
+//!
+//! - requirements of the spec (FR-001, NFR-002) that no task covers
+//! - requirement IDs referenced by the plan or tasks that the spec does not define
+//! - tasks that reference no requirement or user story (`[US1]`)
+//! - MUST / MUST NOT statements that contradict each other
+//!
+//! Claude can then verify the findings, dropping false positives and adding
+//! what the heuristics miss. The report is stored as `analysis.json` in the
+//! feature directory.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Report file in the feature directory
+pub const REPORT_FILE: &str = "analysis.json";
+
+/// Prefixes of requirement IDs tasks are expected to cover
+const REQUIREMENT_PREFIXES: [&str; 2] = ["FR", "NFR"];
+
+/// Task phases that are plumbing rather than requirement work
+const UNTRACED_PHASES: [&str; 3] = ["setup", "foundation", "polish"];
+
+/// What a finding is about
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingKind {
+    /// A requirement no task implements
+    UncoveredRequirement,
+    /// A requirement ID the spec does not define
+    UnknownReference,
+    /// A task tied to no requirement or user story
+    UntracedTask,
+    /// Statements that cannot both hold
+    Contradiction,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+/// Which pass reported a finding
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingSource {
+    Heuristic,
+    Claude,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AnalysisFinding {
+    pub kind: FindingKind,
+    pub severity: FindingSeverity,
+    /// Artifacts involved (e.g. ["spec.md", "tasks.md"])
+    pub artifacts: Vec<String>,
+    /// Requirement or task ID the finding is about
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    pub summary: String,
+    pub source: FindingSource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AnalysisReport {
+    /// Requirements defined by the spec
+    pub requirements: usize,
+    /// Requirements referenced by at least one task
+    pub covered: usize,
+    pub tasks: usize,
+    /// Most severe first
+    pub findings: Vec<AnalysisFinding>,
+    /// Claude verified the findings
+    pub verified: bool,
+    pub analyzed_at: String,
+}
+
+/// Requirement IDs (`FR-001`, `NFR-002`) in `text`, in order of first mention
+fn requirement_ids(text: &str) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for token in text.split(|c: char| !(c.is_ascii_alphanumeric() || c == '-')) {
+        let Some((prefix, number)) = token.split_once('-') else {
+            continue;
+        };
+        let is_id = REQUIREMENT_PREFIXES.contains(&prefix)
+            && number.len() >= 3
+            && number.chars().all(|c| c.is_ascii_digit());
+        if is_id && !ids.iter().any(|id| id == token) {
+            ids.push(token.to_string());
+        }
+    }
+    ids
+}
+
+/// Whether a task description tags a user story (`[US1]`)
+fn has_story_tag(description: &str) -> bool {
+    description.split('[').skip(1).any(|s| {
+        s.split_once(']')
+            .and_then(|(tag, _)| tag.strip_prefix("US"))
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+    })
+}
+
+/// Normative statements of an artifact: (normalized statement, negated, original)
+fn normative_statements(content: &str) -> Vec<(String, bool, String)> {
+    let mut statements = Vec::new();
+    for sentence in content.lines().flat_map(|line| line.split(". ")) {
+        let sentence = sentence.trim().trim_start_matches(['-', '*', ' ']).trim_end_matches('.');
+        let words: Vec<&str> = sentence.split_whitespace().collect();
+        let Some(at) = words.iter().position(|w| *w == "MUST" || *w == "SHALL") else {
+            continue;
+        };
+        let negated = words.get(at + 1) == Some(&"NOT");
+        let predicate = &words[at + 1 + usize::from(negated)..];
+        if at == 0 || predicate.is_empty() {
+            continue;
+        }
+        // Requirement labels ("**FR-002**:") are not part of the statement
+        let normalized = words[..at]
+            .iter()
+            .chain(predicate)
+            .filter(|w| requirement_ids(w).is_empty())
+            .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+            .filter(|w| !w.is_empty() && !matches!(w.as_str(), "a" | "an" | "the"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        statements.push((normalized, negated, sentence.to_string()));
+    }
+    statements
+}
+
+/// Heuristic pass over the three artifacts
+pub fn analyze(spec: &str, plan: &str, tasks_md: &str) -> AnalysisReport {
+    let defined = requirement_ids(spec);
+    let planned = requirement_ids(plan);
+    let tasked = requirement_ids(tasks_md);
+    let tasks = crate::spec_tasks::parse(tasks_md);
+    let mut findings = Vec::new();
+    let mut finding = |kind, severity, artifacts: &[&str], subject: Option<&str>, summary: String| {
+        findings.push(AnalysisFinding {
+            kind,
+            severity,
+            artifacts: artifacts.iter().map(|a| a.to_string()).collect(),
+            subject: subject.map(str::to_string),
+            summary,
+            source: FindingSource::Heuristic,
+        });
+    };
+
+    for id in &defined {
+        if tasked.contains(id) {
+            continue;
+        }
+        if planned.contains(id) {
+            finding(
+                FindingKind::UncoveredRequirement,
+                FindingSeverity::Medium,
+                &["spec.md", "tasks.md"],
+                Some(id),
+                format!("{} is planned but no task implements it", id),
+            );
+        } else {
+            finding(
+                FindingKind::UncoveredRequirement,
+                FindingSeverity::High,
+                &["spec.md", "plan.md", "tasks.md"],
+                Some(id),
+                format!("{} is not addressed by the plan or any task", id),
+            );
+        }
+    }
+
+    for (artifact, ids) in [("plan.md", &planned), ("tasks.md", &tasked)] {
+        for id in ids.iter().filter(|id| !defined.contains(id)) {
+            finding(
+                FindingKind::UnknownReference,
+                FindingSeverity::Medium,
+                &[artifact, "spec.md"],
+                Some(id),
+                format!("{} references {}, which the spec does not define", artifact, id),
+            );
+        }
+    }
+
+    for task in &tasks {
+        let phase = task.phase.to_lowercase();
+        if UNTRACED_PHASES.iter().any(|p| phase.contains(p)) {
+            continue;
+        }
+        if requirement_ids(&task.description).is_empty() && !has_story_tag(&task.description) {
+            finding(
+                FindingKind::UntracedTask,
+                FindingSeverity::Low,
+                &["tasks.md"],
+                Some(&task.id),
+                format!("{} references no requirement or user story", task.id),
+            );
+        }
+    }
+
+    // The same statement required in one place and forbidden in another
+    let mut seen: HashMap<String, (bool, &str, String)> = HashMap::new();
+    for (artifact, content) in [("spec.md", spec), ("plan.md", plan), ("tasks.md", tasks_md)] {
+        for (normalized, negated, original) in normative_statements(content) {
+            match seen.get(&normalized) {
+                Some((other_negated, other_artifact, other)) if *other_negated != negated => {
+                    let artifacts: &[&str] = if *other_artifact == artifact {
+                        &[artifact]
+                    } else {
+                        &[other_artifact, artifact]
+                    };
+                    finding(
+                        FindingKind::Contradiction,
+                        FindingSeverity::Critical,
+                        artifacts,
+                        None,
+                        format!("\"{}\" ({}) contradicts \"{}\" ({})", other, other_artifact, original, artifact),
+                    );
+                }
+                Some(_) => {}
+                None => {
+                    seen.insert(normalized, (negated, artifact, original));
+                }
+            }
+        }
+    }
+
+    findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+    AnalysisReport {
+        requirements: defined.len(),
+        covered: defined.iter().filter(|id| tasked.contains(id)).count(),
+        tasks: tasks.len(),
+        findings,
+        verified: false,
+        analyzed_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+/// Prompt asking Claude to verify the heuristic findings and add missed ones
+pub fn build_verification_prompt(spec: &str, plan: &str, tasks_md: &str, report: &AnalysisReport) -> String {
+    let findings = serde_json::to_string_pretty(&report.findings).unwrap_or_default();
+    format!(
+        r###"You are checking a feature's spec, plan and tasks for consistency.
+
+## spec.md
+{}
+
+## plan.md
+{}
+
+## tasks.md
+{}
+
+## Heuristic Findings
+{}
+
+## Instructions
+Verify each heuristic finding against the documents and drop false positives (e.g. a requirement covered by a task that words it differently).
+Add findings the heuristics missed: requirements no task implements, tasks that implement nothing in the spec, and statements in different documents that contradict each other (including conflicting numbers, formats or technologies).
+
+Kinds: "uncovered_requirement", "unknown_reference", "untraced_task", "contradiction".
+Severity: "critical" (contradiction that blocks implementation), "high", "medium", "low".
+
+Respond with ONLY a JSON object, no other text:
+{{"findings": [{{"kind": "<kind>", "severity": "<severity>", "artifacts": ["spec.md"], "subject": "<requirement or task ID, or null>", "summary": "<one sentence>"}}]}}"###,
+        spec.trim(),
+        plan.trim(),
+        tasks_md.trim(),
+        findings
+    )
+}
+
+#[derive(Deserialize)]
+struct RawVerification {
+    #[serde(default)]
+    findings: Vec<RawFinding>,
+}
+
+#[derive(Deserialize)]
+struct RawFinding {
+    kind: FindingKind,
+    #[serde(default)]
+    severity: String,
+    #[serde(default)]
+    artifacts: Vec<String>,
+    #[serde(default)]
+    subject: Option<String>,
+    #[serde(default)]
+    summary: String,
+}
+
+fn parse_severity(severity: &str) -> FindingSeverity {
+    match severity.trim().to_lowercase().as_str() {
+        "critical" | "blocking" => FindingSeverity::Critical,
+        "high" => FindingSeverity::High,
+        "medium" | "warning" => FindingSeverity::Medium,
+        _ => FindingSeverity::Low,
+    }
+}
+
+/// Replace the report's findings with Claude's verified list. Tolerates
+/// surrounding prose and code fences around the JSON object.
+pub fn apply_verification(report: &AnalysisReport, output: &str) -> Result<AnalysisReport, String> {
+    let json = match (output.find('{'), output.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &output[start..=end],
+        _ => return Err("Analysis verification returned no JSON".to_string()),
+    };
+    let raw: RawVerification =
+        serde_json::from_str(json).map_err(|e| format!("Invalid analysis verification: {}", e))?;
+
+    let mut findings: Vec<AnalysisFinding> = raw
+        .findings
+        .into_iter()
+        .filter(|f| !f.summary.trim().is_empty())
+        .map(|f| {
+            let subject = f.subject.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+            // Confirmed heuristic findings keep their origin
+            let confirmed = subject.is_some()
+                && report.findings.iter().any(|h| h.kind == f.kind && h.subject == subject);
+            AnalysisFinding {
+                kind: f.kind,
+                severity: parse_severity(&f.severity),
+                artifacts: f.artifacts,
+                subject,
+                summary: f.summary.trim().to_string(),
+                source: if confirmed { FindingSource::Heuristic } else { FindingSource::Claude },
+            }
+        })
+        .collect();
+    findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
+
+    Ok(AnalysisReport {
+        findings,
+        verified: true,
+        analyzed_at: chrono::Utc::now().to_rfc3339(),
+        ..report.clone()
+    })
+}
+
+/// Write the report to the feature directory
+pub fn save(feature_dir: &Path, report: &AnalysisReport) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(report).map_err(|e| format!("Failed to serialize analysis: {}", e))?;
+    crate::spec_kit::write_atomic(&feature_dir.join(REPORT_FILE), &json)
+}
+
+/// The stored report of a feature directory, if any
+pub fn load(feature_dir: &Path) -> Option<AnalysisReport> {
+    let json = std::fs::read_to_string(feature_dir.join(REPORT_FILE)).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = "# Spec\n\n## Requirements\n- **FR-001**: Admins can export users as CSV.\n- **FR-002**: Exports MUST include deactivated users.\n- **NFR-001**: Exports finish within 10 seconds.\n\nFormat dates as ISO-8601 and text as UTF-8.\n";
+    const PLAN: &str = "# Plan\n\nFR-001 and FR-002 go through the export service; FR-004 adds a schedule.\n\nExports MUST NOT include deactivated users.\n";
+    const TASKS: &str = "# Tasks\n\n## Phase 1: Setup\n- [ ] T001 Create the export module\n\n## Phase 2: Core\n- [ ] T002 [P] [US1] Implement CSV writer\n- [ ] T003 Add the export endpoint for FR-001\n- [ ] T004 Add a progress bar\n";
+
+    #[test]
+    fn test_analyze() {
+        let report = analyze(SPEC, PLAN, TASKS);
+        assert_eq!((report.requirements, report.covered, report.tasks), (3, 1, 4));
+
+        let subjects = |kind: FindingKind| -> Vec<Option<String>> {
+            report.findings.iter().filter(|f| f.kind == kind).map(|f| f.subject.clone()).collect()
+        };
+        assert_eq!(
+            subjects(FindingKind::UncoveredRequirement),
+            vec![Some("NFR-001".to_string()), Some("FR-002".to_string())]
+        );
+        assert_eq!(subjects(FindingKind::UnknownReference), vec![Some("FR-004".to_string())]);
+        // Setup tasks and [US1]-tagged tasks are traced
+        assert_eq!(subjects(FindingKind::UntracedTask), vec![Some("T004".to_string())]);
+
+        let contradiction = &report.findings[0];
+        assert_eq!(contradiction.kind, FindingKind::Contradiction);
+        assert_eq!(contradiction.severity, FindingSeverity::Critical);
+        assert_eq!(contradiction.artifacts, vec!["spec.md", "plan.md"]);
+    }
+
+    #[test]
+    fn test_apply_verification() {
+        let report = analyze(SPEC, PLAN, TASKS);
+        let output = r#"Here you go:
+```json
+{"findings": [
+  {"kind": "uncovered_requirement", "severity": "high", "artifacts": ["spec.md"], "subject": "NFR-001", "summary": "No task covers the 10 second limit"},
+  {"kind": "contradiction", "severity": "critical", "artifacts": ["spec.md", "plan.md"], "subject": null, "summary": "CSV vs JSON export format"}
+]}
+```"#;
+        let verified = apply_verification(&report, output).unwrap();
+        assert!(verified.verified);
+        assert_eq!(verified.requirements, 3);
+        assert_eq!(verified.findings.len(), 2);
+        assert_eq!(verified.findings[0].source, FindingSource::Claude);
+        assert_eq!(verified.findings[1].source, FindingSource::Heuristic);
+
+        assert!(apply_verification(&report, "no findings").is_err());
+    }
+
+    #[test]
+    fn test_save_load() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(load(dir.path()).is_none());
+        let report = analyze(SPEC, PLAN, TASKS);
+        save(dir.path(), &report).unwrap();
+        assert_eq!(load(dir.path()), Some(report));
+    }
+}
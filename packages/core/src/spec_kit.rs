@@ -0,0 +1,546 @@
+//! Spec-kit workflow engine: specify → clarify → plan → tasks.
+//!
+//! Each feature lives in `specs/<NNN>-<short-name>/` of the worktree. Every
+//! phase is one Claude run whose streamed output becomes an artifact:
+//!
+//! - specify: `spec.md` from the feature description
+//! - clarify: `spec.md` rewritten with ambiguities resolved (from the user's
+//!   answers, or as documented assumptions)
+//! - plan: `plan.md` from the spec
+//! - tasks: `tasks.md` from the spec and plan
+//!
+//! The project constitution, when present, is included in every prompt.
+//! The analyze phase runs natively instead; see [`crate::spec_analysis`].
+//! Without Claude, specify falls back to [`template_spec`].
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Directory (relative to the worktree) holding one directory per feature
+pub const SPECS_DIR: &str = "specs";
+
+/// Default number of ambiguities the clarify phase resolves
+const DEFAULT_MAX_QUESTIONS: usize = 5;
+
+/// Phase of the spec-driven workflow
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecPhase {
+    Specify,
+    Clarify,
+    Plan,
+    Tasks,
+}
+
+impl SpecPhase {
+    /// All phases, in workflow order
+    pub const ALL: [SpecPhase; 4] = [SpecPhase::Specify, SpecPhase::Clarify, SpecPhase::Plan, SpecPhase::Tasks];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SpecPhase::Specify => "specify",
+            SpecPhase::Clarify => "clarify",
+            SpecPhase::Plan => "plan",
+            SpecPhase::Tasks => "tasks",
+        }
+    }
+
+    /// Artifact the phase writes
+    pub fn artifact(self) -> &'static str {
+        match self {
+            SpecPhase::Specify | SpecPhase::Clarify => "spec.md",
+            SpecPhase::Plan => "plan.md",
+            SpecPhase::Tasks => "tasks.md",
+        }
+    }
+
+    /// Artifacts that must exist before the phase can run
+    pub fn requires(self) -> &'static [&'static str] {
+        match self {
+            SpecPhase::Specify => &[],
+            SpecPhase::Clarify | SpecPhase::Plan => &["spec.md"],
+            SpecPhase::Tasks => &["spec.md", "plan.md"],
+        }
+    }
+}
+
+/// Options of the specify phase
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpecifyConfig {
+    /// What the feature should do, in the user's words
+    pub description: String,
+}
+
+/// Options of the clarify phase
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClarifyConfig {
+    /// Answers or notes from the user to fold into the spec (None = Claude
+    /// resolves the ambiguities itself and records its assumptions)
+    pub answers: Option<String>,
+    /// Maximum number of ambiguities to resolve
+    pub max_questions: usize,
+}
+
+/// Options of the plan phase
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanConfig {
+    /// Tech stack or architecture constraints the plan must follow
+    pub tech_notes: Option<String>,
+}
+
+/// Phase to run, with its options
+#[derive(Debug, Clone, PartialEq)]
+pub enum PhaseConfig {
+    Specify(SpecifyConfig),
+    Clarify(ClarifyConfig),
+    Plan(PlanConfig),
+    Tasks,
+}
+
+impl PhaseConfig {
+    /// Config for a phase from the feature description and the optional
+    /// user input of a run (answers for clarify, tech notes for plan)
+    pub fn new(phase: SpecPhase, description: &str, input: Option<String>) -> Self {
+        let input = input.filter(|s| !s.trim().is_empty());
+        match phase {
+            SpecPhase::Specify => PhaseConfig::Specify(SpecifyConfig {
+                description: description.to_string(),
+            }),
+            SpecPhase::Clarify => PhaseConfig::Clarify(ClarifyConfig {
+                answers: input,
+                max_questions: DEFAULT_MAX_QUESTIONS,
+            }),
+            SpecPhase::Plan => PhaseConfig::Plan(PlanConfig { tech_notes: input }),
+            SpecPhase::Tasks => PhaseConfig::Tasks,
+        }
+    }
+
+    pub fn phase(&self) -> SpecPhase {
+        match self {
+            PhaseConfig::Specify(_) => SpecPhase::Specify,
+            PhaseConfig::Clarify(_) => SpecPhase::Clarify,
+            PhaseConfig::Plan(_) => SpecPhase::Plan,
+            PhaseConfig::Tasks => SpecPhase::Tasks,
+        }
+    }
+}
+
+/// A feature directory: `specs/<NNN>-<short_name>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecFeature {
+    pub number: u32,
+    pub short_name: String,
+}
+
+impl SpecFeature {
+    /// Directory name, e.g. "007-user-login"
+    pub fn dir_name(&self) -> String {
+        format!("{:03}-{}", self.number, self.short_name)
+    }
+
+    /// Directory relative to the worktree, e.g. "specs/007-user-login"
+    pub fn relative_dir(&self) -> String {
+        format!("{}/{}", SPECS_DIR, self.dir_name())
+    }
+
+    pub fn dir(&self, worktree: &Path) -> PathBuf {
+        worktree.join(SPECS_DIR).join(self.dir_name())
+    }
+}
+
+/// Number for the next feature: one more than the highest `NNN-` prefix in `specs/`
+pub fn next_feature_number(worktree: &Path) -> u32 {
+    let Ok(entries) = std::fs::read_dir(worktree.join(SPECS_DIR)) else {
+        return 1;
+    };
+    entries
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().into_owned();
+            name.split_once('-').and_then(|(number, _)| number.parse::<u32>().ok())
+        })
+        .max()
+        .map_or(1, |n| n + 1)
+}
+
+/// Feature directories under `specs/`, by number
+pub fn list_features(worktree: &Path) -> Vec<SpecFeature> {
+    let Ok(entries) = std::fs::read_dir(worktree.join(SPECS_DIR)) else {
+        return Vec::new();
+    };
+    let mut features: Vec<SpecFeature> = entries
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().into_owned();
+            let (number, short_name) = name.split_once('-')?;
+            Some(SpecFeature {
+                number: number.parse().ok()?,
+                short_name: short_name.to_string(),
+            })
+        })
+        .collect();
+    features.sort_by_key(|f| f.number);
+    features
+}
+
+/// Feature by directory name ("007-user-login"), short name or number
+pub fn find_feature(worktree: &Path, query: &str) -> Option<SpecFeature> {
+    let query = query.trim().trim_start_matches(&format!("{}/", SPECS_DIR)).trim_end_matches('/');
+    let number = query.parse::<u32>().ok();
+    list_features(worktree)
+        .into_iter()
+        .find(|f| f.dir_name() == query || f.short_name == query || Some(f.number) == number)
+}
+
+/// Create the directory of a new feature
+pub fn create_feature(worktree: &Path, short_name: &str) -> Result<SpecFeature, String> {
+    let feature = SpecFeature {
+        number: next_feature_number(worktree),
+        short_name: if short_name.is_empty() { "feature".to_string() } else { short_name.to_string() },
+    };
+    let dir = feature.dir(worktree);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    Ok(feature)
+}
+
+/// Read an artifact of the feature directory
+pub fn read_artifact(feature_dir: &Path, name: &str) -> Option<String> {
+    std::fs::read_to_string(feature_dir.join(name)).ok()
+}
+
+/// Artifacts required by the phase that are missing
+pub fn missing_artifacts(phase: SpecPhase, feature_dir: &Path) -> Vec<&'static str> {
+    phase
+        .requires()
+        .iter()
+        .copied()
+        .filter(|name| !feature_dir.join(name).is_file())
+        .collect()
+}
+
+/// Copy a feature's artifacts to another worktree (e.g. the feature's own),
+/// keeping files the target already has. Returns the number of files copied.
+pub fn copy_feature(feature: &SpecFeature, from_worktree: &Path, to_worktree: &Path) -> Result<usize, String> {
+    let from = feature.dir(from_worktree);
+    let to = feature.dir(to_worktree);
+    let entries = std::fs::read_dir(&from).map_err(|e| format!("Failed to read {}: {}", from.display(), e))?;
+    std::fs::create_dir_all(&to).map_err(|e| format!("Failed to create {}: {}", to.display(), e))?;
+    let mut copied = 0;
+    for entry in entries.flatten().filter(|e| e.path().is_file()) {
+        let target = to.join(entry.file_name());
+        if !target.exists() {
+            std::fs::copy(entry.path(), &target).map_err(|e| format!("Failed to copy to {}: {}", target.display(), e))?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+/// Replace an artifact with a sibling temp file and a rename, so a crash
+/// never leaves a half-written document behind
+pub fn write_atomic(path: &Path, content: &str) -> Result<(), String> {
+    let tmp = path.with_file_name(format!(
+        ".{}.rstn-tmp",
+        path.file_name().unwrap_or_default().to_string_lossy()
+    ));
+    std::fs::write(&tmp, content).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    std::fs::rename(&tmp, path).map_err(|e| {
+        let _ = std::fs::remove_file(&tmp);
+        format!("Failed to replace {}: {}", path.display(), e)
+    })
+}
+
+/// Prompt for a phase. `feature_dir` is read for the artifacts the phase
+/// builds on; `constitution` is the project's rules, if any.
+pub fn build_prompt(config: &PhaseConfig, feature: &SpecFeature, feature_dir: &Path, constitution: Option<&str>) -> String {
+    let spec = read_artifact(feature_dir, "spec.md").unwrap_or_default();
+    let plan = read_artifact(feature_dir, "plan.md").unwrap_or_default();
+
+    let mut prompt = String::new();
+    if let Some(constitution) = constitution.filter(|c| !c.trim().is_empty()) {
+        prompt.push_str(&format!(
+            "## Project Constitution\nFollow these project rules:\n\n{}\n\n",
+            constitution.trim()
+        ));
+    }
+
+    let task = match config {
+        PhaseConfig::Specify(SpecifyConfig { description }) => format!(
+            r#"You are writing the feature specification for feature {} ("{}").
+
+## Feature Description
+{}
+
+## Instructions
+Write a spec.md document describing WHAT the feature must do and WHY, not how to build it:
+1. **Overview** - The problem and who it is for
+2. **User Scenarios** - Primary flows as Given/When/Then acceptance scenarios, plus edge cases
+3. **Functional Requirements** - Numbered, testable requirements (FR-001, FR-002, ...)
+4. **Key Entities** - Data the feature works with (if any)
+5. **Success Criteria** - Measurable outcomes
+6. **Out of Scope** - What this feature deliberately does not do
+
+Mark anything the description leaves ambiguous with [NEEDS CLARIFICATION: question].
+Do not mention frameworks, libraries or file names."#,
+            feature.dir_name(),
+            feature.short_name,
+            description.trim()
+        ),
+        PhaseConfig::Clarify(ClarifyConfig { answers, max_questions }) => {
+            let resolution = match answers {
+                Some(answers) => format!(
+                    "Resolve them using these answers from the user:\n\n{}\n\nWhere an answer does not cover an ambiguity, choose the most reasonable option.",
+                    answers.trim()
+                ),
+                None => "Resolve each by choosing the most reasonable option.".to_string(),
+            };
+            format!(
+                r###"You are clarifying the feature specification below.
+
+## Current Specification
+{}
+
+## Instructions
+Find the (up to {}) most important ambiguities: [NEEDS CLARIFICATION] markers, vague requirements, missing edge cases or unstated constraints.
+{}
+
+Output the complete updated spec.md: the ambiguities resolved in place and a "## Clarifications" section listing each question with the chosen answer (mark answers you assumed with "(assumed)")."###,
+                spec.trim(),
+                max_questions,
+                resolution
+            )
+        }
+        PhaseConfig::Plan(PlanConfig { tech_notes }) => {
+            let notes = tech_notes
+                .as_deref()
+                .map(|notes| format!("\n## Technical Constraints\n{}\n", notes.trim()))
+                .unwrap_or_default();
+            format!(
+                r#"You are a senior software architect planning the implementation of the feature specification below, in this repository.
+
+## Specification
+{}
+{}
+## Instructions
+Write a plan.md document:
+1. **Technical Context** - Languages, frameworks and modules involved (inspect the repository)
+2. **Architecture** - Components to add or change and how they interact
+3. **Data Model** - Entities, fields and state changes
+4. **Interfaces** - APIs, actions or commands added or changed
+5. **Testing Strategy** - How each functional requirement is verified
+6. **Risks** - What could go wrong and how to mitigate it
+
+Reference the spec's requirement IDs (FR-xxx) where they are addressed."#,
+                spec.trim(),
+                notes
+            )
+        }
+        PhaseConfig::Tasks => format!(
+            r#"You are breaking the implementation plan below into tasks.
+
+## Specification
+{}
+
+## Plan
+{}
+
+## Instructions
+Write a tasks.md document: a dependency-ordered checklist grouped into phases (Setup, Tests, Core, Integration, Polish).
+Each task is one line: `- [ ] T001 Description (path/to/file)`, small enough to finish in one session.
+Mark tasks that can run in parallel with [P]. Reference the requirement IDs (FR-xxx) each task covers."#,
+            spec.trim(),
+            plan.trim()
+        ),
+    };
+    prompt.push_str(&task);
+    prompt.push_str("\n\nOutput ONLY the markdown content of the document, no code fences or extra commentary.");
+    prompt
+}
+
+/// spec.md filled from the template without Claude: the description's
+/// sentences become draft requirements and every section that needs a human
+/// decision is marked `[NEEDS CLARIFICATION: ...]` for the clarify session.
+pub fn template_spec(feature: &SpecFeature, description: &str, created: &str) -> String {
+    let description = description.trim();
+    let title = feature
+        .short_name
+        .split('-')
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            chars.next().map(|c| c.to_uppercase().chain(chars).collect::<String>()).unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let sentences: Vec<&str> = description
+        .split(['.', '\n'])
+        .map(|s| s.trim().trim_start_matches(['-', '*']).trim())
+        .filter(|s| !s.is_empty())
+        .collect();
+    let requirements = if sentences.is_empty() {
+        "- **FR-001**: [NEEDS CLARIFICATION: what must the system do?]".to_string()
+    } else {
+        sentences
+            .iter()
+            .enumerate()
+            .map(|(i, s)| format!("- **FR-{:03}**: {} [NEEDS CLARIFICATION: how is this verified?]", i + 1, s))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    format!(
+        r#"# Feature Specification: {title}
+
+**Feature**: `{dir}`
+**Created**: {created}
+**Status**: Draft (generated from the template; resolve the marked sections)
+
+## Overview
+{description}
+
+## User Scenarios
+### Primary Flow
+- **Given** [NEEDS CLARIFICATION: starting situation], **When** [NEEDS CLARIFICATION: user action], **Then** [NEEDS CLARIFICATION: expected outcome]
+
+### Edge Cases
+- [NEEDS CLARIFICATION: what happens with invalid, missing or very large input?]
+
+## Functional Requirements
+{requirements}
+
+## Key Entities
+- [NEEDS CLARIFICATION: which data does the feature create, read or change?]
+
+## Success Criteria
+- [NEEDS CLARIFICATION: which measurable outcome shows the feature works?]
+
+## Out of Scope
+- [NEEDS CLARIFICATION: what does this feature deliberately not do?]
+"#,
+        title = title,
+        dir = feature.dir_name(),
+        created = created,
+        description = if description.is_empty() { "[NEEDS CLARIFICATION: what is the feature for?]" } else { description },
+        requirements = requirements,
+    )
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_copy_feature() {
+        let from = tempdir().unwrap();
+        let to = tempdir().unwrap();
+        let feature = create_feature(from.path(), "user-export").unwrap();
+        std::fs::write(feature.dir(from.path()).join("spec.md"), "# Spec").unwrap();
+        std::fs::write(feature.dir(from.path()).join("plan.md"), "# Plan").unwrap();
+        std::fs::create_dir_all(feature.dir(to.path())).unwrap();
+        std::fs::write(feature.dir(to.path()).join("plan.md"), "# Newer plan").unwrap();
+
+        assert_eq!(copy_feature(&feature, from.path(), to.path()).unwrap(), 1);
+        assert_eq!(read_artifact(&feature.dir(to.path()), "spec.md").as_deref(), Some("# Spec"));
+        assert_eq!(read_artifact(&feature.dir(to.path()), "plan.md").as_deref(), Some("# Newer plan"));
+    }
+
+    #[test]
+    fn test_template_spec() {
+        let feature = SpecFeature {
+            number: 3,
+            short_name: "user-export".to_string(),
+        };
+        let spec = template_spec(&feature, "Admins export users as CSV. Exports include deactivated users.", "2026-01-01");
+        assert!(spec.starts_with("# Feature Specification: User Export\n"));
+        assert!(spec.contains("**Feature**: `003-user-export`"));
+        assert!(spec.contains("- **FR-002**: Exports include deactivated users [NEEDS CLARIFICATION: how is this verified?]"));
+        // Markers feed the interactive clarify session
+        assert!(!crate::clarify::analyze(&spec).questions.is_empty());
+
+        assert!(template_spec(&feature, "  ", "2026-01-01").contains("- **FR-001**: [NEEDS CLARIFICATION: what must the system do?]"));
+    }
+
+    #[test]
+    fn test_feature_numbering() {
+        let dir = tempdir().unwrap();
+        assert_eq!(next_feature_number(dir.path()), 1);
+
+        std::fs::create_dir_all(dir.path().join("specs/007-login")).unwrap();
+        std::fs::create_dir_all(dir.path().join("specs/notes")).unwrap();
+        std::fs::write(dir.path().join("specs/099-readme.md"), "").unwrap();
+
+        let feature = create_feature(dir.path(), "user-export").unwrap();
+        assert_eq!(feature.number, 8);
+        assert_eq!(feature.relative_dir(), "specs/008-user-export");
+        assert!(dir.path().join("specs/008-user-export").is_dir());
+        assert_eq!(create_feature(dir.path(), "").unwrap().dir_name(), "009-feature");
+
+        let names: Vec<String> = list_features(dir.path()).iter().map(SpecFeature::dir_name).collect();
+        assert_eq!(names, vec!["007-login", "008-user-export", "009-feature"]);
+        assert_eq!(find_feature(dir.path(), "8").unwrap().short_name, "user-export");
+        assert_eq!(find_feature(dir.path(), "specs/007-login/").unwrap().number, 7);
+        assert_eq!(find_feature(dir.path(), "login").unwrap().number, 7);
+        assert!(find_feature(dir.path(), "missing").is_none());
+    }
+
+    #[test]
+    fn test_missing_artifacts() {
+        let dir = tempdir().unwrap();
+        assert!(missing_artifacts(SpecPhase::Specify, dir.path()).is_empty());
+        assert_eq!(missing_artifacts(SpecPhase::Tasks, dir.path()), vec!["spec.md", "plan.md"]);
+
+        std::fs::write(dir.path().join("spec.md"), "# Spec").unwrap();
+        assert!(missing_artifacts(SpecPhase::Plan, dir.path()).is_empty());
+        assert_eq!(missing_artifacts(SpecPhase::Tasks, dir.path()), vec!["plan.md"]);
+    }
+
+    #[test]
+    fn test_build_prompt() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("spec.md"), "FR-001 Export users as CSV").unwrap();
+        std::fs::write(dir.path().join("plan.md"), "Add an export endpoint").unwrap();
+        let feature = SpecFeature {
+            number: 3,
+            short_name: "user-export".to_string(),
+        };
+
+        let specify = PhaseConfig::new(SpecPhase::Specify, "Let admins export users", None);
+        let prompt = build_prompt(&specify, &feature, dir.path(), Some("Use snake_case"));
+        assert!(prompt.starts_with("## Project Constitution"));
+        assert!(prompt.contains("feature 003-user-export"));
+        assert!(prompt.contains("Let admins export users"));
+
+        let clarify = PhaseConfig::new(SpecPhase::Clarify, "", Some("CSV only".to_string()));
+        let prompt = build_prompt(&clarify, &feature, dir.path(), None);
+        assert!(prompt.contains("FR-001 Export users as CSV"));
+        assert!(prompt.contains("CSV only"));
+
+        // Blank input is treated as none
+        assert_eq!(
+            PhaseConfig::new(SpecPhase::Plan, "", Some("  ".to_string())),
+            PhaseConfig::Plan(PlanConfig { tech_notes: None })
+        );
+
+        let prompt = build_prompt(&PhaseConfig::Tasks, &feature, dir.path(), None);
+        assert!(prompt.contains("Add an export endpoint"));
+        assert_eq!(PhaseConfig::Tasks.phase().artifact(), "tasks.md");
+    }
+
+    #[test]
+    fn test_write_atomic() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("spec.md");
+        std::fs::write(&path, "old").unwrap();
+        write_atomic(&path, "new").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
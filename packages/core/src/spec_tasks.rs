@@ -0,0 +1,239 @@
+//! Parser and tracker for spec-kit `tasks.md`.
+//!
+//! Task lines look like `- [ ] T001 [P] Create the export endpoint (src/api/export.rs)`:
+//! a checkbox, an ID, an optional `[P]` marking tasks that can run in
+//! parallel, and a description that may reference files in parentheses or
+//! backticks. Tasks belong to the phase of the nearest heading above them.
+//! Toggling a task rewrites only its checkbox, atomically.
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// One task of tasks.md
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaskItem {
+    /// Task ID (e.g. "T001")
+    pub id: String,
+    /// Heading the task is listed under (e.g. "Phase 1: Setup")
+    pub phase: String,
+    pub description: String,
+    /// Marked `[P]`: can run in parallel with other tasks
+    pub parallel: bool,
+    /// File paths referenced by the task
+    pub files: Vec<String>,
+    pub done: bool,
+    /// 0-based line number in tasks.md
+    pub line: usize,
+}
+
+/// Completion of one phase
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PhaseProgress {
+    pub phase: String,
+    pub total: usize,
+    pub done: usize,
+}
+
+/// Completion of tasks.md
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct TaskProgress {
+    pub total: usize,
+    pub done: usize,
+    /// Per phase, in document order
+    pub phases: Vec<PhaseProgress>,
+}
+
+/// Checkbox state and the text after it, for a `- [ ]` / `- [x]` list line
+pub(crate) fn parse_checkbox(line: &str) -> Option<(bool, &str)> {
+    let rest = line.trim_start().strip_prefix(['-', '*'])?.trim_start();
+    let done = match rest.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    Some((done, rest[3..].trim()))
+}
+
+/// Whether a token looks like a file path (has a directory or an extension)
+fn is_path(token: &str) -> bool {
+    let has_extension = token
+        .rsplit_once('.')
+        .is_some_and(|(stem, ext)| !stem.is_empty() && !ext.is_empty() && ext.chars().all(char::is_alphanumeric));
+    !token.is_empty() && !token.contains(char::is_whitespace) && (token.contains('/') || has_extension)
+}
+
+/// File paths in `(a.rs, b/c.ts)` groups and `backticks`
+fn extract_files(description: &str) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    let mut push = |token: &str| {
+        let token = token.trim().trim_end_matches([',', ';']);
+        if is_path(token) && !files.iter().any(|f| f == token) {
+            files.push(token.to_string());
+        }
+    };
+    for group in description.split('(').skip(1).filter_map(|s| s.split_once(')')) {
+        group.0.split(',').for_each(&mut push);
+    }
+    for (i, segment) in description.split('`').enumerate() {
+        if i % 2 == 1 {
+            push(segment);
+        }
+    }
+    files
+}
+
+/// Parse the task lines of tasks.md. Checkbox lines without a task ID are skipped.
+pub fn parse(content: &str) -> Vec<TaskItem> {
+    let mut phase = String::new();
+    let mut tasks = Vec::new();
+    for (line_no, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('#') {
+            phase = trimmed.trim_start_matches('#').trim().to_string();
+            continue;
+        }
+        let Some((done, rest)) = parse_checkbox(line) else {
+            continue;
+        };
+        let (id, rest) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let id = id.trim_end_matches([':', '.']);
+        let is_id = id.len() > 1 && id.starts_with(|c: char| c.is_ascii_alphabetic()) && id[1..].chars().all(|c| c.is_ascii_digit());
+        if !is_id {
+            continue;
+        }
+        let rest = rest.trim();
+        let (parallel, description) = match rest.strip_prefix("[P]") {
+            Some(description) => (true, description.trim()),
+            None => (false, rest),
+        };
+        tasks.push(TaskItem {
+            id: id.to_string(),
+            phase: phase.clone(),
+            description: description.to_string(),
+            parallel,
+            files: extract_files(description),
+            done,
+            line: line_no,
+        });
+    }
+    tasks
+}
+
+/// Overall and per-phase completion
+pub fn summarize(tasks: &[TaskItem]) -> TaskProgress {
+    let mut progress = TaskProgress::default();
+    for task in tasks {
+        progress.total += 1;
+        progress.done += usize::from(task.done);
+        match progress.phases.iter_mut().find(|p| p.phase == task.phase) {
+            Some(phase) => {
+                phase.total += 1;
+                phase.done += usize::from(task.done);
+            }
+            None => progress.phases.push(PhaseProgress {
+                phase: task.phase.clone(),
+                total: 1,
+                done: usize::from(task.done),
+            }),
+        }
+    }
+    progress
+}
+
+/// tasks.md with the checkbox of task `id` set to `done`; other lines untouched
+pub fn set_done(content: &str, id: &str, done: bool) -> Result<String, String> {
+    let task = parse(content)
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| format!("Task {} not found", id))?;
+
+    let mut lines: Vec<String> = content.split('\n').map(str::to_string).collect();
+    let line = &mut lines[task.line];
+    let start = line.find('[').ok_or_else(|| format!("Task {} has no checkbox", id))?;
+    line.replace_range(start..start + 3, if done { "[x]" } else { "[ ]" });
+    Ok(lines.join("\n"))
+}
+
+/// Read and parse a tasks.md file
+pub fn load(path: &Path) -> Result<Vec<TaskItem>, String> {
+    std::fs::read_to_string(path)
+        .map(|content| parse(&content))
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+/// Set the completion of a task in a tasks.md file and return the re-parsed tasks
+pub fn update_file(path: &Path, id: &str, done: bool) -> Result<Vec<TaskItem>, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let updated = set_done(&content, id, done)?;
+    if updated != content {
+        crate::spec_kit::write_atomic(path, &updated)?;
+    }
+    Ok(parse(&updated))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TASKS: &str = "# Tasks: User export\n\n## Phase 1: Setup\n- [x] T001 Create the export module (src/export/mod.rs)\n- [ ] T002 [P] Add the CSV dependency to `Cargo.toml`\n\n## Phase 2: Core\n- [ ] T003 [P] Implement CSV writer (src/export/csv.rs, src/export/mod.rs)\n* [X] T004: Wire the endpoint (e.g. the admin API)\n- [ ] Follow up with design\n";
+
+    #[test]
+    fn test_parse() {
+        let tasks = parse(TASKS);
+        assert_eq!(tasks.len(), 4);
+
+        assert_eq!(tasks[0].id, "T001");
+        assert_eq!(tasks[0].phase, "Phase 1: Setup");
+        assert!(tasks[0].done);
+        assert!(!tasks[0].parallel);
+        assert_eq!(tasks[0].files, vec!["src/export/mod.rs"]);
+
+        assert!(tasks[1].parallel);
+        assert_eq!(tasks[1].description, "Add the CSV dependency to `Cargo.toml`");
+        assert_eq!(tasks[1].files, vec!["Cargo.toml"]);
+
+        assert_eq!(tasks[2].files, vec!["src/export/csv.rs", "src/export/mod.rs"]);
+        assert_eq!(tasks[2].phase, "Phase 2: Core");
+
+        // "T004:" IDs and capital X; prose in parentheses is not a path
+        assert_eq!(tasks[3].id, "T004");
+        assert!(tasks[3].done);
+        assert!(tasks[3].files.is_empty());
+    }
+
+    #[test]
+    fn test_summarize() {
+        let progress = summarize(&parse(TASKS));
+        assert_eq!((progress.total, progress.done), (4, 2));
+        assert_eq!(
+            progress.phases,
+            vec![
+                PhaseProgress { phase: "Phase 1: Setup".to_string(), total: 2, done: 1 },
+                PhaseProgress { phase: "Phase 2: Core".to_string(), total: 2, done: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tasks.md");
+        std::fs::write(&path, TASKS).unwrap();
+
+        let tasks = update_file(&path, "T002", true).unwrap();
+        assert!(tasks.iter().find(|t| t.id == "T002").unwrap().done);
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("- [x] T002 [P] Add the CSV dependency"));
+        assert_eq!(content.len(), TASKS.len());
+
+        update_file(&path, "T001", false).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().contains("- [ ] T001 Create"));
+
+        assert_eq!(update_file(&path, "T999", true).unwrap_err(), "Task T999 not found");
+    }
+}
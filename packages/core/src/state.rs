@@ -63,4 +63,75 @@ pub struct PortConflictInfo {
     pub is_rstn_managed: bool,
     /// Suggested alternative port
     pub suggested_port: u32,
+    /// Host process holding the port when it is not a Docker container
+    pub process: Option<PortProcessInfo>,
+}
+
+/// A non-Docker process listening on a port
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PortProcessInfo {
+    pub pid: u32,
+    /// Process name (e.g. "node")
+    pub name: String,
+    /// Executable path, if readable
+    pub exe: Option<String>,
+    /// Owning user, if known
+    pub user: Option<String>,
+    /// Full command line, if readable
+    pub command: Option<String>,
+}
+
+/// Local Docker image
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerImage {
+    pub id: String,
+    /// Tags (e.g. "postgres:16-alpine"), empty for dangling images
+    pub repo_tags: Vec<String>,
+    /// Size in bytes
+    pub size: i64,
+    /// Creation time (Unix seconds)
+    pub created: i64,
+    /// Untagged image left behind by a newer pull/build
+    pub dangling: bool,
+}
+
+/// Database inside a database container
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerDatabase {
+    pub name: String,
+    /// Size on disk in bytes (if the engine reports it)
+    pub size: Option<i64>,
+}
+
+/// Table (MongoDB: collection) inside a database
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DockerTable {
+    /// Schema (PostgreSQL/MySQL), absent for MongoDB collections
+    pub schema: Option<String>,
+    pub name: String,
+    /// Estimated row (document) count
+    pub row_count: Option<i64>,
+}
+
+/// Output of a command run in a container
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecOutput {
+    /// None if Docker didn't report one
+    pub exit_code: Option<i64>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Result of pruning dangling images
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImagePruneResult {
+    pub images_deleted: u32,
+    /// Disk space reclaimed in bytes
+    pub space_reclaimed: i64,
 }
@@ -0,0 +1,406 @@
+//! WebSocket state bridge for external tools (feature `state-bridge`).
+//!
+//! A localhost WebSocket server that lets tooling (a browser devtools panel,
+//! automated UI tests) observe and drive the state without going through
+//! napi:
+//!
+//! - On connect the client receives `{"type":"state","state":{...}}`, then
+//!   `{"type":"diff","ops":[...]}` (JSON Patch) after every state change.
+//! - Text messages are actions (`{"type":"ActionName","payload":{...}}`),
+//!   dispatched like `state_dispatch`. Failures are answered with
+//!   `{"type":"error","message":"..."}`.
+//!
+//! Clients must pass the token returned at startup (`/?token=...` or an
+//! `Authorization: Bearer` header), so web pages in the user's browser
+//! cannot connect. The bridge is an axum route; axum handles the WebSocket
+//! protocol (fragmentation, ping/pong, close).
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{FromRequestParts, Query, State};
+use axum::http::request::Parts;
+use axum::http::{header, StatusCode};
+use axum::response::Response;
+use axum::routing::get;
+use axum::Router;
+use futures_util::{SinkExt, StreamExt};
+use napi_derive::napi;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio_util::sync::CancellationToken;
+
+/// Largest incoming message accepted
+const MAX_MESSAGE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Diffs buffered per client before it must resync with a full state
+const UPDATE_BUFFER: usize = 256;
+
+/// Where a started bridge listens
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateBridgeInfo {
+    pub port: u32,
+    /// Token clients must pass as `?token=`
+    pub token: String,
+    /// ws:// URL including the token
+    pub url: String,
+}
+
+/// An action received from a client; `reply` carries the dispatch result
+pub struct BridgeRequest {
+    pub action_json: String,
+    pub reply: oneshot::Sender<Result<(), String>>,
+}
+
+struct Shared {
+    token: String,
+    /// Last published state (sent in full to new clients)
+    last: Mutex<Value>,
+    updates: broadcast::Sender<Arc<String>>,
+    requests: mpsc::UnboundedSender<BridgeRequest>,
+    cancel: CancellationToken,
+}
+
+/// A running bridge; dropping it stops the server and closes all clients.
+pub struct StateBridge {
+    port: u16,
+    shared: Arc<Shared>,
+}
+
+impl StateBridge {
+    /// Listen on 127.0.0.1:`port` (0 = any free port).
+    /// Actions sent by clients arrive on the returned receiver.
+    pub async fn bind(
+        port: u16,
+        initial_state: Value,
+    ) -> std::io::Result<(Self, mpsc::UnboundedReceiver<BridgeRequest>)> {
+        let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+        let port = listener.local_addr()?.port();
+        let (requests, requests_rx) = mpsc::unbounded_channel();
+        let shared = Arc::new(Shared {
+            token: uuid::Uuid::new_v4().simple().to_string(),
+            last: Mutex::new(initial_state),
+            updates: broadcast::channel(UPDATE_BUFFER).0,
+            requests,
+            cancel: CancellationToken::new(),
+        });
+        let app = Router::new()
+            .route("/", get(handle_upgrade))
+            .with_state(shared.clone());
+        let cancel = shared.cancel.clone();
+        tokio::spawn(async move {
+            axum::serve(listener, app)
+                .with_graceful_shutdown(async move { cancel.cancelled().await })
+                .await
+                .ok();
+        });
+        Ok((Self { port, shared }, requests_rx))
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Token clients must pass as `?token=`
+    pub fn token(&self) -> &str {
+        &self.shared.token
+    }
+
+    pub fn url(&self) -> String {
+        format!("ws://127.0.0.1:{}/?token={}", self.port, self.shared.token)
+    }
+
+    pub fn info(&self) -> StateBridgeInfo {
+        StateBridgeInfo {
+            port: self.port as u32,
+            token: self.shared.token.clone(),
+            url: self.url(),
+        }
+    }
+
+    /// Broadcast the changes from the last published state
+    pub fn publish(&self, state: Value) {
+        let ops = {
+            let mut last = self.shared.last.lock().unwrap_or_else(|e| e.into_inner());
+            let ops = diff(&last, &state);
+            *last = state;
+            ops
+        };
+        if !ops.is_empty() {
+            let message = json!({ "type": "diff", "ops": ops }).to_string();
+            // No receivers is fine
+            let _ = self.shared.updates.send(Arc::new(message));
+        }
+    }
+}
+
+impl Drop for StateBridge {
+    fn drop(&mut self) {
+        self.shared.cancel.cancel();
+    }
+}
+
+/// A request carrying the bridge token (`?token=` or `Authorization: Bearer`)
+struct BridgeToken;
+
+#[derive(Deserialize)]
+struct TokenQuery {
+    token: Option<String>,
+}
+
+#[axum::async_trait]
+impl FromRequestParts<Arc<Shared>> for BridgeToken {
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, shared: &Arc<Shared>) -> Result<Self, Self::Rejection> {
+        let query = Query::<TokenQuery>::try_from_uri(&parts.uri).ok().and_then(|q| q.0.token);
+        let bearer = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::to_string);
+        match query.or(bearer) {
+            Some(token) if token == shared.token => Ok(BridgeToken),
+            _ => Err(StatusCode::UNAUTHORIZED),
+        }
+    }
+}
+
+async fn handle_upgrade(_: BridgeToken, State(shared): State<Arc<Shared>>, ws: WebSocketUpgrade) -> Response {
+    ws.max_message_size(MAX_MESSAGE_BYTES)
+        .on_upgrade(move |socket| serve_client(socket, shared))
+}
+
+async fn serve_client(socket: WebSocket, shared: Arc<Shared>) {
+    let (mut writer, mut reader) = socket.split();
+
+    // Subscribe before sending the full state so no change is missed
+    let mut updates = shared.updates.subscribe();
+    let full_state = || {
+        let state = shared.last.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        Message::Text(json!({ "type": "state", "state": state }).to_string())
+    };
+    if writer.send(full_state()).await.is_err() {
+        return;
+    }
+
+    // Messages to send from the reader side (errors, close)
+    let (replies, mut replies_rx) = mpsc::unbounded_channel::<Message>();
+    let reader_shared = shared.clone();
+    let reader_task = tokio::spawn(async move {
+        loop {
+            let message = match reader.next().await {
+                Some(Ok(message)) => message,
+                Some(Err(_)) | None => {
+                    let _ = replies.send(Message::Close(None));
+                    break;
+                }
+            };
+            match message {
+                Message::Text(action_json) => {
+                    let (reply, result) = oneshot::channel();
+                    let request = BridgeRequest { action_json, reply };
+                    if reader_shared.requests.send(request).is_err() {
+                        break;
+                    }
+                    if let Ok(Err(message)) = result.await {
+                        let error = json!({ "type": "error", "message": message }).to_string();
+                        let _ = replies.send(Message::Text(error));
+                    }
+                }
+                Message::Close(_) => {
+                    let _ = replies.send(Message::Close(None));
+                    break;
+                }
+                // Pongs are sent by axum; binary messages are ignored
+                Message::Binary(_) | Message::Ping(_) | Message::Pong(_) => {}
+            }
+        }
+    });
+
+    loop {
+        let sent = tokio::select! {
+            _ = shared.cancel.cancelled() => {
+                let _ = writer.send(Message::Close(None)).await;
+                break;
+            }
+            reply = replies_rx.recv() => match reply {
+                Some(Message::Close(_)) | None => {
+                    let _ = writer.send(Message::Close(None)).await;
+                    break;
+                }
+                Some(message) => writer.send(message).await,
+            },
+            update = updates.recv() => match update {
+                Ok(message) => writer.send(Message::Text(message.as_str().to_string())).await,
+                // Too slow to keep up with diffs: resync with the full state
+                Err(broadcast::error::RecvError::Lagged(_)) => writer.send(full_state()).await,
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        };
+        if sent.is_err() {
+            break;
+        }
+    }
+    reader_task.abort();
+}
+
+// ============================================================================
+// State diff (JSON Patch, RFC 6902)
+// ============================================================================
+
+/// Patch operations turning `old` into `new`. Arrays that changed are
+/// replaced whole except for appended items (e.g. streaming output, logs).
+pub fn diff(old: &Value, new: &Value) -> Vec<Value> {
+    let mut ops = Vec::new();
+    diff_at("", old, new, &mut ops);
+    ops
+}
+
+fn diff_at(path: &str, old: &Value, new: &Value, ops: &mut Vec<Value>) {
+    if old == new {
+        return;
+    }
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => diff_objects(path, old, new, ops),
+        (Value::Array(old), Value::Array(new))
+            if new.len() > old.len() && new[..old.len()] == old[..] =>
+        {
+            for value in &new[old.len()..] {
+                ops.push(json!({ "op": "add", "path": format!("{}/-", path), "value": value }));
+            }
+        }
+        _ => ops.push(json!({ "op": "replace", "path": path, "value": new })),
+    }
+}
+
+fn diff_objects(path: &str, old: &Map<String, Value>, new: &Map<String, Value>, ops: &mut Vec<Value>) {
+    for (key, old_value) in old {
+        let child = format!("{}/{}", path, escape_pointer(key));
+        match new.get(key) {
+            Some(new_value) => diff_at(&child, old_value, new_value, ops),
+            None => ops.push(json!({ "op": "remove", "path": child })),
+        }
+    }
+    for (key, new_value) in new {
+        if !old.contains_key(key) {
+            let child = format!("{}/{}", path, escape_pointer(key));
+            ops.push(json!({ "op": "add", "path": child, "value": new_value }));
+        }
+    }
+}
+
+/// Escape a JSON Pointer reference token
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    const OPCODE_TEXT: u8 = 0x1;
+    const OPCODE_PING: u8 = 0x9;
+    const OPCODE_PONG: u8 = 0xA;
+
+    /// Minimal client: masked frame
+    fn client_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [1u8, 2, 3, 4];
+        let mut frame = vec![0x80 | opcode, 0x80 | payload.len() as u8];
+        frame.extend_from_slice(&mask);
+        frame.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        frame
+    }
+
+    async fn read_server_text(stream: &mut TcpStream) -> Value {
+        let mut head = [0u8; 2];
+        stream.read_exact(&mut head).await.unwrap();
+        let len = match head[1] {
+            126 => stream.read_u16().await.unwrap() as usize,
+            127 => stream.read_u64().await.unwrap() as usize,
+            n => n as usize,
+        };
+        let mut payload = vec![0u8; len];
+        stream.read_exact(&mut payload).await.unwrap();
+        serde_json::from_slice(&payload).unwrap()
+    }
+
+    async fn connect(bridge: &StateBridge, token: &str) -> (TcpStream, String) {
+        let mut stream = TcpStream::connect(("127.0.0.1", bridge.port())).await.unwrap();
+        let request = format!(
+            "GET /?token={} HTTP/1.1\r\nHost: localhost\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\nSec-WebSocket-Version: 13\r\n\r\n",
+            token
+        );
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = Vec::new();
+        while !response.windows(4).any(|w| w == b"\r\n\r\n") {
+            let mut byte = [0u8; 1];
+            if stream.read(&mut byte).await.unwrap() == 0 {
+                break;
+            }
+            response.push(byte[0]);
+        }
+        (stream, String::from_utf8(response).unwrap())
+    }
+
+    #[test]
+    fn test_diff_produces_json_patch() {
+        let old = json!({ "a": 1, "b": { "c": [1, 2] }, "gone": true, "x/y": 0 });
+        let new = json!({ "a": 2, "b": { "c": [1, 2, 3] }, "added": null, "x/y": 1 });
+        let ops = diff(&old, &new);
+        assert_eq!(
+            ops,
+            vec![
+                json!({ "op": "replace", "path": "/a", "value": 2 }),
+                json!({ "op": "add", "path": "/b/c/-", "value": 3 }),
+                json!({ "op": "remove", "path": "/gone" }),
+                json!({ "op": "replace", "path": "/x~1y", "value": 1 }),
+                json!({ "op": "add", "path": "/added", "value": null }),
+            ]
+        );
+        assert!(diff(&new, &new).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bridge_streams_diffs_and_accepts_actions() {
+        let (bridge, mut requests) = StateBridge::bind(0, json!({ "theme": "system" })).await.unwrap();
+
+        let (_, response) = connect(&bridge, "wrong").await;
+        assert!(response.starts_with("HTTP/1.1 401"));
+
+        let (mut client, response) = connect(&bridge, bridge.token()).await;
+        assert!(response.starts_with("HTTP/1.1 101"));
+        assert!(response.contains("s3pPLMBiTxaQ9kYGzzhZRbK+xOo="));
+        assert_eq!(read_server_text(&mut client).await, json!({ "type": "state", "state": { "theme": "system" } }));
+
+        bridge.publish(json!({ "theme": "dark" }));
+        assert_eq!(
+            read_server_text(&mut client).await,
+            json!({ "type": "diff", "ops": [{ "op": "replace", "path": "/theme", "value": "dark" }] })
+        );
+
+        let action = r#"{"type":"Nope"}"#;
+        client.write_all(&client_frame(OPCODE_TEXT, action.as_bytes())).await.unwrap();
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.action_json, action);
+        request.reply.send(Err("Invalid action JSON".to_string())).unwrap();
+        assert_eq!(
+            read_server_text(&mut client).await,
+            json!({ "type": "error", "message": "Invalid action JSON" })
+        );
+
+        client.write_all(&client_frame(OPCODE_PING, b"hi")).await.unwrap();
+        let mut pong = [0u8; 4];
+        client.read_exact(&mut pong).await.unwrap();
+        assert_eq!(pong, [0x80 | OPCODE_PONG, 2, b'h', b'i']);
+    }
+}
@@ -0,0 +1,441 @@
+//! Symbol outlines (functions, types, methods) parsed with tree-sitter.
+//!
+//! Supports Rust, TypeScript/TSX, JavaScript and Python. Outlines are cached
+//! per file in `shared_cache()`; the worktree watcher drops entries of files
+//! that change, and the modification time guards files outside watched
+//! worktrees. The context engine uses `outline` to summarize files too large
+//! to include in full.
+
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::SystemTime;
+use tree_sitter::{Node, Parser};
+
+/// Files larger than this are not parsed
+pub const MAX_SYMBOL_FILE_SIZE: u64 = 2_000_000;
+
+/// Cached files before the cache starts over
+const MAX_CACHED_FILES: usize = 2000;
+
+/// Longest signature kept (chars)
+const MAX_SIGNATURE_LEN: usize = 200;
+
+/// A definition in a source file
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Symbol {
+    pub name: String,
+    /// function, method, struct, enum, union, trait, impl, module, type,
+    /// constant, static, macro, class, interface
+    pub kind: String,
+    /// Declaration without its body, whitespace collapsed
+    pub signature: String,
+    /// 1-based
+    pub start_line: u32,
+    pub end_line: u32,
+    /// Nesting level (0 = top level)
+    pub depth: u32,
+    /// Enclosing impl, trait, module or class
+    pub parent: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolLanguage {
+    Rust,
+    TypeScript,
+    Tsx,
+    JavaScript,
+    Python,
+}
+
+impl SymbolLanguage {
+    pub fn for_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|ext| ext.to_str())? {
+            "rs" => Some(Self::Rust),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "py" | "pyi" => Some(Self::Python),
+            _ => None,
+        }
+    }
+
+    fn grammar(self) -> tree_sitter::Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+        }
+    }
+}
+
+/// Extract the symbols of a source file
+pub fn extract(language: SymbolLanguage, content: &str) -> Result<Vec<Symbol>, String> {
+    let mut parser = Parser::new();
+    parser
+        .set_language(&language.grammar())
+        .map_err(|e| format!("Failed to load grammar: {}", e))?;
+    let tree = parser.parse(content, None).ok_or("Failed to parse file")?;
+    let mut symbols = Vec::new();
+    collect(language, tree.root_node(), content, 0, None, &mut symbols);
+    Ok(symbols)
+}
+
+/// Walk the declarations directly under `node`, descending into the bodies
+/// of impls, traits, modules and classes. `parent` is the enclosing symbol's
+/// name and kind.
+fn collect(
+    language: SymbolLanguage,
+    node: Node,
+    source: &str,
+    depth: u32,
+    parent: Option<(&str, &str)>,
+    out: &mut Vec<Symbol>,
+) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        // Wrappers: `export ...`, `declare ...`, `@decorator def ...`
+        let (decl, start) = match child.kind() {
+            "export_statement" => match child.child_by_field_name("declaration") {
+                Some(decl) => (decl, child.start_byte()),
+                None => continue,
+            },
+            "ambient_declaration" => match child.named_child(0) {
+                Some(decl) => (decl, child.start_byte()),
+                None => continue,
+            },
+            "decorated_definition" => match child.child_by_field_name("definition") {
+                Some(decl) => (decl, decl.start_byte()),
+                None => continue,
+            },
+            _ => (child, child.start_byte()),
+        };
+
+        if matches!(decl.kind(), "lexical_declaration" | "variable_declaration") {
+            if depth == 0 {
+                collect_variables(decl, start, source, out);
+            }
+            continue;
+        }
+        let in_type = parent.is_some_and(|(_, kind)| matches!(kind, "impl" | "trait" | "class"));
+        let Some(kind) = symbol_kind(language, decl.kind(), in_type) else {
+            continue;
+        };
+        let Some(name) = symbol_name(decl, kind, source) else {
+            continue;
+        };
+        let body = decl.child_by_field_name("body");
+        out.push(Symbol {
+            signature: signature(source, start, body.map_or(decl.end_byte(), |b| b.start_byte())),
+            start_line: line(decl.start_position().row),
+            end_line: line(decl.end_position().row),
+            depth,
+            parent: parent.map(|(name, _)| name.to_string()),
+            kind: kind.to_string(),
+            name: name.clone(),
+        });
+        if let Some(body) = body.filter(|_| matches!(kind, "impl" | "trait" | "module" | "class")) {
+            collect(language, body, source, depth + 1, Some((&name, kind)), out);
+        }
+    }
+}
+
+/// Top-level `const`/`let`/`var` declarations: functions bound to a name,
+/// and constants
+fn collect_variables(decl: Node, start: usize, source: &str, out: &mut Vec<Symbol>) {
+    let is_const = decl.child(0).is_some_and(|keyword| keyword.kind() == "const");
+    let mut cursor = decl.walk();
+    for declarator in decl.named_children(&mut cursor).filter(|n| n.kind() == "variable_declarator") {
+        let Some(name) = declarator.child_by_field_name("name").and_then(|n| n.utf8_text(source.as_bytes()).ok()) else {
+            continue;
+        };
+        let function_body = declarator
+            .child_by_field_name("value")
+            .filter(|v| matches!(v.kind(), "arrow_function" | "function_expression" | "function"))
+            .and_then(|v| v.child_by_field_name("body"));
+        let (kind, end) = match function_body {
+            Some(body) => ("function", body.start_byte()),
+            None if is_const => ("constant", line_end(source, start)),
+            None => continue,
+        };
+        out.push(Symbol {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            signature: signature(source, start, end),
+            start_line: line(declarator.start_position().row),
+            end_line: line(declarator.end_position().row),
+            depth: 0,
+            parent: None,
+        });
+    }
+}
+
+fn symbol_kind(language: SymbolLanguage, node_kind: &str, in_type: bool) -> Option<&'static str> {
+    let kind = match language {
+        SymbolLanguage::Rust => match node_kind {
+            "function_item" | "function_signature_item" if in_type => "method",
+            "function_item" | "function_signature_item" => "function",
+            "struct_item" => "struct",
+            "enum_item" => "enum",
+            "union_item" => "union",
+            "trait_item" => "trait",
+            "impl_item" => "impl",
+            "mod_item" => "module",
+            "type_item" => "type",
+            "const_item" => "constant",
+            "static_item" => "static",
+            "macro_definition" => "macro",
+            _ => return None,
+        },
+        SymbolLanguage::TypeScript | SymbolLanguage::Tsx | SymbolLanguage::JavaScript => match node_kind {
+            "function_declaration" | "generator_function_declaration" | "function_signature" => "function",
+            "class_declaration" | "abstract_class_declaration" => "class",
+            "method_definition" | "abstract_method_signature" => "method",
+            "interface_declaration" => "interface",
+            "type_alias_declaration" => "type",
+            "enum_declaration" => "enum",
+            "internal_module" | "module" => "module",
+            _ => return None,
+        },
+        SymbolLanguage::Python => match node_kind {
+            "function_definition" if in_type => "method",
+            "function_definition" => "function",
+            "class_definition" => "class",
+            _ => return None,
+        },
+    };
+    Some(kind)
+}
+
+fn symbol_name(node: Node, kind: &str, source: &str) -> Option<String> {
+    let text = |field: &str| node.child_by_field_name(field).and_then(|n| n.utf8_text(source.as_bytes()).ok());
+    if kind == "impl" {
+        let ty = text("type")?;
+        return Some(match text("trait") {
+            Some(trait_name) => format!("{} for {}", trait_name, ty),
+            None => ty.to_string(),
+        });
+    }
+    text("name").map(str::to_string)
+}
+
+/// `source[start..end]` on one line, without a trailing `{`
+fn signature(source: &str, start: usize, end: usize) -> String {
+    let text = source.get(start..end.max(start)).unwrap_or_default();
+    let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let trimmed = collapsed.trim_end_matches('{').trim_end();
+    if trimmed.chars().count() > MAX_SIGNATURE_LEN {
+        format!("{}…", trimmed.chars().take(MAX_SIGNATURE_LEN).collect::<String>())
+    } else {
+        trimmed.to_string()
+    }
+}
+
+fn line_end(source: &str, start: usize) -> usize {
+    source[start..].find('\n').map_or(source.len(), |i| start + i)
+}
+
+fn line(row: usize) -> u32 {
+    u32::try_from(row + 1).unwrap_or(u32::MAX)
+}
+
+/// Signatures-only summary of a file, one symbol per line
+pub fn outline(symbols: &[Symbol]) -> String {
+    symbols
+        .iter()
+        .map(|s| format!("{:>5}: {}{}\n", s.start_line, "    ".repeat(s.depth as usize), s.signature))
+        .collect()
+}
+
+// ============================================================================
+// Cache
+// ============================================================================
+
+struct CachedSymbols {
+    modified: Option<SystemTime>,
+    len: u64,
+    symbols: Vec<Symbol>,
+}
+
+/// Parsed symbols per file
+#[derive(Default)]
+pub struct SymbolCache {
+    entries: Mutex<HashMap<PathBuf, CachedSymbols>>,
+}
+
+/// The process-wide cache (invalidated by the worktree watcher)
+pub fn shared_cache() -> &'static SymbolCache {
+    static CACHE: OnceLock<SymbolCache> = OnceLock::new();
+    CACHE.get_or_init(SymbolCache::new)
+}
+
+impl SymbolCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<PathBuf, CachedSymbols>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Symbols of a file, parsed on first use or when the file changed
+    pub fn symbols_for_file(&self, path: &Path) -> Result<Vec<Symbol>, String> {
+        let language = SymbolLanguage::for_path(path)
+            .ok_or_else(|| format!("No symbol support for {}", path.display()))?;
+        let metadata = std::fs::metadata(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if metadata.len() > MAX_SYMBOL_FILE_SIZE {
+            return Err(format!("{} is too large to parse", path.display()));
+        }
+        let modified = metadata.modified().ok();
+        if let Some(cached) = self.lock().get(path) {
+            if cached.modified == modified && cached.len == metadata.len() {
+                return Ok(cached.symbols.clone());
+            }
+        }
+
+        let content =
+            std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let symbols = extract(language, &content)?;
+        let mut entries = self.lock();
+        if entries.len() >= MAX_CACHED_FILES {
+            entries.clear();
+        }
+        entries.insert(
+            path.to_path_buf(),
+            CachedSymbols {
+                modified,
+                len: metadata.len(),
+                symbols: symbols.clone(),
+            },
+        );
+        Ok(symbols)
+    }
+
+    /// Drop a changed file, or everything under a changed directory
+    pub fn invalidate(&self, path: &Path) {
+        self.lock().retain(|cached, _| !cached.starts_with(path));
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(symbols: &[Symbol]) -> Vec<(String, String, u32)> {
+        symbols.iter().map(|s| (s.kind.clone(), s.name.clone(), s.depth)).collect()
+    }
+
+    fn entry(kind: &str, name: &str, depth: u32) -> (String, String, u32) {
+        (kind.to_string(), name.to_string(), depth)
+    }
+
+    #[test]
+    fn test_extract_rust() {
+        let source = r#"
+/// A port
+pub struct Port(u16);
+
+impl Display for Port {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+mod registry {
+    pub const MAX: usize = 10;
+    pub async fn reserve(
+        port: u16,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+}
+"#;
+        let symbols = extract(SymbolLanguage::Rust, source).unwrap();
+        assert_eq!(
+            summary(&symbols),
+            vec![
+                entry("struct", "Port", 0),
+                entry("impl", "Display for Port", 0),
+                entry("method", "fmt", 1),
+                entry("module", "registry", 0),
+                entry("constant", "MAX", 1),
+                entry("function", "reserve", 1),
+            ]
+        );
+        assert_eq!(symbols[2].signature, "fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result");
+        assert_eq!(symbols[2].parent.as_deref(), Some("Display for Port"));
+        assert_eq!(symbols[5].signature, "pub async fn reserve( port: u16, ) -> Result<(), String>");
+        assert_eq!((symbols[5].start_line, symbols[5].end_line), (13, 17));
+        assert!(outline(&symbols).contains("    6:     fn fmt(&self"));
+    }
+
+    #[test]
+    fn test_extract_typescript_and_python() {
+        let source = r#"
+export interface Props { id: string }
+export const useThing = async (id: string): Promise<void> => {
+  await load(id)
+}
+const LIMIT = 5
+export default class Store extends Base {
+  get(key: string): string { return key }
+}
+"#;
+        let symbols = extract(SymbolLanguage::TypeScript, source).unwrap();
+        assert_eq!(
+            summary(&symbols),
+            vec![
+                entry("interface", "Props", 0),
+                entry("function", "useThing", 0),
+                entry("constant", "LIMIT", 0),
+                entry("class", "Store", 0),
+                entry("method", "get", 1),
+            ]
+        );
+        assert_eq!(symbols[1].signature, "export const useThing = async (id: string): Promise<void> =>");
+        assert_eq!(symbols[3].signature, "export default class Store extends Base");
+
+        let source = "class Repo:\n    @property\n    def name(self) -> str:\n        return 'x'\n\ndef main():\n    pass\n";
+        let symbols = extract(SymbolLanguage::Python, source).unwrap();
+        assert_eq!(
+            summary(&symbols),
+            vec![entry("class", "Repo", 0), entry("method", "name", 1), entry("function", "main", 0)]
+        );
+        assert_eq!(symbols[1].signature, "def name(self) -> str:");
+    }
+
+    #[test]
+    fn test_cache_invalidation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lib.rs");
+        std::fs::write(&path, "fn alpha() {}\n").unwrap();
+        let cache = SymbolCache::new();
+        assert_eq!(cache.symbols_for_file(&path).unwrap()[0].name, "alpha");
+        assert_eq!(cache.len(), 1);
+
+        // A stale entry is re-parsed when the file size changes
+        std::fs::write(&path, "fn alpha_two() {}\n").unwrap();
+        assert_eq!(cache.symbols_for_file(&path).unwrap()[0].name, "alpha_two");
+
+        cache.invalidate(dir.path());
+        assert!(cache.is_empty());
+        assert!(cache.symbols_for_file(&dir.path().join("notes.txt")).is_err());
+    }
+}
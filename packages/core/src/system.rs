@@ -0,0 +1,326 @@
+//! Machine resource sampling for the dashboard.
+//!
+//! Every `SAMPLE_INTERVAL` the monitor reads CPU, memory and disk usage of
+//! the machine, plus what rstn's own work costs: Claude CLI runs and terminal
+//! shells (each with all their child processes) and rstn-managed containers.
+//! Warnings are derived from the sample so the UI can flag an AI run or a
+//! container stack that is eating the machine.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use sysinfo::{Disks, Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// How often the machine is sampled
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Machine-wide usage (percent) above which a warning is raised
+const MACHINE_WARN_PERCENT: f64 = 90.0;
+
+/// Disk usage (percent) above which a warning is raised
+const DISK_WARN_PERCENT: f64 = 95.0;
+
+/// Share of the machine (percent of all cores / of total memory) a single
+/// rstn process group may take before a warning is raised
+const GROUP_WARN_PERCENT: f64 = 50.0;
+
+/// Kind of rstn-spawned work whose usage is aggregated
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessGroup {
+    ClaudeCli,
+    Containers,
+    Terminals,
+}
+
+impl ProcessGroup {
+    pub fn label(self) -> &'static str {
+        match self {
+            ProcessGroup::ClaudeCli => "Claude CLI",
+            ProcessGroup::Containers => "Containers",
+            ProcessGroup::Terminals => "Terminals",
+        }
+    }
+}
+
+/// Aggregated usage of one process group
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GroupUsage {
+    pub group: ProcessGroup,
+    /// Processes (or containers) counted
+    pub count: u32,
+    /// CPU usage (100.0 = one full core)
+    pub cpu_percent: f64,
+    /// Resident memory (bytes)
+    pub memory_bytes: u64,
+}
+
+/// One machine sample
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SystemStats {
+    /// CPU usage of the whole machine (0-100)
+    pub cpu_percent: f64,
+    pub cpu_count: u32,
+    pub memory_used: u64,
+    pub memory_total: u64,
+    pub swap_used: u64,
+    pub swap_total: u64,
+    /// Disk holding the home directory
+    pub disk_mount: String,
+    pub disk_used: u64,
+    pub disk_total: u64,
+    /// rstn-spawned work, one entry per group
+    pub groups: Vec<GroupUsage>,
+    /// Human-readable warnings for this sample
+    pub warnings: Vec<String>,
+    /// When the sample was taken (ISO 8601)
+    pub timestamp: String,
+}
+
+/// Keeps the previous readings sysinfo needs to compute CPU usage
+pub struct SystemMonitor {
+    system: System,
+    disks: Disks,
+}
+
+impl Default for SystemMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemMonitor {
+    pub fn new() -> Self {
+        Self {
+            system: System::new(),
+            disks: Disks::new_with_refreshed_list(),
+        }
+    }
+
+    /// Sample the machine. `roots` are the processes of each group (their
+    /// descendants are counted too); `extra` holds groups measured elsewhere
+    /// (containers, via Docker).
+    pub fn sample(&mut self, roots: &[(ProcessGroup, Vec<u32>)], extra: Vec<GroupUsage>) -> SystemStats {
+        self.system.refresh_cpu_usage();
+        self.system.refresh_memory();
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_cpu().with_memory(),
+        );
+        self.disks.refresh(true);
+
+        let nodes: HashMap<u32, ProcessNode> = self
+            .system
+            .processes()
+            .iter()
+            .filter(|(_, p)| p.thread_kind().is_none())
+            .map(|(pid, p)| {
+                (
+                    pid.as_u32(),
+                    ProcessNode {
+                        parent: p.parent().map(Pid::as_u32),
+                        cpu_percent: p.cpu_usage() as f64,
+                        memory_bytes: p.memory(),
+                    },
+                )
+            })
+            .collect();
+
+        let mut groups: Vec<GroupUsage> = roots
+            .iter()
+            .map(|(group, pids)| tree_usage(*group, &nodes, pids))
+            .collect();
+        groups.extend(extra);
+
+        let home = dirs::home_dir().unwrap_or_else(|| "/".into());
+        let disk = self
+            .disks
+            .list()
+            .iter()
+            .filter(|d| home.starts_with(d.mount_point()))
+            .max_by_key(|d| d.mount_point().as_os_str().len());
+        let (disk_mount, disk_total, disk_available) = disk.map_or_else(
+            || (String::new(), 0, 0),
+            |d| (d.mount_point().to_string_lossy().into_owned(), d.total_space(), d.available_space()),
+        );
+
+        let mut stats = SystemStats {
+            cpu_percent: self.system.global_cpu_usage() as f64,
+            cpu_count: self.system.cpus().len() as u32,
+            memory_used: self.system.used_memory(),
+            memory_total: self.system.total_memory(),
+            swap_used: self.system.used_swap(),
+            swap_total: self.system.total_swap(),
+            disk_mount,
+            disk_used: disk_total.saturating_sub(disk_available),
+            disk_total,
+            groups,
+            warnings: Vec::new(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+        stats.warnings = usage_warnings(&stats);
+        stats
+    }
+}
+
+/// A process as seen by one sample
+struct ProcessNode {
+    parent: Option<u32>,
+    cpu_percent: f64,
+    memory_bytes: u64,
+}
+
+/// Usage of `roots` and all their descendants (each process counted once)
+fn tree_usage(group: ProcessGroup, nodes: &HashMap<u32, ProcessNode>, roots: &[u32]) -> GroupUsage {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for (pid, node) in nodes {
+        if let Some(parent) = node.parent {
+            children.entry(parent).or_default().push(*pid);
+        }
+    }
+
+    let mut seen = HashSet::new();
+    let mut stack: Vec<u32> = roots.iter().copied().filter(|pid| nodes.contains_key(pid)).collect();
+    while let Some(pid) = stack.pop() {
+        if seen.insert(pid) {
+            stack.extend(children.get(&pid).into_iter().flatten().copied());
+        }
+    }
+
+    GroupUsage {
+        group,
+        count: seen.len() as u32,
+        cpu_percent: seen.iter().map(|pid| nodes[pid].cpu_percent).sum(),
+        memory_bytes: seen.iter().map(|pid| nodes[pid].memory_bytes).sum(),
+    }
+}
+
+fn percent(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64 * 100.0
+    }
+}
+
+/// Warnings for a sample: the machine running out of CPU, memory or disk,
+/// and any rstn process group taking a large share of it
+pub fn usage_warnings(stats: &SystemStats) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if stats.cpu_percent >= MACHINE_WARN_PERCENT {
+        warnings.push(format!("CPU is at {:.0}%", stats.cpu_percent));
+    }
+    let memory = percent(stats.memory_used, stats.memory_total);
+    if memory >= MACHINE_WARN_PERCENT {
+        warnings.push(format!("Memory is at {:.0}%", memory));
+    }
+    let disk = percent(stats.disk_used, stats.disk_total);
+    if disk >= DISK_WARN_PERCENT {
+        warnings.push(format!("Disk {} is {:.0}% full", stats.disk_mount, disk));
+    }
+
+    let cpu_capacity = stats.cpu_count.max(1) as f64 * 100.0;
+    for group in &stats.groups {
+        let cpu_share = group.cpu_percent / cpu_capacity * 100.0;
+        let memory_share = percent(group.memory_bytes, stats.memory_total);
+        if cpu_share >= GROUP_WARN_PERCENT {
+            warnings.push(format!("{} use {:.0}% of CPU", group.group.label(), cpu_share));
+        }
+        if memory_share >= GROUP_WARN_PERCENT {
+            warnings.push(format!("{} use {:.0}% of memory", group.group.label(), memory_share));
+        }
+    }
+    warnings
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(parent: Option<u32>, cpu_percent: f64, memory_bytes: u64) -> ProcessNode {
+        ProcessNode {
+            parent,
+            cpu_percent,
+            memory_bytes,
+        }
+    }
+
+    #[test]
+    fn test_tree_usage_counts_descendants_once() {
+        let nodes = HashMap::from([
+            (1, node(None, 0.0, 0)),
+            (10, node(Some(1), 5.0, 100)),
+            (11, node(Some(10), 20.0, 300)),
+            (12, node(Some(11), 1.0, 50)),
+            (20, node(Some(1), 99.0, 9999)),
+        ]);
+
+        // 11 is both a root and a descendant of 10
+        let usage = tree_usage(ProcessGroup::ClaudeCli, &nodes, &[10, 11, 404]);
+        assert_eq!(usage.count, 3);
+        assert_eq!(usage.cpu_percent, 26.0);
+        assert_eq!(usage.memory_bytes, 450);
+
+        let empty = tree_usage(ProcessGroup::Terminals, &nodes, &[]);
+        assert_eq!(empty.count, 0);
+        assert_eq!(empty.memory_bytes, 0);
+    }
+
+    #[test]
+    fn test_usage_warnings() {
+        let mut stats = SystemStats {
+            cpu_percent: 20.0,
+            cpu_count: 4,
+            memory_used: 8,
+            memory_total: 16,
+            disk_mount: "/".to_string(),
+            disk_used: 50,
+            disk_total: 100,
+            groups: vec![GroupUsage {
+                group: ProcessGroup::ClaudeCli,
+                count: 2,
+                cpu_percent: 100.0,
+                memory_bytes: 1,
+            }],
+            ..Default::default()
+        };
+        assert!(usage_warnings(&stats).is_empty());
+
+        stats.cpu_percent = 95.0;
+        stats.disk_used = 99;
+        // Two of four cores
+        stats.groups[0].cpu_percent = 200.0;
+        stats.groups.push(GroupUsage {
+            group: ProcessGroup::Containers,
+            count: 3,
+            cpu_percent: 0.0,
+            memory_bytes: 12,
+        });
+        assert_eq!(
+            usage_warnings(&stats),
+            vec![
+                "CPU is at 95%".to_string(),
+                "Disk / is 99% full".to_string(),
+                "Claude CLI use 50% of CPU".to_string(),
+                "Containers use 75% of memory".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sample_reads_machine() {
+        let mut monitor = SystemMonitor::new();
+        let stats = monitor.sample(&[(ProcessGroup::Terminals, vec![std::process::id()])], Vec::new());
+        assert!(stats.memory_total > 0);
+        assert!(stats.cpu_count > 0);
+        assert_eq!(stats.groups.len(), 1);
+        assert!(stats.groups[0].count >= 1);
+        assert!(stats.groups[0].memory_bytes > 0);
+    }
+}
@@ -0,0 +1,267 @@
+//! Background task queue.
+//!
+//! Tasks run as tracked child processes, at most `max_parallel` at a time;
+//! the rest wait in FIFO order. Every run gets an ID that `CancelTask` uses
+//! to drop a queued run or kill a running one.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use tokio::sync::oneshot;
+
+/// Default number of tasks that may run at once
+pub const DEFAULT_MAX_PARALLEL: usize = 4;
+
+/// Number of output lines kept per run
+pub const OUTPUT_TAIL_LINES: usize = 200;
+
+/// Number of finished runs kept per worktree
+pub const MAX_FINISHED_RUNS: usize = 50;
+
+/// Lifecycle of a queued task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskRunStatus {
+    Queued,
+    Running,
+    Success,
+    Error,
+    Cancelled,
+}
+
+impl TaskRunStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, TaskRunStatus::Success | TaskRunStatus::Error | TaskRunStatus::Cancelled)
+    }
+}
+
+/// A queued, running or finished task run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskRun {
+    /// Run ID (used by CancelTask)
+    pub id: String,
+    /// Key in `task_statuses` (just recipe name or "<provider>:<name>")
+    pub task_key: String,
+    /// Command line, for display
+    pub command: String,
+    pub worktree_path: String,
+    pub status: TaskRunStatus,
+    /// ISO 8601 timestamp when the run was queued
+    pub queued_at: String,
+    pub exit_code: Option<i32>,
+    /// Wall-clock run time (set when finished)
+    pub duration_ms: Option<u64>,
+    /// Last OUTPUT_TAIL_LINES lines of stdout/stderr
+    #[serde(default)]
+    pub output_tail: Vec<String>,
+}
+
+impl TaskRun {
+    /// Append an output line, keeping only the tail
+    pub fn push_output(&mut self, line: String) {
+        self.output_tail.push(line);
+        if self.output_tail.len() > OUTPUT_TAIL_LINES {
+            let excess = self.output_tail.len() - OUTPUT_TAIL_LINES;
+            self.output_tail.drain(..excess);
+        }
+    }
+}
+
+/// What to run for a queued task
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskSpec {
+    pub id: String,
+    pub program: String,
+    pub argv: Vec<String>,
+    pub cwd: String,
+}
+
+/// Result of cancelling a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CancelOutcome {
+    /// Run was still queued and has been removed
+    Dequeued,
+    /// Running process has been asked to stop
+    Signalled,
+    NotFound,
+}
+
+/// FIFO queue of pending tasks plus cancel handles for running ones
+pub struct TaskQueue {
+    pending: Mutex<VecDeque<TaskSpec>>,
+    running: Mutex<HashMap<String, oneshot::Sender<()>>>,
+    max_parallel: AtomicUsize,
+    next_id: AtomicU64,
+}
+
+impl Default for TaskQueue {
+    fn default() -> Self {
+        Self {
+            pending: Mutex::new(VecDeque::new()),
+            running: Mutex::new(HashMap::new()),
+            max_parallel: AtomicUsize::new(DEFAULT_MAX_PARALLEL),
+            next_id: AtomicU64::new(1),
+        }
+    }
+}
+
+impl TaskQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn pending(&self) -> std::sync::MutexGuard<'_, VecDeque<TaskSpec>> {
+        self.pending.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn running(&self) -> std::sync::MutexGuard<'_, HashMap<String, oneshot::Sender<()>>> {
+        self.running.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// New unique run ID
+    pub fn next_id(&self) -> String {
+        format!("task-{}", self.next_id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    pub fn max_parallel(&self) -> usize {
+        self.max_parallel.load(Ordering::Relaxed)
+    }
+
+    /// Set the concurrency limit (at least 1)
+    pub fn set_max_parallel(&self, max_parallel: usize) {
+        self.max_parallel.store(max_parallel.max(1), Ordering::Relaxed);
+    }
+
+    pub fn enqueue(&self, spec: TaskSpec) {
+        self.pending().push_back(spec);
+    }
+
+    /// Dequeue as many tasks as the limit allows, registering a cancel
+    /// handle for each. The caller must run them and call `finish`.
+    pub fn take_startable(&self) -> Vec<(TaskSpec, oneshot::Receiver<()>)> {
+        let mut pending = self.pending();
+        let mut running = self.running();
+        let mut started = Vec::new();
+        while running.len() < self.max_parallel() {
+            let Some(spec) = pending.pop_front() else {
+                break;
+            };
+            let (tx, rx) = oneshot::channel();
+            running.insert(spec.id.clone(), tx);
+            started.push((spec, rx));
+        }
+        started
+    }
+
+    /// Forget a run that has exited
+    pub fn finish(&self, id: &str) {
+        self.running().remove(id);
+    }
+
+    /// Drop a queued run or signal a running one
+    pub fn cancel(&self, id: &str) -> CancelOutcome {
+        {
+            let mut pending = self.pending();
+            if let Some(index) = pending.iter().position(|spec| spec.id == id) {
+                pending.remove(index);
+                return CancelOutcome::Dequeued;
+            }
+        }
+        match self.running().remove(id) {
+            Some(cancel) => {
+                let _ = cancel.send(());
+                CancelOutcome::Signalled
+            }
+            None => CancelOutcome::NotFound,
+        }
+    }
+
+    pub fn running_count(&self) -> usize {
+        self.running().len()
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending().len()
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(queue: &TaskQueue) -> TaskSpec {
+        TaskSpec {
+            id: queue.next_id(),
+            program: "true".to_string(),
+            argv: vec![],
+            cwd: ".".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_take_startable_respects_max_parallel() {
+        let queue = TaskQueue::new();
+        queue.set_max_parallel(2);
+        for _ in 0..3 {
+            queue.enqueue(spec(&queue));
+        }
+
+        let started = queue.take_startable();
+        let ids: Vec<&str> = started.iter().map(|(s, _)| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["task-1", "task-2"]);
+        assert_eq!((queue.running_count(), queue.pending_count()), (2, 1));
+        assert!(queue.take_startable().is_empty());
+
+        queue.finish("task-1");
+        let started = queue.take_startable();
+        assert_eq!(started[0].0.id, "task-3");
+        assert_eq!(queue.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_cancel_queued_and_running() {
+        let queue = TaskQueue::new();
+        queue.set_max_parallel(1);
+        queue.enqueue(spec(&queue));
+        queue.enqueue(spec(&queue));
+
+        let mut started = queue.take_startable();
+        assert_eq!(queue.cancel("task-2"), CancelOutcome::Dequeued);
+        assert_eq!(queue.cancel("task-1"), CancelOutcome::Signalled);
+        assert!(started[0].1.try_recv().is_ok());
+        assert_eq!(queue.cancel("task-1"), CancelOutcome::NotFound);
+        assert_eq!((queue.running_count(), queue.pending_count()), (0, 0));
+    }
+
+    #[test]
+    fn test_output_tail_is_capped() {
+        let mut run = TaskRun {
+            id: "task-1".to_string(),
+            task_key: "build".to_string(),
+            command: "just build".to_string(),
+            worktree_path: "/repo".to_string(),
+            status: TaskRunStatus::Running,
+            queued_at: String::new(),
+            exit_code: None,
+            duration_ms: None,
+            output_tail: Vec::new(),
+        };
+        for i in 0..OUTPUT_TAIL_LINES + 5 {
+            run.push_output(i.to_string());
+        }
+        assert_eq!(run.output_tail.len(), OUTPUT_TAIL_LINES);
+        assert_eq!(run.output_tail[0], "5");
+    }
+
+    #[test]
+    fn test_max_parallel_is_at_least_one() {
+        let queue = TaskQueue::new();
+        queue.set_max_parallel(0);
+        assert_eq!(queue.max_parallel(), 1);
+    }
+}
@@ -0,0 +1,533 @@
+//! Task providers for the Tasks view.
+//!
+//! Each provider discovers runnable tasks from one kind of project file
+//! (justfile, package.json scripts, Makefile targets, cargo aliases) and
+//! knows how to build the command line that runs them.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Child;
+use tokio::sync::mpsc;
+
+use crate::app_state::JustParameterInfo;
+use crate::justfile;
+
+/// Where a task comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskProviderKind {
+    Just,
+    Npm,
+    Yarn,
+    Pnpm,
+    Make,
+    Cargo,
+}
+
+impl TaskProviderKind {
+    /// Program used to run tasks of this kind
+    pub fn program(&self) -> &'static str {
+        match self {
+            TaskProviderKind::Just => "just",
+            TaskProviderKind::Npm => "npm",
+            TaskProviderKind::Yarn => "yarn",
+            TaskProviderKind::Pnpm => "pnpm",
+            TaskProviderKind::Make => "make",
+            TaskProviderKind::Cargo => "cargo",
+        }
+    }
+}
+
+/// A runnable task, regardless of provider
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskDescriptor {
+    /// Unique within a worktree: "<provider>:<name>"
+    pub id: String,
+    pub provider: TaskProviderKind,
+    pub name: String,
+    pub description: Option<String>,
+    /// What the task runs (recipe, script body, make recipe, alias expansion)
+    pub command: String,
+    /// Recipe parameters (justfile only)
+    #[serde(default)]
+    pub parameters: Vec<JustParameterInfo>,
+}
+
+impl TaskDescriptor {
+    fn new(provider: TaskProviderKind, name: String, description: Option<String>, command: String) -> Self {
+        Self {
+            id: task_id(provider, &name),
+            provider,
+            name,
+            description,
+            command,
+            parameters: Vec::new(),
+        }
+    }
+}
+
+/// Task id for a provider and task name
+pub fn task_id(provider: TaskProviderKind, name: &str) -> String {
+    format!("{}:{}", provider.program(), name)
+}
+
+/// A source of runnable tasks
+pub trait TaskProvider: Send + Sync {
+    fn kind(&self) -> TaskProviderKind;
+
+    /// Whether this provider applies to the directory
+    fn detect(&self, dir: &Path) -> bool;
+
+    /// Discover the tasks defined in the directory
+    fn load(&self, dir: &Path) -> Result<Vec<TaskDescriptor>, String>;
+
+    /// Program arguments that run `task` with extra `args`
+    fn command_args(&self, task: &str, args: &[String]) -> Vec<String> {
+        let mut argv = vec![task.to_string()];
+        argv.extend(args.iter().cloned());
+        argv
+    }
+}
+
+/// All known providers
+pub fn providers() -> Vec<Box<dyn TaskProvider>> {
+    vec![
+        Box::new(JustfileProvider),
+        Box::new(PackageScriptsProvider(TaskProviderKind::Npm)),
+        Box::new(PackageScriptsProvider(TaskProviderKind::Yarn)),
+        Box::new(PackageScriptsProvider(TaskProviderKind::Pnpm)),
+        Box::new(MakefileProvider),
+        Box::new(CargoAliasProvider),
+    ]
+}
+
+/// Provider for a kind
+pub fn provider(kind: TaskProviderKind) -> Box<dyn TaskProvider> {
+    providers()
+        .into_iter()
+        .find(|p| p.kind() == kind)
+        .expect("every kind has a provider")
+}
+
+/// Load tasks from every detected provider (or only `kind`).
+/// Errors are reported per provider so one broken file does not hide the rest.
+pub fn load_tasks(dir: &Path, kind: Option<TaskProviderKind>) -> (Vec<TaskDescriptor>, Vec<String>) {
+    let mut tasks = Vec::new();
+    let mut errors = Vec::new();
+    for provider in providers() {
+        if kind.is_some_and(|k| k != provider.kind()) || !provider.detect(dir) {
+            continue;
+        }
+        match provider.load(dir) {
+            Ok(found) => tasks.extend(found),
+            Err(e) => errors.push(e),
+        }
+    }
+    (tasks, errors)
+}
+
+// ============================================================================
+// Running
+// ============================================================================
+
+/// Spawn `program <argv...>` in `cwd` with piped stdout/stderr.
+///
+/// Arguments are passed as separate argv entries (no shell interpolation).
+pub fn spawn_task(program: &str, argv: &[String], cwd: &str) -> Result<Child, String> {
+    let cwd_path = Path::new(cwd);
+    if !cwd_path.exists() {
+        return Err(format!("Directory does not exist: {}", cwd));
+    }
+
+    tokio::process::Command::new(program)
+        .args(argv)
+        .current_dir(cwd_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))
+}
+
+/// Stream stdout and stderr lines of a spawned child as they arrive.
+/// The receiver closes once both streams have ended.
+pub fn output_lines(child: &mut Child) -> mpsc::UnboundedReceiver<String> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    if let Some(stdout) = child.stdout.take() {
+        forward_lines(stdout, tx.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_lines(stderr, tx);
+    }
+    rx
+}
+
+fn forward_lines<R: AsyncRead + Unpin + Send + 'static>(reader: R, tx: mpsc::UnboundedSender<String>) {
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+// ============================================================================
+// justfile
+// ============================================================================
+
+pub struct JustfileProvider;
+
+impl JustfileProvider {
+    fn path(dir: &Path) -> Option<std::path::PathBuf> {
+        ["justfile", "Justfile", ".justfile"]
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    }
+}
+
+impl TaskProvider for JustfileProvider {
+    fn kind(&self) -> TaskProviderKind {
+        TaskProviderKind::Just
+    }
+
+    fn detect(&self, dir: &Path) -> bool {
+        Self::path(dir).is_some()
+    }
+
+    fn load(&self, dir: &Path) -> Result<Vec<TaskDescriptor>, String> {
+        let Some(path) = Self::path(dir) else {
+            return Ok(Vec::new());
+        };
+        let commands = justfile::parse_justfile(&path.to_string_lossy())?;
+        Ok(commands
+            .into_iter()
+            .map(|c| {
+                let mut task = TaskDescriptor::new(self.kind(), c.name, c.description, c.recipe);
+                task.parameters = c.parameters.into_iter().map(Into::into).collect();
+                task
+            })
+            .collect())
+    }
+}
+
+// ============================================================================
+// package.json scripts
+// ============================================================================
+
+/// package.json scripts, run with the package manager whose lockfile is present
+/// (npm when there is none)
+pub struct PackageScriptsProvider(pub TaskProviderKind);
+
+impl PackageScriptsProvider {
+    fn detect_manager(dir: &Path) -> TaskProviderKind {
+        if dir.join("pnpm-lock.yaml").exists() {
+            TaskProviderKind::Pnpm
+        } else if dir.join("yarn.lock").exists() {
+            TaskProviderKind::Yarn
+        } else {
+            TaskProviderKind::Npm
+        }
+    }
+}
+
+impl TaskProvider for PackageScriptsProvider {
+    fn kind(&self) -> TaskProviderKind {
+        self.0
+    }
+
+    fn detect(&self, dir: &Path) -> bool {
+        dir.join("package.json").is_file() && Self::detect_manager(dir) == self.0
+    }
+
+    fn load(&self, dir: &Path) -> Result<Vec<TaskDescriptor>, String> {
+        let content = fs::read_to_string(dir.join("package.json"))
+            .map_err(|e| format!("Failed to read package.json: {}", e))?;
+        parse_package_scripts(&content, self.0)
+    }
+
+    fn command_args(&self, task: &str, args: &[String]) -> Vec<String> {
+        let mut argv = vec!["run".to_string(), task.to_string()];
+        if !args.is_empty() {
+            // npm only forwards arguments after `--`
+            if self.0 == TaskProviderKind::Npm {
+                argv.push("--".to_string());
+            }
+            argv.extend(args.iter().cloned());
+        }
+        argv
+    }
+}
+
+/// Parse the `scripts` of a package.json (sorted by name)
+pub fn parse_package_scripts(content: &str, kind: TaskProviderKind) -> Result<Vec<TaskDescriptor>, String> {
+    let json: serde_json::Value =
+        serde_json::from_str(content).map_err(|e| format!("Invalid package.json: {}", e))?;
+    let Some(scripts) = json.get("scripts").and_then(|s| s.as_object()) else {
+        return Ok(Vec::new());
+    };
+
+    Ok(scripts
+        .iter()
+        .filter_map(|(name, script)| {
+            script
+                .as_str()
+                .map(|script| TaskDescriptor::new(kind, name.clone(), None, script.to_string()))
+        })
+        .collect())
+}
+
+// ============================================================================
+// Makefile
+// ============================================================================
+
+pub struct MakefileProvider;
+
+impl MakefileProvider {
+    fn path(dir: &Path) -> Option<std::path::PathBuf> {
+        ["GNUmakefile", "makefile", "Makefile"]
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    }
+}
+
+impl TaskProvider for MakefileProvider {
+    fn kind(&self) -> TaskProviderKind {
+        TaskProviderKind::Make
+    }
+
+    fn detect(&self, dir: &Path) -> bool {
+        Self::path(dir).is_some()
+    }
+
+    fn load(&self, dir: &Path) -> Result<Vec<TaskDescriptor>, String> {
+        let Some(path) = Self::path(dir) else {
+            return Ok(Vec::new());
+        };
+        let content =
+            fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        Ok(parse_makefile(&content))
+    }
+}
+
+/// Parse explicit Makefile targets.
+///
+/// Descriptions come from a trailing `## comment` on the target line or a
+/// `#` comment directly above it. Special (`.PHONY`), pattern (`%.o`) and
+/// variable targets are skipped.
+pub fn parse_makefile(content: &str) -> Vec<TaskDescriptor> {
+    let mut tasks: Vec<TaskDescriptor> = Vec::new();
+    let mut pending_description: Option<String> = None;
+    // Indices of the targets whose recipe is being read (`a b:` shares one recipe)
+    let mut current: Vec<usize> = Vec::new();
+
+    for line in content.lines() {
+        if line.starts_with('\t') {
+            for &idx in &current {
+                let task = &mut tasks[idx];
+                if !task.command.is_empty() {
+                    task.command.push('\n');
+                }
+                task.command.push_str(line.trim());
+            }
+            continue;
+        }
+
+        current.clear();
+        if let Some(comment) = line.strip_prefix('#') {
+            pending_description = Some(comment.trim_start_matches('#').trim().to_string());
+            continue;
+        }
+        let description = pending_description.take();
+
+        let (rule, inline_description) = match line.split_once("##") {
+            Some((rule, comment)) => (rule, Some(comment.trim().to_string())),
+            None => (line, None),
+        };
+        let Some((targets, rest)) = rule.split_once(':') else {
+            continue;
+        };
+        // Variable assignments (`:=`, `::=`) and targets containing variables or `=`
+        if rest.starts_with('=') || rest.starts_with(":=") || targets.contains('=') || targets.contains('$') {
+            continue;
+        }
+
+        for target in targets.split_whitespace() {
+            if target.starts_with('.') || target.contains('%') || tasks.iter().any(|t| t.name == target) {
+                continue;
+            }
+            tasks.push(TaskDescriptor::new(
+                TaskProviderKind::Make,
+                target.to_string(),
+                inline_description.clone().or_else(|| description.clone()),
+                String::new(),
+            ));
+            current.push(tasks.len() - 1);
+        }
+    }
+
+    tasks
+}
+
+// ============================================================================
+// cargo aliases
+// ============================================================================
+
+/// `[alias]` entries from `.cargo/config.toml` (or legacy `.cargo/config`)
+pub struct CargoAliasProvider;
+
+impl CargoAliasProvider {
+    fn path(dir: &Path) -> Option<std::path::PathBuf> {
+        ["config.toml", "config"]
+            .iter()
+            .map(|name| dir.join(".cargo").join(name))
+            .find(|path| path.is_file())
+    }
+}
+
+impl TaskProvider for CargoAliasProvider {
+    fn kind(&self) -> TaskProviderKind {
+        TaskProviderKind::Cargo
+    }
+
+    fn detect(&self, dir: &Path) -> bool {
+        Self::path(dir).is_some()
+    }
+
+    fn load(&self, dir: &Path) -> Result<Vec<TaskDescriptor>, String> {
+        let Some(path) = Self::path(dir) else {
+            return Ok(Vec::new());
+        };
+        let content =
+            fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        parse_cargo_aliases(&content).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+/// Parse `[alias]` entries (string or array form), sorted by name
+pub fn parse_cargo_aliases(content: &str) -> Result<Vec<TaskDescriptor>, String> {
+    let config: toml::Table = toml::from_str(content).map_err(|e| format!("Invalid cargo config: {}", e))?;
+    let Some(aliases) = config.get("alias").and_then(|a| a.as_table()) else {
+        return Ok(Vec::new());
+    };
+
+    Ok(aliases
+        .iter()
+        .filter_map(|(name, value)| {
+            let expansion = match value {
+                toml::Value::String(s) => s.clone(),
+                toml::Value::Array(items) => items
+                    .iter()
+                    .filter_map(|item| item.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                _ => return None,
+            };
+            Some(TaskDescriptor::new(TaskProviderKind::Cargo, name.clone(), None, expansion))
+        })
+        .collect())
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_package_scripts() {
+        let content = r#"{ "name": "app", "scripts": { "dev": "vite", "build": "tsc && vite build" } }"#;
+        let tasks = parse_package_scripts(content, TaskProviderKind::Pnpm).unwrap();
+
+        let names: Vec<&str> = tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "dev"]);
+        assert_eq!(tasks[0].id, "pnpm:build");
+        assert_eq!(tasks[0].command, "tsc && vite build");
+
+        assert!(parse_package_scripts(r#"{ "name": "lib" }"#, TaskProviderKind::Npm).unwrap().is_empty());
+        assert!(parse_package_scripts("not json", TaskProviderKind::Npm).is_err());
+    }
+
+    #[test]
+    fn test_parse_makefile() {
+        let content = "\
+CC := gcc
+.PHONY: build test
+
+# Compile everything
+build: deps
+\t$(CC) -o app main.c
+\t@echo done
+
+test: build ## Run the test suite
+\t./app --test
+
+%.o: %.c
+\t$(CC) -c $<
+
+clean install:
+\trm -f app
+";
+        let tasks = parse_makefile(content);
+        let names: Vec<&str> = tasks.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "test", "clean", "install"]);
+        assert_eq!(tasks[0].description, Some("Compile everything".to_string()));
+        assert_eq!(tasks[0].command, "$(CC) -o app main.c\n@echo done");
+        assert_eq!(tasks[1].description, Some("Run the test suite".to_string()));
+        assert_eq!(tasks[2].description, None);
+        assert_eq!(tasks[2].command, "rm -f app");
+        assert_eq!(tasks[3].command, "rm -f app");
+    }
+
+    #[test]
+    fn test_parse_cargo_aliases() {
+        let content = r#"
+[alias]
+xtask = "run --package xtask --"
+lint = ["clippy", "--all-targets", "--", "-D", "warnings"]
+
+[build]
+jobs = 4
+"#;
+        let tasks = parse_cargo_aliases(content).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].id, "cargo:lint");
+        assert_eq!(tasks[0].command, "clippy --all-targets -- -D warnings");
+        assert_eq!(tasks[1].command, "run --package xtask --");
+    }
+
+    #[test]
+    fn test_load_tasks_detects_providers() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("justfile"), "# Build it\nbuild:\n    cargo build\n").unwrap();
+        fs::write(temp.path().join("package.json"), r#"{ "scripts": { "dev": "vite" } }"#).unwrap();
+        fs::write(temp.path().join("yarn.lock"), "").unwrap();
+
+        let (tasks, errors) = load_tasks(temp.path(), None);
+        assert!(errors.is_empty());
+        let ids: Vec<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["just:build", "yarn:dev"]);
+
+        let (tasks, _) = load_tasks(temp.path(), Some(TaskProviderKind::Yarn));
+        assert_eq!(tasks.len(), 1);
+        assert!(load_tasks(temp.path(), Some(TaskProviderKind::Npm)).0.is_empty());
+    }
+
+    #[test]
+    fn test_command_args() {
+        let args = vec!["--watch".to_string()];
+        assert_eq!(provider(TaskProviderKind::Npm).command_args("test", &args), vec!["run", "test", "--", "--watch"]);
+        assert_eq!(provider(TaskProviderKind::Yarn).command_args("test", &args), vec!["run", "test", "--watch"]);
+        assert_eq!(provider(TaskProviderKind::Make).command_args("test", &[]), vec!["test"]);
+    }
+}
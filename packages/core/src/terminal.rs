@@ -1,13 +1,18 @@
 //! Integrated PTY Terminal for worktree-scoped terminal sessions.
 //!
-//! Uses portable-pty to spawn shell sessions and stream I/O.
+//! Uses portable-pty to spawn shell sessions and stream I/O. Sessions that
+//! run elsewhere (e.g. `docker exec` in a container) are attached as remote
+//! sessions and share the same output stream, input and resize handling.
 
-use portable_pty::{native_pty_system, CommandBuilder, PtyPair, PtySize};
+use futures_util::{Stream, StreamExt};
+use portable_pty::{native_pty_system, Child, CommandBuilder, PtyPair, PtySize};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::io::{Read, Write};
-use std::sync::Arc;
-use tokio::sync::{mpsc, RwLock};
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use tokio::io::AsyncWrite;
+use tokio::sync::{mpsc, Mutex};
 
 // ============================================================================
 // Terminal State (serializable part)
@@ -44,40 +49,81 @@ impl TerminalState {
 pub struct TerminalSession {
     /// Unique session ID.
     pub id: String,
-    /// Worktree ID this session belongs to.
+    /// Worktree this session belongs to (worktree path, stable across
+    /// refreshes), or the container of a remote session.
     pub worktree_id: String,
     /// Working directory.
     pub cwd: String,
-    /// PTY pair (master + child).
-    pty_pair: PtyPair,
-    /// Writer to send input to PTY.
-    writer: Box<dyn Write + Send>,
+    /// Where the session's process runs.
+    io: SessionIo,
     /// Channel to stop the reader task.
     stop_tx: Option<mpsc::Sender<()>>,
 }
 
+enum SessionIo {
+    /// Local shell in a PTY
+    Pty {
+        /// PTY pair (master + child).
+        pty_pair: PtyPair,
+        /// Shell process running in the PTY (taken when the session is dropped).
+        child: Option<Box<dyn Child + Send + Sync>>,
+        /// Writer to send input to PTY.
+        writer: Box<dyn Write + Send>,
+    },
+    /// Process driven through streams (see `RemoteIo`)
+    Remote {
+        input_tx: mpsc::UnboundedSender<Vec<u8>>,
+        resize_tx: mpsc::UnboundedSender<(u16, u16)>,
+    },
+}
+
+/// I/O of a session whose process doesn't run in a local PTY.
+pub struct RemoteIo {
+    /// Output chunks; the session's process has exited when the stream ends.
+    pub output: Pin<Box<dyn Stream<Item = Vec<u8>> + Send>>,
+    /// User input. Dropped (closing the process's stdin) when the session is killed.
+    pub input: Pin<Box<dyn AsyncWrite + Send>>,
+    /// Receives resize requests (cols, rows).
+    pub resize_tx: mpsc::UnboundedSender<(u16, u16)>,
+}
+
 impl TerminalSession {
     /// Resize the terminal.
     pub fn resize(&self, cols: u16, rows: u16) -> Result<(), String> {
-        self.pty_pair
-            .master
-            .resize(PtySize {
-                rows,
-                cols,
-                pixel_width: 0,
-                pixel_height: 0,
-            })
-            .map_err(|e| format!("Failed to resize PTY: {}", e))
+        match &self.io {
+            SessionIo::Pty { pty_pair, .. } => pty_pair
+                .master
+                .resize(PtySize {
+                    rows,
+                    cols,
+                    pixel_width: 0,
+                    pixel_height: 0,
+                })
+                .map_err(|e| format!("Failed to resize PTY: {}", e)),
+            SessionIo::Remote { resize_tx, .. } => resize_tx
+                .send((cols, rows))
+                .map_err(|_| "Session has exited".to_string()),
+        }
     }
 
     /// Write data to the terminal (user input).
     pub fn write(&mut self, data: &[u8]) -> Result<(), String> {
-        self.writer
-            .write_all(data)
-            .map_err(|e| format!("Failed to write to PTY: {}", e))?;
-        self.writer
-            .flush()
-            .map_err(|e| format!("Failed to flush PTY: {}", e))
+        match &mut self.io {
+            SessionIo::Pty { writer, .. } => {
+                writer
+                    .write_all(data)
+                    .map_err(|e| format!("Failed to write to PTY: {}", e))?;
+                writer.flush().map_err(|e| format!("Failed to flush PTY: {}", e))
+            }
+            SessionIo::Remote { input_tx, .. } => input_tx
+                .send(data.to_vec())
+                .map_err(|_| "Session has exited".to_string()),
+        }
+    }
+
+    /// Whether the session runs outside a worktree (e.g. in a container)
+    pub fn is_remote(&self) -> bool {
+        matches!(self.io, SessionIo::Remote { .. })
     }
 }
 
@@ -87,7 +133,24 @@ impl Drop for TerminalSession {
         if let Some(tx) = self.stop_tx.take() {
             let _ = tx.try_send(());
         }
-        // The PTY will be closed when pty_pair is dropped
+        // Kill the shell so the reader sees EOF; the PTY closes when pty_pair is dropped.
+        // Remote sessions end when their input channel is dropped.
+        if let SessionIo::Pty { child, .. } = &mut self.io {
+            if let Some(mut child) = child.take() {
+                let _ = child.kill();
+                // Drop runs under the sessions lock: reap the shell off-thread
+                // unless it has already exited
+                if !matches!(child.try_wait(), Ok(Some(_))) {
+                    let reap = move || {
+                        let _ = child.wait();
+                    };
+                    match tokio::runtime::Handle::try_current() {
+                        Ok(handle) => drop(handle.spawn_blocking(reap)),
+                        Err(_) => drop(std::thread::spawn(reap)),
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -100,8 +163,8 @@ pub type OutputCallback = Arc<dyn Fn(String, Vec<u8>) + Send + Sync>;
 
 /// Manager for all terminal sessions.
 pub struct TerminalManager {
-    /// Active sessions by session ID.
-    sessions: RwLock<HashMap<String, TerminalSession>>,
+    /// Active sessions by session ID (Mutex: PTY handles are Send but not Sync).
+    sessions: Mutex<HashMap<String, TerminalSession>>,
     /// Output callback (session_id, data).
     output_callback: RwLock<Option<OutputCallback>>,
 }
@@ -115,14 +178,14 @@ impl Default for TerminalManager {
 impl TerminalManager {
     pub fn new() -> Self {
         Self {
-            sessions: RwLock::new(HashMap::new()),
+            sessions: Mutex::new(HashMap::new()),
             output_callback: RwLock::new(None),
         }
     }
 
     /// Set the output callback for streaming PTY output.
-    pub async fn set_output_callback(&self, callback: OutputCallback) {
-        let mut cb = self.output_callback.write().await;
+    pub fn set_output_callback(&self, callback: OutputCallback) {
+        let mut cb = self.output_callback.write().unwrap_or_else(|e| e.into_inner());
         *cb = Some(callback);
     }
 
@@ -165,7 +228,7 @@ impl TerminalManager {
         cmd.env("COLORTERM", "truecolor");
 
         // Spawn child process
-        let _child = pty_pair
+        let child = pty_pair
             .slave
             .spawn_command(cmd)
             .map_err(|e| format!("Failed to spawn shell: {}", e))?;
@@ -189,12 +252,14 @@ impl TerminalManager {
         let session_id_clone = session_id.clone();
         // Clone the callback Arc if set
         let output_callback = {
-            let cb = self.output_callback.blocking_read();
+            let cb = self.output_callback.read().unwrap_or_else(|e| e.into_inner());
             cb.clone()
         };
 
         tokio::task::spawn_blocking(move || {
             let mut buf = [0u8; 4096];
+            // Bytes of a UTF-8 sequence split across reads
+            let mut pending: Vec<u8> = Vec::new();
             loop {
                 // Check for stop signal (non-blocking)
                 if stop_rx.try_recv().is_ok() {
@@ -204,7 +269,11 @@ impl TerminalManager {
                 match reader.read(&mut buf) {
                     Ok(0) => break, // EOF
                     Ok(n) => {
-                        let data = buf[..n].to_vec();
+                        pending.extend_from_slice(&buf[..n]);
+                        let data = take_utf8_complete(&mut pending);
+                        if data.is_empty() {
+                            continue;
+                        }
                         let sid = session_id_clone.clone();
 
                         // Call output callback if set
@@ -233,20 +302,83 @@ impl TerminalManager {
             id: session_id.clone(),
             worktree_id,
             cwd,
-            pty_pair,
-            writer,
+            io: SessionIo::Pty { pty_pair, child: Some(child), writer },
             stop_tx: Some(stop_tx),
         };
 
-        let mut sessions = self.sessions.write().await;
+        let mut sessions = self.sessions.lock().await;
         sessions.insert(session_id.clone(), session);
 
         Ok(session_id)
     }
 
+    /// Attach a remote session (e.g. `docker exec`) owned by `owner_id`.
+    ///
+    /// Output goes to the output callback like PTY output; when the process
+    /// exits a notice is written and the session stays until killed.
+    pub async fn attach(&self, owner_id: String, cwd: String, io: RemoteIo) -> String {
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let (stop_tx, mut stop_rx) = mpsc::channel::<()>(1);
+        let output_callback = {
+            let cb = self.output_callback.read().unwrap_or_else(|e| e.into_inner());
+            cb.clone()
+        };
+
+        let session_id_clone = session_id.clone();
+        let mut output = io.output;
+        tokio::spawn(async move {
+            let emit = |data: Vec<u8>| {
+                if let Some(ref callback) = output_callback {
+                    callback(session_id_clone.clone(), data);
+                }
+            };
+            let mut pending: Vec<u8> = Vec::new();
+            loop {
+                tokio::select! {
+                    _ = stop_rx.recv() => break,
+                    chunk = output.next() => match chunk {
+                        Some(chunk) => {
+                            pending.extend_from_slice(&chunk);
+                            let data = take_utf8_complete(&mut pending);
+                            if !data.is_empty() {
+                                emit(data);
+                            }
+                        }
+                        None => {
+                            emit(b"\r\n[process exited]\r\n".to_vec());
+                            break;
+                        }
+                    },
+                }
+            }
+        });
+
+        let (input_tx, mut input_rx) = mpsc::unbounded_channel::<Vec<u8>>();
+        let mut input = io.input;
+        tokio::spawn(async move {
+            use tokio::io::AsyncWriteExt;
+            while let Some(data) = input_rx.recv().await {
+                if input.write_all(&data).await.is_err() || input.flush().await.is_err() {
+                    break;
+                }
+            }
+            let _ = input.shutdown().await;
+        });
+
+        let session = TerminalSession {
+            id: session_id.clone(),
+            worktree_id: owner_id,
+            cwd,
+            io: SessionIo::Remote { input_tx, resize_tx: io.resize_tx },
+            stop_tx: Some(stop_tx),
+        };
+        self.sessions.lock().await.insert(session_id.clone(), session);
+        session_id
+    }
+
     /// Resize a terminal session.
     pub async fn resize(&self, session_id: &str, cols: u16, rows: u16) -> Result<(), String> {
-        let sessions = self.sessions.read().await;
+        let sessions = self.sessions.lock().await;
         let session = sessions
             .get(session_id)
             .ok_or_else(|| format!("Session not found: {}", session_id))?;
@@ -255,7 +387,7 @@ impl TerminalManager {
 
     /// Write data to a terminal session.
     pub async fn write(&self, session_id: &str, data: &[u8]) -> Result<(), String> {
-        let mut sessions = self.sessions.write().await;
+        let mut sessions = self.sessions.lock().await;
         let session = sessions
             .get_mut(session_id)
             .ok_or_else(|| format!("Session not found: {}", session_id))?;
@@ -264,7 +396,7 @@ impl TerminalManager {
 
     /// Kill a terminal session.
     pub async fn kill(&self, session_id: &str) -> Result<(), String> {
-        let mut sessions = self.sessions.write().await;
+        let mut sessions = self.sessions.lock().await;
         sessions
             .remove(session_id)
             .ok_or_else(|| format!("Session not found: {}", session_id))?;
@@ -273,25 +405,46 @@ impl TerminalManager {
 
     /// Kill all sessions for a worktree.
     pub async fn kill_worktree_sessions(&self, worktree_id: &str) {
-        let mut sessions = self.sessions.write().await;
+        let mut sessions = self.sessions.lock().await;
         sessions.retain(|_, s| s.worktree_id != worktree_id);
     }
 
+    /// Kill sessions whose worktree is no longer open (remote sessions are
+    /// not tied to a worktree). Returns how many were killed.
+    pub async fn kill_orphaned_sessions(&self, live_worktree_ids: &[String]) -> usize {
+        let mut sessions = self.sessions.lock().await;
+        let before = sessions.len();
+        sessions.retain(|_, s| s.is_remote() || live_worktree_ids.contains(&s.worktree_id));
+        before - sessions.len()
+    }
+
     /// Kill all sessions.
     pub async fn kill_all(&self) {
-        let mut sessions = self.sessions.write().await;
+        let mut sessions = self.sessions.lock().await;
         sessions.clear();
     }
 
     /// Check if a session exists.
     pub async fn has_session(&self, session_id: &str) -> bool {
-        let sessions = self.sessions.read().await;
+        let sessions = self.sessions.lock().await;
         sessions.contains_key(session_id)
     }
 
+    /// Process IDs of the local (PTY) sessions' shells
+    pub async fn local_pids(&self) -> Vec<u32> {
+        let sessions = self.sessions.lock().await;
+        sessions
+            .values()
+            .filter_map(|s| match &s.io {
+                SessionIo::Pty { child, .. } => child.as_ref().and_then(|c| c.process_id()),
+                SessionIo::Remote { .. } => None,
+            })
+            .collect()
+    }
+
     /// Get session info for a worktree.
     pub async fn get_worktree_session(&self, worktree_id: &str) -> Option<String> {
-        let sessions = self.sessions.read().await;
+        let sessions = self.sessions.lock().await;
         sessions
             .values()
             .find(|s| s.worktree_id == worktree_id)
@@ -299,6 +452,22 @@ impl TerminalManager {
     }
 }
 
+/// Drain the longest prefix of `pending` that doesn't end mid UTF-8 sequence.
+///
+/// PTY reads can split multi-byte characters; holding back the incomplete
+/// tail keeps each chunk decodable on the JS side. Invalid bytes are passed
+/// through unchanged.
+fn take_utf8_complete(pending: &mut Vec<u8>) -> Vec<u8> {
+    let split = match std::str::from_utf8(pending) {
+        Ok(_) => pending.len(),
+        // error_len() == None means the input ended mid-sequence
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        Err(_) => pending.len(),
+    };
+    let rest = pending.split_off(split);
+    std::mem::replace(pending, rest)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -334,6 +503,64 @@ mod tests {
         assert!(!manager.has_session("nonexistent").await);
     }
 
+    #[test]
+    fn test_take_utf8_complete_holds_back_split_char() {
+        // "é" is 0xC3 0xA9
+        let mut pending = vec![b'a', 0xC3];
+        assert_eq!(take_utf8_complete(&mut pending), vec![b'a']);
+        assert_eq!(pending, vec![0xC3]);
+
+        pending.push(0xA9);
+        assert_eq!(take_utf8_complete(&mut pending), "é".as_bytes().to_vec());
+        assert!(pending.is_empty());
+
+        // Invalid bytes are not held back forever
+        let mut pending = vec![0xFF, b'b'];
+        assert_eq!(take_utf8_complete(&mut pending), vec![0xFF, b'b']);
+    }
+
+    #[tokio::test]
+    async fn test_kill_orphaned_sessions_without_sessions() {
+        let manager = TerminalManager::new();
+        assert_eq!(manager.kill_orphaned_sessions(&["/repo".to_string()]).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_attach_remote_session() {
+        use tokio::io::AsyncReadExt;
+
+        let manager = TerminalManager::new();
+        let (output_tx, mut output_rx) = mpsc::unbounded_channel::<Vec<u8>>();
+        manager.set_output_callback(Arc::new(move |_, data| {
+            let _ = output_tx.send(data);
+        }));
+
+        let (input, mut process_stdin) = tokio::io::duplex(64);
+        let (resize_tx, mut resize_rx) = mpsc::unbounded_channel();
+        let io = RemoteIo {
+            output: Box::pin(futures_util::stream::iter(vec![b"psql> ".to_vec()])),
+            input: Box::pin(input),
+            resize_tx,
+        };
+        let session_id = manager.attach("rstn-postgres".to_string(), "/".to_string(), io).await;
+
+        assert_eq!(output_rx.recv().await.unwrap(), b"psql> ");
+        assert_eq!(output_rx.recv().await.unwrap(), b"\r\n[process exited]\r\n");
+
+        manager.write(&session_id, b"\\q\r").await.unwrap();
+        let mut buf = [0u8; 3];
+        process_stdin.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"\\q\r");
+
+        manager.resize(&session_id, 100, 30).await.unwrap();
+        assert_eq!(resize_rx.recv().await, Some((100, 30)));
+
+        // Not tied to a worktree
+        assert_eq!(manager.kill_orphaned_sessions(&[]).await, 0);
+        manager.kill(&session_id).await.unwrap();
+        assert!(!manager.has_session(&session_id).await);
+    }
+
     // Note: Full PTY tests require a real terminal environment
     // and are better suited for integration tests
 }
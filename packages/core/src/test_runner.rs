@@ -0,0 +1,708 @@
+//! Test runs after an implementation (CESDD Phase 5).
+//!
+//! Detects the project's test command, runs it in the worktree and parses
+//! the output of cargo test, pytest, Jest and Vitest into `TestResults`.
+//! The results of the last run are stored in
+//! `.rstn/changes/<name>/test-results.json`; a red run blocks approving the
+//! change's reviews until tests pass again or the user overrides the gate.
+//!
+//! Projects that collect coverage run the coverage variant of their tests
+//! instead (cargo llvm-cov, pytest --cov, vitest --coverage). The lcov or
+//! Cobertura report is reduced to per-file line coverage and compared with
+//! the previous coverage run of the worktree (`.rstn/coverage/summary.json`),
+//! so a review shows whether the change lowered coverage.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Maximum time allowed for the post-implementation test run
+pub const TEST_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// File (inside the change directory) that receives failing test output
+pub const TEST_FAILURE_LOG: &str = "test-failure.log";
+
+/// Results file in the change directory
+pub const TEST_RESULTS_FILE: &str = "test-results.json";
+
+/// Number of failing test names kept
+const MAX_FAILING_TESTS: usize = 50;
+
+/// Directory (relative to the worktree) receiving coverage reports
+pub const COVERAGE_DIR: &str = ".rstn/coverage";
+
+/// Last coverage summary of the worktree, the baseline of the next run
+const COVERAGE_SUMMARY_FILE: &str = "summary.json";
+
+/// Smallest drop in a file's coverage (percentage points) reported as a regression
+const REGRESSION_THRESHOLD: f64 = 0.1;
+
+/// A test command to run in the worktree
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCommand {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl TestCommand {
+    fn new(program: &str, args: &[&str]) -> Self {
+        Self {
+            program: program.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    /// Human-readable command line (for logs)
+    pub fn display(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(|a| a.as_str()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Detect the project's test command: `just test`, `cargo test`, `npm test`
+/// or `pytest`
+pub fn detect_test_command(worktree: &Path) -> Option<TestCommand> {
+    let justfile = worktree.join("justfile");
+    if justfile.exists() {
+        if let Ok(commands) = crate::justfile::parse_justfile(&justfile.to_string_lossy()) {
+            if commands.iter().any(|c| c.name == "test") {
+                return Some(TestCommand::new("just", &["test"]));
+            }
+        }
+    }
+
+    if worktree.join("Cargo.toml").exists() {
+        return Some(TestCommand::new("cargo", &["test"]));
+    }
+
+    let package_json = worktree.join("package.json");
+    if let Ok(content) = std::fs::read_to_string(package_json) {
+        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
+            if json.pointer("/scripts/test").and_then(|v| v.as_str()).is_some() {
+                return Some(TestCommand::new("npm", &["test"]));
+            }
+        }
+    }
+
+    let pyproject = std::fs::read_to_string(worktree.join("pyproject.toml")).unwrap_or_default();
+    if worktree.join("pytest.ini").exists() || pyproject.contains("pytest") || worktree.join("tests").join("conftest.py").exists() {
+        return Some(TestCommand::new("pytest", &[]));
+    }
+
+    None
+}
+
+/// Coverage report format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoverageFormat {
+    Lcov,
+    Cobertura,
+}
+
+/// Report written by a coverage run
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoverageReport {
+    /// Path relative to the worktree
+    pub path: PathBuf,
+    pub format: CoverageFormat,
+}
+
+/// Coverage variant of the project's tests: `cargo llvm-cov`,
+/// `vitest --coverage` or `pytest --cov`, with the report it writes
+pub fn detect_coverage_command(worktree: &Path) -> Option<(TestCommand, CoverageReport)> {
+    let lcov = || CoverageReport {
+        path: Path::new(COVERAGE_DIR).join("lcov.info"),
+        format: CoverageFormat::Lcov,
+    };
+
+    if worktree.join("Cargo.toml").exists() {
+        let report = lcov();
+        let output_path = report.path.to_string_lossy().to_string();
+        return Some((TestCommand::new("cargo", &["llvm-cov", "--lcov", "--output-path", &output_path]), report));
+    }
+
+    let package_json = std::fs::read_to_string(worktree.join("package.json")).unwrap_or_default();
+    if package_json.contains("\"vitest\"") || package_json.contains("\"vitest ") {
+        let reports_dir = format!("--coverage.reportsDirectory={}", COVERAGE_DIR);
+        return Some((
+            TestCommand::new("npx", &["vitest", "run", "--coverage", "--coverage.reporter=lcov", &reports_dir]),
+            lcov(),
+        ));
+    }
+
+    if detect_test_command(worktree).is_some_and(|c| c.program == "pytest") {
+        let report = CoverageReport {
+            path: Path::new(COVERAGE_DIR).join("coverage.xml"),
+            format: CoverageFormat::Cobertura,
+        };
+        let xml = format!("--cov-report=xml:{}", report.path.display());
+        return Some((TestCommand::new("pytest", &["--cov", &xml]), report));
+    }
+
+    None
+}
+
+/// Result of a test run
+#[derive(Debug, Clone)]
+pub struct TestRunResult {
+    pub success: bool,
+    /// Combined stdout + stderr
+    pub output: String,
+    pub duration: Duration,
+}
+
+/// Run the test command in the worktree (bounded by TEST_TIMEOUT)
+pub async fn run_test_command(command: &TestCommand, worktree: &Path) -> Result<TestRunResult, String> {
+    let started = Instant::now();
+    let run = tokio::process::Command::new(&command.program)
+        .args(&command.args)
+        .current_dir(worktree)
+        .kill_on_drop(true)
+        .output();
+
+    let output = tokio::time::timeout(TEST_TIMEOUT, run)
+        .await
+        .map_err(|_| format!("`{}` exceeded {} second timeout", command.display(), TEST_TIMEOUT.as_secs()))?
+        .map_err(|e| format!("Failed to run `{}`: {}", command.display(), e))?;
+
+    Ok(TestRunResult {
+        success: output.status.success(),
+        output: format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        ),
+        duration: started.elapsed(),
+    })
+}
+
+/// Write failing test output into the change directory, returning the log path
+pub fn write_test_failure_log(change_dir: &Path, command: &TestCommand, output: &str) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(change_dir)
+        .map_err(|e| format!("Failed to create change directory: {}", e))?;
+    let path = change_dir.join(TEST_FAILURE_LOG);
+    let content = format!("$ {}\n\n{}", command.display(), output);
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", TEST_FAILURE_LOG, e))?;
+    Ok(path)
+}
+
+/// Structured outcome of the last test run of a change
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestResults {
+    /// Command line that was run
+    pub command: String,
+    /// Whether the command exited successfully
+    pub success: bool,
+    pub passed: u32,
+    pub failed: u32,
+    /// Ignored / skipped tests
+    pub ignored: u32,
+    /// Names of failing tests (at most MAX_FAILING_TESTS)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failing_tests: Vec<String>,
+    pub duration_ms: u64,
+    /// ISO 8601 timestamp
+    pub ran_at: String,
+    /// The user chose to approve reviews despite this run failing
+    #[serde(default)]
+    pub overridden: bool,
+    /// Line coverage, when the run collected it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<CoverageSummary>,
+}
+
+impl TestResults {
+    pub fn from_run(command: &TestCommand, run: &TestRunResult) -> Self {
+        let mut results = parse_output(&run.output);
+        results.command = command.display();
+        results.success = run.success;
+        results.duration_ms = run.duration.as_millis() as u64;
+        results
+    }
+
+    pub fn is_green(&self) -> bool {
+        self.success && self.failed == 0
+    }
+
+    /// Why reviews of the change cannot be approved (None = green or overridden)
+    pub fn approval_blocker(&self) -> Option<String> {
+        if self.is_green() || self.overridden {
+            return None;
+        }
+        Some(match self.failed {
+            0 => format!("`{}` failed; fix the tests or override the test gate", self.command),
+            1 => format!("1 test fails in `{}`; fix it or override the test gate", self.command),
+            n => format!("{} tests fail in `{}`; fix them or override the test gate", n, self.command),
+        })
+    }
+}
+
+/// Counts and failing test names found in test output (command, success and
+/// duration are left for the caller)
+pub fn parse_output(output: &str) -> TestResults {
+    let mut results = TestResults {
+        command: String::new(),
+        success: false,
+        passed: 0,
+        failed: 0,
+        ignored: 0,
+        failing_tests: Vec::new(),
+        duration_ms: 0,
+        ran_at: chrono::Utc::now().to_rfc3339(),
+        overridden: false,
+        coverage: None,
+    };
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        let counts = if let Some(rest) = trimmed.strip_prefix("test result: ") {
+            // cargo: "test result: ok. 3 passed; 1 failed; 0 ignored; ..."
+            rest.split_once(". ").map(|(_, counts)| counts.split("; ").collect::<Vec<_>>())
+        } else if let Some(rest) = trimmed.strip_prefix("Tests:") {
+            // Jest: "Tests:       1 failed, 5 passed, 6 total"
+            Some(rest.split(", ").collect())
+        } else if let Some(rest) = trimmed.strip_prefix("Tests ").filter(|r| r.trim_end().ends_with(')')) {
+            // Vitest: "Tests  1 failed | 5 passed (6)"
+            rest.rsplit_once(" (").map(|(counts, _)| counts.split(" | ").collect())
+        } else if trimmed.starts_with('=') && trimmed.ends_with('=') {
+            // pytest: "==== 1 failed, 2 passed, 1 skipped in 0.12s ===="
+            trimmed
+                .trim_matches(|c| c == '=' || c == ' ')
+                .rsplit_once(" in ")
+                .map(|(counts, _)| counts.split(", ").collect())
+        } else {
+            None
+        };
+        for count in counts.unwrap_or_default() {
+            let Some((number, label)) = count.trim().split_once(' ') else {
+                continue;
+            };
+            let Ok(number) = number.parse::<u32>() else {
+                continue;
+            };
+            match label.trim() {
+                "passed" => results.passed += number,
+                "failed" | "error" | "errors" => results.failed += number,
+                "ignored" | "skipped" | "todo" => results.ignored += number,
+                _ => {}
+            }
+        }
+
+        if let Some(name) = failing_test_name(line) {
+            if results.failing_tests.len() < MAX_FAILING_TESTS && !results.failing_tests.iter().any(|n| n == name) {
+                results.failing_tests.push(name.to_string());
+            }
+        }
+    }
+    results
+}
+
+/// Name of the failing test reported on `line`, if any
+fn failing_test_name(line: &str) -> Option<&str> {
+    // cargo: "test auth::tests::login ... FAILED"
+    if let Some(name) = line.strip_prefix("test ").and_then(|l| l.strip_suffix(" ... FAILED")) {
+        return Some(name);
+    }
+    let trimmed = line.trim();
+    // pytest: "FAILED tests/test_auth.py::test_login - AssertionError"
+    if let Some(rest) = trimmed.strip_prefix("FAILED ") {
+        return Some(rest.split_once(" - ").map_or(rest, |(name, _)| name).trim());
+    }
+    // Vitest: "FAIL  src/auth.test.ts > login > rejects bad password"
+    if let Some(rest) = trimmed.strip_prefix("FAIL ").filter(|r| r.contains(" > ")) {
+        return Some(rest.trim());
+    }
+    // Jest: "● login › rejects bad password"
+    trimmed
+        .strip_prefix("● ")
+        .filter(|rest| !rest.starts_with("Test suite failed to run"))
+}
+
+/// Line coverage of one source file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileCoverage {
+    /// Path as given by the report (relative to the worktree when possible)
+    pub path: String,
+    pub lines_covered: u32,
+    pub lines_total: u32,
+}
+
+impl FileCoverage {
+    pub fn percent(&self) -> f64 {
+        percent(self.lines_covered, self.lines_total)
+    }
+}
+
+/// A file whose coverage dropped since the previous run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoverageRegression {
+    pub path: String,
+    pub before: f64,
+    pub after: f64,
+}
+
+/// Coverage of a run, compared with the previous run of the worktree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoverageSummary {
+    pub lines_covered: u32,
+    pub lines_total: u32,
+    /// Covered percentage (0-100)
+    pub percent: f64,
+    /// Files, least covered first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<FileCoverage>,
+    /// Covered percentage of the previous coverage run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_percent: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub regressions: Vec<CoverageRegression>,
+}
+
+impl CoverageSummary {
+    /// Summarize per-file coverage and compare it with the previous run
+    pub fn new(mut files: Vec<FileCoverage>, previous: Option<&CoverageSummary>) -> Self {
+        files.sort_by(|a, b| a.percent().total_cmp(&b.percent()).then_with(|| a.path.cmp(&b.path)));
+        let lines_covered = files.iter().map(|f| f.lines_covered).sum();
+        let lines_total = files.iter().map(|f| f.lines_total).sum();
+        let regressions = previous
+            .map(|previous| {
+                files
+                    .iter()
+                    .filter_map(|file| {
+                        let before = previous.files.iter().find(|f| f.path == file.path)?.percent();
+                        (before - file.percent() >= REGRESSION_THRESHOLD).then(|| CoverageRegression {
+                            path: file.path.clone(),
+                            before,
+                            after: file.percent(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            lines_covered,
+            lines_total,
+            percent: percent(lines_covered, lines_total),
+            files,
+            previous_percent: previous.map(|p| p.percent),
+            regressions,
+        }
+    }
+
+    /// Change in percentage points since the previous run
+    pub fn delta(&self) -> Option<f64> {
+        self.previous_percent.map(|previous| self.percent - previous)
+    }
+}
+
+fn percent(covered: u32, total: u32) -> f64 {
+    if total == 0 {
+        100.0
+    } else {
+        f64::from(covered) * 100.0 / f64::from(total)
+    }
+}
+
+/// Per-file coverage from a coverage run's report
+pub fn read_coverage(worktree: &Path, report: &CoverageReport) -> Result<Vec<FileCoverage>, String> {
+    let path = worktree.join(&report.path);
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let files = match report.format {
+        CoverageFormat::Lcov => parse_lcov(&content),
+        CoverageFormat::Cobertura => parse_cobertura(&content),
+    };
+    Ok(files
+        .into_iter()
+        .map(|file| FileCoverage {
+            path: Path::new(&file.path)
+                .strip_prefix(worktree)
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or(file.path),
+            ..file
+        })
+        .collect())
+}
+
+/// Parse an lcov tracefile (`SF:`, `DA:`, `LH:`/`LF:` records)
+pub fn parse_lcov(content: &str) -> Vec<FileCoverage> {
+    let mut files: Vec<FileCoverage> = Vec::new();
+    let mut current: Option<FileCoverage> = None;
+    // DA lines, used when a record has no LH/LF totals
+    let mut hits: Vec<(u32, bool)> = Vec::new();
+    let mut totals = false;
+    for line in content.lines().map(str::trim) {
+        if let Some(path) = line.strip_prefix("SF:") {
+            current = Some(FileCoverage { path: path.to_string(), lines_covered: 0, lines_total: 0 });
+            hits.clear();
+            totals = false;
+        } else if line == "end_of_record" {
+            if let Some(mut file) = current.take() {
+                if !totals {
+                    hits.sort_unstable();
+                    hits.dedup_by_key(|(number, _)| *number);
+                    file.lines_total = hits.len() as u32;
+                    file.lines_covered = hits.iter().filter(|(_, hit)| *hit).count() as u32;
+                }
+                add_file(&mut files, file);
+            }
+        } else if let Some(file) = current.as_mut() {
+            if let Some(da) = line.strip_prefix("DA:") {
+                let mut fields = da.split(',');
+                if let (Some(Ok(number)), Some(Ok(count))) =
+                    (fields.next().map(str::parse::<u32>), fields.next().map(str::parse::<u64>))
+                {
+                    hits.push((number, count > 0));
+                }
+            } else if let Some(found) = line.strip_prefix("LF:").and_then(|n| n.parse().ok()) {
+                file.lines_total = found;
+                totals = true;
+            } else if let Some(hit) = line.strip_prefix("LH:").and_then(|n| n.parse().ok()) {
+                file.lines_covered = hit;
+                totals = true;
+            }
+        }
+    }
+    files
+}
+
+/// Parse a Cobertura XML report (`<class filename>` with `<line hits>`)
+pub fn parse_cobertura(content: &str) -> Vec<FileCoverage> {
+    let mut files = Vec::new();
+    for class in content.split("<class ").skip(1) {
+        let body = class.split("</class>").next().unwrap_or(class);
+        let Some(path) = xml_attribute(body, "filename") else {
+            continue;
+        };
+        // Methods repeat their lines, so count each line number once
+        let mut lines: Vec<(u32, bool)> = body
+            .split("<line ")
+            .skip(1)
+            .filter_map(|tag| {
+                let number = xml_attribute(tag, "number")?.parse().ok()?;
+                let hits: u64 = xml_attribute(tag, "hits")?.parse().ok()?;
+                Some((number, hits > 0))
+            })
+            .collect();
+        lines.sort_unstable();
+        lines.dedup_by(|next, kept| {
+            let same = next.0 == kept.0;
+            if same {
+                kept.1 |= next.1;
+            }
+            same
+        });
+        add_file(&mut files, FileCoverage {
+            path: path.to_string(),
+            lines_covered: lines.iter().filter(|(_, hit)| *hit).count() as u32,
+            lines_total: lines.len() as u32,
+        });
+    }
+    files
+}
+
+/// Value of `name="..."` in the first tag of `xml`
+fn xml_attribute<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let tag = &xml[..xml.find('>').unwrap_or(xml.len())];
+    let start = tag.find(&format!("{}=\"", name))? + name.len() + 2;
+    tag[start..].split('"').next()
+}
+
+/// Merge records of the same file (e.g. one per Cobertura class)
+fn add_file(files: &mut Vec<FileCoverage>, file: FileCoverage) {
+    match files.iter_mut().find(|f| f.path == file.path) {
+        Some(existing) => {
+            existing.lines_covered += file.lines_covered;
+            existing.lines_total += file.lines_total;
+        }
+        None => files.push(file),
+    }
+}
+
+fn coverage_summary_path(worktree: &Path) -> PathBuf {
+    worktree.join(COVERAGE_DIR).join(COVERAGE_SUMMARY_FILE)
+}
+
+/// Coverage of the worktree's previous coverage run
+pub fn load_last_coverage(worktree: &Path) -> Option<CoverageSummary> {
+    std::fs::read_to_string(coverage_summary_path(worktree))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+}
+
+pub fn save_last_coverage(worktree: &Path, summary: &CoverageSummary) -> Result<(), String> {
+    let path = coverage_summary_path(worktree);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(summary).map_err(|e| format!("Failed to serialize coverage: {}", e))?;
+    crate::spec_kit::write_atomic(&path, &json)
+}
+
+pub fn results_path(worktree: &Path, change_name: &str) -> PathBuf {
+    worktree.join(".rstn").join("changes").join(change_name).join(TEST_RESULTS_FILE)
+}
+
+/// Results of the last test run of a change (None if never run)
+pub fn load(worktree: &Path, change_name: &str) -> Option<TestResults> {
+    std::fs::read_to_string(results_path(worktree, change_name))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+}
+
+pub fn save(worktree: &Path, change_name: &str, results: &TestResults) -> Result<(), String> {
+    let path = results_path(worktree, change_name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let json = serde_json::to_string_pretty(results).map_err(|e| format!("Failed to serialize test results: {}", e))?;
+    crate::spec_kit::write_atomic(&path, &json)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_test_command() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(detect_test_command(dir.path()), None);
+
+        std::fs::write(dir.path().join("pyproject.toml"), "[tool.pytest.ini_options]\n").unwrap();
+        assert_eq!(detect_test_command(dir.path()).unwrap().display(), "pytest");
+
+        std::fs::write(dir.path().join("package.json"), r#"{"scripts":{"test":"vitest"}}"#).unwrap();
+        assert_eq!(detect_test_command(dir.path()).unwrap().display(), "npm test");
+
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        assert_eq!(detect_test_command(dir.path()).unwrap().display(), "cargo test");
+
+        std::fs::write(dir.path().join("justfile"), "# Run tests\ntest:\n    cargo test\n").unwrap();
+        assert_eq!(detect_test_command(dir.path()).unwrap().display(), "just test");
+    }
+
+    #[test]
+    fn test_write_test_failure_log() {
+        let dir = TempDir::new().unwrap();
+        let change_dir = dir.path().join(".rstn/changes/feature-x");
+        let command = TestCommand::new("cargo", &["test"]);
+
+        let path = write_test_failure_log(&change_dir, &command, "test foo ... FAILED").unwrap();
+        let content = std::fs::read_to_string(path).unwrap();
+        assert!(content.starts_with("$ cargo test"));
+        assert!(content.contains("FAILED"));
+    }
+
+    #[test]
+    fn test_parse_cargo_output() {
+        let output = "running 3 tests\ntest auth::login ... ok\ntest auth::logout ... FAILED\n\nfailures:\n    auth::logout\n\n\
+                      test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.01s\n\n\
+                      running 2 tests\ntest result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s\n";
+        let results = parse_output(output);
+        assert_eq!((results.passed, results.failed, results.ignored), (3, 1, 1));
+        assert_eq!(results.failing_tests, vec!["auth::logout"]);
+    }
+
+    #[test]
+    fn test_parse_pytest_and_js_output() {
+        let pytest = "FAILED tests/test_auth.py::test_login - AssertionError: no\n\
+                      ========= 1 failed, 4 passed, 2 skipped in 0.52s =========\n";
+        let results = parse_output(pytest);
+        assert_eq!((results.passed, results.failed, results.ignored), (4, 1, 2));
+        assert_eq!(results.failing_tests, vec!["tests/test_auth.py::test_login"]);
+
+        let jest = "  ● login › rejects bad password\n\nTests:       1 failed, 1 skipped, 5 passed, 7 total\nTime: 1.2 s\n";
+        let results = parse_output(jest);
+        assert_eq!((results.passed, results.failed, results.ignored), (5, 1, 1));
+        assert_eq!(results.failing_tests, vec!["login › rejects bad password"]);
+
+        let vitest = " FAIL  src/auth.test.ts > login > rejects\n      Tests  1 failed | 5 passed (6)\n";
+        let results = parse_output(vitest);
+        assert_eq!((results.passed, results.failed), (5, 1));
+        assert_eq!(results.failing_tests, vec!["src/auth.test.ts > login > rejects"]);
+    }
+
+    #[test]
+    fn test_detect_coverage_command() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(detect_coverage_command(dir.path()), None);
+
+        std::fs::write(dir.path().join("pytest.ini"), "[pytest]\n").unwrap();
+        let (command, report) = detect_coverage_command(dir.path()).unwrap();
+        assert_eq!(command.display(), "pytest --cov --cov-report=xml:.rstn/coverage/coverage.xml");
+        assert_eq!(report.format, CoverageFormat::Cobertura);
+
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        let (command, report) = detect_coverage_command(dir.path()).unwrap();
+        assert_eq!(command.display(), "cargo llvm-cov --lcov --output-path .rstn/coverage/lcov.info");
+        assert_eq!(report.format, CoverageFormat::Lcov);
+    }
+
+    #[test]
+    fn test_parse_coverage_reports() {
+        let lcov = "TN:\nSF:/repo/src/lib.rs\nDA:1,3\nDA:2,0\nLF:2\nLH:1\nend_of_record\n\
+                    SF:/repo/src/main.rs\nDA:1,1\nDA:2,1\nDA:3,0\nDA:4,2\nend_of_record\n";
+        let files = parse_lcov(lcov);
+        assert_eq!(files.len(), 2);
+        assert_eq!((files[0].lines_covered, files[0].lines_total), (1, 2));
+        assert_eq!((files[1].lines_covered, files[1].lines_total), (3, 4));
+
+        let xml = r#"<coverage><packages><package name="app"><classes>
+            <class name="auth.py" filename="app/auth.py" line-rate="0.5">
+                <methods><method name="login"><lines><line number="2" hits="0"/></lines></method></methods>
+                <lines><line number="1" hits="1"/><line number="2" hits="0"/></lines>
+            </class>
+            <class name="util.py" filename="app/util.py"><lines><line number="1" hits="4"/></lines></class>
+        </classes></package></packages></coverage>"#;
+        let files = parse_cobertura(xml);
+        assert_eq!(files[0], FileCoverage { path: "app/auth.py".to_string(), lines_covered: 1, lines_total: 2 });
+        assert_eq!(files[1].percent(), 100.0);
+    }
+
+    #[test]
+    fn test_coverage_summary_diff() {
+        let file = |path: &str, covered, total| FileCoverage { path: path.to_string(), lines_covered: covered, lines_total: total };
+        let previous = CoverageSummary::new(vec![file("a.rs", 8, 10), file("b.rs", 5, 10)], None);
+        assert_eq!(previous.percent, 65.0);
+        assert_eq!(previous.delta(), None);
+
+        let current = CoverageSummary::new(vec![file("a.rs", 6, 10), file("b.rs", 5, 10), file("c.rs", 10, 10)], Some(&previous));
+        assert_eq!(current.percent, 70.0);
+        assert_eq!(current.delta(), Some(5.0));
+        assert_eq!(current.files[0].path, "b.rs");
+        assert_eq!(current.regressions, vec![CoverageRegression { path: "a.rs".to_string(), before: 80.0, after: 60.0 }]);
+
+        let dir = TempDir::new().unwrap();
+        save_last_coverage(dir.path(), &current).unwrap();
+        assert_eq!(load_last_coverage(dir.path()), Some(current));
+    }
+
+    #[test]
+    fn test_approval_blocker() {
+        let command = TestCommand::new("cargo", &["test"]);
+        let run = TestRunResult {
+            success: false,
+            output: "test result: FAILED. 2 passed; 2 failed; 0 ignored".to_string(),
+            duration: Duration::from_millis(1500),
+        };
+        let mut results = TestResults::from_run(&command, &run);
+        assert_eq!(results.duration_ms, 1500);
+        assert_eq!(
+            results.approval_blocker().as_deref(),
+            Some("2 tests fail in `cargo test`; fix them or override the test gate")
+        );
+        results.overridden = true;
+        assert_eq!(results.approval_blocker(), None);
+
+        let dir = TempDir::new().unwrap();
+        save(dir.path(), "add-login", &results).unwrap();
+        assert_eq!(load(dir.path(), "add-login"), Some(results));
+    }
+}
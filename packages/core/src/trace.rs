@@ -0,0 +1,282 @@
+//! Action trace recorder (dev mode).
+//!
+//! While enabled, every reduced action is recorded with the hash of the
+//! resulting state and how long the reducer took, in a ring buffer of the
+//! last MAX_TRACE_ENTRIES. The trace can be exported as JSON and replayed
+//! against a fresh `AppState` to reproduce state bugs from user reports.
+//!
+//! Replayed hashes only match where the reducer is deterministic: actions
+//! that generate IDs or timestamps (e.g. OpenProject) diverge from there on,
+//! as do actions carrying secrets, which exports mask.
+
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use crate::actions::Action;
+use crate::app_state::AppState;
+
+/// Ring buffer size
+pub const MAX_TRACE_ENTRIES: usize = 1000;
+
+/// Trace file format version
+const TRACE_VERSION: u32 = 1;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static RECORDER: OnceLock<Mutex<TraceRecorder>> = OnceLock::new();
+
+thread_local! {
+    /// Set while replaying, so replayed actions are not recorded
+    static REPLAYING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// One reduced action
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceEntry {
+    /// Position in the recording (keeps counting when old entries are dropped)
+    pub seq: u64,
+    pub timestamp: String,
+    pub action: Action,
+    /// Hash of the state after the action
+    pub state_hash: String,
+    /// Time spent in the reducer (microseconds)
+    pub duration_us: u64,
+}
+
+/// Exported trace file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trace {
+    pub version: u32,
+    pub app_version: String,
+    pub exported_at: String,
+    pub entries: Vec<TraceEntry>,
+}
+
+/// Outcome of replaying a trace
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayReport {
+    pub replayed: usize,
+    /// Entries whose replayed state hash differs from the recorded one
+    pub mismatches: usize,
+    /// Seq of the first mismatching entry
+    pub first_mismatch: Option<u64>,
+    /// State after the last replayed action
+    pub state: AppState,
+}
+
+/// Replay outcome returned to JavaScript
+#[napi(object)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceReplayResult {
+    pub replayed: u32,
+    pub mismatches: u32,
+    pub first_mismatch: Option<i64>,
+    /// Replayed state as JSON
+    pub state: String,
+}
+
+impl From<ReplayReport> for TraceReplayResult {
+    fn from(report: ReplayReport) -> Self {
+        Self {
+            replayed: report.replayed as u32,
+            mismatches: report.mismatches as u32,
+            first_mismatch: report.first_mismatch.map(|seq| seq as i64),
+            state: serde_json::to_string(&report.state).unwrap_or_default(),
+        }
+    }
+}
+
+/// Ring buffer of the most recent trace entries
+#[derive(Debug, Default)]
+pub struct TraceRecorder {
+    entries: VecDeque<TraceEntry>,
+    next_seq: u64,
+}
+
+impl TraceRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, action: Action, state: &AppState, duration: Duration) {
+        self.entries.push_back(TraceEntry {
+            seq: self.next_seq,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            action,
+            state_hash: state_hash(state),
+            duration_us: duration.as_micros() as u64,
+        });
+        self.next_seq += 1;
+        if self.entries.len() > MAX_TRACE_ENTRIES {
+            self.entries.pop_front();
+        }
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &TraceEntry> {
+        self.entries.iter()
+    }
+
+    pub fn trace(&self) -> Trace {
+        Trace {
+            version: TRACE_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            entries: self.entries.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Hash of the serialized state (keys sorted, so HashMap order does not matter)
+pub fn state_hash(state: &AppState) -> String {
+    let value = serde_json::to_value(state).unwrap_or_default();
+    let mut hasher = Sha256::new();
+    hasher.update(value.to_string().as_bytes());
+    hex::encode(&hasher.finalize()[..8])
+}
+
+// ============================================================================
+// Global recorder
+// ============================================================================
+
+fn recorder() -> std::sync::MutexGuard<'static, TraceRecorder> {
+    RECORDER
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Start or stop recording (enabled in dev mode)
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the reducer should record the action it is about to apply
+pub fn is_recording() -> bool {
+    ENABLED.load(Ordering::Relaxed) && !REPLAYING.with(Cell::get)
+}
+
+/// Record a reduced action in the global ring buffer
+pub fn record(action: Action, state: &AppState, duration: Duration) {
+    recorder().record(action, state, duration);
+}
+
+/// Write the global ring buffer to `path` as JSON, with secrets masked by
+/// `diagnostics::redact`. Returns the entry count.
+pub fn export(path: &Path) -> Result<usize, String> {
+    let trace = recorder().trace();
+    let mut value = serde_json::to_value(&trace).map_err(|e| format!("Failed to serialize trace: {}", e))?;
+    crate::diagnostics::redact(&mut value);
+    let json = serde_json::to_string_pretty(&value).map_err(|e| format!("Failed to serialize trace: {}", e))?;
+    std::fs::write(path, json).map_err(|e| format!("Failed to write trace: {}", e))?;
+    Ok(trace.entries.len())
+}
+
+/// Read a trace file written by `export`
+pub fn load(path: &Path) -> Result<Trace, String> {
+    let json = std::fs::read_to_string(path).map_err(|e| format!("Failed to read trace: {}", e))?;
+    serde_json::from_str(&json).map_err(|e| format!("Invalid trace file: {}", e))
+}
+
+/// Replay the actions of a trace against a fresh `AppState`
+pub fn replay(trace: &Trace) -> ReplayReport {
+    REPLAYING.with(|r| r.set(true));
+    let mut state = AppState::default();
+    let mut mismatches = 0;
+    let mut first_mismatch = None;
+    for entry in &trace.entries {
+        crate::reducer::reduce(&mut state, entry.action.clone());
+        if state_hash(&state) != entry.state_hash {
+            mismatches += 1;
+            first_mismatch.get_or_insert(entry.seq);
+        }
+    }
+    REPLAYING.with(|r| r.set(false));
+
+    ReplayReport {
+        replayed: trace.entries.len(),
+        mismatches,
+        first_mismatch,
+        state,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::Theme;
+    use crate::reducer::reduce;
+
+    fn record(recorder: &mut TraceRecorder, state: &mut AppState, action: Action) {
+        reduce(state, action.clone());
+        recorder.record(action, state, Duration::from_micros(5));
+    }
+
+    #[test]
+    fn test_ring_buffer_keeps_latest_entries() {
+        let mut recorder = TraceRecorder::new();
+        let mut state = AppState::default();
+        for _ in 0..MAX_TRACE_ENTRIES + 5 {
+            record(&mut recorder, &mut state, Action::ClearNotifications);
+        }
+        let seqs: Vec<u64> = recorder.entries().map(|e| e.seq).collect();
+        assert_eq!(seqs.len(), MAX_TRACE_ENTRIES);
+        assert_eq!(seqs[0], 5);
+        assert_eq!(recorder.entries().next().unwrap().duration_us, 5);
+    }
+
+    #[test]
+    fn test_export_and_replay_round_trip() {
+        let mut recorder = TraceRecorder::new();
+        let mut state = AppState::default();
+        record(&mut recorder, &mut state, Action::SetTheme { theme: Theme::Dark });
+        record(&mut recorder, &mut state, Action::ClearNotifications);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trace.json");
+        std::fs::write(&path, serde_json::to_string(&recorder.trace()).unwrap()).unwrap();
+
+        let report = replay(&load(&path).unwrap());
+        assert_eq!(report.replayed, 2);
+        assert_eq!(report.mismatches, 0);
+        assert_eq!(report.first_mismatch, None);
+        assert_eq!(report.state.global_settings.theme, Theme::Dark);
+        assert_eq!(state_hash(&report.state), state_hash(&state));
+
+        // A recorded hash that replay cannot reproduce is reported
+        let mut trace = recorder.trace();
+        trace.entries[1].state_hash = "0000".to_string();
+        let report = replay(&trace);
+        assert_eq!((report.mismatches, report.first_mismatch), (1, Some(1)));
+    }
+
+    #[test]
+    fn test_export_masks_secrets() {
+        let mut state = AppState::default();
+        let action = Action::SetOpenAiSettings {
+            settings: crate::app_state::OpenAiSettings {
+                base_url: "https://api.openai.com/v1".to_string(),
+                api_key: Some("sk-live-123".to_string()),
+                model: "gpt-4o".to_string(),
+            },
+        };
+        reduce(&mut state, action.clone());
+        super::record(action, &state, Duration::from_micros(5));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("trace.json");
+        export(&path).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert!(written.contains("gpt-4o"));
+        assert!(!written.contains("sk-live-123"));
+    }
+}
@@ -0,0 +1,283 @@
+//! Undo/redo for user edits.
+//!
+//! Undoable actions snapshot the slice of state they touch before and after
+//! the reducer runs; undo writes the "before" snapshot back, redo the "after"
+//! one. Slices are addressed by project/worktree ID, so an edit is undone in
+//! the project it was made in even after switching projects.
+//!
+//! File comments live in the database: their entries are recorded by the
+//! async handlers and the database is updated when they are undone/redone.
+
+use serde::{Deserialize, Serialize};
+
+use crate::actions::{Action, CommentData};
+use crate::app_state::{AgentProfile, AppState, ChangeStatus, ConstitutionPreset, ProjectState, WorktreeState};
+
+/// Number of undo steps kept
+pub const MAX_UNDO_HISTORY: usize = 50;
+
+/// A snapshot of one undoable slice of state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UndoSlice {
+    EnvTrackedPatterns {
+        project_id: String,
+        patterns: Vec<String>,
+    },
+    AgentProfiles {
+        project_id: String,
+        profiles: Vec<AgentProfile>,
+        active_profile_id: Option<String>,
+    },
+    ConstitutionPresets {
+        project_id: String,
+        worktree_id: String,
+        presets: Vec<ConstitutionPreset>,
+        active_preset_id: Option<String>,
+    },
+    ChangeStatus {
+        project_id: String,
+        worktree_id: String,
+        change_id: String,
+        status: ChangeStatus,
+        updated_at: String,
+    },
+    /// A file comment in the database (`comment` None = deleted)
+    FileComment {
+        project_path: String,
+        file_path: String,
+        comment_id: String,
+        comment: Option<CommentData>,
+    },
+}
+
+/// One undoable edit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UndoEntry {
+    /// What the edit did (e.g. "Delete agent profile")
+    pub label: String,
+    pub before: UndoSlice,
+    pub after: UndoSlice,
+}
+
+/// Bounded undo and redo stacks (most recent last)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UndoHistory {
+    pub undo: Vec<UndoEntry>,
+    pub redo: Vec<UndoEntry>,
+    /// Slice restored by the last undo/redo that is also stored outside the
+    /// state (database, status.json), for the async handler
+    #[serde(skip)]
+    pub pending: Option<UndoSlice>,
+}
+
+impl UndoHistory {
+    /// Record a new edit. Clears the redo stack.
+    pub fn record(&mut self, entry: UndoEntry) {
+        self.undo.push(entry);
+        if self.undo.len() > MAX_UNDO_HISTORY {
+            let excess = self.undo.len() - MAX_UNDO_HISTORY;
+            self.undo.drain(..excess);
+        }
+        self.redo.clear();
+    }
+}
+
+/// Snapshot taken before an undoable action is reduced
+pub struct Checkpoint {
+    label: &'static str,
+    before: UndoSlice,
+}
+
+/// Snapshot the slice `action` is about to change (None if not undoable)
+pub fn checkpoint(state: &AppState, action: &Action) -> Option<Checkpoint> {
+    let project = state.active_project()?;
+    let (label, before) = match action {
+        Action::SetEnvTrackedPatterns { .. } => ("Edit tracked env patterns", env_patterns(project)),
+        Action::CreateAgentProfile { .. } => ("Create agent profile", agent_profiles(project)),
+        Action::ImportAgentProfile { .. } => ("Import agent profile", agent_profiles(project)),
+        Action::UpdateAgentProfile { .. } => ("Edit agent profile", agent_profiles(project)),
+        Action::DeleteAgentProfile { .. } => ("Delete agent profile", agent_profiles(project)),
+        Action::CreateConstitutionPreset { .. } => {
+            ("Create constitution preset", constitution_presets(project, project.active_worktree()?))
+        }
+        Action::UpdateConstitutionPreset { .. } => {
+            ("Edit constitution preset", constitution_presets(project, project.active_worktree()?))
+        }
+        Action::DeleteConstitutionPreset { .. } => {
+            ("Delete constitution preset", constitution_presets(project, project.active_worktree()?))
+        }
+        Action::CancelChange { change_id } => {
+            ("Cancel change", change_status(project, project.active_worktree()?, change_id)?)
+        }
+        _ => return None,
+    };
+    Some(Checkpoint { label, before })
+}
+
+/// Record the edit if the action changed the slice
+pub fn commit(state: &mut AppState, checkpoint: Checkpoint) {
+    let Some(after) = current(state, &checkpoint.before) else {
+        return;
+    };
+    if after != checkpoint.before {
+        state.undo.record(UndoEntry {
+            label: checkpoint.label.to_string(),
+            before: checkpoint.before,
+            after,
+        });
+    }
+}
+
+/// Write a snapshot back into state.
+/// Returns false if its project/worktree/change no longer exists.
+/// File comments are restored by the async handler and always succeed here.
+pub fn apply(state: &mut AppState, slice: &UndoSlice) -> bool {
+    match slice {
+        UndoSlice::EnvTrackedPatterns { project_id, patterns } => {
+            let Some(project) = project_mut(state, project_id) else {
+                return false;
+            };
+            project.env_config.tracked_patterns = patterns.clone();
+        }
+        UndoSlice::AgentProfiles { project_id, profiles, active_profile_id } => {
+            let Some(project) = project_mut(state, project_id) else {
+                return false;
+            };
+            project.agent_rules_config.profiles = profiles.clone();
+            project.agent_rules_config.active_profile_id = active_profile_id.clone();
+        }
+        UndoSlice::ConstitutionPresets { project_id, worktree_id, presets, active_preset_id } => {
+            let Some(worktree) = worktree_mut(state, project_id, worktree_id) else {
+                return false;
+            };
+            worktree.tasks.constitution_presets.presets = presets.clone();
+            worktree.tasks.constitution_presets.active_preset_id = active_preset_id.clone();
+        }
+        UndoSlice::ChangeStatus { project_id, worktree_id, change_id, status, updated_at } => {
+            let Some(change) = worktree_mut(state, project_id, worktree_id)
+                .and_then(|w| w.changes.changes.iter_mut().find(|c| &c.id == change_id))
+            else {
+                return false;
+            };
+            change.status = *status;
+            change.updated_at = updated_at.clone();
+        }
+        UndoSlice::FileComment { .. } => {}
+    }
+    true
+}
+
+/// Re-read the slice a snapshot was taken from
+fn current(state: &AppState, slice: &UndoSlice) -> Option<UndoSlice> {
+    match slice {
+        UndoSlice::EnvTrackedPatterns { project_id, .. } => Some(env_patterns(project(state, project_id)?)),
+        UndoSlice::AgentProfiles { project_id, .. } => Some(agent_profiles(project(state, project_id)?)),
+        UndoSlice::ConstitutionPresets { project_id, worktree_id, .. } => {
+            let project = project(state, project_id)?;
+            let worktree = project.worktrees.iter().find(|w| &w.id == worktree_id)?;
+            Some(constitution_presets(project, worktree))
+        }
+        UndoSlice::ChangeStatus { project_id, worktree_id, change_id, .. } => {
+            let project = project(state, project_id)?;
+            let worktree = project.worktrees.iter().find(|w| &w.id == worktree_id)?;
+            change_status(project, worktree, change_id)
+        }
+        UndoSlice::FileComment { .. } => Some(slice.clone()),
+    }
+}
+
+fn env_patterns(project: &ProjectState) -> UndoSlice {
+    UndoSlice::EnvTrackedPatterns {
+        project_id: project.id.clone(),
+        patterns: project.env_config.tracked_patterns.clone(),
+    }
+}
+
+fn agent_profiles(project: &ProjectState) -> UndoSlice {
+    UndoSlice::AgentProfiles {
+        project_id: project.id.clone(),
+        profiles: project.agent_rules_config.profiles.clone(),
+        active_profile_id: project.agent_rules_config.active_profile_id.clone(),
+    }
+}
+
+fn constitution_presets(project: &ProjectState, worktree: &WorktreeState) -> UndoSlice {
+    UndoSlice::ConstitutionPresets {
+        project_id: project.id.clone(),
+        worktree_id: worktree.id.clone(),
+        presets: worktree.tasks.constitution_presets.presets.clone(),
+        active_preset_id: worktree.tasks.constitution_presets.active_preset_id.clone(),
+    }
+}
+
+fn change_status(project: &ProjectState, worktree: &WorktreeState, change_id: &str) -> Option<UndoSlice> {
+    let change = worktree.changes.changes.iter().find(|c| c.id == change_id)?;
+    Some(UndoSlice::ChangeStatus {
+        project_id: project.id.clone(),
+        worktree_id: worktree.id.clone(),
+        change_id: change.id.clone(),
+        status: change.status,
+        updated_at: change.updated_at.clone(),
+    })
+}
+
+fn project<'a>(state: &'a AppState, project_id: &str) -> Option<&'a ProjectState> {
+    state.projects.iter().find(|p| p.id == project_id)
+}
+
+fn project_mut<'a>(state: &'a mut AppState, project_id: &str) -> Option<&'a mut ProjectState> {
+    state.projects.iter_mut().find(|p| p.id == project_id)
+}
+
+fn worktree_mut<'a>(state: &'a mut AppState, project_id: &str, worktree_id: &str) -> Option<&'a mut WorktreeState> {
+    project_mut(state, project_id)?
+        .worktrees
+        .iter_mut()
+        .find(|w| w.id == worktree_id)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(label: &str) -> UndoEntry {
+        let slice = UndoSlice::EnvTrackedPatterns {
+            project_id: "p".to_string(),
+            patterns: vec![],
+        };
+        UndoEntry {
+            label: label.to_string(),
+            before: slice.clone(),
+            after: slice,
+        }
+    }
+
+    #[test]
+    fn test_history_is_bounded_and_record_clears_redo() {
+        let mut history = UndoHistory::default();
+        for i in 0..MAX_UNDO_HISTORY + 3 {
+            history.record(entry(&i.to_string()));
+        }
+        assert_eq!(history.undo.len(), MAX_UNDO_HISTORY);
+        assert_eq!(history.undo[0].label, "3");
+
+        history.redo.push(entry("redo"));
+        history.record(entry("new"));
+        assert!(history.redo.is_empty());
+    }
+
+    #[test]
+    fn test_checkpoint_ignores_other_actions() {
+        let mut state = AppState::default();
+        crate::reducer::reduce(&mut state, Action::OpenProject { path: "/test/project".to_string() });
+        assert!(checkpoint(&state, &Action::SetEnvAutoCopy { enabled: true }).is_none());
+        assert!(checkpoint(&state, &Action::SetEnvTrackedPatterns { patterns: vec![] }).is_some());
+        // Unknown change: nothing to undo
+        assert!(checkpoint(&state, &Action::CancelChange { change_id: "missing".to_string() }).is_none());
+    }
+}
@@ -0,0 +1,165 @@
+//! Claude CLI usage and cost tracking.
+//!
+//! Every Claude CLI run ends with a `result` event carrying token usage and
+//! (usually) the total cost. Each run becomes a `UsageRecord` that is
+//! accumulated in `AppState.usage` and stored in SQLite for summaries over
+//! a period (`usage_summary`).
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Token usage and cost of one Claude CLI run
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UsageRecord {
+    /// Project ID (path hash, as used by the database)
+    pub project_id: String,
+    /// Claude CLI session ID
+    pub session_id: Option<String>,
+    /// What triggered the run (e.g. "chat", "proposal", "plan")
+    pub source: String,
+    /// Model requested via `--model` (None = CLI default)
+    pub model: Option<String>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    /// Cost in USD (reported by the CLI, or estimated)
+    pub cost_usd: f64,
+    /// When the run finished (ISO 8601)
+    pub timestamp: String,
+}
+
+/// Accumulated usage
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UsageTotals {
+    pub request_count: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub cost_usd: f64,
+}
+
+impl UsageTotals {
+    /// Add a record to the totals
+    pub fn add(&mut self, record: &UsageRecord) {
+        self.request_count += 1;
+        self.input_tokens += record.input_tokens;
+        self.output_tokens += record.output_tokens;
+        self.cache_read_tokens += record.cache_read_tokens;
+        self.cache_creation_tokens += record.cache_creation_tokens;
+        self.cost_usd += record.cost_usd;
+    }
+}
+
+/// Time window for usage summaries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsagePeriod {
+    Day,
+    Week,
+    Month,
+    All,
+}
+
+impl UsagePeriod {
+    /// Parse "day" / "week" / "month" / "all"
+    pub fn parse(period: &str) -> Result<Self, String> {
+        match period.to_ascii_lowercase().as_str() {
+            "day" => Ok(Self::Day),
+            "week" => Ok(Self::Week),
+            "month" => Ok(Self::Month),
+            "all" => Ok(Self::All),
+            other => Err(format!("Unknown usage period: {} (expected day, week, month or all)", other)),
+        }
+    }
+
+    /// Start of the window ending at `now` (None = unbounded)
+    pub fn since(self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Day => Some(now - Duration::days(1)),
+            Self::Week => Some(now - Duration::days(7)),
+            Self::Month => Some(now - Duration::days(30)),
+            Self::All => None,
+        }
+    }
+}
+
+/// (input, output) USD per million tokens by model family
+fn model_pricing(model: Option<&str>) -> (f64, f64) {
+    let model = model.unwrap_or("").to_ascii_lowercase();
+    if model.contains("opus") {
+        (15.0, 75.0)
+    } else if model.contains("haiku") {
+        (0.8, 4.0)
+    } else {
+        // Sonnet pricing (also the CLI default)
+        (3.0, 15.0)
+    }
+}
+
+/// Estimate cost when the CLI does not report one.
+///
+/// Cache reads are billed at 10% and cache writes at 125% of the input rate.
+pub fn estimate_cost_usd(
+    model: Option<&str>,
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_read_tokens: u64,
+    cache_creation_tokens: u64,
+) -> f64 {
+    let (input_rate, output_rate) = model_pricing(model);
+    let input = input_tokens as f64 + cache_read_tokens as f64 * 0.1 + cache_creation_tokens as f64 * 1.25;
+    (input * input_rate + output_tokens as f64 * output_rate) / 1_000_000.0
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(input_tokens: u64, output_tokens: u64, cost_usd: f64) -> UsageRecord {
+        UsageRecord {
+            project_id: "abcd1234".to_string(),
+            session_id: Some("session-1".to_string()),
+            source: "chat".to_string(),
+            model: None,
+            input_tokens,
+            output_tokens,
+            cache_read_tokens: 0,
+            cache_creation_tokens: 0,
+            cost_usd,
+            timestamp: Utc::now().to_rfc3339(),
+        }
+    }
+
+    #[test]
+    fn test_usage_totals_add() {
+        let mut totals = UsageTotals::default();
+        totals.add(&record(100, 20, 0.5));
+        totals.add(&record(50, 10, 0.25));
+        assert_eq!(totals.request_count, 2);
+        assert_eq!(totals.input_tokens, 150);
+        assert_eq!(totals.output_tokens, 30);
+        assert!((totals.cost_usd - 0.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_usage_period() {
+        let now = Utc::now();
+        assert_eq!(UsagePeriod::parse("Week").unwrap(), UsagePeriod::Week);
+        assert!(UsagePeriod::parse("year").is_err());
+        assert_eq!(UsagePeriod::Day.since(now), Some(now - Duration::days(1)));
+        assert_eq!(UsagePeriod::All.since(now), None);
+    }
+
+    #[test]
+    fn test_estimate_cost_usd() {
+        // 1M input + 1M output on the default (sonnet) pricing
+        assert!((estimate_cost_usd(None, 1_000_000, 1_000_000, 0, 0) - 18.0).abs() < 1e-9);
+        assert!((estimate_cost_usd(Some("claude-opus-4"), 1_000_000, 0, 0, 0) - 15.0).abs() < 1e-9);
+        assert!((estimate_cost_usd(Some("haiku"), 0, 0, 1_000_000, 0) - 0.08).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,260 @@
+//! File system watchers that keep state in sync with external edits.
+//!
+//! One watcher runs per open worktree. Changed paths are reported relative to
+//! the worktree root; `classify` maps them to what needs refreshing (changes,
+//! tasks, Docker services, env files, constitution, schedule, build check).
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tokio::sync::mpsc;
+
+/// A changed path inside a watched worktree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchEvent {
+    pub worktree_path: String,
+    /// Path relative to the worktree root ('/'-separated)
+    pub relative_path: String,
+}
+
+/// What a changed file affects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum WatchTarget {
+    /// `.rstn/changes/`
+    Changes,
+    /// justfile, package.json, Makefile, cargo config
+    Tasks,
+    /// docker-compose / compose files
+    DockerCompose,
+    /// Tracked env files
+    EnvFiles,
+    /// `.rstn/constitutions/`, `.rstn/constitution.md`, CLAUDE.md
+    Constitution,
+    /// `.rstn/schedule.toml`
+    Schedule,
+    /// `.rstn/requests/`
+    HttpRequests,
+    /// Rust / TypeScript sources and their manifests (build check)
+    Sources,
+}
+
+/// Files whose tasks are listed in the Tasks view
+const TASK_FILES: &[&str] = &[
+    "justfile",
+    "Justfile",
+    ".justfile",
+    "package.json",
+    "Makefile",
+    "makefile",
+    "GNUmakefile",
+    ".cargo/config.toml",
+    ".cargo/config",
+];
+
+/// Extensions of files covered by the build check
+const SOURCE_EXTENSIONS: &[&str] = &["rs", "ts", "tsx", "mts", "cts"];
+
+/// Manifests that change what the build check compiles
+const BUILD_MANIFESTS: &[&str] = &["Cargo.toml", "Cargo.lock", "tsconfig.json"];
+
+/// Dependency and build output directories (writes there must not
+/// re-trigger the check)
+const IGNORED_SOURCE_DIRS: &[&str] = &["target", "node_modules", "dist", "out", "build"];
+
+const COMPOSE_FILES: &[&str] = &[
+    "docker-compose.yml",
+    "docker-compose.yaml",
+    "compose.yml",
+    "compose.yaml",
+];
+
+/// Map a worktree-relative path to the state it affects (None = irrelevant)
+pub fn classify(relative_path: &str, env_patterns: &[String]) -> Option<WatchTarget> {
+    if relative_path.starts_with(".git/") || relative_path.starts_with("node_modules/") {
+        return None;
+    }
+    if relative_path.starts_with(".rstn/changes/") || relative_path == ".rstn/changes" {
+        return Some(WatchTarget::Changes);
+    }
+    if relative_path.starts_with(".rstn/constitutions/")
+        || relative_path == ".rstn/constitution.md"
+        || relative_path == "CLAUDE.md"
+    {
+        return Some(WatchTarget::Constitution);
+    }
+    if relative_path == ".rstn/schedule.toml" {
+        return Some(WatchTarget::Schedule);
+    }
+    if relative_path.starts_with(".rstn/requests/") || relative_path == ".rstn/requests" {
+        return Some(WatchTarget::HttpRequests);
+    }
+    if TASK_FILES.contains(&relative_path) {
+        return Some(WatchTarget::Tasks);
+    }
+    if COMPOSE_FILES.contains(&relative_path) {
+        return Some(WatchTarget::DockerCompose);
+    }
+    if crate::env_secrets::is_tracked_env_file(relative_path, env_patterns) {
+        return Some(WatchTarget::EnvFiles);
+    }
+    if is_build_source(relative_path) {
+        return Some(WatchTarget::Sources);
+    }
+    None
+}
+
+fn is_build_source(relative_path: &str) -> bool {
+    let mut components = relative_path.split('/').rev();
+    let file_name = components.next().unwrap_or(relative_path);
+    if components.any(|dir| dir.starts_with('.') || IGNORED_SOURCE_DIRS.contains(&dir)) {
+        return false;
+    }
+    BUILD_MANIFESTS.contains(&file_name)
+        || Path::new(file_name)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext))
+}
+
+/// Owns one watcher per worktree; dropping a watcher stops it.
+#[derive(Default)]
+pub struct WorktreeWatcher {
+    watchers: Mutex<HashMap<PathBuf, RecommendedWatcher>>,
+}
+
+impl WorktreeWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, RecommendedWatcher>> {
+        self.watchers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Start watching a worktree (no-op if already watched).
+    /// Returns false if the watcher could not be started.
+    pub fn watch(&self, root: &Path, events: mpsc::UnboundedSender<WatchEvent>) -> bool {
+        let mut watchers = self.lock();
+        if watchers.contains_key(root) {
+            return true;
+        }
+        match watch(root, events) {
+            Ok(watcher) => {
+                watchers.insert(root.to_path_buf(), watcher);
+                true
+            }
+            Err(e) => {
+                tracing::warn!("Worktree {} is not watched: {}", root.display(), e);
+                false
+            }
+        }
+    }
+
+    pub fn is_watching(&self, root: &Path) -> bool {
+        self.lock().contains_key(root)
+    }
+
+    /// Stop watchers for worktrees not in `live_roots`. Returns the number stopped.
+    pub fn retain(&self, live_roots: &[String]) -> usize {
+        let mut watchers = self.lock();
+        let before = watchers.len();
+        watchers.retain(|root, _| live_roots.iter().any(|live| Path::new(live) == root));
+        before - watchers.len()
+    }
+}
+
+/// Watch `root` recursively, forwarding changed paths relative to it
+fn watch(root: &Path, events: mpsc::UnboundedSender<WatchEvent>) -> notify::Result<RecommendedWatcher> {
+    let worktree_path = root.to_string_lossy().to_string();
+    // Some backends report canonical paths (e.g. /private/var on macOS)
+    let roots = [root.to_path_buf(), root.canonicalize().unwrap_or_else(|_| root.to_path_buf())];
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let Ok(event) = res else {
+            return;
+        };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        for path in &event.paths {
+            let Some(relative) = roots.iter().find_map(|root| path.strip_prefix(root).ok()) else {
+                continue;
+            };
+            let relative_path = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let _ = events.send(WatchEvent {
+                worktree_path: worktree_path.clone(),
+                relative_path,
+            });
+        }
+    })?;
+    watcher.watch(root, RecursiveMode::Recursive)?;
+    Ok(watcher)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let patterns = vec![".env".to_string(), ".claude/".to_string()];
+        let cases = [
+            (".rstn/changes/add-auth/proposal.md", Some(WatchTarget::Changes)),
+            (".rstn/constitutions/rust.md", Some(WatchTarget::Constitution)),
+            ("CLAUDE.md", Some(WatchTarget::Constitution)),
+            (".rstn/schedule.toml", Some(WatchTarget::Schedule)),
+            (".rstn/requests/api.toml", Some(WatchTarget::HttpRequests)),
+            ("justfile", Some(WatchTarget::Tasks)),
+            ("package.json", Some(WatchTarget::Tasks)),
+            (".cargo/config.toml", Some(WatchTarget::Tasks)),
+            ("docker-compose.yml", Some(WatchTarget::DockerCompose)),
+            (".env", Some(WatchTarget::EnvFiles)),
+            (".claude/settings.json", Some(WatchTarget::EnvFiles)),
+            ("src/main.rs", Some(WatchTarget::Sources)),
+            ("crates/api/Cargo.toml", Some(WatchTarget::Sources)),
+            ("web/src/App.tsx", Some(WatchTarget::Sources)),
+            ("target/debug/build/out.rs", None),
+            ("web/node_modules/react/index.d.ts", None),
+            ("README.md", None),
+            ("apps/web/package.json", None),
+            (".git/index", None),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(classify(path, &patterns), expected, "{}", path);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_reports_relative_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let watcher = WorktreeWatcher::new();
+        assert!(watcher.watch(dir.path(), tx));
+        assert!(watcher.is_watching(dir.path()));
+
+        std::fs::write(dir.path().join("justfile"), "build:\n    cargo build\n").unwrap();
+        let event = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                let event = rx.recv().await.unwrap();
+                if event.relative_path == "justfile" {
+                    return event;
+                }
+            }
+        })
+        .await
+        .expect("no watch event for justfile");
+        assert_eq!(event.worktree_path, dir.path().to_string_lossy());
+
+        assert_eq!(watcher.retain(&[]), 1);
+        assert!(!watcher.is_watching(dir.path()));
+    }
+}
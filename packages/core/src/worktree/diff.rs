@@ -0,0 +1,349 @@
+//! Branch diff between worktrees.
+//!
+//! Runs `git diff base...head` (changes on `head` since it diverged from
+//! `base`) and parses the unified diff into per-file hunks so the UI can
+//! show what a feature worktree changes before merge.
+
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+/// Structured diff between two refs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorktreeDiff {
+    pub base: String,
+    pub head: String,
+    pub files: Vec<DiffFile>,
+}
+
+/// Change status of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffFileStatus {
+    Added,
+    Modified,
+    Deleted,
+    Renamed,
+}
+
+/// A single changed file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffFile {
+    /// Path on the head side (old path for deleted files)
+    pub path: String,
+    /// Previous path for renamed files
+    pub old_path: Option<String>,
+    pub status: DiffFileStatus,
+    pub is_binary: bool,
+    pub additions: u32,
+    pub deletions: u32,
+    pub hunks: Vec<DiffHunk>,
+}
+
+/// A hunk (`@@ -a,b +c,d @@`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffHunk {
+    /// Full header line
+    pub header: String,
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    pub lines: Vec<DiffLine>,
+}
+
+/// Line type within a hunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffLineType {
+    Context,
+    Added,
+    Removed,
+}
+
+/// A single line within a hunk
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub line_type: DiffLineType,
+    /// Line content without the +/-/space prefix
+    pub content: String,
+    /// Line number in the base file (None for added lines)
+    pub old_line: Option<u32>,
+    /// Line number in the head file (None for removed lines)
+    pub new_line: Option<u32>,
+}
+
+/// Compute the diff of `head` relative to its merge base with `base`.
+pub fn diff_branches(repo_path: &str, base: &str, head: &str) -> Result<WorktreeDiff, String> {
+    verify_ref(repo_path, base)?;
+    verify_ref(repo_path, head)?;
+    let range = format!("{}...{}", base, head);
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["diff", "--no-color", "--no-ext-diff", "-M", &range, "--"])
+        .output()
+        .map_err(|e| format!("Failed to run git diff: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git diff failed: {}", stderr.trim()));
+    }
+
+    Ok(WorktreeDiff {
+        base: base.to_string(),
+        head: head.to_string(),
+        files: parse_unified_diff(&String::from_utf8_lossy(&output.stdout)),
+    })
+}
+
+/// Check that `name` is a commit-ish git won't read as an option or a range
+fn verify_ref(repo_path: &str, name: &str) -> Result<(), String> {
+    if name.is_empty() || name.starts_with('-') || name.contains("..") {
+        return Err(format!("Invalid ref: {}", name));
+    }
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo_path)
+        .args(["rev-parse", "--verify", "--quiet"])
+        .arg(format!("{}^{{commit}}", name))
+        .output()
+        .map_err(|e| format!("Failed to run git rev-parse: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("Unknown ref: {}", name))
+    }
+}
+
+/// Parse `@@ -a,b +c,d @@` into (old_start, old_lines, new_start, new_lines)
+pub(crate) fn parse_hunk_header(line: &str) -> Option<(u32, u32, u32, u32)> {
+    let inner = line.strip_prefix("@@ ")?;
+    let ranges = &inner[..inner.find(" @@")?];
+    let (old, new) = ranges.split_once(' ')?;
+
+    fn parse_range(range: &str) -> Option<(u32, u32)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    }
+
+    let (old_start, old_lines) = parse_range(old.strip_prefix('-')?)?;
+    let (new_start, new_lines) = parse_range(new.strip_prefix('+')?)?;
+    Some((old_start, old_lines, new_start, new_lines))
+}
+
+/// Parse `git diff` output into files and hunks.
+pub fn parse_unified_diff(output: &str) -> Vec<DiffFile> {
+    let mut files: Vec<DiffFile> = Vec::new();
+    // True between `diff --git` and the first hunk (file header lines)
+    let mut in_header = false;
+    let mut old_line = 0u32;
+    let mut new_line = 0u32;
+
+    for line in output.lines() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            // "a/<old> b/<new>" - take the b/ side
+            let path = rest
+                .rsplit_once(" b/")
+                .map(|(_, p)| p.to_string())
+                .unwrap_or_else(|| rest.to_string());
+            files.push(DiffFile {
+                path,
+                old_path: None,
+                status: DiffFileStatus::Modified,
+                is_binary: false,
+                additions: 0,
+                deletions: 0,
+                hunks: Vec::new(),
+            });
+            in_header = true;
+            continue;
+        }
+
+        let Some(file) = files.last_mut() else {
+            continue;
+        };
+
+        if in_header {
+            if line.starts_with("new file mode") {
+                file.status = DiffFileStatus::Added;
+            } else if line.starts_with("deleted file mode") {
+                file.status = DiffFileStatus::Deleted;
+            } else if let Some(from) = line.strip_prefix("rename from ") {
+                file.status = DiffFileStatus::Renamed;
+                file.old_path = Some(from.to_string());
+            } else if let Some(to) = line.strip_prefix("rename to ") {
+                file.path = to.to_string();
+            } else if line.starts_with("Binary files ") {
+                file.is_binary = true;
+            } else if let Some(path) = line.strip_prefix("+++ b/") {
+                file.path = path.to_string();
+            } else if let Some((old_start, old_lines, new_start, new_lines)) = parse_hunk_header(line) {
+                in_header = false;
+                old_line = old_start;
+                new_line = new_start;
+                file.hunks.push(DiffHunk {
+                    header: line.to_string(),
+                    old_start,
+                    old_lines,
+                    new_start,
+                    new_lines,
+                    lines: Vec::new(),
+                });
+            }
+            continue;
+        }
+
+        if let Some((old_start, old_lines, new_start, new_lines)) = parse_hunk_header(line) {
+            old_line = old_start;
+            new_line = new_start;
+            file.hunks.push(DiffHunk {
+                header: line.to_string(),
+                old_start,
+                old_lines,
+                new_start,
+                new_lines,
+                lines: Vec::new(),
+            });
+            continue;
+        }
+
+        let Some(hunk) = file.hunks.last_mut() else {
+            continue;
+        };
+
+        let diff_line = if let Some(content) = line.strip_prefix('+') {
+            file.additions += 1;
+            new_line += 1;
+            DiffLine {
+                line_type: DiffLineType::Added,
+                content: content.to_string(),
+                old_line: None,
+                new_line: Some(new_line - 1),
+            }
+        } else if let Some(content) = line.strip_prefix('-') {
+            file.deletions += 1;
+            old_line += 1;
+            DiffLine {
+                line_type: DiffLineType::Removed,
+                content: content.to_string(),
+                old_line: Some(old_line - 1),
+                new_line: None,
+            }
+        } else if let Some(content) = line.strip_prefix(' ') {
+            old_line += 1;
+            new_line += 1;
+            DiffLine {
+                line_type: DiffLineType::Context,
+                content: content.to_string(),
+                old_line: Some(old_line - 1),
+                new_line: Some(new_line - 1),
+            }
+        } else {
+            // "\ No newline at end of file" and anything unexpected
+            continue;
+        };
+        hunk.lines.push(diff_line);
+    }
+
+    files
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@ mod foo;
+ use std::fs;
+--- old comment
++--- new comment
+ fn main() {}
+diff --git a/README.md b/README.md
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/README.md
+@@ -0,0 +1 @@
++# Title
+\\ No newline at end of file
+diff --git a/old.txt b/new.txt
+similarity index 100%
+rename from old.txt
+rename to new.txt
+diff --git a/logo.png b/logo.png
+deleted file mode 100644
+index 4444444..0000000
+Binary files a/logo.png and /dev/null differ
+";
+
+    #[test]
+    fn test_parse_hunk_header() {
+        assert_eq!(parse_hunk_header("@@ -1,3 +1,4 @@ fn main"), Some((1, 3, 1, 4)));
+        assert_eq!(parse_hunk_header("@@ -0,0 +1 @@"), Some((0, 0, 1, 1)));
+        assert_eq!(parse_hunk_header("not a hunk"), None);
+    }
+
+    #[test]
+    fn test_parse_unified_diff_files() {
+        let files = parse_unified_diff(SAMPLE);
+        assert_eq!(files.len(), 4);
+
+        assert_eq!(files[0].path, "src/lib.rs");
+        assert_eq!(files[0].status, DiffFileStatus::Modified);
+        assert_eq!((files[0].additions, files[0].deletions), (1, 1));
+
+        assert_eq!(files[1].path, "README.md");
+        assert_eq!(files[1].status, DiffFileStatus::Added);
+        assert_eq!(files[1].hunks[0].lines.len(), 1);
+
+        assert_eq!(files[2].status, DiffFileStatus::Renamed);
+        assert_eq!(files[2].path, "new.txt");
+        assert_eq!(files[2].old_path.as_deref(), Some("old.txt"));
+        assert!(files[2].hunks.is_empty());
+
+        assert_eq!(files[3].status, DiffFileStatus::Deleted);
+        assert!(files[3].is_binary);
+    }
+
+    #[test]
+    fn test_parse_unified_diff_line_numbers() {
+        let files = parse_unified_diff(SAMPLE);
+        let lines = &files[0].hunks[0].lines;
+
+        // Removed line starting with "--" must not be mistaken for a file header
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1].line_type, DiffLineType::Removed);
+        assert_eq!(lines[1].content, "-- old comment");
+        assert_eq!((lines[1].old_line, lines[1].new_line), (Some(2), None));
+        assert_eq!(lines[2].line_type, DiffLineType::Added);
+        assert_eq!((lines[2].old_line, lines[2].new_line), (None, Some(2)));
+        assert_eq!((lines[3].old_line, lines[3].new_line), (Some(3), Some(3)));
+    }
+
+    #[test]
+    fn test_diff_branches_rejects_bad_refs() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().to_str().unwrap();
+        let git = |args: &[&str]| assert!(Command::new("git").arg("-C").arg(repo).args(args).status().unwrap().success());
+        git(&["init", "-q", "-b", "main"]);
+        git(&["-c", "user.name=Test", "-c", "user.email=test@example.com", "commit", "-q", "--allow-empty", "-m", "init"]);
+        git(&["branch", "feature"]);
+
+        assert!(diff_branches(repo, "main", "feature").unwrap().files.is_empty());
+        let output = dir.path().join("out.txt");
+        let option = format!("--output={}", output.display());
+        assert_eq!(diff_branches(repo, &option, "main").unwrap_err(), format!("Invalid ref: {}", option));
+        assert!(!output.exists());
+        assert_eq!(diff_branches(repo, "main", "main..feature").unwrap_err(), "Invalid ref: main..feature");
+        assert_eq!(diff_branches(repo, "main", "missing").unwrap_err(), "Unknown ref: missing");
+    }
+}
@@ -0,0 +1,185 @@
+//! Worktree health inspection.
+//!
+//! Collects the data behind the per-worktree health badge:
+//! - Dirty (uncommitted / untracked) file count
+//! - Ahead / behind counts against the upstream branch
+//! - Last commit (hash, summary, timestamp)
+//! - Stale branch detection (upstream deleted, or no commits for a while)
+
+use crate::actions::{LastCommitData, WorktreeHealthData};
+use std::process::Command;
+
+/// A branch with no commits for this many days is considered stale
+pub const STALE_BRANCH_DAYS: i64 = 30;
+
+/// Branch / working tree summary from `git status --porcelain=v2 --branch`
+#[derive(Debug, Clone, Default, PartialEq)]
+struct StatusSummary {
+    upstream: Option<String>,
+    /// Upstream is configured but no longer exists (e.g. branch deleted on remote)
+    upstream_gone: bool,
+    ahead: u32,
+    behind: u32,
+    dirty_files: u32,
+}
+
+/// Compute health for the worktree at `worktree_path`.
+pub fn compute_health(worktree_path: &str) -> Result<WorktreeHealthData, String> {
+    let status_output = run_git(worktree_path, &["status", "--porcelain=v2", "--branch"])?;
+    let status = parse_status(&status_output);
+
+    // Fails on a fresh repository without commits; treat as "no last commit"
+    let last_commit = run_git(worktree_path, &["log", "-1", "--format=%H%x1f%s%x1f%ct"])
+        .ok()
+        .and_then(|out| parse_last_commit(&out));
+
+    let now = chrono::Utc::now().timestamp();
+    let is_stale = is_stale(status.upstream_gone, last_commit.as_ref().map(|c| c.timestamp), now);
+
+    Ok(WorktreeHealthData {
+        worktree_path: worktree_path.to_string(),
+        dirty_files: status.dirty_files,
+        upstream: status.upstream,
+        ahead: status.ahead,
+        behind: status.behind,
+        last_commit,
+        is_stale,
+    })
+}
+
+/// Run a git command in the worktree, returning stdout
+fn run_git(worktree_path: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(worktree_path)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run git {}: {}", args[0], e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git {} failed: {}", args[0], stderr.trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Parse `git status --porcelain=v2 --branch` output.
+///
+/// Header lines start with `#`; every other non-empty line is a changed,
+/// unmerged or untracked entry. Ignored entries (`!`) are not counted.
+fn parse_status(output: &str) -> StatusSummary {
+    let mut summary = StatusSummary::default();
+    let mut has_ab = false;
+
+    for line in output.lines() {
+        if let Some(upstream) = line.strip_prefix("# branch.upstream ") {
+            summary.upstream = Some(upstream.trim().to_string());
+        } else if let Some(ab) = line.strip_prefix("# branch.ab ") {
+            has_ab = true;
+            for part in ab.split_whitespace() {
+                if let Some(n) = part.strip_prefix('+') {
+                    summary.ahead = n.parse().unwrap_or(0);
+                } else if let Some(n) = part.strip_prefix('-') {
+                    summary.behind = n.parse().unwrap_or(0);
+                }
+            }
+        } else if line.starts_with('#') || line.starts_with('!') || line.trim().is_empty() {
+            continue;
+        } else {
+            summary.dirty_files += 1;
+        }
+    }
+
+    // git omits branch.ab when the configured upstream ref is missing
+    summary.upstream_gone = summary.upstream.is_some() && !has_ab;
+    summary
+}
+
+/// Parse `git log -1 --format=%H%x1f%s%x1f%ct` output
+fn parse_last_commit(output: &str) -> Option<LastCommitData> {
+    let mut parts = output.trim_end_matches('\n').split('\x1f');
+    let hash = parts.next()?.trim();
+    let summary = parts.next()?;
+    let timestamp = parts.next()?.trim().parse().ok()?;
+
+    if hash.is_empty() {
+        return None;
+    }
+
+    Some(LastCommitData {
+        hash: hash.to_string(),
+        summary: summary.to_string(),
+        timestamp,
+    })
+}
+
+/// A branch is stale if its upstream was deleted or it has had no commits
+/// for `STALE_BRANCH_DAYS`
+fn is_stale(upstream_gone: bool, last_commit_ts: Option<i64>, now: i64) -> bool {
+    if upstream_gone {
+        return true;
+    }
+    match last_commit_ts {
+        Some(ts) => now - ts > STALE_BRANCH_DAYS * 24 * 60 * 60,
+        None => false,
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status_with_upstream_and_changes() {
+        let output = "# branch.oid abc123\n\
+                      # branch.head feature/auth\n\
+                      # branch.upstream origin/feature/auth\n\
+                      # branch.ab +2 -1\n\
+                      1 .M N... 100644 100644 100644 aaa bbb src/lib.rs\n\
+                      ? notes.txt\n\
+                      ! target/\n";
+        let summary = parse_status(output);
+        assert_eq!(summary.upstream, Some("origin/feature/auth".to_string()));
+        assert_eq!(summary.ahead, 2);
+        assert_eq!(summary.behind, 1);
+        assert_eq!(summary.dirty_files, 2);
+        assert!(!summary.upstream_gone);
+    }
+
+    #[test]
+    fn test_parse_status_upstream_gone_and_no_upstream() {
+        let gone = "# branch.oid abc123\n# branch.head old\n# branch.upstream origin/old\n";
+        assert!(parse_status(gone).upstream_gone);
+
+        let local = "# branch.oid abc123\n# branch.head local\n";
+        let summary = parse_status(local);
+        assert_eq!(summary.upstream, None);
+        assert!(!summary.upstream_gone);
+        assert_eq!(summary.dirty_files, 0);
+    }
+
+    #[test]
+    fn test_parse_last_commit() {
+        let commit = parse_last_commit("abc123\x1fFix login bug\x1f1700000000\n").unwrap();
+        assert_eq!(commit.hash, "abc123");
+        assert_eq!(commit.summary, "Fix login bug");
+        assert_eq!(commit.timestamp, 1_700_000_000);
+
+        assert!(parse_last_commit("").is_none());
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let day = 24 * 60 * 60;
+        let now = 100 * day;
+        assert!(is_stale(true, Some(now), now));
+        assert!(is_stale(false, Some(now - (STALE_BRANCH_DAYS + 1) * day), now));
+        assert!(!is_stale(false, Some(now - day), now));
+        assert!(!is_stale(false, None, now));
+    }
+}
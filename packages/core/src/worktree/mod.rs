@@ -5,6 +5,11 @@
 //! - List available branches
 //! - Create new worktrees (from existing or new branch)
 //! - Remove worktrees
+//! - Compute per-worktree health (see [`health`])
+//! - Diff a worktree's branch against a base branch (see [`diff`])
+
+pub mod diff;
+pub mod health;
 
 use crate::actions::WorktreeData;
 use std::path::Path;